@@ -0,0 +1,420 @@
+use crate::error::{LauncherError, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Time given to the restored window to take keyboard focus before pasting
+pub const PASTE_SETTLE_DELAY: Duration = Duration::from_millis(120);
+
+/// Focus and keyboard input used to paste into another application
+///
+/// Implemented with Win32 calls in the app; tests use a scripted backend to
+/// simulate windows that can't be focused.
+pub trait PasteInput: Send + Sync {
+    /// Handle of the window in the foreground, unless it belongs to the launcher
+    fn foreground_window(&self) -> Option<isize>;
+    /// Brings `window` back to the foreground
+    ///
+    /// Fails for windows that closed, run elevated while the launcher doesn't,
+    /// or didn't end up in the foreground.
+    fn restore_focus(&self, window: isize) -> Result<()>;
+    /// Sends Ctrl+V to the foreground window
+    fn send_paste(&self) -> Result<()>;
+}
+
+/// What happened after a clipboard result was copied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasteOutcome {
+    /// Auto-paste is off, so the item was only copied
+    Disabled,
+    /// The item was pasted into the previous window
+    Pasted,
+    /// Pasting was aborted and the item stays on the clipboard
+    CopiedOnly { reason: String },
+}
+
+type HideLauncher = Box<dyn Fn() + Send + Sync>;
+type FallbackListener = Box<dyn Fn(&str) + Send + Sync>;
+
+/// Pastes restored clipboard items into the window that was active before the launcher
+///
+/// The window is remembered each time the launcher is shown. Pasting hides
+/// the launcher, focuses that window again, waits for it to settle and sends
+/// Ctrl+V. When the window can't be focused the item is left on the
+/// clipboard and the fallback listener is told why.
+pub struct AutoPaste {
+    input: Arc<dyn PasteInput>,
+    enabled: AtomicBool,
+    previous_window: Mutex<Option<isize>>,
+    settle_delay: Duration,
+    hide_launcher: Mutex<Option<HideLauncher>>,
+    fallback_listener: Mutex<Option<FallbackListener>>,
+}
+
+impl AutoPaste {
+    /// Creates a disabled auto-paste using the system's focus and keyboard input
+    pub fn new() -> Self {
+        Self::with_input(Arc::new(SystemInput))
+    }
+
+    /// Creates a disabled auto-paste using the given input backend
+    pub fn with_input(input: Arc<dyn PasteInput>) -> Self {
+        Self {
+            input,
+            enabled: AtomicBool::new(false),
+            previous_window: Mutex::new(None),
+            settle_delay: PASTE_SETTLE_DELAY,
+            hide_launcher: Mutex::new(None),
+            fallback_listener: Mutex::new(None),
+        }
+    }
+
+    /// Sets how long to wait between restoring focus and pasting
+    pub fn with_settle_delay(mut self, delay: Duration) -> Self {
+        self.settle_delay = delay;
+        self
+    }
+
+    /// Enables or disables pasting after clipboard results are copied
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns whether clipboard results are pasted after being copied
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Sets the callback hiding the launcher before focus is restored
+    pub fn set_hide_launcher<F>(&self, hide: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        *self.hide_launcher.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(hide));
+    }
+
+    /// Sets a callback run with the reason whenever pasting falls back to copy-only
+    pub fn set_fallback_listener<F>(&self, listener: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        *self.fallback_listener.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(listener));
+    }
+
+    /// Remembers the foreground window; call just before showing the launcher
+    ///
+    /// Keeps the previous window when the launcher itself is in the foreground.
+    pub fn remember_foreground(&self) {
+        if let Some(window) = self.input.foreground_window() {
+            *self.previous_window.lock().unwrap_or_else(|e| e.into_inner()) = Some(window);
+        }
+    }
+
+    /// Returns the window clipboard items are pasted into
+    pub fn previous_window(&self) -> Option<isize> {
+        *self.previous_window.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Pastes the clipboard into the remembered window, if enabled
+    ///
+    /// Call after the item is on the clipboard. Failures never undo the copy.
+    pub async fn paste(&self) -> PasteOutcome {
+        if !self.is_enabled() {
+            return PasteOutcome::Disabled;
+        }
+
+        let outcome = match self.previous_window() {
+            Some(window) => self.paste_into(window).await,
+            None => Err(LauncherError::WindowError(
+                "No window was active before the launcher opened".to_string(),
+            )),
+        };
+
+        match outcome {
+            Ok(()) => {
+                tracing::info!("Pasted clipboard item into the previous window");
+                PasteOutcome::Pasted
+            }
+            Err(e) => {
+                let reason = e.to_string();
+                tracing::warn!("Auto-paste aborted, item left on the clipboard: {}", reason);
+                if let Some(listener) = self.fallback_listener.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+                    listener(&reason);
+                }
+                PasteOutcome::CopiedOnly { reason }
+            }
+        }
+    }
+
+    async fn paste_into(&self, window: isize) -> Result<()> {
+        if let Some(hide) = self.hide_launcher.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            hide();
+        }
+
+        self.input.restore_focus(window)?;
+        tokio::time::sleep(self.settle_delay).await;
+        self.input.send_paste()
+    }
+}
+
+impl Default for AutoPaste {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Win32 focus handling and keyboard input
+struct SystemInput;
+
+#[cfg(windows)]
+impl SystemInput {
+    /// Whether the process runs elevated, or `None` if its token can't be read
+    fn process_is_elevated(process_id: u32) -> Option<bool> {
+        use windows::Win32::Foundation::{CloseHandle, HANDLE};
+        use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+        use windows::Win32::System::Threading::{OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION};
+
+        unsafe {
+            let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id).ok()?;
+            let mut token = HANDLE::default();
+            let opened = OpenProcessToken(process, TOKEN_QUERY, &mut token);
+            CloseHandle(process).ok();
+            opened.ok()?;
+
+            let mut elevation = TOKEN_ELEVATION::default();
+            let mut length = 0u32;
+            let queried = GetTokenInformation(
+                token,
+                TokenElevation,
+                Some(&mut elevation as *mut TOKEN_ELEVATION as *mut std::ffi::c_void),
+                std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+                &mut length,
+            );
+            CloseHandle(token).ok();
+            queried.ok()?;
+
+            Some(elevation.TokenIsElevated != 0)
+        }
+    }
+}
+
+#[cfg(windows)]
+impl PasteInput for SystemInput {
+    fn foreground_window(&self) -> Option<isize> {
+        use windows::Win32::System::Threading::GetCurrentProcessId;
+        use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.0.is_null() {
+                return None;
+            }
+
+            let mut process_id = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+            (process_id != GetCurrentProcessId()).then_some(hwnd.0 as isize)
+        }
+    }
+
+    fn restore_focus(&self, window: isize) -> Result<()> {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::Threading::GetCurrentProcessId;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            GetForegroundWindow, GetWindowThreadProcessId, IsIconic, IsWindow, SetForegroundWindow,
+            ShowWindow, SW_RESTORE,
+        };
+
+        let hwnd = HWND(window as *mut std::ffi::c_void);
+        unsafe {
+            if !IsWindow(hwnd).as_bool() {
+                return Err(LauncherError::WindowError("The previous window was closed".to_string()));
+            }
+
+            // Input sent to elevated windows is silently dropped, so never try
+            let mut process_id = 0u32;
+            GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+            let launcher_elevated = Self::process_is_elevated(GetCurrentProcessId()).unwrap_or(false);
+            match Self::process_is_elevated(process_id) {
+                Some(false) => {}
+                Some(true) if launcher_elevated => {}
+                _ => {
+                    return Err(LauncherError::SecurityError(
+                        "The previous window runs as administrator".to_string(),
+                    ));
+                }
+            }
+
+            if IsIconic(hwnd).as_bool() {
+                let _ = ShowWindow(hwnd, SW_RESTORE);
+            }
+            let _ = SetForegroundWindow(hwnd);
+
+            if GetForegroundWindow() != hwnd {
+                return Err(LauncherError::WindowError(
+                    "Windows did not let the previous window take focus".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn send_paste(&self) -> Result<()> {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP,
+            VIRTUAL_KEY, VK_CONTROL, VK_V,
+        };
+
+        let key = |vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT { wVk: vk, dwFlags: flags, ..Default::default() },
+            },
+        };
+        let inputs = [
+            key(VK_CONTROL, KEYBD_EVENT_FLAGS(0)),
+            key(VK_V, KEYBD_EVENT_FLAGS(0)),
+            key(VK_V, KEYEVENTF_KEYUP),
+            key(VK_CONTROL, KEYEVENTF_KEYUP),
+        ];
+
+        let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+        if sent as usize != inputs.len() {
+            return Err(LauncherError::ExecutionError(format!(
+                "Only {} of {} paste key events were sent",
+                sent,
+                inputs.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(not(windows))]
+impl PasteInput for SystemInput {
+    fn foreground_window(&self) -> Option<isize> {
+        None
+    }
+
+    fn restore_focus(&self, _window: isize) -> Result<()> {
+        Err(LauncherError::WindowError(
+            "Restoring focus is not implemented for this platform".to_string(),
+        ))
+    }
+
+    fn send_paste(&self) -> Result<()> {
+        Err(LauncherError::ExecutionError(
+            "Simulating paste is not implemented for this platform".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records calls and fails focus restoration on request
+    #[derive(Default)]
+    struct ScriptedInput {
+        foreground: Mutex<Option<isize>>,
+        focus_error: Option<String>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedInput {
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl PasteInput for ScriptedInput {
+        fn foreground_window(&self) -> Option<isize> {
+            *self.foreground.lock().unwrap()
+        }
+
+        fn restore_focus(&self, window: isize) -> Result<()> {
+            self.calls.lock().unwrap().push(format!("focus {}", window));
+            match &self.focus_error {
+                Some(error) => Err(LauncherError::SecurityError(error.clone())),
+                None => Ok(()),
+            }
+        }
+
+        fn send_paste(&self) -> Result<()> {
+            self.calls.lock().unwrap().push("paste".to_string());
+            Ok(())
+        }
+    }
+
+    fn auto_paste(input: &Arc<ScriptedInput>) -> (AutoPaste, Arc<Mutex<Vec<String>>>) {
+        let auto_paste = AutoPaste::with_input(Arc::clone(input) as Arc<dyn PasteInput>)
+            .with_settle_delay(Duration::ZERO);
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let notified_clone = Arc::clone(&notified);
+        auto_paste.set_fallback_listener(move |reason| notified_clone.lock().unwrap().push(reason.to_string()));
+        (auto_paste, notified)
+    }
+
+    #[tokio::test]
+    async fn test_disabled_only_copies() {
+        let input = Arc::new(ScriptedInput::default());
+        *input.foreground.lock().unwrap() = Some(42);
+        let (auto_paste, notified) = auto_paste(&input);
+        auto_paste.remember_foreground();
+
+        assert_eq!(auto_paste.paste().await, PasteOutcome::Disabled);
+        assert!(input.calls().is_empty());
+        assert!(notified.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_pastes_into_remembered_window() {
+        let input = Arc::new(ScriptedInput::default());
+        *input.foreground.lock().unwrap() = Some(42);
+        let (auto_paste, notified) = auto_paste(&input);
+        let hidden = Arc::new(AtomicBool::new(false));
+        let hidden_clone = Arc::clone(&hidden);
+        auto_paste.set_hide_launcher(move || hidden_clone.store(true, Ordering::Relaxed));
+        auto_paste.set_enabled(true);
+
+        auto_paste.remember_foreground();
+        // The launcher's own window is never reported, so 42 is kept
+        *input.foreground.lock().unwrap() = None;
+        auto_paste.remember_foreground();
+
+        assert_eq!(auto_paste.paste().await, PasteOutcome::Pasted);
+        assert!(hidden.load(Ordering::Relaxed));
+        assert_eq!(input.calls(), vec!["focus 42", "paste"]);
+        assert!(notified.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_focus_failure_falls_back_to_copy_and_notifies() {
+        let input = Arc::new(ScriptedInput {
+            focus_error: Some("The previous window runs as administrator".to_string()),
+            ..Default::default()
+        });
+        *input.foreground.lock().unwrap() = Some(7);
+        let (auto_paste, notified) = auto_paste(&input);
+        auto_paste.set_enabled(true);
+        auto_paste.remember_foreground();
+
+        let outcome = auto_paste.paste().await;
+        assert!(matches!(&outcome, PasteOutcome::CopiedOnly { reason } if reason.contains("administrator")));
+        // No keystrokes reach a window that couldn't be focused
+        assert_eq!(input.calls(), vec!["focus 7"]);
+        assert_eq!(notified.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_remembered_window_falls_back_to_copy() {
+        let input = Arc::new(ScriptedInput::default());
+        let (auto_paste, notified) = auto_paste(&input);
+        auto_paste.set_enabled(true);
+
+        assert!(matches!(auto_paste.paste().await, PasteOutcome::CopiedOnly { .. }));
+        assert!(input.calls().is_empty());
+        assert_eq!(notified.lock().unwrap().len(), 1);
+    }
+}
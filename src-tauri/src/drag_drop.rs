@@ -0,0 +1,387 @@
+use crate::error::{LauncherError, Result};
+use crate::types::{ResultType, SearchResult};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// `DROPEFFECT_COPY`
+pub const DROP_EFFECT_COPY: u32 = 1;
+/// `DROPEFFECT_MOVE`
+pub const DROP_EFFECT_MOVE: u32 = 2;
+/// `DROPEFFECT_LINK`
+pub const DROP_EFFECT_LINK: u32 = 4;
+
+/// Effects offered for dragged files; the drop target picks one
+pub const ALLOWED_EFFECTS: u32 = DROP_EFFECT_COPY | DROP_EFFECT_MOVE | DROP_EFFECT_LINK;
+
+/// What the drop target did with the dragged files, as returned by `begin_drag`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DropEffect {
+    /// The drag was cancelled or nothing accepted the files
+    None,
+    Copy,
+    Move,
+    Link,
+}
+
+impl DropEffect {
+    /// Reads the effect a drag finished with from its `DROPEFFECT` flags
+    ///
+    /// Flags that weren't offered are ignored. Targets should report a single
+    /// effect; when several are set, move wins over copy over link, since a
+    /// move is the one that changes what the launcher shows.
+    pub fn from_flags(flags: u32, allowed: u32) -> Self {
+        let flags = flags & allowed;
+        if flags & DROP_EFFECT_MOVE != 0 {
+            DropEffect::Move
+        } else if flags & DROP_EFFECT_COPY != 0 {
+            DropEffect::Copy
+        } else if flags & DROP_EFFECT_LINK != 0 {
+            DropEffect::Link
+        } else {
+            DropEffect::None
+        }
+    }
+}
+
+/// Runs a native drag of files
+///
+/// Implemented with OLE in the app; tests use a scripted source to simulate
+/// drops without a mouse.
+pub trait DragSource: Send + Sync {
+    /// Drags `paths` until they're dropped or the drag is cancelled, offering
+    /// the `allowed` effects, and returns the `DROPEFFECT` flags it ended with
+    ///
+    /// Blocks for the whole drag.
+    fn drag_files(&self, paths: &[PathBuf], allowed: u32) -> Result<u32>;
+}
+
+/// Drags file results out of the launcher into other applications
+///
+/// While a drag runs the launcher window has to stay open: it loses focus to
+/// the drop target, and hiding it would end the drag. The hide and toggle
+/// paths check `is_dragging` before hiding.
+pub struct DragDrop {
+    source: Arc<dyn DragSource>,
+    dragging: Arc<AtomicBool>,
+}
+
+/// Clears the dragging flag when the drag ends, however it ends
+struct DragGuard(Arc<AtomicBool>);
+
+impl Drop for DragGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+impl DragDrop {
+    /// Creates a DragDrop using OLE drag and drop
+    pub fn new() -> Self {
+        Self::with_source(Arc::new(OleDragSource))
+    }
+
+    /// Creates a DragDrop using the given drag source
+    pub fn with_source(source: Arc<dyn DragSource>) -> Self {
+        Self {
+            source,
+            dragging: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns whether a drag is in progress, during which the window must not hide
+    pub fn is_dragging(&self) -> bool {
+        self.dragging.load(Ordering::SeqCst)
+    }
+
+    /// Drags a file result's path and waits until it's dropped or cancelled
+    ///
+    /// Only File and RecentFile results (including folders) can be dragged,
+    /// and only one drag runs at a time.
+    pub async fn begin(&self, result: &SearchResult) -> Result<DropEffect> {
+        let path = draggable_path(result)?;
+
+        if self.dragging.swap(true, Ordering::SeqCst) {
+            return Err(LauncherError::WindowError("Another drag is in progress".to_string()));
+        }
+        let guard = DragGuard(Arc::clone(&self.dragging));
+
+        tracing::info!("Dragging {}", path.display());
+        let source = Arc::clone(&self.source);
+        let flags = tokio::task::spawn_blocking(move || {
+            let _guard = guard;
+            source.drag_files(&[path], ALLOWED_EFFECTS)
+        })
+        .await
+        .map_err(|e| LauncherError::WindowError(format!("Drag task failed: {}", e)))??;
+
+        let effect = DropEffect::from_flags(flags, ALLOWED_EFFECTS);
+        tracing::info!("Drag finished with {:?}", effect);
+        Ok(effect)
+    }
+}
+
+impl Default for DragDrop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The path a result drags, or an error for results that aren't files
+fn draggable_path(result: &SearchResult) -> Result<PathBuf> {
+    match result.result_type {
+        ResultType::File | ResultType::RecentFile => result
+            .file_path()
+            .map(PathBuf::from)
+            .ok_or_else(|| LauncherError::Unsupported(format!("'{}' has no file path to drag", result.title))),
+        _ => Err(LauncherError::Unsupported(format!(
+            "{:?} results can't be dragged",
+            result.result_type
+        ))),
+    }
+}
+
+/// OLE drag and drop with a `CF_HDROP` payload
+struct OleDragSource;
+
+#[cfg(windows)]
+impl OleDragSource {
+    /// Builds the `DROPFILES` block of a `CF_HDROP` payload: the header
+    /// followed by the nul-terminated wide paths and a final nul
+    fn drop_files_payload(paths: &[PathBuf]) -> Result<windows::Win32::Foundation::HGLOBAL> {
+        use std::os::windows::ffi::OsStrExt;
+        use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GHND};
+        use windows::Win32::UI::Shell::DROPFILES;
+
+        let mut wide: Vec<u16> = Vec::new();
+        for path in paths {
+            wide.extend(path.as_os_str().encode_wide());
+            wide.push(0);
+        }
+        wide.push(0);
+
+        let header = std::mem::size_of::<DROPFILES>();
+        let size = header + wide.len() * std::mem::size_of::<u16>();
+
+        unsafe {
+            let memory = GlobalAlloc(GHND, size)
+                .map_err(|e| LauncherError::WindowError(format!("Failed to allocate drag data: {}", e)))?;
+            let block = GlobalLock(memory) as *mut u8;
+            if block.is_null() {
+                return Err(LauncherError::WindowError("Failed to lock drag data".to_string()));
+            }
+
+            let drop_files = block as *mut DROPFILES;
+            (*drop_files).pFiles = header as u32;
+            (*drop_files).fWide = true.into();
+            std::ptr::copy_nonoverlapping(wide.as_ptr(), block.add(header) as *mut u16, wide.len());
+
+            let _ = GlobalUnlock(memory);
+            Ok(memory)
+        }
+    }
+
+    /// Runs the drag on the current thread, which must be a fresh STA thread
+    fn drag_on_sta_thread(paths: &[PathBuf], allowed: u32) -> Result<u32> {
+        use std::mem::ManuallyDrop;
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::Com::{IDataObject, DVASPECT_CONTENT, FORMATETC, STGMEDIUM, STGMEDIUM_0, TYMED_HGLOBAL};
+        use windows::Win32::System::Ole::{IDropSource, OleInitialize, OleUninitialize, CF_HDROP, DROPEFFECT};
+        use windows::Win32::UI::Shell::{SHCreateDataObject, SHDoDragDrop};
+
+        unsafe {
+            OleInitialize(None).map_err(|e| LauncherError::WindowError(format!("Failed to initialize OLE: {}", e)))?;
+
+            let result = (|| {
+                // The shell's data object stores what it's given, so only the
+                // file list has to be built here
+                let data: IDataObject = SHCreateDataObject(None, None, None)
+                    .map_err(|e| LauncherError::WindowError(format!("Failed to create drag data: {}", e)))?;
+
+                let format = FORMATETC {
+                    cfFormat: CF_HDROP.0,
+                    ptd: std::ptr::null_mut(),
+                    dwAspect: DVASPECT_CONTENT.0,
+                    lindex: -1,
+                    tymed: TYMED_HGLOBAL.0 as u32,
+                };
+                let medium = STGMEDIUM {
+                    tymed: TYMED_HGLOBAL.0 as u32,
+                    u: STGMEDIUM_0 { hGlobal: Self::drop_files_payload(paths)? },
+                    pUnkForRelease: ManuallyDrop::new(None),
+                };
+                data.SetData(&format, &medium, true)
+                    .map_err(|e| LauncherError::WindowError(format!("Failed to set drag data: {}", e)))?;
+
+                // Without a drop source of our own the shell supplies the default one
+                let effect = SHDoDragDrop(HWND(std::ptr::null_mut()), &data, None::<&IDropSource>, DROPEFFECT(allowed))
+                    .map_err(|e| LauncherError::WindowError(format!("Drag failed: {}", e)))?;
+                Ok(effect.0)
+            })();
+
+            OleUninitialize();
+            result
+        }
+    }
+}
+
+#[cfg(windows)]
+impl DragSource for OleDragSource {
+    fn drag_files(&self, paths: &[PathBuf], allowed: u32) -> Result<u32> {
+        // DoDragDrop pumps messages on an OLE-initialized single-threaded
+        // apartment, which pooled threads can't be guaranteed to be
+        let paths = paths.to_vec();
+        std::thread::Builder::new()
+            .name("drag-drop".to_string())
+            .spawn(move || Self::drag_on_sta_thread(&paths, allowed))
+            .map_err(|e| LauncherError::WindowError(format!("Failed to start drag thread: {}", e)))?
+            .join()
+            .map_err(|_| LauncherError::WindowError("Drag thread panicked".to_string()))?
+    }
+}
+
+#[cfg(not(windows))]
+impl DragSource for OleDragSource {
+    fn drag_files(&self, _paths: &[PathBuf], _allowed: u32) -> Result<u32> {
+        Err(LauncherError::Unsupported(
+            "Dragging files is not supported on this platform".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultAction;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Records dragged paths and ends each drag after a delay, with a fixed
+    /// effect or with an error when `flags` is `None`
+    struct ScriptedSource {
+        flags: Option<u32>,
+        delay: Duration,
+        dragged: Mutex<Vec<Vec<PathBuf>>>,
+    }
+
+    impl ScriptedSource {
+        fn new(flags: Option<u32>, delay: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                flags,
+                delay,
+                dragged: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    impl DragSource for ScriptedSource {
+        fn drag_files(&self, paths: &[PathBuf], _allowed: u32) -> Result<u32> {
+            self.dragged.lock().unwrap().push(paths.to_vec());
+            std::thread::sleep(self.delay);
+            self.flags
+                .ok_or_else(|| LauncherError::WindowError("The drop target crashed".to_string()))
+        }
+    }
+
+    fn drag_drop(source: &Arc<ScriptedSource>) -> DragDrop {
+        DragDrop::with_source(Arc::clone(source) as Arc<dyn DragSource>)
+    }
+
+    fn result(result_type: ResultType, action: ResultAction) -> SearchResult {
+        SearchResult {
+            id: "result".to_string(),
+            title: "report.docx".to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type,
+            score: 50.0,
+            metadata: HashMap::new(),
+            action,
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    fn file(path: &str) -> SearchResult {
+        result(ResultType::File, ResultAction::OpenFile { path: path.to_string() })
+    }
+
+    #[test]
+    fn test_drop_effect_from_flags() {
+        assert_eq!(DropEffect::from_flags(DROP_EFFECT_COPY, ALLOWED_EFFECTS), DropEffect::Copy);
+        assert_eq!(DropEffect::from_flags(DROP_EFFECT_MOVE, ALLOWED_EFFECTS), DropEffect::Move);
+        assert_eq!(DropEffect::from_flags(DROP_EFFECT_LINK, ALLOWED_EFFECTS), DropEffect::Link);
+        assert_eq!(DropEffect::from_flags(0, ALLOWED_EFFECTS), DropEffect::None);
+        assert_eq!(
+            DropEffect::from_flags(DROP_EFFECT_COPY | DROP_EFFECT_MOVE, ALLOWED_EFFECTS),
+            DropEffect::Move
+        );
+        // Effects that weren't offered don't count, e.g. the scroll flag
+        assert_eq!(DropEffect::from_flags(0x8000_0000, ALLOWED_EFFECTS), DropEffect::None);
+        assert_eq!(DropEffect::from_flags(DROP_EFFECT_MOVE, DROP_EFFECT_COPY), DropEffect::None);
+        assert_eq!(serde_json::to_value(DropEffect::Copy).unwrap(), serde_json::json!("copy"));
+    }
+
+    #[tokio::test]
+    async fn test_file_results_are_dragged_with_their_path() {
+        let source = ScriptedSource::new(Some(DROP_EFFECT_COPY), Duration::ZERO);
+        let drag_drop = drag_drop(&source);
+
+        assert_eq!(drag_drop.begin(&file(r"C:\Users\me\report.docx")).await.unwrap(), DropEffect::Copy);
+
+        let mut recent = result(ResultType::RecentFile, ResultAction::ExecuteCommand { command: "open".to_string(), args: vec![] });
+        recent.metadata.insert("path".to_string(), serde_json::json!(r"C:\Users\me\Projects"));
+        drag_drop.begin(&recent).await.unwrap();
+
+        assert_eq!(
+            *source.dragged.lock().unwrap(),
+            vec![vec![PathBuf::from(r"C:\Users\me\report.docx")], vec![PathBuf::from(r"C:\Users\me\Projects")]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_other_results_are_unsupported() {
+        let source = ScriptedSource::new(Some(DROP_EFFECT_COPY), Duration::ZERO);
+        let drag_drop = drag_drop(&source);
+
+        let app = result(ResultType::Application, ResultAction::LaunchApp { path: r"C:\app.exe".to_string() });
+        assert!(matches!(drag_drop.begin(&app).await, Err(LauncherError::Unsupported(_))));
+        let url = result(ResultType::File, ResultAction::OpenUrl { url: "https://example.com".to_string() });
+        assert!(matches!(drag_drop.begin(&url).await, Err(LauncherError::Unsupported(_))));
+
+        assert!(source.dragged.lock().unwrap().is_empty());
+        assert!(!drag_drop.is_dragging());
+    }
+
+    #[tokio::test]
+    async fn test_window_stays_open_only_while_dragging() {
+        let source = ScriptedSource::new(Some(DROP_EFFECT_MOVE), Duration::from_millis(50));
+        let drag_drop = Arc::new(drag_drop(&source));
+        assert!(!drag_drop.is_dragging());
+
+        let dragging = Arc::clone(&drag_drop);
+        let drag = tokio::spawn(async move { dragging.begin(&file(r"C:\a.txt")).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        assert!(drag_drop.is_dragging());
+        // A second drag can't start while the first runs
+        assert!(drag_drop.begin(&file(r"C:\b.txt")).await.is_err());
+
+        assert_eq!(drag.await.unwrap().unwrap(), DropEffect::Move);
+        assert!(!drag_drop.is_dragging());
+        assert_eq!(source.dragged.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_drag_releases_the_window() {
+        let source = ScriptedSource::new(None, Duration::ZERO);
+        let drag_drop = drag_drop(&source);
+
+        assert!(drag_drop.begin(&file(r"C:\a.txt")).await.is_err());
+        assert!(!drag_drop.is_dragging());
+    }
+}
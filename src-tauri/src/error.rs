@@ -44,6 +44,12 @@ pub enum LauncherError {
 
     #[error("Window error: {0}")]
     WindowError(String),
+
+    #[error("Unverified result: {0}")]
+    UnverifiedResult(String),
+
+    #[error("Not supported: {0}")]
+    Unsupported(String),
 }
 
 /// Result type alias for launcher operations
@@ -1,66 +1,192 @@
 use crate::error::LauncherError;
-use tauri::{AppHandle, Emitter};
+use serde::Serialize;
+use crate::utils::show_latency::show_latency;
+use crate::utils::sound::{SoundEvent, SoundPlayer};
+use tauri::{AppHandle, Emitter, Manager};
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-/// Manages global keyboard shortcuts for the application
-pub struct GlobalHotkeyManager {
-    app_handle: AppHandle,
-    registered_shortcuts: Arc<Mutex<Vec<String>>>,
+/// Shortcuts tried in order when the configured hotkey is taken by another app
+pub const FALLBACK_HOTKEYS: &[&str] = &["Ctrl+Space", "Alt+Space", "Alt+Shift+Space", "Ctrl+Shift+Space"];
+
+/// Payload of the `hotkey-fallback-active` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HotkeyFallback {
+    /// The configured hotkey that could not be registered
+    pub preferred: String,
+    /// The temporary hotkey that opens the launcher instead
+    pub active: String,
+    /// Why the preferred hotkey could not be registered
+    pub reason: String,
 }
 
-impl GlobalHotkeyManager {
-    /// Creates a new GlobalHotkeyManager instance
-    pub fn new(app_handle: AppHandle) -> Self {
-        Self {
-            app_handle,
-            registered_shortcuts: Arc::new(Mutex::new(Vec::new())),
+/// The hotkey that currently opens the launcher, as returned by `get_active_hotkey`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ActiveHotkey {
+    /// Shortcut registered with the system
+    pub shortcut: String,
+    /// Shortcut configured in settings
+    pub preferred: String,
+    /// Whether `shortcut` is a temporary stand-in for `preferred`
+    pub is_fallback: bool,
+}
+
+/// What a global hotkey does when pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    /// Shows or hides the launcher
+    ToggleLauncher,
+    /// Evaluates the selected text as a calculation
+    QuickMath,
+}
+
+impl HotkeyAction {
+    /// Event emitted to the frontend when the hotkey is pressed
+    pub fn event(self) -> &'static str {
+        match self {
+            HotkeyAction::ToggleLauncher => "hotkey-pressed",
+            HotkeyAction::QuickMath => "quick-math-pressed",
         }
     }
+}
 
-    /// Registers a global hotkey
-    /// 
-    /// # Arguments
-    /// * `shortcut` - The keyboard shortcut string (e.g., "Ctrl+K", "Alt+Space")
-    /// 
-    /// # Returns
-    /// * `Result<()>` - Ok if registration succeeded, Err otherwise
-    pub fn register_hotkey(&self, shortcut: &str) -> Result<(), LauncherError> {
-        // Validate the shortcut format
-        self.validate_shortcut(shortcut)?;
+/// Registers shortcuts with the system
+///
+/// Implemented by the global shortcut plugin in the app; tests use an
+/// in-memory backend to simulate shortcuts taken by other applications.
+pub trait ShortcutBackend: Send + Sync {
+    fn register(&self, shortcut: &str, action: HotkeyAction) -> Result<(), LauncherError>;
+    fn unregister(&self, shortcut: &str) -> Result<(), LauncherError>;
+}
 
-        // Parse the shortcut
-        let parsed_shortcut = shortcut.parse::<Shortcut>()
+/// Backend emitting each action's event through the global shortcut plugin
+struct PluginBackend {
+    app_handle: AppHandle,
+}
+
+impl PluginBackend {
+    fn parse(shortcut: &str) -> Result<Shortcut, LauncherError> {
+        shortcut.parse::<Shortcut>()
             .map_err(|e| LauncherError::HotkeyRegistrationError(
                 format!("Invalid shortcut format '{}': {}", shortcut, e)
-            ))?;
+            ))
+    }
+}
+
+impl ShortcutBackend for PluginBackend {
+    fn register(&self, shortcut: &str, action: HotkeyAction) -> Result<(), LauncherError> {
+        let parsed_shortcut = Self::parse(shortcut)?;
 
         // Register the shortcut with the global shortcut plugin
         let app_handle = self.app_handle.clone();
         let shortcut_str = shortcut.to_string();
-        
+
         self.app_handle
             .global_shortcut()
             .on_shortcut(parsed_shortcut, move |_app, _shortcut, event| {
                 if event.state == ShortcutState::Pressed {
                     tracing::debug!("Global hotkey triggered: {}", shortcut_str);
-                    
+
+                    // A hidden window is shown right here, without a round trip
+                    // through the frontend; hiding is still left to the frontend
+                    let hidden = action == HotkeyAction::ToggleLauncher
+                        && app_handle
+                            .get_webview_window("main")
+                            .is_some_and(|window| !window.is_visible().unwrap_or(true));
+                    if hidden {
+                        show_latency().record_hotkey(Instant::now());
+                        if let Some(sound_player) = app_handle.try_state::<Arc<SoundPlayer>>() {
+                            sound_player.play(SoundEvent::Shown);
+                        }
+                        match crate::window_show::show_main_window(&app_handle) {
+                            Ok(()) => return,
+                            Err(e) => tracing::warn!("Failed to show window from the hotkey, leaving it to the frontend: {}", e),
+                        }
+                    }
+
                     // Emit event to frontend
-                    if let Err(e) = app_handle.emit("hotkey-pressed", ()) {
+                    if let Err(e) = app_handle.emit(action.event(), ()) {
                         tracing::error!("Failed to emit hotkey event: {}", e);
                     }
                 }
             })
             .map_err(|e| LauncherError::HotkeyRegistrationError(
                 format!("Failed to register shortcut '{}': {}", shortcut, e)
-            ))?;
+            ))
+    }
+
+    fn unregister(&self, shortcut: &str) -> Result<(), LauncherError> {
+        self.app_handle
+            .global_shortcut()
+            .unregister(Self::parse(shortcut)?)
+            .map_err(|e| LauncherError::HotkeyRegistrationError(
+                format!("Failed to unregister shortcut '{}': {}", shortcut, e)
+            ))
+    }
+}
+
+type FallbackListener = Box<dyn Fn(&HotkeyFallback) + Send + Sync>;
+
+/// Manages global keyboard shortcuts for the application
+pub struct GlobalHotkeyManager {
+    backend: Box<dyn ShortcutBackend>,
+    registered_shortcuts: Arc<Mutex<Vec<String>>>,
+    /// Hotkey opening the launcher, set by `register_with_fallback`
+    active: Mutex<Option<ActiveHotkey>>,
+    /// Hotkey evaluating the selection, set by `set_quick_math_hotkey`
+    quick_math: Mutex<Option<String>>,
+    fallback_listener: Mutex<Option<FallbackListener>>,
+}
+
+impl GlobalHotkeyManager {
+    /// Creates a new GlobalHotkeyManager instance
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self::with_backend(Box::new(PluginBackend { app_handle }))
+    }
+
+    /// Creates a manager registering shortcuts through the given backend
+    pub fn with_backend(backend: Box<dyn ShortcutBackend>) -> Self {
+        Self {
+            backend,
+            registered_shortcuts: Arc::new(Mutex::new(Vec::new())),
+            active: Mutex::new(None),
+            quick_math: Mutex::new(None),
+            fallback_listener: Mutex::new(None),
+        }
+    }
+
+    /// Sets a callback run whenever a fallback hotkey goes live
+    pub fn set_fallback_listener<F>(&self, listener: F)
+    where
+        F: Fn(&HotkeyFallback) + Send + Sync + 'static,
+    {
+        *self.fallback_listener.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(listener));
+    }
+
+    /// Registers a global hotkey
+    ///
+    /// # Arguments
+    /// * `shortcut` - The keyboard shortcut string (e.g., "Ctrl+K", "Alt+Space")
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok if registration succeeded, Err otherwise
+    pub fn register_hotkey(&self, shortcut: &str) -> Result<(), LauncherError> {
+        self.register_action(shortcut, HotkeyAction::ToggleLauncher)
+    }
+
+    fn register_action(&self, shortcut: &str, action: HotkeyAction) -> Result<(), LauncherError> {
+        // Validate the shortcut format
+        self.validate_shortcut(shortcut)?;
+
+        self.backend.register(shortcut, action)?;
 
         // Store the registered shortcut
         let mut shortcuts = self.registered_shortcuts.lock()
             .map_err(|e| LauncherError::HotkeyRegistrationError(
                 format!("Failed to acquire lock: {}", e)
             ))?;
-        
+
         if !shortcuts.contains(&shortcut.to_string()) {
             shortcuts.push(shortcut.to_string());
         }
@@ -69,45 +195,204 @@ impl GlobalHotkeyManager {
         Ok(())
     }
 
+    /// Registers the launcher hotkey, falling back to a free shortcut if it is taken
+    ///
+    /// When `preferred` cannot be registered, the shortcuts in
+    /// `FALLBACK_HOTKEYS` are tried in order and the first free one is
+    /// registered for this session only; nothing is saved. The fallback
+    /// listener is told which one is live. Registering the preferred hotkey
+    /// later removes the temporary one.
+    ///
+    /// # Returns
+    /// * `Result<String>` - The shortcut now opening the launcher
+    pub fn register_with_fallback(&self, preferred: &str) -> Result<String, LauncherError> {
+        // Release a previous fallback first so it can be reused or replaced
+        self.clear_fallback();
+
+        let error = match self.register_hotkey(preferred) {
+            Ok(()) => {
+                self.set_active(preferred, preferred, false);
+                return Ok(preferred.to_string());
+            }
+            Err(e) => e,
+        };
+        tracing::warn!("Preferred hotkey '{}' is unavailable: {}", preferred, error);
+
+        for candidate in fallback_candidates(preferred) {
+            if let Err(e) = self.register_hotkey(candidate) {
+                tracing::debug!("Fallback hotkey '{}' is unavailable: {}", candidate, e);
+                continue;
+            }
+
+            self.set_active(candidate, preferred, true);
+            let fallback = HotkeyFallback {
+                preferred: preferred.to_string(),
+                active: candidate.to_string(),
+                reason: error.to_string(),
+            };
+            tracing::warn!("Using temporary hotkey '{}' instead of '{}'", candidate, preferred);
+            if let Some(listener) = &*self.fallback_listener.lock().unwrap_or_else(|e| e.into_inner()) {
+                listener(&fallback);
+            }
+            return Ok(candidate.to_string());
+        }
+
+        *self.active.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Err(LauncherError::HotkeyRegistrationError(format!(
+            "{}; no fallback hotkey ({}) was free either",
+            error,
+            FALLBACK_HOTKEYS.join(", ")
+        )))
+    }
+
+    /// Registers the launcher hotkey again, e.g. after the system resumed
+    ///
+    /// Windows occasionally stops delivering a hotkey after sleep even though
+    /// it still counts as registered; unregistering and registering it again
+    /// restores it. A temporary hotkey gives the preferred one another try.
+    ///
+    /// # Returns
+    /// * `Result<String>` - The shortcut now opening the launcher
+    pub fn refresh_registration(&self) -> Result<String, LauncherError> {
+        let active = self.get_active_hotkey().ok_or_else(|| {
+            LauncherError::HotkeyRegistrationError("No launcher hotkey is registered".to_string())
+        })?;
+
+        if !active.is_fallback {
+            if let Err(e) = self.unregister_hotkey(&active.shortcut) {
+                tracing::warn!("Failed to unregister hotkey '{}' before refreshing it: {}", active.shortcut, e);
+            }
+        }
+
+        self.register_with_fallback(&active.preferred)
+    }
+
+    /// Registers the hotkey evaluating the selection, replacing the previous one
+    ///
+    /// `None` turns quick math off. The previous hotkey stays registered when
+    /// the new one can't be, and the launcher's own hotkey is rejected.
+    pub fn set_quick_math_hotkey(&self, shortcut: Option<&str>) -> Result<(), LauncherError> {
+        let previous = self.get_quick_math_hotkey();
+        let unchanged = match (previous.as_deref(), shortcut) {
+            (Some(previous), Some(shortcut)) => previous.eq_ignore_ascii_case(shortcut),
+            (None, None) => true,
+            _ => false,
+        };
+        if unchanged {
+            return Ok(());
+        }
+
+        if let Some(shortcut) = shortcut {
+            let taken_by_launcher = self
+                .get_active_hotkey()
+                .is_some_and(|active| active.shortcut.eq_ignore_ascii_case(shortcut));
+            if taken_by_launcher {
+                return Err(LauncherError::HotkeyRegistrationError(format!(
+                    "'{}' already opens the launcher",
+                    shortcut
+                )));
+            }
+            self.register_action(shortcut, HotkeyAction::QuickMath)?;
+        }
+
+        if let Some(previous) = &previous {
+            if let Err(e) = self.unregister_hotkey(previous) {
+                tracing::warn!("Failed to remove quick math hotkey '{}': {}", previous, e);
+            }
+        }
+        *self.quick_math.lock().unwrap_or_else(|e| e.into_inner()) = shortcut.map(str::to_string);
+        Ok(())
+    }
+
+    /// Returns the hotkey evaluating the selection, if one is registered
+    pub fn get_quick_math_hotkey(&self) -> Option<String> {
+        self.quick_math.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Returns the hotkey currently opening the launcher, if any
+    pub fn get_active_hotkey(&self) -> Option<ActiveHotkey> {
+        self.active.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Returns whether a temporary hotkey stands in for the configured one
+    pub fn is_fallback_active(&self) -> bool {
+        self.get_active_hotkey().is_some_and(|active| active.is_fallback)
+    }
+
+    fn set_active(&self, shortcut: &str, preferred: &str, is_fallback: bool) {
+        *self.active.lock().unwrap_or_else(|e| e.into_inner()) = Some(ActiveHotkey {
+            shortcut: shortcut.to_string(),
+            preferred: preferred.to_string(),
+            is_fallback,
+        });
+    }
+
+    /// Unregisters the temporary hotkey, if one is live
+    fn clear_fallback(&self) {
+        let fallback = {
+            let mut active = self.active.lock().unwrap_or_else(|e| e.into_inner());
+            match active.take() {
+                Some(current) if current.is_fallback => current.shortcut,
+                other => {
+                    *active = other;
+                    return;
+                }
+            }
+        };
+
+        if let Err(e) = self.unregister_hotkey(&fallback) {
+            tracing::warn!("Failed to remove temporary hotkey '{}': {}", fallback, e);
+        }
+    }
+
     /// Unregisters a global hotkey
-    /// 
+    ///
     /// # Arguments
     /// * `shortcut` - The keyboard shortcut string to unregister
-    /// 
+    ///
     /// # Returns
     /// * `Result<()>` - Ok if unregistration succeeded, Err otherwise
     pub fn unregister_hotkey(&self, shortcut: &str) -> Result<(), LauncherError> {
-        // Parse the shortcut
-        let parsed_shortcut = shortcut.parse::<Shortcut>()
-            .map_err(|e| LauncherError::HotkeyRegistrationError(
-                format!("Invalid shortcut format '{}': {}", shortcut, e)
-            ))?;
-
-        // Unregister the shortcut
-        self.app_handle
-            .global_shortcut()
-            .unregister(parsed_shortcut)
-            .map_err(|e| LauncherError::HotkeyRegistrationError(
-                format!("Failed to unregister shortcut '{}': {}", shortcut, e)
-            ))?;
+        self.backend.unregister(shortcut)?;
 
         // Remove from registered shortcuts list
         let mut shortcuts = self.registered_shortcuts.lock()
             .map_err(|e| LauncherError::HotkeyRegistrationError(
                 format!("Failed to acquire lock: {}", e)
             ))?;
-        
+
         shortcuts.retain(|s| s != shortcut);
 
         tracing::info!("Successfully unregistered global hotkey: {}", shortcut);
         Ok(())
     }
 
+    /// Checks that a hotkey is well-formed and free to register
+    ///
+    /// Shortcuts not held by this app are briefly registered and released,
+    /// so conflicts with other applications show up before settings are saved.
+    pub fn validate_hotkey(&self, shortcut: &str) -> Result<(), LauncherError> {
+        self.validate_shortcut(shortcut)?;
+
+        let held = self.get_registered_shortcuts()?.iter().any(|s| s.eq_ignore_ascii_case(shortcut));
+        if held {
+            return Ok(());
+        }
+
+        self.backend.register(shortcut, HotkeyAction::ToggleLauncher).map_err(|e| LauncherError::HotkeyRegistrationError(
+            format!("'{}' is already in use by another application: {}", shortcut, e)
+        ))?;
+        if let Err(e) = self.backend.unregister(shortcut) {
+            tracing::warn!("Failed to release '{}' after checking it: {}", shortcut, e);
+        }
+        Ok(())
+    }
+
     /// Validates a shortcut string format
-    /// 
+    ///
     /// # Arguments
     /// * `shortcut` - The keyboard shortcut string to validate
-    /// 
+    ///
     /// # Returns
     /// * `Result<()>` - Ok if valid, Err with validation error otherwise
     fn validate_shortcut(&self, shortcut: &str) -> Result<(), LauncherError> {
@@ -120,7 +405,7 @@ impl GlobalHotkeyManager {
         // Check for valid modifier keys
         let valid_modifiers = ["Ctrl", "Alt", "Shift", "Super", "Command", "Option"];
         let parts: Vec<&str> = shortcut.split('+').collect();
-        
+
         if parts.len() < 2 {
             return Err(LauncherError::HotkeyRegistrationError(
                 format!("Shortcut '{}' must include at least one modifier key", shortcut)
@@ -147,16 +432,26 @@ impl GlobalHotkeyManager {
             .map_err(|e| LauncherError::HotkeyRegistrationError(
                 format!("Failed to acquire lock: {}", e)
             ))?;
-        
+
         Ok(shortcuts.clone())
     }
 }
 
+/// Fallback shortcuts to try for `preferred`, in order, skipping `preferred` itself
+pub fn fallback_candidates(preferred: &str) -> impl Iterator<Item = &'static str> + '_ {
+    FALLBACK_HOTKEYS
+        .iter()
+        .copied()
+        .filter(move |candidate| !candidate.eq_ignore_ascii_case(preferred))
+}
+
 #[cfg(test)]
 mod tests {
-    // Note: These tests require a Tauri app context which is not available in unit tests
-    // Integration tests should be used for full hotkey functionality testing
-    
+    // Note: The plugin backend requires a Tauri app context which is not available in unit tests,
+    // so the tests below register shortcuts through an in-memory backend
+    use super::*;
+    use std::collections::HashSet;
+
     #[test]
     fn test_validate_shortcut_empty() {
         // We can't create a real GlobalHotkeyManager without AppHandle,
@@ -180,4 +475,178 @@ mod tests {
         let parts: Vec<&str> = shortcut.split('+').collect();
         assert!(parts.len() < 2, "Shortcut without modifier should be invalid");
     }
+
+    /// Backend where some shortcuts are held by other applications
+    #[derive(Clone, Default)]
+    struct FakeBackend {
+        taken: Arc<Mutex<HashSet<String>>>,
+        registered: Arc<Mutex<Vec<String>>>,
+        attempts: Arc<Mutex<Vec<String>>>,
+        actions: Arc<Mutex<Vec<(String, HotkeyAction)>>>,
+    }
+
+    impl FakeBackend {
+        fn taking(shortcuts: &[&str]) -> Self {
+            let backend = Self::default();
+            backend.taken.lock().unwrap().extend(shortcuts.iter().map(|s| s.to_string()));
+            backend
+        }
+    }
+
+    impl ShortcutBackend for FakeBackend {
+        fn register(&self, shortcut: &str, action: HotkeyAction) -> Result<(), LauncherError> {
+            self.attempts.lock().unwrap().push(shortcut.to_string());
+            let mut registered = self.registered.lock().unwrap();
+            if self.taken.lock().unwrap().contains(shortcut) || registered.contains(&shortcut.to_string()) {
+                return Err(LauncherError::HotkeyRegistrationError(format!("'{}' is taken", shortcut)));
+            }
+            registered.push(shortcut.to_string());
+            self.actions.lock().unwrap().push((shortcut.to_string(), action));
+            Ok(())
+        }
+
+        fn unregister(&self, shortcut: &str) -> Result<(), LauncherError> {
+            self.registered.lock().unwrap().retain(|s| s != shortcut);
+            self.actions.lock().unwrap().retain(|(s, _)| s != shortcut);
+            Ok(())
+        }
+    }
+
+    fn manager(backend: &FakeBackend) -> (GlobalHotkeyManager, Arc<Mutex<Vec<HotkeyFallback>>>) {
+        let manager = GlobalHotkeyManager::with_backend(Box::new(backend.clone()));
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        manager.set_fallback_listener(move |fallback| sink.lock().unwrap().push(fallback.clone()));
+        (manager, events)
+    }
+
+    #[test]
+    fn test_fallbacks_are_tried_in_order() {
+        let backend = FakeBackend::taking(&["Ctrl+K", "Ctrl+Space", "Alt+Space"]);
+        let (manager, _) = manager(&backend);
+
+        assert_eq!(manager.register_with_fallback("Ctrl+K").unwrap(), "Alt+Shift+Space");
+        assert_eq!(
+            *backend.attempts.lock().unwrap(),
+            vec!["Ctrl+K", "Ctrl+Space", "Alt+Space", "Alt+Shift+Space"]
+        );
+
+        // The preferred hotkey is never retried as its own fallback
+        assert_eq!(
+            fallback_candidates("alt+space").collect::<Vec<_>>(),
+            vec!["Ctrl+Space", "Alt+Shift+Space", "Ctrl+Shift+Space"]
+        );
+    }
+
+    #[test]
+    fn test_fallback_event_and_active_hotkey() {
+        let backend = FakeBackend::taking(&["Ctrl+K"]);
+        let (manager, events) = manager(&backend);
+
+        manager.register_with_fallback("Ctrl+K").unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].preferred, "Ctrl+K");
+        assert_eq!(events[0].active, "Ctrl+Space");
+        assert!(events[0].reason.contains("taken"));
+
+        let payload = serde_json::to_value(&events[0]).unwrap();
+        assert_eq!(payload["preferred"], "Ctrl+K");
+        assert_eq!(payload["active"], "Ctrl+Space");
+
+        let active = manager.get_active_hotkey().unwrap();
+        assert_eq!(active.shortcut, "Ctrl+Space");
+        assert_eq!(active.preferred, "Ctrl+K");
+        assert!(active.is_fallback);
+    }
+
+    #[test]
+    fn test_registering_preferred_removes_temporary_hotkey() {
+        let backend = FakeBackend::taking(&["Ctrl+K"]);
+        let (manager, events) = manager(&backend);
+        manager.register_with_fallback("Ctrl+K").unwrap();
+        assert!(manager.is_fallback_active());
+
+        // The other application lets go of the hotkey
+        backend.taken.lock().unwrap().clear();
+        assert_eq!(manager.register_with_fallback("Ctrl+K").unwrap(), "Ctrl+K");
+
+        assert_eq!(*backend.registered.lock().unwrap(), vec!["Ctrl+K"]);
+        assert_eq!(manager.get_registered_shortcuts().unwrap(), vec!["Ctrl+K"]);
+        assert!(!manager.is_fallback_active());
+        assert_eq!(manager.get_active_hotkey().unwrap().shortcut, "Ctrl+K");
+        assert_eq!(events.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_no_free_fallback_reports_error() {
+        let mut taken = vec!["Ctrl+K"];
+        taken.extend_from_slice(FALLBACK_HOTKEYS);
+        let backend = FakeBackend::taking(&taken);
+        let (manager, events) = manager(&backend);
+
+        let error = manager.register_with_fallback("Ctrl+K").unwrap_err().to_string();
+        assert!(error.contains("no fallback hotkey"));
+        assert!(manager.get_active_hotkey().is_none());
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_refresh_registration_registers_the_hotkey_again() {
+        let backend = FakeBackend::default();
+        let (manager, _) = manager(&backend);
+        assert!(manager.refresh_registration().is_err());
+
+        manager.register_with_fallback("Ctrl+K").unwrap();
+        assert_eq!(manager.refresh_registration().unwrap(), "Ctrl+K");
+        assert_eq!(*backend.attempts.lock().unwrap(), vec!["Ctrl+K", "Ctrl+K"]);
+        assert_eq!(*backend.registered.lock().unwrap(), vec!["Ctrl+K"]);
+    }
+
+    #[test]
+    fn test_validate_hotkey_detects_conflicts() {
+        let backend = FakeBackend::taking(&["Ctrl+K"]);
+        let (manager, _) = manager(&backend);
+
+        assert!(manager.validate_hotkey("Ctrl+K").unwrap_err().to_string().contains("already in use"));
+        assert!(manager.validate_hotkey("K").is_err());
+
+        // Checking a free hotkey leaves nothing registered
+        manager.validate_hotkey("Ctrl+J").unwrap();
+        assert!(backend.registered.lock().unwrap().is_empty());
+
+        // A hotkey this app already holds is not a conflict
+        manager.register_hotkey("Ctrl+J").unwrap();
+        manager.validate_hotkey("Ctrl+J").unwrap();
+    }
+
+    #[test]
+    fn test_quick_math_hotkey_is_replaced_and_cleared() {
+        let backend = FakeBackend::taking(&["Ctrl+Shift+C"]);
+        let (manager, _) = manager(&backend);
+        manager.register_with_fallback("Ctrl+K").unwrap();
+
+        manager.set_quick_math_hotkey(Some("Ctrl+Shift+M")).unwrap();
+        assert_eq!(
+            *backend.actions.lock().unwrap(),
+            vec![
+                ("Ctrl+K".to_string(), HotkeyAction::ToggleLauncher),
+                ("Ctrl+Shift+M".to_string(), HotkeyAction::QuickMath),
+            ]
+        );
+        assert_eq!(HotkeyAction::QuickMath.event(), "quick-math-pressed");
+
+        // A taken or launcher hotkey leaves the current one in place
+        assert!(manager.set_quick_math_hotkey(Some("Ctrl+Shift+C")).is_err());
+        assert!(manager.set_quick_math_hotkey(Some("ctrl+k")).unwrap_err().to_string().contains("launcher"));
+        assert_eq!(manager.get_quick_math_hotkey().as_deref(), Some("Ctrl+Shift+M"));
+
+        manager.set_quick_math_hotkey(Some("Ctrl+Alt+M")).unwrap();
+        assert_eq!(*backend.registered.lock().unwrap(), vec!["Ctrl+K", "Ctrl+Alt+M"]);
+
+        manager.set_quick_math_hotkey(None).unwrap();
+        assert_eq!(*backend.registered.lock().unwrap(), vec!["Ctrl+K"]);
+        assert_eq!(manager.get_quick_math_hotkey(), None);
+    }
 }
@@ -8,12 +8,46 @@ pub mod hotkey;
 pub mod tray;
 pub mod autostart;
 pub mod updater;
+pub mod single_instance;
+pub mod auto_paste;
+pub mod quick_math;
+pub mod drag_drop;
+pub mod system_events;
+pub mod uri_scheme;
+pub mod window_show;
+pub mod shutdown;
+pub mod self_test;
+pub mod startup;
 
-use settings::AppSettings;
-use hotkey::GlobalHotkeyManager;
-use search::{SearchEngine, SearchProvider};
-use types::SearchResult;
-use std::sync::Arc;
+use auto_paste::AutoPaste;
+use quick_math::{QuickMath, QuickMathOutcome};
+use drag_drop::{DragDrop, DropEffect};
+use utils::notification::NotificationRouter;
+use utils::sound::{SoundEvent, SoundPlayer};
+use settings::{AppSettings, NotificationCategory, SaveOutcome, SettingsConflict};
+use system_events::ResumeCoordinator;
+use hotkey::{ActiveHotkey, GlobalHotkeyManager};
+use search::{
+    ActionDescriptor, FeedbackPenalty, LazyProvider, ProviderConfigSchema, ProviderGuard, ResultFeedback, SearchAnalytics,
+    SearchEngine, SearchProvider, SharedProvider,
+};
+use search::providers::favorites::{FavoritesStore, Pin};
+use search::providers::saved_searches::{reserved_keywords, SavedSearch, SavedSearchStore};
+use search::providers::{CalculatorHistory, PluginHost, PluginInfo};
+use search::providers::{ActiveExtensionWeights, ActiveMacros, ActiveProfiles, ActiveWeatherPreferences, FolderScopes, OpenInSourceBrowser};
+use types::{
+    BatchAction, BatchSummary, Diagnostics, ExecutionState, ExecutionTicket, ProviderStatus, ResultPage,
+    SearchResponse, SearchResult, SearchSection,
+};
+use utils::db_integrity::{CheckPolicy, IntegrityOutcome};
+use utils::show_latency::show_latency;
+use utils::memory::{process_working_set, MemoryWatchdog, MEMORY_CHECK_INTERVAL};
+use window_show::PreparedPlacement;
+use shutdown::{CleanShutdownMarker, ShutdownCoordinator, ShutdownReason};
+use self_test::{SelfTest, SelfTestReport};
+use startup::{ProviderStartups, StartupEvent, MAX_CONCURRENT_STARTS, PROVIDERS_READY_EVENT, PROVIDER_REGISTERED_EVENT};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tauri::{Manager, Emitter};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -54,112 +88,938 @@ fn get_registered_hotkeys(
         .map_err(|e| e.to_string())
 }
 
+/// Tauri command returning the hotkey that actually opens the launcher,
+/// which is a temporary fallback while the configured one is taken
+#[tauri::command]
+fn get_active_hotkey(hotkey_manager: tauri::State<Arc<GlobalHotkeyManager>>) -> Option<ActiveHotkey> {
+    hotkey_manager.get_active_hotkey()
+}
+
+/// Tauri command checking that a hotkey is well-formed and not taken by another app
+#[tauri::command]
+fn validate_hotkey(
+    hotkey_manager: tauri::State<Arc<GlobalHotkeyManager>>,
+    shortcut: String,
+) -> Result<(), String> {
+    hotkey_manager
+        .validate_hotkey(&shortcut)
+        .map_err(|e| e.to_string())
+}
+
+/// Resizes the main window to the configured width and visible rows, using the
+/// scale factor of the monitor it is currently on
+pub(crate) fn apply_window_appearance(
+    window: &tauri::WebviewWindow,
+    appearance: &settings::Appearance,
+) -> tauri::Result<()> {
+    let scale_factor = match window.current_monitor()? {
+        Some(monitor) => monitor.scale_factor(),
+        None => window.scale_factor()?,
+    };
+    resize_window(window, appearance, scale_factor)
+}
+
+/// Resizes the main window in physical pixels for the given scale factor
+fn resize_window(
+    window: &tauri::WebviewWindow,
+    appearance: &settings::Appearance,
+    scale_factor: f64,
+) -> tauri::Result<()> {
+    let (width, height) = appearance.physical_size(scale_factor);
+    window.set_size(tauri::PhysicalSize::new(width, height))
+}
+
+/// Returns the monitor the launcher should open on: the one under the cursor,
+/// else the window's current monitor, else the primary monitor
+pub(crate) fn target_monitor(window: &tauri::WebviewWindow) -> Option<tauri::Monitor> {
+    window
+        .cursor_position()
+        .ok()
+        .and_then(|cursor| window.monitor_from_point(cursor.x, cursor.y).ok().flatten())
+        .or_else(|| window.current_monitor().ok().flatten())
+        .or_else(|| window.primary_monitor().ok().flatten())
+}
+
+/// Loads the configured appearance, falling back to defaults
+pub(crate) fn load_appearance() -> settings::Appearance {
+    AppSettings::load_effective()
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load settings for window appearance: {}, using defaults", e);
+            AppSettings::default()
+        })
+        .appearance()
+}
+
 /// Tauri command to show the main window
 #[tauri::command]
 fn show_window(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("main") {
-        window.show().map_err(|e| e.to_string())?;
-        window.set_focus().map_err(|e| e.to_string())?;
-        window.center().map_err(|e| e.to_string())?;
-        tracing::info!("Window shown and centered");
-        Ok(())
-    } else {
-        Err("Main window not found".to_string())
+    window_show::show_main_window(&app).map_err(|e| e.to_string())
+}
+
+/// Tauri command the frontend calls once the search box has focus after a
+/// show, closing the hotkey latency sample
+#[tauri::command]
+fn report_input_ready() {
+    if let Some(latency) = show_latency().report_input_ready(std::time::Instant::now()) {
+        tracing::debug!("Search box ready {:?} after the hotkey", latency);
+    }
+}
+
+/// Registers Windows Search as the file search fallback unless its service is unavailable
+async fn register_windows_search_fallback(
+    engine: &SearchEngine,
+    app: &tauri::AppHandle,
+    scopes: &FolderScopes,
+    extension_weights: &ActiveExtensionWeights,
+) {
+    let Ok(provider) = search::providers::WindowsSearchProvider::new() else {
+        tracing::error!("Failed to initialize WindowsSearchProvider fallback");
+        return;
+    };
+    let mut provider = provider
+        .with_folder_scopes(scopes.clone())
+        .with_extension_weights(extension_weights.clone());
+
+    if let Err(e) = provider.initialize().await {
+        tracing::error!("Windows Search fallback is unhealthy: {}", e);
+        utils::notify_warning(app, NotificationCategory::ProviderHealth, "File Search Unavailable", Some(e.to_string()));
+        return;
     }
+
+    engine.register_provider(Arc::new(provider)).await;
+    tracing::info!("WindowsSearchProvider registered as fallback");
+}
+
+/// Checks the SQLite stores, rebuilding damaged ones, and tells the user once
+/// what was recovered
+///
+/// Under `CheckPolicy::Scheduled` each store is checked at most once a day,
+/// so this is cheap on most starts.
+async fn check_store_integrity(app: &tauri::AppHandle, feedback: &Arc<ResultFeedback>, policy: CheckPolicy) {
+    let now = chrono::Utc::now();
+    let mut outcomes = Vec::new();
+
+    if let Some(recent_files) = app.try_state::<Arc<tokio::sync::RwLock<search::providers::RecentFilesProvider>>>() {
+        outcomes.push(("Recent files", recent_files.read().await.check_integrity(now, policy).await));
+    }
+    if let Some(clipboard) = app.try_state::<Arc<tokio::sync::RwLock<search::providers::ClipboardHistoryProvider>>>() {
+        outcomes.push(("Clipboard history", clipboard.read().await.check_integrity(now, policy).await));
+    }
+    let feedback = Arc::clone(feedback);
+    let feedback_outcome = tokio::task::spawn_blocking(move || feedback.check_integrity(now, policy))
+        .await
+        .unwrap_or_else(|e| Err(error::LauncherError::ExecutionError(e.to_string())));
+    outcomes.push(("Search feedback", feedback_outcome));
+
+    let mut recovered = Vec::new();
+    for (store, outcome) in outcomes {
+        match outcome {
+            Ok(IntegrityOutcome::Recovered(recovery)) => recovered.push(format!(
+                "{}: {} entries recovered, damaged copy kept as {}",
+                store,
+                recovery.rows,
+                recovery.archive_path.display()
+            )),
+            Ok(outcome) => tracing::debug!("{} integrity check: {:?}", store, outcome),
+            Err(e) => tracing::error!("{} integrity check failed: {}", store, e),
+        }
+    }
+
+    if !recovered.is_empty() {
+        utils::notify_warning(app, NotificationCategory::General, "Repaired Damaged Data", Some(recovered.join("\n")));
+    }
+}
+
+/// Returns a lazy provider's ready handler, which drops cached searches that
+/// were answered while the provider was still loading
+fn invalidate_cache_on_ready(engine: &Arc<SearchEngine>) -> impl Fn(&str) + Send + Sync + 'static {
+    let engine = Arc::downgrade(engine);
+    move |provider| {
+        let Some(engine) = engine.upgrade() else {
+            return;
+        };
+        tracing::info!("{} is ready, invalidating search cache", provider);
+        tauri::async_runtime::spawn(async move {
+            engine.invalidate_cache().await;
+        });
+    }
+}
+
+/// Tauri command returning the query passed on the command line, only the first time
+#[tauri::command]
+fn take_initial_query(pending: tauri::State<single_instance::PendingQuery>) -> Option<String> {
+    pending.take()
 }
 
 /// Tauri command to hide the main window
+///
+/// Does nothing while a result is being dragged out, since hiding the
+/// window would cancel the drag.
 #[tauri::command]
-fn hide_window(app: tauri::AppHandle) -> Result<(), String> {
+fn hide_window(app: tauri::AppHandle, drag_drop: tauri::State<Arc<DragDrop>>) -> Result<(), String> {
+    if drag_drop.is_dragging() {
+        tracing::debug!("Not hiding the window during a drag");
+        return Ok(());
+    }
+
     if let Some(window) = app.get_webview_window("main") {
         window.hide().map_err(|e| e.to_string())?;
         tracing::info!("Window hidden");
+        window_show::prepare_next_show(&app, &window);
         Ok(())
     } else {
         Err("Main window not found".to_string())
     }
 }
 
-/// Tauri command to perform a search query
+/// Tauri command to save state and exit, like the tray's Exit item
+#[tauri::command]
+fn quit_app(app: tauri::AppHandle) {
+    tracing::info!("Quit command received");
+    shutdown::quit(&app);
+}
+
+/// Tauri command to perform a search query
+///
+/// Returns the first page of results; `search_id` is set when more can be
+/// fetched with `get_results_page`.
+#[tauri::command]
+async fn search_query(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    query: String,
+) -> Result<SearchResponse, String> {
+    tracing::debug!("Search command received: '{}'", query);
+    
+    let response = search_engine.search_paged(&query).await;
+    Ok(response)
+}
+
+/// Tauri command previewing the instant answers for the query being typed
+///
+/// Only providers such as the calculator are asked, within a few
+/// milliseconds; results carry the `peek` metadata flag since
+/// `search_query` returns them again.
+#[tauri::command]
+async fn peek(search_engine: tauri::State<'_, Arc<SearchEngine>>, query: String) -> Result<Vec<SearchResult>, String> {
+    Ok(search_engine.peek(&query).await)
+}
+
+/// Tauri command narrowing a search to the result types of the selected
+/// filter chips
+///
+/// Reuses the results `search_query` found for the same query when they are
+/// still kept; an empty `active_facets` shows every type again.
+#[tauri::command]
+async fn search_query_faceted(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    query: String,
+    active_facets: Vec<types::ResultType>,
+) -> Result<SearchResponse, String> {
+    Ok(search_engine.search_faceted(&query, &active_facets).await)
+}
+
+/// Tauri command returning more results of a search made with `search_query`
+///
+/// Pass the active facets of a narrowed search as `types`.
+#[tauri::command]
+async fn get_results_page(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    search_id: String,
+    offset: usize,
+    limit: usize,
+    types: Option<Vec<types::ResultType>>,
+) -> Result<ResultPage, String> {
+    search_engine
+        .results_page_of_types(&search_id, offset, limit, types.as_deref().unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command dropping the results kept for paging through a search
+#[tauri::command]
+async fn release_search(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    search_id: String,
+) -> Result<bool, String> {
+    Ok(search_engine.release_search(&search_id))
+}
+
+/// Tauri command asking providers to fill in details of shown results
+///
+/// Enriched results arrive in batches through the `results-enriched` event.
+#[tauri::command]
+async fn request_enrichment(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    search_id: String,
+    result_ids: Vec<String>,
+) -> Result<usize, String> {
+    Ok(search_engine.inner().request_enrichment(&search_id, result_ids))
+}
+
+/// Tauri command returning the inline completion for the search box, if any
+///
+/// Reads only in-memory candidates (pins, recent executions, app names), so
+/// the frontend can call it on every keystroke alongside the debounced search.
+#[tauri::command]
+async fn get_completion(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    query: String,
+) -> Result<Option<search::completion::Completion>, String> {
+    Ok(search_engine.completion(&query).await)
+}
+
+/// Tauri command reporting the health of every registered provider
+#[tauri::command]
+async fn get_provider_health(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+) -> Result<Vec<ProviderStatus>, String> {
+    Ok(search_engine.provider_health().await)
+}
+
+/// Tauri command reporting cache sizes, the result cache hit rate and the working set
+#[tauri::command]
+async fn get_diagnostics(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+) -> Result<Diagnostics, String> {
+    Ok(search_engine.diagnostics().await)
+}
+
+/// Tauri command dropping cold cache entries, returning how many were removed
+#[tauri::command]
+async fn trim_caches(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+) -> Result<usize, String> {
+    Ok(search_engine.trim_caches().await)
+}
+
+/// Tauri command to search once and split the results into keyed sections
+#[tauri::command]
+async fn search_sections(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    query: String,
+    sections: Vec<SearchSection>,
+) -> Result<HashMap<usize, Vec<SearchResult>>, String> {
+    tracing::debug!("Sectioned search command received: '{}' ({} sections)", query, sections.len());
+    
+    let results = search_engine.search_sections(&query, &sections).await;
+    Ok(results)
+}
+
+/// Tauri command to move a file result to the Recycle Bin
+///
+/// The frontend must ask the user first and pass `confirmed`; unconfirmed
+/// requests are rejected without touching the file.
+#[tauri::command]
+async fn recycle_file_result(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+    confirmed: bool,
+) -> Result<(), String> {
+    tracing::info!("Recycle file command received: {} (confirmed={})", result.title, confirmed);
+    
+    search_engine
+        .recycle_file_result(&result, confirmed)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to explain how a result's score was computed for a query
+#[tauri::command]
+async fn explain_result(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    query: String,
+    result_id: String,
+) -> Result<search::ScoreBreakdown, String> {
+    tracing::debug!("Explain result command received: '{}' for '{}'", result_id, query);
+    
+    search_engine
+        .explain_result(&query, &result_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to mark a result as not useful for a query
+///
+/// The result ranks lower for the query from now on and is hidden once it
+/// was demoted often enough.
+#[tauri::command]
+async fn demote_result(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    query: String,
+    result_id: String,
+) -> Result<FeedbackPenalty, String> {
+    tracing::info!("Demote result command received: '{}' for '{}'", result_id, query);
+
+    search_engine
+        .demote_result(&query, &result_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to forget every "Not useful" demotion
+#[tauri::command]
+async fn clear_feedback(search_engine: tauri::State<'_, Arc<SearchEngine>>) -> Result<usize, String> {
+    tracing::info!("Clear feedback command received");
+
+    search_engine.clear_feedback().await.map_err(|e| e.to_string())
+}
+
+/// Tauri command to write the recorded search analytics to a JSON file
+#[tauri::command]
+async fn export_analytics(search_engine: tauri::State<'_, Arc<SearchEngine>>, path: String) -> Result<usize, String> {
+    tracing::info!("Export analytics command received: {}", path);
+
+    search_engine
+        .analytics()
+        .export(std::path::Path::new(&path))
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to delete the recorded search analytics
+#[tauri::command]
+async fn clear_analytics(search_engine: tauri::State<'_, Arc<SearchEngine>>) -> Result<usize, String> {
+    tracing::info!("Clear analytics command received");
+
+    search_engine.analytics().clear().map_err(|e| e.to_string())
+}
+
+/// Tauri command to execute a search result action
+///
+/// Returns as soon as the action has started; progress arrives through
+/// `execution-status` events. When `keep_open` is omitted the
+/// `hide_after_execute` setting decides whether the window hides on success.
+#[tauri::command]
+async fn execute_result(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+    keep_open: Option<bool>,
+) -> Result<ExecutionTicket, String> {
+    tracing::info!("Execute result command received: {} (keep_open={:?})", result.title, keep_open);
+    search_engine.verify_execution(&result)?;
+    
+    let settings = AppSettings::load_effective().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load settings for execute_result: {}, using defaults", e);
+        AppSettings::default()
+    });
+    // Results opening something inside the window, such as the Settings view,
+    // must not hide it
+    let keep_open = if result.keeps_window_open() { Some(true) } else { keep_open };
+    let hide_on_success = settings.should_hide_after_execute(true, keep_open);
+    
+    let execution = search_engine.inner().execute_result(result).await;
+    
+    Ok(ExecutionTicket {
+        execution_id: execution.id,
+        hide_on_success,
+        duplicate: execution.duplicate,
+    })
+}
+
+/// Tauri command to apply one action to several file results
+///
+/// Waits until every result was processed; non-file results reject the whole batch.
+#[tauri::command]
+async fn execute_results_batch(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    results: Vec<SearchResult>,
+    action: BatchAction,
+) -> Result<BatchSummary, String> {
+    tracing::info!("Batch execute command received: {} results ({:?})", results.len(), action);
+    for result in &results {
+        search_engine.verify_result(result)?;
+    }
+    
+    search_engine
+        .execute_results_batch(&results, action)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to stop waiting for a stuck execution
+#[tauri::command]
+async fn cancel_execution(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    execution_id: String,
+) -> Result<(), String> {
+    tracing::info!("Cancel execution command received: {}", execution_id);
+    
+    search_engine
+        .cancel_execution(&execution_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to pin a search result under an alias
+#[tauri::command]
+async fn pin_result(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+    alias: Option<String>,
+) -> Result<Pin, String> {
+    tracing::info!("Pin result command received: {}", result.title);
+    // Pins come back from searches signed, so only real results may be pinned
+    search_engine.verify_result(&result)?;
+    
+    search_engine
+        .pin_result(result, alias.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to remove a pinned result
+#[tauri::command]
+async fn unpin_result(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    id: String,
+) -> Result<bool, String> {
+    tracing::info!("Unpin result command received: {}", id);
+    
+    search_engine.unpin_result(&id).await.map_err(|e| e.to_string())
+}
+
+/// Tauri command to list pinned results
+#[tauri::command]
+async fn list_pins(
+    favorites: tauri::State<'_, Arc<FavoritesStore>>,
+) -> Result<Vec<Pin>, String> {
+    tracing::debug!("List pins command received");
+    
+    Ok(favorites.list().await)
+}
+
+/// Tauri command to save a query under a name, with the sections it ran with
+///
+/// Names starting with a search keyword, such as `ssh` or a macro's, are rejected.
+#[tauri::command]
+async fn save_search(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    saved_searches: tauri::State<'_, Arc<SavedSearchStore>>,
+    active_macros: tauri::State<'_, ActiveMacros>,
+    name: String,
+    query: String,
+    sections: Option<Vec<SearchSection>>,
+) -> Result<SavedSearch, String> {
+    tracing::info!("Save search command received: {}", name);
+
+    let reserved = reserved_keywords(&search_engine.syntax_registry(), &active_macros.get());
+    let saved = saved_searches
+        .save(&name, &query, sections.unwrap_or_default(), &reserved)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // The name must find the search on the next keystroke
+    search_engine.invalidate_results().await;
+    Ok(saved)
+}
+
+/// Tauri command to delete a saved search
+#[tauri::command]
+async fn delete_saved_search(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    saved_searches: tauri::State<'_, Arc<SavedSearchStore>>,
+    name: String,
+) -> Result<bool, String> {
+    tracing::info!("Delete saved search command received: {}", name);
+
+    let removed = saved_searches.delete(&name).await.map_err(|e| e.to_string())?;
+    search_engine.invalidate_results().await;
+    Ok(removed)
+}
+
+/// Tauri command to list saved searches
+#[tauri::command]
+async fn list_saved_searches(
+    saved_searches: tauri::State<'_, Arc<SavedSearchStore>>,
+) -> Result<Vec<SavedSearch>, String> {
+    tracing::debug!("List saved searches command received");
+
+    Ok(saved_searches.list().await)
+}
+
+/// Tauri command forgetting every past calculation, returning how many there were
+#[tauri::command]
+async fn clear_calculator_history(
+    calculator_history: tauri::State<'_, Arc<CalculatorHistory>>,
+) -> Result<usize, String> {
+    tracing::info!("Clear calculator history command received");
+
+    calculator_history.clear().await.map_err(|e| e.to_string())
+}
+
+/// Runs the self-test against the running app and writes the report to the log
+pub(crate) async fn run_self_test_checks(app: &tauri::AppHandle) -> SelfTestReport {
+    let mut checks = SelfTest::new();
+    let search_engine = Arc::clone(&app.state::<Arc<SearchEngine>>());
+    checks.add_engine_checks(search_engine).await;
+
+    let active_hotkey = app.state::<Arc<GlobalHotkeyManager>>().get_active_hotkey();
+    checks.add_check("hotkey", move || async move { Ok(self_test::hotkey_outcome(active_hotkey)) });
+    checks.add_check("settings", self_test::check_settings_round_trip);
+    for (name, dir) in self_test::required_directories() {
+        checks.add_check(name, move || self_test::check_writable(dir));
+    }
+    checks.add_check("Everything", || async { Ok(self_test::everything_outcome()) });
+    checks.add_check("Windows Search", || async { Ok(self_test::windows_search_outcome()) });
+
+    let report = checks.run().await;
+    report.log();
+    report
+}
+
+/// Runs the self-test and copies its redacted report, returning the report
+pub(crate) async fn copy_self_test_checks(app: &tauri::AppHandle) -> Result<SelfTestReport, String> {
+    let report = run_self_test_checks(app).await;
+    utils::clipboard::set_clipboard_text(&report.to_text())
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(report)
+}
+
+/// Tauri command checking end to end that search, the hotkey, settings and
+/// the file indexes work
+#[tauri::command]
+async fn run_self_test(app: tauri::AppHandle) -> Result<SelfTestReport, String> {
+    tracing::info!("Run self-test command received");
+
+    Ok(run_self_test_checks(&app).await)
+}
+
+/// Tauri command running the self-test and copying its report, with paths
+/// and the user name removed, for pasting into an issue
+#[tauri::command]
+async fn copy_self_test_report(app: tauri::AppHandle) -> Result<SelfTestReport, String> {
+    tracing::info!("Copy self-test report command received");
+
+    copy_self_test_checks(&app).await
+}
+
+/// Tauri command to list the external plugins, including those that failed to load
+#[tauri::command]
+async fn list_plugins(plugin_host: tauri::State<'_, Arc<PluginHost>>) -> Result<Vec<PluginInfo>, String> {
+    tracing::debug!("List plugins command received");
+
+    Ok(plugin_host.list().await)
+}
+
+/// Tauri command to load the plugins again after one was added, edited or removed
+///
+/// Running plugins are stopped; each starts again on its next query.
+#[tauri::command]
+async fn reload_plugins(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    plugin_host: tauri::State<'_, Arc<PluginHost>>,
+) -> Result<Vec<PluginInfo>, String> {
+    tracing::info!("Reload plugins command received");
+
+    plugin_host.reload(&search_engine).await;
+    Ok(plugin_host.list().await)
+}
+
+/// Tauri command to export clipboard history, optionally without the copied text
+#[tauri::command]
+async fn export_clipboard_history(
+    clipboard: tauri::State<'_, Arc<tokio::sync::RwLock<search::providers::ClipboardHistoryProvider>>>,
+    path: String,
+    metadata_only: bool,
+) -> Result<usize, String> {
+    tracing::info!("Export clipboard history command received: {}", path);
+
+    let clipboard = clipboard.read().await;
+    clipboard
+        .export(std::path::Path::new(&path), metadata_only)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to export the recent files list
+#[tauri::command]
+async fn export_recent_files(
+    recent_files: tauri::State<'_, Arc<tokio::sync::RwLock<search::providers::RecentFilesProvider>>>,
+    path: String,
+) -> Result<usize, String> {
+    tracing::info!("Export recent files command received: {}", path);
+
+    let recent_files = recent_files.read().await;
+    recent_files
+        .export(std::path::Path::new(&path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to clear clipboard history, or only items older than `older_than`
+#[tauri::command]
+async fn clear_clipboard_history(
+    clipboard: tauri::State<'_, Arc<tokio::sync::RwLock<search::providers::ClipboardHistoryProvider>>>,
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    older_than: Option<std::time::Duration>,
+) -> Result<usize, String> {
+    tracing::info!("Clear clipboard history command received");
+
+    let removed = clipboard.read().await.clear(older_than).await.map_err(|e| e.to_string())?;
+
+    // Cleared items must not come back from a cached query
+    search_engine.invalidate_cache().await;
+    Ok(removed)
+}
+
+/// Tauri command to copy a JSON or XML clipboard item pretty-printed
+#[tauri::command]
+async fn copy_formatted_clipboard_item(
+    clipboard: tauri::State<'_, Arc<tokio::sync::RwLock<search::providers::ClipboardHistoryProvider>>>,
+    result_id: String,
+) -> Result<(), String> {
+    tracing::info!("Copy formatted command received: {}", result_id);
+
+    clipboard
+        .read()
+        .await
+        .copy_formatted(&result_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to copy a betterfinder:// link to a result
+#[tauri::command]
+async fn copy_result_link(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+) -> Result<String, String> {
+    tracing::info!("Copy result link command received: {}", result.id);
+    // Opening the link executes the result, so only real results get one
+    search_engine.verify_result(&result)?;
+
+    search_engine.copy_result_link(&result).await.map_err(|e| e.to_string())
+}
+
+/// Tauri command to list the actions available for a result
+#[tauri::command]
+async fn get_result_actions(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+) -> Result<Vec<ActionDescriptor>, String> {
+    tracing::debug!("Get result actions command received: {}", result.id);
+
+    Ok(search_engine.result_actions(&result))
+}
+
+/// Tauri command to run one of a result's actions by id
+///
+/// Returns the execution id when the primary action was run.
+#[tauri::command]
+async fn execute_result_action_by_id(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+    action_id: String,
+    query: Option<String>,
+    confirmed: Option<bool>,
+) -> Result<Option<String>, String> {
+    tracing::info!("Execute result action command received: '{}' on {}", action_id, result.id);
+
+    search_engine
+        .execute_result_action(
+            &result,
+            &action_id,
+            query.as_deref().unwrap_or_default(),
+            confirmed.unwrap_or(false),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command to open the terminal in a file result's folder
+#[tauri::command]
+async fn open_in_terminal(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    result: SearchResult,
+) -> Result<(), String> {
+    tracing::info!("Open in terminal command received: {}", result.id);
+
+    search_engine.open_in_terminal(&result).map_err(|e| e.to_string())
+}
+
+/// Tauri command dragging a file result out of the launcher
+///
+/// Returns once the result is dropped or the drag is cancelled, with what
+/// the drop target did.
+#[tauri::command]
+async fn begin_drag(
+    search_engine: tauri::State<'_, Arc<SearchEngine>>,
+    drag_drop: tauri::State<'_, Arc<DragDrop>>,
+    result_id: String,
+) -> Result<DropEffect, String> {
+    tracing::info!("Begin drag command received: {}", result_id);
+
+    let result = search_engine.find_result(&result_id).await.map_err(|e| e.to_string())?;
+    drag_drop.begin(&result).await.map_err(|e| e.to_string())
+}
+
+/// Tauri command evaluating the text selected in another app, run by the quick math hotkey
+///
+/// Depending on settings the result is shown in a toast or typed over the
+/// selection; the clipboard is left as it was either way.
+#[tauri::command]
+async fn evaluate_clipboard(
+    app: tauri::AppHandle,
+    quick_math: tauri::State<'_, Arc<QuickMath>>,
+) -> Result<QuickMathOutcome, String> {
+    tracing::info!("Evaluate clipboard command received");
+
+    let outcome = quick_math.evaluate_selection().await.map_err(|e| e.to_string())?;
+    match &outcome {
+        QuickMathOutcome::Shown { expression, result } => {
+            utils::notify_success(&app, NotificationCategory::Execution, format!("= {}", result), Some(expression.clone()));
+        }
+        QuickMathOutcome::Replaced { expression, result } => {
+            tracing::info!("Replaced '{}' with {}", expression, result);
+        }
+        QuickMathOutcome::NotACalculation => {
+            utils::notify_info(&app, NotificationCategory::Execution, "Not a calculation", Some("Select an expression such as 12 * 4"));
+        }
+        QuickMathOutcome::NothingSelected => {
+            utils::notify_info(&app, NotificationCategory::Execution, "Nothing selected", None::<String>);
+        }
+    }
+    Ok(outcome)
+}
+
+/// Tauri command to clear recent files, or only entries older than `older_than`
 #[tauri::command]
-async fn search_query(
+async fn clear_recent_files(
+    recent_files: tauri::State<'_, Arc<tokio::sync::RwLock<search::providers::RecentFilesProvider>>>,
     search_engine: tauri::State<'_, Arc<SearchEngine>>,
-    query: String,
-) -> Result<Vec<SearchResult>, String> {
-    tracing::debug!("Search command received: '{}'", query);
+    older_than: Option<std::time::Duration>,
+) -> Result<usize, String> {
+    tracing::info!("Clear recent files command received");
+
+    let removed = recent_files.read().await.clear(older_than).await.map_err(|e| e.to_string())?;
+    search_engine.invalidate_cache().await;
+    Ok(removed)
+}
+
+/// Tauri command to get current settings
+#[tauri::command]
+fn get_settings() -> Result<AppSettings, String> {
+    tracing::debug!("Get settings command received");
     
-    let results = search_engine.search(&query).await;
-    Ok(results)
+    AppSettings::load()
+        .map_err(|e| e.to_string())
 }
 
-/// Tauri command to execute a search result action
+/// Tauri command to get the settings in effect, with the active profile applied
 #[tauri::command]
-async fn execute_result(
-    search_engine: tauri::State<'_, Arc<SearchEngine>>,
-    result: SearchResult,
-) -> Result<(), String> {
-    tracing::info!("Execute result command received: {}", result.title);
+fn get_effective_settings() -> Result<AppSettings, String> {
+    tracing::debug!("Get effective settings command received");
     
-    search_engine
-        .execute_result(&result)
-        .await
+    AppSettings::load_effective()
         .map_err(|e| e.to_string())
 }
 
-/// Tauri command to get current settings
+/// Tauri command listing providers skipped at startup after failing repeatedly
 #[tauri::command]
-fn get_settings() -> Result<AppSettings, String> {
-    tracing::debug!("Get settings command received");
+fn get_disabled_providers(provider_guard: tauri::State<Arc<ProviderGuard>>) -> Vec<String> {
+    provider_guard.disabled_providers()
+}
+
+/// Tauri command letting a skipped provider start again on the next launch
+#[tauri::command]
+fn retry_provider(provider_guard: tauri::State<Arc<ProviderGuard>>, provider: String) -> Result<(), String> {
+    tracing::info!("Retry provider command received: {}", provider);
     
-    AppSettings::load()
+    provider_guard.reset(&provider)
         .map_err(|e| e.to_string())
 }
 
+/// Tauri command listing the defaults and JSON schema of each provider's options
+#[tauri::command]
+fn get_provider_config_schema() -> Vec<ProviderConfigSchema> {
+    search::provider_config::provider_config_schemas()
+}
+
 /// Tauri command to get the resolved theme (resolves 'system' to actual theme)
+/// together with the Windows accent color
 #[tauri::command]
-fn get_resolved_theme() -> Result<settings::Theme, String> {
+fn get_resolved_theme() -> Result<utils::theme::ResolvedTheme, String> {
     tracing::debug!("Get resolved theme command received");
     
-    let settings = AppSettings::load().map_err(|e| e.to_string())?;
-    utils::theme::resolve_theme(settings.theme)
+    let settings = AppSettings::load_effective().map_err(|e| e.to_string())?;
+    utils::theme::resolve_theme_with_accent(settings.theme)
         .map_err(|e| e.to_string())
 }
 
 /// Tauri command to update settings
 #[tauri::command]
-async fn update_settings(
-    app: tauri::AppHandle,
-    hotkey_manager: tauri::State<'_, Arc<GlobalHotkeyManager>>,
-    settings: AppSettings,
-) -> Result<(), String> {
+async fn update_settings(app: tauri::AppHandle, settings: AppSettings) -> Result<(), String> {
     tracing::info!("Update settings command received");
     
     // Validate settings before applying
     settings.validate().map_err(|e| e.to_string())?;
+    settings.validate_launchers().map_err(|e| e.to_string())?;
     
     // Load current settings to compare
-    let current_settings = AppSettings::load().map_err(|e| e.to_string())?;
+    let current_settings = AppSettings::load_effective().map_err(|e| e.to_string())?;
+    
+    // Save first, so what gets applied is what is stored, including edits made to the file by hand
+    let settings = save_settings(&app, settings).await?;
+    let settings = settings.effective().map_err(|e| e.to_string())?;
+    apply_settings(&app, &current_settings, &settings).await?;
+    
+    tracing::info!("Settings updated successfully");
+    Ok(())
+}
+
+/// Saves settings, telling the frontend when the file changed underneath
+async fn save_settings(app: &tauri::AppHandle, settings: AppSettings) -> Result<AppSettings, String> {
+    match settings.save().await.map_err(|e| e.to_string())? {
+        SaveOutcome::Saved(saved) => Ok(saved),
+        SaveOutcome::Conflict { on_disk, fields } => {
+            let message = format!(
+                "The settings file was changed outside Better Finder ({}). Review the reloaded settings and save again.",
+                fields.join(", ")
+            );
+            if let Err(e) = app.emit("settings-conflict", SettingsConflict { settings: on_disk, fields }) {
+                tracing::warn!("Failed to emit settings-conflict event: {}", e);
+            }
+            Err(message)
+        }
+    }
+}
+
+/// Applies effective settings to the running app, changing what differs from `current`
+async fn apply_settings(app: &tauri::AppHandle, current: &AppSettings, settings: &AppSettings) -> Result<(), String> {
+    let hotkey_manager = app.state::<Arc<GlobalHotkeyManager>>();
+    let search_engine = app.state::<Arc<SearchEngine>>();
     
-    // If hotkey changed, re-register it
-    if settings.hotkey != current_settings.hotkey {
-        tracing::info!("Hotkey changed from '{}' to '{}'", current_settings.hotkey, settings.hotkey);
+    // If hotkey changed, re-register it; saving again while a fallback is live retries the preferred one
+    if settings.hotkey != current.hotkey || hotkey_manager.is_fallback_active() {
+        tracing::info!("Hotkey changed from '{}' to '{}'", current.hotkey, settings.hotkey);
         
         // Unregister old hotkey
-        if let Err(e) = hotkey_manager.unregister_hotkey(&current_settings.hotkey) {
+        if let Err(e) = hotkey_manager.unregister_hotkey(&current.hotkey) {
             tracing::warn!("Failed to unregister old hotkey: {}", e);
         }
         
-        // Register new hotkey
-        hotkey_manager
-            .register_hotkey(&settings.hotkey)
-            .map_err(|e| format!("Failed to register new hotkey: {}", e))?;
+        // Register new hotkey, or a temporary one if it is taken; when neither
+        // works, e.g. after switching away from a profile's hotkey, keep the old one
+        let active = match hotkey_manager.register_with_fallback(&settings.hotkey) {
+            Ok(active) => active,
+            Err(e) => {
+                if let Err(restore) = hotkey_manager.register_with_fallback(&current.hotkey) {
+                    tracing::error!("Failed to restore hotkey '{}': {}", current.hotkey, restore);
+                }
+                return Err(format!("Failed to register new hotkey: {}", e));
+            }
+        };
         
-        tracing::info!("Hotkey successfully changed to '{}'", settings.hotkey);
+        tracing::info!("Hotkey successfully changed to '{}'", active);
     }
     
+    // Register, replace or remove the quick math hotkey; a no-op when it's already live
+    hotkey_manager
+        .set_quick_math_hotkey(settings.quick_math_hotkey.as_deref())
+        .map_err(|e| format!("Failed to register quick math hotkey: {}", e))?;
+    
     // If theme changed, emit event to frontend
-    if settings.theme != current_settings.theme {
-        tracing::info!("Theme changed from {:?} to {:?}", current_settings.theme, settings.theme);
+    if settings.theme != current.theme {
+        tracing::info!("Theme changed from {:?} to {:?}", current.theme, settings.theme);
         
         if let Err(e) = app.emit("theme-changed", &settings.theme) {
             tracing::warn!("Failed to emit theme-changed event: {}", e);
@@ -167,9 +1027,9 @@ async fn update_settings(
     }
     
     // If start_with_windows changed, update registry
-    if settings.start_with_windows != current_settings.start_with_windows {
+    if settings.start_with_windows != current.start_with_windows {
         tracing::info!("Auto-start changed from {} to {}", 
-            current_settings.start_with_windows, settings.start_with_windows);
+            current.start_with_windows, settings.start_with_windows);
         
         if settings.start_with_windows {
             autostart::enable_auto_start()
@@ -180,13 +1040,106 @@ async fn update_settings(
         }
     }
     
-    // Save settings to disk
-    settings.save().map_err(|e| e.to_string())?;
+    // If the window geometry or density changed, resize and tell the frontend
+    let appearance = settings.appearance();
+    if appearance != current.appearance() {
+        tracing::info!("Appearance changed to {:?}", appearance);
+        
+        if let Some(window) = app.get_webview_window("main") {
+            if let Err(e) = apply_window_appearance(&window, &appearance) {
+                tracing::warn!("Failed to resize main window: {}", e);
+            }
+        }
+        
+        if let Err(e) = app.emit("appearance-changed", &appearance) {
+            tracing::warn!("Failed to emit appearance-changed event: {}", e);
+        }
+    }
+    
+    // Show animation is a window attribute, so it applies immediately
+    if settings.disable_show_animation != current.disable_show_animation {
+        if let Some(window) = app.get_webview_window("main") {
+            window_show::set_show_animation(&window, !settings.disable_show_animation)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    
+    search_engine.set_strict_results(!settings.allows_unsigned_results());
+    search_engine.set_screen_reader_support(settings.screen_reader_support);
+    let (duplicate_window, confirmed_duplicate_window) = settings.duplicate_windows();
+    search_engine.set_duplicate_windows(duplicate_window, confirmed_duplicate_window);
+    
+    // Debug mode, the delete action, grouping, accent folding, stopwords, privacy mode, the result limit and provider options only affect result output, so they apply immediately
+    search_engine.set_debug_mode(settings.debug_mode).await;
+    search_engine.set_file_delete_enabled(settings.enable_file_delete_action).await;
+    search_engine.set_group_file_results(settings.group_file_results).await;
+    search_engine.set_stable_result_slots(settings.stable_result_slots);
+    search_engine.set_fold_diacritics(settings.fold_diacritics).await;
+    search_engine.set_stopwords(settings.ranking_stopwords.clone()).await;
+    search_engine.set_plain_text_markers(&settings.plain_text_markers).await;
+    search_engine.set_privacy_mode(settings.privacy_mode).await;
+    if let Some(history) = app.try_state::<Arc<CalculatorHistory>>() {
+        history.set_privacy_mode(settings.privacy_mode);
+    }
+    search_engine.analytics().set_enabled(settings.analytics_local);
+    search_engine.set_max_results(settings.max_results).await;
+    search_engine.set_launchers(settings.launchers());
+    search_engine.set_provider_settings(settings.resolved_provider_settings()).await;
+    app.state::<Arc<Mutex<MemoryWatchdog>>>()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .set_threshold_mb(settings.memory_trim_threshold_mb);
+    app.state::<Arc<AutoPaste>>().set_enabled(settings.clipboard_auto_paste);
+    app.state::<Arc<QuickMath>>().set_output(settings.quick_math_output);
+    app.state::<Arc<SoundPlayer>>().configure(settings.sound_feedback, settings.custom_sounds.clone());
+    app.state::<Arc<NotificationRouter>>().configure(settings.notification_routing.clone());
+    app.state::<ActiveWeatherPreferences>().set(settings.weather_preferences());
+    app.state::<ActiveMacros>().set(settings.macros.clone());
+    app.state::<ActiveExtensionWeights>().set(settings.extension_class_weights.clone());
+    app.state::<OpenInSourceBrowser>().set(settings.open_bookmarks_in_source_browser);
+    
+    // Folder scopes and the web search engine, so a profile switch changes where files are found
+    search::providers::apply_search_scopes(
+        settings,
+        &search_engine,
+        &app.state::<FolderScopes>(),
+        &app.state::<ActiveProfiles>(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    tray::refresh_menu(app);
     
-    tracing::info!("Settings updated successfully");
     Ok(())
 }
 
+/// Makes `name` the active profile, or none, and applies the result
+/// without a restart
+pub(crate) async fn activate_profile(app: &tauri::AppHandle, name: Option<&str>) -> Result<AppSettings, String> {
+    let stored = AppSettings::load().map_err(|e| e.to_string())?;
+    let current = stored.effective().map_err(|e| e.to_string())?;
+    let switched = stored.with_active_profile(name).map_err(|e| e.to_string())?;
+    
+    let saved = save_settings(app, switched).await?;
+    apply_settings(app, &current, &saved.effective().map_err(|e| e.to_string())?).await?;
+    // Results of other providers may depend on overridden settings too
+    app.state::<Arc<SearchEngine>>().invalidate_results().await;
+    
+    tracing::info!("Switched to profile {:?}", saved.active_profile);
+    if let Err(e) = app.emit("profile-changed", &saved.active_profile) {
+        tracing::warn!("Failed to emit profile-changed event: {}", e);
+    }
+    Ok(saved)
+}
+
+/// Tauri command switching to a settings profile, or back to the plain
+/// settings with `None`
+#[tauri::command]
+async fn switch_profile(app: tauri::AppHandle, name: Option<String>) -> Result<AppSettings, String> {
+    tracing::info!("Switch profile command received: {:?}", name);
+    
+    activate_profile(&app, name.as_deref()).await
+}
+
 /// Tauri command to check if auto-start is enabled
 #[tauri::command]
 fn is_auto_start_enabled() -> Result<bool, String> {
@@ -214,6 +1167,50 @@ fn disable_auto_start() -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Tauri command adding "Search in Better Finder" to Explorer's folder menus
+#[tauri::command]
+fn install_context_menu() -> Result<(), String> {
+    tracing::info!("Install context menu command received");
+    
+    uri_scheme::install_context_menu()
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command removing the Explorer context menu entries
+#[tauri::command]
+fn uninstall_context_menu() -> Result<(), String> {
+    tracing::info!("Uninstall context menu command received");
+    
+    uri_scheme::uninstall_context_menu()
+        .map_err(|e| e.to_string())
+}
+
+/// Opens what a `betterfinder://` link from another launch points at
+///
+/// Files and web pages open directly; everything else shows the launcher on
+/// the link's query so the user confirms it.
+fn handle_launch_link(app: &tauri::AppHandle, link: &str) {
+    match utils::deeplink::uri_to_pending_action(link) {
+        Ok(utils::deeplink::PendingAction::Execute(mut result)) => {
+            let Some(search_engine) = app.try_state::<Arc<SearchEngine>>() else {
+                tracing::warn!("Search engine not ready, ignoring link {}", link);
+                return;
+            };
+            let search_engine = Arc::clone(search_engine.inner());
+            search_engine.sign_result(&mut result);
+            tauri::async_runtime::spawn(async move {
+                search_engine.execute_result(result).await;
+            });
+        }
+        Ok(utils::deeplink::PendingAction::Show(context)) => {
+            if let Err(e) = app.emit(uri_scheme::OPEN_WITH_CONTEXT_EVENT, &context) {
+                tracing::warn!("Failed to emit open-with-context event: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!("Ignoring launch link: {}", e),
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize logging
@@ -223,8 +1220,41 @@ pub fn run() {
 
     tracing::info!("Starting Better Finder application");
 
-    // Load settings
-    let settings = match AppSettings::load() {
+    // Hand this launch over to an already running instance instead of starting a second one
+    let launch_message = single_instance::ActivationMessage::from_args(std::env::args());
+    let _instance_guard = match single_instance::acquire_or_forward(&launch_message) {
+        single_instance::InstanceRole::Primary(guard) => guard,
+        single_instance::InstanceRole::Secondary => {
+            tracing::info!("Activated the running instance, exiting");
+            return;
+        }
+    };
+
+    // Without the marker the last run was killed before it saved its state
+    let clean_shutdown = CleanShutdownMarker::load();
+    let last_run_clean = clean_shutdown.take_previous();
+    let shutdown_coordinator = Arc::new(ShutdownCoordinator::new(clean_shutdown));
+
+    // A link this launch was opened with either shows its query or opens its target once the engine is up
+    let launch_action = launch_message.link.as_deref().and_then(|link| {
+        utils::deeplink::uri_to_pending_action(link)
+            .map_err(|e| tracing::warn!("Ignoring launch link: {}", e))
+            .ok()
+    });
+    let (initial_query, launch_result) = match launch_action {
+        Some(utils::deeplink::PendingAction::Show(context)) => (Some(context.query), None),
+        Some(utils::deeplink::PendingAction::Execute(result)) => (None, Some(result)),
+        None => (launch_message.query.clone(), None),
+    };
+
+    // Keep betterfinder:// links pointing at this executable
+    #[cfg(target_os = "windows")]
+    if let Err(e) = uri_scheme::register_uri_scheme() {
+        tracing::warn!("Failed to register the betterfinder:// scheme: {}", e);
+    }
+
+    // Load settings, with the active profile applied
+    let settings = match AppSettings::load_effective() {
         Ok(s) => {
             tracing::info!("Settings loaded successfully");
             s
@@ -239,198 +1269,839 @@ pub fn run() {
         settings.hotkey, settings.theme, settings.max_results);
 
     let hotkey = settings.hotkey.clone();
+    let quick_math_hotkey = settings.quick_math_hotkey.clone();
+    let clipboard_history_size = settings.clipboard_history_size;
+    let provider_settings = settings.resolved_provider_settings();
+    let debug_mode = settings.debug_mode;
+    let max_results = settings.max_results;
+    let appearance = settings.appearance();
+    let enable_file_delete_action = settings.enable_file_delete_action;
+    let group_file_results = settings.group_file_results;
+    let stable_result_slots = settings.stable_result_slots;
+    let fold_diacritics = settings.fold_diacritics;
+    let ranking_stopwords = settings.ranking_stopwords.clone();
+    let plain_text_markers = settings.plain_text_markers.clone();
+    let privacy_mode = settings.privacy_mode;
+    let analytics_local = settings.analytics_local;
+    let web_search_engine = settings.web_search_engine.clone();
+    let launchers = settings.launchers();
+    let custom_bookmark_files = settings.custom_bookmark_files.clone();
+    let open_in_source_browser = OpenInSourceBrowser::default();
+    open_in_source_browser.set(settings.open_bookmarks_in_source_browser);
+    let folder_scopes = FolderScopes::default();
+    folder_scopes.set(&settings.excluded_paths, &settings.preferred_paths);
+    let extension_weights = ActiveExtensionWeights::default();
+    extension_weights.set(settings.extension_class_weights.clone());
+    let active_profiles = ActiveProfiles::default();
+    active_profiles.set(search::providers::ProfileList::from_settings(&settings));
+    let browser_history_enabled = settings.enabled_providers.browser_history;
+    let weather_enabled = settings.enabled_providers.weather;
+    let weather_preferences = ActiveWeatherPreferences::default();
+    weather_preferences.set(settings.weather_preferences());
+    let disable_show_animation = settings.disable_show_animation;
+    let allow_unsigned_results = settings.allows_unsigned_results();
+    let duplicate_windows = settings.duplicate_windows();
+    let screen_reader_support = settings.screen_reader_support;
+    let active_macros = ActiveMacros::default();
+    active_macros.set(settings.macros.clone());
+    let memory_watchdog = Arc::new(Mutex::new(MemoryWatchdog::new(settings.memory_trim_threshold_mb)));
+    let auto_paste = Arc::new(AutoPaste::new());
+    auto_paste.set_enabled(settings.clipboard_auto_paste);
+    let calculator_history = match CalculatorHistory::new() {
+        Ok(history) => Some(Arc::new(history)),
+        Err(e) => {
+            tracing::error!("Failed to initialize calculator history: {}", e);
+            None
+        }
+    };
+    let mut quick_math = QuickMath::new();
+    if let Some(history) = &calculator_history {
+        history.set_privacy_mode(settings.privacy_mode);
+        quick_math = quick_math.with_history(Arc::clone(history));
+    }
+    let quick_math = Arc::new(quick_math);
+    quick_math.set_output(settings.quick_math_output);
+    let drag_drop = Arc::new(DragDrop::new());
+    let provider_guard = Arc::new(ProviderGuard::load());
+    let sound_player = Arc::new(SoundPlayer::new());
+    sound_player.configure(settings.sound_feedback, settings.custom_sounds.clone());
+    let notification_router = Arc::new(NotificationRouter::new());
+    notification_router.configure(settings.notification_routing.clone());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .setup(move |app| {
+            // Managed first so notifications raised during setup are routed too
+            app.manage(Arc::clone(&notification_router));
+
             // Initialize global hotkey manager
             let hotkey_manager = GlobalHotkeyManager::new(app.handle().clone());
             
-            // Register the default hotkey
-            if let Err(e) = hotkey_manager.register_hotkey(&hotkey) {
-                tracing::error!("Failed to register global hotkey '{}': {}", hotkey, e);
-                // Continue running even if hotkey registration fails
-            } else {
-                tracing::info!("Global hotkey '{}' registered successfully", hotkey);
+            // Tell the user when a temporary hotkey stands in for theirs
+            let app_handle_for_hotkey = app.handle().clone();
+            hotkey_manager.set_fallback_listener(move |fallback| {
+                if let Err(e) = app_handle_for_hotkey.emit("hotkey-fallback-active", fallback) {
+                    tracing::warn!("Failed to emit hotkey-fallback-active event: {}", e);
+                }
+                utils::notify_warning(
+                    &app_handle_for_hotkey,
+                    NotificationCategory::General,
+                    format!("Hotkey {} is in use", fallback.preferred),
+                    Some(format!(
+                        "Another app is using {}. Press {} to open the launcher for now, or pick another hotkey in Settings.",
+                        fallback.preferred, fallback.active
+                    )),
+                );
+            });
+            
+            // Register the configured hotkey, or a free fallback if it is taken
+            match hotkey_manager.register_with_fallback(&hotkey) {
+                Ok(active) => tracing::info!("Global hotkey '{}' registered successfully", active),
+                Err(e) => {
+                    tracing::error!("Failed to register global hotkey '{}': {}", hotkey, e);
+                    // Continue running even if hotkey registration fails
+                }
+            }
+            if let Some(shortcut) = &quick_math_hotkey {
+                if let Err(e) = hotkey_manager.set_quick_math_hotkey(Some(shortcut)) {
+                    tracing::error!("Failed to register quick math hotkey '{}': {}", shortcut, e);
+                }
             }
 
             // Store the hotkey manager in app state for later access
-            app.manage(Arc::new(hotkey_manager));
+            let hotkey_manager = Arc::new(hotkey_manager);
+            app.manage(Arc::clone(&hotkey_manager));
+
+            // Keep the launch query for the frontend and listen for later launches
+            app.manage(single_instance::PendingQuery::new(initial_query));
+            let app_handle_for_activation = app.handle().clone();
+            single_instance::start_activation_listener(move |message| {
+                tracing::info!("Activated by another launch (query: {:?})", message.query);
+                // Links and the Explorer verb open their target or the launcher on their query
+                if let Some(link) = &message.link {
+                    handle_launch_link(&app_handle_for_activation, link);
+                    return;
+                }
+                if let Err(e) = app_handle_for_activation.emit(single_instance::ACTIVATION_EVENT, &message) {
+                    tracing::warn!("Failed to emit activation event: {}", e);
+                }
+            });
+
+            // Size the main window from settings instead of the static config
+            if let Some(window) = app.get_webview_window("main") {
+                if let Err(e) = apply_window_appearance(&window, &appearance) {
+                    tracing::warn!("Failed to apply window appearance: {}", e);
+                }
+                if let Err(e) = window_show::set_show_animation(&window, !disable_show_animation) {
+                    tracing::warn!("Failed to configure the show animation: {}", e);
+                }
+
+                // Keep the window's proportions when it is dragged to a monitor with another DPI
+                let scaled_window = window.clone();
+                window.on_window_event(move |event| {
+                    if let tauri::WindowEvent::ScaleFactorChanged { scale_factor, .. } = event {
+                        tracing::info!("Main window scale factor changed to {}", scale_factor);
+                        if let Err(e) = resize_window(&scaled_window, &load_appearance(), *scale_factor) {
+                            tracing::warn!("Failed to resize main window for new scale factor: {}", e);
+                        }
+                    }
+                });
+            }
+
+            // Follow Windows dark mode and accent color changes made while running
+            let app_handle_for_theme = app.handle().clone();
+            utils::theme::watch_system_appearance(move |change| match change {
+                utils::theme::AppearanceChange::Theme(theme) => {
+                    // Only matters when the app follows the system theme
+                    let follows_system = AppSettings::load_effective()
+                        .map(|settings| settings.theme == settings::Theme::System)
+                        .unwrap_or(true);
+                    if follows_system {
+                        tracing::info!("System theme changed to {:?}", theme);
+                        if let Err(e) = app_handle_for_theme.emit("theme-changed", settings::Theme::System) {
+                            tracing::warn!("Failed to emit theme-changed event: {}", e);
+                        }
+                    }
+                }
+                utils::theme::AppearanceChange::Accent(accent) => {
+                    tracing::info!("Accent color changed to {:?}", accent);
+                    if let Err(e) = app_handle_for_theme.emit("accent-changed", &accent) {
+                        tracing::warn!("Failed to emit accent-changed event: {}", e);
+                    }
+                }
+            });
 
             // Initialize search engine
             let search_engine = Arc::new(SearchEngine::new());
+            let result_feedback = Arc::new(ResultFeedback::load());
+            search_engine.set_result_feedback(Arc::clone(&result_feedback));
+            let search_analytics = Arc::new(SearchAnalytics::load());
+            search_analytics.set_enabled(analytics_local);
+            search_engine.set_search_analytics(search_analytics);
+            search_engine.set_strict_results(!allow_unsigned_results);
+            search_engine.set_duplicate_windows(duplicate_windows.0, duplicate_windows.1);
+            search_engine.set_screen_reader_support(screen_reader_support);
             tracing::info!("Search engine initialized");
             
+            // Initialize the favorites store shared by the pin commands and FavoritesProvider
+            let favorites_store = match FavoritesStore::new() {
+                Ok(store) => Some(Arc::new(store)),
+                Err(e) => {
+                    tracing::error!("Failed to initialize favorites store: {}", e);
+                    None
+                }
+            };
+            if let Some(ref store) = favorites_store {
+                app.manage(Arc::clone(store));
+                search_engine.set_favorites_store(Arc::clone(store));
+            }
+            
+            // Initialize the saved search store shared by its commands and SavedSearchProvider
+            let saved_search_store = match SavedSearchStore::new() {
+                Ok(store) => Some(Arc::new(store)),
+                Err(e) => {
+                    tracing::error!("Failed to initialize saved search store: {}", e);
+                    None
+                }
+            };
+            if let Some(ref store) = saved_search_store {
+                app.manage(Arc::clone(store));
+            }
+
+            // External plugins are loaded once the built-in providers are registered
+            let plugin_host = Arc::new(PluginHost::new(search::providers::plugin::plugins_dir()));
+            app.manage(Arc::clone(&plugin_host));
+            
+            app.manage(Arc::clone(&memory_watchdog));
+
+            // Auto-paste hides the launcher itself and explains when it only copied
+            let app_handle_for_paste = app.handle().clone();
+            auto_paste.set_hide_launcher(move || {
+                if let Some(window) = app_handle_for_paste.get_webview_window("main") {
+                    if let Err(e) = window.hide() {
+                        tracing::warn!("Failed to hide window before pasting: {}", e);
+                    }
+                }
+                window_show::end_search_session(&app_handle_for_paste);
+            });
+            let app_handle_for_paste = app.handle().clone();
+            auto_paste.set_fallback_listener(move |reason| {
+                utils::notify_warning(
+                    &app_handle_for_paste,
+                    NotificationCategory::Execution,
+                    "Copied to clipboard",
+                    Some(format!("Couldn't paste into the previous window: {}", reason)),
+                );
+            });
+            app.manage(Arc::clone(&auto_paste));
+            app.manage(Arc::clone(&quick_math));
+            if let Some(history) = &calculator_history {
+                app.manage(Arc::clone(history));
+            }
+            app.manage(Arc::clone(&sound_player));
+            app.manage(Arc::clone(&drag_drop));
+            app.manage(Arc::clone(&provider_guard));
+            app.manage(weather_preferences.clone());
+            app.manage(active_macros.clone());
+            app.manage(folder_scopes.clone());
+            app.manage(extension_weights.clone());
+            app.manage(open_in_source_browser.clone());
+            app.manage(active_profiles.clone());
+            app.manage(PreparedPlacement::default());
+
+            // Save what a kill at session end would lose, most important first. Icon
+            // and favicon caches are rebuilt on demand, so they aren't flushed
+            let app_handle_for_flush = app.handle().clone();
+            shutdown_coordinator.add_flusher("clipboard_history", std::time::Duration::from_millis(800), move || {
+                let clipboard = app_handle_for_flush
+                    .try_state::<Arc<tokio::sync::RwLock<search::providers::ClipboardHistoryProvider>>>()
+                    .map(|state| Arc::clone(&state));
+                async move {
+                    match clipboard {
+                        Some(clipboard) => clipboard.read().await.flush().await,
+                        None => Ok(()),
+                    }
+                }
+            });
+            let app_handle_for_flush = app.handle().clone();
+            shutdown_coordinator.add_flusher("recent_files", std::time::Duration::from_millis(600), move || {
+                let recent_files = app_handle_for_flush
+                    .try_state::<Arc<tokio::sync::RwLock<search::providers::RecentFilesProvider>>>()
+                    .map(|state| Arc::clone(&state));
+                async move {
+                    match recent_files {
+                        Some(recent_files) => recent_files.read().await.flush().await,
+                        None => Ok(()),
+                    }
+                }
+            });
+            shutdown_coordinator.add_flusher("settings", std::time::Duration::from_millis(400), || async {
+                AppSettings::finish_pending_save().await;
+                Ok(())
+            });
+            app.manage(Arc::clone(&shutdown_coordinator));
+            
             // Register providers in background for fast startup
             let search_engine_clone = Arc::clone(&search_engine);
             let app_handle_clone = app.handle().clone();
+            let memory_watchdog = Arc::clone(&memory_watchdog);
+            let auto_paste = Arc::clone(&auto_paste);
+            let sound_player = Arc::clone(&sound_player);
+            let weather_preferences = weather_preferences.clone();
+            let active_macros = active_macros.clone();
+            let provider_guard = Arc::clone(&provider_guard);
+            let result_feedback = Arc::clone(&result_feedback);
             tauri::async_runtime::spawn(async move {
                 let start_time = std::time::Instant::now();
+                search_engine_clone.set_auto_paste(auto_paste).await;
+                search_engine_clone.set_debug_mode(debug_mode).await;
+                search_engine_clone.set_file_delete_enabled(enable_file_delete_action).await;
+                search_engine_clone.set_group_file_results(group_file_results).await;
+                search_engine_clone.set_stable_result_slots(stable_result_slots);
+                search_engine_clone.set_fold_diacritics(fold_diacritics).await;
+                search_engine_clone.set_stopwords(ranking_stopwords).await;
+                search_engine_clone.set_plain_text_markers(&plain_text_markers).await;
+                search_engine_clone.set_privacy_mode(privacy_mode).await;
+                if let Err(e) = search_engine_clone.set_web_search_engine(&web_search_engine).await {
+                    tracing::warn!("{}, using the default web search engine", e);
+                }
+                search_engine_clone.set_launchers(launchers);
+                search_engine_clone.set_max_results(max_results).await;
+                // Before any provider registers, so each starts with its options
+                search_engine_clone.set_provider_settings(provider_settings).await;
+                
+                // Forward execution progress to the UI; failures also get a toast
+                let app_handle_for_execution = app_handle_clone.clone();
+                search_engine_clone.set_execution_listener(move |status| {
+                    if let Err(e) = app_handle_for_execution.emit("execution-status", status) {
+                        tracing::warn!("Failed to emit execution-status event: {}", e);
+                    }
+                    if let ExecutionState::Failed { error } = &status.state {
+                        utils::notify_error(&app_handle_for_execution, NotificationCategory::Execution, "Failed to open result", Some(error.clone()));
+                        sound_player.play(SoundEvent::ExecutionFailed);
+                    }
+                }).await;
+                
+                // Results whose icons and other details were filled in after the search
+                let app_handle_for_enrichment = app_handle_clone.clone();
+                search_engine_clone.set_enrichment_listener(move |batch| {
+                    if let Err(e) = app_handle_for_enrichment.emit("results-enriched", batch) {
+                        tracing::warn!("Failed to emit results-enriched event: {}", e);
+                    }
+                });
                 tracing::info!("Starting provider registration...");
                 
-                // Phase 1: Register critical providers immediately (Calculator, QuickAction, WebSearch)
+                // Each provider starts through the guard: one that fails or panics is
+                // logged and counted, and the others are still tried.
+                // Providers that failed on the last few launches are skipped.
+                let app_handle_for_skips = app_handle_clone.clone();
+                provider_guard.set_skip_listener(move |provider| {
+                    utils::notify_warning(
+                        &app_handle_for_skips,
+                        NotificationCategory::ProviderHealth,
+                        format!("{} disabled after repeated errors", provider),
+                        Some("It failed to start on the last few launches. Re-enable it in Settings."),
+                    );
+                });
+                
+                // Phase 1: Register critical providers immediately (Calculator, DateTime, QuickAction, WebSearch)
                 // These are lightweight and don't require initialization
                 
                 // Register CalculatorProvider (instant, no initialization needed)
-                if let Ok(calculator_provider) = search::providers::CalculatorProvider::new() {
-                    search_engine_clone.register_provider(Box::new(calculator_provider)).await;
+                provider_guard.run("Calculator", async {
+                    let mut calculator_provider = search::providers::CalculatorProvider::new()?;
+                    if let Some(history) = calculator_history {
+                        calculator_provider = calculator_provider.with_history(history);
+                    }
+                    let app_handle_for_calculator = app_handle_clone.clone();
+                    calculator_provider.set_insert_handler(move |expression| {
+                        if let Err(e) = app_handle_for_calculator.emit("set-query", expression) {
+                            tracing::warn!("Failed to emit set-query event: {}", e);
+                        }
+                    });
+                    search_engine_clone.register_provider(Arc::new(calculator_provider)).await;
                     tracing::info!("CalculatorProvider registered");
-                } else {
-                    tracing::error!("Failed to initialize CalculatorProvider");
+                    Ok(())
+                }).await;
+                
+                // Register FavoritesProvider (instant, pins are loaded with the store)
+                if let Some(store) = favorites_store {
+                    provider_guard.run("Favorites", async {
+                        let mut favorites_provider = search::providers::FavoritesProvider::new(store)?;
+                        let app_handle_for_pins = app_handle_clone.clone();
+                        favorites_provider.set_stale_pin_handler(move |pin: &Pin| {
+                            utils::notify_warning(
+                                &app_handle_for_pins,
+                                NotificationCategory::General,
+                                "Pinned item not found",
+                                Some(format!("'{}' no longer exists. Unpin it to remove it from results.", pin.alias))
+                            );
+                            if let Err(e) = app_handle_for_pins.emit("stale-pin", pin) {
+                                tracing::warn!("Failed to emit stale-pin event: {}", e);
+                            }
+                        });
+                        search_engine_clone.register_provider(Arc::new(favorites_provider)).await;
+                        tracing::info!("FavoritesProvider registered");
+                        Ok(())
+                    }).await;
                 }
                 
+                // Register SavedSearchProvider (instant, searches are loaded with the store)
+                if let Some(store) = saved_search_store {
+                    provider_guard.run("Saved Searches", async {
+                        let mut saved_search_provider = search::providers::SavedSearchProvider::new(store)?;
+                        saved_search_provider.set_engine(&search_engine_clone);
+                        let app_handle_for_saved = app_handle_clone.clone();
+                        saved_search_provider.set_run_handler(move |search: &SavedSearch| {
+                            if let Err(e) = app_handle_for_saved.emit("run-saved-search", search) {
+                                tracing::warn!("Failed to emit run-saved-search event: {}", e);
+                            }
+                        });
+                        search_engine_clone.register_provider(Arc::new(saved_search_provider)).await;
+                        tracing::info!("SavedSearchProvider registered");
+                        Ok(())
+                    }).await;
+                }
+                
+                // Register DateTimeProvider (instant, no initialization needed)
+                provider_guard.run("Date & Time", async {
+                    let datetime_provider = search::providers::DateTimeProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(datetime_provider)).await;
+                    tracing::info!("DateTimeProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register GeneratorProvider (instant, no initialization needed)
+                provider_guard.run("Generator", async {
+                    let generator_provider = search::providers::GeneratorProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(generator_provider)).await;
+                    tracing::info!("GeneratorProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register TextToolsProvider (instant, no initialization needed)
+                provider_guard.run("Text Tools", async {
+                    let text_tools_provider = search::providers::TextToolsProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(text_tools_provider)).await;
+                    tracing::info!("TextToolsProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register TextTransformProvider (instant, computed from the query)
+                provider_guard.run("Text Transform", async {
+                    let text_transform_provider = search::providers::TextTransformProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(text_transform_provider)).await;
+                    tracing::info!("TextTransformProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register HashProvider (instant, files are hashed on demand)
+                provider_guard.run("Hash", async {
+                    let hash_provider = search::providers::HashProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(hash_provider)).await;
+                    tracing::info!("HashProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register MacroProvider (instant, macros come from settings)
+                provider_guard.run("Macros", async {
+                    let macro_provider = search::providers::MacroProvider::new(active_macros)?;
+                    search_engine_clone.register_provider(Arc::new(macro_provider)).await;
+                    tracing::info!("MacroProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register DirectUrlProvider (instant, no initialization needed)
+                provider_guard.run("Direct URL", async {
+                    let direct_url_provider = search::providers::DirectUrlProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(direct_url_provider)).await;
+                    tracing::info!("DirectUrlProvider registered");
+                    Ok(())
+                }).await;
+                
                 // Register QuickActionProvider (instant, no initialization needed)
-                if let Ok(quick_action_provider) = search::providers::QuickActionProvider::new() {
-                    search_engine_clone.register_provider(Box::new(quick_action_provider)).await;
+                provider_guard.run("Quick Actions", async {
+                    let quick_action_provider = search::providers::QuickActionProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(quick_action_provider)).await;
                     tracing::info!("QuickActionProvider registered");
-                } else {
-                    tracing::error!("Failed to initialize QuickActionProvider");
-                }
+                    Ok(())
+                }).await;
+                
+                // Register ConnectivityProvider (radio state is read on first match)
+                provider_guard.run("Connectivity", async {
+                    let connectivity_provider = search::providers::ConnectivityProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(connectivity_provider)).await;
+                    tracing::info!("ConnectivityProvider registered");
+                    Ok(())
+                }).await;
+                
+                // Register AdminToolsProvider (static table, no initialization needed)
+                provider_guard.run("Admin Tools", async {
+                    let admin_tools_provider = search::providers::AdminToolsProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(admin_tools_provider)).await;
+                    tracing::info!("AdminToolsProvider registered");
+                    Ok(())
+                }).await;
+                
+                // Register HelpProvider (lists the syntax other providers register)
+                provider_guard.run("Help", async {
+                    let help_provider = search::providers::HelpProvider::new(search_engine_clone.syntax_registry())?;
+                    search_engine_clone.register_provider(Arc::new(help_provider)).await;
+                    tracing::info!("HelpProvider registered");
+                    Ok(())
+                }).await;
+                
+                // Register SettingsSearchProvider (static table, filtered by provider health)
+                provider_guard.run("Settings Search", async {
+                    let mut settings_search_provider = search::providers::SettingsSearchProvider::new()?;
+                    settings_search_provider.set_engine(&search_engine_clone);
+                    let app_handle_for_settings = app_handle_clone.clone();
+                    settings_search_provider.set_open_handler(move |location| {
+                        if let Err(e) = app_handle_for_settings.emit("open-settings", location) {
+                            tracing::warn!("Failed to emit open-settings event: {}", e);
+                        }
+                    });
+                    search_engine_clone.register_provider(Arc::new(settings_search_provider)).await;
+                    tracing::info!("SettingsSearchProvider registered");
+                    Ok(())
+                }).await;
+                
+                // Register ProfileProvider (switches profiles from the "profile" keyword)
+                provider_guard.run("Profiles", async {
+                    let mut profile_provider = search::providers::ProfileProvider::new(active_profiles.clone())?;
+                    let app_handle_for_profiles = app_handle_clone.clone();
+                    profile_provider.set_switch_handler(move |profile| {
+                        let app = app_handle_for_profiles.clone();
+                        tauri::async_runtime::spawn(async move {
+                            if let Err(e) = activate_profile(&app, profile.as_deref()).await {
+                                tracing::error!("Failed to switch profile: {}", e);
+                                utils::notify_warning(&app, NotificationCategory::General, "Profile Not Switched", Some(e));
+                            }
+                        });
+                    });
+                    search_engine_clone.register_provider(Arc::new(profile_provider)).await;
+                    tracing::info!("ProfileProvider registered");
+                    Ok(())
+                }).await;
+
+                // Register DoctorProvider (runs the self-test from "/doctor")
+                provider_guard.run("Doctor", async {
+                    let mut doctor_provider = search::providers::DoctorProvider::new()?;
+                    let app_handle_for_doctor = app_handle_clone.clone();
+                    doctor_provider.set_run_handler(move || {
+                        let app = app_handle_for_doctor.clone();
+                        tauri::async_runtime::spawn(async move {
+                            match copy_self_test_checks(&app).await {
+                                Ok(report) => {
+                                    let summary = format!(
+                                        "{} passed, {} warnings, {} failures. The report is on the clipboard.",
+                                        report.passed, report.warnings, report.failures
+                                    );
+                                    if report.status == self_test::CheckStatus::Pass {
+                                        utils::notify_success(&app, NotificationCategory::General, "Self-Test Passed", Some(summary));
+                                    } else {
+                                        utils::notify_warning(&app, NotificationCategory::General, "Self-Test Found Problems", Some(summary));
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::error!("Failed to copy the self-test report: {}", e);
+                                    utils::notify_warning(&app, NotificationCategory::General, "Self-Test Report Not Copied", Some(e));
+                                }
+                            }
+                        });
+                    });
+                    search_engine_clone.register_provider(Arc::new(doctor_provider)).await;
+                    tracing::info!("DoctorProvider registered");
+                    Ok(())
+                }).await;
+                
+                // Register WindowsSettingsProvider (static table, no initialization needed)
+                provider_guard.run("Windows Settings", async {
+                    let windows_settings_provider = search::providers::WindowsSettingsProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(windows_settings_provider)).await;
+                    tracing::info!("WindowsSettingsProvider registered");
+                    Ok(())
+                }).await;
+                
+                // Register NetworkBrowseProvider (shares are enumerated on demand)
+                provider_guard.run("Network Browse", async {
+                    let network_browse_provider = search::providers::NetworkBrowseProvider::new()?;
+                    search_engine_clone.register_provider(Arc::new(network_browse_provider)).await;
+                    tracing::info!("NetworkBrowseProvider registered");
+                    Ok(())
+                }).await;
                 
                 // Register WebSearchProvider (instant, no initialization needed)
-                if let Ok(web_search_provider) = search::providers::WebSearchProvider::new() {
-                    search_engine_clone.register_provider(Box::new(web_search_provider)).await;
+                provider_guard.run("Web Search", async {
+                    let web_search_provider = search::providers::WebSearchProvider::new()?
+                        .with_active_engine(search_engine_clone.web_search_engine());
+                    search_engine_clone.register_provider(Arc::new(web_search_provider)).await;
                     tracing::info!("WebSearchProvider registered");
-                } else {
-                    tracing::error!("Failed to initialize WebSearchProvider");
-                }
+                    Ok(())
+                }).await;
                 
                 tracing::info!("Phase 1 complete: Critical providers registered in {:.2}ms", start_time.elapsed().as_millis());
                 
                 // Phase 2: Register providers that require initialization
+                // Each starts as its own task, a few at a time, and registers as soon as
+                // it's ready. Recent files, file search and clipboard stay eager: file
+                // access tracking, the Everything/Windows Search fallback choice and
+                // clipboard monitoring all have to be in place from startup. The rest
+                // initialize on first use.
+                let mut startups = ProviderStartups::new(MAX_CONCURRENT_STARTS);
+
                 // Register RecentFilesProvider (high priority)
-                let recent_files_provider = match search::providers::RecentFilesProvider::new() {
-                    Ok(mut provider) => {
-                        // Initialize the provider
+                let (guard, engine, app_handle) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone), app_handle_clone.clone());
+                startups.add("Recent Files", async move {
+                    guard.run("Recent Files", async {
+                        let mut provider = search::providers::RecentFilesProvider::new()?;
                         if let Err(e) = provider.initialize().await {
                             tracing::error!("Failed to initialize RecentFilesProvider: {}", e);
                         }
-                        Some(Arc::new(tokio::sync::RwLock::new(provider)))
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create RecentFilesProvider: {}", e);
-                        None
-                    }
-                };
-
-                // Set up file access tracker if RecentFilesProvider was created
-                if let Some(ref recent_provider) = recent_files_provider {
-                    let provider_clone = Arc::clone(recent_provider);
-                    search_engine_clone.set_file_access_tracker(move |path: &str| {
-                        let provider = Arc::clone(&provider_clone);
-                        let path_owned = path.to_string();
-                        tokio::spawn(async move {
-                            let provider_lock = provider.read().await;
-                            if let Err(e) = provider_lock.track_file_access(std::path::Path::new(&path_owned)).await {
-                                tracing::error!("Failed to track file access: {}", e);
-                            }
-                        });
-                    }).await;
-                    tracing::info!("File access tracker registered");
-                }
+                        let recent_provider = Arc::new(tokio::sync::RwLock::new(provider));
+
+                        // Set up the file access tracker before the provider can be found
+                        let provider_clone = Arc::clone(&recent_provider);
+                        engine.set_file_access_tracker(move |access: &types::FileAccess| {
+                            let provider = Arc::clone(&provider_clone);
+                            let access = access.clone();
+                            tokio::spawn(async move {
+                                let provider_lock = provider.read().await;
+                                let path = std::path::Path::new(&access.path);
+                                if let Err(e) = provider_lock.track_file_access(path, access.kind, access.result_type).await {
+                                    tracing::error!("Failed to track file access: {}", e);
+                                }
+                            });
+                        }).await;
+                        tracing::info!("File access tracker registered");
+
+                        // Recycled files must not linger in the recent files list
+                        let provider_clone = Arc::clone(&recent_provider);
+                        engine.set_file_removal_tracker(move |path: &str| {
+                            let provider = Arc::clone(&provider_clone);
+                            let path_owned = path.to_string();
+                            tokio::spawn(async move {
+                                let provider_lock = provider.read().await;
+                                if let Err(e) = provider_lock.forget_file(std::path::Path::new(&path_owned)).await {
+                                    tracing::error!("Failed to remove recycled file from recent files: {}", e);
+                                }
+                            });
+                        }).await;
 
-                // Register the RecentFilesProvider
-                if let Some(_recent_provider) = recent_files_provider {
-                    // We need to create a new instance to register
-                    // The original is kept for file access tracking
-                    if let Ok(provider_instance) = search::providers::RecentFilesProvider::new() {
-                        search_engine_clone.register_provider(Box::new(provider_instance)).await;
+                        // The engine searches the same instance the trackers write to
+                        engine
+                            .register_provider(Arc::new(SharedProvider::new(Arc::clone(&recent_provider)).await))
+                            .await;
+                        app_handle.manage(recent_provider);
                         tracing::info!("RecentFilesProvider registered");
-                    }
-                }
-                
-                // Register FileSearchProvider (Everything SDK) with fallback to Windows Search
-                match search::providers::FileSearchProvider::new() {
-                    Ok(file_provider) => {
-                        if file_provider.is_enabled() {
-                            search_engine_clone.register_provider(Box::new(file_provider)).await;
-                            tracing::info!("FileSearchProvider (Everything SDK) registered");
-                        } else {
-                            tracing::warn!("Everything SDK not available, registering Windows Search fallback");
-                            utils::notify_warning(
-                                &app_handle_clone,
-                                "File Search Limited",
-                                Some("Everything SDK not found. Using Windows Search as fallback. Install Everything for faster file search.")
-                            );
-                            
-                            // Register Windows Search as fallback
-                            if let Ok(windows_search_provider) = search::providers::WindowsSearchProvider::new() {
-                                search_engine_clone.register_provider(Box::new(windows_search_provider)).await;
-                                tracing::info!("WindowsSearchProvider registered as fallback");
-                            } else {
-                                tracing::error!("Failed to initialize WindowsSearchProvider fallback");
-                            }
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Register FileSearchProvider (Everything SDK) with fallback to Windows Search,
+                // decided in the same start once Everything was tried
+                let (guard, engine, app_handle) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone), app_handle_clone.clone());
+                startups.add("File Search", async move {
+                    let everything_available = guard.run("File Search", async {
+                        let file_provider = search::providers::FileSearchProvider::new()?;
+                        if !file_provider.is_enabled() {
+                            return Ok(false);
                         }
+                        let file_provider = file_provider
+                            .with_folder_scopes(folder_scopes.clone())
+                            .with_extension_weights(extension_weights.clone());
+                        engine.register_provider(Arc::new(file_provider)).await;
+                        tracing::info!("FileSearchProvider (Everything SDK) registered");
+                        Ok(true)
+                    }).await;
+                    if everything_available == Some(true) {
+                        return true;
                     }
-                    Err(e) => {
-                        tracing::error!("Failed to create FileSearchProvider: {}", e);
+
+                    let message = if everything_available == Some(false) {
+                        tracing::warn!("Everything SDK not available, registering Windows Search fallback");
+                        "Everything SDK not found. Using Windows Search as fallback. Install Everything for faster file search."
+                    } else {
                         tracing::warn!("Registering Windows Search fallback");
-                        utils::notify_warning(
-                            &app_handle_clone,
-                            "File Search Limited",
-                            Some("File search provider initialization failed. Using Windows Search as fallback.")
-                        );
-                        
-                        // Register Windows Search as fallback
-                        if let Ok(windows_search_provider) = search::providers::WindowsSearchProvider::new() {
-                            search_engine_clone.register_provider(Box::new(windows_search_provider)).await;
-                            tracing::info!("WindowsSearchProvider registered as fallback");
-                        } else {
-                            tracing::error!("Failed to initialize WindowsSearchProvider fallback");
-                        }
-                    }
-                }
-                
-                // Register AppSearchProvider
-                match search::providers::AppSearchProvider::new() {
-                    Ok(mut app_provider) => {
-                        // Initialize the provider (scans for applications)
-                        if let Err(e) = app_provider.initialize().await {
-                            tracing::error!("Failed to initialize AppSearchProvider: {}", e);
-                        } else {
-                            search_engine_clone.register_provider(Box::new(app_provider)).await;
-                            tracing::info!("AppSearchProvider registered and initialized");
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create AppSearchProvider: {}", e);
-                    }
+                        "File search provider initialization failed. Using Windows Search as fallback."
+                    };
+                    utils::notify_warning(&app_handle, NotificationCategory::ProviderHealth, "File Search Limited", Some(message));
+
+                    guard.run("Windows Search", async {
+                        register_windows_search_fallback(&engine, &app_handle, &folder_scopes, &extension_weights).await;
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Shared with the recent projects provider, which opens projects in the scanned IDEs
+                let app_cache: search::providers::app_search::AppCache = Default::default();
+
+                // Register AppSearchProvider (starts the application scan on first use)
+                let (guard, engine, app_handle, app_cache_for_apps) = (
+                    Arc::clone(&provider_guard),
+                    Arc::clone(&search_engine_clone),
+                    app_handle_clone.clone(),
+                    Arc::clone(&app_cache),
+                );
+                startups.add("App Search", async move {
+                    guard.run("App Search", async {
+                        let app_provider = LazyProvider::new("AppSearch", 85, Some(&[types::ResultType::Application]), move || {
+                            let mut app_provider = search::providers::AppSearchProvider::new()?.with_app_cache(app_cache_for_apps);
+                            app_provider.set_progress_handler(move |progress| {
+                                if let Err(e) = app_handle.emit("provider-progress", &progress) {
+                                    tracing::warn!("Failed to emit provider-progress event: {}", e);
+                                }
+                            });
+                            Ok(app_provider)
+                        })
+                        .with_ready_handler(invalidate_cache_on_ready(&engine));
+                        engine.register_provider(Arc::new(app_provider)).await;
+                        tracing::info!("AppSearchProvider registered (lazy)");
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Register BookmarkProvider (loads bookmarks from browsers on first use)
+                let (guard, engine, open_in_source_browser, app_cache_for_bookmarks) = (
+                    Arc::clone(&provider_guard),
+                    Arc::clone(&search_engine_clone),
+                    open_in_source_browser.clone(),
+                    Arc::clone(&app_cache),
+                );
+                startups.add("Bookmarks", async move {
+                    guard.run("Bookmarks", async {
+                        let bookmark_provider = LazyProvider::new("Bookmarks", 50, Some(&[types::ResultType::Bookmark]), move || {
+                            Ok(search::providers::BookmarkProvider::new()?
+                                .with_custom_files(&custom_bookmark_files)
+                                .with_source_browser(open_in_source_browser, app_cache_for_bookmarks))
+                        })
+                        .with_min_query_len(2)
+                        .with_ready_handler(invalidate_cache_on_ready(&engine));
+                        engine.register_provider(Arc::new(bookmark_provider)).await;
+                        tracing::info!("BookmarkProvider registered (lazy)");
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Register SshProvider (reads ssh config and PuTTY sessions on first use)
+                let (guard, engine) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone));
+                startups.add("SSH", async move {
+                    guard.run("SSH", async {
+                        let ssh_provider = LazyProvider::new("SSH", 55, Some(&[types::ResultType::Ssh]), search::providers::SshProvider::new)
+                            .with_min_query_len(2)
+                            .with_syntax(search::providers::ssh::SSH_SYNTAX)
+                            .with_ready_handler(invalidate_cache_on_ready(&engine));
+                        engine.register_provider(Arc::new(ssh_provider)).await;
+                        tracing::info!("SshProvider registered (lazy)");
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Register RecentProjectsProvider (reads the IDE histories on first use)
+                let (guard, engine) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone));
+                startups.add("Recent Projects", async move {
+                    guard.run("Recent Projects", async {
+                        let projects_provider = LazyProvider::new("RecentProjects", 60, Some(&[types::ResultType::Project]), move || {
+                            search::providers::RecentProjectsProvider::new(app_cache)
+                        })
+                        .with_min_query_len(2)
+                        .with_ready_handler(invalidate_cache_on_ready(&engine));
+                        engine.register_provider(Arc::new(projects_provider)).await;
+                        tracing::info!("RecentProjectsProvider registered (lazy)");
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Register BrowserHistoryProvider (opt-in; reads history from browsers on first use)
+                if browser_history_enabled {
+                    let (guard, engine) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone));
+                    startups.add("Browser History", async move {
+                        guard.run("Browser History", async {
+                            let history_provider = LazyProvider::new(
+                                "BrowserHistory",
+                                40,
+                                Some(&[types::ResultType::History]),
+                                search::providers::BrowserHistoryProvider::new,
+                            )
+                            .with_min_query_len(2)
+                            .with_ready_handler(invalidate_cache_on_ready(&engine));
+                            engine.register_provider(Arc::new(history_provider)).await;
+                            tracing::info!("BrowserHistoryProvider registered (lazy)");
+                            Ok(())
+                        }).await.is_some()
+                    });
                 }
-                
-                // Register BookmarkProvider
-                match search::providers::BookmarkProvider::new() {
-                    Ok(mut bookmark_provider) => {
-                        // Initialize the provider (loads bookmarks from browsers)
-                        if let Err(e) = bookmark_provider.initialize().await {
-                            tracing::error!("Failed to initialize BookmarkProvider: {}", e);
-                        } else {
-                            search_engine_clone.register_provider(Box::new(bookmark_provider)).await;
-                            tracing::info!("BookmarkProvider registered and initialized");
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create BookmarkProvider: {}", e);
-                    }
+
+                // Register WeatherProvider (opt-in; only goes online for "weather" queries)
+                if weather_enabled {
+                    let (guard, engine) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone));
+                    startups.add("Weather", async move {
+                        guard.run("Weather", async {
+                            let weather_provider = search::providers::WeatherProvider::new(weather_preferences)?;
+                            engine.register_provider(Arc::new(weather_provider)).await;
+                            tracing::info!("WeatherProvider registered");
+                            Ok(())
+                        }).await.is_some()
+                    });
                 }
-                
+
+                // Register DevDocsProvider (only initialized by queries such as "rust Vec")
+                let (guard, engine) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone));
+                startups.add("Dev Docs", async move {
+                    guard.run("Dev Docs", async {
+                        let dev_docs_provider = LazyProvider::new(
+                            "DevDocs",
+                            75,
+                            Some(&[types::ResultType::Url]),
+                            search::providers::DevDocsProvider::new,
+                        )
+                        .with_keywords(search::providers::dev_docs::DEV_DOCS_KEYWORDS)
+                        .with_syntax(search::providers::dev_docs::DEV_DOCS_SYNTAX)
+                        .with_ready_handler(invalidate_cache_on_ready(&engine));
+                        engine.register_provider(Arc::new(dev_docs_provider)).await;
+                        tracing::info!("DevDocsProvider registered (lazy)");
+                        Ok(())
+                    }).await.is_some()
+                });
+
                 // Register ClipboardHistoryProvider
-                match search::providers::ClipboardHistoryProvider::new() {
-                    Ok(mut clipboard_provider) => {
+                let (guard, engine, app_handle) = (Arc::clone(&provider_guard), Arc::clone(&search_engine_clone), app_handle_clone.clone());
+                startups.add("Clipboard History", async move {
+                    guard.run("Clipboard History", async {
+                        let mut clipboard_provider = search::providers::ClipboardHistoryProvider::new()?
+                            .with_max_items(clipboard_history_size);
                         // Initialize the provider (starts clipboard monitoring)
-                        if let Err(e) = clipboard_provider.initialize().await {
-                            tracing::error!("Failed to initialize ClipboardHistoryProvider: {}", e);
-                        } else {
-                            search_engine_clone.register_provider(Box::new(clipboard_provider)).await;
-                            tracing::info!("ClipboardHistoryProvider registered and initialized");
+                        clipboard_provider.initialize().await?;
+                        // One instance serves both searches and clipboard commands
+                        let clipboard_provider = Arc::new(tokio::sync::RwLock::new(clipboard_provider));
+                        engine
+                            .register_provider(Arc::new(SharedProvider::new(Arc::clone(&clipboard_provider)).await))
+                            .await;
+                        app_handle.manage(clipboard_provider);
+                        tracing::info!("ClipboardHistoryProvider registered and initialized");
+                        Ok(())
+                    }).await.is_some()
+                });
+
+                // Let the UI un-grey each feature as its provider lands
+                startups.run(|event| {
+                    let emitted = match event {
+                        StartupEvent::Registered(startup) => {
+                            search_engine_clone.record_provider_startup(startup.clone());
+                            app_handle_clone.emit(PROVIDER_REGISTERED_EVENT, &startup)
                         }
+                        StartupEvent::Ready(all) => app_handle_clone.emit(PROVIDERS_READY_EVENT, &all),
+                    };
+                    if let Err(e) = emitted {
+                        tracing::warn!("Failed to emit provider startup event: {}", e);
                     }
-                    Err(e) => {
-                        tracing::error!("Failed to create ClipboardHistoryProvider: {}", e);
-                    }
-                }
+                }).await;
                 
+                // Register external plugins last, so their keywords can't shadow built-in syntax
+                plugin_host.load(&search_engine_clone).await;
+
                 // Log final provider count and startup time
                 let provider_count = search_engine_clone.provider_count().await;
                 let provider_names = search_engine_clone.provider_names().await;
@@ -442,13 +2113,74 @@ pub fn run() {
                     provider_names
                 );
                 
+                // A run killed mid-write may have damaged a store, so they are checked
+                // before anything else rather than with the deferred tasks
+                if !last_run_clean {
+                    tracing::warn!("The last run didn't exit cleanly, checking stores now");
+                    check_store_integrity(&app_handle_clone, &result_feedback, CheckPolicy::Always).await;
+                }
+
                 // Defer non-critical background tasks
                 tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                 tracing::info!("Starting deferred background tasks...");
                 
-                // Background tasks can be added here (e.g., cache warming, index updates)
+                // Catch stores damaged by a crash or power loss before they fail every search
+                if last_run_clean {
+                    check_store_integrity(&app_handle_clone, &result_feedback, CheckPolicy::Scheduled).await;
+                }
+
+                // Trim caches when the working set grows past the configured threshold
+                let mut memory_check = tokio::time::interval(MEMORY_CHECK_INTERVAL);
+                loop {
+                    memory_check.tick().await;
+                    let Some(working_set) = process_working_set() else {
+                        tracing::debug!("Working set unavailable, memory watchdog stopped");
+                        break;
+                    };
+                    let should_trim = memory_watchdog
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .should_trim(working_set, std::time::Instant::now());
+                    if should_trim {
+                        tracing::info!("Working set is {} MB, trimming caches", working_set / (1024 * 1024));
+                        search_engine_clone.trim_caches().await;
+                    }
+                }
             });
             
+            // Refresh stale caches, the clipboard monitor and the hotkey after sleep or unlock
+            let resume_coordinator = Arc::new(ResumeCoordinator::new());
+            let search_engine_for_resume = Arc::clone(&search_engine);
+            resume_coordinator.add_hook("providers", move || {
+                let search_engine = Arc::clone(&search_engine_for_resume);
+                tauri::async_runtime::spawn(async move {
+                    search_engine.refresh_after_resume().await;
+                });
+            });
+            resume_coordinator.add_hook("global_hotkey", move || {
+                match hotkey_manager.refresh_registration() {
+                    Ok(active) => tracing::info!("Global hotkey '{}' registered again after resume", active),
+                    Err(e) => tracing::warn!("Failed to register global hotkey again after resume: {}", e),
+                }
+            });
+            // Windows may kill the process as soon as the session end message returns
+            let coordinator = Arc::clone(&shutdown_coordinator);
+            let on_session_end = move || {
+                shutdown::flush_blocking(&coordinator, ShutdownReason::SessionEnd);
+            };
+            if let Err(e) = system_events::watch_system_resume(resume_coordinator, on_session_end) {
+                tracing::error!("Failed to watch for system resume: {}", e);
+            }
+
+            // Open the target of the link this launch was opened with
+            if let Some(mut result) = launch_result {
+                let search_engine = Arc::clone(&search_engine);
+                search_engine.sign_result(&mut result);
+                tauri::async_runtime::spawn(async move {
+                    search_engine.execute_result(result).await;
+                });
+            }
+
             // Store the search engine in app state
             app.manage(search_engine);
 
@@ -475,18 +2207,79 @@ pub fn run() {
             register_hotkey,
             unregister_hotkey,
             get_registered_hotkeys,
+            get_active_hotkey,
+            validate_hotkey,
             show_window,
+            report_input_ready,
             hide_window,
+            quit_app,
+            take_initial_query,
             search_query,
+            peek,
+            search_query_faceted,
+            get_results_page,
+            release_search,
+            request_enrichment,
+            get_completion,
+            get_provider_health,
+            get_diagnostics,
+            trim_caches,
+            search_sections,
             execute_result,
+            cancel_execution,
+            explain_result,
+            demote_result,
+            clear_feedback,
+            export_analytics,
+            clear_analytics,
+            recycle_file_result,
+            execute_results_batch,
+            pin_result,
+            unpin_result,
+            list_pins,
+            save_search,
+            delete_saved_search,
+            list_saved_searches,
+            clear_calculator_history,
+            run_self_test,
+            copy_self_test_report,
+            list_plugins,
+            reload_plugins,
+            export_clipboard_history,
+            export_recent_files,
+            clear_clipboard_history,
+            copy_formatted_clipboard_item,
+            copy_result_link,
+            get_result_actions,
+            execute_result_action_by_id,
+            open_in_terminal,
+            begin_drag,
+            evaluate_clipboard,
+            clear_recent_files,
             get_settings,
+            get_effective_settings,
             update_settings,
+            switch_profile,
+            get_provider_config_schema,
+            get_disabled_providers,
+            retry_provider,
             get_resolved_theme,
             is_auto_start_enabled,
             enable_auto_start,
             disable_auto_start,
-            updater::check_for_updates_manual
+            install_context_menu,
+            uninstall_context_menu,
+            updater::check_for_updates_manual,
+            updater::get_update_info
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Covers exits that didn't go through `shutdown::quit`; a no-op after one that did
+            if let tauri::RunEvent::Exit = event {
+                if let Some(coordinator) = app.try_state::<Arc<ShutdownCoordinator>>() {
+                    shutdown::flush_blocking(&coordinator, ShutdownReason::Exit);
+                }
+            }
+        });
 }
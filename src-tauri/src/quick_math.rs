@@ -0,0 +1,618 @@
+use crate::error::{LauncherError, Result};
+use crate::search::providers::calculator::CalculatorProvider;
+use crate::search::providers::calculator_history::CalculatorHistory;
+use crate::search::providers::clipboard::HistoryPause;
+use crate::settings::QuickMathOutput;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long the hotkey's modifiers may stay held before copying gives up
+pub const MODIFIER_RELEASE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long the foreground application gets to answer Ctrl+C
+pub const COPY_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Interval between checks for released modifiers and a changed clipboard
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Clipboard content saved as raw data per format
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClipboardSnapshot {
+    pub formats: Vec<(u32, Vec<u8>)>,
+}
+
+/// Keyboard input and clipboard access used to read the selection of another application
+///
+/// Implemented with Win32 calls in the app; tests use a scripted backend to
+/// check that the clipboard is always put back.
+pub trait SelectionInput: Send + Sync {
+    /// Whether Ctrl, Shift, Alt or a Windows key is held down
+    fn modifiers_held(&self) -> bool;
+    /// Number the system bumps whenever the clipboard changes
+    fn clipboard_sequence(&self) -> u32;
+    /// Copies everything on the clipboard
+    fn save_clipboard(&self) -> Result<ClipboardSnapshot>;
+    /// Puts `snapshot` back, marked so clipboard managers don't record it
+    fn restore_clipboard(&self, snapshot: ClipboardSnapshot) -> Result<()>;
+    /// Text on the clipboard, if any
+    fn read_text(&self) -> Result<Option<String>>;
+    /// Sends Ctrl+C to the foreground window
+    fn send_copy(&self) -> Result<()>;
+    /// Types `text` into the foreground window
+    fn type_text(&self, text: &str) -> Result<()>;
+}
+
+/// What the quick math hotkey did with the selection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuickMathOutcome {
+    /// The result is to be shown to the user
+    Shown { expression: String, result: String },
+    /// The result was typed over the selection
+    Replaced { expression: String, result: String },
+    /// The selection isn't something the calculator evaluates
+    NotACalculation,
+    /// Nothing was selected, or the application didn't copy it
+    NothingSelected,
+}
+
+/// Evaluates the text selected in another application as a calculation
+///
+/// The selection is copied with Ctrl+C once the hotkey's modifiers are
+/// released, read, and the previous clipboard content put back. The
+/// clipboard history is paused throughout, so neither ends up in it.
+pub struct QuickMath {
+    input: Arc<dyn SelectionInput>,
+    calculator: CalculatorProvider,
+    output: Mutex<QuickMathOutput>,
+    modifier_timeout: Duration,
+    copy_timeout: Duration,
+}
+
+impl QuickMath {
+    /// Creates quick math showing results, using the system's keyboard and clipboard
+    pub fn new() -> Self {
+        Self::with_input(Arc::new(SystemSelectionInput))
+    }
+
+    /// Creates quick math showing results, using the given input backend
+    pub fn with_input(input: Arc<dyn SelectionInput>) -> Self {
+        Self {
+            input,
+            calculator: CalculatorProvider::default(),
+            output: Mutex::new(QuickMathOutput::default()),
+            modifier_timeout: MODIFIER_RELEASE_TIMEOUT,
+            copy_timeout: COPY_TIMEOUT,
+        }
+    }
+
+    /// Records evaluated selections in the calculator history
+    pub fn with_history(mut self, history: Arc<CalculatorHistory>) -> Self {
+        self.calculator = self.calculator.with_history(history);
+        self
+    }
+
+    /// Sets how long to wait for released modifiers and for the copy to land
+    pub fn with_timeouts(mut self, modifier_timeout: Duration, copy_timeout: Duration) -> Self {
+        self.modifier_timeout = modifier_timeout;
+        self.copy_timeout = copy_timeout;
+        self
+    }
+
+    /// Sets where results go
+    pub fn set_output(&self, output: QuickMathOutput) {
+        *self.output.lock().unwrap_or_else(|e| e.into_inner()) = output;
+    }
+
+    /// Returns where results go
+    pub fn output(&self) -> QuickMathOutput {
+        *self.output.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Copies the selection, evaluates it and shows or types the result
+    pub async fn evaluate_selection(&self) -> Result<QuickMathOutcome> {
+        let Some(text) = self.copy_selection().await? else {
+            return Ok(QuickMathOutcome::NothingSelected);
+        };
+
+        let expression = text.trim().trim_end_matches('=').trim().to_string();
+        if expression.is_empty() {
+            return Ok(QuickMathOutcome::NothingSelected);
+        }
+        // A lone number evaluates to itself, which tells the user nothing
+        if expression.parse::<f64>().is_ok() {
+            return Ok(QuickMathOutcome::NotACalculation);
+        }
+        let Some(result) = self.calculator.calculate(&expression) else {
+            return Ok(QuickMathOutcome::NotACalculation);
+        };
+        self.calculator.record_calculation(&expression, &result).await;
+
+        match self.output() {
+            QuickMathOutput::Toast => Ok(QuickMathOutcome::Shown { expression, result }),
+            QuickMathOutput::Replace => {
+                self.input.type_text(&result)?;
+                Ok(QuickMathOutcome::Replaced { expression, result })
+            }
+        }
+    }
+
+    /// Copies the selected text, leaving the clipboard as it was
+    async fn copy_selection(&self) -> Result<Option<String>> {
+        self.wait_for_released_modifiers().await?;
+
+        let _pause = HistoryPause::new();
+        let snapshot = self.input.save_clipboard()?;
+        let sequence = self.input.clipboard_sequence();
+
+        let text = match self.input.send_copy() {
+            Ok(()) if self.wait_for_copy(sequence).await => self.input.read_text(),
+            Ok(()) => Ok(None),
+            Err(e) => Err(e),
+        };
+
+        // Put the previous content back even when copying failed
+        if let Err(e) = self.input.restore_clipboard(snapshot) {
+            tracing::warn!("Failed to restore the clipboard after quick math: {}", e);
+        }
+        text
+    }
+
+    /// Waits for the hotkey to be let go, so Ctrl+C isn't sent as e.g. Ctrl+Shift+C
+    async fn wait_for_released_modifiers(&self) -> Result<()> {
+        let started = tokio::time::Instant::now();
+        while self.input.modifiers_held() {
+            if started.elapsed() >= self.modifier_timeout {
+                return Err(LauncherError::ExecutionError(
+                    "Release the quick math hotkey to copy the selection".to_string(),
+                ));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        Ok(())
+    }
+
+    /// Waits for the clipboard to change from `sequence`; false if it never did
+    async fn wait_for_copy(&self, sequence: u32) -> bool {
+        let started = tokio::time::Instant::now();
+        loop {
+            if self.input.clipboard_sequence() != sequence {
+                return true;
+            }
+            if started.elapsed() >= self.copy_timeout {
+                return false;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Default for QuickMath {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Win32 clipboard access and keyboard input
+struct SystemSelectionInput;
+
+#[cfg(windows)]
+impl SystemSelectionInput {
+    /// Registered clipboard format `name`, or `None` if it can't be registered
+    fn registered_format(name: &str) -> Option<u32> {
+        use windows::core::PCWSTR;
+        use windows::Win32::System::DataExchange::RegisterClipboardFormatW;
+
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let format = unsafe { RegisterClipboardFormatW(PCWSTR(wide.as_ptr())) };
+        (format != 0).then_some(format)
+    }
+
+    /// Opens the clipboard, runs `f` and closes it again
+    fn with_clipboard<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::System::DataExchange::{CloseClipboard, OpenClipboard};
+
+        unsafe {
+            if OpenClipboard(HWND(std::ptr::null_mut())).is_err() {
+                return Err(LauncherError::ExecutionError("Failed to open clipboard".to_string()));
+            }
+            let result = f();
+            CloseClipboard().ok();
+            result
+        }
+    }
+
+    /// Puts `data` on the opened clipboard as `format`
+    fn set_data(format: u32, data: &[u8]) -> Result<()> {
+        use windows::Win32::Foundation::{HANDLE, HGLOBAL};
+        use windows::Win32::System::DataExchange::SetClipboardData;
+        use windows::Win32::System::Memory::{GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+
+        unsafe {
+            let hmem: HGLOBAL = GlobalAlloc(GMEM_MOVEABLE, data.len().max(1))
+                .map_err(|_| LauncherError::ExecutionError("Failed to allocate memory".to_string()))?;
+            let ptr = GlobalLock(hmem);
+            if ptr.is_null() {
+                GlobalFree(hmem).ok();
+                return Err(LauncherError::ExecutionError("Failed to lock memory".to_string()));
+            }
+            std::ptr::copy_nonoverlapping(data.as_ptr(), ptr as *mut u8, data.len());
+            GlobalUnlock(hmem).ok();
+
+            if SetClipboardData(format, HANDLE(hmem.0)).is_err() {
+                GlobalFree(hmem).ok();
+                return Err(LauncherError::ExecutionError(format!(
+                    "Failed to set clipboard format {}",
+                    format
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn send_keys(inputs: &[windows::Win32::UI::Input::KeyboardAndMouse::INPUT], what: &str) -> Result<()> {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{SendInput, INPUT};
+
+        let sent = unsafe { SendInput(inputs, std::mem::size_of::<INPUT>() as i32) };
+        if sent as usize != inputs.len() {
+            return Err(LauncherError::ExecutionError(format!(
+                "Only {} of {} {} key events were sent",
+                sent,
+                inputs.len(),
+                what
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+impl SelectionInput for SystemSelectionInput {
+    fn modifiers_held(&self) -> bool {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            GetAsyncKeyState, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+        };
+
+        [VK_CONTROL, VK_SHIFT, VK_MENU, VK_LWIN, VK_RWIN]
+            .iter()
+            .any(|key| unsafe { GetAsyncKeyState(key.0 as i32) } < 0)
+    }
+
+    fn clipboard_sequence(&self) -> u32 {
+        unsafe { windows::Win32::System::DataExchange::GetClipboardSequenceNumber() }
+    }
+
+    fn save_clipboard(&self) -> Result<ClipboardSnapshot> {
+        use windows::Win32::Foundation::HGLOBAL;
+        use windows::Win32::System::DataExchange::{EnumClipboardFormats, GetClipboardData};
+        use windows::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+
+        // Bitmaps, metafiles and palettes are GDI handles rather than memory;
+        // Windows recreates the bitmap from CF_DIB when asked
+        const GDI_FORMATS: &[u32] = &[2, 3, 9, 14, 0x80, 0x82, 0x83, 0x8E];
+
+        Self::with_clipboard(|| unsafe {
+            let mut snapshot = ClipboardSnapshot::default();
+            let mut format = EnumClipboardFormats(0);
+            while format != 0 {
+                if !GDI_FORMATS.contains(&format) {
+                    if let Ok(handle) = GetClipboardData(format) {
+                        let hmem = HGLOBAL(handle.0);
+                        let size = GlobalSize(hmem);
+                        let ptr = GlobalLock(hmem);
+                        if size > 0 && !ptr.is_null() {
+                            let data = std::slice::from_raw_parts(ptr as *const u8, size).to_vec();
+                            snapshot.formats.push((format, data));
+                        }
+                        if !ptr.is_null() {
+                            GlobalUnlock(hmem).ok();
+                        }
+                    }
+                }
+                format = EnumClipboardFormats(format);
+            }
+            Ok(snapshot)
+        })
+    }
+
+    fn restore_clipboard(&self, snapshot: ClipboardSnapshot) -> Result<()> {
+        use crate::search::providers::clipboard::EXCLUDE_FROM_MONITORING_FORMAT;
+        use windows::Win32::System::DataExchange::EmptyClipboard;
+
+        Self::with_clipboard(|| {
+            unsafe { EmptyClipboard() }
+                .map_err(|_| LauncherError::ExecutionError("Failed to empty clipboard".to_string()))?;
+            for (format, data) in &snapshot.formats {
+                if let Err(e) = Self::set_data(*format, data) {
+                    tracing::debug!("Skipping clipboard format while restoring: {}", e);
+                }
+            }
+
+            // Keep the restore out of clipboard managers and Windows' own history
+            if let Some(format) = Self::registered_format(EXCLUDE_FROM_MONITORING_FORMAT) {
+                Self::set_data(format, &[0])?;
+            }
+            if let Some(format) = Self::registered_format("CanIncludeInClipboardHistory") {
+                Self::set_data(format, &0u32.to_le_bytes())?;
+            }
+            Ok(())
+        })
+    }
+
+    fn read_text(&self) -> Result<Option<String>> {
+        use windows::Win32::Foundation::HGLOBAL;
+        use windows::Win32::System::DataExchange::{GetClipboardData, IsClipboardFormatAvailable};
+        use windows::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+
+        const CF_UNICODETEXT: u32 = 13;
+        Self::with_clipboard(|| unsafe {
+            if IsClipboardFormatAvailable(CF_UNICODETEXT).is_err() {
+                return Ok(None);
+            }
+            let handle = GetClipboardData(CF_UNICODETEXT)
+                .map_err(|_| LauncherError::ExecutionError("Failed to get clipboard data".to_string()))?;
+            let hmem = HGLOBAL(handle.0);
+            let ptr = GlobalLock(hmem);
+            if ptr.is_null() {
+                return Err(LauncherError::ExecutionError("Failed to lock clipboard memory".to_string()));
+            }
+
+            let units = std::slice::from_raw_parts(ptr as *const u16, GlobalSize(hmem) / 2);
+            let len = units.iter().position(|&unit| unit == 0).unwrap_or(units.len());
+            let text = String::from_utf16_lossy(&units[..len]);
+            GlobalUnlock(hmem).ok();
+            Ok(Some(text))
+        })
+    }
+
+    fn send_copy(&self) -> Result<()> {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS, KEYEVENTF_KEYUP, VIRTUAL_KEY,
+            VK_C, VK_CONTROL,
+        };
+
+        let key = |vk: VIRTUAL_KEY, flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT { wVk: vk, dwFlags: flags, ..Default::default() },
+            },
+        };
+        Self::send_keys(
+            &[
+                key(VK_CONTROL, KEYBD_EVENT_FLAGS(0)),
+                key(VK_C, KEYBD_EVENT_FLAGS(0)),
+                key(VK_C, KEYEVENTF_KEYUP),
+                key(VK_CONTROL, KEYEVENTF_KEYUP),
+            ],
+            "copy",
+        )
+    }
+
+    fn type_text(&self, text: &str) -> Result<()> {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE,
+        };
+
+        let key = |unit: u16, up: bool| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wScan: unit,
+                    dwFlags: if up { KEYEVENTF_UNICODE | KEYEVENTF_KEYUP } else { KEYEVENTF_UNICODE },
+                    ..Default::default()
+                },
+            },
+        };
+        let inputs: Vec<INPUT> = text
+            .encode_utf16()
+            .flat_map(|unit| [key(unit, false), key(unit, true)])
+            .collect();
+        Self::send_keys(&inputs, "typing")
+    }
+}
+
+#[cfg(not(windows))]
+impl SelectionInput for SystemSelectionInput {
+    fn modifiers_held(&self) -> bool {
+        false
+    }
+
+    fn clipboard_sequence(&self) -> u32 {
+        0
+    }
+
+    fn save_clipboard(&self) -> Result<ClipboardSnapshot> {
+        Err(LauncherError::ExecutionError(
+            "Clipboard operations not supported on this platform".to_string(),
+        ))
+    }
+
+    fn restore_clipboard(&self, _snapshot: ClipboardSnapshot) -> Result<()> {
+        Err(LauncherError::ExecutionError(
+            "Clipboard operations not supported on this platform".to_string(),
+        ))
+    }
+
+    fn read_text(&self) -> Result<Option<String>> {
+        Err(LauncherError::ExecutionError(
+            "Clipboard operations not supported on this platform".to_string(),
+        ))
+    }
+
+    fn send_copy(&self) -> Result<()> {
+        Err(LauncherError::ExecutionError(
+            "Simulating copy is not implemented for this platform".to_string(),
+        ))
+    }
+
+    fn type_text(&self, _text: &str) -> Result<()> {
+        Err(LauncherError::ExecutionError(
+            "Simulating typing is not implemented for this platform".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Clipboard holding one text item, with a foreground app copying `selection` on Ctrl+C
+    struct ScriptedInput {
+        clipboard: Mutex<Option<String>>,
+        sequence: Mutex<u32>,
+        selection: Option<String>,
+        /// Polls left before the hotkey's modifiers are released
+        held_polls: AtomicUsize,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedInput {
+        fn selecting(selection: Option<&str>) -> Arc<Self> {
+            Arc::new(Self {
+                clipboard: Mutex::new(Some("previous".to_string())),
+                sequence: Mutex::new(1),
+                selection: selection.map(str::to_string),
+                held_polls: AtomicUsize::new(0),
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+
+        fn record(&self, call: impl Into<String>) {
+            self.calls.lock().unwrap().push(call.into());
+        }
+    }
+
+    impl SelectionInput for ScriptedInput {
+        fn modifiers_held(&self) -> bool {
+            self.held_polls
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |polls| polls.checked_sub(1))
+                .is_ok()
+        }
+
+        fn clipboard_sequence(&self) -> u32 {
+            *self.sequence.lock().unwrap()
+        }
+
+        fn save_clipboard(&self) -> Result<ClipboardSnapshot> {
+            self.record("save");
+            let text = self.clipboard.lock().unwrap().clone().unwrap_or_default();
+            Ok(ClipboardSnapshot { formats: vec![(13, text.into_bytes())] })
+        }
+
+        fn restore_clipboard(&self, snapshot: ClipboardSnapshot) -> Result<()> {
+            self.record(format!("restore (history paused: {})", HistoryPause::is_active()));
+            let text = String::from_utf8(snapshot.formats[0].1.clone()).unwrap();
+            *self.clipboard.lock().unwrap() = Some(text);
+            Ok(())
+        }
+
+        fn read_text(&self) -> Result<Option<String>> {
+            self.record("read");
+            Ok(self.clipboard.lock().unwrap().clone())
+        }
+
+        fn send_copy(&self) -> Result<()> {
+            self.record("copy");
+            if let Some(selection) = &self.selection {
+                *self.clipboard.lock().unwrap() = Some(selection.clone());
+                *self.sequence.lock().unwrap() += 1;
+            }
+            Ok(())
+        }
+
+        fn type_text(&self, text: &str) -> Result<()> {
+            self.record(format!("type {}", text));
+            Ok(())
+        }
+    }
+
+    fn quick_math(input: &Arc<ScriptedInput>) -> QuickMath {
+        QuickMath::with_input(Arc::clone(input) as Arc<dyn SelectionInput>)
+            .with_timeouts(Duration::from_millis(50), Duration::from_millis(30))
+    }
+
+    #[tokio::test]
+    async fn test_selection_is_evaluated_and_clipboard_restored() {
+        let input = ScriptedInput::selecting(Some(" 12 * 4 ="));
+        input.held_polls.store(2, Ordering::SeqCst);
+        let quick_math = quick_math(&input);
+
+        let outcome = quick_math.evaluate_selection().await.unwrap();
+        assert_eq!(
+            outcome,
+            QuickMathOutcome::Shown { expression: "12 * 4".to_string(), result: "48".to_string() }
+        );
+        assert_eq!(input.calls(), vec!["save", "copy", "read", "restore (history paused: true)"]);
+        assert_eq!(input.clipboard.lock().unwrap().as_deref(), Some("previous"));
+    }
+
+    #[tokio::test]
+    async fn test_replace_types_the_result_after_restoring() {
+        let input = ScriptedInput::selecting(Some("(3+4)*2"));
+        let quick_math = quick_math(&input);
+        quick_math.set_output(QuickMathOutput::Replace);
+
+        let outcome = quick_math.evaluate_selection().await.unwrap();
+        assert!(matches!(outcome, QuickMathOutcome::Replaced { ref result, .. } if result == "14"));
+        assert_eq!(
+            input.calls(),
+            vec!["save", "copy", "read", "restore (history paused: true)", "type 14"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_text_and_empty_selections_are_not_evaluated() {
+        for (selection, expected) in [
+            (Some("see you at 5"), QuickMathOutcome::NotACalculation),
+            (Some("42"), QuickMathOutcome::NotACalculation),
+            (Some("   "), QuickMathOutcome::NothingSelected),
+            (None, QuickMathOutcome::NothingSelected),
+        ] {
+            let input = ScriptedInput::selecting(selection);
+            let quick_math = quick_math(&input);
+            quick_math.set_output(QuickMathOutput::Replace);
+
+            assert_eq!(quick_math.evaluate_selection().await.unwrap(), expected, "{:?}", selection);
+            assert!(!input.calls().iter().any(|call| call.starts_with("type")));
+            assert_eq!(input.clipboard.lock().unwrap().as_deref(), Some("previous"));
+        }
+
+        // Nothing copied means nothing is read back either
+        let input = ScriptedInput::selecting(None);
+        quick_math(&input).evaluate_selection().await.unwrap();
+        assert_eq!(input.calls(), vec!["save", "copy", "restore (history paused: true)"]);
+    }
+
+    #[tokio::test]
+    async fn test_held_modifiers_leave_the_clipboard_alone() {
+        let input = ScriptedInput::selecting(Some("2+2"));
+        input.held_polls.store(usize::MAX, Ordering::SeqCst);
+
+        assert!(quick_math(&input).evaluate_selection().await.is_err());
+        assert!(input.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_evaluated_selections_are_recorded() {
+        let path = std::env::temp_dir().join("BetterFinder").join("calculator_history_quick_math.json");
+        let _ = std::fs::remove_file(&path);
+        let history = Arc::new(CalculatorHistory::with_path(path.clone()).unwrap());
+
+        let input = ScriptedInput::selecting(Some("12 * 4"));
+        quick_math(&input).with_history(Arc::clone(&history)).evaluate_selection().await.unwrap();
+        let input = ScriptedInput::selecting(Some("hello"));
+        quick_math(&input).with_history(Arc::clone(&history)).evaluate_selection().await.unwrap();
+
+        let entries = history.list().await;
+        assert_eq!(entries.len(), 1);
+        assert_eq!((entries[0].expression.as_str(), entries[0].result.as_str()), ("12 * 4", "48"));
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::time::{format_relative, Locale};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+/// Metadata key holding what screen readers announce for a result, e.g.
+/// "Application: Chrome, press Enter to launch"
+pub const SPOKEN_LABEL_KEY: &str = "spoken_label";
+
+/// Metadata key holding a stable name for what a result is, which
+/// high-contrast themes show as a shaped glyph instead of a colored icon
+pub const SEMANTIC_KIND_KEY: &str = "semantic_kind";
+
+/// Returns the stable kind name for a result type
+///
+/// The frontend keys its glyphs on these, so they must not change.
+pub fn semantic_kind(result_type: ResultType) -> &'static str {
+    match result_type {
+        ResultType::File => "file",
+        ResultType::Application => "application",
+        ResultType::QuickAction => "command",
+        ResultType::Calculator => "answer",
+        ResultType::DateTime => "time",
+        ResultType::Clipboard => "clipboard",
+        ResultType::Bookmark => "bookmark",
+        ResultType::History => "history",
+        ResultType::RecentFile => "recent_file",
+        ResultType::WebSearch => "web_search",
+        ResultType::Ssh => "remote_host",
+        ResultType::Project => "project",
+        ResultType::Suggestion => "suggestion",
+        ResultType::Generator => "generated",
+        ResultType::Text => "text_stats",
+        ResultType::Hash => "checksum",
+        ResultType::Url => "link",
+        ResultType::SavedSearch => "saved_search",
+        ResultType::FileGroup => "folder",
+    }
+}
+
+/// What a result type is called and what Enter does to it, in `locale`
+fn template(result_type: ResultType, locale: Locale) -> (&'static str, &'static str) {
+    match locale {
+        Locale::En => match result_type {
+            ResultType::File => ("File", "open"),
+            ResultType::Application => ("Application", "launch"),
+            ResultType::QuickAction => ("Quick action", "run"),
+            ResultType::Calculator => ("Calculator answer", "copy"),
+            ResultType::DateTime => ("Date and time", "copy"),
+            ResultType::Clipboard => ("Clipboard item", "copy"),
+            ResultType::Bookmark => ("Bookmark", "open"),
+            ResultType::History => ("History page", "open"),
+            ResultType::RecentFile => ("Recent file", "open"),
+            ResultType::WebSearch => ("Web search", "search"),
+            ResultType::Ssh => ("SSH host", "connect"),
+            ResultType::Project => ("Project", "open"),
+            ResultType::Suggestion => ("Suggestion", "search for it"),
+            ResultType::Generator => ("Generated value", "copy"),
+            ResultType::Text => ("Text", "copy"),
+            ResultType::Hash => ("Checksum", "copy"),
+            ResultType::Url => ("Link", "open"),
+            ResultType::SavedSearch => ("Saved search", "run it"),
+            ResultType::FileGroup => ("Folder group", "expand"),
+        },
+        Locale::Pt => match result_type {
+            ResultType::File => ("Ficheiro", "abrir"),
+            ResultType::Application => ("Aplicação", "iniciar"),
+            ResultType::QuickAction => ("Ação rápida", "executar"),
+            ResultType::Calculator => ("Resultado da calculadora", "copiar"),
+            ResultType::DateTime => ("Data e hora", "copiar"),
+            ResultType::Clipboard => ("Item da área de transferência", "copiar"),
+            ResultType::Bookmark => ("Marcador", "abrir"),
+            ResultType::History => ("Página do histórico", "abrir"),
+            ResultType::RecentFile => ("Ficheiro recente", "abrir"),
+            ResultType::WebSearch => ("Pesquisa na web", "pesquisar"),
+            ResultType::Ssh => ("Servidor SSH", "ligar"),
+            ResultType::Project => ("Projeto", "abrir"),
+            ResultType::Suggestion => ("Sugestão", "pesquisar"),
+            ResultType::Generator => ("Valor gerado", "copiar"),
+            ResultType::Text => ("Texto", "copiar"),
+            ResultType::Hash => ("Soma de verificação", "copiar"),
+            ResultType::Url => ("Ligação", "abrir"),
+            ResultType::SavedSearch => ("Pesquisa guardada", "executar"),
+            ResultType::FileGroup => ("Grupo de pasta", "expandir"),
+        },
+    }
+}
+
+/// Builds the label screen readers announce for `result`
+///
+/// Adds what tells similar results apart: the folder a file is in, the
+/// domain of a bookmark and how long ago a clipboard item was copied.
+pub fn spoken_label(result: &SearchResult, locale: Locale, now: DateTime<Utc>) -> String {
+    let (kind, verb) = template(result.result_type, locale);
+    let press = match locale {
+        Locale::En => format!("press Enter to {}", verb),
+        Locale::Pt => format!("prima Enter para {}", verb),
+    };
+
+    match context(result, locale, now) {
+        Some(context) => format!("{}: {}, {}, {}", kind, result.title, context, press),
+        None => format!("{}: {}, {}", kind, result.title, press),
+    }
+}
+
+fn context(result: &SearchResult, locale: Locale, now: DateTime<Utc>) -> Option<String> {
+    match result.result_type {
+        ResultType::File | ResultType::RecentFile => {
+            let ResultAction::OpenFile { path } = &result.action else {
+                return None;
+            };
+            let folder = Path::new(path).parent()?.file_name()?.to_string_lossy().into_owned();
+            Some(match locale {
+                Locale::En => format!("in folder {}", folder),
+                Locale::Pt => format!("na pasta {}", folder),
+            })
+        }
+        ResultType::Bookmark | ResultType::History | ResultType::Url => {
+            let ResultAction::OpenUrl { url } = &result.action else {
+                return None;
+            };
+            let domain = domain(url)?;
+            Some(match locale {
+                Locale::En => format!("on {}", domain),
+                Locale::Pt => format!("em {}", domain),
+            })
+        }
+        ResultType::Clipboard => {
+            let copied = result
+                .metadata
+                .get("timestamp")
+                .and_then(|value| serde_json::from_value::<DateTime<Utc>>(value.clone()).ok())?;
+            let age = format_relative(copied, now, locale).to_lowercase();
+            Some(match locale {
+                Locale::En => format!("copied {}", age),
+                Locale::Pt => format!("copiado {}", age),
+            })
+        }
+        ResultType::Ssh => Some(result.subtitle.clone()).filter(|subtitle| !subtitle.is_empty()),
+        _ => None,
+    }
+}
+
+/// The host of a URL without "www.", e.g. "docs.rs" for "https://www.docs.rs/tokio"
+fn domain(url: &str) -> Option<&str> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = host.split(':').next()?;
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    (!host.is_empty()).then_some(host)
+}
+
+/// Adds the semantic kind to each result, and the spoken label when `locale`
+/// is given because a screen reader may read it
+pub fn annotate(results: &mut [SearchResult], locale: Option<Locale>, now: DateTime<Utc>) {
+    for result in results {
+        result.metadata.insert(
+            SEMANTIC_KIND_KEY.to_string(),
+            serde_json::json!(semantic_kind(result.result_type)),
+        );
+        if let Some(locale) = locale {
+            let label = spoken_label(result, locale, now);
+            result.metadata.insert(SPOKEN_LABEL_KEY.to_string(), serde_json::json!(label));
+        }
+    }
+}
+
+/// Returns whether Windows reports a running screen reader
+#[cfg(target_os = "windows")]
+pub fn screen_reader_running() -> bool {
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETSCREENREADER, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    };
+
+    let mut running = BOOL(0);
+    let queried = unsafe {
+        SystemParametersInfoW(
+            SPI_GETSCREENREADER,
+            0,
+            Some(&mut running as *mut BOOL as *mut std::ffi::c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    queried.is_ok() && running.as_bool()
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn screen_reader_running() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn result(result_type: ResultType, title: &str, action: ResultAction) -> SearchResult {
+        SearchResult {
+            id: format!("test:{}", title),
+            title: title.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type,
+            score: 50.0,
+            metadata: HashMap::new(),
+            action,
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_label_per_type() {
+        let now = Utc::now();
+        let app = result(
+            ResultType::Application,
+            "Chrome",
+            ResultAction::LaunchApp { path: "C:\\Program Files\\Chrome\\chrome.exe".to_string() },
+        );
+        assert_eq!(spoken_label(&app, Locale::En, now), "Application: Chrome, press Enter to launch");
+
+        let file = result(
+            ResultType::File,
+            "report.pdf",
+            ResultAction::OpenFile { path: "/home/ana/Documents/report.pdf".to_string() },
+        );
+        assert_eq!(
+            spoken_label(&file, Locale::En, now),
+            "File: report.pdf, in folder Documents, press Enter to open"
+        );
+
+        let bookmark = result(
+            ResultType::Bookmark,
+            "Tokio",
+            ResultAction::OpenUrl { url: "https://user@www.docs.rs:443/tokio?x=1".to_string() },
+        );
+        assert_eq!(spoken_label(&bookmark, Locale::En, now), "Bookmark: Tokio, on docs.rs, press Enter to open");
+
+        let mut clip = result(
+            ResultType::Clipboard,
+            "hello",
+            ResultAction::CopyToClipboard { content: "hello".to_string() },
+        );
+        let copied = now - chrono::Duration::minutes(5);
+        clip.metadata.insert("timestamp".to_string(), serde_json::json!(copied));
+        assert_eq!(
+            spoken_label(&clip, Locale::En, now),
+            "Clipboard item: hello, copied 5 min ago, press Enter to copy"
+        );
+
+        let answer = result(ResultType::Calculator, "= 42", ResultAction::CopyToClipboard { content: "42".to_string() });
+        assert_eq!(semantic_kind(answer.result_type), "answer");
+        assert_eq!(spoken_label(&answer, Locale::En, now), "Calculator answer: = 42, press Enter to copy");
+    }
+
+    #[test]
+    fn test_label_localized() {
+        let now = Utc::now();
+        let mut clip = result(
+            ResultType::Clipboard,
+            "olá",
+            ResultAction::CopyToClipboard { content: "olá".to_string() },
+        );
+        clip.metadata.insert("timestamp".to_string(), serde_json::json!(now - chrono::Duration::hours(2)));
+        assert_eq!(
+            spoken_label(&clip, Locale::from_tag("pt-PT"), now),
+            "Item da área de transferência: olá, copiado há 2 horas, prima Enter para copiar"
+        );
+    }
+
+    #[test]
+    fn test_annotate_adds_label_only_when_asked() {
+        let now = Utc::now();
+        let mut results = vec![result(ResultType::Url, "example.com", ResultAction::OpenUrl { url: "example.com".to_string() })];
+
+        annotate(&mut results, None, now);
+        assert_eq!(results[0].metadata[SEMANTIC_KIND_KEY], "link");
+        assert!(!results[0].metadata.contains_key(SPOKEN_LABEL_KEY));
+
+        annotate(&mut results, Some(Locale::En), now);
+        assert_eq!(
+            results[0].metadata[SPOKEN_LABEL_KEY],
+            "Link: example.com, on example.com, press Enter to open"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::types::{ResultAction, ResultType, SearchResult};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Identifier of a result's primary action, the one Enter runs
+pub const EXECUTE_ACTION_ID: &str = "execute";
+
+/// Identifier of the action showing a file selected in the file manager
+pub const REVEAL_ACTION_ID: &str = "reveal_in_file_manager";
+
+/// Identifier of the action opening the terminal in a file's folder
+pub const OPEN_TERMINAL_ACTION_ID: &str = "open_in_terminal";
+
+/// Identifier of the action copying a result's title
+pub const COPY_TITLE_ACTION_ID: &str = "copy_title";
+
+/// Identifier of the action pinning a result to the favorites
+pub const PIN_ACTION_ID: &str = "pin";
+
+/// Identifier of the action removing a pinned result from the favorites
+pub const UNPIN_ACTION_ID: &str = "unpin";
+
+/// One entry of a result's actions menu
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActionDescriptor {
+    /// Passed back to `execute_result_action` to run the action
+    pub id: String,
+    /// Text shown in the menu
+    pub label: String,
+    /// Name of the lucide icon shown next to the label
+    pub icon: Option<String>,
+    /// Whether the frontend must ask the user before running it
+    pub requires_confirmation: bool,
+    /// Whether this is what Enter does
+    pub is_primary: bool,
+}
+
+impl ActionDescriptor {
+    /// A non-primary action that runs without confirmation
+    pub fn new(id: &str, label: &str, icon: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            icon: Some(icon.to_string()),
+            requires_confirmation: false,
+            is_primary: false,
+        }
+    }
+
+    /// Marks the action as needing the user's confirmation
+    pub fn confirmed(mut self) -> Self {
+        self.requires_confirmation = true;
+        self
+    }
+
+    /// The primary action, labelled after what the result does
+    pub fn primary(result: &SearchResult) -> Self {
+        let (label, icon) = match &result.action {
+            ResultAction::OpenFile { path } if Path::new(path).is_dir() => ("Open folder", "FolderOpen"),
+            ResultAction::OpenFile { .. } => ("Open", "File"),
+            ResultAction::LaunchApp { .. } => ("Launch", "AppWindow"),
+            ResultAction::ExecuteCommand { .. } => ("Run", "Play"),
+            ResultAction::CopyToClipboard { .. } => ("Copy to clipboard", "Clipboard"),
+            ResultAction::OpenUrl { .. } => ("Open in browser", "Globe"),
+            ResultAction::WebSearch { .. } => ("Search the web", "Search"),
+            ResultAction::SetQuery { .. } => ("Use suggestion", "CornerDownLeft"),
+            ResultAction::ExpandGroup { .. } => ("Show files", "FolderTree"),
+        };
+
+        Self {
+            is_primary: true,
+            ..Self::new(EXECUTE_ACTION_ID, label, icon)
+        }
+    }
+
+    /// Reads an entry of a result's `secondary_actions`
+    ///
+    /// Entries without an id are skipped; a missing title falls back to the id.
+    pub fn from_secondary(entry: &serde_json::Value) -> Option<Self> {
+        let id = entry.get("id")?.as_str()?;
+        let label = entry.get("title").and_then(|title| title.as_str()).unwrap_or(id);
+
+        Some(Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            icon: None,
+            requires_confirmation: entry
+                .get("requires_confirmation")
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false),
+            is_primary: false,
+        })
+    }
+}
+
+/// Whether a result stands for a file or folder on disk
+pub fn is_file_result(result: &SearchResult) -> bool {
+    matches!(result.result_type, ResultType::File | ResultType::RecentFile) && result.file_path().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn result(action: ResultAction) -> SearchResult {
+        SearchResult {
+            id: "id".to_string(),
+            title: "Title".to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type: ResultType::Application,
+            score: 1.0,
+            metadata: HashMap::new(),
+            action,
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_primary_action_labels_follow_result_action() {
+        let launch = ActionDescriptor::primary(&result(ResultAction::LaunchApp { path: "notepad.exe".to_string() }));
+        assert_eq!(launch.id, EXECUTE_ACTION_ID);
+        assert_eq!(launch.label, "Launch");
+        assert!(launch.is_primary);
+
+        let folder = std::env::temp_dir().to_string_lossy().to_string();
+        assert_eq!(ActionDescriptor::primary(&result(ResultAction::OpenFile { path: folder })).label, "Open folder");
+        let url = ActionDescriptor::primary(&result(ResultAction::OpenUrl { url: "https://x.dev".to_string() }));
+        assert_eq!(url.icon.as_deref(), Some("Globe"));
+    }
+
+    #[test]
+    fn test_secondary_entries_need_an_id() {
+        let entry = serde_json::json!({ "id": "copy_formatted", "title": "Copy formatted", "requires_confirmation": true });
+        let action = ActionDescriptor::from_secondary(&entry).unwrap();
+        assert_eq!(action.label, "Copy formatted");
+        assert!(action.requires_confirmation);
+        assert!(!action.is_primary);
+
+        assert_eq!(ActionDescriptor::from_secondary(&serde_json::json!({ "id": "x" })).unwrap().label, "x");
+        assert!(ActionDescriptor::from_secondary(&serde_json::json!({ "title": "No id" })).is_none());
+    }
+}
@@ -0,0 +1,330 @@
+use crate::error::Result;
+use crate::types::{ResultType, SearchResult};
+use crate::utils::db_integrity;
+use crate::utils::write_json_export;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Records kept; the oldest are deleted past this
+pub const MAX_ANALYTICS_RECORDS: usize = 10_000;
+
+/// How one provider did in one search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderTiming {
+    pub provider: String,
+    pub latency_ms: u64,
+    pub result_count: usize,
+    pub failed: bool,
+}
+
+impl ProviderTiming {
+    pub fn new(provider: &str, latency: Duration, result_count: usize, failed: bool) -> Self {
+        Self {
+            provider: provider.to_string(),
+            latency_ms: latency.as_millis() as u64,
+            result_count,
+            failed,
+        }
+    }
+}
+
+/// One thing recorded for tuning
+///
+/// Neither kind has a field for query text, result titles or paths, so
+/// they can't be recorded by mistake.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalyticsRecord {
+    Search {
+        /// Characters in the query
+        query_length: usize,
+        result_count: usize,
+        /// Whether the results came from the cache, with no provider asked
+        cached: bool,
+        providers: Vec<ProviderTiming>,
+    },
+    Execution {
+        /// Position of the result in the latest search, from 0; `None` when
+        /// it wasn't among those results
+        rank: Option<usize>,
+        result_type: ResultType,
+        /// Whether the web search offered for the query was chosen
+        web_fallback: bool,
+    },
+}
+
+/// A stored record and when it was made
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimedRecord {
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub record: AnalyticsRecord,
+}
+
+/// Opt-in search analytics kept on this computer only, for tuning ranking
+///
+/// Records go to a small SQLite table capped at `MAX_ANALYTICS_RECORDS`
+/// and only leave it through `export`. Nothing is recorded, and the
+/// database isn't created, until the `analytics_local` setting is on; when
+/// off, recording costs one atomic load.
+pub struct SearchAnalytics {
+    enabled: AtomicBool,
+    /// Database records go to; they're dropped without one
+    path: Option<PathBuf>,
+    /// Opened on the first write
+    conn: Mutex<Option<Connection>>,
+    /// Positions of the latest search's result ids, to tell which rank an
+    /// execution picked; kept in memory only
+    latest_ranks: Mutex<HashMap<String, usize>>,
+}
+
+impl SearchAnalytics {
+    /// Creates analytics that record nothing
+    pub fn disabled() -> Self {
+        Self::with_path(None)
+    }
+
+    /// Uses the analytics database under `%APPDATA%\BetterFinder`
+    pub fn load() -> Self {
+        let Some(app_data) = std::env::var_os("APPDATA") else {
+            warn!("APPDATA not found, search analytics can't be recorded");
+            return Self::disabled();
+        };
+
+        Self::at(&PathBuf::from(app_data).join("BetterFinder").join("analytics.db"))
+    }
+
+    /// Uses the analytics database at `path`, opened once something is recorded
+    pub fn at(path: &Path) -> Self {
+        Self::with_path(Some(path.to_path_buf()))
+    }
+
+    fn with_path(path: Option<PathBuf>) -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            path,
+            conn: Mutex::new(None),
+            latest_ranks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Turns recording on or off; records already made are kept
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.latest_ranks.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Records a search of a `query_length`-character query that returned `results`
+    pub fn record_search(&self, query_length: usize, results: &[SearchResult], cached: bool, providers: Vec<ProviderTiming>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        *self.latest_ranks.lock().unwrap_or_else(|e| e.into_inner()) =
+            results.iter().enumerate().map(|(rank, result)| (result.id.clone(), rank)).collect();
+        self.write(AnalyticsRecord::Search {
+            query_length,
+            result_count: results.len(),
+            cached,
+            providers,
+        });
+    }
+
+    /// Records that `result` was executed
+    pub fn record_execution(&self, result: &SearchResult) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let rank = self.latest_ranks.lock().unwrap_or_else(|e| e.into_inner()).get(&result.id).copied();
+        self.write(AnalyticsRecord::Execution {
+            rank,
+            result_type: result.result_type,
+            web_fallback: result.result_type == ResultType::WebSearch,
+        });
+    }
+
+    fn write(&self, record: AnalyticsRecord) {
+        if let Err(e) = self.try_write(&record, Utc::now()) {
+            warn!("Failed to record search analytics: {}", e);
+        }
+    }
+
+    fn try_write(&self, record: &AnalyticsRecord, at: DateTime<Utc>) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        if conn.is_none() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            *conn = Some(Self::connect(path)?);
+        }
+        let Some(conn) = conn.as_ref() else {
+            return Ok(());
+        };
+
+        conn.execute(
+            "INSERT INTO analytics (recorded_at, record) VALUES (?1, ?2)",
+            params![at.to_rfc3339(), serde_json::to_string(record)?],
+        )?;
+        conn.execute(
+            "DELETE FROM analytics WHERE id <= (SELECT MAX(id) FROM analytics) - ?1",
+            params![MAX_ANALYTICS_RECORDS],
+        )?;
+        Ok(())
+    }
+
+    fn connect(path: &Path) -> Result<Connection> {
+        let conn = Connection::open(path)?;
+        db_integrity::configure_durability(&conn)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS analytics (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                recorded_at TEXT NOT NULL,
+                record TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(conn)
+    }
+
+    /// Runs `f` on the database, or returns `None` when there is none yet
+    fn with_existing<T>(&self, f: impl FnOnce(&Connection) -> Result<T>) -> Result<Option<T>> {
+        let Some(path) = &self.path else {
+            return Ok(None);
+        };
+
+        let mut conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        if conn.is_none() {
+            if !path.exists() {
+                return Ok(None);
+            }
+            *conn = Some(Self::connect(path)?);
+        }
+        conn.as_ref().map(f).transpose()
+    }
+
+    /// Reads every stored record, oldest first
+    pub fn records(&self) -> Result<Vec<TimedRecord>> {
+        let records = self.with_existing(|conn| {
+            let mut stmt = conn.prepare("SELECT recorded_at, record FROM analytics ORDER BY id")?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            // Rows are parsed back into records, so only their fields can be exported
+            Ok(rows
+                .into_iter()
+                .filter_map(|(recorded_at, record)| {
+                    Some(TimedRecord {
+                        recorded_at: DateTime::parse_from_rfc3339(&recorded_at).ok()?.with_timezone(&Utc),
+                        record: serde_json::from_str(&record).ok()?,
+                    })
+                })
+                .collect())
+        })?;
+        Ok(records.unwrap_or_default())
+    }
+
+    /// Writes every stored record to a JSON file, returning how many
+    pub fn export(&self, path: &Path) -> Result<usize> {
+        let records = self.records()?;
+        write_json_export(
+            path,
+            &serde_json::json!({
+                "exported_at": Utc::now(),
+                "records": records,
+            }),
+        )?;
+
+        info!("Exported {} search analytics records to {}", records.len(), path.display());
+        Ok(records.len())
+    }
+
+    /// Deletes every stored record, returning how many there were
+    pub fn clear(&self) -> Result<usize> {
+        self.latest_ranks.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        let cleared = self
+            .with_existing(|conn| Ok(conn.execute("DELETE FROM analytics", [])?))?
+            .unwrap_or(0);
+
+        info!("Cleared {} search analytics records", cleared);
+        Ok(cleared)
+    }
+}
+
+impl Default for SearchAnalytics {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_database(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_nothing_is_recorded_until_enabled() {
+        let path = temp_database("analytics_disabled");
+        let analytics = SearchAnalytics::at(&path);
+
+        analytics.record_search(5, &[], false, Vec::new());
+        assert!(!path.exists());
+        assert!(analytics.records().unwrap().is_empty());
+
+        analytics.set_enabled(true);
+        analytics.record_search(5, &[], false, vec![ProviderTiming::new("Calculator", Duration::from_millis(3), 0, false)]);
+        let records = analytics.records().unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(matches!(&records[0].record, AnalyticsRecord::Search { query_length: 5, providers, .. } if providers[0].latency_ms == 3));
+
+        assert_eq!(analytics.clear().unwrap(), 1);
+        assert!(analytics.records().unwrap().is_empty());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_oldest_records_are_dropped_past_the_cap() {
+        let path = temp_database("analytics_ring");
+        let analytics = SearchAnalytics::at(&path);
+        let now = Utc::now();
+        for length in 0..MAX_ANALYTICS_RECORDS + 5 {
+            let record = AnalyticsRecord::Search {
+                query_length: length,
+                result_count: 0,
+                cached: true,
+                providers: Vec::new(),
+            };
+            analytics.try_write(&record, now).unwrap();
+        }
+
+        let records = analytics.records().unwrap();
+        assert_eq!(records.len(), MAX_ANALYTICS_RECORDS);
+        assert!(matches!(records[0].record, AnalyticsRecord::Search { query_length: 5, .. }));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::search::providers::text_tools::{TextStats, COPY_JSON_ACTION_ID};
+use crate::search::SECONDARY_ACTIONS_KEY;
+use crate::types::{ResultAction, ResultType, SearchResult};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use unicode_normalization::UnicodeNormalization;
+
+/// Queries longer than this, in characters, are treated as pasted text
+pub const BLOB_MIN_CHARS: usize = 500;
+
+/// Queries with at least this many lines are treated as pasted text
+pub const BLOB_MIN_LINES: usize = 5;
+
+/// Characters of pasted text handed to the providers that take it
+pub const MAX_BLOB_CHARS: usize = 64 * 1024;
+
+/// Identifier of the action searching the web for the first line of pasted text
+pub const SEARCH_FIRST_LINE_ACTION_ID: &str = "search_first_line";
+
+/// Metadata key holding the first line of pasted text
+pub const FIRST_LINE_KEY: &str = "first_line";
+
+/// Returns whether `query` looks pasted rather than typed: a stack trace,
+/// a log excerpt or a document
+///
+/// Such queries only go to providers that handle long text, as matching
+/// them against names and paths only finds noise.
+pub fn is_blob(query: &str) -> bool {
+    let query = query.trim();
+    query.chars().nth(BLOB_MIN_CHARS).is_some() || query.lines().nth(BLOB_MIN_LINES - 1).is_some()
+}
+
+/// The pasted text as providers that handle it receive it: trimmed,
+/// NFC-normalized and cut to `MAX_BLOB_CHARS`, with line breaks kept
+pub fn full_text(query: &str) -> String {
+    query.trim().nfc().take(MAX_BLOB_CHARS).collect()
+}
+
+/// Key the results for pasted text are cached under, a hash rather than the
+/// text itself
+pub fn cache_key(text: &str) -> String {
+    format!("blob:{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// The first line of `text` with any text, trimmed
+pub fn first_line(text: &str) -> &str {
+    text.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or_default()
+}
+
+/// The result shown in place of the usual ones for pasted text
+///
+/// Enter copies the counts of the text; the actions menu copies them as
+/// JSON or searches the web for the first line, which for a stack trace is
+/// usually the error.
+pub fn blob_result(text: &str) -> SearchResult {
+    let stats = TextStats::count(text);
+    let first_line = first_line(text);
+
+    let mut metadata = HashMap::new();
+    metadata.insert("kind".to_string(), serde_json::json!("blob"));
+    metadata.insert("stats".to_string(), serde_json::json!(stats));
+    metadata.insert(FIRST_LINE_KEY.to_string(), serde_json::json!(first_line));
+    metadata.insert(
+        SECONDARY_ACTIONS_KEY.to_string(),
+        serde_json::json!([
+            {
+                "id": SEARCH_FIRST_LINE_ACTION_ID,
+                "title": "Search the web for the first line",
+                "requires_confirmation": false,
+            },
+            {
+                "id": COPY_JSON_ACTION_ID,
+                "title": "Copy counts as JSON",
+                "requires_confirmation": false,
+            },
+        ]),
+    );
+
+    SearchResult {
+        id: "text:blob".to_string(),
+        title: "Analyze text".to_string(),
+        subtitle: format!("{} · Enter to copy", stats.summary()),
+        icon: None,
+        result_type: ResultType::Text,
+        score: 100.0,
+        metadata,
+        action: ResultAction::CopyToClipboard { content: stats.summary() },
+        normalized_title: None,
+        enrichment_pending: false,
+        integrity: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blob_thresholds() {
+        assert!(!is_blob("visual studio"));
+        assert!(!is_blob(&"a".repeat(BLOB_MIN_CHARS)));
+        assert!(is_blob(&"a".repeat(BLOB_MIN_CHARS + 1)));
+
+        // Surrounding whitespace doesn't count
+        assert!(!is_blob(&format!("  {}  \n", "a".repeat(BLOB_MIN_CHARS))));
+
+        let lines = |count: usize| vec!["at frame"; count].join("\n");
+        assert!(!is_blob(&lines(BLOB_MIN_LINES - 1)));
+        assert!(is_blob(&lines(BLOB_MIN_LINES)));
+        assert!(is_blob(&lines(BLOB_MIN_LINES).replace('\n', "\r\n")));
+    }
+
+    #[test]
+    fn test_full_text_keeps_lines_and_is_capped() {
+        let trace = "  Error: boom\n    at main (app.js:1)\n\tat run (app.js:2)\n";
+        assert_eq!(full_text(trace), "Error: boom\n    at main (app.js:1)\n\tat run (app.js:2)");
+        assert_eq!(full_text(&"é".repeat(MAX_BLOB_CHARS + 10)).chars().count(), MAX_BLOB_CHARS);
+        assert_eq!(full_text("Joa\u{0303}o"), "João");
+
+        assert_eq!(first_line("\n\n  Error: boom  \n at main"), "Error: boom");
+        assert_eq!(first_line(" \n "), "");
+    }
+
+    #[test]
+    fn test_cache_key_hashes_the_text() {
+        let text = "line\n".repeat(1000);
+        let key = cache_key(&text);
+        assert_eq!(key.len(), "blob:".len() + 64);
+        assert!(!key.contains("line"));
+        assert_eq!(key, cache_key(&text));
+        assert_ne!(key, cache_key(&"line\n".repeat(1001)));
+    }
+
+    #[test]
+    fn test_blob_result_counts_and_offers_first_line() {
+        let result = blob_result("Error: boom\n  at main\n  at run");
+        assert_eq!(result.metadata[FIRST_LINE_KEY], "Error: boom");
+        assert_eq!(result.metadata["stats"]["lines"], 3);
+        assert_eq!(result.metadata[SECONDARY_ACTIONS_KEY][0]["id"], SEARCH_FIRST_LINE_ACTION_ID);
+        assert!(matches!(&result.action, ResultAction::CopyToClipboard { content } if content.contains("3 lines")));
+    }
+}
@@ -1,6 +1,7 @@
-use crate::types::SearchResult;
+use crate::types::{CacheStats, SearchResult};
 use lru::LruCache;
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
@@ -11,12 +12,34 @@ use tracing::debug;
 struct CacheEntry {
     results: Vec<SearchResult>,
     timestamp: Instant,
+    /// Approximate memory held by `results`
+    bytes: usize,
+}
+
+/// Approximates the memory held by a list of results
+///
+/// Counts the struct sizes plus the text of the fields that grow with the
+/// content; metadata maps are counted by their entry count only.
+fn estimate_bytes(results: &[SearchResult]) -> usize {
+    results
+        .iter()
+        .map(|result| {
+            std::mem::size_of::<SearchResult>()
+                + result.id.len()
+                + result.title.len()
+                + result.subtitle.len()
+                + result.icon.as_ref().map_or(0, String::len)
+                + result.metadata.len() * 64
+        })
+        .sum()
 }
 
 /// LRU cache for search results with TTL support
 pub struct ResultCache {
     cache: Arc<RwLock<LruCache<String, CacheEntry>>>,
     ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
 }
 
 impl ResultCache {
@@ -26,6 +49,8 @@ impl ResultCache {
         Self {
             cache: Arc::new(RwLock::new(LruCache::new(capacity))),
             ttl: Duration::from_secs(ttl_seconds),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 
@@ -37,6 +62,7 @@ impl ResultCache {
             // Check if entry is still valid (not expired)
             if entry.timestamp.elapsed() < self.ttl {
                 debug!("Cache hit for query: '{}'", query);
+                self.hits.fetch_add(1, Ordering::Relaxed);
                 return Some(entry.results.clone());
             } else {
                 debug!("Cache entry expired for query: '{}'", query);
@@ -46,6 +72,7 @@ impl ResultCache {
         }
         
         debug!("Cache miss for query: '{}'", query);
+        self.misses.fetch_add(1, Ordering::Relaxed);
         None
     }
 
@@ -54,6 +81,7 @@ impl ResultCache {
         let mut cache = self.cache.write().await;
         
         let entry = CacheEntry {
+            bytes: estimate_bytes(&results),
             results,
             timestamp: Instant::now(),
         };
@@ -76,6 +104,80 @@ impl ResultCache {
         debug!("Invalidated cache for query: '{}'", query);
     }
 
+    /// Invalidates every cached query whose results include the given file path
+    ///
+    /// Returns the number of entries removed.
+    pub async fn invalidate_path(&self, path: &str) -> usize {
+        let mut cache = self.cache.write().await;
+        
+        let stale: Vec<String> = cache
+            .iter()
+            .filter(|(_, entry)| {
+                entry.results.iter().any(|result| {
+                    result
+                        .file_path()
+                        .is_some_and(|result_path| result_path.eq_ignore_ascii_case(path))
+                })
+            })
+            .map(|(query, _)| query.clone())
+            .collect();
+        
+        for query in &stale {
+            cache.pop(query);
+        }
+        
+        debug!("Invalidated {} cached queries containing '{}'", stale.len(), path);
+        stale.len()
+    }
+
+    /// Returns a result with the given id from any unexpired entry
+    ///
+    /// Doesn't count as a hit or refresh the entry's position.
+    pub async fn find(&self, result_id: &str) -> Option<SearchResult> {
+        let cache = self.cache.read().await;
+        cache
+            .iter()
+            .filter(|(_, entry)| entry.timestamp.elapsed() < self.ttl)
+            .find_map(|(_, entry)| entry.results.iter().find(|result| result.id == result_id))
+            .cloned()
+    }
+
+    /// Removes expired entries, returning how many were removed
+    pub async fn trim(&self) -> usize {
+        let mut cache = self.cache.write().await;
+        
+        let expired: Vec<String> = cache
+            .iter()
+            .filter(|(_, entry)| entry.timestamp.elapsed() >= self.ttl)
+            .map(|(query, _)| query.clone())
+            .collect();
+        
+        for query in &expired {
+            cache.pop(query);
+        }
+        
+        debug!("Trimmed {} expired cached queries", expired.len());
+        expired.len()
+    }
+
+    /// Reports entry count and approximate bytes held for diagnostics
+    pub async fn stats(&self) -> CacheStats {
+        let cache = self.cache.read().await;
+        CacheStats {
+            name: "Search results".to_string(),
+            entries: cache.len(),
+            bytes: cache.iter().map(|(query, entry)| query.len() + entry.bytes).sum(),
+            byte_budget: None,
+        }
+    }
+
+    /// Returns the share of lookups answered from the cache, or `None` before any lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let lookups = hits + self.misses.load(Ordering::Relaxed);
+        (lookups > 0).then(|| hits as f64 / lookups as f64)
+    }
+
     /// Returns the number of entries currently in the cache
     pub async fn len(&self) -> usize {
         let cache = self.cache.read().await;
@@ -107,6 +209,9 @@ mod tests {
             action: ResultAction::OpenFile {
                 path: "/test".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 
@@ -174,6 +279,22 @@ mod tests {
         assert!(cache.is_empty().await);
     }
 
+    #[tokio::test]
+    async fn test_cache_invalidate_path() {
+        let cache = ResultCache::new(10, 5);
+        let mut other = create_test_result("2", "other");
+        other.action = ResultAction::OpenFile {
+            path: "/other".to_string(),
+        };
+        
+        cache.put("query1".to_string(), vec![create_test_result("1", "test1")]).await;
+        cache.put("query2".to_string(), vec![other]).await;
+        
+        assert_eq!(cache.invalidate_path("/TEST").await, 1);
+        assert!(cache.get("query1").await.is_none());
+        assert!(cache.get("query2").await.is_some());
+    }
+
     #[tokio::test]
     async fn test_lru_eviction() {
         let cache = ResultCache::new(2, 5); // Only 2 entries
@@ -187,4 +308,32 @@ mod tests {
         assert!(cache.get("query2").await.is_some());
         assert!(cache.get("query3").await.is_some());
     }
+
+    #[tokio::test]
+    async fn test_hit_rate_and_stats() {
+        let cache = ResultCache::new(10, 5);
+        assert_eq!(cache.hit_rate(), None);
+
+        cache.put("query".to_string(), vec![create_test_result("1", "test")]).await;
+        cache.get("query").await;
+        cache.get("query").await;
+        cache.get("other").await;
+        assert_eq!(cache.hit_rate(), Some(2.0 / 3.0));
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.entries, 1);
+        assert!(stats.bytes > std::mem::size_of::<SearchResult>());
+    }
+
+    #[tokio::test]
+    async fn test_trim_removes_only_expired_entries() {
+        let cache = ResultCache::new(10, 1);
+        cache.put("old".to_string(), vec![create_test_result("1", "test")]).await;
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        cache.put("new".to_string(), vec![create_test_result("2", "test")]).await;
+
+        assert_eq!(cache.trim().await, 1);
+        assert_eq!(cache.len().await, 1);
+        assert!(cache.get("new").await.is_some());
+    }
 }
@@ -0,0 +1,242 @@
+use crate::types::SearchResult;
+use crate::utils::normalize_for_search;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Number of executed results remembered for completion
+pub const HISTORY_CAPACITY: usize = 50;
+
+/// Where a completion candidate comes from, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompletionSource {
+    /// A pinned result's alias or title
+    Pin,
+    /// A result executed earlier in this session
+    History,
+    /// An installed application's name
+    App,
+}
+
+/// Text the search box could be completed to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionCandidate {
+    /// Full text the query would complete to
+    pub text: String,
+    /// Result preselected when the completion is accepted
+    pub result_id: String,
+    pub source: CompletionSource,
+}
+
+impl CompletionCandidate {
+    pub fn new(text: impl Into<String>, result_id: impl Into<String>, source: CompletionSource) -> Self {
+        Self {
+            text: text.into(),
+            result_id: result_id.into(),
+            source,
+        }
+    }
+}
+
+/// Inline completion offered for a query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Completion {
+    /// Text to show after the query as ghost text
+    pub suffix: String,
+    /// The full candidate text
+    pub text: String,
+    /// Result to select once the completion is accepted
+    pub result_id: String,
+}
+
+/// Returns what `text` adds after `query` when it literally starts with it
+///
+/// The comparison ignores case and diacritics, so "cafe" extends "Café Bar"
+/// with " Bar". Candidates equal to the query have nothing to add.
+pub fn completion_suffix(query: &str, text: &str) -> Option<String> {
+    suffix_after(&normalize_for_search(query), text)
+}
+
+fn suffix_after(normalized_query: &str, text: &str) -> Option<String> {
+    if normalized_query.is_empty() || !normalize_for_search(text).starts_with(normalized_query) {
+        return None;
+    }
+
+    // Find where the query ends in the original text, which may fold to fewer
+    // characters; the last such point keeps trailing combining marks in the prefix
+    let end = text
+        .char_indices()
+        .map(|(index, c)| index + c.len_utf8())
+        .map(|end| (end, normalize_for_search(&text[..end])))
+        .take_while(|(_, prefix)| prefix.len() <= normalized_query.len())
+        .filter(|(_, prefix)| prefix == normalized_query)
+        .map(|(end, _)| end)
+        .last()?;
+
+    let suffix = &text[end..];
+    (!suffix.is_empty()).then(|| suffix.to_string())
+}
+
+/// Picks the completion for a query from the candidates
+///
+/// Pins beat history, which beats apps. Among pins and apps the shortest
+/// completion wins; history candidates come newest first and keep that order.
+pub fn best_completion<I>(query: &str, candidates: I) -> Option<Completion>
+where
+    I: IntoIterator<Item = CompletionCandidate>,
+{
+    let query = normalize_for_search(query);
+
+    candidates
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, candidate)| {
+            let suffix = suffix_after(&query, &candidate.text)?;
+            let length = match candidate.source {
+                CompletionSource::History => 0,
+                _ => suffix.chars().count(),
+            };
+            Some(((candidate.source, length, index), suffix, candidate))
+        })
+        .min_by_key(|(rank, _, _)| *rank)
+        .map(|(_, suffix, candidate)| Completion {
+            suffix,
+            text: candidate.text,
+            result_id: candidate.result_id,
+        })
+}
+
+/// Recently executed results, kept in memory only
+#[derive(Debug, Default)]
+pub struct ExecutionHistory {
+    /// Titles and result ids, newest first
+    entries: Mutex<VecDeque<(String, String)>>,
+}
+
+impl ExecutionHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an executed result, moving it to the front if already known
+    pub fn record(&self, result: &SearchResult) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|(_, id)| id != &result.id);
+        entries.push_front((result.title.clone(), result.id.clone()));
+        entries.truncate(HISTORY_CAPACITY);
+    }
+
+    /// Forgets every recorded execution
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Returns the history as completion candidates, newest first
+    pub fn candidates(&self) -> Vec<CompletionCandidate> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .map(|(title, id)| CompletionCandidate::new(title.clone(), id.clone(), CompletionSource::History))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ResultAction, ResultType};
+    use std::collections::HashMap;
+
+    fn candidate(text: &str, source: CompletionSource) -> CompletionCandidate {
+        CompletionCandidate::new(text, format!("{:?}:{}", source, text), source)
+    }
+
+    fn result(id: &str, title: &str) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: title.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type: ResultType::Application,
+            score: 0.0,
+            metadata: HashMap::new(),
+            action: ResultAction::LaunchApp { path: title.to_string() },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_completion_suffix() {
+        assert_eq!(completion_suffix("chr", "Chrome"), Some("ome".to_string()));
+        assert_eq!(completion_suffix("CHR", "Chrome"), Some("ome".to_string()));
+        assert_eq!(completion_suffix("cafe", "Café Bar"), Some(" Bar".to_string()));
+        assert_eq!(completion_suffix("café", "Cafe Bar"), Some(" Bar".to_string()));
+        assert_eq!(completion_suffix("cafe", "Cafe\u{301} Bar"), Some(" Bar".to_string()));
+        assert_eq!(completion_suffix("chrome", "Chrome"), None);
+        assert_eq!(completion_suffix("", "Chrome"), None);
+        assert_eq!(completion_suffix("hrome", "Chrome"), None);
+    }
+
+    #[test]
+    fn test_priority_pins_history_apps() {
+        let candidates = vec![
+            candidate("Chromium", CompletionSource::App),
+            candidate("Chrome Canary", CompletionSource::History),
+            candidate("Chrome Work Profile", CompletionSource::Pin),
+        ];
+        let completion = best_completion("chr", candidates.clone()).unwrap();
+        assert_eq!(completion.text, "Chrome Work Profile");
+        assert_eq!(completion.suffix, "ome Work Profile");
+        assert_eq!(completion.result_id, "Pin:Chrome Work Profile");
+
+        let completion = best_completion("chr", candidates[..2].to_vec()).unwrap();
+        assert_eq!(completion.text, "Chrome Canary");
+
+        let completion = best_completion("chr", candidates[..1].to_vec()).unwrap();
+        assert_eq!(completion.text, "Chromium");
+    }
+
+    #[test]
+    fn test_shortest_app_and_newest_history_win() {
+        let apps = vec![
+            candidate("Chrome Remote Desktop", CompletionSource::App),
+            candidate("Chrome", CompletionSource::App),
+        ];
+        assert_eq!(best_completion("chr", apps).unwrap().text, "Chrome");
+
+        let history = vec![
+            candidate("Chrome Remote Desktop", CompletionSource::History),
+            candidate("Chrome", CompletionSource::History),
+        ];
+        assert_eq!(best_completion("chr", history).unwrap().text, "Chrome Remote Desktop");
+    }
+
+    #[test]
+    fn test_never_suggests_non_prefix_matches() {
+        let candidates = vec![
+            candidate("Google Chrome", CompletionSource::Pin),
+            candidate("Visual Studio Code", CompletionSource::History),
+            candidate("Chorme Typo", CompletionSource::App),
+        ];
+        assert_eq!(best_completion("chr", candidates.clone()), None);
+        assert_eq!(best_completion("vsc", candidates), None);
+    }
+
+    #[test]
+    fn test_history_keeps_newest_first_without_duplicates() {
+        let history = ExecutionHistory::new();
+        history.record(&result("app:chrome", "Chrome"));
+        history.record(&result("app:code", "Code"));
+        history.record(&result("app:chrome", "Chrome"));
+
+        let ids: Vec<String> = history.candidates().into_iter().map(|c| c.result_id).collect();
+        assert_eq!(ids, vec!["app:chrome", "app:code"]);
+
+        for i in 0..HISTORY_CAPACITY + 5 {
+            history.record(&result(&format!("file:{}", i), "report"));
+        }
+        assert_eq!(history.candidates().len(), HISTORY_CAPACITY);
+    }
+}
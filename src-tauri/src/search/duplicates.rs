@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a repeated run of the same action on the same result is ignored
+pub const DUPLICATE_WINDOW: Duration = Duration::from_millis(500);
+
+/// The window for results that ask for confirmation, such as shutdown
+pub const CONFIRMED_DUPLICATE_WINDOW: Duration = Duration::from_secs(2);
+
+/// Entries at which expired ones are dropped
+const PRUNE_AT: usize = 32;
+
+/// Entries kept at most; the oldest goes first when all are still fresh
+const MAX_ENTRIES: usize = 256;
+
+/// Whether a run went ahead
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Admission {
+    Admitted,
+    /// The same action ran on the result moments ago, as the execution given
+    /// when it had one
+    Duplicate { execution_id: Option<String> },
+}
+
+struct RecentRun {
+    at: Instant,
+    confirmed: bool,
+    execution_id: Option<String>,
+}
+
+struct State {
+    window: Duration,
+    confirmed_window: Duration,
+    recent: HashMap<(String, String), RecentRun>,
+}
+
+impl State {
+    fn is_fresh(&self, run: &RecentRun, now: Instant) -> bool {
+        let window = if run.confirmed { self.confirmed_window } else { self.window };
+        now.saturating_duration_since(run.at) < window
+    }
+}
+
+/// Remembers recently run actions so a double Enter runs them once
+///
+/// Runs are keyed by result id and action, so another action on the same
+/// result, or the same action on another result, is never held back.
+pub struct DuplicateGuard {
+    state: Mutex<State>,
+}
+
+impl DuplicateGuard {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                window: DUPLICATE_WINDOW,
+                confirmed_window: CONFIRMED_DUPLICATE_WINDOW,
+                recent: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Sets the windows for ordinary results and for those asking for
+    /// confirmation, applying to runs already remembered too
+    pub fn set_windows(&self, window: Duration, confirmed_window: Duration) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.window = window;
+        state.confirmed_window = confirmed_window;
+    }
+
+    /// Records a run of `action` on `result_id`, unless one ran within the window
+    pub fn admit(&self, result_id: &str, action: &str, confirmed: bool, execution_id: Option<&str>) -> Admission {
+        self.admit_at(result_id, action, confirmed, execution_id, Instant::now())
+    }
+
+    pub(crate) fn admit_at(
+        &self,
+        result_id: &str,
+        action: &str,
+        confirmed: bool,
+        execution_id: Option<&str>,
+        now: Instant,
+    ) -> Admission {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (result_id.to_string(), action.to_string());
+
+        if let Some(run) = state.recent.get(&key).filter(|run| state.is_fresh(run, now)) {
+            return Admission::Duplicate { execution_id: run.execution_id.clone() };
+        }
+
+        if state.recent.len() >= PRUNE_AT {
+            let (window, confirmed_window) = (state.window, state.confirmed_window);
+            state.recent.retain(|_, run| {
+                let window = if run.confirmed { confirmed_window } else { window };
+                now.saturating_duration_since(run.at) < window
+            });
+        }
+        if state.recent.len() >= MAX_ENTRIES {
+            let oldest = state.recent.iter().min_by_key(|(_, run)| run.at).map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                state.recent.remove(&oldest);
+            }
+        }
+
+        state.recent.insert(
+            key,
+            RecentRun {
+                at: now,
+                confirmed,
+                execution_id: execution_id.map(str::to_string),
+            },
+        );
+        Admission::Admitted
+    }
+
+    /// Number of runs remembered, expired ones included until pruned
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).recent.len()
+    }
+}
+
+impl Default for DuplicateGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_per_category() {
+        let guard = DuplicateGuard::new();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        assert_eq!(guard.admit_at("app:notepad", "execute", false, Some("exec-1"), at(0)), Admission::Admitted);
+        assert_eq!(
+            guard.admit_at("app:notepad", "execute", false, Some("exec-2"), at(300)),
+            Admission::Duplicate { execution_id: Some("exec-1".to_string()) }
+        );
+        // The window counts from the run that went ahead, not from the repeat
+        assert_eq!(guard.admit_at("app:notepad", "execute", false, Some("exec-3"), at(500)), Admission::Admitted);
+
+        assert_eq!(guard.admit_at("quick:shutdown", "execute", true, Some("exec-4"), at(0)), Admission::Admitted);
+        assert!(matches!(
+            guard.admit_at("quick:shutdown", "execute", true, Some("exec-5"), at(1900)),
+            Admission::Duplicate { .. }
+        ));
+        assert_eq!(guard.admit_at("quick:shutdown", "execute", true, Some("exec-6"), at(2000)), Admission::Admitted);
+
+        // Turned off, nothing is held back, including runs from before
+        guard.set_windows(Duration::ZERO, Duration::ZERO);
+        assert_eq!(guard.admit_at("app:notepad", "execute", false, None, at(501)), Admission::Admitted);
+        assert_eq!(guard.admit_at("app:notepad", "execute", false, None, at(501)), Admission::Admitted);
+    }
+
+    #[test]
+    fn test_distinct_results_and_actions_unaffected() {
+        let guard = DuplicateGuard::new();
+        let now = Instant::now();
+
+        assert_eq!(guard.admit_at("file:a", "execute", false, Some("exec-1"), now), Admission::Admitted);
+        assert_eq!(guard.admit_at("file:b", "execute", false, Some("exec-2"), now), Admission::Admitted);
+        assert_eq!(guard.admit_at("file:a", "reveal_in_file_manager", false, None, now), Admission::Admitted);
+        assert_eq!(
+            guard.admit_at("file:a", "reveal_in_file_manager", false, None, now),
+            Admission::Duplicate { execution_id: None }
+        );
+    }
+
+    #[test]
+    fn test_expired_runs_pruned_and_size_bounded() {
+        let guard = DuplicateGuard::new();
+        let start = Instant::now();
+
+        for index in 0..PRUNE_AT {
+            guard.admit_at(&format!("file:{}", index), "execute", false, None, start);
+        }
+        assert_eq!(guard.len(), PRUNE_AT);
+
+        // Once the earlier runs expired, the next one clears them out
+        guard.admit_at("file:late", "execute", false, None, start + DUPLICATE_WINDOW);
+        assert_eq!(guard.len(), 1);
+
+        // Fresh runs beyond the limit push out the oldest
+        for index in 0..MAX_ENTRIES * 2 {
+            let at = start + DUPLICATE_WINDOW + Duration::from_micros(index as u64);
+            guard.admit_at(&format!("burst:{}", index), "execute", false, None, at);
+        }
+        assert_eq!(guard.len(), MAX_ENTRIES);
+        assert!(matches!(
+            guard.admit_at(&format!("burst:{}", MAX_ENTRIES * 2 - 1), "execute", false, None, start + DUPLICATE_WINDOW),
+            Admission::Duplicate { .. }
+        ));
+    }
+}
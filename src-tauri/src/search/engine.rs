@@ -1,9 +1,51 @@
+use crate::auto_paste::AutoPaste;
 use crate::error::{LauncherError, Result};
-use crate::search::{ResultCache, SearchProvider};
-use crate::types::{ResultAction, ResultType, SearchResult};
-use std::sync::Arc;
+use crate::search::actions::{
+    is_file_result, ActionDescriptor, COPY_TITLE_ACTION_ID, EXECUTE_ACTION_ID, OPEN_TERMINAL_ACTION_ID,
+    PIN_ACTION_ID, REVEAL_ACTION_ID, UNPIN_ACTION_ID,
+};
+use crate::search::accessibility;
+use crate::search::analytics::{ProviderTiming, SearchAnalytics};
+use crate::search::blob;
+use crate::search::completion::{best_completion, Completion, ExecutionHistory};
+use crate::search::duplicates::{Admission, DuplicateGuard};
+use crate::search::enrichment::{EnrichmentPipeline, ENRICHMENT_PROVIDER_KEY};
+use crate::search::facets::{self, FACET_PROVIDER_KEY};
+use crate::search::feedback::{not_useful_action, FeedbackPenalty, FeedbackView, ResultFeedback, NOT_USEFUL_ACTION_ID};
+use crate::search::grouping::{group_file_results, GroupFolders};
+use crate::search::plain::{self, DEFAULT_PLAIN_TEXT_MARKERS, PLAIN_TEXT_SYNTAX};
+use crate::search::integrity::ResultSigner;
+use crate::search::progress::{ExecutionListener, ExecutionProgress};
+use crate::search::provider_config::section_for_provider;
+use crate::search::providers::favorites::{FavoritesStore, Pin};
+use crate::search::recent_boost::{RecentBoosts, RecentExecutions};
+use crate::search::sessions::SearchSessions;
+use crate::search::slots::{assign_slots, SlotMemory};
+use crate::search::web_search::ActiveWebSearchEngine;
+use crate::search::syntax::SyntaxRegistry;
+use crate::search::token_match;
+use crate::search::suggestion::{suggestion_result, SuggestionDictionary, TERMS_PER_PROVIDER};
+use crate::search::{QueryContext, ResultCache, SearchProvider};
+use crate::types::{
+    BatchAction, BatchFailure, BatchSummary, Diagnostics, EnrichedResult, Execution, ExecutionState, ExecutionStatus,
+    FileAccess, InteractionKind, ProviderStartup, ProviderStatus, ResultAction, ResultFacets, ResultPage, ResultType, SearchResponse, SearchResult, SearchSection,
+};
+use crate::utils::launchers::Launchers;
+use crate::utils::process::{self, Wait};
+use crate::utils::show_latency::show_latency;
+use crate::utils::time::Locale;
+use crate::utils::{clipboard::set_clipboard_text, deeplink, memory::process_working_set, move_to_recycle_bin};
+use chrono::Utc;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
+use unicode_normalization::UnicodeNormalization;
 
 /// Maximum number of results to return per provider
 const MAX_RESULTS_PER_PROVIDER: usize = 20;
@@ -11,19 +53,229 @@ const MAX_RESULTS_PER_PROVIDER: usize = 20;
 /// Maximum total results to return
 const MAX_TOTAL_RESULTS: usize = 50;
 
+/// Maximum results kept for paging through one search
+const MAX_SESSION_RESULTS: usize = 300;
+
 /// Cache capacity (number of queries to cache)
 const CACHE_CAPACITY: usize = 100;
 
 /// Cache TTL in seconds
 const CACHE_TTL_SECONDS: u64 = 5;
 
+/// Maximum number of results in one batch execution
+pub const MAX_BATCH_SIZE: usize = 25;
+
+/// Pause between batch items so Explorer windows don't race each other
+const BATCH_STAGGER: Duration = Duration::from_millis(150);
+
+/// Metadata key holding the score breakdown in debug mode
+pub const SCORE_BREAKDOWN_KEY: &str = "score_breakdown";
+
+/// Metadata key listing the secondary actions a result offers
+pub const SECONDARY_ACTIONS_KEY: &str = "secondary_actions";
+
+/// Identifier of the "Move to Recycle Bin" secondary action
+pub const RECYCLE_ACTION_ID: &str = "move_to_recycle_bin";
+
+/// Time a provider has to answer a peek; slower answers are dropped
+pub const PEEK_BUDGET: Duration = Duration::from_millis(5);
+
+/// Metadata key marking results returned by `peek`, which the full search returns again
+pub const PEEK_KEY: &str = "peek";
+
+/// Removes a file from disk; replaceable so tests never touch the Recycle Bin
+type FileDeleter = Arc<dyn Fn(&str) -> Result<()> + Send + Sync>;
+
+/// Immutable snapshot of the registered providers, sorted by priority
+type ProviderList = Arc<Vec<Arc<dyn SearchProvider>>>;
+
+/// Running executions keyed by execution id, with the result id they run
+type InFlightExecutions = Arc<Mutex<HashMap<String, (String, JoinHandle<()>)>>>;
+
+/// How `rank_results` arrived at a result's final score
+///
+/// The ranker applies title and word match boosts, the boost for results
+/// executed in the last few minutes and "Not useful" demotions only; there
+/// are no type weights, frecency bonuses or dedup merges to report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ScoreBreakdown {
+    /// Score reported by the provider
+    pub base: f64,
+    /// Bonus for a title equal to the query
+    pub exact_match: f64,
+    /// Bonus for a title starting with the query
+    pub prefix_match: f64,
+    /// Bonus for a title containing the query
+    pub contains_match: f64,
+    /// Bonus for the query's words appearing in any order in the title or subtitle
+    pub token_match: f64,
+    /// Decaying bonus for a result executed in the last few minutes
+    pub recent_execution: f64,
+    /// Pinned results sort above everything else regardless of score
+    pub pinned: bool,
+    /// Times the result was marked "Not useful" for this query's family
+    pub demotions: u32,
+    /// Points those demotions took off, after decay
+    pub demotion_penalty: f64,
+    /// Final score used for sorting
+    pub total: f64,
+}
+
+impl ScoreBreakdown {
+    /// Applies the title boosts to a provider score
+    ///
+    /// Both the title and the query must already be normalized.
+    pub fn compute(base: f64, title: &str, query: &str, pinned: bool) -> Self {
+        let exact_match = if title == query { 100.0 } else { 0.0 };
+        let prefix_match = if title.starts_with(query) { 50.0 } else { 0.0 };
+        let contains_match = if title.contains(query) { 25.0 } else { 0.0 };
+
+        Self {
+            base,
+            exact_match,
+            prefix_match,
+            contains_match,
+            pinned,
+            total: base + exact_match + prefix_match + contains_match,
+            ..Self::default()
+        }
+    }
+
+    /// Adds the bonus for matching the query word by word
+    pub fn with_token_match(mut self, bonus: f64) -> Self {
+        self.token_match = bonus;
+        self.total += bonus;
+        self
+    }
+
+    /// Adds the bonus for a recent execution of the result
+    pub fn with_recent_execution(mut self, bonus: f64) -> Self {
+        self.recent_execution = bonus;
+        self.total += bonus;
+        self
+    }
+
+    /// Takes the demotion penalty off the total
+    pub fn with_feedback(mut self, feedback: Option<FeedbackPenalty>) -> Self {
+        if let Some(feedback) = feedback {
+            self.demotions = feedback.demotions;
+            self.demotion_penalty = feedback.penalty;
+            self.total -= feedback.penalty;
+        }
+        self
+    }
+
+    /// Reports a provider score that ranking leaves untouched
+    pub fn unboosted(base: f64, pinned: bool) -> Self {
+        Self {
+            base,
+            pinned,
+            total: base,
+            ..Self::default()
+        }
+    }
+
+    /// Computes the breakdown for a result, skipping boosts for results that
+    /// keep their provider order
+    fn for_result(result: &SearchResult, base: f64, query: &QueryContext) -> Self {
+        if result.preserves_provider_order() {
+            return Self::unboosted(base, result.is_pinned());
+        }
+
+        // Precomputed titles are folded, which only fits a folded query
+        let title = if query.folds_diacritics() {
+            result.normalized_title()
+        } else {
+            Cow::Owned(query.fold(&result.title))
+        };
+        let breakdown = Self::compute(base, &title, &query.folded_query, result.is_pinned());
+        // Web searches restate the query in their title, so every word would match
+        if query.match_tokens.len() < 2 || result.result_type == ResultType::WebSearch {
+            return breakdown;
+        }
+
+        let subtitle = query.fold(token_match::matched_part(&result.subtitle));
+        breakdown.with_token_match(token_match::token_bonus(&title, &subtitle, &query.match_tokens))
+    }
+}
+
 /// SearchEngine coordinates search across multiple providers
 pub struct SearchEngine {
-    providers: Arc<RwLock<Vec<Box<dyn SearchProvider>>>>,
+    /// Current provider snapshot; the lock is only held to clone or swap the `Arc`,
+    /// so searches never hold it across provider futures and never block registration
+    providers: std::sync::RwLock<ProviderList>,
     /// Optional callback for tracking file access
-    file_access_tracker: Arc<RwLock<Option<Box<dyn Fn(&str) + Send + Sync>>>>,
+    file_access_tracker: Arc<RwLock<Option<Box<dyn Fn(&FileAccess) + Send + Sync>>>>,
     /// LRU cache for search results
     cache: ResultCache,
+    /// Whether ranked results carry a score breakdown
+    debug_mode: AtomicBool,
+    /// Maximum number of results a search returns, from the `max_results` setting
+    max_results: AtomicUsize,
+    /// Whether file results offer the Recycle Bin action
+    file_delete_enabled: AtomicBool,
+    /// Whether many file results from one folder collapse into a group
+    group_file_results: AtomicBool,
+    /// Signs produced results so ones sent back for execution can be verified
+    signer: ResultSigner,
+    /// Whether results from the frontend must carry a valid signature
+    strict_results: AtomicBool,
+    /// Whether matching ignores diacritics, so "joao" finds "João"
+    fold_diacritics: AtomicBool,
+    /// Characters that, leading a query, mark it as plain text; none when empty
+    plain_text_markers: std::sync::RwLock<String>,
+    /// Words left out when ranking matches the query word by word, if not the defaults
+    stopwords: std::sync::RwLock<Option<Arc<Vec<String>>>>,
+    /// Whether results carry spoken labels even when no screen reader is detected
+    screen_reader_support: AtomicBool,
+    /// Performs the Recycle Bin move for confirmed delete requests
+    file_deleter: Arc<RwLock<FileDeleter>>,
+    /// Optional callback for files that were moved to the Recycle Bin
+    file_removal_tracker: Arc<RwLock<Option<Box<dyn Fn(&str) + Send + Sync>>>>,
+    /// Optional callback for execution status changes
+    execution_listener: Arc<RwLock<Option<ExecutionListener>>>,
+    /// Pastes copied clipboard results into the previous window, when enabled
+    auto_paste: RwLock<Option<Arc<AutoPaste>>>,
+    /// Per-provider options from settings, applied to providers as they register
+    provider_settings: RwLock<HashMap<String, serde_json::Value>>,
+    /// Executions that have started but not yet finished
+    in_flight: InFlightExecutions,
+    /// Counter used to build execution ids
+    next_execution_id: AtomicU64,
+    /// Recent executions, so a double Enter runs a result once
+    duplicates: DuplicateGuard,
+    /// Spelling correction terms, built on first use from the provider caches
+    suggestions: RwLock<Option<Arc<SuggestionDictionary>>>,
+    /// Results executed this session, offered as inline completions
+    history: ExecutionHistory,
+    /// Results executed in the last few minutes, boosted when ranking
+    recent: RecentExecutions,
+    /// Whether executions are kept out of `history` and `recent`
+    privacy_mode: AtomicBool,
+    /// Full result lists of recent searches, for paging past the first page
+    sessions: SearchSessions,
+    /// Whether the top results keep their number shortcuts while a query is typed
+    stable_result_slots: AtomicBool,
+    /// Number shortcuts of the search being typed
+    result_slots: SlotMemory,
+    /// Engine web searches go to, shared with the WebSearch provider
+    web_search_engine: ActiveWebSearchEngine,
+    /// File manager and terminal from settings
+    launchers: std::sync::RwLock<Launchers>,
+    /// Workers and batching for results whose details are filled in after the search
+    enrichment: EnrichmentPipeline,
+    /// Query syntax of the enabled providers, shared with the Help provider
+    syntax: SyntaxRegistry,
+    /// Results marked "Not useful", demoted or hidden when ranking
+    feedback: std::sync::RwLock<Arc<ResultFeedback>>,
+    /// Pinned results; results can't be pinned until it's set
+    favorites: std::sync::RwLock<Option<Arc<FavoritesStore>>>,
+    /// Opt-in local analytics of searches and executions
+    analytics: std::sync::RwLock<Arc<SearchAnalytics>>,
+    /// Peeks each provider was dropped from for missing `PEEK_BUDGET`
+    slow_peeks: Mutex<HashMap<String, u32>>,
+    /// How long each provider took to start, in the order they finished
+    provider_startup: Mutex<Vec<ProviderStartup>>,
 }
 
 impl SearchEngine {
@@ -31,56 +283,1132 @@ impl SearchEngine {
     pub fn new() -> Self {
         info!("Initializing SearchEngine with result cache");
         Self {
-            providers: Arc::new(RwLock::new(Vec::new())),
+            providers: std::sync::RwLock::new(Arc::new(Vec::new())),
             file_access_tracker: Arc::new(RwLock::new(None)),
             cache: ResultCache::new(CACHE_CAPACITY, CACHE_TTL_SECONDS),
+            debug_mode: AtomicBool::new(false),
+            max_results: AtomicUsize::new(MAX_TOTAL_RESULTS),
+            file_delete_enabled: AtomicBool::new(false),
+            group_file_results: AtomicBool::new(false),
+            signer: ResultSigner::new(),
+            strict_results: AtomicBool::new(false),
+            fold_diacritics: AtomicBool::new(true),
+            plain_text_markers: std::sync::RwLock::new(DEFAULT_PLAIN_TEXT_MARKERS.to_string()),
+            stopwords: std::sync::RwLock::new(None),
+            screen_reader_support: AtomicBool::new(false),
+            file_deleter: Arc::new(RwLock::new(Arc::new(move_to_recycle_bin))),
+            file_removal_tracker: Arc::new(RwLock::new(None)),
+            execution_listener: Arc::new(RwLock::new(None)),
+            auto_paste: RwLock::new(None),
+            provider_settings: RwLock::new(HashMap::new()),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            next_execution_id: AtomicU64::new(0),
+            duplicates: DuplicateGuard::new(),
+            suggestions: RwLock::new(None),
+            history: ExecutionHistory::new(),
+            recent: RecentExecutions::new(),
+            privacy_mode: AtomicBool::new(false),
+            sessions: SearchSessions::new(),
+            stable_result_slots: AtomicBool::new(false),
+            result_slots: SlotMemory::default(),
+            web_search_engine: ActiveWebSearchEngine::default(),
+            launchers: std::sync::RwLock::new(Launchers::default()),
+            enrichment: EnrichmentPipeline::new(),
+            syntax: SyntaxRegistry::default(),
+            feedback: std::sync::RwLock::new(Arc::new(ResultFeedback::in_memory())),
+            favorites: std::sync::RwLock::new(None),
+            analytics: std::sync::RwLock::new(Arc::new(SearchAnalytics::disabled())),
+            slow_peeks: Mutex::new(HashMap::new()),
+            provider_startup: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets a callback for execution status changes, e.g. to emit `execution-status`
+    pub async fn set_execution_listener<F>(&self, listener: F)
+    where
+        F: Fn(&ExecutionStatus) + Send + Sync + 'static,
+    {
+        let mut execution_listener = self.execution_listener.write().await;
+        *execution_listener = Some(Arc::new(listener));
+        info!("Execution listener registered");
+    }
+
+    /// Sets a callback for batches of enriched results, e.g. to emit `results-enriched`
+    pub fn set_enrichment_listener<F>(&self, listener: F)
+    where
+        F: Fn(&[EnrichedResult]) + Send + Sync + 'static,
+    {
+        self.enrichment.set_listener(Arc::new(listener));
+        info!("Enrichment listener registered");
+    }
+
+    /// Enables or disables score breakdowns on search results
+    pub async fn set_debug_mode(&self, enabled: bool) {
+        if self.debug_mode.swap(enabled, Ordering::Relaxed) != enabled {
+            // Cached results were ranked under the previous mode
+            self.cache.invalidate_all().await;
+            info!("Search debug mode {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Returns whether score breakdowns are attached to results
+    pub fn debug_mode(&self) -> bool {
+        self.debug_mode.load(Ordering::Relaxed)
+    }
+
+    /// Sets how many results a search returns, never more than `MAX_TOTAL_RESULTS`
+    pub async fn set_max_results(&self, max_results: usize) {
+        let max_results = max_results.clamp(1, MAX_TOTAL_RESULTS);
+        if self.max_results.swap(max_results, Ordering::Relaxed) != max_results {
+            // Cached results were truncated to the previous limit
+            self.cache.invalidate_all().await;
+            info!("Search result limit set to {}", max_results);
         }
     }
 
+    /// Returns how many results a search returns at most
+    pub fn max_results(&self) -> usize {
+        self.max_results.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables the "Move to Recycle Bin" action on file results
+    pub async fn set_file_delete_enabled(&self, enabled: bool) {
+        if self.file_delete_enabled.swap(enabled, Ordering::Relaxed) != enabled {
+            // Cached results carry the previous set of secondary actions
+            self.cache.invalidate_all().await;
+            info!("File delete action {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Returns whether file results offer the Recycle Bin action
+    pub fn file_delete_enabled(&self) -> bool {
+        self.file_delete_enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables the check that results sent back for execution
+    /// came from a search in this session
+    pub fn set_strict_results(&self, enabled: bool) {
+        if self.strict_results.swap(enabled, Ordering::Relaxed) != enabled {
+            info!("Strict result verification {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Sets how long a repeated run of the same result is ignored, for
+    /// ordinary results and for those the user confirms first
+    pub fn set_duplicate_windows(&self, window: Duration, confirmed_window: Duration) {
+        self.duplicates.set_windows(window, confirmed_window);
+    }
+
+    /// Enables or disables spoken labels on results when Windows reports no
+    /// screen reader; with one running they are always added
+    pub fn set_screen_reader_support(&self, enabled: bool) {
+        if self.screen_reader_support.swap(enabled, Ordering::Relaxed) != enabled {
+            info!("Screen reader support {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Returns the locale to build spoken labels in, or `None` when nothing
+    /// would read them
+    fn spoken_label_locale(&self) -> Option<Locale> {
+        let active = self.screen_reader_support.load(Ordering::Relaxed) || accessibility::screen_reader_running();
+        active.then(Locale::system)
+    }
+
+    /// Adds accessibility metadata to results about to leave the engine
+    ///
+    /// Runs after caching, so clipboard ages in the labels stay current and
+    /// a screen reader started mid-session is served from the cache too.
+    fn annotate_results(&self, results: &mut [SearchResult]) {
+        accessibility::annotate(results, self.spoken_label_locale(), Utc::now());
+    }
+
+    /// Returns whether results sent back for execution are verified
+    pub fn strict_results(&self) -> bool {
+        self.strict_results.load(Ordering::Relaxed)
+    }
+
+    /// Signs a result built by the backend itself, e.g. from a link, so it
+    /// executes like a search result
+    pub fn sign_result(&self, result: &mut SearchResult) {
+        self.signer.sign(result);
+    }
+
+    fn sign_results(&self, results: &mut [SearchResult]) {
+        for result in results {
+            self.signer.sign(result);
+        }
+    }
+
+    /// Checks that a result sent by the frontend is one a search returned,
+    /// with its id and action unchanged
+    ///
+    /// Always passes unless strict verification is on. Needed before acting on
+    /// anything but the result's own action, e.g. the file path in its metadata.
+    pub fn verify_result(&self, result: &SearchResult) -> Result<()> {
+        if !self.strict_results() {
+            return Ok(());
+        }
+        self.signer.verify(result).inspect_err(|e| warn!("Rejected result from the frontend: {}", e))
+    }
+
+    /// Checks a result sent by the frontend for executing its action
+    ///
+    /// Like [`Self::verify_result`], but unsigned results are also accepted
+    /// for actions the frontend could rebuild on its own, such as copying
+    /// text; those never reach a provider.
+    pub fn verify_execution(&self, result: &SearchResult) -> Result<()> {
+        if !self.strict_results() {
+            return Ok(());
+        }
+        self.signer
+            .verify_execution(result)
+            .inspect_err(|e| warn!("Rejected result from the frontend: {}", e))
+    }
+
+    /// Returns whether the result's metadata can be acted on: it was signed,
+    /// or strict verification is off
+    fn is_trusted(&self, result: &SearchResult) -> bool {
+        !self.strict_results() || self.signer.is_signed(result)
+    }
+
+    /// Enables or disables collapsing file results by folder
+    pub async fn set_group_file_results(&self, enabled: bool) {
+        if self.group_file_results.swap(enabled, Ordering::Relaxed) != enabled {
+            // Cached results were grouped under the previous setting
+            self.cache.invalidate_all().await;
+            info!("File result grouping {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Returns whether file results are collapsed by folder
+    pub fn group_file_results(&self) -> bool {
+        self.group_file_results.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables diacritic-insensitive matching
+    pub async fn set_fold_diacritics(&self, enabled: bool) {
+        if self.fold_diacritics.swap(enabled, Ordering::Relaxed) != enabled {
+            // Cached results were matched under the previous setting
+            self.cache.invalidate_all().await;
+            info!("Diacritic folding {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Returns whether matching ignores diacritics
+    pub fn fold_diacritics(&self) -> bool {
+        self.fold_diacritics.load(Ordering::Relaxed)
+    }
+
+    /// Sets the characters that mark a query as plain text; empty turns the
+    /// marker off
+    pub async fn set_plain_text_markers(&self, markers: &str) {
+        let changed = {
+            let mut current = self.plain_text_markers.write().unwrap_or_else(|e| e.into_inner());
+            std::mem::replace(&mut *current, markers.to_string()) != markers
+        };
+        if changed {
+            // The help lists the marker only while there is one, and cached
+            // results may be of queries it now escapes
+            self.refresh_syntax().await;
+            self.cache.invalidate_all().await;
+            info!("Plain text markers set to '{}'", markers);
+        }
+    }
+
+    /// Returns the characters that mark a query as plain text
+    pub fn plain_text_markers(&self) -> String {
+        self.plain_text_markers.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Sets the words ranking ignores when matching the query word by word
+    pub async fn set_stopwords(&self, stopwords: Vec<String>) {
+        let stopwords = Some(Arc::new(stopwords));
+        let changed = {
+            let mut current = self.stopwords.write().unwrap_or_else(|e| e.into_inner());
+            std::mem::replace(&mut *current, stopwords.clone()) != stopwords
+        };
+        if changed {
+            // Cached results were ranked with the previous stopwords
+            self.cache.invalidate_all().await;
+        }
+    }
+
+    /// Enables or disables privacy mode, which stops remembering executed
+    /// results for completions and the recent execution boost
+    ///
+    /// Turning it on forgets the executions remembered so far.
+    pub async fn set_privacy_mode(&self, enabled: bool) {
+        if self.privacy_mode.swap(enabled, Ordering::Relaxed) == enabled {
+            return;
+        }
+        if enabled {
+            self.history.clear();
+            self.recent.clear();
+            // Cached results may carry the recent execution boost
+            self.cache.invalidate_all().await;
+        }
+        info!("Privacy mode {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    /// Returns whether executed results are kept out of session memory
+    pub fn privacy_mode(&self) -> bool {
+        self.privacy_mode.load(Ordering::Relaxed)
+    }
+
+    /// Remembers a successfully executed result for completions and the
+    /// recent execution boost, unless privacy mode is on
+    pub async fn remember_execution(&self, result: &SearchResult) {
+        if self.privacy_mode() {
+            return;
+        }
+        self.history.record(result);
+        self.recent.record(&result.id, Instant::now());
+        // Cached rankings predate the boost
+        self.cache.invalidate_all().await;
+    }
+
+    /// Enables or disables keeping the top results in their number shortcut
+    /// slots while a query is typed
+    pub fn set_stable_result_slots(&self, enabled: bool) {
+        if self.stable_result_slots.swap(enabled, Ordering::Relaxed) != enabled {
+            self.result_slots.clear();
+            info!("Stable result slots {}", if enabled { "enabled" } else { "disabled" });
+        }
+    }
+
+    /// Returns whether the top results keep their slots while a query is typed
+    pub fn stable_result_slots(&self) -> bool {
+        self.stable_result_slots.load(Ordering::Relaxed)
+    }
+
+    /// Forgets the slots of the search being typed, e.g. when the window hides
+    pub fn reset_result_slots(&self) {
+        self.result_slots.clear();
+    }
+
+    /// Switches the engine web searches go to, by its settings id
+    pub async fn set_web_search_engine(&self, id: &str) -> Result<()> {
+        if self.web_search_engine.set(id)? {
+            // Cached web search results name the previous engine
+            self.cache.invalidate_all().await;
+            info!("Web search engine set to {}", self.web_search_engine.get().name);
+        }
+        Ok(())
+    }
+
+    /// Returns the engine choice to share with the WebSearch provider
+    pub fn web_search_engine(&self) -> ActiveWebSearchEngine {
+        self.web_search_engine.clone()
+    }
+
+    /// Returns the syntax registry to share with the Help provider
+    pub fn syntax_registry(&self) -> SyntaxRegistry {
+        self.syntax.clone()
+    }
+
+    /// Collects the query syntax of the enabled providers
+    async fn refresh_syntax(&self) {
+        let mut features = Vec::new();
+        for provider in self.providers().iter().filter(|p| p.is_enabled()) {
+            features.extend(provider.syntax_features().await);
+        }
+        if !self.plain_text_markers().is_empty() {
+            features.push(PLAIN_TEXT_SYNTAX);
+        }
+        self.syntax.set(features);
+    }
+
+    /// Sets the file manager and terminal folders open in
+    pub fn set_launchers(&self, launchers: Launchers) {
+        *self.launchers.write().unwrap_or_else(|e| e.into_inner()) = launchers;
+    }
+
+    fn launchers(&self) -> Launchers {
+        self.launchers.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Sets where "Not useful" feedback is kept; it's only in memory until then
+    pub fn set_result_feedback(&self, feedback: Arc<ResultFeedback>) {
+        *self.feedback.write().unwrap_or_else(|e| e.into_inner()) = feedback;
+    }
+
+    fn feedback(&self) -> Arc<ResultFeedback> {
+        Arc::clone(&self.feedback.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Marks a result as not useful for a query
+    ///
+    /// The result ranks lower for the query and its prefix family from now
+    /// on, and is hidden after `SUPPRESS_AFTER_DEMOTIONS` demotions.
+    pub async fn demote_result(&self, query: &str, result_id: &str) -> Result<FeedbackPenalty> {
+        let penalty = self.feedback().demote(&Self::sanitize_query(query), result_id)?;
+        self.cache.invalidate_all().await;
+        Ok(penalty)
+    }
+
+    /// Forgets every "Not useful" demotion, returning how many results had any
+    pub async fn clear_feedback(&self) -> Result<usize> {
+        let cleared = self.feedback().clear()?;
+        self.cache.invalidate_all().await;
+        Ok(cleared)
+    }
+
+    /// Sets where search analytics are recorded; nothing is recorded until then
+    pub fn set_search_analytics(&self, analytics: Arc<SearchAnalytics>) {
+        *self.analytics.write().unwrap_or_else(|e| e.into_inner()) = analytics;
+    }
+
+    /// Returns where search analytics are recorded, to turn them on or export them
+    pub fn analytics(&self) -> Arc<SearchAnalytics> {
+        Arc::clone(&self.analytics.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Records a search for the opt-in analytics; only its length is kept of the query
+    fn record_search(&self, query: &str, results: &[SearchResult], cached: bool, timings: Vec<ProviderTiming>) {
+        self.analytics().record_search(query.trim().chars().count(), results, cached, timings);
+    }
+
+    /// Sets the store results are pinned to
+    pub fn set_favorites_store(&self, store: Arc<FavoritesStore>) {
+        *self.favorites.write().unwrap_or_else(|e| e.into_inner()) = Some(store);
+    }
+
+    fn favorites(&self) -> Result<Arc<FavoritesStore>> {
+        self.favorites
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| LauncherError::ExecutionError("Favorites are unavailable".to_string()))
+    }
+
+    /// Pins a result under an alias
+    pub async fn pin_result(&self, result: SearchResult, alias: Option<&str>) -> Result<Pin> {
+        let pin = self.favorites()?.pin(result, alias).await?;
+
+        // Pins must show up on the next keystroke
+        self.cache.invalidate_all().await;
+        Ok(pin)
+    }
+
+    /// Removes a pin by pin id or original result id, returning whether one was removed
+    pub async fn unpin_result(&self, id: &str) -> Result<bool> {
+        let removed = self.favorites()?.unpin(id).await?;
+        self.cache.invalidate_all().await;
+        Ok(removed)
+    }
+
+    /// Copies a betterfinder:// link to the result, returning the link
+    pub async fn copy_result_link(&self, result: &SearchResult) -> Result<String> {
+        let link = deeplink::result_to_uri(result);
+        set_clipboard_text(&link).await?;
+        Ok(link)
+    }
+
+    /// Builds the context providers receive for a sanitized query
+    fn query_context(&self, sanitized_query: &str) -> QueryContext {
+        let context = QueryContext::new(sanitized_query, self.fold_diacritics());
+        let stopwords = self.stopwords.read().unwrap_or_else(|e| e.into_inner()).clone();
+        match stopwords {
+            Some(stopwords) => context.with_stopwords(&stopwords),
+            None => context,
+        }
+    }
+
+    /// Replaces the function that moves files to the Recycle Bin
+    pub async fn set_file_deleter<F>(&self, deleter: F)
+    where
+        F: Fn(&str) -> Result<()> + Send + Sync + 'static,
+    {
+        let mut file_deleter = self.file_deleter.write().await;
+        *file_deleter = Arc::new(deleter);
+    }
+
+    /// Sets the auto-paste run after clipboard results are copied
+    pub async fn set_auto_paste(&self, auto_paste: Arc<AutoPaste>) {
+        *self.auto_paste.write().await = Some(auto_paste);
+        info!("Auto-paste registered");
+    }
+
+    /// Sets a callback for files that were moved to the Recycle Bin
+    pub async fn set_file_removal_tracker<F>(&self, tracker: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        let mut removal_tracker = self.file_removal_tracker.write().await;
+        *removal_tracker = Some(Box::new(tracker));
+        info!("File removal tracker registered");
+    }
+
     /// Sets a callback for tracking file access
     pub async fn set_file_access_tracker<F>(&self, tracker: F)
     where
-        F: Fn(&str) + Send + Sync + 'static,
+        F: Fn(&FileAccess) + Send + Sync + 'static,
     {
         let mut file_tracker = self.file_access_tracker.write().await;
         *file_tracker = Some(Box::new(tracker));
         info!("File access tracker registered");
     }
 
+    /// Returns the current provider snapshot
+    ///
+    /// Searches and executions keep using the snapshot they loaded even if
+    /// providers are registered or replaced meanwhile.
+    fn providers(&self) -> ProviderList {
+        Arc::clone(&self.providers.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Builds a new provider list from the current one and swaps it in
+    fn update_providers<F>(&self, update: F)
+    where
+        F: FnOnce(&mut Vec<Arc<dyn SearchProvider>>),
+    {
+        let mut current = self.providers.write().unwrap_or_else(|e| e.into_inner());
+        let mut providers: Vec<Arc<dyn SearchProvider>> = current.iter().cloned().collect();
+        update(&mut providers);
+
+        // Sort providers by priority (highest first)
+        providers.sort_by(|a, b| b.priority().cmp(&a.priority()));
+        *current = Arc::new(providers);
+    }
+
+    /// Drops cached results, paged searches and suggestions after the providers
+    /// changed, and collects their syntax again
+    async fn providers_changed(&self) {
+        self.cache.invalidate_all().await;
+        self.sessions.clear();
+        *self.suggestions.write().await = None;
+        self.refresh_syntax().await;
+    }
+
     /// Registers a new search provider
-    pub async fn register_provider(&self, provider: Box<dyn SearchProvider>) {
+    pub async fn register_provider(&self, provider: Arc<dyn SearchProvider>) {
         let name = provider.name().to_string();
         let priority = provider.priority();
+
+        let settings = self.provider_settings.read().await.clone();
+        Self::configure_provider(provider.as_ref(), &settings).await;
         
-        let mut providers = self.providers.write().await;
-        providers.push(provider);
-        
-        // Sort providers by priority (highest first)
-        providers.sort_by(|a, b| b.priority().cmp(&a.priority()));
+        self.update_providers(|providers| providers.push(provider));
         
         // Invalidate cache when providers change
-        self.cache.invalidate_all().await;
+        self.providers_changed().await;
         
         info!("Registered provider '{}' with priority {}", name, priority);
     }
 
-    /// Performs a search across all enabled providers in parallel
-    pub async fn search(&self, query: &str) -> Vec<SearchResult> {
-        if query.trim().is_empty() {
-            debug!("Empty query, returning no results");
-            return Vec::new();
+    /// Stores per-provider options and applies them to registered providers
+    ///
+    /// Providers registered later are configured from the stored options.
+    /// A provider rejecting its section keeps its previous options.
+    pub async fn set_provider_settings(&self, settings: HashMap<String, serde_json::Value>) {
+        if *self.provider_settings.read().await == settings {
+            return;
+        }
+
+        for provider in self.providers().iter() {
+            Self::configure_provider(provider.as_ref(), &settings).await;
+        }
+        *self.provider_settings.write().await = settings;
+
+        // Cached results were produced under the previous options
+        self.cache.invalidate_all().await;
+        self.refresh_syntax().await;
+        info!("Provider settings applied");
+    }
+
+    /// Passes a provider its section of the options, if it has any
+    async fn configure_provider(provider: &dyn SearchProvider, settings: &HashMap<String, serde_json::Value>) {
+        let Some(section) = section_for_provider(settings, provider.name()) else {
+            return;
+        };
+
+        if let Err(e) = provider.configure(section).await {
+            warn!("Failed to configure provider '{}': {}", provider.name(), e);
+        }
+    }
+
+    /// Removes the provider with the given name, returning whether one was registered
+    pub async fn unregister_provider(&self, name: &str) -> bool {
+        let mut removed = false;
+        self.update_providers(|providers| {
+            let before = providers.len();
+            providers.retain(|p| p.name() != name);
+            removed = providers.len() != before;
+        });
+
+        if removed {
+            self.providers_changed().await;
+            info!("Unregistered provider '{}'", name);
+        }
+        removed
+    }
+
+    /// Swaps in a provider in place of the registered one with the same name,
+    /// or registers it if there is none
+    pub async fn replace_provider(&self, provider: Arc<dyn SearchProvider>) {
+        let name = provider.name().to_string();
+
+        self.update_providers(|providers| {
+            providers.retain(|p| p.name() != name);
+            providers.push(provider);
+        });
+        self.providers_changed().await;
+
+        info!("Replaced provider '{}'", name);
+    }
+
+    /// Performs a search across all enabled providers in parallel
+    pub async fn search(&self, query: &str) -> Vec<SearchResult> {
+        let mut results = self.search_all(query).await;
+        results.truncate(self.max_results());
+        results
+    }
+
+    /// Previews the instant answers for a query as it's typed, e.g. a sum or a date
+    ///
+    /// A cheap path beside the full search: only providers that support
+    /// peeking are asked, each has `PEEK_BUDGET` to answer and gives at most
+    /// one result, and nothing is ranked or cached. Providers answering late
+    /// are left out and counted in `provider_health`. Results are marked with
+    /// `PEEK_KEY`, since the full search returns them again.
+    pub async fn peek(&self, query: &str) -> Vec<SearchResult> {
+        // Instant answers are what a plain text query opts out of
+        if query.trim().is_empty() || blob::is_blob(query) || self.strip_plain_text_marker(query).is_some() {
+            return Vec::new();
+        }
+
+        let context = self.query_context(&Self::sanitize_query(query));
+        let providers = self.providers();
+        let peeks = providers
+            .iter()
+            .filter(|provider| provider.supports_peek() && provider.is_enabled())
+            .map(|provider| {
+                let context = &context;
+                async move {
+                    // A provider computing without yielding can't be interrupted,
+                    // so an answer arriving past the budget is dropped too
+                    let started = Instant::now();
+                    let answer = tokio::time::timeout(PEEK_BUDGET, provider.search(context)).await;
+                    match answer {
+                        Ok(Ok(results)) if started.elapsed() <= PEEK_BUDGET => results.into_iter().next(),
+                        Ok(Err(e)) => {
+                            debug!("Provider '{}' failed to peek: {}", provider.name(), e);
+                            None
+                        }
+                        _ => {
+                            debug!("Provider '{}' missed the peek budget", provider.name());
+                            *self
+                                .slow_peeks
+                                .lock()
+                                .unwrap_or_else(|e| e.into_inner())
+                                .entry(provider.name().to_string())
+                                .or_default() += 1;
+                            None
+                        }
+                    }
+                }
+            });
+
+        let mut results: Vec<SearchResult> = futures::future::join_all(peeks).await.into_iter().flatten().collect();
+        for result in results.iter_mut() {
+            result.metadata.insert(PEEK_KEY.to_string(), serde_json::json!(true));
+        }
+        self.sign_results(&mut results);
+        self.annotate_results(&mut results);
+        results
+    }
+
+    /// Searches and returns the first page of results inline
+    ///
+    /// When more results were found than fit on the first page, results
+    /// await enrichment or results of several types could be narrowed with
+    /// `search_faceted`, the full list is kept for `results_page` and
+    /// `request_enrichment` under the returned search id. With stable result
+    /// slots on, the top results keep the slots of the previous search when
+    /// the query continues it.
+    pub async fn search_paged(&self, query: &str) -> SearchResponse {
+        let mut results = self.search_all(query).await;
+        if self.stable_result_slots() {
+            self.result_slots.stabilize(&Self::sanitize_query(query), &mut results);
+        }
+        let first_page = self.max_results();
+        let facets = facets::summarize(&results, &[], first_page);
+
+        let several_types = facets.as_ref().is_some_and(|facets| facets.types.len() > 1);
+        let keep = results.len() > first_page || several_types || results.iter().any(|r| r.enrichment_pending);
+        let search_id = keep.then(|| self.sessions.insert(&Self::sanitize_query(query), results.clone()));
+
+        Self::narrowed_response(search_id, results, &[], first_page, facets)
+    }
+
+    /// Returns the first page of a search's results of the `active` types,
+    /// or of every type when none are
+    ///
+    /// Serves the results kept for the same query by `search_paged` without
+    /// asking providers again; only searches when they are no longer kept.
+    /// Facet counts still cover every type, so chips keep their numbers.
+    pub async fn search_faceted(&self, query: &str, active: &[ResultType]) -> SearchResponse {
+        let sanitized_query = Self::sanitize_query(query);
+        let (search_id, results) = match self.sessions.find_query(&sanitized_query) {
+            Some((search_id, results)) => (Some(search_id), results),
+            None => {
+                let results = self.search_all(query).await;
+                let search_id = (!results.is_empty()).then(|| self.sessions.insert(&sanitized_query, results.clone()));
+                (search_id, results)
+            }
+        };
+
+        let first_page = self.max_results();
+        let facets = facets::summarize(&results, active, first_page);
+        Self::narrowed_response(search_id, results, active, first_page, facets)
+    }
+
+    /// Cuts `results` to the first page of the `active` types
+    fn narrowed_response(
+        search_id: Option<String>,
+        results: Vec<SearchResult>,
+        active: &[ResultType],
+        first_page: usize,
+        facets: Option<ResultFacets>,
+    ) -> SearchResponse {
+        let mut results: Vec<SearchResult> = results
+            .into_iter()
+            .filter(|result| facets::accepts(active, result.result_type))
+            .collect();
+        let total = results.len();
+        results.truncate(first_page);
+        let slots = assign_slots(&results);
+
+        SearchResponse { search_id, total, results, facets, slots }
+    }
+
+    /// Returns up to `limit` results of a paged search, starting at `offset`
+    pub fn results_page(&self, search_id: &str, offset: usize, limit: usize) -> Result<ResultPage> {
+        self.results_page_of_types(search_id, offset, limit, &[])
+    }
+
+    /// Like `results_page`, counting only results of the `types` given, as
+    /// a search narrowed by `search_faceted` does
+    pub fn results_page_of_types(
+        &self,
+        search_id: &str,
+        offset: usize,
+        limit: usize,
+        types: &[ResultType],
+    ) -> Result<ResultPage> {
+        self.sessions.page_of_types(search_id, offset, limit, types).ok_or_else(|| {
+            LauncherError::NotFound(format!("Search '{}' expired or was released", search_id))
+        })
+    }
+
+    /// Drops the results kept for a paged search, returning whether they were still kept
+    ///
+    /// Results of the search still waiting for enrichment are skipped.
+    pub fn release_search(&self, search_id: &str) -> bool {
+        self.sessions.release(search_id)
+    }
+
+    /// Queues the pending results among `result_ids` for enrichment by the
+    /// providers that returned them, returning how many were queued
+    ///
+    /// Enriched results are reported to the enrichment listener in batches.
+    /// Nothing is enriched or reported once the search was released or
+    /// expired, and each result is only enriched once.
+    pub fn request_enrichment(self: &Arc<Self>, search_id: &str, result_ids: Vec<String>) -> usize {
+        let Some(pending) = self.sessions.take_pending(search_id, &result_ids) else {
+            debug!("Search '{}' is gone, not enriching its results", search_id);
+            return 0;
+        };
+
+        let queued = pending.len();
+        for result in pending {
+            let engine = Arc::clone(self);
+            let search_id = search_id.to_string();
+            tokio::spawn(async move { engine.enrich_result(search_id, result).await });
+        }
+        queued
+    }
+
+    /// Enriches one result once a worker is free and adds it to the next batch
+    async fn enrich_result(self: Arc<Self>, search_id: String, mut result: SearchResult) {
+        let _worker = self.enrichment.worker().await;
+        // The user may have moved on while the result waited for a worker
+        if !self.sessions.contains(&search_id) {
+            debug!("Skipping enrichment of '{}' for abandoned search '{}'", result.id, search_id);
+            return;
+        }
+
+        let owner = result.metadata.get(ENRICHMENT_PROVIDER_KEY).and_then(|v| v.as_str());
+        let provider = owner.and_then(|name| self.providers().iter().find(|p| p.name() == name).cloned());
+        match provider {
+            Some(provider) => {
+                if let Err(e) = provider.enrich(&mut result).await {
+                    debug!("Provider '{}' could not enrich '{}': {}", provider.name(), result.id, e);
+                }
+            }
+            None => debug!("No provider to enrich '{}'", result.id),
+        }
+        result.enrichment_pending = false;
+        self.signer.sign(&mut result);
+        self.annotate_results(std::slice::from_mut(&mut result));
+
+        if !self.sessions.update(&search_id, &result) {
+            return;
+        }
+        if self.enrichment.push(EnrichedResult { search_id, result }) {
+            let engine = Arc::clone(&self);
+            tokio::spawn(async move {
+                tokio::time::sleep(engine.enrichment.interval()).await;
+                engine.enrichment.flush(|enriched| engine.sessions.contains(&enriched.search_id));
+            });
+        }
+    }
+
+    /// Searches all enabled providers, returning up to `MAX_SESSION_RESULTS`
+    /// ranked results
+    async fn search_all(&self, query: &str) -> Vec<SearchResult> {
+        if query.trim().is_empty() {
+            debug!("Empty query, returning no results");
+            return Vec::new();
+        }
+
+        // Pasted text only goes to the providers that handle it
+        if blob::is_blob(query) {
+            return self.search_blob(query).await;
+        }
+
+        // So does plain text, without its marker
+        if let Some(text) = self.strip_plain_text_marker(query) {
+            return self.search_plain(query, &text).await;
+        }
+
+        let sanitized_query = Self::sanitize_query(query);
+        debug!("Searching for: '{}'", sanitized_query);
+
+        // Check cache first
+        if let Some(mut cached_results) = self.cache.get(&sanitized_query).await {
+            info!("Returning {} cached results for query: '{}'", cached_results.len(), sanitized_query);
+            self.annotate_results(&mut cached_results);
+            self.record_search(query, &cached_results, true, Vec::new());
+            return cached_results;
+        }
+
+        let context = self.query_context(&sanitized_query);
+        let (mut all_results, cacheable, timings) = self.collect_results(&context, |_| true).await;
+        // Before grouping, which copies file results into their group
+        self.sign_results(&mut all_results);
+
+        // Rank and sort results
+        let feedback = self.feedback();
+        let recent = self.recent.boosts(Instant::now());
+        let mut ranked_results = Self::rank(all_results, &context, self.debug_mode(), Some(&feedback), &recent);
+
+        // Collapse files crowding one folder before the limit is applied
+        if self.group_file_results() {
+            let mut folders = GroupFolders::new();
+            ranked_results = group_file_results(ranked_results, |path| folders.folder_of(path));
+        }
+        
+        // Keep enough results for paging; callers cut them to the first page
+        let mut final_results: Vec<SearchResult> = ranked_results
+            .into_iter()
+            .take(MAX_SESSION_RESULTS)
+            .collect();
+        
+        // Offer what providers keep for queries nothing else matched, ahead
+        // of the web search fallback
+        let only_web_search = final_results.iter().all(|r| r.result_type == ResultType::WebSearch);
+        if only_web_search {
+            let mut fallback = self.fallback_results(&context).await;
+            fallback.append(&mut final_results);
+            final_results = fallback;
+        }
+
+        self.attach_secondary_actions(&mut final_results);
+
+        // Offer a spelling correction when only the web search fallback matched
+        if only_web_search {
+            if let Some(suggestion) = self.suggest_correction(&sanitized_query).await {
+                final_results.push(suggestion);
+            }
+        }
+
+        // List the values a trailing token such as `sort:` accepts
+        if let Some(hint) = self.syntax.hint_for(&sanitized_query) {
+            final_results.push(hint);
+        }
+
+        self.sign_results(&mut final_results);
+        info!("Search completed: {} total results", final_results.len());
+        
+        // Cache the results unless a provider generates fresh ones per search
+        if cacheable {
+            self.cache.put(sanitized_query, final_results.clone()).await;
+        }
+        
+        self.annotate_results(&mut final_results);
+        self.record_search(query, &final_results, false, timings);
+        final_results
+    }
+
+    /// Searches long pasted text with the providers that handle it, after a
+    /// result analyzing the text
+    ///
+    /// Results are cached under a hash of the text rather than the text.
+    async fn search_blob(&self, query: &str) -> Vec<SearchResult> {
+        let text = blob::full_text(query);
+        let cache_key = blob::cache_key(&text);
+        debug!("Searching pasted text of {} characters", text.chars().count());
+
+        if let Some(mut cached_results) = self.cache.get(&cache_key).await {
+            info!("Returning {} cached results for pasted text", cached_results.len());
+            self.annotate_results(&mut cached_results);
+            self.record_search(query, &cached_results, true, Vec::new());
+            return cached_results;
+        }
+
+        let context = self.query_context(&Self::sanitize_query(query)).with_full_text(text);
+        let (all_results, cacheable, timings) = self.collect_results(&context, |provider| provider.handles_blobs()).await;
+
+        let feedback = self.feedback();
+        let recent = self.recent.boosts(Instant::now());
+        let mut final_results = vec![blob::blob_result(context.full_text())];
+        final_results.extend(Self::rank(all_results, &context, self.debug_mode(), Some(&feedback), &recent));
+        final_results.truncate(MAX_SESSION_RESULTS);
+
+        self.attach_secondary_actions(&mut final_results);
+        self.sign_results(&mut final_results);
+        info!("Pasted text search completed: {} total results", final_results.len());
+
+        if cacheable {
+            self.cache.put(cache_key, final_results.clone()).await;
+        }
+
+        self.annotate_results(&mut final_results);
+        self.record_search(query, &final_results, false, timings);
+        final_results
+    }
+
+    /// The sanitized text of a query starting with a plain-text marker
+    fn strip_plain_text_marker(&self, query: &str) -> Option<String> {
+        plain::strip_marker(query, &self.plain_text_markers()).map(Self::sanitize_query)
+    }
+
+    /// Searches for `text` as typed with the providers matching names, for a
+    /// query whose plain-text marker was stripped
+    ///
+    /// Nothing gets to reinterpret the text, so there is no web search
+    /// fallback, spelling correction or syntax hint either. Results are cached
+    /// apart from the same text typed without the marker.
+    async fn search_plain(&self, query: &str, text: &str) -> Vec<SearchResult> {
+        if text.is_empty() {
+            return Vec::new();
+        }
+        let cache_key = plain::cache_key(text);
+        debug!("Searching for plain text: '{}'", text);
+
+        if let Some(mut cached_results) = self.cache.get(&cache_key).await {
+            info!("Returning {} cached results for plain text: '{}'", cached_results.len(), text);
+            self.annotate_results(&mut cached_results);
+            self.record_search(query, &cached_results, true, Vec::new());
+            return cached_results;
+        }
+
+        let context = self.query_context(text);
+        let (mut all_results, cacheable, timings) = self.collect_results(&context, plain::handles_plain_text).await;
+        self.sign_results(&mut all_results);
+
+        let feedback = self.feedback();
+        let recent = self.recent.boosts(Instant::now());
+        let mut ranked_results = Self::rank(all_results, &context, self.debug_mode(), Some(&feedback), &recent);
+        if self.group_file_results() {
+            let mut folders = GroupFolders::new();
+            ranked_results = group_file_results(ranked_results, |path| folders.folder_of(path));
+        }
+        let mut final_results: Vec<SearchResult> = ranked_results.into_iter().take(MAX_SESSION_RESULTS).collect();
+
+        self.attach_secondary_actions(&mut final_results);
+        self.sign_results(&mut final_results);
+        info!("Plain text search completed: {} total results", final_results.len());
+
+        if cacheable {
+            self.cache.put(cache_key, final_results.clone()).await;
+        }
+
+        self.annotate_results(&mut final_results);
+        self.record_search(query, &final_results, false, timings);
+        final_results
+    }
+
+    /// Collects the results providers only offer when nothing else matched
+    async fn fallback_results(&self, context: &QueryContext) -> Vec<SearchResult> {
+        let mut results = Vec::new();
+        for provider in self.providers().iter().filter(|provider| provider.is_enabled()) {
+            results.extend(provider.fallback_results(context).await);
+        }
+        results
+    }
+
+    /// Returns a "Did you mean" result for a query that matched nothing
+    async fn suggest_correction(&self, query: &str) -> Option<SearchResult> {
+        let dictionary = self.suggestion_dictionary().await;
+        let term = dictionary.suggest(query)?;
+        debug!("Suggesting '{}' for query '{}'", term, query);
+        Some(suggestion_result(term))
+    }
+
+    /// Completes the query to a pinned, recently executed or application name
+    ///
+    /// Only asks providers for their in-memory candidates and never runs a
+    /// search, so it is cheap enough to call on every keystroke.
+    pub async fn completion(&self, query: &str) -> Option<Completion> {
+        if query.trim().is_empty() {
+            return None;
+        }
+
+        let mut candidates = self.history.candidates();
+        for provider in self.providers().iter() {
+            if provider.is_enabled() {
+                candidates.extend(provider.completion_candidates(query).await);
+            }
+        }
+
+        best_completion(query, candidates)
+    }
+
+    /// Returns the suggestion dictionary, rebuilding it when missing or stale
+    async fn suggestion_dictionary(&self) -> Arc<SuggestionDictionary> {
+        if let Some(dictionary) = self.suggestions.read().await.as_ref() {
+            if !dictionary.is_stale(Instant::now()) {
+                return Arc::clone(dictionary);
+            }
+        }
+
+        let mut names = Vec::new();
+        for provider in self.providers().iter() {
+            if provider.is_enabled() {
+                names.extend(provider.suggestion_terms(TERMS_PER_PROVIDER).await);
+            }
+        }
+
+        let dictionary = Arc::new(SuggestionDictionary::build(names));
+        debug!("Built suggestion dictionary with {} terms", dictionary.len());
+        *self.suggestions.write().await = Some(Arc::clone(&dictionary));
+        dictionary
+    }
+
+    /// Searches once and splits the results into the requested sections
+    ///
+    /// Providers whose `result_types()` match no section are skipped. Each result
+    /// lands in the first section that accepts its type and still has room, so a
+    /// result never appears twice. The map is keyed by section index.
+    pub async fn search_sections(
+        &self,
+        query: &str,
+        sections: &[SearchSection],
+    ) -> HashMap<usize, Vec<SearchResult>> {
+        let mut sectioned: HashMap<usize, Vec<SearchResult>> =
+            (0..sections.len()).map(|index| (index, Vec::new())).collect();
+
+        if query.trim().is_empty() || sections.is_empty() {
+            debug!("Empty query or no sections, returning empty sections");
+            return sectioned;
+        }
+
+        let sanitized_query = Self::sanitize_query(query);
+        let full_text = blob::is_blob(query).then(|| blob::full_text(query));
+        let plain_text = full_text.is_none().then(|| self.strip_plain_text_marker(query)).flatten();
+        if plain_text.as_deref().is_some_and(str::is_empty) {
+            return sectioned;
+        }
+        let query_key = match (&full_text, &plain_text) {
+            (Some(text), _) => blob::cache_key(text),
+            (None, Some(text)) => plain::cache_key(text),
+            (None, None) => sanitized_query.clone(),
+        };
+        let cache_key = format!("{}\u{0}{}", query_key, Self::sections_signature(sections));
+
+        let mut candidates = match self.cache.get(&cache_key).await {
+            Some(cached_results) => {
+                info!("Returning cached sections for query: '{}'", sanitized_query);
+                cached_results
+            }
+            None => {
+                let mut context = self.query_context(plain_text.as_deref().unwrap_or(&sanitized_query));
+                if let Some(text) = &full_text {
+                    context = context.with_full_text(text.clone());
+                }
+                let (all_results, cacheable, _) = self
+                    .collect_results(&context, |provider| {
+                        (full_text.is_none() || provider.handles_blobs())
+                            && (plain_text.is_none() || plain::handles_plain_text(provider))
+                            && provider.result_types().is_none_or(|types| {
+                                types.iter().any(|t| sections.iter().any(|s| s.accepts(*t)))
+                            })
+                    })
+                    .await;
+
+                let mut seen = HashSet::new();
+                let feedback = self.feedback();
+                let recent = self.recent.boosts(Instant::now());
+                let mut ranked: Vec<SearchResult> = Self::rank(all_results, &context, self.debug_mode(), Some(&feedback), &recent)
+                    .into_iter()
+                    .filter(|result| sections.iter().any(|s| s.accepts(result.result_type)))
+                    .filter(|result| seen.insert(result.id.clone()))
+                    .collect();
+
+                self.attach_secondary_actions(&mut ranked);
+                self.sign_results(&mut ranked);
+
+                if cacheable {
+                    self.cache.put(cache_key, ranked.clone()).await;
+                }
+                ranked
+            }
+        };
+        self.annotate_results(&mut candidates);
+
+        for result in candidates {
+            let slot = sections.iter().enumerate().find(|(index, section)| {
+                section.accepts(result.result_type)
+                    && sectioned[index].len() < section.limit.min(MAX_TOTAL_RESULTS)
+            });
+
+            if let Some((index, _)) = slot {
+                if let Some(bucket) = sectioned.get_mut(&index) {
+                    bucket.push(result);
+                }
+            }
         }
 
-        let sanitized_query = Self::sanitize_query(query);
-        debug!("Searching for: '{}'", sanitized_query);
+        info!("Sectioned search completed for {} sections", sections.len());
+        sectioned
+    }
 
-        // Check cache first
-        if let Some(cached_results) = self.cache.get(&sanitized_query).await {
-            info!("Returning {} cached results for query: '{}'", cached_results.len(), sanitized_query);
-            return cached_results;
-        }
+    /// Builds the part of a sectioned cache key that describes the sections
+    fn sections_signature(sections: &[SearchSection]) -> String {
+        sections
+            .iter()
+            .map(|section| {
+                let mut types: Vec<String> = section.types.iter().map(|t| format!("{:?}", t)).collect();
+                types.sort();
+                format!("{}:{}", types.join(","), section.limit)
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    }
 
-        let providers = self.providers.read().await;
+    /// Runs the enabled providers in parallel and merges their results
+    ///
+    /// Providers rejected by `include` are never searched. The flag is `false`
+    /// when a provider that opted out of caching contributed results. How
+    /// long each provider took is only returned while analytics are on.
+    async fn collect_results<F>(&self, context: &QueryContext, include: F) -> (Vec<SearchResult>, bool, Vec<ProviderTiming>)
+    where
+        F: Fn(&dyn SearchProvider) -> bool,
+    {
+        let providers = self.providers();
+        let timed = self.analytics().is_enabled();
         
         // Collect search futures from all enabled providers
         let mut search_futures = Vec::new();
@@ -91,25 +1419,43 @@ impl SearchEngine {
                 continue;
             }
 
+            if !include(provider.as_ref()) {
+                debug!("Skipping provider outside requested types: {}", provider.name());
+                continue;
+            }
+
             let provider_name = provider.name().to_string();
-            let query_clone = sanitized_query.clone();
+            let provider_cacheable = provider.cacheable();
+            let context = context.clone();
             
             // Execute search and collect the future
             let search_future = async move {
-                match provider.search(&query_clone).await {
+                let started = Instant::now();
+                match provider.search(&context).await {
                     Ok(mut results) => {
                         // Limit results per provider
                         results.truncate(MAX_RESULTS_PER_PROVIDER);
+                        for result in results.iter_mut() {
+                            result
+                                .metadata
+                                .insert(FACET_PROVIDER_KEY.to_string(), serde_json::json!(provider_name));
+                            // Remember who fills in the rest of pending results
+                            if result.enrichment_pending {
+                                result
+                                    .metadata
+                                    .insert(ENRICHMENT_PROVIDER_KEY.to_string(), serde_json::json!(provider_name));
+                            }
+                        }
                         debug!(
                             "Provider '{}' returned {} results",
                             provider_name,
                             results.len()
                         );
-                        Ok((provider_name, results))
+                        Ok((provider_name, provider_cacheable, results, started.elapsed()))
                     }
                     Err(e) => {
                         error!("Provider '{}' search failed: {}", provider_name, e);
-                        Err((provider_name, e))
+                        Err((provider_name, e, started.elapsed()))
                     }
                 }
             };
@@ -122,43 +1468,373 @@ impl SearchEngine {
 
         // Collect and merge results
         let mut all_results = Vec::new();
+        let mut cacheable = true;
+        let mut timings = Vec::new();
         
         for task_result in task_results {
             match task_result {
-                Ok((provider_name, results)) => {
+                Ok((provider_name, provider_cacheable, results, elapsed)) => {
                     debug!("Successfully collected {} results from '{}'", results.len(), provider_name);
+                    cacheable &= provider_cacheable || results.is_empty();
+                    if timed {
+                        timings.push(ProviderTiming::new(&provider_name, elapsed, results.len(), false));
+                    }
                     all_results.extend(results);
                 }
-                Err((provider_name, error)) => {
+                Err((provider_name, error, elapsed)) => {
                     warn!("Provider '{}' failed with error: {}", provider_name, error);
+                    if timed {
+                        timings.push(ProviderTiming::new(&provider_name, elapsed, 0, true));
+                    }
                     // Continue with other providers (graceful degradation)
                 }
             }
         }
 
-        // Rank and sort results
-        let ranked_results = Self::rank_results(all_results, &sanitized_query);
-        
-        // Limit total results
-        let final_results: Vec<SearchResult> = ranked_results
-            .into_iter()
-            .take(MAX_TOTAL_RESULTS)
+        (all_results, cacheable, timings)
+    }
+
+    /// Starts executing a result in the background and returns its execution id
+    ///
+    /// A `Started` status is reported before this returns; `Succeeded` or
+    /// `Failed` follows when the provider finishes. File access is tracked on
+    /// success only.
+    ///
+    /// Executing the same result again within the duplicate window, 500 ms
+    /// by default or 2 s for results asking for confirmation, starts nothing
+    /// and returns the earlier execution marked as a duplicate.
+    pub async fn execute_result(self: &Arc<Self>, result: SearchResult) -> Execution {
+        let execution_id = self.next_execution_id();
+        let admission = self.duplicates.admit(
+            &result.id,
+            EXECUTE_ACTION_ID,
+            result.requires_confirmation(),
+            Some(&execution_id),
+        );
+        if let Admission::Duplicate { execution_id: earlier } = admission {
+            info!("Ignoring repeated execution of '{}'", result.id);
+            return Execution {
+                id: earlier.unwrap_or(execution_id),
+                duplicate: true,
+            };
+        }
+
+        self.notify_execution(&execution_id, &result.id, ExecutionState::Started).await;
+        self.analytics().record_execution(&result);
+
+        let engine = Arc::clone(self);
+        let task_id = execution_id.clone();
+        let result_id = result.id.clone();
+        let progress = ExecutionProgress::new(&execution_id, &result.id, self.execution_listener.read().await.clone());
+
+        // Hold the map while spawning so the task can't finish before it is tracked
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+        let handle = tokio::spawn(async move {
+            let outcome = progress.scope(engine.run_result(&result)).await;
+            if outcome.is_ok() && engine.is_trusted(&result) {
+                engine.remember_execution(&result).await;
+                engine.track_file_interaction(&result, Self::executed_interaction(&result)).await;
+            }
+
+            // A cancelled execution was already removed and reported
+            let tracked = engine
+                .in_flight
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&task_id)
+                .is_some();
+            if !tracked {
+                return;
+            }
+
+            let state = match outcome {
+                Ok(()) => ExecutionState::Succeeded,
+                Err(e) => ExecutionState::Failed { error: e.to_string() },
+            };
+            engine.notify_execution(&task_id, &result.id, state).await;
+        });
+        in_flight.insert(execution_id.clone(), (result_id, handle));
+
+        Execution {
+            id: execution_id,
+            duplicate: false,
+        }
+    }
+
+    /// Builds the id reported in execution status events
+    fn next_execution_id(&self) -> String {
+        format!("exec-{}", self.next_execution_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    /// Applies one action to several file results and waits for all of them
+    ///
+    /// The whole batch is rejected up front if it is empty, larger than
+    /// `MAX_BATCH_SIZE` or contains non-file results. Items run one after
+    /// another with a short stagger and each reports its own execution status.
+    /// Items the same action ran on moments ago are skipped and listed as
+    /// suppressed.
+    pub async fn execute_results_batch(
+        &self,
+        results: &[SearchResult],
+        action: BatchAction,
+    ) -> Result<BatchSummary> {
+        Self::validate_batch(results)?;
+        for result in results {
+            self.verify_result(result)?;
+        }
+
+        let discriminator = format!("batch:{:?}", action);
+        let (results, suppressed): (Vec<SearchResult>, Vec<SearchResult>) =
+            results.iter().cloned().partition(|result| {
+                self.duplicates.admit(&result.id, &discriminator, result.requires_confirmation(), None)
+                    == Admission::Admitted
+            });
+        let mut summary = BatchSummary {
+            suppressed: suppressed.into_iter().map(|result| result.id).collect(),
+            ..BatchSummary::default()
+        };
+        if results.is_empty() {
+            info!("Ignoring repeated batch of {} results: {:?}", summary.suppressed.len(), action);
+            return Ok(summary);
+        }
+        let results = results.as_slice();
+        info!("Executing batch of {} results: {:?}", results.len(), action);
+
+        let execution_ids: Vec<String> = results.iter().map(|_| self.next_execution_id()).collect();
+        for (result, execution_id) in results.iter().zip(&execution_ids) {
+            self.notify_execution(execution_id, &result.id, ExecutionState::Started).await;
+        }
+
+        let outcomes: Vec<Result<()>> = match action {
+            BatchAction::CopyPaths => {
+                // One clipboard write covers every item
+                let outcome = set_clipboard_text(&Self::join_result_paths(results)).await;
+                results
+                    .iter()
+                    .map(|_| match &outcome {
+                        Ok(()) => Ok(()),
+                        Err(e) => Err(LauncherError::ExecutionError(e.to_string())),
+                    })
+                    .collect()
+            }
+            BatchAction::Open | BatchAction::RevealInExplorer => {
+                let mut outcomes = Vec::with_capacity(results.len());
+                for (index, result) in results.iter().enumerate() {
+                    if index > 0 {
+                        tokio::time::sleep(BATCH_STAGGER).await;
+                    }
+
+                    let outcome = if action == BatchAction::Open {
+                        self.execute_result_blocking(result).await
+                    } else {
+                        self.reveal_in_file_manager(result)
+                    };
+                    outcomes.push(outcome);
+                }
+                outcomes
+            }
+        };
+
+        // Opened files were tracked as they were executed
+        let interaction = match action {
+            BatchAction::Open => None,
+            BatchAction::CopyPaths => Some(InteractionKind::Copied),
+            BatchAction::RevealInExplorer => Some(InteractionKind::Revealed),
+        };
+
+        for ((result, execution_id), outcome) in results.iter().zip(&execution_ids).zip(outcomes) {
+            let state = match outcome {
+                Ok(()) => {
+                    if let Some(kind) = interaction {
+                        self.track_file_interaction(result, kind).await;
+                    }
+                    summary.succeeded.push(result.id.clone());
+                    ExecutionState::Succeeded
+                }
+                Err(e) => {
+                    summary.failed.push(BatchFailure {
+                        id: result.id.clone(),
+                        error: e.to_string(),
+                    });
+                    ExecutionState::Failed { error: e.to_string() }
+                }
+            };
+            self.notify_execution(execution_id, &result.id, state).await;
+        }
+
+        info!(
+            "Batch finished: {} succeeded, {} failed",
+            summary.succeeded.len(),
+            summary.failed.len()
+        );
+        Ok(summary)
+    }
+
+    /// Rejects empty or oversized batches and batches with non-file results
+    pub fn validate_batch(results: &[SearchResult]) -> Result<()> {
+        if results.is_empty() {
+            return Err(LauncherError::ExecutionError("The batch is empty".to_string()));
+        }
+
+        if results.len() > MAX_BATCH_SIZE {
+            return Err(LauncherError::ExecutionError(format!(
+                "A batch can hold at most {} results, got {}",
+                MAX_BATCH_SIZE,
+                results.len()
+            )));
+        }
+
+        let invalid: Vec<&str> = results
+            .iter()
+            .filter(|r| {
+                !matches!(r.result_type, ResultType::File | ResultType::RecentFile)
+                    || r.file_path().is_none()
+            })
+            .map(|r| r.id.as_str())
             .collect();
+        if !invalid.is_empty() {
+            return Err(LauncherError::ExecutionError(format!(
+                "Batch actions only apply to file results; not files: {}",
+                invalid.join(", ")
+            )));
+        }
 
-        info!("Search completed: {} total results", final_results.len());
-        
-        // Cache the results
-        self.cache.put(sanitized_query, final_results.clone()).await;
-        
-        final_results
+        Ok(())
+    }
+
+    /// Joins the file paths of results, one per line
+    pub fn join_result_paths(results: &[SearchResult]) -> String {
+        results
+            .iter()
+            .filter_map(|r| r.file_path())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Opens the file manager on the folder holding the result's file, with the file selected
+    fn reveal_in_file_manager(&self, result: &SearchResult) -> Result<()> {
+        self.verify_result(result)?;
+        let path = Self::required_file_path(result)?;
+        info!("Revealing in file manager: {}", path);
+
+        self.launchers().file_manager().reveal(path).spawn()
+    }
+
+    /// Opens the terminal in the result's folder, or in the folder holding its file
+    pub fn open_in_terminal(&self, result: &SearchResult) -> Result<()> {
+        self.verify_result(result)?;
+        let path = Self::required_file_path(result)?;
+        let folder = if std::path::Path::new(path).is_dir() {
+            path
+        } else {
+            std::path::Path::new(path).parent().and_then(|parent| parent.to_str()).unwrap_or(path)
+        };
+        info!("Opening terminal in: {}", folder);
+
+        self.launchers().terminal().open_in(folder)?.spawn()
+    }
+
+    fn required_file_path(result: &SearchResult) -> Result<&str> {
+        result.file_path().ok_or_else(|| {
+            LauncherError::ExecutionError(format!("Result '{}' has no file path", result.title))
+        })
+    }
+
+    /// Stops waiting for a running execution and reports it as cancelled
+    ///
+    /// Work already handed to the OS (e.g. a launch in progress) may still finish.
+    pub async fn cancel_execution(&self, execution_id: &str) -> Result<()> {
+        let entry = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(execution_id);
+
+        match entry {
+            Some((result_id, handle)) => {
+                handle.abort();
+                info!("Cancelled execution {}", execution_id);
+                self.notify_execution(execution_id, &result_id, ExecutionState::Cancelled).await;
+                Ok(())
+            }
+            None => Err(LauncherError::NotFound(format!("Execution {}", execution_id))),
+        }
+    }
+
+    /// Returns the number of executions still running
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Reports an execution status change to the listener, if any
+    async fn notify_execution(&self, execution_id: &str, result_id: &str, state: ExecutionState) {
+        debug!("Execution {} for '{}': {:?}", execution_id, result_id, state);
+
+        let listener = self.execution_listener.read().await.clone();
+        if let Some(listener) = listener {
+            listener(&ExecutionStatus {
+                execution_id: execution_id.to_string(),
+                result_id: result_id.to_string(),
+                state,
+            });
+        }
+    }
+
+    /// Executes a result and waits for it, tracking file access on success
+    pub async fn execute_result_blocking(&self, result: &SearchResult) -> Result<()> {
+        let outcome = self.run_result(result).await;
+
+        if outcome.is_ok() && self.is_trusted(result) {
+            self.track_file_interaction(result, Self::executed_interaction(result)).await;
+        }
+
+        outcome
     }
 
-    /// Executes the action associated with a search result
-    pub async fn execute_result(&self, result: &SearchResult) -> Result<()> {
+    /// Runs the action associated with a search result
+    ///
+    /// Clipboard history items are pasted into the previous window afterwards
+    /// when auto-paste is enabled; a failed paste still counts as success
+    /// since the item was copied.
+    async fn run_result(&self, result: &SearchResult) -> Result<()> {
+        // A result the frontend rebuilt gets its own action only, never a provider's
+        if !self.is_trusted(result) {
+            return self.execute_default_action(&result.action).await;
+        }
+
+        self.dispatch_result(result).await?;
+
+        if matches!(result.result_type, ResultType::Clipboard)
+            && matches!(result.action, ResultAction::CopyToClipboard { .. })
+        {
+            let auto_paste = self.auto_paste.read().await.clone();
+            if let Some(auto_paste) = auto_paste {
+                let outcome = auto_paste.paste().await;
+                debug!("Auto-paste for '{}': {:?}", result.id, outcome);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Hands a result to the first provider that can execute it
+    async fn dispatch_result(&self, result: &SearchResult) -> Result<()> {
         info!("Executing result: {} (type: {:?})", result.title, result.result_type);
 
+        // File groups are built by the engine, so no provider can run them
+        if matches!(result.action, ResultAction::ExpandGroup { .. }) {
+            return self.execute_default_action(&result.action).await;
+        }
+
+        // Folders open in the file manager from settings rather than the provider's default
+        if let ResultAction::OpenFile { path } = &result.action {
+            if std::path::Path::new(path).is_dir() {
+                return self.launchers().file_manager().open_folder(path).spawn();
+            }
+        }
+
         // Find the provider that can handle this result type
-        let providers = self.providers.read().await;
+        let providers = self.providers();
         
         for provider in providers.iter() {
             if !provider.is_enabled() {
@@ -169,10 +1845,6 @@ impl SearchEngine {
             match provider.execute(result).await {
                 Ok(()) => {
                     info!("Result executed successfully by provider '{}'", provider.name());
-                    
-                    // Track file access if this is a file result
-                    self.track_file_access_if_needed(result).await;
-                    
                     return Ok(());
                 }
                 Err(e) => {
@@ -183,93 +1855,402 @@ impl SearchEngine {
         }
 
         // If no provider could execute, try default execution based on action type
-        let execution_result = Self::execute_default_action(&result.action).await;
-        
-        // Track file access if execution was successful
-        if execution_result.is_ok() {
-            self.track_file_access_if_needed(result).await;
+        self.execute_default_action(&result.action).await
+    }
+
+    /// Moves the file behind a File or RecentFile result to the Recycle Bin
+    ///
+    /// Refused unless the action is enabled in settings and the user confirmed
+    /// it. On success cached results containing the path are dropped and the
+    /// file removal tracker is notified so recent files forget the entry.
+    pub async fn recycle_file_result(&self, result: &SearchResult, confirmed: bool) -> Result<()> {
+        self.verify_result(result)?;
+
+        if !self.file_delete_enabled() {
+            return Err(LauncherError::ExecutionError(
+                "The Move to Recycle Bin action is disabled in settings".to_string(),
+            ));
         }
-        
-        execution_result
+
+        if !matches!(result.result_type, ResultType::File | ResultType::RecentFile) {
+            return Err(LauncherError::ExecutionError(
+                "Only file results can be moved to the Recycle Bin".to_string(),
+            ));
+        }
+
+        if !confirmed {
+            return Err(LauncherError::ExecutionError(
+                "Moving a file to the Recycle Bin requires confirmation".to_string(),
+            ));
+        }
+
+        let path = result.file_path().map(str::to_string).ok_or_else(|| {
+            LauncherError::ExecutionError(format!("Result '{}' has no file path", result.title))
+        })?;
+
+        info!("Moving to Recycle Bin: {}", path);
+
+        let deleter = Arc::clone(&*self.file_deleter.read().await);
+        let path_clone = path.clone();
+        tokio::task::spawn_blocking(move || deleter(&path_clone))
+            .await
+            .map_err(|e| {
+                LauncherError::ExecutionError(format!("Failed to spawn recycle task: {}", e))
+            })??;
+
+        let invalidated = self.cache.invalidate_path(&path).await;
+        debug!("Dropped {} cached queries after recycling {}", invalidated, path);
+
+        let tracker = self.file_removal_tracker.read().await;
+        if let Some(track_fn) = tracker.as_ref() {
+            track_fn(&path);
+        }
+
+        Ok(())
     }
 
-    /// Tracks file access in RecentFilesProvider if the result is a file
-    async fn track_file_access_if_needed(&self, result: &SearchResult) {
-        // Only track file results
-        if result.result_type != ResultType::File {
+    /// Lists everything that can be done with a result, for its actions menu
+    ///
+    /// The primary action comes first, then the provider's own secondary
+    /// actions, then the engine's: file actions, copying, pinning, "Not
+    /// useful" and, last, the Recycle Bin when it's enabled.
+    pub fn result_actions(&self, result: &SearchResult) -> Vec<ActionDescriptor> {
+        let engine_ids = [
+            REVEAL_ACTION_ID,
+            OPEN_TERMINAL_ACTION_ID,
+            COPY_TITLE_ACTION_ID,
+            deeplink::COPY_LINK_ACTION_ID,
+            PIN_ACTION_ID,
+            UNPIN_ACTION_ID,
+            NOT_USEFUL_ACTION_ID,
+            RECYCLE_ACTION_ID,
+        ];
+
+        let mut actions = vec![ActionDescriptor::primary(result)];
+
+        let provider_actions = result
+            .metadata
+            .get(SECONDARY_ACTIONS_KEY)
+            .and_then(|actions| actions.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(ActionDescriptor::from_secondary)
+            .filter(|action| action.id != EXECUTE_ACTION_ID && !engine_ids.contains(&action.id.as_str()));
+        actions.extend(provider_actions);
+
+        if is_file_result(result) {
+            actions.push(ActionDescriptor::new(REVEAL_ACTION_ID, "Show in file manager", "FolderSearch"));
+            actions.push(ActionDescriptor::new(OPEN_TERMINAL_ACTION_ID, "Open in terminal", "Terminal"));
+        }
+        actions.push(ActionDescriptor::new(COPY_TITLE_ACTION_ID, "Copy title", "Copy"));
+        actions.push(ActionDescriptor::new(deeplink::COPY_LINK_ACTION_ID, "Copy link", "Link"));
+        if self.favorites().is_ok() {
+            actions.push(if result.is_pinned() {
+                ActionDescriptor::new(UNPIN_ACTION_ID, "Unpin", "PinOff")
+            } else {
+                ActionDescriptor::new(PIN_ACTION_ID, "Pin", "Pin")
+            });
+        }
+        if !result.is_pinned() {
+            actions.push(ActionDescriptor::new(NOT_USEFUL_ACTION_ID, "Not useful", "ThumbsDown"));
+        }
+        if self.file_delete_enabled() && is_file_result(result) {
+            actions.push(ActionDescriptor::new(RECYCLE_ACTION_ID, "Move to Recycle Bin", "Trash2").confirmed());
+        }
+
+        actions
+    }
+
+    /// Runs an action from `result_actions` by id
+    ///
+    /// Each action goes through the same path as its own command, so the
+    /// menu and the keyboard shortcuts behave alike. `query` is the search
+    /// the result came from, used by "Not useful"; `confirmed` is required
+    /// for actions that ask first. Returns the execution id when the primary
+    /// action started an execution.
+    pub async fn execute_result_action(
+        self: &Arc<Self>,
+        result: &SearchResult,
+        action_id: &str,
+        query: &str,
+        confirmed: bool,
+    ) -> Result<Option<String>> {
+        debug!("Running action '{}' on '{}'", action_id, result.id);
+
+        // Only a plain execute may run an unsigned result: every other action reads its metadata
+        if action_id == EXECUTE_ACTION_ID {
+            self.verify_execution(result)?;
+        } else {
+            self.verify_result(result)?;
+        }
+
+        if action_id != EXECUTE_ACTION_ID {
+            // A confirmed run follows the unconfirmed one asking for it, so they are told apart
+            let discriminator = if confirmed { format!("{}:confirmed", action_id) } else { action_id.to_string() };
+            if let Admission::Duplicate { .. } = self.duplicates.admit(&result.id, &discriminator, confirmed, None) {
+                info!("Ignoring repeated '{}' on '{}'", action_id, result.id);
+                return Ok(None);
+            }
+        }
+
+        match action_id {
+            EXECUTE_ACTION_ID => return Ok(Some(self.execute_result(result.clone()).await.id)),
+            REVEAL_ACTION_ID => {
+                self.reveal_in_file_manager(result)?;
+                self.track_file_interaction(result, InteractionKind::Revealed).await;
+            }
+            OPEN_TERMINAL_ACTION_ID => self.open_in_terminal(result)?,
+            COPY_TITLE_ACTION_ID => set_clipboard_text(&result.title).await?,
+            deeplink::COPY_LINK_ACTION_ID => {
+                self.copy_result_link(result).await?;
+            }
+            PIN_ACTION_ID => {
+                self.pin_result(result.clone(), None).await?;
+            }
+            UNPIN_ACTION_ID => {
+                self.unpin_result(&result.id).await?;
+            }
+            NOT_USEFUL_ACTION_ID => {
+                self.demote_result(query, &result.id).await?;
+            }
+            RECYCLE_ACTION_ID => self.recycle_file_result(result, confirmed).await?,
+            blob::SEARCH_FIRST_LINE_ACTION_ID => {
+                let first_line = result
+                    .metadata
+                    .get(blob::FIRST_LINE_KEY)
+                    .and_then(|line| line.as_str())
+                    .map(Self::sanitize_query)
+                    .filter(|line| !line.is_empty())
+                    .ok_or_else(|| LauncherError::ExecutionError("The pasted text has no first line".to_string()))?;
+                self.execute_default_action(&ResultAction::WebSearch { query: first_line }).await?;
+            }
+            _ => self.execute_provider_action(result, action_id).await?,
+        }
+        Ok(None)
+    }
+
+    /// Hands a secondary action to the first provider that offers it
+    async fn execute_provider_action(&self, result: &SearchResult, action_id: &str) -> Result<()> {
+        for provider in self.providers().iter().filter(|provider| provider.is_enabled()) {
+            match provider.execute_action(result, action_id).await {
+                Err(LauncherError::NotFound(_)) => continue,
+                outcome => return outcome,
+            }
+        }
+
+        Err(LauncherError::NotFound(format!("Action '{}' for '{}'", action_id, result.id)))
+    }
+
+    /// Adds the engine's secondary actions after those the providers set:
+    /// the Recycle Bin action first on file results, then "Copy link" and
+    /// "Not useful" last on all
+    fn attach_secondary_actions(&self, results: &mut [SearchResult]) {
+        let recycle_enabled = self.file_delete_enabled();
+        for result in results {
+            if recycle_enabled {
+                Self::attach_recycle_action(result);
+            }
+            let actions = Self::secondary_actions(result);
+            actions.push(deeplink::copy_link_action());
+            actions.push(not_useful_action());
+        }
+    }
+
+    /// Adds the Recycle Bin secondary action to file results
+    fn attach_recycle_action(result: &mut SearchResult) {
+        if !matches!(result.result_type, ResultType::File | ResultType::RecentFile) {
             return;
         }
 
-        // Extract file path from the result
-        let file_path = match &result.action {
-            ResultAction::OpenFile { path } => Some(path.as_str()),
-            _ => result.metadata.get("path").and_then(|v| v.as_str()),
-        };
+        Self::secondary_actions(result).insert(
+            0,
+            serde_json::json!({
+                "id": RECYCLE_ACTION_ID,
+                "title": "Move to Recycle Bin",
+                "requires_confirmation": true,
+            }),
+        );
+    }
+
+    /// The result's secondary actions, creating the list if it has none
+    fn secondary_actions(result: &mut SearchResult) -> &mut Vec<serde_json::Value> {
+        let actions = result
+            .metadata
+            .entry(SECONDARY_ACTIONS_KEY.to_string())
+            .or_insert_with(|| serde_json::json!([]));
+        if !actions.is_array() {
+            *actions = serde_json::json!([]);
+        }
+        actions.as_array_mut().expect("secondary actions were just made an array")
+    }
+
+    /// How executing a result's primary action used its file: copying the
+    /// file's own path counts as copied, anything else as opened
+    fn executed_interaction(result: &SearchResult) -> InteractionKind {
+        match &result.action {
+            ResultAction::CopyToClipboard { content } if Some(content.as_str()) == result.file_path() => {
+                InteractionKind::Copied
+            }
+            _ => InteractionKind::Opened,
+        }
+    }
+
+    /// Reports a file or recent file result's file to the file access tracker
+    async fn track_file_interaction(&self, result: &SearchResult, kind: InteractionKind) {
+        // Only track file results
+        if !matches!(result.result_type, ResultType::File | ResultType::RecentFile) {
+            return;
+        }
 
-        if let Some(path_str) = file_path {
+        if let Some(path_str) = result.file_path() {
             // Call the file access tracker if registered
             let tracker = self.file_access_tracker.read().await;
             if let Some(track_fn) = tracker.as_ref() {
-                debug!("Tracking file access for: {}", path_str);
-                track_fn(path_str);
+                debug!("Tracking file access ({}) for: {}", kind.as_str(), path_str);
+                track_fn(&FileAccess {
+                    path: path_str.to_string(),
+                    kind,
+                    result_type: result.result_type,
+                });
             }
         }
     }
 
     /// Sanitizes user query to prevent issues
+    ///
+    /// The result is NFC-normalized, so composed and decomposed input (as
+    /// some IMEs and dead keys send) share one cache entry and match alike.
     pub fn sanitize_query(query: &str) -> String {
-        query
-            .trim()
-            .chars()
-            .filter(|c| !c.is_control())
-            .take(256) // Limit query length
-            .collect()
+        let chars = query.trim().chars().filter(|c| !c.is_control());
+
+        // Limit query length; ASCII is already in NFC
+        if query.is_ascii() {
+            chars.take(256).collect()
+        } else {
+            chars.nfc().take(256).collect()
+        }
     }
 
     /// Ranks and sorts results by relevance
-    pub fn rank_results(mut results: Vec<SearchResult>, query: &str) -> Vec<SearchResult> {
-        let query_lower = query.to_lowercase();
-        
-        // Boost scores based on various factors
+    pub fn rank_results(results: Vec<SearchResult>, query: &str) -> Vec<SearchResult> {
+        Self::rank(results, &QueryContext::from(query), false, None, &RecentBoosts::default())
+    }
+
+    /// Ranks results, attaching a `score_breakdown` entry to each when `explain` is set
+    pub fn rank_results_explained(results: Vec<SearchResult>, query: &str) -> Vec<SearchResult> {
+        Self::rank(results, &QueryContext::from(query), true, None, &RecentBoosts::default())
+    }
+
+    fn rank(
+        mut results: Vec<SearchResult>,
+        query: &QueryContext,
+        explain: bool,
+        feedback: Option<&ResultFeedback>,
+        recent: &RecentBoosts,
+    ) -> Vec<SearchResult> {
+        // Demotions are read from memory; the common case of none skips the lookups
+        let feedback = feedback
+            .filter(|feedback| !feedback.is_empty())
+            .map(|feedback| feedback.view(&query.query));
+        if let Some(view) = &feedback {
+            results.retain(|result| !Self::demotion(view, result).is_some_and(|penalty| penalty.suppressed));
+        }
+
+        // Boost scores based on title and word matches and recent executions; sorted listings keep their provider
+        // scores, which strictly decrease, so the stable sort below keeps their order
         for result in &mut results {
-            let title_lower = result.title.to_lowercase();
-            
-            // Exact match bonus
-            if title_lower == query_lower {
-                result.score += 100.0;
-            }
-            
-            // Starts with query bonus
-            if title_lower.starts_with(&query_lower) {
-                result.score += 50.0;
-            }
+            let demotion = feedback.as_ref().and_then(|view| Self::demotion(view, result));
+            let breakdown = ScoreBreakdown::for_result(result, result.score, query)
+                .with_recent_execution(recent.for_result(result))
+                .with_feedback(demotion);
+            result.score = breakdown.total;
             
-            // Contains query bonus
-            if title_lower.contains(&query_lower) {
-                result.score += 25.0;
+            if explain {
+                if let Ok(value) = serde_json::to_value(breakdown) {
+                    result.metadata.insert(SCORE_BREAKDOWN_KEY.to_string(), value);
+                }
             }
         }
 
-        // Sort by score (highest first)
+        // Sort pinned results first, then by score (highest first); ties fall
+        // back to the id so results don't depend on which provider answered first
         results.sort_by(|a, b| {
-            b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal)
+            b.is_pinned()
+                .cmp(&a.is_pinned())
+                .then_with(|| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| a.id.cmp(&b.id))
         });
 
         results
     }
 
+    /// Demotions of a result for the viewed query; pinned results are never demoted
+    fn demotion(view: &FeedbackView<'_>, result: &SearchResult) -> Option<FeedbackPenalty> {
+        if result.is_pinned() {
+            return None;
+        }
+        view.penalty(&result.id)
+    }
+
+    /// Explains the score of one result from the results for `query`
+    ///
+    /// Uses the cached results when available, otherwise runs the search again.
+    /// The provider score is recovered by removing the title boosts, the
+    /// recent execution boost and any demotion penalty, so this works whether or not debug mode was on when
+    /// the results were ranked.
+    pub async fn explain_result(&self, query: &str, result_id: &str) -> Result<ScoreBreakdown> {
+        let sanitized_query = Self::sanitize_query(query);
+
+        let results = match self.cache.get(&sanitized_query).await {
+            Some(results) => results,
+            None => self.search(&sanitized_query).await,
+        };
+
+        let result = results
+            .iter()
+            .find(|result| result.id == result_id)
+            .ok_or_else(|| {
+                LauncherError::NotFound(format!("Result '{}' for query '{}'", result_id, sanitized_query))
+            })?;
+
+        let context = self.query_context(&sanitized_query);
+        let feedback = self.feedback();
+        let demotion = Self::demotion(&feedback.view(&sanitized_query), result);
+        let recent = self.recent.boosts(Instant::now()).for_result(result);
+        let adjustments = ScoreBreakdown::for_result(result, 0.0, &context)
+            .with_recent_execution(recent)
+            .with_feedback(demotion);
+
+        Ok(ScoreBreakdown::for_result(result, result.score - adjustments.total, &context)
+            .with_recent_execution(recent)
+            .with_feedback(demotion))
+    }
+
+    /// Looks up a result the frontend is showing by its id
+    ///
+    /// Searches the paged searches first, which hold full result lists, then
+    /// the result cache. Results of searches that are neither kept nor
+    /// cached can't be found.
+    pub async fn find_result(&self, result_id: &str) -> Result<SearchResult> {
+        if let Some(result) = self.sessions.find(result_id) {
+            return Ok(result);
+        }
+        self.cache
+            .find(result_id)
+            .await
+            .ok_or_else(|| LauncherError::NotFound(format!("Result '{}'", result_id)))
+    }
+
     /// Default action execution when no provider handles it
-    async fn execute_default_action(action: &ResultAction) -> Result<()> {
+    async fn execute_default_action(&self, action: &ResultAction) -> Result<()> {
         match action {
             ResultAction::OpenFile { path } => {
                 info!("Opening file: {}", path);
                 #[cfg(target_os = "windows")]
                 {
-                    std::process::Command::new("cmd")
-                        .args(["/C", "start", "", path])
-                        .spawn()
-                        .map_err(|e| LauncherError::ExecutionError(format!("Failed to open file: {}", e)))?;
-                    Ok(())
+                    let mut command = std::process::Command::new("cmd");
+                    command.args(["/C", "start", "", path]);
+                    process::start("open_file", command, Wait::UpTo(process::INSTANT_EXIT_TIMEOUT)).await
                 }
                 #[cfg(not(target_os = "windows"))]
                 {
@@ -282,10 +2263,7 @@ impl SearchEngine {
                 info!("Launching application: {}", path);
                 #[cfg(target_os = "windows")]
                 {
-                    std::process::Command::new(path)
-                        .spawn()
-                        .map_err(|e| LauncherError::ExecutionError(format!("Failed to launch app: {}", e)))?;
-                    Ok(())
+                    process::start("launch_app", std::process::Command::new(path), Wait::Detached).await
                 }
                 #[cfg(not(target_os = "windows"))]
                 {
@@ -296,11 +2274,9 @@ impl SearchEngine {
             }
             ResultAction::ExecuteCommand { command, args } => {
                 info!("Executing command: {} {:?}", command, args);
-                std::process::Command::new(command)
-                    .args(args)
-                    .spawn()
-                    .map_err(|e| LauncherError::ExecutionError(format!("Failed to execute command: {}", e)))?;
-                Ok(())
+                let mut program = std::process::Command::new(command);
+                program.args(args);
+                process::start("command", program, Wait::Detached).await
             }
             ResultAction::CopyToClipboard { content } => {
                 info!("Copying to clipboard: {} chars", content.len());
@@ -313,11 +2289,9 @@ impl SearchEngine {
                 info!("Opening URL: {}", url);
                 #[cfg(target_os = "windows")]
                 {
-                    std::process::Command::new("cmd")
-                        .args(["/C", "start", "", url])
-                        .spawn()
-                        .map_err(|e| LauncherError::ExecutionError(format!("Failed to open URL: {}", e)))?;
-                    Ok(())
+                    let mut command = std::process::Command::new("cmd");
+                    command.args(["/C", "start", "", url]);
+                    process::start("open_url", command, Wait::UpTo(process::INSTANT_EXIT_TIMEOUT)).await
                 }
                 #[cfg(not(target_os = "windows"))]
                 {
@@ -326,18 +2300,29 @@ impl SearchEngine {
                     ))
                 }
             }
+            ResultAction::SetQuery { query } => {
+                // Handled by the search box, which replaces its query instead of executing
+                Err(LauncherError::ExecutionError(format!(
+                    "Set query action for '{}' must be applied by the search box",
+                    query
+                )))
+            }
+            ResultAction::ExpandGroup { group_id } => {
+                // Handled by the search box, which lists the group's members
+                Err(LauncherError::ExecutionError(format!(
+                    "File group '{}' must be expanded by the search box",
+                    group_id
+                )))
+            }
             ResultAction::WebSearch { query } => {
                 info!("Performing web search: {}", query);
                 
                 #[cfg(target_os = "windows")]
                 {
-                    let encoded_query = urlencoding::encode(query);
-                    let search_url = format!("https://www.google.com/search?q={}", encoded_query);
-                    std::process::Command::new("cmd")
-                        .args(["/C", "start", "", &search_url])
-                        .spawn()
-                        .map_err(|e| LauncherError::ExecutionError(format!("Failed to open web search: {}", e)))?;
-                    Ok(())
+                    let search_url = self.web_search_url(query);
+                    let mut command = std::process::Command::new("cmd");
+                    command.args(["/C", "start", "", &search_url]);
+                    process::start("web_search", command, Wait::UpTo(process::INSTANT_EXIT_TIMEOUT)).await
                 }
                 #[cfg(not(target_os = "windows"))]
                 {
@@ -350,24 +2335,125 @@ impl SearchEngine {
         }
     }
 
+    /// URL the default action opens for a web search
+    pub fn web_search_url(&self, query: &str) -> String {
+        self.web_search_engine.get().construct_search_url(query)
+    }
+
     /// Returns the number of registered providers
     pub async fn provider_count(&self) -> usize {
-        self.providers.read().await.len()
+        self.providers().len()
     }
 
     /// Returns the names of all registered providers
     pub async fn provider_names(&self) -> Vec<String> {
-        self.providers
-            .read()
-            .await
+        self.providers()
             .iter()
             .map(|p| p.name().to_string())
             .collect()
     }
 
+    /// Returns the health of every registered provider
+    pub async fn provider_health(&self) -> Vec<ProviderStatus> {
+        let slow_peeks = self.slow_peeks.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        self.providers()
+            .iter()
+            .map(|p| {
+                let health = p.health();
+                ProviderStatus {
+                    provider: p.name().to_string(),
+                    message: health.to_string(),
+                    health,
+                    slow_peeks: slow_peeks.get(p.name()).copied().unwrap_or(0),
+                }
+            })
+            .collect()
+    }
+
+    /// Records how long a provider took to start, for `diagnostics`
+    ///
+    /// Replaces an earlier record of the same provider.
+    pub fn record_provider_startup(&self, startup: ProviderStartup) {
+        let mut startups = self.provider_startup.lock().unwrap_or_else(|e| e.into_inner());
+        startups.retain(|existing| existing.provider != startup.provider);
+        startups.push(startup);
+    }
+
+    /// Asks one provider directly, without the cache, ranking or the other providers
+    ///
+    /// Used by the self-test to see whether a provider answers at all.
+    pub async fn search_provider(&self, name: &str, query: &str) -> Result<Vec<SearchResult>> {
+        let provider = self
+            .providers()
+            .iter()
+            .find(|p| p.name() == name)
+            .cloned()
+            .ok_or_else(|| LauncherError::NotFound(format!("Provider '{}' not found", name)))?;
+
+        let context = self.query_context(&Self::sanitize_query(query));
+        provider.search(&context).await
+    }
+
+    /// Reports cache sizes, the result cache hit rate, the process working
+    /// set, the processes started by executing results, the show latency, the
+    /// results boosted for a recent execution and how long providers took to start
+    pub async fn diagnostics(&self) -> Diagnostics {
+        let mut caches = Vec::new();
+        for provider in self.providers().iter() {
+            caches.extend(provider.cache_stats().await);
+        }
+
+        Diagnostics {
+            working_set_bytes: process_working_set(),
+            result_cache: self.cache.stats().await,
+            result_cache_hit_rate: self.cache.hit_rate(),
+            caches,
+            processes: process::tracker().launched(),
+            show_latency: show_latency().stats(),
+            recent_executions: self.recent.snapshot(Instant::now()),
+            provider_startup: self.provider_startup.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        }
+    }
+
+    /// Drops cold cache entries everywhere they can be rebuilt on demand
+    ///
+    /// Returns the number of entries removed.
+    pub async fn trim_caches(&self) -> usize {
+        let mut removed = self.cache.trim().await;
+        if self.suggestions.write().await.take().is_some() {
+            removed += 1;
+        }
+        for provider in self.providers().iter() {
+            removed += provider.trim_caches().await;
+        }
+
+        info!("Trimmed {} cache entries", removed);
+        removed
+    }
+
+    /// Lets every enabled provider refresh state that went stale while the
+    /// system slept or the session was locked
+    ///
+    /// Cached results may predate the resume, so they are dropped too.
+    pub async fn refresh_after_resume(&self) {
+        self.invalidate_cache().await;
+        for provider in self.providers().iter().filter(|provider| provider.is_enabled()) {
+            debug!("Refreshing '{}' after resume", provider.name());
+            provider.refresh_after_resume().await;
+        }
+    }
+
+    /// Drops cached results, keeping paged searches and the suggestion
+    /// dictionary, e.g. once saved searches changed
+    pub async fn invalidate_results(&self) {
+        self.cache.invalidate_all().await;
+    }
+
     /// Invalidates the search result cache
     pub async fn invalidate_cache(&self) {
         self.cache.invalidate_all().await;
+        self.sessions.clear();
+        *self.suggestions.write().await = None;
         info!("Search cache invalidated");
     }
 }
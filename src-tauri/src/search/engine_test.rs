@@ -2,9 +2,16 @@
 mod tests {
     use super::super::*;
     use crate::error::Result;
-    use crate::types::{ResultAction, ResultType, SearchResult};
+    use crate::search::completion::{CompletionCandidate, CompletionSource};
+    use crate::types::{
+        BatchAction, ExecutionState, ExecutionStatus, FileAccess, InteractionKind, ResultAction, ResultType,
+        SearchResponse, SearchResult, SearchSection, PRESERVE_PROVIDER_ORDER_KEY,
+    };
     use async_trait::async_trait;
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
 
     // Mock search provider for testing
     struct MockProvider {
@@ -13,6 +20,16 @@ mod tests {
         results: Vec<SearchResult>,
         enabled: bool,
         should_fail: bool,
+        search_calls: Arc<AtomicUsize>,
+        declared_types: Option<&'static [ResultType]>,
+        execute_delay: Duration,
+        search_delay: Duration,
+        suggestion_terms: Vec<String>,
+        completions: Vec<CompletionCandidate>,
+        failing_ids: Vec<String>,
+        cacheable: bool,
+        peekable: bool,
+        resume_calls: Arc<AtomicUsize>,
     }
 
     impl MockProvider {
@@ -29,6 +46,9 @@ mod tests {
                     action: ResultAction::OpenFile {
                         path: format!("/path/to/file{}", i),
                     },
+                    normalized_title: None,
+                    enrichment_pending: false,
+                    integrity: None,
                 })
                 .collect();
 
@@ -38,18 +58,91 @@ mod tests {
                 results,
                 enabled: true,
                 should_fail: false,
+                search_calls: Arc::new(AtomicUsize::new(0)),
+                declared_types: None,
+                execute_delay: Duration::ZERO,
+                search_delay: Duration::ZERO,
+                suggestion_terms: Vec::new(),
+                completions: Vec::new(),
+                failing_ids: Vec::new(),
+                cacheable: true,
+                peekable: false,
+                resume_calls: Arc::new(AtomicUsize::new(0)),
             }
         }
 
+        /// Fails executing these results; their fallback action fails on every platform
+        fn failing_on(mut self, ids: &[&str]) -> Self {
+            self.failing_ids = ids.iter().map(|id| id.to_string()).collect();
+            for result in &mut self.results {
+                if self.failing_ids.contains(&result.id) {
+                    result.action = ResultAction::ExecuteCommand {
+                        command: "better-finder-missing-command".to_string(),
+                        args: vec![],
+                    };
+                    result.metadata.insert("path".to_string(), serde_json::json!("/path/to/missing"));
+                }
+            }
+            self
+        }
+
+        /// Makes `search` take this long before returning
+        fn with_search_delay(mut self, delay: Duration) -> Self {
+            self.search_delay = delay;
+            self
+        }
+
+        /// Offers these names for spelling suggestions
+        fn with_suggestion_terms(mut self, terms: &[&str]) -> Self {
+            self.suggestion_terms = terms.iter().map(|term| term.to_string()).collect();
+            self
+        }
+
+        /// Offers these texts as inline completions from the given source
+        fn with_completions(mut self, source: CompletionSource, texts: &[&str]) -> Self {
+            self.completions = texts
+                .iter()
+                .map(|text| CompletionCandidate::new(*text, format!("{}:{}", self.name, text), source))
+                .collect();
+            self
+        }
+
+        /// Declares the provider's result types and retypes its results to the first one
+        fn typed(mut self, types: &'static [ResultType]) -> Self {
+            for result in &mut self.results {
+                result.result_type = types[0];
+            }
+            self.declared_types = Some(types);
+            self
+        }
+
         fn with_failure(mut self) -> Self {
             self.should_fail = true;
             self
         }
 
+        /// Makes `execute` take this long before completing
+        fn with_execute_delay(mut self, delay: Duration) -> Self {
+            self.execute_delay = delay;
+            self
+        }
+
         fn disabled(mut self) -> Self {
             self.enabled = false;
             self
         }
+
+        /// Opts the provider out of the result cache
+        fn uncacheable(mut self) -> Self {
+            self.cacheable = false;
+            self
+        }
+
+        /// Makes the provider answer peeks
+        fn peekable(mut self) -> Self {
+            self.peekable = true;
+            self
+        }
     }
 
     #[async_trait]
@@ -62,7 +155,13 @@ mod tests {
             self.priority
         }
 
-        async fn search(&self, _query: &str) -> Result<Vec<SearchResult>> {
+        fn result_types(&self) -> Option<&'static [ResultType]> {
+            self.declared_types
+        }
+
+        async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
+            self.search_calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.search_delay).await;
             if self.should_fail {
                 return Err(crate::error::LauncherError::SearchError(
                     "Mock provider failure".to_string(),
@@ -71,10 +170,36 @@ mod tests {
             Ok(self.results.clone())
         }
 
-        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+        async fn execute(&self, result: &SearchResult) -> Result<()> {
+            tokio::time::sleep(self.execute_delay).await;
+            if self.should_fail || self.failing_ids.contains(&result.id) {
+                return Err(crate::error::LauncherError::ExecutionError(
+                    "Mock execute failure".to_string(),
+                ));
+            }
             Ok(())
         }
 
+        async fn suggestion_terms(&self, limit: usize) -> Vec<String> {
+            self.suggestion_terms.iter().take(limit).cloned().collect()
+        }
+
+        async fn completion_candidates(&self, _query: &str) -> Vec<CompletionCandidate> {
+            self.completions.clone()
+        }
+
+        async fn refresh_after_resume(&self) {
+            self.resume_calls.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn cacheable(&self) -> bool {
+            self.cacheable
+        }
+
+        fn supports_peek(&self) -> bool {
+            self.peekable
+        }
+
         fn is_enabled(&self) -> bool {
             self.enabled
         }
@@ -86,12 +211,12 @@ mod tests {
         
         assert_eq!(engine.provider_count().await, 0);
 
-        let provider1 = Box::new(MockProvider::new("provider1", 50, 3));
+        let provider1 = Arc::new(MockProvider::new("provider1", 50, 3));
         engine.register_provider(provider1).await;
         
         assert_eq!(engine.provider_count().await, 1);
 
-        let provider2 = Box::new(MockProvider::new("provider2", 100, 3));
+        let provider2 = Arc::new(MockProvider::new("provider2", 100, 3));
         engine.register_provider(provider2).await;
         
         assert_eq!(engine.provider_count().await, 2);
@@ -107,9 +232,9 @@ mod tests {
         let engine = SearchEngine::new();
 
         // Register providers in reverse priority order
-        let provider_low = Box::new(MockProvider::new("low_priority", 10, 2));
-        let provider_high = Box::new(MockProvider::new("high_priority", 100, 2));
-        let provider_mid = Box::new(MockProvider::new("mid_priority", 50, 2));
+        let provider_low = Arc::new(MockProvider::new("low_priority", 10, 2));
+        let provider_high = Arc::new(MockProvider::new("high_priority", 100, 2));
+        let provider_mid = Arc::new(MockProvider::new("mid_priority", 50, 2));
 
         engine.register_provider(provider_low).await;
         engine.register_provider(provider_high).await;
@@ -127,9 +252,9 @@ mod tests {
     async fn test_parallel_search_execution() {
         let engine = SearchEngine::new();
 
-        let provider1 = Box::new(MockProvider::new("provider1", 50, 3));
-        let provider2 = Box::new(MockProvider::new("provider2", 60, 4));
-        let provider3 = Box::new(MockProvider::new("provider3", 70, 2));
+        let provider1 = Arc::new(MockProvider::new("provider1", 50, 3));
+        let provider2 = Arc::new(MockProvider::new("provider2", 60, 4));
+        let provider3 = Arc::new(MockProvider::new("provider3", 70, 2));
 
         engine.register_provider(provider1).await;
         engine.register_provider(provider2).await;
@@ -146,8 +271,8 @@ mod tests {
         let engine = SearchEngine::new();
 
         // Create providers with different result counts
-        let provider1 = Box::new(MockProvider::new("provider1", 50, 5));
-        let provider2 = Box::new(MockProvider::new("provider2", 60, 3));
+        let provider1 = Arc::new(MockProvider::new("provider1", 50, 5));
+        let provider2 = Arc::new(MockProvider::new("provider2", 60, 3));
 
         engine.register_provider(provider1).await;
         engine.register_provider(provider2).await;
@@ -163,13 +288,1155 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_pinned_results_rank_first() {
+        let mut pinned = SearchResult {
+            id: "pin:file".to_string(),
+            title: "Unrelated title".to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type: ResultType::File,
+            score: 1.0,
+            metadata: HashMap::new(),
+            action: ResultAction::OpenFile {
+                path: "C:\\pinned.txt".to_string(),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        };
+        pinned.metadata.insert("pinned".to_string(), serde_json::json!(true));
+
+        let mut exact = pinned.clone();
+        exact.id = "exact".to_string();
+        exact.title = "roadmap".to_string();
+        exact.score = 500.0;
+        exact.metadata.clear();
+
+        let ranked = SearchEngine::rank_results(vec![exact, pinned], "roadmap");
+        assert_eq!(ranked[0].id, "pin:file");
+        assert_eq!(ranked[1].id, "exact");
+    }
+
+    fn titled_result(id: &str, title: &str, score: f64) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: title.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type: ResultType::File,
+            score,
+            metadata: HashMap::new(),
+            action: ResultAction::OpenFile {
+                path: format!("C:\\{}.txt", id),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_ranking_ties_break_on_id() {
+        let forward = vec![
+            titled_result("b", "Same", 10.0),
+            titled_result("a", "Same", 10.0),
+            titled_result("c", "Same", 10.0),
+        ];
+        let mut backward = forward.clone();
+        backward.reverse();
+
+        let ids = |results: Vec<SearchResult>| results.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(ids(SearchEngine::rank_results(forward, "x")), vec!["a", "b", "c"]);
+        assert_eq!(ids(SearchEngine::rank_results(backward, "x")), vec!["a", "b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_preserve_provider_order_survives_ranking() {
+        let mut sorted: Vec<SearchResult> = [("newest", "notes draft", 150.0), ("middle", "other", 149.0), ("oldest", "roadmap", 148.0)]
+            .into_iter()
+            .map(|(id, title, score)| {
+                let mut result = titled_result(id, title, score);
+                result.metadata.insert(PRESERVE_PROVIDER_ORDER_KEY.to_string(), serde_json::json!(true));
+                result
+            })
+            .collect();
+        sorted.push(titled_result("unsorted", "roadmap", 10.0));
+
+        let ranked = SearchEngine::rank_results_explained(sorted.clone(), "roadmap");
+        let ids: Vec<&str> = ranked.iter().map(|r| r.id.as_str()).collect();
+
+        // The exact title match in the sorted listing gets no boost; the unflagged one does
+        assert_eq!(ids, vec!["unsorted", "newest", "middle", "oldest"]);
+        assert_eq!(ranked[3].score, 148.0);
+        assert_eq!(ranked[3].metadata[SCORE_BREAKDOWN_KEY]["total"], 148.0);
+
+        // Without the flag the textual boosts reorder the listing
+        for result in &mut sorted {
+            result.metadata.clear();
+        }
+        let ranked = SearchEngine::rank_results(sorted, "roadmap");
+        assert_eq!(ranked[0].id, "oldest");
+    }
+
+    #[tokio::test]
+    async fn test_score_breakdown_matches_arithmetic() {
+        let results = vec![
+            titled_result("exact", "Roadmap", 10.0),
+            titled_result("prefix", "Roadmap notes", 5.0),
+            titled_result("contains", "Q3 roadmap", 7.0),
+            titled_result("none", "Budget", 40.0),
+        ];
+
+        let ranked = SearchEngine::rank_results_explained(results, "roadmap");
+        let breakdown = |id: &str| {
+            let result = ranked.iter().find(|r| r.id == id).unwrap();
+            (result.score, result.metadata.get(SCORE_BREAKDOWN_KEY).cloned().unwrap())
+        };
+
+        let (score, exact) = breakdown("exact");
+        assert_eq!(score, 185.0);
+        assert_eq!(
+            exact,
+            serde_json::json!({
+                "base": 10.0,
+                "exact_match": 100.0,
+                "prefix_match": 50.0,
+                "contains_match": 25.0,
+                "token_match": 0.0,
+                "recent_execution": 0.0,
+                "pinned": false,
+                "demotions": 0,
+                "demotion_penalty": 0.0,
+                "total": 185.0
+            })
+        );
+
+        let (score, prefix) = breakdown("prefix");
+        assert_eq!(score, 80.0);
+        assert_eq!(prefix["exact_match"], 0.0);
+        assert_eq!(prefix["prefix_match"], 50.0);
+        assert_eq!(prefix["contains_match"], 25.0);
+        assert_eq!(prefix["total"], 80.0);
+
+        let (score, contains) = breakdown("contains");
+        assert_eq!(score, 32.0);
+        assert_eq!(contains["base"], 7.0);
+        assert_eq!(contains["prefix_match"], 0.0);
+        assert_eq!(contains["contains_match"], 25.0);
+
+        let (score, none) = breakdown("none");
+        assert_eq!(score, 40.0);
+        assert_eq!(none["total"], 40.0);
+    }
+
+    #[test]
+    fn test_query_words_match_in_any_order() {
+        let results = vec![
+            titled_result("single", "Budget", 30.0),
+            titled_result("reordered", "2024 budget final", 10.0),
+            titled_result("partial", "Budget final", 10.0),
+        ];
+
+        let ranked = SearchEngine::rank_results_explained(results, "budget final 2024");
+        let ids: Vec<&str> = ranked.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["reordered", "single", "partial"]);
+        assert_eq!(ranked[0].metadata[SCORE_BREAKDOWN_KEY]["token_match"], 40.0);
+        assert_eq!(ranked[0].score, 50.0);
+
+        // Two of three words earn partial credit, short of the phrase boosts
+        let partial = &ranked[2].metadata[SCORE_BREAKDOWN_KEY];
+        assert!(partial["token_match"].as_f64().unwrap() > 0.0);
+        assert!(partial["token_match"].as_f64().unwrap() < 10.0);
+    }
+
+    #[test]
+    fn test_subtitle_words_score_below_title_words() {
+        let mut in_subtitle = titled_result("subtitle", "Invoice", 10.0);
+        in_subtitle.subtitle = "C:\\Clients\\Acme".to_string();
+        let results = vec![in_subtitle, titled_result("title", "Acme invoice", 10.0)];
+
+        let ranked = SearchEngine::rank_results_explained(results, "invoice acme");
+        assert_eq!(ranked[0].id, "title");
+        assert_eq!(ranked[0].metadata[SCORE_BREAKDOWN_KEY]["token_match"], 40.0);
+        assert_eq!(ranked[1].metadata[SCORE_BREAKDOWN_KEY]["token_match"], 20.0);
+    }
+
+    #[test]
+    fn test_stopwords_do_not_block_word_matches() {
+        let results = vec![
+            titled_result("meeting", "Meeting notes", 10.0),
+            titled_result("notes", "Notes", 20.0),
+        ];
+
+        // "of" and "the" are missing from every title but don't count against it
+        let ranked = SearchEngine::rank_results_explained(results, "notes of the meeting");
+        assert_eq!(ranked[0].id, "meeting");
+        assert_eq!(ranked[0].metadata[SCORE_BREAKDOWN_KEY]["token_match"], 40.0);
+
+        // A query that is one word once stopwords are gone gets no word bonus
+        let ranked = SearchEngine::rank_results_explained(vec![titled_result("notes", "Notes", 20.0)], "the notes");
+        assert_eq!(ranked[0].metadata[SCORE_BREAKDOWN_KEY]["token_match"], 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_score_breakdown_absent_in_normal_mode() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("provider1", 50, 3))).await;
+
+        let results = engine.search("result").await;
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| !r.metadata.contains_key(SCORE_BREAKDOWN_KEY)));
+
+        let json = serde_json::to_string(&results).unwrap();
+        assert!(!json.contains(SCORE_BREAKDOWN_KEY));
+
+        // Switching modes invalidates cached results ranked without breakdowns
+        engine.set_debug_mode(true).await;
+        let results = engine.search("result").await;
+        for result in &results {
+            let breakdown = &result.metadata[SCORE_BREAKDOWN_KEY];
+            assert_eq!(breakdown["total"], result.score);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_explain_result_recovers_breakdown() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("provider1", 50, 3))).await;
+
+        let results = engine.search("Result 0 from provider1").await;
+        let top = &results[0];
+        assert_eq!(top.id, "provider1-0");
+
+        let breakdown = engine.explain_result("Result 0 from provider1", "provider1-0").await.unwrap();
+        assert_eq!(breakdown.base, 3.0);
+        assert_eq!(breakdown.exact_match, 100.0);
+        assert_eq!(breakdown.total, top.score);
+
+        let missing = engine.explain_result("Result 0 from provider1", "nope").await;
+        assert!(matches!(missing, Err(crate::error::LauncherError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_not_useful_demotes_then_hides_result() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("provider1", 50, 3))).await;
+
+        let results = engine.search("result").await;
+        assert_eq!(results[0].id, "provider1-0");
+        let actions = results[0].metadata[SECONDARY_ACTIONS_KEY].as_array().unwrap();
+        assert_eq!(actions.last().unwrap()["id"], crate::search::feedback::NOT_USEFUL_ACTION_ID);
+
+        let penalty = engine.demote_result("result", "provider1-0").await.unwrap();
+        assert_eq!(penalty.demotions, 1);
+        assert!(!penalty.suppressed);
+        let results = engine.search("result").await;
+        assert_eq!(results.last().unwrap().id, "provider1-0");
+
+        engine.set_debug_mode(true).await;
+        let demoted = engine.search("resul").await.into_iter().find(|r| r.id == "provider1-0").unwrap();
+        assert_eq!(demoted.metadata[SCORE_BREAKDOWN_KEY]["demotions"], 1);
+        // The penalty keeps decaying between ranking and explaining
+        let breakdown = engine.explain_result("result", "provider1-0").await.unwrap();
+        assert!((breakdown.base - 3.0).abs() < 1e-3);
+        assert_eq!(breakdown.demotions, 1);
+        assert!((breakdown.total - demoted.score).abs() < 1e-3);
+
+        engine.demote_result("result", "provider1-0").await.unwrap();
+        assert!(engine.demote_result("result", "provider1-0").await.unwrap().suppressed);
+        for query in ["result", "res", "Result 0"] {
+            let results = engine.search(query).await;
+            assert!(results.iter().all(|r| r.id != "provider1-0"), "{}", query);
+        }
+
+        assert_eq!(engine.clear_feedback().await.unwrap(), 1);
+        assert_eq!(engine.search("result").await[0].id, "provider1-0");
+    }
+
+    #[tokio::test]
+    async fn test_analytics_export_has_no_queries_or_paths() {
+        use crate::search::analytics::AnalyticsRecord;
+
+        let dir = std::env::temp_dir().join("BetterFinder").join(format!("analytics_export_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let analytics = Arc::new(SearchAnalytics::at(&dir.join("analytics.db")));
+        let engine = Arc::new(SearchEngine::new());
+        engine.set_search_analytics(Arc::clone(&analytics));
+        engine.register_provider(Arc::new(MockProvider::new("Mock", 50, 3))).await;
+
+        // Off by default: searching records nothing
+        engine.search("quarterly report").await;
+        assert!(analytics.records().unwrap().is_empty());
+
+        analytics.set_enabled(true);
+        let results = engine.search("quarterly report draft").await;
+        engine.search("quarterly report draft").await;
+        engine.execute_result(results[1].clone()).await;
+
+        let records: Vec<AnalyticsRecord> = analytics.records().unwrap().into_iter().map(|r| r.record).collect();
+        assert_eq!(records.len(), 3);
+        assert!(matches!(
+            &records[0],
+            AnalyticsRecord::Search { query_length: 22, result_count: 3, cached: false, providers }
+                if providers.len() == 1 && providers[0].result_count == 3
+        ));
+        assert!(matches!(&records[1], AnalyticsRecord::Search { cached: true, .. }));
+        assert!(matches!(
+            &records[2],
+            AnalyticsRecord::Execution { rank: Some(1), result_type: ResultType::File, web_fallback: false }
+        ));
+
+        let export = dir.join("analytics.json");
+        assert_eq!(analytics.export(&export).unwrap(), 3);
+        let json = std::fs::read_to_string(&export).unwrap();
+        for leaked in ["quarterly", "report", "draft", "/path", "file1", "Mock-", "Result", "\\"] {
+            assert!(!json.contains(leaked), "export contains {}", leaked);
+        }
+        assert!(!json.contains('/'), "export contains a path: {}", json);
+
+        drop(engine);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Engine with the delete action enabled and a deleter that only counts calls
+    async fn recycle_engine() -> (SearchEngine, Arc<AtomicUsize>) {
+        let engine = SearchEngine::new();
+        engine.set_file_delete_enabled(true).await;
+
+        let deletes = Arc::new(AtomicUsize::new(0));
+        let deletes_clone = Arc::clone(&deletes);
+        engine
+            .set_file_deleter(move |_path: &str| {
+                deletes_clone.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+
+        (engine, deletes)
+    }
+
+    #[tokio::test]
+    async fn test_web_search_result_and_default_action_build_the_same_url() {
+        let engine = SearchEngine::new();
+        let provider = crate::search::providers::WebSearchProvider::new()
+            .unwrap()
+            .with_active_engine(engine.web_search_engine());
+        let queries = ["how do I use rust", "what is c++? 2+2=4 & 100%", "why does c# need /path/to#anchor", "who wrote 日本語 tëxt {query}"];
+
+        for engine_id in ["google", "bing", "duckduckgo"] {
+            engine.set_web_search_engine(engine_id).await.unwrap();
+            for query in queries {
+                let results = provider.search(&query.into()).await.unwrap();
+                let result = &results[0];
+                assert_eq!(result.metadata["url"], engine.web_search_url(query), "{} / {}", engine_id, query);
+                assert!(result.title.contains(engine.web_search_engine().get().name));
+                assert_eq!(result.metadata["search_engine"], engine.web_search_engine().get().name);
+                assert!(result.icon.as_deref().is_some_and(|icon| icon.starts_with("data:image/svg+xml;base64,")));
+            }
+        }
+
+        assert!(engine.set_web_search_engine("altavista").await.is_err());
+        assert!(engine.web_search_url("a b").starts_with("https://duckduckgo.com/?q=a%20b"));
+    }
+
+    #[tokio::test]
+    async fn test_recycle_disabled_by_settings_gate() {
+        let (engine, deletes) = recycle_engine().await;
+        engine.set_file_delete_enabled(false).await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 50, 1))).await;
+
+        let results = engine.search("Result").await;
+        let actions = results[0].metadata[SECONDARY_ACTIONS_KEY].as_array().unwrap();
+        assert!(actions.iter().all(|action| action["id"] != RECYCLE_ACTION_ID));
+
+        let outcome = engine.recycle_file_result(&results[0], true).await;
+        assert!(outcome.is_err());
+        assert_eq!(deletes.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_recycle_requires_confirmation() {
+        let (engine, deletes) = recycle_engine().await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 50, 1))).await;
+
+        let results = engine.search("Result").await;
+        let actions = &results[0].metadata[SECONDARY_ACTIONS_KEY];
+        assert_eq!(actions[0]["id"], RECYCLE_ACTION_ID);
+        assert_eq!(actions[0]["requires_confirmation"], true);
+        assert_eq!(actions[1]["id"], crate::utils::deeplink::COPY_LINK_ACTION_ID);
+
+        let outcome = engine.recycle_file_result(&results[0], false).await;
+        assert!(outcome.unwrap_err().to_string().contains("requires confirmation"));
+        assert_eq!(deletes.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_recycle_rejects_non_file_results() {
+        let (engine, deletes) = recycle_engine().await;
+        let mut result = titled_result("app", "Notepad", 1.0);
+        result.result_type = ResultType::Application;
+
+        assert!(engine.recycle_file_result(&result, true).await.is_err());
+        assert_eq!(deletes.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_recycle_invalidates_cache_and_recents() {
+        let (engine, deletes) = recycle_engine().await;
+        let provider = MockProvider::new("files", 50, 1);
+        let search_calls = Arc::clone(&provider.search_calls);
+        engine.register_provider(Arc::new(provider)).await;
+
+        let removed = Arc::new(Mutex::new(Vec::new()));
+        let removed_clone = Arc::clone(&removed);
+        engine
+            .set_file_removal_tracker(move |path: &str| {
+                removed_clone.lock().unwrap().push(path.to_string());
+            })
+            .await;
+
+        let results = engine.search("Result").await;
+        engine.search("Result").await;
+        assert_eq!(search_calls.load(Ordering::SeqCst), 1);
+
+        engine.recycle_file_result(&results[0], true).await.unwrap();
+        assert_eq!(deletes.load(Ordering::SeqCst), 1);
+        assert_eq!(*removed.lock().unwrap(), vec!["/path/to/file0".to_string()]);
+
+        // The cached query contained the recycled path, so providers run again
+        engine.search("Result").await;
+        assert_eq!(search_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_uncacheable_provider_results_are_not_cached() {
+        let engine = SearchEngine::new();
+        let generator = MockProvider::new("generator", 50, 1).uncacheable();
+        let generator_calls = Arc::clone(&generator.search_calls);
+        let files = MockProvider::new("files", 40, 2);
+        let file_calls = Arc::clone(&files.search_calls);
+        engine.register_provider(Arc::new(generator)).await;
+        engine.register_provider(Arc::new(files)).await;
+
+        engine.search("uuid").await;
+        engine.search("uuid").await;
+        assert_eq!(generator_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(file_calls.load(Ordering::SeqCst), 2);
+
+        let sections = [section(&[ResultType::File], 5)];
+        engine.search_sections("uuid", &sections).await;
+        engine.search_sections("uuid", &sections).await;
+        assert_eq!(generator_calls.load(Ordering::SeqCst), 4);
+
+        // Without any results from it, the provider doesn't stop caching
+        let engine = SearchEngine::new();
+        let generator = MockProvider::new("generator", 50, 0).uncacheable();
+        let generator_calls = Arc::clone(&generator.search_calls);
+        engine.register_provider(Arc::new(generator)).await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 40, 2))).await;
+
+        engine.search("notes").await;
+        engine.search("notes").await;
+        assert_eq!(generator_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_composed_and_decomposed_queries_share_cache() {
+        let composed = "Jo\u{e3}o";
+        let decomposed = "Joa\u{303}o";
+        assert_eq!(SearchEngine::sanitize_query(decomposed), composed);
+
+        let engine = SearchEngine::new();
+        let mut provider = MockProvider::new("contacts", 50, 1);
+        provider.results[0].title = "Jo\u{e3}o Silva".to_string();
+        let search_calls = Arc::clone(&provider.search_calls);
+        engine.register_provider(Arc::new(provider)).await;
+
+        let first = engine.search(composed).await;
+        let second = engine.search(decomposed).await;
+        assert_eq!(search_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first[0].score, second[0].score);
+        assert_eq!(first[0].score, 1.0 + 50.0 + 25.0);
+    }
+
+    #[tokio::test]
+    async fn test_fold_diacritics_setting_controls_ranking() {
+        let engine = SearchEngine::new();
+        let mut provider = MockProvider::new("contacts", 50, 1);
+        provider.results[0].title = "Jo\u{e3}o Silva".to_string();
+        engine.register_provider(Arc::new(provider)).await;
+        assert!(engine.fold_diacritics());
+
+        // "joao" gets the prefix and contains boosts while folding is on
+        assert_eq!(engine.search("joao").await[0].score, 76.0);
+
+        engine.set_fold_diacritics(false).await;
+        assert_eq!(engine.search("joao").await[0].score, 1.0);
+        assert_eq!(engine.search("JOA\u{303}O").await[0].score, 76.0);
+    }
+
+    #[tokio::test]
+    async fn test_recycle_failure_keeps_cache() {
+        let (engine, _) = recycle_engine().await;
+        engine
+            .set_file_deleter(|path: &str| {
+                Err(crate::error::LauncherError::ExecutionError(format!("cannot delete {}", path)))
+            })
+            .await;
+        let provider = MockProvider::new("files", 50, 1);
+        let search_calls = Arc::clone(&provider.search_calls);
+        engine.register_provider(Arc::new(provider)).await;
+
+        let results = engine.search("Result").await;
+        assert!(engine.recycle_file_result(&results[0], true).await.is_err());
+
+        engine.search("Result").await;
+        assert_eq!(search_calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// Offers a "shout" secondary action and counts how often it ran
+    struct ActionProvider {
+        shouts: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl SearchProvider for ActionProvider {
+        fn name(&self) -> &str {
+            "actions"
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok(Vec::new())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+
+        async fn execute_action(&self, _result: &SearchResult, action_id: &str) -> Result<()> {
+            if action_id != "shout" {
+                return Err(crate::error::LauncherError::NotFound(action_id.to_string()));
+            }
+            self.shouts.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn favorites_store(name: &str) -> Arc<crate::search::providers::favorites::FavoritesStore> {
+        let path = std::env::temp_dir().join("BetterFinder").join(format!("favorites_engine_{}.json", name));
+        let _ = std::fs::remove_file(&path);
+        Arc::new(crate::search::providers::favorites::FavoritesStore::with_path(path).unwrap())
+    }
+
+    fn action_ids(engine: &SearchEngine, result: &SearchResult) -> Vec<String> {
+        engine.result_actions(result).into_iter().map(|action| action.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_result_actions_follow_result_type() {
+        let (engine, _) = recycle_engine().await;
+        engine.set_favorites_store(favorites_store("actions_list"));
+        let file = MockProvider::new("files", 50, 1).results.remove(0);
+
+        assert_eq!(
+            action_ids(&engine, &file),
+            ["execute", "reveal_in_file_manager", "open_in_terminal", "copy_title", "copy_link", "pin", "not_useful", "move_to_recycle_bin"]
+        );
+        let actions = engine.result_actions(&file);
+        assert!(actions[0].is_primary && actions.iter().skip(1).all(|action| !action.is_primary));
+        assert!(actions.last().unwrap().requires_confirmation);
+
+        let mut app = titled_result("app", "Notepad", 1.0);
+        app.result_type = ResultType::Application;
+        app.action = ResultAction::LaunchApp { path: "notepad.exe".to_string() };
+        assert_eq!(action_ids(&engine, &app), ["execute", "copy_title", "copy_link", "pin", "not_useful"]);
+        assert_eq!(engine.result_actions(&app)[0].label, "Launch");
+
+        let mut clip = titled_result("clip", "{}", 1.0);
+        clip.result_type = ResultType::Clipboard;
+        clip.metadata.insert(
+            SECONDARY_ACTIONS_KEY.to_string(),
+            serde_json::json!([{ "id": "copy_formatted", "title": "Copy formatted" }, { "id": "not_useful", "title": "Not useful" }]),
+        );
+        clip.metadata.insert("pinned".to_string(), serde_json::json!(true));
+        assert_eq!(action_ids(&engine, &clip), ["execute", "copy_formatted", "copy_title", "copy_link", "unpin"]);
+
+        engine.set_file_delete_enabled(false).await;
+        assert!(!action_ids(&SearchEngine::new(), &file).contains(&"pin".to_string()));
+        assert_eq!(action_ids(&engine, &file).last().unwrap(), "not_useful");
+    }
+
+    #[tokio::test]
+    async fn test_result_actions_run_like_their_commands() {
+        let (engine, deletes) = recycle_engine().await;
+        let engine = Arc::new(engine);
+        let favorites = favorites_store("actions_run");
+        engine.set_favorites_store(Arc::clone(&favorites));
+        let shouts = Arc::new(AtomicUsize::new(0));
+        engine.register_provider(Arc::new(MockProvider::new("files", 50, 2))).await;
+        engine.register_provider(Arc::new(ActionProvider { shouts: Arc::clone(&shouts) })).await;
+        let file = engine.search("result").await.remove(0);
+
+        assert!(engine.execute_result_action(&file, "execute", "result", false).await.unwrap().is_some());
+
+        engine.execute_result_action(&file, "not_useful", "result", false).await.unwrap();
+        assert_eq!(engine.demote_result("result", &file.id).await.unwrap().demotions, 2);
+
+        engine.execute_result_action(&file, "pin", "result", false).await.unwrap();
+        assert_eq!(favorites.list().await.len(), 1);
+        engine.execute_result_action(&file, "unpin", "result", false).await.unwrap();
+        assert!(favorites.list().await.is_empty());
+
+        assert!(engine.execute_result_action(&file, "move_to_recycle_bin", "result", false).await.is_err());
+        engine.execute_result_action(&file, "move_to_recycle_bin", "result", true).await.unwrap();
+        assert_eq!(deletes.load(Ordering::SeqCst), 1);
+
+        engine.execute_result_action(&file, "shout", "result", false).await.unwrap();
+        assert_eq!(shouts.load(Ordering::SeqCst), 1);
+        let unknown = engine.execute_result_action(&file, "whisper", "result", false).await;
+        assert!(matches!(unknown, Err(crate::error::LauncherError::NotFound(_))));
+    }
+
+    /// Engine whose execution listener records every status it receives
+    async fn recording_engine() -> (Arc<SearchEngine>, Arc<Mutex<Vec<ExecutionStatus>>>) {
+        let engine = Arc::new(SearchEngine::new());
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let statuses_clone = Arc::clone(&statuses);
+        engine
+            .set_execution_listener(move |status| {
+                statuses_clone.lock().unwrap().push(status.clone());
+            })
+            .await;
+
+        (engine, statuses)
+    }
+
+    /// Waits until `count` statuses were recorded and returns their states
+    async fn wait_for_states(statuses: &Arc<Mutex<Vec<ExecutionStatus>>>, count: usize) -> Vec<ExecutionState> {
+        for _ in 0..200 {
+            if statuses.lock().unwrap().len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        statuses.lock().unwrap().iter().map(|s| s.state.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_execution_reports_started_then_succeeded() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 1).with_execute_delay(Duration::from_millis(20));
+        let result = provider.results[0].clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        let accessed = Arc::new(Mutex::new(Vec::new()));
+        let accessed_clone = Arc::clone(&accessed);
+        engine
+            .set_file_access_tracker(move |access: &FileAccess| {
+                accessed_clone.lock().unwrap().push(access.path.clone());
+            })
+            .await;
+
+        let execution_id = engine.execute_result(result.clone()).await.id;
+
+        // Returns before the provider finishes
+        assert_eq!(wait_for_states(&statuses, 1).await, vec![ExecutionState::Started]);
+        assert!(accessed.lock().unwrap().is_empty());
+
+        let states = wait_for_states(&statuses, 2).await;
+        assert_eq!(states, vec![ExecutionState::Started, ExecutionState::Succeeded]);
+        assert!(statuses
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|s| s.execution_id == execution_id && s.result_id == result.id));
+        assert_eq!(*accessed.lock().unwrap(), vec!["/path/to/file0".to_string()]);
+        assert_eq!(engine.in_flight_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_execution_reports_failure_without_tracking() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 1)
+            .with_failure()
+            .with_execute_delay(Duration::from_millis(10));
+        let mut result = provider.results[0].clone();
+        result.action = ResultAction::ExecuteCommand {
+            command: "better-finder-missing-command".to_string(),
+            args: vec![],
+        };
+        engine.register_provider(Arc::new(provider)).await;
+
+        let accessed = Arc::new(AtomicUsize::new(0));
+        let accessed_clone = Arc::clone(&accessed);
+        engine
+            .set_file_access_tracker(move |_access: &FileAccess| {
+                accessed_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        engine.execute_result(result).await;
+
+        let states = wait_for_states(&statuses, 2).await;
+        assert_eq!(states[0], ExecutionState::Started);
+        assert!(matches!(&states[1], ExecutionState::Failed { error } if error.contains("Failed to start better-finder-missing-command")));
+        assert_eq!(accessed.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_execution_cancel_reports_cancelled_only() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 1).with_execute_delay(Duration::from_secs(30));
+        let result = provider.results[0].clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        let execution_id = engine.execute_result(result).await.id;
+        assert_eq!(engine.in_flight_count(), 1);
+
+        engine.cancel_execution(&execution_id).await.unwrap();
+        assert_eq!(engine.in_flight_count(), 0);
+
+        // Give an aborted task the chance to report, which it must not do
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let states = wait_for_states(&statuses, 2).await;
+        assert_eq!(states, vec![ExecutionState::Started, ExecutionState::Cancelled]);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_execution_runs_once() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 2);
+        let results = provider.results.clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        let first = engine.execute_result(results[0].clone()).await;
+        let repeat = engine.execute_result(results[0].clone()).await;
+        assert!(!first.duplicate);
+        assert!(repeat.duplicate);
+        assert_eq!(repeat.id, first.id);
+
+        // Another result is not held back
+        let other = engine.execute_result(results[1].clone()).await;
+        assert!(!other.duplicate);
+
+        wait_for_states(&statuses, 4).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(statuses.lock().unwrap().len(), 4);
+
+        // A repeated batch suppresses the items that just ran in one
+        let summary = engine.execute_results_batch(&results, BatchAction::Open).await.unwrap();
+        assert_eq!(summary.succeeded.len(), 2);
+        let summary = engine.execute_results_batch(&results, BatchAction::Open).await.unwrap();
+        assert!(summary.succeeded.is_empty());
+        assert_eq!(summary.suppressed, vec!["files-0", "files-1"]);
+
+        engine.set_duplicate_windows(Duration::ZERO, Duration::ZERO);
+        let again = engine.execute_result(results[0].clone()).await;
+        assert!(!again.duplicate);
+        assert_ne!(again.id, first.id);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_unknown_execution_not_found() {
+        let (engine, statuses) = recording_engine().await;
+
+        let err = engine.cancel_execution("exec-missing").await.unwrap_err();
+        assert!(matches!(err, crate::error::LauncherError::NotFound(_)));
+        assert!(statuses.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_result_blocking_tracks_file_access() {
+        let engine = SearchEngine::new();
+        let provider = MockProvider::new("files", 50, 1);
+        let result = provider.results[0].clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        let accessed = Arc::new(AtomicUsize::new(0));
+        let accessed_clone = Arc::clone(&accessed);
+        engine
+            .set_file_access_tracker(move |_access: &FileAccess| {
+                accessed_clone.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        engine.execute_result_blocking(&result).await.unwrap();
+        assert_eq!(accessed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_access_reports_interaction_kind() {
+        let engine = SearchEngine::new();
+        let provider = MockProvider::new("files", 50, 1);
+        let opened = provider.results[0].clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        let accessed = Arc::new(Mutex::new(Vec::new()));
+        let accessed_clone = Arc::clone(&accessed);
+        engine
+            .set_file_access_tracker(move |access: &FileAccess| {
+                accessed_clone.lock().unwrap().push(access.clone());
+            })
+            .await;
+
+        // Copying the file's own path is a copy, not an open
+        let mut copied = opened.clone();
+        copied.metadata.insert("path".to_string(), serde_json::json!("/path/to/file0"));
+        copied.action = ResultAction::CopyToClipboard { content: "/path/to/file0".to_string() };
+
+        let mut recent = opened.clone();
+        recent.result_type = ResultType::RecentFile;
+
+        // Other results aren't files
+        let mut app = opened.clone();
+        app.result_type = ResultType::Application;
+
+        for result in [&opened, &copied, &recent, &app] {
+            engine.execute_result_blocking(result).await.unwrap();
+        }
+
+        let accessed = accessed.lock().unwrap();
+        let kinds: Vec<_> = accessed.iter().map(|access| (access.kind, access.result_type)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (InteractionKind::Opened, ResultType::File),
+                (InteractionKind::Copied, ResultType::File),
+                (InteractionKind::Opened, ResultType::RecentFile),
+            ]
+        );
+        assert!(accessed.iter().all(|access| access.path == "/path/to/file0"));
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_executes_only_searched_results() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("files", 50, 1))).await;
+
+        let searched = engine.search("file").await.remove(0);
+        let fabricated = SearchResult {
+            action: ResultAction::ExecuteCommand {
+                command: "cmd".to_string(),
+                args: vec!["/C".to_string(), "calc".to_string()],
+            },
+            integrity: None,
+            ..searched.clone()
+        };
+
+        // Off until settings turn it on
+        assert!(engine.verify_result(&fabricated).is_ok());
+        engine.set_strict_results(true);
+
+        // Legitimate results survive the trip through the frontend
+        let returned: SearchResult = serde_json::from_str(&serde_json::to_string(&searched).unwrap()).unwrap();
+        engine.verify_result(&returned).unwrap();
+        engine.execute_result_blocking(&returned).await.unwrap();
+
+        let mut tampered = returned.clone();
+        tampered.action = ResultAction::OpenFile { path: "C:\\Windows\\System32\\cmd.exe".to_string() };
+        assert!(matches!(engine.verify_result(&tampered), Err(crate::error::LauncherError::UnverifiedResult(_))));
+        assert!(matches!(engine.verify_result(&fabricated), Err(crate::error::LauncherError::UnverifiedResult(_))));
+
+        // Copying text is safe to rebuild in the frontend
+        let copy = SearchResult {
+            action: ResultAction::CopyToClipboard { content: "42".to_string() },
+            integrity: None,
+            ..searched
+        };
+        assert!(engine.verify_execution(&copy).is_ok());
+        assert!(engine.verify_result(&copy).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode_rejects_forged_copy_result_for_other_actions() {
+        let (engine, deletes) = recycle_engine().await;
+        let engine = Arc::new(engine);
+        let shouts = Arc::new(AtomicUsize::new(0));
+        engine.register_provider(Arc::new(ActionProvider { shouts: Arc::clone(&shouts) })).await;
+        engine.set_strict_results(true);
+
+        // A copy action passes unsigned, but the path it carries is made up
+        let mut forged = MockProvider::new("files", 50, 1).results.remove(0);
+        forged.action = ResultAction::CopyToClipboard { content: "x".to_string() };
+        forged.metadata.insert("path".to_string(), serde_json::json!("C:\\Users"));
+        forged.integrity = None;
+        assert!(engine.verify_execution(&forged).is_ok());
+
+        let unverified = |outcome: Result<Option<String>>| {
+            matches!(outcome, Err(crate::error::LauncherError::UnverifiedResult(_)))
+        };
+        assert!(matches!(
+            engine.recycle_file_result(&forged, true).await,
+            Err(crate::error::LauncherError::UnverifiedResult(_))
+        ));
+        assert!(matches!(engine.open_in_terminal(&forged), Err(crate::error::LauncherError::UnverifiedResult(_))));
+        for action_id in [RECYCLE_ACTION_ID, actions::REVEAL_ACTION_ID, actions::OPEN_TERMINAL_ACTION_ID, "shout"] {
+            assert!(unverified(engine.execute_result_action(&forged, action_id, "", true).await), "{}", action_id);
+        }
+        assert!(engine.execute_results_batch(&[forged.clone()], BatchAction::CopyPaths).await.is_err());
+        assert_eq!(deletes.load(Ordering::SeqCst), 0);
+        assert_eq!(shouts.load(Ordering::SeqCst), 0);
+
+        // The same result signed by the engine goes through
+        engine.sign_result(&mut forged);
+        engine.recycle_file_result(&forged, true).await.unwrap();
+        engine.execute_result_action(&forged, "shout", "", false).await.unwrap();
+        assert_eq!(deletes.load(Ordering::SeqCst), 1);
+        assert_eq!(shouts.load(Ordering::SeqCst), 1);
+    }
+
+    /// Counts pastes into a window that always takes focus
+    #[derive(Default)]
+    struct CountingPasteInput {
+        pastes: AtomicUsize,
+    }
+
+    impl crate::auto_paste::PasteInput for CountingPasteInput {
+        fn foreground_window(&self) -> Option<isize> {
+            Some(1)
+        }
+
+        fn restore_focus(&self, _window: isize) -> Result<()> {
+            Ok(())
+        }
+
+        fn send_paste(&self) -> Result<()> {
+            self.pastes.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auto_paste_runs_for_clipboard_results_only() {
+        use crate::auto_paste::AutoPaste;
+
+        let engine = SearchEngine::new();
+        let mut clipboard = MockProvider::new("clipboard", 50, 1).typed(&[ResultType::Clipboard]);
+        clipboard.results[0].action = ResultAction::CopyToClipboard { content: "copied".to_string() };
+        let clipboard_result = clipboard.results[0].clone();
+        let files = MockProvider::new("files", 40, 1);
+        let file_result = files.results[0].clone();
+        engine.register_provider(Arc::new(clipboard)).await;
+        engine.register_provider(Arc::new(files)).await;
+
+        let input = Arc::new(CountingPasteInput::default());
+        let auto_paste = Arc::new(
+            AutoPaste::with_input(Arc::clone(&input) as Arc<dyn crate::auto_paste::PasteInput>)
+                .with_settle_delay(Duration::ZERO),
+        );
+        auto_paste.remember_foreground();
+        engine.set_auto_paste(Arc::clone(&auto_paste)).await;
+
+        // Off by default: the item is only copied
+        engine.execute_result_blocking(&clipboard_result).await.unwrap();
+        assert_eq!(input.pastes.load(Ordering::SeqCst), 0);
+
+        auto_paste.set_enabled(true);
+        engine.execute_result_blocking(&file_result).await.unwrap();
+        assert_eq!(input.pastes.load(Ordering::SeqCst), 0);
+
+        engine.execute_result_blocking(&clipboard_result).await.unwrap();
+        assert_eq!(input.pastes.load(Ordering::SeqCst), 1);
+    }
+
+    /// Records each section `configure` receives
+    struct ConfigurableProvider {
+        name: &'static str,
+        configured: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    #[async_trait]
+    impl SearchProvider for ConfigurableProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok(Vec::new())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+
+        async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+            self.configured.lock().unwrap().push(config.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_settings_apply_on_registration_and_change() {
+        let engine = SearchEngine::new();
+        let recent = Arc::new(Mutex::new(Vec::new()));
+        let other = Arc::new(Mutex::new(Vec::new()));
+
+        let settings = HashMap::from([("recent_files".to_string(), serde_json::json!({ "limit": 3 }))]);
+        engine.set_provider_settings(settings).await;
+
+        engine
+            .register_provider(Arc::new(ConfigurableProvider { name: "Recent Files", configured: Arc::clone(&recent) }))
+            .await;
+        engine
+            .register_provider(Arc::new(ConfigurableProvider { name: "Calculator", configured: Arc::clone(&other) }))
+            .await;
+        assert_eq!(*recent.lock().unwrap(), vec![serde_json::json!({ "limit": 3 })]);
+
+        // Removing the section hands the provider its defaults
+        engine.set_provider_settings(HashMap::new()).await;
+        assert_eq!(recent.lock().unwrap().last(), Some(&serde_json::Value::Null));
+        assert_eq!(recent.lock().unwrap().len(), 2);
+
+        // Providers without options are never configured
+        assert!(other.lock().unwrap().is_empty());
+    }
+
+    fn section(types: &[ResultType], limit: usize) -> SearchSection {
+        SearchSection {
+            types: types.to_vec(),
+            limit,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_sections_match_filtered_searches() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("apps", 50, 4).typed(&[ResultType::Application]))).await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 60, 5).typed(&[ResultType::File]))).await;
+        engine.register_provider(Arc::new(MockProvider::new("web", 1, 2).typed(&[ResultType::WebSearch]))).await;
+
+        let sections = vec![
+            section(&[ResultType::Application], 3),
+            section(&[ResultType::File, ResultType::RecentFile], 2),
+            section(&[ResultType::WebSearch], 5),
+        ];
+        let sectioned = engine.search_sections("Result", &sections).await;
+        assert_eq!(sectioned.len(), 3);
+
+        let all = engine.search("Result").await;
+        for (index, section) in sections.iter().enumerate() {
+            let expected: Vec<String> = all
+                .iter()
+                .filter(|r| section.accepts(r.result_type))
+                .take(section.limit)
+                .map(|r| r.id.clone())
+                .collect();
+            let actual: Vec<String> = sectioned[&index].iter().map(|r| r.id.clone()).collect();
+            assert_eq!(actual, expected, "section {}", index);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_sections_skip_uncovered_providers() {
+        let engine = SearchEngine::new();
+        let apps = MockProvider::new("apps", 50, 2).typed(&[ResultType::Application]);
+        let files = MockProvider::new("files", 60, 2).typed(&[ResultType::File]);
+        let untyped = MockProvider::new("any", 40, 1);
+        let app_calls = Arc::clone(&apps.search_calls);
+        let file_calls = Arc::clone(&files.search_calls);
+        let untyped_calls = Arc::clone(&untyped.search_calls);
+        engine.register_provider(Arc::new(apps)).await;
+        engine.register_provider(Arc::new(files)).await;
+        engine.register_provider(Arc::new(untyped)).await;
+
+        let sectioned = engine
+            .search_sections("Result", &[section(&[ResultType::Application], 5)])
+            .await;
+
+        assert_eq!(sectioned[&0].len(), 2);
+        assert_eq!(app_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(file_calls.load(Ordering::SeqCst), 0);
+        // Providers without declared types may return anything, so they always run
+        assert_eq!(untyped_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_sections_dedup_across_overlapping_sections() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("files", 50, 3))).await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 40, 3))).await;
+
+        let sectioned = engine
+            .search_sections("Result", &[section(&[ResultType::File], 2), section(&[], 10)])
+            .await;
+
+        // Both providers share ids, so only three distinct results exist
+        assert_eq!(sectioned[&0].len(), 2);
+        assert_eq!(sectioned[&1].len(), 1);
+        assert!(sectioned[&0].iter().all(|r| r.id != sectioned[&1][0].id));
+    }
+
+    #[tokio::test]
+    async fn test_search_sections_cache_keyed_by_signature() {
+        let engine = SearchEngine::new();
+        let provider = MockProvider::new("apps", 50, 3).typed(&[ResultType::Application]);
+        let calls = Arc::clone(&provider.search_calls);
+        engine.register_provider(Arc::new(provider)).await;
+
+        let sections = [section(&[ResultType::Application], 2)];
+        engine.search_sections("Result", &sections).await;
+        engine.search_sections("Result", &sections).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let wider = engine
+            .search_sections("Result", &[section(&[ResultType::Application], 3)])
+            .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(wider[&0].len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_spoken_labels_only_when_enabled() {
+        use crate::search::accessibility::{SEMANTIC_KIND_KEY, SPOKEN_LABEL_KEY};
+
+        let engine = SearchEngine::new();
+        let provider = MockProvider::new("apps", 50, 2).typed(&[ResultType::Application]);
+        let calls = Arc::clone(&provider.search_calls);
+        engine.register_provider(Arc::new(provider)).await;
+
+        let results = engine.search("Result").await;
+        assert!(results.iter().all(|r| r.metadata[SEMANTIC_KIND_KEY] == "application"));
+        assert!(results.iter().all(|r| !r.metadata.contains_key(SPOKEN_LABEL_KEY)));
+
+        // Labels are added to cached results as well
+        engine.set_screen_reader_support(true);
+        let results = engine.search("Result").await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let label = results[0].metadata[SPOKEN_LABEL_KEY].as_str().unwrap();
+        assert!(label.contains(&results[0].title), "{}", label);
+
+        let sectioned = engine.search_sections("Result", &[section(&[ResultType::Application], 2)]).await;
+        assert!(sectioned[&0].iter().all(|r| r.metadata.contains_key(SPOKEN_LABEL_KEY)));
+    }
+
+    #[tokio::test]
+    async fn test_search_sections_empty_query() {
+        let engine = SearchEngine::new();
+        let provider = MockProvider::new("apps", 50, 3);
+        let calls = Arc::clone(&provider.search_calls);
+        engine.register_provider(Arc::new(provider)).await;
+
+        let sectioned = engine.search_sections("  ", &[section(&[], 5)]).await;
+        assert!(sectioned[&0].is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn test_error_handling_graceful_degradation() {
         let engine = SearchEngine::new();
 
         // One provider that works, one that fails
-        let good_provider = Box::new(MockProvider::new("good", 50, 3));
-        let bad_provider = Box::new(MockProvider::new("bad", 60, 0).with_failure());
+        let good_provider = Arc::new(MockProvider::new("good", 50, 3));
+        let bad_provider = Arc::new(MockProvider::new("bad", 60, 0).with_failure());
 
         engine.register_provider(good_provider).await;
         engine.register_provider(bad_provider).await;
@@ -185,8 +1452,8 @@ mod tests {
     async fn test_disabled_provider_skipped() {
         let engine = SearchEngine::new();
 
-        let enabled_provider = Box::new(MockProvider::new("enabled", 50, 3));
-        let disabled_provider = Box::new(MockProvider::new("disabled", 60, 5).disabled());
+        let enabled_provider = Arc::new(MockProvider::new("enabled", 50, 3));
+        let disabled_provider = Arc::new(MockProvider::new("disabled", 60, 5).disabled());
 
         engine.register_provider(enabled_provider).await;
         engine.register_provider(disabled_provider).await;
@@ -202,7 +1469,7 @@ mod tests {
     async fn test_empty_query_returns_no_results() {
         let engine = SearchEngine::new();
 
-        let provider = Box::new(MockProvider::new("provider", 50, 5));
+        let provider = Arc::new(MockProvider::new("provider", 50, 5));
         engine.register_provider(provider).await;
 
         let results = engine.search("").await;
@@ -216,7 +1483,7 @@ mod tests {
     async fn test_query_sanitization() {
         let engine = SearchEngine::new();
 
-        let provider = Box::new(MockProvider::new("provider", 50, 3));
+        let provider = Arc::new(MockProvider::new("provider", 50, 3));
         engine.register_provider(provider).await;
 
         // Query with control characters should be sanitized
@@ -231,7 +1498,7 @@ mod tests {
         let engine = SearchEngine::new();
 
         // Create provider with more than MAX_RESULTS_PER_PROVIDER results
-        let provider = Box::new(MockProvider::new("provider", 50, 30));
+        let provider = Arc::new(MockProvider::new("provider", 50, 30));
         engine.register_provider(provider).await;
 
         let results = engine.search("test").await;
@@ -246,7 +1513,7 @@ mod tests {
 
         // Register multiple providers with many results each
         for i in 0..5 {
-            let provider = Box::new(MockProvider::new(&format!("provider{}", i), 50 + i, 20));
+            let provider = Arc::new(MockProvider::new(&format!("provider{}", i), 50 + i, 20));
             engine.register_provider(provider).await;
         }
 
@@ -256,6 +1523,790 @@ mod tests {
         assert!(results.len() <= 50);
     }
 
+    #[tokio::test]
+    async fn test_max_results_setting_limits_results() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(MockProvider::new("many", 50, 20)))
+            .await;
+
+        assert_eq!(engine.search("test").await.len(), 20);
+
+        // A lower limit applies to the next search even for a cached query
+        engine.set_max_results(8).await;
+        assert_eq!(engine.max_results(), 8);
+        assert_eq!(engine.search("test").await.len(), 8);
+
+        // The setting can never raise the limit past MAX_TOTAL_RESULTS
+        engine.set_max_results(500).await;
+        assert_eq!(engine.max_results(), 50);
+        assert_eq!(engine.search("test").await.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_paged_search_returns_first_page_inline() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(MockProvider::new("many", 50, 20)))
+            .await;
+        engine.set_max_results(8).await;
+
+        let response = engine.search_paged("test").await;
+        assert_eq!(response.total, 20);
+        assert_eq!(response.results.len(), 8);
+        let inline: Vec<&str> = response.results.iter().map(|r| r.id.as_str()).collect();
+        let search_id = response.search_id.as_deref().expect("results past the first page are kept");
+
+        // Later pages continue where the inline page stopped
+        let page = engine.results_page(search_id, 8, 8).unwrap();
+        assert_eq!(page.total, 20);
+        assert_eq!(page.results.len(), 8);
+        assert!(page.results.iter().all(|r| !inline.contains(&r.id.as_str())));
+        assert_eq!(engine.results_page(search_id, 16, 8).unwrap().results.len(), 4);
+
+        // Everything fits on the first page, so nothing is kept
+        engine.set_max_results(50).await;
+        let response = engine.search_paged("test").await;
+        assert_eq!(response.results.len(), 20);
+        assert!(response.search_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_facets_count_types_and_providers() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("apps", 50, 3).typed(&[ResultType::Application]))).await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 60, 12).typed(&[ResultType::File]))).await;
+        engine.register_provider(Arc::new(MockProvider::new("recent", 40, 2).typed(&[ResultType::File]))).await;
+        engine.set_max_results(8).await;
+
+        let response = engine.search_paged("test").await;
+        let facets = response.facets.expect("facets accompany results");
+        let count = |result_type: ResultType| {
+            let facet = facets.types.iter().find(|f| f.result_type == result_type).unwrap();
+            (facet.count, facet.shown, facet.truncated)
+        };
+        let (apps, apps_shown, apps_truncated) = count(ResultType::Application);
+        let (files, files_shown, files_truncated) = count(ResultType::File);
+        assert_eq!((apps, files), (3, 14));
+        assert_eq!(apps_shown + files_shown, 8);
+        assert!(files_truncated);
+        assert_eq!(apps_truncated, apps_shown < 3);
+
+        let mut providers: Vec<_> = facets.providers.iter().map(|f| (f.provider.as_str(), f.count)).collect();
+        providers.sort();
+        assert_eq!(providers, vec![("apps", 3), ("files", 12), ("recent", 2)]);
+
+        // Nothing found, nothing to count
+        let empty = SearchEngine::new();
+        assert!(empty.search_paged("test").await.facets.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_faceted_search_narrows_the_kept_results() {
+        let engine = SearchEngine::new();
+        let apps = MockProvider::new("apps", 50, 3).typed(&[ResultType::Application]).uncacheable();
+        let app_calls = Arc::clone(&apps.search_calls);
+        engine.register_provider(Arc::new(apps)).await;
+        engine.register_provider(Arc::new(MockProvider::new("files", 60, 12).typed(&[ResultType::File]))).await;
+        engine.set_max_results(5).await;
+
+        // Several types, so the results are kept even though few would fit
+        let response = engine.search_paged("test").await;
+        let search_id = response.search_id.expect("mixed results are kept for narrowing");
+        assert_eq!(app_calls.load(Ordering::SeqCst), 1);
+
+        let narrowed = engine.search_faceted("test", &[ResultType::Application]).await;
+        assert_eq!(app_calls.load(Ordering::SeqCst), 1, "narrowing must not search again");
+        assert_eq!(narrowed.search_id.as_deref(), Some(search_id.as_str()));
+        assert_eq!(narrowed.total, 3);
+        assert!(narrowed.results.iter().all(|r| r.result_type == ResultType::Application));
+        let facets = narrowed.facets.unwrap();
+        let files = facets.types.iter().find(|f| f.result_type == ResultType::File).unwrap();
+        assert_eq!((files.count, files.shown, files.truncated), (12, 0, true));
+
+        // Later pages stay within the narrowed types
+        let page = engine.results_page_of_types(&search_id, 5, 5, &[ResultType::File]).unwrap();
+        assert_eq!(page.total, 12);
+        assert_eq!(page.results.len(), 5);
+        assert!(page.results.iter().all(|r| r.result_type == ResultType::File));
+
+        // No active facets shows every type again
+        assert_eq!(engine.search_faceted("test", &[]).await.total, 15);
+        // A type nothing matched gives an empty page without facets
+        let none = engine.search_faceted("test", &[ResultType::Hash]).await;
+        assert!(none.results.is_empty() && none.facets.is_none());
+        assert_eq!(app_calls.load(Ordering::SeqCst), 1);
+
+        // Once the kept results are gone, the search runs again
+        engine.release_search(&search_id);
+        let rerun = engine.search_faceted("test", &[ResultType::Application]).await;
+        assert_eq!(rerun.total, 3);
+        assert_eq!(app_calls.load(Ordering::SeqCst), 2);
+        assert!(engine.results_page(rerun.search_id.as_deref().unwrap(), 0, 5).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_paged_searches_are_dropped_when_providers_change() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(MockProvider::new("many", 50, 20)))
+            .await;
+        engine.set_max_results(5).await;
+
+        let search_id = engine.search_paged("test").await.search_id.unwrap();
+        assert!(engine.results_page(&search_id, 5, 5).is_ok());
+
+        engine
+            .register_provider(Arc::new(MockProvider::new("late", 40, 3)))
+            .await;
+        assert!(matches!(
+            engine.results_page(&search_id, 5, 5),
+            Err(crate::error::LauncherError::NotFound(_))
+        ));
+
+        let search_id = engine.search_paged("test").await.search_id.unwrap();
+        engine.invalidate_cache().await;
+        assert!(engine.results_page(&search_id, 0, 5).is_err());
+
+        let search_id = engine.search_paged("test").await.search_id.unwrap();
+        assert!(engine.release_search(&search_id));
+        assert!(engine.results_page(&search_id, 0, 5).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_result_looks_in_kept_and_cached_searches() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(MockProvider::new("many", 50, 20)))
+            .await;
+        engine.set_max_results(5).await;
+
+        // Past the first page, only the kept search has it
+        engine.search_paged("test").await;
+        assert_eq!(engine.find_result("many-17").await.unwrap().id, "many-17");
+
+        engine.set_max_results(50).await;
+        engine.invalidate_cache().await;
+        assert!(engine.search_paged("test").await.search_id.is_none());
+        assert_eq!(engine.find_result("many-3").await.unwrap().id, "many-3");
+
+        engine.invalidate_cache().await;
+        assert!(matches!(
+            engine.find_result("many-3").await,
+            Err(crate::error::LauncherError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_group_file_results_setting_collapses_crowded_folders() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(MockProvider::new("many", 50, 20)))
+            .await;
+
+        assert_eq!(engine.search("test").await.len(), 20);
+
+        // Every mock file lives in /path/to; all but the leading files collapse
+        engine.set_group_file_results(true).await;
+        let results = engine.search("test").await;
+        let leading = crate::search::grouping::UNGROUPED_LEADING_FILES;
+        assert_eq!(results.len(), leading + 1);
+        assert_eq!(results[leading].result_type, ResultType::FileGroup);
+        assert_eq!(results[leading].subtitle, format!("{} matches", 20 - leading));
+
+        let group_error = engine.execute_result_blocking(&results[leading]).await;
+        assert!(group_error.is_err());
+
+        engine.set_group_file_results(false).await;
+        assert_eq!(engine.search("test").await.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_diagnostics_report_result_cache_usage() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(MockProvider::new("files", 50, 3)))
+            .await;
+
+        let diagnostics = engine.diagnostics().await;
+        assert_eq!(diagnostics.result_cache.entries, 0);
+        assert_eq!(diagnostics.result_cache_hit_rate, None);
+        assert!(diagnostics.caches.is_empty());
+
+        engine.search("test").await;
+        engine.search("test").await;
+        let diagnostics = engine.diagnostics().await;
+        assert_eq!(diagnostics.result_cache.entries, 1);
+        assert!(diagnostics.result_cache.bytes > 0);
+        assert_eq!(diagnostics.result_cache_hit_rate, Some(0.5));
+
+        // Fresh results are not cold, so trimming keeps them
+        engine.trim_caches().await;
+        assert_eq!(engine.diagnostics().await.result_cache.entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_resume_refreshes_enabled_providers_and_drops_cached_results() {
+        let engine = SearchEngine::new();
+        let apps = MockProvider::new("apps", 50, 3);
+        let app_resumes = Arc::clone(&apps.resume_calls);
+        let app_searches = Arc::clone(&apps.search_calls);
+        let hidden = MockProvider::new("hidden", 50, 0).disabled();
+        let hidden_resumes = Arc::clone(&hidden.resume_calls);
+        engine.register_provider(Arc::new(apps)).await;
+        engine.register_provider(Arc::new(hidden)).await;
+
+        engine.search("test").await;
+        engine.refresh_after_resume().await;
+        assert_eq!(app_resumes.load(Ordering::SeqCst), 1);
+        assert_eq!(hidden_resumes.load(Ordering::SeqCst), 0);
+
+        // Results cached before the resume are searched again
+        engine.search("test").await;
+        assert_eq!(app_searches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_completion_uses_candidates_without_searching() {
+        let engine = SearchEngine::new();
+        let apps = MockProvider::new("apps", 50, 0)
+            .with_completions(CompletionSource::App, &["Chrome Remote Desktop", "Chrome", "Google Chrome"]);
+        let search_calls = Arc::clone(&apps.search_calls);
+        engine.register_provider(Arc::new(apps)).await;
+        engine
+            .register_provider(Arc::new(
+                MockProvider::new("hidden", 50, 0).with_completions(CompletionSource::Pin, &["Chrome Beta"]).disabled(),
+            ))
+            .await;
+
+        let completion = engine.completion("chr").await.unwrap();
+        assert_eq!(completion.suffix, "ome");
+        assert_eq!(completion.result_id, "apps:Chrome");
+        assert!(engine.completion("hrom").await.is_none());
+        assert!(engine.completion("  ").await.is_none());
+        assert_eq!(search_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_completion_prefers_executed_results_over_apps() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 1).with_completions(CompletionSource::App, &["Result A"]);
+        let result = provider.results[0].clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        assert_eq!(engine.completion("res").await.unwrap().text, "Result A");
+
+        engine.execute_result(result.clone()).await;
+        wait_for_states(&statuses, 2).await;
+
+        let completion = engine.completion("res").await.unwrap();
+        assert_eq!(completion.text, "Result 0 from files");
+        assert_eq!(completion.result_id, result.id);
+    }
+
+    #[tokio::test]
+    async fn test_recent_execution_boost_adds_to_provider_score() {
+        let (engine, statuses) = recording_engine().await;
+        engine.set_debug_mode(true).await;
+        let mut provider = MockProvider::new("files", 50, 3);
+        // Stands in for the frecency bonus Recent Files folds into its scores
+        provider.results[2].score += 30.0;
+        let results = provider.results.clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        engine.execute_result(results[1].clone()).await;
+        engine.execute_result(results[2].clone()).await;
+        wait_for_states(&statuses, 4).await;
+
+        let ranked = engine.search("files").await;
+        let ids: Vec<&str> = ranked.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["files-2", "files-1", "files-0"]);
+
+        let breakdown = &ranked[0].metadata[SCORE_BREAKDOWN_KEY];
+        assert_eq!(breakdown["base"], 31.0);
+        let boost = breakdown["recent_execution"].as_f64().unwrap();
+        assert!(boost > 39.0 && boost <= 40.0);
+        assert_eq!(ranked[0].score, 31.0 + 25.0 + boost);
+        assert_eq!(ranked[2].metadata[SCORE_BREAKDOWN_KEY]["recent_execution"], 0.0);
+
+        let recent = engine.diagnostics().await.recent_executions;
+        let recent_ids: Vec<&str> = recent.iter().map(|entry| entry.result_id.as_str()).collect();
+        assert_eq!(recent_ids, vec!["files-2", "files-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_privacy_mode_forgets_executions() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 2).with_completions(CompletionSource::App, &["Result A"]);
+        let results = provider.results.clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        engine.execute_result(results[1].clone()).await;
+        wait_for_states(&statuses, 2).await;
+        assert_eq!(engine.search("files").await[0].id, "files-1");
+        assert_eq!(engine.completion("res").await.unwrap().result_id, "files-1");
+
+        engine.set_privacy_mode(true).await;
+        assert!(engine.diagnostics().await.recent_executions.is_empty());
+        assert_eq!(engine.search("files").await[0].id, "files-0");
+        assert_eq!(engine.completion("res").await.unwrap().text, "Result A");
+
+        // Executions while it's on are not remembered either
+        engine.execute_result(results[0].clone()).await;
+        wait_for_states(&statuses, 4).await;
+        assert!(engine.diagnostics().await.recent_executions.is_empty());
+        assert_eq!(engine.completion("res").await.unwrap().text, "Result A");
+    }
+
+    /// Handles pasted text and records the full text of each query it gets
+    struct PastedTextProvider {
+        texts: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl SearchProvider for PastedTextProvider {
+        fn name(&self) -> &str {
+            "pasted"
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            self.texts.lock().unwrap().push(query.full_text().to_string());
+            let mut result = titled_result("pasted:count", "Count", 10.0);
+            result.result_type = ResultType::Text;
+            Ok(vec![result])
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+
+        fn handles_blobs(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pasted_text_only_reaches_blob_providers() {
+        let engine = SearchEngine::new();
+        let files = MockProvider::new("files", 50, 2);
+        let file_searches = Arc::clone(&files.search_calls);
+        engine.register_provider(Arc::new(files)).await;
+        let texts = Arc::new(Mutex::new(Vec::new()));
+        engine.register_provider(Arc::new(PastedTextProvider { texts: Arc::clone(&texts) })).await;
+
+        let trace = format!("Error: boom\n{}", "    at frame (app.js:1)\n".repeat(40));
+        let results = engine.search(&trace).await;
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["text:blob", "pasted:count"]);
+        assert_eq!(file_searches.load(Ordering::SeqCst), 0);
+
+        // The provider got the whole text with its line breaks, not the 256 sanitized characters
+        assert_eq!(texts.lock().unwrap().as_slice(), [trace.trim().to_string()]);
+        let actions = action_ids(&engine, &results[0]);
+        assert!(actions.contains(&crate::search::blob::SEARCH_FIRST_LINE_ACTION_ID.to_string()));
+
+        // Pasting it again is answered from the cache
+        engine.search(&trace).await;
+        assert_eq!(texts.lock().unwrap().len(), 1);
+
+        // Typed queries reach every provider, with the sanitized query as full text
+        engine.search("frame").await;
+        assert_eq!(file_searches.load(Ordering::SeqCst), 1);
+        assert_eq!(texts.lock().unwrap()[1], "frame");
+    }
+
+    /// Matches names of one type and records the query of each search it gets
+    struct NameProvider {
+        name: &'static str,
+        types: &'static [ResultType],
+        queries: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl NameProvider {
+        fn new(name: &'static str, types: &'static [ResultType]) -> Self {
+            Self { name, types, queries: Arc::new(Mutex::new(Vec::new())) }
+        }
+    }
+
+    #[async_trait]
+    impl SearchProvider for NameProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        fn result_types(&self) -> Option<&'static [ResultType]> {
+            Some(self.types)
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            self.queries.lock().unwrap().push(query.query.clone());
+            let mut result = titled_result(&format!("{}:{}", self.name, query.query), &query.query, 10.0);
+            result.result_type = self.types[0];
+            Ok(vec![result])
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plain_text_marker_skips_the_calculator() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(crate::search::providers::CalculatorProvider::new().unwrap()))
+            .await;
+        let files = NameProvider::new("files", &[ResultType::File]);
+        let file_queries = Arc::clone(&files.queries);
+        let apps = NameProvider::new("apps", &[ResultType::Application]);
+        let app_queries = Arc::clone(&apps.queries);
+        // Could return anything, so it isn't asked for plain text
+        let untyped = MockProvider::new("untyped", 50, 1);
+        let untyped_searches = Arc::clone(&untyped.search_calls);
+        engine.register_provider(Arc::new(files)).await;
+        engine.register_provider(Arc::new(apps)).await;
+        engine.register_provider(Arc::new(untyped)).await;
+
+        let sum = engine.search("2+2").await;
+        assert!(sum.iter().any(|r| r.result_type == ResultType::Calculator));
+
+        let results = engine.search("'2+2").await;
+        let mut ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["apps:2+2", "files:2+2"]);
+        assert_eq!(untyped_searches.load(Ordering::SeqCst), 1);
+
+        // Providers only ever saw the text without its marker
+        assert_eq!(file_queries.lock().unwrap().as_slice(), ["2+2", "2+2"]);
+        assert_eq!(app_queries.lock().unwrap().as_slice(), ["2+2", "2+2"]);
+        assert!(engine.peek("`2+2").await.is_empty());
+        assert!(engine.search("'").await.is_empty());
+
+        // With the marker off, the quote is part of the query
+        engine.set_plain_text_markers("").await;
+        engine.search("'3*3").await;
+        assert_eq!(file_queries.lock().unwrap().last().unwrap(), "'3*3");
+    }
+
+    #[tokio::test]
+    async fn test_plain_text_is_cached_apart_from_the_query() {
+        let engine = SearchEngine::new();
+        let files = MockProvider::new("files", 50, 2).typed(&[ResultType::File]);
+        let file_searches = Arc::clone(&files.search_calls);
+        engine.register_provider(Arc::new(files)).await;
+
+        engine.search("report").await;
+        engine.search("'report").await;
+        assert_eq!(file_searches.load(Ordering::SeqCst), 2);
+
+        // Each is answered from its own cache entry
+        engine.search("report").await;
+        engine.search("`report").await;
+        assert_eq!(file_searches.load(Ordering::SeqCst), 2);
+
+        let sections = [SearchSection { types: vec![ResultType::File], limit: 5 }];
+        engine.search_sections("report", &sections).await;
+        engine.search_sections("'report", &sections).await;
+        assert_eq!(file_searches.load(Ordering::SeqCst), 4);
+    }
+
+    /// Scores its results differently for each query, as ranking shifts while typing
+    struct ShiftingProvider {
+        scores: &'static [(&'static str, &'static [(&'static str, f64)])],
+    }
+
+    #[async_trait]
+    impl SearchProvider for ShiftingProvider {
+        fn name(&self) -> &str {
+            "shifting"
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            let scores = self.scores.iter().find(|(typed, _)| *typed == query.query).map_or(&[][..], |(_, scores)| scores);
+            Ok(scores.iter().map(|(id, score)| titled_result(id, &format!("Item {}", id), *score)).collect())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn slot_ids(response: &SearchResponse) -> Vec<&str> {
+        response.slots.iter().map(|slot| slot.result_id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_stable_slots_follow_the_typed_query_until_reset() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(ShiftingProvider {
+                scores: &[
+                    ("zq", &[("a", 60.0), ("b", 55.0)]),
+                    ("zqx", &[("a", 58.0), ("b", 63.0), ("c", 70.0)]),
+                    ("zqxy", &[("a", 40.0), ("b", 63.0), ("c", 70.0)]),
+                ],
+            }))
+            .await;
+
+        // Off by default, so the slots follow the ranking
+        engine.search_paged("zq").await;
+        assert_eq!(slot_ids(&engine.search_paged("zqx").await), vec!["c", "b", "a"]);
+
+        engine.set_stable_result_slots(true);
+        assert_eq!(slot_ids(&engine.search_paged("zq").await), vec!["a", "b"]);
+        // "b" edges ahead and "c" is new, so "a" keeps slot 1
+        let response = engine.search_paged("zqx").await;
+        assert_eq!(slot_ids(&response), vec!["a", "b", "c"]);
+        assert_eq!(response.slots[0].slot, 1);
+        assert_eq!(response.results[0].id, "a");
+
+        // A lead past the hysteresis moves results up
+        assert_eq!(slot_ids(&engine.search_paged("zqxy").await), vec!["b", "c", "a"]);
+
+        // Hiding the window ends the session
+        engine.reset_result_slots();
+        assert_eq!(slot_ids(&engine.search_paged("zqx").await), vec!["c", "b", "a"]);
+    }
+
+    #[tokio::test]
+    async fn test_peek_gives_one_uncached_answer_per_fast_provider() {
+        let engine = SearchEngine::new();
+        let calculator = MockProvider::new("calculator", 90, 3).peekable();
+        let calculator_searches = Arc::clone(&calculator.search_calls);
+        let files = MockProvider::new("files", 50, 2);
+        let file_searches = Arc::clone(&files.search_calls);
+        engine.register_provider(Arc::new(calculator)).await;
+        engine.register_provider(Arc::new(files)).await;
+        engine
+            .register_provider(Arc::new(
+                MockProvider::new("slow", 80, 1).peekable().with_search_delay(Duration::from_millis(50)),
+            ))
+            .await;
+
+        let started = Instant::now();
+        let results = engine.peek("12*3").await;
+        assert!(started.elapsed() < Duration::from_millis(50), "{:?}", started.elapsed());
+        let ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["calculator-0"]);
+        assert_eq!(results[0].metadata.get(PEEK_KEY), Some(&serde_json::json!(true)));
+        assert_eq!(file_searches.load(Ordering::SeqCst), 0);
+
+        // The slow provider was dropped and shows in its health
+        let health = engine.provider_health().await;
+        let slow_peeks = |name: &str| health.iter().find(|s| s.provider == name).unwrap().slow_peeks;
+        assert_eq!((slow_peeks("slow"), slow_peeks("calculator")), (1, 0));
+
+        // Peeks neither fill nor read the result cache, and the full search isn't marked
+        let full = engine.search("12*3").await;
+        assert_eq!(calculator_searches.load(Ordering::SeqCst), 2);
+        assert!(full.iter().all(|r| !r.metadata.contains_key(PEEK_KEY)));
+        assert_eq!(full.iter().filter(|r| r.id.starts_with("calculator")).count(), 3);
+        engine.peek("12*3").await;
+        assert_eq!(calculator_searches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_spelling_suggestion_only_when_nothing_matched() {
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(
+                MockProvider::new("apps", 50, 0).with_suggestion_terms(&["Google Chrome", "Spotify"]),
+            ))
+            .await;
+        engine
+            .register_provider(Arc::new(MockProvider::new("web", 1, 1).typed(&[ResultType::WebSearch])))
+            .await;
+
+        // Only the web search fallback matched, so the correction is appended
+        let results = engine.search("chrme").await;
+        assert_eq!(results.len(), 2);
+        let suggestion = &results[1];
+        assert_eq!(suggestion.result_type, ResultType::Suggestion);
+        assert_eq!(suggestion.title, "Did you mean: Chrome?");
+        assert!(matches!(&suggestion.action, ResultAction::SetQuery { query } if query == "Chrome"));
+
+        // Nothing close enough in the dictionary
+        let results = engine.search("xqzvwk").await;
+        assert!(results.iter().all(|r| r.result_type != ResultType::Suggestion));
+
+        // Real matches suppress the suggestion
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(
+                MockProvider::new("files", 50, 1).with_suggestion_terms(&["Google Chrome"]),
+            ))
+            .await;
+        let results = engine.search("chrme").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result_type, ResultType::File);
+    }
+
+    #[tokio::test]
+    async fn test_batch_aggregates_outcomes() {
+        let (engine, statuses) = recording_engine().await;
+        let provider = MockProvider::new("files", 50, 3).failing_on(&["files-1"]);
+        let batch = provider.results.clone();
+        engine.register_provider(Arc::new(provider)).await;
+
+        let summary = engine.execute_results_batch(&batch, BatchAction::Open).await.unwrap();
+
+        assert_eq!(summary.succeeded, vec!["files-0", "files-2"]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].id, "files-1");
+        assert!(!summary.failed[0].error.is_empty());
+
+        // Every item reports Started and then its own outcome
+        let statuses = statuses.lock().unwrap().clone();
+        assert_eq!(statuses.len(), 6);
+        let outcome = |id: &str| {
+            statuses
+                .iter()
+                .rev()
+                .find(|s| s.result_id == id)
+                .map(|s| s.state.clone())
+                .unwrap()
+        };
+        assert_eq!(outcome("files-0"), ExecutionState::Succeeded);
+        assert!(matches!(outcome("files-1"), ExecutionState::Failed { .. }));
+        assert_eq!(outcome("files-2"), ExecutionState::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_batch_rejects_non_file_results() {
+        let (engine, statuses) = recording_engine().await;
+        let mut batch = MockProvider::new("files", 50, 2).results;
+        batch.extend(MockProvider::new("apps", 50, 2).typed(&[ResultType::Application]).results);
+
+        let error = engine
+            .execute_results_batch(&batch, BatchAction::Open)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("apps-0, apps-1"), "{}", error);
+        assert!(!error.contains("files-0"));
+
+        // Nothing ran
+        assert!(statuses.lock().unwrap().is_empty());
+
+        // Empty and oversized batches are rejected too
+        assert!(SearchEngine::validate_batch(&[]).is_err());
+        let oversized = MockProvider::new("files", 50, MAX_BATCH_SIZE + 1).results;
+        assert!(SearchEngine::validate_batch(&oversized).is_err());
+        assert!(SearchEngine::validate_batch(&oversized[..MAX_BATCH_SIZE]).is_ok());
+    }
+
+    #[test]
+    fn test_batch_joins_paths_one_per_line() {
+        let batch = MockProvider::new("files", 50, 3).results;
+        assert_eq!(
+            SearchEngine::join_result_paths(&batch),
+            "/path/to/file0\n/path/to/file1\n/path/to/file2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registration_does_not_wait_for_running_search() {
+        let engine = Arc::new(SearchEngine::new());
+        engine
+            .register_provider(Arc::new(
+                MockProvider::new("slow", 50, 2).with_search_delay(Duration::from_millis(500)),
+            ))
+            .await;
+
+        let searching = Arc::clone(&engine);
+        let search = tokio::spawn(async move { searching.search("slow").await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Registering while the slow search runs completes long before it does
+        tokio::time::timeout(Duration::from_millis(250), async {
+            for i in 0..5 {
+                engine
+                    .register_provider(Arc::new(MockProvider::new(&format!("late{}", i), 60, 1)))
+                    .await;
+            }
+        })
+        .await
+        .expect("registration blocked behind a running search");
+        assert!(!search.is_finished());
+
+        // The running search completes against the snapshot it started with
+        let results = search.await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.id.starts_with("slow")));
+
+        // New searches see every provider
+        assert_eq!(engine.provider_count().await, 6);
+        assert_eq!(engine.search("fresh").await.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_searches_and_registrations_do_not_deadlock() {
+        let engine = Arc::new(SearchEngine::new());
+        engine
+            .register_provider(Arc::new(
+                MockProvider::new("base", 50, 3).with_search_delay(Duration::from_millis(5)),
+            ))
+            .await;
+
+        let mut tasks = Vec::new();
+        for i in 0..20 {
+            let engine = Arc::clone(&engine);
+            tasks.push(tokio::spawn(async move {
+                if i % 2 == 0 {
+                    engine
+                        .register_provider(Arc::new(
+                            MockProvider::new(&format!("p{}", i), 40, 1)
+                                .with_search_delay(Duration::from_millis(5)),
+                        ))
+                        .await;
+                } else {
+                    // Distinct queries so every search reaches the providers
+                    let results = engine.search(&format!("query {}", i)).await;
+                    assert!(results.len() >= 3);
+                }
+            }));
+        }
+
+        tokio::time::timeout(Duration::from_secs(5), futures::future::join_all(tasks))
+            .await
+            .expect("searches and registrations deadlocked")
+            .into_iter()
+            .for_each(|task| task.unwrap());
+
+        // No registration was lost to a concurrent swap
+        assert_eq!(engine.provider_count().await, 11);
+    }
+
+    #[tokio::test]
+    async fn test_unregister_and_replace_provider() {
+        let engine = SearchEngine::new();
+        engine.register_provider(Arc::new(MockProvider::new("files", 50, 2))).await;
+        engine.register_provider(Arc::new(MockProvider::new("apps", 60, 1))).await;
+        assert_eq!(engine.search("test").await.len(), 3);
+
+        // Replacing keeps one provider under the name and drops cached results
+        engine.replace_provider(Arc::new(MockProvider::new("files", 50, 4))).await;
+        assert_eq!(engine.provider_count().await, 2);
+        assert_eq!(engine.search("test").await.len(), 5);
+
+        assert!(engine.unregister_provider("apps").await);
+        assert!(!engine.unregister_provider("apps").await);
+        assert_eq!(engine.provider_names().await, vec!["files"]);
+        assert_eq!(engine.search("test").await.len(), 4);
+    }
+
     #[tokio::test]
     async fn test_all_providers_integration() {
         // This test verifies that all providers can be registered together
@@ -266,53 +2317,53 @@ mod tests {
         
         // RecentFilesProvider
         if let Ok(provider) = crate::search::providers::RecentFilesProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // FileSearchProvider (with fallback to WindowsSearch)
         match crate::search::providers::FileSearchProvider::new() {
             Ok(provider) => {
                 if provider.is_enabled() {
-                    engine.register_provider(Box::new(provider)).await;
+                    engine.register_provider(Arc::new(provider)).await;
                 } else if let Ok(fallback) = crate::search::providers::WindowsSearchProvider::new() {
-                    engine.register_provider(Box::new(fallback)).await;
+                    engine.register_provider(Arc::new(fallback)).await;
                 }
             }
             Err(_) => {
                 if let Ok(fallback) = crate::search::providers::WindowsSearchProvider::new() {
-                    engine.register_provider(Box::new(fallback)).await;
+                    engine.register_provider(Arc::new(fallback)).await;
                 }
             }
         }
 
         // CalculatorProvider
         if let Ok(provider) = crate::search::providers::CalculatorProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // QuickActionProvider
         if let Ok(provider) = crate::search::providers::QuickActionProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // AppSearchProvider
         if let Ok(provider) = crate::search::providers::AppSearchProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // BookmarkProvider
         if let Ok(provider) = crate::search::providers::BookmarkProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // ClipboardHistoryProvider
         if let Ok(provider) = crate::search::providers::ClipboardHistoryProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // WebSearchProvider
         if let Ok(provider) = crate::search::providers::WebSearchProvider::new() {
-            engine.register_provider(Box::new(provider)).await;
+            engine.register_provider(Arc::new(provider)).await;
         }
 
         // Verify providers are registered
@@ -363,6 +2414,9 @@ mod tests {
             action: ResultAction::OpenFile {
                 path: "C:\\test.txt".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }];
 
         let app_results = vec![SearchResult {
@@ -376,6 +2430,9 @@ mod tests {
             action: ResultAction::LaunchApp {
                 path: "C:\\app.exe".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }];
 
         struct TypedMockProvider {
@@ -394,7 +2451,7 @@ mod tests {
                 self.priority
             }
 
-            async fn search(&self, _query: &str) -> Result<Vec<SearchResult>> {
+            async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
                 Ok(self.results.clone())
             }
 
@@ -407,13 +2464,13 @@ mod tests {
             }
         }
 
-        let file_provider = Box::new(TypedMockProvider {
+        let file_provider = Arc::new(TypedMockProvider {
             name: "files".to_string(),
             priority: 90,
             results: file_results,
         });
 
-        let app_provider = Box::new(TypedMockProvider {
+        let app_provider = Arc::new(TypedMockProvider {
             name: "apps".to_string(),
             priority: 85,
             results: app_results,
@@ -0,0 +1,269 @@
+use crate::types::EnrichedResult;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Number of results enriched at the same time
+pub const ENRICHMENT_WORKERS: usize = 4;
+
+/// How long enriched results are collected before they're sent together
+pub const ENRICHMENT_BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Metadata key naming the provider that enriches a result
+pub const ENRICHMENT_PROVIDER_KEY: &str = "enrichment_provider";
+
+/// Callback receiving each batch of enriched results, e.g. to emit `results-enriched`
+pub type EnrichmentListener = Arc<dyn Fn(&[EnrichedResult]) + Send + Sync>;
+
+/// Bounds how many results are enriched at once and collects the enriched
+/// results into batches
+///
+/// The engine decides what to enrich and when a batch is sent; this only
+/// hands out worker slots and buffers the output, so a burst of icons
+/// reaches the frontend as one event instead of one per result.
+pub struct EnrichmentPipeline {
+    workers: Arc<Semaphore>,
+    batch: Mutex<Vec<EnrichedResult>>,
+    listener: std::sync::RwLock<Option<EnrichmentListener>>,
+    interval: Duration,
+}
+
+impl EnrichmentPipeline {
+    /// Creates a pipeline with `ENRICHMENT_WORKERS` workers sending a batch
+    /// every `ENRICHMENT_BATCH_INTERVAL`
+    pub fn new() -> Self {
+        Self::with_limits(ENRICHMENT_WORKERS, ENRICHMENT_BATCH_INTERVAL)
+    }
+
+    /// Creates a pipeline with `workers` workers sending a batch every `interval`
+    pub fn with_limits(workers: usize, interval: Duration) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(workers.max(1))),
+            batch: Mutex::new(Vec::new()),
+            listener: std::sync::RwLock::new(None),
+            interval,
+        }
+    }
+
+    /// Sets the callback receiving enriched results
+    pub fn set_listener(&self, listener: EnrichmentListener) {
+        *self.listener.write().unwrap_or_else(|e| e.into_inner()) = Some(listener);
+    }
+
+    /// Waits for a free worker; the slot is released when the permit drops
+    pub async fn worker(&self) -> OwnedSemaphorePermit {
+        Arc::clone(&self.workers)
+            .acquire_owned()
+            .await
+            .expect("the enrichment semaphore is never closed")
+    }
+
+    /// How long a batch collects results before it's sent
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Adds an enriched result to the current batch
+    ///
+    /// Returns `true` for the first result of a batch, in which case the
+    /// caller schedules `flush` after `interval`.
+    pub fn push(&self, enriched: EnrichedResult) -> bool {
+        let mut batch = self.batch.lock().unwrap_or_else(|e| e.into_inner());
+        batch.push(enriched);
+        batch.len() == 1
+    }
+
+    /// Sends the current batch to the listener, leaving out results `keep` rejects
+    pub fn flush(&self, keep: impl Fn(&EnrichedResult) -> bool) {
+        let batch = std::mem::take(&mut *self.batch.lock().unwrap_or_else(|e| e.into_inner()));
+        let batch: Vec<EnrichedResult> = batch.into_iter().filter(|enriched| keep(enriched)).collect();
+        if batch.is_empty() {
+            return;
+        }
+
+        let listener = self.listener.read().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some(listener) = listener {
+            listener(&batch);
+        }
+    }
+}
+
+impl Default for EnrichmentPipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::search::{QueryContext, SearchEngine, SearchProvider};
+    use crate::types::{ResultAction, ResultType, SearchResult};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Provider whose results all need enrichment, which takes `delay`
+    struct SlowIconProvider {
+        name: &'static str,
+        count: usize,
+        delay: Duration,
+        enriched: Mutex<Vec<String>>,
+        running: AtomicUsize,
+        most_running: AtomicUsize,
+    }
+
+    impl SlowIconProvider {
+        fn new(name: &'static str, count: usize, delay: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                count,
+                delay,
+                enriched: Mutex::new(Vec::new()),
+                running: AtomicUsize::new(0),
+                most_running: AtomicUsize::new(0),
+            })
+        }
+
+        fn enriched(&self) -> Vec<String> {
+            self.enriched.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl SearchProvider for SlowIconProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok((0..self.count)
+                .map(|i| SearchResult {
+                    id: format!("{}:{}", self.name, i),
+                    title: format!("test {} {}", self.name, i),
+                    subtitle: String::new(),
+                    icon: Some("file".to_string()),
+                    result_type: ResultType::File,
+                    score: 50.0 - i as f64,
+                    metadata: HashMap::new(),
+                    action: ResultAction::OpenFile { path: format!("C:\\{}\\{}.txt", self.name, i) },
+                    normalized_title: None,
+                    enrichment_pending: true,
+                    integrity: None,
+                })
+                .collect())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+
+        async fn enrich(&self, result: &mut SearchResult) -> Result<()> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.most_running.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            result.icon = Some(format!("data:{}", self.name));
+            self.enriched.lock().unwrap().push(result.id.clone());
+            Ok(())
+        }
+    }
+
+    async fn engine_with(providers: &[Arc<SlowIconProvider>]) -> (Arc<SearchEngine>, Arc<Mutex<Vec<Vec<EnrichedResult>>>>) {
+        let engine = Arc::new(SearchEngine::new());
+        for provider in providers {
+            engine.register_provider(Arc::clone(provider) as Arc<dyn SearchProvider>).await;
+        }
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&batches);
+        engine.set_enrichment_listener(move |batch| received.lock().unwrap().push(batch.to_vec()));
+        (engine, batches)
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<String> {
+        results.iter().map(|r| r.id.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_results_enriched_together_arrive_in_one_batch() {
+        let provider = SlowIconProvider::new("icons", 3, Duration::from_millis(5));
+        let (engine, batches) = engine_with(&[Arc::clone(&provider)]).await;
+
+        let response = engine.search_paged("test").await;
+        assert!(response.results.iter().all(|r| r.enrichment_pending));
+        let search_id = response.search_id.expect("pending results keep their search");
+
+        assert_eq!(engine.request_enrichment(&search_id, ids(&response.results)), 3);
+        // Asking again doesn't enrich the same results twice
+        assert_eq!(engine.request_enrichment(&search_id, ids(&response.results)), 0);
+        tokio::time::sleep(ENRICHMENT_BATCH_INTERVAL * 3).await;
+
+        let batches = batches.lock().unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].len(), 3);
+        assert!(batches[0].iter().all(|e| e.search_id == search_id && !e.result.enrichment_pending));
+        assert!(batches[0].iter().all(|e| e.result.icon.as_deref() == Some("data:icons")));
+
+        // Later pages serve the enriched results
+        let page = engine.results_page(&search_id, 0, 3).unwrap();
+        assert!(page.results.iter().all(|r| !r.enrichment_pending));
+    }
+
+    #[tokio::test]
+    async fn test_no_more_than_four_results_are_enriched_at_once() {
+        let provider = SlowIconProvider::new("icons", 12, Duration::from_millis(20));
+        let (engine, batches) = engine_with(&[Arc::clone(&provider)]).await;
+
+        let response = engine.search_paged("test").await;
+        let search_id = response.search_id.unwrap();
+        // Results past the first page can be enriched as they're paged in
+        let all: Vec<String> = (0..12).map(|i| format!("icons:{}", i)).collect();
+        assert_eq!(engine.request_enrichment(&search_id, all), 12);
+        tokio::time::sleep(Duration::from_millis(400)).await;
+
+        assert_eq!(provider.enriched().len(), 12);
+        assert_eq!(provider.most_running.load(Ordering::SeqCst), ENRICHMENT_WORKERS);
+        let sent: usize = batches.lock().unwrap().iter().map(Vec::len).sum();
+        assert_eq!(sent, 12);
+    }
+
+    #[tokio::test]
+    async fn test_each_result_goes_to_the_provider_that_returned_it() {
+        let files = SlowIconProvider::new("files", 2, Duration::ZERO);
+        let links = SlowIconProvider::new("links", 2, Duration::ZERO);
+        let (engine, _) = engine_with(&[Arc::clone(&files), Arc::clone(&links)]).await;
+
+        let response = engine.search_paged("test").await;
+        let search_id = response.search_id.unwrap();
+        engine.request_enrichment(&search_id, vec!["files:1".to_string(), "links:0".to_string()]);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(files.enriched(), vec!["files:1"]);
+        assert_eq!(links.enriched(), vec!["links:0"]);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_searches_are_not_enriched() {
+        let provider = SlowIconProvider::new("icons", 8, Duration::from_millis(30));
+        let (engine, batches) = engine_with(&[Arc::clone(&provider)]).await;
+
+        let response = engine.search_paged("test").await;
+        let search_id = response.search_id.unwrap();
+        engine.request_enrichment(&search_id, ids(&response.results));
+
+        // The user typed on: the first four are already running, the rest never start
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(engine.release_search(&search_id));
+        tokio::time::sleep(ENRICHMENT_BATCH_INTERVAL * 2).await;
+
+        assert_eq!(provider.enriched().len(), ENRICHMENT_WORKERS);
+        assert!(batches.lock().unwrap().is_empty());
+        assert_eq!(engine.request_enrichment(&search_id, ids(&response.results)), 0);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::types::{ProviderFacet, ResultFacets, ResultType, SearchResult, TypeFacet};
+use std::collections::HashMap;
+
+/// Metadata key holding the name of the provider that returned a result
+pub const FACET_PROVIDER_KEY: &str = "provider";
+
+/// Returns whether a result of `result_type` is on a page narrowed to `active`
+///
+/// No active facets means every type.
+pub fn accepts(active: &[ResultType], result_type: ResultType) -> bool {
+    active.is_empty() || active.contains(&result_type)
+}
+
+/// Counts `results` per type and provider in one pass
+///
+/// `shown` and `truncated` describe the page holding the first `page_size`
+/// results of the `active` types. Returns `None` when that page is empty, as
+/// chips are only shown next to results.
+pub fn summarize(results: &[SearchResult], active: &[ResultType], page_size: usize) -> Option<ResultFacets> {
+    let mut facets = ResultFacets::default();
+    let mut type_index: HashMap<ResultType, usize> = HashMap::new();
+    let mut provider_index: HashMap<&str, usize> = HashMap::new();
+    let mut accepted = 0;
+
+    for result in results {
+        let index = *type_index.entry(result.result_type).or_insert_with(|| {
+            facets.types.push(TypeFacet {
+                result_type: result.result_type,
+                count: 0,
+                shown: 0,
+                truncated: false,
+            });
+            facets.types.len() - 1
+        });
+        let facet = &mut facets.types[index];
+        facet.count += 1;
+
+        if accepts(active, result.result_type) {
+            if accepted < page_size {
+                facet.shown += 1;
+            }
+            accepted += 1;
+        }
+
+        // Results the engine adds itself, such as syntax hints, have no provider
+        if let Some(provider) = result.metadata.get(FACET_PROVIDER_KEY).and_then(|v| v.as_str()) {
+            let index = *provider_index.entry(provider).or_insert_with(|| {
+                facets.providers.push(ProviderFacet {
+                    provider: provider.to_string(),
+                    count: 0,
+                });
+                facets.providers.len() - 1
+            });
+            facets.providers[index].count += 1;
+        }
+    }
+
+    // Types narrowed out of the page count as truncated too, so their chip
+    // can tell there is more to see
+    for facet in &mut facets.types {
+        facet.truncated = facet.shown < facet.count;
+    }
+
+    (accepted > 0 && page_size > 0).then_some(facets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultAction;
+
+    fn result(id: &str, result_type: ResultType, provider: Option<&str>) -> SearchResult {
+        let mut metadata = HashMap::new();
+        if let Some(provider) = provider {
+            metadata.insert(FACET_PROVIDER_KEY.to_string(), serde_json::json!(provider));
+        }
+        SearchResult {
+            id: id.to_string(),
+            title: id.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type,
+            score: 50.0,
+            metadata,
+            action: ResultAction::OpenFile { path: format!("/{}", id) },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_in_rank_order() {
+        let results = vec![
+            result("app", ResultType::Application, Some("Apps")),
+            result("file-1", ResultType::File, Some("Files")),
+            result("recent", ResultType::File, Some("Recent")),
+            result("file-2", ResultType::File, Some("Files")),
+            result("hint", ResultType::Suggestion, None),
+        ];
+
+        let facets = summarize(&results, &[], 3).unwrap();
+        let types: Vec<_> = facets.types.iter().map(|f| (f.result_type, f.count, f.shown, f.truncated)).collect();
+        assert_eq!(
+            types,
+            vec![
+                (ResultType::Application, 1, 1, false),
+                (ResultType::File, 3, 2, true),
+                (ResultType::Suggestion, 1, 0, true),
+            ]
+        );
+        let providers: Vec<_> = facets.providers.iter().map(|f| (f.provider.as_str(), f.count)).collect();
+        assert_eq!(providers, vec![("Apps", 1), ("Files", 2), ("Recent", 1)]);
+
+        // Narrowed, other types still count but none of them is shown
+        let narrowed = summarize(&results, &[ResultType::File], 3).unwrap();
+        assert_eq!(narrowed.types[0].shown, 0);
+        assert!(narrowed.types[0].truncated);
+        assert_eq!((narrowed.types[1].shown, narrowed.types[1].truncated), (3, false));
+
+        assert!(summarize(&results, &[ResultType::Hash], 3).is_none());
+        assert!(summarize(&[], &[], 3).is_none());
+    }
+}
@@ -0,0 +1,414 @@
+use crate::error::{LauncherError, Result};
+use crate::utils::db_integrity::{self, CheckPolicy, IntegrityOutcome};
+use crate::utils::normalize_for_search;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock, RwLockReadGuard};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Identifier of the "Not useful" secondary action
+pub const NOT_USEFUL_ACTION_ID: &str = "not_useful";
+
+/// Points one demotion takes off a result's score
+pub const DEMOTION_PENALTY: f64 = 50.0;
+
+/// Time after which a demotion only costs half its penalty
+pub const PENALTY_HALF_LIFE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Demotions of one result for one query after which it's hidden for that query
+pub const SUPPRESS_AFTER_DEMOTIONS: u32 = 3;
+
+/// Shortest query whose demotions carry over to longer or shorter queries
+const MIN_FAMILY_LENGTH: usize = 3;
+
+/// The `secondary_actions` entry for demoting a result
+pub fn not_useful_action() -> serde_json::Value {
+    serde_json::json!({
+        "id": NOT_USEFUL_ACTION_ID,
+        "title": "Not useful",
+        "requires_confirmation": false,
+    })
+}
+
+/// Penalty of `demotions` whose latest is `age` old
+///
+/// Each demotion takes `DEMOTION_PENALTY` points, halving every
+/// `PENALTY_HALF_LIFE`.
+pub fn decayed_penalty(demotions: u32, age: Duration) -> f64 {
+    let half_lives = age.as_secs_f64() / PENALTY_HALF_LIFE.as_secs_f64();
+    DEMOTION_PENALTY * f64::from(demotions) * 0.5f64.powf(half_lives)
+}
+
+/// Normalizes a query the way demotions are recorded
+pub fn normalize_query(query: &str) -> String {
+    normalize_for_search(query).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether demotions recorded for `recorded` apply while typing `typed`
+///
+/// Queries are one family when one starts with the other, so "chro" inherits
+/// demotions recorded for "chrome". The shorter one needs a few characters,
+/// so a single letter doesn't pick up everything.
+pub fn same_family(typed: &str, recorded: &str) -> bool {
+    if typed == recorded {
+        return true;
+    }
+
+    let (shorter, longer) = if typed.len() < recorded.len() {
+        (typed, recorded)
+    } else {
+        (recorded, typed)
+    };
+    shorter.chars().count() >= MIN_FAMILY_LENGTH && longer.starts_with(shorter)
+}
+
+/// How demotions affect one result for a query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct FeedbackPenalty {
+    /// Demotions recorded for the query's family
+    pub demotions: u32,
+    /// Points taken off the result's score
+    pub penalty: f64,
+    /// Whether the result is hidden for the query
+    pub suppressed: bool,
+}
+
+/// Demotions of one result for one normalized query
+#[derive(Debug, Clone, PartialEq)]
+struct Demotion {
+    query: String,
+    count: u32,
+    last_demoted: DateTime<Utc>,
+}
+
+/// Demotions by result id
+type DemotionMap = HashMap<String, Vec<Demotion>>;
+
+/// Results the user marked "Not useful" for a query
+///
+/// Demotions are kept in memory, where ranking reads them, and written
+/// through to a small SQLite table so they survive restarts. Searches never
+/// touch the database.
+pub struct ResultFeedback {
+    demotions: RwLock<DemotionMap>,
+    /// Database demotions are written to; they only live in memory without one
+    conn: Option<Mutex<Connection>>,
+    /// Path of that database
+    path: Option<PathBuf>,
+}
+
+impl ResultFeedback {
+    /// Creates feedback kept only in memory
+    pub fn in_memory() -> Self {
+        Self {
+            demotions: RwLock::new(HashMap::new()),
+            conn: None,
+            path: None,
+        }
+    }
+
+    /// Opens the feedback database under `%APPDATA%\BetterFinder`
+    ///
+    /// Falls back to memory when the database can't be opened.
+    pub fn load() -> Self {
+        let Some(app_data) = std::env::var_os("APPDATA") else {
+            warn!("APPDATA not found, result feedback is not remembered");
+            return Self::in_memory();
+        };
+
+        let path = PathBuf::from(app_data).join("BetterFinder").join("feedback.db");
+        Self::open(&path).unwrap_or_else(|e| {
+            warn!("Failed to open result feedback {}: {}", path.display(), e);
+            Self::in_memory()
+        })
+    }
+
+    /// Opens the feedback database at `path`, loading its demotions
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Self::connect(path)?;
+        let demotions = Self::read_demotions(&conn)?;
+        info!("Loaded result feedback for {} results", demotions.len());
+
+        Ok(Self {
+            demotions: RwLock::new(demotions),
+            conn: Some(Mutex::new(conn)),
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    fn connect(path: &Path) -> Result<Connection> {
+        let conn = Connection::open(path)?;
+        db_integrity::configure_durability(&conn)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS demotions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                query TEXT NOT NULL,
+                result_id TEXT NOT NULL,
+                demoted_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(conn)
+    }
+
+    /// Checks the feedback database for damage, rebuilding it from its readable rows
+    ///
+    /// Ranking reads the demotions loaded into memory, so only those lost
+    /// with the damaged rows are gone after the next restart.
+    pub fn check_integrity(&self, now: DateTime<Utc>, policy: CheckPolicy) -> Result<IntegrityOutcome> {
+        let (Some(conn), Some(path)) = (&self.conn, &self.path) else {
+            return Ok(IntegrityOutcome::Skipped);
+        };
+
+        let mut conn = conn.lock().unwrap_or_else(|e| e.into_inner());
+        drop(std::mem::replace(&mut *conn, Connection::open_in_memory()?));
+        let outcome = db_integrity::check_database(path, now, policy);
+        *conn = Self::connect(path)?;
+        outcome
+    }
+
+    fn read_demotions(conn: &Connection) -> Result<DemotionMap> {
+        let mut stmt = conn.prepare(
+            "SELECT query, result_id, COUNT(*), MAX(demoted_at)
+             FROM demotions
+             GROUP BY query, result_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut demotions = DemotionMap::new();
+        for row in rows {
+            let (query, result_id, count, demoted_at) = row?;
+            let Ok(last_demoted) = DateTime::parse_from_rfc3339(&demoted_at) else {
+                warn!("Skipping demotion of '{}' with unreadable time '{}'", result_id, demoted_at);
+                continue;
+            };
+            demotions.entry(result_id).or_default().push(Demotion {
+                query,
+                count,
+                last_demoted: last_demoted.with_timezone(&Utc),
+            });
+        }
+        Ok(demotions)
+    }
+
+    /// Records that `result_id` was not useful for `query`
+    ///
+    /// Returns how the result ranks for the query from now on.
+    pub fn demote(&self, query: &str, result_id: &str) -> Result<FeedbackPenalty> {
+        self.demote_at(query, result_id, Utc::now())
+    }
+
+    fn demote_at(&self, query: &str, result_id: &str, at: DateTime<Utc>) -> Result<FeedbackPenalty> {
+        let query = normalize_query(query);
+        if query.is_empty() {
+            return Err(LauncherError::SearchError("Can't demote a result for an empty query".to_string()));
+        }
+
+        if let Some(conn) = &self.conn {
+            conn.lock().unwrap_or_else(|e| e.into_inner()).execute(
+                "INSERT INTO demotions (query, result_id, demoted_at) VALUES (?1, ?2, ?3)",
+                params![query, result_id, at.to_rfc3339()],
+            )?;
+        }
+
+        {
+            let mut demotions = self.demotions.write().unwrap_or_else(|e| e.into_inner());
+            let entries = demotions.entry(result_id.to_string()).or_default();
+            match entries.iter_mut().find(|demotion| demotion.query == query) {
+                Some(demotion) => {
+                    demotion.count += 1;
+                    demotion.last_demoted = demotion.last_demoted.max(at);
+                }
+                None => entries.push(Demotion {
+                    query: query.clone(),
+                    count: 1,
+                    last_demoted: at,
+                }),
+            }
+        }
+
+        info!("Demoted '{}' for query '{}'", result_id, query);
+        Ok(self.view_at(&query, at).penalty(result_id).unwrap_or_default())
+    }
+
+    /// Forgets every demotion, returning how many results had any
+    pub fn clear(&self) -> Result<usize> {
+        if let Some(conn) = &self.conn {
+            conn.lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .execute("DELETE FROM demotions", [])?;
+        }
+
+        let mut demotions = self.demotions.write().unwrap_or_else(|e| e.into_inner());
+        let cleared = demotions.len();
+        demotions.clear();
+        info!("Cleared result feedback for {} results", cleared);
+        Ok(cleared)
+    }
+
+    /// Whether no result was demoted
+    pub fn is_empty(&self) -> bool {
+        self.demotions.read().unwrap_or_else(|e| e.into_inner()).is_empty()
+    }
+
+    /// Reads the demotions that apply to `query`, for ranking its results
+    pub fn view(&self, query: &str) -> FeedbackView<'_> {
+        self.view_at(&normalize_query(query), Utc::now())
+    }
+
+    fn view_at(&self, normalized_query: &str, now: DateTime<Utc>) -> FeedbackView<'_> {
+        FeedbackView {
+            demotions: self.demotions.read().unwrap_or_else(|e| e.into_inner()),
+            query: normalized_query.to_string(),
+            now,
+        }
+    }
+}
+
+impl Default for ResultFeedback {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+/// Demotions applying to one query, held while its results are ranked
+pub struct FeedbackView<'a> {
+    demotions: RwLockReadGuard<'a, DemotionMap>,
+    query: String,
+    now: DateTime<Utc>,
+}
+
+impl FeedbackView<'_> {
+    /// How demotions affect `result_id`, or `None` if it has none for the query
+    pub fn penalty(&self, result_id: &str) -> Option<FeedbackPenalty> {
+        let mut penalty = FeedbackPenalty::default();
+        for demotion in self.demotions.get(result_id)? {
+            if !same_family(&self.query, &demotion.query) {
+                continue;
+            }
+
+            let age = (self.now - demotion.last_demoted).to_std().unwrap_or_default();
+            penalty.demotions += demotion.count;
+            penalty.penalty += decayed_penalty(demotion.count, age);
+            penalty.suppressed |= demotion.count >= SUPPRESS_AFTER_DEMOTIONS;
+        }
+
+        (penalty.demotions > 0).then_some(penalty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: Duration = Duration::from_secs(24 * 3600);
+
+    fn temp_database(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_penalty_halves_every_half_life() {
+        assert_eq!(decayed_penalty(1, Duration::ZERO), DEMOTION_PENALTY);
+        assert_eq!(decayed_penalty(2, Duration::ZERO), 2.0 * DEMOTION_PENALTY);
+        assert!((decayed_penalty(1, PENALTY_HALF_LIFE) - DEMOTION_PENALTY / 2.0).abs() < 1e-9);
+        assert!((decayed_penalty(2, PENALTY_HALF_LIFE * 2) - DEMOTION_PENALTY / 2.0).abs() < 1e-9);
+        assert!(decayed_penalty(1, DAY * 365) < 1.0);
+    }
+
+    #[test]
+    fn test_penalty_decays_from_latest_demotion() {
+        let feedback = ResultFeedback::in_memory();
+        let start = Utc::now() - chrono::Duration::days(60);
+        feedback.demote_at("chrome", "app:chromium", start).unwrap();
+
+        let view = feedback.view("chrome");
+        let penalty = view.penalty("app:chromium").unwrap();
+        assert_eq!(penalty.demotions, 1);
+        assert!((penalty.penalty - DEMOTION_PENALTY / 4.0).abs() < 0.01);
+        drop(view);
+
+        // A fresh demotion restarts the decay for both
+        feedback.demote("chrome", "app:chromium").unwrap();
+        let penalty = feedback.view("chrome").penalty("app:chromium").unwrap();
+        assert_eq!(penalty.demotions, 2);
+        assert!((penalty.penalty - 2.0 * DEMOTION_PENALTY).abs() < 0.01);
+        assert!(!penalty.suppressed);
+    }
+
+    #[test]
+    fn test_third_demotion_suppresses_result() {
+        let feedback = ResultFeedback::in_memory();
+        for demotions in 1..SUPPRESS_AFTER_DEMOTIONS {
+            let penalty = feedback.demote("Chrome", "app:chromium").unwrap();
+            assert_eq!(penalty.demotions, demotions);
+            assert!(!penalty.suppressed);
+        }
+
+        assert!(feedback.demote("chrome ", "app:chromium").unwrap().suppressed);
+        assert!(feedback.view("chrome").penalty("app:chromium").unwrap().suppressed);
+        assert_eq!(feedback.view("chrome").penalty("app:chrome"), None);
+        assert!(feedback.demote("  ", "app:chromium").is_err());
+    }
+
+    #[test]
+    fn test_demotions_apply_to_query_family() {
+        let feedback = ResultFeedback::in_memory();
+        for _ in 0..SUPPRESS_AFTER_DEMOTIONS {
+            feedback.demote("chrome", "app:chromium").unwrap();
+        }
+
+        assert!(feedback.view("chro").penalty("app:chromium").unwrap().suppressed);
+        assert!(feedback.view("Chrome Canary").penalty("app:chromium").unwrap().suppressed);
+        assert_eq!(feedback.view("ch").penalty("app:chromium"), None);
+        assert_eq!(feedback.view("chat").penalty("app:chromium"), None);
+
+        assert!(same_family("chrome", "chrome"));
+        assert!(same_family("chro", "chrome"));
+        assert!(!same_family("c", "chrome"));
+        assert!(!same_family("chrome", "chromium"));
+    }
+
+    #[test]
+    fn test_demotions_survive_reopening() {
+        let path = temp_database("result_feedback_round_trip");
+        let earlier = Utc::now() - chrono::Duration::days(30);
+        {
+            let feedback = ResultFeedback::open(&path).unwrap();
+            feedback.demote_at("notepad", "app:notepad++", earlier).unwrap();
+            feedback.demote_at("notepad", "app:notepad++", earlier).unwrap();
+            feedback.demote("paint", "app:mspaint").unwrap();
+        }
+
+        let feedback = ResultFeedback::open(&path).unwrap();
+        let penalty = feedback.view("notepad").penalty("app:notepad++").unwrap();
+        assert_eq!(penalty.demotions, 2);
+        assert!((penalty.penalty - DEMOTION_PENALTY).abs() < 0.01);
+        assert_eq!(feedback.view("paint").penalty("app:mspaint").unwrap().demotions, 1);
+
+        assert_eq!(feedback.clear().unwrap(), 2);
+        assert!(feedback.is_empty());
+        drop(feedback);
+        assert!(ResultFeedback::open(&path).unwrap().is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+}
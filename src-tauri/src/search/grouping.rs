@@ -0,0 +1,271 @@
+/// Collapses many file results from one folder into a single expandable row
+///
+/// Common names such as `index.ts` match dozens of files spread over several
+/// projects. After ranking, file results past the first few are keyed by
+/// their project root (the nearest folder holding `.git` or `package.json`)
+/// or, outside a project, by their parent folder. A key shared by more than
+/// `GROUP_THRESHOLD` results becomes one `FileGroup` result that carries its
+/// members in metadata and takes the place of its best-ranked member.
+
+use crate::types::{ResultAction, ResultType, SearchResult};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A folder needs more than this many file results to be collapsed
+pub const GROUP_THRESHOLD: usize = 3;
+
+/// The best-ranked file results are always shown on their own
+pub const UNGROUPED_LEADING_FILES: usize = 3;
+
+/// Metadata key holding a group's member results, best first
+pub const GROUP_MEMBERS_KEY: &str = "members";
+
+/// Entries marking a folder as the root of a project
+const PROJECT_MARKERS: &[&str] = &[".git", "package.json"];
+
+/// Returns the nearest folder above `path` that holds a project marker
+pub fn find_project_root(path: &Path) -> Option<PathBuf> {
+    path.parent()?
+        .ancestors()
+        .find(|dir| PROJECT_MARKERS.iter().any(|marker| dir.join(marker).exists()))
+        .map(Path::to_path_buf)
+}
+
+/// Finds the folder a file result is grouped under, remembering project
+/// roots so files in the same folder walk up the tree only once
+#[derive(Debug, Default)]
+pub struct GroupFolders {
+    roots: HashMap<PathBuf, Option<PathBuf>>,
+}
+
+impl GroupFolders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The project root containing `path`, or else its parent folder
+    pub fn folder_of(&mut self, path: &str) -> Option<PathBuf> {
+        let parent = Path::new(path).parent()?;
+        let root = self
+            .roots
+            .entry(parent.to_path_buf())
+            .or_insert_with(|| find_project_root(path.as_ref()));
+
+        Some(root.clone().unwrap_or_else(|| parent.to_path_buf()))
+    }
+}
+
+/// Where a result ends up once groups are formed
+enum Slot {
+    Single(Box<SearchResult>),
+    Group(PathBuf),
+}
+
+/// Collapses ranked file results sharing a folder into `FileGroup` results
+///
+/// `folder_of` maps a file path to its grouping folder. Pinned results and
+/// the first `UNGROUPED_LEADING_FILES` file results are never grouped. Each
+/// group sits where its best member ranked and takes that member's score.
+pub fn group_file_results<F>(results: Vec<SearchResult>, mut folder_of: F) -> Vec<SearchResult>
+where
+    F: FnMut(&str) -> Option<PathBuf>,
+{
+    let mut files_seen = 0;
+    let keys: Vec<Option<PathBuf>> = results
+        .iter()
+        .map(|result| {
+            if result.result_type != ResultType::File || result.is_pinned() {
+                return None;
+            }
+            files_seen += 1;
+            if files_seen <= UNGROUPED_LEADING_FILES {
+                return None;
+            }
+            result.file_path().and_then(&mut folder_of)
+        })
+        .collect();
+
+    let mut counts: HashMap<PathBuf, usize> = HashMap::new();
+    for key in keys.iter().flatten() {
+        *counts.entry(key.clone()).or_default() += 1;
+    }
+    if counts.values().all(|&count| count <= GROUP_THRESHOLD) {
+        return results;
+    }
+
+    let mut slots = Vec::with_capacity(results.len());
+    let mut members: HashMap<PathBuf, Vec<SearchResult>> = HashMap::new();
+    for (result, key) in results.into_iter().zip(keys) {
+        match key.filter(|key| counts[key] > GROUP_THRESHOLD) {
+            Some(key) => {
+                let group = members.entry(key.clone()).or_default();
+                if group.is_empty() {
+                    slots.push(Slot::Group(key));
+                }
+                group.push(result);
+            }
+            None => slots.push(Slot::Single(Box::new(result))),
+        }
+    }
+
+    slots
+        .into_iter()
+        .map(|slot| match slot {
+            Slot::Single(result) => *result,
+            Slot::Group(folder) => {
+                let group = members.remove(&folder).unwrap_or_default();
+                file_group_result(&folder, group)
+            }
+        })
+        .collect()
+}
+
+/// Builds the row standing in for the files found under `folder`
+fn file_group_result(folder: &Path, members: Vec<SearchResult>) -> SearchResult {
+    let folder_display = folder.display().to_string();
+    let id = format!("file_group:{}", folder_display.to_lowercase());
+    let score = members.iter().map(|member| member.score).fold(f64::MIN, f64::max);
+
+    let mut metadata = HashMap::new();
+    metadata.insert("path".to_string(), serde_json::json!(folder_display));
+    metadata.insert("member_count".to_string(), serde_json::json!(members.len()));
+    metadata.insert(
+        GROUP_MEMBERS_KEY.to_string(),
+        serde_json::to_value(&members).unwrap_or_default(),
+    );
+
+    SearchResult {
+        title: folder_display,
+        subtitle: format!("{} matches", members.len()),
+        icon: None,
+        result_type: ResultType::FileGroup,
+        score,
+        metadata,
+        action: ResultAction::ExpandGroup { group_id: id.clone() },
+        id,
+        normalized_title: None,
+        enrichment_pending: false,
+        integrity: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(folder: &str, name: &str, score: f64) -> SearchResult {
+        let path = format!("{}/{}", folder, name);
+        SearchResult {
+            id: format!("file:{}", path),
+            title: name.to_string(),
+            subtitle: path.clone(),
+            icon: None,
+            result_type: ResultType::File,
+            score,
+            metadata: HashMap::new(),
+            action: ResultAction::OpenFile { path },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    fn by_parent(path: &str) -> Option<PathBuf> {
+        Path::new(path).parent().map(Path::to_path_buf)
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_find_project_root() {
+        let base = std::env::temp_dir().join("better_finder_grouping_roots");
+        let _ = std::fs::remove_dir_all(&base);
+        let package = base.join("web").join("src").join("components");
+        let repo = base.join("api").join("src");
+        let loose = base.join("downloads");
+        for dir in [&package, &repo, &loose, &base.join("api").join(".git")] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        std::fs::write(base.join("web").join("package.json"), "{}").unwrap();
+
+        assert_eq!(find_project_root(&package.join("index.ts")), Some(base.join("web")));
+        assert_eq!(find_project_root(&repo.join("main.rs")), Some(base.join("api")));
+        assert_eq!(find_project_root(&loose.join("index.ts")), None);
+
+        let mut folders = GroupFolders::new();
+        let path = package.join("index.ts").display().to_string();
+        assert_eq!(folders.folder_of(&path), Some(base.join("web")));
+        let path = loose.join("index.ts").display().to_string();
+        assert_eq!(folders.folder_of(&path), Some(loose));
+
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_groups_only_above_threshold_and_after_leading_files() {
+        let mut results = vec![
+            file("/web", "index.ts", 90.0),
+            file("/web", "a/index.ts", 89.0),
+            file("/web", "b/index.ts", 88.0),
+        ];
+        // Exactly GROUP_THRESHOLD files in one folder stay separate
+        results.extend((0..GROUP_THRESHOLD).map(|i| file("/api", &format!("index{}.ts", i), 80.0 - i as f64)));
+        let unchanged = group_file_results(results.clone(), by_parent);
+        assert_eq!(ids(&unchanged), ids(&results));
+
+        // One more collapses them, but the leading files stay on their own
+        results.push(file("/api", "index9.ts", 70.0));
+        let grouped = group_file_results(results, by_parent);
+        assert_eq!(grouped.len(), 4);
+        assert_eq!(ids(&grouped[..3]), vec!["file:/web/index.ts", "file:/web/a/index.ts", "file:/web/b/index.ts"]);
+
+        let group = &grouped[3];
+        assert_eq!(group.result_type, ResultType::FileGroup);
+        assert_eq!(group.title, "/api");
+        assert_eq!(group.subtitle, "4 matches");
+        let members = group.metadata[GROUP_MEMBERS_KEY].as_array().unwrap();
+        assert_eq!(members.len(), 4);
+        assert_eq!(members[0]["id"], "file:/api/index0.ts");
+        assert!(matches!(&group.action, ResultAction::ExpandGroup { group_id } if *group_id == group.id));
+    }
+
+    #[test]
+    fn test_group_takes_best_member_position_and_score() {
+        let mut results: Vec<SearchResult> = (0..UNGROUPED_LEADING_FILES)
+            .map(|i| file("/top", &format!("f{}.ts", i), 100.0 - i as f64))
+            .collect();
+        results.push(file("/api", "index.ts", 80.0));
+        results.push(file("/other", "index.ts", 75.0));
+        results.extend((1..5).map(|i| file("/api", &format!("index{}.ts", i), 60.0 - i as f64)));
+        results.push(SearchResult {
+            result_type: ResultType::Application,
+            ..file("/apps", "Index.exe", 70.0)
+        });
+
+        let grouped = group_file_results(results, by_parent);
+        let order: Vec<&str> = grouped[UNGROUPED_LEADING_FILES..].iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(order, vec!["file_group:/api", "file:/other/index.ts", "file:/apps/Index.exe"]);
+        assert_eq!(grouped[UNGROUPED_LEADING_FILES].score, 80.0);
+    }
+
+    #[test]
+    fn test_pinned_and_non_file_results_are_never_grouped() {
+        let mut results: Vec<SearchResult> = (0..UNGROUPED_LEADING_FILES)
+            .map(|i| file("/top", &format!("f{}.ts", i), 100.0 - i as f64))
+            .collect();
+        for i in 0..GROUP_THRESHOLD + 2 {
+            let mut result = file("/api", &format!("index{}.ts", i), 50.0);
+            if i == 0 {
+                result.metadata.insert("pinned".to_string(), serde_json::json!(true));
+            } else if i == 1 {
+                result.result_type = ResultType::RecentFile;
+            }
+            results.push(result);
+        }
+
+        let grouped = group_file_results(results.clone(), by_parent);
+        assert_eq!(ids(&grouped), ids(&results));
+    }
+}
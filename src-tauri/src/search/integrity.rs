@@ -0,0 +1,187 @@
+use crate::error::{LauncherError, Result};
+use crate::types::{ResultAction, SearchResult};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signs the results the engine produces, so results the webview sends back
+/// for execution can be checked against what a search actually returned
+///
+/// The token is an HMAC over the result's id and action, keyed with a random
+/// key that lives only in this process. Everything else about a result may
+/// change on its way through the frontend; the id and action may not. As the
+/// metadata isn't covered, only signed results may be handed to anything that
+/// reads it, such as revealing or recycling a file.
+pub struct ResultSigner {
+    key: [u8; 32],
+}
+
+impl ResultSigner {
+    /// Creates a signer with a fresh random key, valid for this session only
+    pub fn new() -> Self {
+        Self::with_key(rand::random())
+    }
+
+    /// Creates a signer with a fixed key
+    pub fn with_key(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    fn mac(&self, result: &SearchResult) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(result.id.as_bytes());
+        // The separator keeps an id ending like an action from passing for another result
+        mac.update(&[0]);
+        // Field order is fixed by the enum, so the same action always serializes the same
+        mac.update(&serde_json::to_vec(&result.action).unwrap_or_default());
+        mac
+    }
+
+    /// Stores the token for the result's current id and action
+    pub fn sign(&self, result: &mut SearchResult) {
+        let token = URL_SAFE_NO_PAD.encode(self.mac(result).finalize().into_bytes());
+        result.integrity = Some(token);
+    }
+
+    /// Checks whether the result carries a valid token
+    pub fn is_signed(&self, result: &SearchResult) -> bool {
+        result
+            .integrity
+            .as_deref()
+            .and_then(|token| URL_SAFE_NO_PAD.decode(token).ok())
+            .is_some_and(|token| self.mac(result).verify_slice(&token).is_ok())
+    }
+
+    /// Accepts signed results only
+    pub fn verify(&self, result: &SearchResult) -> Result<()> {
+        match &result.integrity {
+            Some(_) if self.is_signed(result) => Ok(()),
+            Some(_) => Err(LauncherError::UnverifiedResult(format!(
+                "'{}' was changed after the search returned it",
+                result.id
+            ))),
+            None => Err(LauncherError::UnverifiedResult(format!(
+                "'{}' did not come from a search",
+                result.id
+            ))),
+        }
+    }
+
+    /// Accepts signed results, and unsigned ones whose action is harmless to
+    /// run whatever the rest of the result says
+    ///
+    /// Only for running the result's own action: nothing else about an
+    /// unsigned result can be trusted. A token that doesn't match is rejected
+    /// even for such actions, since the result was changed after it was signed.
+    pub fn verify_execution(&self, result: &SearchResult) -> Result<()> {
+        match &result.integrity {
+            None if is_reconstructible(&result.action) => Ok(()),
+            _ => self.verify(result),
+        }
+    }
+}
+
+impl Default for ResultSigner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Actions the frontend may build itself, as running them can do no more
+/// than the user could by hand
+pub fn is_reconstructible(action: &ResultAction) -> bool {
+    matches!(action, ResultAction::CopyToClipboard { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultType;
+    use std::collections::HashMap;
+
+    fn command_result() -> SearchResult {
+        SearchResult {
+            id: "ssh:build-box".to_string(),
+            title: "build-box".to_string(),
+            subtitle: "build.example.com:22".to_string(),
+            icon: None,
+            result_type: ResultType::Ssh,
+            score: 90.0,
+            metadata: HashMap::new(),
+            action: ResultAction::ExecuteCommand {
+                command: "wt".to_string(),
+                args: vec!["ssh".to_string(), "build.example.com".to_string()],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_token_survives_serde_round_trip() {
+        let signer = ResultSigner::new();
+        let mut result = command_result();
+        signer.sign(&mut result);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert!(json["integrity"].is_string());
+        let mut returned: SearchResult = serde_json::from_value(json).unwrap();
+        // Display fields may change in the frontend, e.g. when enriched
+        returned.title = "Build box".to_string();
+        returned.metadata.insert("selected".to_string(), serde_json::json!(true));
+
+        assert!(signer.verify(&returned).is_ok());
+        assert!(!ResultSigner::new().is_signed(&returned));
+    }
+
+    #[test]
+    fn test_changed_action_or_id_rejected() {
+        let signer = ResultSigner::with_key([7; 32]);
+        let mut result = command_result();
+        signer.sign(&mut result);
+
+        let mut changed_action = result.clone();
+        changed_action.action = ResultAction::ExecuteCommand {
+            command: "powershell".to_string(),
+            args: vec!["-Command".to_string(), "Remove-Item C:\\ -Recurse".to_string()],
+        };
+        assert!(matches!(signer.verify(&changed_action), Err(LauncherError::UnverifiedResult(_))));
+
+        let mut changed_id = result.clone();
+        changed_id.id = "ssh:other".to_string();
+        assert!(signer.verify(&changed_id).is_err());
+
+        let mut unsigned = result.clone();
+        unsigned.integrity = None;
+        assert!(signer.verify(&unsigned).is_err());
+
+        let mut garbage = result;
+        garbage.integrity = Some("not a token!".to_string());
+        assert!(signer.verify(&garbage).is_err());
+    }
+
+    #[test]
+    fn test_copy_to_clipboard_needs_no_token() {
+        let signer = ResultSigner::new();
+        let mut copy = command_result();
+        copy.action = ResultAction::CopyToClipboard {
+            content: "42".to_string(),
+        };
+        assert!(signer.verify_execution(&copy).is_ok());
+        assert!(!signer.is_signed(&copy));
+
+        // Only to be executed: its metadata may say anything
+        assert!(signer.verify(&copy).is_err());
+
+        // ...and a token that no longer matches still fails
+        signer.sign(&mut copy);
+        copy.action = ResultAction::CopyToClipboard {
+            content: "43".to_string(),
+        };
+        assert!(signer.verify_execution(&copy).is_err());
+    }
+}
@@ -0,0 +1,504 @@
+use async_trait::async_trait;
+use crate::error::{LauncherError, Result};
+use crate::search::completion::CompletionCandidate;
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{CacheStats, ProviderHealth, ResultType, SearchResult};
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+type Constructor<P> = Box<dyn FnOnce() -> Result<P> + Send>;
+type ReadyHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+enum LazyState<P> {
+    /// Waiting for the first query that passes the gate
+    Pending(Constructor<P>),
+    /// Constructor and `initialize` running in the background
+    Initializing,
+    Ready(Arc<RwLock<P>>),
+    Failed(String),
+    /// Shut down; never initializes again
+    Stopped,
+}
+
+/// Defers building a heavy provider until a query needs it
+///
+/// Registration only stores the constructor. The first search passing the
+/// gate (minimum length and, if set, a leading keyword) builds and initializes
+/// the provider in the background and returns no results; later searches are
+/// served by the provider once it is ready. The type gate comes for free: the
+/// engine never searches providers whose `result_types` were not requested.
+pub struct LazyProvider<P: SearchProvider> {
+    name: String,
+    priority: u8,
+    result_types: Option<&'static [ResultType]>,
+    min_query_len: usize,
+    keywords: Vec<String>,
+    /// Syntax listed by help before the provider is built
+    syntax: &'static [SyntaxFeature],
+    state: Arc<Mutex<LazyState<P>>>,
+    ready_handler: Option<ReadyHandler>,
+    /// Last `configure` value, applied once the provider is built
+    config: Arc<Mutex<serde_json::Value>>,
+}
+
+impl<P: SearchProvider + 'static> LazyProvider<P> {
+    /// Wraps a provider constructor; `name`, `priority` and `result_types`
+    /// must match what the provider reports
+    pub fn new<F>(name: &str, priority: u8, result_types: Option<&'static [ResultType]>, constructor: F) -> Self
+    where
+        F: FnOnce() -> Result<P> + Send + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            priority,
+            result_types,
+            min_query_len: 1,
+            keywords: Vec::new(),
+            syntax: &[],
+            state: Arc::new(Mutex::new(LazyState::Pending(Box::new(constructor)))),
+            ready_handler: None,
+            config: Arc::new(Mutex::new(serde_json::Value::Null)),
+        }
+    }
+
+    /// Only queries at least this many characters long trigger initialization
+    pub fn with_min_query_len(mut self, min_query_len: usize) -> Self {
+        self.min_query_len = min_query_len;
+        self
+    }
+
+    /// Only queries starting with one of these words trigger initialization
+    pub fn with_keywords(mut self, keywords: &[&str]) -> Self {
+        self.keywords = keywords.iter().map(|k| k.to_lowercase()).collect();
+        self
+    }
+
+    /// Query syntax the provider understands, so help lists it before the
+    /// provider is built
+    pub fn with_syntax(mut self, syntax: &'static [SyntaxFeature]) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    /// Sets a callback run once the provider is ready, e.g. to drop cached
+    /// searches that were answered without it
+    pub fn with_ready_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.ready_handler = Some(Arc::new(handler));
+        self
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, LazyState<P>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the provider if it has been initialized
+    fn ready(&self) -> Option<Arc<RwLock<P>>> {
+        match &*self.lock_state() {
+            LazyState::Ready(inner) => Some(Arc::clone(inner)),
+            _ => None,
+        }
+    }
+
+    /// Whether a query is relevant enough to pay for initialization
+    fn passes_gate(&self, query: &str) -> bool {
+        let query = query.trim();
+        if query.chars().count() < self.min_query_len {
+            return false;
+        }
+
+        self.keywords.is_empty()
+            || query
+                .split_whitespace()
+                .next()
+                .is_some_and(|word| self.keywords.contains(&word.to_lowercase()))
+    }
+
+    /// Builds and initializes the provider in the background, exactly once
+    fn start_initialization(&self) {
+        let constructor = {
+            let mut state = self.lock_state();
+            match std::mem::replace(&mut *state, LazyState::Initializing) {
+                LazyState::Pending(constructor) => constructor,
+                other => {
+                    *state = other;
+                    return;
+                }
+            }
+        };
+
+        let state = Arc::clone(&self.state);
+        let name = self.name.clone();
+        let ready_handler = self.ready_handler.clone();
+        let config = Arc::clone(&self.config);
+        info!("Initializing {} on first use", name);
+
+        tokio::spawn(async move {
+            let start = std::time::Instant::now();
+            let initialized = async {
+                let mut provider = constructor()?;
+                let config = config.lock().unwrap_or_else(|e| e.into_inner()).clone();
+                provider.configure(&config).await?;
+                provider.initialize().await?;
+                Ok::<_, LauncherError>(provider)
+            }
+            .await;
+
+            let mut provider = match initialized {
+                Ok(provider) => provider,
+                Err(e) => {
+                    error!("Failed to initialize {}: {}", name, e);
+                    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                    if matches!(*state, LazyState::Initializing) {
+                        *state = LazyState::Failed(e.to_string());
+                    }
+                    return;
+                }
+            };
+
+            {
+                let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                if matches!(*state, LazyState::Initializing) {
+                    *state = LazyState::Ready(Arc::new(RwLock::new(provider)));
+                    info!("{} initialized in {}ms", name, start.elapsed().as_millis());
+                    drop(state);
+                    if let Some(handler) = ready_handler {
+                        handler(&name);
+                    }
+                    return;
+                }
+            }
+
+            // Shut down while initializing; release what was just set up
+            if let Err(e) = provider.shutdown().await {
+                error!("Failed to shut down {}: {}", name, e);
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<P: SearchProvider + 'static> SearchProvider for LazyProvider<P> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        self.result_types
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if let Some(inner) = self.ready() {
+            return inner.read().await.search(query).await;
+        }
+
+        if self.passes_gate(&query.query) {
+            self.start_initialization();
+        }
+        Ok(Vec::new())
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        match self.ready() {
+            Some(inner) => inner.read().await.execute(result).await,
+            None => Err(LauncherError::ProviderError(format!("{} is not initialized", self.name))),
+        }
+    }
+
+    async fn execute_action(&self, result: &SearchResult, action_id: &str) -> Result<()> {
+        match self.ready() {
+            Some(inner) => inner.read().await.execute_action(result, action_id).await,
+            None => Err(LauncherError::NotFound(format!("{} is not initialized", self.name))),
+        }
+    }
+
+    async fn enrich(&self, result: &mut SearchResult) -> Result<()> {
+        match self.ready() {
+            Some(inner) => inner.read().await.enrich(result).await,
+            None => Err(LauncherError::ProviderError(format!("{} is not initialized", self.name))),
+        }
+    }
+
+    async fn suggestion_terms(&self, limit: usize) -> Vec<String> {
+        match self.ready() {
+            Some(inner) => inner.read().await.suggestion_terms(limit).await,
+            None => Vec::new(),
+        }
+    }
+
+    async fn completion_candidates(&self, query: &str) -> Vec<CompletionCandidate> {
+        match self.ready() {
+            Some(inner) => inner.read().await.completion_candidates(query).await,
+            None => Vec::new(),
+        }
+    }
+
+    async fn fallback_results(&self, query: &QueryContext) -> Vec<SearchResult> {
+        match self.ready() {
+            Some(inner) => inner.read().await.fallback_results(query).await,
+            None => Vec::new(),
+        }
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        self.syntax.to_vec()
+    }
+
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        match self.ready() {
+            Some(inner) => inner.read().await.cache_stats().await,
+            None => Vec::new(),
+        }
+    }
+
+    async fn trim_caches(&self) -> usize {
+        match self.ready() {
+            Some(inner) => inner.read().await.trim_caches().await,
+            None => 0,
+        }
+    }
+
+    async fn refresh_after_resume(&self) {
+        // Nothing is cached before the first search initializes the provider
+        if let Some(inner) = self.ready() {
+            inner.read().await.refresh_after_resume().await;
+        }
+    }
+
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        *self.config.lock().unwrap_or_else(|e| e.into_inner()) = config.clone();
+        match self.ready() {
+            Some(inner) => inner.read().await.configure(config).await,
+            None => Ok(()),
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        self.ready()
+            .and_then(|inner| inner.try_read().ok().map(|provider| provider.cacheable()))
+            .unwrap_or(true)
+    }
+
+    fn handles_blobs(&self) -> bool {
+        self.ready()
+            .and_then(|inner| inner.try_read().ok().map(|provider| provider.handles_blobs()))
+            .unwrap_or(false)
+    }
+
+    fn supports_peek(&self) -> bool {
+        self.ready()
+            .and_then(|inner| inner.try_read().ok().map(|provider| provider.supports_peek()))
+            .unwrap_or(false)
+    }
+
+    fn is_enabled(&self) -> bool {
+        !matches!(self.health(), ProviderHealth::Failed { .. } | ProviderHealth::Disabled)
+    }
+
+    fn health(&self) -> ProviderHealth {
+        let inner = match &*self.lock_state() {
+            LazyState::Pending(_) => return ProviderHealth::NotInitialized,
+            LazyState::Initializing => return ProviderHealth::Initializing,
+            LazyState::Failed(error) => return ProviderHealth::Failed { error: error.clone() },
+            LazyState::Stopped => return ProviderHealth::Disabled,
+            LazyState::Ready(inner) => Arc::clone(inner),
+        };
+
+        // A writer holding the lock means the provider is busy, not unhealthy
+        let health = inner.try_read().map(|provider| provider.health());
+        health.unwrap_or(ProviderHealth::Ready)
+    }
+
+    /// Does nothing; initialization waits for the first relevant query
+    async fn initialize(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        let previous = std::mem::replace(&mut *self.lock_state(), LazyState::Stopped);
+        match previous {
+            LazyState::Ready(inner) => inner.write().await.shutdown().await,
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultAction;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct Counters {
+        constructed: AtomicUsize,
+        initialized: AtomicUsize,
+        shut_down: AtomicUsize,
+        ready: AtomicUsize,
+    }
+
+    /// Provider whose initialization takes a while, counting lifecycle calls
+    struct SlowProvider {
+        counters: Arc<Counters>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl SearchProvider for SlowProvider {
+        fn name(&self) -> &str {
+            "Slow"
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok(vec![SearchResult {
+                id: format!("slow:{}", query.query),
+                title: query.query.clone(),
+                subtitle: String::new(),
+                icon: None,
+                result_type: ResultType::Bookmark,
+                score: 50.0,
+                metadata: HashMap::new(),
+                action: ResultAction::OpenUrl {
+                    url: "https://example.com".to_string(),
+                },
+                normalized_title: None,
+                enrichment_pending: false,
+                integrity: None,
+            }])
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+
+        async fn initialize(&mut self) -> Result<()> {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.counters.initialized.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(LauncherError::ProviderError("no bookmarks file".to_string()));
+            }
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> Result<()> {
+            self.counters.shut_down.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    fn lazy(counters: &Arc<Counters>, fail: bool) -> LazyProvider<SlowProvider> {
+        let for_constructor = Arc::clone(counters);
+        let for_handler = Arc::clone(counters);
+        LazyProvider::new("Slow", 50, Some(&[ResultType::Bookmark]), move || {
+            for_constructor.constructed.fetch_add(1, Ordering::SeqCst);
+            Ok(SlowProvider {
+                counters: for_constructor,
+                fail,
+            })
+        })
+        .with_ready_handler(move |_| {
+            for_handler.ready.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    async fn wait_until_settled(provider: &LazyProvider<SlowProvider>) {
+        for _ in 0..100 {
+            if !matches!(provider.health(), ProviderHealth::Initializing) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("provider never finished initializing");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_first_searches_initialize_once() {
+        let counters = Arc::new(Counters::default());
+        let provider = lazy(&counters, false);
+        assert_eq!(provider.health(), ProviderHealth::NotInitialized);
+        assert_eq!(provider.health().to_string(), "not yet initialized (lazy)");
+
+        // Both first searches come back empty while the provider loads
+        let (github, gitlab) = (QueryContext::from("github"), QueryContext::from("gitlab"));
+        let (first, second) = tokio::join!(provider.search(&github), provider.search(&gitlab));
+        assert!(first.unwrap().is_empty());
+        assert!(second.unwrap().is_empty());
+        assert_eq!(provider.health(), ProviderHealth::Initializing);
+        assert!(provider.search(&"github".into()).await.unwrap().is_empty());
+
+        wait_until_settled(&provider).await;
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+        assert_eq!(counters.constructed.load(Ordering::SeqCst), 1);
+        assert_eq!(counters.initialized.load(Ordering::SeqCst), 1);
+        assert_eq!(counters.ready.load(Ordering::SeqCst), 1);
+
+        let results = provider.search(&"github".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "slow:github");
+        assert_eq!(counters.constructed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_queries_failing_the_gate_do_not_initialize() {
+        let counters = Arc::new(Counters::default());
+        let provider = lazy(&counters, false).with_min_query_len(3).with_keywords(&["bm"]);
+
+        assert!(provider.search(&"bm".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"github".into()).await.unwrap().is_empty());
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(provider.health(), ProviderHealth::NotInitialized);
+        assert_eq!(counters.constructed.load(Ordering::SeqCst), 0);
+
+        assert!(provider.search(&"BM github".into()).await.unwrap().is_empty());
+        wait_until_settled(&provider).await;
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_failed_initialization_disables_provider() {
+        let counters = Arc::new(Counters::default());
+        let provider = lazy(&counters, true);
+
+        provider.search(&"github".into()).await.unwrap();
+        wait_until_settled(&provider).await;
+
+        assert!(matches!(provider.health(), ProviderHealth::Failed { ref error } if error.contains("no bookmarks file")));
+        assert!(!provider.is_enabled());
+        assert_eq!(counters.ready.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_handles_never_initialized_provider() {
+        let counters = Arc::new(Counters::default());
+        let mut provider = lazy(&counters, false);
+
+        provider.shutdown().await.unwrap();
+        assert_eq!(provider.health(), ProviderHealth::Disabled);
+
+        // A stopped wrapper never initializes
+        provider.search(&"github".into()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(counters.constructed.load(Ordering::SeqCst), 0);
+        assert_eq!(counters.shut_down.load(Ordering::SeqCst), 0);
+
+        // An initialized one shuts its provider down
+        let mut provider = lazy(&counters, false);
+        provider.search(&"github".into()).await.unwrap();
+        wait_until_settled(&provider).await;
+        provider.shutdown().await.unwrap();
+        assert_eq!(counters.shut_down.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,7 +1,31 @@
 pub mod provider;
+pub mod lazy_provider;
 pub mod engine;
 pub mod providers;
 pub mod cache;
+pub mod suggestion;
+pub mod completion;
+pub mod grouping;
+pub mod query;
+pub mod provider_config;
+pub mod sessions;
+pub mod web_search;
+pub mod enrichment;
+pub mod syntax;
+pub mod provider_guard;
+pub mod feedback;
+pub mod actions;
+pub mod integrity;
+pub mod progress;
+pub mod duplicates;
+pub mod accessibility;
+pub mod facets;
+pub mod token_match;
+pub mod recent_boost;
+pub mod blob;
+pub mod plain;
+pub mod slots;
+pub mod analytics;
 
 #[cfg(test)]
 mod engine_test;
@@ -9,7 +33,25 @@ mod engine_test;
 #[cfg(test)]
 mod performance_bench;
 
-pub use provider::SearchProvider;
-pub use engine::SearchEngine;
+#[cfg(test)]
+mod test_support;
+
+#[cfg(test)]
+mod ranking_test;
+
+pub use provider::{SearchProvider, SharedProvider};
+pub use lazy_provider::LazyProvider;
+pub use query::QueryContext;
+pub use syntax::{SyntaxFeature, SyntaxRegistry};
+pub use provider_guard::ProviderGuard;
+pub use feedback::{FeedbackPenalty, ResultFeedback};
+pub use analytics::SearchAnalytics;
+pub use actions::ActionDescriptor;
+pub use progress::ExecutionProgress;
+pub use provider_config::{ProviderConfig, ProviderConfigSchema};
+pub use engine::{
+    ScoreBreakdown, SearchEngine, MAX_BATCH_SIZE, PEEK_BUDGET, PEEK_KEY, RECYCLE_ACTION_ID,
+    SCORE_BREAKDOWN_KEY, SECONDARY_ACTIONS_KEY,
+};
 pub use providers::FileSearchProvider;
 pub use cache::ResultCache;
@@ -9,6 +9,7 @@
 mod benchmarks {
     use crate::search::{ResultCache, SearchEngine};
     use crate::types::{ResultAction, ResultType, SearchResult};
+    use crate::utils::normalize_for_search;
     use std::collections::HashMap;
     use std::time::Instant;
 
@@ -26,6 +27,9 @@ mod benchmarks {
                 action: ResultAction::OpenFile {
                     path: format!("/test/file{}.txt", i),
                 },
+                normalized_title: None,
+                enrichment_pending: false,
+                integrity: None,
             })
             .collect()
     }
@@ -203,6 +207,57 @@ mod benchmarks {
         );
     }
 
+    #[test]
+    fn benchmark_serialization_excludes_normalized_title() {
+        // Precomputed match fields must not change the IPC payload
+        let plain = create_test_results(1).remove(0);
+        let mut precomputed = plain.clone();
+        precomputed.normalized_title = Some(normalize_for_search(&precomputed.title));
+
+        let plain_json = serde_json::to_string(&plain).unwrap();
+        let precomputed_json = serde_json::to_string(&precomputed).unwrap();
+
+        assert_eq!(plain_json, precomputed_json);
+        assert!(!precomputed_json.contains("normalized"));
+    }
+
+    #[test]
+    fn benchmark_normalized_matching() {
+        // Compare per-keystroke lowercasing against precomputed normalized titles
+        let titles: Vec<String> = (0..5000)
+            .map(|i| format!("Café Résumé Document {}", i))
+            .collect();
+        let normalized: Vec<String> = titles.iter().map(|t| normalize_for_search(t)).collect();
+        let keystrokes = ["d", "do", "doc", "docu", "docum", "document 4"];
+
+        let start = Instant::now();
+        let mut per_keystroke_matches = 0;
+        for query in keystrokes {
+            let query_lower = query.to_lowercase();
+            per_keystroke_matches += titles
+                .iter()
+                .filter(|t| t.to_lowercase().contains(&query_lower))
+                .count();
+        }
+        let per_keystroke = start.elapsed();
+
+        let start = Instant::now();
+        let mut precomputed_matches = 0;
+        for query in keystrokes {
+            let query_normalized = normalize_for_search(query);
+            precomputed_matches += normalized
+                .iter()
+                .filter(|t| t.contains(&query_normalized))
+                .count();
+        }
+        let precomputed = start.elapsed();
+
+        println!("Per-keystroke lowercase over 5000 titles: {:?}", per_keystroke);
+        println!("Precomputed normalized over 5000 titles: {:?}", precomputed);
+
+        assert_eq!(per_keystroke_matches, precomputed_matches);
+    }
+
     #[test]
     fn benchmark_query_sanitization() {
         // Test query sanitization performance
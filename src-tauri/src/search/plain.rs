@@ -0,0 +1,74 @@
+/// Plain text queries, escaped from every special syntax
+///
+/// A query starting with a plain-text marker, a backtick or `'` unless
+/// configured otherwise, is searched for as typed: `'2+2 notes` finds a file
+/// named so instead of a sum. The marker is stripped and only the providers
+/// matching names see the rest, so the calculator, web questions, bangs,
+/// keywords and slash commands never get to reinterpret it.
+
+use crate::search::{SearchProvider, SyntaxFeature};
+use crate::types::ResultType;
+
+/// Characters that mark a query as plain text unless settings say otherwise
+pub const DEFAULT_PLAIN_TEXT_MARKERS: &str = "`'";
+
+/// Result types of the providers plain text queries go to
+pub const PLAIN_TEXT_TYPES: &[ResultType] = &[
+    ResultType::File,
+    ResultType::Application,
+    ResultType::Bookmark,
+    ResultType::RecentFile,
+];
+
+/// Help entry for the plain-text marker
+pub const PLAIN_TEXT_SYNTAX: SyntaxFeature = SyntaxFeature {
+    id: "plain_text",
+    title: "Plain text search",
+    description: "Starting with ' or ` finds names as typed, without calculations, bangs or keywords",
+    example: "'2+2 notes",
+    token: None,
+    values: &[],
+};
+
+/// The query after its plain-text marker, or `None` when it has none
+pub fn strip_marker<'a>(query: &'a str, markers: &str) -> Option<&'a str> {
+    let query = query.trim_start();
+    let marker = query.chars().next().filter(|c| markers.contains(*c))?;
+    Some(&query[marker.len_utf8()..])
+}
+
+/// Whether a provider only returns names it matched, so it is asked for plain text
+///
+/// Providers that don't declare their result types could return anything
+/// and are left out.
+pub fn handles_plain_text(provider: &dyn SearchProvider) -> bool {
+    provider
+        .result_types()
+        .is_some_and(|types| types.iter().all(|t| PLAIN_TEXT_TYPES.contains(t)))
+}
+
+/// Key the results for plain text are cached under, apart from the same
+/// text typed without a marker
+pub fn cache_key(text: &str) -> String {
+    // Sanitized queries never contain control characters
+    format!("plain\u{0}{}", text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_is_stripped_from_the_start_only() {
+        assert_eq!(strip_marker("'2+2 notes", DEFAULT_PLAIN_TEXT_MARKERS), Some("2+2 notes"));
+        assert_eq!(strip_marker("  `how to cook", DEFAULT_PLAIN_TEXT_MARKERS), Some("how to cook"));
+        assert_eq!(strip_marker("'", DEFAULT_PLAIN_TEXT_MARKERS), Some(""));
+        assert_eq!(strip_marker("rock 'n' roll", DEFAULT_PLAIN_TEXT_MARKERS), None);
+        assert_eq!(strip_marker("2+2", DEFAULT_PLAIN_TEXT_MARKERS), None);
+
+        // Only the configured markers count
+        assert_eq!(strip_marker("'2+2", "§"), None);
+        assert_eq!(strip_marker("§2+2", "§"), Some("2+2"));
+        assert_eq!(strip_marker("'2+2", ""), None);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::types::{ExecutionState, ExecutionStatus};
+use std::future::Future;
+use std::sync::Arc;
+
+/// Receives every execution status change
+pub(crate) type ExecutionListener = Arc<dyn Fn(&ExecutionStatus) + Send + Sync>;
+
+tokio::task_local! {
+    static CURRENT: ExecutionProgress;
+}
+
+/// Reports how far a running execution got, as `execution-status` events
+///
+/// The engine sets one up for each result it executes in the background.
+/// Providers doing long work in `execute` fetch it with `current` and may
+/// move it to a blocking thread.
+#[derive(Clone)]
+pub struct ExecutionProgress {
+    execution_id: String,
+    result_id: String,
+    listener: Option<ExecutionListener>,
+}
+
+impl ExecutionProgress {
+    pub(crate) fn new(execution_id: &str, result_id: &str, listener: Option<ExecutionListener>) -> Self {
+        Self {
+            execution_id: execution_id.to_string(),
+            result_id: result_id.to_string(),
+            listener,
+        }
+    }
+
+    /// The reporter of the execution running on this task, if any
+    ///
+    /// `None` outside `execute_result`, e.g. for batches and secondary actions.
+    pub fn current() -> Option<Self> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Runs `future` with this as the current reporter
+    pub(crate) async fn scope<F: Future>(self, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Reports that `done` of `total` units, e.g. bytes, are finished
+    pub fn report(&self, done: u64, total: u64) {
+        if let Some(listener) = &self.listener {
+            listener(&ExecutionStatus {
+                execution_id: self.execution_id.clone(),
+                result_id: self.result_id.clone(),
+                state: ExecutionState::Progress { done, total },
+            });
+        }
+    }
+}
@@ -1,6 +1,12 @@
 use async_trait::async_trait;
-use crate::error::Result;
-use crate::types::SearchResult;
+use crate::error::{LauncherError, Result};
+use crate::search::completion::CompletionCandidate;
+use crate::search::syntax::SyntaxFeature;
+use crate::search::QueryContext;
+use crate::types::{CacheStats, ProviderHealth, ResultType, SearchResult};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
 /// Trait that all search providers must implement
 #[async_trait]
@@ -12,18 +18,132 @@ pub trait SearchProvider: Send + Sync {
     /// Typical values: 0-100
     fn priority(&self) -> u8;
 
+    /// Returns the result types this provider can produce
+    /// `None` means any type; such providers are never skipped by type filters
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        None
+    }
+
     /// Performs a search with the given query
     /// Returns a vector of search results
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>>;
+    ///
+    /// `query.query` is the sanitized text as typed; providers doing their own
+    /// case-insensitive matching compare against `query.folded_query`.
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>>;
 
     /// Executes the action associated with a search result
     async fn execute(&self, result: &SearchResult) -> Result<()>;
 
+    /// Runs one of the secondary actions this provider put on its results
+    ///
+    /// Returns `LauncherError::NotFound` for actions the provider doesn't
+    /// offer, so the engine can ask the next one.
+    async fn execute_action(&self, _result: &SearchResult, action_id: &str) -> Result<()> {
+        Err(LauncherError::NotFound(format!("{} has no action '{}'", self.name(), action_id)))
+    }
+
+    /// Fills in what `search` left out of a result marked `enrichment_pending`,
+    /// e.g. a downloaded favicon or an extracted icon
+    ///
+    /// Only called for this provider's own results, once the frontend asks
+    /// for them, on a small pool of workers shared by all providers.
+    async fn enrich(&self, _result: &mut SearchResult) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns up to `limit` names used to suggest spelling corrections,
+    /// taken from the provider's cache without any scanning
+    async fn suggestion_terms(&self, _limit: usize) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns texts that may complete `query` inline, e.g. app names
+    ///
+    /// Called on every keystroke, so it must only read in-memory caches.
+    async fn completion_candidates(&self, _query: &str) -> Vec<CompletionCandidate> {
+        Vec::new()
+    }
+
+    /// Returns results offered only when no provider other than web search
+    /// matched, e.g. the launcher's own settings for a query naming one
+    async fn fallback_results(&self, _query: &QueryContext) -> Vec<SearchResult> {
+        Vec::new()
+    }
+
+    /// Returns the query syntax the provider understands, listed by `?` and `help`
+    ///
+    /// Read again whenever providers or their options change; disabled
+    /// providers aren't asked.
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        Vec::new()
+    }
+
+    /// Returns the size of each in-memory cache the provider keeps
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        Vec::new()
+    }
+
+    /// Drops cold entries from caches that can be refilled on demand
+    ///
+    /// Returns the number of entries removed.
+    async fn trim_caches(&self) -> usize {
+        0
+    }
+
+    /// Refreshes state that may have gone stale while the system slept or
+    /// the session was locked, e.g. by starting a rescan
+    ///
+    /// Runs after every resume and unlock, so slow work belongs in a background task.
+    async fn refresh_after_resume(&self) {}
+
+    /// Returns whether searches answered by this provider may be cached
+    ///
+    /// Providers whose results change on every search (e.g. random values)
+    /// return `false`; queries they contribute results to are never cached.
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the provider is searched for long pasted text
+    ///
+    /// Other providers are skipped for such queries. Those that return
+    /// `true` read the text from `QueryContext::full_text`.
+    fn handles_blobs(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the provider answers instantly from the query alone,
+    /// e.g. arithmetic or dates
+    ///
+    /// Such providers are asked again by `SearchEngine::peek` as the query is
+    /// typed, so their searches must not wait on disk or the network.
+    fn supports_peek(&self) -> bool {
+        false
+    }
+
+    /// Applies the provider's section of `provider_settings`
+    ///
+    /// Called on registration and whenever the section changes, so providers
+    /// with options keep them in shared state they can update through `&self`.
+    /// `Value::Null` means the section is missing and defaults apply.
+    async fn configure(&self, _config: &serde_json::Value) -> Result<()> {
+        Ok(())
+    }
+
     /// Returns whether this provider is currently enabled
     fn is_enabled(&self) -> bool {
         true
     }
 
+    /// Returns the provider's health for status reporting
+    fn health(&self) -> ProviderHealth {
+        if self.is_enabled() {
+            ProviderHealth::Ready
+        } else {
+            ProviderHealth::Disabled
+        }
+    }
+
     /// Optional: Initialize the provider (e.g., load cache, connect to services)
     async fn initialize(&mut self) -> Result<()> {
         Ok(())
@@ -34,3 +154,299 @@ pub trait SearchProvider: Send + Sync {
         Ok(())
     }
 }
+
+/// Lets one provider instance be registered with the engine and kept in app state
+///
+/// The engine only needs `&self` access to providers, but `initialize` and
+/// `shutdown` take `&mut self`. Wrapping a shared `Arc<RwLock<P>>` means commands
+/// holding the same `Arc` (e.g. to track file access) act on exactly the
+/// instance that answers searches.
+pub struct SharedProvider<P: SearchProvider> {
+    inner: Arc<RwLock<P>>,
+    name: String,
+    priority: u8,
+    result_types: Option<&'static [ResultType]>,
+    cacheable: bool,
+    handles_blobs: bool,
+    supports_peek: bool,
+    /// Last known `is_enabled`, reported while the provider is locked for writing
+    enabled: AtomicBool,
+}
+
+impl<P: SearchProvider> SharedProvider<P> {
+    /// Wraps a shared provider, caching the values the engine reads synchronously
+    pub async fn new(inner: Arc<RwLock<P>>) -> Self {
+        let provider = inner.read().await;
+        let name = provider.name().to_string();
+        let priority = provider.priority();
+        let result_types = provider.result_types();
+        let cacheable = provider.cacheable();
+        let handles_blobs = provider.handles_blobs();
+        let supports_peek = provider.supports_peek();
+        let enabled = AtomicBool::new(provider.is_enabled());
+        drop(provider);
+
+        Self {
+            inner,
+            name,
+            priority,
+            result_types,
+            cacheable,
+            handles_blobs,
+            supports_peek,
+            enabled,
+        }
+    }
+
+    /// Returns another handle to the wrapped provider
+    pub fn inner(&self) -> Arc<RwLock<P>> {
+        Arc::clone(&self.inner)
+    }
+}
+
+#[async_trait]
+impl<P: SearchProvider + 'static> SearchProvider for SharedProvider<P> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        self.result_types
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        self.inner.read().await.search(query).await
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        self.inner.read().await.execute(result).await
+    }
+
+    async fn execute_action(&self, result: &SearchResult, action_id: &str) -> Result<()> {
+        self.inner.read().await.execute_action(result, action_id).await
+    }
+
+    async fn enrich(&self, result: &mut SearchResult) -> Result<()> {
+        self.inner.read().await.enrich(result).await
+    }
+
+    async fn suggestion_terms(&self, limit: usize) -> Vec<String> {
+        self.inner.read().await.suggestion_terms(limit).await
+    }
+
+    async fn completion_candidates(&self, query: &str) -> Vec<CompletionCandidate> {
+        self.inner.read().await.completion_candidates(query).await
+    }
+
+    async fn fallback_results(&self, query: &QueryContext) -> Vec<SearchResult> {
+        self.inner.read().await.fallback_results(query).await
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        self.inner.read().await.syntax_features().await
+    }
+
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        self.inner.read().await.cache_stats().await
+    }
+
+    async fn trim_caches(&self) -> usize {
+        self.inner.read().await.trim_caches().await
+    }
+
+    async fn refresh_after_resume(&self) {
+        self.inner.read().await.refresh_after_resume().await
+    }
+
+    fn cacheable(&self) -> bool {
+        self.cacheable
+    }
+
+    fn handles_blobs(&self) -> bool {
+        self.handles_blobs
+    }
+
+    fn supports_peek(&self) -> bool {
+        self.supports_peek
+    }
+
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        self.inner.read().await.configure(config).await
+    }
+
+    fn is_enabled(&self) -> bool {
+        match self.inner.try_read() {
+            Ok(provider) => {
+                let enabled = provider.is_enabled();
+                self.enabled.store(enabled, Ordering::Relaxed);
+                enabled
+            }
+            Err(_) => self.enabled.load(Ordering::Relaxed),
+        }
+    }
+
+    /// The wrapped provider's health; a writer holding the lock, e.g. while
+    /// the provider initializes, reads as initializing
+    fn health(&self) -> ProviderHealth {
+        match self.inner.try_read() {
+            Ok(provider) => provider.health(),
+            Err(_) => ProviderHealth::Initializing,
+        }
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.inner.write().await.initialize().await
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.inner.write().await.shutdown().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SearchEngine;
+    use crate::types::ResultAction;
+    use std::collections::HashMap;
+
+    /// Provider keeping its state in memory, like a cache that app commands update
+    struct NotesProvider {
+        notes: Vec<String>,
+        opened: std::sync::Mutex<Vec<String>>,
+        enabled: bool,
+        /// Error reported as the provider's health, like a database that failed to open
+        failure: Option<String>,
+    }
+
+    impl NotesProvider {
+        fn new() -> Self {
+            Self {
+                notes: Vec::new(),
+                opened: std::sync::Mutex::new(Vec::new()),
+                enabled: true,
+                failure: None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SearchProvider for NotesProvider {
+        fn name(&self) -> &str {
+            "Notes"
+        }
+
+        fn priority(&self) -> u8 {
+            42
+        }
+
+        fn result_types(&self) -> Option<&'static [ResultType]> {
+            Some(&[ResultType::Clipboard])
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok(self
+                .notes
+                .iter()
+                .filter(|note| note.contains(query.query.as_str()))
+                .map(|note| SearchResult {
+                    id: format!("note:{}", note),
+                    title: note.clone(),
+                    subtitle: String::new(),
+                    icon: None,
+                    result_type: ResultType::Clipboard,
+                    score: 50.0,
+                    metadata: HashMap::new(),
+                    action: ResultAction::CopyToClipboard {
+                        content: note.clone(),
+                    },
+                    normalized_title: None,
+                    enrichment_pending: false,
+                    integrity: None,
+                })
+                .collect())
+        }
+
+        async fn execute(&self, result: &SearchResult) -> Result<()> {
+            self.opened.lock().unwrap().push(result.id.clone());
+            Ok(())
+        }
+
+        fn is_enabled(&self) -> bool {
+            self.enabled
+        }
+
+        fn health(&self) -> ProviderHealth {
+            match &self.failure {
+                Some(error) => ProviderHealth::Failed { error: error.clone() },
+                None => ProviderHealth::Ready,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shared_provider_delegates_metadata() {
+        let shared = Arc::new(RwLock::new(NotesProvider::new()));
+        let adapter = SharedProvider::new(Arc::clone(&shared)).await;
+
+        assert_eq!(adapter.name(), "Notes");
+        assert_eq!(adapter.priority(), 42);
+        assert_eq!(adapter.result_types(), Some(&[ResultType::Clipboard][..]));
+        assert!(Arc::ptr_eq(&adapter.inner(), &shared));
+    }
+
+    #[tokio::test]
+    async fn test_registered_and_managed_instances_see_each_others_writes() {
+        let shared = Arc::new(RwLock::new(NotesProvider::new()));
+        let engine = SearchEngine::new();
+        engine
+            .register_provider(Arc::new(SharedProvider::new(Arc::clone(&shared)).await))
+            .await;
+
+        // A write through the managed handle is visible to engine searches
+        shared.write().await.notes.push("alpha note".to_string());
+        let results = engine.search("alpha").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "note:alpha note");
+
+        // An execution through the engine is visible through the managed handle
+        engine.execute_result_blocking(&results[0]).await.unwrap();
+        assert_eq!(*shared.read().await.opened.lock().unwrap(), vec!["note:alpha note"]);
+    }
+
+    #[tokio::test]
+    async fn test_shared_provider_enabled_state() {
+        let shared = Arc::new(RwLock::new(NotesProvider::new()));
+        let adapter = SharedProvider::new(Arc::clone(&shared)).await;
+        assert!(adapter.is_enabled());
+
+        shared.write().await.enabled = false;
+        assert!(!adapter.is_enabled());
+
+        // While a writer holds the lock the last known state is reported
+        let guard = shared.write().await;
+        assert!(!adapter.is_enabled());
+        drop(guard);
+    }
+
+    #[tokio::test]
+    async fn test_shared_provider_reports_inner_health() {
+        let shared = Arc::new(RwLock::new(NotesProvider::new()));
+        let adapter = SharedProvider::new(Arc::clone(&shared)).await;
+        assert_eq!(adapter.health(), ProviderHealth::Ready);
+
+        shared.write().await.failure = Some("unable to open database file".to_string());
+        assert_eq!(
+            adapter.health(),
+            ProviderHealth::Failed { error: "unable to open database file".to_string() }
+        );
+
+        let guard = shared.write().await;
+        assert_eq!(adapter.health(), ProviderHealth::Initializing);
+        drop(guard);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::error::{LauncherError, Result};
+use crate::search::providers::{
+    BookmarkConfig, ClipboardConfig, DevDocsConfig, FileSearchConfig, RecentFilesConfig, WebSearchConfig,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// A whole-number option a provider accepts, with its allowed range
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigOption {
+    pub field: &'static str,
+    pub description: &'static str,
+    pub min: u64,
+    pub max: u64,
+}
+
+/// An on/off option a provider accepts
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigFlag {
+    pub field: &'static str,
+    pub description: &'static str,
+}
+
+/// Options a provider reads from its section of `provider_settings`
+///
+/// Config structs use `#[serde(default)]`, so missing sections and fields fall
+/// back to the defaults, and `deny_unknown_fields` so typos are reported
+/// instead of silently ignored.
+pub trait ProviderConfig: Serialize + DeserializeOwned + Default {
+    /// Key of the section in `provider_settings`
+    const KEY: &'static str;
+    /// Name of the provider reading the section, as returned by `SearchProvider::name`
+    const PROVIDER: &'static str;
+    /// Range and description of each whole-number field
+    const OPTIONS: &'static [ConfigOption];
+    /// Description of each boolean field; serde rejects values of other types
+    const FLAGS: &'static [ConfigFlag] = &[];
+
+    /// Reads and validates a section; `null` means all defaults
+    fn parse(value: &Value) -> Result<Self> {
+        if value.is_null() {
+            return Ok(Self::default());
+        }
+
+        let config: Self = serde_json::from_value(value.clone()).map_err(|e| {
+            LauncherError::ConfigError(format!("{} settings are invalid: {}", Self::PROVIDER, e))
+        })?;
+
+        let fields = serde_json::to_value(&config)?;
+        for option in Self::OPTIONS {
+            let in_range = fields
+                .get(option.field)
+                .and_then(Value::as_u64)
+                .is_some_and(|v| (option.min..=option.max).contains(&v));
+            if !in_range {
+                return Err(LauncherError::ConfigError(format!(
+                    "{} setting '{}' must be between {} and {}",
+                    Self::PROVIDER,
+                    option.field,
+                    option.min,
+                    option.max
+                )));
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Describes the section for the settings UI
+    fn schema() -> ProviderConfigSchema {
+        let defaults = serde_json::to_value(Self::default()).unwrap_or(Value::Null);
+        let mut properties: Map<String, Value> = Self::OPTIONS
+            .iter()
+            .map(|option| {
+                let property = json!({
+                    "type": "integer",
+                    "description": option.description,
+                    "minimum": option.min,
+                    "maximum": option.max,
+                    "default": defaults.get(option.field),
+                });
+                (option.field.to_string(), property)
+            })
+            .collect();
+        for flag in Self::FLAGS {
+            let property = json!({
+                "type": "boolean",
+                "description": flag.description,
+                "default": defaults.get(flag.field),
+            });
+            properties.insert(flag.field.to_string(), property);
+        }
+
+        ProviderConfigSchema {
+            key: Self::KEY.to_string(),
+            provider: Self::PROVIDER.to_string(),
+            schema: json!({
+                "type": "object",
+                "properties": properties,
+                "additionalProperties": false,
+            }),
+            defaults,
+        }
+    }
+}
+
+/// Defaults and JSON schema of one provider's settings section
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProviderConfigSchema {
+    /// Key of the section in `provider_settings`
+    pub key: String,
+    /// Name of the provider the section configures
+    pub provider: String,
+    pub defaults: Value,
+    pub schema: Value,
+}
+
+/// One option of a provider's section, numeric or on/off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderOption {
+    /// Key of the section in `provider_settings`
+    pub section: &'static str,
+    /// Name of the provider reading the section
+    pub provider: &'static str,
+    pub field: &'static str,
+    pub description: &'static str,
+}
+
+/// A provider with options, with its config type erased
+struct ConfigEntry {
+    key: &'static str,
+    provider: &'static str,
+    options: &'static [ConfigOption],
+    flags: &'static [ConfigFlag],
+    validate: fn(&Value) -> Result<()>,
+    schema: fn() -> ProviderConfigSchema,
+}
+
+fn entry<C: ProviderConfig>() -> ConfigEntry {
+    ConfigEntry {
+        key: C::KEY,
+        provider: C::PROVIDER,
+        options: C::OPTIONS,
+        flags: C::FLAGS,
+        validate: |value| C::parse(value).map(|_| ()),
+        schema: C::schema,
+    }
+}
+
+fn entries() -> [ConfigEntry; 6] {
+    [
+        entry::<ClipboardConfig>(),
+        entry::<BookmarkConfig>(),
+        entry::<RecentFilesConfig>(),
+        entry::<WebSearchConfig>(),
+        entry::<FileSearchConfig>(),
+        entry::<DevDocsConfig>(),
+    ]
+}
+
+/// Checks every section, naming the provider and field of the first bad value
+pub fn validate_provider_settings(settings: &HashMap<String, Value>) -> Result<()> {
+    let entries = entries();
+    for (key, value) in settings {
+        let entry = entries.iter().find(|entry| entry.key == key).ok_or_else(|| {
+            LauncherError::ConfigError(format!("Unknown provider settings section '{}'", key))
+        })?;
+        (entry.validate)(value)?;
+    }
+    Ok(())
+}
+
+/// Defaults and schema of every provider with options
+pub fn provider_config_schemas() -> Vec<ProviderConfigSchema> {
+    entries().iter().map(|entry| (entry.schema)()).collect()
+}
+
+/// Every option of every provider, numeric options first within a section
+pub fn provider_options() -> Vec<ProviderOption> {
+    entries()
+        .iter()
+        .flat_map(|entry| {
+            let numeric = entry.options.iter().map(|option| (option.field, option.description));
+            let flags = entry.flags.iter().map(|flag| (flag.field, flag.description));
+            numeric.chain(flags).map(|(field, description)| ProviderOption {
+                section: entry.key,
+                provider: entry.provider,
+                field,
+                description,
+            })
+        })
+        .collect()
+}
+
+/// Returns the section configuring the named provider
+///
+/// `Some(Value::Null)` means the provider has options but no section, so it
+/// uses its defaults; `None` means the provider has no options.
+pub fn section_for_provider<'a>(settings: &'a HashMap<String, Value>, provider: &str) -> Option<&'a Value> {
+    static NULL: Value = Value::Null;
+
+    entries()
+        .iter()
+        .find(|entry| entry.provider == provider)
+        .map(|entry| settings.get(entry.key).unwrap_or(&NULL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_sections_and_fields_use_defaults() {
+        assert_eq!(ClipboardConfig::parse(&Value::Null).unwrap(), ClipboardConfig::default());
+        assert_eq!(RecentFilesConfig::parse(&json!({})).unwrap(), RecentFilesConfig::default());
+        assert!(validate_provider_settings(&HashMap::new()).is_ok());
+
+        let settings = HashMap::new();
+        assert_eq!(section_for_provider(&settings, "Recent Files"), Some(&Value::Null));
+        assert_eq!(section_for_provider(&settings, "Calculator"), None);
+    }
+
+    #[test]
+    fn test_out_of_range_values_name_the_provider_and_field() {
+        let settings = HashMap::from([("recent_files".to_string(), json!({ "limit": 500 }))]);
+        let error = validate_provider_settings(&settings).unwrap_err().to_string();
+        assert!(error.contains("Recent Files"), "{}", error);
+        assert!(error.contains("limit"), "{}", error);
+
+        let settings = HashMap::from([("clipboard".to_string(), json!({ "max_itmes": 10 }))]);
+        let error = validate_provider_settings(&settings).unwrap_err().to_string();
+        assert!(error.contains("Clipboard History"), "{}", error);
+
+        let settings = HashMap::from([("calculator".to_string(), json!({}))]);
+        assert!(validate_provider_settings(&settings).is_err());
+    }
+
+    #[test]
+    fn test_schemas_describe_every_option_with_its_default() {
+        let schemas = provider_config_schemas();
+        assert_eq!(schemas.len(), entries().len());
+
+        let clipboard = schemas.iter().find(|s| s.key == "clipboard").unwrap();
+        let max_items = &clipboard.schema["properties"]["max_items"];
+        assert_eq!(max_items["type"], "integer");
+        assert_eq!(max_items["default"], clipboard.defaults["max_items"]);
+        assert!(max_items["maximum"].as_u64().unwrap() >= max_items["minimum"].as_u64().unwrap());
+
+        let dev_docs = schemas.iter().find(|s| s.key == "dev_docs").unwrap();
+        let lookup_titles = &dev_docs.schema["properties"]["lookup_titles"];
+        assert_eq!(lookup_titles["type"], "boolean");
+        assert_eq!(lookup_titles["default"], false);
+    }
+
+    #[test]
+    fn test_flags_must_be_booleans() {
+        assert!(DevDocsConfig::parse(&json!({ "lookup_titles": true })).unwrap().lookup_titles);
+
+        let settings = HashMap::from([("dev_docs".to_string(), json!({ "lookup_titles": 1 }))]);
+        let error = validate_provider_settings(&settings).unwrap_err().to_string();
+        assert!(error.contains("DevDocs"), "{}", error);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::error::{LauncherError, Result};
+use futures::FutureExt;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::{Mutex, RwLock};
+use tracing::{error, info, warn};
+
+/// Launches in a row a provider may fail to start before it's skipped
+pub const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Callback told which provider was skipped, e.g. to show a notification
+pub type SkipListener = Box<dyn Fn(&str) + Send + Sync>;
+
+/// Starts providers so one that fails or panics can't take the others down
+///
+/// Each start is counted as a failure before it runs and the count is only
+/// cleared once it succeeds, so a provider that crashes the whole process
+/// is still counted. After `MAX_CONSECUTIVE_FAILURES` launches in a row the
+/// provider is skipped until `reset` is called from settings.
+pub struct ProviderGuard {
+    /// File the counts are kept in; counts only live in memory without one
+    path: Option<PathBuf>,
+    /// Consecutive failed starts by provider
+    failures: Mutex<BTreeMap<String, u32>>,
+    on_skip: RwLock<Option<SkipListener>>,
+}
+
+impl ProviderGuard {
+    /// Creates a guard keeping its counts under `%APPDATA%\BetterFinder`
+    pub fn load() -> Self {
+        match std::env::var_os("APPDATA") {
+            Some(app_data) => Self::with_path(
+                PathBuf::from(app_data)
+                    .join("BetterFinder")
+                    .join("provider_failures.json"),
+            ),
+            None => {
+                warn!("APPDATA not found, provider failures are not remembered");
+                Self::new(None, BTreeMap::new())
+            }
+        }
+    }
+
+    /// Creates a guard keeping its counts in `path`
+    ///
+    /// A missing or unreadable file starts every provider from zero.
+    pub fn with_path(path: PathBuf) -> Self {
+        let failures = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(failures) => Some(failures),
+                Err(e) => {
+                    warn!("Ignoring unreadable provider failure counts {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self::new(Some(path), failures)
+    }
+
+    fn new(path: Option<PathBuf>, failures: BTreeMap<String, u32>) -> Self {
+        Self {
+            path,
+            failures: Mutex::new(failures),
+            on_skip: RwLock::new(None),
+        }
+    }
+
+    /// Sets the callback told about skipped providers
+    pub fn set_skip_listener(&self, listener: impl Fn(&str) + Send + Sync + 'static) {
+        *self.on_skip.write().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(listener));
+    }
+
+    /// Consecutive failed starts of `provider`
+    pub fn failures(&self, provider: &str) -> u32 {
+        self.lock().get(provider).copied().unwrap_or(0)
+    }
+
+    /// Whether `provider` is skipped at startup
+    pub fn is_disabled(&self, provider: &str) -> bool {
+        self.failures(provider) >= MAX_CONSECUTIVE_FAILURES
+    }
+
+    /// Providers skipped at startup, by name
+    pub fn disabled_providers(&self) -> Vec<String> {
+        self.lock()
+            .iter()
+            .filter(|(_, failures)| **failures >= MAX_CONSECUTIVE_FAILURES)
+            .map(|(provider, _)| provider.clone())
+            .collect()
+    }
+
+    /// Clears the count of `provider`, so the next launch tries it again
+    pub fn reset(&self, provider: &str) -> Result<()> {
+        let mut failures = self.lock();
+        if failures.remove(provider).is_none() {
+            return Err(LauncherError::NotFound(format!("No failures recorded for '{}'", provider)));
+        }
+        self.save(&failures);
+        info!("{} will be started again on the next launch", provider);
+        Ok(())
+    }
+
+    /// Runs `start` unless `provider` failed too often, returning its output
+    /// when it succeeds
+    ///
+    /// Errors and panics are logged and counted; either way the caller goes
+    /// on with the next provider.
+    pub async fn run<T>(&self, provider: &str, start: impl Future<Output = Result<T>>) -> Option<T> {
+        if self.is_disabled(provider) {
+            warn!("Skipping {} after {} failed starts", provider, self.failures(provider));
+            if let Some(listener) = self.on_skip.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+                listener(provider);
+            }
+            return None;
+        }
+
+        self.update(provider, |failures| failures + 1);
+        match AssertUnwindSafe(start).catch_unwind().await {
+            Ok(Ok(value)) => {
+                self.update(provider, |_| 0);
+                Some(value)
+            }
+            Ok(Err(e)) => {
+                error!("Failed to start {}: {}", provider, e);
+                None
+            }
+            Err(panic) => {
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                error!("{} panicked while starting: {}", provider, message);
+                None
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, u32>> {
+        self.failures.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn update(&self, provider: &str, count: impl Fn(u32) -> u32) {
+        let mut failures = self.lock();
+        let previous = failures.get(provider).copied().unwrap_or(0);
+        let next = count(previous);
+        if next == previous {
+            return;
+        }
+        if next == 0 {
+            failures.remove(provider);
+        } else {
+            failures.insert(provider.to_string(), next);
+        }
+        self.save(&failures);
+    }
+
+    fn save(&self, failures: &BTreeMap<String, u32>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = (|| {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_vec_pretty(failures)?)?;
+            Ok::<_, LauncherError>(())
+        })();
+        if let Err(e) = result {
+            warn!("Failed to save provider failure counts {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{QueryContext, SearchEngine, SearchProvider};
+    use crate::types::SearchResult;
+    use async_trait::async_trait;
+    use std::sync::Arc;
+
+    struct NamedProvider(&'static str);
+
+    #[async_trait]
+    impl SearchProvider for NamedProvider {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok(Vec::new())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Counts read back from the file, as the next launch would see them
+    fn counts_on_disk(path: &std::path::Path) -> BTreeMap<String, u32> {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn temp_counts(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    async fn failing_start() -> Result<()> {
+        Err(LauncherError::ProviderError("corrupted history".to_string()))
+    }
+
+    async fn panicking_start(engine: &SearchEngine) -> Result<()> {
+        engine.register_provider(Arc::new(NamedProvider("Broken"))).await;
+        panic!("index out of bounds");
+    }
+
+    #[tokio::test]
+    async fn test_provider_is_skipped_after_three_failed_launches() {
+        let path = temp_counts("provider_failures_threshold");
+
+        for launch in 1..=MAX_CONSECUTIVE_FAILURES {
+            let guard = ProviderGuard::with_path(path.clone());
+            assert!(!guard.is_disabled("Clipboard History"), "launch {}", launch);
+            assert_eq!(guard.run("Clipboard History", failing_start()).await, None);
+            assert_eq!(guard.failures("Clipboard History"), launch);
+        }
+
+        let guard = ProviderGuard::with_path(path.clone());
+        let skipped = Arc::new(Mutex::new(Vec::new()));
+        let told = Arc::clone(&skipped);
+        guard.set_skip_listener(move |provider| told.lock().unwrap().push(provider.to_string()));
+        assert_eq!(guard.run("Clipboard History", async { Ok(1) }).await, None);
+        assert_eq!(*skipped.lock().unwrap(), vec!["Clipboard History"]);
+        assert_eq!(guard.disabled_providers(), vec!["Clipboard History"]);
+
+        // Retrying from settings starts it on the next launch, and a success clears the count
+        guard.reset("Clipboard History").unwrap();
+        assert!(guard.reset("Clipboard History").is_err());
+        let guard = ProviderGuard::with_path(path.clone());
+        assert_eq!(guard.run("Clipboard History", async { Ok(1) }).await, Some(1));
+        assert!(counts_on_disk(&path).is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_success_breaks_the_run_of_failures() {
+        let path = temp_counts("provider_failures_consecutive");
+        let guard = ProviderGuard::with_path(path.clone());
+
+        guard.run("Bookmarks", failing_start()).await;
+        guard.run("Bookmarks", failing_start()).await;
+        guard.run("Bookmarks", async { Ok(()) }).await;
+        guard.run("Bookmarks", failing_start()).await;
+        assert_eq!(guard.failures("Bookmarks"), 1);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_start_is_counted_before_it_runs() {
+        // A crash that takes the process down never reports back
+        let path = temp_counts("provider_failures_crash");
+        let guard = ProviderGuard::with_path(path.clone());
+
+        let during = guard
+            .run("Recent Files", async { Ok(counts_on_disk(&path)) })
+            .await
+            .unwrap();
+        assert_eq!(during.get("Recent Files"), Some(&1));
+        assert_eq!(guard.failures("Recent Files"), 0);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_counts_start_from_zero() {
+        let path = temp_counts("provider_failures_corrupt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let guard = ProviderGuard::with_path(path.clone());
+        assert!(guard.disabled_providers().is_empty());
+        guard.run("Files", failing_start()).await;
+        assert_eq!(counts_on_disk(&path).get("Files"), Some(&1));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_provider_does_not_stop_later_registrations() {
+        let path = temp_counts("provider_failures_panic");
+        let guard = ProviderGuard::with_path(path.clone());
+        let engine = SearchEngine::new();
+
+        assert_eq!(guard.run("Broken", panicking_start(&engine)).await, None);
+
+        let started = guard
+            .run("Calculator", async {
+                engine.register_provider(Arc::new(NamedProvider("Calculator"))).await;
+                Ok(())
+            })
+            .await;
+        assert_eq!(started, Some(()));
+
+        assert_eq!(guard.failures("Broken"), 1);
+        assert!(engine.provider_names().await.contains(&"Calculator".to_string()));
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,591 @@
+/// Admin tools provider for Windows management consoles
+///
+/// Searching "services", "device manager", "event viewer", "environment
+/// variables" or "hosts file" offers the matching MMC snap-in or dialog.
+/// Tools that need administrator rights are launched with the `runas` verb,
+/// so Windows shows its elevation prompt.
+///
+/// `regedit HKCU\Software\BetterFinder` (or just the key path) opens the
+/// Registry Editor at that key. Regedit reopens the key stored in its
+/// `LastKey` value, so the provider writes the key there before launching.
+/// A path that isn't a valid key falls back to opening Regedit as is.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// Shortest query that matches a tool by alias
+const MIN_QUERY_LEN: usize = 3;
+
+/// Query prefixes naming the Registry Editor, followed by a key path
+const REGEDIT_PREFIXES: &[&str] = &["regedit", "registry", "reg"];
+
+/// Id of the Registry Editor in `ADMIN_TOOLS`
+const REGEDIT_ID: &str = "regedit";
+
+/// Regedit's settings key, relative to HKEY_CURRENT_USER
+#[cfg_attr(not(windows), allow(dead_code))]
+const REGEDIT_APPLET_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Applets\Regedit";
+
+/// Longest name a single registry key may have
+const MAX_KEY_NAME_LEN: usize = 255;
+
+/// Root keys and their abbreviations
+const REGISTRY_ROOTS: &[(&str, &str)] = &[
+    ("HKEY_CLASSES_ROOT", "HKCR"),
+    ("HKEY_CURRENT_USER", "HKCU"),
+    ("HKEY_LOCAL_MACHINE", "HKLM"),
+    ("HKEY_USERS", "HKU"),
+    ("HKEY_CURRENT_CONFIG", "HKCC"),
+];
+
+/// A management tool that can be opened from search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdminTool {
+    /// Identifier used in result ids and metadata
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Words a query is matched against, besides the name
+    pub aliases: &'static [&'static str],
+    /// Program or console file opened by ShellExecute
+    pub program: &'static str,
+    /// File opened by the program, relative to the Windows folder
+    pub windows_file: Option<&'static str>,
+    /// Whether the tool is launched with the `runas` verb
+    pub elevated: bool,
+}
+
+/// Every tool the provider offers
+pub const ADMIN_TOOLS: &[AdminTool] = &[
+    AdminTool {
+        id: "services",
+        name: "Services",
+        description: "Start, stop and configure Windows services",
+        aliases: &["services.msc", "service manager"],
+        program: "services.msc",
+        windows_file: None,
+        elevated: false,
+    },
+    AdminTool {
+        id: "device_manager",
+        name: "Device Manager",
+        description: "View hardware and update drivers",
+        aliases: &["devmgmt.msc", "devices", "drivers", "hardware"],
+        program: "devmgmt.msc",
+        windows_file: None,
+        elevated: false,
+    },
+    AdminTool {
+        id: "event_viewer",
+        name: "Event Viewer",
+        description: "Browse system and application logs",
+        aliases: &["eventvwr.msc", "eventvwr", "event log", "logs"],
+        program: "eventvwr.msc",
+        windows_file: None,
+        elevated: false,
+    },
+    AdminTool {
+        id: REGEDIT_ID,
+        name: "Registry Editor",
+        description: "Type regedit followed by a key path to open it directly",
+        aliases: &["regedit", "registry"],
+        program: "regedit.exe",
+        windows_file: None,
+        elevated: true,
+    },
+    AdminTool {
+        id: "environment_variables",
+        name: "Environment Variables",
+        description: "Edit PATH and other variables in System Properties",
+        aliases: &["env vars", "environment", "path variable", "system properties"],
+        program: "SystemPropertiesAdvanced.exe",
+        windows_file: None,
+        elevated: false,
+    },
+    AdminTool {
+        id: "hosts_file",
+        name: "Hosts File",
+        description: "Edit the hosts file in Notepad as administrator",
+        aliases: &["hosts", "etc hosts"],
+        program: "notepad.exe",
+        windows_file: Some(r"System32\drivers\etc\hosts"),
+        elevated: true,
+    },
+    AdminTool {
+        id: "computer_management",
+        name: "Computer Management",
+        description: "Disks, users, shared folders and services in one console",
+        aliases: &["compmgmt.msc", "compmgmt"],
+        program: "compmgmt.msc",
+        windows_file: None,
+        elevated: false,
+    },
+    AdminTool {
+        id: "disk_management",
+        name: "Disk Management",
+        description: "Partition and format drives",
+        aliases: &["diskmgmt.msc", "partitions", "disks"],
+        program: "diskmgmt.msc",
+        windows_file: None,
+        elevated: true,
+    },
+    AdminTool {
+        id: "task_scheduler",
+        name: "Task Scheduler",
+        description: "Create and manage scheduled tasks",
+        aliases: &["taskschd.msc", "scheduled tasks", "cron"],
+        program: "taskschd.msc",
+        windows_file: None,
+        elevated: false,
+    },
+];
+
+impl AdminTool {
+    /// Looks up a tool by id
+    pub fn find(id: &str) -> Option<&'static AdminTool> {
+        ADMIN_TOOLS.iter().find(|tool| tool.id == id)
+    }
+
+    /// Scores how well a lowercase query names the tool
+    pub fn match_score(&self, query: &str) -> Option<f64> {
+        if query.chars().count() < MIN_QUERY_LEN {
+            return None;
+        }
+
+        std::iter::once(self.name)
+            .chain(self.aliases.iter().copied())
+            .map(str::to_lowercase)
+            .filter_map(|alias| {
+                if alias == query {
+                    Some(100.0)
+                } else if alias.starts_with(query) {
+                    Some(90.0)
+                } else if alias.split(' ').any(|word| word.starts_with(query)) {
+                    Some(75.0)
+                } else {
+                    None
+                }
+            })
+            .reduce(f64::max)
+    }
+}
+
+/// A Registry Editor query carrying a key path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegeditRequest {
+    /// The canonical key to open, e.g. `HKEY_CURRENT_USER\Software`
+    Key(String),
+    /// Text that isn't a valid key path
+    Invalid(String),
+}
+
+/// Normalizes a registry key path, expanding root abbreviations
+///
+/// Accepts the `Computer\` prefix shown in Regedit's address bar and
+/// trailing backslashes. Returns `None` for unknown roots, empty key names
+/// and names that are too long or contain control characters.
+pub fn parse_registry_key(path: &str) -> Option<String> {
+    let path = path.trim().trim_end_matches('\\');
+    let path = match path.get(..9) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(r"computer\") => &path[9..],
+        _ => path,
+    };
+
+    let mut segments = path.split('\\');
+    let root = segments.next()?;
+    let (root, _) = REGISTRY_ROOTS
+        .iter()
+        .find(|(name, short)| root.eq_ignore_ascii_case(name) || root.eq_ignore_ascii_case(short))?;
+
+    let mut key = root.to_string();
+    for segment in segments {
+        if segment.is_empty() || segment.len() > MAX_KEY_NAME_LEN || segment.chars().any(char::is_control) {
+            return None;
+        }
+        key.push('\\');
+        key.push_str(segment);
+    }
+    Some(key)
+}
+
+/// The `LastKey` value that makes Regedit open at `key`
+pub fn last_key_value(key: &str) -> String {
+    format!(r"Computer\{}", key)
+}
+
+/// Whether text after "regedit" is meant as a key path rather than more words
+fn looks_like_key_path(text: &str) -> bool {
+    text.contains('\\') || text.get(..2).is_some_and(|start| start.eq_ignore_ascii_case("hk"))
+}
+
+/// Recognizes `regedit <path>` queries and bare key paths
+///
+/// Bare text is only taken as a key when it is valid, so ordinary words
+/// starting with "hk" are left to other providers.
+pub fn parse_regedit_query(query: &str) -> Option<RegeditRequest> {
+    let query = query.trim();
+
+    let argument = REGEDIT_PREFIXES.iter().find_map(|prefix| {
+        let rest = query.get(prefix.len()..)?;
+        let matches = query[..prefix.len()].eq_ignore_ascii_case(prefix) && rest.starts_with(char::is_whitespace);
+        matches.then(|| rest.trim()).filter(|path| looks_like_key_path(path))
+    });
+
+    match argument {
+        Some(path) => Some(match parse_registry_key(path) {
+            Some(key) => RegeditRequest::Key(key),
+            None => RegeditRequest::Invalid(path.to_string()),
+        }),
+        None if query.contains('\\') => parse_registry_key(query).map(RegeditRequest::Key),
+        None => None,
+    }
+}
+
+/// Admin tools search provider
+pub struct AdminToolsProvider {
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl AdminToolsProvider {
+    /// Creates a new AdminToolsProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing AdminToolsProvider");
+        Ok(Self { enabled: true })
+    }
+
+    /// Converts a tool to a SearchResult, optionally opening Regedit at a key
+    fn create_search_result(tool: &AdminTool, score: f64, registry_key: Option<&str>) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("tool".to_string(), serde_json::json!(tool.id));
+        metadata.insert("elevated".to_string(), serde_json::json!(tool.elevated));
+
+        let (id, title, subtitle) = match registry_key {
+            Some(key) => {
+                metadata.insert("registry_key".to_string(), serde_json::json!(key));
+                (
+                    format!("admin_tool:{}:{}", tool.id, key.to_lowercase()),
+                    format!("Open {}", key),
+                    "Registry Editor".to_string(),
+                )
+            }
+            None => (
+                format!("admin_tool:{}", tool.id),
+                tool.name.to_string(),
+                tool.description.to_string(),
+            ),
+        };
+
+        SearchResult {
+            id,
+            title,
+            subtitle,
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: format!("admin_tool:{}", tool.id),
+                args: vec![],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Result for a Regedit query; invalid paths fall back to plain Regedit
+    fn regedit_result(request: &RegeditRequest) -> Option<SearchResult> {
+        let tool = AdminTool::find(REGEDIT_ID)?;
+        Some(match request {
+            RegeditRequest::Key(key) => Self::create_search_result(tool, 100.0, Some(key)),
+            RegeditRequest::Invalid(path) => {
+                let mut result = Self::create_search_result(tool, 90.0, None);
+                result.subtitle = format!("\"{}\" is not a registry key — opens Registry Editor", path);
+                result
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl SearchProvider for AdminToolsProvider {
+    fn name(&self) -> &str {
+        "AdminTools"
+    }
+
+    fn priority(&self) -> u8 {
+        80 // Same as the other quick actions
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if let Some(request) = parse_regedit_query(&query.query) {
+            debug!("Registry Editor query: {:?}", request);
+            return Ok(Self::regedit_result(&request).into_iter().collect());
+        }
+
+        let query = query.folded_query.trim();
+        let mut results: Vec<SearchResult> = ADMIN_TOOLS
+            .iter()
+            .filter_map(|tool| Some(Self::create_search_result(tool, tool.match_score(query)?, None)))
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(results)
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let tool = result
+            .metadata
+            .get("tool")
+            .and_then(|v| v.as_str())
+            .and_then(AdminTool::find)
+            .ok_or_else(|| LauncherError::ExecutionError("Not an admin tool result".to_string()))?;
+        let registry_key = result
+            .metadata
+            .get("registry_key")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        info!("Opening {} (elevated: {})", tool.name, tool.elevated);
+
+        tokio::task::spawn_blocking(move || {
+            if let Some(key) = registry_key {
+                set_regedit_last_key(&key)?;
+            }
+            launch_tool(tool)
+        })
+        .await
+        .map_err(|e| LauncherError::ExecutionError(format!("Admin tool task failed: {}", e)))?
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "regedit",
+            title: "Open a registry key",
+            description: "Opens Registry Editor at the key; HKCU and HKLM are expanded",
+            example: r"regedit HKCU\Software",
+            token: None,
+            values: &[],
+        }]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Default for AdminToolsProvider {
+    fn default() -> Self {
+        Self::new().unwrap_or(Self { enabled: false })
+    }
+}
+
+/// Stores the key Regedit opens at on its next launch
+#[cfg(windows)]
+fn set_regedit_last_key(key: &str) -> Result<()> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::System::Registry::{
+        RegCloseKey, RegCreateKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_WRITE,
+        REG_OPTION_NON_VOLATILE, REG_SZ,
+    };
+
+    let value: Vec<u16> = last_key_value(key).encode_utf16().chain(std::iter::once(0)).collect();
+
+    unsafe {
+        let mut hkey = HKEY::default();
+        let result = RegCreateKeyExW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(REGEDIT_APPLET_KEY),
+            0,
+            PCWSTR::null(),
+            REG_OPTION_NON_VOLATILE,
+            KEY_WRITE,
+            None,
+            &mut hkey,
+            None,
+        );
+        if result.is_err() {
+            return Err(LauncherError::ExecutionError(format!(
+                "Failed to open Regedit settings: {:?}",
+                result.0
+            )));
+        }
+
+        let result = RegSetValueExW(hkey, &HSTRING::from("LastKey"), 0, REG_SZ, Some(value.align_to::<u8>().1));
+        RegCloseKey(hkey).ok();
+
+        if result.is_err() {
+            return Err(LauncherError::ExecutionError(format!(
+                "Failed to set Regedit's last key: {:?}",
+                result.0
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn set_regedit_last_key(_key: &str) -> Result<()> {
+    Err(LauncherError::ExecutionError(
+        "Registry Editor is not supported on this platform".to_string(),
+    ))
+}
+
+/// Opens the tool, asking for elevation when it needs administrator rights
+#[cfg(windows)]
+fn launch_tool(tool: &AdminTool) -> Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let to_wide = |text: &str| -> Vec<u16> {
+        std::ffi::OsStr::new(text)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    };
+
+    let parameters = tool.windows_file.map(|file| {
+        let windows_dir = std::env::var("SystemRoot").unwrap_or_else(|_| r"C:\Windows".to_string());
+        format!("\"{}\\{}\"", windows_dir, file)
+    });
+
+    let verb = to_wide(if tool.elevated { "runas" } else { "open" });
+    let program = to_wide(tool.program);
+    let parameters = parameters.as_deref().map(to_wide);
+
+    let result = unsafe {
+        ShellExecuteW(
+            HWND(std::ptr::null_mut()),
+            PCWSTR(verb.as_ptr()),
+            PCWSTR(program.as_ptr()),
+            parameters.as_ref().map_or(PCWSTR::null(), |p| PCWSTR(p.as_ptr())),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // ShellExecuteW returns a value > 32 on success
+    match result.0 as isize {
+        code if code > 32 => Ok(()),
+        // Also returned when the user declines the elevation prompt
+        5 => Err(LauncherError::SecurityError(format!(
+            "{} was not opened: administrator access was denied",
+            tool.name
+        ))),
+        code => Err(LauncherError::ExecutionError(format!(
+            "Failed to open {} (code: {})",
+            tool.name, code
+        ))),
+    }
+}
+
+#[cfg(not(windows))]
+fn launch_tool(tool: &AdminTool) -> Result<()> {
+    Err(LauncherError::ExecutionError(format!(
+        "{} is not available on this platform",
+        tool.name
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn search(query: &str) -> Vec<SearchResult> {
+        AdminToolsProvider::new().unwrap().search(&query.into()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_alias_matching() {
+        let results = search("services").await;
+        assert_eq!(results[0].id, "admin_tool:services");
+        assert_eq!(results[0].score, 100.0);
+
+        assert_eq!(search("device man").await[0].id, "admin_tool:device_manager");
+        assert_eq!(search("devmgmt").await[0].id, "admin_tool:device_manager");
+        assert_eq!(search("Event Viewer").await[0].id, "admin_tool:event_viewer");
+        assert_eq!(search("env vars").await[0].id, "admin_tool:environment_variables");
+        assert_eq!(search("hosts file").await[0].id, "admin_tool:hosts_file");
+
+        // Later words of a name match too, but rank below prefix matches
+        let results = search("manager").await;
+        assert!(results.iter().any(|r| r.id == "admin_tool:device_manager"));
+        assert!(results.iter().all(|r| r.score < 90.0));
+
+        assert!(search("ev").await.is_empty());
+        assert!(search("spreadsheet").await.is_empty());
+    }
+
+    #[test]
+    fn test_parse_registry_key() {
+        assert_eq!(
+            parse_registry_key(r"HKCU\Software\BetterFinder").as_deref(),
+            Some(r"HKEY_CURRENT_USER\Software\BetterFinder")
+        );
+        assert_eq!(
+            parse_registry_key(r"Computer\hkey_local_machine\SYSTEM\").as_deref(),
+            Some(r"HKEY_LOCAL_MACHINE\SYSTEM")
+        );
+        assert_eq!(parse_registry_key("HKU").as_deref(), Some("HKEY_USERS"));
+
+        assert_eq!(parse_registry_key(r"HKXX\Software"), None);
+        assert_eq!(parse_registry_key(r"HKCU\\Software"), None);
+        assert_eq!(parse_registry_key(&format!(r"HKCU\{}", "k".repeat(256))), None);
+        assert_eq!(parse_registry_key(r"C:\Windows"), None);
+    }
+
+    #[test]
+    fn test_last_key_value() {
+        assert_eq!(
+            last_key_value(r"HKEY_CURRENT_USER\Software\BetterFinder"),
+            r"Computer\HKEY_CURRENT_USER\Software\BetterFinder"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_regedit_query_opens_key() {
+        for query in [r"regedit HKCU\Software\BetterFinder", r"HKCU\Software\BetterFinder"] {
+            let results = search(query).await;
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].title, r"Open HKEY_CURRENT_USER\Software\BetterFinder");
+            assert_eq!(
+                results[0].metadata.get("registry_key"),
+                Some(&serde_json::json!(r"HKEY_CURRENT_USER\Software\BetterFinder"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_registry_path_falls_back_to_plain_regedit() {
+        let results = search(r"regedit HKXX\Software").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "admin_tool:regedit");
+        assert_eq!(results[0].metadata.get("tool"), Some(&serde_json::json!("regedit")));
+        assert!(!results[0].metadata.contains_key("registry_key"));
+        assert!(results[0].subtitle.contains("not a registry key"));
+
+        // Bare invalid paths aren't taken for registry queries at all
+        assert_eq!(parse_regedit_query(r"C:\Windows"), None);
+        assert_eq!(parse_regedit_query("regedit"), None);
+        assert_eq!(search("regedit").await[0].id, "admin_tool:regedit");
+
+        // Words after "registry" are an ordinary search, not a path
+        assert_eq!(parse_regedit_query("registry editor"), None);
+        let results = search("registry editor").await;
+        assert_eq!(results[0].id, "admin_tool:regedit");
+        assert_eq!(results[0].score, 100.0);
+    }
+}
@@ -5,15 +5,18 @@
 /// - Program Files directories (.exe files)
 /// - User AppData directories
 ///
-/// It maintains a cache of applications that is refreshed periodically.
+/// It maintains a cache of applications that is refreshed periodically. Scans
+/// run in the background; searches use whatever the cache holds at the time.
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
-use crate::utils::IconCache;
+use crate::search::completion::{CompletionCandidate, CompletionSource};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{CacheStats, ProviderProgress, ResultAction, ResultType, SearchResult};
+use crate::utils::{normalize_for_search, IconCache};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tokio::sync::RwLock;
@@ -31,6 +34,45 @@ use windows::{
 const MAX_RESULTS: usize = 20;
 const CACHE_REFRESH_INTERVAL: Duration = Duration::from_secs(300); // 5 minutes
 
+/// Maximum executables kept from a single Program Files vendor directory
+const MAX_EXES_PER_VENDOR: usize = 5;
+
+/// Publish partial scan results after this many vendor directories
+const VENDOR_BATCH_SIZE: usize = 10;
+
+/// Helper executables that are never worth launching (`*` matches any text)
+const HELPER_EXE_PATTERNS: &[&str] = &[
+    "unins*",
+    "*uninstall*",
+    "*update*",
+    "crashpad*",
+    "*crashhandler*",
+    "*crashreporter*",
+    "*helper*",
+];
+
+/// Directories that only hold shared components and SDK tools
+const SKIPPED_DIRS: &[&str] = &[
+    "Common Files",
+    "Windows Kits",
+    "Windows Defender",
+    "Windows NT",
+    "Microsoft.NET",
+    "Reference Assemblies",
+];
+
+/// Callback receiving scan progress updates
+type ProgressHandler = Arc<dyn Fn(ProviderProgress) + Send + Sync>;
+
+/// Receives (completed, total) steps from a running scan
+type ScanProgressSink = Box<dyn FnMut(usize, usize) + Send>;
+
+/// Applications found by the scan, shared with providers that launch apps
+pub type AppCache = Arc<RwLock<Vec<Application>>>;
+
+/// Receives everything a running scan has found so far
+type ScanBatchSink = Box<dyn FnMut(&[Application]) + Send>;
+
 /// Represents an installed application
 #[derive(Debug, Clone)]
 pub struct Application {
@@ -42,6 +84,94 @@ pub struct Application {
     pub description: Option<String>,
     /// Whether this is a .lnk file or direct .exe
     pub is_shortcut: bool,
+    /// The .lnk file this entry came from; launching it keeps the shortcut's
+    /// arguments and "Start in" folder
+    pub shortcut_path: Option<PathBuf>,
+    /// Command-line arguments stored in the shortcut
+    pub arguments: Option<String>,
+    /// Working directory ("Start in") stored in the shortcut
+    pub working_dir: Option<PathBuf>,
+    /// Lowercase, diacritic-folded name used for matching
+    pub normalized_name: String,
+}
+
+impl Application {
+    /// Creates an application entry, precomputing its normalized name
+    pub fn new(name: String, path: PathBuf, description: Option<String>, is_shortcut: bool) -> Self {
+        let normalized_name = normalize_for_search(&name);
+
+        Self {
+            name,
+            path,
+            description,
+            is_shortcut,
+            shortcut_path: None,
+            arguments: None,
+            working_dir: None,
+            normalized_name,
+        }
+    }
+
+    /// Records the shortcut an entry was read from, with its arguments and working directory
+    pub fn with_shortcut(
+        mut self,
+        shortcut_path: PathBuf,
+        arguments: Option<String>,
+        working_dir: Option<PathBuf>,
+    ) -> Self {
+        self.shortcut_path = Some(shortcut_path);
+        self.arguments = arguments;
+        self.working_dir = working_dir;
+        self
+    }
+
+    /// Returns what makes two entries the same launch: the target and its arguments
+    fn launch_key(&self) -> (&Path, &str) {
+        (&self.path, self.arguments.as_deref().unwrap_or(""))
+    }
+}
+
+/// What ShellExecuteW opens for an application result
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(not(windows), allow(dead_code))]
+struct LaunchTarget {
+    /// Shortcut or executable to open
+    file: String,
+    /// Arguments passed to an executable
+    parameters: Option<String>,
+    /// Working directory for an executable
+    directory: Option<String>,
+}
+
+impl LaunchTarget {
+    /// Prefers the original shortcut, which applies its own arguments and working
+    /// directory; otherwise launches the target with them passed explicitly
+    fn for_result(path: &str, metadata: &HashMap<String, serde_json::Value>) -> Self {
+        let text = |key: &str| {
+            metadata
+                .get(key)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+        };
+
+        if let Some(shortcut) = text("shortcut_path") {
+            if Path::new(&shortcut).exists() {
+                return Self {
+                    file: shortcut,
+                    parameters: None,
+                    directory: None,
+                };
+            }
+            debug!("Shortcut {} is gone, launching its target directly", shortcut);
+        }
+
+        Self {
+            file: path.to_string(),
+            parameters: text("arguments"),
+            directory: text("working_dir"),
+        }
+    }
 }
 
 /// Application scanner that finds installed applications
@@ -50,35 +180,68 @@ pub struct AppScanner;
 impl AppScanner {
     /// Scans all common locations for installed applications
     pub fn scan_applications() -> Result<Vec<Application>> {
+        Self::scan_applications_with(|_, _| {}, |_| {})
+    }
+
+    /// Scans all locations, reporting progress and partial results as it goes
+    ///
+    /// `on_progress` receives (completed, total) steps, where each Program Files
+    /// vendor directory is one step. `on_batch` receives everything found so far.
+    pub fn scan_applications_with<P, B>(mut on_progress: P, mut on_batch: B) -> Result<Vec<Application>>
+    where
+        P: FnMut(usize, usize),
+        B: FnMut(&[Application]),
+    {
         info!("Starting application scan");
         let mut apps = Vec::new();
 
-        // Scan Start Menu
+        let vendor_dirs: Vec<PathBuf> = Self::program_files_roots()
+            .iter()
+            .flat_map(|root| Self::vendor_dirs(root))
+            .collect();
+        let total = vendor_dirs.len() + 2;
+
+        // Scan Start Menu first; it holds the apps people search for most
         if let Ok(start_menu_apps) = Self::scan_start_menu() {
             debug!("Found {} apps in Start Menu", start_menu_apps.len());
             apps.extend(start_menu_apps);
         }
+        on_batch(&apps);
+        on_progress(1, total);
+
+        // Scan Program Files one vendor directory at a time
+        let before = apps.len();
+        for (index, vendor_dir) in vendor_dirs.iter().enumerate() {
+            apps.extend(Self::scan_vendor_dir(vendor_dir));
+            on_progress(index + 2, total);
 
-        // Scan Program Files
-        if let Ok(program_files_apps) = Self::scan_program_files() {
-            debug!("Found {} apps in Program Files", program_files_apps.len());
-            apps.extend(program_files_apps);
+            if (index + 1) % VENDOR_BATCH_SIZE == 0 {
+                on_batch(&apps);
+            }
         }
+        debug!("Found {} apps in Program Files", apps.len() - before);
 
         // Scan user AppData
         if let Ok(appdata_apps) = Self::scan_appdata() {
             debug!("Found {} apps in AppData", appdata_apps.len());
             apps.extend(appdata_apps);
         }
+        on_progress(total, total);
 
-        // Deduplicate by path
-        apps.sort_by(|a, b| a.path.cmp(&b.path));
-        apps.dedup_by(|a, b| a.path == b.path);
+        let apps = Self::dedup_apps(apps);
 
         info!("Application scan complete: {} unique apps found", apps.len());
         Ok(apps)
     }
 
+    /// Deduplicates applications by target path and arguments, so shortcuts
+    /// starting the same exe with different arguments are all kept
+    fn dedup_apps(mut apps: Vec<Application>) -> Vec<Application> {
+        apps.sort_by(|a, b| a.launch_key().cmp(&b.launch_key()));
+        apps.dedup_by(|a, b| a.launch_key() == b.launch_key());
+        apps
+    }
+
     /// Scans the Start Menu for .lnk files
     fn scan_start_menu() -> Result<Vec<Application>> {
         let mut apps = Vec::new();
@@ -113,22 +276,107 @@ impl AppScanner {
         }
     }
 
-    /// Scans Program Files directories for .exe files
-    fn scan_program_files() -> Result<Vec<Application>> {
-        let mut apps = Vec::new();
-
-        let program_files_paths = vec![
+    /// Gets the Program Files directories
+    fn program_files_roots() -> Vec<PathBuf> {
+        vec![
             std::env::var("ProgramFiles").ok().map(PathBuf::from),
             std::env::var("ProgramFiles(x86)").ok().map(PathBuf::from),
-        ];
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
 
-        for program_files_path in program_files_paths.into_iter().flatten() {
-            if let Ok(found_apps) = Self::scan_directory_for_executables(&program_files_path, 2) {
-                apps.extend(found_apps);
-            }
+    /// Lists the vendor directories directly under a Program Files root
+    fn vendor_dirs(root: &Path) -> Vec<PathBuf> {
+        let entries = match std::fs::read_dir(root) {
+            Ok(e) => e,
+            Err(_) => return Vec::new(), // Skip roots we can't read
+        };
+
+        let mut dirs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir() && !Self::is_skipped_dir(path))
+            .collect();
+        dirs.sort();
+        dirs
+    }
+
+    /// Scans one vendor directory, keeping the executables most likely to be the app
+    ///
+    /// Executables named like their folder come first; at most
+    /// `MAX_EXES_PER_VENDOR` are kept.
+    fn scan_vendor_dir(vendor_dir: &Path) -> Vec<Application> {
+        let mut apps = Self::scan_directory_for_executables(vendor_dir, 2).unwrap_or_default();
+
+        apps.sort_by(|a, b| {
+            Self::folder_resemblance(b, vendor_dir)
+                .cmp(&Self::folder_resemblance(a, vendor_dir))
+                .then_with(|| a.path.components().count().cmp(&b.path.components().count()))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        apps.truncate(MAX_EXES_PER_VENDOR);
+        apps
+    }
+
+    /// Scores how closely an executable's name matches its folder or vendor folder
+    ///
+    /// 2 for an exact match, 1 when one name contains the other, 0 otherwise.
+    fn folder_resemblance(app: &Application, vendor_dir: &Path) -> u8 {
+        let simplify = |s: &str| -> String {
+            s.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(|c| c.to_lowercase())
+                .collect()
+        };
+
+        let exe_name = simplify(&app.name);
+        if exe_name.is_empty() {
+            return 0;
         }
 
-        Ok(apps)
+        [app.path.parent(), Some(vendor_dir)]
+            .into_iter()
+            .flatten()
+            .filter_map(|dir| dir.file_name().and_then(|s| s.to_str()))
+            .map(|folder| {
+                let folder = simplify(folder);
+                if folder == exe_name {
+                    2
+                } else if folder.len() >= 3
+                    && exe_name.len() >= 3
+                    && (folder.contains(&exe_name) || exe_name.contains(&folder))
+                {
+                    1
+                } else {
+                    0
+                }
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns true for executables that match a helper name pattern
+    fn is_helper_exe(name: &str) -> bool {
+        let name = name.to_lowercase();
+
+        HELPER_EXE_PATTERNS.iter().any(|pattern| {
+            match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+                (Some(rest), _) if rest.ends_with('*') => name.contains(&rest[..rest.len() - 1]),
+                (Some(suffix), _) => name.ends_with(suffix),
+                (None, Some(prefix)) => name.starts_with(prefix),
+                (None, None) => name == *pattern,
+            }
+        })
+    }
+
+    /// Returns true for shared-component directories that hold no apps
+    fn is_skipped_dir(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|s| s.to_str())
+            .map(|name| SKIPPED_DIRS.iter().any(|skipped| skipped.eq_ignore_ascii_case(name)))
+            .unwrap_or(false)
     }
 
     /// Scans user AppData for installed applications
@@ -200,19 +448,24 @@ impl AppScanner {
             let path = entry.path();
 
             if path.is_dir() {
+                if Self::is_skipped_dir(&path) {
+                    continue;
+                }
+
                 // Recursively scan subdirectories
                 if let Ok(sub_apps) = Self::scan_directory_for_executables(&path, max_depth - 1) {
                     apps.extend(sub_apps);
                 }
-            } else if path.extension().and_then(|s| s.to_str()) == Some("exe") {
-                // Create application entry from .exe
+            } else if path
+                .extension()
+                .and_then(|s| s.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+            {
+                // Create application entry from .exe, skipping uninstallers and updaters
                 if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
-                    apps.push(Application {
-                        name: name.to_string(),
-                        path: path.clone(),
-                        description: None,
-                        is_shortcut: false,
-                    });
+                    if !Self::is_helper_exe(name) {
+                        apps.push(Application::new(name.to_string(), path.clone(), None, false));
+                    }
                 }
             }
         }
@@ -282,6 +535,22 @@ impl AppScanner {
                     Err(_) => None,
                 };
 
+                // Get arguments (INFOTIPSIZE is their documented limit) and the "Start in" folder
+                let mut arguments_buf = vec![0u16; 1024];
+                let arguments = shell_link
+                    .GetArguments(&mut arguments_buf)
+                    .ok()
+                    .map(|_| Self::wide_to_string(&arguments_buf))
+                    .filter(|args| !args.trim().is_empty());
+
+                let mut working_dir_buf = vec![0u16; 260];
+                let working_dir = shell_link
+                    .GetWorkingDirectory(&mut working_dir_buf)
+                    .ok()
+                    .map(|_| Self::wide_to_string(&working_dir_buf))
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from);
+
                 // Extract name from shortcut filename
                 let name = lnk_path
                     .file_stem()
@@ -289,12 +558,8 @@ impl AppScanner {
                     .unwrap_or("Unknown")
                     .to_string();
 
-                Ok(Application {
-                    name,
-                    path: target_path,
-                    description,
-                    is_shortcut: true,
-                })
+                Ok(Application::new(name, target_path, description, true)
+                    .with_shortcut(lnk_path.to_path_buf(), arguments, working_dir))
             })();
 
             // Uninitialize COM
@@ -304,6 +569,13 @@ impl AppScanner {
         }
     }
 
+    /// Converts a null-terminated UTF-16 buffer to a string
+    #[cfg(windows)]
+    fn wide_to_string(buffer: &[u16]) -> String {
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..len])
+    }
+
     #[cfg(not(windows))]
     fn parse_shortcut(_lnk_path: &Path) -> Result<Application> {
         Err(LauncherError::ProviderError("Shortcut parsing not supported on this platform".to_string()))
@@ -336,11 +608,15 @@ impl AppScanner {
 /// Application search provider with caching
 pub struct AppSearchProvider {
     /// Cached list of applications
-    app_cache: Arc<RwLock<Vec<Application>>>,
+    app_cache: AppCache,
     /// Icon cache for application icons
     icon_cache: Arc<IconCache>,
     /// Last cache refresh time
     last_refresh: Arc<RwLock<SystemTime>>,
+    /// Whether a background scan is running
+    scanning: Arc<AtomicBool>,
+    /// Optional callback for scan progress
+    progress_handler: Option<ProgressHandler>,
     /// Whether the provider is enabled
     enabled: bool,
 }
@@ -354,50 +630,109 @@ impl AppSearchProvider {
             app_cache: Arc::new(RwLock::new(Vec::new())),
             icon_cache: Arc::new(IconCache::new()),
             last_refresh: Arc::new(RwLock::new(SystemTime::UNIX_EPOCH)),
+            scanning: Arc::new(AtomicBool::new(false)),
+            progress_handler: None,
             enabled: true,
         })
     }
 
-    /// Refreshes the application cache
-    async fn refresh_cache(&self) -> Result<()> {
+    /// Publishes scanned applications to `app_cache`, so other providers can
+    /// look up installed apps
+    pub fn with_app_cache(mut self, app_cache: AppCache) -> Self {
+        self.app_cache = app_cache;
+        self
+    }
+
+    /// Sets a callback for scan progress, e.g. to emit `provider-progress` events
+    pub fn set_progress_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(ProviderProgress) + Send + Sync + 'static,
+    {
+        self.progress_handler = Some(Arc::new(handler));
+    }
+
+    /// Returns whether a background scan is running
+    pub fn is_scanning(&self) -> bool {
+        self.scanning.load(Ordering::SeqCst)
+    }
+
+    /// Starts a background scan if the cache is stale; never waits for the scan
+    async fn refresh_cache(&self) {
         let last_refresh = *self.last_refresh.read().await;
-        let now = SystemTime::now();
 
         // Check if refresh is needed
-        if let Ok(elapsed) = now.duration_since(last_refresh) {
+        if let Ok(elapsed) = SystemTime::now().duration_since(last_refresh) {
             if elapsed < CACHE_REFRESH_INTERVAL {
                 debug!("Cache is still fresh, skipping refresh");
-                return Ok(());
+                return;
             }
         }
 
-        info!("Refreshing application cache");
-
-        // Scan applications in a blocking task
-        let apps = tokio::task::spawn_blocking(|| AppScanner::scan_applications())
-            .await
-            .map_err(|e| LauncherError::ProviderError(format!("Failed to scan applications: {}", e)))??;
+        self.start_scan_with(AppScanner::scan_applications_with).await;
+    }
 
-        // Update cache
-        {
-            let mut cache = self.app_cache.write().await;
-            *cache = apps;
-            info!("Application cache updated: {} apps", cache.len());
+    /// Runs `scan` in a blocking task, publishing partial results to the cache
+    ///
+    /// Partial results are only published while the cache is empty; a rescan
+    /// keeps serving the previous list until it completes.
+    async fn start_scan_with<S>(&self, scan: S)
+    where
+        S: FnOnce(ScanProgressSink, ScanBatchSink) -> Result<Vec<Application>> + Send + 'static,
+    {
+        if self.scanning.swap(true, Ordering::SeqCst) {
+            debug!("Application scan already running");
+            return;
         }
 
-        // Update last refresh time
-        {
-            let mut last_refresh = self.last_refresh.write().await;
-            *last_refresh = now;
-        }
+        // Mark the refresh up front so concurrent searches don't queue more scans
+        *self.last_refresh.write().await = SystemTime::now();
+        info!("Refreshing application cache in the background");
 
-        Ok(())
+        let initial = self.app_cache.read().await.is_empty();
+        let app_cache = Arc::clone(&self.app_cache);
+        let batch_cache = Arc::clone(&self.app_cache);
+        let scanning = Arc::clone(&self.scanning);
+        let progress_handler = self.progress_handler.clone();
+
+        tokio::spawn(async move {
+            let on_progress: ScanProgressSink = {
+                let mut last_percent = None;
+                Box::new(move |completed: usize, total: usize| {
+                    let progress = ProviderProgress::new("AppSearch", completed, total);
+                    if last_percent != Some(progress.percent) {
+                        last_percent = Some(progress.percent);
+                        if let Some(handler) = &progress_handler {
+                            handler(progress);
+                        }
+                    }
+                })
+            };
+            let on_batch: ScanBatchSink = Box::new(move |apps: &[Application]| {
+                if initial {
+                    *batch_cache.blocking_write() = AppScanner::dedup_apps(apps.to_vec());
+                }
+            });
+
+            let scanned = tokio::task::spawn_blocking(move || scan(on_progress, on_batch)).await;
+
+            match scanned {
+                Ok(Ok(apps)) => {
+                    let mut cache = app_cache.write().await;
+                    *cache = apps;
+                    info!("Application cache updated: {} apps", cache.len());
+                }
+                Ok(Err(e)) => error!("Application scan failed: {}", e),
+                Err(e) => error!("Failed to scan applications: {}", e),
+            }
+
+            scanning.store(false, Ordering::SeqCst);
+        });
     }
 
     /// Performs fuzzy search on application names
-    fn fuzzy_match(query: &str, app_name: &str) -> Option<f64> {
-        let query_lower = query.to_lowercase();
-        let name_lower = app_name.to_lowercase();
+    ///
+    /// Both arguments are expected to be normalized with `normalize_for_search`.
+    fn fuzzy_match(query_lower: &str, name_lower: &str) -> Option<f64> {
 
         // Exact match
         if name_lower == query_lower {
@@ -405,22 +740,22 @@ impl AppSearchProvider {
         }
 
         // Starts with query
-        if name_lower.starts_with(&query_lower) {
+        if name_lower.starts_with(query_lower) {
             return Some(90.0);
         }
 
         // Contains query
-        if name_lower.contains(&query_lower) {
+        if name_lower.contains(query_lower) {
             return Some(70.0);
         }
 
         // Check for acronym match (e.g., "vsc" matches "Visual Studio Code")
-        if Self::matches_acronym(&query_lower, &name_lower) {
+        if Self::matches_acronym(query_lower, name_lower) {
             return Some(60.0);
         }
 
         // Check for fuzzy character match
-        if Self::fuzzy_char_match(&query_lower, &name_lower) {
+        if Self::fuzzy_char_match(query_lower, name_lower) {
             return Some(40.0);
         }
 
@@ -439,7 +774,7 @@ impl AppSearchProvider {
             .filter_map(|word| word.chars().next())
             .collect();
 
-        acronym.to_lowercase().starts_with(query)
+        acronym.starts_with(query)
     }
 
     /// Checks if all characters in query appear in order in name
@@ -462,6 +797,15 @@ impl AppSearchProvider {
         Some("app-icon".to_string())
     }
 
+    /// Result id of an application
+    fn result_id(app: &Application) -> String {
+        // Shortcuts to the same exe with different arguments are different results
+        match &app.arguments {
+            Some(arguments) => format!("app:{} {}", app.path.display(), arguments),
+            None => format!("app:{}", app.path.display()),
+        }
+    }
+
     /// Converts Application to SearchResult
     async fn convert_to_search_result(&self, app: &Application, score: f64) -> SearchResult {
         let icon = self.get_app_icon(&app.path).await;
@@ -472,9 +816,18 @@ impl AppSearchProvider {
         if let Some(desc) = &app.description {
             metadata.insert("description".to_string(), serde_json::json!(desc));
         }
+        if let Some(shortcut_path) = &app.shortcut_path {
+            metadata.insert("shortcut_path".to_string(), serde_json::json!(shortcut_path.to_string_lossy()));
+        }
+        if let Some(arguments) = &app.arguments {
+            metadata.insert("arguments".to_string(), serde_json::json!(arguments));
+        }
+        if let Some(working_dir) = &app.working_dir {
+            metadata.insert("working_dir".to_string(), serde_json::json!(working_dir.to_string_lossy()));
+        }
 
         SearchResult {
-            id: format!("app:{}", app.path.display()),
+            id: Self::result_id(app),
             title: app.name.clone(),
             subtitle: app.path.to_string_lossy().to_string(),
             icon,
@@ -484,6 +837,9 @@ impl AppSearchProvider {
             action: ResultAction::LaunchApp {
                 path: app.path.to_string_lossy().to_string(),
             },
+            normalized_title: Some(app.normalized_name.clone()),
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 
@@ -493,9 +849,7 @@ impl AppSearchProvider {
             loop {
                 tokio::time::sleep(CACHE_REFRESH_INTERVAL).await;
 
-                if let Err(e) = self.refresh_cache().await {
-                    error!("Background cache refresh failed: {}", e);
-                }
+                self.refresh_cache().await;
             }
         });
     }
@@ -511,23 +865,28 @@ impl SearchProvider for AppSearchProvider {
         85 // High priority, slightly lower than file search
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        if query.trim().is_empty() {
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Application])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if query.query.trim().is_empty() {
             return Ok(Vec::new());
         }
 
-        debug!("Searching applications for query: '{}'", query);
+        debug!("Searching applications for query: '{}'", query.query);
 
-        // Ensure cache is populated
-        self.refresh_cache().await?;
+        // Kick off a scan when stale; results come from whatever is cached now
+        self.refresh_cache().await;
 
         // Get cached applications
         let apps = self.app_cache.read().await;
 
-        // Perform fuzzy search
+        // Perform fuzzy search against the precomputed names, which are always folded
+        let normalized_query = normalize_for_search(query.query.trim());
         let mut results = Vec::new();
         for app in apps.iter() {
-            if let Some(score) = Self::fuzzy_match(query, &app.name) {
+            if let Some(score) = Self::fuzzy_match(&normalized_query, &app.normalized_name) {
                 let result = self.convert_to_search_result(app, score).await;
                 results.push(result);
             }
@@ -552,7 +911,7 @@ impl SearchProvider for AppSearchProvider {
 
         match &result.action {
             ResultAction::LaunchApp { path } => {
-                Self::launch_application(path).await
+                Self::launch_application(&LaunchTarget::for_result(path, &result.metadata)).await
             }
             _ => Err(LauncherError::ExecutionError(
                 "Invalid action for application result".to_string(),
@@ -560,13 +919,62 @@ impl SearchProvider for AppSearchProvider {
         }
     }
 
+    async fn suggestion_terms(&self, limit: usize) -> Vec<String> {
+        let apps = self.app_cache.read().await;
+        apps.iter().take(limit).map(|app| app.name.clone()).collect()
+    }
+
+    async fn completion_candidates(&self, query: &str) -> Vec<CompletionCandidate> {
+        let query = normalize_for_search(query);
+        let apps = self.app_cache.read().await;
+        apps.iter()
+            .filter(|app| app.normalized_name.starts_with(&query))
+            .map(|app| CompletionCandidate::new(app.name.clone(), Self::result_id(app), CompletionSource::App))
+            .collect()
+    }
+
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        let apps = self.app_cache.read().await;
+        let bytes = apps
+            .iter()
+            .map(|app| {
+                std::mem::size_of::<Application>()
+                    + app.name.len()
+                    + app.normalized_name.len()
+                    + app.path.as_os_str().len()
+                    + app.shortcut_path.as_ref().map_or(0, |path| path.as_os_str().len())
+            })
+            .sum();
+
+        vec![
+            CacheStats {
+                name: "Applications".to_string(),
+                entries: apps.len(),
+                bytes,
+                byte_budget: None,
+            },
+            self.icon_cache.stats().await,
+        ]
+    }
+
+    async fn trim_caches(&self) -> usize {
+        // The application list is what searches run against, so only icons are dropped
+        self.icon_cache.trim().await
+    }
+
+    async fn refresh_after_resume(&self) {
+        // Apps may have been installed or removed while the system slept
+        self.start_scan_with(AppScanner::scan_applications_with).await;
+    }
+
     fn is_enabled(&self) -> bool {
         self.enabled
     }
 
     async fn initialize(&mut self) -> Result<()> {
         info!("Initializing AppSearchProvider");
-        self.refresh_cache().await?;
+        // The scan runs in the background so startup never waits on Program Files
+        self.refresh_cache().await;
         Ok(())
     }
 }
@@ -577,6 +985,8 @@ impl Default for AppSearchProvider {
             app_cache: Arc::new(RwLock::new(Vec::new())),
             icon_cache: Arc::new(IconCache::new()),
             last_refresh: Arc::new(RwLock::new(SystemTime::UNIX_EPOCH)),
+            scanning: Arc::new(AtomicBool::new(false)),
+            progress_handler: None,
             enabled: false,
         })
     }
@@ -585,8 +995,9 @@ impl Default for AppSearchProvider {
 impl AppSearchProvider {
     /// Launches an application using Windows ShellExecute API
     #[cfg(windows)]
-    async fn launch_application(path: &str) -> Result<()> {
-        info!("Launching application: {}", path);
+    async fn launch_application(target: &LaunchTarget) -> Result<()> {
+        let path = target.file.as_str();
+        info!("Launching application: {} (arguments: {:?})", path, target.parameters);
 
         let app_path = PathBuf::from(path);
 
@@ -600,9 +1011,9 @@ impl AppSearchProvider {
         }
 
         // Launch application in a blocking task
-        let path_owned = path.to_string();
+        let target_owned = target.clone();
         tokio::task::spawn_blocking(move || {
-            Self::launch_application_sync(&path_owned)
+            Self::launch_application_sync(&target_owned)
         })
         .await
         .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn launch task: {}", e)))??;
@@ -613,18 +1024,24 @@ impl AppSearchProvider {
 
     /// Synchronously launches an application using ShellExecute
     #[cfg(windows)]
-    fn launch_application_sync(path: &str) -> Result<()> {
+    fn launch_application_sync(target: &LaunchTarget) -> Result<()> {
         use std::os::windows::ffi::OsStrExt;
         use windows::Win32::UI::Shell::ShellExecuteW;
         use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
         use windows::Win32::Foundation::HWND;
 
-        unsafe {
-            // Convert path to wide string
-            let path_wide: Vec<u16> = std::ffi::OsStr::new(path)
+        let to_wide = |text: &str| -> Vec<u16> {
+            std::ffi::OsStr::new(text)
                 .encode_wide()
                 .chain(std::iter::once(0))
-                .collect();
+                .collect()
+        };
+
+        unsafe {
+            // Convert path, arguments and working directory to wide strings
+            let path_wide = to_wide(&target.file);
+            let parameters_wide = target.parameters.as_deref().map(to_wide);
+            let directory_wide = target.directory.as_deref().map(to_wide);
 
             // Convert "open" verb to wide string
             let verb_wide: Vec<u16> = std::ffi::OsStr::new("open")
@@ -637,8 +1054,8 @@ impl AppSearchProvider {
                 HWND(std::ptr::null_mut()),
                 PCWSTR(verb_wide.as_ptr()),
                 PCWSTR(path_wide.as_ptr()),
-                PCWSTR::null(),
-                PCWSTR::null(),
+                parameters_wide.as_ref().map_or(PCWSTR::null(), |p| PCWSTR(p.as_ptr())),
+                directory_wide.as_ref().map_or(PCWSTR::null(), |d| PCWSTR(d.as_ptr())),
                 SW_SHOWNORMAL,
             );
 
@@ -675,9 +1092,9 @@ impl AppSearchProvider {
     }
 
     #[cfg(not(windows))]
-    async fn launch_application(path: &str) -> Result<()> {
+    async fn launch_application(target: &LaunchTarget) -> Result<()> {
         Err(LauncherError::ExecutionError(
-            format!("Application launching not supported on this platform: {}", path)
+            format!("Application launching not supported on this platform: {}", target.file)
         ))
     }
 }
@@ -773,7 +1190,7 @@ mod tests {
         let test_queries = vec!["notepad", "calc", "paint"];
 
         for query in test_queries {
-            match provider.search(query).await {
+            match provider.search(&query.into()).await {
                 Ok(results) => {
                     println!("Search for '{}' found {} results", query, results.len());
                     for result in results.iter().take(3) {
@@ -804,7 +1221,234 @@ mod tests {
         }
     }
 
+    /// Builds a throwaway Program Files tree under the temp directory
+    fn synthetic_program_files(name: &str, files: &[&str]) -> PathBuf {
+        let mut root = std::env::temp_dir();
+        root.push("BetterFinder");
+        root.push(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+
+        for file in files {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, b"").unwrap();
+        }
+
+        root
+    }
+
+    fn app_names(apps: &[Application]) -> Vec<String> {
+        apps.iter().map(|app| app.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_helper_exe_patterns() {
+        assert!(AppScanner::is_helper_exe("unins000"));
+        assert!(AppScanner::is_helper_exe("Uninstall"));
+        assert!(AppScanner::is_helper_exe("GoogleUpdate"));
+        assert!(AppScanner::is_helper_exe("crashpad_handler"));
+        assert!(AppScanner::is_helper_exe("SlackHelper"));
+        assert!(!AppScanner::is_helper_exe("Code"));
+        assert!(!AppScanner::is_helper_exe("firefox"));
+    }
+
+    #[test]
+    fn test_vendor_scan_filters_helpers_and_skipped_dirs() {
+        let root = synthetic_program_files(
+            "app_scan_filter_test",
+            &[
+                "Acme/Acme.exe",
+                "Acme/unins000.exe",
+                "Acme/AcmeUpdater.exe",
+                "Acme/bin/crashpad_handler.exe",
+                "Acme/bin/render-helper.exe",
+                "Common Files/Shared/shared.exe",
+                "Windows Kits/10/bin/signtool.exe",
+            ],
+        );
+
+        let vendors = AppScanner::vendor_dirs(&root);
+        let vendor_names: Vec<_> = vendors
+            .iter()
+            .map(|dir| dir.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(vendor_names, vec!["Acme".to_string()]);
+
+        let apps = AppScanner::scan_vendor_dir(&vendors[0]);
+        assert_eq!(app_names(&apps), vec!["Acme".to_string()]);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_vendor_scan_prefers_folder_name_and_caps_results() {
+        let root = synthetic_program_files(
+            "app_scan_cap_test",
+            &[
+                "Vendor/Tools/a_tool.exe",
+                "Vendor/Tools/b_tool.exe",
+                "Vendor/Tools/c_tool.exe",
+                "Vendor/Tools/d_tool.exe",
+                "Vendor/Tools/e_tool.exe",
+                "Vendor/Tools/f_tool.exe",
+                "Vendor/Tools/g_tool.exe",
+                "Vendor/Editor/Editor.exe",
+                "Vendor/VendorApp.exe",
+            ],
+        );
+
+        let apps = AppScanner::scan_vendor_dir(&root.join("Vendor"));
+        let names = app_names(&apps);
+
+        assert_eq!(apps.len(), MAX_EXES_PER_VENDOR);
+        assert_eq!(names[0], "Editor");
+        assert_eq!(names[1], "VendorApp");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn test_search_does_not_wait_for_scan() {
+        let provider = AppSearchProvider::new().unwrap();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+
+        provider
+            .start_scan_with(move |mut on_progress, mut on_batch| {
+                let partial = vec![Application::new("Notepad".to_string(), PathBuf::from("C:\\notepad.exe"), None, false)];
+                on_batch(&partial);
+                on_progress(1, 2);
+
+                // Hold the scan open until the test has searched
+                release_rx.recv().ok();
+
+                let mut complete = partial;
+                complete.push(Application::new("Calculator".to_string(), PathBuf::from("C:\\calc.exe"), None, false));
+                on_progress(2, 2);
+                Ok(complete)
+            })
+            .await;
+        assert!(provider.is_scanning());
+
+        // Wait for the partial batch to be published
+        for _ in 0..100 {
+            if !provider.app_cache.read().await.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let results = tokio::time::timeout(Duration::from_millis(500), provider.search(&"notepad".into()))
+            .await
+            .expect("search must not wait for the running scan")
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(provider.search(&"calc".into()).await.unwrap().is_empty());
+        assert!(provider.is_scanning());
+
+        release_tx.send(()).unwrap();
+        for _ in 0..100 {
+            if !provider.is_scanning() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert!(!provider.is_scanning());
+        assert_eq!(provider.search(&"calc".into()).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_scan_reports_progress() {
+        let mut provider = AppSearchProvider::new().unwrap();
+        let updates = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let updates_clone = Arc::clone(&updates);
+        provider.set_progress_handler(move |progress: ProviderProgress| {
+            updates_clone.lock().unwrap().push(progress.percent);
+        });
+
+        provider
+            .start_scan_with(|mut on_progress, _on_batch| {
+                for completed in [1, 1, 2, 4] {
+                    on_progress(completed, 4);
+                }
+                Ok(Vec::new())
+            })
+            .await;
+
+        for _ in 0..100 {
+            if !provider.is_scanning() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // Repeated percentages are only reported once
+        assert_eq!(*updates.lock().unwrap(), vec![25, 50, 100]);
+    }
+
     #[cfg(windows)]
+    #[test]
+    fn test_dedup_keeps_shortcuts_with_different_arguments() {
+        let exe = PathBuf::from("C:\\Apps\\Browser\\browser.exe");
+        let shortcut = |name: &str, args: Option<&str>| {
+            Application::new(name.to_string(), exe.clone(), None, true).with_shortcut(
+                PathBuf::from(format!("C:\\Start Menu\\{}.lnk", name)),
+                args.map(str::to_string),
+                None,
+            )
+        };
+
+        let apps = AppScanner::dedup_apps(vec![
+            shortcut("Browser", None),
+            shortcut("Browser (Work)", Some("--profile-directory=Work")),
+            shortcut("Browser (Personal)", Some("--profile-directory=Personal")),
+            shortcut("Browser (Work) copy", Some("--profile-directory=Work")),
+            Application::new("browser".to_string(), exe.clone(), None, false),
+        ]);
+
+        let mut arguments: Vec<_> = apps.iter().map(|app| app.arguments.clone()).collect();
+        arguments.sort();
+        assert_eq!(
+            arguments,
+            vec![
+                None,
+                Some("--profile-directory=Personal".to_string()),
+                Some("--profile-directory=Work".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shortcut_arguments_reach_launch_target() {
+        let provider = AppSearchProvider::new().unwrap();
+        let root = synthetic_program_files("launch_target", &["Work.lnk"]);
+        let exe = PathBuf::from("C:\\Apps\\Browser\\browser.exe");
+        let app = Application::new("Browser (Work)".to_string(), exe.clone(), None, true)
+            .with_shortcut(
+                root.join("Work.lnk"),
+                Some("--profile-directory=Work".to_string()),
+                Some(PathBuf::from("C:\\Apps\\Browser")),
+            );
+
+        let result = provider.convert_to_search_result(&app, 1.0).await;
+        assert_eq!(result.id, format!("app:{} --profile-directory=Work", exe.display()));
+        assert_eq!(result.metadata["arguments"], "--profile-directory=Work");
+
+        let exe_path = exe.to_string_lossy().to_string();
+
+        // The shortcut itself is launched while it still exists
+        let target = LaunchTarget::for_result(&exe_path, &result.metadata);
+        assert_eq!(target.file, root.join("Work.lnk").to_string_lossy());
+        assert_eq!(target.parameters, None);
+
+        // Otherwise the exe gets the shortcut's arguments and working directory
+        std::fs::remove_dir_all(&root).unwrap();
+        let target = LaunchTarget::for_result(&exe_path, &result.metadata);
+        assert_eq!(target.file, exe_path);
+        assert_eq!(target.parameters.as_deref(), Some("--profile-directory=Work"));
+        assert_eq!(target.directory.as_deref(), Some("C:\\Apps\\Browser"));
+    }
+
     #[test]
     fn test_file_exists() {
         // Test with Windows system file
@@ -1,31 +1,110 @@
 /// Bookmark provider for searching browser bookmarks
 ///
-/// This provider searches bookmarks from Chrome, Edge, and Firefox browsers,
-/// allowing users to quickly access their saved websites.
+/// This provider searches bookmarks from Chrome, Edge, Opera, Opera GX and
+/// Firefox, plus any bookmark files the user registered in settings, allowing
+/// users to quickly access their saved websites. Icons come from the
+/// browsers' own favicon databases; downloading the missing ones is opt-in.
+/// Bookmarks open in the default browser, or, when settings ask for it, in
+/// the browser and profile they were saved in.
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::search::provider_config::{ConfigFlag, ConfigOption, ProviderConfig};
+use crate::search::providers::app_search::AppCache;
+use crate::search::providers::browser_favicons::{FaviconDatabase, FaviconFetcher, HttpFaviconFetcher};
+use crate::search::providers::browser_launch::{self, BookmarkLaunch, OpenInSourceBrowser};
+use crate::search::{QueryContext, SearchProvider};
+use crate::settings::{BookmarkFileFormat, CustomBookmarkFile};
+use crate::types::{CacheStats, ProviderHealth, ResultAction, ResultType, SearchResult};
+use crate::utils::process::{self, Wait};
+use crate::utils::{normalize_for_search, ByteBudgetCache};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, error, info, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
 /// Maximum number of bookmarks to cache
 const MAX_BOOKMARKS: usize = 1000;
 
-/// Cache refresh interval in seconds
-const CACHE_REFRESH_INTERVAL: u64 = 300; // 5 minutes
+/// Largest Chromium bookmarks file that will be parsed
+const MAX_BOOKMARK_FILE_SIZE: u64 = 64 * 1024 * 1024; // 64 MB
+
+/// Root folders of a Chromium bookmarks file, in display order
+const CHROME_ROOT_KEYS: &[&str] = &["bookmark_bar", "other", "synced"];
+
+/// URL prefixes that cannot be opened from the launcher
+const IGNORED_URL_PREFIXES: &[&str] = &["chrome://", "edge://", "opera://", "javascript:"];
+
+/// Opera profile folders under `%APPDATA%\Opera Software`
+const OPERA_PROFILE_DIR: &str = "Opera Stable";
+const OPERA_GX_PROFILE_DIR: &str = "Opera GX Stable";
+
+/// Safety-net full refresh interval in seconds
+const FALLBACK_REFRESH_INTERVAL: u64 = 1800; // 30 minutes
+
+/// Options under `provider_settings.bookmarks`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BookmarkConfig {
+    /// Seconds between full reloads of every bookmark file
+    pub refresh_interval_secs: u64,
+    /// Whether icons the browsers don't have are downloaded from each site
+    pub download_missing_favicons: bool,
+}
+
+impl Default for BookmarkConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: FALLBACK_REFRESH_INTERVAL,
+            download_missing_favicons: false,
+        }
+    }
+}
+
+impl ProviderConfig for BookmarkConfig {
+    const KEY: &'static str = "bookmarks";
+    const PROVIDER: &'static str = "Bookmarks";
+    const OPTIONS: &'static [ConfigOption] = &[ConfigOption {
+        field: "refresh_interval_secs",
+        description: "Seconds between full reloads; changed files reload right away",
+        min: 60,
+        max: 24 * 3600,
+    }];
+    const FLAGS: &'static [ConfigFlag] = &[ConfigFlag {
+        field: "download_missing_favicons",
+        description: "Download icons the browsers haven't stored from each site (contacts every bookmarked site)",
+    }];
+}
+
+/// Interval for re-locating bookmark files in seconds
+const LOCATOR_INTERVAL: u64 = 300; // 5 minutes
+
+/// Quiet period after the last change before a bookmark file is re-parsed
+const WATCH_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// How often pending changes are checked against the debounce window
+const DEBOUNCE_TICK: Duration = Duration::from_millis(500);
+
+/// Maximum number of cached favicons
+const FAVICON_CACHE_CAPACITY: usize = 500;
+
+/// Maximum total size of cached favicons (base64 text)
+const FAVICON_CACHE_BYTES: usize = 2 * 1024 * 1024; // 2 MB
 
 /// Supported browser types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BrowserType {
     Chrome,
     Edge,
     Firefox,
+    Opera,
+    OperaGx,
 }
 
 impl BrowserType {
@@ -35,8 +114,81 @@ impl BrowserType {
             BrowserType::Chrome => "Chrome",
             BrowserType::Edge => "Edge",
             BrowserType::Firefox => "Firefox",
+            BrowserType::Opera => "Opera",
+            BrowserType::OperaGx => "Opera GX",
         }
     }
+
+    /// Returns the format of the browser's bookmark file
+    pub fn format(&self) -> BookmarkFileFormat {
+        match self {
+            BrowserType::Firefox => BookmarkFileFormat::Firefox,
+            _ => BookmarkFileFormat::Chromium,
+        }
+    }
+}
+
+/// Where a bookmark came from: a detected browser or a user-registered file
+///
+/// Untagged so browsers still serialize as their bare name, which keeps
+/// previously stored bookmarks readable. Custom sources carry their label.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BookmarkOrigin {
+    Browser(BrowserType),
+    Custom(String),
+}
+
+impl BookmarkOrigin {
+    /// Returns the browser display name or the user's label
+    pub fn display_name(&self) -> &str {
+        match self {
+            BookmarkOrigin::Browser(browser) => browser.display_name(),
+            BookmarkOrigin::Custom(label) => label,
+        }
+    }
+}
+
+impl From<BrowserType> for BookmarkOrigin {
+    fn from(browser: BrowserType) -> Self {
+        BookmarkOrigin::Browser(browser)
+    }
+}
+
+impl PartialEq<BrowserType> for BookmarkOrigin {
+    fn eq(&self, other: &BrowserType) -> bool {
+        matches!(self, BookmarkOrigin::Browser(browser) if browser == other)
+    }
+}
+
+/// Identifies a bookmark source: its browser or label, and the browser
+/// profile when the browser has several
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceKey {
+    pub origin: BookmarkOrigin,
+    pub profile: Option<String>,
+}
+
+impl SourceKey {
+    /// Returns the origin's name, followed by the profile when there is one
+    pub fn display_name(&self) -> String {
+        match &self.profile {
+            Some(profile) => format!("{} ({})", self.origin.display_name(), profile),
+            None => self.origin.display_name().to_string(),
+        }
+    }
+}
+
+impl From<BookmarkOrigin> for SourceKey {
+    fn from(origin: BookmarkOrigin) -> Self {
+        Self { origin, profile: None }
+    }
+}
+
+impl From<BrowserType> for SourceKey {
+    fn from(browser: BrowserType) -> Self {
+        BookmarkOrigin::from(browser).into()
+    }
 }
 
 /// Represents a browser bookmark
@@ -48,27 +200,53 @@ pub struct Bookmark {
     pub url: String,
     /// Folder/path in bookmark hierarchy
     pub folder: Option<String>,
-    /// Browser this bookmark is from
-    pub browser: BrowserType,
+    /// Browser or custom source this bookmark is from
+    pub browser: BookmarkOrigin,
     /// Base64 encoded favicon (if available)
     pub favicon: Option<String>,
+    /// Browser profile the bookmark was saved in: the profile folder for
+    /// Chromium browsers, the `profiles.ini` name for Firefox
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Normalized title, computed once when the bookmark is loaded
+    #[serde(skip)]
+    normalized_title: String,
+    /// Normalized URL, computed once when the bookmark is loaded
+    #[serde(skip)]
+    normalized_url: String,
 }
 
 impl Bookmark {
     /// Creates a new bookmark
-    pub fn new(title: String, url: String, browser: BrowserType) -> Self {
+    pub fn new(title: String, url: String, browser: impl Into<BookmarkOrigin>) -> Self {
+        let normalized_title = normalize_for_search(&title);
+        let normalized_url = normalize_for_search(&url);
+
         Self {
             title,
             url,
             folder: None,
-            browser,
+            browser: browser.into(),
             favicon: None,
+            profile: None,
+            normalized_title,
+            normalized_url,
         }
     }
 
     /// Creates a unique ID for the bookmark
+    ///
+    /// The same page saved in two profiles gives two results.
     pub fn id(&self) -> String {
-        format!("bookmark:{}:{}", self.browser.display_name(), self.url)
+        format!("bookmark:{}:{}", self.source_key().display_name(), self.url)
+    }
+
+    /// Returns the source the bookmark was loaded from
+    pub fn source_key(&self) -> SourceKey {
+        SourceKey {
+            origin: self.browser.clone(),
+            profile: self.profile.clone(),
+        }
     }
 
     /// Returns a display subtitle showing the URL and browser
@@ -81,134 +259,316 @@ impl Bookmark {
     }
 }
 
-/// Chrome/Edge bookmark structure (JSON format)
-#[derive(Debug, Deserialize)]
-struct ChromeBookmarkRoot {
-    roots: ChromeBookmarkRoots,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChromeBookmarkRoots {
-    bookmark_bar: ChromeBookmarkNode,
-    other: ChromeBookmarkNode,
-    #[serde(default)]
-    synced: Option<ChromeBookmarkNode>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChromeBookmarkNode {
-    #[serde(default)]
-    name: String,
-    #[serde(rename = "type")]
-    node_type: String,
-    #[serde(default)]
-    url: Option<String>,
-    #[serde(default)]
-    children: Vec<ChromeBookmarkNode>,
+/// Statistics from parsing a browser's bookmark file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BookmarkParseStats {
+    /// Bookmarks added to the cache
+    pub parsed: usize,
+    /// Well-formed nodes that were intentionally ignored (internal URLs, separators)
+    pub skipped: usize,
+    /// Malformed nodes, truncated data, or failed parses
+    pub errors: usize,
+    /// Why the latest load of the file failed, naming it; cleared once it loads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
 }
 
-/// Parser for Chrome/Edge bookmarks
+/// Parser for Chromium bookmarks (Chrome, Edge, Opera)
+///
+/// The file is walked at the JSON value level so that a single malformed
+/// node only drops itself, not its siblings or the whole file.
 pub struct ChromeBookmarkParser;
 
 impl ChromeBookmarkParser {
-    /// Parses Chrome or Edge bookmarks from the Bookmarks file
-    pub fn parse(path: &PathBuf, browser: BrowserType) -> Result<Vec<Bookmark>> {
-        debug!("Parsing {} bookmarks from: {:?}", browser.display_name(), path);
+    /// Parses Chromium bookmarks from the Bookmarks file
+    pub fn parse(path: &PathBuf, origin: impl Into<BookmarkOrigin>) -> Result<Vec<Bookmark>> {
+        Self::parse_with_stats(path, origin).map(|(bookmarks, _)| bookmarks)
+    }
+
+    /// Parses a Bookmarks file, reporting what was parsed, skipped and broken
+    pub fn parse_with_stats(
+        path: &PathBuf,
+        origin: impl Into<BookmarkOrigin>,
+    ) -> Result<(Vec<Bookmark>, BookmarkParseStats)> {
+        let origin = origin.into();
+        debug!("Parsing {} bookmarks from: {:?}", origin.display_name(), path);
 
         if !path.exists() {
             warn!("Bookmark file not found: {:?}", path);
-            return Ok(Vec::new());
+            return Ok((Vec::new(), BookmarkParseStats::default()));
+        }
+
+        let size = std::fs::metadata(path)
+            .map_err(|e| LauncherError::SearchError(format!("Failed to read bookmarks: {}", e)))?
+            .len();
+
+        if size > MAX_BOOKMARK_FILE_SIZE {
+            return Err(LauncherError::SearchError(format!(
+                "Bookmarks file is too large ({} bytes, limit {})",
+                size, MAX_BOOKMARK_FILE_SIZE
+            )));
         }
 
         let content = std::fs::read_to_string(path)
             .map_err(|e| LauncherError::SearchError(format!("Failed to read bookmarks: {}", e)))?;
 
-        let root: ChromeBookmarkRoot = serde_json::from_str(&content)
-            .map_err(|e| LauncherError::SearchError(format!("Failed to parse bookmarks: {}", e)))?;
+        let (bookmarks, stats) = Self::parse_str(&content, &origin)?;
 
-        let mut bookmarks = Vec::new();
+        info!(
+            "Parsed {} bookmarks from {} ({} skipped, {} errors)",
+            stats.parsed,
+            origin.display_name(),
+            stats.skipped,
+            stats.errors
+        );
+        Ok((bookmarks, stats))
+    }
 
-        // Parse bookmark bar
-        Self::parse_node(&root.roots.bookmark_bar, None, browser, &mut bookmarks);
+    /// Parses the contents of a Bookmarks file
+    ///
+    /// Truncated or corrupted files are cut back to the last complete node
+    /// and parsed from there.
+    fn parse_str(content: &str, origin: &BookmarkOrigin) -> Result<(Vec<Bookmark>, BookmarkParseStats)> {
+        let mut stats = BookmarkParseStats::default();
+
+        let root: serde_json::Value = match serde_json::from_str(content) {
+            Ok(root) => root,
+            Err(e) => {
+                let offset = Self::error_offset(content, &e);
+                let repaired = Self::repair_truncated(&content[..offset])
+                    .and_then(|repaired| serde_json::from_str(&repaired).ok())
+                    .ok_or_else(|| {
+                        LauncherError::SearchError(format!("Failed to parse bookmarks: {}", e))
+                    })?;
+
+                warn!(
+                    "{} bookmarks file is malformed ({}), keeping entries before the error",
+                    origin.display_name(),
+                    e
+                );
+                stats.errors += 1;
+                repaired
+            }
+        };
 
-        // Parse other bookmarks
-        Self::parse_node(&root.roots.other, None, browser, &mut bookmarks);
+        let roots = root
+            .get("roots")
+            .and_then(|roots| roots.as_object())
+            .ok_or_else(|| {
+                LauncherError::SearchError("Failed to parse bookmarks: missing roots".to_string())
+            })?;
 
-        // Parse synced bookmarks if available
-        if let Some(synced) = root.roots.synced {
-            Self::parse_node(&synced, None, browser, &mut bookmarks);
+        let mut bookmarks = Vec::new();
+
+        // Some Edge builds omit `other` or `bookmark_bar`, so every root is optional
+        for key in CHROME_ROOT_KEYS {
+            if let Some(node) = roots.get(*key) {
+                Self::parse_node(node, None, origin, &mut bookmarks, &mut stats);
+            }
         }
 
-        info!("Parsed {} bookmarks from {}", bookmarks.len(), browser.display_name());
-        Ok(bookmarks)
+        Ok((bookmarks, stats))
     }
 
-    /// Recursively parses bookmark nodes
+    /// Recursively parses bookmark nodes, skipping malformed ones
     fn parse_node(
-        node: &ChromeBookmarkNode,
-        parent_folder: Option<String>,
-        browser: BrowserType,
+        node: &serde_json::Value,
+        parent_folder: Option<&str>,
+        origin: &BookmarkOrigin,
         bookmarks: &mut Vec<Bookmark>,
+        stats: &mut BookmarkParseStats,
     ) {
-        if node.node_type == "url" {
-            if let Some(url) = &node.url {
-                let mut bookmark = Bookmark::new(node.name.clone(), url.clone(), browser);
-                bookmark.folder = parent_folder;
-                bookmarks.push(bookmark);
-            }
-        } else if node.node_type == "folder" {
-            let folder_path = if let Some(parent) = parent_folder {
-                format!("{}/{}", parent, node.name)
-            } else {
-                node.name.clone()
-            };
+        let Some(node) = node.as_object() else {
+            stats.errors += 1;
+            return;
+        };
 
-            for child in &node.children {
-                Self::parse_node(child, Some(folder_path.clone()), browser, bookmarks);
-            }
-        }
-    }
+        let name = node.get("name").and_then(|v| v.as_str()).unwrap_or_default();
 
-    /// Locates the Chrome bookmarks file
-    pub fn locate_chrome_bookmarks() -> Option<PathBuf> {
-        #[cfg(windows)]
-        {
-            if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
-                let path = PathBuf::from(local_app_data)
-                    .join("Google")
-                    .join("Chrome")
-                    .join("User Data")
-                    .join("Default")
-                    .join("Bookmarks");
-
-                if path.exists() {
-                    return Some(path);
+        match node.get("type").and_then(|v| v.as_str()) {
+            Some("url") => match node.get("url").and_then(|v| v.as_str()) {
+                Some(url) if Self::is_ignored_url(url) => stats.skipped += 1,
+                Some(url) => {
+                    let mut bookmark = Bookmark::new(name.to_string(), url.to_string(), origin.clone());
+                    bookmark.folder = parent_folder.map(str::to_string);
+                    bookmarks.push(bookmark);
+                    stats.parsed += 1;
+                }
+                None => stats.errors += 1,
+            },
+            Some("folder") => {
+                let folder_path = match parent_folder {
+                    Some(parent) => format!("{}/{}", parent, name),
+                    None => name.to_string(),
+                };
+
+                match node.get("children") {
+                    Some(serde_json::Value::Array(children)) => {
+                        for child in children {
+                            Self::parse_node(child, Some(&folder_path), origin, bookmarks, stats);
+                        }
+                    }
+                    None => {}
+                    Some(_) => stats.errors += 1,
                 }
             }
+            Some(_) => stats.skipped += 1,
+            None => stats.errors += 1,
         }
+    }
 
-        None
+    /// Checks whether a URL points at browser internals or scripts
+    fn is_ignored_url(url: &str) -> bool {
+        let url = url.trim_start().to_ascii_lowercase();
+        IGNORED_URL_PREFIXES.iter().any(|prefix| url.starts_with(prefix))
     }
 
-    /// Locates the Edge bookmarks file
-    pub fn locate_edge_bookmarks() -> Option<PathBuf> {
-        #[cfg(windows)]
-        {
-            if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
-                let path = PathBuf::from(local_app_data)
-                    .join("Microsoft")
-                    .join("Edge")
-                    .join("User Data")
-                    .join("Default")
-                    .join("Bookmarks");
-
-                if path.exists() {
-                    return Some(path);
+    /// Converts the line/column of a parse error into a byte offset
+    fn error_offset(content: &str, error: &serde_json::Error) -> usize {
+        let line_start: usize = content
+            .split_inclusive('\n')
+            .take(error.line().saturating_sub(1))
+            .map(str::len)
+            .sum();
+
+        let mut offset = (line_start + error.column()).min(content.len());
+        while !content.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Cuts JSON back to the last complete object or array and closes the open ones
+    ///
+    /// Returns `None` if no container was ever completed.
+    fn repair_truncated(content: &str) -> Option<String> {
+        let mut open = Vec::new();
+        let mut last_complete = None;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        for (i, byte) in content.bytes().enumerate() {
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+
+            match byte {
+                b'"' => in_string = true,
+                b'{' | b'[' => open.push(byte),
+                b'}' | b']' => {
+                    open.pop()?;
+                    // Only pushes follow the last close, so `open[..depth]`
+                    // is still the stack as it was at this point
+                    last_complete = Some((i + 1, open.len()));
                 }
+                _ => {}
             }
         }
 
-        None
+        let (end, depth) = last_complete?;
+        let mut repaired = content[..end].to_string();
+        for opener in open[..depth].iter().rev() {
+            repaired.push(if *opener == b'{' { '}' } else { ']' });
+        }
+
+        Some(repaired)
+    }
+
+    /// Locates the bookmark files of every Chrome profile
+    pub fn locate_chrome_profiles() -> Vec<LocatedProfile> {
+        Self::locate_user_data(&["Google", "Chrome"])
+    }
+
+    /// Locates the bookmark files of every Edge profile
+    pub fn locate_edge_profiles() -> Vec<LocatedProfile> {
+        Self::locate_user_data(&["Microsoft", "Edge"])
+    }
+
+    /// Locates the profiles of a Chromium browser's `User Data` under `%LOCALAPPDATA%`
+    fn locate_user_data(vendor_dirs: &[&str]) -> Vec<LocatedProfile> {
+        let Some(local_app_data) = std::env::var_os("LOCALAPPDATA") else {
+            return Vec::new();
+        };
+        let user_data = PathBuf::from(local_app_data)
+            .join(vendor_dirs.iter().collect::<PathBuf>())
+            .join("User Data");
+        Self::resolve_profiles(&user_data)
+    }
+
+    /// Finds the Bookmarks file of each profile in a Chromium `User Data` folder
+    ///
+    /// Profiles are listed in `Local State` under `profile.info_cache`; when
+    /// it can't be read, the `Default` and `Profile N` folders are used.
+    /// Profiles that never saved a bookmark have no file and are left out.
+    /// `Default` comes first.
+    pub fn resolve_profiles(user_data: &Path) -> Vec<LocatedProfile> {
+        let mut profiles = Self::profiles_in_local_state(user_data)
+            .unwrap_or_else(|| Self::profile_folders(user_data));
+        profiles.sort_by(|a, b| (a != "Default", a).cmp(&(b != "Default", b)));
+
+        profiles
+            .into_iter()
+            .filter_map(|profile| {
+                let path = user_data.join(&profile).join("Bookmarks");
+                path.exists().then_some((Some(profile), path))
+            })
+            .collect()
+    }
+
+    /// Profile folders listed in `Local State`
+    fn profiles_in_local_state(user_data: &Path) -> Option<Vec<String>> {
+        let content = std::fs::read_to_string(user_data.join("Local State")).ok()?;
+        let state: serde_json::Value = serde_json::from_str(&content).ok()?;
+        let profiles = state.get("profile")?.get("info_cache")?.as_object()?;
+        Some(profiles.keys().cloned().collect())
+    }
+
+    /// Folders named like Chromium profiles
+    fn profile_folders(user_data: &Path) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(user_data) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name == "Default" || name.starts_with("Profile "))
+            .collect()
+    }
+
+    /// Locates the Opera bookmarks file
+    pub fn locate_opera_bookmarks() -> Option<PathBuf> {
+        Self::locate_opera_profile(OPERA_PROFILE_DIR)
+    }
+
+    /// Locates the Opera GX bookmarks file
+    pub fn locate_opera_gx_bookmarks() -> Option<PathBuf> {
+        Self::locate_opera_profile(OPERA_GX_PROFILE_DIR)
+    }
+
+    /// Locates the bookmarks file of an Opera profile under `%APPDATA%`
+    fn locate_opera_profile(profile_dir: &str) -> Option<PathBuf> {
+        let app_data = std::env::var_os("APPDATA")?;
+        Self::resolve_opera_bookmarks(Path::new(&app_data), profile_dir)
+    }
+
+    /// Finds the Bookmarks file of an Opera profile inside an app data directory
+    ///
+    /// Opera keeps Chrome's JSON format but stores the file directly in the
+    /// profile folder; newer builds moved it into a `Default` subfolder.
+    pub fn resolve_opera_bookmarks(app_data: &Path, profile_dir: &str) -> Option<PathBuf> {
+        let profile = app_data.join("Opera Software").join(profile_dir);
+
+        [profile.join("Bookmarks"), profile.join("Default").join("Bookmarks")]
+            .into_iter()
+            .find(|path| path.exists())
     }
 }
 
@@ -218,7 +578,13 @@ pub struct FirefoxBookmarkParser;
 impl FirefoxBookmarkParser {
     /// Parses Firefox bookmarks from the places.sqlite database
     pub fn parse(path: &PathBuf) -> Result<Vec<Bookmark>> {
-        debug!("Parsing Firefox bookmarks from: {:?}", path);
+        Self::parse_as(path, BrowserType::Firefox)
+    }
+
+    /// Parses a places.sqlite database, attributing the bookmarks to `origin`
+    pub fn parse_as(path: &PathBuf, origin: impl Into<BookmarkOrigin>) -> Result<Vec<Bookmark>> {
+        let origin = origin.into();
+        debug!("Parsing {} bookmarks from: {:?}", origin.display_name(), path);
 
         if !path.exists() {
             warn!("Firefox places database not found: {:?}", path);
@@ -253,55 +619,275 @@ impl FirefoxBookmarkParser {
                 }
 
                 let title = title.unwrap_or_else(|| url.clone());
-                bookmarks.push(Bookmark::new(title, url, BrowserType::Firefox));
+                bookmarks.push(Bookmark::new(title, url, origin.clone()));
             }
         }
 
-        info!("Parsed {} bookmarks from Firefox", bookmarks.len());
+        info!("Parsed {} bookmarks from {}", bookmarks.len(), origin.display_name());
         Ok(bookmarks)
     }
 
-    /// Locates the Firefox places.sqlite file
+    /// Locates the places.sqlite file of the default Firefox profile
     pub fn locate_firefox_places() -> Option<PathBuf> {
-        #[cfg(windows)]
-        {
-            if let Ok(app_data) = std::env::var("APPDATA") {
-                let firefox_dir = PathBuf::from(app_data)
-                    .join("Mozilla")
-                    .join("Firefox")
-                    .join("Profiles");
-
-                if firefox_dir.exists() {
-                    // Find the default profile directory
-                    if let Ok(entries) = std::fs::read_dir(&firefox_dir) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            if path.is_dir() {
-                                let places_path = path.join("places.sqlite");
-                                if places_path.exists() {
-                                    return Some(places_path);
-                                }
-                            }
-                        }
-                    }
+        Self::locate_firefox_profiles().into_iter().next().map(|(_, path)| path)
+    }
+
+    /// Locates the places.sqlite file of every Firefox profile, the default one first
+    pub fn locate_firefox_profiles() -> Vec<LocatedProfile> {
+        let Some(app_data) = std::env::var_os("APPDATA") else {
+            return Vec::new();
+        };
+        Self::resolve_profiles(&PathBuf::from(app_data).join("Mozilla").join("Firefox"))
+    }
+
+    /// Finds the places.sqlite file of each profile listed in the
+    /// `profiles.ini` of `firefox_dir`, named the way `-P` takes them
+    ///
+    /// The default profile comes first, the others in the file's order.
+    pub fn resolve_profiles(firefox_dir: &Path) -> Vec<LocatedProfile> {
+        let Ok(ini) = std::fs::read_to_string(firefox_dir.join("profiles.ini")) else {
+            return Vec::new();
+        };
+        let mut profiles = browser_launch::parse_profiles_ini(&ini);
+        profiles.sort_by_key(|profile| !profile.is_default);
+
+        profiles
+            .into_iter()
+            .filter_map(|profile| {
+                let places = profile.dir(firefox_dir).join("places.sqlite");
+                places.exists().then_some((Some(profile.name), places))
+            })
+            .collect()
+    }
+}
+
+/// A located bookmark file and the browser profile it belongs to
+pub type LocatedProfile = (Option<String>, PathBuf);
+
+/// Finds a browser's bookmark files, one per profile, if the browser is installed
+type BookmarkLocator = fn() -> Vec<LocatedProfile>;
+
+/// A bookmark file of a detected browser or a user-registered source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookmarkSource {
+    /// Browser or custom label owning the file
+    pub origin: BookmarkOrigin,
+    /// Format of the file
+    pub format: BookmarkFileFormat,
+    /// Path to the Bookmarks JSON or places.sqlite file
+    pub path: PathBuf,
+    /// Browser profile the file belongs to, as the browser's command line takes it
+    pub profile: Option<String>,
+}
+
+impl BookmarkSource {
+    /// Creates a source for a browser's own bookmark file
+    pub fn browser(browser: BrowserType, path: PathBuf) -> Self {
+        Self::browser_profile(browser, None, path)
+    }
+
+    /// Creates a source for the bookmark file of one of a browser's profiles
+    pub fn browser_profile(browser: BrowserType, profile: Option<String>, path: PathBuf) -> Self {
+        Self {
+            origin: browser.into(),
+            format: browser.format(),
+            path,
+            profile,
+        }
+    }
+
+    /// Returns the key the source's bookmarks and statistics are kept under
+    pub fn key(&self) -> SourceKey {
+        SourceKey {
+            origin: self.origin.clone(),
+            profile: self.profile.clone(),
+        }
+    }
+
+    /// Creates a source for a bookmark file registered in settings
+    ///
+    /// An empty label falls back to the file path.
+    pub fn custom(file: &CustomBookmarkFile) -> Self {
+        let label = match file.label.trim() {
+            "" => file.path.clone(),
+            label => label.to_string(),
+        };
+
+        Self {
+            origin: BookmarkOrigin::Custom(label),
+            format: file.format,
+            path: PathBuf::from(&file.path),
+            profile: None,
+        }
+    }
+
+    /// Locates the bookmark files of all installed browsers, one source per profile
+    ///
+    /// Opera keeps a single profile per install, so its sources have none.
+    pub fn locate_all() -> Vec<Self> {
+        let locators: [(BrowserType, BookmarkLocator); 5] = [
+            (BrowserType::Chrome, ChromeBookmarkParser::locate_chrome_profiles),
+            (BrowserType::Edge, ChromeBookmarkParser::locate_edge_profiles),
+            (BrowserType::Opera, || {
+                ChromeBookmarkParser::locate_opera_bookmarks().map(|path| (None, path)).into_iter().collect()
+            }),
+            (BrowserType::OperaGx, || {
+                ChromeBookmarkParser::locate_opera_gx_bookmarks().map(|path| (None, path)).into_iter().collect()
+            }),
+            (BrowserType::Firefox, FirefoxBookmarkParser::locate_firefox_profiles),
+        ];
+
+        locators
+            .into_iter()
+            .flat_map(|(browser, locate)| {
+                locate()
+                    .into_iter()
+                    .map(move |(profile, path)| Self::browser_profile(browser, profile, path))
+            })
+            .collect()
+    }
+
+    /// Parses the bookmarks stored in this source
+    pub fn parse(&self) -> Result<(Vec<Bookmark>, BookmarkParseStats)> {
+        // Located files exist, but a registered path may be wrong; report it
+        // as an error instead of treating it as an empty file
+        if matches!(self.origin, BookmarkOrigin::Custom(_)) && !self.path.exists() {
+            return Err(LauncherError::NotFound(format!(
+                "Bookmark file {}",
+                self.path.display()
+            )));
+        }
+
+        let (mut bookmarks, stats) = match self.format {
+            BookmarkFileFormat::Chromium => {
+                ChromeBookmarkParser::parse_with_stats(&self.path, self.origin.clone())?
+            }
+            BookmarkFileFormat::Firefox => {
+                let bookmarks = FirefoxBookmarkParser::parse_as(&self.path, self.origin.clone())?;
+                let stats = BookmarkParseStats {
+                    parsed: bookmarks.len(),
+                    ..BookmarkParseStats::default()
+                };
+                (bookmarks, stats)
+            }
+        };
+
+        self.attach_favicons(&mut bookmarks);
+        for bookmark in bookmarks.iter_mut() {
+            bookmark.profile = self.profile.clone();
+        }
+        Ok((bookmarks, stats))
+    }
+
+    /// Fills in icons from the favicon database in the same profile
+    ///
+    /// Without a readable database the bookmarks keep no icon.
+    fn attach_favicons(&self, bookmarks: &mut [Bookmark]) {
+        let Some(database) = FaviconDatabase::beside(self.format, &self.path) else {
+            return;
+        };
+
+        let urls: Vec<&str> = bookmarks.iter().map(|bookmark| bookmark.url.as_str()).collect();
+        match database.read(&urls) {
+            Ok(mut icons) => {
+                for bookmark in bookmarks.iter_mut() {
+                    bookmark.favicon = icons.remove(&bookmark.url);
                 }
             }
+            Err(e) => warn!("Failed to read {} favicons: {}", self.key().display_name(), e),
+        }
+    }
+
+    /// Checks whether a changed path belongs to this source
+    ///
+    /// Firefox writes bookmark changes to the write-ahead log before
+    /// checkpointing them, so `places.sqlite-wal` counts as well.
+    pub fn matches(&self, changed: &Path) -> bool {
+        if changed == self.path {
+            return true;
         }
 
-        None
+        if self.format != BookmarkFileFormat::Firefox || changed.parent() != self.path.parent() {
+            return false;
+        }
+
+        match (changed.file_name(), self.path.file_name()) {
+            (Some(changed_name), Some(name)) => {
+                changed_name.to_string_lossy() == format!("{}-wal", name.to_string_lossy())
+            }
+            _ => false,
+        }
     }
 }
 
+/// Coalesces bursts of file change events per source
+#[derive(Debug)]
+struct ChangeDebouncer {
+    /// Quiet period required after the last event
+    delay: Duration,
+    /// Time of the most recent event per source
+    pending: HashMap<SourceKey, Instant>,
+}
+
+impl ChangeDebouncer {
+    fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Records a change event; each event restarts the quiet period
+    fn record(&mut self, source: SourceKey, at: Instant) {
+        self.pending.insert(source, at);
+    }
+
+    /// Returns the sources whose files have been quiet for the full delay
+    fn take_ready(&mut self, now: Instant) -> Vec<SourceKey> {
+        let ready: Vec<SourceKey> = self
+            .pending
+            .iter()
+            .filter(|(_, last)| now.saturating_duration_since(**last) >= self.delay)
+            .map(|(source, _)| source.clone())
+            .collect();
+
+        for source in &ready {
+            self.pending.remove(source);
+        }
+
+        ready
+    }
+}
+
+/// Latest parse statistics per source
+type ParseStatsMap = HashMap<SourceKey, BookmarkParseStats>;
+
 /// Bookmark search provider
 pub struct BookmarkProvider {
     /// Cached bookmarks
     bookmarks: Arc<RwLock<Vec<Bookmark>>>,
-    /// Favicon cache (URL -> base64 encoded image)
-    favicon_cache: Arc<RwLock<HashMap<String, String>>>,
+    /// Parse statistics from the most recent load of each source
+    parse_stats: Arc<RwLock<ParseStatsMap>>,
+    /// Bookmark files registered in settings, loaded alongside detected browsers
+    custom_sources: Vec<BookmarkSource>,
+    /// Downloaded favicons (URL -> base64 encoded image)
+    favicon_cache: Arc<RwLock<ByteBudgetCache<String>>>,
+    /// Downloads icons for bookmarks the browsers have none for
+    favicon_fetcher: Arc<dyn FaviconFetcher>,
+    /// Whether missing icons are downloaded, set from `BookmarkConfig`
+    download_missing_favicons: AtomicBool,
     /// Whether the provider is enabled
     enabled: bool,
-    /// Last cache refresh time
-    last_refresh: Arc<RwLock<std::time::Instant>>,
+    /// Background task watching the bookmark files
+    refresh_task: Option<JoinHandle<()>>,
+    /// Seconds between full reloads, read by the refresh task
+    refresh_interval: Arc<AtomicU64>,
+    /// Whether bookmarks open in the browser they came from, set from settings
+    open_in_source_browser: OpenInSourceBrowser,
+    /// Scanned applications, searched for the browsers' executables
+    app_cache: AppCache,
+    /// Folders browsers are installed under, used when the app scan hasn't found one
+    browser_install_roots: Vec<PathBuf>,
 }
 
 impl BookmarkProvider {
@@ -311,109 +897,187 @@ impl BookmarkProvider {
 
         Ok(Self {
             bookmarks: Arc::new(RwLock::new(Vec::new())),
-            favicon_cache: Arc::new(RwLock::new(HashMap::new())),
+            parse_stats: Arc::new(RwLock::new(HashMap::new())),
+            custom_sources: Vec::new(),
+            favicon_cache: Arc::new(RwLock::new(Self::new_favicon_cache())),
+            favicon_fetcher: Arc::new(HttpFaviconFetcher),
+            download_missing_favicons: AtomicBool::new(false),
             enabled: true,
-            last_refresh: Arc::new(RwLock::new(std::time::Instant::now())),
+            refresh_task: None,
+            refresh_interval: Arc::new(AtomicU64::new(FALLBACK_REFRESH_INTERVAL)),
+            open_in_source_browser: OpenInSourceBrowser::default(),
+            app_cache: AppCache::default(),
+            browser_install_roots: browser_launch::locate_install_roots(),
         })
     }
 
-    /// Loads bookmarks from all supported browsers
-    async fn load_bookmarks(&self) -> Result<Vec<Bookmark>> {
-        let mut all_bookmarks = Vec::new();
+    /// Loads the given user-registered bookmark files alongside detected browsers
+    pub fn with_custom_files(mut self, files: &[CustomBookmarkFile]) -> Self {
+        self.custom_sources = files.iter().map(BookmarkSource::custom).collect();
+        self
+    }
 
-        // Load Chrome bookmarks
-        if let Some(chrome_path) = ChromeBookmarkParser::locate_chrome_bookmarks() {
-            match ChromeBookmarkParser::parse(&chrome_path, BrowserType::Chrome) {
-                Ok(bookmarks) => {
-                    debug!("Loaded {} Chrome bookmarks", bookmarks.len());
-                    all_bookmarks.extend(bookmarks);
-                }
-                Err(e) => {
-                    warn!("Failed to parse Chrome bookmarks: {}", e);
-                }
-            }
-        }
+    /// Opens bookmarks in the browser and profile they came from while `flag` is set,
+    /// finding the browsers among the applications in `app_cache`
+    pub fn with_source_browser(mut self, flag: OpenInSourceBrowser, app_cache: AppCache) -> Self {
+        self.open_in_source_browser = flag;
+        self.app_cache = app_cache;
+        self
+    }
 
-        // Load Edge bookmarks
-        if let Some(edge_path) = ChromeBookmarkParser::locate_edge_bookmarks() {
-            match ChromeBookmarkParser::parse(&edge_path, BrowserType::Edge) {
-                Ok(bookmarks) => {
-                    debug!("Loaded {} Edge bookmarks", bookmarks.len());
-                    all_bookmarks.extend(bookmarks);
-                }
-                Err(e) => {
-                    warn!("Failed to parse Edge bookmarks: {}", e);
-                }
-            }
-        }
+    /// Downloads missing favicons through `fetcher` instead of over HTTPS
+    pub fn with_favicon_fetcher(mut self, fetcher: Arc<dyn FaviconFetcher>) -> Self {
+        self.favicon_fetcher = fetcher;
+        self
+    }
 
-        // Load Firefox bookmarks
-        if let Some(firefox_path) = FirefoxBookmarkParser::locate_firefox_places() {
-            match FirefoxBookmarkParser::parse(&firefox_path) {
-                Ok(bookmarks) => {
-                    debug!("Loaded {} Firefox bookmarks", bookmarks.len());
-                    all_bookmarks.extend(bookmarks);
-                }
-                Err(e) => {
-                    warn!("Failed to parse Firefox bookmarks: {}", e);
-                }
-            }
-        }
+    /// Returns parse statistics for each source, for health reporting
+    ///
+    /// Custom files that are missing or unreadable show up here as errors.
+    pub async fn parse_stats(&self) -> ParseStatsMap {
+        self.parse_stats.read().await.clone()
+    }
 
-        // Limit to MAX_BOOKMARKS
-        if all_bookmarks.len() > MAX_BOOKMARKS {
-            all_bookmarks.truncate(MAX_BOOKMARKS);
+    /// Health summarizing the latest parse of each source
+    ///
+    /// Any source with skipped or broken entries, or one that failed to
+    /// load, makes the provider degraded, listing every source's counts and
+    /// why the failed ones didn't load.
+    fn health_from_stats(stats: &ParseStatsMap) -> ProviderHealth {
+        if stats.values().all(|stats| stats.skipped == 0 && stats.errors == 0) {
+            return ProviderHealth::Ready;
         }
 
-        info!("Loaded total of {} bookmarks", all_bookmarks.len());
-        Ok(all_bookmarks)
+        let mut sources: Vec<String> = stats
+            .iter()
+            .map(|(source, stats)| {
+                let counts = format!(
+                    "{}: {} parsed, {} skipped, {} errors",
+                    source.display_name(),
+                    stats.parsed,
+                    stats.skipped,
+                    stats.errors
+                );
+                match &stats.failure {
+                    Some(failure) => format!("{} ({})", counts, failure),
+                    None => counts,
+                }
+            })
+            .collect();
+        sources.sort();
+        ProviderHealth::Degraded { reason: sources.join("; ") }
     }
 
-    /// Refreshes the bookmark cache
-    async fn refresh_cache(&self) -> Result<()> {
-        debug!("Refreshing bookmark cache");
+    fn new_favicon_cache() -> ByteBudgetCache<String> {
+        ByteBudgetCache::new(FAVICON_CACHE_CAPACITY, FAVICON_CACHE_BYTES)
+    }
 
-        let bookmarks = self.load_bookmarks().await?;
-        
-        let mut cache = self.bookmarks.write().await;
-        *cache = bookmarks;
+    /// Detected browser sources followed by the custom ones
+    fn all_sources(custom_sources: &[BookmarkSource]) -> Vec<BookmarkSource> {
+        let mut sources = BookmarkSource::locate_all();
+        sources.extend(custom_sources.iter().cloned());
+        sources
+    }
 
-        let mut last_refresh = self.last_refresh.write().await;
-        *last_refresh = std::time::Instant::now();
+    /// Parses a single source off the async runtime
+    async fn load_source(source: &BookmarkSource) -> Result<(Vec<Bookmark>, BookmarkParseStats)> {
+        let source = source.clone();
 
-        info!("Bookmark cache refreshed with {} items", cache.len());
-        Ok(())
+        tokio::task::spawn_blocking(move || source.parse())
+            .await
+            .map_err(|e| {
+                LauncherError::SearchError(format!("Failed to spawn bookmark parse task: {}", e))
+            })?
     }
 
-    /// Checks if cache needs refresh and refreshes if necessary
-    async fn check_and_refresh_cache(&self) {
-        let last_refresh = self.last_refresh.read().await;
-        let elapsed = last_refresh.elapsed().as_secs();
+    /// Replaces one source's bookmarks in the cache, keeping other sources' entries
+    fn merge_browser_bookmarks(
+        cache: &mut Vec<Bookmark>,
+        source: &SourceKey,
+        bookmarks: Vec<Bookmark>,
+    ) {
+        cache.retain(|bookmark| bookmark.source_key() != *source);
+
+        // Limit to MAX_BOOKMARKS
+        let room = MAX_BOOKMARKS.saturating_sub(cache.len());
+        cache.extend(bookmarks.into_iter().take(room));
+    }
 
-        if elapsed >= CACHE_REFRESH_INTERVAL {
-            drop(last_refresh);
-            if let Err(e) = self.refresh_cache().await {
-                error!("Failed to refresh bookmark cache: {}", e);
+    /// Re-parses one source and merges it into the cache
+    ///
+    /// A failed parse keeps the previously cached entries for that source
+    /// and counts as an error in its statistics.
+    async fn reload_source(
+        cache: &RwLock<Vec<Bookmark>>,
+        stats: &RwLock<ParseStatsMap>,
+        source: &BookmarkSource,
+    ) {
+        let key = source.key();
+        match Self::load_source(source).await {
+            Ok((bookmarks, source_stats)) => {
+                debug!("Loaded {} {} bookmarks", bookmarks.len(), key.display_name());
+                {
+                    let mut cache = cache.write().await;
+                    Self::merge_browser_bookmarks(&mut cache, &key, bookmarks);
+                }
+                stats.write().await.insert(key, source_stats);
             }
+            Err(e) => {
+                warn!("Failed to parse {} bookmarks: {}", key.display_name(), e);
+                let mut stats = stats.write().await;
+                let source_stats = stats.entry(key.clone()).or_default();
+                source_stats.errors += 1;
+                source_stats.failure = Some(format!(
+                    "{} couldn't be loaded: {}",
+                    source.path.display(),
+                    e
+                ));
+            }
+        }
+    }
+
+    /// Reloads every source and drops sources that are no longer present
+    async fn refresh_all(
+        cache: &RwLock<Vec<Bookmark>>,
+        stats: &RwLock<ParseStatsMap>,
+        sources: &[BookmarkSource],
+    ) {
+        debug!("Refreshing bookmark cache");
+
+        for source in sources {
+            Self::reload_source(cache, stats, source).await;
         }
+
+        stats
+            .write()
+            .await
+            .retain(|key, _| sources.iter().any(|source| source.key() == *key));
+
+        let mut cache = cache.write().await;
+        cache.retain(|bookmark| {
+            let key = bookmark.source_key();
+            sources.iter().any(|source| source.key() == key)
+        });
+
+        info!("Bookmark cache refreshed with {} items", cache.len());
     }
 
     /// Searches bookmarks using fuzzy matching
     async fn search_bookmarks(&self, query: &str) -> Vec<SearchResult> {
         let bookmarks = self.bookmarks.read().await;
-        let query_lower = query.to_lowercase();
+        let query_lower = normalize_for_search(query);
 
         let mut results: Vec<(Bookmark, f64)> = bookmarks
             .iter()
             .filter_map(|bookmark| {
-                let title_lower = bookmark.title.to_lowercase();
-                let url_lower = bookmark.url.to_lowercase();
+                let title_lower = &bookmark.normalized_title;
+                let url_lower = &bookmark.normalized_url;
 
                 // Calculate score based on matches
                 let mut score = 0.0;
 
                 // Exact title match
-                if title_lower == query_lower {
+                if *title_lower == query_lower {
                     score = 100.0;
                 }
                 // Title starts with query
@@ -461,30 +1125,23 @@ impl BookmarkProvider {
         if let Some(folder) = &bookmark.folder {
             metadata.insert("folder".to_string(), serde_json::json!(folder));
         }
+        if let Some(profile) = &bookmark.profile {
+            metadata.insert("profile".to_string(), serde_json::json!(profile));
+        }
 
-        // Try to get favicon from cache
-        let favicon = {
-            let cache = self.favicon_cache.read().await;
-            cache.get(&bookmark.url).cloned()
+        // The browser's own icon comes first; others not downloaded yet are
+        // fetched by `enrich` once the result is shown, if downloads are on
+        let favicon = match &bookmark.favicon {
+            Some(favicon) => Some(favicon.clone()),
+            None => self.favicon_cache.write().await.get(&bookmark.url).cloned(),
         };
 
-        // If not in cache, download asynchronously (don't block)
-        if favicon.is_none() {
-            let url = bookmark.url.clone();
-            let favicon_cache = Arc::clone(&self.favicon_cache);
-            
-            tokio::spawn(async move {
-                if let Ok(favicon_data) = Self::download_favicon(&url).await {
-                    let mut cache = favicon_cache.write().await;
-                    cache.insert(url, favicon_data);
-                }
-            });
-        }
-
         SearchResult {
             id: bookmark.id(),
             title: bookmark.title.clone(),
             subtitle: bookmark.subtitle(),
+            enrichment_pending: favicon.is_none() && self.download_missing_favicons.load(Ordering::Relaxed),
+            integrity: None,
             icon: favicon.or_else(|| Some("bookmark".to_string())),
             result_type: ResultType::Bookmark,
             score,
@@ -492,58 +1149,136 @@ impl BookmarkProvider {
             action: ResultAction::OpenUrl {
                 url: bookmark.url.clone(),
             },
+            normalized_title: Some(bookmark.normalized_title.clone()),
         }
     }
 
-    /// Downloads a favicon for a URL
-    async fn download_favicon(url: &str) -> Result<String> {
-        // Extract domain from URL
-        let domain = url
-            .split("://")
-            .nth(1)
-            .and_then(|s| s.split('/').next())
-            .ok_or_else(|| LauncherError::SearchError("Invalid URL".to_string()))?;
+    /// Returns the cached favicon for a URL, downloading it on a miss when enabled
+    async fn favicon(&self, url: &str) -> Result<String> {
+        if let Some(favicon) = self.favicon_cache.write().await.get(url).cloned() {
+            return Ok(favicon);
+        }
 
-        // Try to download favicon
-        let favicon_url = format!("https://{}/favicon.ico", domain);
-        
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(5))
-            .build()
-            .map_err(|e| LauncherError::SearchError(format!("Failed to create HTTP client: {}", e)))?;
-
-        let response = client
-            .get(&favicon_url)
-            .send()
-            .await
-            .map_err(|e| LauncherError::SearchError(format!("Failed to download favicon: {}", e)))?;
+        if !self.download_missing_favicons.load(Ordering::Relaxed) {
+            return Err(LauncherError::NotFound(format!("No stored favicon for {}", url)));
+        }
+
+        let favicon = self.favicon_fetcher.fetch(url).await?;
+        self.favicon_cache.write().await.put(url.to_string(), favicon.clone());
+        Ok(favicon)
+    }
+
+    /// Starts the background task that keeps the cache in sync with the bookmark files
+    fn start_cache_refresh_task(&mut self, sources: Vec<BookmarkSource>) {
+        if let Some(task) = self.refresh_task.take() {
+            task.abort();
+        }
+
+        let bookmarks = Arc::clone(&self.bookmarks);
+        let stats = Arc::clone(&self.parse_stats);
+        let custom_sources = self.custom_sources.clone();
+        let refresh_interval = Arc::clone(&self.refresh_interval);
+        self.refresh_task = Some(tokio::spawn(Self::run_refresh_loop(
+            bookmarks,
+            stats,
+            sources,
+            custom_sources,
+            refresh_interval,
+        )));
+    }
 
-        if !response.status().is_success() {
-            return Err(LauncherError::SearchError("Favicon not found".to_string()));
+    /// Watches the directories containing the bookmark files
+    ///
+    /// Directories are watched rather than the files themselves because
+    /// browsers replace their bookmark files when saving.
+    fn watch_sources(
+        sources: &[BookmarkSource],
+        tx: mpsc::UnboundedSender<notify::Event>,
+    ) -> Option<RecommendedWatcher> {
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            match res {
+                Ok(event) if !event.kind.is_access() => {
+                    let _ = tx.send(event);
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Bookmark file watcher error: {}", e),
+            }
+        });
+
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                warn!("Failed to create bookmark file watcher: {}", e);
+                return None;
+            }
+        };
+
+        for source in sources {
+            if let Some(dir) = source.path.parent() {
+                if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                    warn!("Failed to watch {:?}: {}", dir, e);
+                }
+            }
         }
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| LauncherError::SearchError(format!("Failed to read favicon: {}", e)))?;
-
-        // Encode as base64
-        let base64_data = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
-        Ok(format!("data:image/x-icon;base64,{}", base64_data))
+        Some(watcher)
     }
 
-    /// Starts the background cache refresh task
-    fn start_cache_refresh_task(provider: Arc<RwLock<Self>>) {
-        tokio::spawn(async move {
-            loop {
-                tokio::time::sleep(tokio::time::Duration::from_secs(CACHE_REFRESH_INTERVAL)).await;
-                
-                let provider_lock = provider.read().await;
-                if let Err(e) = provider_lock.refresh_cache().await {
-                    error!("Background cache refresh failed: {}", e);
+    /// Reloads sources whose bookmark files changed, with a slow full refresh as a fallback
+    async fn run_refresh_loop(
+        bookmarks: Arc<RwLock<Vec<Bookmark>>>,
+        stats: Arc<RwLock<ParseStatsMap>>,
+        mut sources: Vec<BookmarkSource>,
+        custom_sources: Vec<BookmarkSource>,
+        refresh_interval: Arc<AtomicU64>,
+    ) {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut _watcher = Self::watch_sources(&sources, tx.clone());
+        let mut debouncer = ChangeDebouncer::new(WATCH_DEBOUNCE);
+
+        let locator_period = Duration::from_secs(LOCATOR_INTERVAL);
+        let mut debounce_tick = tokio::time::interval(DEBOUNCE_TICK);
+        // Checked on every tick so a new interval applies without a restart
+        let mut last_full_refresh = Instant::now();
+        let mut locator = tokio::time::interval_at(
+            tokio::time::Instant::now() + locator_period,
+            locator_period,
+        );
+
+        loop {
+            tokio::select! {
+                Some(event) = rx.recv() => {
+                    for source in sources.iter().filter(|s| event.paths.iter().any(|p| s.matches(p))) {
+                        debug!("{} bookmark file changed", source.key().display_name());
+                        debouncer.record(source.key(), Instant::now());
+                    }
+                }
+                _ = debounce_tick.tick() => {
+                    for key in debouncer.take_ready(Instant::now()) {
+                        if let Some(source) = sources.iter().find(|s| s.key() == key) {
+                            info!("Reloading {} bookmarks after file change", key.display_name());
+                            Self::reload_source(&bookmarks, &stats, source).await;
+                        }
+                    }
+
+                    let fallback_period = Duration::from_secs(refresh_interval.load(Ordering::Relaxed));
+                    if last_full_refresh.elapsed() >= fallback_period {
+                        Self::refresh_all(&bookmarks, &stats, &sources).await;
+                        last_full_refresh = Instant::now();
+                    }
+                }
+                _ = locator.tick() => {
+                    let located = Self::all_sources(&custom_sources);
+                    if located != sources {
+                        info!("Bookmark sources changed, updating file watchers");
+                        Self::refresh_all(&bookmarks, &stats, &located).await;
+                        last_full_refresh = Instant::now();
+                        _watcher = Self::watch_sources(&located, tx.clone());
+                        sources = located;
+                    }
                 }
             }
-        });
+        }
     }
 }
 
@@ -557,17 +1292,18 @@ impl SearchProvider for BookmarkProvider {
         50 // Medium priority
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let trimmed = query.trim();
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Bookmark])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
 
         // Don't search if query is too short
         if trimmed.len() < 2 {
             return Ok(Vec::new());
         }
 
-        // Check if cache needs refresh
-        self.check_and_refresh_cache().await;
-
         // Search bookmarks
         Ok(self.search_bookmarks(trimmed).await)
     }
@@ -582,7 +1318,7 @@ impl SearchProvider for BookmarkProvider {
         // Extract URL from action
         if let ResultAction::OpenUrl { url } = &result.action {
             info!("Opening bookmark: {}", url);
-            Self::open_url(url).await?;
+            self.open_bookmark(result, url).await?;
             info!("Successfully opened bookmark");
             Ok(())
         } else {
@@ -592,17 +1328,90 @@ impl SearchProvider for BookmarkProvider {
         }
     }
 
+    async fn enrich(&self, result: &mut SearchResult) -> Result<()> {
+        if let ResultAction::OpenUrl { url } = &result.action {
+            result.icon = Some(self.favicon(url).await?);
+        }
+        Ok(())
+    }
+
+    async fn suggestion_terms(&self, limit: usize) -> Vec<String> {
+        let bookmarks = self.bookmarks.read().await;
+        bookmarks.iter().take(limit).map(|bookmark| bookmark.title.clone()).collect()
+    }
+
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        let bookmarks = self.bookmarks.read().await;
+        let bytes = bookmarks
+            .iter()
+            .map(|bookmark| {
+                std::mem::size_of::<Bookmark>()
+                    + bookmark.title.len()
+                    + bookmark.url.len()
+                    + bookmark.normalized_title.len()
+                    + bookmark.normalized_url.len()
+                    + bookmark.folder.as_ref().map_or(0, String::len)
+                    + bookmark.favicon.as_ref().map_or(0, String::len)
+                    + bookmark.profile.as_ref().map_or(0, String::len)
+            })
+            .sum();
+
+        vec![
+            CacheStats {
+                name: "Bookmarks".to_string(),
+                entries: bookmarks.len(),
+                bytes,
+                byte_budget: None,
+            },
+            self.favicon_cache.read().await.stats("Favicons"),
+        ]
+    }
+
+    async fn trim_caches(&self) -> usize {
+        self.favicon_cache.write().await.trim()
+    }
+
+    async fn refresh_after_resume(&self) {
+        // Browsers may have synced bookmarks while the system slept
+        let bookmarks = Arc::clone(&self.bookmarks);
+        let stats = Arc::clone(&self.parse_stats);
+        let custom_sources = self.custom_sources.clone();
+        tokio::spawn(async move {
+            info!("Reloading bookmarks after resume");
+            Self::refresh_all(&bookmarks, &stats, &Self::all_sources(&custom_sources)).await;
+        });
+    }
+
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        let config = BookmarkConfig::parse(config)?;
+        self.refresh_interval.store(config.refresh_interval_secs, Ordering::Relaxed);
+        self.download_missing_favicons.store(config.download_missing_favicons, Ordering::Relaxed);
+        Ok(())
+    }
+
     fn is_enabled(&self) -> bool {
         self.enabled
     }
 
+    fn health(&self) -> ProviderHealth {
+        if !self.enabled {
+            return ProviderHealth::Disabled;
+        }
+
+        // A reload holding the lock means the provider is busy, not unhealthy
+        match self.parse_stats.try_read() {
+            Ok(stats) => Self::health_from_stats(&stats),
+            Err(_) => ProviderHealth::Ready,
+        }
+    }
+
     async fn initialize(&mut self) -> Result<()> {
         info!("Initializing BookmarkProvider");
 
-        // Load bookmarks initially
-        if let Err(e) = self.refresh_cache().await {
-            warn!("Failed to load initial bookmarks: {}", e);
-        }
+        // Load bookmarks initially, then keep them in sync with the browser files
+        let sources = Self::all_sources(&self.custom_sources);
+        Self::refresh_all(&self.bookmarks, &self.parse_stats, &sources).await;
+        self.start_cache_refresh_task(sources);
 
         info!("BookmarkProvider initialized successfully");
         Ok(())
@@ -610,14 +1419,50 @@ impl SearchProvider for BookmarkProvider {
 
     async fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down BookmarkProvider");
+
+        if let Some(task) = self.refresh_task.take() {
+            task.abort();
+            let _ = task.await;
+        }
+
         Ok(())
     }
 }
 
 impl BookmarkProvider {
+    /// Opens a bookmark result, in its source browser when settings ask for it
+    ///
+    /// The browser and profile come from the result's metadata. Should the
+    /// browser fail to start, the URL still opens in the default browser.
+    async fn open_bookmark(&self, result: &SearchResult, url: &str) -> Result<()> {
+        let origin = result
+            .metadata
+            .get("browser")
+            .and_then(|browser| serde_json::from_value::<BookmarkOrigin>(browser.clone()).ok());
+        let launch = match origin {
+            Some(origin) if self.open_in_source_browser.get() => {
+                let profile = result.metadata.get("profile").and_then(|profile| profile.as_str());
+                let apps = self.app_cache.read().await;
+                BookmarkLaunch::plan(&origin, profile, url, &apps, &self.browser_install_roots)
+            }
+            _ => BookmarkLaunch::DefaultBrowser,
+        };
+
+        if let BookmarkLaunch::SourceBrowser { program, args } = launch {
+            let mut command = std::process::Command::new(&program);
+            command.args(&args);
+            match process::start("open_bookmark", command, Wait::Detached).await {
+                Ok(()) => return Ok(()),
+                Err(e) => warn!("Failed to start {}, opening bookmark in the default browser: {}", program.display(), e),
+            }
+        }
+
+        Self::open_url(url).await
+    }
+
     /// Opens a URL in the default browser using Windows API
     #[cfg(windows)]
-    async fn open_url(url: &str) -> Result<()> {
+    pub(crate) async fn open_url(url: &str) -> Result<()> {
         use windows::Win32::Foundation::*;
         use windows::Win32::UI::Shell::*;
         use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
@@ -667,7 +1512,7 @@ impl BookmarkProvider {
     }
 
     #[cfg(not(windows))]
-    async fn open_url(_url: &str) -> Result<()> {
+    pub(crate) async fn open_url(_url: &str) -> Result<()> {
         Err(LauncherError::ExecutionError(
             "URL opening not supported on this platform".to_string(),
         ))
@@ -678,9 +1523,17 @@ impl Default for BookmarkProvider {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             bookmarks: Arc::new(RwLock::new(Vec::new())),
-            favicon_cache: Arc::new(RwLock::new(HashMap::new())),
+            parse_stats: Arc::new(RwLock::new(HashMap::new())),
+            custom_sources: Vec::new(),
+            favicon_cache: Arc::new(RwLock::new(Self::new_favicon_cache())),
+            favicon_fetcher: Arc::new(HttpFaviconFetcher),
+            download_missing_favicons: AtomicBool::new(false),
             enabled: false,
-            last_refresh: Arc::new(RwLock::new(std::time::Instant::now())),
+            refresh_task: None,
+            refresh_interval: Arc::new(AtomicU64::new(FALLBACK_REFRESH_INTERVAL)),
+            open_in_source_browser: OpenInSourceBrowser::default(),
+            app_cache: AppCache::default(),
+            browser_install_roots: browser_launch::locate_install_roots(),
         })
     }
 }
@@ -746,6 +1599,62 @@ mod tests {
         assert_eq!(BrowserType::Chrome.display_name(), "Chrome");
         assert_eq!(BrowserType::Edge.display_name(), "Edge");
         assert_eq!(BrowserType::Firefox.display_name(), "Firefox");
+        assert_eq!(BrowserType::Opera.display_name(), "Opera");
+        assert_eq!(BrowserType::OperaGx.display_name(), "Opera GX");
+    }
+
+    #[test]
+    fn test_bookmark_origin_serde_compatible_with_browser_type() {
+        // Bookmarks stored before custom sources existed keep deserializing
+        let stored = r#"{"title": "Docs", "url": "https://docs.rs", "folder": null, "browser": "Edge", "favicon": null}"#;
+        let bookmark: Bookmark = serde_json::from_str(stored).unwrap();
+        assert_eq!(bookmark.browser, BrowserType::Edge);
+
+        let json = serde_json::to_value(&bookmark).unwrap();
+        assert_eq!(json["browser"], "Edge");
+
+        let custom = Bookmark::new(
+            "Wiki".to_string(),
+            "https://wiki.example.com".to_string(),
+            BookmarkOrigin::Custom("Work Vivaldi".to_string()),
+        );
+        let json = serde_json::to_string(&custom).unwrap();
+        let restored: Bookmark = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.browser, BookmarkOrigin::Custom("Work Vivaldi".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_opera_bookmarks() {
+        let app_data = std::env::temp_dir().join("BetterFinder").join("opera_app_data");
+        let _ = std::fs::remove_dir_all(&app_data);
+
+        let opera = app_data.join("Opera Software").join(OPERA_PROFILE_DIR);
+        let opera_gx = app_data.join("Opera Software").join(OPERA_GX_PROFILE_DIR).join("Default");
+        std::fs::create_dir_all(&opera).unwrap();
+        std::fs::create_dir_all(&opera_gx).unwrap();
+
+        assert_eq!(ChromeBookmarkParser::resolve_opera_bookmarks(&app_data, OPERA_PROFILE_DIR), None);
+
+        std::fs::write(opera.join("Bookmarks"), chrome_json(&["opera"])).unwrap();
+        std::fs::write(opera_gx.join("Bookmarks"), chrome_json(&["gx"])).unwrap();
+
+        assert_eq!(
+            ChromeBookmarkParser::resolve_opera_bookmarks(&app_data, OPERA_PROFILE_DIR),
+            Some(opera.join("Bookmarks"))
+        );
+        // Newer builds keep the file in the Default profile folder
+        assert_eq!(
+            ChromeBookmarkParser::resolve_opera_bookmarks(&app_data, OPERA_GX_PROFILE_DIR),
+            Some(opera_gx.join("Bookmarks"))
+        );
+
+        let source = BookmarkSource::browser(BrowserType::OperaGx, opera_gx.join("Bookmarks"));
+        let (bookmarks, _) = source.parse().unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].browser, BrowserType::OperaGx);
+        assert!(bookmarks[0].id().starts_with("bookmark:Opera GX:"));
+
+        std::fs::remove_dir_all(&app_data).ok();
     }
 
     #[test]
@@ -828,6 +1737,110 @@ mod tests {
         assert_eq!(result.unwrap().len(), 0);
     }
 
+    #[test]
+    fn test_chrome_bookmark_parser_recovers_truncated_file() {
+        let path = std::env::temp_dir().join("test_chrome_truncated_bookmarks.json");
+        let full = chrome_json(&["alpha", "beta", "gamma"]);
+        let cut = full.find("\"gamma\"").unwrap() + 3;
+        std::fs::write(&path, &full[..cut]).unwrap();
+
+        let (bookmarks, stats) =
+            ChromeBookmarkParser::parse_with_stats(&path, BrowserType::Chrome).unwrap();
+
+        let titles: Vec<&str> = bookmarks.iter().map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["alpha", "beta"]);
+        assert_eq!(bookmarks[0].folder, Some("Bar".to_string()));
+        assert_eq!(stats, BookmarkParseStats { parsed: 2, skipped: 0, errors: 1, ..Default::default() });
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_chrome_bookmark_parser_without_other_root() {
+        let path = std::env::temp_dir().join("test_chrome_missing_other_bookmarks.json");
+        let bookmarks_json = r#"{
+            "roots": {
+                "bookmark_bar": {
+                    "name": "Favorites bar",
+                    "type": "folder",
+                    "children": [
+                        {"name": "Bing", "type": "url", "url": "https://www.bing.com"}
+                    ]
+                }
+            }
+        }"#;
+        std::fs::write(&path, bookmarks_json).unwrap();
+
+        let (bookmarks, stats) =
+            ChromeBookmarkParser::parse_with_stats(&path, BrowserType::Edge).unwrap();
+
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "Bing");
+        assert_eq!(stats, BookmarkParseStats { parsed: 1, skipped: 0, errors: 0, ..Default::default() });
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_chrome_bookmark_parser_filters_internal_urls() {
+        let path = std::env::temp_dir().join("test_chrome_internal_url_bookmarks.json");
+        let bookmarks_json = r#"{
+            "roots": {
+                "bookmark_bar": {
+                    "name": "Bookmarks Bar",
+                    "type": "folder",
+                    "children": [
+                        {"name": "Bookmarklet", "type": "url", "url": "javascript:alert(1)"},
+                        {"name": "Flags", "type": "url", "url": "chrome://flags"},
+                        {"name": "Rust", "type": "url", "url": "https://www.rust-lang.org"}
+                    ]
+                },
+                "other": {"name": "Other", "type": "folder", "children": []}
+            }
+        }"#;
+        std::fs::write(&path, bookmarks_json).unwrap();
+
+        let (bookmarks, stats) =
+            ChromeBookmarkParser::parse_with_stats(&path, BrowserType::Chrome).unwrap();
+
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "Rust");
+        assert_eq!(stats, BookmarkParseStats { parsed: 1, skipped: 2, errors: 0, ..Default::default() });
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_chrome_bookmark_parser_skips_malformed_nodes() {
+        let content = r#"{
+            "roots": {
+                "bookmark_bar": {
+                    "name": "Bar",
+                    "type": "folder",
+                    "children": [
+                        {"name": "No type", "url": "https://a.com"},
+                        {"name": "No url", "type": "url"},
+                        42,
+                        {"name": "Broken folder", "type": "folder", "children": "oops"},
+                        {"name": "Kept", "type": "url", "url": "https://kept.com"}
+                    ]
+                }
+            }
+        }"#;
+
+        let (bookmarks, stats) = ChromeBookmarkParser::parse_str(content, &BrowserType::Chrome.into()).unwrap();
+
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "Kept");
+        assert_eq!(stats, BookmarkParseStats { parsed: 1, skipped: 0, errors: 4, ..Default::default() });
+    }
+
+    #[test]
+    fn test_chrome_bookmark_parser_rejects_missing_roots() {
+        assert!(ChromeBookmarkParser::parse_str(r#"{"version": 1}"#, &BrowserType::Chrome.into()).is_err());
+        assert!(ChromeBookmarkParser::parse_str("{ not json", &BrowserType::Chrome.into()).is_err());
+    }
+
     #[test]
     fn test_firefox_bookmark_parser_with_valid_database() {
         // Create a temporary Firefox places database
@@ -922,11 +1935,11 @@ mod tests {
         let provider = BookmarkProvider::new().unwrap();
         
         // Empty query should return no results
-        let results = provider.search("").await.unwrap();
+        let results = provider.search(&"".into()).await.unwrap();
         assert_eq!(results.len(), 0);
 
         // Single character query should return no results
-        let results = provider.search("a").await.unwrap();
+        let results = provider.search(&"a".into()).await.unwrap();
         assert_eq!(results.len(), 0);
     }
 
@@ -958,18 +1971,18 @@ mod tests {
         }
 
         // Search for "google"
-        let results = provider.search("google").await.unwrap();
+        let results = provider.search(&"google".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "Google Search");
         assert_eq!(results[0].result_type, ResultType::Bookmark);
 
         // Search for "git"
-        let results = provider.search("git").await.unwrap();
+        let results = provider.search(&"git".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "GitHub");
 
         // Search for "stack"
-        let results = provider.search("stack").await.unwrap();
+        let results = provider.search(&"stack".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "Stack Overflow");
     }
@@ -991,16 +2004,162 @@ mod tests {
         }
 
         // Search with different cases
-        let results = provider.search("github").await.unwrap();
+        let results = provider.search(&"github".into()).await.unwrap();
         assert_eq!(results.len(), 1);
 
-        let results = provider.search("GITHUB").await.unwrap();
+        let results = provider.search(&"GITHUB".into()).await.unwrap();
         assert_eq!(results.len(), 1);
 
-        let results = provider.search("GiTHuB").await.unwrap();
+        let results = provider.search(&"GiTHuB".into()).await.unwrap();
         assert_eq!(results.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_bookmark_provider_search_ignores_diacritics() {
+        let provider = BookmarkProvider::new().unwrap();
+
+        {
+            let mut cache = provider.bookmarks.write().await;
+            *cache = vec![Bookmark::new(
+                "São Paulo Weather".to_string(),
+                "https://example.com/sp".to_string(),
+                BrowserType::Chrome,
+            )];
+        }
+
+        let results = provider.search(&"sao paulo".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "São Paulo Weather");
+
+        let results = provider.search(&"SÃO".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
+    /// Hands out one icon for every page, counting the downloads
+    #[derive(Default)]
+    struct FakeFetcher {
+        calls: std::sync::Mutex<Vec<String>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl FaviconFetcher for FakeFetcher {
+        async fn fetch(&self, page_url: &str) -> Result<String> {
+            self.calls.lock().unwrap().push(page_url.to_string());
+            if self.fail {
+                return Err(LauncherError::SearchError("Favicon not found".to_string()));
+            }
+            Ok("data:image/x-icon;base64,AQ==".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_favicons_are_left_to_enrichment() {
+        let fetcher = Arc::new(FakeFetcher { fail: true, ..FakeFetcher::default() });
+        let provider = BookmarkProvider::new().unwrap().with_favicon_fetcher(fetcher.clone());
+        provider.configure(&serde_json::json!({ "download_missing_favicons": true })).await.unwrap();
+        {
+            let mut cache = provider.bookmarks.write().await;
+            *cache = vec![
+                Bookmark::new("Docs cached".to_string(), "https://docs.rs".to_string(), BrowserType::Chrome),
+                Bookmark::new("Docs missing".to_string(), "https://docs.invalid".to_string(), BrowserType::Chrome),
+            ];
+        }
+        provider
+            .favicon_cache
+            .write()
+            .await
+            .put("https://docs.rs".to_string(), "data:image/x-icon;base64,AA==".to_string());
+
+        let results = provider.search(&"docs".into()).await.unwrap();
+        let cached = results.iter().find(|r| r.title == "Docs cached").unwrap();
+        assert!(!cached.enrichment_pending);
+        assert_eq!(cached.icon.as_deref(), Some("data:image/x-icon;base64,AA=="));
+
+        let mut missing = results.iter().find(|r| r.title == "Docs missing").unwrap().clone();
+        assert!(missing.enrichment_pending);
+        assert_eq!(missing.icon.as_deref(), Some("bookmark"));
+        // Searching didn't start a download behind the engine's back
+        assert_eq!(provider.favicon_cache.read().await.len(), 1);
+
+        // A favicon that can't be downloaded leaves the placeholder icon
+        assert!(provider.enrich(&mut missing).await.is_err());
+        assert_eq!(missing.icon.as_deref(), Some("bookmark"));
+        assert_eq!(*fetcher.calls.lock().unwrap(), vec!["https://docs.invalid"]);
+    }
+
+    #[tokio::test]
+    async fn test_favicon_downloads_are_opt_in() {
+        let fetcher = Arc::new(FakeFetcher::default());
+        let provider = BookmarkProvider::new().unwrap().with_favicon_fetcher(fetcher.clone());
+        {
+            let mut cache = provider.bookmarks.write().await;
+            let mut stored = Bookmark::new("Docs stored".to_string(), "https://docs.rs".to_string(), BrowserType::Chrome);
+            stored.favicon = Some("data:image/png;base64,AA==".to_string());
+            *cache = vec![
+                stored,
+                Bookmark::new("Docs missing".to_string(), "https://docs.invalid".to_string(), BrowserType::Chrome),
+            ];
+        }
+
+        // Off by default: no result waits on a download and none is made
+        let results = provider.search(&"docs".into()).await.unwrap();
+        let stored = results.iter().find(|r| r.title == "Docs stored").unwrap();
+        assert_eq!(stored.icon.as_deref(), Some("data:image/png;base64,AA=="));
+        let mut missing = results.iter().find(|r| r.title == "Docs missing").unwrap().clone();
+        assert!(!missing.enrichment_pending);
+        assert!(provider.enrich(&mut missing).await.is_err());
+        assert!(fetcher.calls.lock().unwrap().is_empty());
+
+        // Once enabled only icons the browser lacks are downloaded, then cached
+        provider.configure(&serde_json::json!({ "download_missing_favicons": true })).await.unwrap();
+        let results = provider.search(&"docs".into()).await.unwrap();
+        assert!(!results.iter().find(|r| r.title == "Docs stored").unwrap().enrichment_pending);
+        let mut missing = results.iter().find(|r| r.title == "Docs missing").unwrap().clone();
+        assert!(missing.enrichment_pending);
+        provider.enrich(&mut missing).await.unwrap();
+        assert_eq!(missing.icon.as_deref(), Some("data:image/x-icon;base64,AQ=="));
+        provider.enrich(&mut missing).await.unwrap();
+        assert_eq!(*fetcher.calls.lock().unwrap(), vec!["https://docs.invalid"]);
+    }
+
+    #[test]
+    fn test_parse_attaches_icons_from_favicon_database() {
+        let mut dir = std::env::temp_dir();
+        dir.push("BetterFinder");
+        dir.push(format!("bookmark_favicons_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Bookmarks"), chrome_json(&["alpha", "beta"])).unwrap();
+
+        let mut png = Vec::new();
+        image::RgbaImage::from_pixel(16, 16, image::Rgba([0, 0, 255, 255]))
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let conn = rusqlite::Connection::open(dir.join("Favicons")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE icon_mapping (id INTEGER PRIMARY KEY, page_url LONGVARCHAR NOT NULL, icon_id INTEGER);
+             CREATE TABLE favicon_bitmaps (id INTEGER PRIMARY KEY, icon_id INTEGER NOT NULL, image_data BLOB, width INTEGER DEFAULT 0);
+             INSERT INTO icon_mapping (page_url, icon_id) VALUES ('https://alpha.example.com', 1);",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO favicon_bitmaps (icon_id, image_data, width) VALUES (1, ?1, 16)",
+            [&png],
+        )
+        .unwrap();
+        drop(conn);
+
+        let source = BookmarkSource::browser(BrowserType::Chrome, dir.join("Bookmarks"));
+        let (bookmarks, _) = source.parse().unwrap();
+
+        let alpha = bookmarks.iter().find(|b| b.title == "alpha").unwrap();
+        assert!(alpha.favicon.as_deref().unwrap().starts_with("data:image/png;base64,"));
+        let beta = bookmarks.iter().find(|b| b.title == "beta").unwrap();
+        assert_eq!(beta.favicon, None);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[tokio::test]
     async fn test_bookmark_provider_search_url_matching() {
         let provider = BookmarkProvider::new().unwrap();
@@ -1018,7 +2177,7 @@ mod tests {
         }
 
         // Search by URL
-        let results = provider.search("example.com").await.unwrap();
+        let results = provider.search(&"example.com".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "My Site");
     }
@@ -1049,7 +2208,7 @@ mod tests {
             *cache = bookmarks;
         }
 
-        let results = provider.search("test").await.unwrap();
+        let results = provider.search(&"test".into()).await.unwrap();
         assert_eq!(results.len(), 3);
         
         // Exact match should score highest
@@ -1080,9 +2239,380 @@ mod tests {
             action: ResultAction::OpenUrl {
                 url: "https://example.com".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         };
 
         let execute_result = provider.execute(&result).await;
         assert!(execute_result.is_err());
     }
+
+    fn chrome_json(names: &[&str]) -> String {
+        let children: Vec<String> = names
+            .iter()
+            .map(|name| {
+                format!(
+                    r#"{{"name": "{}", "type": "url", "url": "https://{}.example.com"}}"#,
+                    name, name
+                )
+            })
+            .collect();
+
+        format!(
+            r#"{{"roots": {{"bookmark_bar": {{"name": "Bar", "type": "folder", "children": [{}]}},
+                "other": {{"name": "Other", "type": "folder", "children": []}}}}}}"#,
+            children.join(",")
+        )
+    }
+
+    #[test]
+    fn test_merge_browser_bookmarks_keeps_other_browsers() {
+        let mut cache = vec![
+            Bookmark::new("Old Chrome".to_string(), "https://a.com".to_string(), BrowserType::Chrome),
+            Bookmark::new("Firefox".to_string(), "https://b.com".to_string(), BrowserType::Firefox),
+            Bookmark::new("Edge".to_string(), "https://c.com".to_string(), BrowserType::Edge),
+        ];
+
+        BookmarkProvider::merge_browser_bookmarks(
+            &mut cache,
+            &BrowserType::Chrome.into(),
+            vec![
+                Bookmark::new("New Chrome 1".to_string(), "https://d.com".to_string(), BrowserType::Chrome),
+                Bookmark::new("New Chrome 2".to_string(), "https://e.com".to_string(), BrowserType::Chrome),
+            ],
+        );
+
+        let titles: Vec<&str> = cache.iter().map(|b| b.title.as_str()).collect();
+        assert_eq!(titles, vec!["Firefox", "Edge", "New Chrome 1", "New Chrome 2"]);
+    }
+
+    #[test]
+    fn test_merge_browser_bookmarks_respects_limit() {
+        let mut cache: Vec<Bookmark> = (0..MAX_BOOKMARKS - 5)
+            .map(|i| Bookmark::new(format!("ff{}", i), format!("https://{}.com", i), BrowserType::Firefox))
+            .collect();
+
+        let chrome: Vec<Bookmark> = (0..10)
+            .map(|i| Bookmark::new(format!("c{}", i), format!("https://c{}.com", i), BrowserType::Chrome))
+            .collect();
+
+        BookmarkProvider::merge_browser_bookmarks(&mut cache, &BrowserType::Chrome.into(), chrome);
+
+        assert_eq!(cache.len(), MAX_BOOKMARKS);
+        assert_eq!(cache.iter().filter(|b| b.browser == BrowserType::Firefox).count(), MAX_BOOKMARKS - 5);
+    }
+
+    #[tokio::test]
+    async fn test_reload_source_merges_changed_browser() {
+        let path = std::env::temp_dir().join("test_reload_chrome_bookmarks.json");
+        std::fs::write(&path, chrome_json(&["alpha", "beta"])).unwrap();
+
+        let source = BookmarkSource::browser(BrowserType::Chrome, path.clone());
+        let cache = RwLock::new(vec![
+            Bookmark::new("Old".to_string(), "https://old.com".to_string(), BrowserType::Chrome),
+            Bookmark::new("Firefox".to_string(), "https://ff.com".to_string(), BrowserType::Firefox),
+        ]);
+        let stats = RwLock::new(HashMap::new());
+
+        BookmarkProvider::reload_source(&cache, &stats, &source).await;
+        {
+            let cache = cache.read().await;
+            assert_eq!(cache.len(), 3);
+            assert!(cache.iter().any(|b| b.title == "Firefox"));
+            assert!(!cache.iter().any(|b| b.title == "Old"));
+        }
+        assert_eq!(stats.read().await[&BrowserType::Chrome.into()].parsed, 2);
+
+        // A broken file keeps the previous entries
+        std::fs::write(&path, "{ not json").unwrap();
+        BookmarkProvider::reload_source(&cache, &stats, &source).await;
+        assert_eq!(cache.read().await.len(), 3);
+        assert_eq!(stats.read().await[&BrowserType::Chrome.into()].errors, 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_parse_problems_per_browser() {
+        let provider = BookmarkProvider::new().unwrap();
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+
+        provider.parse_stats.write().await.extend([
+            (BrowserType::Chrome.into(), BookmarkParseStats { parsed: 120, skipped: 0, errors: 0, ..Default::default() }),
+            (BrowserType::Edge.into(), BookmarkParseStats { parsed: 40, skipped: 0, errors: 0, ..Default::default() }),
+        ]);
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+
+        provider.parse_stats.write().await.insert(
+            BrowserType::Firefox.into(),
+            BookmarkParseStats { parsed: 8, skipped: 2, errors: 1, ..Default::default() },
+        );
+        assert_eq!(
+            provider.health(),
+            ProviderHealth::Degraded {
+                reason: "Chrome: 120 parsed, 0 skipped, 0 errors; \
+                         Edge: 40 parsed, 0 skipped, 0 errors; \
+                         Firefox: 8 parsed, 2 skipped, 1 errors"
+                    .to_string()
+            }
+        );
+
+        // Busy reloading isn't a problem
+        let guard = provider.parse_stats.write().await;
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+        drop(guard);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_all_drops_removed_browsers() {
+        let path = std::env::temp_dir().join("test_refresh_all_edge_bookmarks.json");
+        std::fs::write(&path, chrome_json(&["gamma"])).unwrap();
+
+        let sources = vec![BookmarkSource::browser(BrowserType::Edge, path.clone())];
+        let cache = RwLock::new(vec![
+            Bookmark::new("Uninstalled".to_string(), "https://gone.com".to_string(), BrowserType::Chrome),
+        ]);
+        let stats = RwLock::new(HashMap::from([(BrowserType::Chrome.into(), BookmarkParseStats::default())]));
+
+        BookmarkProvider::refresh_all(&cache, &stats, &sources).await;
+
+        let cache = cache.read().await;
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache[0].browser, BrowserType::Edge);
+
+        let stats = stats.read().await;
+        assert!(!stats.contains_key(&BrowserType::Chrome.into()));
+        assert_eq!(stats[&BrowserType::Edge.into()].parsed, 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_custom_chromium_file_uses_label() {
+        let path = std::env::temp_dir().join("test_custom_vivaldi_bookmarks.json");
+        std::fs::write(&path, chrome_json(&["delta"])).unwrap();
+
+        let provider = BookmarkProvider::new().unwrap().with_custom_files(&[CustomBookmarkFile {
+            path: path.to_string_lossy().to_string(),
+            format: BookmarkFileFormat::Chromium,
+            label: "Work Vivaldi".to_string(),
+        }]);
+        BookmarkProvider::refresh_all(&provider.bookmarks, &provider.parse_stats, &provider.custom_sources).await;
+
+        let results = provider.search(&"delta".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "bookmark:Work Vivaldi:https://delta.example.com");
+        assert_eq!(results[0].metadata["browser"], "Work Vivaldi");
+
+        let label = SourceKey::from(BookmarkOrigin::Custom("Work Vivaldi".to_string()));
+        assert_eq!(provider.parse_stats().await[&label].parsed, 1);
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_missing_custom_file_reported_in_stats() {
+        let path = std::env::temp_dir().join("BetterFinder").join("missing_custom_bookmarks.json");
+        let _ = std::fs::remove_file(&path);
+
+        let provider = BookmarkProvider::new().unwrap().with_custom_files(&[CustomBookmarkFile {
+            path: path.to_string_lossy().to_string(),
+            format: BookmarkFileFormat::Chromium,
+            label: "Gone".to_string(),
+        }]);
+        BookmarkProvider::refresh_all(&provider.bookmarks, &provider.parse_stats, &provider.custom_sources).await;
+
+        assert!(provider.bookmarks.read().await.is_empty());
+        let stats = provider.parse_stats().await;
+        assert_eq!(stats[&BookmarkOrigin::Custom("Gone".to_string()).into()].errors, 1);
+
+        // The user sees which file is missing
+        let ProviderHealth::Degraded { reason } = provider.health() else {
+            panic!("expected degraded health, got {:?}", provider.health());
+        };
+        let expected = format!("Gone: 0 parsed, 0 skipped, 1 errors ({} couldn't be loaded: ", path.display());
+        assert!(reason.starts_with(&expected), "{}", reason);
+
+        // Fixing the path clears the failure
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, chrome_json(&["found"])).unwrap();
+        BookmarkProvider::refresh_all(&provider.bookmarks, &provider.parse_stats, &provider.custom_sources).await;
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_bookmark_source_matches_changed_paths() {
+        let chrome = BookmarkSource::browser(
+            BrowserType::Chrome,
+            PathBuf::from("profile").join("Default").join("Bookmarks"),
+        );
+        assert!(chrome.matches(&PathBuf::from("profile").join("Default").join("Bookmarks")));
+        assert!(!chrome.matches(&PathBuf::from("profile").join("Default").join("History")));
+
+        let firefox = BookmarkSource::browser(
+            BrowserType::Firefox,
+            PathBuf::from("profiles").join("abc.default").join("places.sqlite"),
+        );
+        assert!(firefox.matches(&PathBuf::from("profiles").join("abc.default").join("places.sqlite")));
+        assert!(firefox.matches(&PathBuf::from("profiles").join("abc.default").join("places.sqlite-wal")));
+        assert!(!firefox.matches(&PathBuf::from("profiles").join("other").join("places.sqlite-wal")));
+        assert!(!firefox.matches(&PathBuf::from("profiles").join("abc.default").join("cookies.sqlite-wal")));
+    }
+
+    fn firefox_places(path: &Path, names: &[&str]) {
+        let conn = rusqlite::Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url TEXT);
+             CREATE TABLE moz_bookmarks (id INTEGER PRIMARY KEY, type INTEGER, fk INTEGER, parent INTEGER, title TEXT);",
+        )
+        .unwrap();
+        for (id, name) in names.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO moz_places (id, url) VALUES (?1, ?2)",
+                rusqlite::params![id, format!("https://{}.example.com", name)],
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO moz_bookmarks (id, type, fk, parent, title) VALUES (?1, 1, ?1, 0, ?2)",
+                rusqlite::params![id, name],
+            )
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_each_profile_opens_in_its_own_profile() {
+        let root = std::env::temp_dir().join("bf_test_two_profiles");
+        let _ = std::fs::remove_dir_all(&root);
+
+        // Chrome lists its profiles in Local State
+        let user_data = root.join("User Data");
+        for (folder, name) in [("Default", "personal"), ("Profile 2", "work")] {
+            std::fs::create_dir_all(user_data.join(folder)).unwrap();
+            std::fs::write(user_data.join(folder).join("Bookmarks"), chrome_json(&[name])).unwrap();
+        }
+        std::fs::write(
+            user_data.join("Local State"),
+            r#"{"profile": {"info_cache": {"Profile 2": {"name": "Work"}, "Default": {"name": "Me"}}}}"#,
+        )
+        .unwrap();
+
+        // Firefox lists them in profiles.ini
+        let firefox = root.join("Firefox");
+        for (folder, name) in [("abc.default-release", "home"), ("xyz.work", "office")] {
+            std::fs::create_dir_all(firefox.join("Profiles").join(folder)).unwrap();
+            firefox_places(&firefox.join("Profiles").join(folder).join("places.sqlite"), &[name]);
+        }
+        std::fs::write(
+            firefox.join("profiles.ini"),
+            "[Profile1]\nName=work\nIsRelative=1\nPath=Profiles/xyz.work\n\n\
+             [Profile0]\nName=default-release\nIsRelative=1\nPath=Profiles/abc.default-release\nDefault=1\n",
+        )
+        .unwrap();
+
+        let chrome_profiles = ChromeBookmarkParser::resolve_profiles(&user_data);
+        let firefox_profiles = FirefoxBookmarkParser::resolve_profiles(&firefox);
+        assert_eq!(chrome_profiles.len(), 2);
+        assert_eq!(firefox_profiles.len(), 2);
+        let sources: Vec<BookmarkSource> = chrome_profiles
+            .into_iter()
+            .map(|(profile, path)| BookmarkSource::browser_profile(BrowserType::Chrome, profile, path))
+            .chain(
+                firefox_profiles
+                    .into_iter()
+                    .map(|(profile, path)| BookmarkSource::browser_profile(BrowserType::Firefox, profile, path)),
+            )
+            .collect();
+
+        let provider = BookmarkProvider::new().unwrap();
+        BookmarkProvider::refresh_all(&provider.bookmarks, &provider.parse_stats, &sources).await;
+        let stats = provider.parse_stats().await;
+        for source in &sources {
+            assert_eq!(stats[&source.key()].parsed, 1, "{}", source.key().display_name());
+        }
+
+        // Fake installs for the launch plans
+        let install = root.join("Programs");
+        std::fs::create_dir_all(install.join("Google").join("Chrome").join("Application")).unwrap();
+        std::fs::write(install.join("Google").join("Chrome").join("Application").join("chrome.exe"), "").unwrap();
+        std::fs::create_dir_all(install.join("Mozilla Firefox")).unwrap();
+        std::fs::write(install.join("Mozilla Firefox").join("firefox.exe"), "").unwrap();
+        let roots = vec![install];
+
+        let expected: [(&str, &[&str]); 4] = [
+            ("personal", &["--profile-directory=Default", "--"]),
+            ("work", &["--profile-directory=Profile 2", "--"]),
+            ("home", &["-P", "default-release"]),
+            ("office", &["-P", "work"]),
+        ];
+        for (name, profile_args) in expected {
+            let results = provider.search(&name.into()).await.unwrap();
+            assert_eq!(results.len(), 1, "{}", name);
+            let result = &results[0];
+            let origin: BookmarkOrigin = serde_json::from_value(result.metadata["browser"].clone()).unwrap();
+            let profile = result.metadata.get("profile").and_then(|profile| profile.as_str());
+            let url = format!("https://{}.example.com", name);
+
+            let BookmarkLaunch::SourceBrowser { args, .. } = BookmarkLaunch::plan(&origin, profile, &url, &[], &roots)
+            else {
+                panic!("{} should open in its source browser", name);
+            };
+            let mut expected_args: Vec<String> = profile_args.iter().map(|arg| arg.to_string()).collect();
+            expected_args.push(url);
+            assert_eq!(args, expected_args, "{}", name);
+        }
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_change_debouncer_coalesces_bursts() {
+        let mut debouncer = ChangeDebouncer::new(Duration::from_secs(2));
+        let start = Instant::now();
+
+        // A burst of events restarts the quiet period each time
+        let chrome = SourceKey::from(BrowserType::Chrome);
+        debouncer.record(chrome.clone(), start);
+        debouncer.record(chrome.clone(), start + Duration::from_millis(800));
+        debouncer.record(chrome.clone(), start + Duration::from_millis(1500));
+
+        assert!(debouncer.take_ready(start + Duration::from_secs(2)).is_empty());
+        assert!(debouncer.take_ready(start + Duration::from_millis(3400)).is_empty());
+        assert_eq!(debouncer.take_ready(start + Duration::from_millis(3500)), vec![chrome]);
+
+        // Reported only once
+        assert!(debouncer.take_ready(start + Duration::from_secs(10)).is_empty());
+    }
+
+    #[test]
+    fn test_change_debouncer_tracks_browsers_independently() {
+        let mut debouncer = ChangeDebouncer::new(Duration::from_secs(2));
+        let start = Instant::now();
+
+        let chrome = SourceKey::from(BrowserType::Chrome);
+        let work = SourceKey {
+            origin: BrowserType::Chrome.into(),
+            profile: Some("Profile 2".to_string()),
+        };
+        let custom = SourceKey::from(BookmarkOrigin::Custom("Portable Firefox".to_string()));
+        debouncer.record(chrome.clone(), start);
+        debouncer.record(work.clone(), start + Duration::from_millis(500));
+        debouncer.record(custom.clone(), start + Duration::from_secs(1));
+
+        assert_eq!(debouncer.take_ready(start + Duration::from_secs(2)), vec![chrome]);
+        assert_eq!(debouncer.take_ready(start + Duration::from_millis(2500)), vec![work]);
+        assert_eq!(debouncer.take_ready(start + Duration::from_secs(3)), vec![custom]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_refresh_task() {
+        let mut provider = BookmarkProvider::new().unwrap();
+        provider.start_cache_refresh_task(Vec::new());
+        assert!(provider.refresh_task.is_some());
+
+        provider.shutdown().await.unwrap();
+        assert!(provider.refresh_task.is_none());
+    }
 }
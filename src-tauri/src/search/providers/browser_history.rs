@@ -0,0 +1,826 @@
+/// Browser history provider for re-opening frequently visited pages
+///
+/// This provider reads the Chrome and Edge `History` databases and Firefox's
+/// places.sqlite, keeping the pages each browser ranks highest by its own
+/// visit statistics. It is off unless enabled in settings, since history is
+/// more private than bookmarks.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::bookmark::{BookmarkProvider, BrowserType, FirefoxBookmarkParser};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::normalize_for_search;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Maximum number of history entries to cache
+const MAX_HISTORY_ENTRIES: usize = 2000;
+
+/// Maximum number of results per query
+const MAX_RESULTS: usize = 10;
+
+/// Minimum time between two reads of the browser databases
+const REFRESH_INTERVAL: Duration = Duration::from_secs(600); // 10 minutes
+
+/// How often the refresh task checks whether a refresh is due
+const REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Microseconds between the WebKit epoch (1601-01-01) and the Unix epoch
+const WEBKIT_EPOCH_OFFSET_MICROS: i64 = 11_644_473_600_000_000;
+
+/// Share of a result's score that comes from the browser's popularity ranking
+const POPULARITY_WEIGHT: f64 = 0.2;
+
+/// Chromium visit weights by age in days, modelled on Firefox frecency buckets
+const RECENCY_WEIGHTS: &[(i64, f64)] = &[(4, 100.0), (14, 70.0), (31, 50.0), (90, 30.0)];
+
+/// Weight of visits older than every recency bucket
+const OLD_VISIT_WEIGHT: f64 = 10.0;
+
+/// Distinguishes database snapshots taken at the same time
+static SNAPSHOT_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Converts a Chromium timestamp (microseconds since 1601-01-01) to UTC
+///
+/// Returns `None` for the zero value Chromium uses for "never".
+pub fn webkit_to_datetime(micros: i64) -> Option<DateTime<Utc>> {
+    if micros <= 0 {
+        return None;
+    }
+
+    DateTime::from_timestamp_micros(micros - WEBKIT_EPOCH_OFFSET_MICROS)
+}
+
+/// Represents a visited page
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// Page title, or the URL when the page has none
+    pub title: String,
+    /// Page URL
+    pub url: String,
+    /// Number of recorded visits
+    pub visit_count: u32,
+    /// Time of the most recent visit
+    pub last_visit: Option<DateTime<Utc>>,
+    /// Browser ranking scaled to 0..=1 within the entry's browser
+    pub popularity: f64,
+    /// Browser this entry is from
+    pub browser: BrowserType,
+    /// Normalized title, computed once when the entry is loaded
+    normalized_title: String,
+    /// Normalized URL, computed once when the entry is loaded
+    normalized_url: String,
+}
+
+impl HistoryEntry {
+    /// Creates a new history entry with no popularity yet
+    pub fn new(
+        title: Option<String>,
+        url: String,
+        visit_count: u32,
+        last_visit: Option<DateTime<Utc>>,
+        browser: BrowserType,
+    ) -> Self {
+        let title = title.filter(|t| !t.trim().is_empty()).unwrap_or_else(|| url.clone());
+        let normalized_title = normalize_for_search(&title);
+        let normalized_url = normalize_for_search(&url);
+
+        Self {
+            title,
+            url,
+            visit_count,
+            last_visit,
+            popularity: 0.0,
+            browser,
+            normalized_title,
+            normalized_url,
+        }
+    }
+
+    /// Creates a unique ID for the entry
+    pub fn id(&self) -> String {
+        format!("history:{}:{}", self.browser.display_name(), self.url)
+    }
+}
+
+/// A located history database for one browser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistorySource {
+    /// Browser owning the database
+    pub browser: BrowserType,
+    /// Path to the History or places.sqlite file
+    pub path: PathBuf,
+}
+
+impl HistorySource {
+    /// Locates the history databases of all installed browsers
+    pub fn locate_all() -> Vec<Self> {
+        let mut sources = Vec::new();
+
+        if let Some(path) = Self::locate_chromium_history(&["Google", "Chrome"]) {
+            sources.push(Self { browser: BrowserType::Chrome, path });
+        }
+
+        if let Some(path) = Self::locate_chromium_history(&["Microsoft", "Edge"]) {
+            sources.push(Self { browser: BrowserType::Edge, path });
+        }
+
+        // Firefox keeps history and bookmarks in the same database
+        if let Some(path) = FirefoxBookmarkParser::locate_firefox_places() {
+            sources.push(Self { browser: BrowserType::Firefox, path });
+        }
+
+        sources
+    }
+
+    /// Locates the default profile's History file of a Chromium browser
+    fn locate_chromium_history(vendor_dirs: &[&str]) -> Option<PathBuf> {
+        #[cfg(windows)]
+        {
+            if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
+                let path = PathBuf::from(local_app_data)
+                    .join(vendor_dirs.iter().collect::<PathBuf>())
+                    .join("User Data")
+                    .join("Default")
+                    .join("History");
+
+                if path.exists() {
+                    return Some(path);
+                }
+            }
+        }
+
+        #[cfg(not(windows))]
+        let _ = vendor_dirs;
+
+        None
+    }
+
+    /// Reads the most popular entries from a snapshot of the database
+    ///
+    /// Browsers keep their history locked while running, so the file is
+    /// copied to a temp location first and read from there.
+    pub fn read(&self, now: DateTime<Utc>) -> Result<Vec<HistoryEntry>> {
+        let snapshot = Self::snapshot(&self.path)?;
+
+        let entries = Connection::open(&snapshot)
+            .map_err(LauncherError::from)
+            .and_then(|conn| match self.browser {
+                BrowserType::Firefox => Self::read_firefox(&conn),
+                browser => Self::read_chromium(&conn, browser, now),
+            });
+
+        Self::remove_snapshot(&snapshot);
+
+        let entries = entries?;
+        debug!("Read {} history entries from {}", entries.len(), self.browser.display_name());
+        Ok(entries)
+    }
+
+    /// Copies a database and its write-ahead log to the temp directory
+    ///
+    /// Also used for the favicon databases; remove it with `remove_snapshot`.
+    pub(crate) fn snapshot(path: &Path) -> Result<PathBuf> {
+        let mut target = std::env::temp_dir();
+        target.push("BetterFinder");
+        std::fs::create_dir_all(&target)?;
+        target.push(format!(
+            "browser_snapshot_{}_{}.sqlite",
+            std::process::id(),
+            SNAPSHOT_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        std::fs::copy(path, &target)?;
+
+        // Firefox writes recent visits to the WAL before checkpointing them
+        let wal = Self::sidecar(path, "-wal");
+        if wal.exists() {
+            std::fs::copy(&wal, Self::sidecar(&target, "-wal"))?;
+        }
+
+        Ok(target)
+    }
+
+    /// Deletes a snapshot along with the sidecar files SQLite left next to it
+    pub(crate) fn remove_snapshot(snapshot: &Path) {
+        for path in [snapshot.to_path_buf(), Self::sidecar(snapshot, "-wal"), Self::sidecar(snapshot, "-shm")] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Returns the path of a SQLite sidecar file such as `-wal`
+    fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(suffix);
+        PathBuf::from(name)
+    }
+
+    /// Reads a Chromium `urls` table, ranking pages by visits weighted by recency
+    fn read_chromium(
+        conn: &Connection,
+        browser: BrowserType,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT url, title, visit_count, last_visit_time
+             FROM urls
+             WHERE hidden = 0 AND visit_count > 0
+             ORDER BY visit_count DESC, last_visit_time DESC
+             LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map(params![MAX_HISTORY_ENTRIES], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+
+        let mut ranked = Vec::new();
+        for (url, title, visit_count, last_visit_time) in rows.flatten() {
+            if !Self::is_web_url(&url) {
+                continue;
+            }
+
+            let last_visit = webkit_to_datetime(last_visit_time);
+            let rank = visit_count as f64 * Self::recency_weight(last_visit, now);
+            ranked.push((HistoryEntry::new(title, url, visit_count, last_visit, browser), rank));
+        }
+
+        Ok(Self::scale_popularity(ranked))
+    }
+
+    /// Reads Firefox's `moz_places` table, ranking pages by Firefox's frecency
+    fn read_firefox(conn: &Connection) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT url, title, visit_count, last_visit_date, frecency
+             FROM moz_places
+             WHERE hidden = 0 AND frecency > 0
+             ORDER BY frecency DESC
+             LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map(params![MAX_HISTORY_ENTRIES], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?;
+
+        let mut ranked = Vec::new();
+        for (url, title, visit_count, last_visit_date, frecency) in rows.flatten() {
+            if !Self::is_web_url(&url) {
+                continue;
+            }
+
+            // Firefox stores microseconds since the Unix epoch
+            let last_visit = last_visit_date.and_then(DateTime::from_timestamp_micros);
+            let entry = HistoryEntry::new(title, url, visit_count, last_visit, BrowserType::Firefox);
+            ranked.push((entry, frecency as f64));
+        }
+
+        Ok(Self::scale_popularity(ranked))
+    }
+
+    /// Weighs a visit by its age
+    fn recency_weight(last_visit: Option<DateTime<Utc>>, now: DateTime<Utc>) -> f64 {
+        let Some(last_visit) = last_visit else {
+            return OLD_VISIT_WEIGHT;
+        };
+
+        let age_days = now.signed_duration_since(last_visit).num_days();
+        RECENCY_WEIGHTS
+            .iter()
+            .find(|(max_days, _)| age_days < *max_days)
+            .map_or(OLD_VISIT_WEIGHT, |(_, weight)| *weight)
+    }
+
+    /// Scales raw browser ranks to 0..=1 so browsers with different scales mix
+    fn scale_popularity(ranked: Vec<(HistoryEntry, f64)>) -> Vec<HistoryEntry> {
+        let max_rank = ranked.iter().map(|(_, rank)| *rank).fold(0.0, f64::max);
+
+        ranked
+            .into_iter()
+            .map(|(mut entry, rank)| {
+                entry.popularity = if max_rank > 0.0 { rank / max_rank } else { 0.0 };
+                entry
+            })
+            .collect()
+    }
+
+    /// Checks whether a URL can be opened in a browser from the launcher
+    fn is_web_url(url: &str) -> bool {
+        url.starts_with("http://") || url.starts_with("https://")
+    }
+}
+
+/// Tracks queries in flight and when the cache was last refreshed
+#[derive(Debug, Default)]
+struct RefreshState {
+    /// Number of searches currently running
+    active_queries: AtomicUsize,
+    /// When the browser databases were last read
+    last_refresh: std::sync::Mutex<Option<Instant>>,
+}
+
+impl RefreshState {
+    /// Checks whether the cache is stale and no query is running
+    fn refresh_due(&self, now: Instant) -> bool {
+        if self.active_queries.load(Ordering::SeqCst) > 0 {
+            return false;
+        }
+
+        self.last_refresh
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_none_or(|last| now.saturating_duration_since(last) >= REFRESH_INTERVAL)
+    }
+
+    /// Records a completed refresh
+    fn mark_refreshed(&self, at: Instant) {
+        *self.last_refresh.lock().unwrap_or_else(|e| e.into_inner()) = Some(at);
+    }
+}
+
+/// Counts a query as in flight until dropped
+struct QueryGuard<'a>(&'a RefreshState);
+
+impl<'a> QueryGuard<'a> {
+    fn new(state: &'a RefreshState) -> Self {
+        state.active_queries.fetch_add(1, Ordering::SeqCst);
+        Self(state)
+    }
+}
+
+impl Drop for QueryGuard<'_> {
+    fn drop(&mut self) {
+        self.0.active_queries.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Browser history search provider
+pub struct BrowserHistoryProvider {
+    /// Cached entries, most popular first
+    entries: Arc<RwLock<Vec<HistoryEntry>>>,
+    /// In-flight queries and refresh timing
+    refresh_state: Arc<RefreshState>,
+    /// Whether the provider is enabled
+    enabled: bool,
+    /// Background task refreshing the cache
+    refresh_task: Option<JoinHandle<()>>,
+}
+
+impl BrowserHistoryProvider {
+    /// Creates a new browser history provider
+    pub fn new() -> Result<Self> {
+        info!("Initializing BrowserHistoryProvider");
+
+        Ok(Self {
+            entries: Arc::new(RwLock::new(Vec::new())),
+            refresh_state: Arc::new(RefreshState::default()),
+            enabled: true,
+            refresh_task: None,
+        })
+    }
+
+    /// Reads every source off the async runtime and replaces the cache
+    async fn refresh(entries: &RwLock<Vec<HistoryEntry>>, sources: &[HistorySource]) {
+        let sources = sources.to_vec();
+
+        let loaded = tokio::task::spawn_blocking(move || {
+            let now = Utc::now();
+            sources
+                .iter()
+                .filter_map(|source| match source.read(now) {
+                    Ok(entries) => Some(entries),
+                    Err(e) => {
+                        warn!("Failed to read {} history: {}", source.browser.display_name(), e);
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .await;
+
+        match loaded {
+            Ok(loaded) => {
+                let merged = Self::merge_sources(loaded);
+                info!("History cache refreshed with {} entries", merged.len());
+                *entries.write().await = merged;
+            }
+            Err(e) => warn!("Failed to spawn history read task: {}", e),
+        }
+    }
+
+    /// Merges entries from all browsers, keeping the most popular copy of each URL
+    fn merge_sources(loaded: Vec<Vec<HistoryEntry>>) -> Vec<HistoryEntry> {
+        let mut merged: Vec<HistoryEntry> = loaded.into_iter().flatten().collect();
+        merged.sort_by(|a, b| {
+            b.popularity
+                .partial_cmp(&a.popularity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.visit_count.cmp(&a.visit_count))
+        });
+
+        let mut seen = HashSet::new();
+        merged.retain(|entry| seen.insert(entry.url.clone()));
+        merged.truncate(MAX_HISTORY_ENTRIES);
+        merged
+    }
+
+    /// Starts the background task that refreshes the cache when it goes stale
+    fn start_refresh_task(&mut self, sources: Vec<HistorySource>) {
+        if let Some(task) = self.refresh_task.take() {
+            task.abort();
+        }
+
+        let entries = Arc::clone(&self.entries);
+        let state = Arc::clone(&self.refresh_state);
+        self.refresh_task = Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(REFRESH_CHECK_INTERVAL);
+            loop {
+                ticker.tick().await;
+                if state.refresh_due(Instant::now()) {
+                    Self::refresh(&entries, &sources).await;
+                    state.mark_refreshed(Instant::now());
+                }
+            }
+        }));
+    }
+
+    /// Searches cached entries, blending text match with browser popularity
+    async fn search_entries(&self, query: &str) -> Vec<SearchResult> {
+        let entries = self.entries.read().await;
+        let query_lower = normalize_for_search(query);
+
+        let mut results: Vec<(&HistoryEntry, f64)> = entries
+            .iter()
+            .filter_map(|entry| {
+                let text_score = if entry.normalized_title == query_lower {
+                    100.0
+                } else if entry.normalized_title.starts_with(&query_lower) {
+                    90.0
+                } else if entry.normalized_title.contains(&query_lower) {
+                    70.0
+                } else if entry.normalized_url.contains(&query_lower) {
+                    50.0
+                } else {
+                    return None;
+                };
+
+                let score = text_score * (1.0 - POPULARITY_WEIGHT + POPULARITY_WEIGHT * entry.popularity);
+                Some((entry, score))
+            })
+            .collect();
+
+        // Sort by score (highest first)
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(MAX_RESULTS);
+
+        results
+            .into_iter()
+            .map(|(entry, score)| Self::create_search_result(entry, score))
+            .collect()
+    }
+
+    /// Creates a search result from a history entry
+    fn create_search_result(entry: &HistoryEntry, score: f64) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("url".to_string(), serde_json::json!(entry.url));
+        metadata.insert("browser".to_string(), serde_json::json!(entry.browser));
+        metadata.insert("source".to_string(), serde_json::json!("history"));
+        metadata.insert("visit_count".to_string(), serde_json::json!(entry.visit_count));
+
+        if let Some(last_visit) = entry.last_visit {
+            metadata.insert("last_visit".to_string(), serde_json::json!(last_visit.to_rfc3339()));
+        }
+
+        SearchResult {
+            id: entry.id(),
+            title: entry.title.clone(),
+            subtitle: entry.url.clone(),
+            icon: Some("history".to_string()),
+            result_type: ResultType::History,
+            score,
+            metadata,
+            action: ResultAction::OpenUrl {
+                url: entry.url.clone(),
+            },
+            normalized_title: Some(entry.normalized_title.clone()),
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for BrowserHistoryProvider {
+    fn name(&self) -> &str {
+        "BrowserHistory"
+    }
+
+    fn priority(&self) -> u8 {
+        40 // Below bookmarks
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::History])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
+
+        // Don't search if query is too short
+        if trimmed.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        // Holds off the background refresh while this query runs
+        let _guard = QueryGuard::new(&self.refresh_state);
+        Ok(self.search_entries(trimmed).await)
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::History {
+            return Err(LauncherError::ExecutionError(
+                "Not a history result".to_string(),
+            ));
+        }
+
+        if let ResultAction::OpenUrl { url } = &result.action {
+            info!("Opening history entry: {}", url);
+            BookmarkProvider::open_url(url).await
+        } else {
+            Err(LauncherError::ExecutionError(
+                "Invalid history action".to_string(),
+            ))
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("Initializing BrowserHistoryProvider");
+
+        let sources = HistorySource::locate_all();
+        Self::refresh(&self.entries, &sources).await;
+        self.refresh_state.mark_refreshed(Instant::now());
+        self.start_refresh_task(sources);
+
+        info!("BrowserHistoryProvider initialized successfully");
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        info!("Shutting down BrowserHistoryProvider");
+
+        if let Some(task) = self.refresh_task.take() {
+            task.abort();
+            let _ = task.await;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BrowserHistoryProvider {
+    fn default() -> Self {
+        Self::new().unwrap_or_else(|_| Self {
+            entries: Arc::new(RwLock::new(Vec::new())),
+            refresh_state: Arc::new(RefreshState::default()),
+            enabled: false,
+            refresh_task: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an empty fixture database under the temp directory
+    fn fixture_db(name: &str) -> (PathBuf, Connection) {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        std::fs::create_dir_all(&path).ok();
+        path.push(format!("{}_{}.sqlite", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let conn = Connection::open(&path).unwrap();
+        (path, conn)
+    }
+
+    fn webkit_micros(at: DateTime<Utc>) -> i64 {
+        at.timestamp_micros() + WEBKIT_EPOCH_OFFSET_MICROS
+    }
+
+    #[test]
+    fn test_webkit_timestamp_conversion() {
+        assert_eq!(
+            webkit_to_datetime(WEBKIT_EPOCH_OFFSET_MICROS),
+            DateTime::from_timestamp(0, 0)
+        );
+        assert_eq!(
+            webkit_to_datetime(13_316_000_000_000_000).unwrap().to_rfc3339(),
+            "2022-12-20T08:53:20+00:00"
+        );
+        assert_eq!(webkit_to_datetime(0), None);
+    }
+
+    #[test]
+    fn test_read_chromium_history_fixture() {
+        let (path, conn) = fixture_db("chromium_history_fixture");
+        conn.execute_batch(
+            "CREATE TABLE urls (
+                id INTEGER PRIMARY KEY,
+                url LONGVARCHAR,
+                title LONGVARCHAR,
+                visit_count INTEGER DEFAULT 0 NOT NULL,
+                typed_count INTEGER DEFAULT 0 NOT NULL,
+                last_visit_time INTEGER NOT NULL,
+                hidden INTEGER DEFAULT 0 NOT NULL
+            )",
+        )
+        .unwrap();
+
+        let now = Utc::now();
+        let recent = webkit_micros(now - chrono::Duration::days(1));
+        let old = webkit_micros(now - chrono::Duration::days(200));
+        let rows: [(&str, &str, u32, i64, i32); 5] = [
+            ("https://github.com/", "GitHub", 10, recent, 0),
+            ("https://old.example.com/", "Old Forum", 20, old, 0),
+            ("https://docs.rs/", "", 2, recent, 0),
+            ("https://hidden.example.com/", "Hidden", 50, recent, 1),
+            ("chrome://settings/", "Settings", 30, recent, 0),
+        ];
+        for (url, title, visits, last_visit, hidden) in rows {
+            conn.execute(
+                "INSERT INTO urls (url, title, visit_count, last_visit_time, hidden) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![url, title, visits, last_visit, hidden],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        let source = HistorySource { browser: BrowserType::Chrome, path: path.clone() };
+        let entries = source.read(now).unwrap();
+
+        let urls: Vec<&str> = entries.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://old.example.com/", "https://github.com/", "https://docs.rs/"]);
+
+        // Ten recent visits outrank twenty from months ago
+        let github = entries.iter().find(|e| e.title == "GitHub").unwrap();
+        let forum = entries.iter().find(|e| e.title == "Old Forum").unwrap();
+        assert_eq!(github.popularity, 1.0);
+        assert!(forum.popularity < github.popularity);
+
+        // Untitled pages fall back to their URL
+        assert!(entries.iter().any(|e| e.title == "https://docs.rs/"));
+        assert!(github.last_visit.is_some());
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_read_firefox_history_fixture() {
+        let (path, conn) = fixture_db("firefox_history_fixture");
+        conn.execute_batch(
+            "CREATE TABLE moz_places (
+                id INTEGER PRIMARY KEY,
+                url LONGVARCHAR,
+                title LONGVARCHAR,
+                rev_host LONGVARCHAR,
+                visit_count INTEGER DEFAULT 0,
+                hidden INTEGER DEFAULT 0 NOT NULL,
+                typed INTEGER DEFAULT 0 NOT NULL,
+                frecency INTEGER DEFAULT -1 NOT NULL,
+                last_visit_date INTEGER
+            )",
+        )
+        .unwrap();
+
+        let visited = Utc::now().timestamp_micros();
+        let rows: [(&str, &str, u32, i64, i32); 4] = [
+            ("https://developer.mozilla.org/", "MDN Web Docs", 12, 2000, 0),
+            ("https://crates.io/", "crates.io", 4, 500, 0),
+            ("https://never.example.com/", "Never Visited", 0, 0, 0),
+            ("place:sort=8", "Recent Tags", 0, 100, 0),
+        ];
+        for (url, title, visits, frecency, hidden) in rows {
+            conn.execute(
+                "INSERT INTO moz_places (url, title, visit_count, frecency, hidden, last_visit_date) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![url, title, visits, frecency, hidden, visited],
+            )
+            .unwrap();
+        }
+        drop(conn);
+
+        let source = HistorySource { browser: BrowserType::Firefox, path: path.clone() };
+        let entries = source.read(Utc::now()).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].title, "MDN Web Docs");
+        assert_eq!(entries[0].popularity, 1.0);
+        assert_eq!(entries[1].popularity, 0.25);
+        assert_eq!(entries[0].browser, BrowserType::Firefox);
+        assert_eq!(
+            entries[0].last_visit.map(|t| t.timestamp_micros()),
+            Some(visited)
+        );
+
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_read_missing_database_fails() {
+        let source = HistorySource {
+            browser: BrowserType::Edge,
+            path: PathBuf::from("nonexistent_history_db"),
+        };
+        assert!(source.read(Utc::now()).is_err());
+    }
+
+    #[test]
+    fn test_merge_sources_dedups_by_url() {
+        let mut chrome = HistoryEntry::new(Some("Rust".to_string()), "https://rust-lang.org/".to_string(), 3, None, BrowserType::Chrome);
+        chrome.popularity = 0.4;
+        let mut firefox = HistoryEntry::new(Some("Rust".to_string()), "https://rust-lang.org/".to_string(), 9, None, BrowserType::Firefox);
+        firefox.popularity = 0.9;
+        let mut edge = HistoryEntry::new(Some("Docs".to_string()), "https://docs.rs/".to_string(), 1, None, BrowserType::Edge);
+        edge.popularity = 0.5;
+
+        let merged = BrowserHistoryProvider::merge_sources(vec![vec![chrome, edge], vec![firefox]]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].browser, BrowserType::Firefox);
+        assert_eq!(merged[1].url, "https://docs.rs/");
+    }
+
+    #[tokio::test]
+    async fn test_search_blends_text_match_and_popularity() {
+        let provider = BrowserHistoryProvider::new().unwrap();
+
+        let mut rarely = HistoryEntry::new(Some("Rust Playground".to_string()), "https://play.rust-lang.org/".to_string(), 1, None, BrowserType::Chrome);
+        rarely.popularity = 0.1;
+        let mut often = HistoryEntry::new(Some("Rust Book".to_string()), "https://doc.rust-lang.org/book/".to_string(), 40, None, BrowserType::Chrome);
+        often.popularity = 1.0;
+        *provider.entries.write().await = vec![rarely, often];
+
+        let results = provider.search(&"rust".into()).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].title, "Rust Book");
+        assert_eq!(results[0].result_type, ResultType::History);
+        assert_eq!(results[0].metadata["source"], "history");
+        assert!(results[0].score > results[1].score);
+        assert!(results[0].score <= 90.0);
+
+        // URL-only matches still show up, below title matches
+        let results = provider.search(&"play.rust".into()).await.unwrap();
+        assert_eq!(results[0].title, "Rust Playground");
+    }
+
+    #[test]
+    fn test_refresh_waits_for_interval_and_queries() {
+        let state = RefreshState::default();
+        let now = Instant::now();
+        assert!(state.refresh_due(now));
+
+        state.mark_refreshed(now);
+        assert!(!state.refresh_due(now + Duration::from_secs(60)));
+        assert!(state.refresh_due(now + REFRESH_INTERVAL));
+
+        // Never while a query is in flight
+        let guard = QueryGuard::new(&state);
+        assert!(!state.refresh_due(now + REFRESH_INTERVAL));
+        drop(guard);
+        assert!(state.refresh_due(now + REFRESH_INTERVAL));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejects_other_result_types() {
+        let provider = BrowserHistoryProvider::new().unwrap();
+        let mut result = BrowserHistoryProvider::create_search_result(
+            &HistoryEntry::new(None, "https://example.com/".to_string(), 1, None, BrowserType::Edge),
+            50.0,
+        );
+        result.result_type = ResultType::Bookmark;
+
+        assert!(provider.execute(&result).await.is_err());
+    }
+}
@@ -0,0 +1,361 @@
+/// Opening bookmarks in the browser and profile they were saved in
+///
+/// Chromium browsers select a profile with `--profile-directory`, given the
+/// profile's folder name such as `Profile 2`. Firefox selects one with `-P`,
+/// which takes the name listed in `profiles.ini` rather than the folder, so
+/// Firefox profiles are read from there. The browser's executable comes from the
+/// scanned applications or the usual install folders; when neither has it
+/// the bookmark opens in the default browser as before.
+
+use crate::search::providers::app_search::Application;
+use crate::search::providers::bookmark::{BookmarkOrigin, BrowserType};
+use crate::utils::deeplink::is_web_url;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Whether bookmarks open in the browser they came from
+///
+/// Clones share the flag, so saving settings changes how the registered
+/// provider opens bookmarks without re-registering it.
+#[derive(Debug, Clone, Default)]
+pub struct OpenInSourceBrowser(Arc<AtomicBool>);
+
+impl OpenInSourceBrowser {
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// A Firefox profile listed in `profiles.ini`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirefoxProfile {
+    /// Name `-P` selects the profile by
+    pub name: String,
+    /// Profile folder as written in the file
+    pub path: String,
+    /// Whether `path` is relative to the folder holding `profiles.ini`
+    pub is_relative: bool,
+    /// Whether Firefox starts in this profile when none is asked for
+    pub is_default: bool,
+}
+
+impl FirefoxProfile {
+    /// The profile folder, resolving relative paths against `firefox_dir`
+    pub fn dir(&self, firefox_dir: &Path) -> PathBuf {
+        let root = if self.is_relative { firefox_dir.to_path_buf() } else { PathBuf::new() };
+        self.path.split(['/', '\\']).filter(|part| !part.is_empty()).fold(root, |dir, part| dir.join(part))
+    }
+}
+
+/// A `[ProfileN]` section read so far
+#[derive(Debug)]
+struct ProfileSection {
+    name: Option<String>,
+    path: Option<String>,
+    is_relative: bool,
+    is_default: bool,
+}
+
+impl ProfileSection {
+    fn finish(self) -> Option<FirefoxProfile> {
+        Some(FirefoxProfile {
+            name: self.name?,
+            path: self.path?,
+            is_relative: self.is_relative,
+            is_default: self.is_default,
+        })
+    }
+}
+
+/// Reads the profiles from the contents of `profiles.ini`, in file order
+///
+/// Only `[ProfileN]` sections with both a name and a path count; the
+/// `[Install…]` and `[General]` sections are skipped.
+pub fn parse_profiles_ini(content: &str) -> Vec<FirefoxProfile> {
+    let mut profiles = Vec::new();
+    let mut current: Option<ProfileSection> = None;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(section) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            profiles.extend(current.take().and_then(ProfileSection::finish));
+            if section.starts_with("Profile") {
+                // Paths are relative unless the file says otherwise
+                current = Some(ProfileSection { name: None, path: None, is_relative: true, is_default: false });
+            }
+            continue;
+        }
+
+        let (Some(profile), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Name" => profile.name = Some(value.to_string()),
+            "Path" => profile.path = Some(value.to_string()),
+            "IsRelative" => profile.is_relative = value != "0",
+            "Default" => profile.is_default = value == "1",
+            _ => {}
+        }
+    }
+    profiles.extend(current.and_then(ProfileSection::finish));
+
+    profiles
+}
+
+/// Arguments opening `url` in `browser`, in `profile` when there is one
+///
+/// Chromium browsers stop reading switches at `--`, so the URL can't pass
+/// for one.
+pub fn launch_args(browser: BrowserType, profile: Option<&str>, url: &str) -> Vec<String> {
+    let mut args = match (browser, profile) {
+        (BrowserType::Chrome | BrowserType::Edge, Some(profile)) => {
+            vec![format!("--profile-directory={}", profile)]
+        }
+        (BrowserType::Firefox, Some(profile)) => vec!["-P".to_string(), profile.to_string()],
+        _ => Vec::new(),
+    };
+    if browser != BrowserType::Firefox {
+        args.push("--".to_string());
+    }
+    args.push(url.to_string());
+    args
+}
+
+/// Where the browser's executable is installed, relative to an install root
+fn install_path(browser: BrowserType) -> &'static [&'static str] {
+    match browser {
+        BrowserType::Chrome => &["Google", "Chrome", "Application", "chrome.exe"],
+        BrowserType::Edge => &["Microsoft", "Edge", "Application", "msedge.exe"],
+        BrowserType::Firefox => &["Mozilla Firefox", "firefox.exe"],
+        BrowserType::Opera => &["Opera", "opera.exe"],
+        BrowserType::OperaGx => &["Opera GX", "opera.exe"],
+    }
+}
+
+/// Folders browsers are installed under: both `Program Files`, and
+/// `%LOCALAPPDATA%` and its `Programs` for per-user installs
+pub fn locate_install_roots() -> Vec<PathBuf> {
+    let local_app_data = std::env::var_os("LOCALAPPDATA").map(PathBuf::from);
+    ["ProgramFiles", "ProgramFiles(x86)"]
+        .iter()
+        .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+        .chain(local_app_data.iter().flat_map(|local| [local.clone(), local.join("Programs")]))
+        .collect()
+}
+
+/// Finds the browser's executable among the scanned applications, then
+/// under the install roots
+///
+/// Opera and Opera GX both install an `opera.exe`, so an application only
+/// counts when its path ends the way the browser's install path does.
+pub fn resolve_executable(browser: BrowserType, apps: &[Application], install_roots: &[PathBuf]) -> Option<PathBuf> {
+    let relative: PathBuf = install_path(browser).iter().collect();
+    let ends_with_install_path = |path: &Path| {
+        let path = path.to_string_lossy().to_lowercase();
+        let relative = relative.to_string_lossy().to_lowercase();
+        path.strip_suffix(relative.as_str())
+            .is_some_and(|rest| rest.ends_with(std::path::MAIN_SEPARATOR))
+    };
+
+    apps.iter()
+        .find(|app| ends_with_install_path(&app.path))
+        .map(|app| app.path.clone())
+        .or_else(|| {
+            install_roots
+                .iter()
+                .map(|root| root.join(&relative))
+                .find(|path| path.is_file())
+        })
+}
+
+/// How a bookmark gets opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookmarkLaunch {
+    /// Starts the browser it came from with these arguments
+    SourceBrowser { program: PathBuf, args: Vec<String> },
+    /// Hands the URL to the default browser
+    DefaultBrowser,
+}
+
+impl BookmarkLaunch {
+    /// Plans opening `url`, saved in `origin` under `profile`
+    ///
+    /// Bookmarks from custom files, browsers whose executable can't be found
+    /// and anything but http(s) URLs open in the default browser: a bookmark
+    /// file may hold any text as a URL, and the browser would read it as a switch.
+    pub fn plan(
+        origin: &BookmarkOrigin,
+        profile: Option<&str>,
+        url: &str,
+        apps: &[Application],
+        install_roots: &[PathBuf],
+    ) -> Self {
+        let BookmarkOrigin::Browser(browser) = origin else {
+            return Self::DefaultBrowser;
+        };
+        if !is_web_url(url) {
+            return Self::DefaultBrowser;
+        }
+
+        match resolve_executable(*browser, apps, install_roots) {
+            Some(program) => Self::SourceBrowser {
+                program,
+                args: launch_args(*browser, profile, url),
+            },
+            None => {
+                warn!("{} executable not found, opening bookmark in the default browser", browser.display_name());
+                Self::DefaultBrowser
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_launch_args_per_browser_family() {
+        let url = "https://example.com";
+        assert_eq!(
+            launch_args(BrowserType::Chrome, Some("Profile 2"), url),
+            vec!["--profile-directory=Profile 2", "--", url]
+        );
+        assert_eq!(
+            launch_args(BrowserType::Edge, Some("Default"), url),
+            vec!["--profile-directory=Default", "--", url]
+        );
+        assert_eq!(launch_args(BrowserType::Firefox, Some("work"), url), vec!["-P", "work", url]);
+
+        // Without a profile, or for Opera, no profile is passed
+        assert_eq!(launch_args(BrowserType::Firefox, None, url), vec![url]);
+        assert_eq!(launch_args(BrowserType::OperaGx, Some("Opera GX Stable"), url), vec!["--", url]);
+    }
+
+    #[test]
+    fn test_profiles_ini_maps_folders_to_names() {
+        let ini = "[Install308046B0AF4A39CB]\r\n\
+                   Default=Profiles/abcd1234.default-release\r\n\
+                   \r\n\
+                   [Profile1]\r\n\
+                   Name=work\r\n\
+                   IsRelative=1\r\n\
+                   Path=Profiles/wxyz9876.work\r\n\
+                   \r\n\
+                   [Profile0]\r\n\
+                   Name=default-release\r\n\
+                   IsRelative=1\r\n\
+                   Path=Profiles/abcd1234.default-release\r\n\
+                   Default=1\r\n\
+                   \r\n\
+                   [Profile2]\r\n\
+                   Name=portable\r\n\
+                   IsRelative=0\r\n\
+                   Path=D:\\Firefox\\portable\r\n\
+                   \r\n\
+                   [Profile3]\r\n\
+                   Path=Profiles/nameless\r\n\
+                   \r\n\
+                   [General]\r\n\
+                   StartWithLastProfile=1\r\n";
+
+        let profiles = parse_profiles_ini(ini);
+        let names: Vec<&str> = profiles.iter().map(|profile| profile.name.as_str()).collect();
+        assert_eq!(names, vec!["work", "default-release", "portable"]);
+        assert!(profiles[1].is_default && !profiles[0].is_default);
+        assert!(!profiles[2].is_relative);
+
+        // The folder is what the name points at, not the name itself
+        let firefox_dir = PathBuf::from("Mozilla").join("Firefox");
+        assert_eq!(profiles[0].dir(&firefox_dir), firefox_dir.join("Profiles").join("wxyz9876.work"));
+        assert_eq!(
+            profiles[2].dir(&firefox_dir),
+            ["D:", "Firefox", "portable"].iter().collect::<PathBuf>()
+        );
+    }
+
+    #[test]
+    fn test_launch_falls_back_to_the_default_browser() {
+        let url = "https://example.com";
+        let root = temp_dir("browser_roots");
+        let chrome = root.join("Google").join("Chrome").join("Application").join("chrome.exe");
+        std::fs::create_dir_all(chrome.parent().unwrap()).unwrap();
+        std::fs::write(&chrome, b"").unwrap();
+        let roots = vec![root.clone()];
+        let origin = BookmarkOrigin::from(BrowserType::Chrome);
+
+        assert_eq!(
+            BookmarkLaunch::plan(&origin, Some("Profile 2"), url, &[], &roots),
+            BookmarkLaunch::SourceBrowser {
+                program: chrome.clone(),
+                args: vec!["--profile-directory=Profile 2".to_string(), "--".to_string(), url.to_string()],
+            }
+        );
+
+        // The scanned applications come first, and Opera GX's opera.exe isn't Opera's
+        let apps = vec![Application::new(
+            "Opera GX".to_string(),
+            PathBuf::from("C:").join("Apps").join("Opera GX").join("opera.exe"),
+            None,
+            false,
+        )];
+        let opera = BookmarkOrigin::from(BrowserType::Opera);
+        assert_eq!(BookmarkLaunch::plan(&opera, None, url, &apps, &roots), BookmarkLaunch::DefaultBrowser);
+        assert_eq!(
+            BookmarkLaunch::plan(&BrowserType::OperaGx.into(), None, url, &apps, &roots),
+            BookmarkLaunch::SourceBrowser { program: apps[0].path.clone(), args: vec!["--".to_string(), url.to_string()] }
+        );
+
+        // Uninstalled browsers and custom files use the default browser
+        let firefox = BookmarkOrigin::from(BrowserType::Firefox);
+        assert_eq!(BookmarkLaunch::plan(&firefox, Some("work"), url, &[], &roots), BookmarkLaunch::DefaultBrowser);
+        let custom = BookmarkOrigin::Custom("Exported".to_string());
+        assert_eq!(BookmarkLaunch::plan(&custom, None, url, &[], &roots), BookmarkLaunch::DefaultBrowser);
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_argument_shaped_urls_open_in_the_default_browser() {
+        let root = temp_dir("browser_roots_switches");
+        let chrome = root.join("Google").join("Chrome").join("Application").join("chrome.exe");
+        std::fs::create_dir_all(chrome.parent().unwrap()).unwrap();
+        std::fs::write(&chrome, b"").unwrap();
+        let roots = vec![root.clone()];
+        let origin = BookmarkOrigin::from(BrowserType::Chrome);
+
+        for url in [
+            "--utility-cmd-prefix=calc.exe",
+            "--gpu-launcher=cmd /c calc",
+            "file:///C:/Windows/System32/calc.exe",
+            "ftp://example.com/file",
+            "https://example.com --no-sandbox",
+        ] {
+            assert_eq!(
+                BookmarkLaunch::plan(&origin, Some("Default"), url, &[], &roots),
+                BookmarkLaunch::DefaultBrowser,
+                "{}",
+                url
+            );
+        }
+        assert!(matches!(
+            BookmarkLaunch::plan(&origin, Some("Default"), "HTTPS://example.com", &[], &roots),
+            BookmarkLaunch::SourceBrowser { .. }
+        ));
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
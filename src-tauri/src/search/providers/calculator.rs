@@ -4,15 +4,52 @@
 /// - Basic arithmetic operations (+, -, *, /)
 /// - Parentheses and order of operations
 /// - Decimal numbers
-/// - Common mathematical functions
+/// - Common mathematical functions and constants (sqrt, sin, log, pi, ...)
+/// - Degree-mode trigonometry (`sind(30)` or `sin(30deg)`)
+///
+/// `=` or `calc` on its own lists past calculations from the history, and
+/// narrows them down with the text after it.
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
+use crate::search::providers::calculator_history::{CalculationEntry, CalculatorHistory};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature, SECONDARY_ACTIONS_KEY};
 use crate::types::{ResultAction, ResultType, SearchResult};
 use async_trait::async_trait;
 use regex::Regex;
 use std::collections::HashMap;
-use tracing::{debug, info};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Functions accepted in calculator queries; each must be followed by `(`
+const MATH_FUNCTIONS: &[&str] = &[
+    "sqrt", "abs", "sin", "cos", "tan", "ln", "log", "exp", "floor", "ceil", "round",
+    "sind", "cosd", "tand",
+];
+
+/// Constants accepted in calculator queries
+const MATH_CONSTANTS: &[&str] = &["pi", "e"];
+
+/// Suffix marking a number as degrees, e.g. `sin(30deg)`
+const DEGREE_SUFFIX: &str = "deg";
+
+/// Words listing the calculator history
+const HISTORY_KEYWORDS: &[&str] = &["=", "calc"];
+
+/// Prefix for calculator history result ids
+const HISTORY_ID_PREFIX: &str = "calculator:history:";
+
+/// Identifier of the secondary action putting a past expression in the search box
+pub const INSERT_EXPRESSION_ACTION_ID: &str = "insert_expression";
+
+/// Help entry for the history keyword
+const HISTORY_SYNTAX: SyntaxFeature = SyntaxFeature {
+    id: "calculator_history",
+    title: "Calculator history",
+    description: "Lists past calculations to copy again; = works too",
+    example: "calc ",
+    token: None,
+    values: &[],
+};
 
 /// Expression evaluator wrapper around meval
 pub struct ExpressionEvaluator;
@@ -25,29 +62,118 @@ impl ExpressionEvaluator {
 
     /// Validates if a string is a valid mathematical expression
     pub fn is_valid_expression(expr: &str) -> bool {
-        // Check if expression contains only valid characters
-        let valid_chars = Regex::new(r"^[\d\s\+\-\*/\(\)\.\^%]+$").unwrap();
+        let normalized = match Self::normalize(expr) {
+            Some(normalized) => normalized,
+            None => return false,
+        };
+
+        // Must contain at least one operator or function call, or be a number
+        let has_operator = normalized.contains('+') 
+            || normalized.contains('-') 
+            || normalized.contains('*') 
+            || normalized.contains('/') 
+            || normalized.contains('^')
+            || normalized.contains('%');
         
-        if !valid_chars.is_match(expr) {
-            return false;
+        let has_function = normalized.contains('(')
+            && MATH_FUNCTIONS.iter().any(|f| normalized.contains(&format!("{}(", f)));
+        
+        let is_number = normalized.parse::<f64>().is_ok();
+
+        has_operator || has_function || is_number
+    }
+
+    /// Tokenizes an expression into its normalized form
+    ///
+    /// Returns `None` when the expression contains words other than the
+    /// whitelisted functions (which must be followed by `(`), constants and the
+    /// `deg` suffix, so ordinary text never reaches the evaluator. The result
+    /// is lowercase, with `deg` written as `°` and spaces kept only between
+    /// adjacent operands.
+    pub fn normalize(expr: &str) -> Option<String> {
+        let chars: Vec<char> = expr.trim().to_lowercase().chars().collect();
+        let is_operand = |c: char| c.is_ascii_alphanumeric() || c == '.' || c == '°';
+
+        let mut normalized = String::new();
+        let mut pending_space = false;
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+
+            if c.is_whitespace() {
+                pending_space = true;
+                i += 1;
+                continue;
+            }
+
+            let token: String = if c.is_ascii_alphabetic() {
+                let start = i;
+                while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                    i += 1;
+                }
+                chars[start..i].iter().collect()
+            } else {
+                i += 1;
+                c.to_string()
+            };
+            let next = chars[i..].iter().find(|c| !c.is_whitespace()).copied();
+            let follows_number = normalized.ends_with(|c: char| c.is_ascii_digit());
+
+            let token = match token.as_str() {
+                word if MATH_FUNCTIONS.contains(&word) => {
+                    if next != Some('(') {
+                        return None;
+                    }
+                    token
+                }
+                word if MATH_CONSTANTS.contains(&word) => {
+                    if next == Some('(') {
+                        return None;
+                    }
+                    token
+                }
+                DEGREE_SUFFIX | "°" if follows_number => {
+                    pending_space = false;
+                    "°".to_string()
+                }
+                _ if token.chars().all(|c| c.is_ascii_digit() || "+-*/()^%.".contains(c)) => token,
+                _ => return None,
+            };
+
+            let first = token.chars().next().unwrap_or(' ');
+            if pending_space && normalized.ends_with(is_operand) && is_operand(first) {
+                normalized.push(' ');
+            }
+            pending_space = false;
+            normalized.push_str(&token);
         }
 
-        // Must contain at least one operator or be a number
-        let has_operator = expr.contains('+') 
-            || expr.contains('-') 
-            || expr.contains('*') 
-            || expr.contains('/') 
-            || expr.contains('^')
-            || expr.contains('%');
-        
-        let is_number = expr.trim().parse::<f64>().is_ok();
+        if normalized.is_empty() {
+            None
+        } else {
+            Some(normalized)
+        }
+    }
 
-        has_operator || is_number
+    /// Rewrites degree values like `30°` into radians for meval
+    fn expand_degrees(expr: &str) -> String {
+        let degrees = Regex::new(r"(\d+(?:\.\d+)?)°").unwrap();
+        degrees.replace_all(expr, "($1*pi/180)").to_string()
     }
 
     /// Evaluates a mathematical expression
     pub fn evaluate(expr: &str) -> Result<f64> {
-        meval::eval_str(expr).map_err(|e| {
+        let normalized = Self::normalize(expr).unwrap_or_else(|| expr.to_string());
+
+        let mut context = meval::Context::new();
+        context
+            .func("log", f64::log10)
+            .func("sind", |x: f64| x.to_radians().sin())
+            .func("cosd", |x: f64| x.to_radians().cos())
+            .func("tand", |x: f64| x.to_radians().tan());
+
+        meval::eval_str_with_context(Self::expand_degrees(&normalized), context).map_err(|e| {
             LauncherError::ExecutionError(format!("Failed to evaluate expression: {}", e))
         })
     }
@@ -67,25 +193,126 @@ pub struct CalculatorProvider {
     enabled: bool,
     /// Regex for detecting math expressions
     math_pattern: Regex,
+    /// Where copied calculations are recorded
+    history: Option<Arc<CalculatorHistory>>,
+    /// Called with a past expression to put it in the search box
+    insert_handler: Option<InsertHandler>,
 }
 
+/// Puts an expression in the search box
+type InsertHandler = Box<dyn Fn(&str) + Send + Sync>;
+
 impl CalculatorProvider {
     /// Creates a new CalculatorProvider
     pub fn new() -> Result<Self> {
         info!("Initializing CalculatorProvider");
 
         // Pattern to detect potential math expressions
-        // Matches expressions with numbers and operators
-        let math_pattern = Regex::new(r"^[\d\s\+\-\*/\(\)\.\^%]+$")
+        // Matches numbers, operators and words; words are checked against the whitelist later
+        let math_pattern = Regex::new(r"^[\d\sA-Za-z°\+\-\*/\(\)\.\^%]+$")
             .map_err(|e| LauncherError::ExecutionError(format!("Failed to compile regex: {}", e)))?;
 
         Ok(Self {
             evaluator: ExpressionEvaluator::new(),
             enabled: true,
             math_pattern,
+            history: None,
+            insert_handler: None,
         })
     }
 
+    /// Records copied calculations in `history` and lists it for `=` and `calc`
+    pub fn with_history(mut self, history: Arc<CalculatorHistory>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Sets what puts a past expression in the search box for editing
+    pub fn set_insert_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.insert_handler = Some(Box::new(handler));
+    }
+
+    /// Records an evaluation in the history, if there is one
+    ///
+    /// Failing to save the history never fails the calculation.
+    pub async fn record_calculation(&self, expression: &str, result: &str) {
+        let Some(history) = &self.history else {
+            return;
+        };
+        if let Err(e) = history.record(expression, result).await {
+            warn!("Failed to record calculation '{}': {}", expression, e);
+        }
+    }
+
+    /// Splits `=` or `calc` off a query, returning the rest
+    fn strip_history_keyword(query: &str) -> Option<&str> {
+        let trimmed = query.trim_start();
+        let (keyword, rest) = trimmed.split_once(char::is_whitespace).unwrap_or((trimmed, ""));
+        HISTORY_KEYWORDS
+            .iter()
+            .any(|k| keyword.eq_ignore_ascii_case(k))
+            .then_some(rest.trim())
+    }
+
+    /// Lists past calculations, most recent first, with the evaluation of
+    /// `rest` on top when it is a calculation itself
+    async fn search_history(&self, history: &CalculatorHistory, rest: &str) -> Vec<SearchResult> {
+        let mut results = Vec::new();
+        let mut filter = rest.to_lowercase();
+        if self.is_math_expression(rest) {
+            if let Ok(result) = ExpressionEvaluator::evaluate(rest) {
+                results.push(self.create_search_result(rest, result));
+                filter.clear();
+            }
+        }
+
+        let entries = history.list().await;
+        let matching = entries.iter().filter(|entry| entry.expression.to_lowercase().contains(&filter));
+        results.extend(matching.enumerate().map(|(index, entry)| Self::create_history_result(entry, index)));
+        results
+    }
+
+    /// Converts a past calculation to a SearchResult copying its result
+    fn create_history_result(entry: &CalculationEntry, index: usize) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("expression".to_string(), serde_json::json!(entry.expression));
+        metadata.insert("formatted_result".to_string(), serde_json::json!(entry.result));
+        metadata.insert("uses".to_string(), serde_json::json!(entry.uses));
+        metadata.insert(
+            SECONDARY_ACTIONS_KEY.to_string(),
+            serde_json::json!([{
+                "id": INSERT_EXPRESSION_ACTION_ID,
+                "title": "Insert expression",
+                "requires_confirmation": false,
+            }]),
+        );
+
+        let subtitle = match entry.uses {
+            1 => format!("Calculated {}", entry.last_used.format("%Y-%m-%d %H:%M")),
+            uses => format!("Calculated {} times, last {}", uses, entry.last_used.format("%Y-%m-%d %H:%M")),
+        };
+
+        SearchResult {
+            id: format!("{}{}", HISTORY_ID_PREFIX, index),
+            title: format!("{} = {}", entry.expression, entry.result),
+            subtitle,
+            icon: Some("calculator".to_string()),
+            result_type: ResultType::Calculator,
+            // Keeps the most recent first
+            score: 90.0 - index as f64 * 0.1,
+            metadata,
+            action: ResultAction::CopyToClipboard {
+                content: entry.result.clone(),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
     /// Checks if a query is a mathematical expression
     fn is_math_expression(&self, query: &str) -> bool {
         let trimmed = query.trim();
@@ -104,6 +331,25 @@ impl CalculatorProvider {
         ExpressionEvaluator::is_valid_expression(trimmed)
     }
 
+    /// Evaluates `expression` as a calculator query would, returning the formatted result
+    ///
+    /// Returns `None` for text that isn't a calculation or has no finite result.
+    pub fn calculate(&self, expression: &str) -> Option<String> {
+        let trimmed = expression.trim();
+        if !self.is_math_expression(trimmed) {
+            return None;
+        }
+
+        match ExpressionEvaluator::evaluate(trimmed) {
+            Ok(result) if result.is_finite() => Some(Self::format_result(result)),
+            Ok(_) => None,
+            Err(e) => {
+                debug!("Failed to evaluate expression: {}", e);
+                None
+            }
+        }
+    }
+
     /// Formats a number result with appropriate precision
     fn format_result(value: f64) -> String {
         // If the number is an integer, display without decimals
@@ -119,16 +365,19 @@ impl CalculatorProvider {
     /// Converts calculation result to SearchResult
     fn create_search_result(&self, expression: &str, result: f64) -> SearchResult {
         let formatted_result = Self::format_result(result);
+        let normalized_expression = ExpressionEvaluator::normalize(expression)
+            .unwrap_or_else(|| expression.to_string());
         
         let mut metadata = HashMap::new();
         metadata.insert("expression".to_string(), serde_json::json!(expression));
+        metadata.insert("normalized_expression".to_string(), serde_json::json!(normalized_expression));
         metadata.insert("result".to_string(), serde_json::json!(result));
         metadata.insert("formatted_result".to_string(), serde_json::json!(formatted_result));
 
         SearchResult {
             id: format!("calculator:{}", expression),
             title: formatted_result.clone(),
-            subtitle: format!("{} = {}", expression, formatted_result),
+            subtitle: format!("{} = {}", normalized_expression, formatted_result),
             icon: Some("calculator".to_string()),
             result_type: ResultType::Calculator,
             score: 100.0, // Always high score for valid calculations
@@ -136,6 +385,9 @@ impl CalculatorProvider {
             action: ResultAction::CopyToClipboard {
                 content: formatted_result,
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 }
@@ -150,8 +402,20 @@ impl SearchProvider for CalculatorProvider {
         90 // Very high priority for calculator
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let trimmed = query.trim();
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Calculator])
+    }
+
+    fn supports_peek(&self) -> bool {
+        true
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
+
+        if let (Some(history), Some(rest)) = (&self.history, Self::strip_history_keyword(trimmed)) {
+            return Ok(self.search_history(history, rest).await);
+        }
         
         if !self.is_math_expression(trimmed) {
             return Ok(Vec::new());
@@ -196,11 +460,46 @@ impl SearchProvider for CalculatorProvider {
             ResultAction::CopyToClipboard { content } => {
                 Self::copy_to_clipboard(content).await?;
                 info!("Successfully copied result to clipboard");
-                Ok(())
             }
-            _ => Err(LauncherError::ExecutionError(
-                "Invalid action for calculator result".to_string(),
-            )),
+            _ => {
+                return Err(LauncherError::ExecutionError(
+                    "Invalid action for calculator result".to_string(),
+                ))
+            }
+        }
+
+        // Copying a past calculation again doesn't evaluate anything new
+        if !result.id.starts_with(HISTORY_ID_PREFIX) {
+            if let Some(expression) = result.metadata.get("expression").and_then(|v| v.as_str()) {
+                self.record_calculation(expression, formatted_result).await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn execute_action(&self, result: &SearchResult, action_id: &str) -> Result<()> {
+        if result.result_type != ResultType::Calculator || action_id != INSERT_EXPRESSION_ACTION_ID {
+            return Err(LauncherError::NotFound(format!("Calculator has no action '{}'", action_id)));
+        }
+
+        let expression = result
+            .metadata
+            .get("expression")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| LauncherError::ExecutionError("Invalid calculator result".to_string()))?;
+        let handler = self
+            .insert_handler
+            .as_ref()
+            .ok_or_else(|| LauncherError::ExecutionError("Expressions can't be inserted".to_string()))?;
+        handler(expression);
+        Ok(())
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        if self.history.is_some() {
+            vec![HISTORY_SYNTAX]
+        } else {
+            Vec::new()
         }
     }
 
@@ -219,7 +518,9 @@ impl Default for CalculatorProvider {
         Self::new().unwrap_or_else(|_| Self {
             evaluator: ExpressionEvaluator::new(),
             enabled: false,
-            math_pattern: Regex::new(r"^[\d\s\+\-\*/\(\)\.\^%]+$").unwrap(),
+            math_pattern: Regex::new(r"^[\d\sA-Za-z°\+\-\*/\(\)\.\^%]+$").unwrap(),
+            history: None,
+            insert_handler: None,
         })
     }
 }
@@ -363,6 +664,68 @@ mod tests {
         assert_eq!(ExpressionEvaluator::evaluate("10.5/2").unwrap(), 5.25);
     }
 
+    fn assert_evaluates(expr: &str, expected: f64) {
+        let value = ExpressionEvaluator::evaluate(expr).unwrap();
+        assert!((value - expected).abs() < 1e-9, "{} = {}, expected {}", expr, value, expected);
+    }
+
+    #[test]
+    fn test_whitelisted_functions_evaluate() {
+        assert_evaluates("sqrt(16)", 4.0);
+        assert_evaluates("abs(-3)", 3.0);
+        assert_evaluates("sin(pi/2)", 1.0);
+        assert_evaluates("cos(0)", 1.0);
+        assert_evaluates("tan(0)", 0.0);
+        assert_evaluates("ln(e)", 1.0);
+        assert_evaluates("log(100)", 2.0);
+        assert_evaluates("exp(0)", 1.0);
+        assert_evaluates("floor(2.7)", 2.0);
+        assert_evaluates("ceil(2.1)", 3.0);
+        assert_evaluates("round(2.5)", 3.0);
+        assert_evaluates("2*pi", std::f64::consts::TAU);
+        assert_evaluates("SQRT(2) * Sqrt(2)", 2.0);
+    }
+
+    #[test]
+    fn test_degree_mode_trig() {
+        assert_evaluates("sind(30)", 0.5);
+        assert_evaluates("cosd(60)", 0.5);
+        assert_evaluates("tand(45)", 1.0);
+        assert_evaluates("sin(30deg)", 0.5);
+        assert_evaluates("cos(60 deg)", 0.5);
+        assert_evaluates("sin(90°)", 1.0);
+    }
+
+    #[test]
+    fn test_normalize_expression() {
+        assert_eq!(ExpressionEvaluator::normalize("SQRT( 2 )").as_deref(), Some("sqrt(2)"));
+        assert_eq!(ExpressionEvaluator::normalize("sin(30 deg)").as_deref(), Some("sin(30°)"));
+        assert_eq!(ExpressionEvaluator::normalize(" 2 + 2 ").as_deref(), Some("2+2"));
+        assert_eq!(ExpressionEvaluator::normalize("sin city"), None);
+        assert_eq!(ExpressionEvaluator::normalize("exp report.pdf"), None);
+        assert_eq!(ExpressionEvaluator::normalize("pi(2)"), None);
+        assert_eq!(ExpressionEvaluator::normalize("deg"), None);
+    }
+
+    #[tokio::test]
+    async fn test_function_queries_trigger_only_for_math() {
+        let provider = CalculatorProvider::new().unwrap();
+
+        let results = provider.search(&"sqrt(2)".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "1.4142135624");
+
+        let results = provider.search(&"Sin( 30 deg )".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "0.5");
+        assert_eq!(results[0].subtitle, "sin(30°) = 0.5");
+
+        assert!(provider.search(&"sin city".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"exp report.pdf".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"pi".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"e".into()).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_is_math_expression() {
         let provider = CalculatorProvider::new().unwrap();
@@ -380,6 +743,16 @@ mod tests {
         assert!(!provider.is_math_expression("   "));
     }
 
+    #[test]
+    fn test_calculate_formats_finite_results() {
+        let provider = CalculatorProvider::new().unwrap();
+
+        assert_eq!(provider.calculate(" 12 * 4 ").as_deref(), Some("48"));
+        assert_eq!(provider.calculate("sqrt(2)").as_deref(), Some("1.4142135624"));
+        assert_eq!(provider.calculate("1/0"), None);
+        assert_eq!(provider.calculate("meeting at 3"), None);
+    }
+
     #[tokio::test]
     async fn test_format_result() {
         // Integers
@@ -402,19 +775,19 @@ mod tests {
         let provider = CalculatorProvider::new().unwrap();
 
         // Test addition
-        let results = provider.search("2+2").await.unwrap();
+        let results = provider.search(&"2+2".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "4");
         assert_eq!(results[0].result_type, ResultType::Calculator);
         assert_eq!(results[0].score, 100.0);
 
         // Test multiplication
-        let results = provider.search("10*5").await.unwrap();
+        let results = provider.search(&"10*5".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "50");
 
         // Test division
-        let results = provider.search("20/4").await.unwrap();
+        let results = provider.search(&"20/4".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "5");
     }
@@ -424,17 +797,17 @@ mod tests {
         let provider = CalculatorProvider::new().unwrap();
 
         // Test with parentheses
-        let results = provider.search("(2+3)*4").await.unwrap();
+        let results = provider.search(&"(2+3)*4".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "20");
 
         // Test order of operations
-        let results = provider.search("2+3*4").await.unwrap();
+        let results = provider.search(&"2+3*4".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "14");
 
         // Test with decimals
-        let results = provider.search("2.5+2.5").await.unwrap();
+        let results = provider.search(&"2.5+2.5".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "5");
     }
@@ -444,13 +817,13 @@ mod tests {
         let provider = CalculatorProvider::new().unwrap();
 
         // Non-math queries should return empty results
-        let results = provider.search("hello world").await.unwrap();
+        let results = provider.search(&"hello world".into()).await.unwrap();
         assert!(results.is_empty());
 
-        let results = provider.search("search query").await.unwrap();
+        let results = provider.search(&"search query".into()).await.unwrap();
         assert!(results.is_empty());
 
-        let results = provider.search("").await.unwrap();
+        let results = provider.search(&"".into()).await.unwrap();
         assert!(results.is_empty());
     }
 
@@ -458,7 +831,7 @@ mod tests {
     async fn test_search_result_metadata() {
         let provider = CalculatorProvider::new().unwrap();
 
-        let results = provider.search("2+2").await.unwrap();
+        let results = provider.search(&"2+2".into()).await.unwrap();
         assert_eq!(results.len(), 1);
 
         let result = &results[0];
@@ -479,7 +852,7 @@ mod tests {
     async fn test_search_result_action() {
         let provider = CalculatorProvider::new().unwrap();
 
-        let results = provider.search("2+2").await.unwrap();
+        let results = provider.search(&"2+2".into()).await.unwrap();
         assert_eq!(results.len(), 1);
 
         let result = &results[0];
@@ -509,6 +882,9 @@ mod tests {
             action: ResultAction::CopyToClipboard {
                 content: "test".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         };
 
         let result = provider.execute(&invalid_result).await;
@@ -528,11 +904,11 @@ mod tests {
         let provider = CalculatorProvider::new().unwrap();
 
         // Test with various spacing
-        let results = provider.search("  2 + 2  ").await.unwrap();
+        let results = provider.search(&"  2 + 2  ".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "4");
 
-        let results = provider.search("10   *   5").await.unwrap();
+        let results = provider.search(&"10   *   5".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].title, "50");
     }
@@ -543,14 +919,51 @@ mod tests {
 
         // Invalid expressions should return empty results, not error
         // Unmatched parentheses - these should fail evaluation
-        let results = provider.search("(2+3").await.unwrap();
+        let results = provider.search(&"(2+3".into()).await.unwrap();
         assert!(results.is_empty());
 
-        let results = provider.search("2+3)").await.unwrap();
+        let results = provider.search(&"2+3)".into()).await.unwrap();
         assert!(results.is_empty());
 
         // Expression ending with operator
-        let results = provider.search("2+").await.unwrap();
+        let results = provider.search(&"2+".into()).await.unwrap();
         assert!(results.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_history_keyword_lists_recent_first() {
+        let path = std::env::temp_dir().join("BetterFinder").join("calculator_history_keyword.json");
+        let _ = std::fs::remove_file(&path);
+        let history = Arc::new(CalculatorHistory::with_path(path.clone()).unwrap());
+        let inserted = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut provider = CalculatorProvider::new().unwrap().with_history(Arc::clone(&history));
+        let recorded = Arc::clone(&inserted);
+        provider.set_insert_handler(move |expression| recorded.lock().unwrap().push(expression.to_string()));
+
+        provider.record_calculation("2+2", "4").await;
+        provider.record_calculation("sqrt(16)", "4").await;
+        provider.record_calculation("10/4", "2.5").await;
+
+        let titles = |results: &[SearchResult]| results.iter().map(|r| r.title.clone()).collect::<Vec<_>>();
+        let listed = provider.search(&"=".into()).await.unwrap();
+        assert_eq!(titles(&listed), vec!["10/4 = 2.5", "sqrt(16) = 4", "2+2 = 4"]);
+        assert!(listed.windows(2).all(|pair| pair[0].score > pair[1].score));
+        assert_eq!(titles(&provider.search(&"calc sqrt".into()).await.unwrap()), vec!["sqrt(16) = 4"]);
+
+        // A calculation after the keyword is evaluated above the history
+        let evaluated = provider.search(&"= 3*3".into()).await.unwrap();
+        assert_eq!(evaluated[0].title, "9");
+        assert_eq!(evaluated.len(), 4);
+
+        provider.execute_action(&listed[1], INSERT_EXPRESSION_ACTION_ID).await.unwrap();
+        assert_eq!(*inserted.lock().unwrap(), vec!["sqrt(16)"]);
+        assert!(matches!(
+            provider.execute_action(&listed[1], "copy_stats_json").await,
+            Err(LauncherError::NotFound(_))
+        ));
+
+        // Without a history the keyword means nothing
+        assert!(CalculatorProvider::new().unwrap().search(&"=".into()).await.unwrap().is_empty());
+        let _ = std::fs::remove_file(path);
+    }
 }
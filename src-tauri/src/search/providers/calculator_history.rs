@@ -0,0 +1,242 @@
+/// Persistent history of calculator evaluations
+///
+/// Every calculation the user copies from the launcher or runs through quick
+/// math is kept here, most recent first, so `=` or `calc` can offer it again.
+/// Evaluating the same expression twice in a row bumps its use count instead
+/// of adding a second entry. Nothing is recorded while privacy mode is on.
+
+use crate::error::{LauncherError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::RwLock;
+use tracing::{error, info};
+
+/// Maximum number of calculations kept; the oldest are dropped first
+pub const MAX_HISTORY_ENTRIES: usize = 100;
+
+/// One evaluated expression and its formatted result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalculationEntry {
+    pub expression: String,
+    pub result: String,
+    /// How many times in a row it was evaluated
+    #[serde(default = "default_uses")]
+    pub uses: u32,
+    pub last_used: DateTime<Utc>,
+}
+
+fn default_uses() -> u32 {
+    1
+}
+
+/// Persistent store for calculator history
+pub struct CalculatorHistory {
+    /// Path to the storage file
+    storage_path: PathBuf,
+    /// Calculations, most recent first
+    entries: RwLock<Vec<CalculationEntry>>,
+    /// Whether recording is suppressed
+    privacy_mode: AtomicBool,
+}
+
+impl CalculatorHistory {
+    /// Creates the store and loads existing history from disk
+    pub fn new() -> Result<Self> {
+        Self::with_path(Self::get_storage_path()?)
+    }
+
+    /// Creates a store backed by a specific file
+    pub fn with_path(storage_path: PathBuf) -> Result<Self> {
+        if let Some(parent) = storage_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut entries = match Self::read_entries(&storage_path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                error!("Failed to load calculator history from {:?}: {}", storage_path, e);
+                Vec::new()
+            }
+        };
+        entries.truncate(MAX_HISTORY_ENTRIES);
+
+        info!("Loaded {} calculator history entries", entries.len());
+
+        Ok(Self {
+            storage_path,
+            entries: RwLock::new(entries),
+            privacy_mode: AtomicBool::new(false),
+        })
+    }
+
+    /// Gets the storage file path
+    fn get_storage_path() -> Result<PathBuf> {
+        #[cfg(test)]
+        {
+            // Use temp directory for tests
+            let mut path = std::env::temp_dir();
+            path.push("BetterFinder");
+            path.push("calculator_history_test.json");
+            return Ok(path);
+        }
+
+        #[cfg(not(test))]
+        {
+            let app_data = std::env::var("APPDATA")
+                .map_err(|_| LauncherError::ConfigError("APPDATA not found".to_string()))?;
+
+            let mut path = PathBuf::from(app_data);
+            path.push("BetterFinder");
+            path.push("calculator_history.json");
+
+            Ok(path)
+        }
+    }
+
+    fn read_entries(path: &Path) -> Result<Vec<CalculationEntry>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the history to disk
+    async fn persist(&self, entries: &[CalculationEntry]) -> Result<()> {
+        let path = self.storage_path.clone();
+        let content = serde_json::to_string_pretty(entries)?;
+
+        tokio::task::spawn_blocking(move || std::fs::write(&path, content))
+            .await
+            .map_err(|e| {
+                LauncherError::ExecutionError(format!("Failed to spawn save task: {}", e))
+            })??;
+
+        Ok(())
+    }
+
+    /// Stops or resumes recording calculations
+    pub fn set_privacy_mode(&self, enabled: bool) {
+        self.privacy_mode.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Records an evaluation, returning false when privacy mode suppressed it
+    ///
+    /// The same expression with the same result as the latest entry only
+    /// bumps that entry's use count.
+    pub async fn record(&self, expression: &str, result: &str) -> Result<bool> {
+        if self.privacy_mode.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return Ok(false);
+        }
+
+        let mut entries = self.entries.write().await;
+        match entries.first_mut() {
+            Some(latest) if latest.expression == expression && latest.result == result => {
+                latest.uses = latest.uses.saturating_add(1);
+                latest.last_used = Utc::now();
+            }
+            _ => {
+                entries.insert(
+                    0,
+                    CalculationEntry {
+                        expression: expression.to_string(),
+                        result: result.to_string(),
+                        uses: 1,
+                        last_used: Utc::now(),
+                    },
+                );
+                entries.truncate(MAX_HISTORY_ENTRIES);
+            }
+        }
+        self.persist(&entries).await?;
+        Ok(true)
+    }
+
+    /// Returns the calculations, most recent first
+    pub async fn list(&self) -> Vec<CalculationEntry> {
+        self.entries.read().await.clone()
+    }
+
+    /// Forgets every calculation, returning how many there were
+    pub async fn clear(&self) -> Result<usize> {
+        let mut entries = self.entries.write().await;
+        let removed = entries.len();
+        entries.clear();
+        self.persist(&entries).await?;
+
+        info!("Cleared {} calculator history entries", removed);
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_history(name: &str) -> (CalculatorHistory, PathBuf) {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("calculator_history_{}.json", name));
+        let _ = std::fs::remove_file(&path);
+
+        (CalculatorHistory::with_path(path.clone()).unwrap(), path)
+    }
+
+    #[tokio::test]
+    async fn test_consecutive_duplicates_collapse() {
+        let (history, path) = temp_history("collapse");
+
+        history.record("2+2", "4").await.unwrap();
+        history.record(" 2+2 ", "4").await.unwrap();
+        history.record("3*3", "9").await.unwrap();
+        history.record("2+2", "4").await.unwrap();
+
+        let entries = history.list().await;
+        let listed: Vec<(&str, u32)> = entries.iter().map(|e| (e.expression.as_str(), e.uses)).collect();
+        // Only repeats in a row collapse
+        assert_eq!(listed, vec![("2+2", 1), ("3*3", 1), ("2+2", 2)]);
+
+        let reloaded = CalculatorHistory::with_path(path.clone()).unwrap();
+        assert_eq!(reloaded.list().await, entries);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_history_is_capped_oldest_first() {
+        let (history, path) = temp_history("cap");
+
+        for i in 0..MAX_HISTORY_ENTRIES + 5 {
+            history.record(&format!("{}+1", i), &(i + 1).to_string()).await.unwrap();
+        }
+
+        let entries = history.list().await;
+        assert_eq!(entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(entries[0].expression, format!("{}+1", MAX_HISTORY_ENTRIES + 4));
+        assert_eq!(entries.last().unwrap().expression, "5+1");
+
+        assert_eq!(history.clear().await.unwrap(), MAX_HISTORY_ENTRIES);
+        assert!(CalculatorHistory::with_path(path.clone()).unwrap().list().await.is_empty());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_privacy_mode_suppresses_recording() {
+        let (history, path) = temp_history("privacy");
+
+        history.set_privacy_mode(true);
+        assert!(!history.record("2+2", "4").await.unwrap());
+        assert!(history.list().await.is_empty());
+
+        history.set_privacy_mode(false);
+        assert!(history.record("2+2", "4").await.unwrap());
+        assert_eq!(history.list().await.len(), 1);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,395 @@
+/// City and abbreviation aliases resolved by the date/time provider
+///
+/// Keys are lowercase; values are IANA time zone names understood by chrono-tz.
+/// IANA names themselves (e.g. "europe/lisbon") are resolved separately, so only
+/// common spellings and abbreviations need to be listed here.
+pub const CITY_TIMEZONES: &[(&str, &str)] = &[
+    // Abbreviations
+    ("utc", "UTC"),
+    ("gmt", "Etc/GMT"),
+    ("zulu", "UTC"),
+    ("est", "America/New_York"),
+    ("edt", "America/New_York"),
+    ("eastern", "America/New_York"),
+    ("cst", "America/Chicago"),
+    ("cdt", "America/Chicago"),
+    ("central", "America/Chicago"),
+    ("mst", "America/Denver"),
+    ("mdt", "America/Denver"),
+    ("mountain", "America/Denver"),
+    ("pst", "America/Los_Angeles"),
+    ("pdt", "America/Los_Angeles"),
+    ("pacific", "America/Los_Angeles"),
+    ("akst", "America/Anchorage"),
+    ("hst", "Pacific/Honolulu"),
+    ("bst", "Europe/London"),
+    ("wet", "Europe/Lisbon"),
+    ("cet", "Europe/Paris"),
+    ("cest", "Europe/Paris"),
+    ("eet", "Europe/Athens"),
+    ("eest", "Europe/Athens"),
+    ("msk", "Europe/Moscow"),
+    ("ist", "Asia/Kolkata"),
+    ("pkt", "Asia/Karachi"),
+    ("sgt", "Asia/Singapore"),
+    ("hkt", "Asia/Hong_Kong"),
+    ("jst", "Asia/Tokyo"),
+    ("kst", "Asia/Seoul"),
+    ("aest", "Australia/Sydney"),
+    ("aedt", "Australia/Sydney"),
+    ("acst", "Australia/Adelaide"),
+    ("awst", "Australia/Perth"),
+    ("nzst", "Pacific/Auckland"),
+    ("nzdt", "Pacific/Auckland"),
+    // North America
+    ("new york", "America/New_York"),
+    ("new york city", "America/New_York"),
+    ("nyc", "America/New_York"),
+    ("boston", "America/New_York"),
+    ("philadelphia", "America/New_York"),
+    ("washington", "America/New_York"),
+    ("washington dc", "America/New_York"),
+    ("dc", "America/New_York"),
+    ("baltimore", "America/New_York"),
+    ("pittsburgh", "America/New_York"),
+    ("atlanta", "America/New_York"),
+    ("miami", "America/New_York"),
+    ("orlando", "America/New_York"),
+    ("tampa", "America/New_York"),
+    ("charlotte", "America/New_York"),
+    ("raleigh", "America/New_York"),
+    ("columbus", "America/New_York"),
+    ("cleveland", "America/New_York"),
+    ("cincinnati", "America/New_York"),
+    ("detroit", "America/Detroit"),
+    ("indianapolis", "America/Indiana/Indianapolis"),
+    ("louisville", "America/Kentucky/Louisville"),
+    ("toronto", "America/Toronto"),
+    ("ottawa", "America/Toronto"),
+    ("montreal", "America/Toronto"),
+    ("quebec", "America/Toronto"),
+    ("halifax", "America/Halifax"),
+    ("st johns", "America/St_Johns"),
+    ("chicago", "America/Chicago"),
+    ("houston", "America/Chicago"),
+    ("dallas", "America/Chicago"),
+    ("austin", "America/Chicago"),
+    ("san antonio", "America/Chicago"),
+    ("minneapolis", "America/Chicago"),
+    ("st louis", "America/Chicago"),
+    ("kansas city", "America/Chicago"),
+    ("milwaukee", "America/Chicago"),
+    ("nashville", "America/Chicago"),
+    ("memphis", "America/Chicago"),
+    ("new orleans", "America/Chicago"),
+    ("oklahoma city", "America/Chicago"),
+    ("winnipeg", "America/Winnipeg"),
+    ("denver", "America/Denver"),
+    ("salt lake city", "America/Denver"),
+    ("albuquerque", "America/Denver"),
+    ("boise", "America/Boise"),
+    ("calgary", "America/Edmonton"),
+    ("edmonton", "America/Edmonton"),
+    ("phoenix", "America/Phoenix"),
+    ("tucson", "America/Phoenix"),
+    ("los angeles", "America/Los_Angeles"),
+    ("la", "America/Los_Angeles"),
+    ("san francisco", "America/Los_Angeles"),
+    ("sf", "America/Los_Angeles"),
+    ("san jose", "America/Los_Angeles"),
+    ("san diego", "America/Los_Angeles"),
+    ("sacramento", "America/Los_Angeles"),
+    ("seattle", "America/Los_Angeles"),
+    ("portland", "America/Los_Angeles"),
+    ("las vegas", "America/Los_Angeles"),
+    ("vancouver", "America/Vancouver"),
+    ("anchorage", "America/Anchorage"),
+    ("honolulu", "Pacific/Honolulu"),
+    ("mexico city", "America/Mexico_City"),
+    ("guadalajara", "America/Mexico_City"),
+    ("monterrey", "America/Monterrey"),
+    ("tijuana", "America/Tijuana"),
+    ("cancun", "America/Cancun"),
+    // Central America and Caribbean
+    ("guatemala city", "America/Guatemala"),
+    ("san salvador", "America/El_Salvador"),
+    ("tegucigalpa", "America/Tegucigalpa"),
+    ("managua", "America/Managua"),
+    ("san jose costa rica", "America/Costa_Rica"),
+    ("panama city", "America/Panama"),
+    ("havana", "America/Havana"),
+    ("kingston", "America/Jamaica"),
+    ("santo domingo", "America/Santo_Domingo"),
+    ("san juan", "America/Puerto_Rico"),
+    ("port au prince", "America/Port-au-Prince"),
+    ("nassau", "America/Nassau"),
+    // South America
+    ("bogota", "America/Bogota"),
+    ("medellin", "America/Bogota"),
+    ("caracas", "America/Caracas"),
+    ("quito", "America/Guayaquil"),
+    ("guayaquil", "America/Guayaquil"),
+    ("lima", "America/Lima"),
+    ("la paz", "America/La_Paz"),
+    ("santiago", "America/Santiago"),
+    ("buenos aires", "America/Argentina/Buenos_Aires"),
+    ("cordoba", "America/Argentina/Cordoba"),
+    ("montevideo", "America/Montevideo"),
+    ("asuncion", "America/Asuncion"),
+    ("sao paulo", "America/Sao_Paulo"),
+    ("rio de janeiro", "America/Sao_Paulo"),
+    ("rio", "America/Sao_Paulo"),
+    ("brasilia", "America/Sao_Paulo"),
+    ("belo horizonte", "America/Sao_Paulo"),
+    ("salvador", "America/Bahia"),
+    ("recife", "America/Recife"),
+    ("fortaleza", "America/Fortaleza"),
+    ("manaus", "America/Manaus"),
+    // Europe
+    ("london", "Europe/London"),
+    ("manchester", "Europe/London"),
+    ("birmingham", "Europe/London"),
+    ("liverpool", "Europe/London"),
+    ("leeds", "Europe/London"),
+    ("glasgow", "Europe/London"),
+    ("edinburgh", "Europe/London"),
+    ("cardiff", "Europe/London"),
+    ("belfast", "Europe/London"),
+    ("dublin", "Europe/Dublin"),
+    ("cork", "Europe/Dublin"),
+    ("lisbon", "Europe/Lisbon"),
+    ("porto", "Europe/Lisbon"),
+    ("madrid", "Europe/Madrid"),
+    ("barcelona", "Europe/Madrid"),
+    ("valencia", "Europe/Madrid"),
+    ("seville", "Europe/Madrid"),
+    ("bilbao", "Europe/Madrid"),
+    ("paris", "Europe/Paris"),
+    ("lyon", "Europe/Paris"),
+    ("marseille", "Europe/Paris"),
+    ("toulouse", "Europe/Paris"),
+    ("nice", "Europe/Paris"),
+    ("brussels", "Europe/Brussels"),
+    ("antwerp", "Europe/Brussels"),
+    ("amsterdam", "Europe/Amsterdam"),
+    ("rotterdam", "Europe/Amsterdam"),
+    ("the hague", "Europe/Amsterdam"),
+    ("luxembourg", "Europe/Luxembourg"),
+    ("berlin", "Europe/Berlin"),
+    ("hamburg", "Europe/Berlin"),
+    ("munich", "Europe/Berlin"),
+    ("cologne", "Europe/Berlin"),
+    ("frankfurt", "Europe/Berlin"),
+    ("stuttgart", "Europe/Berlin"),
+    ("dusseldorf", "Europe/Berlin"),
+    ("leipzig", "Europe/Berlin"),
+    ("dresden", "Europe/Berlin"),
+    ("zurich", "Europe/Zurich"),
+    ("geneva", "Europe/Zurich"),
+    ("basel", "Europe/Zurich"),
+    ("bern", "Europe/Zurich"),
+    ("vienna", "Europe/Vienna"),
+    ("salzburg", "Europe/Vienna"),
+    ("rome", "Europe/Rome"),
+    ("milan", "Europe/Rome"),
+    ("naples", "Europe/Rome"),
+    ("turin", "Europe/Rome"),
+    ("florence", "Europe/Rome"),
+    ("venice", "Europe/Rome"),
+    ("bologna", "Europe/Rome"),
+    ("monaco", "Europe/Monaco"),
+    ("valletta", "Europe/Malta"),
+    ("copenhagen", "Europe/Copenhagen"),
+    ("oslo", "Europe/Oslo"),
+    ("bergen", "Europe/Oslo"),
+    ("stockholm", "Europe/Stockholm"),
+    ("gothenburg", "Europe/Stockholm"),
+    ("helsinki", "Europe/Helsinki"),
+    ("reykjavik", "Atlantic/Reykjavik"),
+    ("tallinn", "Europe/Tallinn"),
+    ("riga", "Europe/Riga"),
+    ("vilnius", "Europe/Vilnius"),
+    ("warsaw", "Europe/Warsaw"),
+    ("krakow", "Europe/Warsaw"),
+    ("gdansk", "Europe/Warsaw"),
+    ("wroclaw", "Europe/Warsaw"),
+    ("prague", "Europe/Prague"),
+    ("brno", "Europe/Prague"),
+    ("bratislava", "Europe/Bratislava"),
+    ("budapest", "Europe/Budapest"),
+    ("ljubljana", "Europe/Ljubljana"),
+    ("zagreb", "Europe/Zagreb"),
+    ("belgrade", "Europe/Belgrade"),
+    ("sarajevo", "Europe/Sarajevo"),
+    ("podgorica", "Europe/Podgorica"),
+    ("skopje", "Europe/Skopje"),
+    ("tirana", "Europe/Tirane"),
+    ("sofia", "Europe/Sofia"),
+    ("bucharest", "Europe/Bucharest"),
+    ("chisinau", "Europe/Chisinau"),
+    ("athens", "Europe/Athens"),
+    ("thessaloniki", "Europe/Athens"),
+    ("nicosia", "Asia/Nicosia"),
+    ("istanbul", "Europe/Istanbul"),
+    ("ankara", "Europe/Istanbul"),
+    ("izmir", "Europe/Istanbul"),
+    ("kyiv", "Europe/Kyiv"),
+    ("kiev", "Europe/Kyiv"),
+    ("odesa", "Europe/Kyiv"),
+    ("lviv", "Europe/Kyiv"),
+    ("minsk", "Europe/Minsk"),
+    ("moscow", "Europe/Moscow"),
+    ("saint petersburg", "Europe/Moscow"),
+    ("st petersburg", "Europe/Moscow"),
+    ("kazan", "Europe/Moscow"),
+    ("samara", "Europe/Samara"),
+    ("yekaterinburg", "Asia/Yekaterinburg"),
+    ("novosibirsk", "Asia/Novosibirsk"),
+    ("krasnoyarsk", "Asia/Krasnoyarsk"),
+    ("irkutsk", "Asia/Irkutsk"),
+    ("vladivostok", "Asia/Vladivostok"),
+    // Middle East
+    ("tel aviv", "Asia/Jerusalem"),
+    ("jerusalem", "Asia/Jerusalem"),
+    ("beirut", "Asia/Beirut"),
+    ("damascus", "Asia/Damascus"),
+    ("amman", "Asia/Amman"),
+    ("baghdad", "Asia/Baghdad"),
+    ("kuwait city", "Asia/Kuwait"),
+    ("riyadh", "Asia/Riyadh"),
+    ("jeddah", "Asia/Riyadh"),
+    ("mecca", "Asia/Riyadh"),
+    ("doha", "Asia/Qatar"),
+    ("manama", "Asia/Bahrain"),
+    ("dubai", "Asia/Dubai"),
+    ("abu dhabi", "Asia/Dubai"),
+    ("muscat", "Asia/Muscat"),
+    ("tehran", "Asia/Tehran"),
+    ("baku", "Asia/Baku"),
+    ("tbilisi", "Asia/Tbilisi"),
+    ("yerevan", "Asia/Yerevan"),
+    // Africa
+    ("cairo", "Africa/Cairo"),
+    ("alexandria", "Africa/Cairo"),
+    ("tripoli", "Africa/Tripoli"),
+    ("tunis", "Africa/Tunis"),
+    ("algiers", "Africa/Algiers"),
+    ("casablanca", "Africa/Casablanca"),
+    ("rabat", "Africa/Casablanca"),
+    ("marrakesh", "Africa/Casablanca"),
+    ("dakar", "Africa/Dakar"),
+    ("abidjan", "Africa/Abidjan"),
+    ("accra", "Africa/Accra"),
+    ("lagos", "Africa/Lagos"),
+    ("abuja", "Africa/Lagos"),
+    ("kinshasa", "Africa/Kinshasa"),
+    ("luanda", "Africa/Luanda"),
+    ("khartoum", "Africa/Khartoum"),
+    ("addis ababa", "Africa/Addis_Ababa"),
+    ("nairobi", "Africa/Nairobi"),
+    ("kampala", "Africa/Kampala"),
+    ("dar es salaam", "Africa/Dar_es_Salaam"),
+    ("kigali", "Africa/Kigali"),
+    ("lusaka", "Africa/Lusaka"),
+    ("harare", "Africa/Harare"),
+    ("maputo", "Africa/Maputo"),
+    ("johannesburg", "Africa/Johannesburg"),
+    ("pretoria", "Africa/Johannesburg"),
+    ("cape town", "Africa/Johannesburg"),
+    ("durban", "Africa/Johannesburg"),
+    ("windhoek", "Africa/Windhoek"),
+    ("antananarivo", "Indian/Antananarivo"),
+    ("port louis", "Indian/Mauritius"),
+    // South and Central Asia
+    ("karachi", "Asia/Karachi"),
+    ("lahore", "Asia/Karachi"),
+    ("islamabad", "Asia/Karachi"),
+    ("kabul", "Asia/Kabul"),
+    ("tashkent", "Asia/Tashkent"),
+    ("almaty", "Asia/Almaty"),
+    ("astana", "Asia/Almaty"),
+    ("bishkek", "Asia/Bishkek"),
+    ("dushanbe", "Asia/Dushanbe"),
+    ("ashgabat", "Asia/Ashgabat"),
+    ("delhi", "Asia/Kolkata"),
+    ("new delhi", "Asia/Kolkata"),
+    ("mumbai", "Asia/Kolkata"),
+    ("bombay", "Asia/Kolkata"),
+    ("kolkata", "Asia/Kolkata"),
+    ("calcutta", "Asia/Kolkata"),
+    ("chennai", "Asia/Kolkata"),
+    ("madras", "Asia/Kolkata"),
+    ("bangalore", "Asia/Kolkata"),
+    ("bengaluru", "Asia/Kolkata"),
+    ("hyderabad", "Asia/Kolkata"),
+    ("pune", "Asia/Kolkata"),
+    ("ahmedabad", "Asia/Kolkata"),
+    ("jaipur", "Asia/Kolkata"),
+    ("kathmandu", "Asia/Kathmandu"),
+    ("thimphu", "Asia/Thimphu"),
+    ("dhaka", "Asia/Dhaka"),
+    ("colombo", "Asia/Colombo"),
+    ("male", "Indian/Maldives"),
+    // East and Southeast Asia
+    ("yangon", "Asia/Yangon"),
+    ("bangkok", "Asia/Bangkok"),
+    ("chiang mai", "Asia/Bangkok"),
+    ("phnom penh", "Asia/Phnom_Penh"),
+    ("vientiane", "Asia/Vientiane"),
+    ("hanoi", "Asia/Ho_Chi_Minh"),
+    ("ho chi minh city", "Asia/Ho_Chi_Minh"),
+    ("saigon", "Asia/Ho_Chi_Minh"),
+    ("kuala lumpur", "Asia/Kuala_Lumpur"),
+    ("singapore", "Asia/Singapore"),
+    ("jakarta", "Asia/Jakarta"),
+    ("surabaya", "Asia/Jakarta"),
+    ("bali", "Asia/Makassar"),
+    ("denpasar", "Asia/Makassar"),
+    ("manila", "Asia/Manila"),
+    ("cebu", "Asia/Manila"),
+    ("hong kong", "Asia/Hong_Kong"),
+    ("macau", "Asia/Macau"),
+    ("taipei", "Asia/Taipei"),
+    ("beijing", "Asia/Shanghai"),
+    ("shanghai", "Asia/Shanghai"),
+    ("shenzhen", "Asia/Shanghai"),
+    ("guangzhou", "Asia/Shanghai"),
+    ("chengdu", "Asia/Shanghai"),
+    ("chongqing", "Asia/Shanghai"),
+    ("wuhan", "Asia/Shanghai"),
+    ("hangzhou", "Asia/Shanghai"),
+    ("nanjing", "Asia/Shanghai"),
+    ("tianjin", "Asia/Shanghai"),
+    ("xian", "Asia/Shanghai"),
+    ("urumqi", "Asia/Urumqi"),
+    ("ulaanbaatar", "Asia/Ulaanbaatar"),
+    ("seoul", "Asia/Seoul"),
+    ("busan", "Asia/Seoul"),
+    ("pyongyang", "Asia/Pyongyang"),
+    ("tokyo", "Asia/Tokyo"),
+    ("osaka", "Asia/Tokyo"),
+    ("kyoto", "Asia/Tokyo"),
+    ("yokohama", "Asia/Tokyo"),
+    ("nagoya", "Asia/Tokyo"),
+    ("sapporo", "Asia/Tokyo"),
+    ("fukuoka", "Asia/Tokyo"),
+    // Oceania
+    ("sydney", "Australia/Sydney"),
+    ("canberra", "Australia/Sydney"),
+    ("melbourne", "Australia/Melbourne"),
+    ("brisbane", "Australia/Brisbane"),
+    ("gold coast", "Australia/Brisbane"),
+    ("adelaide", "Australia/Adelaide"),
+    ("darwin", "Australia/Darwin"),
+    ("perth", "Australia/Perth"),
+    ("hobart", "Australia/Hobart"),
+    ("auckland", "Pacific/Auckland"),
+    ("wellington", "Pacific/Auckland"),
+    ("christchurch", "Pacific/Auckland"),
+    ("suva", "Pacific/Fiji"),
+    ("port moresby", "Pacific/Port_Moresby"),
+    ("noumea", "Pacific/Noumea"),
+    ("apia", "Pacific/Apia"),
+    ("tahiti", "Pacific/Tahiti"),
+    ("guam", "Pacific/Guam"),
+];
@@ -1,27 +1,72 @@
 /// Clipboard history provider for tracking and searching clipboard content
 ///
-/// This provider monitors the system clipboard and maintains a history of
-/// the last 20 clipboard items, allowing users to search and restore
-/// previously copied content.
+/// This provider monitors the system clipboard and maintains a configurable
+/// history of copied items in SQLite, allowing users to search and restore
+/// previously copied content. `clip:` queries accept filters such as
+/// `today`, `yesterday`, `url` or `>100` alongside the search text.
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::search::provider_config::{ConfigOption, ProviderConfig};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature, SECONDARY_ACTIONS_KEY};
+use crate::types::{CacheStats, ResultAction, ResultType, SearchResult};
+use crate::utils::db_integrity::{self, CheckPolicy, IntegrityOutcome};
+use crate::utils::{normalize_for_search, structured_text, time, write_json_export};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
-/// Maximum number of clipboard items to store
-const MAX_CLIPBOARD_ITEMS: usize = 20;
+/// Default number of clipboard items to store
+pub const DEFAULT_CLIPBOARD_ITEMS: usize = 100;
+
+/// Upper bound for the configurable history size
+pub const MAX_CLIPBOARD_ITEMS: usize = 500;
+
+/// Options under `provider_settings.clipboard`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClipboardConfig {
+    /// Number of items the history keeps
+    pub max_items: usize,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self { max_items: DEFAULT_CLIPBOARD_ITEMS }
+    }
+}
+
+impl ProviderConfig for ClipboardConfig {
+    const KEY: &'static str = "clipboard";
+    const PROVIDER: &'static str = "Clipboard History";
+    const OPTIONS: &'static [ConfigOption] = &[ConfigOption {
+        field: "max_items",
+        description: "Number of copied items to keep",
+        min: 1,
+        max: MAX_CLIPBOARD_ITEMS as u64,
+    }];
+}
+
+/// How long to wait for a locked database before giving up
+const DB_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Maximum preview length for clipboard content
 const MAX_PREVIEW_LENGTH: usize = 100;
 
+/// Lines of pretty-printed JSON or XML shown under a result
+const FORMATTED_PREVIEW_LINES: usize = 8;
+
+/// Identifier of the "Copy formatted" secondary action
+pub const COPY_FORMATTED_ACTION_ID: &str = "copy_formatted";
+
 /// Represents a single clipboard item
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardItem {
@@ -33,6 +78,15 @@ pub struct ClipboardItem {
     pub timestamp: DateTime<Utc>,
     /// Type of clipboard content
     pub content_type: ClipboardContentType,
+    /// What the text looks like, detected at capture time
+    #[serde(default)]
+    pub kind: ClipboardKind,
+    /// Normalized content used for matching; rebuilt after loading from disk
+    #[serde(skip)]
+    normalized_content: String,
+    /// First lines of the pretty-printed content, for JSON and XML items
+    #[serde(skip)]
+    formatted_preview: Option<String>,
 }
 
 /// Types of clipboard content
@@ -42,20 +96,267 @@ pub enum ClipboardContentType {
     // Future: Image, File, etc.
 }
 
+/// Heuristic classification of clipboard text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardKind {
+    Url,
+    Email,
+    Number,
+    Json,
+    Xml,
+    /// Several URLs, one per line
+    #[serde(rename = "urls")]
+    UrlList,
+    /// A markdown table
+    Table,
+    Code,
+    #[default]
+    Text,
+}
+
+impl ClipboardKind {
+    /// All kinds, in the order they are checked
+    const ALL: [ClipboardKind; 9] = [
+        ClipboardKind::Url,
+        ClipboardKind::Email,
+        ClipboardKind::Number,
+        ClipboardKind::Json,
+        ClipboardKind::Xml,
+        ClipboardKind::UrlList,
+        ClipboardKind::Table,
+        ClipboardKind::Code,
+        ClipboardKind::Text,
+    ];
+
+    /// Detects the kind of a piece of clipboard text
+    pub fn detect(content: &str) -> Self {
+        let text = content.trim();
+
+        if Self::looks_like_url(text) {
+            ClipboardKind::Url
+        } else if Self::looks_like_email(text) {
+            ClipboardKind::Email
+        } else if Self::looks_like_number(text) {
+            ClipboardKind::Number
+        } else if structured_text::pretty_json(text).is_some() {
+            ClipboardKind::Json
+        } else if structured_text::pretty_xml(text).is_some() {
+            ClipboardKind::Xml
+        } else if Self::looks_like_url_list(text) {
+            ClipboardKind::UrlList
+        } else if Self::looks_like_table(text) {
+            ClipboardKind::Table
+        } else if Self::looks_like_code(text) {
+            ClipboardKind::Code
+        } else {
+            ClipboardKind::Text
+        }
+    }
+
+    /// Returns the keyword used for this kind in queries and storage
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClipboardKind::Url => "url",
+            ClipboardKind::Email => "email",
+            ClipboardKind::Number => "number",
+            ClipboardKind::Json => "json",
+            ClipboardKind::Xml => "xml",
+            ClipboardKind::UrlList => "urls",
+            ClipboardKind::Table => "table",
+            ClipboardKind::Code => "code",
+            ClipboardKind::Text => "text",
+        }
+    }
+
+    /// Parses a kind keyword
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == keyword)
+    }
+
+    fn looks_like_url(text: &str) -> bool {
+        let lower = text.to_ascii_lowercase();
+        !text.contains(char::is_whitespace)
+            && ["http://", "https://", "ftp://", "www."]
+                .iter()
+                .any(|prefix| lower.starts_with(prefix) && lower.len() > prefix.len())
+    }
+
+    fn looks_like_email(text: &str) -> bool {
+        if text.contains(char::is_whitespace) {
+            return false;
+        }
+
+        match text.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+            }
+            None => false,
+        }
+    }
+
+    fn looks_like_url_list(text: &str) -> bool {
+        let lines: Vec<&str> = text.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        lines.len() >= 2 && lines.iter().all(|line| Self::looks_like_url(line))
+    }
+
+    /// A header row, a `|---|` separator row and any number of body rows
+    fn looks_like_table(text: &str) -> bool {
+        let lines: Vec<&str> = text.lines().map(str::trim).collect();
+        lines.len() >= 2
+            && lines
+                .iter()
+                .all(|line| line.len() >= 2 && line.starts_with('|') && line.ends_with('|'))
+            && lines[1].contains('-')
+            && lines[1].chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+    }
+
+    fn looks_like_number(text: &str) -> bool {
+        let digits: String = text.chars().filter(|c| *c != ',' && *c != '_').collect();
+        !digits.is_empty() && digits.parse::<f64>().is_ok() && digits.chars().any(|c| c.is_ascii_digit())
+    }
+
+    fn looks_like_code(text: &str) -> bool {
+        const SIGNALS: &[&str] = &[
+            "{", "}", ";", "=>", "->", "()", "</", "==", "fn ", "def ", "function ", "import ",
+            "#include", "const ", "let ", "return ",
+        ];
+
+        SIGNALS.iter().filter(|signal| text.contains(*signal)).count() >= 2
+    }
+}
+
+/// A single `clip:` filter term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardFilter {
+    /// Copied today (local time)
+    Today,
+    /// Copied yesterday (local time)
+    Yesterday,
+    /// Content of the given kind
+    Kind(ClipboardKind),
+    /// Content longer than N characters (`>N`)
+    LongerThan(usize),
+    /// Content shorter than N characters (`<N`)
+    ShorterThan(usize),
+}
+
+impl ClipboardFilter {
+    /// Parses a single query token into a filter
+    fn parse(token: &str) -> Option<Self> {
+        let token = token.to_lowercase();
+
+        if let Some(n) = token.strip_prefix('>') {
+            return n.parse().ok().map(ClipboardFilter::LongerThan);
+        }
+        if let Some(n) = token.strip_prefix('<') {
+            return n.parse().ok().map(ClipboardFilter::ShorterThan);
+        }
+
+        match token.as_str() {
+            "today" => Some(ClipboardFilter::Today),
+            "yesterday" => Some(ClipboardFilter::Yesterday),
+            keyword => ClipboardKind::from_keyword(keyword).map(ClipboardFilter::Kind),
+        }
+    }
+
+    /// Checks an item against this filter; `today` is the current local date
+    fn matches(&self, item: &ClipboardItem, today: NaiveDate) -> bool {
+        let copied_on = item.timestamp.with_timezone(&Local).date_naive();
+
+        match self {
+            ClipboardFilter::Today => copied_on == today,
+            ClipboardFilter::Yesterday => today.pred_opt() == Some(copied_on),
+            ClipboardFilter::Kind(kind) => item.kind == *kind,
+            ClipboardFilter::LongerThan(n) => item.content.chars().count() > *n,
+            ClipboardFilter::ShorterThan(n) => item.content.chars().count() < *n,
+        }
+    }
+}
+
+/// A parsed `clip:` query: free text plus filter terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardQuery {
+    /// Normalized text that must appear in the content
+    pub text: String,
+    /// Filters that every match must satisfy
+    pub filters: Vec<ClipboardFilter>,
+}
+
+impl ClipboardQuery {
+    /// Splits a query into filter terms and search text
+    pub fn parse(query: &str) -> Self {
+        let mut words = Vec::new();
+        let mut filters = Vec::new();
+
+        for token in query.split_whitespace() {
+            match ClipboardFilter::parse(token) {
+                Some(filter) => filters.push(filter),
+                None => words.push(token),
+            }
+        }
+
+        Self {
+            text: normalize_for_search(&words.join(" ")),
+            filters,
+        }
+    }
+
+    /// Applies the text match first, then the filters
+    fn matches(&self, item: &ClipboardItem, today: NaiveDate) -> bool {
+        item.normalized_content.contains(&self.text)
+            && self.filters.iter().all(|filter| filter.matches(item, today))
+    }
+}
+
 impl ClipboardItem {
     /// Creates a new clipboard item
     pub fn new(content: String) -> Self {
         let timestamp = Utc::now();
         let id = format!("clipboard:{}", timestamp.timestamp_millis());
+        let kind = ClipboardKind::detect(&content);
+
+        Self::from_parts(id, content, timestamp, kind)
+    }
+
+    /// Builds an item from stored fields, recomputing derived data
+    fn from_parts(id: String, content: String, timestamp: DateTime<Utc>, kind: ClipboardKind) -> Self {
+        let normalized_content = normalize_for_search(&content);
+        let formatted_preview = Self::format_content(&content, kind)
+            .map(|formatted| structured_text::preview_lines(&formatted, FORMATTED_PREVIEW_LINES));
         
         Self {
             id,
             content,
             timestamp,
             content_type: ClipboardContentType::Text,
+            kind,
+            normalized_content,
+            formatted_preview,
+        }
+    }
+
+    /// Pretty-prints JSON and XML content; other kinds have no formatted form
+    ///
+    /// Content that no longer parses, e.g. a stored kind from an older
+    /// detector, yields `None` rather than an error.
+    fn format_content(content: &str, kind: ClipboardKind) -> Option<String> {
+        match kind {
+            ClipboardKind::Json => structured_text::pretty_json(content),
+            ClipboardKind::Xml => structured_text::pretty_xml(content),
+            _ => None,
         }
     }
 
+    /// Returns the pretty-printed content, if the item is JSON or XML
+    pub fn formatted(&self) -> Option<String> {
+        Self::format_content(&self.content, self.kind)
+    }
+
     /// Returns a preview of the clipboard content
     pub fn preview(&self) -> String {
         let content = self.content.trim();
@@ -69,27 +370,61 @@ impl ClipboardItem {
 
     /// Returns a formatted timestamp
     pub fn formatted_timestamp(&self) -> String {
-        let now = Utc::now();
-        let duration = now.signed_duration_since(self.timestamp);
-
-        if duration.num_seconds() < 60 {
-            "Just now".to_string()
-        } else if duration.num_minutes() < 60 {
-            format!("{} min ago", duration.num_minutes())
-        } else if duration.num_hours() < 24 {
-            format!("{} hours ago", duration.num_hours())
-        } else {
-            format!("{} days ago", duration.num_days())
-        }
+        time::format_relative(self.timestamp, Utc::now(), time::Locale::system())
+    }
+}
+
+/// Clipboard format marking content clipboard managers should not record
+pub const EXCLUDE_FROM_MONITORING_FORMAT: &str = "ExcludeClipboardContentFromMonitorProcessing";
+
+/// Number of live `HistoryPause` guards
+static HISTORY_PAUSES: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps the clipboard monitor from recording anything while it's alive
+///
+/// Taken while the app puts something on the clipboard only to read it back
+/// (e.g. copying the selection for quick math), so neither that content nor
+/// the restored previous content ends up in the history.
+pub struct HistoryPause(());
+
+impl HistoryPause {
+    /// Pauses the history until the guard is dropped
+    pub fn new() -> Self {
+        HISTORY_PAUSES.fetch_add(1, Ordering::SeqCst);
+        Self(())
+    }
+
+    /// Whether a `HistoryPause` is alive
+    pub fn is_active() -> bool {
+        HISTORY_PAUSES.load(Ordering::SeqCst) > 0
+    }
+}
+
+impl Default for HistoryPause {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HistoryPause {
+    fn drop(&mut self) {
+        HISTORY_PAUSES.fetch_sub(1, Ordering::SeqCst);
     }
 }
 
+/// Callback run with the new clipboard text
+type ChangeCallback = Arc<dyn Fn(String) + Send + Sync>;
+
 /// Clipboard monitor that watches for clipboard changes
 pub struct ClipboardMonitor {
     /// Last known clipboard content
     last_content: Arc<RwLock<Option<String>>>,
     /// Whether the monitor is running
     is_running: Arc<RwLock<bool>>,
+    /// Polling task, checked after a resume in case it died
+    task: std::sync::Mutex<Option<JoinHandle<()>>>,
+    /// Callback passed to `start`, kept to restart polling
+    on_change: std::sync::Mutex<Option<ChangeCallback>>,
 }
 
 impl ClipboardMonitor {
@@ -98,6 +433,8 @@ impl ClipboardMonitor {
         Self {
             last_content: Arc::new(RwLock::new(None)),
             is_running: Arc::new(RwLock::new(false)),
+            task: std::sync::Mutex::new(None),
+            on_change: std::sync::Mutex::new(None),
         }
     }
 
@@ -117,11 +454,56 @@ impl ClipboardMonitor {
 
         info!("Starting clipboard monitor");
 
+        let on_change: ChangeCallback = Arc::new(on_change);
+        *self.on_change.lock().unwrap_or_else(|e| e.into_inner()) = Some(Arc::clone(&on_change));
+        self.spawn_polling(on_change);
+
+        Ok(())
+    }
+
+    /// Restarts polling if the task died while the monitor should be running
+    ///
+    /// Returns whether the monitor was restarted.
+    pub async fn ensure_running(&self) -> bool {
+        if !*self.is_running.read().await {
+            return false;
+        }
+
+        let alive = self
+            .task
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_some_and(|task| !task.is_finished());
+        if alive {
+            return false;
+        }
+
+        let on_change = self.on_change.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        match on_change {
+            Some(on_change) => {
+                warn!("Clipboard monitor task is gone, restarting it");
+                self.spawn_polling(on_change);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Polls the clipboard until the monitor is stopped
+    fn spawn_polling(&self, on_change: ChangeCallback) {
         let last_content = Arc::clone(&self.last_content);
         let is_running = Arc::clone(&self.is_running);
 
-        tokio::spawn(async move {
+        let task = tokio::spawn(async move {
             while *is_running.read().await {
+                // Content seen while paused is never recorded; `last_content`
+                // is left alone so the restored content isn't new afterwards
+                if HistoryPause::is_active() {
+                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                    continue;
+                }
+
                 // Check clipboard content
                 match Self::get_clipboard_text().await {
                     Ok(Some(content)) => {
@@ -150,8 +532,7 @@ impl ClipboardMonitor {
 
             info!("Clipboard monitor stopped");
         });
-
-        Ok(())
+        *self.task.lock().unwrap_or_else(|e| e.into_inner()) = Some(task);
     }
 
     /// Stops monitoring the clipboard
@@ -184,6 +565,14 @@ impl ClipboardMonitor {
                     return Ok(None);
                 }
 
+                // Skip content its owner asked clipboard managers to ignore
+                let exclude: Vec<u16> = EXCLUDE_FROM_MONITORING_FORMAT.encode_utf16().chain(std::iter::once(0)).collect();
+                let exclude_format = RegisterClipboardFormatW(windows::core::PCWSTR(exclude.as_ptr()));
+                if exclude_format != 0 && IsClipboardFormatAvailable(exclude_format).is_ok() {
+                    CloseClipboard().ok();
+                    return Ok(None);
+                }
+
                 // Get clipboard data
                 let handle = GetClipboardData(CF_UNICODETEXT);
                 if handle.is_err() {
@@ -244,33 +633,92 @@ impl Default for ClipboardMonitor {
     }
 }
 
-/// Storage for clipboard history with encryption
+/// SQLite storage for clipboard history
+///
+/// Each copy appends one row, so large histories don't rewrite the whole
+/// store. A legacy `clipboard_history.json` is imported once on first use.
+#[derive(Clone)]
 pub struct ClipboardStorage {
-    /// Path to the storage file
-    storage_path: PathBuf,
+    /// Path to the SQLite database
+    db_path: PathBuf,
+    /// Path to the JSON file used by earlier versions
+    legacy_path: PathBuf,
 }
 
 impl ClipboardStorage {
     /// Creates a new clipboard storage
     pub fn new() -> Result<Self> {
-        let storage_path = Self::get_storage_path()?;
-        
+        Self::with_paths(Self::get_db_path()?, Self::get_legacy_path()?)
+    }
+
+    /// Creates storage at explicit paths, importing the legacy file if present
+    fn with_paths(db_path: PathBuf, legacy_path: PathBuf) -> Result<Self> {
         // Ensure the directory exists
-        if let Some(parent) = storage_path.parent() {
+        if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        Ok(Self { storage_path })
+        let storage = Self { db_path, legacy_path };
+        storage.initialize_db()?;
+
+        match storage.migrate_legacy_json() {
+            Ok(0) => {}
+            Ok(count) => info!("Imported {} clipboard items from legacy JSON history", count),
+            Err(e) => warn!("Failed to import legacy clipboard history: {}", e),
+        }
+
+        Ok(storage)
+    }
+
+    /// Opens a database connection that waits briefly on locks
+    fn open(db_path: &Path) -> Result<Connection> {
+        let conn = Connection::open(db_path)?;
+        db_integrity::configure_durability(&conn)?;
+        conn.busy_timeout(DB_BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    /// Checks the database for damage, rebuilding it from its readable rows
+    pub fn check_integrity(&self, now: DateTime<Utc>, policy: CheckPolicy) -> Result<IntegrityOutcome> {
+        let outcome = db_integrity::check_database(&self.db_path, now, policy)?;
+
+        // The damaged schema may not have survived
+        if matches!(outcome, IntegrityOutcome::Recovered(_)) {
+            self.initialize_db()?;
+        }
+        Ok(outcome)
+    }
+
+    /// Copies the database's write-ahead log into it, see `db_integrity::checkpoint`
+    pub fn checkpoint(&self) -> Result<()> {
+        db_integrity::checkpoint(&Self::open(&self.db_path)?)
+    }
+
+    /// Gets the database file path
+    fn get_db_path() -> Result<PathBuf> {
+        Ok(Self::get_data_dir()?.join(if cfg!(test) {
+            "clipboard_history_test.db"
+        } else {
+            "clipboard_history.db"
+        }))
+    }
+
+    /// Gets the path of the JSON history written by earlier versions
+    fn get_legacy_path() -> Result<PathBuf> {
+        Ok(Self::get_data_dir()?.join(if cfg!(test) {
+            "clipboard_history_test.json"
+        } else {
+            "clipboard_history.json"
+        }))
     }
 
-    /// Gets the storage file path
-    fn get_storage_path() -> Result<PathBuf> {
+    /// Gets the BetterFinder data directory
+    fn get_data_dir() -> Result<PathBuf> {
         #[cfg(test)]
         {
             // Use temp directory for tests
             let mut path = std::env::temp_dir();
             path.push("BetterFinder");
-            path.push("clipboard_history_test.json");
             return Ok(path);
         }
         
@@ -281,28 +729,95 @@ impl ClipboardStorage {
             
             let mut path = PathBuf::from(app_data);
             path.push("BetterFinder");
-            path.push("clipboard_history.json");
             
             Ok(path)
         }
     }
 
-    /// Loads clipboard history from disk
-    pub async fn load(&self) -> Result<VecDeque<ClipboardItem>> {
-        let path = self.storage_path.clone();
+    /// Initializes the database schema
+    fn initialize_db(&self) -> Result<()> {
+        let conn = Self::open(&self.db_path)?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS clipboard_history (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                kind TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    /// Imports the legacy JSON history once, then renames the file
+    fn migrate_legacy_json(&self) -> Result<usize> {
+        if !self.legacy_path.exists() {
+            return Ok(0);
+        }
+
+        let content = std::fs::read_to_string(&self.legacy_path)?;
+        let items: Vec<ClipboardItem> = serde_json::from_str(&content)?;
+
+        let mut conn = Self::open(&self.db_path)?;
+        let tx = conn.transaction()?;
+
+        // The JSON file is newest first; insert oldest first so sequence order matches
+        for item in items.iter().rev() {
+            let kind = ClipboardKind::detect(&item.content);
+            Self::insert(&tx, item, kind)?;
+        }
+        tx.commit()?;
+
+        let mut migrated = self.legacy_path.clone().into_os_string();
+        migrated.push(".migrated");
+        std::fs::rename(&self.legacy_path, migrated)?;
+
+        Ok(items.len())
+    }
+
+    /// Inserts a single item row
+    fn insert(conn: &Connection, item: &ClipboardItem, kind: ClipboardKind) -> Result<()> {
+        conn.execute(
+            "INSERT INTO clipboard_history (id, content, timestamp, kind) VALUES (?1, ?2, ?3, ?4)",
+            params![item.id, item.content, item.timestamp.to_rfc3339(), kind.as_str()],
+        )?;
+        Ok(())
+    }
+
+    /// Loads up to `limit` items from disk, newest first
+    pub async fn load(&self, limit: usize) -> Result<VecDeque<ClipboardItem>> {
+        let db_path = self.db_path.clone();
         
         tokio::task::spawn_blocking(move || {
-            if !path.exists() {
-                return Ok(VecDeque::new());
-            }
-
-            let content = std::fs::read_to_string(&path)?;
-            
-            // For now, store as plain JSON
-            // TODO: Add encryption in future
-            let items: Vec<ClipboardItem> = serde_json::from_str(&content)?;
-            
-            Ok(items.into_iter().collect())
+            let conn = Self::open(&db_path)?;
+
+            let mut stmt = conn.prepare(
+                "SELECT id, content, timestamp, kind
+                 FROM clipboard_history
+                 ORDER BY seq DESC
+                 LIMIT ?1",
+            )?;
+
+            let items = stmt
+                .query_map(params![limit], |row| {
+                    let id: String = row.get(0)?;
+                    let content: String = row.get(1)?;
+                    let timestamp: String = row.get(2)?;
+                    let kind: String = row.get(3)?;
+
+                    let timestamp = DateTime::parse_from_rfc3339(&timestamp)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now());
+                    let kind = ClipboardKind::from_keyword(&kind).unwrap_or_default();
+
+                    Ok(ClipboardItem::from_parts(id, content, timestamp, kind))
+                })?
+                .collect::<std::result::Result<VecDeque<_>, _>>()?;
+
+            Ok(items)
         })
         .await
         .map_err(|e| {
@@ -310,30 +825,67 @@ impl ClipboardStorage {
         })?
     }
 
-    /// Saves clipboard history to disk
+    /// Appends one item and drops rows beyond `max_items`
+    pub async fn append(&self, item: &ClipboardItem, max_items: usize) -> Result<()> {
+        let db_path = self.db_path.clone();
+        let item = item.clone();
+        
+        tokio::task::spawn_blocking(move || {
+            let conn = Self::open(&db_path)?;
+
+            Self::insert(&conn, &item, item.kind)?;
+
+            conn.execute(
+                "DELETE FROM clipboard_history
+                 WHERE seq NOT IN (
+                     SELECT seq FROM clipboard_history
+                     ORDER BY seq DESC
+                     LIMIT ?1
+                 )",
+                params![max_items],
+            )?;
+
+            Ok::<(), LauncherError>(())
+        })
+        .await
+        .map_err(|e| {
+            LauncherError::ExecutionError(format!("Failed to spawn append task: {}", e))
+        })??;
+
+        Ok(())
+    }
+
+    /// Replaces the stored history with `items` (newest first)
     pub async fn save(&self, items: &VecDeque<ClipboardItem>) -> Result<()> {
-        let path = self.storage_path.clone();
+        let db_path = self.db_path.clone();
         let items_vec: Vec<ClipboardItem> = items.iter().cloned().collect();
         
         tokio::task::spawn_blocking(move || {
-            // For now, store as plain JSON
-            // TODO: Add encryption in future
-            let content = serde_json::to_string_pretty(&items_vec)?;
-            std::fs::write(&path, content)?;
+            let mut conn = Self::open(&db_path)?;
+            let tx = conn.transaction()?;
+
+            tx.execute("DELETE FROM clipboard_history", [])?;
+            for item in items_vec.iter().rev() {
+                Self::insert(&tx, item, item.kind)?;
+            }
+            tx.commit()?;
             
-            Ok(())
+            Ok::<(), LauncherError>(())
         })
         .await
         .map_err(|e| {
             LauncherError::ExecutionError(format!("Failed to spawn save task: {}", e))
-        })?
+        })??;
+
+        Ok(())
     }
 }
 
 impl Default for ClipboardStorage {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
-            storage_path: PathBuf::from("clipboard_history.json"),
+            db_path: PathBuf::from("clipboard_history.db"),
+            legacy_path: PathBuf::from("clipboard_history.json"),
         })
     }
 }
@@ -342,8 +894,8 @@ impl Default for ClipboardStorage {
 pub struct ClipboardHistoryProvider {
     /// Clipboard history storage
     history: Arc<RwLock<VecDeque<ClipboardItem>>>,
-    /// Maximum number of items to store
-    max_items: usize,
+    /// Maximum number of items to store, shared with the clipboard monitor
+    max_items: Arc<AtomicUsize>,
     /// Storage backend
     storage: ClipboardStorage,
     /// Clipboard monitor
@@ -362,16 +914,143 @@ impl ClipboardHistoryProvider {
 
         Ok(Self {
             history: Arc::new(RwLock::new(VecDeque::new())),
-            max_items: MAX_CLIPBOARD_ITEMS,
+            max_items: Arc::new(AtomicUsize::new(DEFAULT_CLIPBOARD_ITEMS)),
             storage,
             monitor,
             enabled: true,
         })
     }
 
+    /// Sets how many items the history keeps, clamped to `1..=MAX_CLIPBOARD_ITEMS`
+    pub fn with_max_items(self, max_items: usize) -> Self {
+        self.max_items.store(max_items.clamp(1, MAX_CLIPBOARD_ITEMS), Ordering::Relaxed);
+        self
+    }
+
+    /// Returns how many items the history keeps
+    pub fn max_items(&self) -> usize {
+        self.max_items.load(Ordering::Relaxed)
+    }
+
+    /// Writes the history to a JSON file, without the copied text when `metadata_only`
+    pub async fn export(&self, path: &Path, metadata_only: bool) -> Result<usize> {
+        let history = self.history.read().await;
+        write_json_export(path, &Self::export_document(&history, metadata_only, Utc::now()))?;
+
+        info!("Exported {} clipboard items to {}", history.len(), path.display());
+        Ok(history.len())
+    }
+
+    /// Builds the export file contents, newest item first
+    fn export_document(
+        items: &VecDeque<ClipboardItem>,
+        metadata_only: bool,
+        exported_at: DateTime<Utc>,
+    ) -> serde_json::Value {
+        let items: Vec<serde_json::Value> = items
+            .iter()
+            .map(|item| {
+                let mut entry = serde_json::json!({
+                    "id": item.id,
+                    "timestamp": item.timestamp,
+                    "kind": item.kind,
+                    "length": item.content.chars().count(),
+                });
+                if !metadata_only {
+                    entry["content"] = serde_json::json!(item.content);
+                }
+                entry
+            })
+            .collect();
+
+        serde_json::json!({
+            "exported_at": exported_at,
+            "metadata_only": metadata_only,
+            "items": items,
+        })
+    }
+
+    /// Copies the pretty-printed form of a JSON or XML item
+    ///
+    /// The history keeps the original text; only the clipboard receives the
+    /// formatted copy.
+    pub async fn copy_formatted(&self, id: &str) -> Result<()> {
+        let formatted = {
+            let history = self.history.read().await;
+            let item = history
+                .iter()
+                .find(|item| item.id == id)
+                .ok_or_else(|| LauncherError::ExecutionError(format!("Clipboard item not found: {}", id)))?;
+            item.formatted().ok_or_else(|| {
+                LauncherError::ExecutionError(format!("Clipboard item {} has no formatted form", id))
+            })?
+        };
+
+        Self::copy_to_clipboard(&formatted).await
+    }
+
+    /// Deletes every item, or only those copied more than `older_than` ago
+    ///
+    /// The history lock is held across the disk write so copies made
+    /// meanwhile wait, and memory only changes once the database has.
+    pub async fn clear(&self, older_than: Option<Duration>) -> Result<usize> {
+        let mut history = self.history.write().await;
+        let now = Utc::now();
+
+        let kept: VecDeque<ClipboardItem> = history
+            .iter()
+            .filter(|item| !time::is_older_than(item.timestamp, older_than, now))
+            .cloned()
+            .collect();
+        let removed = history.len() - kept.len();
+
+        self.storage.save(&kept).await?;
+        *history = kept;
+
+        info!("Cleared {} clipboard items", removed);
+        Ok(removed)
+    }
+
+    /// Checks the history database for damage, see `ClipboardStorage::check_integrity`
+    ///
+    /// The history lock is held so no copy is written while the file may be
+    /// replaced. Memory keeps the full history either way.
+    pub async fn check_integrity(&self, now: DateTime<Utc>, policy: CheckPolicy) -> Result<IntegrityOutcome> {
+        let _history = self.history.write().await;
+        let storage = self.storage.clone();
+
+        tokio::task::spawn_blocking(move || storage.check_integrity(now, policy))
+            .await
+            .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn integrity check: {}", e)))?
+    }
+
+    /// Stops recording copies, waits for those being written and checkpoints the database
+    ///
+    /// Called before the app exits. Each copy is stored as it's recorded, so
+    /// only one still being written could be lost when the process is killed.
+    pub async fn flush(&self) -> Result<()> {
+        self.monitor.stop().await;
+        let _history = self.history.write().await;
+        let storage = self.storage.clone();
+
+        tokio::task::spawn_blocking(move || storage.checkpoint())
+            .await
+            .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn checkpoint: {}", e)))?
+    }
+
     /// Adds a new clipboard item to history
     async fn add_item(&self, content: String) {
-        let mut history = self.history.write().await;
+        Self::record_item(&self.history, &self.storage, self.max_items(), content).await;
+    }
+
+    /// Pushes copied text onto the history and persists it
+    async fn record_item(
+        history: &RwLock<VecDeque<ClipboardItem>>,
+        storage: &ClipboardStorage,
+        max_items: usize,
+        content: String,
+    ) {
+        let mut history = history.write().await;
         
         // Don't add if it's the same as the most recent item
         if let Some(last) = history.front() {
@@ -386,32 +1065,32 @@ impl ClipboardHistoryProvider {
         }
 
         let item = ClipboardItem::new(content);
-        debug!("Adding clipboard item: {}", item.id);
+        debug!("Adding clipboard item: {} ({})", item.id, item.kind.as_str());
+
+        // Save to disk
+        if let Err(e) = storage.append(&item, max_items).await {
+            error!("Failed to save clipboard history: {}", e);
+        }
         
         // Add to front of queue
         history.push_front(item);
         
         // Remove oldest items if we exceed max
-        while history.len() > self.max_items {
+        while history.len() > max_items {
             history.pop_back();
         }
-
-        // Save to disk
-        if let Err(e) = self.storage.save(&history).await {
-            error!("Failed to save clipboard history: {}", e);
-        }
     }
 
-    /// Searches clipboard history
+    /// Searches clipboard history, applying any filter terms after the text match
     async fn search_history(&self, query: &str) -> Vec<SearchResult> {
         let history = self.history.read().await;
-        let query_lower = query.to_lowercase();
+        let query = ClipboardQuery::parse(query);
+        let today = Local::now().date_naive();
         
         let mut results = Vec::new();
         
         for (index, item) in history.iter().enumerate() {
-            // Search in content
-            if item.content.to_lowercase().contains(&query_lower) {
+            if query.matches(item, today) {
                 let score = 80.0 - (index as f64 * 2.0); // Newer items score higher
                 results.push(self.create_search_result(item, score));
             }
@@ -420,6 +1099,17 @@ impl ClipboardHistoryProvider {
         results
     }
 
+    /// Finds the items holding exactly `text`, give or take surrounding whitespace
+    async fn search_pasted(&self, text: &str) -> Vec<SearchResult> {
+        let history = self.history.read().await;
+        history
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.content.trim() == text)
+            .map(|(index, item)| self.create_search_result(item, 80.0 - (index as f64 * 2.0)))
+            .collect()
+    }
+
     /// Returns recent clipboard items (when query is empty or starts with "clip:")
     async fn get_recent_items(&self, limit: usize) -> Vec<SearchResult> {
         let history = self.history.read().await;
@@ -443,7 +1133,20 @@ impl ClipboardHistoryProvider {
         let mut metadata = HashMap::new();
         metadata.insert("content".to_string(), serde_json::json!(item.content));
         metadata.insert("timestamp".to_string(), serde_json::json!(item.timestamp));
+        time::insert_time_metadata(&mut metadata, item.timestamp);
         metadata.insert("content_type".to_string(), serde_json::json!(item.content_type));
+        metadata.insert("kind".to_string(), serde_json::json!(item.kind));
+        if let Some(formatted_preview) = &item.formatted_preview {
+            metadata.insert("preview".to_string(), serde_json::json!(formatted_preview));
+            metadata.insert(
+                SECONDARY_ACTIONS_KEY.to_string(),
+                serde_json::json!([{
+                    "id": COPY_FORMATTED_ACTION_ID,
+                    "title": "Copy formatted",
+                    "requires_confirmation": false,
+                }]),
+            );
+        }
 
         SearchResult {
             id: item.id.clone(),
@@ -456,6 +1159,9 @@ impl ClipboardHistoryProvider {
             action: ResultAction::CopyToClipboard {
                 content: item.content.clone(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 
@@ -553,8 +1259,12 @@ impl SearchProvider for ClipboardHistoryProvider {
         60 // Medium priority
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let trimmed = query.trim();
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Clipboard])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.full_text().trim();
         
         // Check if query starts with "clip:" prefix
         if let Some(search_query) = trimmed.strip_prefix("clip:") {
@@ -563,6 +1273,9 @@ impl SearchProvider for ClipboardHistoryProvider {
             if search_query.is_empty() {
                 // Show recent items
                 Ok(self.get_recent_items(10).await)
+            } else if search_query.contains('\n') {
+                // Pasted text can't be split into words and filters
+                Ok(self.search_pasted(search_query).await)
             } else {
                 // Search in history
                 Ok(self.search_history(search_query).await)
@@ -598,6 +1311,66 @@ impl SearchProvider for ClipboardHistoryProvider {
         Ok(())
     }
 
+    async fn execute_action(&self, result: &SearchResult, action_id: &str) -> Result<()> {
+        if result.result_type != ResultType::Clipboard || action_id != COPY_FORMATTED_ACTION_ID {
+            return Err(LauncherError::NotFound(format!("Clipboard History has no action '{}'", action_id)));
+        }
+        self.copy_formatted(&result.id).await
+    }
+
+    fn handles_blobs(&self) -> bool {
+        // `clip:` followed by pasted text finds when it was copied
+        true
+    }
+
+    /// Resizes the history; shrinking drops the oldest items right away
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        let config = ClipboardConfig::parse(config)?;
+        self.max_items.store(config.max_items, Ordering::Relaxed);
+
+        // Storage keeps the extra rows until the next copy trims it
+        let mut history = self.history.write().await;
+        history.truncate(config.max_items);
+        debug!("Clipboard history keeps {} items", config.max_items);
+        Ok(())
+    }
+
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        let history = self.history.read().await;
+        let bytes = history
+            .iter()
+            .map(|item| {
+                std::mem::size_of::<ClipboardItem>()
+                    + item.id.len()
+                    + item.content.len()
+                    + item.normalized_content.len()
+                    + item.formatted_preview.as_ref().map_or(0, String::len)
+            })
+            .sum();
+
+        vec![CacheStats {
+            name: "Clipboard history".to_string(),
+            entries: history.len(),
+            bytes,
+            byte_budget: None,
+        }]
+    }
+
+    async fn refresh_after_resume(&self) {
+        self.monitor.ensure_running().await;
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "clip",
+            title: "Search clipboard history",
+            description: "Filters by today, yesterday, >N or <N characters and kinds such as url or code",
+            example: "clip: today url",
+            token: Some("clip:"),
+            values: &[],
+        }]
+    }
+
     fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -606,7 +1379,7 @@ impl SearchProvider for ClipboardHistoryProvider {
         info!("Initializing ClipboardHistoryProvider");
         
         // Load history from disk
-        match self.storage.load().await {
+        match self.storage.load(self.max_items()).await {
             Ok(items) => {
                 let mut history = self.history.write().await;
                 *history = items;
@@ -619,40 +1392,16 @@ impl SearchProvider for ClipboardHistoryProvider {
 
         // Start clipboard monitoring
         let history = Arc::clone(&self.history);
-        let storage = ClipboardStorage::new()?;
+        let storage = self.storage.clone();
+        let max_items = Arc::clone(&self.max_items);
         
         self.monitor.start(move |content| {
             let history = Arc::clone(&history);
-            let storage_clone = storage.clone();
+            let storage = storage.clone();
+            let max_items = max_items.load(Ordering::Relaxed);
             
             tokio::spawn(async move {
-                let mut hist = history.write().await;
-                
-                // Don't add if it's the same as the most recent item
-                if let Some(last) = hist.front() {
-                    if last.content == content {
-                        return;
-                    }
-                }
-
-                // Don't add empty content
-                if content.trim().is_empty() {
-                    return;
-                }
-
-                let item = ClipboardItem::new(content);
-                debug!("Adding clipboard item from monitor: {}", item.id);
-                
-                hist.push_front(item);
-                
-                while hist.len() > MAX_CLIPBOARD_ITEMS {
-                    hist.pop_back();
-                }
-
-                // Save to disk
-                if let Err(e) = storage_clone.save(&hist).await {
-                    error!("Failed to save clipboard history: {}", e);
-                }
+                Self::record_item(&history, &storage, max_items, content).await;
             });
         }).await?;
 
@@ -663,13 +1412,9 @@ impl SearchProvider for ClipboardHistoryProvider {
     async fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down ClipboardHistoryProvider");
         
-        // Stop clipboard monitoring
+        // Stop clipboard monitoring; every item is persisted as it is copied
         self.monitor.stop().await;
         
-        // Save history one last time
-        let history = self.history.read().await;
-        self.storage.save(&history).await?;
-        
         info!("ClipboardHistoryProvider shut down successfully");
         Ok(())
     }
@@ -679,7 +1424,7 @@ impl Default for ClipboardHistoryProvider {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             history: Arc::new(RwLock::new(VecDeque::new())),
-            max_items: MAX_CLIPBOARD_ITEMS,
+            max_items: Arc::new(AtomicUsize::new(DEFAULT_CLIPBOARD_ITEMS)),
             storage: ClipboardStorage::default(),
             monitor: Arc::new(ClipboardMonitor::new()),
             enabled: false,
@@ -687,15 +1432,6 @@ impl Default for ClipboardHistoryProvider {
     }
 }
 
-// Clone implementation for ClipboardStorage (needed for the monitor callback)
-impl Clone for ClipboardStorage {
-    fn clone(&self) -> Self {
-        Self {
-            storage_path: self.storage_path.clone(),
-        }
-    }
-}
-
 
 #[cfg(test)]
 mod tests {
@@ -746,34 +1482,58 @@ mod tests {
         assert!(!*is_running);
     }
 
+    #[tokio::test]
+    async fn test_clipboard_monitor_restarts_dead_task() {
+        let monitor = ClipboardMonitor::new();
+        assert!(!monitor.ensure_running().await);
+
+        monitor.start(|_| {}).await.unwrap();
+        assert!(!monitor.ensure_running().await);
+
+        // The polling task dies, e.g. after a panic in the callback
+        let task = monitor.task.lock().unwrap().take().unwrap();
+        task.abort();
+        let _ = task.await;
+        assert!(monitor.ensure_running().await);
+        assert!(!monitor.ensure_running().await);
+
+        // A stopped monitor stays stopped
+        monitor.stop().await;
+        assert!(!monitor.ensure_running().await);
+    }
+
     #[tokio::test]
     async fn test_clipboard_storage_path() {
-        let result = ClipboardStorage::get_storage_path();
+        let result = ClipboardStorage::get_db_path();
         assert!(result.is_ok());
         
         let path = result.unwrap();
         assert!(path.to_string_lossy().contains("BetterFinder"));
-        // In tests, it uses clipboard_history_test.json
-        #[cfg(test)]
-        assert!(path.to_string_lossy().contains("clipboard_history_test.json"));
-        #[cfg(not(test))]
-        assert!(path.to_string_lossy().contains("clipboard_history.json"));
+        // In tests, it uses clipboard_history_test.db
+        assert!(path.to_string_lossy().contains("clipboard_history_test.db"));
+
+        let legacy = ClipboardStorage::get_legacy_path().unwrap();
+        assert!(legacy.to_string_lossy().contains("clipboard_history_test.json"));
+    }
+
+    /// Creates storage backed by a fresh database unique to one test
+    fn test_storage(name: &str) -> (ClipboardStorage, PathBuf, PathBuf) {
+        let mut dir = std::env::temp_dir();
+        dir.push("BetterFinder");
+        std::fs::create_dir_all(&dir).ok();
+
+        let db_path = dir.join(format!("{}.db", name));
+        let legacy_path = dir.join(format!("{}.json", name));
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = ClipboardStorage::with_paths(db_path.clone(), legacy_path.clone()).unwrap();
+        (storage, db_path, legacy_path)
     }
 
     #[tokio::test]
     async fn test_clipboard_storage_save_and_load() {
-        // Use a unique test file to avoid conflicts with other tests
-        let mut test_path = std::env::temp_dir();
-        test_path.push("BetterFinder");
-        std::fs::create_dir_all(&test_path).ok();
-        test_path.push("clipboard_test_save_load.json");
-        
-        let storage = ClipboardStorage {
-            storage_path: test_path.clone(),
-        };
-        
-        // Cleanup any existing test file first
-        let _ = std::fs::remove_file(&test_path);
+        // Use a unique test database to avoid conflicts with other tests
+        let (storage, test_path, _) = test_storage("clipboard_test_save_load");
         
         // Create test items
         let mut items = VecDeque::new();
@@ -794,7 +1554,7 @@ mod tests {
         assert!(file_size > 0, "Storage file is empty");
 
         // Load
-        let load_result = storage.load().await;
+        let load_result = storage.load(DEFAULT_CLIPBOARD_ITEMS).await;
         assert!(load_result.is_ok(), "Failed to load: {:?}", load_result.err());
         
         let loaded_items = load_result.unwrap();
@@ -816,7 +1576,16 @@ mod tests {
         assert_eq!(provider.name(), "Clipboard History");
         assert_eq!(provider.priority(), 60);
         assert!(provider.is_enabled());
-        assert_eq!(provider.max_items, MAX_CLIPBOARD_ITEMS);
+        assert_eq!(provider.max_items(), DEFAULT_CLIPBOARD_ITEMS);
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_provider_with_max_items_is_clamped() {
+        let provider = ClipboardHistoryProvider::new().unwrap().with_max_items(10_000);
+        assert_eq!(provider.max_items(), MAX_CLIPBOARD_ITEMS);
+
+        let provider = ClipboardHistoryProvider::new().unwrap().with_max_items(0);
+        assert_eq!(provider.max_items(), 1);
     }
 
     #[tokio::test]
@@ -859,7 +1628,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_clipboard_provider_max_items() {
-        let provider = ClipboardHistoryProvider::new().unwrap();
+        let provider = ClipboardHistoryProvider::new().unwrap().with_max_items(20);
         
         // Add more than max items
         for i in 0..25 {
@@ -867,13 +1636,37 @@ mod tests {
         }
         
         let history = provider.history.read().await;
-        // Should only keep MAX_CLIPBOARD_ITEMS
-        assert_eq!(history.len(), MAX_CLIPBOARD_ITEMS);
+        // Should only keep the configured number of items
+        assert_eq!(history.len(), 20);
         
         // Most recent item should be at the front
         assert_eq!(history[0].content, "Item 24");
     }
 
+    #[tokio::test]
+    async fn test_clipboard_provider_configure_resizes_history() {
+        let provider = ClipboardHistoryProvider::new().unwrap().with_max_items(20);
+        for i in 0..10 {
+            provider.add_item(format!("Configured item {}", i)).await;
+        }
+
+        provider.configure(&serde_json::json!({ "max_items": 4 })).await.unwrap();
+        assert_eq!(provider.max_items(), 4);
+        assert_eq!(provider.history.read().await.len(), 4);
+        assert_eq!(provider.history.read().await[0].content, "Configured item 9");
+
+        provider.add_item("Configured item 10".to_string()).await;
+        assert_eq!(provider.history.read().await.len(), 4);
+
+        // Out-of-range values are rejected and leave the size alone
+        assert!(provider.configure(&serde_json::json!({ "max_items": 0 })).await.is_err());
+        assert_eq!(provider.max_items(), 4);
+
+        // A missing section restores the default
+        provider.configure(&serde_json::Value::Null).await.unwrap();
+        assert_eq!(provider.max_items(), DEFAULT_CLIPBOARD_ITEMS);
+    }
+
     #[tokio::test]
     async fn test_clipboard_provider_search_with_clip_prefix() {
         let provider = ClipboardHistoryProvider::new().unwrap();
@@ -884,13 +1677,28 @@ mod tests {
         provider.add_item("Another item".to_string()).await;
         
         // Search with "clip:" prefix
-        let results = provider.search("clip:hello").await.unwrap();
+        let results = provider.search(&"clip:hello".into()).await.unwrap();
         
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].result_type, ResultType::Clipboard);
         assert!(results[0].title.contains("Hello world"));
     }
 
+    #[tokio::test]
+    async fn test_clipboard_provider_search_pasted_text() {
+        let provider = ClipboardHistoryProvider::new().unwrap();
+        let trace = "Error: boom\n    at main (app.js:1)\n    at run (app.js:2)";
+        provider.add_item(format!("{}\n", trace)).await;
+        provider.add_item("Error: boom".to_string()).await;
+
+        // The engine passes the full text of pasted queries, line breaks included
+        let query = QueryContext::from("clip: Error: boom at main").with_full_text(format!("clip: {}", trace));
+        let results = provider.search(&query).await.unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].metadata["content"], format!("{}\n", trace));
+    }
+
     #[tokio::test]
     async fn test_clipboard_provider_search_empty_clip_prefix() {
         let provider = ClipboardHistoryProvider::new().unwrap();
@@ -901,7 +1709,7 @@ mod tests {
         provider.add_item("Item 3".to_string()).await;
         
         // Search with just "clip:" (no query)
-        let results = provider.search("clip:").await.unwrap();
+        let results = provider.search(&"clip:".into()).await.unwrap();
         
         // Should return recent items
         assert_eq!(results.len(), 3);
@@ -915,7 +1723,7 @@ mod tests {
         provider.add_item("Hello world".to_string()).await;
         
         // Search without "clip:" prefix
-        let results = provider.search("hello").await.unwrap();
+        let results = provider.search(&"hello".into()).await.unwrap();
         
         // Should return empty (clipboard only responds to "clip:" prefix)
         assert!(results.is_empty());
@@ -929,11 +1737,11 @@ mod tests {
         provider.add_item("Hello World".to_string()).await;
         
         // Search with lowercase
-        let results = provider.search("clip:hello").await.unwrap();
+        let results = provider.search(&"clip:hello".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         
         // Search with uppercase
-        let results = provider.search("clip:WORLD").await.unwrap();
+        let results = provider.search(&"clip:WORLD".into()).await.unwrap();
         assert_eq!(results.len(), 1);
     }
 
@@ -945,7 +1753,7 @@ mod tests {
         provider.add_item("Old item".to_string()).await;
         provider.add_item("Newer item".to_string()).await;
         
-        let results = provider.search("clip:item").await.unwrap();
+        let results = provider.search(&"clip:item".into()).await.unwrap();
         
         assert_eq!(results.len(), 2);
         // Newer item should have higher score
@@ -995,6 +1803,9 @@ mod tests {
             action: ResultAction::CopyToClipboard {
                 content: "test".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         };
 
         let result = provider.execute(&invalid_result).await;
@@ -1017,4 +1828,272 @@ mod tests {
         // Most recent should be first
         assert!(results[0].title.contains("Item 9"));
     }
+
+    #[test]
+    fn test_clipboard_kind_detection() {
+        assert_eq!(ClipboardKind::detect("https://example.com/path?q=1"), ClipboardKind::Url);
+        assert_eq!(ClipboardKind::detect("  www.rust-lang.org "), ClipboardKind::Url);
+        assert_eq!(ClipboardKind::detect("jane.doe@example.com"), ClipboardKind::Email);
+        assert_eq!(ClipboardKind::detect("1,234.56"), ClipboardKind::Number);
+        assert_eq!(ClipboardKind::detect("-42"), ClipboardKind::Number);
+        assert_eq!(ClipboardKind::detect("let x = compute();"), ClipboardKind::Code);
+        assert_eq!(
+            ClipboardKind::detect("fn main() {\n    println!(\"hi\");\n}"),
+            ClipboardKind::Code
+        );
+
+        // Near misses stay plain text
+        assert_eq!(ClipboardKind::detect("see https://example.com"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("meet me @ noon"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("user@localhost"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("Hello; world"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("http://"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("."), ClipboardKind::Text);
+    }
+
+    #[test]
+    fn test_clipboard_kind_detection_structured() {
+        assert_eq!(
+            ClipboardKind::detect(r#"{"user":{"id":1,"tags":["a","b"],"meta":{"x":null}}}"#),
+            ClipboardKind::Json
+        );
+        assert_eq!(ClipboardKind::detect("[1, 2, {\"a\": [true]}]"), ClipboardKind::Json);
+        assert_eq!(
+            ClipboardKind::detect("<?xml version=\"1.0\"?><config><item key=\"a\">1</item></config>"),
+            ClipboardKind::Xml
+        );
+        assert_eq!(
+            ClipboardKind::detect("https://example.com\n\nhttps://rust-lang.org/learn\n"),
+            ClipboardKind::UrlList
+        );
+        assert_eq!(
+            ClipboardKind::detect("| Name | Size |\n|------|:----:|\n| a.txt | 1 KB |"),
+            ClipboardKind::Table
+        );
+
+        // JSON5-style input and mismatched tags fall back to code detection
+        assert_eq!(ClipboardKind::detect("{name: 'x', list: [1, 2,],}"), ClipboardKind::Code);
+        assert_eq!(ClipboardKind::detect("{\"a\": 1, // comment\n}"), ClipboardKind::Code);
+        assert_eq!(ClipboardKind::detect("<div><span></div></span>"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("https://example.com\nnot a url"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::detect("| just | pipes |"), ClipboardKind::Text);
+        assert_eq!(ClipboardKind::from_keyword("urls"), Some(ClipboardKind::UrlList));
+    }
+
+    #[test]
+    fn test_formatted_preview_keeps_original_content() {
+        let content = r#"{"b":1,"a":[1,2,3,4,5,6,7,8]}"#.to_string();
+        let item = ClipboardItem::new(content.clone());
+
+        assert_eq!(item.kind, ClipboardKind::Json);
+        assert_eq!(item.formatted().unwrap().lines().count(), 13);
+        let preview = item.formatted_preview.clone().unwrap();
+        assert_eq!(preview.lines().count(), FORMATTED_PREVIEW_LINES + 1);
+        assert!(preview.starts_with("{\n  \"b\": 1,\n  \"a\": ["));
+
+        let provider = ClipboardHistoryProvider::new().unwrap();
+        let result = provider.create_search_result(&item, 50.0);
+        assert_eq!(result.metadata["preview"], serde_json::json!(preview));
+        assert_eq!(result.metadata[SECONDARY_ACTIONS_KEY][0]["id"], COPY_FORMATTED_ACTION_ID);
+        assert!(matches!(&result.action, ResultAction::CopyToClipboard { content: copied } if *copied == content));
+        assert_eq!(result.metadata["content"], serde_json::json!(content));
+
+        // Plain items get neither a preview nor the action
+        let plain = provider.create_search_result(&ClipboardItem::new("hello".to_string()), 50.0);
+        assert!(!plain.metadata.contains_key("preview"));
+        assert!(!plain.metadata.contains_key(SECONDARY_ACTIONS_KEY));
+    }
+
+    #[test]
+    fn test_clipboard_item_records_kind() {
+        let item = ClipboardItem::new("https://example.com".to_string());
+        assert_eq!(item.kind, ClipboardKind::Url);
+    }
+
+    #[test]
+    fn test_clipboard_query_parse() {
+        let query = ClipboardQuery::parse("Invoice today url >100 <500");
+        assert_eq!(query.text, "invoice");
+        assert_eq!(
+            query.filters,
+            vec![
+                ClipboardFilter::Today,
+                ClipboardFilter::Kind(ClipboardKind::Url),
+                ClipboardFilter::LongerThan(100),
+                ClipboardFilter::ShorterThan(500),
+            ]
+        );
+
+        // Malformed filter terms are treated as search text
+        let query = ClipboardQuery::parse(">abc yesterday");
+        assert_eq!(query.text, ">abc");
+        assert_eq!(query.filters, vec![ClipboardFilter::Yesterday]);
+
+        let query = ClipboardQuery::parse("EMAIL");
+        assert!(query.text.is_empty());
+        assert_eq!(query.filters, vec![ClipboardFilter::Kind(ClipboardKind::Email)]);
+    }
+
+    #[test]
+    fn test_clipboard_filters_match_dates_and_length() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        let at_local_noon = |date: NaiveDate| {
+            date.and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_local_timezone(Local)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        let mut item = ClipboardItem::new("a".repeat(150));
+        item.timestamp = at_local_noon(today);
+        assert!(ClipboardFilter::Today.matches(&item, today));
+        assert!(!ClipboardFilter::Yesterday.matches(&item, today));
+        assert!(ClipboardFilter::LongerThan(100).matches(&item, today));
+        assert!(!ClipboardFilter::LongerThan(150).matches(&item, today));
+        assert!(ClipboardFilter::ShorterThan(151).matches(&item, today));
+        assert!(ClipboardFilter::Kind(ClipboardKind::Text).matches(&item, today));
+
+        item.timestamp = at_local_noon(today.pred_opt().unwrap());
+        assert!(!ClipboardFilter::Today.matches(&item, today));
+        assert!(ClipboardFilter::Yesterday.matches(&item, today));
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_provider_search_with_filters() {
+        let provider = ClipboardHistoryProvider::new().unwrap();
+
+        provider.add_item("https://docs.rs/serde".to_string()).await;
+        provider.add_item("serde notes".to_string()).await;
+        provider.add_item(format!("serde {}", "x".repeat(120))).await;
+
+        // Kind filter applied after the text match
+        let results = provider.search(&"clip: serde url".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].title.contains("docs.rs"));
+        assert_eq!(results[0].metadata.get("kind"), Some(&serde_json::json!("url")));
+
+        // Filters alone list every matching item
+        let results = provider.search(&"clip: today".into()).await.unwrap();
+        assert_eq!(results.len(), 3);
+
+        let results = provider.search(&"clip: >100".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        let results = provider.search(&"clip: yesterday".into()).await.unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_storage_append_prunes_oldest() {
+        let (storage, db_path, _) = test_storage("clipboard_test_append");
+
+        for i in 0..5 {
+            storage.append(&ClipboardItem::new(format!("Item {}", i)), 3).await.unwrap();
+        }
+
+        let items = storage.load(10).await.unwrap();
+        let contents: Vec<&str> = items.iter().map(|item| item.content.as_str()).collect();
+        assert_eq!(contents, vec!["Item 4", "Item 3", "Item 2"]);
+
+        let _ = std::fs::remove_file(&db_path);
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_storage_migrates_legacy_json_once() {
+        let mut dir = std::env::temp_dir();
+        dir.push("BetterFinder");
+        std::fs::create_dir_all(&dir).ok();
+        let db_path = dir.join("clipboard_test_migration.db");
+        let legacy_path = dir.join("clipboard_test_migration.json");
+        let migrated_path = dir.join("clipboard_test_migration.json.migrated");
+        let _ = std::fs::remove_file(&db_path);
+        let _ = std::fs::remove_file(&migrated_path);
+
+        // Legacy files are newest first and predate the `kind` field
+        let legacy = r#"[
+            {"id": "clipboard:2", "content": "https://example.com", "timestamp": "2024-01-02T10:00:00Z", "content_type": "Text"},
+            {"id": "clipboard:1", "content": "Older note", "timestamp": "2024-01-01T10:00:00Z", "content_type": "Text"}
+        ]"#;
+        std::fs::write(&legacy_path, legacy).unwrap();
+
+        let storage = ClipboardStorage::with_paths(db_path.clone(), legacy_path.clone()).unwrap();
+        assert!(!legacy_path.exists());
+        assert!(migrated_path.exists());
+
+        let items = storage.load(10).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].content, "https://example.com");
+        assert_eq!(items[0].kind, ClipboardKind::Url);
+        assert_eq!(items[1].content, "Older note");
+        assert_eq!(items[1].timestamp.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+
+        // Reopening does not import again
+        let storage = ClipboardStorage::with_paths(db_path.clone(), legacy_path.clone()).unwrap();
+        assert_eq!(storage.load(10).await.unwrap().len(), 2);
+
+        let _ = std::fs::remove_file(&db_path);
+        let _ = std::fs::remove_file(&migrated_path);
+    }
+
+    #[test]
+    fn test_clipboard_export_metadata_only_shape() {
+        let now = Utc::now();
+        let items: VecDeque<ClipboardItem> = vec![ClipboardItem::from_parts(
+            "clipboard:1".to_string(),
+            "secret token".to_string(),
+            now,
+            ClipboardKind::Text,
+        )]
+        .into();
+
+        let full = ClipboardHistoryProvider::export_document(&items, false, now);
+        assert_eq!(full["items"][0]["content"], "secret token");
+
+        let metadata = ClipboardHistoryProvider::export_document(&items, true, now);
+        assert_eq!(metadata["metadata_only"], true);
+        let entry = metadata["items"][0].as_object().unwrap();
+        assert!(!entry.contains_key("content"));
+        assert_eq!(entry["id"], "clipboard:1");
+        assert_eq!(entry["kind"], "text");
+        assert_eq!(entry["length"], 12);
+        assert!(entry.contains_key("timestamp"));
+    }
+
+    #[tokio::test]
+    async fn test_clipboard_clear_keeps_memory_and_disk_in_step() {
+        let (storage, db_path, _) = test_storage("clipboard_test_clear");
+        let now = Utc::now();
+        let item = |id: &str, hours_ago: i64| {
+            ClipboardItem::from_parts(
+                id.to_string(),
+                format!("content {}", id),
+                now - chrono::Duration::hours(hours_ago),
+                ClipboardKind::Text,
+            )
+        };
+
+        let items: VecDeque<ClipboardItem> = vec![item("new", 0), item("day", 30), item("week", 200)].into();
+        storage.save(&items).await.unwrap();
+
+        let provider = ClipboardHistoryProvider {
+            history: Arc::new(RwLock::new(items)),
+            max_items: Arc::new(AtomicUsize::new(DEFAULT_CLIPBOARD_ITEMS)),
+            storage: storage.clone(),
+            monitor: Arc::new(ClipboardMonitor::new()),
+            enabled: true,
+        };
+
+        let ids = |items: &VecDeque<ClipboardItem>| items.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+
+        assert_eq!(provider.clear(Some(Duration::from_secs(24 * 3600))).await.unwrap(), 2);
+        assert_eq!(ids(&*provider.history.read().await), vec!["new"]);
+        assert_eq!(ids(&storage.load(10).await.unwrap()), vec!["new"]);
+
+        assert_eq!(provider.clear(None).await.unwrap(), 1);
+        assert!(provider.history.read().await.is_empty());
+        assert!(storage.load(10).await.unwrap().is_empty());
+
+        let _ = std::fs::remove_file(&db_path);
+    }
 }
@@ -0,0 +1,517 @@
+/// Connectivity provider with Wi-Fi and Bluetooth toggles
+///
+/// Searching "wifi" or "bluetooth" shows the radio's current state and
+/// executing the result flips it. Radios are read and switched through the
+/// Windows.Devices.Radios API, which covers both Wi-Fi and Bluetooth adapters.
+///
+/// States are read lazily when a search matches and kept for a few seconds,
+/// so typing a query doesn't query the radio stack on every keystroke.
+/// Machines without the adapter don't get the toggle at all.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// How long a radio state read is reused
+const STATE_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// Shortest query that matches a toggle
+const MIN_QUERY_LEN: usize = 2;
+
+/// Radios that can be toggled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RadioKind {
+    WiFi,
+    Bluetooth,
+}
+
+impl RadioKind {
+    /// Returns the display name for the radio
+    pub fn display_name(&self) -> &str {
+        match self {
+            RadioKind::WiFi => "Wi-Fi",
+            RadioKind::Bluetooth => "Bluetooth",
+        }
+    }
+
+    /// Identifier used in result ids
+    fn id(&self) -> &'static str {
+        match self {
+            RadioKind::WiFi => "wifi",
+            RadioKind::Bluetooth => "bluetooth",
+        }
+    }
+
+    /// Words a query is matched against
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            RadioKind::WiFi => &["wifi", "wi-fi", "wireless", "wlan"],
+            RadioKind::Bluetooth => &["bluetooth"],
+        }
+    }
+
+    /// Returns all radios in display order
+    pub fn all() -> [RadioKind; 2] {
+        [RadioKind::WiFi, RadioKind::Bluetooth]
+    }
+}
+
+/// Whether a radio is switched on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RadioState {
+    On,
+    Off,
+}
+
+impl RadioState {
+    /// The state a toggle switches to
+    pub fn toggled(self) -> Self {
+        match self {
+            RadioState::On => RadioState::Off,
+            RadioState::Off => RadioState::On,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            RadioState::On => "On",
+            RadioState::Off => "Off",
+        }
+    }
+}
+
+/// Reads and switches radios; blocking, so callers run it off the async runtime
+pub trait RadioControl: Send + Sync {
+    /// Current state of the radio, or `None` when the machine has no such adapter
+    fn state(&self, kind: RadioKind) -> Result<Option<RadioState>>;
+
+    /// Switches the radio on or off
+    fn set_state(&self, kind: RadioKind, state: RadioState) -> Result<()>;
+}
+
+/// Builds the subtitle describing a radio's state and what Enter does
+pub fn toggle_subtitle(kind: RadioKind, state: RadioState) -> String {
+    format!(
+        "{} is {} — press Enter to turn {}",
+        kind.display_name(),
+        state.label(),
+        state.toggled().label()
+    )
+}
+
+struct CachedState {
+    state: Option<RadioState>,
+    read_at: Instant,
+}
+
+/// Connectivity search provider
+pub struct ConnectivityProvider {
+    control: Arc<dyn RadioControl>,
+    /// Last read state per radio
+    cache: Mutex<HashMap<RadioKind, CachedState>>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl ConnectivityProvider {
+    /// Creates a new ConnectivityProvider using the system radios
+    pub fn new() -> Result<Self> {
+        info!("Initializing ConnectivityProvider");
+        Ok(Self::with_control(Arc::new(SystemRadios)))
+    }
+
+    /// Creates a provider backed by the given radio control
+    pub fn with_control(control: Arc<dyn RadioControl>) -> Self {
+        Self {
+            control,
+            cache: Mutex::new(HashMap::new()),
+            enabled: true,
+        }
+    }
+
+    /// Scores how well the query names the radio
+    fn match_score(query: &str, kind: RadioKind) -> Option<f64> {
+        if query.chars().count() < MIN_QUERY_LEN {
+            return None;
+        }
+
+        kind.keywords()
+            .iter()
+            .filter_map(|keyword| {
+                if *keyword == query {
+                    Some(100.0)
+                } else if keyword.starts_with(query) {
+                    Some(90.0)
+                } else {
+                    None
+                }
+            })
+            .reduce(f64::max)
+    }
+
+    /// Returns the radio state, reading it again once the cached value expired
+    async fn current_state(&self, kind: RadioKind) -> Result<Option<RadioState>> {
+        if let Some(cached) = self.cache.lock().unwrap().get(&kind) {
+            if cached.read_at.elapsed() < STATE_CACHE_TTL {
+                return Ok(cached.state);
+            }
+        }
+
+        let control = Arc::clone(&self.control);
+        let state = tokio::task::spawn_blocking(move || control.state(kind))
+            .await
+            .map_err(|e| LauncherError::ProviderError(format!("Radio state task failed: {}", e)))??;
+
+        self.remember(kind, state);
+        Ok(state)
+    }
+
+    fn remember(&self, kind: RadioKind, state: Option<RadioState>) {
+        self.cache.lock().unwrap().insert(
+            kind,
+            CachedState {
+                state,
+                read_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Converts a radio state to a SearchResult
+    fn create_search_result(kind: RadioKind, state: RadioState, score: f64) -> SearchResult {
+        let target = state.toggled();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("radio".to_string(), serde_json::json!(kind));
+        metadata.insert("state".to_string(), serde_json::json!(state));
+        metadata.insert("target_state".to_string(), serde_json::json!(target));
+
+        SearchResult {
+            id: format!("connectivity:{}", kind.id()),
+            title: kind.display_name().to_string(),
+            subtitle: toggle_subtitle(kind, state),
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: format!("radio:{:?}", kind),
+                args: vec![format!("{:?}", target)],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for ConnectivityProvider {
+    fn name(&self) -> &str {
+        "Connectivity"
+    }
+
+    fn priority(&self) -> u8 {
+        80 // Same as the other quick actions
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let query = query.folded_query.trim();
+
+        let mut results = Vec::new();
+        for kind in RadioKind::all() {
+            let Some(score) = Self::match_score(query, kind) else {
+                continue;
+            };
+
+            match self.current_state(kind).await {
+                Ok(Some(state)) => results.push(Self::create_search_result(kind, state, score)),
+                Ok(None) => debug!("No {} adapter, hiding its toggle", kind.display_name()),
+                Err(e) => warn!("Failed to read {} state: {}", kind.display_name(), e),
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let field = |name: &str| result.metadata.get(name).cloned();
+        let kind = field("radio").and_then(|v| serde_json::from_value::<RadioKind>(v).ok());
+        let target = field("target_state").and_then(|v| serde_json::from_value::<RadioState>(v).ok());
+
+        let (Some(kind), Some(target)) = (kind, target) else {
+            return Err(LauncherError::ExecutionError("Not a radio toggle result".to_string()));
+        };
+
+        info!("Turning {} {}", kind.display_name(), target.label());
+
+        let control = Arc::clone(&self.control);
+        let switched = tokio::task::spawn_blocking(move || control.set_state(kind, target))
+            .await
+            .map_err(|e| LauncherError::ExecutionError(format!("Radio toggle task failed: {}", e)))?;
+
+        match switched {
+            Ok(()) => {
+                self.remember(kind, Some(target));
+                Ok(())
+            }
+            Err(e) => {
+                // The radio may have changed anyway, so read it again next time
+                self.cache.lock().unwrap().remove(&kind);
+                Err(e)
+            }
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        // Subtitles show live radio state
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Radio control backed by the operating system
+pub struct SystemRadios;
+
+#[cfg(windows)]
+impl SystemRadios {
+    fn find_radio(kind: RadioKind) -> Result<Option<windows::Devices::Radios::Radio>> {
+        use windows::Devices::Radios::{Radio, RadioKind as WinRadioKind};
+
+        let wanted = match kind {
+            RadioKind::WiFi => WinRadioKind::WiFi,
+            RadioKind::Bluetooth => WinRadioKind::Bluetooth,
+        };
+
+        let radios = Radio::GetRadiosAsync()
+            .and_then(|operation| operation.get())
+            .map_err(|e| LauncherError::ProviderError(format!("Failed to enumerate radios: {}", e)))?;
+
+        for radio in radios {
+            if radio.Kind().ok() == Some(wanted) {
+                return Ok(Some(radio));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn access_error(kind: RadioKind, status: windows::Devices::Radios::RadioAccessStatus) -> LauncherError {
+        use windows::Devices::Radios::RadioAccessStatus;
+
+        let reason = match status {
+            RadioAccessStatus::DeniedByUser => {
+                "radio access is turned off in Settings > Privacy & security > Radios"
+            }
+            RadioAccessStatus::DeniedBySystem => {
+                "Windows blocked the change; it may require administrator rights or be locked by policy"
+            }
+            _ => "Windows refused the change",
+        };
+
+        LauncherError::SecurityError(format!("Cannot switch {}: {}", kind.display_name(), reason))
+    }
+}
+
+#[cfg(windows)]
+impl RadioControl for SystemRadios {
+    fn state(&self, kind: RadioKind) -> Result<Option<RadioState>> {
+        use windows::Devices::Radios::RadioState as WinRadioState;
+
+        let Some(radio) = Self::find_radio(kind)? else {
+            return Ok(None);
+        };
+
+        let state = radio
+            .State()
+            .map_err(|e| LauncherError::ProviderError(format!("Failed to read radio state: {}", e)))?;
+
+        // A disabled radio (e.g. a disabled adapter) can't be switched from here
+        Ok(match state {
+            WinRadioState::On => Some(RadioState::On),
+            WinRadioState::Off => Some(RadioState::Off),
+            _ => None,
+        })
+    }
+
+    fn set_state(&self, kind: RadioKind, state: RadioState) -> Result<()> {
+        use windows::Devices::Radios::{Radio, RadioAccessStatus, RadioState as WinRadioState};
+
+        let access = Radio::RequestAccessAsync()
+            .and_then(|operation| operation.get())
+            .map_err(|e| LauncherError::ExecutionError(format!("Failed to request radio access: {}", e)))?;
+        if access != RadioAccessStatus::Allowed {
+            return Err(Self::access_error(kind, access));
+        }
+
+        let radio = Self::find_radio(kind)?
+            .ok_or_else(|| LauncherError::NotFound(format!("{} adapter", kind.display_name())))?;
+
+        let wanted = match state {
+            RadioState::On => WinRadioState::On,
+            RadioState::Off => WinRadioState::Off,
+        };
+
+        let status = radio
+            .SetStateAsync(wanted)
+            .and_then(|operation| operation.get())
+            .map_err(|e| {
+                LauncherError::ExecutionError(format!("Failed to switch {}: {}", kind.display_name(), e))
+            })?;
+
+        if status == RadioAccessStatus::Allowed {
+            Ok(())
+        } else {
+            Err(Self::access_error(kind, status))
+        }
+    }
+}
+
+#[cfg(not(windows))]
+impl RadioControl for SystemRadios {
+    fn state(&self, _kind: RadioKind) -> Result<Option<RadioState>> {
+        Ok(None)
+    }
+
+    fn set_state(&self, kind: RadioKind, _state: RadioState) -> Result<()> {
+        Err(LauncherError::ExecutionError(format!(
+            "Switching {} is not supported on this platform",
+            kind.display_name()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Radio control with fixed adapters that counts state reads
+    struct MockRadios {
+        states: Mutex<HashMap<RadioKind, RadioState>>,
+        reads: AtomicUsize,
+        deny: bool,
+    }
+
+    impl MockRadios {
+        fn new(states: &[(RadioKind, RadioState)]) -> Arc<Self> {
+            Arc::new(Self {
+                states: Mutex::new(states.iter().copied().collect()),
+                reads: AtomicUsize::new(0),
+                deny: false,
+            })
+        }
+    }
+
+    impl RadioControl for MockRadios {
+        fn state(&self, kind: RadioKind) -> Result<Option<RadioState>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            Ok(self.states.lock().unwrap().get(&kind).copied())
+        }
+
+        fn set_state(&self, kind: RadioKind, state: RadioState) -> Result<()> {
+            if self.deny {
+                return Err(LauncherError::SecurityError("Cannot switch Wi-Fi: denied".to_string()));
+            }
+            self.states.lock().unwrap().insert(kind, state);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_toggle_subtitle() {
+        assert_eq!(
+            toggle_subtitle(RadioKind::WiFi, RadioState::On),
+            "Wi-Fi is On — press Enter to turn Off"
+        );
+        assert_eq!(
+            toggle_subtitle(RadioKind::Bluetooth, RadioState::Off),
+            "Bluetooth is Off — press Enter to turn On"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_shows_current_state() {
+        let radios = MockRadios::new(&[(RadioKind::WiFi, RadioState::On), (RadioKind::Bluetooth, RadioState::Off)]);
+        let provider = ConnectivityProvider::with_control(radios);
+
+        let results = provider.search(&"wifi".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "connectivity:wifi");
+        assert_eq!(results[0].title, "Wi-Fi");
+        assert_eq!(results[0].subtitle, "Wi-Fi is On — press Enter to turn Off");
+        assert_eq!(results[0].score, 100.0);
+
+        let results = provider.search(&"Blue".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subtitle, "Bluetooth is Off — press Enter to turn On");
+        assert_eq!(results[0].metadata.get("target_state"), Some(&serde_json::json!("on")));
+
+        assert!(provider.search(&"w".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"chrome".into()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_no_adapter_hides_toggle() {
+        let radios = MockRadios::new(&[(RadioKind::WiFi, RadioState::Off)]);
+        let provider = ConnectivityProvider::with_control(radios);
+
+        assert!(provider.search(&"bluetooth".into()).await.unwrap().is_empty());
+        assert_eq!(provider.search(&"wifi".into()).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_state_is_cached_between_searches() {
+        let radios = MockRadios::new(&[(RadioKind::WiFi, RadioState::On)]);
+        let provider = ConnectivityProvider::with_control(Arc::clone(&radios) as Arc<dyn RadioControl>);
+
+        provider.search(&"wi".into()).await.unwrap();
+        provider.search(&"wif".into()).await.unwrap();
+        provider.search(&"wifi".into()).await.unwrap();
+        assert_eq!(radios.reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_flips_state() {
+        let radios = MockRadios::new(&[(RadioKind::WiFi, RadioState::On)]);
+        let provider = ConnectivityProvider::with_control(Arc::clone(&radios) as Arc<dyn RadioControl>);
+
+        let result = provider.search(&"wifi".into()).await.unwrap().remove(0);
+        provider.execute(&result).await.unwrap();
+        assert_eq!(radios.states.lock().unwrap().get(&RadioKind::WiFi), Some(&RadioState::Off));
+
+        // The new state shows right away without another read
+        let result = provider.search(&"wifi".into()).await.unwrap().remove(0);
+        assert_eq!(result.subtitle, "Wi-Fi is Off — press Enter to turn On");
+        assert_eq!(radios.reads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_surfaces_denied_access() {
+        let radios = Arc::new(MockRadios {
+            states: Mutex::new([(RadioKind::WiFi, RadioState::On)].into_iter().collect()),
+            reads: AtomicUsize::new(0),
+            deny: true,
+        });
+        let provider = ConnectivityProvider::with_control(radios);
+
+        let result = provider.search(&"wifi".into()).await.unwrap().remove(0);
+        let err = provider.execute(&result).await.unwrap_err();
+        assert!(matches!(err, LauncherError::SecurityError(_)));
+        assert!(err.to_string().contains("Wi-Fi"));
+    }
+}
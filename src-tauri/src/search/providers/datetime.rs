@@ -0,0 +1,752 @@
+/// Date/time provider for natural language time queries
+///
+/// This provider answers a small, anchored grammar of time-related queries:
+/// - `time in <city/timezone>` and `<city> time`
+/// - `now`, `today`, `tomorrow`, `yesterday`
+/// - `next friday`, `last monday`
+/// - `in 3 weeks`, `2 hours ago`
+/// - Unix epoch conversion in both directions (`epoch 1700000000`, `epoch 2024-03-10 14:30`)
+///
+/// The whole query must match one of the forms above, so queries such as
+/// "timer" or "now playing.mp3" are left to the other providers.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::city_timezones::CITY_TIMEZONES;
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::clipboard::set_clipboard_text;
+use async_trait::async_trait;
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc, Weekday,
+};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use std::collections::HashMap;
+use std::fmt::Display;
+use tracing::{debug, info, warn};
+
+/// Millisecond timestamps are recognised by their length (13 digits)
+const EPOCH_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Calendar units accepted by relative offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeUnit {
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+    Months,
+    Years,
+}
+
+impl TimeUnit {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "min" | "mins" | "minute" | "minutes" => Some(Self::Minutes),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Some(Self::Hours),
+            "d" | "day" | "days" => Some(Self::Days),
+            "w" | "wk" | "wks" | "week" | "weeks" => Some(Self::Weeks),
+            "month" | "months" => Some(Self::Months),
+            "y" | "yr" | "yrs" | "year" | "years" => Some(Self::Years),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed date/time query
+#[derive(Debug, Clone, PartialEq)]
+enum DateTimeQuery {
+    /// Current time in another time zone
+    TimeIn { tz: Tz, label: String },
+    /// Current local date and time
+    Now,
+    /// Today shifted by a number of days
+    RelativeDay(i64),
+    /// The next occurrence of a weekday, strictly after today
+    NextWeekday(Weekday),
+    /// The previous occurrence of a weekday, strictly before today
+    LastWeekday(Weekday),
+    /// Now shifted by a signed amount of a unit
+    Offset { amount: i64, unit: TimeUnit },
+    /// Current Unix timestamp
+    CurrentEpoch,
+    /// Unix timestamp to date
+    FromEpoch(i64),
+    /// Date to Unix timestamp; `utc` selects UTC instead of local time
+    ToEpoch { datetime: NaiveDateTime, utc: bool },
+}
+
+/// Formatted answer for a query
+#[derive(Debug, Clone, PartialEq)]
+struct DateTimeAnswer {
+    /// Value shown as the title and copied to the clipboard
+    value: String,
+    /// Context shown below the value
+    subtitle: String,
+}
+
+/// Date/time search provider
+pub struct DateTimeProvider {
+    /// Whether the provider is enabled
+    enabled: bool,
+    /// Lowercase city/abbreviation aliases
+    aliases: HashMap<&'static str, Tz>,
+}
+
+impl DateTimeProvider {
+    /// Creates a new DateTimeProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing DateTimeProvider");
+
+        let mut aliases = HashMap::with_capacity(CITY_TIMEZONES.len());
+        for (city, zone) in CITY_TIMEZONES {
+            match zone.parse::<Tz>() {
+                Ok(tz) => {
+                    aliases.insert(*city, tz);
+                }
+                Err(e) => warn!("Ignoring unknown time zone '{}' for '{}': {}", zone, city, e),
+            }
+        }
+
+        Ok(Self {
+            enabled: true,
+            aliases,
+        })
+    }
+
+    /// Resolves a city, abbreviation or IANA name to a time zone
+    fn resolve_zone(&self, place: &str) -> Option<Tz> {
+        let place = place.trim().replace('_', " ");
+        if place.is_empty() {
+            return None;
+        }
+
+        if let Some(tz) = self.aliases.get(place.as_str()) {
+            return Some(*tz);
+        }
+
+        // Full IANA name, e.g. "america/argentina/salta"
+        if let Some(tz) = TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().replace('_', " ").eq_ignore_ascii_case(&place))
+        {
+            return Some(*tz);
+        }
+
+        // City segment of an IANA name, e.g. "kathmandu" for "Asia/Kathmandu"
+        TZ_VARIANTS
+            .iter()
+            .find(|tz| {
+                tz.name().contains('/')
+                    && tz
+                        .name()
+                        .rsplit('/')
+                        .next()
+                        .is_some_and(|city| city.replace('_', " ").eq_ignore_ascii_case(&place))
+            })
+            .copied()
+    }
+
+    /// Parses a query against the supported grammar
+    fn parse(&self, query: &str) -> Option<DateTimeQuery> {
+        let lower = query.to_lowercase();
+        let tokens: Vec<&str> = lower.split_whitespace().collect();
+
+        match tokens.as_slice() {
+            ["now"] | ["time"] | ["time", "now"] | ["current", "time"] => Some(DateTimeQuery::Now),
+            ["today"] | ["date"] => Some(DateTimeQuery::RelativeDay(0)),
+            ["tomorrow"] => Some(DateTimeQuery::RelativeDay(1)),
+            ["yesterday"] => Some(DateTimeQuery::RelativeDay(-1)),
+            ["next", day] => parse_weekday(day).map(DateTimeQuery::NextWeekday),
+            ["last", day] => parse_weekday(day).map(DateTimeQuery::LastWeekday),
+            ["in", amount, unit] => Some(DateTimeQuery::Offset {
+                amount: parse_amount(amount)?,
+                unit: TimeUnit::parse(unit)?,
+            }),
+            [amount, unit, "ago"] => Some(DateTimeQuery::Offset {
+                amount: -parse_amount(amount)?,
+                unit: TimeUnit::parse(unit)?,
+            }),
+            ["epoch" | "unix"]
+            | ["epoch" | "unix", "now"]
+            | ["unix", "time" | "timestamp"] => Some(DateTimeQuery::CurrentEpoch),
+            ["epoch" | "unix", rest @ ..] => parse_epoch_argument(rest),
+            ["time", "in", place @ ..] => self.time_in(place),
+            [place @ .., "time"] => self.time_in(place),
+            _ => None,
+        }
+    }
+
+    fn time_in(&self, place: &[&str]) -> Option<DateTimeQuery> {
+        let label = place.join(" ");
+        let tz = self.resolve_zone(&label)?;
+        Some(DateTimeQuery::TimeIn { tz, label })
+    }
+
+    /// Evaluates a query relative to `now`, whose time zone is treated as local
+    fn evaluate<Z>(&self, query: &str, now: &DateTime<Z>) -> Option<DateTimeAnswer>
+    where
+        Z: TimeZone,
+        Z::Offset: Display,
+    {
+        let parsed = self.parse(query)?;
+        let today = now.date_naive();
+
+        let answer = match parsed {
+            DateTimeQuery::TimeIn { tz, label } => {
+                let there = now.with_timezone(&tz);
+                let local_offset = now.offset().fix().local_minus_utc();
+                let zone_offset = there.offset().fix().local_minus_utc();
+
+                DateTimeAnswer {
+                    value: there.format("%H:%M %Z, %a %d %b %Y").to_string(),
+                    subtitle: format!(
+                        "{} ({}) · UTC{} · {}",
+                        title_case(&label),
+                        tz.name(),
+                        there.format("%:z"),
+                        describe_delta(zone_offset - local_offset)
+                    ),
+                }
+            }
+            DateTimeQuery::Now => DateTimeAnswer {
+                value: now.format("%H:%M:%S, %a %d %b %Y").to_string(),
+                subtitle: format!("Local time · UTC{}", now.format("%:z")),
+            },
+            DateTimeQuery::RelativeDay(days) => {
+                let date = today.checked_add_signed(Duration::days(days))?;
+                date_answer(date, today)
+            }
+            DateTimeQuery::NextWeekday(weekday) => {
+                let ahead = (weekday.num_days_from_monday() as i64
+                    - today.weekday().num_days_from_monday() as i64)
+                    .rem_euclid(7);
+                let days = if ahead == 0 { 7 } else { ahead };
+                date_answer(today.checked_add_signed(Duration::days(days))?, today)
+            }
+            DateTimeQuery::LastWeekday(weekday) => {
+                let behind = (today.weekday().num_days_from_monday() as i64
+                    - weekday.num_days_from_monday() as i64)
+                    .rem_euclid(7);
+                let days = if behind == 0 { 7 } else { behind };
+                date_answer(today.checked_sub_signed(Duration::days(days))?, today)
+            }
+            DateTimeQuery::Offset { amount, unit } => match unit {
+                TimeUnit::Minutes | TimeUnit::Hours => {
+                    let delta = if unit == TimeUnit::Minutes {
+                        Duration::try_minutes(amount)?
+                    } else {
+                        Duration::try_hours(amount)?
+                    };
+                    let then = now.clone().checked_add_signed(delta)?;
+                    DateTimeAnswer {
+                        value: then.format("%H:%M, %a %d %b %Y").to_string(),
+                        subtitle: format!("{} · UTC{}", relative_label(amount, unit), then.format("%:z")),
+                    }
+                }
+                TimeUnit::Days | TimeUnit::Weeks => {
+                    let days = if unit == TimeUnit::Weeks { amount.checked_mul(7)? } else { amount };
+                    date_answer(today.checked_add_signed(Duration::try_days(days)?)?, today)
+                }
+                TimeUnit::Months | TimeUnit::Years => {
+                    let months = if unit == TimeUnit::Years { amount.checked_mul(12)? } else { amount };
+                    let shift = chrono::Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+                    let date = if months >= 0 {
+                        today.checked_add_months(shift)?
+                    } else {
+                        today.checked_sub_months(shift)?
+                    };
+                    date_answer(date, today)
+                }
+            },
+            DateTimeQuery::CurrentEpoch => DateTimeAnswer {
+                value: now.timestamp().to_string(),
+                subtitle: format!("Unix timestamp for {}", now.format("%Y-%m-%d %H:%M:%S")),
+            },
+            DateTimeQuery::FromEpoch(value) => {
+                let (utc, unit) = if value.abs() >= EPOCH_MILLIS_THRESHOLD {
+                    (Utc.timestamp_millis_opt(value).single()?, "milliseconds")
+                } else {
+                    (Utc.timestamp_opt(value, 0).single()?, "seconds")
+                };
+                let local = utc.with_timezone(&now.timezone());
+                DateTimeAnswer {
+                    value: utc.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                    subtitle: format!(
+                        "Unix {} {} · Local {}",
+                        unit,
+                        value,
+                        local.format("%Y-%m-%d %H:%M:%S")
+                    ),
+                }
+            }
+            DateTimeQuery::ToEpoch { datetime, utc } => {
+                let timestamp = if utc {
+                    datetime.and_utc().timestamp()
+                } else {
+                    resolve_local(&now.timezone(), datetime)?.timestamp()
+                };
+                DateTimeAnswer {
+                    value: timestamp.to_string(),
+                    subtitle: format!(
+                        "Unix timestamp for {} {}",
+                        datetime.format("%Y-%m-%d %H:%M:%S"),
+                        if utc { "UTC" } else { "local time" }
+                    ),
+                }
+            }
+        };
+
+        Some(answer)
+    }
+
+    /// Converts an answer to SearchResult
+    fn create_search_result(&self, query: &str, answer: DateTimeAnswer) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("query".to_string(), serde_json::json!(query));
+        metadata.insert("formatted_value".to_string(), serde_json::json!(answer.value));
+
+        SearchResult {
+            id: format!("datetime:{}", query.to_lowercase()),
+            title: answer.value.clone(),
+            subtitle: answer.subtitle,
+            icon: Some("datetime".to_string()),
+            result_type: ResultType::DateTime,
+            score: 100.0,
+            metadata,
+            action: ResultAction::CopyToClipboard {
+                content: answer.value,
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for DateTimeProvider {
+    fn name(&self) -> &str {
+        "DateTime"
+    }
+
+    fn priority(&self) -> u8 {
+        88 // Just below the calculator
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::DateTime])
+    }
+
+    fn supports_peek(&self) -> bool {
+        true
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
+
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match self.evaluate(trimmed, &Local::now()) {
+            Some(answer) => {
+                debug!("Date/time query '{}' evaluated to '{}'", trimmed, answer.value);
+                Ok(vec![self.create_search_result(trimmed, answer)])
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::DateTime {
+            return Err(LauncherError::ExecutionError(
+                "Not a date/time result".to_string(),
+            ));
+        }
+
+        match &result.action {
+            ResultAction::CopyToClipboard { content } => {
+                info!("Copying date/time result to clipboard: {}", content);
+                set_clipboard_text(content).await
+            }
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for date/time result".to_string(),
+            )),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("DateTimeProvider initialized with {} aliases", self.aliases.len());
+        Ok(())
+    }
+}
+
+impl Default for DateTimeProvider {
+    fn default() -> Self {
+        Self::new().unwrap_or_else(|_| Self {
+            enabled: false,
+            aliases: HashMap::new(),
+        })
+    }
+}
+
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_amount(s: &str) -> Option<i64> {
+    match s {
+        "a" | "an" | "one" => Some(1),
+        _ if s.len() <= 6 && s.chars().all(|c| c.is_ascii_digit()) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Parses the argument of `epoch`/`unix`: either a timestamp or a date
+fn parse_epoch_argument(rest: &[&str]) -> Option<DateTimeQuery> {
+    if let [number] = rest {
+        let digits = number.strip_prefix('-').unwrap_or(number);
+        if !digits.is_empty() && digits.len() <= 13 && digits.chars().all(|c| c.is_ascii_digit()) {
+            return number.parse().ok().map(DateTimeQuery::FromEpoch);
+        }
+    }
+
+    let (rest, utc) = match rest {
+        [head @ .., "utc" | "gmt" | "z"] => (head, true),
+        _ => (rest, false),
+    };
+
+    let datetime = match rest {
+        [date] => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)?,
+        [date, time] => {
+            let joined = format!("{} {}", date, time);
+            NaiveDateTime::parse_from_str(&joined, "%Y-%m-%d %H:%M:%S")
+                .or_else(|_| NaiveDateTime::parse_from_str(&joined, "%Y-%m-%d %H:%M"))
+                .ok()?
+        }
+        _ => return None,
+    };
+
+    Some(DateTimeQuery::ToEpoch { datetime, utc })
+}
+
+/// Resolves a wall-clock time in a zone, skipping forward over DST gaps
+fn resolve_local<Z: TimeZone>(tz: &Z, naive: NaiveDateTime) -> Option<DateTime<Z>> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| tz.from_local_datetime(&(naive + Duration::hours(1))).earliest())
+}
+
+fn date_answer(date: NaiveDate, today: NaiveDate) -> DateTimeAnswer {
+    let days = (date - today).num_days();
+    let relative = match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        d if d > 0 => format!("in {} days", d),
+        d => format!("{} days ago", -d),
+    };
+
+    DateTimeAnswer {
+        value: date.format("%A, %d %B %Y").to_string(),
+        subtitle: format!("{} · {}", date.format("%Y-%m-%d"), relative),
+    }
+}
+
+fn relative_label(amount: i64, unit: TimeUnit) -> String {
+    let name = match unit {
+        TimeUnit::Minutes => "minute",
+        TimeUnit::Hours => "hour",
+        TimeUnit::Days => "day",
+        TimeUnit::Weeks => "week",
+        TimeUnit::Months => "month",
+        TimeUnit::Years => "year",
+    };
+    let count = amount.abs();
+    let plural = if count == 1 { "" } else { "s" };
+
+    if amount >= 0 {
+        format!("in {} {}{}", count, name, plural)
+    } else {
+        format!("{} {}{} ago", count, name, plural)
+    }
+}
+
+/// Describes an offset difference in seconds relative to local time
+fn describe_delta(seconds: i32) -> String {
+    if seconds == 0 {
+        return "same as local time".to_string();
+    }
+
+    let direction = if seconds > 0 { "ahead of" } else { "behind" };
+    let total_minutes = seconds.abs() / 60;
+    let (hours, minutes) = (total_minutes / 60, total_minutes % 60);
+
+    let amount = match (hours, minutes) {
+        (1, 0) => "1 hour".to_string(),
+        (h, 0) => format!("{} hours", h),
+        (0, m) => format!("{} minutes", m),
+        (h, m) => format!("{}h {}m", h, m),
+    };
+
+    format!("{} {} local time", amount, direction)
+}
+
+fn title_case(label: &str) -> String {
+    label
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+    use chrono_tz::Europe::London;
+
+    fn provider() -> DateTimeProvider {
+        DateTimeProvider::new().unwrap()
+    }
+
+    #[test]
+    fn test_provider_creation() {
+        let provider = provider();
+        assert_eq!(provider.name(), "DateTime");
+        assert_eq!(provider.priority(), 88);
+        assert!(provider.is_enabled());
+    }
+
+    #[test]
+    fn test_city_table_entries_are_valid() {
+        for (city, zone) in CITY_TIMEZONES {
+            assert!(zone.parse::<Tz>().is_ok(), "{} maps to unknown zone {}", city, zone);
+            assert_eq!(*city, city.to_lowercase(), "alias '{}' must be lowercase", city);
+        }
+        assert!(CITY_TIMEZONES.len() >= 300);
+    }
+
+    #[test]
+    fn test_resolve_zone() {
+        let provider = provider();
+        assert_eq!(provider.resolve_zone("tokyo"), Some(chrono_tz::Asia::Tokyo));
+        assert_eq!(provider.resolve_zone("nyc"), Some(New_York));
+        assert_eq!(provider.resolve_zone("America/Los_Angeles"), Some(chrono_tz::America::Los_Angeles));
+        assert_eq!(provider.resolve_zone("america/argentina/salta"), Some(chrono_tz::America::Argentina::Salta));
+        assert_eq!(provider.resolve_zone("ulaanbaatar"), Some(chrono_tz::Asia::Ulaanbaatar));
+        assert_eq!(provider.resolve_zone("port_moresby"), Some(chrono_tz::Pacific::Port_Moresby));
+        assert_eq!(provider.resolve_zone("atlantis"), None);
+        assert_eq!(provider.resolve_zone(""), None);
+    }
+
+    #[test]
+    fn test_time_in_city() {
+        let provider = provider();
+        let now = London.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+
+        let answer = provider.evaluate("time in Tokyo", &now).unwrap();
+        assert_eq!(answer.value, "20:00 JST, Sat 01 Jun 2024");
+        assert!(answer.subtitle.contains("Asia/Tokyo"));
+        assert!(answer.subtitle.contains("UTC+09:00"));
+        assert!(answer.subtitle.contains("8 hours ahead of local time"));
+
+        let answer = provider.evaluate("new york time", &now).unwrap();
+        assert_eq!(answer.value, "07:00 EDT, Sat 01 Jun 2024");
+        assert!(answer.subtitle.contains("5 hours behind local time"));
+
+        let answer = provider.evaluate("time in london", &now).unwrap();
+        assert!(answer.subtitle.contains("same as local time"));
+
+        let answer = provider.evaluate("time in kathmandu", &now).unwrap();
+        assert!(answer.subtitle.contains("4h 45m ahead of local time"));
+    }
+
+    #[test]
+    fn test_time_in_across_us_dst_transition() {
+        let provider = provider();
+
+        // 2024-03-10 is the US spring-forward date; London stays on GMT until 2024-03-31
+        let before = Utc.with_ymd_and_hms(2024, 3, 9, 17, 0, 0).unwrap().with_timezone(&London);
+        let after = Utc.with_ymd_and_hms(2024, 3, 11, 17, 0, 0).unwrap().with_timezone(&London);
+
+        let answer = provider.evaluate("time in new york", &before).unwrap();
+        assert_eq!(answer.value, "12:00 EST, Sat 09 Mar 2024");
+        assert!(answer.subtitle.contains("UTC-05:00"));
+        assert!(answer.subtitle.contains("5 hours behind local time"));
+
+        let answer = provider.evaluate("time in new york", &after).unwrap();
+        assert_eq!(answer.value, "13:00 EDT, Mon 11 Mar 2024");
+        assert!(answer.subtitle.contains("UTC-04:00"));
+        assert!(answer.subtitle.contains("4 hours behind local time"));
+    }
+
+    #[test]
+    fn test_now_and_relative_days() {
+        let provider = provider();
+        let now = New_York.with_ymd_and_hms(2024, 2, 28, 9, 30, 15).unwrap();
+
+        let answer = provider.evaluate("now", &now).unwrap();
+        assert_eq!(answer.value, "09:30:15, Wed 28 Feb 2024");
+        assert!(answer.subtitle.contains("UTC-05:00"));
+
+        assert_eq!(provider.evaluate("today", &now).unwrap().value, "Wednesday, 28 February 2024");
+        assert_eq!(provider.evaluate("Tomorrow", &now).unwrap().value, "Thursday, 29 February 2024");
+        assert_eq!(provider.evaluate("yesterday", &now).unwrap().value, "Tuesday, 27 February 2024");
+    }
+
+    #[test]
+    fn test_next_and_last_weekday() {
+        let provider = provider();
+        // Friday
+        let now = New_York.with_ymd_and_hms(2024, 3, 8, 9, 0, 0).unwrap();
+
+        assert_eq!(provider.evaluate("next friday", &now).unwrap().value, "Friday, 15 March 2024");
+        assert_eq!(provider.evaluate("next mon", &now).unwrap().value, "Monday, 11 March 2024");
+        assert_eq!(provider.evaluate("last friday", &now).unwrap().value, "Friday, 01 March 2024");
+        assert_eq!(provider.evaluate("last thursday", &now).unwrap().value, "Thursday, 07 March 2024");
+    }
+
+    #[test]
+    fn test_relative_offsets() {
+        let provider = provider();
+        let now = New_York.with_ymd_and_hms(2024, 1, 31, 9, 0, 0).unwrap();
+
+        assert_eq!(provider.evaluate("in 3 weeks", &now).unwrap().value, "Wednesday, 21 February 2024");
+        assert_eq!(provider.evaluate("in a month", &now).unwrap().value, "Thursday, 29 February 2024");
+        assert_eq!(provider.evaluate("2 years ago", &now).unwrap().value, "Monday, 31 January 2022");
+        assert_eq!(provider.evaluate("10 days ago", &now).unwrap().value, "Sunday, 21 January 2024");
+        assert_eq!(provider.evaluate("in 90 minutes", &now).unwrap().value, "10:30, Wed 31 Jan 2024");
+    }
+
+    #[test]
+    fn test_hour_offset_across_dst_gap() {
+        let provider = provider();
+        // 01:30 EST on the spring-forward date; two elapsed hours land at 04:30 EDT
+        let now = New_York.with_ymd_and_hms(2024, 3, 10, 1, 30, 0).unwrap();
+
+        let answer = provider.evaluate("in 2 hours", &now).unwrap();
+        assert_eq!(answer.value, "04:30, Sun 10 Mar 2024");
+        assert!(answer.subtitle.contains("UTC-04:00"));
+    }
+
+    #[test]
+    fn test_epoch_to_date() {
+        let provider = provider();
+        let now = New_York.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        let answer = provider.evaluate("epoch 1700000000", &now).unwrap();
+        assert_eq!(answer.value, "2023-11-14 22:13:20 UTC");
+        assert!(answer.subtitle.contains("Local 2023-11-14 17:13:20"));
+
+        let answer = provider.evaluate("unix 1700000000123", &now).unwrap();
+        assert_eq!(answer.value, "2023-11-14 22:13:20 UTC");
+        assert!(answer.subtitle.contains("milliseconds"));
+    }
+
+    #[test]
+    fn test_date_to_epoch() {
+        let provider = provider();
+        let now = New_York.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(provider.evaluate("epoch 2023-11-14 22:13:20 utc", &now).unwrap().value, "1700000000");
+        assert_eq!(provider.evaluate("epoch 1970-01-01 UTC", &now).unwrap().value, "0");
+        // Local time in New York is EDT (UTC-4) in July
+        assert_eq!(provider.evaluate("epoch 2024-07-01 20:00", &now).unwrap().value, "1719878400");
+        // 02:30 does not exist on 2024-03-10 in New York; resolves to 03:30 EDT
+        assert_eq!(provider.evaluate("epoch 2024-03-10 02:30", &now).unwrap().value, "1710055800");
+    }
+
+    #[test]
+    fn test_current_epoch() {
+        let provider = provider();
+        let now = Utc.with_ymd_and_hms(2023, 11, 14, 22, 13, 20).unwrap();
+
+        assert_eq!(provider.evaluate("epoch", &now).unwrap().value, "1700000000");
+        assert_eq!(provider.evaluate("unix timestamp", &now).unwrap().value, "1700000000");
+    }
+
+    #[test]
+    fn test_non_matching_queries() {
+        let provider = provider();
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        for query in [
+            "timer",
+            "now playing.mp3",
+            "nowhere",
+            "notes from now",
+            "time in atlantis",
+            "time tracker",
+            "next",
+            "next steps",
+            "in 3 apples",
+            "epoch notes",
+            "epoch 2024-13-01",
+            "2 + 2",
+            "todays report",
+            "",
+        ] {
+            assert!(provider.evaluate(query, &now).is_none(), "'{}' should not match", query);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_returns_copy_result() {
+        let provider = provider();
+
+        let results = provider.search(&"tomorrow".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result_type, ResultType::DateTime);
+        assert!(results[0].metadata.contains_key("formatted_value"));
+        assert!(matches!(
+            &results[0].action,
+            ResultAction::CopyToClipboard { content } if *content == results[0].title
+        ));
+
+        assert!(provider.search(&"timer".into()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_result_type() {
+        let provider = provider();
+        let result = SearchResult {
+            id: "test".to_string(),
+            title: "Test".to_string(),
+            subtitle: "Test".to_string(),
+            icon: None,
+            result_type: ResultType::File,
+            score: 100.0,
+            metadata: HashMap::new(),
+            action: ResultAction::OpenFile {
+                path: "test".to_string(),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        };
+
+        assert!(provider.execute(&result).await.is_err());
+    }
+}
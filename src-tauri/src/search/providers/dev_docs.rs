@@ -0,0 +1,511 @@
+/// Developer documentation provider for prefixed queries
+///
+/// `rust Vec::retain`, `mdn array.map`, `py requests.get`, `cpp std::vector`
+/// and `node fs.readFile` each offer one result opening the matching docs
+/// site. Deep links are used where the site's URL pattern allows it, e.g.
+/// Node.js module pages; otherwise the link runs the site's own search.
+///
+/// With `lookup_titles` on, sites with a public search API (MDN and
+/// cppreference) are asked for their top hit, whose title becomes the
+/// subtitle. The call gives up after a second and results are cached, so an
+/// offline machine still gets the plain link.
+
+use crate::error::{LauncherError, Result};
+use crate::search::provider_config::{ConfigFlag, ConfigOption, ProviderConfig};
+use crate::search::providers::bookmark::BookmarkProvider;
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// How long a top hit lookup may take before the plain link is shown
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Number of looked-up titles kept
+const TITLE_CACHE_SIZE: usize = 200;
+
+/// Query prefixes routed to this provider
+pub const DEV_DOCS_KEYWORDS: &[&str] = &["rust", "mdn", "py", "cpp", "node"];
+
+/// Help entry for the docs prefixes, also listed before the lazy provider is built
+pub const DEV_DOCS_SYNTAX: &[SyntaxFeature] = &[SyntaxFeature {
+    id: "dev_docs",
+    title: "Search developer docs",
+    description: "Start with rust, mdn, py, cpp or node",
+    example: "rust Vec::retain",
+    token: None,
+    values: &[],
+}];
+
+/// Node.js core modules, which have a page at `nodejs.org/api/<module>.html`
+const NODE_MODULES: &[&str] = &[
+    "assert", "buffer", "child_process", "cluster", "crypto", "dgram", "dns", "events", "fs",
+    "http", "http2", "https", "module", "net", "os", "path", "perf_hooks", "process", "querystring",
+    "readline", "stream", "string_decoder", "timers", "tls", "url", "util", "v8", "vm",
+    "worker_threads", "zlib",
+];
+
+/// Options under `provider_settings.dev_docs`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DevDocsConfig {
+    /// Whether the top hit's title is fetched from the site's search API
+    pub lookup_titles: bool,
+}
+
+impl ProviderConfig for DevDocsConfig {
+    const KEY: &'static str = "dev_docs";
+    const PROVIDER: &'static str = "DevDocs";
+    const OPTIONS: &'static [ConfigOption] = &[];
+    const FLAGS: &'static [ConfigFlag] = &[ConfigFlag {
+        field: "lookup_titles",
+        description: "Show the top match from the docs site's search (needs internet)",
+    }];
+}
+
+/// A documentation site reached through a query prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocSite {
+    Rust,
+    Mdn,
+    Python,
+    Cpp,
+    Node,
+}
+
+impl DocSite {
+    pub const ALL: [DocSite; 5] = [DocSite::Rust, DocSite::Mdn, DocSite::Python, DocSite::Cpp, DocSite::Node];
+
+    /// Word that routes a query to this site
+    pub fn prefix(self) -> &'static str {
+        match self {
+            DocSite::Rust => "rust",
+            DocSite::Mdn => "mdn",
+            DocSite::Python => "py",
+            DocSite::Cpp => "cpp",
+            DocSite::Node => "node",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            DocSite::Rust => "Rust std docs",
+            DocSite::Mdn => "MDN",
+            DocSite::Python => "Python docs",
+            DocSite::Cpp => "cppreference",
+            DocSite::Node => "Node.js docs",
+        }
+    }
+
+    /// Page answering `query`: a deep link where the URL pattern is known,
+    /// otherwise the site's search
+    pub fn docs_url(self, query: &str) -> String {
+        let encoded = urlencoding::encode(query);
+        match self {
+            DocSite::Rust => format!("https://doc.rust-lang.org/std/index.html?search={}", encoded),
+            DocSite::Mdn => format!("https://developer.mozilla.org/en-US/search?q={}", encoded),
+            DocSite::Python => format!("https://docs.python.org/3/search.html?q={}", encoded),
+            // MediaWiki jumps straight to a page whose title matches exactly
+            DocSite::Cpp => format!("https://en.cppreference.com/mwiki/index.php?search={}", encoded),
+            DocSite::Node => match node_module(query) {
+                Some(module) => format!("https://nodejs.org/api/{}.html", module),
+                None => format!(
+                    "https://www.google.com/search?q={}",
+                    urlencoding::encode(&format!("site:nodejs.org/api {}", query))
+                ),
+            },
+        }
+    }
+
+    /// Search API returning the site's top hit, if the site has a public one
+    pub fn lookup_url(self, query: &str) -> Option<String> {
+        let encoded = urlencoding::encode(query);
+        match self {
+            DocSite::Mdn => Some(format!(
+                "https://developer.mozilla.org/api/v1/search?q={}&locale=en-US&size=1",
+                encoded
+            )),
+            DocSite::Cpp => Some(format!(
+                "https://en.cppreference.com/mwiki/api.php?action=opensearch&format=json&limit=1&search={}",
+                encoded
+            )),
+            DocSite::Rust | DocSite::Python | DocSite::Node => None,
+        }
+    }
+
+    /// Reads the top hit's title from a `lookup_url` response
+    pub fn top_hit_title(self, response: &serde_json::Value) -> Option<String> {
+        let title = match self {
+            DocSite::Mdn => response.pointer("/documents/0/title"),
+            // OpenSearch answers [query, [titles], [descriptions], [urls]]
+            DocSite::Cpp => response.pointer("/1/0"),
+            DocSite::Rust | DocSite::Python | DocSite::Node => None,
+        }?;
+        title.as_str().map(str::to_string).filter(|title| !title.is_empty())
+    }
+}
+
+/// The core module a Node.js query names, e.g. `fs` for `fs.readFile`
+fn node_module(query: &str) -> Option<&'static str> {
+    let first = query
+        .trim_start_matches("node:")
+        .split(['.', ' ', '('])
+        .next()?
+        .to_ascii_lowercase();
+    NODE_MODULES.iter().copied().find(|module| *module == first)
+}
+
+/// Splits a docs query into its site and the text to look up
+pub fn parse_docs_query(query: &str) -> Option<(DocSite, &str)> {
+    let (prefix, rest) = query.trim().split_once(char::is_whitespace)?;
+    let site = DocSite::ALL
+        .into_iter()
+        .find(|site| site.prefix().eq_ignore_ascii_case(prefix))?;
+    let rest = rest.trim();
+    (!rest.is_empty()).then_some((site, rest))
+}
+
+/// Fetches JSON from a docs site's search API
+#[async_trait]
+pub trait DocsFetcher: Send + Sync {
+    async fn fetch_json(&self, url: &str) -> Result<serde_json::Value>;
+}
+
+/// Fetches over HTTPS with reqwest
+pub struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpFetcher {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(LOOKUP_TIMEOUT)
+            .build()
+            .map_err(|e| LauncherError::SearchError(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl DocsFetcher for HttpFetcher {
+    async fn fetch_json(&self, url: &str) -> Result<serde_json::Value> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| LauncherError::SearchError(format!("Docs lookup failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(LauncherError::SearchError(format!(
+                "Docs lookup returned {}",
+                response.status()
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| LauncherError::SearchError(format!("Invalid docs lookup response: {}", e)))
+    }
+}
+
+/// Developer documentation provider
+pub struct DevDocsProvider {
+    fetcher: Arc<dyn DocsFetcher>,
+    lookup_titles: AtomicBool,
+    lookup_timeout: Duration,
+    /// Top hit titles by site and query; `None` when the site found nothing
+    title_cache: Mutex<LruCache<(DocSite, String), Option<String>>>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl DevDocsProvider {
+    /// Creates a new DevDocsProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing DevDocsProvider");
+        Ok(Self::with_fetcher(Arc::new(HttpFetcher::new()?)))
+    }
+
+    /// Creates a provider looking up top hits through `fetcher`
+    pub fn with_fetcher(fetcher: Arc<dyn DocsFetcher>) -> Self {
+        Self {
+            fetcher,
+            lookup_titles: AtomicBool::new(false),
+            lookup_timeout: LOOKUP_TIMEOUT,
+            title_cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(TITLE_CACHE_SIZE).expect("cache size is not zero"),
+            )),
+            enabled: true,
+        }
+    }
+
+    /// Sets how long a top hit lookup may take
+    pub fn with_lookup_timeout(mut self, timeout: Duration) -> Self {
+        self.lookup_timeout = timeout;
+        self
+    }
+
+    /// Title of the site's top hit, or `None` when off, offline or not found
+    async fn top_hit_title(&self, site: DocSite, query: &str) -> Option<String> {
+        if !self.lookup_titles.load(Ordering::Relaxed) {
+            return None;
+        }
+        let url = site.lookup_url(query)?;
+
+        let key = (site, query.to_lowercase());
+        if let Some(cached) = self.title_cache.lock().await.get(&key) {
+            return cached.clone();
+        }
+
+        // Failures aren't cached, so the titles appear once back online
+        let response = match tokio::time::timeout(self.lookup_timeout, self.fetcher.fetch_json(&url)).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                debug!("{} lookup for '{}' failed: {}", site.display_name(), query, e);
+                return None;
+            }
+            Err(_) => {
+                debug!("{} lookup for '{}' timed out", site.display_name(), query);
+                return None;
+            }
+        };
+
+        let title = site.top_hit_title(&response);
+        self.title_cache.lock().await.put(key, title.clone());
+        title
+    }
+
+    fn create_search_result(site: DocSite, query: &str, top_hit: Option<String>) -> SearchResult {
+        let url = site.docs_url(query);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("url".to_string(), serde_json::json!(url));
+        metadata.insert("site".to_string(), serde_json::json!(site.prefix()));
+
+        let subtitle = match top_hit {
+            Some(title) => {
+                metadata.insert("top_hit".to_string(), serde_json::json!(title));
+                format!("Top match: {}", title)
+            }
+            None => url.clone(),
+        };
+
+        SearchResult {
+            id: format!("dev_docs:{}:{}", site.prefix(), query),
+            title: format!("{}: {}", site.display_name(), query),
+            subtitle,
+            icon: Some("web".to_string()),
+            result_type: ResultType::Url,
+            score: 95.0,
+            metadata,
+            action: ResultAction::OpenUrl { url },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for DevDocsProvider {
+    fn name(&self) -> &str {
+        "DevDocs"
+    }
+
+    fn priority(&self) -> u8 {
+        75
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Url])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let Some((site, text)) = parse_docs_query(&query.query) else {
+            return Ok(Vec::new());
+        };
+
+        let top_hit = self.top_hit_title(site, text).await;
+        Ok(vec![Self::create_search_result(site, text, top_hit)])
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if !result.id.starts_with("dev_docs:") {
+            return Err(LauncherError::ExecutionError("Not a docs result".to_string()));
+        }
+
+        match &result.action {
+            ResultAction::OpenUrl { url } => {
+                info!("Opening docs: {}", url);
+                BookmarkProvider::open_url(url).await
+            }
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for docs result".to_string(),
+            )),
+        }
+    }
+
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        let config = DevDocsConfig::parse(config)?;
+        self.lookup_titles.store(config.lookup_titles, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        DEV_DOCS_SYNTAX.to_vec()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Answers every lookup with `response`, or fails when it is `None`
+    struct FakeFetcher {
+        response: Option<serde_json::Value>,
+        delay: Duration,
+        calls: AtomicUsize,
+    }
+
+    impl FakeFetcher {
+        fn answering(response: Option<serde_json::Value>) -> Arc<Self> {
+            Arc::new(Self {
+                response,
+                delay: Duration::ZERO,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DocsFetcher for FakeFetcher {
+        async fn fetch_json(&self, _url: &str) -> Result<serde_json::Value> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.response
+                .clone()
+                .ok_or_else(|| LauncherError::SearchError("offline".to_string()))
+        }
+    }
+
+    async fn provider_with(fetcher: Arc<FakeFetcher>) -> DevDocsProvider {
+        let provider = DevDocsProvider::with_fetcher(fetcher);
+        provider.configure(&serde_json::json!({ "lookup_titles": true })).await.unwrap();
+        provider
+    }
+
+    #[test]
+    fn test_parse_docs_query() {
+        assert_eq!(parse_docs_query("rust Vec::retain"), Some((DocSite::Rust, "Vec::retain")));
+        assert_eq!(parse_docs_query("MDN  array.map "), Some((DocSite::Mdn, "array.map")));
+        assert_eq!(parse_docs_query("py requests.get"), Some((DocSite::Python, "requests.get")));
+
+        // Prefixes alone, inside words or without a space never match
+        assert_eq!(parse_docs_query("rust"), None);
+        assert_eq!(parse_docs_query("rusty nail"), None);
+        assert_eq!(parse_docs_query("python docs"), None);
+        assert_eq!(parse_docs_query("cpp"), None);
+    }
+
+    #[test]
+    fn test_docs_urls_encode_special_characters() {
+        assert_eq!(
+            DocSite::Rust.docs_url("Vec::retain"),
+            "https://doc.rust-lang.org/std/index.html?search=Vec%3A%3Aretain"
+        );
+        assert_eq!(
+            DocSite::Rust.docs_url("Option<T>"),
+            "https://doc.rust-lang.org/std/index.html?search=Option%3CT%3E"
+        );
+        assert_eq!(
+            DocSite::Mdn.docs_url("array.map"),
+            "https://developer.mozilla.org/en-US/search?q=array.map"
+        );
+        assert_eq!(
+            DocSite::Mdn.docs_url("#private fields"),
+            "https://developer.mozilla.org/en-US/search?q=%23private%20fields"
+        );
+        assert_eq!(
+            DocSite::Python.docs_url("requests.get"),
+            "https://docs.python.org/3/search.html?q=requests.get"
+        );
+        assert_eq!(
+            DocSite::Cpp.docs_url("std::vector<int>"),
+            "https://en.cppreference.com/mwiki/index.php?search=std%3A%3Avector%3Cint%3E"
+        );
+        assert_eq!(DocSite::Node.docs_url("fs.readFile"), "https://nodejs.org/api/fs.html");
+        assert_eq!(DocSite::Node.docs_url("node:child_process"), "https://nodejs.org/api/child_process.html");
+        assert_eq!(
+            DocSite::Node.docs_url("express#router"),
+            "https://www.google.com/search?q=site%3Anodejs.org%2Fapi%20express%23router"
+        );
+    }
+
+    #[test]
+    fn test_top_hit_titles_from_api_responses() {
+        let mdn = serde_json::json!({ "documents": [{ "title": "Array.prototype.map()", "mdn_url": "/en-US/docs/x" }] });
+        assert_eq!(DocSite::Mdn.top_hit_title(&mdn).as_deref(), Some("Array.prototype.map()"));
+        assert_eq!(DocSite::Mdn.top_hit_title(&serde_json::json!({ "documents": [] })), None);
+
+        let cpp = serde_json::json!(["std::vector", ["cpp/container/vector"], [""], ["https://en.cppreference.com/w/cpp/container/vector"]]);
+        assert_eq!(DocSite::Cpp.top_hit_title(&cpp).as_deref(), Some("cpp/container/vector"));
+        assert_eq!(DocSite::Rust.lookup_url("Vec"), None);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_adds_cached_subtitle() {
+        let fetcher = FakeFetcher::answering(Some(serde_json::json!({ "documents": [{ "title": "Array.prototype.map()" }] })));
+        let provider = provider_with(fetcher.clone()).await;
+
+        let results = provider.search(&"mdn array.map".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subtitle, "Top match: Array.prototype.map()");
+        assert!(matches!(&results[0].action, ResultAction::OpenUrl { url } if url.contains("q=array.map")));
+
+        provider.search(&"mdn Array.map".into()).await.unwrap();
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_or_slow_lookup_falls_back_to_plain_link() {
+        let provider = provider_with(FakeFetcher::answering(None)).await;
+        let results = provider.search(&"cpp std::vector".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].subtitle, DocSite::Cpp.docs_url("std::vector"));
+
+        let slow = Arc::new(FakeFetcher {
+            response: Some(serde_json::json!({ "documents": [{ "title": "late" }] })),
+            delay: Duration::from_millis(500),
+            calls: AtomicUsize::new(0),
+        });
+        let provider = provider_with(slow).await.with_lookup_timeout(Duration::from_millis(20));
+        let results = provider.search(&"mdn fetch".into()).await.unwrap();
+        assert_eq!(results[0].subtitle, DocSite::Mdn.docs_url("fetch"));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_is_off_by_default_and_normal_queries_are_ignored() {
+        let fetcher = FakeFetcher::answering(Some(serde_json::json!({ "documents": [{ "title": "x" }] })));
+        let provider = DevDocsProvider::with_fetcher(fetcher.clone());
+
+        let results = provider.search(&"mdn array.map".into()).await.unwrap();
+        assert_eq!(results[0].subtitle, DocSite::Mdn.docs_url("array.map"));
+        assert_eq!(fetcher.calls.load(Ordering::SeqCst), 0);
+
+        assert!(provider.search(&"array.map".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"rustacean".into()).await.unwrap().is_empty());
+    }
+}
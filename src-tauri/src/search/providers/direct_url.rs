@@ -0,0 +1,393 @@
+/// Direct URL provider for addresses typed straight into the search bar
+///
+/// Recognizes full URLs, bare domains such as `github.com/rust-lang/rust`,
+/// `localhost:3000`, IPv4 addresses with optional ports and email addresses,
+/// and offers to open them instead of searching the web for them.
+///
+/// Bare domains must end in a known top-level domain, so `report.pdf` stays a
+/// file name. Two-part names whose TLD doubles as a common file extension
+/// (`main.rs`, `notes.md`) are only treated as URLs with a scheme or a path.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::bookmark::BookmarkProvider;
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// Top-level domains accepted on bare domains and email addresses
+const KNOWN_TLDS: &[&str] = &[
+    "com", "net", "org", "edu", "gov", "mil", "int", "info", "biz", "io", "co", "ai", "app", "dev",
+    "page", "me", "tv", "cc", "xyz", "online", "site", "tech", "store", "blog", "cloud", "news",
+    "shop", "art", "ly", "gg", "fm", "gl", "to", "ws", "eu", "asia", "us", "uk", "ca", "au", "nz",
+    "ie", "de", "fr", "es", "it", "nl", "be", "ch", "at", "se", "no", "dk", "fi", "is", "pl", "pt",
+    "br", "ar", "mx", "cl", "jp", "cn", "kr", "in", "ru", "ua", "tr", "gr", "cz", "sk", "hu", "ro",
+    "bg", "hr", "si", "rs", "lt", "lv", "ee", "lu", "md", "sh", "py", "zip", "mov", "ps", "ml",
+    "so", "mk", "za", "sg", "hk", "tw", "il", "id", "my", "ph", "th", "vn", "pk", "ng", "ke", "eg",
+    "sa", "ae",
+];
+
+/// Known TLDs that are also common file extensions
+const FILE_LIKE_TLDS: &[&str] = &["zip", "mov", "py", "rs", "md", "sh", "pl", "ps", "ai", "cc", "ml", "so", "mk"];
+
+/// What a query points at directly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DirectTarget {
+    /// A web address, always with a scheme
+    Url(String),
+    /// An email address, without `mailto:`
+    Email(String),
+}
+
+impl DirectTarget {
+    /// Detects a URL or email address filling the whole query
+    pub fn detect(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if query.is_empty() || query.chars().any(char::is_whitespace) {
+            return None;
+        }
+
+        if let Some(address) = strip_prefix_ignore_case(query, "mailto:") {
+            return is_email(address).then(|| DirectTarget::Email(address.to_string()));
+        }
+
+        if is_email(query) {
+            return Some(DirectTarget::Email(query.to_string()));
+        }
+
+        detect_url(query).map(DirectTarget::Url)
+    }
+
+    /// The address handed to the shell
+    pub fn target(&self) -> String {
+        match self {
+            DirectTarget::Url(url) => url.clone(),
+            DirectTarget::Email(address) => format!("mailto:{}", address),
+        }
+    }
+}
+
+/// How a host name was recognized
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HostKind {
+    Localhost,
+    Ipv4,
+    /// A domain ending in a known TLD
+    Domain,
+    /// Any other syntactically valid host, only accepted after a scheme
+    Other,
+}
+
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    text.get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .map(|_| &text[prefix.len()..])
+}
+
+/// Parses a URL with or without a scheme, returning it with one
+///
+/// Domains get `https://`; localhost and IP addresses, which are usually
+/// development servers or devices without certificates, get `http://`.
+fn detect_url(query: &str) -> Option<String> {
+    let (has_scheme, rest) = match strip_prefix_ignore_case(query, "https://")
+        .or_else(|| strip_prefix_ignore_case(query, "http://"))
+    {
+        Some(rest) => (true, rest),
+        None => (false, query),
+    };
+
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+
+    // Credentials in the authority are either a typo or an attempt to disguise the host
+    if authority.contains('@') {
+        return None;
+    }
+
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (authority, None),
+    };
+
+    if let Some(port) = port {
+        if port.is_empty() || !port.chars().all(|c| c.is_ascii_digit()) || port.parse::<u16>().ok()? == 0 {
+            return None;
+        }
+    }
+
+    let kind = classify_host(host)?;
+    if has_scheme {
+        return Some(query.to_string());
+    }
+
+    match kind {
+        HostKind::Other => None,
+        HostKind::Domain if port.is_none() && tail.is_empty() && looks_like_file_name(host) => None,
+        HostKind::Domain => Some(format!("https://{}", query)),
+        HostKind::Localhost | HostKind::Ipv4 => Some(format!("http://{}", query)),
+    }
+}
+
+fn classify_host(host: &str) -> Option<HostKind> {
+    if host.eq_ignore_ascii_case("localhost") {
+        return Some(HostKind::Localhost);
+    }
+    if is_ipv4(host) {
+        return Some(HostKind::Ipv4);
+    }
+
+    let labels: Vec<&str> = host.split('.').collect();
+    if !labels.iter().all(|label| is_host_label(label)) {
+        return None;
+    }
+
+    let tld = labels[labels.len() - 1].to_ascii_lowercase();
+    if labels.len() >= 2 && KNOWN_TLDS.contains(&tld.as_str()) {
+        Some(HostKind::Domain)
+    } else {
+        Some(HostKind::Other)
+    }
+}
+
+fn is_host_label(label: &str) -> bool {
+    !label.is_empty()
+        && label.len() <= 63
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn is_ipv4(host: &str) -> bool {
+    let octets: Vec<&str> = host.split('.').collect();
+    octets.len() == 4
+        && octets.iter().all(|octet| {
+            (1..=3).contains(&octet.len())
+                && octet.chars().all(|c| c.is_ascii_digit())
+                && octet.parse::<u8>().is_ok()
+        })
+}
+
+/// Whether a bare `name.ext` reads more like a file than a site
+fn looks_like_file_name(host: &str) -> bool {
+    match host.split_once('.') {
+        Some((_, tld)) if !tld.contains('.') => FILE_LIKE_TLDS.contains(&tld.to_ascii_lowercase().as_str()),
+        _ => false,
+    }
+}
+
+fn is_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else {
+        return false;
+    };
+
+    let local_valid = !local.is_empty()
+        && local.len() <= 64
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+/=?^_`{|}~.-".contains(c));
+
+    local_valid && classify_host(domain) == Some(HostKind::Domain)
+}
+
+/// Direct URL search provider
+pub struct DirectUrlProvider {
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl DirectUrlProvider {
+    /// Creates a new DirectUrlProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing DirectUrlProvider");
+        Ok(Self { enabled: true })
+    }
+
+    /// Creates the result offering to open the typed address
+    fn create_search_result(&self, query: &str, target: &DirectTarget) -> SearchResult {
+        let address = target.target();
+        let (kind, subtitle) = match target {
+            DirectTarget::Url(url) => ("url", format!("Open URL · {}", url)),
+            DirectTarget::Email(_) => ("email", "Compose email".to_string()),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!(kind));
+        metadata.insert("url".to_string(), serde_json::json!(address));
+
+        SearchResult {
+            id: format!("direct_url:{}", address),
+            title: query.to_string(),
+            subtitle,
+            icon: None,
+            result_type: ResultType::Url,
+            score: 100.0,
+            metadata,
+            action: ResultAction::OpenUrl { url: address },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for DirectUrlProvider {
+    fn name(&self) -> &str {
+        "DirectUrl"
+    }
+
+    fn priority(&self) -> u8 {
+        89 // Between the calculator and date/time
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Url])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
+
+        match DirectTarget::detect(trimmed) {
+            Some(target) => {
+                debug!("Query '{}' is a direct address: {:?}", trimmed, target);
+                Ok(vec![self.create_search_result(trimmed, &target)])
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::Url {
+            return Err(LauncherError::ExecutionError(
+                "Not a URL result".to_string(),
+            ));
+        }
+
+        match &result.action {
+            ResultAction::OpenUrl { url } => {
+                info!("Opening typed address: {}", url);
+                // ShellExecute hands mailto: links to the default mail client
+                BookmarkProvider::open_url(url).await
+            }
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for URL result".to_string(),
+            )),
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Default for DirectUrlProvider {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::providers::WebSearchProvider;
+    use crate::search::SearchEngine;
+
+    fn url(query: &str) -> Option<String> {
+        match DirectTarget::detect(query)? {
+            DirectTarget::Url(url) => Some(url),
+            DirectTarget::Email(_) => None,
+        }
+    }
+
+    #[test]
+    fn test_detects_domains_and_urls() {
+        assert_eq!(url("github.com/rust-lang/rust").as_deref(), Some("https://github.com/rust-lang/rust"));
+        assert_eq!(url("example.co.uk").as_deref(), Some("https://example.co.uk"));
+        assert_eq!(url("www.Example.COM?q=1").as_deref(), Some("https://www.Example.COM?q=1"));
+        assert_eq!(url("docs.rs/tokio").as_deref(), Some("https://docs.rs/tokio"));
+        assert_eq!(url("HTTP://intranet/wiki").as_deref(), Some("HTTP://intranet/wiki"));
+        assert_eq!(url("https://example.com:8443/a").as_deref(), Some("https://example.com:8443/a"));
+
+        assert_eq!(url("github"), None);
+        assert_eq!(url("foo..com"), None);
+        assert_eq!(url("-bad.com"), None);
+        assert_eq!(url("example.com:99999"), None);
+        assert_eq!(url("user:pass@example.com"), None);
+        assert_eq!(url("how to use github.com"), None);
+    }
+
+    #[test]
+    fn test_file_names_are_not_urls() {
+        assert_eq!(url("report.pdf"), None);
+        assert_eq!(url("index.html"), None);
+        assert_eq!(url("main.rs"), None);
+        assert_eq!(url("notes.md"), None);
+        assert_eq!(url("archive.zip"), None);
+        assert_eq!(url("1.2.3"), None);
+
+        // A scheme or path makes the intent clear
+        assert_eq!(url("https://main.rs").as_deref(), Some("https://main.rs"));
+        assert_eq!(url("crates.io/crates/serde").as_deref(), Some("https://crates.io/crates/serde"));
+    }
+
+    #[test]
+    fn test_detects_localhost_and_ipv4() {
+        assert_eq!(url("localhost:3000").as_deref(), Some("http://localhost:3000"));
+        assert_eq!(url("localhost/admin").as_deref(), Some("http://localhost/admin"));
+        assert_eq!(url("192.168.1.10:8080").as_deref(), Some("http://192.168.1.10:8080"));
+        assert_eq!(url("10.0.0.1").as_deref(), Some("http://10.0.0.1"));
+
+        assert_eq!(url("256.1.1.1"), None);
+        assert_eq!(url("192.168.1"), None);
+        assert_eq!(url("localhost:"), None);
+    }
+
+    #[test]
+    fn test_detects_email_addresses() {
+        let email = |query: &str| match DirectTarget::detect(query) {
+            Some(DirectTarget::Email(address)) => Some(address),
+            _ => None,
+        };
+
+        assert_eq!(email("someone@example.com").as_deref(), Some("someone@example.com"));
+        assert_eq!(email("first.last+tag@mail.example.org").as_deref(), Some("first.last+tag@mail.example.org"));
+        assert_eq!(email("MAILTO:someone@example.com").as_deref(), Some("someone@example.com"));
+
+        assert_eq!(email("someone@example"), None);
+        assert_eq!(email("someone@localhost"), None);
+        assert_eq!(email(".someone@example.com"), None);
+        assert_eq!(email("some..one@example.com"), None);
+        assert_eq!(email("a@b@example.com"), None);
+        assert_eq!(email("@example.com"), None);
+        assert_eq!(email("mailto:not an address"), None);
+
+        assert_eq!(
+            DirectTarget::Email("someone@example.com".to_string()).target(),
+            "mailto:someone@example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_result_outranks_web_search() {
+        let provider = DirectUrlProvider::new().unwrap();
+        let web = WebSearchProvider::new().unwrap();
+        let query = "github.com/rust-lang/rust";
+
+        let direct = provider.search(&query.into()).await.unwrap();
+        assert_eq!(direct.len(), 1);
+        assert_eq!(direct[0].result_type, ResultType::Url);
+        assert!(matches!(&direct[0].action, ResultAction::OpenUrl { url } if url == "https://github.com/rust-lang/rust"));
+
+        let mut results = web.search(&query.into()).await.unwrap();
+        results.extend(direct);
+        let ranked = SearchEngine::rank_results(results, query);
+        assert_eq!(ranked[0].result_type, ResultType::Url);
+        assert_eq!(ranked[1].result_type, ResultType::WebSearch);
+
+        assert!(provider.search(&"report.pdf".into()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+/// Self-test from the launcher
+///
+/// `/doctor` offers to run the self-test, which checks that search, the
+/// hotkey, settings and the file indexes work and copies a redacted report
+/// for a support request. Typing the start of the command, such as `/doc`,
+/// offers it too.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Command that runs the self-test
+pub const DOCTOR_COMMAND: &str = "/doctor";
+
+/// Id of the single result
+const DOCTOR_ID: &str = "doctor:self-test";
+
+/// Shortest start of the command that offers it
+const MIN_PREFIX_LEN: usize = 3;
+
+/// Help entry for the command
+const DOCTOR_SYNTAX: SyntaxFeature = SyntaxFeature {
+    id: "doctor",
+    title: "Self-test",
+    description: "Checks search, the hotkey, settings and the file indexes, and copies a report",
+    example: DOCTOR_COMMAND,
+    token: None,
+    values: &[],
+};
+
+/// Search provider offering the self-test
+pub struct DoctorProvider {
+    /// Runs the self-test when the result is executed
+    run_handler: Option<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl DoctorProvider {
+    /// Creates a DoctorProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing DoctorProvider");
+        Ok(Self { run_handler: None })
+    }
+
+    /// Sets what runs the self-test
+    pub fn set_run_handler<F>(&mut self, handler: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.run_handler = Some(Box::new(handler));
+    }
+}
+
+#[async_trait]
+impl SearchProvider for DoctorProvider {
+    fn name(&self) -> &str {
+        "Doctor"
+    }
+
+    fn priority(&self) -> u8 {
+        80 // Same as the other quick actions
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let folded = query.folded_query.trim();
+        if folded.len() < MIN_PREFIX_LEN || !DOCTOR_COMMAND.starts_with(folded) {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![SearchResult {
+            id: DOCTOR_ID.to_string(),
+            title: "Run self-test".to_string(),
+            subtitle: "Check search, the hotkey, settings and indexes, and copy a report for an issue".to_string(),
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score: if folded == DOCTOR_COMMAND { 100.0 } else { 80.0 },
+            metadata: HashMap::new(),
+            action: ResultAction::ExecuteCommand {
+                command: DOCTOR_ID.to_string(),
+                args: vec![],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }])
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.id != DOCTOR_ID {
+            return Err(LauncherError::ExecutionError("Not a self-test result".to_string()));
+        }
+
+        let handler = self.run_handler.as_ref().ok_or_else(|| {
+            LauncherError::ExecutionError("The self-test can't be run".to_string())
+        })?;
+
+        info!("Running the self-test from the launcher");
+        handler();
+        Ok(())
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![DOCTOR_SYNTAX]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_doctor_command_runs_the_self_test() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut provider = DoctorProvider::new().unwrap();
+        let counted = Arc::clone(&runs);
+        provider.set_run_handler(move || {
+            counted.fetch_add(1, Ordering::SeqCst);
+        });
+
+        for query in ["doctor", "/d", "/doctors"] {
+            assert!(provider.search(&QueryContext::new(query, true)).await.unwrap().is_empty(), "{}", query);
+        }
+
+        let partial = provider.search(&QueryContext::new("/doc", true)).await.unwrap();
+        let exact = provider.search(&QueryContext::new("/Doctor", true)).await.unwrap();
+        assert_eq!(partial.len(), 1);
+        assert!(exact[0].score > partial[0].score);
+
+        provider.execute(&exact[0]).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -4,7 +4,9 @@
 /// which enables ultra-fast file searching on Windows.
 
 use crate::error::{LauncherError, Result};
+use crate::search::providers::file_search::FileSort;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[cfg(windows)]
 use std::ffi::OsStr;
@@ -44,12 +46,34 @@ const EVERYTHING_SORT_NAME_DESCENDING: u32 = 2;
 #[allow(dead_code)]
 const EVERYTHING_SORT_PATH_ASCENDING: u32 = 3;
 #[cfg(windows)]
-#[allow(dead_code)]
 const EVERYTHING_SORT_SIZE_ASCENDING: u32 = 5;
 #[cfg(windows)]
-#[allow(dead_code)]
+const EVERYTHING_SORT_SIZE_DESCENDING: u32 = 6;
+#[cfg(windows)]
+const EVERYTHING_SORT_DATE_MODIFIED_ASCENDING: u32 = 11;
+#[cfg(windows)]
 const EVERYTHING_SORT_DATE_MODIFIED_DESCENDING: u32 = 12;
 
+/// Seconds between the FILETIME epoch (1601-01-01) and the Unix epoch
+const FILETIME_UNIX_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+/// FILETIME ticks (100 ns) per second
+const FILETIME_TICKS_PER_SEC: i64 = 10_000_000;
+
+/// Size reported for results whose size Everything doesn't know
+pub const UNKNOWN_SIZE: u64 = u64::MAX;
+
+/// Converts a FILETIME value to seconds since the Unix epoch
+///
+/// Returns 0 for the zero FILETIME Everything uses for unknown dates.
+pub fn filetime_to_unix_seconds(filetime: i64) -> i64 {
+    if filetime <= 0 {
+        return 0;
+    }
+
+    filetime / FILETIME_TICKS_PER_SEC - FILETIME_UNIX_EPOCH_OFFSET_SECS
+}
+
 // Everything SDK FFI function types
 #[cfg(windows)]
 type EverythingSetSearchW = unsafe extern "C" fn(*const u16);
@@ -70,9 +94,9 @@ type EverythingGetResultPathW = unsafe extern "C" fn(u32) -> *const u16;
 #[cfg(windows)]
 type EverythingGetResultFullPathNameW = unsafe extern "C" fn(u32, *mut u16, u32) -> u32;
 #[cfg(windows)]
-type EverythingGetResultSize = unsafe extern "C" fn(u32, *mut u32) -> u32;
+type EverythingGetResultSize = unsafe extern "C" fn(u32, *mut i64) -> bool;
 #[cfg(windows)]
-type EverythingGetResultDateModified = unsafe extern "C" fn(u32, *mut u32) -> u32;
+type EverythingGetResultDateModified = unsafe extern "C" fn(u32, *mut i64) -> bool;
 #[cfg(windows)]
 type EverythingGetLastError = unsafe extern "C" fn() -> u32;
 #[cfg(windows)]
@@ -102,13 +126,23 @@ pub struct EverythingFile {
     pub name: String,
     pub path: String,
     pub full_path: PathBuf,
+    /// Size in bytes, or `UNKNOWN_SIZE`
     pub size: u64,
+    /// Last modification in seconds since the Unix epoch, or 0 when unknown
     pub modified: i64,
 }
 
+impl EverythingFile {
+    /// Returns true if Everything reported a size for this result
+    pub fn size_known(&self) -> bool {
+        self.size != UNKNOWN_SIZE
+    }
+}
+
 /// Everything SDK client wrapper
 pub struct EverythingClient {
-    is_available: bool,
+    /// Whether Everything answered with a loaded database at the last check
+    is_available: AtomicBool,
     #[cfg(windows)]
     functions: Option<EverythingFunctions>,
 }
@@ -131,7 +165,7 @@ impl EverythingClient {
 
             info!("Everything SDK is available and database is loaded");
             Ok(Self {
-                is_available: true,
+                is_available: AtomicBool::new(true),
                 functions: Some(functions),
             })
         }
@@ -183,12 +217,44 @@ impl EverythingClient {
 
     /// Checks if Everything SDK is available
     pub fn is_available(&self) -> bool {
-        self.is_available
+        self.is_available.load(Ordering::Relaxed)
+    }
+
+    /// Asks Everything again whether its database is loaded
+    ///
+    /// Everything may have restarted while the system slept; searches fail
+    /// fast while it reports no database. Returns the new availability.
+    pub fn revalidate(&self) -> bool {
+        #[cfg(windows)]
+        let available = self
+            .functions
+            .as_ref()
+            .is_some_and(|functions| unsafe { (functions.is_db_loaded)() });
+
+        #[cfg(not(windows))]
+        let available = false;
+
+        if self.is_available.swap(available, Ordering::Relaxed) != available {
+            tracing::info!("Everything SDK is {}", if available { "available again" } else { "no longer available" });
+        }
+        available
+    }
+
+    /// Maps a requested order to the Everything sort constant
+    #[cfg(windows)]
+    fn sort_constant(sort: Option<FileSort>) -> u32 {
+        match sort {
+            None => EVERYTHING_SORT_NAME_ASCENDING,
+            Some(FileSort::Newest) => EVERYTHING_SORT_DATE_MODIFIED_DESCENDING,
+            Some(FileSort::Oldest) => EVERYTHING_SORT_DATE_MODIFIED_ASCENDING,
+            Some(FileSort::Largest) => EVERYTHING_SORT_SIZE_DESCENDING,
+            Some(FileSort::Smallest) => EVERYTHING_SORT_SIZE_ASCENDING,
+        }
     }
 
-    /// Searches for files matching the query
-    pub fn search(&self, query: &str, max_results: u32) -> Result<Vec<EverythingFile>> {
-        if !self.is_available {
+    /// Searches for files matching the query, sorted by name unless `sort` says otherwise
+    pub fn search(&self, query: &str, max_results: u32, sort: Option<FileSort>) -> Result<Vec<EverythingFile>> {
+        if !self.is_available() {
             return Err(LauncherError::EverythingNotAvailable);
         }
 
@@ -213,8 +279,8 @@ impl EverythingClient {
                 // Set max results
                 (functions.set_max)(max_results);
 
-                // Set sort order (by name)
-                (functions.set_sort)(EVERYTHING_SORT_NAME_ASCENDING);
+                // Set sort order
+                (functions.set_sort)(Self::sort_constant(sort));
 
                 // Execute query
                 let success = (functions.query_w)(true);
@@ -245,7 +311,7 @@ impl EverythingClient {
 
         #[cfg(not(windows))]
         {
-            let _ = (query, max_results);
+            let _ = (query, max_results, sort);
             Err(LauncherError::EverythingNotAvailable)
         }
     }
@@ -277,15 +343,21 @@ impl EverythingClient {
             PathBuf::from(&path).join(&name)
         };
 
-        // Get size
-        let mut size_high: u32 = 0;
-        let size_low = (functions.get_result_size)(index, &mut size_high);
-        let size = ((size_high as u64) << 32) | (size_low as u64);
+        // Get size (folders report -1 when folder sizes aren't indexed)
+        let mut size_raw: i64 = 0;
+        let size = if (functions.get_result_size)(index, &mut size_raw) && size_raw >= 0 {
+            size_raw as u64
+        } else {
+            UNKNOWN_SIZE
+        };
 
         // Get modified date
-        let mut modified_high: u32 = 0;
-        let modified_low = (functions.get_result_date_modified)(index, &mut modified_high);
-        let modified = ((modified_high as i64) << 32) | (modified_low as i64);
+        let mut filetime: i64 = 0;
+        let modified = if (functions.get_result_date_modified)(index, &mut filetime) {
+            filetime_to_unix_seconds(filetime)
+        } else {
+            0
+        };
 
         Some(EverythingFile {
             name,
@@ -343,13 +415,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_filetime_conversion() {
+        // 2023-11-14T22:13:20Z
+        assert_eq!(filetime_to_unix_seconds(133_444_736_000_000_000), 1_700_000_000);
+        assert_eq!(filetime_to_unix_seconds(FILETIME_UNIX_EPOCH_OFFSET_SECS * FILETIME_TICKS_PER_SEC), 0);
+        assert_eq!(filetime_to_unix_seconds(0), 0);
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_everything_search() {
         match EverythingClient::new() {
             Ok(client) => {
                 // Search for .txt files
-                match client.search("*.txt", 10) {
+                match client.search("*.txt", 10, None) {
                     Ok(results) => {
                         println!("Found {} .txt files", results.len());
                         for file in results.iter().take(5) {
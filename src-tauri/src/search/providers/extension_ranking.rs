@@ -0,0 +1,285 @@
+/// Extension-aware adjustments to file result scores
+///
+/// Files sharing a name stem score alike on the name alone, so `report`
+/// could rank `report.tmp` above `report.docx`. Each extension belongs to a
+/// class with its own weight: documents, images, media and code rank
+/// slightly higher, while noise such as temp files, logs and dumps ranks
+/// lower but is never hidden. A query word naming a class, such as `png`,
+/// `photo` or `sheet`, lifts that class further. The weights of each class
+/// can be overridden in settings.
+
+use crate::types::SearchResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Metadata key holding a file result's `ExtensionScore`
+pub const EXTENSION_SCORE_KEY: &str = "extension_score";
+
+/// Bonus for a class a query word asks for
+const INTENT_BOOST: f64 = 25.0;
+
+/// Largest weight, either way, settings may give a class
+pub const MAX_CLASS_WEIGHT: f64 = 50.0;
+
+/// Kinds of files that rank alike
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionClass {
+    Documents,
+    Images,
+    Media,
+    Code,
+    Archives,
+    /// Files people rarely look for by name, such as temp files and logs
+    Noise,
+}
+
+impl ExtensionClass {
+    /// Weight used unless settings override it
+    pub fn default_weight(self) -> f64 {
+        match self {
+            ExtensionClass::Documents => 10.0,
+            ExtensionClass::Images => 6.0,
+            ExtensionClass::Media => 4.0,
+            ExtensionClass::Code => 2.0,
+            ExtensionClass::Archives => 0.0,
+            ExtensionClass::Noise => -15.0,
+        }
+    }
+}
+
+/// Lowercase extensions of each class
+const EXTENSION_CLASSES: &[(ExtensionClass, &[&str])] = &[
+    (
+        ExtensionClass::Documents,
+        &[
+            "doc", "docx", "odt", "rtf", "pdf", "txt", "md", "xls", "xlsx", "ods", "csv", "ppt", "pptx", "odp",
+            "pages", "numbers", "key", "epub",
+        ],
+    ),
+    (
+        ExtensionClass::Images,
+        &["png", "jpg", "jpeg", "gif", "bmp", "webp", "svg", "tif", "tiff", "heic", "raw", "ico", "psd"],
+    ),
+    (
+        ExtensionClass::Media,
+        &["mp3", "wav", "flac", "aac", "ogg", "m4a", "mp4", "mkv", "avi", "mov", "wmv", "webm"],
+    ),
+    (
+        ExtensionClass::Code,
+        &[
+            "rs", "py", "js", "ts", "tsx", "jsx", "c", "cpp", "h", "cs", "java", "go", "rb", "php", "html", "css",
+            "json", "toml", "yaml", "yml", "xml", "sql", "sh", "ps1", "bat",
+        ],
+    ),
+    (ExtensionClass::Archives, &["zip", "rar", "7z", "tar", "gz", "bz2", "xz", "iso", "cab"]),
+    (
+        ExtensionClass::Noise,
+        &["tmp", "temp", "log", "dmp", "pf", "dll", "sys", "bak", "old", "cache", "etl", "lock", "crdownload"],
+    ),
+];
+
+/// Query words that ask for a class without naming an extension
+const INTENT_WORDS: &[(ExtensionClass, &[&str])] = &[
+    (
+        ExtensionClass::Documents,
+        &["document", "documents", "sheet", "sheets", "spreadsheet", "presentation", "slides", "ebook"],
+    ),
+    (
+        ExtensionClass::Images,
+        &["image", "images", "photo", "photos", "picture", "pictures", "pic", "pics", "screenshot", "screenshots", "icon", "logo"],
+    ),
+    (
+        ExtensionClass::Media,
+        &["video", "videos", "movie", "movies", "song", "songs", "music", "audio", "recording", "podcast"],
+    ),
+    (ExtensionClass::Code, &["code", "script", "source"]),
+    (ExtensionClass::Archives, &["archive", "archives", "compressed"]),
+    (ExtensionClass::Noise, &["dump", "crash", "crashdump"]),
+];
+
+/// Class of a file's extension, `None` when it has none or it is unknown
+pub fn extension_class(file_name: &str) -> Option<ExtensionClass> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    if stem.is_empty() {
+        return None;
+    }
+    class_of_extension(&extension.to_lowercase())
+}
+
+fn class_of_extension(extension: &str) -> Option<ExtensionClass> {
+    EXTENSION_CLASSES
+        .iter()
+        .find(|(_, extensions)| extensions.contains(&extension))
+        .map(|(class, _)| *class)
+}
+
+/// Classes the query words ask for, by extension (`png`, `.pdf`) or by
+/// name (`photo`, `sheet`)
+///
+/// Expects lowercase words, as `query_tokens` returns them.
+pub fn intent_classes(tokens: &[String]) -> Vec<ExtensionClass> {
+    let mut classes = Vec::new();
+    for token in tokens {
+        let word = token.trim_start_matches('.');
+        let class = class_of_extension(word).or_else(|| {
+            INTENT_WORDS
+                .iter()
+                .find(|(_, words)| words.contains(&word))
+                .map(|(class, _)| *class)
+        });
+        if let Some(class) = class.filter(|class| !classes.contains(class)) {
+            classes.push(class);
+        }
+    }
+    classes
+}
+
+/// Class weights set in settings, in place of the defaults
+pub type ExtensionWeights = HashMap<ExtensionClass, f64>;
+
+/// Weight of `class`, from `overrides` when set there
+pub fn class_weight(class: ExtensionClass, overrides: &ExtensionWeights) -> f64 {
+    overrides.get(&class).copied().unwrap_or_else(|| class.default_weight())
+}
+
+/// The class weights in use
+///
+/// Clones share the weights, so saving settings, e.g. on a profile switch,
+/// changes how the registered file providers rank without re-registering them.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveExtensionWeights(Arc<RwLock<ExtensionWeights>>);
+
+impl ActiveExtensionWeights {
+    pub fn get(&self) -> ExtensionWeights {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, weights: ExtensionWeights) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = weights;
+    }
+}
+
+/// How a file's extension adjusted its score
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ExtensionScore {
+    pub class: Option<ExtensionClass>,
+    /// Weight of the class, a penalty when negative
+    pub weight: f64,
+    /// Bonus for a class the query asks for
+    pub intent: f64,
+    /// Sum of the two
+    pub total: f64,
+}
+
+impl ExtensionScore {
+    /// Scores a file name against the classes the query asks for
+    ///
+    /// Unknown extensions score nothing either way.
+    pub fn compute(file_name: &str, intent: &[ExtensionClass], overrides: &ExtensionWeights) -> Self {
+        let Some(class) = extension_class(file_name) else {
+            return Self::default();
+        };
+
+        let weight = class_weight(class, overrides);
+        let intent = if intent.contains(&class) { INTENT_BOOST } else { 0.0 };
+        Self {
+            class: Some(class),
+            weight,
+            intent,
+            total: weight + intent,
+        }
+    }
+}
+
+/// Adds the extension score of each file result to its score, recorded under
+/// `extension_score`; the caller sorts afterwards
+pub fn apply_extension_scores(results: &mut [SearchResult], tokens: &[String], overrides: &ExtensionWeights) {
+    let intent = intent_classes(tokens);
+    for result in results {
+        let extension_score = ExtensionScore::compute(&result.title, &intent, overrides);
+        result.score += extension_score.total;
+        if let Ok(value) = serde_json::to_value(extension_score) {
+            result.metadata.insert(EXTENSION_SCORE_KEY.to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::providers::path_ranking::query_tokens;
+
+    #[test]
+    fn test_extensions_map_to_classes() {
+        assert_eq!(extension_class("Report.DOCX"), Some(ExtensionClass::Documents));
+        assert_eq!(extension_class("logo.final.png"), Some(ExtensionClass::Images));
+        assert_eq!(extension_class("setup.log"), Some(ExtensionClass::Noise));
+        assert_eq!(extension_class("~$report.tmp"), Some(ExtensionClass::Noise));
+        assert_eq!(extension_class("backup.7z"), Some(ExtensionClass::Archives));
+        assert_eq!(extension_class("main.rs"), Some(ExtensionClass::Code));
+        assert_eq!(extension_class("Makefile"), None);
+        assert_eq!(extension_class(".gitignore"), None);
+    }
+
+    #[test]
+    fn test_intent_comes_from_extensions_and_words() {
+        assert_eq!(intent_classes(&query_tokens("logo png")), vec![ExtensionClass::Images]);
+        assert_eq!(intent_classes(&query_tokens("screenshot from yesterday")), vec![ExtensionClass::Images]);
+        assert_eq!(
+            intent_classes(&query_tokens("budget sheet .pdf")),
+            vec![ExtensionClass::Documents]
+        );
+        assert_eq!(
+            intent_classes(&query_tokens("vacation video photos")),
+            vec![ExtensionClass::Media, ExtensionClass::Images]
+        );
+        // Directives are left to the file filters
+        assert!(intent_classes(&query_tokens("ext:png report")).is_empty());
+        assert!(intent_classes(&query_tokens("report")).is_empty());
+    }
+
+    #[test]
+    fn test_documents_outrank_noise_and_intent_lifts_images() {
+        let none = ExtensionWeights::new();
+        let score = |name: &str, query: &str| {
+            ExtensionScore::compute(name, &intent_classes(&query_tokens(query)), &none).total
+        };
+
+        assert!(score("report.docx", "report") > score("report.tmp", "report"));
+        assert!(score("report.pdf", "report") > score("report.dll", "report"));
+        // Noise is only penalized, never hidden, so it still has a score
+        assert_eq!(score("report.log", "report"), ExtensionClass::Noise.default_weight());
+
+        assert!(score("logo.png", "logo png") > score("logo.pdf", "logo png"));
+        assert!(score("IMG_2041.jpg", "screenshot from yesterday") > score("notes.docx", "screenshot from yesterday"));
+    }
+
+    #[test]
+    fn test_overrides_take_precedence_over_defaults() {
+        let overrides: ExtensionWeights = [(ExtensionClass::Noise, 5.0), (ExtensionClass::Documents, -5.0)].into();
+
+        assert_eq!(class_weight(ExtensionClass::Noise, &overrides), 5.0);
+        assert_eq!(class_weight(ExtensionClass::Documents, &overrides), -5.0);
+        assert_eq!(class_weight(ExtensionClass::Images, &overrides), ExtensionClass::Images.default_weight());
+
+        // The intent bonus still applies on top of an overridden weight
+        let intent = intent_classes(&query_tokens("report doc"));
+        assert_eq!(ExtensionScore::compute("report.doc", &intent, &overrides).total, -5.0 + INTENT_BOOST);
+        assert!(
+            ExtensionScore::compute("report.log", &[], &overrides).total
+                > ExtensionScore::compute("report.docx", &[], &overrides).total
+        );
+    }
+
+    #[test]
+    fn test_unknown_extensions_are_neutral() {
+        let overrides: ExtensionWeights = [(ExtensionClass::Documents, 40.0)].into();
+        let intent = intent_classes(&query_tokens("design photo pdf"));
+
+        for name in ["design.fig", "design", "archive.part01", "README"] {
+            assert_eq!(ExtensionScore::compute(name, &intent, &overrides), ExtensionScore::default(), "{}", name);
+        }
+    }
+}
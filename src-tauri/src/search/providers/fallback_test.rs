@@ -62,7 +62,7 @@ mod tests {
         let provider = WindowsSearchProvider::new().unwrap();
         
         // Search with an empty query should return empty results, not error
-        let results = provider.search("").await;
+        let results = provider.search(&"".into()).await;
         assert!(results.is_ok());
         assert_eq!(results.unwrap().len(), 0);
     }
@@ -0,0 +1,584 @@
+/// Favorites provider for user-pinned search results
+///
+/// Any result can be pinned under an alias. Typing the alias (or part of the
+/// original title) brings the pinned result back with a score that keeps it
+/// above everything else.
+
+use crate::error::{LauncherError, Result};
+use crate::search::completion::{CompletionCandidate, CompletionSource};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, SearchResult};
+use crate::utils::normalize_for_search;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, error, info, warn};
+
+/// Prefix for pin identifiers
+pub const PIN_ID_PREFIX: &str = "pin:";
+
+/// Base score for pinned results, well above any regular provider score
+const PIN_SCORE: f64 = 1000.0;
+
+/// Maximum alias length in characters
+const MAX_ALIAS_LENGTH: usize = 64;
+
+/// Metadata keys that change between searches and must not be persisted
+const VOLATILE_METADATA_KEYS: &[&str] = &[
+    "timestamp",
+    "last_accessed",
+    "access_count",
+    "modified",
+    "size",
+    "pinned",
+    "pin_alias",
+];
+
+/// A result pinned under an alias
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pin {
+    /// Pin identifier (`pin:` + original result id)
+    pub id: String,
+    /// Alias the pin is matched against
+    pub alias: String,
+    /// Normalized copy of the pinned result
+    pub result: SearchResult,
+    /// When the result was pinned
+    pub pinned_at: DateTime<Utc>,
+}
+
+impl Pin {
+    /// Creates a pin from a search result, normalizing it for storage
+    pub fn new(result: SearchResult, alias: Option<&str>) -> Self {
+        let result = normalize_result(result);
+        let alias = alias
+            .map(normalize_alias)
+            .filter(|alias| !alias.is_empty())
+            .unwrap_or_else(|| normalize_alias(&result.title));
+
+        Self {
+            id: format!("{}{}", PIN_ID_PREFIX, result.id),
+            alias,
+            result,
+            pinned_at: Utc::now(),
+        }
+    }
+
+    /// Returns the file system target of the pin, if it has one
+    pub fn target_path(&self) -> Option<&str> {
+        match &self.result.action {
+            ResultAction::OpenFile { path } | ResultAction::LaunchApp { path } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the pinned target still exists
+    pub fn is_target_valid(&self) -> bool {
+        self.target_path()
+            .map(|path| Path::new(path).exists())
+            .unwrap_or(true)
+    }
+}
+
+/// Strips volatile fields so a pinned result round-trips cleanly
+pub fn normalize_result(mut result: SearchResult) -> SearchResult {
+    if let Some(original_id) = result.id.strip_prefix(PIN_ID_PREFIX) {
+        result.id = original_id.to_string();
+    }
+
+    result
+        .metadata
+        .retain(|key, _| !VOLATILE_METADATA_KEYS.contains(&key.as_str()));
+    result.title = result.title.trim().to_string();
+    result.subtitle = result.subtitle.trim().to_string();
+    result.score = 0.0;
+
+    result
+}
+
+/// Collapses whitespace and limits the alias length
+fn normalize_alias(alias: &str) -> String {
+    alias
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .chars()
+        .take(MAX_ALIAS_LENGTH)
+        .collect()
+}
+
+/// Checks whether all characters of `needle` appear in order in `haystack`
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack_chars = haystack.chars();
+    needle
+        .chars()
+        .all(|c| haystack_chars.any(|h| h == c))
+}
+
+/// Scores a query against a pin's alias and original title
+fn match_score(query: &str, pin: &Pin) -> Option<f64> {
+    let query = normalize_for_search(query);
+    let alias = normalize_for_search(&pin.alias);
+    let title = pin.result.normalized_title();
+
+    let bonus = if alias == query {
+        100.0
+    } else if alias.starts_with(&query) {
+        80.0
+    } else if alias.contains(&query) {
+        60.0
+    } else if title.contains(&query) {
+        50.0
+    } else if query.chars().count() >= 2 && is_subsequence(&query, &alias) {
+        30.0
+    } else if query.chars().count() >= 3 && is_subsequence(&query, &title) {
+        20.0
+    } else {
+        return None;
+    };
+
+    Some(PIN_SCORE + bonus)
+}
+
+/// Persistent store for pinned results
+pub struct FavoritesStore {
+    /// Path to the storage file
+    storage_path: PathBuf,
+    /// Pinned results, most recent first
+    pins: RwLock<Vec<Pin>>,
+}
+
+impl FavoritesStore {
+    /// Creates the store and loads existing pins from disk
+    pub fn new() -> Result<Self> {
+        Self::with_path(Self::get_storage_path()?)
+    }
+
+    /// Creates a store backed by a specific file
+    pub fn with_path(storage_path: PathBuf) -> Result<Self> {
+        if let Some(parent) = storage_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let pins = match Self::read_pins(&storage_path) {
+            Ok(pins) => pins,
+            Err(e) => {
+                error!("Failed to load pinned results from {:?}: {}", storage_path, e);
+                Vec::new()
+            }
+        };
+
+        info!("Loaded {} pinned results", pins.len());
+
+        Ok(Self {
+            storage_path,
+            pins: RwLock::new(pins),
+        })
+    }
+
+    /// Gets the storage file path
+    fn get_storage_path() -> Result<PathBuf> {
+        #[cfg(test)]
+        {
+            // Use temp directory for tests
+            let mut path = std::env::temp_dir();
+            path.push("BetterFinder");
+            path.push("favorites_test.json");
+            return Ok(path);
+        }
+
+        #[cfg(not(test))]
+        {
+            let app_data = std::env::var("APPDATA")
+                .map_err(|_| LauncherError::ConfigError("APPDATA not found".to_string()))?;
+
+            let mut path = PathBuf::from(app_data);
+            path.push("BetterFinder");
+            path.push("favorites.json");
+
+            Ok(path)
+        }
+    }
+
+    fn read_pins(path: &Path) -> Result<Vec<Pin>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the pins to disk
+    async fn save(&self, pins: &[Pin]) -> Result<()> {
+        let path = self.storage_path.clone();
+        let content = serde_json::to_string_pretty(pins)?;
+
+        tokio::task::spawn_blocking(move || std::fs::write(&path, content))
+            .await
+            .map_err(|e| {
+                LauncherError::ExecutionError(format!("Failed to spawn save task: {}", e))
+            })??;
+
+        Ok(())
+    }
+
+    /// Pins a result under an alias, replacing an existing pin of the same result
+    pub async fn pin(&self, result: SearchResult, alias: Option<&str>) -> Result<Pin> {
+        let pin = Pin::new(result, alias);
+
+        let mut pins = self.pins.write().await;
+        pins.retain(|existing| existing.id != pin.id);
+        pins.insert(0, pin.clone());
+        self.save(&pins).await?;
+
+        info!("Pinned '{}' as '{}'", pin.result.title, pin.alias);
+        Ok(pin)
+    }
+
+    /// Removes a pin by pin id or original result id
+    ///
+    /// Returns false if nothing was pinned under that id.
+    pub async fn unpin(&self, id: &str) -> Result<bool> {
+        let pin_id = if id.starts_with(PIN_ID_PREFIX) {
+            id.to_string()
+        } else {
+            format!("{}{}", PIN_ID_PREFIX, id)
+        };
+
+        let mut pins = self.pins.write().await;
+        let before = pins.len();
+        pins.retain(|pin| pin.id != pin_id);
+
+        if pins.len() == before {
+            return Ok(false);
+        }
+
+        self.save(&pins).await?;
+        info!("Unpinned '{}'", pin_id);
+        Ok(true)
+    }
+
+    /// Returns all pins, most recent first
+    pub async fn list(&self) -> Vec<Pin> {
+        self.pins.read().await.clone()
+    }
+
+    /// Finds a pin by its id
+    pub async fn get(&self, id: &str) -> Option<Pin> {
+        self.pins.read().await.iter().find(|pin| pin.id == id).cloned()
+    }
+}
+
+/// Favorites search provider
+pub struct FavoritesProvider {
+    /// Shared pin store (also used by the Tauri commands)
+    store: Arc<FavoritesStore>,
+    /// Whether the provider is enabled
+    enabled: bool,
+    /// Called when a pinned target no longer exists
+    stale_pin_handler: Option<Box<dyn Fn(&Pin) + Send + Sync>>,
+}
+
+impl FavoritesProvider {
+    /// Creates a new FavoritesProvider backed by a shared store
+    pub fn new(store: Arc<FavoritesStore>) -> Result<Self> {
+        info!("Initializing FavoritesProvider");
+
+        Ok(Self {
+            store,
+            enabled: true,
+            stale_pin_handler: None,
+        })
+    }
+
+    /// Sets a callback for pins whose target has disappeared
+    pub fn set_stale_pin_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&Pin) + Send + Sync + 'static,
+    {
+        self.stale_pin_handler = Some(Box::new(handler));
+    }
+
+    /// Converts a pin to the SearchResult returned to the frontend
+    fn create_search_result(pin: &Pin, score: f64) -> SearchResult {
+        let mut result = pin.result.clone();
+        result.id = pin.id.clone();
+        result.score = score;
+        result
+            .metadata
+            .insert("pinned".to_string(), serde_json::json!(true));
+        result
+            .metadata
+            .insert("pin_alias".to_string(), serde_json::json!(pin.alias));
+        result
+    }
+}
+
+#[async_trait]
+impl SearchProvider for FavoritesProvider {
+    fn name(&self) -> &str {
+        "Favorites"
+    }
+
+    fn priority(&self) -> u8 {
+        95 // Pins are checked before any other provider
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
+
+        if trimmed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pins = self.store.pins.read().await;
+        let mut results: Vec<SearchResult> = pins
+            .iter()
+            .filter_map(|pin| {
+                match_score(trimmed, pin).map(|score| Self::create_search_result(pin, score))
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+
+        debug!("Found {} pinned results for '{}'", results.len(), trimmed);
+        Ok(results)
+    }
+
+    async fn completion_candidates(&self, query: &str) -> Vec<CompletionCandidate> {
+        let query = normalize_for_search(query);
+        let pins = self.store.pins.read().await;
+
+        let mut candidates = Vec::new();
+        for pin in pins.iter() {
+            for text in [&pin.alias, &pin.result.title] {
+                if normalize_for_search(text).starts_with(&query) {
+                    candidates.push(CompletionCandidate::new(text.clone(), pin.id.clone(), CompletionSource::Pin));
+                }
+            }
+        }
+        candidates
+    }
+
+    /// Re-validates a pinned target before it is opened
+    ///
+    /// Pinned results keep their original type and action, so once the target
+    /// is confirmed this provider declines the result and the engine hands it
+    /// to the provider that produced it.
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if !result.is_pinned() {
+            return Err(LauncherError::ExecutionError(
+                "Not a pinned result".to_string(),
+            ));
+        }
+
+        let pin = self.store.get(&result.id).await.ok_or_else(|| {
+            LauncherError::NotFound(format!("Pin no longer exists: {}", result.id))
+        })?;
+
+        if !pin.is_target_valid() {
+            warn!("Pinned target for '{}' no longer exists", pin.alias);
+
+            if let Some(handler) = &self.stale_pin_handler {
+                handler(&pin);
+            }
+
+            return Err(LauncherError::NotFound(format!(
+                "Pinned target no longer exists: {}",
+                pin.target_path().unwrap_or_default()
+            )));
+        }
+
+        debug!("Pinned target for '{}' is valid", pin.alias);
+        Err(LauncherError::ExecutionError(
+            "Pinned result is executed by its original provider".to_string(),
+        ))
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultType;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    fn file_result(id: &str, title: &str, path: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("path".to_string(), serde_json::json!(path));
+        metadata.insert("modified".to_string(), serde_json::json!(1_700_000_000));
+        metadata.insert("size".to_string(), serde_json::json!(1024));
+
+        SearchResult {
+            id: id.to_string(),
+            title: title.to_string(),
+            subtitle: path.to_string(),
+            icon: None,
+            result_type: ResultType::File,
+            score: 42.0,
+            metadata,
+            action: ResultAction::OpenFile {
+                path: path.to_string(),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    fn temp_store(name: &str) -> (Arc<FavoritesStore>, PathBuf) {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("favorites_{}.json", name));
+        let _ = std::fs::remove_file(&path);
+
+        (Arc::new(FavoritesStore::with_path(path.clone()).unwrap()), path)
+    }
+
+    #[test]
+    fn test_pin_normalizes_result() {
+        let pin = Pin::new(file_result("file:1", "  Roadmap.md ", "C:\\docs\\roadmap.md"), Some("  road   map "));
+
+        assert_eq!(pin.id, "pin:file:1");
+        assert_eq!(pin.alias, "road map");
+        assert_eq!(pin.result.title, "Roadmap.md");
+        assert_eq!(pin.result.score, 0.0);
+        assert!(pin.result.metadata.contains_key("path"));
+        assert!(!pin.result.metadata.contains_key("modified"));
+        assert!(!pin.result.metadata.contains_key("size"));
+
+        // Re-pinning a pinned result keeps the original id
+        let repinned = Pin::new(FavoritesProvider::create_search_result(&pin, 1100.0), None);
+        assert_eq!(repinned.id, "pin:file:1");
+        assert_eq!(repinned.alias, "Roadmap.md");
+        assert!(!repinned.result.metadata.contains_key("pinned"));
+    }
+
+    #[test]
+    fn test_alias_matching() {
+        let pin = Pin::new(file_result("file:1", "Q3 Product Plan.docx", "C:\\plan.docx"), Some("roadmap"));
+
+        let exact = match_score("roadmap", &pin).unwrap();
+        let prefix = match_score("road", &pin).unwrap();
+        let contains = match_score("map", &pin).unwrap();
+        let title = match_score("product", &pin).unwrap();
+        let fuzzy = match_score("rdmp", &pin).unwrap();
+
+        assert!(exact > prefix && prefix > contains && contains > title && title > fuzzy);
+        assert!(fuzzy > PIN_SCORE);
+        assert!(match_score("ROADMAP", &pin).is_some());
+        assert!(match_score("xyz", &pin).is_none());
+        assert!(match_score("r", &pin).is_some());
+        assert!(match_score("zr", &pin).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_provider_returns_pinned_result_on_top() {
+        let (store, path) = temp_store("search");
+        store.pin(file_result("file:1", "Plan.docx", "C:\\plan.docx"), Some("roadmap")).await.unwrap();
+
+        let provider = FavoritesProvider::new(Arc::clone(&store)).unwrap();
+        assert_eq!(provider.name(), "Favorites");
+        assert!(provider.priority() > 90);
+
+        let results = provider.search(&"roadmap".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "pin:file:1");
+        assert_eq!(results[0].result_type, ResultType::File);
+        assert!(results[0].is_pinned());
+        assert!(results[0].score >= PIN_SCORE);
+
+        assert!(provider.search(&"".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"unrelated".into()).await.unwrap().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_persistence_round_trip() {
+        let (store, path) = temp_store("round_trip");
+        let original = file_result("file:2", "Budget.xlsx", "C:\\budget.xlsx");
+
+        store.pin(original.clone(), Some("budget")).await.unwrap();
+        store.pin(file_result("file:3", "Notes", "C:\\notes.txt"), None).await.unwrap();
+        // Pinning the same result again replaces the earlier pin
+        store.pin(original, Some("money")).await.unwrap();
+
+        let reloaded = FavoritesStore::with_path(path.clone()).unwrap();
+        let pins = reloaded.list().await;
+        assert_eq!(pins.len(), 2);
+        assert_eq!(pins[0].id, "pin:file:2");
+        assert_eq!(pins[0].alias, "money");
+        assert_eq!(pins[0].result.title, "Budget.xlsx");
+        assert!(matches!(&pins[0].result.action, ResultAction::OpenFile { path } if path == "C:\\budget.xlsx"));
+        assert_eq!(pins[1].alias, "Notes");
+
+        assert!(reloaded.unpin("file:3").await.unwrap());
+        assert!(!reloaded.unpin("pin:file:3").await.unwrap());
+
+        let reloaded = FavoritesStore::with_path(path.clone()).unwrap();
+        assert_eq!(reloaded.list().await.len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_execute_stale_target_notifies() {
+        let (store, path) = temp_store("stale");
+        let missing = std::env::temp_dir().join("BetterFinder").join("definitely_missing_pin_target.txt");
+        let pin = store
+            .pin(file_result("file:4", "Gone", &missing.to_string_lossy()), Some("gone"))
+            .await
+            .unwrap();
+
+        let stale = Arc::new(Mutex::new(Vec::new()));
+        let stale_clone = Arc::clone(&stale);
+        let mut provider = FavoritesProvider::new(Arc::clone(&store)).unwrap();
+        provider.set_stale_pin_handler(move |pin: &Pin| {
+            stale_clone.lock().unwrap().push(pin.id.clone());
+        });
+
+        let result = FavoritesProvider::create_search_result(&pin, PIN_SCORE);
+        let err = provider.execute(&result).await.unwrap_err();
+        assert!(matches!(err, LauncherError::NotFound(_)));
+        assert_eq!(*stale.lock().unwrap(), vec!["pin:file:4".to_string()]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_execute_valid_target_defers_to_original_provider() {
+        let (store, path) = temp_store("valid");
+        let existing = std::env::temp_dir();
+        let pin = store
+            .pin(file_result("file:5", "Temp", &existing.to_string_lossy()), Some("temp"))
+            .await
+            .unwrap();
+
+        let provider = FavoritesProvider::new(Arc::clone(&store)).unwrap();
+        let result = FavoritesProvider::create_search_result(&pin, PIN_SCORE);
+        let err = provider.execute(&result).await.unwrap_err();
+        assert!(matches!(err, LauncherError::ExecutionError(_)));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_execute_not_pinned() {
+        let (store, path) = temp_store("not_pinned");
+        let provider = FavoritesProvider::new(store).unwrap();
+
+        let result = file_result("file:6", "Plain", "C:\\plain.txt");
+        assert!(provider.execute(&result).await.is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
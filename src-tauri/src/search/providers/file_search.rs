@@ -5,21 +5,183 @@
 
 use crate::error::{LauncherError, Result};
 use crate::search::providers::everything::{EverythingClient, EverythingFile};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::search::providers::index_freshness::{everything_freshness, FreshnessMonitor, IndexTool};
+use crate::search::providers::path_exclusions::{hidden_results_row, strip_bypass_prefix, FolderScopes, BYPASS_SYNTAX};
+use crate::search::providers::extension_ranking::{apply_extension_scores, ActiveExtensionWeights};
+use crate::search::providers::path_ranking::{query_tokens, PathScore, PATH_SCORE_KEY};
+use crate::search::provider_config::{ConfigOption, ProviderConfig};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ProviderHealth, ResultAction, ResultType, SearchResult, PRESERVE_PROVIDER_ORDER_KEY};
 use crate::utils::IconCache;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
+/// Files returned per search unless configured otherwise
 const MAX_RESULTS: u32 = 20;
 
+/// Upper bound for the configurable number of files per search
+const MAX_CONFIGURABLE_RESULTS: u32 = 100;
+
+/// How many times more files are fetched while exclusions apply, so hiding
+/// some still leaves a full page
+const EXCLUSION_OVERFETCH: u32 = 3;
+
+/// Score of the first result in a sorted listing; later results step down by one
+const SORTED_BASE_SCORE: f64 = 150.0;
+
+/// Help entry for the `sort:` directive
+const SORT_SYNTAX: SyntaxFeature = SyntaxFeature {
+    id: "sort",
+    title: "Sort files",
+    description: "Lists files newest, oldest, largest or smallest first",
+    example: "sort:new report",
+    token: Some("sort:"),
+    values: FileSort::NAMES,
+};
+
+/// Options under `provider_settings.file_search`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileSearchConfig {
+    /// Files returned per search
+    pub max_results: u32,
+}
+
+impl Default for FileSearchConfig {
+    fn default() -> Self {
+        Self { max_results: MAX_RESULTS }
+    }
+}
+
+impl ProviderConfig for FileSearchConfig {
+    const KEY: &'static str = "file_search";
+    const PROVIDER: &'static str = "FileSearch";
+    const OPTIONS: &'static [ConfigOption] = &[ConfigOption {
+        field: "max_results",
+        description: "Files fetched from Everything per search",
+        min: 1,
+        max: MAX_CONFIGURABLE_RESULTS as u64,
+    }];
+}
+
+/// Sort order requested with a `sort:` directive in the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileSort {
+    /// `sort:new` - most recently modified first
+    Newest,
+    /// `sort:old` - least recently modified first
+    Oldest,
+    /// `sort:big` - largest first
+    Largest,
+    /// `sort:small` - smallest first
+    Smallest,
+}
+
+impl FileSort {
+    /// Values accepted after `sort:`
+    pub const NAMES: &'static [&'static str] = &["new", "old", "big", "small"];
+
+    /// Splits a `sort:` directive off the query
+    ///
+    /// Returns the remaining query text and the requested order. Unknown
+    /// directives are left in the query untouched.
+    pub fn parse_directive(query: &str) -> (String, Option<FileSort>) {
+        let mut sort = None;
+        let mut remaining = Vec::new();
+
+        for word in query.split_whitespace() {
+            let directive = word
+                .get(..5)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("sort:"))
+                .and_then(|_| Self::from_name(&word[5..]));
+
+            match directive {
+                Some(parsed) if sort.is_none() => sort = Some(parsed),
+                Some(_) => {}
+                None => remaining.push(word),
+            }
+        }
+
+        (remaining.join(" "), sort)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "new" => Some(FileSort::Newest),
+            "old" => Some(FileSort::Oldest),
+            "big" => Some(FileSort::Largest),
+            "small" => Some(FileSort::Smallest),
+            _ => None,
+        }
+    }
+
+    /// Returns the score for the result at `index` of a sorted listing
+    ///
+    /// Scores strictly decrease so ranking keeps the provider's order.
+    pub fn score_at(index: usize) -> f64 {
+        SORTED_BASE_SCORE - index as f64
+    }
+}
+
+/// Formats a byte count for display, e.g. "2.3 MB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Describes how long ago a Unix timestamp was, e.g. "3 days ago"
+pub fn format_age(modified: i64, now: i64) -> String {
+    let seconds = (now - modified).max(0);
+    let (count, unit) = match seconds {
+        s if s < 60 => return "just now".to_string(),
+        s if s < 3600 => (s / 60, "minute"),
+        s if s < 86_400 => (s / 3600, "hour"),
+        s if s < 30 * 86_400 => (s / 86_400, "day"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "month"),
+        s => (s / (365 * 86_400), "year"),
+    };
+
+    format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// Builds the "2.3 MB • modified 3 days ago" part of a file subtitle
+///
+/// Unknown values are left out; returns `None` when neither is known.
+pub fn format_file_details(size: Option<u64>, modified: Option<i64>, now: i64) -> Option<String> {
+    let parts: Vec<String> = size
+        .map(format_size)
+        .into_iter()
+        .chain(modified.map(|modified| format!("modified {}", format_age(modified, now))))
+        .collect();
+
+    (!parts.is_empty()).then(|| parts.join(" • "))
+}
+
 /// File search provider
 pub struct FileSearchProvider {
     everything_client: Option<EverythingClient>,
     icon_cache: Arc<IconCache>,
+    scopes: FolderScopes,
+    extension_weights: ActiveExtensionWeights,
+    max_results: AtomicU32,
+    freshness: FreshnessMonitor,
 }
 
 impl FileSearchProvider {
@@ -42,9 +204,28 @@ impl FileSearchProvider {
         Ok(Self {
             everything_client,
             icon_cache: Arc::new(IconCache::new()),
+            scopes: FolderScopes::default(),
+            extension_weights: ActiveExtensionWeights::default(),
+            max_results: AtomicU32::new(MAX_RESULTS),
+            freshness: FreshnessMonitor::new(IndexTool::Everything),
         })
     }
 
+    /// Hides files under the user-excluded folders as well as the built-in
+    /// ones, and ranks files under the preferred folders higher, like those
+    /// on the Desktop
+    pub fn with_folder_scopes(mut self, scopes: FolderScopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Ranks files by the class of their extension with these weights, so
+    /// documents come before temp files and logs of the same name
+    pub fn with_extension_weights(mut self, weights: ActiveExtensionWeights) -> Self {
+        self.extension_weights = weights;
+        self
+    }
+
     /// Gets file icon using the centralized icon cache
     async fn get_file_icon(&self, path: &Path) -> Option<String> {
         // Use generic icon based on extension for better performance
@@ -55,15 +236,28 @@ impl FileSearchProvider {
     async fn convert_to_search_result(&self, file: EverythingFile, score: f64) -> SearchResult {
         let icon = self.get_file_icon(&file.full_path).await;
 
+        let size = file.size_known().then_some(file.size);
+        let modified = (file.modified > 0).then_some(file.modified);
+
         let mut metadata = HashMap::new();
-        metadata.insert("size".to_string(), serde_json::json!(file.size));
-        metadata.insert("modified".to_string(), serde_json::json!(file.modified));
+        if let Some(size) = size {
+            metadata.insert("size".to_string(), serde_json::json!(size));
+        }
+        if let Some(modified) = modified {
+            metadata.insert("modified".to_string(), serde_json::json!(modified));
+        }
         metadata.insert("path".to_string(), serde_json::json!(file.path));
 
+        let details = format_file_details(size, modified, chrono::Utc::now().timestamp());
+        let subtitle = match details {
+            Some(details) => format!("{} • {}", file.path, details),
+            None => file.path.clone(),
+        };
+
         SearchResult {
             id: format!("file:{}", file.full_path.display()),
             title: file.name.clone(),
-            subtitle: file.path.clone(),
+            subtitle,
             icon,
             result_type: ResultType::File,
             score,
@@ -71,9 +265,48 @@ impl FileSearchProvider {
             action: ResultAction::OpenFile {
                 path: file.full_path.to_string_lossy().to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 
+    /// Converts the files Everything found to results, best first
+    ///
+    /// Sorted listings keep Everything's order; otherwise each file's name
+    /// score is adjusted by where it lives, recorded under `path_score`, and
+    /// by its extension, recorded under `extension_score`.
+    async fn rank_files(&self, files: Vec<EverythingFile>, search_text: &str, sort: Option<FileSort>) -> Vec<SearchResult> {
+        let tokens = query_tokens(search_text);
+        let preferred_locations = self.scopes.preferred_locations();
+
+        let mut results = Vec::new();
+        for (index, file) in files.into_iter().enumerate() {
+            if sort.is_some() {
+                let mut result = self.convert_to_search_result(file, FileSort::score_at(index)).await;
+                result.metadata.insert(PRESERVE_PROVIDER_ORDER_KEY.to_string(), serde_json::json!(true));
+                results.push(result);
+                continue;
+            }
+
+            let path_score = PathScore::compute(&file.path, &tokens, &preferred_locations);
+            let score = Self::calculate_score(&file, search_text) + path_score.total;
+            let mut result = self.convert_to_search_result(file, score).await;
+            if let Ok(value) = serde_json::to_value(path_score) {
+                result.metadata.insert(PATH_SCORE_KEY.to_string(), value);
+            }
+            results.push(result);
+        }
+
+        // Everything already returned sorted listings in the requested order
+        if sort.is_none() {
+            apply_extension_scores(&mut results, &tokens, &self.extension_weights.get());
+            results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        }
+
+        results
+    }
+
     /// Calculates relevance score for a file based on query
     fn calculate_score(file: &EverythingFile, query: &str) -> f64 {
         let query_lower = query.to_lowercase();
@@ -106,7 +339,7 @@ impl FileSearchProvider {
         }
 
         // Penalize very large files (might be less relevant)
-        if file.size > 1_000_000_000 {
+        if file.size_known() && file.size > 1_000_000_000 {
             // > 1GB
             score -= 5.0;
         }
@@ -125,11 +358,24 @@ impl SearchProvider for FileSearchProvider {
         90 // High priority for file search
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        if query.trim().is_empty() {
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::File])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if query.query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (query, show_all) = strip_bypass_prefix(&query.query);
+        if query.is_empty() {
             return Ok(Vec::new());
         }
 
+        // A bare sort directive lists the whole index in that order
+        let (search_text, sort) = FileSort::parse_directive(query);
+        let search_text = search_text.as_str();
+
         // Check if Everything is available
         let client = match &self.everything_client {
             Some(client) => client,
@@ -139,26 +385,35 @@ impl SearchProvider for FileSearchProvider {
             }
         };
 
-        debug!("Searching files for query: '{}'", query);
+        // Asking whether the database is loaded and reading its file's age is
+        // cheap, so the probe runs inline
+        self.freshness
+            .check(std::time::Instant::now(), || Some(everything_freshness(client.revalidate())));
+
+        debug!("Searching files for query: '{}'", search_text);
 
         // Perform search using Everything SDK
-        let files = client.search(query, MAX_RESULTS).map_err(|e| {
+        let max_results = self.max_results.load(Ordering::Relaxed);
+        let limit = if show_all { max_results } else { max_results * EXCLUSION_OVERFETCH };
+        let mut files = client.search(search_text, limit, sort).map_err(|e| {
             error!("File search failed: {}", e);
             LauncherError::SearchError(format!("File search failed: {}", e))
         })?;
 
-        debug!("Found {} files", files.len());
+        let hidden = if show_all {
+            0
+        } else {
+            self.scopes
+                .exclusions()
+                .retain_visible(&mut files, |file| file.full_path.to_str().unwrap_or_default())
+        };
+        files.truncate(max_results as usize);
 
-        // Convert to search results
-        let mut results = Vec::new();
-        for file in files {
-            let score = Self::calculate_score(&file, query);
-            let result = self.convert_to_search_result(file, score).await;
-            results.push(result);
-        }
+        debug!("Found {} files ({} hidden by exclusions)", files.len(), hidden);
 
-        // Sort by score
-        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        let mut results = self.rank_files(files, search_text, sort).await;
+        results.extend(hidden_results_row(self.name(), query, hidden));
+        results.extend(self.freshness.take_notice(self.name(), chrono::Utc::now()));
 
         Ok(results)
     }
@@ -223,9 +478,33 @@ impl SearchProvider for FileSearchProvider {
         }
     }
 
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        let config = FileSearchConfig::parse(config)?;
+        self.max_results.store(config.max_results, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn refresh_after_resume(&self) {
+        if let Some(client) = &self.everything_client {
+            client.revalidate();
+        }
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SORT_SYNTAX, BYPASS_SYNTAX]
+    }
+
     fn is_enabled(&self) -> bool {
         self.everything_client.is_some()
     }
+
+    fn health(&self) -> ProviderHealth {
+        if self.is_enabled() {
+            self.freshness.health(chrono::Utc::now())
+        } else {
+            ProviderHealth::Disabled
+        }
+    }
 }
 
 impl Default for FileSearchProvider {
@@ -233,6 +512,10 @@ impl Default for FileSearchProvider {
         Self::new().unwrap_or_else(|_| Self {
             everything_client: None,
             icon_cache: Arc::new(IconCache::new()),
+            scopes: FolderScopes::default(),
+            extension_weights: ActiveExtensionWeights::default(),
+            max_results: AtomicU32::new(MAX_RESULTS),
+            freshness: FreshnessMonitor::new(IndexTool::Everything),
         })
     }
 }
@@ -240,6 +523,9 @@ impl Default for FileSearchProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::search::providers::extension_ranking::{ExtensionClass, EXTENSION_SCORE_KEY};
+    use crate::search::providers::path_exclusions::PathExclusions;
+    use crate::search::providers::path_ranking::PreferredLocations;
     use std::path::PathBuf;
 
     #[tokio::test]
@@ -260,7 +546,7 @@ mod tests {
     async fn test_file_search() {
         if let Ok(provider) = FileSearchProvider::new() {
             if provider.is_enabled() {
-                let results = provider.search("*.txt").await;
+                let results = provider.search(&"*.txt".into()).await;
                 match results {
                     Ok(files) => {
                         println!("Found {} files", files.len());
@@ -294,4 +580,151 @@ mod tests {
         let exact_score = FileSearchProvider::calculate_score(&file, "test.txt");
         assert!(exact_score > score, "Exact match should have higher score");
     }
+
+    #[test]
+    fn test_parse_sort_directive() {
+        assert_eq!(
+            FileSort::parse_directive("report sort:new"),
+            ("report".to_string(), Some(FileSort::Newest))
+        );
+        assert_eq!(
+            FileSort::parse_directive("SORT:Big  *.mp4"),
+            ("*.mp4".to_string(), Some(FileSort::Largest))
+        );
+        assert_eq!(FileSort::parse_directive("sort:old").1, Some(FileSort::Oldest));
+        assert_eq!(FileSort::parse_directive("a sort:small b").0, "a b");
+
+        // Only the first directive counts; unknown ones stay part of the query
+        assert_eq!(
+            FileSort::parse_directive("sort:new x sort:big"),
+            ("x".to_string(), Some(FileSort::Newest))
+        );
+        assert_eq!(
+            FileSort::parse_directive("sort:name notes"),
+            ("sort:name notes".to_string(), None)
+        );
+        assert_eq!(FileSort::parse_directive("resort:new").1, None);
+    }
+
+    #[test]
+    fn test_sorted_scores_strictly_decrease() {
+        assert!((0..MAX_RESULTS as usize).all(|i| FileSort::score_at(i) > FileSort::score_at(i + 1)));
+    }
+
+    #[test]
+    fn test_format_file_details() {
+        let now = 1_700_000_000;
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2_411_724), "2.3 MB");
+        assert_eq!(format_age(now - 3 * 86_400, now), "3 days ago");
+        assert_eq!(format_age(now - 3600, now), "1 hour ago");
+        assert_eq!(format_age(now + 10, now), "just now");
+        assert_eq!(
+            format_file_details(Some(2_411_724), Some(now - 3 * 86_400), now).unwrap(),
+            "2.3 MB • modified 3 days ago"
+        );
+        assert_eq!(format_file_details(None, Some(now - 120), now).unwrap(), "modified 2 minutes ago");
+        assert_eq!(format_file_details(None, None, now), None);
+    }
+
+    #[tokio::test]
+    async fn test_result_metadata_includes_size_and_date() {
+        let provider = FileSearchProvider::default();
+        let modified = chrono::Utc::now().timestamp() - 3 * 86_400;
+        let file = EverythingFile {
+            name: "video.mp4".to_string(),
+            path: "C:\\Videos".to_string(),
+            full_path: PathBuf::from("C:\\Videos\\video.mp4"),
+            size: 2_411_724,
+            modified,
+        };
+
+        let result = provider.convert_to_search_result(file, 50.0).await;
+        assert_eq!(result.metadata["size"], 2_411_724);
+        assert_eq!(result.metadata["modified"], modified);
+        assert_eq!(result.subtitle, "C:\\Videos • 2.3 MB • modified 3 days ago");
+        assert!(!result.preserves_provider_order());
+    }
+
+    fn file_in(folder: &str, name: &str) -> EverythingFile {
+        EverythingFile {
+            name: name.to_string(),
+            path: folder.to_string(),
+            full_path: PathBuf::from(format!("{}\\{}", folder, name)),
+            size: 1024,
+            modified: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rank_files_prefers_closer_paths() {
+        let provider = FileSearchProvider::default().with_folder_scopes(FolderScopes::new(
+            PathExclusions::default(),
+            PreferredLocations::with_profile(Some(r"C:\Users\me"), &[r"D:\Work".to_string()]),
+        ));
+
+        // Same name everywhere, in the order Everything might return them
+        let files = vec![
+            file_in(r"E:\Backup\2016\c\Users\me\Documents\misc\taxes\scans", "invoice.pdf"),
+            file_in(r"C:\Temp\export", "invoice.pdf"),
+            file_in(r"D:\Work\clients", "invoice.pdf"),
+            file_in(r"C:\Users\me\Documents\Invoices", "invoice.pdf"),
+        ];
+
+        let results = provider.rank_files(files, "invoice", None).await;
+        let folders: Vec<&str> = results.iter().map(|r| r.metadata["path"].as_str().unwrap()).collect();
+        assert_eq!(
+            folders,
+            vec![
+                r"C:\Users\me\Documents\Invoices",
+                r"D:\Work\clients",
+                r"C:\Temp\export",
+                r"E:\Backup\2016\c\Users\me\Documents\misc\taxes\scans",
+            ]
+        );
+
+        let top = &results[0].metadata[PATH_SCORE_KEY];
+        assert_eq!(top["folder_match"], 20.0);
+        assert_eq!(top["location"], 15.0);
+        let buried = &results[3].metadata[PATH_SCORE_KEY];
+        assert_eq!(buried["archive"], 10.0);
+        assert_eq!(buried["depth"], 12.0);
+        assert_eq!(results[0].score - results[2].score, 35.0);
+    }
+
+    #[tokio::test]
+    async fn test_rank_files_prefers_documents_over_noise() {
+        let weights = ActiveExtensionWeights::default();
+        let provider = FileSearchProvider::default().with_extension_weights(weights.clone());
+        let files = || {
+            vec![
+                file_in(r"C:\Temp", "report.tmp"),
+                file_in(r"C:\Temp", "report.log"),
+                file_in(r"C:\Temp", "report.docx"),
+            ]
+        };
+
+        let results = provider.rank_files(files(), "report", None).await;
+        assert_eq!(results[0].title, "report.docx");
+        assert_eq!(results[0].metadata[EXTENSION_SCORE_KEY]["class"], "documents");
+        // Penalized, not hidden
+        assert_eq!(results.len(), 3);
+
+        weights.set([(ExtensionClass::Noise, 30.0)].into());
+        let results = provider.rank_files(files(), "report", None).await;
+        assert_ne!(results[0].title, "report.docx");
+    }
+
+    #[tokio::test]
+    async fn test_sorted_listing_ignores_path_scores() {
+        let provider = FileSearchProvider::default();
+        let files = vec![
+            file_in(r"E:\Backup\old", "a.txt"),
+            file_in(r"C:\Users\me\Desktop", "b.txt"),
+        ];
+
+        let results = provider.rank_files(files, "", Some(FileSort::Newest)).await;
+        assert_eq!(results[0].title, "a.txt");
+        assert!(!results[0].metadata.contains_key(PATH_SCORE_KEY));
+    }
 }
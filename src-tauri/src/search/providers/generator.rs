@@ -0,0 +1,621 @@
+/// Generator provider for small random values
+///
+/// This provider answers a handful of anchored commands:
+/// - `uuid` (or `guid`): a fresh v4 UUID
+/// - `password [length] [classes]`: a random password, e.g. `password 24 alnum`
+/// - `lorem [paragraphs]`: paragraphs of lorem ipsum
+/// - `random [min-max]`: a whole number in a range, `1-100` by default
+///
+/// Every search generates new values, so the provider opts out of the result
+/// cache. Randomness comes from the operating system's CSPRNG.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::clipboard::set_clipboard_text;
+use async_trait::async_trait;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore};
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// Password length when none is given
+const DEFAULT_PASSWORD_LENGTH: usize = 16;
+
+/// Longest password that can be generated
+const MAX_PASSWORD_LENGTH: usize = 256;
+
+/// Most lorem ipsum paragraphs generated at once
+const MAX_LOREM_PARAGRAPHS: usize = 10;
+
+/// Range used by a bare `random`
+const DEFAULT_RANDOM_RANGE: (i64, i64) = (1, 100);
+
+/// Characters shown in place of a password in the result title
+const MASK_CHAR: char = '•';
+
+/// Longest lorem ipsum preview shown in the result title
+const LOREM_PREVIEW_CHARS: usize = 60;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>?";
+
+const LOREM_OPENING: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+
+const LOREM_WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do",
+    "eiusmod", "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua", "enim",
+    "ad", "minim", "veniam", "quis", "nostrud", "exercitation", "ullamco", "laboris", "nisi",
+    "aliquip", "ex", "ea", "commodo", "consequat", "duis", "aute", "irure", "in", "reprehenderit",
+    "voluptate", "velit", "esse", "cillum", "eu", "fugiat", "nulla", "pariatur", "excepteur",
+    "sint", "occaecat", "cupidatat", "non", "proident", "sunt", "culpa", "qui", "officia",
+    "deserunt", "mollit", "anim", "id", "est", "laborum",
+];
+
+/// Character classes a password is built from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharClasses {
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+}
+
+impl CharClasses {
+    /// Every class enabled, the default for `password`
+    pub const ALL: Self = Self {
+        lowercase: true,
+        uppercase: true,
+        digits: true,
+        symbols: true,
+    };
+
+    const NONE: Self = Self {
+        lowercase: false,
+        uppercase: false,
+        digits: false,
+        symbols: false,
+    };
+
+    /// Builds the classes from query words
+    ///
+    /// Naming classes (`lower`, `upper`, `letters`, `digits`, `symbols`, `alnum`)
+    /// keeps only those; `no-<class>` removes one from the selection. Returns
+    /// `None` for unknown words or when nothing is left.
+    pub fn from_words(words: &[&str]) -> Option<Self> {
+        let mut included = Self::NONE;
+        let mut excluded = Self::NONE;
+
+        for word in words {
+            let (target, name) = match word.strip_prefix("no-").or_else(|| word.strip_prefix("no")) {
+                Some(name) if !name.is_empty() => (&mut excluded, name),
+                _ => (&mut included, *word),
+            };
+
+            match name {
+                "lower" | "lowercase" => target.lowercase = true,
+                "upper" | "uppercase" => target.uppercase = true,
+                "letters" => {
+                    target.lowercase = true;
+                    target.uppercase = true;
+                }
+                "digits" | "numbers" => target.digits = true,
+                "symbols" => target.symbols = true,
+                "alnum" => {
+                    target.lowercase = true;
+                    target.uppercase = true;
+                    target.digits = true;
+                }
+                _ => return None,
+            }
+        }
+
+        let mut classes = if included == Self::NONE { Self::ALL } else { included };
+        classes.lowercase &= !excluded.lowercase;
+        classes.uppercase &= !excluded.uppercase;
+        classes.digits &= !excluded.digits;
+        classes.symbols &= !excluded.symbols;
+
+        (classes != Self::NONE).then_some(classes)
+    }
+
+    /// Returns the character set of each enabled class
+    fn sets(&self) -> Vec<&'static str> {
+        [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, set)| enabled.then_some(set))
+        .collect()
+    }
+
+    /// Describes the enabled classes, e.g. "lowercase, digits"
+    fn describe(&self) -> String {
+        [
+            (self.lowercase, "lowercase"),
+            (self.uppercase, "uppercase"),
+            (self.digits, "digits"),
+            (self.symbols, "symbols"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, label)| enabled.then_some(label))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// A parsed generator command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratorQuery {
+    Uuid,
+    Password { length: usize, classes: CharClasses },
+    Lorem { paragraphs: usize },
+    Random { min: i64, max: i64 },
+}
+
+impl GeneratorQuery {
+    /// Parses a query; the whole query must be one of the supported commands
+    pub fn parse(query: &str) -> Option<Self> {
+        let lowered = query.trim().to_lowercase();
+        let mut words = lowered.split_whitespace();
+        let command = words.next()?;
+        let args: Vec<&str> = words.collect();
+
+        match command {
+            "uuid" | "guid" if args.is_empty() => Some(Self::Uuid),
+            "password" | "pwd" => Self::parse_password(&args),
+            "lorem" => Self::parse_lorem(&args),
+            "random" | "rand" => Self::parse_random(&args),
+            _ => None,
+        }
+    }
+
+    fn parse_password(args: &[&str]) -> Option<Self> {
+        let (length, class_words) = match args.split_first() {
+            Some((first, rest)) if first.chars().all(|c| c.is_ascii_digit()) => {
+                (first.parse::<usize>().ok()?, rest)
+            }
+            _ => (DEFAULT_PASSWORD_LENGTH, args),
+        };
+
+        let classes = CharClasses::from_words(class_words)?;
+
+        // Every class must fit at least once
+        if length < classes.sets().len() || length > MAX_PASSWORD_LENGTH {
+            return None;
+        }
+
+        Some(Self::Password { length, classes })
+    }
+
+    fn parse_lorem(args: &[&str]) -> Option<Self> {
+        // Accept "lorem ipsum" and "lorem ipsum 3" as well as "lorem 3"
+        let args = match args.split_first() {
+            Some((&"ipsum", rest)) => rest,
+            _ => args,
+        };
+
+        let paragraphs = match args {
+            [] => 1,
+            [count] => count.parse::<usize>().ok()?,
+            _ => return None,
+        };
+
+        (1..=MAX_LOREM_PARAGRAPHS)
+            .contains(&paragraphs)
+            .then_some(Self::Lorem { paragraphs })
+    }
+
+    fn parse_random(args: &[&str]) -> Option<Self> {
+        let (min, max) = match args.join(" ").as_str() {
+            "" => DEFAULT_RANDOM_RANGE,
+            range => parse_range(range)?,
+        };
+
+        // Inverted ranges such as "100-1" mean the same as "1-100"
+        Some(Self::Random {
+            min: min.min(max),
+            max: min.max(max),
+        })
+    }
+}
+
+/// Parses "1-100", "1..100", "1 to 100" or a single upper bound such as "6"
+fn parse_range(range: &str) -> Option<(i64, i64)> {
+    if let Ok(max) = range.parse::<i64>() {
+        return Some((1, max));
+    }
+
+    for separator in ["..", " to ", "-"] {
+        // Skip a leading minus so negative lower bounds split correctly
+        let search_from = usize::from(range.starts_with('-'));
+        if let Some(position) = range[search_from..].find(separator) {
+            let split = search_from + position;
+            let min = range[..split].trim().parse::<i64>().ok()?;
+            let max = range[split + separator.len()..].trim().parse::<i64>().ok()?;
+            return Some((min, max));
+        }
+    }
+
+    None
+}
+
+/// Returns a random v4 UUID in its hyphenated form
+pub fn generate_uuid() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+
+    // Version 4, RFC 4122 variant
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Returns a random password with at least one character of every class
+pub fn generate_password(length: usize, classes: CharClasses) -> String {
+    let sets = classes.sets();
+    let alphabet: Vec<char> = sets.concat().chars().collect();
+    let mut rng = OsRng;
+
+    let mut password: Vec<char> = sets
+        .iter()
+        .filter_map(|set| set.chars().collect::<Vec<_>>().choose(&mut rng).copied())
+        .collect();
+
+    while password.len() < length {
+        password.push(alphabet[rng.gen_range(0..alphabet.len())]);
+    }
+
+    password.shuffle(&mut rng);
+    password.into_iter().collect()
+}
+
+/// Returns paragraphs of lorem ipsum separated by blank lines
+pub fn generate_lorem(paragraphs: usize) -> String {
+    let mut rng = OsRng;
+
+    (0..paragraphs)
+        .map(|index| {
+            let sentence_count = rng.gen_range(4..=6);
+            let mut sentences = Vec::with_capacity(sentence_count);
+
+            if index == 0 {
+                sentences.push(LOREM_OPENING.to_string());
+            }
+
+            while sentences.len() < sentence_count {
+                let word_count = rng.gen_range(6..=12);
+                let words: Vec<&str> = (0..word_count)
+                    .filter_map(|_| LOREM_WORDS.choose(&mut rng).copied())
+                    .collect();
+                let sentence = words.join(" ");
+
+                let mut chars = sentence.chars();
+                let capitalized: String = chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default();
+                sentences.push(format!("{}.", capitalized));
+            }
+
+            sentences.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Returns a random number in `min..=max`
+pub fn generate_number(min: i64, max: i64) -> i64 {
+    OsRng.gen_range(min..=max)
+}
+
+/// Generator search provider
+pub struct GeneratorProvider {
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl GeneratorProvider {
+    /// Creates a new GeneratorProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing GeneratorProvider");
+        Ok(Self { enabled: true })
+    }
+
+    /// Generates a value for a parsed command and wraps it in a result
+    fn create_search_result(&self, query: &GeneratorQuery) -> SearchResult {
+        let (kind, value, title, subtitle) = match query {
+            GeneratorQuery::Uuid => {
+                let uuid = generate_uuid();
+                ("uuid", uuid.clone(), uuid, "Random UUID (v4)".to_string())
+            }
+            GeneratorQuery::Password { length, classes } => {
+                let password = generate_password(*length, *classes);
+                (
+                    "password",
+                    password,
+                    MASK_CHAR.to_string().repeat(*length),
+                    format!("{}-character password · {}", length, classes.describe()),
+                )
+            }
+            GeneratorQuery::Lorem { paragraphs } => {
+                let text = generate_lorem(*paragraphs);
+                let preview: String = text.chars().take(LOREM_PREVIEW_CHARS).collect();
+                (
+                    "lorem",
+                    text,
+                    format!("{}…", preview.trim_end()),
+                    format!(
+                        "{} {} of lorem ipsum",
+                        paragraphs,
+                        if *paragraphs == 1 { "paragraph" } else { "paragraphs" }
+                    ),
+                )
+            }
+            GeneratorQuery::Random { min, max } => {
+                let number = generate_number(*min, *max).to_string();
+                (
+                    "random",
+                    number.clone(),
+                    number,
+                    format!("Random number from {} to {}", min, max),
+                )
+            }
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!(kind));
+        metadata.insert("value".to_string(), serde_json::json!(value));
+
+        SearchResult {
+            id: format!("generator:{}", kind),
+            title,
+            subtitle: format!("{} · Enter to copy", subtitle),
+            icon: None,
+            result_type: ResultType::Generator,
+            score: 100.0,
+            metadata,
+            action: ResultAction::CopyToClipboard { content: value },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for GeneratorProvider {
+    fn name(&self) -> &str {
+        "Generator"
+    }
+
+    fn priority(&self) -> u8 {
+        87 // Just below date/time
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Generator])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        match GeneratorQuery::parse(&query.query) {
+            Some(parsed) => {
+                debug!("Generating value for '{}'", query.query.trim());
+                Ok(vec![self.create_search_result(&parsed)])
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::Generator {
+            return Err(LauncherError::ExecutionError(
+                "Not a generator result".to_string(),
+            ));
+        }
+
+        match &result.action {
+            ResultAction::CopyToClipboard { content } => {
+                info!("Copying generated {} to clipboard", result.metadata["kind"]);
+                set_clipboard_text(content).await
+            }
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for generator result".to_string(),
+            )),
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "generate",
+            title: "Generate values",
+            description: "uuid, password [length] [classes], lorem [paragraphs] or random [min-max]",
+            example: "password 24",
+            token: None,
+            values: &[],
+        }]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("GeneratorProvider initialized");
+        Ok(())
+    }
+}
+
+impl Default for GeneratorProvider {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_creation() {
+        let provider = GeneratorProvider::new().unwrap();
+        assert_eq!(provider.name(), "Generator");
+        assert_eq!(provider.priority(), 87);
+        assert!(provider.is_enabled());
+        assert!(!provider.cacheable());
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(GeneratorQuery::parse("uuid"), Some(GeneratorQuery::Uuid));
+        assert_eq!(GeneratorQuery::parse("  GUID "), Some(GeneratorQuery::Uuid));
+        assert_eq!(
+            GeneratorQuery::parse("password"),
+            Some(GeneratorQuery::Password { length: 16, classes: CharClasses::ALL })
+        );
+        assert_eq!(
+            GeneratorQuery::parse("password 24"),
+            Some(GeneratorQuery::Password { length: 24, classes: CharClasses::ALL })
+        );
+        assert_eq!(GeneratorQuery::parse("lorem"), Some(GeneratorQuery::Lorem { paragraphs: 1 }));
+        assert_eq!(GeneratorQuery::parse("lorem 2"), Some(GeneratorQuery::Lorem { paragraphs: 2 }));
+        assert_eq!(
+            GeneratorQuery::parse("lorem ipsum 3"),
+            Some(GeneratorQuery::Lorem { paragraphs: 3 })
+        );
+
+        // Ordinary searches and out-of-range arguments are left alone
+        assert_eq!(GeneratorQuery::parse("uuid generator"), None);
+        assert_eq!(GeneratorQuery::parse("password manager"), None);
+        assert_eq!(GeneratorQuery::parse("password 0"), None);
+        assert_eq!(GeneratorQuery::parse("password 100000"), None);
+        assert_eq!(GeneratorQuery::parse("lorem 0"), None);
+        assert_eq!(GeneratorQuery::parse("lorem 50"), None);
+        assert_eq!(GeneratorQuery::parse("randomizer"), None);
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        let random = |query: &str| match GeneratorQuery::parse(query) {
+            Some(GeneratorQuery::Random { min, max }) => Some((min, max)),
+            _ => None,
+        };
+
+        assert_eq!(random("random"), Some((1, 100)));
+        assert_eq!(random("random 1-100"), Some((1, 100)));
+        assert_eq!(random("random 5..10"), Some((5, 10)));
+        assert_eq!(random("random 1 to 6"), Some((1, 6)));
+        assert_eq!(random("random 6"), Some((1, 6)));
+        assert_eq!(random("random -10-10"), Some((-10, 10)));
+        assert_eq!(random("random -20--5"), Some((-20, -5)));
+
+        // Inverted ranges are swapped
+        assert_eq!(random("random 100-1"), Some((1, 100)));
+        assert_eq!(random("random 10 to -10"), Some((-10, 10)));
+
+        assert_eq!(random("random a-b"), None);
+        assert_eq!(random("random 1-"), None);
+    }
+
+    #[test]
+    fn test_generated_numbers_stay_in_range() {
+        for _ in 0..200 {
+            let value = generate_number(-3, 3);
+            assert!((-3..=3).contains(&value));
+        }
+        assert_eq!(generate_number(7, 7), 7);
+    }
+
+    #[test]
+    fn test_character_class_composition() {
+        let classes = |words: &[&str]| CharClasses::from_words(words);
+
+        assert_eq!(classes(&[]), Some(CharClasses::ALL));
+        assert_eq!(
+            classes(&["alnum"]),
+            Some(CharClasses { symbols: false, ..CharClasses::ALL })
+        );
+        assert_eq!(
+            classes(&["no-symbols"]),
+            Some(CharClasses { symbols: false, ..CharClasses::ALL })
+        );
+        assert_eq!(
+            classes(&["digits"]),
+            Some(CharClasses { digits: true, ..CharClasses::NONE })
+        );
+        assert_eq!(
+            classes(&["letters", "nouppercase"]),
+            Some(CharClasses { lowercase: true, ..CharClasses::NONE })
+        );
+        assert_eq!(classes(&["digits", "no-digits"]), None);
+        assert_eq!(classes(&["emoji"]), None);
+
+        // Passwords use only the chosen classes and include each of them
+        let chosen = CharClasses { lowercase: true, digits: true, ..CharClasses::NONE };
+        for _ in 0..50 {
+            let password = generate_password(4, chosen);
+            assert_eq!(password.chars().count(), 4);
+            assert!(password.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+            assert!(password.chars().any(|c| c.is_ascii_lowercase()));
+            assert!(password.chars().any(|c| c.is_ascii_digit()));
+        }
+
+        // Too short to hold one character of every class
+        assert_eq!(GeneratorQuery::parse("password 3"), None);
+        assert!(GeneratorQuery::parse("password 3 alnum").is_some());
+    }
+
+    #[test]
+    fn test_uuid_format() {
+        let uuid = generate_uuid();
+        let groups: Vec<&str> = uuid.split('-').collect();
+
+        assert_eq!(groups.iter().map(|g| g.len()).collect::<Vec<_>>(), vec![8, 4, 4, 4, 12]);
+        assert!(groups[2].starts_with('4'));
+        assert!(matches!(groups[3].chars().next(), Some('8' | '9' | 'a' | 'b')));
+        assert_ne!(uuid, generate_uuid());
+    }
+
+    #[tokio::test]
+    async fn test_search_masks_password_and_regenerates() {
+        let provider = GeneratorProvider::new().unwrap();
+
+        let first = provider.search(&"password 24".into()).await.unwrap().remove(0);
+        let second = provider.search(&"password 24".into()).await.unwrap().remove(0);
+
+        assert_eq!(first.title, "•".repeat(24));
+        let value = first.metadata["value"].as_str().unwrap();
+        assert_eq!(value.chars().count(), 24);
+        match &first.action {
+            ResultAction::CopyToClipboard { content } => assert_eq!(content, value),
+            _ => panic!("Expected CopyToClipboard action"),
+        }
+        assert_ne!(first.metadata["value"], second.metadata["value"]);
+
+        let lorem = provider.search(&"lorem 2".into()).await.unwrap().remove(0);
+        let text = lorem.metadata["value"].as_str().unwrap();
+        assert!(text.starts_with(LOREM_OPENING));
+        assert_eq!(text.split("\n\n").count(), 2);
+
+        assert!(provider.search(&"notepad".into()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,657 @@
+/// Hash provider for file checksums
+///
+/// Answers `<algorithm> <path>` with the file's digest, e.g.
+/// `sha256 C:\Downloads\setup.exe`, and `<algorithm> <path> <expected>` with
+/// whether the digest matches a published one. MD5, SHA-1 and SHA-256 are
+/// supported; `hash <path>` computes SHA-256.
+///
+/// Files up to 100 MB are hashed while searching. Larger ones are hashed
+/// when the result is executed, reporting progress as `execution-status`
+/// events; `cancel_execution` stops the hashing. Digests are remembered per
+/// file version, so searching again shows the value computed before.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::file_search::format_size;
+use crate::search::{ExecutionProgress, QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::clipboard::set_clipboard_text;
+use async_trait::async_trait;
+use lru::LruCache;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::digest::DynDigest;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tracing::{debug, info};
+
+/// Files larger than this, in bytes, are hashed on execution with progress
+pub const LARGE_FILE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Bytes read per step; cancellation is checked between steps
+const CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Digests remembered across searches
+const MEMO_CAPACITY: usize = 32;
+
+/// Metadata key hinting how to color the result: "success" or "danger"
+pub const TONE_KEY: &str = "tone";
+
+/// Digest algorithms the provider computes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Parses an algorithm name such as `sha256` or `SHA-1`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "md5" => Some(Self::Md5),
+            "sha1" | "sha-1" => Some(Self::Sha1),
+            "sha256" | "sha-256" => Some(Self::Sha256),
+            _ => None,
+        }
+    }
+
+    /// Name shown to the user, e.g. "SHA-256"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA-1",
+            Self::Sha256 => "SHA-256",
+        }
+    }
+
+    /// Number of hex digits in a digest
+    pub fn hex_len(&self) -> usize {
+        match self {
+            Self::Md5 => 32,
+            Self::Sha1 => 40,
+            Self::Sha256 => 64,
+        }
+    }
+
+    fn hasher(&self) -> Box<dyn DynDigest + Send> {
+        match self {
+            Self::Md5 => Box::new(md5::Md5::default()),
+            Self::Sha1 => Box::new(sha1::Sha1::default()),
+            Self::Sha256 => Box::new(sha2::Sha256::default()),
+        }
+    }
+}
+
+/// Hashes everything `reader` yields, returning the digest as lowercase hex
+///
+/// Stops with an error once `cancelled` is set. `on_progress` receives the
+/// number of bytes hashed so far after each chunk.
+pub fn hash_reader(
+    algorithm: HashAlgorithm,
+    mut reader: impl Read,
+    cancelled: &AtomicBool,
+    mut on_progress: impl FnMut(u64),
+) -> Result<String> {
+    let mut hasher = algorithm.hasher();
+    let mut buffer = vec![0u8; CHUNK_BYTES];
+    let mut done = 0u64;
+
+    loop {
+        if cancelled.load(Ordering::Relaxed) {
+            return Err(LauncherError::ExecutionError(format!("{} hashing was cancelled", algorithm.label())));
+        }
+
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        hasher.update(&buffer[..read]);
+        done += read as u64;
+        on_progress(done);
+    }
+
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Reads a digest the way tools and download pages print it
+///
+/// Case, whitespace and `:` separators are ignored, as are a leading `0x`
+/// and an algorithm label such as `sha256:`. Returns `None` unless only hex
+/// digits remain.
+pub fn normalize_hex(value: &str) -> Option<String> {
+    let mut value = value.trim();
+    if let Some((label, rest)) = value.split_once([':', '=']) {
+        if HashAlgorithm::parse(label.trim()).is_some() {
+            value = rest.trim();
+        }
+    }
+    if let Some(rest) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        value = rest;
+    }
+
+    let digits: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit())).then_some(digits)
+}
+
+/// A parsed hash query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashQuery {
+    pub algorithm: HashAlgorithm,
+    pub path: PathBuf,
+    /// Digest to compare against, as typed
+    pub expected: Option<String>,
+}
+
+impl HashQuery {
+    /// Parses `<algorithm> <path> [expected]` or `hash [algorithm] <path> [expected]`
+    ///
+    /// The path must name an existing file by its absolute path; it may be
+    /// quoted, and may contain spaces without quotes.
+    pub fn parse(query: &str) -> Option<Self> {
+        let (command, rest) = query.trim().split_once(char::is_whitespace)?;
+        let (algorithm, rest) = match HashAlgorithm::parse(command) {
+            Some(algorithm) => (algorithm, rest),
+            None if command.eq_ignore_ascii_case("hash") => {
+                let rest = rest.trim_start();
+                match rest.split_once(char::is_whitespace) {
+                    Some((name, after)) => match HashAlgorithm::parse(name) {
+                        Some(algorithm) => (algorithm, after),
+                        None => (HashAlgorithm::Sha256, rest),
+                    },
+                    None => (HashAlgorithm::Sha256, rest),
+                }
+            }
+            None => return None,
+        };
+
+        let (path, expected) = split_path(rest.trim())?;
+        Some(Self { algorithm, path, expected })
+    }
+}
+
+/// Splits the file path off the front of `rest`, returning what follows as
+/// the expected digest
+fn split_path(rest: &str) -> Option<(PathBuf, Option<String>)> {
+    let non_empty = |text: &str| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let (path, after) = quoted.split_once('"')?;
+        let path = PathBuf::from(path);
+        return is_hashable(&path).then(|| (path, non_empty(after)));
+    }
+
+    // The longest prefix naming a file, so unquoted paths may contain spaces
+    let ends: Vec<usize> = rest
+        .char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .map(|(index, _)| index)
+        .chain(std::iter::once(rest.len()))
+        .collect();
+    ends.into_iter().rev().find_map(|end| {
+        let path = PathBuf::from(rest[..end].trim_end());
+        is_hashable(&path).then(|| (path, non_empty(&rest[end..])))
+    })
+}
+
+fn is_hashable(path: &Path) -> bool {
+    path.is_absolute() && path.is_file()
+}
+
+/// A file version a digest was computed for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DigestKey {
+    path: PathBuf,
+    algorithm: HashAlgorithm,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+impl DigestKey {
+    fn for_file(path: &Path, algorithm: HashAlgorithm) -> Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            algorithm,
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// Sets its flag when dropped, so hashing on a blocking thread stops once
+/// the search or execution waiting for it is cancelled
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Hash search provider
+pub struct HashProvider {
+    memo: Arc<Mutex<LruCache<DigestKey, String>>>,
+    /// Files larger than this are hashed on execution
+    large_file_bytes: u64,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl HashProvider {
+    /// Creates a new HashProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing HashProvider");
+        Ok(Self::default())
+    }
+
+    /// Sets the size above which files are hashed on execution
+    pub fn with_large_file_bytes(mut self, bytes: u64) -> Self {
+        self.large_file_bytes = bytes;
+        self
+    }
+
+    fn remembered(&self, key: &DigestKey) -> Option<String> {
+        self.memo.lock().unwrap_or_else(|e| e.into_inner()).get(key).cloned()
+    }
+
+    /// Hashes the file on a blocking thread, reporting progress to `progress`
+    ///
+    /// Dropping the returned future stops the hashing.
+    async fn digest(&self, key: &DigestKey, progress: Option<ExecutionProgress>) -> Result<String> {
+        if let Some(digest) = self.remembered(key) {
+            return Ok(digest);
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let _guard = CancelOnDrop(Arc::clone(&cancelled));
+        let (path, algorithm, total) = (key.path.clone(), key.algorithm, key.size);
+
+        debug!("Computing {} of {}", algorithm.label(), path.display());
+        let digest = tokio::task::spawn_blocking(move || {
+            let file = File::open(&path)?;
+            // About a hundred updates, whatever the size
+            let step = (total / 100).max(1);
+            let mut reported = 0;
+            hash_reader(algorithm, file, &cancelled, |done| {
+                if let Some(progress) = &progress {
+                    if done - reported >= step || done == total {
+                        progress.report(done, total);
+                        reported = done;
+                    }
+                }
+            })
+        })
+        .await
+        .map_err(|e| LauncherError::ExecutionError(format!("Failed to hash file: {}", e)))??;
+
+        self.memo
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(key.clone(), digest.clone());
+        Ok(digest)
+    }
+
+    /// Builds the result for a query, hashing the file unless it is large
+    async fn create_search_result(&self, query: &HashQuery) -> Result<SearchResult> {
+        let key = DigestKey::for_file(&query.path, query.algorithm)?;
+        let digest = match self.remembered(&key) {
+            Some(digest) => Some(digest),
+            None if key.size <= self.large_file_bytes => Some(self.digest(&key, None).await?),
+            None => None,
+        };
+
+        let label = query.algorithm.label();
+        let file_name = query
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| query.path.display().to_string());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("algorithm".to_string(), serde_json::json!(query.algorithm));
+        metadata.insert("path".to_string(), serde_json::json!(query.path));
+        if let Some(expected) = &query.expected {
+            metadata.insert("expected".to_string(), serde_json::json!(expected));
+        }
+
+        let Some(digest) = digest else {
+            metadata.insert("pending".to_string(), serde_json::json!(true));
+            let verb = if query.expected.is_some() { "verify" } else { "copy" };
+            return Ok(self.result(
+                query,
+                format!("Compute {} of {}", label, file_name),
+                format!("{} · Enter to compute and {}", format_size(key.size), verb),
+                String::new(),
+                metadata,
+            ));
+        };
+
+        metadata.insert("digest".to_string(), serde_json::json!(digest));
+        let (title, subtitle) = match &query.expected {
+            None => (digest.clone(), format!("{} of {} · Enter to copy", label, file_name)),
+            Some(expected) => {
+                let (verdict, title) = match normalize_hex(expected) {
+                    Some(hex) if hex.len() != query.algorithm.hex_len() => {
+                        ("invalid", format!("The expected value isn't a {} digest", label))
+                    }
+                    Some(hex) if hex == digest => ("match", format!("{} matches", label)),
+                    Some(_) => ("mismatch", format!("{} does not match", label)),
+                    None => ("invalid", format!("The expected value isn't a {} digest", label)),
+                };
+                let tone = if verdict == "match" { "success" } else { "danger" };
+                metadata.insert("verdict".to_string(), serde_json::json!(verdict));
+                metadata.insert(TONE_KEY.to_string(), serde_json::json!(tone));
+                (title, format!("{} is {} · Enter to copy", file_name, digest))
+            }
+        };
+
+        Ok(self.result(query, title, subtitle, digest, metadata))
+    }
+
+    fn result(
+        &self,
+        query: &HashQuery,
+        title: String,
+        subtitle: String,
+        digest: String,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> SearchResult {
+        SearchResult {
+            id: format!("hash:{}:{}", query.algorithm.label().to_lowercase(), query.path.display()),
+            title,
+            subtitle,
+            icon: None,
+            result_type: ResultType::Hash,
+            score: 100.0,
+            metadata,
+            action: ResultAction::CopyToClipboard { content: digest },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Hashes the file of a result left pending by the search, with progress
+    ///
+    /// Copies the digest, and fails when it doesn't match the expected one.
+    async fn compute_pending(&self, result: &SearchResult) -> Result<()> {
+        let (Some(algorithm), Some(path)) = (
+            metadata_field::<HashAlgorithm>(result, "algorithm"),
+            metadata_field::<PathBuf>(result, "path"),
+        ) else {
+            return Err(LauncherError::ExecutionError("Hash result has no file".to_string()));
+        };
+
+        let key = DigestKey::for_file(&path, algorithm)?;
+        let digest = self.digest(&key, ExecutionProgress::current()).await?;
+        info!("Computed {} of {}", algorithm.label(), path.display());
+        set_clipboard_text(&digest).await?;
+
+        match metadata_field::<String>(result, "expected").as_deref().map(normalize_hex) {
+            Some(Some(hex)) if hex == digest => Ok(()),
+            Some(_) => Err(LauncherError::ExecutionError(format!(
+                "{} does not match: the file's is {}",
+                algorithm.label(),
+                digest
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn metadata_field<T: DeserializeOwned>(result: &SearchResult, key: &str) -> Option<T> {
+    serde_json::from_value(result.metadata.get(key)?.clone()).ok()
+}
+
+#[async_trait]
+impl SearchProvider for HashProvider {
+    fn name(&self) -> &str {
+        "Hash"
+    }
+
+    fn priority(&self) -> u8 {
+        85 // Just below text tools
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Hash])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let Some(parsed) = HashQuery::parse(&query.query) else {
+            return Ok(Vec::new());
+        };
+        Ok(vec![self.create_search_result(&parsed).await?])
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::Hash {
+            return Err(LauncherError::ExecutionError("Not a hash result".to_string()));
+        }
+
+        if result.metadata.get("pending").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return self.compute_pending(result).await;
+        }
+        match &result.action {
+            ResultAction::CopyToClipboard { content } => set_clipboard_text(content).await,
+            _ => Err(LauncherError::ExecutionError("Invalid action for hash result".to_string())),
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        // Files change; the memo checks their size and modification time
+        false
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "hash",
+            title: "File checksum",
+            description: "md5, sha1 or sha256 <path> [expected]: compute or verify a file's digest",
+            example: "sha256 ",
+            token: None,
+            values: &[],
+        }]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("HashProvider initialized");
+        Ok(())
+    }
+}
+
+impl Default for HashProvider {
+    fn default() -> Self {
+        Self {
+            memo: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(MEMO_CAPACITY).expect("memo capacity is not zero"),
+            ))),
+            large_file_bytes: LARGE_FILE_BYTES,
+            enabled: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::progress::ExecutionListener;
+    use crate::types::{ExecutionState, ExecutionStatus};
+
+    fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+        let dir = std::env::temp_dir().join("better_finder_hash_tests");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn hash_bytes(algorithm: HashAlgorithm, bytes: &[u8]) -> String {
+        hash_reader(algorithm, bytes, &AtomicBool::new(false), |_| {}).unwrap()
+    }
+
+    #[test]
+    fn test_known_vectors() {
+        assert_eq!(hash_bytes(HashAlgorithm::Md5, b""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hash_bytes(HashAlgorithm::Md5, b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hash_bytes(HashAlgorithm::Sha1, b""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(hash_bytes(HashAlgorithm::Sha1, b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(
+            hash_bytes(HashAlgorithm::Sha256, b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hash_bytes(HashAlgorithm::Sha256, b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        // A million 'a's spans several chunks
+        assert_eq!(
+            hash_bytes(HashAlgorithm::Sha256, &vec![b'a'; 1_000_000]),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+
+        let cancelled = AtomicBool::new(true);
+        assert!(hash_reader(HashAlgorithm::Md5, &b"abc"[..], &cancelled, |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_expected_hex_normalization() {
+        let digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        for written in [
+            digest.to_string(),
+            digest.to_uppercase(),
+            format!("  {}  ", digest),
+            format!("0x{}", digest),
+            format!("sha256:{}", digest),
+            format!("SHA-256: {}", digest.to_uppercase()),
+            "BA7816BF 8F01CFEA 414140DE 5DAE2223 B00361A3 96177A9C B410FF61 F20015AD".to_string(),
+            "ba:78:16:bf:8f:01:cf:ea:41:41:40:de:5d:ae:22:23:b0:03:61:a3:96:17:7a:9c:b4:10:ff:61:f2:00:15:ad".to_string(),
+        ] {
+            assert_eq!(normalize_hex(&written).as_deref(), Some(digest), "{}", written);
+        }
+
+        assert_eq!(normalize_hex("not a digest"), None);
+        assert_eq!(normalize_hex("0x"), None);
+        assert_eq!(normalize_hex("md5:xyz"), None);
+    }
+
+    #[test]
+    fn test_parse_queries() {
+        let path = temp_file("setup file.exe", b"abc");
+        let shown = path.display().to_string();
+
+        let parsed = HashQuery::parse(&format!("sha256 {}", shown)).unwrap();
+        assert_eq!(parsed.algorithm, HashAlgorithm::Sha256);
+        assert_eq!(parsed.path, path);
+        assert_eq!(parsed.expected, None);
+
+        let parsed = HashQuery::parse(&format!("MD5 \"{}\" ABCD EF", shown)).unwrap();
+        assert_eq!(parsed.algorithm, HashAlgorithm::Md5);
+        assert_eq!(parsed.expected.as_deref(), Some("ABCD EF"));
+
+        // Unquoted paths with spaces, followed by a spaced digest
+        let parsed = HashQuery::parse(&format!("sha-1 {} ab cd", shown)).unwrap();
+        assert_eq!(parsed.path, path);
+        assert_eq!(parsed.expected.as_deref(), Some("ab cd"));
+
+        assert_eq!(HashQuery::parse(&format!("hash {}", shown)).unwrap().algorithm, HashAlgorithm::Sha256);
+        assert_eq!(HashQuery::parse(&format!("hash sha1 {}", shown)).unwrap().algorithm, HashAlgorithm::Sha1);
+
+        assert_eq!(HashQuery::parse("sha256"), None);
+        assert_eq!(HashQuery::parse("sha256 tutorial"), None);
+        assert_eq!(HashQuery::parse("md5 /no/such/file.iso"), None);
+        assert_eq!(HashQuery::parse(&format!("sha512 {}", shown)), None);
+    }
+
+    #[tokio::test]
+    async fn test_search_returns_digest_and_comparison() {
+        let path = temp_file("abc.txt", b"abc");
+        let provider = HashProvider::new().unwrap();
+
+        let query = format!("sha256 {}", path.display());
+        let result = provider.search(&query.as_str().into()).await.unwrap().remove(0);
+        let digest = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert_eq!(result.title, digest);
+        assert_eq!(result.result_type, ResultType::Hash);
+        let ResultAction::CopyToClipboard { content } = &result.action else {
+            panic!("Expected CopyToClipboard action");
+        };
+        assert_eq!(content, digest);
+
+        let query = format!("sha256 {} SHA256:{}", path.display(), digest.to_uppercase());
+        let matched = provider.search(&query.as_str().into()).await.unwrap().remove(0);
+        assert_eq!(matched.title, "SHA-256 matches");
+        assert_eq!(matched.metadata[TONE_KEY], "success");
+
+        let query = format!("md5 {} 00000000000000000000000000000000", path.display());
+        let mismatched = provider.search(&query.as_str().into()).await.unwrap().remove(0);
+        assert_eq!(mismatched.title, "MD5 does not match");
+        assert_eq!(mismatched.metadata["verdict"], "mismatch");
+        assert_eq!(mismatched.metadata[TONE_KEY], "danger");
+
+        let query = format!("md5 {} abc123", path.display());
+        let invalid = provider.search(&query.as_str().into()).await.unwrap().remove(0);
+        assert_eq!(invalid.metadata["verdict"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn test_large_file_hashed_on_execution_with_progress() {
+        let content = vec![7u8; 3 * CHUNK_BYTES + 1000];
+        let path = temp_file("large.bin", &content);
+        let provider = HashProvider::new().unwrap().with_large_file_bytes(CHUNK_BYTES as u64);
+
+        let query = format!("sha1 {}", path.display());
+        let pending = provider.search(&query.as_str().into()).await.unwrap().remove(0);
+        assert_eq!(pending.metadata["pending"], true);
+        assert!(pending.title.starts_with("Compute SHA-1 of large.bin"));
+
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&statuses);
+        let listener: ExecutionListener = Arc::new(move |status: &ExecutionStatus| {
+            recorded.lock().unwrap().push(status.state.clone());
+        });
+        let progress = ExecutionProgress::new("exec-1", &pending.id, Some(listener));
+        // No clipboard off Windows, but the digest is computed and remembered first
+        let _ = progress.scope(provider.execute(&pending)).await;
+
+        let total = content.len() as u64;
+        let reported: Vec<u64> = statuses
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|state| match state {
+                ExecutionState::Progress { done, total: reported_total } => {
+                    assert_eq!(*reported_total, total);
+                    *done
+                }
+                other => panic!("Unexpected state {:?}", other),
+            })
+            .collect();
+        assert_eq!(reported.len(), 4);
+        assert!(reported.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(reported.last(), Some(&total));
+
+        // Searching again shows the remembered digest
+        let done = provider.search(&query.as_str().into()).await.unwrap().remove(0);
+        assert_eq!(done.title, hash_bytes(HashAlgorithm::Sha1, &content));
+        assert!(!done.metadata.contains_key("pending"));
+    }
+}
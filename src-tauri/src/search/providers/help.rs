@@ -0,0 +1,232 @@
+/// Help provider listing the query syntax of the enabled providers
+///
+/// `?` or `help` offers one result per feature in the shared
+/// `SyntaxRegistry`, with an example in the subtitle. Selecting a result puts
+/// the example in the search box. The engine keeps the registry to what the
+/// enabled providers report, so features that can't work aren't listed.
+
+use crate::error::{LauncherError, Result};
+use crate::search::syntax::SYNTAX_FEATURE_KEY;
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature, SyntaxRegistry};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Queries answered with the feature list
+pub const HELP_QUERIES: &[&str] = &["?", "help"];
+
+/// Score of the first help result; later results step down by one
+const HELP_BASE_SCORE: f64 = 100.0;
+
+/// Help search provider
+pub struct HelpProvider {
+    /// Features of the enabled providers, kept up to date by the engine
+    registry: SyntaxRegistry,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl HelpProvider {
+    /// Creates a HelpProvider listing the features in `registry`
+    pub fn new(registry: SyntaxRegistry) -> Result<Self> {
+        info!("Initializing HelpProvider");
+        Ok(Self { registry, enabled: true })
+    }
+
+    /// Converts a feature to a result that fills in its example
+    fn create_search_result(feature: &SyntaxFeature, index: usize) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert(SYNTAX_FEATURE_KEY.to_string(), serde_json::json!(feature.id));
+
+        SearchResult {
+            id: format!("help:{}", feature.id),
+            title: feature.title.to_string(),
+            subtitle: format!("{} — e.g. {}", feature.description, feature.example.trim()),
+            icon: None,
+            result_type: ResultType::Suggestion,
+            score: HELP_BASE_SCORE - index as f64,
+            metadata,
+            action: ResultAction::SetQuery {
+                query: feature.example.to_string(),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for HelpProvider {
+    fn name(&self) -> &str {
+        "Help"
+    }
+
+    fn priority(&self) -> u8 {
+        95
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Suggestion])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if !HELP_QUERIES.contains(&query.folded_query.trim()) {
+            return Ok(Vec::new());
+        }
+
+        Ok(self
+            .registry
+            .features()
+            .iter()
+            .enumerate()
+            .map(|(index, feature)| Self::create_search_result(feature, index))
+            .collect())
+    }
+
+    async fn execute(&self, _result: &SearchResult) -> Result<()> {
+        // Help results only set the query, which the search box does itself
+        Err(LauncherError::ExecutionError(
+            "Help results must be applied by the search box".to_string(),
+        ))
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::providers::{AdminToolsProvider, GeneratorProvider};
+    use crate::search::{LazyProvider, SearchEngine};
+    use std::sync::Arc;
+
+    const SORT: SyntaxFeature = SyntaxFeature {
+        id: "sort",
+        title: "Sort files",
+        description: "Orders file results",
+        example: "sort:new report",
+        token: Some("sort:"),
+        values: &["new", "old"],
+    };
+
+    /// Provider reporting `sort:` that may be disabled
+    struct SortingProvider {
+        enabled: bool,
+    }
+
+    #[async_trait]
+    impl SearchProvider for SortingProvider {
+        fn name(&self) -> &str {
+            "Sorting"
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, _query: &QueryContext) -> Result<Vec<SearchResult>> {
+            Ok(Vec::new())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+
+        async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+            vec![SORT]
+        }
+
+        fn is_enabled(&self) -> bool {
+            self.enabled
+        }
+    }
+
+    async fn engine_with(providers: Vec<Arc<dyn SearchProvider>>) -> Arc<SearchEngine> {
+        let engine = Arc::new(SearchEngine::new());
+        // Leaves only the providers' own syntax listed
+        engine.set_plain_text_markers("").await;
+        for provider in providers {
+            engine.register_provider(provider).await;
+        }
+        let help = HelpProvider::new(engine.syntax_registry()).unwrap();
+        engine.register_provider(Arc::new(help)).await;
+        engine
+    }
+
+    fn help_ids(results: &[SearchResult]) -> Vec<String> {
+        results.iter().filter(|r| r.id.starts_with("help:")).map(|r| r.id.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_registry_is_filled_from_registered_providers() {
+        let engine = engine_with(vec![
+            Arc::new(GeneratorProvider::new().unwrap()),
+            Arc::new(AdminToolsProvider::new().unwrap()),
+        ])
+        .await;
+
+        let ids: Vec<&str> = engine.syntax_registry().features().iter().map(|f| f.id).collect();
+        assert_eq!(ids, vec!["generate", "regedit"]);
+
+        for query in ["?", "help", "HELP"] {
+            assert_eq!(help_ids(&engine.search(query).await), vec!["help:generate", "help:regedit"]);
+        }
+
+        let results = engine.search("?").await;
+        let regedit = results.iter().find(|r| r.id == "help:regedit").unwrap();
+        assert!(regedit.subtitle.contains(r"e.g. regedit HKCU\Software"));
+        assert!(matches!(&regedit.action, ResultAction::SetQuery { query } if query == r"regedit HKCU\Software"));
+
+        assert!(help_ids(&engine.search("helpful").await).is_empty());
+
+        // The engine lists the plain-text marker while there is one
+        engine.set_plain_text_markers("'").await;
+        assert_eq!(
+            help_ids(&engine.search("?").await),
+            vec!["help:generate", "help:regedit", "help:plain_text"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_features_of_disabled_providers_are_hidden() {
+        let engine = engine_with(vec![Arc::new(SortingProvider { enabled: false })]).await;
+        assert!(help_ids(&engine.search("?").await).is_empty());
+        assert!(engine.search("report sort:").await.iter().all(|r| r.id != "syntax_hint:sort"));
+
+        engine.replace_provider(Arc::new(SortingProvider { enabled: true })).await;
+        assert_eq!(help_ids(&engine.search("?").await), vec!["help:sort"]);
+
+        assert!(engine.unregister_provider("Sorting").await);
+        assert!(help_ids(&engine.search("?").await).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lazy_providers_list_their_syntax_before_they_are_built() {
+        let lazy = LazyProvider::new("Sorting", 50, None, || Ok(SortingProvider { enabled: true }))
+            .with_syntax(&[SORT]);
+        let engine = engine_with(vec![Arc::new(lazy)]).await;
+
+        assert_eq!(help_ids(&engine.search("help").await), vec!["help:sort"]);
+    }
+
+    #[tokio::test]
+    async fn test_incomplete_token_gets_a_hint_after_the_results() {
+        let engine = engine_with(vec![
+            Arc::new(SortingProvider { enabled: true }),
+            Arc::new(GeneratorProvider::new().unwrap()),
+        ])
+        .await;
+
+        let results = engine.search("report sort:").await;
+        let hint = results.last().unwrap();
+        assert_eq!(hint.id, "syntax_hint:sort");
+        assert_eq!(hint.title, "sort: new, old");
+        assert!(matches!(&hint.action, ResultAction::SetQuery { query } if query == "report sort:new"));
+
+        assert!(engine.search("report sort:new").await.iter().all(|r| r.id != "syntax_hint:sort"));
+    }
+}
@@ -0,0 +1,490 @@
+/// Freshness probes for the file indexes behind file search
+///
+/// Everything's database silently lags when its service was stopped, and
+/// Windows Search pauses indexing on battery or low disk space; users then
+/// blame the launcher for missing new files. Each file provider keeps a
+/// `FreshnessMonitor` that probes its index at most every `PROBE_INTERVAL`,
+/// reports a stale index in the provider health and adds one notice per
+/// session to file searches, whose action opens the tool fixing it.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::file_search::format_age;
+use crate::types::{ProviderHealth, ResultAction, ResultType, SearchResult};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Least time between two probes of the same index
+pub const PROBE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Age past which an index is considered out of date
+pub const STALE_AFTER: chrono::Duration = chrono::Duration::days(2);
+
+/// Items waiting to be indexed past which new files are likely missing
+pub const BACKLOG_THRESHOLD: u32 = 5_000;
+
+/// `CATALOG_STATUS` values of `ISearchCatalogManager::GetCatalogStatus`
+const CATALOG_STATUS_PAUSED: i32 = 1;
+const CATALOG_STATUS_RECOVERING: i32 = 2;
+const CATALOG_STATUS_SHUTTING_DOWN: i32 = 6;
+
+/// Why Windows Search stopped indexing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseReason {
+    HighIo,
+    HighCpu,
+    HighNotificationRate,
+    LowBattery,
+    LowMemory,
+    LowDisk,
+    DelayedRecovery,
+    UserActive,
+    /// Paused by the user or a policy
+    External,
+    Upgrading,
+    /// Rebuilding a damaged index
+    Recovering,
+    Unknown,
+}
+
+impl PauseReason {
+    /// Maps a `CATALOG_PAUSED_REASON` value
+    fn from_raw(reason: i32) -> Self {
+        match reason {
+            1 => Self::HighIo,
+            2 => Self::HighCpu,
+            3 => Self::HighNotificationRate,
+            4 => Self::LowBattery,
+            5 => Self::LowMemory,
+            6 => Self::LowDisk,
+            7 => Self::DelayedRecovery,
+            8 => Self::UserActive,
+            9 => Self::External,
+            10 => Self::Upgrading,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Whether the indexer resumes within moments on its own, as it does
+    /// once the PC is idle again
+    fn is_transient(self) -> bool {
+        matches!(self, Self::HighIo | Self::HighCpu | Self::HighNotificationRate | Self::UserActive)
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::HighIo | Self::HighCpu | Self::HighNotificationRate | Self::UserActive => {
+                "indexing is paused while the PC is busy"
+            }
+            Self::LowBattery => "indexing is paused to save battery",
+            Self::LowMemory => "indexing is paused because memory is low",
+            Self::LowDisk => "indexing is paused because disk space is low",
+            Self::DelayedRecovery | Self::Recovering => "the index is being rebuilt",
+            Self::External => "indexing was paused",
+            Self::Upgrading => "the index is being upgraded",
+            Self::Unknown => "indexing is paused",
+        }
+    }
+}
+
+/// What a probe found out about an index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexFreshness {
+    /// Whether the index can answer searches at all
+    pub loaded: bool,
+    /// When the index was last written, if known
+    pub last_updated: Option<DateTime<Utc>>,
+    /// Files and notifications waiting to be indexed
+    pub pending_items: u32,
+    /// Why indexing stopped, when it did
+    pub paused: Option<PauseReason>,
+}
+
+impl IndexFreshness {
+    /// Describes why the index may miss new files, or `None` when it looks current
+    pub fn staleness(&self, now: DateTime<Utc>) -> Option<String> {
+        if !self.loaded {
+            return Some("the index isn't loaded".to_string());
+        }
+        if let Some(reason) = self.paused.filter(|reason| !reason.is_transient()) {
+            return Some(reason.describe().to_string());
+        }
+        if self.pending_items > BACKLOG_THRESHOLD {
+            return Some(format!("{} items are waiting to be indexed", self.pending_items));
+        }
+        match self.last_updated {
+            Some(updated) if now - updated > STALE_AFTER => Some(format!(
+                "last updated {}",
+                format_age(updated.timestamp(), now.timestamp())
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Raw status of the Windows Search system catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatalogStatus {
+    /// A `CATALOG_STATUS` value
+    pub status: i32,
+    /// A `CATALOG_PAUSED_REASON` value
+    pub paused_reason: i32,
+    pub items_to_index: u32,
+}
+
+/// Reads the status of the Windows Search catalog
+pub trait SearchCatalog: Send + Sync {
+    fn status(&self) -> Result<CatalogStatus>;
+}
+
+/// Maps the catalog status to the freshness of the index
+pub fn catalog_freshness(status: CatalogStatus) -> IndexFreshness {
+    let paused = match status.status {
+        CATALOG_STATUS_PAUSED => Some(PauseReason::from_raw(status.paused_reason)),
+        CATALOG_STATUS_RECOVERING => Some(PauseReason::Recovering),
+        _ => None,
+    };
+
+    IndexFreshness {
+        loaded: status.status != CATALOG_STATUS_SHUTTING_DOWN,
+        last_updated: None,
+        pending_items: status.items_to_index,
+        paused,
+    }
+}
+
+/// The `SystemIndex` catalog, read through `ISearchCatalogManager`
+pub struct SystemCatalog;
+
+#[cfg(windows)]
+impl SearchCatalog for SystemCatalog {
+    fn status(&self) -> Result<CatalogStatus> {
+        use windows::core::w;
+        use windows::Win32::System::Com::{
+            CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_LOCAL_SERVER, COINIT_MULTITHREADED,
+        };
+        use windows::Win32::System::Search::{
+            CSearchManager, ISearchCatalogManager, ISearchManager, CATALOG_PAUSED_REASON, CATALOG_STATUS,
+        };
+
+        unsafe {
+            let initialized = CoInitializeEx(None, COINIT_MULTITHREADED).is_ok();
+            let status = (|| -> windows::core::Result<CatalogStatus> {
+                let manager: ISearchManager = CoCreateInstance(&CSearchManager, None, CLSCTX_LOCAL_SERVER)?;
+                let catalog: ISearchCatalogManager = manager.GetCatalog(w!("SystemIndex"))?;
+
+                let (mut incremental, mut notifications, mut high_priority) = (0i32, 0i32, 0i32);
+                catalog.NumberOfItemsToIndex(&mut incremental, &mut notifications, &mut high_priority)?;
+
+                let mut status = CATALOG_STATUS::default();
+                let mut paused_reason = CATALOG_PAUSED_REASON::default();
+                catalog.GetCatalogStatus(&mut status, &mut paused_reason)?;
+
+                let pending = [incremental, notifications, high_priority]
+                    .iter()
+                    .map(|count| (*count).max(0) as u32)
+                    .fold(0u32, u32::saturating_add);
+                Ok(CatalogStatus {
+                    status: status.0,
+                    paused_reason: paused_reason.0,
+                    items_to_index: pending,
+                })
+            })();
+            if initialized {
+                CoUninitialize();
+            }
+
+            status.map_err(|e| LauncherError::SearchError(format!("Windows Search catalog status unavailable: {}", e)))
+        }
+    }
+}
+
+#[cfg(not(windows))]
+impl SearchCatalog for SystemCatalog {
+    fn status(&self) -> Result<CatalogStatus> {
+        Err(LauncherError::SearchError(
+            "Windows Search is only available on Windows".to_string(),
+        ))
+    }
+}
+
+/// Folders Everything may keep its database in, depending on how it was installed
+fn everything_folders() -> Vec<PathBuf> {
+    ["APPDATA", "LOCALAPPDATA", "ProgramFiles", "ProgramFiles(x86)"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(|dir| PathBuf::from(dir).join("Everything"))
+        .collect()
+}
+
+/// When Everything last saved its database
+///
+/// Everything keeps its index in memory and writes it out now and then, so
+/// an old file is only a hint that its service stopped.
+pub fn everything_db_modified() -> Option<DateTime<Utc>> {
+    everything_folders()
+        .into_iter()
+        .filter_map(|folder| std::fs::metadata(folder.join("Everything.db")).and_then(|m| m.modified()).ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+}
+
+/// Freshness of Everything's index, given whether its database is loaded
+pub fn everything_freshness(loaded: bool) -> IndexFreshness {
+    IndexFreshness {
+        loaded,
+        last_updated: everything_db_modified(),
+        pending_items: 0,
+        paused: None,
+    }
+}
+
+/// The tool a stale index notice opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexTool {
+    Everything,
+    IndexingOptions,
+}
+
+impl IndexTool {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Everything => "Everything",
+            Self::IndexingOptions => "Indexing Options",
+        }
+    }
+
+    fn action(self) -> ResultAction {
+        match self {
+            // Starting Everything again brings up the running instance's window
+            Self::Everything => ResultAction::ExecuteCommand {
+                command: everything_folders()
+                    .into_iter()
+                    .map(|folder| folder.join("Everything.exe"))
+                    .find(|exe| exe.is_file())
+                    .map_or_else(|| "Everything.exe".to_string(), |exe| exe.to_string_lossy().into_owned()),
+                args: Vec::new(),
+            },
+            Self::IndexingOptions => ResultAction::ExecuteCommand {
+                command: "control.exe".to_string(),
+                args: vec!["srchadmin.dll".to_string()],
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct MonitorState {
+    probed_at: Option<Instant>,
+    freshness: Option<IndexFreshness>,
+}
+
+/// Probes one index lazily and tells the user once per session when it is stale
+pub struct FreshnessMonitor {
+    tool: IndexTool,
+    state: Mutex<MonitorState>,
+    notified: AtomicBool,
+}
+
+impl FreshnessMonitor {
+    pub fn new(tool: IndexTool) -> Self {
+        Self {
+            tool,
+            state: Mutex::new(MonitorState::default()),
+            notified: AtomicBool::new(false),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MonitorState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns whether a probe is due, claiming it so concurrent searches
+    /// don't probe too; report its outcome with `record`
+    pub fn begin_probe(&self, now: Instant) -> bool {
+        let mut state = self.lock();
+        let due = state
+            .probed_at
+            .is_none_or(|probed_at| now.saturating_duration_since(probed_at) >= PROBE_INTERVAL);
+        if due {
+            state.probed_at = Some(now);
+        }
+        due
+    }
+
+    /// Stores what a probe found; `None` keeps the previous finding
+    pub fn record(&self, freshness: Option<IndexFreshness>) {
+        if let Some(freshness) = freshness {
+            self.lock().freshness = Some(freshness);
+        }
+    }
+
+    /// Probes with `probe` when due and returns the latest finding
+    pub fn check(&self, now: Instant, probe: impl FnOnce() -> Option<IndexFreshness>) -> Option<IndexFreshness> {
+        if self.begin_probe(now) {
+            self.record(probe());
+        }
+        self.lock().freshness.clone()
+    }
+
+    /// Why the index may be out of date, from the latest probe
+    pub fn staleness(&self, now: DateTime<Utc>) -> Option<String> {
+        self.lock().freshness.as_ref()?.staleness(now)
+    }
+
+    /// Health of a provider answering from this index, from the latest probe
+    pub fn health(&self, now: DateTime<Utc>) -> ProviderHealth {
+        match self.staleness(now) {
+            Some(reason) => ProviderHealth::Stale { reason },
+            None => ProviderHealth::Ready,
+        }
+    }
+
+    /// Returns the notice to add to a file search, only the first time the
+    /// index is found stale this session
+    pub fn take_notice(&self, provider: &str, now: DateTime<Utc>) -> Option<SearchResult> {
+        let reason = self.staleness(now)?;
+        if self.notified.swap(true, Ordering::Relaxed) {
+            return None;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!("index_notice"));
+
+        Some(SearchResult {
+            id: format!("index_notice:{}", provider),
+            title: format!("Index may be out of date — {}", reason),
+            subtitle: format!("Newer files may be missing · Enter opens {}", self.tool.name()),
+            icon: None,
+            result_type: ResultType::Suggestion,
+            score: 0.0,
+            metadata,
+            action: self.tool.action(),
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn updated(days_ago: i64, now: DateTime<Utc>) -> IndexFreshness {
+        IndexFreshness {
+            loaded: true,
+            last_updated: Some(now - chrono::Duration::days(days_ago)),
+            pending_items: 0,
+            paused: None,
+        }
+    }
+
+    /// Stands in for `ISearchCatalogManager`
+    struct FakeCatalog(Result<CatalogStatus>);
+
+    impl SearchCatalog for FakeCatalog {
+        fn status(&self) -> Result<CatalogStatus> {
+            match &self.0 {
+                Ok(status) => Ok(*status),
+                Err(e) => Err(LauncherError::SearchError(e.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_staleness_threshold() {
+        let now = Utc::now();
+        assert_eq!(updated(1, now).staleness(now), None);
+        assert_eq!(updated(2, now).staleness(now), None);
+        assert_eq!(updated(3, now).staleness(now).as_deref(), Some("last updated 3 days ago"));
+
+        // Without a known age only the other signs count
+        let unknown = IndexFreshness { last_updated: None, ..updated(0, now) };
+        assert_eq!(unknown.staleness(now), None);
+        let unloaded = IndexFreshness { loaded: false, ..updated(0, now) };
+        assert_eq!(unloaded.staleness(now).as_deref(), Some("the index isn't loaded"));
+        let backlog = IndexFreshness { pending_items: BACKLOG_THRESHOLD + 1, ..unknown.clone() };
+        assert!(backlog.staleness(now).unwrap().contains("waiting to be indexed"));
+    }
+
+    #[test]
+    fn test_catalog_status_mapping() {
+        let now = Utc::now();
+        let freshness = |status: i32, paused_reason: i32, items_to_index: u32| {
+            let catalog = FakeCatalog(Ok(CatalogStatus { status, paused_reason, items_to_index }));
+            catalog_freshness(catalog.status().unwrap())
+        };
+
+        // Idle and crawling incrementally are current
+        assert_eq!(freshness(0, 0, 0).staleness(now), None);
+        assert_eq!(freshness(4, 0, 120).staleness(now), None);
+
+        let on_battery = freshness(1, 4, 0);
+        assert_eq!(on_battery.paused, Some(PauseReason::LowBattery));
+        assert_eq!(on_battery.staleness(now).as_deref(), Some("indexing is paused to save battery"));
+
+        // Backing off while the user is active resumes on its own
+        assert_eq!(freshness(1, 8, 0).staleness(now), None);
+        assert!(freshness(1, 8, BACKLOG_THRESHOLD * 2).staleness(now).is_some());
+
+        assert_eq!(freshness(2, 0, 0).paused, Some(PauseReason::Recovering));
+        assert!(!freshness(6, 0, 0).loaded);
+        assert_eq!(freshness(1, 42, 0).paused, Some(PauseReason::Unknown));
+
+        let failing = FakeCatalog(Err(LauncherError::SearchError("service stopped".to_string())));
+        assert!(failing.status().is_err());
+    }
+
+    #[test]
+    fn test_probes_at_most_every_interval() {
+        let monitor = FreshnessMonitor::new(IndexTool::Everything);
+        let start = Instant::now();
+        let now = Utc::now();
+        let probes = std::cell::Cell::new(0);
+        let probe = |days_ago: i64| {
+            probes.set(probes.get() + 1);
+            Some(updated(days_ago, now))
+        };
+
+        monitor.check(start, || probe(5));
+        monitor.check(start + Duration::from_secs(60), || probe(0));
+        assert_eq!(probes.get(), 1);
+        assert!(monitor.staleness(now).is_some());
+
+        let fresh = monitor.check(start + PROBE_INTERVAL, || probe(0));
+        assert_eq!(probes.get(), 2);
+        assert_eq!(fresh, Some(updated(0, now)));
+        assert_eq!(monitor.health(now), ProviderHealth::Ready);
+    }
+
+    #[test]
+    fn test_notice_shown_once_per_session() {
+        let now = Utc::now();
+        let monitor = FreshnessMonitor::new(IndexTool::IndexingOptions);
+
+        // Nothing probed yet, or a current index, gives no notice and doesn't use it up
+        assert!(monitor.take_notice("WindowsSearch", now).is_none());
+        monitor.record(Some(updated(0, now)));
+        assert!(monitor.take_notice("WindowsSearch", now).is_none());
+
+        monitor.record(Some(catalog_freshness(CatalogStatus { status: 1, paused_reason: 4, items_to_index: 0 })));
+        assert!(matches!(monitor.health(now), ProviderHealth::Stale { .. }));
+        let notice = monitor.take_notice("WindowsSearch", now).unwrap();
+        assert_eq!(notice.title, "Index may be out of date — indexing is paused to save battery");
+        assert!(matches!(
+            &notice.action,
+            ResultAction::ExecuteCommand { command, args } if command == "control.exe" && args == &["srchadmin.dll"]
+        ));
+        assert!(monitor.take_notice("WindowsSearch", now).is_none());
+
+        // Another provider's monitor has its own notice
+        let everything = FreshnessMonitor::new(IndexTool::Everything);
+        everything.record(Some(updated(4, now)));
+        let notice = everything.take_notice("FileSearch", now).unwrap();
+        assert_eq!(notice.title, "Index may be out of date — last updated 4 days ago");
+    }
+}
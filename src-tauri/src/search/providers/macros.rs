@@ -0,0 +1,363 @@
+/// Query macro provider for user-defined parameterized actions
+///
+/// A macro pairs a keyword with an action template, e.g. `proj` opening
+/// `C:\Projects\{arg}` in VS Code. Typing `proj website` offers one result
+/// previewing the action with `{arg}` replaced by `website`; a bare `proj`
+/// offers a hint instead, since there is nothing to substitute yet. Macros
+/// come from the settings and are checked by `validate_macros` on save.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider};
+use crate::settings::QueryMacro;
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// The only placeholder a template may use
+pub const ARG_PLACEHOLDER: &str = "{arg}";
+
+/// Score of a macro result; a typed keyword is an explicit request
+const MACRO_SCORE: f64 = 100.0;
+
+/// The macros in use
+///
+/// Clones share the macros, so saving settings changes what the registered
+/// provider matches without re-registering it.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveMacros(Arc<RwLock<Vec<QueryMacro>>>);
+
+impl ActiveMacros {
+    pub fn get(&self) -> Vec<QueryMacro> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, macros: Vec<QueryMacro>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = macros;
+    }
+}
+
+/// The text fields of an action, which may hold placeholders
+fn template_fields(action: &ResultAction) -> Vec<&str> {
+    match action {
+        ResultAction::OpenFile { path } | ResultAction::LaunchApp { path } => vec![path],
+        ResultAction::ExecuteCommand { command, args } => {
+            std::iter::once(command.as_str()).chain(args.iter().map(String::as_str)).collect()
+        }
+        ResultAction::CopyToClipboard { content } => vec![content],
+        ResultAction::OpenUrl { url } => vec![url],
+        ResultAction::WebSearch { query } | ResultAction::SetQuery { query } => vec![query],
+        ResultAction::ExpandGroup { group_id } => vec![group_id],
+    }
+}
+
+/// Names of the `{name}` placeholders in `text`
+///
+/// Braces around anything but a plain identifier, such as `{}` or `{ x }`,
+/// aren't placeholders.
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        let Some(close) = rest.find(['{', '}']) else {
+            break;
+        };
+        let name = &rest[..close];
+        if rest[close..].starts_with('}')
+            && !name.is_empty()
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            names.push(name);
+        }
+        rest = &rest[close..];
+    }
+    names
+}
+
+/// Checks the macros the settings define
+///
+/// Keywords must be single words, used once. Each template must use `{arg}`
+/// at least once and no other placeholder.
+pub fn validate_macros(macros: &[QueryMacro]) -> Result<()> {
+    let mut keywords = Vec::new();
+    for query_macro in macros {
+        let keyword = query_macro.keyword.trim().to_lowercase();
+        if keyword.is_empty() || keyword.contains(char::is_whitespace) {
+            return Err(LauncherError::ConfigError(format!(
+                "Macro keyword '{}' must be a single word",
+                query_macro.keyword
+            )));
+        }
+        if keywords.contains(&keyword) {
+            return Err(LauncherError::ConfigError(format!("Macro keyword '{}' is used twice", keyword)));
+        }
+
+        if matches!(query_macro.template_action, ResultAction::ExpandGroup { .. }) {
+            return Err(LauncherError::ConfigError(format!(
+                "Macro '{}' can't expand a file group",
+                keyword
+            )));
+        }
+
+        let fields = template_fields(&query_macro.template_action);
+        if let Some(unknown) = fields.iter().flat_map(|field| placeholders(field)).find(|name| *name != "arg") {
+            return Err(LauncherError::ConfigError(format!(
+                "Macro '{}' uses the placeholder {{{}}}; only {} is supported",
+                keyword, unknown, ARG_PLACEHOLDER
+            )));
+        }
+        if !fields.iter().any(|field| field.contains(ARG_PLACEHOLDER)) {
+            return Err(LauncherError::ConfigError(format!(
+                "Macro '{}' must use {} in its action",
+                keyword, ARG_PLACEHOLDER
+            )));
+        }
+
+        keywords.push(keyword);
+    }
+    Ok(())
+}
+
+/// Replaces `{arg}` in the template's fields
+///
+/// URLs get the argument URL-encoded. Web search queries take it as typed,
+/// since the search URL is encoded when it is built; paths, commands and
+/// text take it as typed too.
+pub fn expand(template: &ResultAction, arg: &str) -> ResultAction {
+    let plain = |field: &str| field.replace(ARG_PLACEHOLDER, arg);
+
+    match template {
+        ResultAction::OpenFile { path } => ResultAction::OpenFile { path: plain(path) },
+        ResultAction::LaunchApp { path } => ResultAction::LaunchApp { path: plain(path) },
+        ResultAction::ExecuteCommand { command, args } => ResultAction::ExecuteCommand {
+            command: plain(command),
+            args: args.iter().map(|a| plain(a)).collect(),
+        },
+        ResultAction::CopyToClipboard { content } => ResultAction::CopyToClipboard { content: plain(content) },
+        ResultAction::OpenUrl { url } => ResultAction::OpenUrl {
+            url: url.replace(ARG_PLACEHOLDER, &urlencoding::encode(arg)),
+        },
+        ResultAction::WebSearch { query } => ResultAction::WebSearch { query: plain(query) },
+        ResultAction::SetQuery { query } => ResultAction::SetQuery { query: plain(query) },
+        ResultAction::ExpandGroup { group_id } => ResultAction::ExpandGroup { group_id: plain(group_id) },
+    }
+}
+
+/// Describes what a resolved action will do, for the preview subtitle
+fn describe(action: &ResultAction) -> String {
+    match action {
+        ResultAction::OpenFile { path } => format!("Open {}", path),
+        ResultAction::LaunchApp { path } => format!("Launch {}", path),
+        ResultAction::ExecuteCommand { command, args } => {
+            format!("Run {}", std::iter::once(command).chain(args).cloned().collect::<Vec<_>>().join(" "))
+        }
+        ResultAction::CopyToClipboard { content } => format!("Copy \"{}\"", content),
+        ResultAction::OpenUrl { url } => format!("Open {}", url),
+        ResultAction::WebSearch { query } => format!("Search the web for \"{}\"", query),
+        ResultAction::SetQuery { query } => format!("Search for \"{}\"", query),
+        ResultAction::ExpandGroup { group_id } => format!("Show group {}", group_id),
+    }
+}
+
+/// Query macro search provider
+pub struct MacroProvider {
+    macros: ActiveMacros,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl MacroProvider {
+    /// Creates a MacroProvider expanding the macros in `macros`
+    pub fn new(macros: ActiveMacros) -> Result<Self> {
+        info!("Initializing MacroProvider");
+        Ok(Self { macros, enabled: true })
+    }
+
+    /// Preview of the macro's action with `arg` filled in
+    fn create_preview_result(query_macro: &QueryMacro, keyword: &str, arg: &str) -> SearchResult {
+        let action = expand(&query_macro.template_action, arg);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("macro".to_string(), serde_json::json!(keyword));
+
+        SearchResult {
+            id: format!("macro:{}:{}", keyword, arg),
+            title: format!("{} {}", keyword, arg),
+            subtitle: describe(&action),
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score: MACRO_SCORE,
+            metadata,
+            action,
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Hint shown for a keyword typed without its argument
+    ///
+    /// Selecting it only puts the keyword in the search box, so nothing runs
+    /// with an empty argument.
+    fn create_hint_result(query_macro: &QueryMacro, keyword: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("macro".to_string(), serde_json::json!(keyword));
+
+        SearchResult {
+            id: format!("macro:{}", keyword),
+            title: format!("{} <argument>", keyword),
+            subtitle: format!(
+                "Type an argument after '{}' — {}",
+                keyword,
+                describe(&query_macro.template_action)
+            ),
+            icon: None,
+            result_type: ResultType::Suggestion,
+            score: MACRO_SCORE,
+            metadata,
+            action: ResultAction::SetQuery {
+                query: format!("{} ", keyword),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for MacroProvider {
+    fn name(&self) -> &str {
+        "Macros"
+    }
+
+    fn priority(&self) -> u8 {
+        90 // An explicit keyword, like the direct URL
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction, ResultType::Suggestion])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let typed = query.query.trim();
+        let (keyword, arg) = typed.split_once(char::is_whitespace).unwrap_or((typed, ""));
+        let keyword = keyword.to_lowercase();
+        let arg = arg.trim();
+
+        let macros = self.macros.get();
+        let Some(query_macro) = macros.iter().find(|m| m.keyword.trim().to_lowercase() == keyword) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(vec![if arg.is_empty() {
+            Self::create_hint_result(query_macro, &keyword)
+        } else {
+            Self::create_preview_result(query_macro, &keyword, arg)
+        }])
+    }
+
+    /// Macro results run through the engine's default actions
+    async fn execute(&self, _result: &SearchResult) -> Result<()> {
+        Err(LauncherError::ExecutionError("Macros run through the default actions".to_string()))
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_macro(keyword: &str, template_action: ResultAction) -> QueryMacro {
+        QueryMacro {
+            keyword: keyword.to_string(),
+            template_action,
+        }
+    }
+
+    fn provider(macros: Vec<QueryMacro>) -> MacroProvider {
+        let active = ActiveMacros::default();
+        active.set(macros);
+        MacroProvider::new(active).unwrap()
+    }
+
+    #[test]
+    fn test_expand_encodes_only_urls() {
+        let url = expand(&ResultAction::OpenUrl { url: "https://github.com/{arg}".to_string() }, "a b&c");
+        assert!(matches!(url, ResultAction::OpenUrl { url } if url == "https://github.com/a%20b%26c"));
+
+        let search = expand(&ResultAction::WebSearch { query: "rust {arg}".to_string() }, "a&b");
+        assert!(matches!(search, ResultAction::WebSearch { query } if query == "rust a&b"));
+
+        let path = expand(&ResultAction::OpenFile { path: r"C:\Projects\{arg}".to_string() }, "my site");
+        assert!(matches!(path, ResultAction::OpenFile { path } if path == r"C:\Projects\my site"));
+
+        let command = expand(
+            &ResultAction::ExecuteCommand {
+                command: "code".to_string(),
+                args: vec![r"C:\Projects\{arg}".to_string(), "--goto={arg}".to_string()],
+            },
+            "web&api",
+        );
+        assert!(matches!(
+            command,
+            ResultAction::ExecuteCommand { command, args }
+                if command == "code" && args == vec![r"C:\Projects\web&api".to_string(), "--goto=web&api".to_string()]
+        ));
+
+        let copy = expand(&ResultAction::CopyToClipboard { content: "Hi {arg}!".to_string() }, "Ana");
+        assert!(matches!(copy, ResultAction::CopyToClipboard { content } if content == "Hi Ana!"));
+    }
+
+    #[test]
+    fn test_validation_requires_only_arg_placeholder() {
+        let open = |path: &str| ResultAction::OpenFile { path: path.to_string() };
+
+        assert!(validate_macros(&[query_macro("proj", open(r"C:\Projects\{arg}"))]).is_ok());
+        assert!(validate_macros(&[query_macro("json", open(r"C:\{}\{arg}"))]).is_ok());
+
+        let unknown = validate_macros(&[query_macro("proj", open(r"C:\Projects\{name}"))]).unwrap_err();
+        assert!(unknown.to_string().contains("{name}"));
+        let mixed = validate_macros(&[query_macro("proj", open(r"C:\{arg}\{sub}"))]).unwrap_err();
+        assert!(mixed.to_string().contains("{sub}"));
+        let missing = validate_macros(&[query_macro("home", open(r"C:\Users"))]).unwrap_err();
+        assert!(missing.to_string().contains("must use {arg}"));
+
+        assert!(validate_macros(&[query_macro("two words", open("{arg}"))]).is_err());
+        assert!(validate_macros(&[query_macro("", open("{arg}"))]).is_err());
+        assert!(validate_macros(&[query_macro("p", open("{arg}")), query_macro("P", open("x{arg}"))]).is_err());
+        assert!(validate_macros(&[query_macro("g", ResultAction::ExpandGroup { group_id: "{arg}".to_string() })]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keyword_with_argument_previews_action() {
+        let provider = provider(vec![query_macro(
+            "meet",
+            ResultAction::OpenUrl { url: "msteams:/l/meetup-join/{arg}".to_string() },
+        )]);
+
+        let results = provider.search(&"Meet Room 4".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result_type, ResultType::QuickAction);
+        assert_eq!(results[0].subtitle, "Open msteams:/l/meetup-join/Room%204");
+        assert!(matches!(&results[0].action, ResultAction::OpenUrl { url } if url.ends_with("Room%204")));
+
+        assert!(provider.search(&"meeting notes".into()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_keyword_without_argument_shows_hint() {
+        let provider = provider(vec![query_macro("proj", ResultAction::OpenFile { path: r"C:\Projects\{arg}".to_string() })]);
+
+        let results = provider.search(&"proj ".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result_type, ResultType::Suggestion);
+        assert!(matches!(&results[0].action, ResultAction::SetQuery { query } if query == "proj "));
+        assert!(provider.execute(&results[0]).await.is_err());
+    }
+}
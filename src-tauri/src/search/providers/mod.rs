@@ -1,23 +1,83 @@
 pub mod everything;
 pub mod file_search;
 pub mod windows_search;
+pub mod search_index;
+pub mod index_freshness;
+pub mod path_exclusions;
+pub mod path_ranking;
+pub mod extension_ranking;
 pub mod app_search;
 pub mod quick_action;
+pub mod admin_tools;
+pub mod windows_settings;
+pub mod windows_settings_pages;
+pub mod settings_search;
+pub mod network_browse;
+pub mod connectivity;
 pub mod calculator;
+pub mod calculator_history;
+pub mod city_timezones;
+pub mod datetime;
+pub mod favorites;
 pub mod clipboard;
 pub mod bookmark;
+pub mod browser_favicons;
+pub mod browser_launch;
+pub mod browser_history;
 pub mod recent_files;
 pub mod web_search;
+pub mod ssh;
+pub mod recent_projects;
+pub mod generator;
+pub mod text_tools;
+pub mod text_transform;
+pub mod hash;
+pub mod direct_url;
+pub mod macros;
+pub mod dev_docs;
+pub mod help;
+pub mod weather;
+pub mod saved_searches;
+pub mod plugin;
+pub mod profiles;
+pub mod doctor;
 
 #[cfg(test)]
 mod fallback_test;
 
-pub use file_search::FileSearchProvider;
+pub use file_search::{FileSearchConfig, FileSearchProvider};
+pub use path_exclusions::FolderScopes;
+pub use extension_ranking::{ActiveExtensionWeights, ExtensionClass};
 pub use windows_search::WindowsSearchProvider;
 pub use app_search::AppSearchProvider;
 pub use quick_action::QuickActionProvider;
+pub use admin_tools::AdminToolsProvider;
+pub use windows_settings::WindowsSettingsProvider;
+pub use settings_search::{SettingsLocation, SettingsSearchProvider};
+pub use network_browse::NetworkBrowseProvider;
+pub use connectivity::ConnectivityProvider;
 pub use calculator::CalculatorProvider;
-pub use clipboard::ClipboardHistoryProvider;
-pub use bookmark::BookmarkProvider;
-pub use recent_files::RecentFilesProvider;
-pub use web_search::WebSearchProvider;
+pub use calculator_history::CalculatorHistory;
+pub use datetime::DateTimeProvider;
+pub use favorites::FavoritesProvider;
+pub use clipboard::{ClipboardConfig, ClipboardHistoryProvider};
+pub use bookmark::{BookmarkConfig, BookmarkProvider};
+pub use browser_launch::OpenInSourceBrowser;
+pub use browser_history::BrowserHistoryProvider;
+pub use recent_files::{RecentFilesConfig, RecentFilesProvider};
+pub use web_search::{WebSearchConfig, WebSearchProvider};
+pub use ssh::SshProvider;
+pub use recent_projects::RecentProjectsProvider;
+pub use generator::GeneratorProvider;
+pub use text_tools::TextToolsProvider;
+pub use text_transform::TextTransformProvider;
+pub use hash::HashProvider;
+pub use direct_url::DirectUrlProvider;
+pub use macros::{ActiveMacros, MacroProvider};
+pub use dev_docs::{DevDocsConfig, DevDocsProvider};
+pub use help::HelpProvider;
+pub use weather::{ActiveWeatherPreferences, WeatherPreferences, WeatherProvider};
+pub use saved_searches::{SavedSearchProvider, SavedSearchStore};
+pub use plugin::{PluginHost, PluginInfo, PluginProvider};
+pub use profiles::{apply_search_scopes, ActiveProfiles, ProfileList, ProfileProvider};
+pub use doctor::DoctorProvider;
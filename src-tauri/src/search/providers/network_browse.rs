@@ -0,0 +1,700 @@
+/// Network browser for UNC paths and mapped network drives
+///
+/// `\\fileserver` lists the server's disk shares, enumerated with NetShareEnum
+/// on a blocking thread under a short timeout so an offline host can't stall
+/// the search. Each host's shares are cached for a minute.
+/// `\\fileserver\projects\` lists the share's contents, as does a path on a
+/// mapped network drive. Text after the last backslash filters the listing.
+///
+/// Hidden administrative shares such as `C$` are left out unless the query
+/// contains a `$`. When the server refuses the current credentials, a single
+/// result opens Explorer on the path so Windows asks for them.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult, PRESERVE_PROVIDER_ORDER_KEY};
+use crate::utils::IconCache;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// How long share enumeration and directory listing may take
+const NETWORK_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// How long a host's share list is reused
+const SHARE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Most entries listed for one share or folder
+const MAX_ENTRIES: usize = 50;
+
+/// Score of the first listed entry; later entries score slightly less
+const LISTING_BASE_SCORE: f64 = 95.0;
+
+/// What a network path query asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkQuery {
+    /// `\\host` or `\\host\fil`: the host's shares matching `filter`
+    Shares { host: String, filter: String },
+    /// `\\host\share\dir\fil` or `Z:\dir\fil`: entries of `dir` matching `filter`
+    Directory { dir: String, filter: String },
+}
+
+/// Detects UNC and drive-letter path queries
+///
+/// `dir` keeps its trailing backslash. Device paths such as `\\?\C:\` and
+/// `\\.\pipe\` are not network paths and are ignored.
+pub fn parse_network_query(query: &str) -> Option<NetworkQuery> {
+    let query = query.trim().replace('/', "\\");
+
+    if let Some(rest) = query.strip_prefix(r"\\") {
+        let (host, path) = rest.split_once('\\').unwrap_or((rest, ""));
+        if host.is_empty() || host == "?" || host == "." {
+            return None;
+        }
+
+        return Some(match path.rfind('\\') {
+            None => NetworkQuery::Shares {
+                host: host.to_string(),
+                filter: path.to_string(),
+            },
+            Some(split) => NetworkQuery::Directory {
+                dir: format!(r"\\{}\{}", host, &path[..=split]),
+                filter: path[split + 1..].to_string(),
+            },
+        });
+    }
+
+    let mut chars = query.chars();
+    let is_drive_path = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.next() == Some(':')
+        && chars.next() == Some('\\');
+    if !is_drive_path {
+        return None;
+    }
+
+    let split = query.rfind('\\')?;
+    Some(NetworkQuery::Directory {
+        dir: query[..=split].to_string(),
+        filter: query[split + 1..].to_string(),
+    })
+}
+
+/// A shared folder on a server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub name: String,
+    /// Description set by the server's administrator
+    pub remark: String,
+}
+
+impl Share {
+    /// Administrative and other hidden shares end with `$`
+    pub fn is_hidden(&self) -> bool {
+        self.name.ends_with('$')
+    }
+}
+
+/// Why a host's shares couldn't be listed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnumerationError {
+    /// The server refused the current credentials
+    AccessDenied,
+    /// The server didn't answer within the timeout
+    TimedOut,
+    Failed(String),
+}
+
+/// Lists the disk shares of a host; calls may block on the network
+pub trait ShareEnumerator: Send + Sync {
+    fn enumerate(&self, host: &str) -> std::result::Result<Vec<Share>, EnumerationError>;
+}
+
+/// Enumerates shares with NetShareEnum
+pub struct SystemShareEnumerator;
+
+#[cfg(windows)]
+impl ShareEnumerator for SystemShareEnumerator {
+    fn enumerate(&self, host: &str) -> std::result::Result<Vec<Share>, EnumerationError> {
+        use windows::core::HSTRING;
+        use windows::Win32::Foundation::{ERROR_ACCESS_DENIED, ERROR_LOGON_FAILURE};
+        use windows::Win32::NetworkManagement::NetManagement::NetApiBufferFree;
+        use windows::Win32::Storage::FileSystem::{NetShareEnum, SHARE_INFO_1, STYPE_DISKTREE, STYPE_MASK};
+
+        let server = HSTRING::from(format!(r"\\{}", host));
+        let mut buffer: *mut u8 = std::ptr::null_mut();
+        let mut read = 0u32;
+        let mut total = 0u32;
+
+        let status = unsafe {
+            NetShareEnum(&server, 1, &mut buffer, u32::MAX, &mut read, &mut total, None)
+        };
+
+        let shares = if status == 0 && !buffer.is_null() {
+            let entries = unsafe { std::slice::from_raw_parts(buffer as *const SHARE_INFO_1, read as usize) };
+            entries
+                .iter()
+                .filter(|entry| entry.shi1_type.0 & STYPE_MASK.0 == STYPE_DISKTREE.0)
+                .map(|entry| Share {
+                    name: unsafe { entry.shi1_netname.to_string() }.unwrap_or_default(),
+                    remark: unsafe { entry.shi1_remark.to_string() }.unwrap_or_default(),
+                })
+                .filter(|share| !share.name.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if !buffer.is_null() {
+            unsafe {
+                NetApiBufferFree(Some(buffer as *const _));
+            }
+        }
+
+        match status {
+            0 => Ok(shares),
+            code if code == ERROR_ACCESS_DENIED.0 || code == ERROR_LOGON_FAILURE.0 => {
+                Err(EnumerationError::AccessDenied)
+            }
+            code => Err(EnumerationError::Failed(format!("NetShareEnum failed with error {}", code))),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+impl ShareEnumerator for SystemShareEnumerator {
+    fn enumerate(&self, _host: &str) -> std::result::Result<Vec<Share>, EnumerationError> {
+        Err(EnumerationError::Failed(
+            "Share enumeration is only supported on Windows".to_string(),
+        ))
+    }
+}
+
+/// Shares whose names contain `filter`, case-insensitively
+///
+/// Hidden shares are only kept when the filter itself contains a `$`.
+pub fn filter_shares<'a>(shares: &'a [Share], filter: &str) -> Vec<&'a Share> {
+    let filter = filter.to_lowercase();
+    let show_hidden = filter.contains('$');
+
+    shares
+        .iter()
+        .filter(|share| show_hidden || !share.is_hidden())
+        .filter(|share| share.name.to_lowercase().contains(&filter))
+        .collect()
+}
+
+/// Whether `dir` is on the network: a UNC path or a mapped drive
+#[cfg(windows)]
+fn is_network_path(dir: &str) -> bool {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    /// `DRIVE_REMOTE` from WindowsProgramming, which isn't otherwise needed
+    const DRIVE_REMOTE: u32 = 4;
+
+    if dir.starts_with(r"\\") {
+        return true;
+    }
+    let root = HSTRING::from(&dir[..3]);
+    unsafe { GetDriveTypeW(&root) == DRIVE_REMOTE }
+}
+
+#[cfg(not(windows))]
+fn is_network_path(dir: &str) -> bool {
+    dir.starts_with(r"\\")
+}
+
+/// An entry of a listed folder
+#[derive(Debug, Clone)]
+struct DirEntry {
+    name: String,
+    is_dir: bool,
+}
+
+/// Lists `dir`, folders first, skipping entries whose names don't contain `filter`
+fn list_directory(dir: &str, filter: &str) -> std::io::Result<Vec<DirEntry>> {
+    let filter = filter.to_lowercase();
+    let mut entries: Vec<DirEntry> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.to_lowercase().contains(&filter) {
+                return None;
+            }
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            Some(DirEntry { name, is_dir })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    entries.truncate(MAX_ENTRIES);
+    Ok(entries)
+}
+
+/// A host's shares and when they were enumerated
+struct CachedShares {
+    shares: Vec<Share>,
+    fetched_at: Instant,
+}
+
+/// Network share and folder browser
+pub struct NetworkBrowseProvider {
+    enumerator: Arc<dyn ShareEnumerator>,
+    /// Last successful enumeration per lowercase host name
+    share_cache: Mutex<HashMap<String, CachedShares>>,
+    timeout: Duration,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl NetworkBrowseProvider {
+    /// Creates a new NetworkBrowseProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing NetworkBrowseProvider");
+        Ok(Self::with_enumerator(Arc::new(SystemShareEnumerator)))
+    }
+
+    /// Creates a provider listing shares with `enumerator`
+    pub fn with_enumerator(enumerator: Arc<dyn ShareEnumerator>) -> Self {
+        Self {
+            enumerator,
+            share_cache: Mutex::new(HashMap::new()),
+            timeout: NETWORK_TIMEOUT,
+            enabled: true,
+        }
+    }
+
+    /// Sets how long enumeration and listing may take
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Shares of `host`, from the cache when enumerated in the last minute
+    async fn shares(&self, host: &str) -> std::result::Result<Vec<Share>, EnumerationError> {
+        let key = host.to_lowercase();
+        if let Some(cached) = self.share_cache.lock().unwrap().get(&key) {
+            if cached.fetched_at.elapsed() < SHARE_CACHE_TTL {
+                return Ok(cached.shares.clone());
+            }
+        }
+
+        // On timeout the blocking call is left to finish on its own thread
+        let enumerator = self.enumerator.clone();
+        let owned_host = host.to_string();
+        let task = tokio::task::spawn_blocking(move || enumerator.enumerate(&owned_host));
+        let mut shares = match tokio::time::timeout(self.timeout, task).await {
+            Ok(Ok(result)) => result?,
+            Ok(Err(e)) => return Err(EnumerationError::Failed(format!("Share enumeration task failed: {}", e))),
+            Err(_) => return Err(EnumerationError::TimedOut),
+        };
+
+        shares.sort_by_key(|share| share.name.to_lowercase());
+        self.share_cache.lock().unwrap().insert(
+            key,
+            CachedShares {
+                shares: shares.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(shares)
+    }
+
+    async fn share_results(&self, host: &str, filter: &str) -> Vec<SearchResult> {
+        let unc = format!(r"\\{}", host);
+        match self.shares(host).await {
+            Ok(shares) => filter_shares(&shares, filter)
+                .into_iter()
+                .enumerate()
+                .map(|(index, share)| {
+                    let path = format!(r"{}\{}", unc, share.name);
+                    let subtitle = if share.remark.is_empty() {
+                        unc.clone()
+                    } else {
+                        format!("{} • {}", unc, share.remark)
+                    };
+                    Self::folder_result(&path, &share.name, subtitle, index)
+                })
+                .collect(),
+            Err(EnumerationError::AccessDenied) => vec![Self::access_denied_result(&unc)],
+            Err(EnumerationError::TimedOut) => {
+                debug!("Share enumeration on {} timed out", unc);
+                vec![Self::explorer_result(&unc, "isn't responding")]
+            }
+            Err(EnumerationError::Failed(reason)) => {
+                warn!("Could not list shares on {}: {}", unc, reason);
+                vec![Self::explorer_result(&unc, "couldn't be listed")]
+            }
+        }
+    }
+
+    async fn directory_results(&self, dir: &str, filter: &str) -> Vec<SearchResult> {
+        let owned_dir = dir.to_string();
+        let owned_filter = filter.to_string();
+        let task = tokio::task::spawn_blocking(move || list_directory(&owned_dir, &owned_filter));
+        let location = dir.trim_end_matches('\\');
+
+        match tokio::time::timeout(self.timeout, task).await {
+            Ok(Ok(Ok(entries))) => entries
+                .into_iter()
+                .enumerate()
+                .map(|(index, entry)| {
+                    let path = format!("{}{}", dir, entry.name);
+                    if entry.is_dir {
+                        Self::folder_result(&path, &entry.name, location.to_string(), index)
+                    } else {
+                        Self::file_result(&path, &entry.name, location, index)
+                    }
+                })
+                .collect(),
+            Ok(Ok(Err(e))) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                vec![Self::access_denied_result(location)]
+            }
+            Ok(Ok(Err(e))) => {
+                debug!("Could not list {}: {}", dir, e);
+                Vec::new()
+            }
+            Ok(Err(e)) => {
+                warn!("Directory listing task failed: {}", e);
+                Vec::new()
+            }
+            Err(_) => {
+                debug!("Listing {} timed out", dir);
+                vec![Self::explorer_result(location, "isn't responding")]
+            }
+        }
+    }
+
+    /// Metadata marking a listing entry, whose order must survive ranking
+    fn listing_metadata(path: &str) -> HashMap<String, serde_json::Value> {
+        let mut metadata = HashMap::new();
+        metadata.insert("path".to_string(), serde_json::json!(path));
+        metadata.insert(PRESERVE_PROVIDER_ORDER_KEY.to_string(), serde_json::json!(true));
+        metadata
+    }
+
+    /// A share or folder; selecting it lists its contents
+    fn folder_result(path: &str, name: &str, subtitle: String, index: usize) -> SearchResult {
+        SearchResult {
+            id: format!("network:{}", path.to_lowercase()),
+            title: name.to_string(),
+            subtitle,
+            icon: Some("folder".to_string()),
+            result_type: ResultType::File,
+            score: LISTING_BASE_SCORE - index as f64 * 0.1,
+            metadata: Self::listing_metadata(path),
+            action: ResultAction::SetQuery {
+                query: format!(r"{}\", path),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    fn file_result(path: &str, name: &str, location: &str, index: usize) -> SearchResult {
+        SearchResult {
+            id: format!("network:{}", path.to_lowercase()),
+            title: name.to_string(),
+            subtitle: location.to_string(),
+            icon: Some(IconCache::get_generic_icon(Path::new(path))),
+            result_type: ResultType::File,
+            score: LISTING_BASE_SCORE - index as f64 * 0.1,
+            metadata: Self::listing_metadata(path),
+            action: ResultAction::OpenFile {
+                path: path.to_string(),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Opens Explorer on `unc`, which asks for credentials when needed
+    fn explorer_action(unc: &str) -> ResultAction {
+        ResultAction::ExecuteCommand {
+            command: "explorer".to_string(),
+            args: vec![unc.to_string()],
+        }
+    }
+
+    fn access_denied_result(unc: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("path".to_string(), serde_json::json!(unc));
+        metadata.insert("access_denied".to_string(), serde_json::json!(true));
+
+        SearchResult {
+            id: format!("network_access_denied:{}", unc.to_lowercase()),
+            title: "Access denied — open in Explorer to authenticate".to_string(),
+            subtitle: unc.to_string(),
+            icon: Some("folder".to_string()),
+            result_type: ResultType::QuickAction,
+            score: LISTING_BASE_SCORE,
+            metadata,
+            action: Self::explorer_action(unc),
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Stands in for a listing that couldn't be produced
+    fn explorer_result(unc: &str, problem: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("path".to_string(), serde_json::json!(unc));
+
+        SearchResult {
+            id: format!("network_explorer:{}", unc.to_lowercase()),
+            title: format!("Open {} in Explorer", unc),
+            subtitle: format!("{} {}", unc, problem),
+            icon: Some("folder".to_string()),
+            result_type: ResultType::QuickAction,
+            score: LISTING_BASE_SCORE,
+            metadata,
+            action: Self::explorer_action(unc),
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for NetworkBrowseProvider {
+    fn name(&self) -> &str {
+        "NetworkBrowse"
+    }
+
+    fn priority(&self) -> u8 {
+        90 // A typed path is unambiguous
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::File, ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        match parse_network_query(&query.query) {
+            Some(NetworkQuery::Shares { host, filter }) => Ok(self.share_results(&host, &filter).await),
+            Some(NetworkQuery::Directory { dir, filter }) if is_network_path(&dir) => {
+                Ok(self.directory_results(&dir, &filter).await)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if !result.id.starts_with("network") {
+            return Err(LauncherError::ExecutionError("Not a network result".to_string()));
+        }
+
+        let path = match &result.action {
+            ResultAction::ExecuteCommand { args, .. } => args.first(),
+            ResultAction::OpenFile { path } => Some(path),
+            _ => None,
+        }
+        .ok_or_else(|| LauncherError::ExecutionError("Invalid action for network result".to_string()))?;
+
+        // Explorer opens folders in a window and files with their default program
+        info!("Opening {} with Explorer", path);
+        std::process::Command::new("explorer")
+            .arg(path)
+            .spawn()
+            .map_err(|e| LauncherError::ExecutionError(format!("Failed to start Explorer: {}", e)))?;
+        Ok(())
+    }
+
+    fn cacheable(&self) -> bool {
+        // Listings change, and timeouts shouldn't stick
+        false
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "network",
+            title: "Browse network shares",
+            description: "Lists the shares of a computer on the network",
+            example: r"\\server\",
+            token: None,
+            values: &[],
+        }]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Default for NetworkBrowseProvider {
+    fn default() -> Self {
+        Self::with_enumerator(Arc::new(SystemShareEnumerator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns fixed shares after an optional delay, counting calls
+    struct FakeEnumerator {
+        result: std::result::Result<Vec<Share>, EnumerationError>,
+        delay: Duration,
+        calls: AtomicUsize,
+    }
+
+    impl FakeEnumerator {
+        fn new(result: std::result::Result<Vec<Share>, EnumerationError>) -> Arc<Self> {
+            Arc::new(Self {
+                result,
+                delay: Duration::ZERO,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    impl ShareEnumerator for FakeEnumerator {
+        fn enumerate(&self, _host: &str) -> std::result::Result<Vec<Share>, EnumerationError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(self.delay);
+            self.result.clone()
+        }
+    }
+
+    fn share(name: &str) -> Share {
+        Share {
+            name: name.to_string(),
+            remark: String::new(),
+        }
+    }
+
+    fn office_shares() -> Vec<Share> {
+        vec![share("Projects"), share("Public"), share("C$"), share("ADMIN$")]
+    }
+
+    #[test]
+    fn test_parse_network_query() {
+        assert_eq!(
+            parse_network_query(r"\\fileserver"),
+            Some(NetworkQuery::Shares {
+                host: "fileserver".to_string(),
+                filter: String::new(),
+            })
+        );
+        assert_eq!(
+            parse_network_query(r"\\fileserver\pro"),
+            Some(NetworkQuery::Shares {
+                host: "fileserver".to_string(),
+                filter: "pro".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_network_query(r"\\fileserver\projects\"),
+            Some(NetworkQuery::Directory {
+                dir: r"\\fileserver\projects\".to_string(),
+                filter: String::new(),
+            })
+        );
+        assert_eq!(
+            parse_network_query("//fileserver/projects/2024/rep"),
+            Some(NetworkQuery::Directory {
+                dir: r"\\fileserver\projects\2024\".to_string(),
+                filter: "rep".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_network_query(r"Z:\reports\q"),
+            Some(NetworkQuery::Directory {
+                dir: r"Z:\reports\".to_string(),
+                filter: "q".to_string(),
+            })
+        );
+
+        assert_eq!(parse_network_query(r"\\"), None);
+        assert_eq!(parse_network_query(r"\\?\C:\Windows"), None);
+        assert_eq!(parse_network_query(r"\\.\pipe\name"), None);
+        assert_eq!(parse_network_query("Z:"), None);
+        assert_eq!(parse_network_query("fileserver"), None);
+    }
+
+    #[test]
+    fn test_hidden_shares_need_an_explicit_dollar() {
+        let shares = office_shares();
+        let names = |filter: &str| -> Vec<String> {
+            filter_shares(&shares, filter).into_iter().map(|s| s.name.clone()).collect()
+        };
+
+        assert_eq!(names(""), vec!["Projects", "Public"]);
+        assert_eq!(names("pro"), vec!["Projects"]);
+        assert_eq!(names("c$"), vec!["C$"]);
+        assert_eq!(names("$"), vec!["C$", "ADMIN$"]);
+    }
+
+    #[tokio::test]
+    async fn test_shares_are_listed_and_cached_per_host() {
+        let enumerator = FakeEnumerator::new(Ok(office_shares()));
+        let provider = NetworkBrowseProvider::with_enumerator(enumerator.clone());
+
+        let results = provider.search(&r"\\FileServer".into()).await.unwrap();
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Projects", "Public"]);
+        assert!(results[0].preserves_provider_order());
+        assert!(
+            matches!(&results[0].action, ResultAction::SetQuery { query } if query == r"\\FileServer\Projects\")
+        );
+
+        provider.search(&r"\\fileserver\pu".into()).await.unwrap();
+        assert_eq!(enumerator.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_slow_host_falls_back_to_explorer() {
+        let enumerator = Arc::new(FakeEnumerator {
+            result: Ok(office_shares()),
+            delay: Duration::from_millis(500),
+            calls: AtomicUsize::new(0),
+        });
+        let provider =
+            NetworkBrowseProvider::with_enumerator(enumerator).with_timeout(Duration::from_millis(50));
+
+        let started = Instant::now();
+        let results = provider.search(&r"\\offline".into()).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(400));
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].title.contains("Explorer"));
+        assert!(matches!(
+            &results[0].action,
+            ResultAction::ExecuteCommand { command, args } if command == "explorer" && args == &[r"\\offline".to_string()]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_access_denied_offers_explorer() {
+        let enumerator = FakeEnumerator::new(Err(EnumerationError::AccessDenied));
+        let provider = NetworkBrowseProvider::with_enumerator(enumerator.clone());
+
+        let results = provider.search(&r"\\secure".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Access denied — open in Explorer to authenticate");
+        assert!(matches!(&results[0].action, ResultAction::ExecuteCommand { command, .. } if command == "explorer"));
+
+        // Failures aren't cached, so signing in through Explorer takes effect
+        provider.search(&r"\\secure".into()).await.unwrap();
+        assert_eq!(enumerator.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_local_drive_paths_are_ignored() {
+        let provider = NetworkBrowseProvider::with_enumerator(FakeEnumerator::new(Ok(Vec::new())));
+        assert!(provider.search(&r"C:\Windows\".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"projects".into()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+/// Post-filter hiding system and build clutter from file results
+///
+/// Common words otherwise match thousands of files in component stores,
+/// dependency folders and browser caches. File providers drop results whose
+/// path contains a built-in fragment or lies under a user-excluded folder,
+/// and end their list with a row saying how many were hidden.
+///
+/// Prefixing the query with `all:` turns the filter off for that search.
+
+use crate::search::providers::path_ranking::PreferredLocations;
+use crate::search::SyntaxFeature;
+use crate::types::{ResultAction, ResultType, SearchResult};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Query prefix that bypasses the filter
+pub const BYPASS_PREFIX: &str = "all:";
+
+/// Help entry for the bypass prefix, listed by providers that apply the filter
+pub const BYPASS_SYNTAX: SyntaxFeature = SyntaxFeature {
+    id: "all",
+    title: "Include excluded folders",
+    description: "Also searches node_modules, system caches and folders you excluded",
+    example: "all: package.json",
+    token: None,
+    values: &[],
+};
+
+/// Path fragments hidden by default; `*` stands for any run of characters
+const DEFAULT_EXCLUDED_FRAGMENTS: &[&str] = &[
+    r"\Windows\WinSxS\",
+    r"\$Recycle.Bin\",
+    r"\node_modules\",
+    r"\.git\objects\",
+    r"\AppData\Local\*\Cache\",
+];
+
+/// Lowercases a path and uses backslashes throughout, with a trailing one so
+/// folder fragments also match the folder itself
+fn normalize_path(path: &str) -> String {
+    let mut normalized = path.to_lowercase().replace('/', "\\");
+    if !normalized.ends_with('\\') {
+        normalized.push('\\');
+    }
+    normalized
+}
+
+/// A compiled deny-list entry
+#[derive(Debug, Clone)]
+enum Pattern {
+    /// Matches paths containing the fragment
+    Fragment(String),
+    /// Matches paths containing the first part with the second somewhere after it
+    Wildcard(String, String),
+    /// Matches paths under the folder
+    Folder(String),
+}
+
+impl Pattern {
+    fn fragment(fragment: &str) -> Self {
+        let fragment = normalize_path(fragment);
+        match fragment.split_once('*') {
+            Some((head, tail)) => Pattern::Wildcard(head.to_string(), tail.to_string()),
+            None => Pattern::Fragment(fragment),
+        }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        match self {
+            Pattern::Fragment(fragment) => path.contains(fragment.as_str()),
+            Pattern::Wildcard(head, tail) => path
+                .find(head.as_str())
+                .is_some_and(|start| path[start + head.len()..].contains(tail.as_str())),
+            Pattern::Folder(folder) => path.starts_with(folder.as_str()),
+        }
+    }
+}
+
+/// Built-in and user-configured exclusions, compiled once per provider
+#[derive(Debug, Clone)]
+pub struct PathExclusions {
+    patterns: Vec<Pattern>,
+}
+
+impl PathExclusions {
+    /// Compiles the built-in deny-list plus the user's excluded paths
+    ///
+    /// Absolute entries (`D:\Archive`) hide everything under that folder;
+    /// other entries (`build`, `target\debug`) hide paths containing them
+    /// as whole path segments.
+    pub fn new(user_paths: &[String]) -> Self {
+        let mut patterns: Vec<Pattern> = DEFAULT_EXCLUDED_FRAGMENTS.iter().map(|f| Pattern::fragment(f)).collect();
+
+        for path in user_paths.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            let is_absolute = path.contains(':') || path.starts_with("\\\\") || path.starts_with('/');
+            patterns.push(if is_absolute {
+                Pattern::Folder(normalize_path(path))
+            } else {
+                Pattern::fragment(&format!("\\{}\\", path.trim_matches(['\\', '/'])))
+            });
+        }
+
+        Self { patterns }
+    }
+
+    /// Whether a file at this path should be hidden
+    pub fn is_excluded(&self, path: &str) -> bool {
+        let path = normalize_path(path);
+        self.patterns.iter().any(|pattern| pattern.matches(&path))
+    }
+
+    /// Drops the items whose path is excluded, returning how many were dropped
+    pub fn retain_visible<T>(&self, items: &mut Vec<T>, path: impl Fn(&T) -> &str) -> usize {
+        let before = items.len();
+        items.retain(|item| !self.is_excluded(path(item)));
+        before - items.len()
+    }
+}
+
+impl Default for PathExclusions {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+/// Folders the file providers hide and rank higher, as currently configured
+///
+/// Clones share the folders, so applying settings, e.g. on a profile switch,
+/// changes what the registered providers hide and prefer without
+/// re-registering them.
+#[derive(Debug, Clone)]
+pub struct FolderScopes(Arc<RwLock<(PathExclusions, PreferredLocations)>>);
+
+impl FolderScopes {
+    pub fn new(exclusions: PathExclusions, preferred: PreferredLocations) -> Self {
+        Self(Arc::new(RwLock::new((exclusions, preferred))))
+    }
+
+    /// Compiles the user's excluded and preferred paths in place of the current ones
+    pub fn set(&self, excluded_paths: &[String], preferred_paths: &[String]) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) =
+            (PathExclusions::new(excluded_paths), PreferredLocations::new(preferred_paths));
+    }
+
+    pub fn exclusions(&self) -> PathExclusions {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).0.clone()
+    }
+
+    pub fn preferred_locations(&self) -> PreferredLocations {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).1.clone()
+    }
+}
+
+impl Default for FolderScopes {
+    fn default() -> Self {
+        Self::new(PathExclusions::default(), PreferredLocations::new(&[]))
+    }
+}
+
+/// Splits the bypass prefix off a query, returning the rest and whether it was there
+pub fn strip_bypass_prefix(query: &str) -> (&str, bool) {
+    let trimmed = query.trim_start();
+    match trimmed.get(..BYPASS_PREFIX.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(BYPASS_PREFIX) => {
+            (trimmed[BYPASS_PREFIX.len()..].trim_start(), true)
+        }
+        _ => (query, false),
+    }
+}
+
+/// Row appended after filtered results, or `None` when nothing was hidden
+///
+/// Selecting it reruns the search with the bypass prefix.
+pub fn hidden_results_row(provider: &str, query: &str, hidden: usize) -> Option<SearchResult> {
+    if hidden == 0 {
+        return None;
+    }
+
+    let noun = if hidden == 1 { "result" } else { "results" };
+
+    let mut metadata = HashMap::new();
+    metadata.insert("hidden_count".to_string(), serde_json::json!(hidden));
+
+    Some(SearchResult {
+        id: format!("hidden_results:{}", provider),
+        title: format!("{} {} hidden — type {} to include", hidden, noun, BYPASS_PREFIX),
+        subtitle: "Excluded folders such as node_modules and system caches".to_string(),
+        icon: None,
+        result_type: ResultType::Suggestion,
+        score: 0.0,
+        metadata,
+        action: ResultAction::SetQuery {
+            query: format!("{} {}", BYPASS_PREFIX, query.trim()),
+        },
+        normalized_title: None,
+        enrichment_pending: false,
+        integrity: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_exclusions() {
+        let exclusions = PathExclusions::default();
+
+        assert!(exclusions.is_excluded(r"C:\Windows\WinSxS\amd64_foo\report.dll"));
+        assert!(exclusions.is_excluded(r"D:\$RECYCLE.BIN\S-1-5-21\$R1.txt"));
+        assert!(exclusions.is_excluded(r"C:\Users\me\code\app\node_modules\lodash\README.md"));
+        assert!(exclusions.is_excluded(r"C:\Users\me\code\app\node_modules"));
+        assert!(exclusions.is_excluded(r"C:\Users\me\code\app\.git\objects\ab\cdef"));
+        assert!(exclusions.is_excluded(r"C:\Users\me\AppData\Local\Google\Chrome\User Data\Default\Cache\f_0001"));
+        assert!(exclusions.is_excluded("C:/Users/me/project/node_modules/x/index.js"));
+
+        assert!(!exclusions.is_excluded(r"C:\Users\me\Documents\report.docx"));
+        assert!(!exclusions.is_excluded(r"C:\Users\me\code\app\.git\config"));
+        assert!(!exclusions.is_excluded(r"C:\Users\me\code\my_node_modules_notes.txt"));
+        assert!(!exclusions.is_excluded(r"C:\Users\me\AppData\Local\Programs\Cache Viewer.exe"));
+    }
+
+    #[test]
+    fn test_user_exclusions() {
+        let exclusions = PathExclusions::new(&[r"D:\Archive".to_string(), "target".to_string(), "  ".to_string()]);
+
+        assert!(exclusions.is_excluded(r"d:\archive\2019\report.pdf"));
+        assert!(exclusions.is_excluded(r"C:\code\app\target\debug\app.exe"));
+        assert!(!exclusions.is_excluded(r"D:\Archives\report.pdf"));
+        assert!(!exclusions.is_excluded(r"C:\code\app\targets.txt"));
+    }
+
+    #[test]
+    fn test_retain_visible_counts_hidden() {
+        let mut paths = vec![
+            r"C:\Users\me\report.docx",
+            r"C:\Users\me\app\node_modules\report\index.js",
+            r"C:\Windows\WinSxS\x86_report\report.dll",
+            r"C:\Users\me\Desktop\report.txt",
+        ];
+
+        let hidden = PathExclusions::default().retain_visible(&mut paths, |path| path);
+        assert_eq!(hidden, 2);
+        assert_eq!(paths, vec![r"C:\Users\me\report.docx", r"C:\Users\me\Desktop\report.txt"]);
+    }
+
+    #[test]
+    fn test_strip_bypass_prefix() {
+        assert_eq!(strip_bypass_prefix("all: report"), ("report", true));
+        assert_eq!(strip_bypass_prefix("ALL:report"), ("report", true));
+        assert_eq!(strip_bypass_prefix("report"), ("report", false));
+        assert_eq!(strip_bypass_prefix("allergy"), ("allergy", false));
+    }
+
+    #[test]
+    fn test_hidden_results_row_only_when_something_was_hidden() {
+        assert!(hidden_results_row("FileSearch", "report", 0).is_none());
+
+        let row = hidden_results_row("FileSearch", "report", 28).unwrap();
+        assert_eq!(row.title, "28 results hidden — type all: to include");
+        assert_eq!(row.metadata.get("hidden_count"), Some(&serde_json::json!(28)));
+        assert!(matches!(row.action, ResultAction::SetQuery { ref query } if query == "all: report"));
+
+        let row = hidden_results_row("FileSearch", "report", 1).unwrap();
+        assert!(row.title.starts_with("1 result hidden"));
+    }
+}
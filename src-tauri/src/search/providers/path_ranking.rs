@@ -0,0 +1,259 @@
+/// Path-aware adjustments to file result scores
+///
+/// Everything matches on file names only, so two files with the same name
+/// score alike wherever they live. These heuristics look at the folder a file
+/// is in: a folder named like the query, a shallow path and a file under the
+/// user's own folders rank higher, while deep paths and backup or archive
+/// folders rank slightly lower.
+
+use serde::Serialize;
+
+/// Metadata key holding a file result's `PathScore`
+pub const PATH_SCORE_KEY: &str = "path_score";
+
+/// Bonus when the file's folder is named like a query word
+const FOLDER_MATCH_BOOST: f64 = 20.0;
+
+/// Bonus for files under the user's own or configured folders
+const PREFERRED_LOCATION_BOOST: f64 = 15.0;
+
+/// Folders a path may be nested in before it is penalized
+const NATURAL_DEPTH: usize = 6;
+
+/// Penalty per folder beyond `NATURAL_DEPTH`...
+const DEPTH_PENALTY_PER_LEVEL: f64 = 4.0;
+
+/// ...up to this much
+const MAX_DEPTH_PENALTY: f64 = 20.0;
+
+/// Penalty for files in a backup or archive folder
+const ARCHIVE_PENALTY: f64 = 10.0;
+
+/// Words marking a folder as a backup or archive
+const ARCHIVE_WORDS: &[&str] = &["backup", "backups", "old", "archive", "archives", "archived"];
+
+/// Folders under the user profile whose files rank higher
+const PROFILE_FOLDERS: &[&str] = &["Desktop", "Documents", "Downloads"];
+
+/// Lowercased folder names of a path, without the drive or share prefix
+fn folders(path: &str) -> Vec<String> {
+    path.split(['\\', '/'])
+        .filter(|segment| !segment.is_empty() && !segment.ends_with(':'))
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Lowercases a folder path with backslashes and a trailing one, so a folder
+/// prefix can't match a sibling such as `Documents2`
+fn normalize_folder(path: &str) -> String {
+    let mut normalized = path.trim().to_lowercase().replace('/', "\\");
+    if !normalized.ends_with('\\') {
+        normalized.push('\\');
+    }
+    normalized
+}
+
+/// Words of a query that can name a folder
+///
+/// Directives such as `ext:pdf` are skipped and wildcards dropped.
+pub fn query_tokens(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter(|token| !token.contains(':'))
+        .map(|token| token.replace(['*', '?', '"'], "").to_lowercase())
+        .filter(|token| token.chars().count() >= 2)
+        .collect()
+}
+
+/// Bonus when the folder holding the file contains a query word
+pub fn folder_match_boost(folder: &str, tokens: &[String]) -> f64 {
+    let Some(parent) = folders(folder).pop() else {
+        return 0.0;
+    };
+
+    if tokens.iter().any(|token| parent.contains(token.as_str())) {
+        FOLDER_MATCH_BOOST
+    } else {
+        0.0
+    }
+}
+
+/// Penalty for folders nested deeper than `NATURAL_DEPTH`
+pub fn depth_penalty(folder: &str) -> f64 {
+    let excess = folders(folder).len().saturating_sub(NATURAL_DEPTH);
+    (excess as f64 * DEPTH_PENALTY_PER_LEVEL).min(MAX_DEPTH_PENALTY)
+}
+
+/// Penalty when any folder on the path is a backup or archive
+///
+/// Folder names are split into words, so `Old Projects` and `site_backup`
+/// count but `Golden` doesn't.
+pub fn archive_penalty(folder: &str) -> f64 {
+    let is_archive = folders(folder).iter().any(|name| {
+        name.split(|c: char| !c.is_alphanumeric())
+            .any(|word| ARCHIVE_WORDS.contains(&word))
+    });
+
+    if is_archive {
+        ARCHIVE_PENALTY
+    } else {
+        0.0
+    }
+}
+
+/// Folders whose files rank higher: the user's Desktop, Documents and
+/// Downloads plus the configured preferred paths
+#[derive(Debug, Clone, Default)]
+pub struct PreferredLocations {
+    roots: Vec<String>,
+}
+
+impl PreferredLocations {
+    /// Uses the current user's profile folders plus `configured`
+    pub fn new(configured: &[String]) -> Self {
+        let profile = std::env::var("USERPROFILE").ok();
+        Self::with_profile(profile.as_deref(), configured)
+    }
+
+    /// Uses the profile folders under `profile`, if any, plus `configured`
+    pub fn with_profile(profile: Option<&str>, configured: &[String]) -> Self {
+        let profile_folders = profile
+            .into_iter()
+            .flat_map(|profile| PROFILE_FOLDERS.iter().map(move |folder| format!("{}\\{}", profile, folder)));
+
+        let roots = profile_folders
+            .chain(configured.iter().filter(|path| !path.trim().is_empty()).cloned())
+            .map(|path| normalize_folder(&path))
+            .collect();
+
+        Self { roots }
+    }
+
+    /// Bonus when the folder lies under one of the preferred locations
+    pub fn boost(&self, folder: &str) -> f64 {
+        let folder = normalize_folder(folder);
+        if self.roots.iter().any(|root| folder.starts_with(root.as_str())) {
+            PREFERRED_LOCATION_BOOST
+        } else {
+            0.0
+        }
+    }
+}
+
+/// How a file's folder adjusted its score
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PathScore {
+    /// Bonus for a folder named like a query word
+    pub folder_match: f64,
+    /// Bonus for a file under a preferred location
+    pub location: f64,
+    /// Penalty for a deeply nested folder
+    pub depth: f64,
+    /// Penalty for a backup or archive folder
+    pub archive: f64,
+    /// Sum of the bonuses minus the penalties
+    pub total: f64,
+}
+
+impl PathScore {
+    /// Scores the folder a file is in against the query words
+    pub fn compute(folder: &str, tokens: &[String], locations: &PreferredLocations) -> Self {
+        let folder_match = folder_match_boost(folder, tokens);
+        let location = locations.boost(folder);
+        let depth = depth_penalty(folder);
+        let archive = archive_penalty(folder);
+
+        Self {
+            folder_match,
+            location,
+            depth,
+            archive,
+            total: folder_match + location - depth - archive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(query: &str) -> Vec<String> {
+        query_tokens(query)
+    }
+
+    #[test]
+    fn test_query_tokens_skip_directives_and_wildcards() {
+        assert_eq!(tokens("Invoice  *.pdf"), vec!["invoice", ".pdf"]);
+        assert_eq!(tokens("ext:pdf taxes"), vec!["taxes"]);
+        assert_eq!(tokens("a \"budget\""), vec!["budget"]);
+        assert!(tokens("").is_empty());
+    }
+
+    #[test]
+    fn test_folder_match_checks_parent_folder_only() {
+        let query = tokens("invoice 2024");
+        assert_eq!(folder_match_boost(r"C:\Users\me\Documents\Invoices", &query), FOLDER_MATCH_BOOST);
+        assert_eq!(folder_match_boost(r"D:\Taxes\2024", &query), FOLDER_MATCH_BOOST);
+        assert_eq!(folder_match_boost("C:/Users/me/invoice", &query), FOLDER_MATCH_BOOST);
+        assert_eq!(folder_match_boost(r"C:\Invoices\scans", &query), 0.0);
+        assert_eq!(folder_match_boost(r"C:\", &query), 0.0);
+        assert_eq!(folder_match_boost(r"C:\Users\me", &[]), 0.0);
+    }
+
+    #[test]
+    fn test_depth_penalty_starts_past_natural_depth() {
+        assert_eq!(depth_penalty(r"C:\Users\me\Documents"), 0.0);
+        assert_eq!(depth_penalty(r"C:\a\b\c\d\e\f"), 0.0);
+        assert_eq!(depth_penalty(r"C:\a\b\c\d\e\f\g"), DEPTH_PENALTY_PER_LEVEL);
+        assert_eq!(depth_penalty(r"\\nas\share\a\b\c\d\e\f\g"), 3.0 * DEPTH_PENALTY_PER_LEVEL);
+        assert_eq!(depth_penalty(r"C:\1\2\3\4\5\6\7\8\9\10\11\12"), MAX_DEPTH_PENALTY);
+    }
+
+    #[test]
+    fn test_archive_penalty_matches_whole_words() {
+        assert_eq!(archive_penalty(r"D:\Backup\2019"), ARCHIVE_PENALTY);
+        assert_eq!(archive_penalty(r"C:\Users\me\Old Projects\site"), ARCHIVE_PENALTY);
+        assert_eq!(archive_penalty(r"C:\sites\site_backup"), ARCHIVE_PENALTY);
+        assert_eq!(archive_penalty(r"C:\ARCHIVES"), ARCHIVE_PENALTY);
+        assert_eq!(archive_penalty(r"C:\Users\me\Golden\Bold"), 0.0);
+        assert_eq!(archive_penalty(r"C:\Users\me\Documents"), 0.0);
+    }
+
+    #[test]
+    fn test_preferred_locations_cover_profile_and_configured_paths() {
+        let locations = PreferredLocations::with_profile(Some(r"C:\Users\me"), &[r"D:\Work".to_string(), " ".to_string()]);
+
+        assert_eq!(locations.boost(r"C:\Users\me\Desktop"), PREFERRED_LOCATION_BOOST);
+        assert_eq!(locations.boost(r"c:\users\me\documents\taxes"), PREFERRED_LOCATION_BOOST);
+        assert_eq!(locations.boost(r"C:\Users\me\Downloads"), PREFERRED_LOCATION_BOOST);
+        assert_eq!(locations.boost("D:/Work/clients"), PREFERRED_LOCATION_BOOST);
+        assert_eq!(locations.boost(r"C:\Users\me\AppData\Roaming"), 0.0);
+        assert_eq!(locations.boost(r"C:\Users\me\Documents2"), 0.0);
+        assert_eq!(locations.boost(r"E:\Desktop"), 0.0);
+
+        let without_profile = PreferredLocations::with_profile(None, &[]);
+        assert_eq!(without_profile.boost(r"C:\Users\me\Desktop"), 0.0);
+    }
+
+    #[test]
+    fn test_path_score_sums_components() {
+        let locations = PreferredLocations::with_profile(Some(r"C:\Users\me"), &[]);
+
+        let score = PathScore::compute(r"C:\Users\me\Documents\Invoices", &tokens("invoice"), &locations);
+        assert_eq!(
+            score,
+            PathScore {
+                folder_match: FOLDER_MATCH_BOOST,
+                location: PREFERRED_LOCATION_BOOST,
+                depth: 0.0,
+                archive: 0.0,
+                total: FOLDER_MATCH_BOOST + PREFERRED_LOCATION_BOOST,
+            }
+        );
+
+        let buried = PathScore::compute(r"E:\Backup\2015\c\Users\me\Documents\misc\stuff", &tokens("invoice"), &locations);
+        assert_eq!(buried.depth, 2.0 * DEPTH_PENALTY_PER_LEVEL);
+        assert_eq!(buried.archive, ARCHIVE_PENALTY);
+        assert_eq!(buried.total, -(2.0 * DEPTH_PENALTY_PER_LEVEL + ARCHIVE_PENALTY));
+    }
+}
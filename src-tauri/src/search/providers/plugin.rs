@@ -0,0 +1,801 @@
+/// External plugins answering queries over stdin and stdout
+///
+/// A plugin is a program in its own folder under
+/// `%APPDATA%\BetterFinder\plugins`, described by a `manifest.json`:
+///
+/// ```json
+/// { "name": "Notes", "command": "python", "args": ["notes.py"], "keyword": "note",
+///   "timeout_ms": 1000, "schema_version": 1 }
+/// ```
+///
+/// It is started on the first query beginning with its keyword and kept
+/// running. Each such query is written to its stdin as one JSON line,
+/// `{"id": 1, "query": "text after the keyword"}`, and it answers with one
+/// line, `{"id": 1, "results": [{"title", "subtitle", "icon", "action"}]}`.
+/// Actions take the launcher's own form, e.g.
+/// `{"type": "open_url", "url": "https://…"}`, but only `open_url` with web
+/// addresses, `open_file` with files that don't run code, and
+/// `copy_to_clipboard` are accepted; other results are dropped.
+///
+/// A plugin that misses its timeout, writes more than `MAX_RESPONSE_BYTES`
+/// or answers with something else is killed and started again on the next
+/// query. After `MAX_PROTOCOL_ERRORS` such failures in a row it is disabled
+/// and reported as failed until the plugins are reloaded.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::bookmark::BookmarkProvider;
+use crate::search::{QueryContext, SearchEngine, SearchProvider};
+use crate::types::{ProviderHealth, ResultAction, ResultType, SearchResult};
+use crate::utils::clipboard::set_clipboard_text;
+use crate::utils::deeplink::{is_executable, is_web_url};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tracing::{debug, info, warn};
+
+/// Result schema version plugins may declare
+pub const PLUGIN_SCHEMA_VERSION: u32 = 1;
+
+/// Name of the file describing a plugin
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Metadata key naming the plugin a result came from
+pub const PLUGIN_KEY: &str = "plugin";
+
+/// Time a plugin gets to answer when its manifest sets none
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Longest timeout a manifest may set
+const MAX_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest response line accepted from a plugin
+const MAX_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Results kept from one response
+const MAX_RESULTS: usize = 20;
+
+/// Failed exchanges in a row after which a plugin is disabled
+const MAX_PROTOCOL_ERRORS: u32 = 3;
+
+/// Priority of plugin providers; they only answer queries addressed to them
+const PLUGIN_PRIORITY: u8 = 70;
+
+/// Score of a plugin's first result; later ones get slightly less, keeping
+/// the plugin's order
+const PLUGIN_SCORE: f64 = 80.0;
+
+/// What a plugin's `manifest.json` declares
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginManifest {
+    /// Shown in the plugin list; the folder name when missing
+    #[serde(default)]
+    pub name: String,
+    /// Program to run, relative to the plugin's folder or found on `PATH`
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Word a query starts with to be sent to the plugin
+    pub keyword: String,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Version of the response format the plugin writes
+    pub schema_version: u32,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl PluginManifest {
+    /// Reads and checks the manifest in a plugin's folder
+    pub fn load(dir: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(dir.join(MANIFEST_FILE))?;
+        let mut manifest: PluginManifest = serde_json::from_str(&content)?;
+        if manifest.name.trim().is_empty() {
+            manifest.name = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.schema_version != PLUGIN_SCHEMA_VERSION {
+            return Err(LauncherError::ConfigError(format!(
+                "Plugin '{}' uses result schema version {}, only {} is supported",
+                self.name, self.schema_version, PLUGIN_SCHEMA_VERSION
+            )));
+        }
+        if self.command.trim().is_empty() {
+            return Err(LauncherError::ConfigError(format!("Plugin '{}' has no command", self.name)));
+        }
+        if self.keyword.is_empty() || self.keyword.contains(char::is_whitespace) {
+            return Err(LauncherError::ConfigError(format!(
+                "Plugin '{}' needs a keyword of one word",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Time the plugin gets to answer one query
+    pub fn timeout(&self) -> Duration {
+        self.timeout_ms
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_TIMEOUT)
+            .min(MAX_TIMEOUT)
+    }
+}
+
+/// A plugin as listed by `list_plugins`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginInfo {
+    pub name: String,
+    /// Folder holding the manifest
+    pub dir: String,
+    /// `None` when the manifest couldn't be read
+    pub keyword: Option<String>,
+    pub description: Option<String>,
+    pub health: ProviderHealth,
+}
+
+#[derive(Serialize)]
+struct PluginRequest<'a> {
+    id: u64,
+    query: &'a str,
+}
+
+#[derive(Deserialize)]
+struct PluginResponse {
+    id: u64,
+    results: Vec<serde_json::Value>,
+}
+
+/// One result as a plugin writes it
+#[derive(Deserialize)]
+struct PluginResult {
+    title: String,
+    #[serde(default)]
+    subtitle: String,
+    #[serde(default)]
+    icon: Option<String>,
+    action: ResultAction,
+}
+
+/// Checks that a plugin may use an action, returning the result type it gets
+///
+/// Plugins can't run commands or launch programs: only web pages, files that
+/// don't run code when opened, and text to copy are allowed.
+fn allowed_action(action: &ResultAction) -> Result<ResultType> {
+    match action {
+        ResultAction::OpenUrl { url } if is_web_url(url) => Ok(ResultType::Url),
+        ResultAction::OpenFile { path } if !is_executable(Path::new(path)) => Ok(ResultType::File),
+        ResultAction::CopyToClipboard { .. } => Ok(ResultType::Text),
+        action => Err(LauncherError::SecurityError(format!("Plugins can't use the action {:?}", action))),
+    }
+}
+
+/// The running plugin process with its pipes
+struct PluginProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl PluginProcess {
+    fn spawn(manifest: &PluginManifest, dir: &Path) -> Result<Self> {
+        // A program shipped with the plugin is run from its folder
+        let bundled = dir.join(&manifest.command);
+        let program = if bundled.is_file() { bundled } else { PathBuf::from(&manifest.command) };
+
+        let mut command = Command::new(program);
+        command
+            .args(&manifest.args)
+            .current_dir(dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        #[cfg(windows)]
+        {
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        let mut child = command.spawn().map_err(|e| {
+            LauncherError::ProviderError(format!("Failed to start plugin '{}': {}", manifest.name, e))
+        })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(LauncherError::ProviderError(format!("Plugin '{}' has no pipes", manifest.name)));
+        };
+
+        info!("Started plugin '{}' (pid {:?})", manifest.name, child.id());
+        Ok(Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    /// Sends one query and reads the response line
+    async fn exchange(&mut self, request: &[u8]) -> Result<Vec<u8>> {
+        self.stdin.write_all(request).await?;
+        self.stdin.flush().await?;
+        read_line_capped(&mut self.stdout, MAX_RESPONSE_BYTES).await
+    }
+
+    async fn kill(mut self) {
+        if let Err(e) = self.child.kill().await {
+            debug!("Plugin process already gone: {}", e);
+        }
+    }
+}
+
+/// Reads up to a newline, failing once the line grows past `limit` bytes
+async fn read_line_capped<R: AsyncBufRead + Unpin>(reader: &mut R, limit: usize) -> Result<Vec<u8>> {
+    let mut line = Vec::new();
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            return Err(LauncherError::ProviderError("Plugin closed its output".to_string()));
+        }
+
+        let newline = available.iter().position(|&byte| byte == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        if line.len() + chunk.len() > limit {
+            return Err(LauncherError::ProviderError(format!(
+                "Plugin response is larger than {} bytes",
+                limit
+            )));
+        }
+        line.extend_from_slice(chunk);
+
+        let consumed = newline.map_or(available.len(), |end| end + 1);
+        reader.consume(consumed);
+        if newline.is_some() {
+            return Ok(line);
+        }
+    }
+}
+
+/// Search provider backed by an external plugin program
+pub struct PluginProvider {
+    name: String,
+    manifest: PluginManifest,
+    dir: PathBuf,
+    /// Started on the first addressed query; one exchange at a time
+    process: tokio::sync::Mutex<Option<PluginProcess>>,
+    next_id: AtomicU64,
+    /// Failed exchanges since the last good one
+    errors: AtomicU32,
+    /// Why the plugin was disabled, once it was
+    disabled: Mutex<Option<String>>,
+}
+
+impl PluginProvider {
+    /// Creates the provider for a loaded manifest; nothing is started yet
+    pub fn new(manifest: PluginManifest, dir: &Path) -> Self {
+        Self {
+            name: format!("Plugin: {}", manifest.name),
+            manifest,
+            dir: dir.to_path_buf(),
+            process: tokio::sync::Mutex::new(None),
+            next_id: AtomicU64::new(1),
+            errors: AtomicU32::new(0),
+            disabled: Mutex::new(None),
+        }
+    }
+
+    pub fn manifest(&self) -> &PluginManifest {
+        &self.manifest
+    }
+
+    /// Returns the query text meant for the plugin, if the query starts with its keyword
+    fn addressed_query<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let query = query.trim_start();
+        let keyword = query.split_whitespace().next()?;
+        keyword
+            .eq_ignore_ascii_case(&self.manifest.keyword)
+            .then(|| query[keyword.len()..].trim())
+    }
+
+    fn disabled_reason(&self) -> Option<String> {
+        self.disabled.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Sends a query to the plugin, starting it if needed
+    ///
+    /// Any failure kills the process, so the next query starts from a clean
+    /// pipe, and counts towards disabling the plugin.
+    async fn request(&self, query: &str) -> Result<Vec<serde_json::Value>> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut line = serde_json::to_vec(&PluginRequest { id, query })?;
+        line.push(b'\n');
+
+        let mut slot = self.process.lock().await;
+        let outcome = async {
+            if slot.is_none() {
+                *slot = Some(PluginProcess::spawn(&self.manifest, &self.dir)?);
+            }
+            let process = slot.as_mut().expect("plugin process was just started");
+            let response = tokio::time::timeout(self.manifest.timeout(), process.exchange(&line))
+                .await
+                .map_err(|_| {
+                    LauncherError::ProviderError(format!(
+                        "Plugin did not answer within {}ms",
+                        self.manifest.timeout().as_millis()
+                    ))
+                })??;
+
+            let response: PluginResponse = serde_json::from_slice(&response)?;
+            if response.id != id {
+                return Err(LauncherError::ProviderError(format!(
+                    "Plugin answered request {} instead of {}",
+                    response.id, id
+                )));
+            }
+            Ok(response.results)
+        }
+        .await;
+
+        match outcome {
+            Ok(results) => {
+                self.errors.store(0, Ordering::Relaxed);
+                Ok(results)
+            }
+            Err(e) => {
+                if let Some(process) = slot.take() {
+                    process.kill().await;
+                }
+                self.record_error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    fn record_error(&self, error: &LauncherError) {
+        let errors = self.errors.fetch_add(1, Ordering::Relaxed) + 1;
+        warn!("Plugin '{}' failed ({} in a row): {}", self.manifest.name, errors, error);
+        if errors >= MAX_PROTOCOL_ERRORS {
+            warn!("Disabling plugin '{}'", self.manifest.name);
+            *self.disabled.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(format!("disabled after {} failed queries, the last: {}", errors, error));
+        }
+    }
+
+    /// Turns the plugin's results into search results, dropping those using
+    /// actions plugins may not use
+    fn translate(&self, results: Vec<serde_json::Value>) -> Vec<SearchResult> {
+        results
+            .into_iter()
+            .filter_map(|value| match serde_json::from_value::<PluginResult>(value) {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    warn!("Plugin '{}' wrote a result that isn't understood: {}", self.manifest.name, e);
+                    None
+                }
+            })
+            .filter_map(|result| match allowed_action(&result.action) {
+                Ok(result_type) => Some((result, result_type)),
+                Err(e) => {
+                    warn!("Dropping a result of plugin '{}': {}", self.manifest.name, e);
+                    None
+                }
+            })
+            .take(MAX_RESULTS)
+            .enumerate()
+            .map(|(index, (result, result_type))| {
+                let mut metadata = HashMap::new();
+                metadata.insert(PLUGIN_KEY.to_string(), serde_json::json!(self.manifest.name));
+
+                SearchResult {
+                    id: format!("plugin:{}:{}:{}", self.manifest.name, index, result.title),
+                    title: result.title,
+                    subtitle: result.subtitle,
+                    icon: result.icon,
+                    result_type,
+                    score: PLUGIN_SCORE - index as f64 * 0.1,
+                    metadata,
+                    action: result.action,
+                    normalized_title: None,
+                    enrichment_pending: false,
+                    integrity: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Kills the plugin process, if it runs
+    pub async fn stop(&self) {
+        if let Some(process) = self.process.lock().await.take() {
+            process.kill().await;
+            info!("Stopped plugin '{}'", self.manifest.name);
+        }
+    }
+
+    /// Whether the plugin process is running
+    pub async fn is_running(&self) -> bool {
+        self.process.lock().await.is_some()
+    }
+}
+
+#[async_trait]
+impl SearchProvider for PluginProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn priority(&self) -> u8 {
+        PLUGIN_PRIORITY
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Url, ResultType::File, ResultType::Text])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let Some(plugin_query) = self.addressed_query(&query.query) else {
+            return Ok(Vec::new());
+        };
+        if let Some(reason) = self.disabled_reason() {
+            return Err(LauncherError::ProviderError(reason));
+        }
+
+        let results = self.request(plugin_query).await?;
+        debug!("Plugin '{}' returned {} results", self.manifest.name, results.len());
+        Ok(self.translate(results))
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let owner = result.metadata.get(PLUGIN_KEY).and_then(|v| v.as_str());
+        if owner != Some(self.manifest.name.as_str()) {
+            return Err(LauncherError::ExecutionError("Not a result of this plugin".to_string()));
+        }
+        // Checked again, as the result came back from the frontend
+        allowed_action(&result.action)?;
+
+        match &result.action {
+            ResultAction::OpenUrl { url } => BookmarkProvider::open_url(url).await,
+            ResultAction::OpenFile { path } => {
+                if !Path::new(path).exists() {
+                    return Err(LauncherError::NotFound(format!("File does not exist: {}", path)));
+                }
+                // ShellExecute opens files with their default application too
+                BookmarkProvider::open_url(path).await
+            }
+            ResultAction::CopyToClipboard { content } => set_clipboard_text(content).await,
+            _ => unreachable!("allowed_action rejects other actions"),
+        }
+    }
+
+    /// Plugins may answer differently every time
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.disabled_reason().is_none()
+    }
+
+    fn health(&self) -> ProviderHealth {
+        match self.disabled_reason() {
+            Some(error) => ProviderHealth::Failed { error },
+            None => ProviderHealth::Ready,
+        }
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.stop().await;
+        Ok(())
+    }
+}
+
+/// Returns `%APPDATA%\BetterFinder\plugins`
+pub fn plugins_dir() -> Option<PathBuf> {
+    std::env::var_os("APPDATA").map(|app_data| PathBuf::from(app_data).join("BetterFinder").join("plugins"))
+}
+
+/// Loads the plugins in a folder and registers them with the search engine
+///
+/// Kept in app state so `reload_plugins` can replace them after the user
+/// adds, edits or removes one.
+pub struct PluginHost {
+    dir: Option<PathBuf>,
+    providers: tokio::sync::Mutex<Vec<Arc<PluginProvider>>>,
+    /// Folders whose manifest couldn't be used, with the reason
+    failures: Mutex<Vec<PluginInfo>>,
+}
+
+impl PluginHost {
+    /// Creates a host reading plugins from `dir`; without one there are none
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            providers: tokio::sync::Mutex::new(Vec::new()),
+            failures: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Registers every usable plugin, skipping those whose keyword is taken
+    /// by a built-in syntax or an earlier plugin
+    pub async fn load(&self, engine: &SearchEngine) {
+        let mut providers = self.providers.lock().await;
+        let mut failures = Vec::new();
+        let mut keywords = engine.syntax_registry().keywords();
+
+        for dir in self.plugin_dirs() {
+            let failed = |error: String| PluginInfo {
+                name: dir.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+                dir: dir.display().to_string(),
+                keyword: None,
+                description: None,
+                health: ProviderHealth::Failed { error },
+            };
+
+            let manifest = match PluginManifest::load(&dir) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    warn!("Skipping plugin in {}: {}", dir.display(), e);
+                    failures.push(failed(e.to_string()));
+                    continue;
+                }
+            };
+
+            let keyword = manifest.keyword.to_lowercase();
+            if keywords.contains(&keyword) {
+                warn!("Skipping plugin '{}': keyword '{}' is taken", manifest.name, keyword);
+                failures.push(failed(format!("keyword '{}' is already used", keyword)));
+                continue;
+            }
+            keywords.push(keyword);
+
+            let provider = Arc::new(PluginProvider::new(manifest, &dir));
+            engine.register_provider(Arc::clone(&provider) as Arc<dyn SearchProvider>).await;
+            providers.push(provider);
+        }
+
+        info!("Loaded {} plugins, skipped {}", providers.len(), failures.len());
+        *self.failures.lock().unwrap_or_else(|e| e.into_inner()) = failures;
+    }
+
+    /// Stops and unregisters the loaded plugins, then loads them again
+    pub async fn reload(&self, engine: &SearchEngine) {
+        let previous = std::mem::take(&mut *self.providers.lock().await);
+        for provider in previous {
+            engine.unregister_provider(provider.name()).await;
+            provider.stop().await;
+        }
+        self.load(engine).await;
+    }
+
+    /// Lists the loaded plugins and the folders that failed to load
+    pub async fn list(&self) -> Vec<PluginInfo> {
+        let mut plugins: Vec<PluginInfo> = self
+            .providers
+            .lock()
+            .await
+            .iter()
+            .map(|provider| PluginInfo {
+                name: provider.manifest.name.clone(),
+                dir: provider.dir.display().to_string(),
+                keyword: Some(provider.manifest.keyword.clone()),
+                description: provider.manifest.description.clone(),
+                health: provider.health(),
+            })
+            .collect();
+        plugins.extend(self.failures.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned());
+        plugins
+    }
+
+    /// Subfolders of the plugin folder, in name order
+    fn plugin_dirs(&self) -> Vec<PathBuf> {
+        let Some(dir) = &self.dir else {
+            return Vec::new();
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            debug!("No plugin folder at {}", dir.display());
+            return Vec::new();
+        };
+
+        let mut dirs: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+        dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Fixture plugin: echoes the query back with one result per action,
+    /// including two plugins may not use; `hang` never answers and `flood`
+    /// writes an oversized line
+    #[cfg(not(windows))]
+    const FIXTURE: (&str, &str) = (
+        "fixture.sh",
+        r#"while IFS= read -r line; do
+  id=${line#*\"id\":}; id=${id%%,*}
+  query=${line#*\"query\":\"}; query=${query%\"*}
+  case "$1" in
+    hang) continue ;;
+    flood) printf '{"id":%s,"results":[{"title":"' "$id"; head -c 70000 /dev/zero | tr '\0' a; printf '"}]}\n' ;;
+    *) printf '{"id":%s,"results":[{"title":"%s","subtitle":"echo","action":{"type":"open_url","url":"https://example.com"}},{"title":"notes","action":{"type":"open_file","path":"notes.txt"}},{"title":"copy","action":{"type":"copy_to_clipboard","content":"%s"}},{"title":"run","action":{"type":"execute_command","command":"calc","args":[]}},{"title":"setup","action":{"type":"open_file","path":"setup.exe"}}]}\n' "$id" "$query" "$query" ;;
+  esac
+done
+"#,
+    );
+
+    #[cfg(windows)]
+    const FIXTURE: (&str, &str) = (
+        "fixture.ps1",
+        r#"param([string]$Mode)
+while ($null -ne ($line = [Console]::In.ReadLine())) {
+  $request = $line | ConvertFrom-Json
+  if ($Mode -eq 'hang') { continue }
+  if ($Mode -eq 'flood') {
+    $results = @(@{ title = 'a' * 70000 })
+  } else {
+    $results = @(
+      @{ title = $request.query; subtitle = 'echo'; action = @{ type = 'open_url'; url = 'https://example.com' } },
+      @{ title = 'notes'; action = @{ type = 'open_file'; path = 'notes.txt' } },
+      @{ title = 'copy'; action = @{ type = 'copy_to_clipboard'; content = $request.query } },
+      @{ title = 'run'; action = @{ type = 'execute_command'; command = 'calc'; args = @() } },
+      @{ title = 'setup'; action = @{ type = 'open_file'; path = 'setup.exe' } }
+    )
+  }
+  [Console]::Out.WriteLine((@{ id = $request.id; results = $results } | ConvertTo-Json -Compress -Depth 5))
+  [Console]::Out.Flush()
+}
+"#,
+    );
+
+    /// Writes the fixture plugin and its manifest into a fresh folder
+    fn fixture_plugin(name: &str, mode: &str, timeout_ms: u64) -> (PluginManifest, PathBuf) {
+        let mut dir = std::env::temp_dir();
+        dir.push("BetterFinder");
+        dir.push(format!("plugin_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(FIXTURE.0), FIXTURE.1).unwrap();
+
+        #[cfg(not(windows))]
+        let (command, args) = ("sh", vec![FIXTURE.0.to_string(), mode.to_string()]);
+        #[cfg(windows)]
+        let (command, args) = (
+            "powershell",
+            ["-NoProfile", "-ExecutionPolicy", "Bypass", "-File", FIXTURE.0, mode]
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect(),
+        );
+
+        let manifest = PluginManifest {
+            name: name.to_string(),
+            command: command.to_string(),
+            args,
+            keyword: "fx".to_string(),
+            timeout_ms: Some(timeout_ms),
+            schema_version: PLUGIN_SCHEMA_VERSION,
+            description: None,
+        };
+        std::fs::write(dir.join(MANIFEST_FILE), serde_json::to_string(&manifest).unwrap()).unwrap();
+        (manifest, dir)
+    }
+
+    fn search_query(text: &str) -> QueryContext {
+        QueryContext::new(text, true)
+    }
+
+    #[test]
+    fn test_manifest_checks() {
+        let (_, dir) = fixture_plugin("manifest", "echo", 500);
+        let manifest = PluginManifest::load(&dir).unwrap();
+        assert_eq!(manifest.keyword, "fx");
+        assert_eq!(manifest.timeout(), Duration::from_millis(500));
+
+        let newer = r#"{"command": "notes.exe", "keyword": "note", "schema_version": 2}"#;
+        std::fs::write(dir.join(MANIFEST_FILE), newer).unwrap();
+        assert!(matches!(PluginManifest::load(&dir), Err(LauncherError::ConfigError(_))));
+
+        let two_words = r#"{"command": "notes.exe", "keyword": "my notes", "schema_version": 1, "timeout_ms": 60000}"#;
+        std::fs::write(dir.join(MANIFEST_FILE), two_words).unwrap();
+        assert!(matches!(PluginManifest::load(&dir), Err(LauncherError::ConfigError(_))));
+
+        // The folder names a plugin without a name, and timeouts are capped
+        let unnamed = r#"{"command": "notes.exe", "keyword": "note", "schema_version": 1, "timeout_ms": 60000}"#;
+        std::fs::write(dir.join(MANIFEST_FILE), unnamed).unwrap();
+        let manifest = PluginManifest::load(&dir).unwrap();
+        assert_eq!(manifest.name, dir.file_name().unwrap().to_string_lossy());
+        assert_eq!(manifest.timeout(), MAX_TIMEOUT);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_keeps_only_allowed_actions() {
+        let (manifest, dir) = fixture_plugin("echo", "echo", 5000);
+        let provider = PluginProvider::new(manifest, &dir);
+
+        // Queries without the keyword don't start the plugin
+        assert!(provider.search(&search_query("hello")).await.unwrap().is_empty());
+        assert!(!provider.is_running().await);
+
+        for query in ["hello", "world"] {
+            let results = provider.search(&search_query(&format!("FX {}", query))).await.unwrap();
+            let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+            assert_eq!(titles, vec![query, "notes", "copy"]);
+            assert_eq!(results[0].result_type, ResultType::Url);
+            assert_eq!(results[0].subtitle, "echo");
+            assert_eq!(results[1].result_type, ResultType::File);
+            assert!(matches!(&results[2].action, ResultAction::CopyToClipboard { content } if content == query));
+            assert!(results.iter().all(|r| r.metadata.get(PLUGIN_KEY) == Some(&serde_json::json!("echo"))));
+        }
+        assert!(provider.is_running().await);
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+
+        // Commands are refused even if a result claims to come from the plugin
+        let mut forged = provider.search(&search_query("fx forged")).await.unwrap().remove(0);
+        forged.action = ResultAction::ExecuteCommand {
+            command: "calc".to_string(),
+            args: Vec::new(),
+        };
+        assert!(matches!(provider.execute(&forged).await, Err(LauncherError::SecurityError(_))));
+
+        provider.stop().await;
+        assert!(!provider.is_running().await);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_kill_the_plugin_until_it_is_disabled() {
+        let (manifest, dir) = fixture_plugin("hang", "hang", 300);
+        let provider = PluginProvider::new(manifest, &dir);
+
+        for attempt in 1..=MAX_PROTOCOL_ERRORS {
+            let started = Instant::now();
+            assert!(provider.search(&search_query("fx anything")).await.is_err());
+            assert!(started.elapsed() < Duration::from_secs(3), "{:?}", started.elapsed());
+            assert!(!provider.is_running().await);
+            assert_eq!(provider.is_enabled(), attempt < MAX_PROTOCOL_ERRORS);
+        }
+
+        assert!(matches!(provider.health(), ProviderHealth::Failed { .. }));
+        // A disabled plugin isn't started again
+        assert!(provider.search(&search_query("fx anything")).await.is_err());
+        assert!(!provider.is_running().await);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_oversized_response_is_rejected() {
+        let (manifest, dir) = fixture_plugin("flood", "flood", 5000);
+        let provider = PluginProvider::new(manifest, &dir);
+
+        let error = provider.search(&search_query("fx big")).await.unwrap_err();
+        assert!(error.to_string().contains("larger than"), "{}", error);
+        assert!(!provider.is_running().await);
+        assert!(provider.is_enabled());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_read_line_capped() {
+        let mut reader = BufReader::new(&b"{\"id\":1}\nrest"[..]);
+        assert_eq!(read_line_capped(&mut reader, 16).await.unwrap(), b"{\"id\":1}");
+        assert!(read_line_capped(&mut reader, 16).await.is_err());
+
+        let mut reader = BufReader::with_capacity(4, &b"0123456789\n"[..]);
+        assert!(read_line_capped(&mut reader, 8).await.is_err());
+    }
+}
@@ -0,0 +1,308 @@
+/// Settings profile switcher
+///
+/// `profile` lists the profiles from settings, such as "work" and
+/// "personal", and `profile wo` narrows them down. Selecting one makes it
+/// the active profile, applying its overrides at once; the "Default settings"
+/// row switches back to the settings without a profile.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::path_exclusions::FolderScopes;
+use crate::search::{QueryContext, SearchEngine, SearchProvider, SyntaxFeature};
+use crate::settings::AppSettings;
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::info;
+
+/// Word that starts a profile query
+pub const PROFILE_KEYWORD: &str = "profile";
+
+/// Metadata key holding the profile a result switches to; `null` for none
+const PROFILE_KEY: &str = "profile";
+
+/// Help entry for the profile keyword
+const PROFILE_SYNTAX: SyntaxFeature = SyntaxFeature {
+    id: "profile",
+    title: "Switch profile",
+    description: "Applies a settings profile, such as work or personal",
+    example: "profile ",
+    token: None,
+    values: &[],
+};
+
+/// The configured profile names and the active one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileList {
+    pub names: Vec<String>,
+    pub active: Option<String>,
+}
+
+impl ProfileList {
+    /// Lists the profiles of `settings`
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self {
+            names: settings
+                .profiles
+                .iter()
+                .map(|profile| profile.name.clone())
+                .collect(),
+            active: settings.active_profile.clone(),
+        }
+    }
+}
+
+/// Profiles as last applied
+///
+/// Clones share the list, so applying settings updates what the registered
+/// provider and the tray menu offer.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveProfiles(Arc<RwLock<ProfileList>>);
+
+impl ActiveProfiles {
+    pub fn get(&self) -> ProfileList {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, profiles: ProfileList) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = profiles;
+    }
+}
+
+/// Applies the parts of `settings` that decide where and how to search: the
+/// folder scopes, the web search engine and the profiles on offer
+///
+/// Called with the effective settings on every save and profile switch.
+pub async fn apply_search_scopes(
+    settings: &AppSettings,
+    engine: &SearchEngine,
+    scopes: &FolderScopes,
+    profiles: &ActiveProfiles,
+) -> Result<()> {
+    scopes.set(&settings.excluded_paths, &settings.preferred_paths);
+    engine.set_web_search_engine(&settings.web_search_engine).await?;
+    profiles.set(ProfileList::from_settings(settings));
+    Ok(())
+}
+
+/// Profile switcher search provider
+pub struct ProfileProvider {
+    profiles: ActiveProfiles,
+    /// Called with the profile to switch to, `None` for the default settings
+    switch_handler: Option<Box<dyn Fn(Option<String>) + Send + Sync>>,
+}
+
+impl ProfileProvider {
+    /// Creates a ProfileProvider offering the profiles in `profiles`
+    pub fn new(profiles: ActiveProfiles) -> Result<Self> {
+        info!("Initializing ProfileProvider");
+        Ok(Self {
+            profiles,
+            switch_handler: None,
+        })
+    }
+
+    /// Sets what switches profiles when a result is executed
+    pub fn set_switch_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(Option<String>) + Send + Sync + 'static,
+    {
+        self.switch_handler = Some(Box::new(handler));
+    }
+
+    /// Creates the row switching to `profile`, or to no profile
+    fn create_search_result(profile: Option<&str>, active: bool, score: f64) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert(PROFILE_KEY.to_string(), serde_json::json!(profile));
+
+        let name = profile.unwrap_or("Default settings");
+        SearchResult {
+            id: format!("profile:{}", profile.unwrap_or_default()),
+            title: name.to_string(),
+            subtitle: if active {
+                "Active profile".to_string()
+            } else {
+                format!("Switch to {}", name)
+            },
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: format!("profile:{}", profile.unwrap_or_default()),
+                args: vec![],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for ProfileProvider {
+    fn name(&self) -> &str {
+        "Profiles"
+    }
+
+    fn priority(&self) -> u8 {
+        80 // Same as the other quick actions
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let folded = query.folded_query.trim();
+        let (keyword, rest) = folded.split_once(' ').unwrap_or((folded, ""));
+        if keyword != PROFILE_KEYWORD {
+            return Ok(Vec::new());
+        }
+
+        let profiles = self.profiles.get();
+        let rest = rest.trim();
+        let is_active = |name: &str| {
+            profiles
+                .active
+                .as_deref()
+                .is_some_and(|active| active.eq_ignore_ascii_case(name))
+        };
+
+        let mut results: Vec<SearchResult> = profiles
+            .names
+            .iter()
+            .filter(|name| name.to_lowercase().starts_with(rest))
+            .enumerate()
+            .map(|(index, name)| {
+                Self::create_search_result(Some(name), is_active(name), 90.0 - index as f64)
+            })
+            .collect();
+        if rest.is_empty() && profiles.active.is_some() {
+            results.push(Self::create_search_result(None, false, 50.0));
+        }
+        Ok(results)
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let profile = result
+            .metadata
+            .get(PROFILE_KEY)
+            .filter(|_| result.id.starts_with("profile:"))
+            .ok_or_else(|| LauncherError::ExecutionError("Not a profile result".to_string()))?;
+
+        let handler = self.switch_handler.as_ref().ok_or_else(|| {
+            LauncherError::ExecutionError("Profiles can't be switched".to_string())
+        })?;
+
+        let profile = profile.as_str().map(str::to_string);
+        info!("Switching to profile {:?}", profile);
+        handler(profile);
+        Ok(())
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![PROFILE_SYNTAX]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_profile_keyword_lists_and_switches() {
+        let profiles = ActiveProfiles::default();
+        profiles.set(ProfileList {
+            names: vec!["Work".to_string(), "Personal".to_string()],
+            active: Some("Work".to_string()),
+        });
+
+        let switched = Arc::new(Mutex::new(Vec::new()));
+        let mut provider = ProfileProvider::new(profiles.clone()).unwrap();
+        let recorded = Arc::clone(&switched);
+        provider.set_switch_handler(move |profile| recorded.lock().unwrap().push(profile));
+
+        assert!(provider
+            .search(&QueryContext::new("work", true))
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(provider
+            .search(&QueryContext::new("profiles", true))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let all = provider
+            .search(&QueryContext::new("profile", true))
+            .await
+            .unwrap();
+        let titles: Vec<&str> = all.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["Work", "Personal", "Default settings"]);
+        assert_eq!(all[0].subtitle, "Active profile");
+
+        let personal = provider
+            .search(&QueryContext::new("Profile pers", true))
+            .await
+            .unwrap();
+        assert_eq!(personal.len(), 1);
+        provider.execute(&personal[0]).await.unwrap();
+        provider.execute(&all[2]).await.unwrap();
+        assert_eq!(
+            *switched.lock().unwrap(),
+            vec![Some("Personal".to_string()), None]
+        );
+
+        // Without an active profile there is nothing to switch back from
+        profiles.set(ProfileList {
+            names: vec!["Work".to_string()],
+            active: None,
+        });
+        let titles: Vec<String> = provider
+            .search(&QueryContext::new("profile ", true))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.title)
+            .collect();
+        assert_eq!(titles, vec!["Work"]);
+    }
+
+    #[tokio::test]
+    async fn test_switching_profiles_applies_scopes_and_engine() {
+        let work = serde_json::from_value(serde_json::json!({
+            "name": "Work",
+            "overrides": { "excluded_paths": ["D:\\Personal"], "web_search_engine": "bing" },
+        }))
+        .unwrap();
+        let base = AppSettings {
+            excluded_paths: vec!["scratch".to_string()],
+            web_search_engine: "google".to_string(),
+            profiles: vec![work],
+            ..AppSettings::default()
+        };
+        let engine = SearchEngine::new();
+        let scopes = FolderScopes::default();
+        let profiles = ActiveProfiles::default();
+
+        let work = base.with_active_profile(Some("work")).unwrap();
+        apply_search_scopes(&work.effective().unwrap(), &engine, &scopes, &profiles)
+            .await
+            .unwrap();
+        assert!(scopes.exclusions().is_excluded(r"D:\Personal\taxes.pdf"));
+        assert!(!scopes.exclusions().is_excluded(r"C:\code\scratch\notes.txt"));
+        assert_eq!(engine.web_search_engine().get().id, "bing");
+        assert_eq!(profiles.get().active.as_deref(), Some("Work"));
+
+        let default = work.with_active_profile(None).unwrap();
+        apply_search_scopes(&default.effective().unwrap(), &engine, &scopes, &profiles)
+            .await
+            .unwrap();
+        assert!(!scopes.exclusions().is_excluded(r"D:\Personal\taxes.pdf"));
+        assert!(scopes.exclusions().is_excluded(r"C:\code\scratch\notes.txt"));
+        assert_eq!(engine.web_search_engine().get().id, "google");
+        assert_eq!(profiles.get().active, None);
+    }
+}
@@ -9,8 +9,8 @@
 /// - Log Off
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult, REQUIRES_CONFIRMATION_KEY};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -188,7 +188,7 @@ impl QuickActionProvider {
             serde_json::json!(action.command),
         );
         metadata.insert(
-            "requires_confirmation".to_string(),
+            REQUIRES_CONFIRMATION_KEY.to_string(),
             serde_json::json!(action.command.requires_confirmation()),
         );
 
@@ -204,6 +204,9 @@ impl QuickActionProvider {
                 command: format!("system:{:?}", action.command),
                 args: vec![],
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 }
@@ -218,17 +221,21 @@ impl SearchProvider for QuickActionProvider {
         80 // High priority for quick actions
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        if query.trim().is_empty() {
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if query.query.trim().is_empty() {
             return Ok(Vec::new());
         }
 
-        debug!("Searching quick actions for query: '{}'", query);
+        debug!("Searching quick actions for query: '{}'", query.query);
 
         // Perform fuzzy search on action names
         let mut results = Vec::new();
         for action in &self.actions {
-            if let Some(score) = Self::fuzzy_match(query, &action.name) {
+            if let Some(score) = Self::fuzzy_match(&query.folded_query, &query.fold(&action.name)) {
                 let result = self.convert_to_search_result(action, score);
                 results.push(result);
             }
@@ -287,87 +294,41 @@ impl Default for QuickActionProvider {
 
 impl QuickActionProvider {
     /// Executes a system command
+    ///
+    /// The commands return at once, so they are waited for briefly to report
+    /// failures such as a shutdown already being scheduled.
     #[cfg(windows)]
     async fn execute_system_command(command: SystemCommand) -> Result<()> {
-        info!("Executing system command: {:?}", command);
+        use crate::utils::process::{self, Wait};
 
-        // Execute command in a blocking task
-        tokio::task::spawn_blocking(move || Self::execute_system_command_sync(command))
-            .await
-            .map_err(|e| {
-                LauncherError::ExecutionError(format!("Failed to spawn command task: {}", e))
-            })??;
+        info!("Executing system command: {:?}", command);
+        process::start("system", Self::system_process(command), Wait::UpTo(process::INSTANT_EXIT_TIMEOUT)).await?;
 
         info!("Successfully executed system command: {:?}", command);
         Ok(())
     }
 
-    /// Synchronously executes a system command using Windows API
+    /// Builds the process carrying out a system command
     #[cfg(windows)]
-    fn execute_system_command_sync(command: SystemCommand) -> Result<()> {
-        use std::process::Command;
-
-        match command {
-            SystemCommand::Shutdown => {
-                // shutdown /s /t 0 - Shutdown immediately
-                Command::new("shutdown")
-                    .args(["/s", "/t", "0"])
-                    .spawn()
-                    .map_err(|e| {
-                        LauncherError::ExecutionError(format!("Failed to execute shutdown: {}", e))
-                    })?;
-            }
-            SystemCommand::Restart => {
-                // shutdown /r /t 0 - Restart immediately
-                Command::new("shutdown")
-                    .args(["/r", "/t", "0"])
-                    .spawn()
-                    .map_err(|e| {
-                        LauncherError::ExecutionError(format!("Failed to execute restart: {}", e))
-                    })?;
-            }
-            SystemCommand::Lock => {
-                // rundll32.exe user32.dll,LockWorkStation - Lock the workstation
-                Command::new("rundll32.exe")
-                    .args(["user32.dll,LockWorkStation"])
-                    .spawn()
-                    .map_err(|e| {
-                        LauncherError::ExecutionError(format!("Failed to execute lock: {}", e))
-                    })?;
-            }
-            SystemCommand::Sleep => {
-                // rundll32.exe powrprof.dll,SetSuspendState 0,1,0 - Sleep
-                Command::new("rundll32.exe")
-                    .args(["powrprof.dll,SetSuspendState", "0,1,0"])
-                    .spawn()
-                    .map_err(|e| {
-                        LauncherError::ExecutionError(format!("Failed to execute sleep: {}", e))
-                    })?;
-            }
-            SystemCommand::Hibernate => {
-                // shutdown /h - Hibernate
-                Command::new("shutdown")
-                    .args(["/h"])
-                    .spawn()
-                    .map_err(|e| {
-                        LauncherError::ExecutionError(format!(
-                            "Failed to execute hibernate: {}",
-                            e
-                        ))
-                    })?;
-            }
-            SystemCommand::LogOff => {
-                // shutdown /l - Log off
-                Command::new("shutdown")
-                    .args(["/l"])
-                    .spawn()
-                    .map_err(|e| {
-                        LauncherError::ExecutionError(format!("Failed to execute logoff: {}", e))
-                    })?;
-            }
-        }
+    fn system_process(command: SystemCommand) -> std::process::Command {
+        let (program, args): (&str, &[&str]) = match command {
+            // shutdown /s /t 0 - Shutdown immediately
+            SystemCommand::Shutdown => ("shutdown", &["/s", "/t", "0"]),
+            // shutdown /r /t 0 - Restart immediately
+            SystemCommand::Restart => ("shutdown", &["/r", "/t", "0"]),
+            // rundll32.exe user32.dll,LockWorkStation - Lock the workstation
+            SystemCommand::Lock => ("rundll32.exe", &["user32.dll,LockWorkStation"]),
+            // rundll32.exe powrprof.dll,SetSuspendState 0,1,0 - Sleep
+            SystemCommand::Sleep => ("rundll32.exe", &["powrprof.dll,SetSuspendState", "0,1,0"]),
+            // shutdown /h - Hibernate
+            SystemCommand::Hibernate => ("shutdown", &["/h"]),
+            // shutdown /l - Log off
+            SystemCommand::LogOff => ("shutdown", &["/l"]),
+        };
 
-        Ok(())
+        let mut process = std::process::Command::new(program);
+        process.args(args);
+        process
     }
 
     #[cfg(not(windows))]
@@ -420,7 +381,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Test exact match
-        let results = provider.search("shutdown").await.unwrap();
+        let results = provider.search(&"shutdown".into()).await.unwrap();
         assert!(!results.is_empty());
         assert_eq!(results[0].title, "Shutdown");
         assert_eq!(results[0].score, 100.0);
@@ -432,7 +393,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Test starts with
-        let results = provider.search("rest").await.unwrap();
+        let results = provider.search(&"rest".into()).await.unwrap();
         assert!(!results.is_empty());
         assert_eq!(results[0].title, "Restart");
         assert_eq!(results[0].score, 90.0);
@@ -443,7 +404,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Test contains
-        let results = provider.search("lock").await.unwrap();
+        let results = provider.search(&"lock".into()).await.unwrap();
         assert!(!results.is_empty());
         assert_eq!(results[0].title, "Lock");
         assert_eq!(results[0].score, 100.0); // Exact match
@@ -454,7 +415,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Test partial match
-        let results = provider.search("slp").await.unwrap();
+        let results = provider.search(&"slp".into()).await.unwrap();
         assert!(!results.is_empty());
         
         // Should find "Sleep" with fuzzy matching
@@ -467,7 +428,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Test no match
-        let results = provider.search("xyz123").await.unwrap();
+        let results = provider.search(&"xyz123".into()).await.unwrap();
         assert!(results.is_empty());
     }
 
@@ -476,7 +437,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Test empty query
-        let results = provider.search("").await.unwrap();
+        let results = provider.search(&"".into()).await.unwrap();
         assert!(results.is_empty());
     }
 
@@ -485,7 +446,7 @@ mod tests {
         let provider = QuickActionProvider::new().unwrap();
 
         // Search with a query that matches multiple actions
-        let results = provider.search("s").await.unwrap();
+        let results = provider.search(&"s".into()).await.unwrap();
         
         // Results should be sorted by score (highest first)
         for i in 1..results.len() {
@@ -497,7 +458,7 @@ mod tests {
     async fn test_search_result_metadata() {
         let provider = QuickActionProvider::new().unwrap();
 
-        let results = provider.search("shutdown").await.unwrap();
+        let results = provider.search(&"shutdown".into()).await.unwrap();
         assert!(!results.is_empty());
 
         let result = &results[0];
@@ -561,6 +522,9 @@ mod tests {
                 command: "test".to_string(),
                 args: vec![],
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         };
 
         let result = provider.execute(&invalid_result).await;
@@ -1,19 +1,27 @@
 /// Recent files provider for tracking and displaying recently accessed files
 ///
-/// This provider maintains a history of files opened through the launcher,
-/// allowing users to quickly access their recent work.
+/// This provider maintains a history of files used through the launcher,
+/// allowing users to quickly access their recent work. Each entry records
+/// how the file was used: opening it counts far more than revealing it or
+/// copying its path, and only opened files are listed before you type.
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::search::provider_config::{ConfigOption, ProviderConfig};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{CacheStats, InteractionKind, ProviderHealth, ResultAction, ResultType, SearchResult};
+use crate::utils::canonical_path::{canonical_path, PathResolver, SystemResolver};
+use crate::utils::db_integrity::{self, CheckPolicy, IntegrityOutcome};
+use crate::utils::{time, write_json_export, IconCache};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{oneshot, Mutex, RwLock};
 use tracing::{info, warn};
 
 /// Maximum number of recent files to display by default
@@ -22,24 +30,78 @@ const DEFAULT_RECENT_FILES_LIMIT: usize = 5;
 /// Maximum number of recent files to store in database
 const MAX_RECENT_FILES: usize = 50;
 
+/// Entries past MAX_RECENT_FILES are deleted after this many writes...
+const CLEANUP_WRITE_INTERVAL: usize = 20;
+
+/// ...or once this much time has passed since the last cleanup
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a write waits for a locked database before failing
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bonus per unit of log frecency when matching a typed query...
+const FRECENCY_BONUS_SCALE: f64 = 5.0;
+
+/// ...capped so a well-used file can't outrank a better name match by much
+const MAX_FRECENCY_BONUS: f64 = 20.0;
+
+/// Options under `provider_settings.recent_files`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecentFilesConfig {
+    /// Number of recent files shown for an empty query
+    pub limit: usize,
+}
+
+impl Default for RecentFilesConfig {
+    fn default() -> Self {
+        Self { limit: DEFAULT_RECENT_FILES_LIMIT }
+    }
+}
+
+impl ProviderConfig for RecentFilesConfig {
+    const KEY: &'static str = "recent_files";
+    const PROVIDER: &'static str = "Recent Files";
+    const OPTIONS: &'static [ConfigOption] = &[ConfigOption {
+        field: "limit",
+        description: "Recent files shown before you type",
+        min: 1,
+        max: MAX_RECENT_FILES as u64,
+    }];
+}
+
 /// Represents a recently accessed file
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecentFile {
-    /// File path
+    /// File path, as it was last used
     pub path: PathBuf,
+    /// Path the file finally lives at, past junctions and symlinks; entries
+    /// are told apart by it
+    pub canonical_path: PathBuf,
     /// Last time the file was accessed
     pub last_accessed: DateTime<Utc>,
     /// Number of times the file has been accessed
     pub access_count: u32,
+    /// Strongest way the file was ever used
+    pub interaction_kind: InteractionKind,
+    /// Type of the result the file was last used through; `None` for
+    /// entries recorded before it was stored
+    pub result_type: Option<ResultType>,
+    /// Sum of the weights of every interaction's kind
+    pub weight: f64,
 }
 
 impl RecentFile {
     /// Creates a new recent file entry
     pub fn new(path: PathBuf) -> Self {
         Self {
+            canonical_path: path.clone(),
             path,
             last_accessed: Utc::now(),
             access_count: 1,
+            interaction_kind: InteractionKind::Opened,
+            result_type: None,
+            weight: InteractionKind::Opened.weight(),
         }
     }
 
@@ -47,29 +109,37 @@ impl RecentFile {
     pub fn update_access(&mut self) {
         self.last_accessed = Utc::now();
         self.access_count += 1;
+        self.weight += InteractionKind::Opened.weight();
+    }
+
+    /// Kind-weighted use, decayed by how long ago the file was last used
+    pub fn frecency(&self, now: DateTime<Utc>) -> f64 {
+        let recency = match now.signed_duration_since(self.last_accessed).num_days() {
+            days if days < 1 => 1.0,
+            days if days < 7 => 0.7,
+            days if days < 30 => 0.5,
+            _ => 0.3,
+        };
+        self.weight * recency
     }
 
     /// Returns a formatted timestamp
     pub fn formatted_timestamp(&self) -> String {
         let now = Utc::now();
-        let duration = now.signed_duration_since(self.last_accessed);
-
-        if duration.num_seconds() < 60 {
-            "Just now".to_string()
-        } else if duration.num_minutes() < 60 {
-            format!("{} min ago", duration.num_minutes())
-        } else if duration.num_hours() < 24 {
-            format!("{} hours ago", duration.num_hours())
-        } else if duration.num_days() < 7 {
-            format!("{} days ago", duration.num_days())
+
+        if now.signed_duration_since(self.last_accessed).num_days() < 7 {
+            time::format_relative(self.last_accessed, now, time::Locale::system())
         } else {
             self.last_accessed.format("%Y-%m-%d").to_string()
         }
     }
 
     /// Checks if the file still exists on disk
+    ///
+    /// The canonical path is checked, and a disk that can't be read counts as
+    /// present, so a slow junction target doesn't make a file look deleted.
     pub fn exists(&self) -> bool {
-        self.path.exists()
+        self.canonical_path.try_exists().unwrap_or(true)
     }
 
     /// Gets the file name
@@ -88,9 +158,151 @@ impl RecentFile {
 }
 
 /// Storage backend for recent files using SQLite
+///
+/// One connection is opened in `new()` and shared by every operation.
+/// Accesses tracked while another write holds the connection are queued and
+/// written together in a single transaction.
 pub struct RecentFilesStorage {
     /// Path to the SQLite database
     db_path: PathBuf,
+    /// Shared connection plus cleanup bookkeeping
+    connection: Arc<Mutex<StorageConnection>>,
+    /// Accesses waiting for the next batch write
+    pending: Arc<std::sync::Mutex<Vec<PendingTrack>>>,
+    /// Resolves the canonical paths entries are keyed by
+    resolver: Arc<dyn PathResolver>,
+    /// Why the database couldn't be reopened after the last integrity check
+    reopen_failure: Arc<std::sync::Mutex<Option<String>>>,
+}
+
+/// A tracked access waiting to be written
+struct PendingTrack {
+    /// File path, as used
+    path: String,
+    /// Canonical path the entry is found by
+    canonical_path: String,
+    kind: InteractionKind,
+    /// Stored name of the result type the file was used through
+    result_type: String,
+    /// RFC 3339 access time, taken when the access was tracked
+    accessed_at: String,
+    /// Reports the outcome of the batch this access was written in
+    done: oneshot::Sender<std::result::Result<(), String>>,
+}
+
+/// The shared SQLite connection and when it last trimmed old entries
+struct StorageConnection {
+    conn: Connection,
+    /// Writes since entries past MAX_RECENT_FILES were last deleted
+    writes_since_cleanup: usize,
+    /// When entries past MAX_RECENT_FILES were last deleted
+    last_cleanup: Option<Instant>,
+}
+
+impl StorageConnection {
+    /// Writes queued accesses, reporting the outcome to each one's caller
+    fn write_queued(&mut self, batch: Vec<PendingTrack>) {
+        let result = self.write_batch(&batch).map_err(|e| e.to_string());
+        if let Err(e) = &result {
+            warn!("Failed to write {} recent file accesses: {}", batch.len(), e);
+        }
+
+        for entry in batch {
+            let _ = entry.done.send(result.clone());
+        }
+    }
+
+    /// Writes a batch of accesses in one transaction, trimming old entries when due
+    fn write_batch(&mut self, batch: &[PendingTrack]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        for entry in batch {
+            // A path now leading to another file leaves its old entry behind
+            tx.execute(
+                "DELETE FROM recent_files WHERE path = ?1 AND canonical_path IS NOT ?2",
+                params![entry.path, entry.canonical_path],
+            )?;
+
+            let existing: Option<String> = tx
+                .query_row(
+                    "SELECT interaction_kind FROM recent_files WHERE canonical_path = ?1",
+                    params![entry.canonical_path],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            match existing {
+                Some(kind) => {
+                    // An entry keeps the strongest way it was ever used, and
+                    // shows the path it was last used through
+                    let kind = InteractionKind::parse(&kind).min(entry.kind);
+                    tx.execute(
+                        "UPDATE recent_files 
+                         SET path = ?1, last_accessed = ?2, access_count = access_count + 1, weight = weight + ?3,
+                             interaction_kind = ?4, result_type = ?5
+                         WHERE canonical_path = ?6",
+                        params![
+                            entry.path,
+                            entry.accessed_at,
+                            entry.kind.weight(),
+                            kind.as_str(),
+                            entry.result_type,
+                            entry.canonical_path
+                        ],
+                    )?;
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO recent_files (path, canonical_path, last_accessed, access_count, weight, interaction_kind, result_type) 
+                         VALUES (?1, ?2, ?3, 1, ?4, ?5, ?6)",
+                        params![
+                            entry.path,
+                            entry.canonical_path,
+                            entry.accessed_at,
+                            entry.kind.weight(),
+                            entry.kind.as_str(),
+                            entry.result_type
+                        ],
+                    )?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        self.writes_since_cleanup += batch.len();
+
+        if self.cleanup_due(Instant::now()) {
+            self.cleanup()?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether enough writes or time have passed since the last cleanup
+    fn cleanup_due(&self, now: Instant) -> bool {
+        self.writes_since_cleanup >= CLEANUP_WRITE_INTERVAL
+            || self
+                .last_cleanup
+                .is_none_or(|last| now.saturating_duration_since(last) >= CLEANUP_INTERVAL)
+    }
+
+    /// Deletes entries beyond the MAX_RECENT_FILES most recent ones
+    fn cleanup(&mut self) -> Result<usize> {
+        let removed = self.conn.execute(
+            "DELETE FROM recent_files 
+             WHERE id NOT IN (
+                 SELECT id FROM recent_files 
+                 ORDER BY last_accessed DESC 
+                 LIMIT ?1
+             )",
+            params![MAX_RECENT_FILES],
+        )?;
+
+        self.writes_since_cleanup = 0;
+        self.last_cleanup = Some(Instant::now());
+
+        Ok(removed)
+    }
 }
 
 impl RecentFilesStorage {
@@ -103,19 +315,91 @@ impl RecentFilesStorage {
             std::fs::create_dir_all(parent)?;
         }
 
-        let storage = Self {
-            db_path,
-        };
+        Self::open(&db_path)
+    }
+
+    /// Opens the database at a path and prepares the shared connection
+    fn open(db_path: &Path) -> Result<Self> {
+        Self::open_with_resolver(db_path, Arc::new(SystemResolver))
+    }
+
+    /// Opens the database at a path, resolving canonical paths with `resolver`
+    fn open_with_resolver(db_path: &Path, resolver: Arc<dyn PathResolver>) -> Result<Self> {
+        let conn = Self::connect(db_path, resolver.as_ref())?;
+
+        Ok(Self {
+            db_path: db_path.to_path_buf(),
+            connection: Arc::new(Mutex::new(StorageConnection {
+                conn,
+                writes_since_cleanup: 0,
+                last_cleanup: None,
+            })),
+            pending: Arc::new(std::sync::Mutex::new(Vec::new())),
+            resolver,
+            reopen_failure: Arc::new(std::sync::Mutex::new(None)),
+        })
+    }
+
+    /// Opens a connection to the database, creating the schema if needed
+    fn connect(db_path: &Path, resolver: &dyn PathResolver) -> Result<Connection> {
+        let conn = Connection::open(db_path)?;
+
+        // WAL lets reads run alongside a write; the timeout rides out short
+        // lock contention instead of failing with SQLITE_BUSY
+        db_integrity::configure_durability(&conn)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
 
         // Initialize the database
-        storage.initialize_db()?;
+        Self::initialize_db(&conn, resolver)?;
 
-        Ok(storage)
+        Ok(conn)
     }
 
-    /// Gets a database connection
-    fn get_connection(&self) -> Result<Connection> {
-        Ok(Connection::open(&self.db_path)?)
+    /// Checks the database for damage, rebuilding it from its readable rows
+    ///
+    /// The shared connection is closed during the check so a damaged file
+    /// can be replaced, then opened again on whichever file is in place,
+    /// whatever the check found. Should that fail, e.g. while the file is
+    /// locked, accesses are kept in memory until the next check reopens it;
+    /// `reopen_failure` says why meanwhile.
+    pub async fn check_integrity(&self, now: DateTime<Utc>, policy: CheckPolicy) -> Result<IntegrityOutcome> {
+        let mut connection = Arc::clone(&self.connection).lock_owned().await;
+        let db_path = self.db_path.clone();
+        let resolver = Arc::clone(&self.resolver);
+        let reopen_failure = Arc::clone(&self.reopen_failure);
+
+        tokio::task::spawn_blocking(move || {
+            drop(std::mem::replace(&mut connection.conn, Connection::open_in_memory()?));
+            let outcome = db_integrity::check_database(&db_path, now, policy);
+
+            let failure = match Self::connect(&db_path, resolver.as_ref()) {
+                Ok(conn) => {
+                    connection.conn = conn;
+                    None
+                }
+                Err(e) => {
+                    warn!("Failed to reopen {}, keeping recent files in memory: {}", db_path.display(), e);
+                    let conn = Connection::open_in_memory()?;
+                    Self::initialize_db(&conn, resolver.as_ref())?;
+                    connection.conn = conn;
+                    Some(format!(
+                        "{} couldn't be reopened, so recent files are kept in memory for now: {}",
+                        db_path.display(),
+                        e
+                    ))
+                }
+            };
+            *reopen_failure.lock().unwrap_or_else(|e| e.into_inner()) = failure;
+
+            outcome
+        })
+        .await
+        .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn integrity check: {}", e)))?
+    }
+
+    /// Why the database couldn't be reopened after the last integrity check, if it couldn't
+    pub fn reopen_failure(&self) -> Option<String> {
+        self.reopen_failure.lock().unwrap_or_else(|e| e.into_inner()).clone()
     }
 
     /// Gets the database file path
@@ -143,18 +427,22 @@ impl RecentFilesStorage {
     }
 
     /// Initializes the database schema
-    fn initialize_db(&self) -> Result<()> {
-        let conn = self.get_connection()?;
-
+    fn initialize_db(conn: &Connection, resolver: &dyn PathResolver) -> Result<()> {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS recent_files (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 path TEXT NOT NULL UNIQUE,
+                canonical_path TEXT,
                 last_accessed TEXT NOT NULL,
-                access_count INTEGER NOT NULL DEFAULT 1
+                access_count INTEGER NOT NULL DEFAULT 1,
+                interaction_kind TEXT NOT NULL DEFAULT 'opened',
+                result_type TEXT,
+                weight REAL NOT NULL DEFAULT 1
             )",
             [],
         )?;
+        Self::migrate(conn)?;
+        Self::migrate_canonical_paths(conn, resolver)?;
 
         // Create index on last_accessed for faster queries
         conn.execute(
@@ -165,83 +453,227 @@ impl RecentFilesStorage {
         Ok(())
     }
 
-    /// Adds or updates a file in the recent files list
-    pub async fn track_file(&self, path: &Path) -> Result<()> {
-        let path_str = path.to_string_lossy().to_string();
-        let now = Utc::now().to_rfc3339();
-        let db_path = self.db_path.clone();
-        
-        tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
-
-            // Try to update existing entry
-            let updated = conn.execute(
-                "UPDATE recent_files 
-                 SET last_accessed = ?1, access_count = access_count + 1 
-                 WHERE path = ?2",
-                params![now, path_str],
-            )?;
+    /// Adds the columns recording how files were used to a database
+    /// created before they existed
+    ///
+    /// Every access was an open back then, so old entries count as opened
+    /// with a weight of one per access.
+    fn migrate(conn: &Connection) -> Result<()> {
+        let columns = conn
+            .prepare("PRAGMA table_info(recent_files)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if columns.iter().any(|column| column == "interaction_kind") {
+            return Ok(());
+        }
 
-            // If no rows were updated, insert a new entry
-            if updated == 0 {
-                conn.execute(
-                    "INSERT INTO recent_files (path, last_accessed, access_count) 
-                     VALUES (?1, ?2, 1)",
-                    params![path_str, now],
-                )?;
+        conn.execute_batch(
+            "BEGIN;
+             ALTER TABLE recent_files ADD COLUMN interaction_kind TEXT NOT NULL DEFAULT 'opened';
+             ALTER TABLE recent_files ADD COLUMN result_type TEXT;
+             ALTER TABLE recent_files ADD COLUMN weight REAL NOT NULL DEFAULT 1;
+             UPDATE recent_files SET weight = access_count;
+             COMMIT;",
+        )?;
+        info!("Migrated the recent files database to record interaction kinds");
+        Ok(())
+    }
+
+    /// Records the canonical path of entries stored without one, merging
+    /// entries that reach the same file through different paths
+    ///
+    /// A merged entry adds up the uses of both, keeps the strongest way the
+    /// file was used and shows the path it was used through last.
+    fn migrate_canonical_paths(conn: &Connection, resolver: &dyn PathResolver) -> Result<()> {
+        let columns = conn
+            .prepare("PRAGMA table_info(recent_files)")?
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let tx = conn.unchecked_transaction()?;
+        if !columns.iter().any(|column| column == "canonical_path") {
+            tx.execute("ALTER TABLE recent_files ADD COLUMN canonical_path TEXT", [])?;
+        }
+
+        // Newest first, so the entry kept from a merge is the one used last
+        let unresolved = tx
+            .prepare(
+                "SELECT id, path, last_accessed, access_count, weight, interaction_kind
+                 FROM recent_files WHERE canonical_path IS NULL ORDER BY last_accessed DESC",
+            )?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, u32>(3)?,
+                    row.get::<_, f64>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut merged = 0;
+        for (id, path, last_accessed, access_count, weight, kind) in &unresolved {
+            let canonical = canonical_path(Path::new(path), resolver).to_string_lossy().to_string();
+            let existing: Option<(i64, String)> = tx
+                .query_row(
+                    "SELECT id, interaction_kind FROM recent_files WHERE canonical_path = ?1",
+                    params![canonical],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+
+            match existing {
+                Some((kept, kept_kind)) => {
+                    let kind = InteractionKind::parse(&kept_kind).min(InteractionKind::parse(kind));
+                    tx.execute("DELETE FROM recent_files WHERE id = ?1", params![id])?;
+                    tx.execute(
+                        "UPDATE recent_files
+                         SET last_accessed = MAX(last_accessed, ?1), access_count = access_count + ?2,
+                             weight = weight + ?3, interaction_kind = ?4
+                         WHERE id = ?5",
+                        params![last_accessed, access_count, weight, kind.as_str(), kept],
+                    )?;
+                    merged += 1;
+                }
+                None => {
+                    tx.execute(
+                        "UPDATE recent_files SET canonical_path = ?1 WHERE id = ?2",
+                        params![canonical, id],
+                    )?;
+                }
             }
+        }
 
-            // Clean up old entries if we exceed the maximum
-            conn.execute(
-                "DELETE FROM recent_files 
-                 WHERE id NOT IN (
-                     SELECT id FROM recent_files 
-                     ORDER BY last_accessed DESC 
-                     LIMIT ?1
-                 )",
-                params![MAX_RECENT_FILES],
-            )?;
+        tx.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_canonical_path ON recent_files(canonical_path)",
+            [],
+        )?;
+        tx.commit()?;
+
+        if !unresolved.is_empty() {
+            info!(
+                "Recorded canonical paths for {} recent files, merging {} duplicates",
+                unresolved.len(),
+                merged
+            );
+        }
+        Ok(())
+    }
 
-            Ok::<(), LauncherError>(())
+    /// Reads a row selected by `query_files`
+    fn read_file(row: &rusqlite::Row) -> rusqlite::Result<RecentFile> {
+        let path_str: String = row.get(0)?;
+        let last_accessed_str: String = row.get(1)?;
+        let result_type: Option<String> = row.get(4)?;
+        let canonical_path: Option<String> = row.get(6)?;
+
+        let last_accessed = DateTime::parse_from_rfc3339(&last_accessed_str)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+
+        Ok(RecentFile {
+            canonical_path: PathBuf::from(canonical_path.as_deref().unwrap_or(&path_str)),
+            path: PathBuf::from(path_str),
+            last_accessed,
+            access_count: row.get(2)?,
+            interaction_kind: InteractionKind::parse(&row.get::<_, String>(3)?),
+            result_type: result_type.and_then(|name| serde_json::from_value(serde_json::json!(name)).ok()),
+            weight: row.get(5)?,
+        })
+    }
+
+    /// Adds or updates a file in the recent files list
+    ///
+    /// The access is queued, and whichever call gets the connection next
+    /// writes the whole queue, so a burst of calls costs a few transactions.
+    pub async fn track_file(&self, path: &Path, kind: InteractionKind, result_type: ResultType) -> Result<()> {
+        let canonical_path = self.resolve(path).await?;
+        let (done, outcome) = oneshot::channel();
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(PendingTrack {
+                path: path.to_string_lossy().to_string(),
+                canonical_path,
+                kind,
+                result_type: serde_json::json!(result_type).as_str().unwrap_or_default().to_string(),
+                accessed_at: Utc::now().to_rfc3339(),
+                done,
+            });
+
+        let mut connection = Arc::clone(&self.connection).lock_owned().await;
+        let batch = std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+
+        // An empty queue means an earlier call already wrote this access
+        if !batch.is_empty() {
+            tokio::task::spawn_blocking(move || connection.write_queued(batch))
+                .await
+                .map_err(|e| {
+                    LauncherError::ExecutionError(format!("Failed to spawn track task: {}", e))
+                })?;
+        }
+
+        outcome
+            .await
+            .map_err(|_| LauncherError::ProviderError("Recent file write was dropped".to_string()))?
+            .map_err(|e| LauncherError::ProviderError(format!("Failed to record recent file: {}", e)))
+    }
+
+    /// Writes accesses still queued and checkpoints the database, before the app exits
+    pub async fn flush(&self) -> Result<()> {
+        let mut connection = Arc::clone(&self.connection).lock_owned().await;
+        let batch = std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+
+        tokio::task::spawn_blocking(move || {
+            if !batch.is_empty() {
+                connection.write_queued(batch);
+            }
+            db_integrity::checkpoint(&connection.conn)
         })
         .await
-        .map_err(|e| {
-            LauncherError::ExecutionError(format!("Failed to spawn track task: {}", e))
-        })??;
+        .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn checkpoint: {}", e)))?
+    }
 
-        Ok(())
+    /// Resolves the canonical path of `path` off the async runtime, since it
+    /// may wait on a slow disk
+    async fn resolve(&self, path: &Path) -> Result<String> {
+        let resolver = Arc::clone(&self.resolver);
+        let path = path.to_path_buf();
+        tokio::task::spawn_blocking(move || canonical_path(&path, resolver.as_ref()).to_string_lossy().to_string())
+            .await
+            .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn path resolution: {}", e)))
     }
 
-    /// Retrieves recent files, optionally filtering by query
+    /// Retrieves recent files, however they were used, newest first
+    ///
+    /// At most MAX_RECENT_FILES are returned; cleanup is batched, so a few
+    /// older rows may still be stored until it next runs.
     pub async fn get_recent_files(&self, limit: usize) -> Result<Vec<RecentFile>> {
-        let db_path = self.db_path.clone();
+        self.query_files(limit, false).await
+    }
+
+    /// Retrieves recent files that were opened at least once, newest first
+    pub async fn get_opened_files(&self, limit: usize) -> Result<Vec<RecentFile>> {
+        self.query_files(limit, true).await
+    }
+
+    async fn query_files(&self, limit: usize, only_opened: bool) -> Result<Vec<RecentFile>> {
+        let connection = Arc::clone(&self.connection).lock_owned().await;
+        let limit = limit.min(MAX_RECENT_FILES);
         
         tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
-
-            let mut stmt = conn.prepare(
-                "SELECT path, last_accessed, access_count 
+            let mut stmt = connection.conn.prepare(
+                "SELECT path, last_accessed, access_count, interaction_kind, result_type, weight, canonical_path 
                  FROM recent_files 
+                 WHERE ?2 = 0 OR interaction_kind = 'opened'
                  ORDER BY last_accessed DESC 
                  LIMIT ?1",
             )?;
 
             let files = stmt
-                .query_map(params![limit], |row| {
-                    let path_str: String = row.get(0)?;
-                    let last_accessed_str: String = row.get(1)?;
-                    let access_count: u32 = row.get(2)?;
-
-                    let last_accessed = DateTime::parse_from_rfc3339(&last_accessed_str)
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(|_| Utc::now());
-
-                    Ok(RecentFile {
-                        path: PathBuf::from(path_str),
-                        last_accessed,
-                        access_count,
-                    })
-                })?
+                .query_map(params![limit, only_opened], Self::read_file)?
                 .collect::<std::result::Result<Vec<_>, _>>()?;
 
             Ok(files)
@@ -268,16 +700,15 @@ impl RecentFilesStorage {
     }
 
     /// Removes a file from the recent files list
-    async fn remove_file(&self, path: &Path) -> Result<()> {
+    pub async fn remove_file(&self, path: &Path) -> Result<()> {
         let path_str = path.to_string_lossy().to_string();
-        let db_path = self.db_path.clone();
+        let canonical_path = self.resolve(path).await?;
+        let connection = Arc::clone(&self.connection).lock_owned().await;
         
         tokio::task::spawn_blocking(move || {
-            let conn = Connection::open(&db_path)?;
-
-            conn.execute(
-                "DELETE FROM recent_files WHERE path = ?1",
-                params![path_str],
+            connection.conn.execute(
+                "DELETE FROM recent_files WHERE path = ?1 OR canonical_path = ?2",
+                params![path_str, canonical_path],
             )?;
 
             Ok::<(), LauncherError>(())
@@ -289,13 +720,52 @@ impl RecentFilesStorage {
 
         Ok(())
     }
+
+    /// Deletes every entry, or only those last accessed more than `older_than` ago
+    pub async fn clear(&self, older_than: Option<Duration>) -> Result<usize> {
+        let mut connection = Arc::clone(&self.connection).lock_owned().await;
+
+        tokio::task::spawn_blocking(move || {
+            let now = Utc::now();
+            let tx = connection.conn.transaction()?;
+
+            let stale: Vec<i64> = {
+                let mut stmt = tx.prepare("SELECT id, last_accessed FROM recent_files")?;
+                let rows = stmt
+                    .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+
+                rows.into_iter()
+                    .filter(|(_, last_accessed)| {
+                        // Unreadable times count as now, as they do when listing
+                        let last_accessed = DateTime::parse_from_rfc3339(last_accessed)
+                            .map(|dt| dt.with_timezone(&Utc))
+                            .unwrap_or(now);
+                        time::is_older_than(last_accessed, older_than, now)
+                    })
+                    .map(|(id, _)| id)
+                    .collect()
+            };
+
+            for id in &stale {
+                tx.execute("DELETE FROM recent_files WHERE id = ?1", params![id])?;
+            }
+            tx.commit()?;
+
+            Ok::<usize, LauncherError>(stale.len())
+        })
+        .await
+        .map_err(|e| {
+            LauncherError::ExecutionError(format!("Failed to spawn clear task: {}", e))
+        })?
+    }
 }
 
 impl Default for RecentFilesStorage {
     fn default() -> Self {
-        Self::new().unwrap_or_else(|_| Self {
-            db_path: PathBuf::from("recent_files.db"),
-        })
+        Self::new()
+            .or_else(|_| Self::open(Path::new(":memory:")))
+            .expect("Failed to open an in-memory recent files database")
     }
 }
 
@@ -384,10 +854,7 @@ mod tests {
         // Clean up any existing test file
         let _ = std::fs::remove_file(&db_path);
         
-        let storage = RecentFilesStorage {
-            db_path: db_path.clone(),
-        };
-        storage.initialize_db().unwrap();
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
         
         // Create a temporary test file
         let mut test_path = std::env::temp_dir();
@@ -395,7 +862,7 @@ mod tests {
         File::create(&test_path).unwrap();
         
         // Track the file
-        let result = storage.track_file(&test_path).await;
+        let result = storage.track_file(&test_path, InteractionKind::Opened, ResultType::File).await;
         assert!(result.is_ok());
 
         // Retrieve recent files
@@ -405,7 +872,7 @@ mod tests {
         assert_eq!(files[0].access_count, 1);
 
         // Track the same file again
-        storage.track_file(&test_path).await.unwrap();
+        storage.track_file(&test_path, InteractionKind::Opened, ResultType::File).await.unwrap();
 
         // Should update the existing entry
         let files = storage.get_recent_files(10).await.unwrap();
@@ -417,6 +884,30 @@ mod tests {
         std::fs::remove_file(&db_path).ok();
     }
 
+    #[tokio::test]
+    async fn test_storage_remove_file() {
+        let mut db_path = std::env::temp_dir();
+        db_path.push("BetterFinder");
+        std::fs::create_dir_all(&db_path).ok();
+        db_path.push(format!("recent_files_remove_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+        
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+        
+        let kept = PathBuf::from("C:\\test\\kept.txt");
+        let recycled = PathBuf::from("C:\\test\\recycled.txt");
+        storage.track_file(&kept, InteractionKind::Opened, ResultType::File).await.unwrap();
+        storage.track_file(&recycled, InteractionKind::Opened, ResultType::File).await.unwrap();
+        
+        storage.remove_file(&recycled).await.unwrap();
+        
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, kept);
+        
+        std::fs::remove_file(&db_path).ok();
+    }
+
     #[tokio::test]
     async fn test_storage_get_recent_files_limit() {
         // Create a unique test database
@@ -428,15 +919,12 @@ mod tests {
         // Clean up any existing test file
         let _ = std::fs::remove_file(&db_path);
         
-        let storage = RecentFilesStorage {
-            db_path: db_path.clone(),
-        };
-        storage.initialize_db().unwrap();
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
 
         // Track multiple files
         for i in 0..10 {
             let path = PathBuf::from(format!("C:\\test\\file{}.txt", i));
-            storage.track_file(&path).await.unwrap();
+            storage.track_file(&path, InteractionKind::Opened, ResultType::File).await.unwrap();
         }
 
         // Get only 5 recent files
@@ -450,6 +938,77 @@ mod tests {
         std::fs::remove_file(&db_path).ok();
     }
 
+    #[tokio::test]
+    async fn test_storage_recovers_from_damaged_database() {
+        let dir = std::env::temp_dir().join("BetterFinder").join(format!("recent_files_damaged_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("recent_files.db");
+
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+        storage.track_file(Path::new("C:\\test\\before.txt"), InteractionKind::Opened, ResultType::File).await.unwrap();
+        assert_eq!(storage.check_integrity(Utc::now(), CheckPolicy::Scheduled).await.unwrap(), IntegrityOutcome::Clean);
+        assert_eq!(storage.get_recent_files(10).await.unwrap().len(), 1);
+
+        std::fs::write(&db_path, vec![0xAB; 8192]).unwrap();
+        let outcome = storage.check_integrity(Utc::now(), CheckPolicy::Scheduled).await.unwrap();
+        assert!(matches!(outcome, IntegrityOutcome::Recovered(ref recovery) if recovery.archive_path.exists()));
+
+        // The storage keeps working on the rebuilt database
+        storage.track_file(Path::new("C:\\test\\after.txt"), InteractionKind::Opened, ResultType::File).await.unwrap();
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.to_string_lossy().contains("after"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_storage_stays_usable_when_reopen_fails() {
+        let dir = std::env::temp_dir().join("BetterFinder").join(format!("recent_files_reopen_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("recent_files.db");
+
+        let mut storage = RecentFilesStorage::open(&db_path).unwrap();
+        storage.track_file(Path::new("C:\\test\\before.txt"), InteractionKind::Opened, ResultType::File).await.unwrap();
+
+        // A folder can't be opened as a database
+        storage.db_path = dir.clone();
+        let _ = storage.check_integrity(Utc::now(), CheckPolicy::Scheduled).await;
+
+        let provider = RecentFilesProvider {
+            storage: Arc::new(RwLock::new(storage)),
+            limit: AtomicUsize::new(DEFAULT_RECENT_FILES_LIMIT),
+            icon_cache: Arc::new(IconCache::new()),
+            enabled: true,
+        };
+        let ProviderHealth::Degraded { reason } = provider.health() else {
+            panic!("expected degraded health, got {:?}", provider.health());
+        };
+        assert!(reason.contains("couldn't be reopened"), "{}", reason);
+
+        // Accesses are still tracked, in memory
+        provider
+            .track_file_access(Path::new("C:\\test\\during.txt"), InteractionKind::Opened, ResultType::File)
+            .await
+            .unwrap();
+        let files = provider.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.to_string_lossy().contains("during"));
+
+        // The next check reopens the file, and its outcome comes back
+        provider.storage.write().await.db_path = db_path.clone();
+        assert_eq!(provider.check_integrity(Utc::now(), CheckPolicy::Scheduled).await.unwrap(), IntegrityOutcome::Clean);
+        assert_eq!(provider.health(), ProviderHealth::Ready);
+        let files = provider.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].path.to_string_lossy().contains("before"));
+
+        drop(provider);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[tokio::test]
     async fn test_storage_cleanup_missing_files() {
         // Create a unique test database
@@ -461,14 +1020,11 @@ mod tests {
         // Clean up any existing test file
         let _ = std::fs::remove_file(&test_path);
         
-        let storage = RecentFilesStorage {
-            db_path: test_path.clone(),
-        };
-        storage.initialize_db().unwrap();
+        let storage = RecentFilesStorage::open(&test_path).unwrap();
 
         // Track a non-existent file
         let fake_path = PathBuf::from("C:\\nonexistent\\file.txt");
-        storage.track_file(&fake_path).await.unwrap();
+        storage.track_file(&fake_path, InteractionKind::Opened, ResultType::File).await.unwrap();
 
         // Cleanup should remove it
         let removed = storage.cleanup_missing_files().await.unwrap();
@@ -493,15 +1049,12 @@ mod tests {
         // Clean up any existing test file
         let _ = std::fs::remove_file(&db_path);
         
-        let storage = RecentFilesStorage {
-            db_path: db_path.clone(),
-        };
-        storage.initialize_db().unwrap();
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
 
         // Track more than MAX_RECENT_FILES
         for i in 0..60 {
             let path = PathBuf::from(format!("C:\\test\\file{}.txt", i));
-            storage.track_file(&path).await.unwrap();
+            storage.track_file(&path, InteractionKind::Opened, ResultType::File).await.unwrap();
         }
 
         // Should only keep MAX_RECENT_FILES
@@ -511,12 +1064,359 @@ mod tests {
         // Cleanup
         std::fs::remove_file(&db_path).ok();
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_storage_concurrent_track_file() {
+        let mut db_path = std::env::temp_dir();
+        db_path.push("BetterFinder");
+        std::fs::create_dir_all(&db_path).ok();
+        db_path.push(format!("recent_files_concurrent_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = Arc::new(RecentFilesStorage::open(&db_path).unwrap());
+
+        // 100 accesses spread over 10 files
+        let tasks = (0..100).map(|i| {
+            let storage = Arc::clone(&storage);
+            tokio::spawn(async move {
+                let path = PathBuf::from(format!("C:\\test\\burst{}.txt", i % 10));
+                storage.track_file(&path, InteractionKind::Opened, ResultType::File).await
+            })
+        });
+
+        for outcome in futures::future::join_all(tasks).await {
+            assert!(outcome.unwrap().is_ok());
+        }
+
+        let files = storage.get_recent_files(100).await.unwrap();
+        assert_eq!(files.len(), 10);
+        assert!(files.iter().all(|file| file.access_count == 10));
+
+        drop(storage);
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_storage_batched_cleanup_enforces_max() {
+        let mut db_path = std::env::temp_dir();
+        db_path.push("BetterFinder");
+        std::fs::create_dir_all(&db_path).ok();
+        db_path.push(format!("recent_files_batched_cleanup_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+
+        for i in 0..70 {
+            let path = PathBuf::from(format!("C:\\test\\file{}.txt", i));
+            storage.track_file(&path, InteractionKind::Opened, ResultType::File).await.unwrap();
+        }
+
+        // Cleanup runs every CLEANUP_WRITE_INTERVAL writes, so storage never
+        // grows far past the limit
+        let stored: usize = {
+            let connection = storage.connection.lock().await;
+            connection
+                .conn
+                .query_row("SELECT COUNT(*) FROM recent_files", [], |row| row.get(0))
+                .unwrap()
+        };
+        assert!(stored <= MAX_RECENT_FILES + CLEANUP_WRITE_INTERVAL);
+
+        // Readers only ever see the most recent MAX_RECENT_FILES
+        let files = storage.get_recent_files(100).await.unwrap();
+        assert_eq!(files.len(), MAX_RECENT_FILES);
+        assert!(files[0].path.to_string_lossy().contains("file69"));
+
+        // The oldest entries were deleted rather than hidden
+        let oldest = PathBuf::from("C:\\test\\file0.txt");
+        let connection = storage.connection.lock().await;
+        let kept: usize = connection
+            .conn
+            .query_row(
+                "SELECT COUNT(*) FROM recent_files WHERE path = ?1",
+                params![oldest.to_string_lossy()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(kept, 0);
+        drop(connection);
+
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_storage_clear_older_than() {
+        let mut db_path = std::env::temp_dir();
+        db_path.push("BetterFinder");
+        std::fs::create_dir_all(&db_path).ok();
+        db_path.push(format!("recent_files_clear_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+        let now = Utc::now();
+        {
+            let connection = storage.connection.lock().await;
+            for (name, hours_ago) in [("new", 1), ("day", 30), ("week", 200)] {
+                connection
+                    .conn
+                    .execute(
+                        "INSERT INTO recent_files (path, last_accessed, access_count) VALUES (?1, ?2, 1)",
+                        params![name, (now - chrono::Duration::hours(hours_ago)).to_rfc3339()],
+                    )
+                    .unwrap();
+            }
+        }
+
+        let removed = storage.clear(Some(Duration::from_secs(24 * 3600))).await.unwrap();
+        assert_eq!(removed, 2);
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, PathBuf::from("new"));
+
+        assert_eq!(storage.clear(None).await.unwrap(), 1);
+        assert!(storage.get_recent_files(10).await.unwrap().is_empty());
+
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_storage_migrates_v1_database() {
+        let dir = std::env::temp_dir().join("BetterFinder").join(format!("recent_files_v1_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("recent_files.db");
+
+        // The schema before interaction kinds were recorded
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE recent_files (
+                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                     path TEXT NOT NULL UNIQUE,
+                     last_accessed TEXT NOT NULL,
+                     access_count INTEGER NOT NULL DEFAULT 1
+                 );
+                 CREATE INDEX idx_last_accessed ON recent_files(last_accessed DESC);",
+            )
+            .unwrap();
+            conn.execute(
+                "INSERT INTO recent_files (path, last_accessed, access_count) VALUES (?1, ?2, 4)",
+                params!["C:\\test\\report.docx", Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+        }
+
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+        let files = storage.get_opened_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].access_count, 4);
+        assert_eq!(files[0].interaction_kind, InteractionKind::Opened);
+        assert_eq!(files[0].result_type, None);
+        assert_eq!(files[0].weight, 4.0);
+
+        // Migrated entries take new interactions, and opening again doesn't migrate twice
+        storage
+            .track_file(Path::new("C:\\test\\report.docx"), InteractionKind::Copied, ResultType::File)
+            .await
+            .unwrap();
+        drop(storage);
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files[0].access_count, 5);
+        assert_eq!(files[0].interaction_kind, InteractionKind::Opened);
+        assert_eq!(files[0].result_type, Some(ResultType::File));
+        assert!((files[0].weight - 4.1).abs() < 1e-9);
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Resolves the listed paths to others, as junctions would, and every
+    /// other path to itself
+    struct MappedResolver(HashMap<PathBuf, PathBuf>);
+
+    impl PathResolver for MappedResolver {
+        fn final_path(&self, path: &Path) -> Option<PathBuf> {
+            Some(self.0.get(path).cloned().unwrap_or_else(|| path.to_path_buf()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_dedups_paths_to_one_file() {
+        let dir = std::env::temp_dir().join("BetterFinder").join(format!("recent_files_links_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let target = dir.join("Docs");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("report.txt"), "draft").unwrap();
+
+        // Links on Windows need Developer Mode or elevation; without them a
+        // mapped resolver stands in for the file system
+        let link = dir.join("Documents");
+        #[cfg(unix)]
+        let linked = std::os::unix::fs::symlink(&target, &link).is_ok();
+        #[cfg(windows)]
+        let linked = std::os::windows::fs::symlink_dir(&target, &link).is_ok();
+        let resolver: Arc<dyn PathResolver> = if linked {
+            Arc::new(SystemResolver)
+        } else {
+            Arc::new(MappedResolver(HashMap::from([(link.join("report.txt"), target.join("report.txt"))])))
+        };
+
+        let storage = RecentFilesStorage::open_with_resolver(&dir.join("recent_files.db"), resolver).unwrap();
+        storage.track_file(&target.join("report.txt"), InteractionKind::Opened, ResultType::File).await.unwrap();
+        storage.track_file(&link.join("report.txt"), InteractionKind::Copied, ResultType::RecentFile).await.unwrap();
+
+        // One entry, showing the path it was last used through
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].access_count, 2);
+        assert_eq!(files[0].interaction_kind, InteractionKind::Opened);
+        assert_eq!(files[0].path, link.join("report.txt"));
+        assert!(files[0].exists());
+        assert_eq!(storage.cleanup_missing_files().await.unwrap(), 0);
+
+        // Forgetting the file through either path forgets the entry
+        storage.remove_file(&target.join("report.txt")).await.unwrap();
+        assert!(storage.get_recent_files(10).await.unwrap().is_empty());
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_storage_migrates_to_canonical_paths() {
+        let dir = std::env::temp_dir().join("BetterFinder").join(format!("recent_files_v2_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("recent_files.db");
+
+        // The schema before canonical paths were recorded, holding one file
+        // under its junction path and its target path
+        let now = Utc::now();
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE recent_files (
+                     id INTEGER PRIMARY KEY AUTOINCREMENT,
+                     path TEXT NOT NULL UNIQUE,
+                     last_accessed TEXT NOT NULL,
+                     access_count INTEGER NOT NULL DEFAULT 1,
+                     interaction_kind TEXT NOT NULL DEFAULT 'opened',
+                     result_type TEXT,
+                     weight REAL NOT NULL DEFAULT 1
+                 );",
+            )
+            .unwrap();
+            for (path, hours_ago, count, kind) in [
+                ("C:\\Users\\ana\\Documents\\report.docx", 5, 2, "copied"),
+                ("D:\\Docs\\report.docx", 1, 3, "opened"),
+                ("C:\\test\\notes.txt", 2, 1, "revealed"),
+            ] {
+                conn.execute(
+                    "INSERT INTO recent_files (path, last_accessed, access_count, interaction_kind, weight)
+                     VALUES (?1, ?2, ?3, ?4, ?3)",
+                    params![path, (now - chrono::Duration::hours(hours_ago)).to_rfc3339(), count, kind],
+                )
+                .unwrap();
+            }
+        }
+
+        let resolver = || {
+            Arc::new(MappedResolver(HashMap::from([(
+                PathBuf::from("C:\\Users\\ana\\Documents\\report.docx"),
+                PathBuf::from("D:\\Docs\\report.docx"),
+            )])))
+        };
+        let storage = RecentFilesStorage::open_with_resolver(&db_path, resolver()).unwrap();
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, PathBuf::from("D:\\Docs\\report.docx"));
+        assert_eq!(files[0].canonical_path, PathBuf::from("D:\\Docs\\report.docx"));
+        assert_eq!(files[0].access_count, 5);
+        assert_eq!(files[0].interaction_kind, InteractionKind::Opened);
+        assert_eq!(files[0].weight, 5.0);
+        assert_eq!(files[1].canonical_path, PathBuf::from("C:\\test\\notes.txt"));
+
+        // Using the junction path again finds the merged entry
+        storage
+            .track_file(Path::new("C:\\Users\\ana\\Documents\\report.docx"), InteractionKind::Opened, ResultType::File)
+            .await
+            .unwrap();
+        drop(storage);
+        let storage = RecentFilesStorage::open_with_resolver(&db_path, resolver()).unwrap();
+        let files = storage.get_recent_files(10).await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].access_count, 6);
+        assert_eq!(files[0].path, PathBuf::from("C:\\Users\\ana\\Documents\\report.docx"));
+
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_storage_records_strongest_interaction() {
+        let mut db_path = std::env::temp_dir();
+        db_path.push("BetterFinder");
+        std::fs::create_dir_all(&db_path).ok();
+        db_path.push(format!("recent_files_kinds_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&db_path);
+
+        let storage = RecentFilesStorage::open(&db_path).unwrap();
+        let revealed = Path::new("C:\\test\\revealed.txt");
+        let promoted = Path::new("C:\\test\\promoted.txt");
+        storage.track_file(revealed, InteractionKind::Revealed, ResultType::File).await.unwrap();
+        storage.track_file(revealed, InteractionKind::Copied, ResultType::File).await.unwrap();
+        storage.track_file(promoted, InteractionKind::Copied, ResultType::File).await.unwrap();
+        storage.track_file(promoted, InteractionKind::Opened, ResultType::RecentFile).await.unwrap();
+
+        let files = storage.get_recent_files(10).await.unwrap();
+        let revealed_file = files.iter().find(|file| file.path == revealed).unwrap();
+        assert_eq!(revealed_file.interaction_kind, InteractionKind::Revealed);
+        assert!((revealed_file.weight - 0.4).abs() < 1e-9);
+        let promoted_file = files.iter().find(|file| file.path == promoted).unwrap();
+        assert_eq!(promoted_file.interaction_kind, InteractionKind::Opened);
+        assert_eq!(promoted_file.result_type, Some(ResultType::RecentFile));
+
+        // Only files opened at least once are listed before typing
+        let opened = storage.get_opened_files(10).await.unwrap();
+        assert_eq!(opened.len(), 1);
+        assert_eq!(opened[0].path, promoted);
+
+        drop(storage);
+        std::fs::remove_file(&db_path).ok();
+    }
+
+    #[test]
+    fn test_cleanup_due_by_writes_or_time() {
+        let conn = Connection::open_in_memory().unwrap();
+        let now = Instant::now();
+        let mut connection = StorageConnection {
+            conn,
+            writes_since_cleanup: 0,
+            last_cleanup: None,
+        };
+
+        // Never cleaned up yet
+        assert!(connection.cleanup_due(now));
+
+        connection.last_cleanup = Some(now);
+        connection.writes_since_cleanup = CLEANUP_WRITE_INTERVAL - 1;
+        assert!(!connection.cleanup_due(now + Duration::from_secs(1)));
+        assert!(connection.cleanup_due(now + CLEANUP_INTERVAL));
+
+        connection.writes_since_cleanup = CLEANUP_WRITE_INTERVAL;
+        assert!(connection.cleanup_due(now + Duration::from_secs(1)));
+    }
 }
 
 /// Recent files search provider
 pub struct RecentFilesProvider {
     /// Storage backend
     storage: Arc<RwLock<RecentFilesStorage>>,
+    /// Number of files shown for an empty query
+    limit: AtomicUsize,
+    /// File icons extracted during enrichment
+    icon_cache: Arc<IconCache>,
     /// Whether the provider is enabled
     enabled: bool,
 }
@@ -530,6 +1430,8 @@ impl RecentFilesProvider {
 
         Ok(Self {
             storage: Arc::new(RwLock::new(storage)),
+            limit: AtomicUsize::new(DEFAULT_RECENT_FILES_LIMIT),
+            icon_cache: Arc::new(IconCache::new()),
             enabled: true,
         })
     }
@@ -540,10 +1442,50 @@ impl RecentFilesProvider {
         storage.get_recent_files(limit).await
     }
 
-    /// Tracks a file access
-    pub async fn track_file_access(&self, path: &Path) -> Result<()> {
+    /// Tracks a file used through a result of `result_type`
+    pub async fn track_file_access(&self, path: &Path, kind: InteractionKind, result_type: ResultType) -> Result<()> {
         let storage = self.storage.read().await;
-        storage.track_file(path).await
+        storage.track_file(path, kind, result_type).await
+    }
+
+    /// Forgets a file that no longer exists, e.g. after it was recycled
+    pub async fn forget_file(&self, path: &Path) -> Result<()> {
+        let storage = self.storage.read().await;
+        storage.remove_file(path).await
+    }
+
+    /// Writes queued accesses and checkpoints the database, see `RecentFilesStorage::flush`
+    pub async fn flush(&self) -> Result<()> {
+        self.storage.read().await.flush().await
+    }
+
+    /// Checks the recent files database for damage, see `RecentFilesStorage::check_integrity`
+    pub async fn check_integrity(&self, now: DateTime<Utc>, policy: CheckPolicy) -> Result<IntegrityOutcome> {
+        self.storage.read().await.check_integrity(now, policy).await
+    }
+
+    /// Writes every stored recent file to a JSON file
+    pub async fn export(&self, path: &Path) -> Result<usize> {
+        let files = self.get_recent_files(MAX_RECENT_FILES).await?;
+        write_json_export(
+            path,
+            &serde_json::json!({
+                "exported_at": Utc::now(),
+                "files": files,
+            }),
+        )?;
+
+        info!("Exported {} recent files to {}", files.len(), path.display());
+        Ok(files.len())
+    }
+
+    /// Deletes every recent file, or only those last accessed more than `older_than` ago
+    pub async fn clear(&self, older_than: Option<Duration>) -> Result<usize> {
+        let storage = self.storage.read().await;
+        let removed = storage.clear(older_than).await?;
+
+        info!("Cleared {} recent files", removed);
+        Ok(removed)
     }
 
     /// Creates a search result from a recent file
@@ -555,12 +1497,21 @@ impl RecentFilesProvider {
         let mut metadata = HashMap::new();
         metadata.insert("path".to_string(), serde_json::json!(path_str));
         metadata.insert("last_accessed".to_string(), serde_json::json!(file.last_accessed));
+        time::insert_time_metadata(&mut metadata, file.last_accessed);
         metadata.insert("access_count".to_string(), serde_json::json!(file.access_count));
+        metadata.insert("interaction_kind".to_string(), serde_json::json!(file.interaction_kind));
 
+        let used = match file.interaction_kind {
+            InteractionKind::Opened => "Opened",
+            InteractionKind::Revealed => "Shown in folder",
+            InteractionKind::Copied => "Path copied",
+        };
+
+        // The file's own icon is extracted by `enrich` once the result is shown
         SearchResult {
             id: format!("recent:{}", path_str),
             title: file_name,
-            subtitle: format!("{} • Opened {}", path_str, timestamp),
+            subtitle: format!("{} • {} {}", path_str, used, timestamp),
             icon: Self::get_file_icon(&file.path),
             result_type: ResultType::RecentFile,
             score,
@@ -568,10 +1519,38 @@ impl RecentFilesProvider {
             action: ResultAction::OpenFile {
                 path: path_str,
             },
+            normalized_title: None,
+            enrichment_pending: true,
+            integrity: None,
+        }
+    }
+
+    /// Scores how well a folded file name matches the folded query
+    fn name_match(query: &str, name: &str) -> Option<f64> {
+        if name == query {
+            return Some(100.0);
+        }
+        if name.starts_with(query) {
+            return Some(90.0);
         }
+        if name.contains(query) {
+            return Some(70.0);
+        }
+
+        // Every query character in order
+        let mut name_chars = name.chars();
+        query.chars().all(|c| name_chars.any(|n| n == c)).then_some(50.0)
+    }
+
+    /// Score of a recent file matching a typed query: the name match plus a
+    /// bonus for how much, how recently and how strongly it was used
+    fn match_score(name_score: f64, file: &RecentFile, now: DateTime<Utc>) -> f64 {
+        let bonus = (file.frecency(now).ln_1p() * FRECENCY_BONUS_SCALE).min(MAX_FRECENCY_BONUS);
+        name_score * 0.8 + bonus
     }
 
-    /// Gets an icon for a file based on its extension
+    /// Gets a generic icon for a file based on its extension, shown until
+    /// the file's own icon is extracted
     fn get_file_icon(path: &Path) -> Option<String> {
         // For now, return a generic file icon name
         // In a full implementation, this would extract the actual icon
@@ -655,12 +1634,16 @@ impl SearchProvider for RecentFilesProvider {
         90 // High priority - show recent files prominently
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let trimmed = query.trim();
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::RecentFile])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
 
-        // Only show recent files when query is empty
+        // Only files that were opened are listed before you type
         if trimmed.is_empty() {
-            let files = self.get_recent_files(DEFAULT_RECENT_FILES_LIMIT).await?;
+            let files = self.storage.read().await.get_opened_files(self.limit.load(Ordering::Relaxed)).await?;
             
             // Filter out files that no longer exist
             let valid_files: Vec<_> = files.into_iter().filter(|f| f.exists()).collect();
@@ -678,9 +1661,21 @@ impl SearchProvider for RecentFilesProvider {
 
             Ok(results)
         } else {
-            // Don't show recent files for non-empty queries
-            // Other providers will handle the search
-            Ok(Vec::new())
+            // A typed query matches files however they were used, weighted by kind
+            let now = Utc::now();
+            let mut results: Vec<SearchResult> = self
+                .get_recent_files(MAX_RECENT_FILES)
+                .await?
+                .into_iter()
+                .filter(|file| file.exists())
+                .filter_map(|file| {
+                    let name_score = Self::name_match(&query.folded_query, &query.fold(&file.file_name()))?;
+                    Some(self.create_search_result(&file, Self::match_score(name_score, &file, now)))
+                })
+                .collect();
+            results.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+            Ok(results)
         }
     }
 
@@ -702,20 +1697,52 @@ impl SearchProvider for RecentFilesProvider {
 
         info!("Opening recent file: {}", path);
 
-        // Open the file
+        // The engine tracks the access once this succeeds
         Self::open_file(path).await?;
 
-        // Track the access (this will update the timestamp and count)
-        self.track_file_access(Path::new(path)).await?;
-
         info!("Successfully opened recent file");
         Ok(())
     }
 
+    async fn enrich(&self, result: &mut SearchResult) -> Result<()> {
+        if let Some(path) = result.file_path() {
+            if let Some(icon) = self.icon_cache.get_or_extract(Path::new(path)).await {
+                result.icon = Some(icon);
+            }
+        }
+        Ok(())
+    }
+
+    async fn cache_stats(&self) -> Vec<CacheStats> {
+        vec![self.icon_cache.stats().await]
+    }
+
+    async fn trim_caches(&self) -> usize {
+        self.icon_cache.trim().await
+    }
+
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        let config = RecentFilesConfig::parse(config)?;
+        self.limit.store(config.limit, Ordering::Relaxed);
+        Ok(())
+    }
+
     fn is_enabled(&self) -> bool {
         self.enabled
     }
 
+    /// Degraded while the database couldn't be reopened after an integrity check
+    fn health(&self) -> ProviderHealth {
+        if !self.is_enabled() {
+            return ProviderHealth::Disabled;
+        }
+        // A writer holding the storage is busy with it, not failing
+        match self.storage.try_read().ok().and_then(|storage| storage.reopen_failure()) {
+            Some(reason) => ProviderHealth::Degraded { reason },
+            None => ProviderHealth::Ready,
+        }
+    }
+
     async fn initialize(&mut self) -> Result<()> {
         info!("Initializing RecentFilesProvider");
 
@@ -747,6 +1774,8 @@ impl Default for RecentFilesProvider {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             storage: Arc::new(RwLock::new(RecentFilesStorage::default())),
+            limit: AtomicUsize::new(DEFAULT_RECENT_FILES_LIMIT),
+            icon_cache: Arc::new(IconCache::new()),
             enabled: false,
         })
     }
@@ -773,10 +1802,10 @@ mod provider_tests {
 
         // Track some files first
         let test_path = PathBuf::from("C:\\test\\file.txt");
-        provider.track_file_access(&test_path).await.unwrap();
+        provider.track_file_access(&test_path, InteractionKind::Opened, ResultType::File).await.unwrap();
 
         // Search with empty query should return recent files
-        let results = provider.search("").await.unwrap();
+        let results = provider.search(&"".into()).await.unwrap();
         
         // Note: Results might be empty if the file doesn't exist
         // In a real scenario with existing files, this would return results
@@ -788,20 +1817,118 @@ mod provider_tests {
         let provider = RecentFilesProvider::new().unwrap();
 
         // Search with non-empty query should return nothing
-        let results = provider.search("test").await.unwrap();
+        let results = provider.search(&"test".into()).await.unwrap();
         assert_eq!(results.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_provider_configure_changes_limit() {
+        let mut dir = std::env::temp_dir();
+        dir.push("BetterFinder");
+        dir.push(format!("recent_files_configure_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("recent.db");
+        let _ = std::fs::remove_file(&db_path);
+
+        let provider = RecentFilesProvider {
+            storage: Arc::new(RwLock::new(RecentFilesStorage::open(&db_path).unwrap())),
+            limit: AtomicUsize::new(DEFAULT_RECENT_FILES_LIMIT),
+            icon_cache: Arc::new(IconCache::new()),
+            enabled: true,
+        };
+        for i in 0..8 {
+            let path = dir.join(format!("file{}.txt", i));
+            std::fs::write(&path, "x").unwrap();
+            provider.track_file_access(&path, InteractionKind::Opened, ResultType::File).await.unwrap();
+        }
+        assert_eq!(provider.search(&"".into()).await.unwrap().len(), DEFAULT_RECENT_FILES_LIMIT);
+
+        provider.configure(&serde_json::json!({ "limit": 2 })).await.unwrap();
+        assert_eq!(provider.search(&"".into()).await.unwrap().len(), 2);
+
+        provider.configure(&serde_json::json!({ "limit": 7 })).await.unwrap();
+        assert_eq!(provider.search(&"".into()).await.unwrap().len(), 7);
+
+        assert!(provider.configure(&serde_json::json!({ "limit": MAX_RECENT_FILES + 1 })).await.is_err());
+        assert_eq!(provider.search(&"".into()).await.unwrap().len(), 7);
+
+        drop(provider);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_provider_typed_query_weighs_kinds() {
+        let dir = std::env::temp_dir()
+            .join("BetterFinder")
+            .join(format!("recent_files_weighted_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("recent.db");
+        let _ = std::fs::remove_file(&db_path);
+
+        let provider = RecentFilesProvider {
+            storage: Arc::new(RwLock::new(RecentFilesStorage::open(&db_path).unwrap())),
+            limit: AtomicUsize::new(DEFAULT_RECENT_FILES_LIMIT),
+            icon_cache: Arc::new(IconCache::new()),
+            enabled: true,
+        };
+        for (name, kind) in [
+            ("budget-copied.xlsx", InteractionKind::Copied),
+            ("budget-opened.xlsx", InteractionKind::Opened),
+            ("budget-revealed.xlsx", InteractionKind::Revealed),
+        ] {
+            let path = dir.join(name);
+            std::fs::write(&path, "x").unwrap();
+            provider.track_file_access(&path, kind, ResultType::File).await.unwrap();
+        }
+
+        // Equal name matches rank by how the files were used
+        let results = provider.search(&"budget".into()).await.unwrap();
+        let titles: Vec<_> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["budget-opened.xlsx", "budget-revealed.xlsx", "budget-copied.xlsx"]);
+        assert!(results[2].subtitle.contains("Path copied"));
+
+        // A better name match still wins over a stronger kind
+        let results = provider.search(&"budget-copied.xlsx".into()).await.unwrap();
+        assert_eq!(results[0].title, "budget-copied.xlsx");
+
+        // Before typing, only the opened file is listed
+        let results = provider.search(&"".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "budget-opened.xlsx");
+
+        drop(provider);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_frecency_weighs_kind_and_age() {
+        let now = Utc::now();
+        let mut file = RecentFile::new(PathBuf::from("report.docx"));
+        file.last_accessed = now;
+        let mut copied = file.clone();
+        copied.weight = InteractionKind::Copied.weight();
+        let mut old = file.clone();
+        old.last_accessed = now - chrono::Duration::days(40);
+
+        assert!(file.frecency(now) > copied.frecency(now));
+        assert!(file.frecency(now) > old.frecency(now));
+
+        let score = |file: &RecentFile| RecentFilesProvider::match_score(90.0, file, now);
+        assert!(score(&file) > score(&copied));
+        assert!(score(&copied) < RecentFilesProvider::match_score(100.0, &copied, now));
+        assert!(score(&file) - 90.0 * 0.8 <= MAX_FRECENCY_BONUS);
+    }
+
     #[tokio::test]
     async fn test_provider_track_file_access() {
         let provider = RecentFilesProvider::new().unwrap();
 
         let test_path = PathBuf::from("C:\\test\\document.txt");
-        let result = provider.track_file_access(&test_path).await;
+        let result = provider.track_file_access(&test_path, InteractionKind::Opened, ResultType::File).await;
         assert!(result.is_ok());
 
         // Track again to test update
-        let result = provider.track_file_access(&test_path).await;
+        let result = provider.track_file_access(&test_path, InteractionKind::Opened, ResultType::File).await;
         assert!(result.is_ok());
     }
 
@@ -849,5 +1976,12 @@ mod provider_tests {
         assert_eq!(result.title, "document.txt");
         assert!(result.subtitle.contains(&test_path.to_string_lossy().to_string()));
         assert_eq!(result.score, 95.0);
+
+        // The generic icon shows until enrichment extracts the file's own
+        assert!(result.enrichment_pending);
+        assert_eq!(result.icon.as_deref(), Some("file-text"));
+        let mut enriched = result.clone();
+        provider.enrich(&mut enriched).await.unwrap();
+        assert!(enriched.icon.is_some());
     }
 }
@@ -0,0 +1,1140 @@
+/// Recent projects provider for developer tools
+///
+/// This provider lists the projects recently opened in VS Code, the JetBrains
+/// IDEs and Visual Studio, read from each IDE's own history, and reopens the
+/// chosen one in the IDE it came from. Projects whose IDE can't be found are
+/// left out, since there would be nothing to open them with.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::app_search::{AppCache, Application};
+use crate::search::providers::browser_history::HistorySource;
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::normalize_for_search;
+use crate::utils::process::{self, Wait};
+use async_trait::async_trait;
+use chrono::DateTime;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Maximum number of results per query
+const MAX_RESULTS: usize = 10;
+
+/// Minimum time between two reads of the IDE histories
+const REFRESH_INTERVAL: Duration = Duration::from_secs(300); // 5 minutes
+
+/// Key of VS Code's recently opened list in `state.vscdb`
+const VSCODE_RECENT_KEY: &str = "history.recentlyOpenedPathsList";
+
+/// Collection of Visual Studio's recently opened solutions and folders
+const VISUAL_STUDIO_RECENT_COLLECTION: &str = "CodeContainers.Offline";
+
+/// A JetBrains IDE, recognized by its config folder under `%APPDATA%\JetBrains`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct JetBrainsProduct {
+    /// Config folder name without the version, e.g. `PyCharmCE`
+    pub config_prefix: &'static str,
+    /// Name shown in results
+    pub name: &'static str,
+    /// Launcher in the IDE's `bin` folder
+    pub executable: &'static str,
+}
+
+/// JetBrains IDEs whose recent projects are read
+pub const JETBRAINS_PRODUCTS: &[JetBrainsProduct] = &[
+    JetBrainsProduct { config_prefix: "IntelliJIdea", name: "IntelliJ IDEA", executable: "idea64.exe" },
+    JetBrainsProduct { config_prefix: "IdeaIC", name: "IntelliJ IDEA Community", executable: "idea64.exe" },
+    JetBrainsProduct { config_prefix: "PyCharm", name: "PyCharm", executable: "pycharm64.exe" },
+    JetBrainsProduct { config_prefix: "PyCharmCE", name: "PyCharm Community", executable: "pycharm64.exe" },
+    JetBrainsProduct { config_prefix: "WebStorm", name: "WebStorm", executable: "webstorm64.exe" },
+    JetBrainsProduct { config_prefix: "PhpStorm", name: "PhpStorm", executable: "phpstorm64.exe" },
+    JetBrainsProduct { config_prefix: "CLion", name: "CLion", executable: "clion64.exe" },
+    JetBrainsProduct { config_prefix: "Rider", name: "Rider", executable: "rider64.exe" },
+    JetBrainsProduct { config_prefix: "GoLand", name: "GoLand", executable: "goland64.exe" },
+    JetBrainsProduct { config_prefix: "RubyMine", name: "RubyMine", executable: "rubymine64.exe" },
+    JetBrainsProduct { config_prefix: "DataGrip", name: "DataGrip", executable: "datagrip64.exe" },
+    JetBrainsProduct { config_prefix: "RustRover", name: "RustRover", executable: "rustrover64.exe" },
+];
+
+impl JetBrainsProduct {
+    /// Recognizes a config folder name such as `PyCharmCE2024.1`
+    ///
+    /// The prefix must be followed by the version, so `PyCharm` doesn't
+    /// claim the folders of PyCharm Community.
+    pub fn from_config_dir(dir_name: &str) -> Option<Self> {
+        JETBRAINS_PRODUCTS.iter().copied().find(|product| {
+            dir_name
+                .strip_prefix(product.config_prefix)
+                .is_some_and(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+        })
+    }
+}
+
+/// A developer tool whose recent projects are listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ide {
+    VsCode,
+    JetBrains(JetBrainsProduct),
+    VisualStudio,
+}
+
+impl Ide {
+    /// Name shown in results
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ide::VsCode => "VS Code",
+            Ide::JetBrains(product) => product.name,
+            Ide::VisualStudio => "Visual Studio",
+        }
+    }
+
+    /// Identifier used in result IDs and metadata
+    pub fn id(&self) -> String {
+        match self {
+            Ide::VsCode => "vscode".to_string(),
+            Ide::JetBrains(product) => product.config_prefix.to_lowercase(),
+            Ide::VisualStudio => "visual_studio".to_string(),
+        }
+    }
+
+    /// File name of the executable that opens projects
+    pub fn executable(&self) -> &'static str {
+        match self {
+            Ide::VsCode => "Code.exe",
+            Ide::JetBrains(product) => product.executable,
+            Ide::VisualStudio => "devenv.exe",
+        }
+    }
+
+    /// Arguments that open `project` in the IDE
+    ///
+    /// VS Code gets a window of its own instead of replacing the project in
+    /// the last active one; the others open a path passed on its own.
+    pub fn launch_args(&self, project: &Path) -> Vec<String> {
+        let project = project.to_string_lossy().to_string();
+        match self {
+            Ide::VsCode => vec!["--new-window".to_string(), project],
+            Ide::JetBrains(_) | Ide::VisualStudio => vec![project],
+        }
+    }
+
+    /// Locations of the executable relative to an install root such as
+    /// `Program Files`; `*` stands for any folder
+    fn install_patterns(&self) -> Vec<Vec<&'static str>> {
+        match self {
+            Ide::VsCode => vec![vec!["Microsoft VS Code", "Code.exe"]],
+            Ide::JetBrains(product) => vec![
+                vec!["JetBrains", "*", "bin", product.executable],
+                // Toolbox installs each IDE straight under %LOCALAPPDATA%\Programs
+                vec!["*", "bin", product.executable],
+            ],
+            Ide::VisualStudio => vec![vec!["Microsoft Visual Studio", "*", "*", "Common7", "IDE", "devenv.exe"]],
+        }
+    }
+
+    /// Finds the executable among the scanned applications
+    fn find_in_apps(&self, apps: &[Application]) -> Option<PathBuf> {
+        apps.iter()
+            .find(|app| {
+                app.path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.eq_ignore_ascii_case(self.executable()))
+            })
+            .map(|app| app.path.clone())
+    }
+}
+
+/// A project recently opened in an IDE
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentProject {
+    /// Folder, workspace or solution name
+    pub name: String,
+    /// Folder, `.code-workspace` file or solution file
+    pub path: PathBuf,
+    /// IDE the project was opened in
+    pub ide: Ide,
+    /// Position in the IDE's recent list, 0 being the most recent
+    pub rank: usize,
+    /// Normalized name, computed once when the project is loaded
+    normalized_name: String,
+}
+
+impl RecentProject {
+    /// Creates a project named after the last component of its path
+    ///
+    /// Workspace and solution files are named without their extension.
+    pub fn new(path: PathBuf, ide: Ide, rank: usize) -> Self {
+        let name = if path.is_file() || path.extension().is_some_and(|ext| ext == "sln" || ext == "code-workspace") {
+            path.file_stem()
+        } else {
+            path.file_name()
+        }
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+        Self {
+            normalized_name: normalize_for_search(&name),
+            name,
+            path,
+            ide,
+            rank,
+        }
+    }
+
+    /// Creates a unique ID for the project
+    pub fn id(&self) -> String {
+        format!("project:{}:{}", self.ide.id(), self.path.to_string_lossy())
+    }
+}
+
+/// Converts a `file://` URI as stored by VS Code to a path
+///
+/// Windows drive paths come as `file:///c%3A/dev/app` and network shares
+/// as `file://server/share`; other schemes, such as remote folders, give `None`.
+pub fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    let decoded = urlencoding::decode(rest).ok()?;
+
+    match decoded.strip_prefix('/') {
+        Some(local) if local.as_bytes().get(1) == Some(&b':') && local.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+            Some(PathBuf::from(local.replace('/', "\\")))
+        }
+        Some(_) => Some(PathBuf::from(decoded.as_ref())),
+        None => Some(PathBuf::from(format!("\\\\{}", decoded.replace('/', "\\")))),
+    }
+}
+
+/// Reads the folders and workspaces from VS Code's recently opened list
+///
+/// Accepts the list as stored in `state.vscdb`, with an `entries` array, and
+/// the older `storage.json` layout with `workspaces3`. Opened files are not
+/// projects and are skipped.
+pub fn parse_vscode_recent(list: &serde_json::Value) -> Vec<PathBuf> {
+    let entries = list
+        .get("entries")
+        .or_else(|| list.get("workspaces3"))
+        .and_then(|entries| entries.as_array());
+    let Some(entries) = entries else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let uri = match entry {
+                serde_json::Value::String(uri) => Some(uri.as_str()),
+                entry => entry
+                    .get("folderUri")
+                    .or_else(|| entry.get("workspace").and_then(|workspace| workspace.get("configPath")))
+                    .or_else(|| entry.get("configURIPath"))
+                    .and_then(|uri| uri.as_str()),
+            }?;
+            file_uri_to_path(uri)
+        })
+        .collect()
+}
+
+/// Reads VS Code's `storage.json`, where versions before 1.64 kept the list
+pub fn parse_vscode_storage(content: &str) -> Result<Vec<PathBuf>> {
+    let storage: serde_json::Value = serde_json::from_str(content)?;
+    Ok(storage
+        .get("openedPathsList")
+        .map(parse_vscode_recent)
+        .unwrap_or_default())
+}
+
+/// Reads the recently opened list from a VS Code `state.vscdb` database
+pub fn read_vscode_state(conn: &Connection) -> Result<Vec<PathBuf>> {
+    let value: Option<String> = conn
+        .query_row("SELECT value FROM ItemTable WHERE key = ?1", [VSCODE_RECENT_KEY], |row| {
+            // VS Code has written the value both as text and as a blob
+            Ok(match row.get_ref(0)? {
+                ValueRef::Text(bytes) | ValueRef::Blob(bytes) => String::from_utf8_lossy(bytes).to_string(),
+                _ => String::new(),
+            })
+        })
+        .optional()?;
+
+    match value {
+        Some(value) => Ok(parse_vscode_recent(&serde_json::from_str(&value)?)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Undoes the entity escaping of an XML attribute or text
+fn unescape_xml(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&#10;", "\n")
+        .replace("&amp;", "&")
+}
+
+/// Returns the value of attribute `name` in the text of a start tag
+fn xml_attribute(tag: &str, name: &str) -> Option<String> {
+    let needle = format!(" {}=\"", name);
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    Some(unescape_xml(&tag[start..end]))
+}
+
+/// Reads the project paths from a JetBrains `recentProjects.xml`, most recent first
+///
+/// Current IDEs list projects as `additionalInfo` map entries with an
+/// activation timestamp; older ones keep a plain `recentPaths` list, already
+/// in order. `$USER_HOME$` is replaced with `home`, and paths using other
+/// IDE macros are skipped.
+pub fn parse_jetbrains_recent(xml: &str, home: Option<&Path>) -> Vec<PathBuf> {
+    let mut entries: Vec<(String, i64)> = Vec::new();
+    let mut recent_paths: Vec<String> = Vec::new();
+    // Map nesting inside `additionalInfo`; 0 outside of it
+    let mut info_depth = 0usize;
+    let mut in_recent_paths = false;
+    let mut in_info = false;
+
+    for tag in xml.split('<').skip(1) {
+        let tag = tag.split('>').next().unwrap_or_default();
+        let element = tag.split_whitespace().next().unwrap_or_default();
+
+        match element {
+            "option" => match xml_attribute(tag, "name").as_deref() {
+                Some("additionalInfo") => in_info = true,
+                Some("recentPaths") => in_recent_paths = true,
+                Some("activationTimestamp") if info_depth > 0 => {
+                    let timestamp = xml_attribute(tag, "value").and_then(|value| value.parse().ok());
+                    if let (Some(entry), Some(timestamp)) = (entries.last_mut(), timestamp) {
+                        entry.1 = timestamp;
+                    }
+                }
+                None if in_recent_paths => recent_paths.extend(xml_attribute(tag, "value")),
+                _ => {}
+            },
+            "map" if in_info => info_depth += 1,
+            "/map" if info_depth > 0 => {
+                info_depth -= 1;
+                in_info = info_depth > 0;
+            }
+            "entry" if info_depth == 1 => entries.extend(xml_attribute(tag, "key").map(|key| (key, 0))),
+            "/list" => in_recent_paths = false,
+            _ => {}
+        }
+    }
+
+    // Most recently activated first; the sort is stable for missing timestamps
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+    let mut seen = HashSet::new();
+
+    entries
+        .into_iter()
+        .map(|(path, _)| path)
+        .chain(recent_paths)
+        .filter_map(|path| {
+            let path = match (path.strip_prefix("$USER_HOME$"), home) {
+                (Some(rest), Some(home)) => format!("{}{}", home.to_string_lossy(), rest),
+                (Some(_), None) => return None,
+                (None, _) => path,
+            };
+            (!path.contains('$')).then(|| PathBuf::from(path))
+        })
+        .filter(|path| seen.insert(path.clone()))
+        .collect()
+}
+
+/// Reads the solutions and folders from Visual Studio's
+/// `ApplicationPrivateSettings.xml`, most recent first
+///
+/// The list is a JSON array stored as escaped text in the
+/// `CodeContainers.Offline` collection.
+pub fn parse_visual_studio_settings(xml: &str) -> Result<Vec<PathBuf>> {
+    let collection = format!("<collection name=\"{}\">", VISUAL_STUDIO_RECENT_COLLECTION);
+    let Some(start) = xml.find(&collection) else {
+        return Ok(Vec::new());
+    };
+    let rest = &xml[start + collection.len()..];
+    let value = rest
+        .find("<value name=\"value\">")
+        .map(|offset| &rest[offset + "<value name=\"value\">".len()..])
+        .and_then(|value| value.find("</value>").map(|end| &value[..end]))
+        .ok_or_else(|| LauncherError::ProviderError("Visual Studio recent list has no value".to_string()))?;
+
+    let containers: Vec<serde_json::Value> = serde_json::from_str(&unescape_xml(value))?;
+    let mut projects: Vec<(PathBuf, Option<DateTime<chrono::FixedOffset>>)> = containers
+        .iter()
+        .filter_map(|container| {
+            let path = container
+                .pointer("/Value/LocalProperties/FullPath")
+                .or_else(|| container.get("Key"))
+                .and_then(|path| path.as_str())?;
+            let last_accessed = container
+                .pointer("/Value/LastAccessed")
+                .and_then(|time| time.as_str())
+                .and_then(|time| DateTime::parse_from_rfc3339(time).ok());
+            Some((PathBuf::from(path), last_accessed))
+        })
+        .collect();
+
+    projects.sort_by_key(|project| std::cmp::Reverse(project.1));
+    Ok(projects.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Where the IDEs keep their recent projects and where they are installed
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSources {
+    /// VS Code's `User\globalStorage` folder
+    pub vscode_storage: Option<PathBuf>,
+    /// `%APPDATA%\JetBrains`, with a config folder per IDE and version
+    pub jetbrains_config: Option<PathBuf>,
+    /// `%LOCALAPPDATA%\Microsoft\VisualStudio`, with a folder per instance
+    pub visual_studio_data: Option<PathBuf>,
+    /// User's home folder, for JetBrains' `$USER_HOME$`
+    pub home: Option<PathBuf>,
+    /// Folders IDEs are installed under, used when the app scan hasn't found one
+    pub install_roots: Vec<PathBuf>,
+}
+
+impl ProjectSources {
+    /// Locates the sources from the environment
+    pub fn locate() -> Self {
+        let app_data = std::env::var_os("APPDATA").map(PathBuf::from);
+        let local_app_data = std::env::var_os("LOCALAPPDATA").map(PathBuf::from);
+
+        let install_roots = ["ProgramFiles", "ProgramFiles(x86)"]
+            .iter()
+            .filter_map(|var| std::env::var_os(var).map(PathBuf::from))
+            .chain(local_app_data.as_ref().map(|local| local.join("Programs")))
+            .collect();
+
+        Self {
+            vscode_storage: app_data.as_ref().map(|app_data| app_data.join("Code").join("User").join("globalStorage")),
+            jetbrains_config: app_data.as_ref().map(|app_data| app_data.join("JetBrains")),
+            visual_studio_data: local_app_data.map(|local| local.join("Microsoft").join("VisualStudio")),
+            home: std::env::var_os("USERPROFILE").or_else(|| std::env::var_os("HOME")).map(PathBuf::from),
+            install_roots,
+        }
+    }
+
+    /// Reads every IDE's recent projects, keeping those that still exist
+    ///
+    /// A source that fails to read is logged and skipped, so a format change
+    /// in one IDE never hides the projects of the others.
+    pub fn load_projects(&self) -> Vec<RecentProject> {
+        let mut projects = Vec::new();
+
+        let vscode: Vec<PathBuf> = self.vscode_paths();
+        projects.extend(vscode.into_iter().enumerate().map(|(rank, path)| RecentProject::new(path, Ide::VsCode, rank)));
+
+        for (product, paths) in self.jetbrains_paths() {
+            projects.extend(
+                paths
+                    .into_iter()
+                    .enumerate()
+                    .map(|(rank, path)| RecentProject::new(path, Ide::JetBrains(product), rank)),
+            );
+        }
+
+        let visual_studio = self.visual_studio_paths();
+        projects.extend(
+            visual_studio
+                .into_iter()
+                .enumerate()
+                .map(|(rank, path)| RecentProject::new(path, Ide::VisualStudio, rank)),
+        );
+
+        let mut seen = HashSet::new();
+        projects.retain(|project| {
+            project.path.exists() && seen.insert((project.ide, project.path.to_string_lossy().to_lowercase()))
+        });
+        debug!("Loaded {} recent projects", projects.len());
+        projects
+    }
+
+    /// VS Code's list from `state.vscdb`, followed by any older `storage.json` entries
+    fn vscode_paths(&self) -> Vec<PathBuf> {
+        let Some(storage) = &self.vscode_storage else {
+            return Vec::new();
+        };
+        let mut paths = Vec::new();
+
+        let database = storage.join("state.vscdb");
+        if database.exists() {
+            // VS Code keeps the database open, so read a copy
+            let read = HistorySource::snapshot(&database).and_then(|snapshot| {
+                let paths = Connection::open(&snapshot)
+                    .map_err(LauncherError::from)
+                    .and_then(|conn| read_vscode_state(&conn));
+                HistorySource::remove_snapshot(&snapshot);
+                paths
+            });
+            match read {
+                Ok(state) => paths.extend(state),
+                Err(e) => warn!("Failed to read VS Code recent list from {}: {}", database.display(), e),
+            }
+        }
+
+        let storage_json = storage.join("storage.json");
+        if storage_json.exists() {
+            match std::fs::read_to_string(&storage_json)
+                .map_err(LauncherError::from)
+                .and_then(|content| parse_vscode_storage(&content))
+            {
+                Ok(stored) => paths.extend(stored),
+                Err(e) => warn!("Failed to read VS Code recent list from {}: {}", storage_json.display(), e),
+            }
+        }
+
+        paths
+    }
+
+    /// Recent projects of each JetBrains IDE, newest version of each product first
+    fn jetbrains_paths(&self) -> Vec<(JetBrainsProduct, Vec<PathBuf>)> {
+        let Some(config) = &self.jetbrains_config else {
+            return Vec::new();
+        };
+        let Ok(dirs) = std::fs::read_dir(config) else {
+            return Vec::new();
+        };
+
+        let mut dirs: Vec<PathBuf> = dirs.flatten().map(|entry| entry.path()).collect();
+        dirs.sort_by(|a, b| b.cmp(a));
+
+        let mut by_product: Vec<(JetBrainsProduct, Vec<PathBuf>)> = Vec::new();
+        for dir in dirs {
+            let Some(product) = dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(JetBrainsProduct::from_config_dir)
+            else {
+                continue;
+            };
+
+            let file = dir.join("options").join("recentProjects.xml");
+            let paths = match std::fs::read_to_string(&file) {
+                Ok(xml) => parse_jetbrains_recent(&xml, self.home.as_deref()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    warn!("Failed to read {} recent projects from {}: {}", product.name, file.display(), e);
+                    continue;
+                }
+            };
+
+            match by_product.iter_mut().find(|(known, _)| *known == product) {
+                Some((_, known_paths)) => known_paths.extend(paths),
+                None => by_product.push((product, paths)),
+            }
+        }
+
+        by_product
+    }
+
+    /// Visual Studio's recent solutions and folders, across all installed instances
+    fn visual_studio_paths(&self) -> Vec<PathBuf> {
+        let Some(data) = &self.visual_studio_data else {
+            return Vec::new();
+        };
+        let Ok(instances) = std::fs::read_dir(data) else {
+            return Vec::new();
+        };
+
+        let mut instances: Vec<PathBuf> = instances.flatten().map(|entry| entry.path()).collect();
+        instances.sort_by(|a, b| b.cmp(a));
+
+        let mut paths = Vec::new();
+        for instance in instances {
+            let file = instance.join("ApplicationPrivateSettings.xml");
+            if !file.exists() {
+                continue;
+            }
+            match std::fs::read_to_string(&file)
+                .map_err(LauncherError::from)
+                .and_then(|xml| parse_visual_studio_settings(&xml))
+            {
+                Ok(recent) => paths.extend(recent),
+                Err(e) => warn!("Failed to read Visual Studio recent list from {}: {}", file.display(), e),
+            }
+        }
+
+        paths
+    }
+
+    /// Looks for each IDE's executable under the install roots
+    pub fn locate_installed(&self, ides: &HashSet<Ide>) -> HashMap<Ide, PathBuf> {
+        ides.iter()
+            .filter_map(|ide| {
+                self.install_roots
+                    .iter()
+                    .flat_map(|root| ide.install_patterns().into_iter().map(move |pattern| (root, pattern)))
+                    .find_map(|(root, pattern)| find_in_tree(root, &pattern))
+                    .map(|path| (*ide, path))
+            })
+            .collect()
+    }
+}
+
+/// Finds a file below `root` following `pattern`, where `*` matches any
+/// folder; names sorting last, usually the newest versions, are tried first
+fn find_in_tree(root: &Path, pattern: &[&str]) -> Option<PathBuf> {
+    let Some((segment, rest)) = pattern.split_first() else {
+        return root.is_file().then(|| root.to_path_buf());
+    };
+
+    if *segment != "*" {
+        return find_in_tree(&root.join(segment), rest);
+    }
+
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(root)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort_by(|a, b| b.cmp(a));
+    dirs.iter().find_map(|dir| find_in_tree(dir, rest))
+}
+
+/// Loaded projects and the IDEs found at their default install locations
+#[derive(Debug, Default)]
+struct ProjectCatalog {
+    projects: Vec<RecentProject>,
+    installed: HashMap<Ide, PathBuf>,
+    loaded_at: Option<Instant>,
+}
+
+/// Recent projects search provider
+pub struct RecentProjectsProvider {
+    /// Projects and install locations, reloaded when stale
+    catalog: Arc<RwLock<ProjectCatalog>>,
+    /// Applications found by the app scan, used to find the IDEs
+    app_cache: AppCache,
+    /// Where the histories are read from
+    sources: ProjectSources,
+    /// Whether a reload is running
+    refreshing: Arc<std::sync::atomic::AtomicBool>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl RecentProjectsProvider {
+    /// Creates a new recent projects provider resolving IDEs through `app_cache`
+    pub fn new(app_cache: AppCache) -> Result<Self> {
+        info!("Initializing RecentProjectsProvider");
+
+        Ok(Self {
+            catalog: Arc::new(RwLock::new(ProjectCatalog::default())),
+            app_cache,
+            sources: ProjectSources::locate(),
+            refreshing: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            enabled: true,
+        })
+    }
+
+    /// Reads the histories from `sources` instead of the default locations
+    pub fn with_sources(mut self, sources: ProjectSources) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Reads the histories and install locations off the async runtime
+    async fn load(sources: ProjectSources) -> Result<ProjectCatalog> {
+        tokio::task::spawn_blocking(move || {
+            let projects = sources.load_projects();
+            let ides: HashSet<Ide> = projects.iter().map(|project| project.ide).collect();
+            let installed = sources.locate_installed(&ides);
+            ProjectCatalog {
+                projects,
+                installed,
+                loaded_at: Some(Instant::now()),
+            }
+        })
+        .await
+        .map_err(|e| LauncherError::ProviderError(format!("Failed to spawn recent projects load task: {}", e)))
+    }
+
+    /// Reloads the catalog in the background once it is older than the refresh interval
+    async fn refresh_if_stale(&self) {
+        let stale = self
+            .catalog
+            .read()
+            .await
+            .loaded_at
+            .is_none_or(|loaded_at| loaded_at.elapsed() >= REFRESH_INTERVAL);
+        if !stale || self.refreshing.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+
+        let catalog = Arc::clone(&self.catalog);
+        let refreshing = Arc::clone(&self.refreshing);
+        let sources = self.sources.clone();
+        tokio::spawn(async move {
+            match Self::load(sources).await {
+                Ok(loaded) => *catalog.write().await = loaded,
+                Err(e) => warn!("Failed to refresh recent projects: {}", e),
+            }
+            refreshing.store(false, std::sync::atomic::Ordering::SeqCst);
+        });
+    }
+
+    /// Finds the executable of `ide`, preferring what the app scan found
+    fn resolve_executable(ide: Ide, apps: &[Application], installed: &HashMap<Ide, PathBuf>) -> Option<PathBuf> {
+        ide.find_in_apps(apps).or_else(|| installed.get(&ide).cloned())
+    }
+
+    /// Scores a project name against the query
+    ///
+    /// Both arguments are expected to be normalized with `normalize_for_search`.
+    fn match_score(query: &str, name: &str) -> Option<f64> {
+        if name == query {
+            Some(100.0)
+        } else if name.starts_with(query) {
+            Some(90.0)
+        } else if name.contains(query) {
+            Some(70.0)
+        } else {
+            let mut name_chars = name.chars();
+            query
+                .chars()
+                .all(|query_char| name_chars.any(|c| c == query_char))
+                .then_some(40.0)
+        }
+    }
+
+    /// Creates a search result opening `project` with `executable`
+    fn create_search_result(project: &RecentProject, executable: &Path, score: f64) -> SearchResult {
+        let path = project.path.to_string_lossy().to_string();
+        let mut metadata = HashMap::new();
+        metadata.insert("ide".to_string(), serde_json::json!(project.ide.id()));
+        metadata.insert("path".to_string(), serde_json::json!(path));
+
+        SearchResult {
+            id: project.id(),
+            title: project.name.clone(),
+            subtitle: format!("{} · {}", project.ide.name(), path),
+            icon: Some("code".to_string()),
+            result_type: ResultType::Project,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: executable.to_string_lossy().to_string(),
+                args: project.ide.launch_args(&project.path),
+            },
+            normalized_title: Some(project.normalized_name.clone()),
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for RecentProjectsProvider {
+    fn name(&self) -> &str {
+        "RecentProjects"
+    }
+
+    fn priority(&self) -> u8 {
+        60 // Above SSH hosts, below applications
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Project])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        if query.query.trim().len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        self.refresh_if_stale().await;
+
+        let query_normalized = normalize_for_search(query.query.trim());
+        let catalog = self.catalog.read().await;
+        let apps = self.app_cache.read().await;
+        let mut executables: HashMap<Ide, Option<PathBuf>> = HashMap::new();
+
+        let mut results: Vec<(&RecentProject, PathBuf, f64)> = catalog
+            .projects
+            .iter()
+            .filter_map(|project| {
+                let score = Self::match_score(&query_normalized, &project.normalized_name)?;
+                let executable = executables
+                    .entry(project.ide)
+                    .or_insert_with(|| Self::resolve_executable(project.ide, &apps, &catalog.installed))
+                    .clone()?;
+                Some((project, executable, score))
+            })
+            .collect();
+
+        // Sort by score (highest first), the most recently opened first among equals
+        results.sort_by(|a, b| {
+            b.2.partial_cmp(&a.2)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.rank.cmp(&b.0.rank))
+        });
+        results.truncate(MAX_RESULTS);
+
+        Ok(results
+            .into_iter()
+            .map(|(project, executable, score)| Self::create_search_result(project, &executable, score))
+            .collect())
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::Project {
+            return Err(LauncherError::ExecutionError(
+                "Not a recent project result".to_string(),
+            ));
+        }
+
+        let ResultAction::ExecuteCommand { command, args } = &result.action else {
+            return Err(LauncherError::ExecutionError(
+                "Invalid recent project action".to_string(),
+            ));
+        };
+
+        info!("Opening recent project: {}", result.title);
+        let mut launch = std::process::Command::new(command);
+        launch.args(args);
+        process::start("open_project", launch, Wait::Detached).await
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("Initializing RecentProjectsProvider");
+
+        let catalog = Self::load(self.sources.clone()).await?;
+        info!(
+            "RecentProjectsProvider initialized with {} projects from {} installed IDEs",
+            catalog.projects.len(),
+            catalog.installed.len()
+        );
+        *self.catalog.write().await = catalog;
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        info!("Shutting down RecentProjectsProvider");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    /// Creates a project folder and returns it
+    fn project_dir(root: &Path, name: &str) -> PathBuf {
+        let path = root.join("projects").join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    /// The `file://` URI VS Code stores for `path`
+    fn file_uri(path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let path = if path.starts_with('/') { path } else { format!("/{}", path) };
+        format!("file://{}", path.replacen(':', "%3A", 1).replace(' ', "%20"))
+    }
+
+    fn xml_path(path: &Path) -> String {
+        path.to_string_lossy().replace('&', "&amp;")
+    }
+
+    fn pycharm() -> Ide {
+        Ide::JetBrains(JetBrainsProduct::from_config_dir("PyCharm2024.1").unwrap())
+    }
+
+    #[test]
+    fn test_file_uri_to_path() {
+        assert_eq!(
+            file_uri_to_path("file:///c%3A/Users/me/dev%20app"),
+            Some(PathBuf::from("c:\\Users\\me\\dev app"))
+        );
+        assert_eq!(file_uri_to_path("file:///home/me/app"), Some(PathBuf::from("/home/me/app")));
+        assert_eq!(file_uri_to_path("file://server/share/app"), Some(PathBuf::from("\\\\server\\share\\app")));
+        assert_eq!(file_uri_to_path("vscode-remote://ssh-remote%2Bbox/srv/app"), None);
+    }
+
+    #[test]
+    fn test_jetbrains_product_from_config_dir() {
+        assert_eq!(JetBrainsProduct::from_config_dir("PyCharm2024.1").unwrap().name, "PyCharm");
+        assert_eq!(JetBrainsProduct::from_config_dir("PyCharmCE2023.3").unwrap().name, "PyCharm Community");
+        assert_eq!(JetBrainsProduct::from_config_dir("IntelliJIdea2024.2").unwrap().executable, "idea64.exe");
+        assert_eq!(JetBrainsProduct::from_config_dir("consentOptions"), None);
+        assert_eq!(JetBrainsProduct::from_config_dir("PyCharm"), None);
+    }
+
+    #[test]
+    fn test_launch_args_per_ide() {
+        let project = Path::new("C:\\dev\\app");
+        assert_eq!(Ide::VsCode.launch_args(project), vec!["--new-window", "C:\\dev\\app"]);
+        assert_eq!(pycharm().launch_args(project), vec!["C:\\dev\\app"]);
+        assert_eq!(Ide::VisualStudio.launch_args(Path::new("C:\\dev\\app\\App.sln")), vec!["C:\\dev\\app\\App.sln"]);
+    }
+
+    #[test]
+    fn test_parse_vscode_state_database() {
+        let root = temp_dir("recent_projects_vscode_state");
+        let folder = project_dir(&root, "my app");
+        let workspace = root.join("projects").join("team.code-workspace");
+        std::fs::write(&workspace, "{}").unwrap();
+
+        let list = serde_json::json!({
+            "entries": [
+                { "folderUri": file_uri(&folder) },
+                { "workspace": { "id": "a1b2", "configPath": file_uri(&workspace) }, "label": "team" },
+                { "fileUri": file_uri(&root.join("notes.md")) },
+                { "folderUri": "vscode-remote://ssh-remote%2Bbox/srv/app" }
+            ]
+        });
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB)").unwrap();
+        conn.execute(
+            "INSERT INTO ItemTable (key, value) VALUES (?1, ?2)",
+            rusqlite::params![VSCODE_RECENT_KEY, list.to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(read_vscode_state(&conn).unwrap(), vec![folder, workspace]);
+
+        let empty = Connection::open_in_memory().unwrap();
+        empty.execute_batch("CREATE TABLE ItemTable (key TEXT UNIQUE ON CONFLICT REPLACE, value BLOB)").unwrap();
+        assert!(read_vscode_state(&empty).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_vscode_storage_json() {
+        let storage = r#"{
+            "openedPathsList": {
+                "workspaces3": [
+                    "file:///c%3A/dev/api",
+                    { "id": "f00", "configURIPath": "file:///c%3A/dev/all.code-workspace" }
+                ],
+                "files2": ["file:///c%3A/dev/readme.md"]
+            },
+            "windowsState": {}
+        }"#;
+
+        assert_eq!(
+            parse_vscode_storage(storage).unwrap(),
+            vec![PathBuf::from("c:\\dev\\api"), PathBuf::from("c:\\dev\\all.code-workspace")]
+        );
+        assert!(parse_vscode_storage("{}").unwrap().is_empty());
+        assert!(parse_vscode_storage("{ not json").is_err());
+    }
+
+    #[test]
+    fn test_parse_jetbrains_recent_projects() {
+        let home = Path::new("C:\\Users\\me");
+        let xml = r#"<application>
+  <component name="RecentProjectsManager">
+    <option name="additionalInfo">
+      <map>
+        <entry key="$USER_HOME$/PycharmProjects/old">
+          <value>
+            <RecentProjectMetaInfo frameTitle="old">
+              <option name="activationTimestamp" value="1600000000000" />
+              <option name="binFolder" value="$APPLICATION_HOME_DIR$/bin" />
+            </RecentProjectMetaInfo>
+          </value>
+        </entry>
+        <entry key="C:/dev/R&amp;D">
+          <value>
+            <RecentProjectMetaInfo frameTitle="R&amp;D">
+              <option name="activationTimestamp" value="1700000000000" />
+            </RecentProjectMetaInfo>
+          </value>
+        </entry>
+        <entry key="$APPLICATION_CONFIG_DIR$/scratches">
+          <value><RecentProjectMetaInfo /></value>
+        </entry>
+      </map>
+    </option>
+    <option name="lastProjectLocation" value="$USER_HOME$/PycharmProjects" />
+  </component>
+</application>"#;
+
+        assert_eq!(
+            parse_jetbrains_recent(xml, Some(home)),
+            vec![PathBuf::from("C:/dev/R&D"), PathBuf::from("C:\\Users\\me/PycharmProjects/old")]
+        );
+
+        let legacy = r#"<application>
+  <component name="RecentProjectsManager">
+    <option name="recentPaths">
+      <list>
+        <option value="$USER_HOME$/IdeaProjects/newest" />
+        <option value="C:/dev/older" />
+      </list>
+    </option>
+    <option name="pid" value="" />
+  </component>
+</application>"#;
+
+        assert_eq!(
+            parse_jetbrains_recent(legacy, Some(home)),
+            vec![PathBuf::from("C:\\Users\\me/IdeaProjects/newest"), PathBuf::from("C:/dev/older")]
+        );
+        assert_eq!(parse_jetbrains_recent(legacy, None), vec![PathBuf::from("C:/dev/older")]);
+        assert!(parse_jetbrains_recent("<application><broken", Some(home)).is_empty());
+    }
+
+    #[test]
+    fn test_parse_visual_studio_settings() {
+        let xml = r#"<content>
+  <indexed>
+    <collection name="CodeContainers.Offline">
+      <value name="value">[{&quot;Key&quot;:&quot;C:\\src\\Old\\Old.sln&quot;,&quot;Value&quot;:{&quot;LocalProperties&quot;:{&quot;FullPath&quot;:&quot;C:\\src\\Old\\Old.sln&quot;,&quot;Type&quot;:0},&quot;LastAccessed&quot;:&quot;2023-01-05T09:00:00.0000000+00:00&quot;}},{&quot;Key&quot;:&quot;C:\\src\\Web&quot;,&quot;Value&quot;:{&quot;LocalProperties&quot;:{&quot;FullPath&quot;:&quot;C:\\src\\Web&quot;,&quot;Type&quot;:1},&quot;LastAccessed&quot;:&quot;2024-03-10T16:30:00.0000000+01:00&quot;}}]</value>
+    </collection>
+    <collection name="Other">
+      <value name="value">1</value>
+    </collection>
+  </indexed>
+</content>"#;
+
+        assert_eq!(
+            parse_visual_studio_settings(xml).unwrap(),
+            vec![PathBuf::from("C:\\src\\Web"), PathBuf::from("C:\\src\\Old\\Old.sln")]
+        );
+        assert!(parse_visual_studio_settings("<content />").unwrap().is_empty());
+
+        let changed = r#"<collection name="CodeContainers.Offline"><value name="value">{"schema": 2}</value></collection>"#;
+        assert!(parse_visual_studio_settings(changed).is_err());
+    }
+
+    /// Writes one source of each kind under `root`, the VS Code one broken
+    fn write_sources(root: &Path) -> ProjectSources {
+        let api = project_dir(root, "api");
+        let web = project_dir(root, "web");
+        let solution = project_dir(root, "desktop").join("Desktop.sln");
+        std::fs::write(&solution, "").unwrap();
+
+        let vscode = root.join("Code").join("User").join("globalStorage");
+        std::fs::create_dir_all(&vscode).unwrap();
+        std::fs::write(vscode.join("storage.json"), "{ \"openedPathsList\": ").unwrap();
+
+        let pycharm = root.join("JetBrains").join("PyCharm2024.1").join("options");
+        std::fs::create_dir_all(&pycharm).unwrap();
+        std::fs::write(
+            pycharm.join("recentProjects.xml"),
+            format!(
+                r#"<application><component name="RecentProjectsManager"><option name="recentPaths"><list>
+                <option value="{}" /><option value="{}" /><option value="{}" />
+                </list></option></component></application>"#,
+                xml_path(&api),
+                xml_path(&root.join("projects").join("deleted")),
+                xml_path(&api),
+            ),
+        )
+        .unwrap();
+
+        let instance = root.join("VisualStudio").join("17.0_1a2b3c4d");
+        std::fs::create_dir_all(&instance).unwrap();
+        let containers = serde_json::json!([
+            { "Key": solution.to_string_lossy(), "Value": { "LastAccessed": "2024-03-10T16:30:00+00:00" } },
+            { "Key": web.to_string_lossy(), "Value": { "LastAccessed": "2024-03-09T16:30:00+00:00" } }
+        ]);
+        std::fs::write(
+            instance.join("ApplicationPrivateSettings.xml"),
+            format!(
+                r#"<content><indexed><collection name="CodeContainers.Offline"><value name="value">{}</value></collection></indexed></content>"#,
+                containers.to_string().replace('&', "&amp;").replace('"', "&quot;")
+            ),
+        )
+        .unwrap();
+
+        ProjectSources {
+            vscode_storage: Some(vscode),
+            jetbrains_config: Some(root.join("JetBrains")),
+            visual_studio_data: Some(root.join("VisualStudio")),
+            home: None,
+            install_roots: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_load_skips_failing_sources_and_missing_paths() {
+        let root = temp_dir("recent_projects_load");
+        let sources = write_sources(&root);
+
+        let projects = sources.load_projects();
+        let summary: Vec<(&str, Ide, usize)> = projects
+            .iter()
+            .map(|project| (project.name.as_str(), project.ide, project.rank))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("api", pycharm(), 0),
+                ("Desktop", Ide::VisualStudio, 0),
+                ("web", Ide::VisualStudio, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_locate_installed_under_install_roots() {
+        let root = temp_dir("recent_projects_installed");
+        let rider = root.join("JetBrains").join("JetBrains Rider 2024.1").join("bin");
+        std::fs::create_dir_all(&rider).unwrap();
+        std::fs::write(rider.join("rider64.exe"), "").unwrap();
+
+        let sources = ProjectSources {
+            install_roots: vec![root.clone()],
+            ..Default::default()
+        };
+        let rider_ide = Ide::JetBrains(JetBrainsProduct::from_config_dir("Rider2024.1").unwrap());
+        let installed = sources.locate_installed(&HashSet::from([rider_ide, Ide::VsCode]));
+
+        assert_eq!(installed.get(&rider_ide), Some(&rider.join("rider64.exe")));
+        assert_eq!(installed.get(&Ide::VsCode), None);
+    }
+
+    #[tokio::test]
+    async fn test_projects_hidden_until_ide_resolved() {
+        let root = temp_dir("recent_projects_search");
+        let app_cache: AppCache = Arc::new(RwLock::new(Vec::new()));
+        let mut provider = RecentProjectsProvider::new(Arc::clone(&app_cache))
+            .unwrap()
+            .with_sources(write_sources(&root));
+        provider.initialize().await.unwrap();
+
+        // Neither PyCharm nor Visual Studio is installed
+        assert!(provider.search(&QueryContext::from("api")).await.unwrap().is_empty());
+        assert!(provider.search(&QueryContext::from("desktop")).await.unwrap().is_empty());
+
+        let pycharm_exe = root.join("PyCharm 2024.1").join("bin").join("pycharm64.exe");
+        app_cache
+            .write()
+            .await
+            .push(Application::new("PyCharm 2024.1".to_string(), pycharm_exe.clone(), None, true));
+
+        let results = provider.search(&QueryContext::from("api")).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].result_type, ResultType::Project);
+        assert_eq!(results[0].subtitle, format!("PyCharm · {}", root.join("projects").join("api").display()));
+        let ResultAction::ExecuteCommand { command, args } = &results[0].action else {
+            panic!("expected a command, got {:?}", results[0].action);
+        };
+        assert_eq!(command, &pycharm_exe.to_string_lossy());
+        assert_eq!(args, &vec![root.join("projects").join("api").to_string_lossy().to_string()]);
+        assert!(provider.search(&QueryContext::from("desktop")).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,626 @@
+/// Saved search provider for queries the user runs again and again
+///
+/// A saved search stores a query, e.g. `ext:pdf invoices sort:new`, under a
+/// name, along with the sections it was run with. Typing the name, or
+/// `saved` to list them all, offers it as a result. Executing the result runs
+/// the query through the engine again and puts it in the search box, so the
+/// launcher shows its fresh results instead of opening anything.
+
+use crate::error::{LauncherError, Result};
+use crate::search::engine::SearchEngine;
+use crate::search::providers::help::HELP_QUERIES;
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature, SyntaxRegistry};
+use crate::settings::QueryMacro;
+use crate::types::{ResultAction, ResultType, SearchResult, SearchSection, KEEP_WINDOW_OPEN_KEY};
+use crate::utils::normalize_for_search;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use tokio::sync::RwLock;
+use tracing::{debug, error, info};
+
+/// Keyword listing every saved search
+pub const SAVED_KEYWORD: &str = "saved";
+
+/// Prefix for saved search result ids
+pub const SAVED_SEARCH_ID_PREFIX: &str = "saved_search:";
+
+/// Maximum name length in characters
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Score of a saved search whose name was typed in full
+const EXACT_SCORE: f64 = 100.0;
+
+/// Score of a saved search whose name starts with the query
+const PREFIX_SCORE: f64 = 70.0;
+
+/// A query stored under a name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    /// Sections the query was run with; empty for a plain search
+    #[serde(default)]
+    pub sections: Vec<SearchSection>,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Words a saved search name must not start with, since typing them
+/// already means something: query syntax, help, macros and `saved`
+pub fn reserved_keywords(registry: &SyntaxRegistry, macros: &[QueryMacro]) -> Vec<String> {
+    let mut keywords = registry.keywords();
+    keywords.extend(HELP_QUERIES.iter().map(|query| query.to_string()));
+    keywords.push(SAVED_KEYWORD.to_string());
+    keywords.extend(macros.iter().map(|query_macro| query_macro.keyword.trim().to_lowercase()));
+    keywords
+}
+
+/// Collapses whitespace in `name` and checks it can be typed to find the search
+fn validate_name(name: &str, reserved: &[String]) -> Result<String> {
+    let name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if name.is_empty() {
+        return Err(LauncherError::ConfigError("A saved search needs a name".to_string()));
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(LauncherError::ConfigError(format!(
+            "Saved search names can have at most {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+
+    // Typed first, a symbol makes the name a bang, a slash command or plain text
+    if !name.starts_with(char::is_alphanumeric) {
+        return Err(LauncherError::ConfigError(
+            "Saved search names must start with a letter or digit".to_string(),
+        ));
+    }
+
+    let first_word = name.split(' ').next().unwrap_or_default().to_lowercase();
+    let first_word = first_word.trim_end_matches(':');
+    if reserved.iter().any(|keyword| keyword == first_word) {
+        return Err(LauncherError::ConfigError(format!(
+            "'{}' is already a search keyword; pick another name",
+            first_word
+        )));
+    }
+    Ok(name)
+}
+
+/// Persistent store for saved searches
+pub struct SavedSearchStore {
+    /// Path to the storage file
+    storage_path: PathBuf,
+    /// Saved searches, most recent first
+    searches: RwLock<Vec<SavedSearch>>,
+}
+
+impl SavedSearchStore {
+    /// Creates the store and loads existing saved searches from disk
+    pub fn new() -> Result<Self> {
+        Self::with_path(Self::get_storage_path()?)
+    }
+
+    /// Creates a store backed by a specific file
+    pub fn with_path(storage_path: PathBuf) -> Result<Self> {
+        if let Some(parent) = storage_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let searches = match Self::read_searches(&storage_path) {
+            Ok(searches) => searches,
+            Err(e) => {
+                error!("Failed to load saved searches from {:?}: {}", storage_path, e);
+                Vec::new()
+            }
+        };
+
+        info!("Loaded {} saved searches", searches.len());
+
+        Ok(Self {
+            storage_path,
+            searches: RwLock::new(searches),
+        })
+    }
+
+    /// Gets the storage file path
+    fn get_storage_path() -> Result<PathBuf> {
+        #[cfg(test)]
+        {
+            // Use temp directory for tests
+            let mut path = std::env::temp_dir();
+            path.push("BetterFinder");
+            path.push("saved_searches_test.json");
+            return Ok(path);
+        }
+
+        #[cfg(not(test))]
+        {
+            let app_data = std::env::var("APPDATA")
+                .map_err(|_| LauncherError::ConfigError("APPDATA not found".to_string()))?;
+
+            let mut path = PathBuf::from(app_data);
+            path.push("BetterFinder");
+            path.push("saved_searches.json");
+
+            Ok(path)
+        }
+    }
+
+    fn read_searches(path: &Path) -> Result<Vec<SavedSearch>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Writes the saved searches to disk
+    async fn persist(&self, searches: &[SavedSearch]) -> Result<()> {
+        let path = self.storage_path.clone();
+        let content = serde_json::to_string_pretty(searches)?;
+
+        tokio::task::spawn_blocking(move || std::fs::write(&path, content))
+            .await
+            .map_err(|e| {
+                LauncherError::ExecutionError(format!("Failed to spawn save task: {}", e))
+            })??;
+
+        Ok(())
+    }
+
+    /// Saves `query` under `name`, replacing a saved search of the same name
+    ///
+    /// Names starting with one of the `reserved` keywords or a symbol are
+    /// rejected. The query is kept as typed, plain-text marker included, so
+    /// running it again escapes the same syntax.
+    pub async fn save(
+        &self,
+        name: &str,
+        query: &str,
+        sections: Vec<SearchSection>,
+        reserved: &[String],
+    ) -> Result<SavedSearch> {
+        let name = validate_name(name, reserved)?;
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(LauncherError::ConfigError(format!("Saved search '{}' needs a query", name)));
+        }
+
+        let search = SavedSearch {
+            name,
+            query: query.to_string(),
+            sections,
+            saved_at: Utc::now(),
+        };
+
+        let mut searches = self.searches.write().await;
+        searches.retain(|existing| !existing.name.eq_ignore_ascii_case(&search.name));
+        searches.insert(0, search.clone());
+        self.persist(&searches).await?;
+
+        info!("Saved search '{}' for '{}'", search.name, search.query);
+        Ok(search)
+    }
+
+    /// Deletes a saved search by name, returning false if there was none
+    pub async fn delete(&self, name: &str) -> Result<bool> {
+        let mut searches = self.searches.write().await;
+        let before = searches.len();
+        searches.retain(|search| !search.name.eq_ignore_ascii_case(name.trim()));
+
+        if searches.len() == before {
+            return Ok(false);
+        }
+
+        self.persist(&searches).await?;
+        info!("Deleted saved search '{}'", name.trim());
+        Ok(true)
+    }
+
+    /// Returns all saved searches, most recent first
+    pub async fn list(&self) -> Vec<SavedSearch> {
+        self.searches.read().await.clone()
+    }
+
+    /// Finds a saved search by name
+    pub async fn get(&self, name: &str) -> Option<SavedSearch> {
+        self.searches
+            .read()
+            .await
+            .iter()
+            .find(|search| search.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+}
+
+/// Saved search provider
+pub struct SavedSearchProvider {
+    /// Shared store (also used by the Tauri commands)
+    store: Arc<SavedSearchStore>,
+    /// Engine the saved queries run through again
+    engine: Option<Weak<SearchEngine>>,
+    /// Called with the saved search once it ran, to show it in the search box
+    run_handler: Option<Box<dyn Fn(&SavedSearch) + Send + Sync>>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl SavedSearchProvider {
+    /// Creates a new SavedSearchProvider backed by a shared store
+    pub fn new(store: Arc<SavedSearchStore>) -> Result<Self> {
+        info!("Initializing SavedSearchProvider");
+        Ok(Self {
+            store,
+            engine: None,
+            run_handler: None,
+            enabled: true,
+        })
+    }
+
+    /// Runs saved queries through `engine`
+    ///
+    /// The engine is held weakly, as it owns this provider.
+    pub fn set_engine(&mut self, engine: &Arc<SearchEngine>) {
+        self.engine = Some(Arc::downgrade(engine));
+    }
+
+    /// Sets what shows a saved search in the launcher once it ran
+    pub fn set_run_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&SavedSearch) + Send + Sync + 'static,
+    {
+        self.run_handler = Some(Box::new(handler));
+    }
+
+    /// Converts a saved search to a SearchResult
+    fn create_search_result(search: &SavedSearch, score: f64) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("query".to_string(), serde_json::json!(search.query));
+        metadata.insert(KEEP_WINDOW_OPEN_KEY.to_string(), serde_json::json!(true));
+
+        let subtitle = if search.sections.is_empty() {
+            search.query.clone()
+        } else {
+            format!("{} · {} sections", search.query, search.sections.len())
+        };
+
+        SearchResult {
+            id: format!("{}{}", SAVED_SEARCH_ID_PREFIX, search.name),
+            title: search.name.clone(),
+            subtitle,
+            icon: None,
+            result_type: ResultType::SavedSearch,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: format!("{}{}", SAVED_SEARCH_ID_PREFIX, search.name),
+                args: vec![],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Runs the query through the engine as it was saved, with its sections
+    /// when it has any, returning how many results it found
+    async fn rerun(engine: &SearchEngine, search: &SavedSearch) -> usize {
+        if search.sections.is_empty() {
+            engine.search(&search.query).await.len()
+        } else {
+            engine
+                .search_sections(&search.query, &search.sections)
+                .await
+                .values()
+                .map(Vec::len)
+                .sum()
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for SavedSearchProvider {
+    fn name(&self) -> &str {
+        "SavedSearches"
+    }
+
+    fn priority(&self) -> u8 {
+        90
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::SavedSearch])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let folded = query.folded_query.trim();
+        if folded.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let searches = self.store.searches.read().await;
+
+        // `saved` lists everything, `saved inv` the names starting with "inv"
+        let listing = folded
+            .strip_prefix(SAVED_KEYWORD)
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+            .map(str::trim);
+        if let Some(filter) = listing {
+            return Ok(searches
+                .iter()
+                .filter(|search| normalize_for_search(&search.name).starts_with(filter))
+                .enumerate()
+                .map(|(index, search)| Self::create_search_result(search, EXACT_SCORE - index as f64))
+                .collect());
+        }
+
+        let mut results: Vec<SearchResult> = searches
+            .iter()
+            .filter_map(|search| {
+                let name = normalize_for_search(&search.name);
+                let score = if name == folded {
+                    EXACT_SCORE
+                } else if name.starts_with(folded) {
+                    PREFIX_SCORE
+                } else {
+                    return None;
+                };
+                Some(Self::create_search_result(search, score))
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        debug!("Found {} saved searches for '{}'", results.len(), folded);
+        Ok(results)
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let name = result
+            .id
+            .strip_prefix(SAVED_SEARCH_ID_PREFIX)
+            .filter(|_| result.result_type == ResultType::SavedSearch)
+            .ok_or_else(|| LauncherError::ExecutionError("Not a saved search".to_string()))?;
+
+        let search = self
+            .store
+            .get(name)
+            .await
+            .ok_or_else(|| LauncherError::NotFound(format!("Saved search no longer exists: {}", name)))?;
+
+        let engine = self
+            .engine
+            .as_ref()
+            .and_then(Weak::upgrade)
+            .ok_or_else(|| LauncherError::ExecutionError("Saved searches can't be run".to_string()))?;
+        let found = Self::rerun(&engine, &search).await;
+        info!("Saved search '{}' found {} results", search.name, found);
+
+        if let Some(handler) = &self.run_handler {
+            handler(&search);
+        }
+        Ok(())
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "saved",
+            title: "Saved searches",
+            description: "saved lists your saved searches; type a name to run one again",
+            example: "saved ",
+            token: None,
+            values: &[],
+        }]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    fn temp_store(name: &str) -> (Arc<SavedSearchStore>, PathBuf) {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("saved_searches_{}.json", name));
+        let _ = std::fs::remove_file(&path);
+
+        (Arc::new(SavedSearchStore::with_path(path.clone()).unwrap()), path)
+    }
+
+    fn files_section() -> SearchSection {
+        SearchSection {
+            types: vec![ResultType::File],
+            limit: 10,
+        }
+    }
+
+    /// Provider that records the queries the engine passes it
+    struct SpyProvider {
+        name: &'static str,
+        result_types: &'static [ResultType],
+        queries: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl SearchProvider for SpyProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        fn result_types(&self) -> Option<&'static [ResultType]> {
+            Some(self.result_types)
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            self.queries.lock().unwrap().push(query.query.clone());
+            Ok(Vec::new())
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Err(LauncherError::ExecutionError("Spy results can't run".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_round_trip() {
+        let (store, path) = temp_store("round_trip");
+
+        store
+            .save("  accounting   pdfs ", "ext:pdf invoices sort:new", vec![files_section()], &[])
+            .await
+            .unwrap();
+        store.save("notes", "ext:md notes", Vec::new(), &[]).await.unwrap();
+        // Saving under a known name replaces the earlier search
+        store.save("Notes", "ext:txt notes", Vec::new(), &[]).await.unwrap();
+
+        let reloaded = SavedSearchStore::with_path(path.clone()).unwrap();
+        let searches = reloaded.list().await;
+        assert_eq!(searches.len(), 2);
+        assert_eq!(searches[0].name, "Notes");
+        assert_eq!(searches[0].query, "ext:txt notes");
+        assert_eq!(searches[1].name, "accounting pdfs");
+        assert_eq!(searches[1].sections, vec![files_section()]);
+
+        assert!(reloaded.delete("NOTES").await.unwrap());
+        assert!(!reloaded.delete("notes").await.unwrap());
+        assert!(reloaded.save("empty", "   ", Vec::new(), &[]).await.is_err());
+
+        let reloaded = SavedSearchStore::with_path(path.clone()).unwrap();
+        assert_eq!(reloaded.list().await.len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_keyword_collisions_rejected() {
+        let (store, path) = temp_store("collisions");
+        let registry = SyntaxRegistry::default();
+        registry.set(vec![
+            SyntaxFeature {
+                id: "sort",
+                title: "Sort files",
+                description: "Orders file results",
+                example: "sort:new report",
+                token: Some("sort:"),
+                values: &["new", "old"],
+            },
+            SyntaxFeature {
+                id: "ssh",
+                title: "SSH hosts",
+                description: "Connects to a host",
+                example: "ssh ",
+                token: None,
+                values: &[],
+            },
+        ]);
+        let macros = vec![QueryMacro {
+            keyword: "Proj".to_string(),
+            template_action: ResultAction::OpenFile { path: "C:\\Projects\\{arg}".to_string() },
+        }];
+        let reserved = reserved_keywords(&registry, &macros);
+
+        for name in ["sort", "SSH servers", "saved", "help", "?", "proj", "sort: mine", "'sums", "`sums", "/sums"] {
+            let error = store.save(name, "report", Vec::new(), &reserved).await.unwrap_err();
+            assert!(matches!(error, LauncherError::ConfigError(_)), "{}", name);
+        }
+        assert!(store.save("", "report", Vec::new(), &reserved).await.is_err());
+        assert!(store.save(&"x".repeat(65), "report", Vec::new(), &reserved).await.is_err());
+
+        assert!(store.save("sorted reports", "report sort:new", Vec::new(), &reserved).await.is_ok());
+        // The plain-text marker belongs to the query
+        let sums = store.save("sums", " '2+2 ", Vec::new(), &reserved).await.unwrap();
+        assert_eq!(sums.query, "'2+2");
+        assert_eq!(store.list().await.len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_names_and_lists_on_keyword() {
+        let (store, path) = temp_store("search");
+        store.save("invoices", "ext:pdf invoices", Vec::new(), &[]).await.unwrap();
+        store.save("inbox", "ext:eml", Vec::new(), &[]).await.unwrap();
+        let provider = SavedSearchProvider::new(Arc::clone(&store)).unwrap();
+
+        let results = provider.search(&"invoices".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "saved_search:invoices");
+        assert_eq!(results[0].score, EXACT_SCORE);
+        assert_eq!(results[0].result_type, ResultType::SavedSearch);
+
+        assert_eq!(provider.search(&"in".into()).await.unwrap().len(), 2);
+        assert_eq!(provider.search(&"saved".into()).await.unwrap().len(), 2);
+        assert_eq!(provider.search(&"saved inv".into()).await.unwrap().len(), 1);
+        assert!(provider.search(&"savedx".into()).await.unwrap().is_empty());
+        assert!(provider.search(&"report".into()).await.unwrap().is_empty());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_execute_reruns_query_through_engine() {
+        let (store, path) = temp_store("execute");
+        let engine = Arc::new(SearchEngine::new());
+        let file_queries = Arc::new(Mutex::new(Vec::new()));
+        let app_queries = Arc::new(Mutex::new(Vec::new()));
+        engine
+            .register_provider(Arc::new(SpyProvider {
+                name: "files",
+                result_types: &[ResultType::File],
+                queries: Arc::clone(&file_queries),
+            }))
+            .await;
+        engine
+            .register_provider(Arc::new(SpyProvider {
+                name: "apps",
+                result_types: &[ResultType::Application],
+                queries: Arc::clone(&app_queries),
+            }))
+            .await;
+
+        let shown = Arc::new(AtomicUsize::new(0));
+        let shown_clone = Arc::clone(&shown);
+        let mut provider = SavedSearchProvider::new(Arc::clone(&store)).unwrap();
+        provider.set_engine(&engine);
+        provider.set_run_handler(move |search: &SavedSearch| {
+            assert_eq!(search.query, "ext:pdf invoices");
+            shown_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Saved with a files-only section, so only the file provider is asked
+        let search = store
+            .save("accounting", "ext:pdf invoices", vec![files_section()], &[])
+            .await
+            .unwrap();
+        let result = SavedSearchProvider::create_search_result(&search, EXACT_SCORE);
+        provider.execute(&result).await.unwrap();
+
+        assert_eq!(*file_queries.lock().unwrap(), vec!["ext:pdf invoices".to_string()]);
+        assert!(app_queries.lock().unwrap().is_empty());
+        assert_eq!(shown.load(Ordering::SeqCst), 1);
+
+        // Other results are left to their own providers
+        let mut foreign = result.clone();
+        foreign.result_type = ResultType::QuickAction;
+        assert!(provider.execute(&foreign).await.is_err());
+
+        store.delete("accounting").await.unwrap();
+        assert!(matches!(provider.execute(&result).await, Err(LauncherError::NotFound(_))));
+        assert_eq!(shown.load(Ordering::SeqCst), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
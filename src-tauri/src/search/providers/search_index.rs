@@ -0,0 +1,578 @@
+/// Persistent connection to the Windows Search index
+///
+/// The Search.CollatorDSO OLE DB provider is apartment-threaded, so every
+/// query is sent over a channel to one STA worker thread that owns the
+/// connection. The connection is opened on first use, reused across queries
+/// and reopened once when a query fails (for example after the WSearch
+/// service restarted). Callers that stop waiting simply drop their reply
+/// receiver and the worker skips the request if it has not started yet.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::file_search::FileSort;
+use std::sync::mpsc;
+use tokio::sync::oneshot;
+use tracing::{debug, warn};
+
+/// Maximum number of rows a single index query returns
+pub const MAX_INDEX_ROWS: usize = 20;
+
+/// A file found in the index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexRow {
+    pub path: String,
+    /// Size in bytes, absent for folders
+    pub size: Option<u64>,
+    /// Last modification time in Unix seconds
+    pub modified: Option<i64>,
+}
+
+/// A filename search against the index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexQuery {
+    pub pattern: String,
+    pub sort: Option<FileSort>,
+}
+
+/// An open connection to the index
+pub trait IndexConnection {
+    fn execute(&mut self, sql: &str) -> Result<Vec<IndexRow>>;
+}
+
+/// Opens connections to the index on the worker thread
+pub trait IndexConnector: Send + 'static {
+    type Connection: IndexConnection;
+
+    fn connect(&self) -> Result<Self::Connection>;
+}
+
+/// The filename query shape, prepared once per sort order
+///
+/// Only the pattern changes between keystrokes, so the statement is split
+/// around it and the escaped pattern is substituted on each query.
+#[derive(Debug, Clone)]
+pub struct FilenameQueryTemplate {
+    sort: Option<FileSort>,
+    prefix: String,
+    suffix: String,
+}
+
+impl FilenameQueryTemplate {
+    /// Prepares the statement for a search scope such as the user profile
+    pub fn new(scope: &str, sort: Option<FileSort>) -> Self {
+        let (files_only, order_by) = match sort {
+            None => ("", ""),
+            Some(FileSort::Newest) => ("", " ORDER BY System.DateModified DESC"),
+            Some(FileSort::Oldest) => ("", " ORDER BY System.DateModified ASC"),
+            Some(FileSort::Largest) => (" AND System.ItemType <> 'Directory'", " ORDER BY System.Size DESC"),
+            Some(FileSort::Smallest) => (" AND System.ItemType <> 'Directory'", " ORDER BY System.Size ASC"),
+        };
+
+        Self {
+            sort,
+            prefix: format!(
+                "SELECT TOP {} System.ItemPathDisplay, System.Size, System.DateModified FROM SystemIndex \
+                 WHERE SCOPE='file:{}' AND System.FileName LIKE '%",
+                MAX_INDEX_ROWS,
+                scope.replace('\'', "''")
+            ),
+            suffix: format!("%'{}{}", files_only, order_by),
+        }
+    }
+
+    /// Fills in the pattern, escaping quotes and LIKE wildcards
+    pub fn render(&self, pattern: &str) -> String {
+        format!("{}{}{}", self.prefix, escape_like(pattern), self.suffix)
+    }
+}
+
+/// Escapes a value for use inside a quoted LIKE pattern
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\'' => escaped.push_str("''"),
+            '%' => escaped.push_str("[%]"),
+            '_' => escaped.push_str("[_]"),
+            '[' => escaped.push_str("[[]"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+struct IndexRequest {
+    query: IndexQuery,
+    reply: oneshot::Sender<Result<Vec<IndexRow>>>,
+}
+
+/// Handle to the worker thread that owns the index connection
+pub struct SearchIndexClient {
+    requests: mpsc::Sender<IndexRequest>,
+}
+
+impl SearchIndexClient {
+    /// Starts the worker thread; the connection itself is opened on the first query
+    pub fn spawn<C: IndexConnector>(connector: C, scope: String) -> Self {
+        let (requests, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            #[cfg(windows)]
+            let _apartment = ado::Apartment::enter();
+
+            run_worker(&connector, &scope, receiver);
+        });
+
+        Self { requests }
+    }
+
+    /// Runs a query on the worker thread
+    ///
+    /// Dropping the returned future abandons the reply; the worker skips the
+    /// request when it reaches it.
+    pub async fn query(&self, query: IndexQuery) -> Result<Vec<IndexRow>> {
+        let (reply, response) = oneshot::channel();
+        self.requests.send(IndexRequest { query, reply }).map_err(|_| {
+            LauncherError::SearchError("Windows Search worker has stopped".to_string())
+        })?;
+
+        response.await.map_err(|_| {
+            LauncherError::SearchError("Windows Search worker dropped the query".to_string())
+        })?
+    }
+}
+
+fn run_worker<C: IndexConnector>(connector: &C, scope: &str, requests: mpsc::Receiver<IndexRequest>) {
+    let mut templates: Vec<FilenameQueryTemplate> = Vec::new();
+    let mut connection = None;
+
+    while let Ok(request) = requests.recv() {
+        if request.reply.is_closed() {
+            debug!("Skipping abandoned Windows Search query '{}'", request.query.pattern);
+            continue;
+        }
+
+        let template = match templates.iter().position(|t| t.sort == request.query.sort) {
+            Some(index) => &templates[index],
+            None => {
+                templates.push(FilenameQueryTemplate::new(scope, request.query.sort));
+                &templates[templates.len() - 1]
+            }
+        };
+
+        let sql = template.render(&request.query.pattern);
+        let rows = execute_with_reconnect(connector, &mut connection, &sql);
+        let _ = request.reply.send(rows);
+    }
+
+    debug!("Windows Search worker stopped");
+}
+
+/// Runs a statement, reopening a failed connection once before giving up
+fn execute_with_reconnect<C: IndexConnector>(
+    connector: &C,
+    connection: &mut Option<C::Connection>,
+    sql: &str,
+) -> Result<Vec<IndexRow>> {
+    if let Some(open) = connection.as_mut() {
+        match open.execute(sql) {
+            Ok(rows) => return Ok(rows),
+            Err(e) => {
+                warn!("Windows Search query failed, reconnecting: {}", e);
+                *connection = None;
+            }
+        }
+    }
+
+    connection.insert(connector.connect()?).execute(sql)
+}
+
+/// State of the WSearch service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceHealth {
+    Running,
+    Stopped,
+    NotInstalled,
+    Unknown,
+}
+
+impl ServiceHealth {
+    /// Describes why the index can't be queried, or `None` when it can
+    pub fn error(self) -> Option<LauncherError> {
+        let reason = match self {
+            ServiceHealth::Running => return None,
+            ServiceHealth::Stopped => "the Windows Search service (WSearch) is not running",
+            ServiceHealth::NotInstalled => "the Windows Search service (WSearch) is not installed",
+            ServiceHealth::Unknown => "the state of the Windows Search service (WSearch) could not be read",
+        };
+        Some(LauncherError::ProviderError(format!("Windows Search unavailable: {}", reason)))
+    }
+}
+
+/// Reads the state of the WSearch service
+#[cfg(windows)]
+pub fn search_service_health() -> ServiceHealth {
+    use windows::core::w;
+    use windows::Win32::Foundation::ERROR_SERVICE_DOES_NOT_EXIST;
+    use windows::Win32::System::Services::{
+        CloseServiceHandle, OpenSCManagerW, OpenServiceW, QueryServiceStatus, SC_MANAGER_CONNECT,
+        SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_STATUS,
+    };
+
+    unsafe {
+        let Ok(manager) = OpenSCManagerW(None, None, SC_MANAGER_CONNECT) else {
+            return ServiceHealth::Unknown;
+        };
+
+        let health = match OpenServiceW(manager, w!("WSearch"), SERVICE_QUERY_STATUS) {
+            Ok(service) => {
+                let mut status = SERVICE_STATUS::default();
+                let health = match QueryServiceStatus(service, &mut status) {
+                    Ok(()) if status.dwCurrentState == SERVICE_RUNNING => ServiceHealth::Running,
+                    Ok(()) => ServiceHealth::Stopped,
+                    Err(_) => ServiceHealth::Unknown,
+                };
+                let _ = CloseServiceHandle(service);
+                health
+            }
+            Err(e) if e.code() == ERROR_SERVICE_DOES_NOT_EXIST.to_hresult() => ServiceHealth::NotInstalled,
+            Err(_) => ServiceHealth::Unknown,
+        };
+
+        let _ = CloseServiceHandle(manager);
+        health
+    }
+}
+
+#[cfg(not(windows))]
+pub fn search_service_health() -> ServiceHealth {
+    ServiceHealth::NotInstalled
+}
+
+pub use ado::AdoConnector;
+
+/// Stand-in so the provider compiles everywhere; the index only exists on Windows
+#[cfg(not(windows))]
+mod ado {
+    use super::{IndexConnection, IndexConnector, IndexRow};
+    use crate::error::{LauncherError, Result};
+
+    pub struct AdoConnector;
+
+    pub enum NoConnection {}
+
+    impl IndexConnector for AdoConnector {
+        type Connection = NoConnection;
+
+        fn connect(&self) -> Result<NoConnection> {
+            Err(LauncherError::SearchError("Windows Search is only available on Windows".to_string()))
+        }
+    }
+
+    impl IndexConnection for NoConnection {
+        fn execute(&mut self, _sql: &str) -> Result<Vec<IndexRow>> {
+            match *self {}
+        }
+    }
+}
+
+/// OLE DB access through late-bound ADO objects
+#[cfg(windows)]
+mod ado {
+    use super::{IndexConnection, IndexConnector, IndexRow};
+    use crate::error::{LauncherError, Result};
+    use windows::core::{Interface, BSTR, GUID, HSTRING, PCWSTR, VARIANT};
+    use windows::Win32::System::Com::{
+        CLSIDFromProgID, CoCreateInstance, CoInitializeEx, CoUninitialize, IDispatch, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED, DISPATCH_FLAGS, DISPATCH_METHOD, DISPATCH_PROPERTYGET, DISPPARAMS,
+    };
+
+    const CONNECTION_STRING: &str = "Provider=Search.CollatorDSO;Extended Properties='Application=Windows';";
+    const LOCALE_USER_DEFAULT: u32 = 0x0400;
+    const VT_DISPATCH: u16 = 9;
+    const VT_DATE: u16 = 7;
+    /// Days between the OLE automation epoch (1899-12-30) and the Unix epoch
+    const OLE_UNIX_EPOCH_DAYS: f64 = 25569.0;
+
+    /// Single-threaded apartment for the lifetime of the worker thread
+    pub struct Apartment {
+        initialized: bool,
+    }
+
+    impl Apartment {
+        pub fn enter() -> Self {
+            let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+            Self { initialized }
+        }
+    }
+
+    impl Drop for Apartment {
+        fn drop(&mut self) {
+            if self.initialized {
+                unsafe { CoUninitialize() };
+            }
+        }
+    }
+
+    /// Opens ADODB connections to the Search.CollatorDSO provider
+    pub struct AdoConnector;
+
+    pub struct AdoConnection {
+        connection: IDispatch,
+    }
+
+    impl IndexConnector for AdoConnector {
+        type Connection = AdoConnection;
+
+        fn connect(&self) -> Result<AdoConnection> {
+            unsafe {
+                let clsid = CLSIDFromProgID(&HSTRING::from("ADODB.Connection")).map_err(com_error)?;
+                let connection: IDispatch =
+                    CoCreateInstance(&clsid, None, CLSCTX_INPROC_SERVER).map_err(com_error)?;
+                invoke(&connection, "Open", DISPATCH_METHOD, vec![VARIANT::from(CONNECTION_STRING)])?;
+                Ok(AdoConnection { connection })
+            }
+        }
+    }
+
+    impl IndexConnection for AdoConnection {
+        fn execute(&mut self, sql: &str) -> Result<Vec<IndexRow>> {
+            unsafe {
+                let recordset = invoke(&self.connection, "Execute", DISPATCH_METHOD, vec![VARIANT::from(sql)])?;
+                let recordset = dispatch_from(&recordset)?;
+                let rows = read_rows(&recordset);
+                let _ = invoke(&recordset, "Close", DISPATCH_METHOD, Vec::new());
+                rows
+            }
+        }
+    }
+
+    impl Drop for AdoConnection {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = invoke(&self.connection, "Close", DISPATCH_METHOD, Vec::new());
+            }
+        }
+    }
+
+    unsafe fn read_rows(recordset: &IDispatch) -> Result<Vec<IndexRow>> {
+        let mut rows = Vec::new();
+        let fields = dispatch_from(&invoke(recordset, "Fields", DISPATCH_PROPERTYGET, Vec::new())?)?;
+
+        while !bool::try_from(&invoke(recordset, "EOF", DISPATCH_PROPERTYGET, Vec::new())?).unwrap_or(true) {
+            let path = BSTR::try_from(&field_value(&fields, 0)?).map(|path| path.to_string()).unwrap_or_default();
+            let size = u64::try_from(&field_value(&fields, 1)?).ok();
+            let modified = ole_date_to_unix(&field_value(&fields, 2)?);
+
+            if !path.is_empty() {
+                rows.push(IndexRow { path, size, modified });
+            }
+            invoke(recordset, "MoveNext", DISPATCH_METHOD, Vec::new())?;
+        }
+
+        Ok(rows)
+    }
+
+    unsafe fn field_value(fields: &IDispatch, index: i32) -> Result<VARIANT> {
+        let field = dispatch_from(&invoke(fields, "Item", DISPATCH_PROPERTYGET, vec![VARIANT::from(index)])?)?;
+        invoke(&field, "Value", DISPATCH_PROPERTYGET, Vec::new())
+    }
+
+    fn ole_date_to_unix(value: &VARIANT) -> Option<i64> {
+        let raw = value.as_raw();
+        unsafe {
+            (raw.Anonymous.Anonymous.vt == VT_DATE).then(|| {
+                let days = raw.Anonymous.Anonymous.Anonymous.date;
+                ((days - OLE_UNIX_EPOCH_DAYS) * 86_400.0).round() as i64
+            })
+        }
+    }
+
+    /// Borrows the object out of a VT_DISPATCH variant, adding a reference
+    unsafe fn dispatch_from(value: &VARIANT) -> Result<IDispatch> {
+        let raw = &value.as_raw().Anonymous.Anonymous;
+        if raw.vt != VT_DISPATCH {
+            return Err(LauncherError::SearchError(format!(
+                "Expected an ADO object, got variant type {}",
+                raw.vt
+            )));
+        }
+
+        IDispatch::from_raw_borrowed(&raw.Anonymous.pdispVal)
+            .cloned()
+            .ok_or_else(|| LauncherError::SearchError("ADO returned a null object".to_string()))
+    }
+
+    /// Calls a member by name; arguments are given in declaration order
+    unsafe fn invoke(object: &IDispatch, name: &str, flags: DISPATCH_FLAGS, mut args: Vec<VARIANT>) -> Result<VARIANT> {
+        let name = HSTRING::from(name);
+        let names = [PCWSTR(name.as_ptr())];
+        let mut dispid = 0;
+        object
+            .GetIDsOfNames(&GUID::zeroed(), names.as_ptr(), 1, LOCALE_USER_DEFAULT, &mut dispid)
+            .map_err(com_error)?;
+
+        // IDispatch takes arguments last to first
+        args.reverse();
+        let params = DISPPARAMS {
+            rgvarg: args.as_mut_ptr(),
+            rgdispidNamedArgs: std::ptr::null_mut(),
+            cArgs: args.len() as u32,
+            cNamedArgs: 0,
+        };
+
+        let mut result = VARIANT::default();
+        object
+            .Invoke(dispid, &GUID::zeroed(), LOCALE_USER_DEFAULT, flags, &params, Some(&mut result as *mut VARIANT), None, None)
+            .map_err(com_error)?;
+        Ok(result)
+    }
+
+    fn com_error(e: windows::core::Error) -> LauncherError {
+        LauncherError::SearchError(format!("Windows Search index error: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Scripted connector; each connection answers with the next outcome or echoes the SQL
+    #[derive(Clone, Default)]
+    struct MockConnector {
+        connects: Arc<Mutex<usize>>,
+        failures: Arc<Mutex<VecDeque<usize>>>,
+        executed: Arc<Mutex<Vec<(usize, String)>>>,
+        delay: Option<Duration>,
+    }
+
+    impl MockConnector {
+        /// Makes the query with this zero-based index fail on its first attempt
+        fn failing_at(self, query: usize) -> Self {
+            self.failures.lock().unwrap().push_back(query);
+            self
+        }
+
+        fn with_delay(mut self, delay: Duration) -> Self {
+            self.delay = Some(delay);
+            self
+        }
+    }
+
+    struct MockConnection {
+        id: usize,
+        connector: MockConnector,
+    }
+
+    impl IndexConnector for MockConnector {
+        type Connection = MockConnection;
+
+        fn connect(&self) -> Result<MockConnection> {
+            let mut connects = self.connects.lock().unwrap();
+            *connects += 1;
+            Ok(MockConnection { id: *connects, connector: self.clone() })
+        }
+    }
+
+    impl IndexConnection for MockConnection {
+        fn execute(&mut self, sql: &str) -> Result<Vec<IndexRow>> {
+            if let Some(delay) = self.connector.delay {
+                std::thread::sleep(delay);
+            }
+
+            let mut executed = self.connector.executed.lock().unwrap();
+            let attempt = executed.len();
+            executed.push((self.id, sql.to_string()));
+
+            let mut failures = self.connector.failures.lock().unwrap();
+            if failures.front() == Some(&attempt) {
+                failures.pop_front();
+                return Err(LauncherError::SearchError("connection reset".to_string()));
+            }
+
+            Ok(vec![IndexRow { path: sql.to_string(), size: None, modified: None }])
+        }
+    }
+
+    fn query(pattern: &str) -> IndexQuery {
+        IndexQuery { pattern: pattern.to_string(), sort: None }
+    }
+
+    #[test]
+    fn test_template_escapes_and_sorts() {
+        let unsorted = FilenameQueryTemplate::new("C:\\Users\\o'brien", None);
+        let sql = unsorted.render("50%_off[1]'s");
+        assert!(sql.contains("SCOPE='file:C:\\Users\\o''brien'"));
+        assert!(sql.contains("LIKE '%50[%][_]off[[]1]''s%'"));
+        assert!(!sql.contains("ORDER BY"));
+
+        let largest = FilenameQueryTemplate::new("C:\\Users\\me", Some(FileSort::Largest)).render("report");
+        assert!(largest.ends_with("%' AND System.ItemType <> 'Directory' ORDER BY System.Size DESC"));
+
+        let newest = FilenameQueryTemplate::new("C:\\Users\\me", Some(FileSort::Newest)).render("report");
+        assert!(newest.ends_with("LIKE '%report%' ORDER BY System.DateModified DESC"));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_connection_error() {
+        let connector = MockConnector::default().failing_at(1);
+        let client = SearchIndexClient::spawn(connector.clone(), "C:\\Users\\me".to_string());
+
+        assert_eq!(client.query(query("first")).await.unwrap().len(), 1);
+        // The open connection fails, so a new one is opened and the query retried
+        let rows = client.query(query("second")).await.unwrap();
+        assert!(rows[0].path.contains("'%second%'"));
+        assert_eq!(client.query(query("third")).await.unwrap().len(), 1);
+
+        assert_eq!(*connector.connects.lock().unwrap(), 2);
+        let connections: Vec<usize> = connector.executed.lock().unwrap().iter().map(|(id, _)| *id).collect();
+        assert_eq!(connections, vec![1, 1, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_replies_match_requests_in_order() {
+        let connector = MockConnector::default();
+        let client = SearchIndexClient::spawn(connector.clone(), "C:\\Users\\me".to_string());
+
+        let patterns = ["alpha", "beta", "gamma", "delta"];
+        let replies = futures::future::join_all(patterns.iter().map(|p| client.query(query(p)))).await;
+
+        for (pattern, reply) in patterns.iter().zip(replies) {
+            assert!(reply.unwrap()[0].path.contains(&format!("'%{}%'", pattern)));
+        }
+
+        let executed = connector.executed.lock().unwrap();
+        for (pattern, (connection, sql)) in patterns.iter().zip(executed.iter()) {
+            assert_eq!(*connection, 1, "one connection serves every query");
+            assert!(sql.contains(pattern));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_query_is_skipped() {
+        let connector = MockConnector::default().with_delay(Duration::from_millis(100));
+        let client = SearchIndexClient::spawn(connector.clone(), "C:\\Users\\me".to_string());
+
+        let (slow, abandoned) = tokio::join!(
+            client.query(query("slow")),
+            tokio::time::timeout(Duration::from_millis(10), client.query(query("abandoned"))),
+        );
+        assert!(slow.is_ok());
+        assert!(abandoned.is_err());
+
+        client.query(query("next")).await.unwrap();
+        let executed = connector.executed.lock().unwrap();
+        assert!(executed.iter().all(|(_, sql)| !sql.contains("abandoned")));
+        assert_eq!(executed.len(), 2);
+    }
+
+    #[test]
+    fn test_service_health_errors() {
+        assert!(ServiceHealth::Running.error().is_none());
+        let error = ServiceHealth::Stopped.error().unwrap().to_string();
+        assert!(error.contains("WSearch") && error.contains("not running"));
+        assert!(ServiceHealth::NotInstalled.error().unwrap().to_string().contains("not installed"));
+    }
+}
@@ -0,0 +1,356 @@
+/// Settings search provider for the launcher's own settings
+///
+/// `setting clipboard size` or `option hotkey` offers the matching entries of
+/// `settings_entries`, which lists every `AppSettings` field and the options
+/// providers declare. Queries nothing else matched are tried as well, without
+/// the prefix. Selecting a result opens the Settings view scrolled to the
+/// option. Options of providers that failed or are disabled are left out.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchEngine, SearchProvider};
+use crate::settings::{settings_entries, SettingEntry};
+use crate::types::{ProviderHealth, ProviderStatus, ResultAction, ResultType, SearchResult, KEEP_WINDOW_OPEN_KEY};
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use tracing::info;
+
+/// Words that ask for settings rather than anything else
+const PREFIXES: &[&str] = &["setting", "settings", "option", "options"];
+
+/// Shortest query, or query word, that matches an entry
+const MIN_QUERY_LEN: usize = 3;
+
+/// Most entries offered for one query
+const MAX_RESULTS: usize = 8;
+
+/// Metadata key holding the setting's key
+const SETTING_KEY: &str = "setting_key";
+
+/// Where the Settings view should scroll to, sent with `open-settings`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettingsLocation {
+    pub section: String,
+    pub key: String,
+}
+
+/// Lowercase names an entry is known by: its name, keywords and key
+fn aliases(entry: &SettingEntry) -> impl Iterator<Item = String> + '_ {
+    std::iter::once(entry.name.to_lowercase())
+        .chain(entry.keywords.iter().map(|keyword| keyword.to_lowercase()))
+        .chain(std::iter::once(entry.key.replace(['_', '.'], " ")))
+}
+
+/// Whether every character of `query` appears in `text`, in order
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut chars = text.chars();
+    query.chars().all(|wanted| chars.any(|c| c == wanted))
+}
+
+/// Scores how well a lowercase query names or describes the entry
+fn match_score(entry: &SettingEntry, query: &str) -> Option<f64> {
+    if query.chars().count() < MIN_QUERY_LEN {
+        return None;
+    }
+
+    aliases(entry)
+        .filter_map(|alias| {
+            if alias == query {
+                Some(100.0)
+            } else if alias.starts_with(query) {
+                Some(90.0)
+            } else if alias.split(' ').any(|word| word.starts_with(query)) {
+                Some(75.0)
+            } else {
+                None
+            }
+        })
+        .reduce(f64::max)
+        .or_else(|| keyword_score(entry, query))
+        .or_else(|| is_subsequence(&query.replace(' ', ""), &entry.name.to_lowercase()).then_some(30.0))
+}
+
+/// Scores a query by how many of its words the entry's aliases contain
+///
+/// At least half the words have to match, ranking below any match on a
+/// whole name or keyword.
+fn keyword_score(entry: &SettingEntry, query: &str) -> Option<f64> {
+    let words: Vec<&str> = query
+        .split_whitespace()
+        .filter(|word| word.chars().count() >= MIN_QUERY_LEN)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let vocabulary: Vec<String> = aliases(entry)
+        .flat_map(|alias| alias.split([' ', '-']).map(str::to_string).collect::<Vec<_>>())
+        .collect();
+    let matched = words
+        .iter()
+        .filter(|word| vocabulary.iter().any(|known| known.starts_with(*word)))
+        .count();
+
+    (matched > 0 && matched * 2 >= words.len()).then(|| 40.0 + 30.0 * matched as f64 / words.len() as f64)
+}
+
+/// Splits a settings prefix off a lowercase query, returning the rest
+fn strip_prefix(query: &str) -> Option<&str> {
+    let (first, rest) = query.split_once(' ').unwrap_or((query, ""));
+    PREFIXES.contains(&first).then(|| rest.trim())
+}
+
+/// Whether an entry can take effect given the providers' health
+///
+/// Entries tied to a provider need it registered and neither failed nor
+/// disabled; all others always apply.
+fn is_applicable(entry: &SettingEntry, health: &[ProviderStatus]) -> bool {
+    let Some(provider) = &entry.provider else {
+        return true;
+    };
+
+    health.iter().any(|status| {
+        status.provider == *provider
+            && !matches!(status.health, ProviderHealth::Failed { .. } | ProviderHealth::Disabled)
+    })
+}
+
+/// Search provider for the launcher's settings
+pub struct SettingsSearchProvider {
+    /// Every searchable setting
+    entries: Vec<SettingEntry>,
+    /// Engine whose provider health filters the entries
+    engine: Option<Weak<SearchEngine>>,
+    /// Called with the setting to show when a result is executed
+    open_handler: Option<Box<dyn Fn(&SettingsLocation) + Send + Sync>>,
+}
+
+impl SettingsSearchProvider {
+    /// Creates a SettingsSearchProvider over `settings_entries`
+    pub fn new() -> Result<Self> {
+        info!("Initializing SettingsSearchProvider");
+        Ok(Self {
+            entries: settings_entries(),
+            engine: None,
+            open_handler: None,
+        })
+    }
+
+    /// Filters entries by the health of `engine`'s providers
+    ///
+    /// The engine is held weakly, as it owns this provider.
+    pub fn set_engine(&mut self, engine: &Arc<SearchEngine>) {
+        self.engine = Some(Arc::downgrade(engine));
+    }
+
+    /// Sets what opens the Settings view at a setting
+    pub fn set_open_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&SettingsLocation) + Send + Sync + 'static,
+    {
+        self.open_handler = Some(Box::new(handler));
+    }
+
+    /// Health of the engine's providers, or `None` without an engine
+    async fn provider_health(&self) -> Option<Vec<ProviderStatus>> {
+        let engine = self.engine.as_ref()?.upgrade()?;
+        Some(engine.provider_health().await)
+    }
+
+    /// Matches the entries against a lowercase query without its prefix
+    ///
+    /// An empty query lists the entries in table order.
+    async fn matching_results(&self, query: &str) -> Vec<SearchResult> {
+        let health = self.provider_health().await;
+        let applicable = self
+            .entries
+            .iter()
+            .filter(|entry| health.as_deref().is_none_or(|health| is_applicable(entry, health)));
+
+        let mut results: Vec<SearchResult> = if query.is_empty() {
+            applicable.map(|entry| Self::create_search_result(entry, 50.0)).collect()
+        } else {
+            applicable
+                .filter_map(|entry| Some(Self::create_search_result(entry, match_score(entry, query)?)))
+                .collect()
+        };
+
+        // Stable, so ties keep the table's order
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(MAX_RESULTS);
+        results
+    }
+
+    /// Converts an entry to a SearchResult
+    fn create_search_result(entry: &SettingEntry, score: f64) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert(SETTING_KEY.to_string(), serde_json::json!(entry.key));
+        metadata.insert("section".to_string(), serde_json::json!(entry.section));
+        metadata.insert(KEEP_WINDOW_OPEN_KEY.to_string(), serde_json::json!(true));
+
+        SearchResult {
+            id: format!("settings:{}", entry.key),
+            title: entry.name.clone(),
+            subtitle: format!("Settings › {}", entry.section),
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: format!("settings:{}", entry.key),
+                args: vec![],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for SettingsSearchProvider {
+    fn name(&self) -> &str {
+        "SettingsSearch"
+    }
+
+    fn priority(&self) -> u8 {
+        80 // Same as the other quick actions
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        match strip_prefix(query.folded_query.trim()) {
+            Some(rest) => Ok(self.matching_results(rest).await),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn fallback_results(&self, query: &QueryContext) -> Vec<SearchResult> {
+        let folded = query.folded_query.trim();
+        if folded.is_empty() || strip_prefix(folded).is_some() {
+            return Vec::new();
+        }
+        self.matching_results(folded).await
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let location = SettingsLocation {
+            key: result
+                .metadata
+                .get(SETTING_KEY)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| LauncherError::ExecutionError("Not a settings result".to_string()))?
+                .to_string(),
+            section: result
+                .metadata
+                .get("section")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        };
+
+        let handler = self
+            .open_handler
+            .as_ref()
+            .ok_or_else(|| LauncherError::ExecutionError("Settings can't be opened".to_string()))?;
+
+        info!("Opening settings at {}", location.key);
+        handler(&location);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn status(provider: &str, health: ProviderHealth) -> ProviderStatus {
+        ProviderStatus {
+            provider: provider.to_string(),
+            message: health.to_string(),
+            health,
+            slow_peeks: 0,
+        }
+    }
+
+    fn keys(results: &[SearchResult]) -> Vec<String> {
+        results
+            .iter()
+            .filter_map(|r| r.metadata.get(SETTING_KEY).and_then(|v| v.as_str()).map(str::to_string))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_prefixed_query_matches_settings() {
+        let provider = SettingsSearchProvider::new().unwrap();
+
+        let results = provider.search(&QueryContext::new("setting clipboard size", true)).await.unwrap();
+        assert_eq!(keys(&results).first().map(String::as_str), Some("clipboard_history_size"));
+        assert!(results[0].keeps_window_open());
+
+        let hotkey = provider.search(&QueryContext::new("option hotkey", true)).await.unwrap();
+        assert_eq!(keys(&hotkey).first().map(String::as_str), Some("hotkey"));
+
+        let listing = provider.search(&QueryContext::new("settings", true)).await.unwrap();
+        assert_eq!(listing.len(), MAX_RESULTS);
+
+        assert!(provider.search(&QueryContext::new("clipboard size", true)).await.unwrap().is_empty());
+        assert!(provider.search(&QueryContext::new("setting zzqx", true)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_matches_without_prefix() {
+        let provider = SettingsSearchProvider::new().unwrap();
+
+        let results = provider.fallback_results(&QueryContext::new("clipboard size", true)).await;
+        assert_eq!(keys(&results).first().map(String::as_str), Some("clipboard_history_size"));
+        assert!(provider.fallback_results(&QueryContext::new("setting clipboard", true)).await.is_empty());
+    }
+
+    #[test]
+    fn test_entries_filtered_by_provider_health() {
+        let entry = |provider: Option<&str>| SettingEntry {
+            key: "key".to_string(),
+            name: "Name".to_string(),
+            section: "search".to_string(),
+            keywords: Vec::new(),
+            provider: provider.map(str::to_string),
+        };
+        let health = vec![
+            status("WebSearch", ProviderHealth::Ready),
+            status("Bookmarks", ProviderHealth::NotInitialized),
+            status("FileSearch", ProviderHealth::Failed { error: "SDK missing".to_string() }),
+            status("Weather", ProviderHealth::Disabled),
+        ];
+
+        assert!(is_applicable(&entry(None), &health));
+        assert!(is_applicable(&entry(Some("WebSearch")), &health));
+        assert!(is_applicable(&entry(Some("Bookmarks")), &health));
+        assert!(!is_applicable(&entry(Some("FileSearch")), &health));
+        assert!(!is_applicable(&entry(Some("Weather")), &health));
+        assert!(!is_applicable(&entry(Some("Clipboard History")), &health));
+    }
+
+    #[tokio::test]
+    async fn test_execute_opens_settings_at_entry() {
+        let opened = Arc::new(Mutex::new(Vec::new()));
+        let mut provider = SettingsSearchProvider::new().unwrap();
+        let sink = Arc::clone(&opened);
+        provider.set_open_handler(move |location| sink.lock().unwrap().push(location.clone()));
+
+        let results = provider.search(&QueryContext::new("setting window width", true)).await.unwrap();
+        provider.execute(&results[0]).await.unwrap();
+        assert_eq!(
+            *opened.lock().unwrap(),
+            vec![SettingsLocation {
+                section: "appearance".to_string(),
+                key: "window_width".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,1020 @@
+/// SSH connection provider
+///
+/// This provider lists the hosts from the user's OpenSSH config and the
+/// sessions saved in PuTTY, and opens a terminal connected to the chosen one.
+/// Typing `ssh` in front of the host name is optional.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::normalize_for_search;
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Maximum number of results per query
+const MAX_RESULTS: usize = 10;
+
+/// Port used when neither the config nor the session names one
+const DEFAULT_SSH_PORT: u16 = 22;
+
+/// Nesting limit for Include directives, matching OpenSSH
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Registry key holding PuTTY's saved sessions
+#[cfg(windows)]
+const PUTTY_SESSIONS_KEY: &str = "Software\\SimonTatham\\PuTTY\\Sessions";
+
+/// Matches `text` against an OpenSSH pattern where `*` and `?` are wildcards
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character and retry
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Checks whether a Host pattern names a single concrete host
+fn is_concrete_host(pattern: &str) -> bool {
+    !pattern.starts_with('!') && !pattern.contains(['*', '?'])
+}
+
+/// Returns the user's home directory
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("USERPROFILE")
+        .or_else(|| std::env::var_os("HOME"))
+        .map(PathBuf::from)
+}
+
+/// A host defined in the OpenSSH config, with its options resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshHost {
+    /// First name on the Host line
+    pub alias: String,
+    /// Other concrete names on the same Host line
+    pub other_aliases: Vec<String>,
+    /// Address to connect to; the alias itself when no HostName is set
+    pub host_name: String,
+    /// Login user, if the config names one
+    pub user: Option<String>,
+    /// Port to connect to
+    pub port: u16,
+}
+
+/// A session saved in PuTTY
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuttySession {
+    /// Session name as shown in PuTTY
+    pub name: String,
+    /// Address the session connects to
+    pub host_name: Option<String>,
+    /// Login user, if the session stores one
+    pub user: Option<String>,
+    /// Port the session connects to
+    pub port: Option<u16>,
+}
+
+impl PuttySession {
+    /// Creates a session from its registry key name
+    ///
+    /// PuTTY escapes spaces and punctuation in key names as `%XX`.
+    pub fn from_key_name(key_name: &str) -> Self {
+        let name = urlencoding::decode(key_name)
+            .map(|name| name.into_owned())
+            .unwrap_or_else(|_| key_name.to_string());
+
+        Self {
+            name,
+            host_name: None,
+            user: None,
+            port: None,
+        }
+    }
+}
+
+/// A program and arguments to spawn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchCommand {
+    /// Program to run
+    pub program: String,
+    /// Arguments passed to the program
+    pub args: Vec<String>,
+}
+
+impl LaunchCommand {
+    fn new(program: &str, args: Vec<String>) -> Self {
+        Self {
+            program: program.to_string(),
+            args,
+        }
+    }
+}
+
+/// Something the user can connect to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SshTarget {
+    /// Host from the OpenSSH config
+    Config(SshHost),
+    /// Saved PuTTY session
+    Putty(PuttySession),
+}
+
+impl SshTarget {
+    /// Name shown as the result title
+    pub fn title(&self) -> &str {
+        match self {
+            SshTarget::Config(host) => &host.alias,
+            SshTarget::Putty(session) => &session.name,
+        }
+    }
+
+    /// Creates a unique ID for the target
+    pub fn id(&self) -> String {
+        match self {
+            SshTarget::Config(host) => format!("ssh:{}", host.alias),
+            SshTarget::Putty(session) => format!("putty:{}", session.name),
+        }
+    }
+
+    /// Names the query is matched against
+    fn names(&self) -> Vec<&str> {
+        match self {
+            SshTarget::Config(host) => std::iter::once(host.alias.as_str())
+                .chain(host.other_aliases.iter().map(String::as_str))
+                .chain(std::iter::once(host.host_name.as_str()))
+                .collect(),
+            SshTarget::Putty(session) => std::iter::once(session.name.as_str())
+                .chain(session.host_name.as_deref())
+                .collect(),
+        }
+    }
+
+    /// Returns the commands to try, in order, to open a connection
+    ///
+    /// Config hosts prefer Windows Terminal and fall back to a plain console;
+    /// PuTTY sessions are loaded by name so their saved settings apply.
+    pub fn launch_commands(&self) -> Vec<LaunchCommand> {
+        match self {
+            SshTarget::Config(host) => {
+                let destination = match &host.user {
+                    Some(user) => format!("{}@{}", user, host.host_name),
+                    None => host.host_name.clone(),
+                };
+                let ssh_args = vec![
+                    "ssh".to_string(),
+                    destination,
+                    "-p".to_string(),
+                    host.port.to_string(),
+                ];
+
+                vec![
+                    LaunchCommand::new("wt", ssh_args.clone()),
+                    LaunchCommand::new("cmd", std::iter::once("/K".to_string()).chain(ssh_args).collect()),
+                ]
+            }
+            SshTarget::Putty(session) => vec![LaunchCommand::new(
+                "putty",
+                vec!["-load".to_string(), session.name.clone()],
+            )],
+        }
+    }
+}
+
+/// Options from one Host block of an OpenSSH config
+#[derive(Debug, Default)]
+struct HostBlock {
+    /// Patterns on the Host line
+    patterns: Vec<String>,
+    host_name: Option<String>,
+    user: Option<String>,
+    port: Option<u16>,
+}
+
+impl HostBlock {
+    fn new(patterns: Vec<String>) -> Self {
+        Self {
+            patterns,
+            ..Default::default()
+        }
+    }
+
+    /// Checks whether this block applies to `host`, honouring `!` negations
+    fn matches(&self, host: &str) -> bool {
+        let mut matched = false;
+        for pattern in &self.patterns {
+            if let Some(negated) = pattern.strip_prefix('!') {
+                if glob_match(negated, host) {
+                    return false;
+                }
+            } else if glob_match(pattern, host) {
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+/// Parser for OpenSSH client config files
+///
+/// Only the options needed to connect are read. Lines that fail to parse are
+/// skipped so one bad entry never hides the rest of the file.
+#[derive(Debug)]
+pub struct SshConfigParser {
+    /// Blocks in file order; the first holds options set before any Host line
+    blocks: Vec<HostBlock>,
+    /// Block receiving options, or `None` inside an unsupported Match block
+    current: Option<usize>,
+}
+
+impl SshConfigParser {
+    fn new() -> Self {
+        Self {
+            blocks: vec![HostBlock::new(vec!["*".to_string()])],
+            current: Some(0),
+        }
+    }
+
+    /// Locates the user's OpenSSH config file
+    pub fn locate_config() -> Option<PathBuf> {
+        let path = home_dir()?.join(".ssh").join("config");
+        path.exists().then_some(path)
+    }
+
+    /// Parses a config file, following its Include directives
+    pub fn parse_file(path: &Path) -> Result<Vec<SshHost>> {
+        let content = std::fs::read_to_string(path)?;
+        let ssh_dir = path.parent().unwrap_or(Path::new("."));
+        Ok(Self::parse_str(&content, ssh_dir))
+    }
+
+    /// Parses config text; relative Include paths resolve against `ssh_dir`
+    pub fn parse_str(content: &str, ssh_dir: &Path) -> Vec<SshHost> {
+        let mut parser = Self::new();
+        parser.parse_lines(content, ssh_dir, 0);
+        parser.resolve()
+    }
+
+    fn parse_lines(&mut self, content: &str, ssh_dir: &Path, depth: usize) {
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (keyword, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+                Some((keyword, value)) => (keyword, value.trim_start_matches(|c: char| c.is_whitespace() || c == '=').trim()),
+                None => (line, ""),
+            };
+
+            match keyword.to_ascii_lowercase().as_str() {
+                "host" => {
+                    let patterns: Vec<String> = value
+                        .split_whitespace()
+                        .map(|pattern| pattern.trim_matches('"').to_string())
+                        .collect();
+                    self.blocks.push(HostBlock::new(patterns));
+                    self.current = Some(self.blocks.len() - 1);
+                }
+                "match" => {
+                    // Match criteria can't be evaluated here, so their options are ignored
+                    self.current = None;
+                }
+                "include" => {
+                    if depth >= MAX_INCLUDE_DEPTH {
+                        warn!("Ignoring ssh config Include nested deeper than {} levels", MAX_INCLUDE_DEPTH);
+                        continue;
+                    }
+                    for path in Self::expand_include(value, ssh_dir) {
+                        match std::fs::read_to_string(&path) {
+                            Ok(included) => self.parse_lines(&included, ssh_dir, depth + 1),
+                            Err(e) => warn!("Failed to read included ssh config {}: {}", path.display(), e),
+                        }
+                    }
+                }
+                option => {
+                    let Some(block) = self.current.map(|index| &mut self.blocks[index]) else {
+                        continue;
+                    };
+                    let value = value.trim_matches('"');
+
+                    // The first value given for an option wins
+                    match option {
+                        "hostname" if block.host_name.is_none() => block.host_name = Some(value.to_string()),
+                        "user" if block.user.is_none() => block.user = Some(value.to_string()),
+                        "port" if block.port.is_none() => match value.parse() {
+                            Ok(port) => block.port = Some(port),
+                            Err(_) => debug!("Skipping invalid ssh config port '{}' on line {}", value, line_number + 1),
+                        },
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolves Include arguments to files, expanding `~` and wildcards
+    fn expand_include(value: &str, ssh_dir: &Path) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+
+        for argument in value.split_whitespace().map(|arg| arg.trim_matches('"')) {
+            let path = match argument.strip_prefix("~/") {
+                Some(rest) => match home_dir() {
+                    Some(home) => home.join(rest),
+                    None => continue,
+                },
+                None => ssh_dir.join(argument),
+            };
+
+            let file_pattern = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+            if !file_pattern.contains(['*', '?']) {
+                paths.push(path);
+                continue;
+            }
+
+            let Some(dir) = path.parent() else {
+                continue;
+            };
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+
+            let mut matched: Vec<PathBuf> = entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|entry| {
+                    entry.is_file()
+                        && entry
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| glob_match(file_pattern, name))
+                })
+                .collect();
+            matched.sort();
+            paths.extend(matched);
+        }
+
+        paths
+    }
+
+    /// Turns the parsed blocks into one entry per concrete Host line
+    fn resolve(self) -> Vec<SshHost> {
+        let mut seen = HashSet::new();
+        let mut hosts = Vec::new();
+
+        for block in &self.blocks {
+            let mut aliases = block
+                .patterns
+                .iter()
+                .filter(|pattern| is_concrete_host(pattern) && seen.insert(pattern.to_lowercase()))
+                .cloned();
+            let Some(alias) = aliases.next() else {
+                continue;
+            };
+
+            let mut host_name = None;
+            let mut user = None;
+            let mut port = None;
+            for candidate in self.blocks.iter().filter(|candidate| candidate.matches(&alias)) {
+                host_name = host_name.or_else(|| candidate.host_name.clone());
+                user = user.or_else(|| candidate.user.clone());
+                port = port.or(candidate.port);
+            }
+
+            hosts.push(SshHost {
+                host_name: host_name
+                    .map(|name| name.replace("%h", &alias))
+                    .unwrap_or_else(|| alias.clone()),
+                other_aliases: aliases.collect(),
+                alias,
+                user,
+                port: port.unwrap_or(DEFAULT_SSH_PORT),
+            });
+        }
+
+        hosts
+    }
+}
+
+/// Help entry for SSH hosts, also listed before the lazy provider is built
+pub const SSH_SYNTAX: &[SyntaxFeature] = &[SyntaxFeature {
+    id: "ssh",
+    title: "Connect over SSH",
+    description: "Hosts from your SSH config and saved PuTTY sessions",
+    example: "ssh ",
+    token: None,
+    values: &[],
+}];
+
+/// SSH connection search provider
+pub struct SshProvider {
+    /// Cached hosts and sessions
+    targets: Arc<RwLock<Vec<SshTarget>>>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl SshProvider {
+    /// Creates a new SSH provider
+    pub fn new() -> Result<Self> {
+        info!("Initializing SshProvider");
+
+        Ok(Self {
+            targets: Arc::new(RwLock::new(Vec::new())),
+            enabled: true,
+        })
+    }
+
+    /// Loads config hosts and PuTTY sessions
+    fn load_targets() -> Vec<SshTarget> {
+        let mut targets = Vec::new();
+
+        if let Some(path) = SshConfigParser::locate_config() {
+            match SshConfigParser::parse_file(&path) {
+                Ok(hosts) => targets.extend(hosts.into_iter().map(SshTarget::Config)),
+                Err(e) => warn!("Failed to read ssh config {}: {}", path.display(), e),
+            }
+        }
+
+        targets.extend(Self::read_putty_sessions().into_iter().map(SshTarget::Putty));
+        targets
+    }
+
+    /// Reads the sessions saved in PuTTY from the registry
+    #[cfg(windows)]
+    fn read_putty_sessions() -> Vec<PuttySession> {
+        use windows::core::{HSTRING, PWSTR};
+        use windows::Win32::System::Registry::*;
+
+        let mut sessions = Vec::new();
+
+        unsafe {
+            let mut sessions_key = HKEY::default();
+            let result = RegOpenKeyExW(
+                HKEY_CURRENT_USER,
+                &HSTRING::from(PUTTY_SESSIONS_KEY),
+                0,
+                KEY_READ,
+                &mut sessions_key,
+            );
+
+            if result.is_err() {
+                debug!("No PuTTY sessions found in the registry");
+                return sessions;
+            }
+
+            let mut index = 0;
+            loop {
+                let mut buffer = vec![0u16; 256];
+                let mut len = buffer.len() as u32;
+                let result = RegEnumKeyExW(
+                    sessions_key,
+                    index,
+                    PWSTR(buffer.as_mut_ptr()),
+                    &mut len,
+                    None,
+                    PWSTR::null(),
+                    None,
+                    None,
+                );
+                if result.is_err() {
+                    break;
+                }
+                index += 1;
+
+                let key_name = String::from_utf16_lossy(&buffer[..len as usize]);
+                let mut session = PuttySession::from_key_name(&key_name);
+                if session.name == "Default Settings" {
+                    continue;
+                }
+
+                let mut session_key = HKEY::default();
+                if RegOpenKeyExW(sessions_key, &HSTRING::from(key_name.as_str()), 0, KEY_READ, &mut session_key).is_ok() {
+                    session.host_name = Self::read_registry_string(session_key, "HostName");
+                    session.user = Self::read_registry_string(session_key, "UserName");
+                    session.port = Self::read_registry_dword(session_key, "PortNumber")
+                        .and_then(|port| u16::try_from(port).ok());
+                    let _ = RegCloseKey(session_key);
+                }
+
+                sessions.push(session);
+            }
+
+            let _ = RegCloseKey(sessions_key);
+        }
+
+        debug!("Found {} PuTTY sessions", sessions.len());
+        sessions
+    }
+
+    /// Reads a non-empty string value from an open registry key
+    #[cfg(windows)]
+    unsafe fn read_registry_string(key: windows::Win32::System::Registry::HKEY, name: &str) -> Option<String> {
+        use windows::core::HSTRING;
+        use windows::Win32::System::Registry::*;
+
+        let mut buffer = vec![0u16; 512];
+        let mut buffer_size = (buffer.len() * 2) as u32;
+        let mut value_type = REG_VALUE_TYPE::default();
+
+        let result = RegQueryValueExW(
+            key,
+            &HSTRING::from(name),
+            None,
+            Some(&mut value_type),
+            Some(buffer.as_mut_ptr() as *mut u8),
+            Some(&mut buffer_size),
+        );
+
+        if result.is_err() || value_type != REG_SZ {
+            return None;
+        }
+
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        let value = String::from_utf16_lossy(&buffer[..len]);
+        (!value.is_empty()).then_some(value)
+    }
+
+    /// Reads a DWORD value from an open registry key
+    #[cfg(windows)]
+    unsafe fn read_registry_dword(key: windows::Win32::System::Registry::HKEY, name: &str) -> Option<u32> {
+        use windows::core::HSTRING;
+        use windows::Win32::System::Registry::*;
+
+        let mut value = 0u32;
+        let mut value_size = std::mem::size_of::<u32>() as u32;
+        let mut value_type = REG_VALUE_TYPE::default();
+
+        let result = RegQueryValueExW(
+            key,
+            &HSTRING::from(name),
+            None,
+            Some(&mut value_type),
+            Some(&mut value as *mut u32 as *mut u8),
+            Some(&mut value_size),
+        );
+
+        (result.is_ok() && value_type == REG_DWORD).then_some(value)
+    }
+
+    #[cfg(not(windows))]
+    fn read_putty_sessions() -> Vec<PuttySession> {
+        Vec::new()
+    }
+
+    /// Scores a target name against the query
+    ///
+    /// Both arguments are expected to be normalized with `normalize_for_search`.
+    fn match_score(query: &str, name: &str) -> Option<f64> {
+        if name == query {
+            Some(100.0)
+        } else if name.starts_with(query) {
+            Some(90.0)
+        } else if name.contains(query) {
+            Some(70.0)
+        } else {
+            let mut name_chars = name.chars();
+            query
+                .chars()
+                .all(|query_char| name_chars.any(|c| c == query_char))
+                .then_some(40.0)
+        }
+    }
+
+    /// Splits an optional leading `ssh` keyword off the query
+    fn strip_keyword(query: &str) -> (bool, &str) {
+        match query.split_once(char::is_whitespace) {
+            Some((keyword, rest)) if keyword.eq_ignore_ascii_case("ssh") => (true, rest.trim()),
+            _ if query.eq_ignore_ascii_case("ssh") => (true, ""),
+            _ => (false, query),
+        }
+    }
+
+    /// Creates a search result for a target
+    fn create_search_result(target: &SshTarget, score: f64) -> SearchResult {
+        let mut metadata = HashMap::new();
+        let subtitle = match target {
+            SshTarget::Config(host) => {
+                metadata.insert("source".to_string(), serde_json::json!("ssh_config"));
+                metadata.insert("host".to_string(), serde_json::json!(host.host_name));
+                metadata.insert("port".to_string(), serde_json::json!(host.port));
+                if let Some(user) = &host.user {
+                    metadata.insert("user".to_string(), serde_json::json!(user));
+                }
+
+                match &host.user {
+                    Some(user) => format!("{}@{}:{}", user, host.host_name, host.port),
+                    None => format!("{}:{}", host.host_name, host.port),
+                }
+            }
+            SshTarget::Putty(session) => {
+                metadata.insert("source".to_string(), serde_json::json!("putty"));
+                if let Some(host) = &session.host_name {
+                    metadata.insert("host".to_string(), serde_json::json!(host));
+                }
+                if let Some(user) = &session.user {
+                    metadata.insert("user".to_string(), serde_json::json!(user));
+                }
+                if let Some(port) = session.port {
+                    metadata.insert("port".to_string(), serde_json::json!(port));
+                }
+
+                match &session.host_name {
+                    Some(host) => format!("PuTTY session · {}", host),
+                    None => "PuTTY session".to_string(),
+                }
+            }
+        };
+
+        let launch = target
+            .launch_commands()
+            .into_iter()
+            .next()
+            .expect("every target has a launch command");
+
+        SearchResult {
+            id: target.id(),
+            title: target.title().to_string(),
+            subtitle,
+            icon: Some("terminal".to_string()),
+            result_type: ResultType::Ssh,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: launch.program,
+                args: launch.args,
+            },
+            normalized_title: Some(normalize_for_search(target.title())),
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Spawns the first launch command that starts
+    async fn launch(commands: Vec<LaunchCommand>) -> Result<()> {
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut last_error = None;
+
+            for command in commands {
+                match std::process::Command::new(&command.program).args(&command.args).spawn() {
+                    Ok(_) => {
+                        info!("Started {} {:?}", command.program, command.args);
+                        return Ok(());
+                    }
+                    Err(e) => {
+                        debug!("Failed to start {}: {}", command.program, e);
+                        last_error = Some(e);
+                    }
+                }
+            }
+
+            Err(LauncherError::ExecutionError(format!(
+                "Failed to open SSH connection: {}",
+                last_error.map_or_else(|| "no command to run".to_string(), |e| e.to_string())
+            )))
+        })
+        .await
+        .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn SSH launch task: {}", e)))?
+    }
+}
+
+#[async_trait]
+impl SearchProvider for SshProvider {
+    fn name(&self) -> &str {
+        "SSH"
+    }
+
+    fn priority(&self) -> u8 {
+        55 // Between bookmarks and clipboard
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Ssh])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let (has_keyword, host_query) = Self::strip_keyword(query.query.trim());
+
+        // Don't search if query is too short, unless it asks for SSH explicitly
+        if !has_keyword && host_query.len() < 2 {
+            return Ok(Vec::new());
+        }
+
+        let targets = self.targets.read().await;
+        let query_normalized = normalize_for_search(host_query);
+
+        let mut results: Vec<(&SshTarget, f64)> = targets
+            .iter()
+            .filter_map(|target| {
+                if query_normalized.is_empty() {
+                    return Some((target, 50.0));
+                }
+
+                target
+                    .names()
+                    .into_iter()
+                    .filter_map(|name| Self::match_score(&query_normalized, &normalize_for_search(name)))
+                    .fold(None, |best: Option<f64>, score| Some(best.map_or(score, |b| b.max(score))))
+                    .map(|score| (target, score))
+            })
+            .collect();
+
+        // Sort by score (highest first)
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(MAX_RESULTS);
+
+        Ok(results
+            .into_iter()
+            .map(|(target, score)| Self::create_search_result(target, score))
+            .collect())
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::Ssh {
+            return Err(LauncherError::ExecutionError(
+                "Not an SSH result".to_string(),
+            ));
+        }
+
+        let commands = {
+            let targets = self.targets.read().await;
+            targets
+                .iter()
+                .find(|target| target.id() == result.id)
+                .map(SshTarget::launch_commands)
+        };
+
+        // Fall back to the result's own command if the cache has moved on
+        let commands = match (commands, &result.action) {
+            (Some(commands), _) => commands,
+            (None, ResultAction::ExecuteCommand { command, args }) => {
+                vec![LaunchCommand::new(command, args.clone())]
+            }
+            (None, _) => {
+                return Err(LauncherError::ExecutionError(
+                    "Invalid SSH action".to_string(),
+                ))
+            }
+        };
+
+        info!("Opening SSH connection: {}", result.title);
+        Self::launch(commands).await
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        SSH_SYNTAX.to_vec()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("Initializing SshProvider");
+
+        let targets = tokio::task::spawn_blocking(Self::load_targets)
+            .await
+            .map_err(|e| LauncherError::ProviderError(format!("Failed to spawn SSH load task: {}", e)))?;
+
+        info!("SshProvider initialized with {} hosts", targets.len());
+        *self.targets.write().await = targets;
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        info!("Shutting down SshProvider");
+        Ok(())
+    }
+}
+
+impl Default for SshProvider {
+    fn default() -> Self {
+        Self::new().unwrap_or_else(|_| Self {
+            targets: Arc::new(RwLock::new(Vec::new())),
+            enabled: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_ssh_dir(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn host(alias: &str, host_name: &str, user: Option<&str>, port: u16) -> SshHost {
+        SshHost {
+            alias: alias.to_string(),
+            other_aliases: Vec::new(),
+            host_name: host_name.to_string(),
+            user: user.map(str::to_string),
+            port,
+        }
+    }
+
+    #[test]
+    fn test_parse_multiple_hosts_and_comments() {
+        let config = "\
+# Work machines
+Host prod-db prod-db.internal
+    HostName 10.0.0.5
+    User admin
+    Port 2222
+
+Host staging
+  hostname=staging.example.com
+  User = deploy
+
+# Host commented-out
+Host bastion
+    Port not-a-number
+";
+
+        let hosts = SshConfigParser::parse_str(config, Path::new("."));
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, vec!["prod-db", "staging", "bastion"]);
+
+        assert_eq!(
+            hosts[0],
+            SshHost {
+                other_aliases: vec!["prod-db.internal".to_string()],
+                ..host("prod-db", "10.0.0.5", Some("admin"), 2222)
+            }
+        );
+        assert_eq!(hosts[1].host_name, "staging.example.com");
+        assert_eq!(hosts[1].user.as_deref(), Some("deploy"));
+
+        // An unparsable port falls back to the default instead of dropping the host
+        assert_eq!(hosts[2], host("bastion", "bastion", None, 22));
+    }
+
+    #[test]
+    fn test_wildcard_hosts_excluded_but_applied() {
+        let config = "\
+Host web-?
+    User www
+
+Host web-1 !web-2
+    HostName %h.example.com
+
+Host *.internal !secret.internal
+    Port 2200
+
+Host *
+    User fallback
+    Port 22
+";
+
+        let hosts = SshConfigParser::parse_str(config, Path::new("."));
+        assert_eq!(hosts, vec![host("web-1", "web-1.example.com", Some("www"), 22)]);
+
+        let config = "\
+Host db.internal secret.internal
+Host *.internal !secret.internal
+    Port 2200
+";
+        let hosts = SshConfigParser::parse_str(config, Path::new("."));
+        assert_eq!(hosts[0].port, 2200);
+        assert_eq!(hosts[0].other_aliases, vec!["secret.internal".to_string()]);
+    }
+
+    #[test]
+    fn test_first_value_wins_across_blocks() {
+        let config = "\
+User global-user
+
+Host box
+    User box-user
+    Port 2022
+";
+        let hosts = SshConfigParser::parse_str(config, Path::new("."));
+        assert_eq!(hosts, vec![host("box", "box", Some("global-user"), 2022)]);
+    }
+
+    #[test]
+    fn test_include_directives() {
+        let dir = temp_ssh_dir("ssh_include");
+        std::fs::create_dir_all(dir.join("config.d")).unwrap();
+        std::fs::write(dir.join("config.d").join("10-work"), "Host work\n    HostName work.example.com\n").unwrap();
+        std::fs::write(dir.join("config.d").join("20-home"), "Host home\n    User me\n").unwrap();
+        std::fs::write(dir.join("extra"), "Host extra\n").unwrap();
+        std::fs::write(
+            dir.join("config"),
+            "Include config.d/*\nInclude extra missing-file\n\nHost main\n    Match host foo\n",
+        )
+        .unwrap();
+
+        let hosts = SshConfigParser::parse_file(&dir.join("config")).unwrap();
+        let aliases: Vec<&str> = hosts.iter().map(|h| h.alias.as_str()).collect();
+        assert_eq!(aliases, vec!["work", "home", "extra", "main"]);
+        assert_eq!(hosts[0].host_name, "work.example.com");
+        assert_eq!(hosts[1].user.as_deref(), Some("me"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_include_recursion_is_bounded() {
+        let dir = temp_ssh_dir("ssh_include_loop");
+        std::fs::write(dir.join("config"), "Host looped\nInclude config\n").unwrap();
+
+        let hosts = SshConfigParser::parse_file(&dir.join("config")).unwrap();
+        assert_eq!(hosts.len(), 1);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("web-?", "WEB-1"));
+        assert!(!glob_match("web-?", "web-10"));
+        assert!(glob_match("*.example.*", "db.example.com"));
+        assert!(!glob_match("*.example.com", "example.com"));
+    }
+
+    #[test]
+    fn test_ssh_launch_commands() {
+        let target = SshTarget::Config(host("prod-db", "10.0.0.5", Some("admin"), 2222));
+        let commands = target.launch_commands();
+
+        assert_eq!(
+            commands,
+            vec![
+                LaunchCommand::new("wt", vec!["ssh".into(), "admin@10.0.0.5".into(), "-p".into(), "2222".into()]),
+                LaunchCommand::new("cmd", vec!["/K".into(), "ssh".into(), "admin@10.0.0.5".into(), "-p".into(), "2222".into()]),
+            ]
+        );
+
+        let target = SshTarget::Config(host("box", "box.lan", None, 22));
+        assert_eq!(target.launch_commands()[0].args, vec!["ssh", "box.lan", "-p", "22"]);
+    }
+
+    #[test]
+    fn test_putty_launch_command() {
+        let session = PuttySession::from_key_name("My%20Server%2Fprod");
+        assert_eq!(session.name, "My Server/prod");
+
+        let commands = SshTarget::Putty(session).launch_commands();
+        assert_eq!(
+            commands,
+            vec![LaunchCommand::new("putty", vec!["-load".into(), "My Server/prod".into()])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_with_and_without_keyword() {
+        let provider = SshProvider::new().unwrap();
+        *provider.targets.write().await = vec![
+            SshTarget::Config(host("prod-db", "10.0.0.5", Some("admin"), 2222)),
+            SshTarget::Config(host("staging", "staging.example.com", None, 22)),
+            SshTarget::Putty(PuttySession {
+                host_name: Some("router.lan".to_string()),
+                ..PuttySession::from_key_name("Home%20Router")
+            }),
+        ];
+
+        let results = provider.search(&"ssh prod-db".into()).await.unwrap();
+        assert_eq!(results[0].title, "prod-db");
+        assert_eq!(results[0].score, 100.0);
+        assert_eq!(results[0].result_type, ResultType::Ssh);
+        assert_eq!(results[0].metadata["host"], "10.0.0.5");
+        assert_eq!(results[0].metadata["user"], "admin");
+        assert_eq!(results[0].metadata["port"], 2222);
+
+        let results = provider.search(&"router".into()).await.unwrap();
+        assert_eq!(results[0].title, "Home Router");
+        assert_eq!(results[0].metadata["source"], "putty");
+
+        // The keyword alone lists every host
+        assert_eq!(provider.search(&"ssh".into()).await.unwrap().len(), 3);
+        assert!(provider.search(&"s".into()).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,506 @@
+/// Text tools provider for instant answers about a piece of text
+///
+/// `count <text>` counts the characters, words and lines of the rest of the
+/// query; `count clip` does the same for the text on the clipboard. Enter
+/// copies a one-line summary, and the actions menu offers the full numbers
+/// as JSON.
+///
+/// Words are runs of non-whitespace, except that Chinese and Japanese
+/// characters count as one word each, as word processors count them.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature, SECONDARY_ACTIONS_KEY};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::clipboard::{set_clipboard_text, ClipboardSource, SystemClipboard};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+/// Identifier of the action copying the statistics as JSON
+pub const COPY_JSON_ACTION_ID: &str = "copy_stats_json";
+
+/// Text longer than this, in bytes, is counted off the async runtime
+const LARGE_TEXT_BYTES: usize = 1024 * 1024;
+
+/// Reading speed used for the reading time estimate
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Words following `count` that count the clipboard instead of themselves
+const CLIPBOARD_WORDS: &[&str] = &["clip", "clipboard"];
+
+/// Counts of a piece of text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextStats {
+    pub characters: usize,
+    pub characters_no_spaces: usize,
+    pub words: usize,
+    pub lines: usize,
+    /// Estimated at 200 words a minute, rounded up
+    pub reading_time_seconds: usize,
+}
+
+impl TextStats {
+    /// Counts `text` in one pass
+    pub fn count(text: &str) -> Self {
+        let mut counter = TextCounter::default();
+        counter.feed(text);
+        counter.finish()
+    }
+
+    /// Reading time for display, e.g. "3 min read"
+    fn reading_time(&self) -> String {
+        match self.reading_time_seconds.div_ceil(60) {
+            0 => "no reading time".to_string(),
+            1 if self.reading_time_seconds < 60 => "< 1 min read".to_string(),
+            minutes => format!("{} min read", group_digits(minutes)),
+        }
+    }
+
+    /// One-line summary, the text Enter copies
+    pub fn summary(&self) -> String {
+        format!(
+            "{}, {} ({} without spaces), {}, {}",
+            counted(self.words, "word"),
+            counted(self.characters, "character"),
+            group_digits(self.characters_no_spaces),
+            counted(self.lines, "line"),
+            self.reading_time()
+        )
+    }
+}
+
+/// Counts text fed to it in pieces, without keeping any of it
+///
+/// Pieces must split the text between characters, which any `&str` does.
+#[derive(Debug, Default)]
+pub struct TextCounter {
+    stats: TextStats,
+    newlines: usize,
+    in_word: bool,
+    last_char: Option<char>,
+}
+
+impl TextCounter {
+    /// Counts the next piece of text
+    pub fn feed(&mut self, chunk: &str) {
+        for c in chunk.chars() {
+            self.stats.characters += 1;
+            self.last_char = Some(c);
+
+            if c.is_whitespace() {
+                if c == '\n' {
+                    self.newlines += 1;
+                }
+                self.in_word = false;
+                continue;
+            }
+
+            self.stats.characters_no_spaces += 1;
+            if is_cjk(c) {
+                self.stats.words += 1;
+                self.in_word = false;
+            } else if is_cjk_punctuation(c) {
+                self.in_word = false;
+            } else if !self.in_word {
+                self.stats.words += 1;
+                self.in_word = true;
+            }
+        }
+    }
+
+    /// Returns the counts of everything fed so far
+    pub fn finish(self) -> TextStats {
+        // A final line without a line break still counts
+        let unterminated = self.last_char.is_some_and(|c| c != '\n');
+        TextStats {
+            lines: self.newlines + usize::from(unterminated),
+            reading_time_seconds: (self.stats.words * 60).div_ceil(WORDS_PER_MINUTE),
+            ..self.stats
+        }
+    }
+}
+
+/// Whether `c` is written without spaces between words: Chinese characters,
+/// kana, and their compatibility forms
+///
+/// Hangul is left out, as Korean separates words with spaces.
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        // Hiragana, katakana and their phonetic extensions
+        '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}'
+        // Ideographs, including extension A, compatibility and supplementary ones
+        | '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FA1F}'
+    ) && c != '\u{30FB}' // Katakana middle dot
+}
+
+/// Whether `c` is punctuation used in Chinese and Japanese text, such as
+/// `。` or `，`, which ends a word without being one
+fn is_cjk_punctuation(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3000}'..='\u{303F}' | '\u{30FB}' | '\u{FF01}'..='\u{FF0F}' | '\u{FF1A}'..='\u{FF20}'
+        | '\u{FF3B}'..='\u{FF40}' | '\u{FF5B}'..='\u{FF65}'
+    )
+}
+
+/// Formats a number with thousands separators, e.g. "12,345"
+fn group_digits(value: usize) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Formats a count with its noun, e.g. "1 word" or "2,000 words"
+fn counted(value: usize, noun: &str) -> String {
+    format!("{} {}{}", group_digits(value), noun, if value == 1 { "" } else { "s" })
+}
+
+/// Counts `text`, on a blocking thread when it is large
+pub async fn count_text(text: String) -> Result<TextStats> {
+    if text.len() <= LARGE_TEXT_BYTES {
+        return Ok(TextStats::count(&text));
+    }
+
+    debug!("Counting {} bytes of text on a blocking thread", text.len());
+    tokio::task::spawn_blocking(move || TextStats::count(&text))
+        .await
+        .map_err(|e| LauncherError::SearchError(format!("Failed to count text: {}", e)))
+}
+
+/// The text a `count` query is about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CountQuery {
+    /// The rest of the query
+    Text(String),
+    /// The text on the clipboard
+    Clipboard,
+}
+
+impl CountQuery {
+    /// Parses `count <text>` or `count clip`; the command is case-insensitive
+    pub fn parse(query: &str) -> Option<Self> {
+        let query = query.trim_start();
+        let (command, rest) = query.split_once(char::is_whitespace)?;
+        if !command.eq_ignore_ascii_case("count") {
+            return None;
+        }
+
+        let text = rest.trim();
+        if text.is_empty() {
+            None
+        } else if CLIPBOARD_WORDS.iter().any(|word| text.eq_ignore_ascii_case(word)) {
+            Some(Self::Clipboard)
+        } else {
+            Some(Self::Text(text.to_string()))
+        }
+    }
+}
+
+/// Text tools search provider
+pub struct TextToolsProvider {
+    clipboard: Arc<dyn ClipboardSource>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl TextToolsProvider {
+    /// Creates a new TextToolsProvider reading the system clipboard
+    pub fn new() -> Result<Self> {
+        info!("Initializing TextToolsProvider");
+        Ok(Self::default())
+    }
+
+    /// Reads the clipboard from `clipboard` instead
+    pub fn with_clipboard(mut self, clipboard: Arc<dyn ClipboardSource>) -> Self {
+        self.clipboard = clipboard;
+        self
+    }
+
+    /// Wraps the statistics of the text in a result
+    fn create_search_result(&self, stats: &TextStats, from_clipboard: bool) -> SearchResult {
+        let source = if from_clipboard { "Clipboard" } else { "Text" };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!("count"));
+        metadata.insert("stats".to_string(), serde_json::json!(stats));
+        metadata.insert(
+            SECONDARY_ACTIONS_KEY.to_string(),
+            serde_json::json!([{
+                "id": COPY_JSON_ACTION_ID,
+                "title": "Copy as JSON",
+                "requires_confirmation": false,
+            }]),
+        );
+
+        SearchResult {
+            id: format!("text:count:{}", source.to_lowercase()),
+            title: format!("{} · {}", counted(stats.words, "word"), counted(stats.characters, "character")),
+            subtitle: format!(
+                "{}: {} without spaces · {} · {} · Enter to copy",
+                source,
+                group_digits(stats.characters_no_spaces),
+                counted(stats.lines, "line"),
+                stats.reading_time()
+            ),
+            icon: None,
+            result_type: ResultType::Text,
+            score: 100.0,
+            metadata,
+            action: ResultAction::CopyToClipboard { content: stats.summary() },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Reads the clipboard, treating a failed read like an empty clipboard
+    async fn clipboard_text(&self) -> Option<String> {
+        match self.clipboard.read_text().await {
+            Ok(text) => text.filter(|text| !text.is_empty()),
+            Err(e) => {
+                warn!("Failed to read clipboard for counting: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for TextToolsProvider {
+    fn name(&self) -> &str {
+        "Text Tools"
+    }
+
+    fn priority(&self) -> u8 {
+        86 // Just below the generator
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Text])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let (text, from_clipboard) = match CountQuery::parse(query.full_text()) {
+            Some(CountQuery::Text(text)) => (text, false),
+            Some(CountQuery::Clipboard) => match self.clipboard_text().await {
+                Some(text) => (text, true),
+                None => return Ok(Vec::new()),
+            },
+            None => return Ok(Vec::new()),
+        };
+
+        let stats = count_text(text).await?;
+        Ok(vec![self.create_search_result(&stats, from_clipboard)])
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if result.result_type != ResultType::Text {
+            return Err(LauncherError::ExecutionError(
+                "Not a text tools result".to_string(),
+            ));
+        }
+
+        match &result.action {
+            ResultAction::CopyToClipboard { content } => set_clipboard_text(content).await,
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for text tools result".to_string(),
+            )),
+        }
+    }
+
+    async fn execute_action(&self, result: &SearchResult, action_id: &str) -> Result<()> {
+        if result.result_type != ResultType::Text || action_id != COPY_JSON_ACTION_ID {
+            return Err(LauncherError::NotFound(format!("Text Tools has no action '{}'", action_id)));
+        }
+
+        let stats: TextStats = result
+            .metadata
+            .get("stats")
+            .and_then(|stats| serde_json::from_value(stats.clone()).ok())
+            .ok_or_else(|| LauncherError::ExecutionError("Result has no text statistics".to_string()))?;
+        let json = serde_json::to_string_pretty(&stats)
+            .map_err(|e| LauncherError::ExecutionError(format!("Failed to serialize statistics: {}", e)))?;
+        set_clipboard_text(&json).await
+    }
+
+    fn cacheable(&self) -> bool {
+        // `count clip` must see the clipboard as it is now
+        false
+    }
+
+    fn handles_blobs(&self) -> bool {
+        true
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![SyntaxFeature {
+            id: "count",
+            title: "Count text",
+            description: "count <text> or count clip: characters, words, lines and reading time",
+            example: "count clip",
+            token: None,
+            values: &[],
+        }]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        info!("TextToolsProvider initialized");
+        Ok(())
+    }
+}
+
+impl Default for TextToolsProvider {
+    fn default() -> Self {
+        Self {
+            clipboard: Arc::new(SystemClipboard),
+            enabled: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clipboard holding fixed content
+    struct FixedClipboard(Option<String>);
+
+    #[async_trait]
+    impl ClipboardSource for FixedClipboard {
+        async fn read_text(&self) -> Result<Option<String>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn with_clipboard(content: Option<&str>) -> TextToolsProvider {
+        TextToolsProvider::default().with_clipboard(Arc::new(FixedClipboard(content.map(str::to_string))))
+    }
+
+    #[test]
+    fn test_parse_count_queries() {
+        assert_eq!(CountQuery::parse("count hello world"), Some(CountQuery::Text("hello world".to_string())));
+        assert_eq!(CountQuery::parse("Count  Hello "), Some(CountQuery::Text("Hello".to_string())));
+        assert_eq!(CountQuery::parse("count clip"), Some(CountQuery::Clipboard));
+        assert_eq!(CountQuery::parse("count CLIPBOARD"), Some(CountQuery::Clipboard));
+        assert_eq!(CountQuery::parse("count clip art"), Some(CountQuery::Text("clip art".to_string())));
+
+        assert_eq!(CountQuery::parse("count"), None);
+        assert_eq!(CountQuery::parse("count   "), None);
+        assert_eq!(CountQuery::parse("counter strike"), None);
+        assert_eq!(CountQuery::parse("word count"), None);
+    }
+
+    #[test]
+    fn test_counting_rules_on_mixed_languages() {
+        let stats = TextStats::count("Hello, world!\nthis is\tfine  ");
+        assert_eq!(stats.words, 5);
+        assert_eq!(stats.characters, 28);
+        assert_eq!(stats.characters_no_spaces, 22);
+        assert_eq!(stats.lines, 2);
+
+        // Each Chinese or Japanese character is a word; ideographic punctuation isn't
+        assert_eq!(TextStats::count("我爱北京。").words, 4);
+        assert_eq!(TextStats::count("你好，世界！").words, 4);
+        assert_eq!(TextStats::count("東京タワーへ行く").words, 8);
+        assert_eq!(TextStats::count("Rust编程 is fun").words, 5);
+        // Korean and accented Latin words are separated by spaces
+        assert_eq!(TextStats::count("안녕하세요 세계").words, 2);
+        assert_eq!(TextStats::count("café naïve résumé").words, 3);
+        // Characters are code points, not bytes
+        assert_eq!(TextStats::count("café").characters, 4);
+
+        // Ideographic spaces separate words too
+        assert_eq!(TextStats::count("one\u{3000}two").words, 2);
+
+        assert_eq!(TextStats::count("a\r\nb\r\n").lines, 2);
+        assert_eq!(TextStats::count("a\n\nb").lines, 3);
+        assert_eq!(TextStats::count(""), TextStats::default());
+    }
+
+    #[test]
+    fn test_reading_time_and_summary() {
+        let stats = TextStats::count(&"word ".repeat(450));
+        assert_eq!(stats.reading_time_seconds, 135);
+        assert_eq!(stats.reading_time(), "3 min read");
+        assert_eq!(TextStats::count("one two").reading_time(), "< 1 min read");
+
+        let summary = TextStats::count("one").summary();
+        assert_eq!(summary, "1 word, 3 characters (3 without spaces), 1 line, < 1 min read");
+        assert_eq!(group_digits(1234567), "1,234,567");
+        assert_eq!(group_digits(999), "999");
+    }
+
+    #[test]
+    fn test_chunked_feeding_matches_single_pass() {
+        let text = "Hello 世界, this is\na test of 東京 counting.\n";
+        let whole = TextStats::count(text);
+
+        // Split between every pair of characters, including inside words
+        for (split, _) in text.char_indices().skip(1) {
+            let mut counter = TextCounter::default();
+            counter.feed(&text[..split]);
+            counter.feed(&text[split..]);
+            assert_eq!(counter.finish(), whole, "split at {}", split);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_large_input_counted_off_runtime() {
+        let line = "The quick brown fox 跳 over the lazy dog\n";
+        let repeats = LARGE_TEXT_BYTES / line.len() + 100;
+        let text = line.repeat(repeats);
+        assert!(text.len() > LARGE_TEXT_BYTES);
+
+        let stats = count_text(text).await.unwrap();
+        assert_eq!(stats.words, repeats * 9);
+        assert_eq!(stats.lines, repeats);
+        assert_eq!(stats.characters, repeats * line.chars().count());
+    }
+
+    #[tokio::test]
+    async fn test_count_typed_text() {
+        let provider = with_clipboard(Some("ignored"));
+        let result = provider.search(&"count two words".into()).await.unwrap().remove(0);
+
+        assert_eq!(result.result_type, ResultType::Text);
+        assert_eq!(result.title, "2 words · 9 characters");
+        assert_eq!(result.metadata["stats"]["words"], 2);
+        assert_eq!(result.metadata[SECONDARY_ACTIONS_KEY][0]["id"], COPY_JSON_ACTION_ID);
+        let ResultAction::CopyToClipboard { content } = &result.action else {
+            panic!("Expected CopyToClipboard action");
+        };
+        assert_eq!(content, "2 words, 9 characters (8 without spaces), 1 line, < 1 min read");
+
+        assert!(provider.search(&"notepad".into()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_count_clipboard() {
+        let provider = with_clipboard(Some("first line\nsecond line\n"));
+        let result = provider.search(&"count clip".into()).await.unwrap().remove(0);
+
+        assert_eq!(result.id, "text:count:clipboard");
+        assert!(result.subtitle.starts_with("Clipboard:"));
+        assert_eq!(result.metadata["stats"]["words"], 4);
+        assert_eq!(result.metadata["stats"]["lines"], 2);
+
+        // Nothing to count without text on the clipboard
+        for empty in [None, Some("")] {
+            let provider = with_clipboard(empty);
+            assert!(provider.search(&"count clip".into()).await.unwrap().is_empty());
+        }
+    }
+}
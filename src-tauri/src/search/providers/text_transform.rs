@@ -0,0 +1,650 @@
+/// Text transform provider for quick conversions typed into the launcher
+///
+/// `upper`, `lower` and `title` change case, `slug` makes a URL slug, and
+/// `url`, `b64` and `json` followed by `encode`/`decode` (or `escape`/
+/// `unescape` for JSON) convert the rest of the query. Enter copies the
+/// output. `regex <pattern> on <text>` lists each match as its own result;
+/// the pattern and text may be quoted, so either can contain " on ".
+///
+/// Everything is computed from the query alone, so results are never cached.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::hash::TONE_KEY;
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::clipboard::set_clipboard_text;
+use crate::utils::normalize_for_search;
+use async_trait::async_trait;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+use base64::Engine as _;
+use regex::RegexBuilder;
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+/// Input longer than this, in characters, is not transformed
+const MAX_INPUT_CHARS: usize = 4096;
+
+/// Longest regex pattern accepted, in characters
+const MAX_PATTERN_CHARS: usize = 256;
+
+/// Memory a compiled regex may use, so patterns like `(a{100}){100}` fail to compile
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Matches listed for one regex
+const MAX_MATCHES: usize = 20;
+
+/// Characters of the output shown in a result title
+const MAX_TITLE_CHARS: usize = 120;
+
+/// Bytes of binary output shown as hex in a result title
+const HEX_PREVIEW_BYTES: usize = 24;
+
+/// Score of a transform result; a typed keyword is an explicit request
+const TRANSFORM_SCORE: f64 = 100.0;
+
+/// A conversion of the whole input text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    Upper,
+    Lower,
+    Title,
+    Slug,
+    UrlEncode,
+    UrlDecode,
+    Base64Encode,
+    Base64Decode,
+    JsonEscape,
+    JsonUnescape,
+}
+
+/// What a transform produced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    Text(String),
+    /// Decoded bytes that aren't UTF-8
+    Binary(Vec<u8>),
+}
+
+impl Transform {
+    /// Id used in result ids
+    fn id(self) -> &'static str {
+        match self {
+            Self::Upper => "upper",
+            Self::Lower => "lower",
+            Self::Title => "title",
+            Self::Slug => "slug",
+            Self::UrlEncode => "url_encode",
+            Self::UrlDecode => "url_decode",
+            Self::Base64Encode => "b64_encode",
+            Self::Base64Decode => "b64_decode",
+            Self::JsonEscape => "json_escape",
+            Self::JsonUnescape => "json_unescape",
+        }
+    }
+
+    /// Name shown in the result subtitle
+    fn label(self) -> &'static str {
+        match self {
+            Self::Upper => "Uppercase",
+            Self::Lower => "Lowercase",
+            Self::Title => "Title case",
+            Self::Slug => "Slug",
+            Self::UrlEncode => "URL encoded",
+            Self::UrlDecode => "URL decoded",
+            Self::Base64Encode => "Base64 encoded",
+            Self::Base64Decode => "Base64 decoded",
+            Self::JsonEscape => "JSON escaped",
+            Self::JsonUnescape => "JSON unescaped",
+        }
+    }
+
+    /// Applies the transform, failing on input that can't be decoded
+    pub fn apply(self, text: &str) -> std::result::Result<Output, String> {
+        let output = match self {
+            Self::Upper => text.to_uppercase(),
+            Self::Lower => text.to_lowercase(),
+            Self::Title => title_case(text),
+            Self::Slug => slugify(text),
+            Self::UrlEncode => urlencoding::encode(text).into_owned(),
+            Self::UrlDecode => return Ok(bytes_output(urlencoding::decode_binary(text.as_bytes()).into_owned())),
+            Self::Base64Encode => STANDARD.encode(text),
+            Self::Base64Decode => return decode_base64(text).map(bytes_output),
+            Self::JsonEscape => {
+                let quoted = serde_json::to_string(text).map_err(|e| e.to_string())?;
+                quoted[1..quoted.len() - 1].to_string()
+            }
+            Self::JsonUnescape => {
+                let quoted = if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+                    text.to_string()
+                } else {
+                    format!("\"{}\"", text)
+                };
+                serde_json::from_str::<String>(&quoted).map_err(|_| "Not a valid JSON string".to_string())?
+            }
+        };
+        Ok(Output::Text(output))
+    }
+}
+
+/// Capitalizes the first letter of each word and lowercases the rest
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut word_start = true;
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            if word_start {
+                result.extend(c.to_uppercase());
+            } else {
+                result.extend(c.to_lowercase());
+            }
+            word_start = false;
+        } else {
+            result.push(c);
+            word_start = c.is_whitespace() || c == '-';
+        }
+    }
+    result
+}
+
+/// Lowercase words without accents joined by hyphens, e.g. "creme-brulee"
+fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in normalize_for_search(text).chars() {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Decodes standard or URL-safe base64, with or without padding
+fn decode_base64(text: &str) -> std::result::Result<Vec<u8>, String> {
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let padded = format!("{}{}", compact, "=".repeat((4 - compact.len() % 4) % 4));
+    STANDARD
+        .decode(&padded)
+        .or_else(|_| URL_SAFE.decode(&padded))
+        .map_err(|_| "Not valid base64".to_string())
+}
+
+/// Text when the bytes are UTF-8, binary otherwise
+fn bytes_output(bytes: Vec<u8>) -> Output {
+    match String::from_utf8(bytes) {
+        Ok(text) => Output::Text(text),
+        Err(e) => Output::Binary(e.into_bytes()),
+    }
+}
+
+fn hex(bytes: &[u8], separator: &str) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(separator)
+}
+
+/// Cuts `text` to `max` characters for display
+fn preview(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max).collect::<String>())
+    }
+}
+
+/// A parsed transform query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransformQuery {
+    Transform(Transform, String),
+    Regex { pattern: String, text: String },
+}
+
+impl TransformQuery {
+    /// Parses `upper <text>`, `b64 decode <text>`, `regex <pattern> on <text>` and the like
+    pub fn parse(query: &str) -> Option<Self> {
+        let (keyword, rest) = query.trim().split_once(char::is_whitespace)?;
+        let rest = rest.trim_start();
+        let keyword = keyword.to_lowercase();
+
+        let simple = match keyword.as_str() {
+            "upper" | "uppercase" => Some(Transform::Upper),
+            "lower" | "lowercase" => Some(Transform::Lower),
+            "title" | "titlecase" => Some(Transform::Title),
+            "slug" | "slugify" => Some(Transform::Slug),
+            _ => None,
+        };
+        if let Some(transform) = simple {
+            return Some(Self::Transform(transform, rest.to_string()));
+        }
+
+        if keyword == "regex" {
+            let (pattern, text) = parse_regex(rest)?;
+            return Some(Self::Regex { pattern, text });
+        }
+
+        let (direction, text) = rest.split_once(char::is_whitespace)?;
+        let transform = match (keyword.as_str(), direction.to_lowercase().as_str()) {
+            ("url", "encode") => Transform::UrlEncode,
+            ("url", "decode") => Transform::UrlDecode,
+            ("b64" | "base64", "encode") => Transform::Base64Encode,
+            ("b64" | "base64", "decode") => Transform::Base64Decode,
+            ("json", "escape") => Transform::JsonEscape,
+            ("json", "unescape") => Transform::JsonUnescape,
+            _ => return None,
+        };
+        Some(Self::Transform(transform, text.trim_start().to_string()))
+    }
+}
+
+/// Splits off a leading double-quoted part, returning it and what follows
+fn take_quoted(text: &str) -> Option<(&str, &str)> {
+    let inner = text.strip_prefix('"')?;
+    let close = inner.find('"')?;
+    Some((&inner[..close], &inner[close + 1..]))
+}
+
+/// Removes the quotes around text quoted as a whole
+fn unquote(text: &str) -> &str {
+    match take_quoted(text) {
+        Some((inner, "")) => inner,
+        _ => text,
+    }
+}
+
+/// Parses `<pattern> on <text>`
+///
+/// An unquoted pattern ends at the first " on "; a quoted one at its closing
+/// quote. The text may be quoted too.
+fn parse_regex(rest: &str) -> Option<(String, String)> {
+    let (pattern, after) = match take_quoted(rest) {
+        Some((pattern, after)) => {
+            let after = after.trim_start();
+            let after = after.strip_prefix("on ").or_else(|| after.strip_prefix("ON "))?;
+            (pattern, after)
+        }
+        None => {
+            let split = rest.find(" on ").or_else(|| rest.find(" ON "))?;
+            (&rest[..split], &rest[split + 4..])
+        }
+    };
+
+    let text = unquote(after.trim());
+    (!pattern.is_empty() && !text.is_empty()).then(|| (pattern.to_string(), text.to_string()))
+}
+
+/// Text transform search provider
+pub struct TextTransformProvider {
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl TextTransformProvider {
+    /// Creates a new TextTransformProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing TextTransformProvider");
+        Ok(Self { enabled: true })
+    }
+
+    /// A result copying `content` when executed
+    fn copy_result(id: String, title: String, subtitle: String, content: String, kind: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!(kind));
+
+        SearchResult {
+            id,
+            title,
+            subtitle,
+            icon: None,
+            result_type: ResultType::Text,
+            score: TRANSFORM_SCORE,
+            metadata,
+            action: ResultAction::CopyToClipboard { content },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// A result explaining why nothing could be produced; selecting it keeps the query
+    fn notice_result(id: String, title: String, subtitle: String, query: &str) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!("notice"));
+
+        SearchResult {
+            id,
+            title,
+            subtitle,
+            icon: None,
+            result_type: ResultType::Text,
+            score: TRANSFORM_SCORE,
+            metadata,
+            action: ResultAction::SetQuery { query: query.to_string() },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// A notice for input or a pattern that can't be used, shown in red
+    fn error_result(id: String, title: String, subtitle: String, query: &str) -> SearchResult {
+        let mut result = Self::notice_result(id, title, subtitle, query);
+        result.metadata.insert(TONE_KEY.to_string(), serde_json::json!("danger"));
+        result
+    }
+
+    fn transform_results(transform: Transform, text: &str, query: &str) -> Vec<SearchResult> {
+        let id = format!("text:transform:{}", transform.id());
+        match transform.apply(text) {
+            Ok(Output::Text(output)) => vec![Self::copy_result(
+                id,
+                preview(&output, MAX_TITLE_CHARS),
+                format!("{} · {} characters · Enter to copy", transform.label(), output.chars().count()),
+                output,
+                "transform",
+            )],
+            Ok(Output::Binary(bytes)) => vec![Self::copy_result(
+                id,
+                preview(&hex(&bytes[..bytes.len().min(HEX_PREVIEW_BYTES + 1)], " "), HEX_PREVIEW_BYTES * 3 - 1),
+                format!("{} · {} bytes of binary data · Enter to copy as hex", transform.label(), bytes.len()),
+                hex(&bytes, ""),
+                "transform",
+            )],
+            Err(error) => vec![Self::error_result(id, error, transform.label().to_string(), query)],
+        }
+    }
+
+    fn regex_results(pattern: &str, text: &str, query: &str) -> Vec<SearchResult> {
+        if pattern.chars().count() > MAX_PATTERN_CHARS {
+            return vec![Self::error_result(
+                "text:regex:error".to_string(),
+                "Pattern too long".to_string(),
+                format!("Patterns can have at most {} characters", MAX_PATTERN_CHARS),
+                query,
+            )];
+        }
+
+        let regex = match RegexBuilder::new(pattern)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REGEX_SIZE_LIMIT)
+            .build()
+        {
+            Ok(regex) => regex,
+            Err(e) => {
+                debug!("Invalid regex '{}': {}", pattern, e);
+                let reason = e.to_string();
+                let reason = reason.lines().last().unwrap_or_default().trim().to_string();
+                return vec![Self::error_result(
+                    "text:regex:error".to_string(),
+                    "Invalid pattern".to_string(),
+                    reason,
+                    query,
+                )];
+            }
+        };
+
+        let matches: Vec<_> = regex.find_iter(text).filter(|m| !m.is_empty()).collect();
+        if matches.is_empty() {
+            return vec![Self::notice_result(
+                "text:regex:none".to_string(),
+                "No matches".to_string(),
+                format!("/{}/ found nothing in {} characters", pattern, text.chars().count()),
+                query,
+            )];
+        }
+
+        let total = matches.len();
+        matches
+            .into_iter()
+            .take(MAX_MATCHES)
+            .enumerate()
+            .map(|(index, found)| {
+                let mut result = Self::copy_result(
+                    format!("text:regex:{}", index),
+                    preview(found.as_str(), MAX_TITLE_CHARS),
+                    format!("Match {} of {} at {}–{} · Enter to copy", index + 1, total, found.start(), found.end()),
+                    found.as_str().to_string(),
+                    "regex",
+                );
+                // Keep matches in the order they appear
+                result.score -= index as f64 * 0.01;
+                result
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl SearchProvider for TextTransformProvider {
+    fn name(&self) -> &str {
+        "Text Transform"
+    }
+
+    fn priority(&self) -> u8 {
+        86 // Same as the other text tools
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Text])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let Some(parsed) = TransformQuery::parse(query.full_text()) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(match parsed {
+            TransformQuery::Transform(_, text) | TransformQuery::Regex { text, .. }
+                if text.is_empty() || text.chars().count() > MAX_INPUT_CHARS =>
+            {
+                Vec::new()
+            }
+            TransformQuery::Transform(transform, text) => Self::transform_results(transform, &text, &query.query),
+            TransformQuery::Regex { pattern, text } => Self::regex_results(&pattern, &text, &query.query),
+        })
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let kind = result.metadata.get("kind").and_then(|v| v.as_str());
+        if result.result_type != ResultType::Text || !matches!(kind, Some("transform" | "regex")) {
+            return Err(LauncherError::ExecutionError(
+                "Not a text transform result".to_string(),
+            ));
+        }
+
+        match &result.action {
+            ResultAction::CopyToClipboard { content } => set_clipboard_text(content).await,
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for text transform result".to_string(),
+            )),
+        }
+    }
+
+    fn cacheable(&self) -> bool {
+        // Outputs are cheap to compute and would only crowd the cache
+        false
+    }
+
+    fn handles_blobs(&self) -> bool {
+        true
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        vec![
+            SyntaxFeature {
+                id: "change_case",
+                title: "Change case",
+                description: "upper, lower or title followed by text",
+                example: "upper some text",
+                token: None,
+                values: &[],
+            },
+            SyntaxFeature {
+                id: "slug",
+                title: "Slugify",
+                description: "slug <text>: lowercase words without accents, joined by hyphens",
+                example: "slug My Blog Post Title!",
+                token: None,
+                values: &[],
+            },
+            SyntaxFeature {
+                id: "base64",
+                title: "Base64",
+                description: "b64 encode or b64 decode followed by text",
+                example: "b64 encode hello",
+                token: None,
+                values: &[],
+            },
+            SyntaxFeature {
+                id: "url_encode",
+                title: "URL encoding",
+                description: "url encode or url decode followed by text",
+                example: "url encode a b&c",
+                token: None,
+                values: &[],
+            },
+            SyntaxFeature {
+                id: "json_escape",
+                title: "JSON strings",
+                description: "json escape or json unescape followed by text",
+                example: "json escape say \"hi\"",
+                token: None,
+                values: &[],
+            },
+            SyntaxFeature {
+                id: "regex",
+                title: "Regex matches",
+                description: "regex <pattern> on <text>; quote either to include \" on \"",
+                example: "regex \\d+ on \"order 123 and 456\"",
+                token: None,
+                values: &[],
+            },
+        ]
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(output: std::result::Result<Output, String>) -> String {
+        match output {
+            Ok(Output::Text(text)) => text,
+            other => panic!("expected text, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_case_and_slug_transforms_handle_unicode() {
+        assert_eq!(text(Transform::Upper.apply("straße café")), "STRASSE CAFÉ");
+        assert_eq!(text(Transform::Lower.apply("ÀÉÎ Öl")), "àéî öl");
+        assert_eq!(text(Transform::Title.apply("élan VITAL of são-paulo")), "Élan Vital Of São-Paulo");
+        assert_eq!(text(Transform::Slug.apply("My Blog Post Title!")), "my-blog-post-title");
+        assert_eq!(text(Transform::Slug.apply("  Crème Brûlée — à la carte ")), "creme-brulee-a-la-carte");
+        assert_eq!(text(Transform::Slug.apply("東京 tower")), "東京-tower");
+    }
+
+    #[test]
+    fn test_encoding_transforms_round_trip() {
+        assert_eq!(text(Transform::UrlEncode.apply("a b&c=é")), "a%20b%26c%3D%C3%A9");
+        assert_eq!(text(Transform::UrlDecode.apply("a%20b%26c%3D%C3%A9")), "a b&c=é");
+
+        assert_eq!(text(Transform::Base64Encode.apply("hello")), "aGVsbG8=");
+        assert_eq!(text(Transform::Base64Decode.apply("aGVsbG8=")), "hello");
+        // Missing padding and the URL-safe alphabet are accepted
+        assert_eq!(text(Transform::Base64Decode.apply("w6k")), "é");
+        assert_eq!(text(Transform::Base64Decode.apply("aGk_")), "hi?");
+        assert!(Transform::Base64Decode.apply("not base64!").is_err());
+
+        assert_eq!(text(Transform::JsonEscape.apply("say \"hi\"\n\tàh")), "say \\\"hi\\\"\\n\\tàh");
+        assert_eq!(text(Transform::JsonUnescape.apply("say \\\"hi\\\"\\n\\u00e0")), "say \"hi\"\nà");
+        assert_eq!(text(Transform::JsonUnescape.apply("\"quoted\\tstring\"")), "quoted\tstring");
+        assert!(Transform::JsonUnescape.apply("bad \\x escape").is_err());
+    }
+
+    #[test]
+    fn test_binary_decode_shows_hex() {
+        // 0x89 'P' 'N' 'G' starts a PNG file and isn't UTF-8
+        assert_eq!(Transform::Base64Decode.apply("iVBORw=="), Ok(Output::Binary(vec![0x89, b'P', b'N', b'G'])));
+        assert_eq!(Transform::UrlDecode.apply("%FF%00"), Ok(Output::Binary(vec![0xff, 0x00])));
+
+        let results = TextTransformProvider::transform_results(Transform::Base64Decode, "iVBORw==", "b64 decode iVBORw==");
+        assert_eq!(results[0].title, "89 50 4e 47");
+        assert!(results[0].subtitle.contains("4 bytes of binary data"));
+        assert!(matches!(&results[0].action, ResultAction::CopyToClipboard { content } if content == "89504e47"));
+    }
+
+    #[test]
+    fn test_query_grammar() {
+        assert_eq!(
+            TransformQuery::parse("UPPER some text"),
+            Some(TransformQuery::Transform(Transform::Upper, "some text".to_string()))
+        );
+        assert_eq!(
+            TransformQuery::parse("b64 decode aGVsbG8="),
+            Some(TransformQuery::Transform(Transform::Base64Decode, "aGVsbG8=".to_string()))
+        );
+        assert_eq!(TransformQuery::parse("b64 hello"), None);
+        assert_eq!(TransformQuery::parse("upper"), None);
+        assert_eq!(TransformQuery::parse("uppercut moves"), None);
+
+        let regex = |query: &str| match TransformQuery::parse(query) {
+            Some(TransformQuery::Regex { pattern, text }) => Some((pattern, text)),
+            _ => None,
+        };
+        assert_eq!(
+            regex(r#"regex \d+ on "order 123 and 456""#),
+            Some((r"\d+".to_string(), "order 123 and 456".to_string()))
+        );
+        // Only the first " on " splits, so quoted or not the text keeps its own
+        assert_eq!(
+            regex(r#"regex \w+ on "go on and on""#),
+            Some((r"\w+".to_string(), "go on and on".to_string()))
+        );
+        assert_eq!(regex("regex o+ on carry on"), Some(("o+".to_string(), "carry on".to_string())));
+        assert_eq!(
+            regex(r#"regex "a on b" on "x a on b y""#),
+            Some(("a on b".to_string(), "x a on b y".to_string()))
+        );
+        assert_eq!(regex(r"regex \d+"), None);
+        assert_eq!(regex("regex  on text"), None);
+    }
+
+    #[tokio::test]
+    async fn test_regex_matches_are_separate_results() {
+        let provider = TextTransformProvider::new().unwrap();
+        let results = provider.search(&r#"regex \d+ on "order 123 and 456""#.into()).await.unwrap();
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["123", "456"]);
+        assert!(results[0].score > results[1].score);
+        assert!(results[1].subtitle.starts_with("Match 2 of 2 at 14–17"));
+
+        let none = provider.search(&"regex x on abc".into()).await.unwrap();
+        assert_eq!(none[0].title, "No matches");
+        assert!(matches!(none[0].action, ResultAction::SetQuery { .. }));
+
+        let invalid = provider.search(&"regex ( on abc".into()).await.unwrap();
+        assert_eq!(invalid[0].title, "Invalid pattern");
+        assert_eq!(invalid[0].metadata[TONE_KEY], "danger");
+
+        // Compiling this would take far more than the size limit
+        let huge = provider.search(&r"regex (\w{100}){100} on abc".into()).await.unwrap();
+        assert_eq!(huge[0].title, "Invalid pattern");
+
+        let long_text = format!("regex a on {}", "a".repeat(MAX_INPUT_CHARS + 1));
+        assert!(provider.search(&long_text.as_str().into()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_results_are_never_cached() {
+        let provider = TextTransformProvider::new().unwrap();
+        assert!(!provider.cacheable());
+
+        let results = provider.search(&"slug Hello World".into()).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "text:transform:slug");
+        assert!(matches!(&results[0].action, ResultAction::CopyToClipboard { content } if content == "hello-world"));
+        assert!(provider.search(&"hello world".into()).await.unwrap().is_empty());
+    }
+}
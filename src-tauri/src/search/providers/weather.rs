@@ -0,0 +1,767 @@
+/// Weather instant answers
+///
+/// `weather` shows the current temperature, conditions and today's high and
+/// low for the user's location, `weather lisbon` for a named city. Data comes
+/// from Open-Meteo, which needs no API key: cities are geocoded once and kept
+/// on disk, forecasts are reused for 15 minutes per location, and every call
+/// gives up after two seconds. Offline or failing lookups show no result
+/// rather than an error.
+///
+/// A bare `weather` asks Windows for the device position and falls back to
+/// the home city from settings when location access is off or unavailable.
+
+use crate::error::{LauncherError, Result};
+use crate::search::providers::bookmark::BookmarkProvider;
+use crate::search::{QueryContext, SearchProvider, SyntaxFeature};
+use crate::settings::TemperatureUnit;
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+/// How long a geocoding, location or forecast call may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long a forecast is reused for the same location
+const FORECAST_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Word that starts a weather query
+pub const WEATHER_KEYWORD: &str = "weather";
+
+/// Name shown for the position reported by Windows
+const CURRENT_LOCATION: &str = "Current location";
+
+const SYNTAX: &[SyntaxFeature] = &[SyntaxFeature {
+    id: "weather",
+    title: "Weather",
+    description: "Current conditions here, or in a city",
+    example: "weather lisbon",
+    token: None,
+    values: &[],
+}];
+
+/// Unit and home city from settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeatherPreferences {
+    pub unit: TemperatureUnit,
+    /// City used by a bare `weather` when the device position is unavailable
+    pub home_city: Option<String>,
+}
+
+/// The weather preferences in use
+///
+/// Clones share the preferences, so saving settings changes the unit and
+/// home city of the registered provider without re-registering it.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveWeatherPreferences(Arc<RwLock<WeatherPreferences>>);
+
+impl ActiveWeatherPreferences {
+    pub fn get(&self) -> WeatherPreferences {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn set(&self, preferences: WeatherPreferences) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = preferences;
+    }
+}
+
+/// A named point forecasts are fetched for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+impl Place {
+    /// Key shared by nearby positions, about a kilometre apart
+    fn cache_key(&self) -> String {
+        format!("{:.2},{:.2}", self.latitude, self.longitude)
+    }
+
+    /// Open-Meteo request for the current and daily values, always in Celsius
+    pub fn forecast_url(&self) -> String {
+        format!(
+            "https://api.open-meteo.com/v1/forecast?latitude={:.4}&longitude={:.4}\
+             &current=temperature_2m,weather_code&daily=temperature_2m_max,temperature_2m_min\
+             &timezone=auto&forecast_days=1",
+            self.latitude, self.longitude
+        )
+    }
+
+    /// Page with the detailed forecast, opened by the result
+    pub fn details_url(&self, unit: TemperatureUnit) -> String {
+        let unit = match unit {
+            TemperatureUnit::Celsius => "m",
+            TemperatureUnit::Fahrenheit => "e",
+        };
+        format!(
+            "https://weather.com/weather/tenday/l/{:.2},{:.2}?unit={}",
+            self.latitude, self.longitude, unit
+        )
+    }
+}
+
+/// Open-Meteo geocoding request for a city name
+pub fn geocoding_url(city: &str) -> String {
+    format!(
+        "https://geocoding-api.open-meteo.com/v1/search?name={}&count=1&language=en&format=json",
+        urlencoding::encode(city)
+    )
+}
+
+/// Reads the best match from a geocoding response
+pub fn place_from_geocoding(response: &serde_json::Value) -> Option<Place> {
+    let first = response.pointer("/results/0")?;
+    Some(Place {
+        name: first.get("name")?.as_str()?.to_string(),
+        latitude: first.get("latitude")?.as_f64()?,
+        longitude: first.get("longitude")?.as_f64()?,
+    })
+}
+
+/// Today's weather at a place, in Celsius
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditions {
+    pub temperature: f64,
+    pub high: f64,
+    pub low: f64,
+    /// WMO weather interpretation code
+    pub code: u8,
+}
+
+impl Conditions {
+    /// Reads the conditions from a `forecast_url` response
+    pub fn from_forecast(response: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            temperature: response.pointer("/current/temperature_2m")?.as_f64()?,
+            high: response.pointer("/daily/temperature_2m_max/0")?.as_f64()?,
+            low: response.pointer("/daily/temperature_2m_min/0")?.as_f64()?,
+            code: u8::try_from(response.pointer("/current/weather_code")?.as_u64()?).ok()?,
+        })
+    }
+
+    /// Describes the WMO code, e.g. "Partly cloudy"
+    pub fn description(&self) -> &'static str {
+        match self.code {
+            0 => "Clear sky",
+            1 => "Mainly clear",
+            2 => "Partly cloudy",
+            3 => "Overcast",
+            45 | 48 => "Fog",
+            51 | 53 | 55 => "Drizzle",
+            56 | 57 => "Freezing drizzle",
+            61 => "Light rain",
+            63 => "Rain",
+            65 => "Heavy rain",
+            66 | 67 => "Freezing rain",
+            71 => "Light snow",
+            73 => "Snow",
+            75 => "Heavy snow",
+            77 => "Snow grains",
+            80..=82 => "Rain showers",
+            85 | 86 => "Snow showers",
+            95 => "Thunderstorm",
+            96 | 99 => "Thunderstorm with hail",
+            _ => "Unknown conditions",
+        }
+    }
+}
+
+/// Formats a Celsius temperature in `unit`, rounded to a whole degree
+pub fn format_temperature(celsius: f64, unit: TemperatureUnit) -> String {
+    let (value, symbol) = match unit {
+        TemperatureUnit::Celsius => (celsius, "°C"),
+        TemperatureUnit::Fahrenheit => (celsius * 9.0 / 5.0 + 32.0, "°F"),
+    };
+    // Avoids showing "-0°C"
+    let rounded = value.round() + 0.0;
+    format!("{}{}", rounded, symbol)
+}
+
+/// Splits a weather query into the city it names, if any
+///
+/// `weather` gives `Some(None)`, `weather in lisbon` gives `Some(Some("lisbon"))`
+/// and anything not starting with the keyword gives `None`.
+pub fn parse_weather_query(query: &str) -> Option<Option<&str>> {
+    let query = query.trim();
+    let (keyword, rest) = query.split_once(char::is_whitespace).unwrap_or((query, ""));
+    if !keyword.eq_ignore_ascii_case(WEATHER_KEYWORD) {
+        return None;
+    }
+    let rest = rest.trim();
+    let city = match rest.split_once(char::is_whitespace) {
+        Some((word, city)) if word.eq_ignore_ascii_case("in") => city.trim(),
+        _ => rest,
+    };
+    Some((!city.is_empty()).then_some(city))
+}
+
+/// Fetches JSON from the Open-Meteo APIs
+#[async_trait]
+pub trait WeatherFetcher: Send + Sync {
+    async fn fetch_json(&self, url: &str) -> Result<serde_json::Value>;
+}
+
+/// Fetches over HTTPS with reqwest
+pub struct HttpWeatherFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpWeatherFetcher {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .build()
+            .map_err(|e| LauncherError::SearchError(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl WeatherFetcher for HttpWeatherFetcher {
+    async fn fetch_json(&self, url: &str) -> Result<serde_json::Value> {
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| LauncherError::SearchError(format!("Weather lookup failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(LauncherError::SearchError(format!(
+                "Weather lookup returned {}",
+                response.status()
+            )));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| LauncherError::SearchError(format!("Invalid weather response: {}", e)))
+    }
+}
+
+/// Reports where the device is
+#[async_trait]
+pub trait Geolocation: Send + Sync {
+    /// Latitude and longitude of the device
+    async fn current_position(&self) -> Result<(f64, f64)>;
+}
+
+/// Position from the Windows location service
+pub struct SystemGeolocation;
+
+#[async_trait]
+impl Geolocation for SystemGeolocation {
+    async fn current_position(&self) -> Result<(f64, f64)> {
+        tokio::task::spawn_blocking(Self::read_position)
+            .await
+            .map_err(|e| LauncherError::ProviderError(format!("Location task failed: {}", e)))?
+    }
+}
+
+impl SystemGeolocation {
+    #[cfg(windows)]
+    fn read_position() -> Result<(f64, f64)> {
+        use windows::Devices::Geolocation::Geolocator;
+
+        // Fails straight away when location access is off in Privacy settings
+        let position = Geolocator::new()
+            .and_then(|locator| locator.GetGeopositionAsync())
+            .and_then(|operation| operation.get())
+            .and_then(|position| position.Coordinate())
+            .and_then(|coordinate| coordinate.Point())
+            .and_then(|point| point.Position())
+            .map_err(|e| LauncherError::ProviderError(format!("Location unavailable: {}", e)))?;
+        Ok((position.Latitude, position.Longitude))
+    }
+
+    #[cfg(not(windows))]
+    fn read_position() -> Result<(f64, f64)> {
+        Err(LauncherError::Unsupported("Location is only available on Windows".to_string()))
+    }
+}
+
+struct CachedConditions {
+    conditions: Conditions,
+    fetched_at: Instant,
+}
+
+/// Weather search provider
+pub struct WeatherProvider {
+    fetcher: Arc<dyn WeatherFetcher>,
+    geolocation: Arc<dyn Geolocation>,
+    preferences: ActiveWeatherPreferences,
+    timeout: Duration,
+    forecast_ttl: Duration,
+    /// Recent forecasts by `Place::cache_key`
+    forecasts: Mutex<HashMap<String, CachedConditions>>,
+    /// Geocoded cities by lowercase name, mirrored to `geocode_cache_path`
+    places: Mutex<HashMap<String, Place>>,
+    geocode_cache_path: Option<PathBuf>,
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl WeatherProvider {
+    /// Creates a new WeatherProvider using Open-Meteo and the Windows location service
+    pub fn new(preferences: ActiveWeatherPreferences) -> Result<Self> {
+        info!("Initializing WeatherProvider");
+        let provider = Self::with_backends(
+            Arc::new(HttpWeatherFetcher::new()?),
+            Arc::new(SystemGeolocation),
+            preferences,
+        );
+        Ok(match Self::default_geocode_cache_path() {
+            Some(path) => provider.with_geocode_cache(path),
+            None => provider,
+        })
+    }
+
+    /// Creates a provider fetching through `fetcher` and locating through `geolocation`
+    pub fn with_backends(
+        fetcher: Arc<dyn WeatherFetcher>,
+        geolocation: Arc<dyn Geolocation>,
+        preferences: ActiveWeatherPreferences,
+    ) -> Self {
+        Self {
+            fetcher,
+            geolocation,
+            preferences,
+            timeout: FETCH_TIMEOUT,
+            forecast_ttl: FORECAST_TTL,
+            forecasts: Mutex::new(HashMap::new()),
+            places: Mutex::new(HashMap::new()),
+            geocode_cache_path: None,
+            enabled: true,
+        }
+    }
+
+    /// Keeps geocoded cities in `path`, loading the ones already there
+    pub fn with_geocode_cache(mut self, path: PathBuf) -> Self {
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            match serde_json::from_str(&contents) {
+                Ok(places) => self.places = Mutex::new(places),
+                Err(e) => debug!("Ignoring unreadable geocoding cache {}: {}", path.display(), e),
+            }
+        }
+        self.geocode_cache_path = Some(path);
+        self
+    }
+
+    /// Sets how long each lookup may take
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how long a forecast is reused
+    pub fn with_forecast_ttl(mut self, ttl: Duration) -> Self {
+        self.forecast_ttl = ttl;
+        self
+    }
+
+    fn default_geocode_cache_path() -> Option<PathBuf> {
+        let app_data = std::env::var_os("APPDATA")?;
+        Some(PathBuf::from(app_data).join("BetterFinder").join("weather_locations.json"))
+    }
+
+    /// Fetches `url`, or `None` when the call fails or takes too long
+    async fn fetch(&self, url: &str) -> Option<serde_json::Value> {
+        match tokio::time::timeout(self.timeout, self.fetcher.fetch_json(url)).await {
+            Ok(Ok(response)) => Some(response),
+            Ok(Err(e)) => {
+                debug!("Weather lookup {} failed: {}", url, e);
+                None
+            }
+            Err(_) => {
+                debug!("Weather lookup {} timed out", url);
+                None
+            }
+        }
+    }
+
+    /// Looks up a city, from the cache when it was found before
+    async fn geocode(&self, city: &str) -> Option<Place> {
+        let key = city.trim().to_lowercase();
+        if let Some(place) = self.places.lock().await.get(&key) {
+            return Some(place.clone());
+        }
+
+        // Unknown cities aren't cached, so a typo fixed on Open-Meteo's side works later
+        let place = place_from_geocoding(&self.fetch(&geocoding_url(city)).await?)?;
+        let snapshot = {
+            let mut places = self.places.lock().await;
+            places.insert(key, place.clone());
+            places.clone()
+        };
+        self.save_places(&snapshot).await;
+        Some(place)
+    }
+
+    async fn save_places(&self, places: &HashMap<String, Place>) {
+        let Some(path) = &self.geocode_cache_path else {
+            return;
+        };
+        let result = async {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::write(path, serde_json::to_vec(places)?).await?;
+            Ok::<_, LauncherError>(())
+        }
+        .await;
+        if let Err(e) = result {
+            debug!("Failed to save geocoding cache {}: {}", path.display(), e);
+        }
+    }
+
+    /// The device position, or the home city when Windows can't tell
+    async fn local_place(&self, home_city: Option<&str>) -> Option<Place> {
+        match tokio::time::timeout(self.timeout, self.geolocation.current_position()).await {
+            Ok(Ok((latitude, longitude))) => {
+                return Some(Place {
+                    name: CURRENT_LOCATION.to_string(),
+                    latitude,
+                    longitude,
+                })
+            }
+            Ok(Err(e)) => debug!("Device position unavailable: {}", e),
+            Err(_) => debug!("Device position timed out"),
+        }
+        self.geocode(home_city?).await
+    }
+
+    /// Today's conditions at `place`, reused for `forecast_ttl`
+    async fn conditions(&self, place: &Place) -> Option<Conditions> {
+        let key = place.cache_key();
+        if let Some(cached) = self.forecasts.lock().await.get(&key) {
+            if cached.fetched_at.elapsed() < self.forecast_ttl {
+                return Some(cached.conditions.clone());
+            }
+        }
+
+        let conditions = Conditions::from_forecast(&self.fetch(&place.forecast_url()).await?)?;
+        self.forecasts.lock().await.insert(
+            key,
+            CachedConditions {
+                conditions: conditions.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Some(conditions)
+    }
+
+    fn create_search_result(place: &Place, conditions: &Conditions, unit: TemperatureUnit) -> SearchResult {
+        let url = place.details_url(unit);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), serde_json::json!("weather"));
+        metadata.insert("url".to_string(), serde_json::json!(url));
+        metadata.insert("weather_code".to_string(), serde_json::json!(conditions.code));
+
+        SearchResult {
+            id: format!("weather:{}", place.cache_key()),
+            title: format!("{}: {}", place.name, format_temperature(conditions.temperature, unit)),
+            subtitle: format!(
+                "{} · High {} · Low {}",
+                conditions.description(),
+                format_temperature(conditions.high, unit),
+                format_temperature(conditions.low, unit)
+            ),
+            icon: None,
+            result_type: ResultType::Url,
+            score: 95.0,
+            metadata,
+            action: ResultAction::OpenUrl { url },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for WeatherProvider {
+    fn name(&self) -> &str {
+        "Weather"
+    }
+
+    fn priority(&self) -> u8 {
+        85
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::Url])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let Some(city) = parse_weather_query(&query.query) else {
+            return Ok(Vec::new());
+        };
+
+        let preferences = self.preferences.get();
+        let place = match city {
+            Some(city) => self.geocode(city).await,
+            None => self.local_place(preferences.home_city.as_deref()).await,
+        };
+        let Some(place) = place else {
+            return Ok(Vec::new());
+        };
+        let Some(conditions) = self.conditions(&place).await else {
+            return Ok(Vec::new());
+        };
+
+        Ok(vec![Self::create_search_result(&place, &conditions, preferences.unit)])
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if !result.id.starts_with("weather:") {
+            return Err(LauncherError::ExecutionError("Not a weather result".to_string()));
+        }
+
+        match &result.action {
+            ResultAction::OpenUrl { url } => {
+                info!("Opening forecast: {}", url);
+                BookmarkProvider::open_url(url).await
+            }
+            _ => Err(LauncherError::ExecutionError(
+                "Invalid action for weather result".to_string(),
+            )),
+        }
+    }
+
+    async fn syntax_features(&self) -> Vec<SyntaxFeature> {
+        SYNTAX.to_vec()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const LISBON: &str = r#"{"results":[{"name":"Lisbon","latitude":38.71667,"longitude":-9.13333,"country":"Portugal"}]}"#;
+    const PORTO: &str = r#"{"results":[{"name":"Porto","latitude":41.14961,"longitude":-8.61099,"country":"Portugal"}]}"#;
+    const FORECAST: &str = r#"{"current":{"temperature_2m":17.6,"weather_code":2},"daily":{"temperature_2m_max":[21.2],"temperature_2m_min":[13.9]}}"#;
+
+    /// Answers geocoding and forecast requests with canned responses
+    struct FakeFetcher {
+        geocoding: HashMap<&'static str, &'static str>,
+        forecast: Option<&'static str>,
+        delay: Duration,
+        calls: std::sync::Mutex<Vec<String>>,
+    }
+
+    impl FakeFetcher {
+        fn new(geocoding: &[(&'static str, &'static str)], forecast: Option<&'static str>) -> Self {
+            Self {
+                geocoding: geocoding.iter().copied().collect(),
+                forecast,
+                delay: Duration::ZERO,
+                calls: std::sync::Mutex::new(Vec::new()),
+            }
+        }
+
+        fn calls_to(&self, host: &str) -> usize {
+            self.calls.lock().unwrap().iter().filter(|url| url.contains(host)).count()
+        }
+    }
+
+    #[async_trait]
+    impl WeatherFetcher for FakeFetcher {
+        async fn fetch_json(&self, url: &str) -> Result<serde_json::Value> {
+            self.calls.lock().unwrap().push(url.to_string());
+            tokio::time::sleep(self.delay).await;
+            let body = if url.contains("geocoding-api") {
+                self.geocoding
+                    .iter()
+                    .find(|(city, _)| url.to_lowercase().contains(&format!("name={}", city.to_lowercase())))
+                    .map(|(_, body)| *body)
+                    .unwrap_or(r#"{"generationtime_ms":0.5}"#)
+            } else {
+                self.forecast
+                    .ok_or_else(|| LauncherError::SearchError("offline".to_string()))?
+            };
+            Ok(serde_json::from_str(body).unwrap())
+        }
+    }
+
+    /// Reports `position`, or fails like a machine with location access off
+    struct FakeGeolocation {
+        position: Option<(f64, f64)>,
+        calls: AtomicUsize,
+    }
+
+    impl FakeGeolocation {
+        fn at(position: Option<(f64, f64)>) -> Arc<Self> {
+            Arc::new(Self {
+                position,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Geolocation for FakeGeolocation {
+        async fn current_position(&self) -> Result<(f64, f64)> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.position
+                .ok_or_else(|| LauncherError::ProviderError("location access is off".to_string()))
+        }
+    }
+
+    fn provider_with(fetcher: Arc<FakeFetcher>, geolocation: Arc<FakeGeolocation>) -> (WeatherProvider, ActiveWeatherPreferences) {
+        let preferences = ActiveWeatherPreferences::default();
+        let provider = WeatherProvider::with_backends(fetcher, geolocation, preferences.clone());
+        (provider, preferences)
+    }
+
+    fn temp_cache(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}.json", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    async fn search(provider: &WeatherProvider, query: &str) -> Vec<SearchResult> {
+        provider.search(&query.into()).await.unwrap()
+    }
+
+    #[test]
+    fn test_parse_weather_query() {
+        assert_eq!(parse_weather_query("weather"), Some(None));
+        assert_eq!(parse_weather_query(" Weather  "), Some(None));
+        assert_eq!(parse_weather_query("weather lisbon"), Some(Some("lisbon")));
+        assert_eq!(parse_weather_query("weather in New York"), Some(Some("New York")));
+
+        assert_eq!(parse_weather_query("weatherproof jacket"), None);
+        assert_eq!(parse_weather_query("lisbon weather"), None);
+    }
+
+    #[test]
+    fn test_temperatures_follow_the_unit() {
+        assert_eq!(format_temperature(17.6, TemperatureUnit::Celsius), "18°C");
+        assert_eq!(format_temperature(17.6, TemperatureUnit::Fahrenheit), "64°F");
+        assert_eq!(format_temperature(-0.3, TemperatureUnit::Celsius), "0°C");
+        assert_eq!(format_temperature(-40.0, TemperatureUnit::Fahrenheit), "-40°F");
+    }
+
+    #[tokio::test]
+    async fn test_city_result_shows_conditions_in_the_chosen_unit() {
+        let fetcher = Arc::new(FakeFetcher::new(&[("lisbon", LISBON)], Some(FORECAST)));
+        let (provider, preferences) = provider_with(Arc::clone(&fetcher), FakeGeolocation::at(None));
+
+        let results = search(&provider, "weather lisbon").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Lisbon: 18°C");
+        assert_eq!(results[0].subtitle, "Partly cloudy · High 21°C · Low 14°C");
+        assert!(matches!(&results[0].action, ResultAction::OpenUrl { url } if url.contains("38.72,-9.13")));
+
+        // Switching units reuses the cached forecast
+        preferences.set(WeatherPreferences {
+            unit: TemperatureUnit::Fahrenheit,
+            home_city: None,
+        });
+        let results = search(&provider, "weather lisbon").await;
+        assert_eq!(results[0].title, "Lisbon: 64°F");
+        assert_eq!(results[0].subtitle, "Partly cloudy · High 70°F · Low 57°F");
+        assert_eq!(fetcher.calls_to("/v1/forecast"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_forecasts_expire() {
+        let fetcher = Arc::new(FakeFetcher::new(&[("lisbon", LISBON)], Some(FORECAST)));
+        let (provider, _) = provider_with(Arc::clone(&fetcher), FakeGeolocation::at(None));
+        let provider = provider.with_forecast_ttl(Duration::from_millis(50));
+
+        search(&provider, "weather lisbon").await;
+        search(&provider, "weather lisbon").await;
+        assert_eq!(fetcher.calls_to("/v1/forecast"), 1);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        search(&provider, "weather lisbon").await;
+        assert_eq!(fetcher.calls_to("/v1/forecast"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_geocoded_cities_are_kept_on_disk() {
+        let cache = temp_cache("weather_locations_test");
+        let fetcher = Arc::new(FakeFetcher::new(&[("lisbon", LISBON)], Some(FORECAST)));
+        let (provider, _) = provider_with(Arc::clone(&fetcher), FakeGeolocation::at(None));
+        let provider = provider.with_geocode_cache(cache.clone());
+        search(&provider, "weather Lisbon").await;
+        search(&provider, "weather lisbon").await;
+        assert_eq!(fetcher.calls_to("geocoding-api"), 1);
+
+        // A new session reads the city from disk
+        let offline = Arc::new(FakeFetcher::new(&[], Some(FORECAST)));
+        let (provider, _) = provider_with(Arc::clone(&offline), FakeGeolocation::at(None));
+        let provider = provider.with_geocode_cache(cache.clone());
+        assert_eq!(search(&provider, "weather lisbon").await[0].title, "Lisbon: 18°C");
+        assert_eq!(offline.calls_to("geocoding-api"), 0);
+
+        // Cities Open-Meteo doesn't know give no result
+        assert!(search(&provider, "weather atlantis").await.is_empty());
+        let _ = std::fs::remove_file(cache);
+    }
+
+    #[tokio::test]
+    async fn test_bare_query_falls_back_to_the_home_city() {
+        let fetcher = Arc::new(FakeFetcher::new(&[("Porto", PORTO)], Some(FORECAST)));
+        let located = FakeGeolocation::at(Some((38.7223, -9.1393)));
+        let (provider, preferences) = provider_with(Arc::clone(&fetcher), Arc::clone(&located));
+        preferences.set(WeatherPreferences {
+            unit: TemperatureUnit::Celsius,
+            home_city: Some("Porto".to_string()),
+        });
+
+        // The device position wins when Windows reports one
+        let results = search(&provider, "weather").await;
+        assert_eq!(results[0].title, "Current location: 18°C");
+        assert_eq!(fetcher.calls_to("geocoding-api"), 0);
+
+        let unlocated = FakeGeolocation::at(None);
+        let (provider, preferences) = provider_with(Arc::clone(&fetcher), Arc::clone(&unlocated));
+        assert!(search(&provider, "weather").await.is_empty());
+
+        preferences.set(WeatherPreferences {
+            unit: TemperatureUnit::Celsius,
+            home_city: Some("Porto".to_string()),
+        });
+        let results = search(&provider, "weather").await;
+        assert_eq!(results[0].title, "Porto: 18°C");
+        assert_eq!(unlocated.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_slow_or_failing_lookups_give_no_result() {
+        let mut slow = FakeFetcher::new(&[("lisbon", LISBON)], Some(FORECAST));
+        slow.delay = Duration::from_millis(200);
+        let slow = Arc::new(slow);
+        let (provider, _) = provider_with(Arc::clone(&slow), FakeGeolocation::at(None));
+        let provider = provider.with_timeout(Duration::from_millis(20));
+
+        let started = Instant::now();
+        assert!(search(&provider, "weather lisbon").await.is_empty());
+        assert!(started.elapsed() < Duration::from_millis(150));
+
+        let offline = Arc::new(FakeFetcher::new(&[("lisbon", LISBON)], None));
+        let (provider, _) = provider_with(Arc::clone(&offline), FakeGeolocation::at(None));
+        assert!(search(&provider, "weather lisbon").await.is_empty());
+        // Failed forecasts aren't cached
+        assert!(search(&provider, "weather lisbon").await.is_empty());
+        assert_eq!(offline.calls_to("/v1/forecast"), 2);
+
+        assert!(search(&provider, "weatherman").await.is_empty());
+    }
+}
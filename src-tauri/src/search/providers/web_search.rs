@@ -6,19 +6,55 @@
 /// - Natural language queries
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
+use crate::search::provider_config::{ConfigOption, ProviderConfig};
+use crate::search::web_search::ActiveWebSearchEngine;
+use crate::search::{QueryContext, SearchProvider};
 use crate::types::{ResultAction, ResultType, SearchResult};
 use async_trait::async_trait;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tracing::{debug, info};
 
+/// Queries shorter than this are likely file or app names
+const DEFAULT_MIN_QUERY_LENGTH: usize = 3;
+
+/// Options under `provider_settings.web_search`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebSearchConfig {
+    /// Shortest query offered a web search
+    pub min_query_length: usize,
+}
+
+impl Default for WebSearchConfig {
+    fn default() -> Self {
+        Self { min_query_length: DEFAULT_MIN_QUERY_LENGTH }
+    }
+}
+
+impl ProviderConfig for WebSearchConfig {
+    const KEY: &'static str = "web_search";
+    const PROVIDER: &'static str = "WebSearch";
+    const OPTIONS: &'static [ConfigOption] = &[ConfigOption {
+        field: "min_query_length",
+        description: "Shortest query offered a web search",
+        min: 1,
+        max: 20,
+    }];
+}
+
 /// Web search provider for fallback searches
 pub struct WebSearchProvider {
     /// Whether the provider is enabled
     enabled: bool,
     /// Regex for detecting question words
     question_pattern: Regex,
+    /// Shortest query offered a web search
+    min_query_length: AtomicUsize,
+    /// Engine the results search, shared with the search engine's fallback action
+    engine: ActiveWebSearchEngine,
 }
 
 impl WebSearchProvider {
@@ -34,9 +70,17 @@ impl WebSearchProvider {
         Ok(Self {
             enabled: true,
             question_pattern,
+            min_query_length: AtomicUsize::new(DEFAULT_MIN_QUERY_LENGTH),
+            engine: ActiveWebSearchEngine::default(),
         })
     }
 
+    /// Searches the engine picked in settings instead of the default one
+    pub fn with_active_engine(mut self, engine: ActiveWebSearchEngine) -> Self {
+        self.engine = engine;
+        self
+    }
+
     /// Checks if a query contains question words
     pub fn has_question_words(&self, query: &str) -> bool {
         self.question_pattern.is_match(query)
@@ -56,7 +100,7 @@ impl WebSearchProvider {
         }
 
         // Don't trigger on very short queries (likely file/app names)
-        if trimmed.len() < 3 {
+        if trimmed.len() < self.min_query_length.load(Ordering::Relaxed) {
             return false;
         }
 
@@ -78,21 +122,27 @@ impl WebSearchProvider {
 
     /// Creates a web search result for the given query
     fn create_web_search_result(&self, query: &str) -> SearchResult {
+        let engine = self.engine.get();
         let mut metadata = HashMap::new();
         metadata.insert("query".to_string(), serde_json::json!(query));
-        metadata.insert("search_engine".to_string(), serde_json::json!("Google"));
+        metadata.insert("search_engine".to_string(), serde_json::json!(engine.name));
+        metadata.insert("search_engine_id".to_string(), serde_json::json!(engine.id));
+        metadata.insert("url".to_string(), serde_json::json!(engine.construct_search_url(query)));
 
         SearchResult {
             id: format!("web_search:{}", query),
-            title: format!("Search Google for \"{}\"", query),
+            title: format!("Search {} for \"{}\"", engine.name, query),
             subtitle: "Press Enter to search on the web".to_string(),
-            icon: Some("web".to_string()),
+            icon: engine.favicon(),
             result_type: ResultType::WebSearch,
             score: 10.0, // Low score so it appears at the bottom
             metadata,
             action: ResultAction::WebSearch {
                 query: query.to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         }
     }
 }
@@ -107,8 +157,12 @@ impl SearchProvider for WebSearchProvider {
         1 // Lowest priority - fallback option
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        let trimmed = query.trim();
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::WebSearch])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let trimmed = query.query.trim();
         
         // For now, always return a web search option if query has question words
         // The actual decision to show this will be made by checking if other results exist
@@ -120,7 +174,7 @@ impl SearchProvider for WebSearchProvider {
 
         // For other queries, we'll return a web search option with very low score
         // so it only shows up when there are few other results
-        if trimmed.len() >= 3 {
+        if trimmed.len() >= self.min_query_length.load(Ordering::Relaxed) {
             debug!("Creating fallback web search result for: '{}'", trimmed);
             let result = self.create_web_search_result(trimmed);
             return Ok(vec![result]);
@@ -140,7 +194,7 @@ impl SearchProvider for WebSearchProvider {
         match &result.action {
             ResultAction::WebSearch { query } => {
                 info!("Executing web search for: {}", query);
-                Self::open_web_search(query).await?;
+                Self::open_web_search(self.engine.get().construct_search_url(query)).await?;
                 Ok(())
             }
             _ => Err(LauncherError::ExecutionError(
@@ -149,6 +203,12 @@ impl SearchProvider for WebSearchProvider {
         }
     }
 
+    async fn configure(&self, config: &serde_json::Value) -> Result<()> {
+        let config = WebSearchConfig::parse(config)?;
+        self.min_query_length.store(config.min_query_length, Ordering::Relaxed);
+        Ok(())
+    }
+
     fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -164,6 +224,8 @@ impl Default for WebSearchProvider {
         Self::new().unwrap_or_else(|_| Self {
             enabled: false,
             question_pattern: Regex::new(r"(?i)^\s*(how|what|why|when|where|who)\b").unwrap(),
+            min_query_length: AtomicUsize::new(DEFAULT_MIN_QUERY_LENGTH),
+            engine: ActiveWebSearchEngine::default(),
         })
     }
 }
@@ -224,18 +286,9 @@ impl WebSearchProvider {
         }
     }
 
-    /// Constructs a Google search URL with encoded query
-    /// This function is platform-independent
-    pub fn construct_search_url(query: &str) -> String {
-        let encoded_query = urlencoding::encode(query);
-        format!("https://www.google.com/search?q={}", encoded_query)
-    }
-
     /// Opens a web search in the default browser
     #[cfg(windows)]
-    async fn open_web_search(query: &str) -> Result<()> {
-        let search_url = Self::construct_search_url(query);
-        
+    async fn open_web_search(search_url: String) -> Result<()> {
         info!("Opening web search URL: {}", search_url);
 
         // Detect default browser (for logging purposes)
@@ -266,7 +319,7 @@ impl WebSearchProvider {
     }
 
     #[cfg(not(windows))]
-    async fn open_web_search(_query: &str) -> Result<()> {
+    async fn open_web_search(_search_url: String) -> Result<()> {
         Err(LauncherError::ExecutionError(
             "Web search not supported on this platform".to_string(),
         ))
@@ -276,6 +329,7 @@ impl WebSearchProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::search::web_search::WebSearchEngine;
 
     #[tokio::test]
     async fn test_web_search_provider_creation() {
@@ -406,12 +460,12 @@ mod tests {
         let provider = WebSearchProvider::new().unwrap();
 
         // Should return web search result for question queries
-        let results = provider.search("how to use rust").await.unwrap();
+        let results = provider.search(&"how to use rust".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].result_type, ResultType::WebSearch);
         assert_eq!(results[0].title, "Search Google for \"how to use rust\"");
 
-        let results = provider.search("what is tauri").await.unwrap();
+        let results = provider.search(&"what is tauri".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].result_type, ResultType::WebSearch);
     }
@@ -421,11 +475,11 @@ mod tests {
         let provider = WebSearchProvider::new().unwrap();
 
         // Should still return web search result as fallback for longer queries
-        let results = provider.search("search query").await.unwrap();
+        let results = provider.search(&"search query".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].result_type, ResultType::WebSearch);
 
-        let results = provider.search("calculator").await.unwrap();
+        let results = provider.search(&"calculator".into()).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].result_type, ResultType::WebSearch);
     }
@@ -435,13 +489,13 @@ mod tests {
         let provider = WebSearchProvider::new().unwrap();
 
         // Should NOT return results for very short queries
-        let results = provider.search("ab").await.unwrap();
+        let results = provider.search(&"ab".into()).await.unwrap();
         assert!(results.is_empty());
 
-        let results = provider.search("a").await.unwrap();
+        let results = provider.search(&"a".into()).await.unwrap();
         assert!(results.is_empty());
 
-        let results = provider.search("").await.unwrap();
+        let results = provider.search(&"".into()).await.unwrap();
         assert!(results.is_empty());
     }
 
@@ -461,6 +515,9 @@ mod tests {
             action: ResultAction::WebSearch {
                 query: "test".to_string(),
             },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
         };
 
         let result = provider.execute(&invalid_result).await;
@@ -479,7 +536,7 @@ mod tests {
     async fn test_search_result_action() {
         let provider = WebSearchProvider::new().unwrap();
 
-        let results = provider.search("how to code").await.unwrap();
+        let results = provider.search(&"how to code".into()).await.unwrap();
         assert_eq!(results.len(), 1);
 
         let result = &results[0];
@@ -521,40 +578,42 @@ mod tests {
 
     #[test]
     fn test_url_construction() {
+        let google = WebSearchEngine::find("google").unwrap();
+
         // Test basic query encoding
-        let url = WebSearchProvider::construct_search_url("hello world");
+        let url = google.construct_search_url("hello world");
         assert_eq!(url, "https://www.google.com/search?q=hello%20world");
 
         // Test special characters encoding
-        let url = WebSearchProvider::construct_search_url("rust & tauri");
+        let url = google.construct_search_url("rust & tauri");
         assert_eq!(url, "https://www.google.com/search?q=rust%20%26%20tauri");
 
         // Test URL-unsafe characters
-        let url = WebSearchProvider::construct_search_url("what is c++?");
+        let url = google.construct_search_url("what is c++?");
         assert_eq!(url, "https://www.google.com/search?q=what%20is%20c%2B%2B%3F");
 
         // Test query with equals sign
-        let url = WebSearchProvider::construct_search_url("2+2=4");
+        let url = google.construct_search_url("2+2=4");
         assert_eq!(url, "https://www.google.com/search?q=2%2B2%3D4");
 
         // Test query with forward slash
-        let url = WebSearchProvider::construct_search_url("path/to/file");
+        let url = google.construct_search_url("path/to/file");
         assert_eq!(url, "https://www.google.com/search?q=path%2Fto%2Ffile");
 
         // Test query with hash
-        let url = WebSearchProvider::construct_search_url("c# programming");
+        let url = google.construct_search_url("c# programming");
         assert_eq!(url, "https://www.google.com/search?q=c%23%20programming");
 
         // Test query with percent sign
-        let url = WebSearchProvider::construct_search_url("100% complete");
+        let url = google.construct_search_url("100% complete");
         assert_eq!(url, "https://www.google.com/search?q=100%25%20complete");
 
         // Test empty query
-        let url = WebSearchProvider::construct_search_url("");
+        let url = google.construct_search_url("");
         assert_eq!(url, "https://www.google.com/search?q=");
 
         // Test unicode characters
-        let url = WebSearchProvider::construct_search_url("hello 世界");
+        let url = google.construct_search_url("hello 世界");
         assert!(url.starts_with("https://www.google.com/search?q=hello%20"));
         assert!(url.contains("%E4%B8%96%E7%95%8C")); // UTF-8 encoded 世界
     }
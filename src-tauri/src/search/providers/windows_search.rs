@@ -1,26 +1,45 @@
 /// Windows Search fallback provider
 ///
 /// This provider uses Windows Search API as a fallback when Everything SDK is not available.
-/// It provides basic file search functionality using the built-in Windows indexing service.
+/// It provides basic file search functionality using the built-in Windows indexing service,
+/// queried over a persistent connection (see `search_index`).
 
 use crate::error::{LauncherError, Result};
-use crate::search::SearchProvider;
-use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::search::providers::extension_ranking::{apply_extension_scores, ActiveExtensionWeights};
+use crate::search::providers::file_search::{format_file_details, FileSort};
+use crate::search::providers::index_freshness::{
+    catalog_freshness, FreshnessMonitor, IndexTool, SearchCatalog, SystemCatalog,
+};
+use crate::search::providers::path_exclusions::{hidden_results_row, strip_bypass_prefix, FolderScopes};
+use crate::search::providers::path_ranking::query_tokens;
+use crate::search::providers::search_index::{
+    self, AdoConnector, IndexQuery, IndexRow, SearchIndexClient,
+};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ProviderHealth, ResultAction, ResultType, SearchResult, PRESERVE_PROVIDER_ORDER_KEY};
 use crate::utils::IconCache;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use tracing::info;
-
-const MAX_RESULTS: usize = 20;
+use tracing::{debug, info, warn};
 
 /// Windows Search fallback provider
 pub struct WindowsSearchProvider {
     icon_cache: Arc<IconCache>,
+    index: SearchIndexClient,
+    scopes: FolderScopes,
+    extension_weights: ActiveExtensionWeights,
+    catalog: Arc<dyn SearchCatalog>,
+    freshness: FreshnessMonitor,
     enabled: bool,
 }
 
+/// Searches are limited to the user profile, like the rest of the file providers
+fn profile_scope() -> String {
+    std::env::var("USERPROFILE").unwrap_or_default()
+}
+
 impl WindowsSearchProvider {
     /// Creates a new WindowsSearchProvider
     pub fn new() -> Result<Self> {
@@ -28,96 +47,109 @@ impl WindowsSearchProvider {
         
         Ok(Self {
             icon_cache: Arc::new(IconCache::new()),
+            index: SearchIndexClient::spawn(AdoConnector, profile_scope()),
+            scopes: FolderScopes::default(),
+            extension_weights: ActiveExtensionWeights::default(),
+            catalog: Arc::new(SystemCatalog),
+            freshness: FreshnessMonitor::new(IndexTool::IndexingOptions),
             enabled: true,
         })
     }
 
-    /// Search files using Windows Search API
-    #[cfg(windows)]
-    fn search_windows(&self, query: &str) -> Result<Vec<SearchResult>> {
-        use std::process::Command;
-        use tracing::{debug, warn};
-        
-        // Use PowerShell to query Windows Search
-        // This is a simplified implementation - a full implementation would use COM APIs
-        let ps_script = format!(
-            r#"Get-ChildItem -Path "$env:USERPROFILE" -Recurse -Filter "*{}*" -ErrorAction SilentlyContinue | Select-Object -First {} | ForEach-Object {{ $_.FullName }}"#,
-            query.replace("\"", "\\\""),
-            MAX_RESULTS
-        );
-        
-        let output = Command::new("powershell")
-            .args(["-NoProfile", "-Command", &ps_script])
-            .output();
+    /// Hides files under the user-excluded folders as well as the built-in ones
+    pub fn with_folder_scopes(mut self, scopes: FolderScopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Ranks files by the class of their extension with these weights, like
+    /// the Everything provider
+    pub fn with_extension_weights(mut self, weights: ActiveExtensionWeights) -> Self {
+        self.extension_weights = weights;
+        self
+    }
+
+    /// Reads the catalog status off the async runtime when a probe is due
+    async fn probe_freshness(&self) {
+        if !self.freshness.begin_probe(std::time::Instant::now()) {
+            return;
+        }
+
+        let catalog = Arc::clone(&self.catalog);
+        let status = tokio::task::spawn_blocking(move || catalog.status()).await;
+        match status {
+            Ok(Ok(status)) => self.freshness.record(Some(catalog_freshness(status))),
+            Ok(Err(e)) => debug!("Could not probe the Windows Search index: {}", e),
+            Err(e) => debug!("Windows Search index probe panicked: {}", e),
+        }
+    }
+
+    /// Converts index rows into results, dropping files deleted since they were indexed
+    fn to_results(rows: Vec<IndexRow>, sort: Option<FileSort>) -> Vec<SearchResult> {
+        let now = chrono::Utc::now().timestamp();
+        let mut results = Vec::new();
         
-        match output {
-            Ok(output) if output.status.success() => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let mut results = Vec::new();
-                
-                for (idx, line) in stdout.lines().enumerate() {
-                    let line = line.trim();
-                    if line.is_empty() {
-                        continue;
-                    }
-                    
-                    let path = Path::new(line);
-                    if !path.exists() {
-                        continue;
-                    }
-                    
-                    let file_name = path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("Unknown")
-                        .to_string();
-                    
-                    let parent_path = path
-                        .parent()
-                        .and_then(|p| p.to_str())
-                        .unwrap_or("")
-                        .to_string();
-                    
-                    let icon = Some(IconCache::get_generic_icon(path));
-                    
-                    let mut metadata = HashMap::new();
-                    metadata.insert("path".to_string(), serde_json::json!(line));
-                    
-                    // Calculate score based on position (earlier results are more relevant)
-                    let score = 50.0 - (idx as f64 * 2.0);
-                    
-                    results.push(SearchResult {
-                        id: format!("windows_search:{}", line),
-                        title: file_name,
-                        subtitle: parent_path,
-                        icon,
-                        result_type: ResultType::File,
-                        score,
-                        metadata,
-                        action: ResultAction::OpenFile {
-                            path: line.to_string(),
-                        },
-                    });
-                }
-                
-                debug!("Windows Search found {} results", results.len());
-                Ok(results)
+        for (idx, row) in rows.into_iter().enumerate() {
+            let path = Path::new(&row.path);
+            if !path.exists() {
+                continue;
             }
-            Ok(output) => {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                warn!("Windows Search failed: {}", stderr);
-                Ok(Vec::new())
+            
+            let file_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("Unknown")
+                .to_string();
+            
+            let parent_path = path
+                .parent()
+                .and_then(|p| p.to_str())
+                .unwrap_or("")
+                .to_string();
+            
+            let icon = Some(IconCache::get_generic_icon(path));
+            
+            let mut metadata = HashMap::new();
+            metadata.insert("path".to_string(), serde_json::json!(row.path));
+            if let Some(size) = row.size {
+                metadata.insert("size".to_string(), serde_json::json!(size));
             }
-            Err(e) => {
-                warn!("Failed to execute Windows Search: {}", e);
-                Ok(Vec::new())
+            if let Some(modified) = row.modified {
+                metadata.insert("modified".to_string(), serde_json::json!(modified));
             }
+            
+            // Calculate score based on position (earlier results are more relevant)
+            let score = match sort {
+                Some(_) => {
+                    metadata.insert(PRESERVE_PROVIDER_ORDER_KEY.to_string(), serde_json::json!(true));
+                    FileSort::score_at(idx)
+                }
+                None => 50.0 - (idx as f64 * 2.0),
+            };
+            
+            let subtitle = match format_file_details(row.size, row.modified, now) {
+                Some(details) => format!("{} • {}", parent_path, details),
+                None => parent_path,
+            };
+            
+            results.push(SearchResult {
+                id: format!("windows_search:{}", row.path),
+                title: file_name,
+                subtitle,
+                icon,
+                result_type: ResultType::File,
+                score,
+                metadata,
+                action: ResultAction::OpenFile {
+                    path: row.path.clone(),
+                },
+                normalized_title: None,
+                enrichment_pending: false,
+                integrity: None,
+            });
         }
-    }
-
-    #[cfg(not(windows))]
-    fn search_windows(&self, _query: &str) -> Result<Vec<SearchResult>> {
-        Ok(Vec::new())
+        
+        results
     }
 }
 
@@ -131,12 +163,46 @@ impl SearchProvider for WindowsSearchProvider {
         85 // Slightly lower priority than Everything
     }
 
-    async fn search(&self, query: &str) -> Result<Vec<SearchResult>> {
-        if query.trim().is_empty() {
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::File])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let (query, show_all) = strip_bypass_prefix(&query.query);
+
+        // Listing the whole profile unfiltered is too slow, so a bare directive finds nothing
+        let (pattern, sort) = FileSort::parse_directive(query);
+        if pattern.is_empty() {
             return Ok(Vec::new());
         }
 
-        self.search_windows(query)
+        let tokens = query_tokens(&pattern);
+        let mut rows = match self.index.query(IndexQuery { pattern, sort }).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!("Windows Search failed: {}", e);
+                return Ok(Vec::new());
+            }
+        };
+
+        let hidden = if show_all {
+            0
+        } else {
+            self.scopes.exclusions().retain_visible(&mut rows, |row| &row.path)
+        };
+
+        let mut results = Self::to_results(rows, sort);
+        if sort.is_none() {
+            apply_extension_scores(&mut results, &tokens, &self.extension_weights.get());
+            results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        }
+        debug!("Windows Search found {} results ({} hidden by exclusions)", results.len(), hidden);
+
+        results.extend(hidden_results_row(self.name(), query, hidden));
+
+        self.probe_freshness().await;
+        results.extend(self.freshness.take_notice(self.name(), chrono::Utc::now()));
+        Ok(results)
     }
 
     async fn execute(&self, result: &SearchResult) -> Result<()> {
@@ -190,12 +256,36 @@ impl SearchProvider for WindowsSearchProvider {
     fn is_enabled(&self) -> bool {
         self.enabled
     }
+
+    fn health(&self) -> ProviderHealth {
+        if self.enabled {
+            self.freshness.health(chrono::Utc::now())
+        } else {
+            ProviderHealth::Disabled
+        }
+    }
+
+    /// Fails with the service state when the index can't answer queries
+    async fn initialize(&mut self) -> Result<()> {
+        match search_index::search_service_health().error() {
+            Some(error) => {
+                self.enabled = false;
+                Err(error)
+            }
+            None => Ok(()),
+        }
+    }
 }
 
 impl Default for WindowsSearchProvider {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             icon_cache: Arc::new(IconCache::new()),
+            index: SearchIndexClient::spawn(AdoConnector, profile_scope()),
+            scopes: FolderScopes::default(),
+            extension_weights: ActiveExtensionWeights::default(),
+            catalog: Arc::new(SystemCatalog),
+            freshness: FreshnessMonitor::new(IndexTool::IndexingOptions),
             enabled: false,
         })
     }
@@ -216,6 +306,37 @@ mod tests {
         assert!(provider.is_enabled());
     }
 
+    #[test]
+    fn test_rows_become_results_and_missing_files_are_dropped() {
+        let dir = std::env::temp_dir().join("bf_windows_search_rows");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("report.docx");
+        std::fs::write(&file, b"report").unwrap();
+        let path = file.to_string_lossy().to_string();
+
+        let rows = vec![
+            IndexRow { path: dir.join("deleted.docx").to_string_lossy().to_string(), size: Some(1), modified: None },
+            IndexRow { path: path.clone(), size: Some(2048), modified: Some(1_700_000_000) },
+        ];
+
+        let results = WindowsSearchProvider::to_results(rows.clone(), None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "report.docx");
+        assert_eq!(results[0].metadata["size"], serde_json::json!(2048));
+        assert!(!results[0].metadata.contains_key(PRESERVE_PROVIDER_ORDER_KEY));
+
+        let sorted = WindowsSearchProvider::to_results(rows, Some(FileSort::Largest));
+        assert_eq!(sorted[0].metadata[PRESERVE_PROVIDER_ORDER_KEY], serde_json::json!(true));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_bare_sort_directive_returns_nothing() {
+        let provider = WindowsSearchProvider::new().unwrap();
+        assert!(provider.search(&"sort:new".into()).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     #[cfg(windows)]
     async fn test_windows_search() {
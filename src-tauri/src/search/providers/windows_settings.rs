@@ -0,0 +1,285 @@
+/// Windows Settings provider for deep links into Settings and Control Panel
+///
+/// Searching "night light", "bluetooth settings" or "default apps" offers the
+/// matching page from `SETTINGS_PAGES`. Queries that don't name a page are
+/// matched word by word against the pages' keywords, so "make screen warmer
+/// at night" still finds Night light. Settings pages open through their
+/// `ms-settings:` URI; Control Panel items through `control.exe /name`.
+
+use super::windows_settings_pages::{SettingsPage, SettingsTarget, SETTINGS_PAGES};
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Shortest query, or query word, that matches a page
+const MIN_QUERY_LEN: usize = 3;
+
+/// Most pages offered for one query
+const MAX_RESULTS: usize = 8;
+
+/// Words that say nothing about which page is meant
+const FILLER_WORDS: &[&str] = &[
+    "settings", "setting", "windows", "change", "open", "make", "turn", "adjust", "show", "the", "and", "for",
+    "with", "how", "where", "can", "off", "page",
+];
+
+impl SettingsPage {
+    /// Scores how well a lowercase query names or describes the page
+    pub fn match_score(&self, query: &str, tokens: &[String]) -> Option<f64> {
+        if query.chars().count() < MIN_QUERY_LEN {
+            return None;
+        }
+
+        self.aliases()
+            .filter_map(|alias| {
+                if alias == query {
+                    Some(100.0)
+                } else if alias.starts_with(query) {
+                    Some(90.0)
+                } else if alias.split(' ').any(|word| word.starts_with(query)) {
+                    Some(75.0)
+                } else {
+                    None
+                }
+            })
+            .reduce(f64::max)
+            .or_else(|| self.keyword_score(tokens))
+    }
+
+    /// Scores a query by how many of its meaningful words the page's aliases contain
+    ///
+    /// At least half the words have to match, ranking below any match on a
+    /// whole name or keyword.
+    fn keyword_score(&self, tokens: &[String]) -> Option<f64> {
+        let words: Vec<&str> = tokens
+            .iter()
+            .map(String::as_str)
+            .filter(|word| word.chars().count() >= MIN_QUERY_LEN && !FILLER_WORDS.contains(word))
+            .collect();
+        if words.is_empty() {
+            return None;
+        }
+
+        let vocabulary: Vec<String> = self
+            .aliases()
+            .flat_map(|alias| alias.split([' ', '-']).map(str::to_string).collect::<Vec<_>>())
+            .collect();
+        let matched = words
+            .iter()
+            .filter(|word| vocabulary.iter().any(|known| known.starts_with(*word)))
+            .count();
+
+        (matched > 0 && matched * 2 >= words.len()).then(|| 40.0 + 30.0 * matched as f64 / words.len() as f64)
+    }
+}
+
+/// Windows Settings search provider
+pub struct WindowsSettingsProvider {
+    /// Whether the provider is enabled
+    enabled: bool,
+}
+
+impl WindowsSettingsProvider {
+    /// Creates a new WindowsSettingsProvider
+    pub fn new() -> Result<Self> {
+        info!("Initializing WindowsSettingsProvider");
+        Ok(Self { enabled: true })
+    }
+
+    /// Converts a page to a SearchResult
+    fn create_search_result(page: &SettingsPage, score: f64) -> SearchResult {
+        let mut metadata = HashMap::new();
+        metadata.insert("page".to_string(), serde_json::json!(page.id));
+
+        let subtitle = match page.target {
+            SettingsTarget::Uri(_) => format!("Settings › {}", page.category),
+            SettingsTarget::ControlPanel(_) => format!("Control Panel › {}", page.category),
+        };
+
+        SearchResult {
+            id: format!("windows_settings:{}", page.id),
+            title: page.name.to_string(),
+            subtitle,
+            icon: None,
+            result_type: ResultType::QuickAction,
+            score,
+            metadata,
+            action: ResultAction::ExecuteCommand {
+                command: format!("windows_settings:{}", page.id),
+                args: vec![],
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for WindowsSettingsProvider {
+    fn name(&self) -> &str {
+        "WindowsSettings"
+    }
+
+    fn priority(&self) -> u8 {
+        80 // Same as the other quick actions
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        Some(&[ResultType::QuickAction])
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let folded = query.folded_query.trim();
+        let mut results: Vec<SearchResult> = SETTINGS_PAGES
+            .iter()
+            .filter_map(|page| Some(Self::create_search_result(page, page.match_score(folded, &query.tokens)?)))
+            .collect();
+
+        // Stable, so ties keep the table's order
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(MAX_RESULTS);
+        Ok(results)
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        let page = result
+            .metadata
+            .get("page")
+            .and_then(|v| v.as_str())
+            .and_then(SettingsPage::find)
+            .ok_or_else(|| LauncherError::ExecutionError("Not a Windows Settings result".to_string()))?;
+
+        info!("Opening {} ({:?})", page.name, page.target);
+
+        tokio::task::spawn_blocking(move || open_page(page))
+            .await
+            .map_err(|e| LauncherError::ExecutionError(format!("Settings task failed: {}", e)))?
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Default for WindowsSettingsProvider {
+    fn default() -> Self {
+        Self::new().unwrap_or(Self { enabled: false })
+    }
+}
+
+/// Opens a Settings URI with the shell, or a Control Panel item with control.exe
+#[cfg(windows)]
+fn open_page(page: &SettingsPage) -> Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Shell::ShellExecuteW;
+    use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+    let to_wide = |text: &str| -> Vec<u16> {
+        std::ffi::OsStr::new(text)
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect()
+    };
+
+    let (file, parameters) = match page.target {
+        SettingsTarget::Uri(uri) => (to_wide(uri), None),
+        SettingsTarget::ControlPanel(name) => (to_wide("control.exe"), Some(to_wide(&format!("/name {}", name)))),
+    };
+    let verb = to_wide("open");
+
+    let result = unsafe {
+        ShellExecuteW(
+            HWND(std::ptr::null_mut()),
+            PCWSTR(verb.as_ptr()),
+            PCWSTR(file.as_ptr()),
+            parameters.as_ref().map_or(PCWSTR::null(), |p| PCWSTR(p.as_ptr())),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // ShellExecuteW returns a value > 32 on success
+    match result.0 as isize {
+        code if code > 32 => Ok(()),
+        code => Err(LauncherError::ExecutionError(format!(
+            "Failed to open {} (code: {})",
+            page.name, code
+        ))),
+    }
+}
+
+#[cfg(not(windows))]
+fn open_page(page: &SettingsPage) -> Result<()> {
+    Err(LauncherError::ExecutionError(format!(
+        "{} is not available on this platform",
+        page.name
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn search(query: &str) -> Vec<SearchResult> {
+        WindowsSettingsProvider::new().unwrap().search(&query.into()).await.unwrap()
+    }
+
+    async fn top(query: &str) -> String {
+        search(query).await.first().map(|r| r.id.clone()).unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_names_and_keywords_match() {
+        let results = search("night light").await;
+        assert_eq!(results[0].id, "windows_settings:night_light");
+        assert_eq!(results[0].score, 100.0);
+        assert_eq!(results[0].subtitle, "Settings › System");
+
+        assert_eq!(top("default apps").await, "windows_settings:default_apps");
+        assert_eq!(top("wifi").await, "windows_settings:wifi");
+        assert_eq!(top("dark mode").await, "windows_settings:colors");
+        assert_eq!(top("wallpaper").await, "windows_settings:background");
+
+        assert!(search("ni").await.is_empty());
+        assert!(search("spreadsheet").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_natural_phrasings_match_by_keyword() {
+        for (query, page) in [
+            ("make screen warmer at night", "night_light"),
+            ("bluetooth settings", "bluetooth"),
+            ("turn off notifications", "notifications"),
+            ("change desktop wallpaper", "background"),
+            ("uninstall program", "programs_features"),
+            ("show hidden files", "file_explorer_options"),
+            ("change time zone", "date_time"),
+            ("pair headphones bluetooth", "bluetooth"),
+        ] {
+            assert_eq!(top(query).await, format!("windows_settings:{}", page), "{}", query);
+        }
+
+        // Keyword matches rank below whole-name matches
+        let results = search("make screen warmer at night").await;
+        assert!(results[0].score < 75.0);
+        // Too few of the words match anything
+        assert!(search("bake bread at night with friends").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_control_panel_items_are_offered() {
+        let results = search("firewall").await;
+        assert_eq!(results[0].id, "windows_settings:firewall");
+        assert_eq!(results[0].subtitle, "Control Panel › System and Security");
+        assert_eq!(
+            SettingsPage::find("firewall").unwrap().target,
+            SettingsTarget::ControlPanel("Microsoft.WindowsFirewall")
+        );
+    }
+}
@@ -0,0 +1,227 @@
+/// Windows Settings pages and Control Panel items offered by the Windows
+/// Settings provider
+///
+/// Names are shown as result titles; keywords are lowercase alternative
+/// names and the words people use for what a page does ("warmer screen" for
+/// Night light). Control Panel items are only listed when Settings has no
+/// page for them, and open through `control.exe /name` with their canonical
+/// name.
+
+/// How a settings page is opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsTarget {
+    /// An `ms-settings:` URI opened with the shell
+    Uri(&'static str),
+    /// A Control Panel item's canonical name, e.g. `Microsoft.PowerOptions`
+    ControlPanel(&'static str),
+}
+
+/// A page the Windows Settings provider can open
+#[derive(Debug, PartialEq, Eq)]
+pub struct SettingsPage {
+    /// Stable identifier stored in result metadata
+    pub id: &'static str,
+    /// Name shown in the result title
+    pub name: &'static str,
+    /// Section of Settings or Control Panel the page belongs to
+    pub category: &'static str,
+    /// Lowercase alternative names and phrases matched against queries
+    pub keywords: &'static [&'static str],
+    /// Where the page opens
+    pub target: SettingsTarget,
+}
+
+const fn settings(
+    id: &'static str,
+    name: &'static str,
+    category: &'static str,
+    keywords: &'static [&'static str],
+    uri: &'static str,
+) -> SettingsPage {
+    SettingsPage { id, name, category, keywords, target: SettingsTarget::Uri(uri) }
+}
+
+const fn control_panel(
+    id: &'static str,
+    name: &'static str,
+    category: &'static str,
+    keywords: &'static [&'static str],
+    canonical_name: &'static str,
+) -> SettingsPage {
+    SettingsPage { id, name, category, keywords, target: SettingsTarget::ControlPanel(canonical_name) }
+}
+
+/// Every page the provider knows, in the order ties are ranked
+pub const SETTINGS_PAGES: &[SettingsPage] = &[
+    // System
+    settings("home", "Settings", "Home", &["windows settings", "pc settings"], "ms-settings:"),
+    settings("display", "Display", "System", &["screen resolution", "brightness", "monitor", "scale", "refresh rate"], "ms-settings:display"),
+    settings("night_light", "Night light", "System", &["blue light", "warmer screen", "warm colors", "night mode", "reduce eye strain"], "ms-settings:nightlight"),
+    settings("graphics", "Graphics", "System", &["gpu preference", "hdr", "graphics card"], "ms-settings:display-advancedgraphics"),
+    settings("sound", "Sound", "System", &["audio", "speakers", "volume", "output device"], "ms-settings:sound"),
+    settings("sound_devices", "Sound devices", "System", &["audio devices", "headphones", "input device"], "ms-settings:sound-devices"),
+    settings("app_volume", "Volume mixer", "System", &["app volume", "per app volume", "mixer"], "ms-settings:apps-volume"),
+    settings("notifications", "Notifications", "System", &["alerts", "toasts", "notification banners"], "ms-settings:notifications"),
+    settings("focus", "Do not disturb", "System", &["focus assist", "quiet hours", "mute notifications", "focus"], "ms-settings:quiethours"),
+    settings("power", "Power & sleep", "System", &["sleep timeout", "screen timeout", "power mode", "turn off screen"], "ms-settings:powersleep"),
+    settings("battery", "Battery saver", "System", &["battery", "battery usage", "energy saver"], "ms-settings:batterysaver"),
+    settings("storage", "Storage", "System", &["disk space", "free up space", "drive usage", "cleanup"], "ms-settings:storagesense"),
+    settings("storage_sense", "Storage Sense", "System", &["empty recycle bin automatically", "delete temporary files"], "ms-settings:storagepolicies"),
+    settings("multitasking", "Multitasking", "System", &["snap windows", "alt tab", "virtual desktops", "snap layouts"], "ms-settings:multitasking"),
+    settings("projecting", "Projecting to this PC", "System", &["miracast", "wireless display", "cast to this pc"], "ms-settings:project"),
+    settings("clipboard", "Clipboard", "System", &["clipboard history", "copy paste history", "cloud clipboard"], "ms-settings:clipboard"),
+    settings("remote_desktop", "Remote Desktop", "System", &["rdp", "remote access", "allow remote connections"], "ms-settings:remotedesktop"),
+    settings("about", "About", "System", &["pc name", "rename pc", "system info", "device specifications", "windows version"], "ms-settings:about"),
+    settings("activation", "Activation", "System", &["product key", "license", "activate windows"], "ms-settings:activation"),
+    settings("troubleshoot", "Troubleshoot", "System", &["troubleshooters", "fix problems"], "ms-settings:troubleshoot"),
+    settings("recovery", "Recovery", "System", &["reset this pc", "factory reset", "advanced startup", "go back"], "ms-settings:recovery"),
+    settings("developers", "For developers", "System", &["developer mode", "sideload apps", "dev drive"], "ms-settings:developers"),
+    // Bluetooth & devices
+    settings("bluetooth", "Bluetooth", "Bluetooth & devices", &["bluetooth devices", "pair device", "add device", "wireless headphones"], "ms-settings:bluetooth"),
+    settings("printers", "Printers & scanners", "Bluetooth & devices", &["printer", "scanner", "add printer", "print queue"], "ms-settings:printers"),
+    settings("mouse", "Mouse", "Bluetooth & devices", &["scroll speed", "primary button", "mouse speed", "left handed mouse"], "ms-settings:mousetouchpad"),
+    settings("touchpad", "Touchpad", "Bluetooth & devices", &["trackpad", "gestures", "tap to click"], "ms-settings:devices-touchpad"),
+    settings("typing", "Typing", "Bluetooth & devices", &["autocorrect", "spell check", "text suggestions", "touch keyboard"], "ms-settings:typing"),
+    settings("pen", "Pen & Windows Ink", "Bluetooth & devices", &["stylus", "windows ink", "handwriting"], "ms-settings:pen"),
+    settings("autoplay", "AutoPlay", "Bluetooth & devices", &["autorun", "removable drives", "memory card"], "ms-settings:autoplay"),
+    settings("usb", "USB", "Bluetooth & devices", &["usb notifications", "usb power"], "ms-settings:usb"),
+    settings("cameras", "Cameras", "Bluetooth & devices", &["webcam", "camera settings"], "ms-settings:camera"),
+    // Network & internet
+    settings("network_status", "Network status", "Network & internet", &["internet connection", "network settings", "network reset"], "ms-settings:network-status"),
+    settings("wifi", "Wi-Fi", "Network & internet", &["wifi", "wireless network", "wlan", "known networks"], "ms-settings:network-wifi"),
+    settings("ethernet", "Ethernet", "Network & internet", &["wired network", "lan", "ip address", "dns server"], "ms-settings:network-ethernet"),
+    settings("vpn", "VPN", "Network & internet", &["virtual private network", "add vpn"], "ms-settings:network-vpn"),
+    settings("airplane_mode", "Airplane mode", "Network & internet", &["flight mode", "turn off wireless"], "ms-settings:network-airplanemode"),
+    settings("hotspot", "Mobile hotspot", "Network & internet", &["hotspot", "share internet", "tethering"], "ms-settings:network-mobilehotspot"),
+    settings("proxy", "Proxy", "Network & internet", &["proxy server", "pac script"], "ms-settings:network-proxy"),
+    settings("data_usage", "Data usage", "Network & internet", &["metered connection", "data limit", "bandwidth"], "ms-settings:datausage"),
+    settings("dial_up", "Dial-up", "Network & internet", &["modem connection", "dial up"], "ms-settings:network-dialup"),
+    // Personalization
+    settings("background", "Background", "Personalization", &["wallpaper", "desktop background", "slideshow"], "ms-settings:personalization-background"),
+    settings("colors", "Colors", "Personalization", &["dark mode", "light mode", "accent color", "dark theme", "transparency"], "ms-settings:colors"),
+    settings("lock_screen", "Lock screen", "Personalization", &["lock screen picture", "screen saver", "windows spotlight"], "ms-settings:lockscreen"),
+    settings("themes", "Themes", "Personalization", &["theme", "desktop icons", "cursor theme"], "ms-settings:themes"),
+    settings("fonts", "Fonts", "Personalization", &["install font", "typefaces"], "ms-settings:fonts"),
+    settings("start", "Start", "Personalization", &["start menu", "recently added apps", "pinned folders"], "ms-settings:personalization-start"),
+    settings("taskbar", "Taskbar", "Personalization", &["system tray", "taskbar alignment", "hide taskbar", "tray icons"], "ms-settings:taskbar"),
+    // Apps
+    settings("installed_apps", "Installed apps", "Apps", &["apps & features", "uninstall apps", "remove app", "app list"], "ms-settings:appsfeatures"),
+    settings("default_apps", "Default apps", "Apps", &["default browser", "file associations", "open with", "default programs"], "ms-settings:defaultapps"),
+    settings("optional_features", "Optional features", "Apps", &["add feature", "windows capabilities"], "ms-settings:optionalfeatures"),
+    settings("startup_apps", "Startup apps", "Apps", &["startup programs", "run at login", "launch on startup", "autostart"], "ms-settings:startupapps"),
+    settings("apps_for_websites", "Apps for websites", "Apps", &["open links in apps"], "ms-settings:appsforwebsites"),
+    settings("offline_maps", "Offline maps", "Apps", &["download maps", "maps"], "ms-settings:maps"),
+    settings("video_playback", "Video playback", "Apps", &["hdr video", "video quality"], "ms-settings:videoplayback"),
+    // Accounts
+    settings("your_info", "Your info", "Accounts", &["account picture", "microsoft account", "profile picture"], "ms-settings:yourinfo"),
+    settings("email_accounts", "Email & accounts", "Accounts", &["email", "accounts used by apps", "add account"], "ms-settings:emailandaccounts"),
+    settings("sign_in", "Sign-in options", "Accounts", &["password", "pin", "windows hello", "fingerprint", "face recognition", "dynamic lock"], "ms-settings:signinoptions"),
+    settings("work_school", "Access work or school", "Accounts", &["work account", "school account", "join domain", "mdm"], "ms-settings:workplace"),
+    settings("other_users", "Other users", "Accounts", &["family", "add user", "guest account", "user accounts"], "ms-settings:otherusers"),
+    settings("windows_backup", "Windows backup", "Accounts", &["backup", "sync settings", "onedrive folders"], "ms-settings:sync"),
+    // Time & language
+    settings("date_time", "Date & time", "Time & language", &["clock", "time zone", "set time", "timezone", "sync time"], "ms-settings:dateandtime"),
+    settings("language", "Language & region", "Time & language", &["display language", "keyboard layout", "input language", "add language"], "ms-settings:regionlanguage"),
+    settings("region_format", "Regional format", "Time & language", &["date format", "number format", "currency format", "first day of week"], "ms-settings:regionformatting"),
+    settings("speech", "Speech", "Time & language", &["voice", "speech recognition", "text to speech"], "ms-settings:speech"),
+    settings("advanced_keyboard", "Advanced keyboard settings", "Time & language", &["input method", "language bar", "switch input"], "ms-settings:keyboard"),
+    // Gaming
+    settings("game_bar", "Game Bar", "Gaming", &["xbox game bar", "game overlay"], "ms-settings:gaming-gamebar"),
+    settings("captures", "Captures", "Gaming", &["game recording", "game dvr", "screen recording"], "ms-settings:gaming-gamedvr"),
+    settings("game_mode", "Game Mode", "Gaming", &["gaming performance"], "ms-settings:gaming-gamemode"),
+    // Accessibility
+    settings("text_size", "Text size", "Accessibility", &["bigger text", "font size", "larger text"], "ms-settings:easeofaccess-display"),
+    settings("magnifier", "Magnifier", "Accessibility", &["zoom screen", "magnify"], "ms-settings:easeofaccess-magnifier"),
+    settings("color_filters", "Color filters", "Accessibility", &["color blind", "grayscale", "invert colors"], "ms-settings:easeofaccess-colorfilter"),
+    settings("contrast_themes", "Contrast themes", "Accessibility", &["high contrast"], "ms-settings:easeofaccess-highcontrast"),
+    settings("narrator", "Narrator", "Accessibility", &["screen reader", "read aloud"], "ms-settings:easeofaccess-narrator"),
+    settings("captions", "Captions", "Accessibility", &["subtitles", "closed captions", "live captions"], "ms-settings:easeofaccess-closedcaptioning"),
+    settings("accessibility_keyboard", "Accessibility keyboard", "Accessibility", &["sticky keys", "filter keys", "on-screen keyboard", "toggle keys"], "ms-settings:easeofaccess-keyboard"),
+    settings("mouse_pointer", "Mouse pointer and touch", "Accessibility", &["cursor size", "pointer size", "pointer color", "bigger cursor"], "ms-settings:easeofaccess-mousepointer"),
+    // Privacy & security
+    settings("windows_security", "Windows Security", "Privacy & security", &["antivirus", "windows defender", "virus protection", "defender"], "ms-settings:windowsdefender"),
+    settings("find_my_device", "Find my device", "Privacy & security", &["lost device", "locate device"], "ms-settings:findmydevice"),
+    settings("location", "Location", "Privacy & security", &["location services", "gps", "location permission"], "ms-settings:privacy-location"),
+    settings("camera_privacy", "Camera access", "Privacy & security", &["camera permission", "webcam access"], "ms-settings:privacy-webcam"),
+    settings("microphone_privacy", "Microphone access", "Privacy & security", &["microphone", "mic permission", "mic access"], "ms-settings:privacy-microphone"),
+    settings("general_privacy", "General privacy", "Privacy & security", &["advertising id", "privacy", "tracking"], "ms-settings:privacy-general"),
+    // Windows Update
+    settings("windows_update", "Windows Update", "Windows Update", &["check for updates", "updates", "install updates"], "ms-settings:windowsupdate"),
+    settings("update_history", "Update history", "Windows Update", &["installed updates", "uninstall updates"], "ms-settings:windowsupdate-history"),
+    settings("update_options", "Advanced update options", "Windows Update", &["active hours", "pause updates", "optional updates"], "ms-settings:windowsupdate-options"),
+    settings("delivery_optimization", "Delivery Optimization", "Windows Update", &["download bandwidth", "update bandwidth"], "ms-settings:delivery-optimization"),
+    settings("insider", "Windows Insider Program", "Windows Update", &["insider", "preview builds", "beta channel"], "ms-settings:windowsinsider"),
+    // Control Panel items without a Settings page
+    control_panel("programs_features", "Programs and Features", "Programs", &["uninstall a program", "add remove programs", "appwiz"], "Microsoft.ProgramsAndFeatures"),
+    control_panel("devices_printers", "Devices and Printers", "Hardware and Sound", &["printer properties", "device list"], "Microsoft.DevicesAndPrinters"),
+    control_panel("power_options", "Power Options", "Hardware and Sound", &["power plan", "lid close action", "power button action", "high performance"], "Microsoft.PowerOptions"),
+    control_panel("sound_control_panel", "Sound Control Panel", "Hardware and Sound", &["playback devices", "recording devices", "sound scheme", "system sounds"], "Microsoft.Sound"),
+    control_panel("keyboard_properties", "Keyboard Properties", "Hardware and Sound", &["key repeat rate", "cursor blink rate"], "Microsoft.Keyboard"),
+    control_panel("color_management", "Color Management", "Hardware and Sound", &["color profile", "icc profile", "monitor calibration"], "Microsoft.ColorManagement"),
+    control_panel("network_sharing", "Network and Sharing Center", "Network and Internet", &["network adapters", "adapter settings", "sharing center"], "Microsoft.NetworkAndSharingCenter"),
+    control_panel("internet_options", "Internet Options", "Network and Internet", &["inetcpl", "internet properties"], "Microsoft.InternetOptions"),
+    control_panel("firewall", "Windows Defender Firewall", "System and Security", &["firewall", "allow app through firewall", "firewall rules"], "Microsoft.WindowsFirewall"),
+    control_panel("bitlocker", "BitLocker Drive Encryption", "System and Security", &["bitlocker", "drive encryption", "recovery key"], "Microsoft.BitLockerDriveEncryption"),
+    control_panel("backup_restore", "Backup and Restore (Windows 7)", "System and Security", &["system image", "restore files"], "Microsoft.BackupAndRestore"),
+    control_panel("file_history", "File History", "System and Security", &["file backup", "previous versions"], "Microsoft.FileHistory"),
+    control_panel("storage_spaces", "Storage Spaces", "System and Security", &["storage pool", "drive mirroring"], "Microsoft.StorageSpaces"),
+    control_panel("credential_manager", "Credential Manager", "User Accounts", &["saved passwords", "windows credentials", "credentials"], "Microsoft.CredentialManager"),
+    control_panel("file_explorer_options", "File Explorer Options", "Appearance and Personalization", &["folder options", "show hidden files", "file extensions"], "Microsoft.FolderOptions"),
+    control_panel("indexing_options", "Indexing Options", "System and Security", &["search index", "rebuild index", "indexed locations"], "Microsoft.IndexingOptions"),
+    control_panel("remote_app", "RemoteApp and Desktop Connections", "Network and Internet", &["remoteapp", "workspace connections"], "Microsoft.RemoteAppAndDesktopConnections"),
+    control_panel("speech_recognition", "Windows Speech Recognition", "Ease of Access", &["voice control", "microphone setup", "dictation training"], "Microsoft.SpeechRecognition"),
+];
+
+impl SettingsPage {
+    /// Looks up a page by id
+    pub fn find(id: &str) -> Option<&'static SettingsPage> {
+        SETTINGS_PAGES.iter().find(|page| page.id == id)
+    }
+
+    /// Lowercase name followed by the keywords
+    pub fn aliases(&self) -> impl Iterator<Item = String> + '_ {
+        std::iter::once(self.name.to_lowercase()).chain(self.keywords.iter().map(|keyword| keyword.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_targets_are_well_formed() {
+        for page in SETTINGS_PAGES {
+            match page.target {
+                SettingsTarget::Uri(uri) => {
+                    let path = uri.strip_prefix("ms-settings:").unwrap_or_else(|| panic!("{} isn't ms-settings", uri));
+                    assert!(
+                        path.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'),
+                        "{} has an invalid page name",
+                        uri
+                    );
+                    assert!(!path.starts_with('-') && !path.ends_with('-'), "{}", uri);
+                }
+                SettingsTarget::ControlPanel(name) => {
+                    let item = name.strip_prefix("Microsoft.").unwrap_or_else(|| panic!("{} isn't canonical", name));
+                    assert!(!item.is_empty() && item.chars().all(|c| c.is_ascii_alphanumeric()), "{}", name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ids_and_aliases_are_unique() {
+        let mut ids = HashSet::new();
+        let mut aliases = HashSet::new();
+        for page in SETTINGS_PAGES {
+            assert!(ids.insert(page.id), "duplicate id {}", page.id);
+            assert!(SettingsPage::find(page.id) == Some(page));
+            for alias in page.aliases() {
+                assert_eq!(alias, alias.trim().to_lowercase(), "keywords are lowercase and trimmed");
+                assert!(aliases.insert(alias.clone()), "duplicate alias {} on {}", alias, page.id);
+            }
+        }
+        assert!(SETTINGS_PAGES.len() >= 80);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::search::token_match::{significant_tokens, DEFAULT_STOPWORDS};
+use crate::utils::normalize_for_search;
+use unicode_normalization::UnicodeNormalization;
+
+/// A sanitized query together with the forms providers match against
+///
+/// The engine builds one per search from the NFC-normalized query, so "João"
+/// typed with a combining tilde reaches providers the same as the composed
+/// form. Providers that lowercase text themselves compare against
+/// `folded_query` and pass their candidates through `fold`; providers with
+/// indexes folded ahead of time keep folding the query with
+/// `normalize_for_search` to match them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryContext {
+    /// The query as typed, trimmed and NFC-normalized
+    pub query: String,
+    /// Lowercase query with diacritics stripped, unless folding is off
+    pub folded_query: String,
+    /// Whitespace-separated words of `folded_query`
+    pub tokens: Vec<String>,
+    /// Words of `tokens` the ranker matches one by one, without stopwords
+    pub match_tokens: Vec<String>,
+    fold_diacritics: bool,
+    /// Pasted text beyond what `query` keeps, for providers that handle it
+    full_text: Option<String>,
+}
+
+impl QueryContext {
+    /// Builds the context for a sanitized query
+    ///
+    /// With `fold_diacritics` off, matching only ignores case, so "joao" no
+    /// longer finds "João".
+    pub fn new(query: &str, fold_diacritics: bool) -> Self {
+        let folded_query = fold_text(query, fold_diacritics);
+        let tokens: Vec<String> = folded_query.split_whitespace().map(str::to_string).collect();
+        let stopwords: Vec<String> = DEFAULT_STOPWORDS.iter().map(|word| fold_text(word, fold_diacritics)).collect();
+        let match_tokens = significant_tokens(&tokens, &stopwords);
+
+        Self {
+            query: query.to_string(),
+            folded_query,
+            tokens,
+            match_tokens,
+            fold_diacritics,
+            full_text: None,
+        }
+    }
+
+    /// Attaches the full pasted text `query` was cut from
+    pub fn with_full_text(mut self, text: String) -> Self {
+        self.full_text = Some(text);
+        self
+    }
+
+    /// Returns the pasted text with its line breaks when the query was one,
+    /// otherwise `query`
+    ///
+    /// Only providers that opt into long text through `handles_blobs` see
+    /// pasted text at all.
+    pub fn full_text(&self) -> &str {
+        self.full_text.as_deref().unwrap_or(&self.query)
+    }
+
+    /// Replaces the default stopwords left out of `match_tokens`
+    pub fn with_stopwords(mut self, stopwords: &[String]) -> Self {
+        let stopwords: Vec<String> = stopwords.iter().map(|word| self.fold(word.trim())).collect();
+        self.match_tokens = significant_tokens(&self.tokens, &stopwords);
+        self
+    }
+
+    /// Normalizes `text` the way `folded_query` was, for comparing against it
+    pub fn fold(&self, text: &str) -> String {
+        fold_text(text, self.fold_diacritics)
+    }
+
+    /// Returns whether diacritics are ignored when matching
+    pub fn folds_diacritics(&self) -> bool {
+        self.fold_diacritics
+    }
+}
+
+impl From<&str> for QueryContext {
+    /// Builds a context with the default folding, mainly for tests
+    fn from(query: &str) -> Self {
+        Self::new(query, true)
+    }
+}
+
+fn fold_text(text: &str, fold_diacritics: bool) -> String {
+    if fold_diacritics {
+        normalize_for_search(text)
+    } else {
+        text.nfc().flat_map(char::to_lowercase).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_folded_query_and_tokens() {
+        let context = QueryContext::new("Relatório  São Paulo", true);
+        assert_eq!(context.query, "Relatório  São Paulo");
+        assert_eq!(context.folded_query, "relatorio  sao paulo");
+        assert_eq!(context.tokens, vec!["relatorio", "sao", "paulo"]);
+        assert_eq!(context.fold("JOÃO"), "joao");
+    }
+
+    #[test]
+    fn test_folding_off_only_ignores_case() {
+        let context = QueryContext::new("João", false);
+        assert_eq!(context.folded_query, "joão");
+        assert!(!context.folds_diacritics());
+
+        // Decomposed candidates still compare equal once composed
+        assert_eq!(context.fold("Joa\u{0303}o"), "joão");
+        assert_ne!(context.fold("Joao"), context.folded_query);
+    }
+
+    #[test]
+    fn test_match_tokens_skip_stopwords() {
+        let context = QueryContext::new("Notas da Reunião de março", true);
+        assert_eq!(context.match_tokens, vec!["notas", "reuniao", "marco"]);
+
+        // Configured stopwords are folded like the query and replace the defaults
+        let context = context.with_stopwords(&["Notas".to_string(), " MARÇO ".to_string()]);
+        assert_eq!(context.match_tokens, vec!["da", "reuniao", "de"]);
+    }
+}
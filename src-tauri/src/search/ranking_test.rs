@@ -0,0 +1,136 @@
+/// Golden ranking snapshots over a fixture world
+///
+/// A fixed battery of queries runs through a SearchEngine holding fixture
+/// providers plus the built-in providers whose answers are deterministic.
+/// The ranked (result id, score bucket) lists are compared with
+/// `fixtures/ranking.golden`, so any change in ordering fails the test.
+/// After an intended ranking change, regenerate the snapshot with
+/// `UPDATE_GOLDENS=1 cargo test ranking_snapshot` and review the diff.
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_support::{fixtures_dir, load_fixture, FixtureProvider, UsageTable};
+    use super::super::*;
+    use crate::search::providers::{CalculatorProvider, DirectUrlProvider, WebSearchProvider};
+    use crate::types::SearchResult;
+    use std::sync::Arc;
+
+    /// Scores are compared in buckets this wide, so small boost tweaks that
+    /// keep the order don't churn the snapshot
+    const SCORE_BUCKET: f64 = 10.0;
+
+    const GOLDEN_FILE: &str = "ranking.golden";
+
+    const QUERIES: &[&str] = &[
+        "chrome",
+        "chr",
+        "google",
+        "code",
+        "visual studio",
+        "spotify",
+        "slack",
+        "zoom",
+        "terminal",
+        "settings",
+        "notes",
+        "photo",
+        "docs",
+        "budget",
+        "invoice",
+        "readme",
+        "report",
+        "report 2024",
+        "2+2",
+        "10 * 3.5",
+        "how to x",
+        "how to center a div",
+        "what is rust",
+        "github",
+        "github.com",
+        "rust-lang.org",
+        "someone@example.com",
+        "localhost:3000",
+        "clip: password",
+        "clip: http",
+        "chorme",
+        "spotfy",
+        "slak",
+    ];
+
+    async fn fixture_engine() -> SearchEngine {
+        let engine = SearchEngine::new();
+        let usage: UsageTable = load_fixture("usage");
+
+        for fixture in ["apps", "bookmarks", "files", "clipboard"] {
+            engine
+                .register_provider(Arc::new(FixtureProvider::load(fixture, usage.clone())))
+                .await;
+        }
+        engine.register_provider(Arc::new(CalculatorProvider::new().unwrap())).await;
+        engine.register_provider(Arc::new(DirectUrlProvider::new().unwrap())).await;
+        engine.register_provider(Arc::new(WebSearchProvider::new().unwrap())).await;
+
+        engine
+    }
+
+    fn render_block(query: &str, results: &[SearchResult]) -> String {
+        let mut block = format!("query: {}\n", query);
+        for result in results {
+            let bucket = (result.score / SCORE_BUCKET).floor() * SCORE_BUCKET;
+            block.push_str(&format!("  {} ({})\n", result.id, bucket));
+        }
+        block
+    }
+
+    #[tokio::test]
+    async fn test_ranking_snapshot() {
+        let engine = fixture_engine().await;
+
+        let mut blocks = Vec::new();
+        for query in QUERIES {
+            blocks.push(render_block(query, &engine.search(query).await));
+        }
+        let snapshot = format!(
+            "# Ranked result ids and score buckets per query, best first.\n\
+             # Regenerate with UPDATE_GOLDENS=1 cargo test ranking_snapshot\n\n{}",
+            blocks.join("\n")
+        );
+
+        let path = fixtures_dir().join(GOLDEN_FILE);
+        if std::env::var_os("UPDATE_GOLDENS").is_some() {
+            std::fs::write(&path, &snapshot).unwrap();
+            return;
+        }
+
+        let expected = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("{} is missing; run with UPDATE_GOLDENS=1", path.display()))
+            .replace("\r\n", "\n");
+
+        if expected != snapshot {
+            // Point at the first query whose ranking moved
+            let changed = blocks
+                .iter()
+                .find(|block| !expected.contains(block.as_str()))
+                .map(String::as_str)
+                .unwrap_or("(header or query list changed)");
+            panic!(
+                "Ranking differs from {}; first changed query now ranks:\n{}\n\
+                 Regenerate with UPDATE_GOLDENS=1 if the change is intended",
+                GOLDEN_FILE, changed
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ranking_is_stable_across_runs() {
+        // Providers finish in a different order each run; ties must still sort the same way
+        for query in ["report", "docs", "chrome"] {
+            let first = fixture_engine().await.search(query).await;
+            for _ in 0..5 {
+                let again = fixture_engine().await.search(query).await;
+                let ids = |results: &[SearchResult]| results.iter().map(|r| r.id.clone()).collect::<Vec<_>>();
+                assert_eq!(ids(&first), ids(&again), "query '{}'", query);
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::types::{ResultType, SearchResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of executed results remembered for the boost
+pub const RECENT_CAPACITY: usize = 10;
+
+/// Boost for a result executed moments ago
+pub const RECENT_BOOST: f64 = 40.0;
+
+/// Time after which an execution no longer boosts its result
+pub const RECENT_BOOST_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Boost for a result executed `age` ago, falling linearly to nothing over
+/// `RECENT_BOOST_WINDOW`
+pub fn decayed_boost(age: Duration) -> f64 {
+    let remaining = RECENT_BOOST_WINDOW.saturating_sub(age);
+    RECENT_BOOST * remaining.as_secs_f64() / RECENT_BOOST_WINDOW.as_secs_f64()
+}
+
+/// A remembered execution, as reported by `get_diagnostics`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentExecution {
+    pub result_id: String,
+    /// Milliseconds since the result was executed
+    pub age_ms: u64,
+    /// Boost the result currently gets
+    pub boost: f64,
+}
+
+/// Results executed in the last few minutes, kept in memory only
+///
+/// Alternating between the same few results takes the provider scores a while
+/// to learn; these float to the top right away and settle back within
+/// `RECENT_BOOST_WINDOW`. The boost adds to whatever the provider score
+/// already includes, such as the Recent Files frecency bonus.
+#[derive(Debug, Default)]
+pub struct RecentExecutions {
+    /// Result ids and when they were executed, newest first
+    entries: Mutex<VecDeque<(String, Instant)>>,
+}
+
+impl RecentExecutions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers an executed result, moving it to the front if already known
+    pub fn record(&self, result_id: &str, now: Instant) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|(id, _)| id != result_id);
+        entries.push_front((result_id.to_string(), now));
+        entries.truncate(RECENT_CAPACITY);
+    }
+
+    /// Forgets every remembered execution
+    pub fn clear(&self) {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Takes the boosts at `now`, for ranking one set of results
+    pub fn boosts(&self, now: Instant) -> RecentBoosts {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let boosts = entries
+            .iter()
+            .map(|(id, executed)| (id.clone(), decayed_boost(now.saturating_duration_since(*executed))))
+            .filter(|(_, boost)| *boost > 0.0)
+            .collect();
+        RecentBoosts(boosts)
+    }
+
+    /// Lists the remembered executions, newest first
+    pub fn snapshot(&self, now: Instant) -> Vec<RecentExecution> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .iter()
+            .map(|(id, executed)| {
+                let age = now.saturating_duration_since(*executed);
+                RecentExecution {
+                    result_id: id.clone(),
+                    age_ms: age.as_millis() as u64,
+                    boost: decayed_boost(age),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Boosts of recently executed result ids at one moment
+#[derive(Debug, Clone, Default)]
+pub struct RecentBoosts(HashMap<String, f64>);
+
+impl RecentBoosts {
+    /// Returns the boost for `result`
+    ///
+    /// Clipboard items are already ordered by recency, and sorted listings
+    /// keep their provider order, so neither is boosted.
+    pub fn for_result(&self, result: &SearchResult) -> f64 {
+        if self.0.is_empty() || result.result_type == ResultType::Clipboard || result.preserves_provider_order() {
+            return 0.0;
+        }
+        self.0.get(&result.id).copied().unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResultAction;
+
+    fn result(id: &str, result_type: ResultType) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: id.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type,
+            score: 10.0,
+            metadata: HashMap::new(),
+            action: ResultAction::CopyToClipboard { content: id.to_string() },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    #[test]
+    fn test_boost_decays_over_the_window() {
+        assert_eq!(decayed_boost(Duration::ZERO), RECENT_BOOST);
+        assert_eq!(decayed_boost(RECENT_BOOST_WINDOW / 2), RECENT_BOOST / 2.0);
+        assert_eq!(decayed_boost(RECENT_BOOST_WINDOW), 0.0);
+        assert_eq!(decayed_boost(RECENT_BOOST_WINDOW * 2), 0.0);
+        assert!(decayed_boost(Duration::from_secs(60)) > decayed_boost(Duration::from_secs(120)));
+
+        let recent = RecentExecutions::new();
+        let start = Instant::now();
+        recent.record("app:code", start);
+        let boosts = recent.boosts(start + Duration::from_secs(150));
+        assert_eq!(boosts.for_result(&result("app:code", ResultType::Application)), 30.0);
+
+        // Expired executions stay in the ring but no longer boost
+        let later = start + RECENT_BOOST_WINDOW;
+        assert_eq!(recent.boosts(later).for_result(&result("app:code", ResultType::Application)), 0.0);
+        assert_eq!(recent.snapshot(later)[0].boost, 0.0);
+    }
+
+    #[test]
+    fn test_ring_keeps_the_latest_executions() {
+        let recent = RecentExecutions::new();
+        let now = Instant::now();
+        for i in 0..RECENT_CAPACITY + 2 {
+            recent.record(&format!("file:{}", i), now);
+        }
+        recent.record("file:5", now);
+
+        let ids: Vec<String> = recent.snapshot(now).into_iter().map(|entry| entry.result_id).collect();
+        assert_eq!(ids.len(), RECENT_CAPACITY);
+        assert_eq!(ids[0], "file:5");
+        assert_eq!(ids.iter().filter(|id| *id == "file:5").count(), 1);
+        assert!(!ids.contains(&"file:0".to_string()));
+    }
+
+    #[test]
+    fn test_clipboard_items_are_not_boosted() {
+        let recent = RecentExecutions::new();
+        let now = Instant::now();
+        recent.record("clipboard:1", now);
+        recent.record("app:code", now);
+
+        let boosts = recent.boosts(now);
+        assert_eq!(boosts.for_result(&result("clipboard:1", ResultType::Clipboard)), 0.0);
+        assert_eq!(boosts.for_result(&result("app:code", ResultType::Application)), RECENT_BOOST);
+        assert_eq!(boosts.for_result(&result("app:slack", ResultType::Application)), 0.0);
+    }
+}
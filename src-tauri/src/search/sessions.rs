@@ -0,0 +1,322 @@
+use crate::search::facets;
+use crate::types::{ResultPage, ResultType, SearchResult};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Number of searches whose results stay available for paging
+pub const MAX_SEARCH_SESSIONS: usize = 3;
+
+/// How long a search's results stay available after they were last read
+pub const SESSION_TTL: Duration = Duration::from_secs(60);
+
+/// Most results returned by one `page` call
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// The full result list of one search
+struct Session {
+    id: String,
+    /// Sanitized query the results were found for
+    query: String,
+    results: Vec<SearchResult>,
+    last_used: Instant,
+}
+
+/// Keeps the full result lists of recent searches so the frontend can page
+/// through them without searching again
+///
+/// Only the most recently used searches are kept, and each expires once it
+/// hasn't been read for the TTL. The engine clears every session when the
+/// providers change, since the lists no longer match what a search returns.
+pub struct SearchSessions {
+    /// Most recently used first
+    sessions: Mutex<VecDeque<Session>>,
+    capacity: usize,
+    ttl: Duration,
+    next_id: AtomicU64,
+}
+
+impl SearchSessions {
+    /// Creates a store holding `MAX_SEARCH_SESSIONS` searches for `SESSION_TTL`
+    pub fn new() -> Self {
+        Self::with_limits(MAX_SEARCH_SESSIONS, SESSION_TTL)
+    }
+
+    /// Creates a store holding `capacity` searches, each for `ttl` after its last read
+    pub fn with_limits(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            sessions: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            ttl,
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<Session>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Stores the results found for `query`, evicting the least recently used
+    /// search when full, and returns the id to page through them with
+    pub fn insert(&self, query: &str, results: Vec<SearchResult>) -> String {
+        let id = format!("search-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        let mut sessions = self.lock();
+        sessions.push_front(Session {
+            id: id.clone(),
+            query: query.to_string(),
+            results,
+            last_used: Instant::now(),
+        });
+        while sessions.len() > self.capacity {
+            let Some(evicted) = sessions.pop_back() else { break };
+            debug!("Evicted result session '{}'", evicted.id);
+        }
+
+        id
+    }
+
+    /// Returns up to `limit` results starting at `offset`, or `None` if the
+    /// search expired, was evicted or released
+    ///
+    /// Offsets past the end give an empty page; `limit` is capped at
+    /// `MAX_PAGE_SIZE`. Reading a page keeps the search alive for another TTL.
+    pub fn page(&self, search_id: &str, offset: usize, limit: usize) -> Option<ResultPage> {
+        self.page_of_types(search_id, offset, limit, &[])
+    }
+
+    /// Like `page`, counting only results of the `types` given, or of every
+    /// type when none are
+    pub fn page_of_types(
+        &self,
+        search_id: &str,
+        offset: usize,
+        limit: usize,
+        types: &[ResultType],
+    ) -> Option<ResultPage> {
+        let mut sessions = self.lock();
+        let now = Instant::now();
+        sessions.retain(|session| now.duration_since(session.last_used) < self.ttl);
+
+        let index = sessions.iter().position(|session| session.id == search_id)?;
+        let mut session = sessions.remove(index)?;
+        session.last_used = now;
+
+        let matching = || session.results.iter().filter(|result| facets::accepts(types, result.result_type));
+        let page = ResultPage {
+            offset,
+            total: matching().count(),
+            results: matching().skip(offset).take(limit.min(MAX_PAGE_SIZE)).cloned().collect(),
+        };
+
+        sessions.push_front(session);
+        Some(page)
+    }
+
+    /// Returns the id and results of the most recent search stored for
+    /// `query`, keeping it alive for another TTL
+    pub fn find_query(&self, query: &str) -> Option<(String, Vec<SearchResult>)> {
+        let mut sessions = self.lock();
+        let now = Instant::now();
+        sessions.retain(|session| now.duration_since(session.last_used) < self.ttl);
+
+        let index = sessions.iter().position(|session| session.query == query)?;
+        let mut session = sessions.remove(index)?;
+        session.last_used = now;
+        let found = (session.id.clone(), session.results.clone());
+
+        sessions.push_front(session);
+        Some(found)
+    }
+
+    /// Returns whether a search's results are still stored
+    pub fn contains(&self, search_id: &str) -> bool {
+        let now = Instant::now();
+        self.lock()
+            .iter()
+            .any(|session| session.id == search_id && now.duration_since(session.last_used) < self.ttl)
+    }
+
+    /// Returns the results among `result_ids` still waiting for enrichment,
+    /// marking them as taken so each is enriched once, or `None` if the
+    /// search is no longer stored
+    pub fn take_pending(&self, search_id: &str, result_ids: &[String]) -> Option<Vec<SearchResult>> {
+        let mut sessions = self.lock();
+        let now = Instant::now();
+        let session = sessions
+            .iter_mut()
+            .find(|session| session.id == search_id && now.duration_since(session.last_used) < self.ttl)?;
+
+        let mut pending = Vec::new();
+        for result in session.results.iter_mut() {
+            if result.enrichment_pending && result_ids.contains(&result.id) {
+                pending.push(result.clone());
+                result.enrichment_pending = false;
+            }
+        }
+        Some(pending)
+    }
+
+    /// Replaces a stored result with its enriched version, returning whether
+    /// the search is still stored
+    pub fn update(&self, search_id: &str, enriched: &SearchResult) -> bool {
+        let mut sessions = self.lock();
+        let Some(session) = sessions.iter_mut().find(|session| session.id == search_id) else {
+            return false;
+        };
+        if let Some(result) = session.results.iter_mut().find(|result| result.id == enriched.id) {
+            *result = enriched.clone();
+        }
+        true
+    }
+
+    /// Returns the stored result with the given id from the most recently
+    /// used search containing it
+    pub fn find(&self, result_id: &str) -> Option<SearchResult> {
+        let now = Instant::now();
+        self.lock()
+            .iter()
+            .filter(|session| now.duration_since(session.last_used) < self.ttl)
+            .find_map(|session| session.results.iter().find(|result| result.id == result_id))
+            .cloned()
+    }
+
+    /// Drops a search's results, returning whether it was still stored
+    pub fn release(&self, search_id: &str) -> bool {
+        let mut sessions = self.lock();
+        let before = sessions.len();
+        sessions.retain(|session| session.id != search_id);
+        sessions.len() != before
+    }
+
+    /// Drops every stored search
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+}
+
+impl Default for SearchSessions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ResultAction, ResultType};
+    use std::collections::HashMap;
+
+    fn results(count: usize) -> Vec<SearchResult> {
+        (0..count)
+            .map(|i| SearchResult {
+                id: format!("result-{}", i),
+                title: format!("Result {}", i),
+                subtitle: String::new(),
+                icon: None,
+                result_type: ResultType::File,
+                score: 100.0 - i as f64,
+                metadata: HashMap::new(),
+                action: ResultAction::OpenFile { path: format!("C:\\{}.txt", i) },
+                normalized_title: None,
+                enrichment_pending: false,
+                integrity: None,
+            })
+            .collect()
+    }
+
+    fn ids(page: &ResultPage) -> Vec<&str> {
+        page.results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_offset_and_limit_edge_cases() {
+        let sessions = SearchSessions::new();
+        let id = sessions.insert("query", results(5));
+
+        let page = sessions.page(&id, 0, 2).unwrap();
+        assert_eq!(ids(&page), vec!["result-0", "result-1"]);
+        assert_eq!(page.total, 5);
+
+        // A page running past the end is cut short
+        assert_eq!(ids(&sessions.page(&id, 3, 10).unwrap()), vec!["result-3", "result-4"]);
+        // Offsets at or past the end and empty limits give empty pages
+        assert!(sessions.page(&id, 5, 10).unwrap().results.is_empty());
+        assert!(sessions.page(&id, usize::MAX, usize::MAX).unwrap().results.is_empty());
+        assert!(sessions.page(&id, 1, 0).unwrap().results.is_empty());
+
+        let id = sessions.insert("query", results(MAX_PAGE_SIZE + 20));
+        assert_eq!(sessions.page(&id, 0, usize::MAX).unwrap().results.len(), MAX_PAGE_SIZE);
+
+        assert!(sessions.page("search-unknown", 0, 10).is_none());
+    }
+
+    #[test]
+    fn test_narrowed_pages_and_lookup_by_query() {
+        let sessions = SearchSessions::new();
+        let mut mixed = results(6);
+        for result in mixed.iter_mut().skip(1).step_by(2) {
+            result.result_type = ResultType::Application;
+        }
+        let id = sessions.insert("mixed", mixed);
+        sessions.insert("other", results(1));
+
+        let apps = sessions.page_of_types(&id, 1, 10, &[ResultType::Application]).unwrap();
+        assert_eq!(apps.total, 3);
+        assert_eq!(ids(&apps), vec!["result-3", "result-5"]);
+        assert_eq!(sessions.page_of_types(&id, 0, 10, &[]).unwrap().total, 6);
+
+        let (found, results) = sessions.find_query("mixed").unwrap();
+        assert_eq!(found, id);
+        assert_eq!(results.len(), 6);
+        assert!(sessions.find_query("missing").is_none());
+    }
+
+    #[test]
+    fn test_keeps_the_three_most_recently_used_searches() {
+        let sessions = SearchSessions::new();
+        let first = sessions.insert("query", results(1));
+        let second = sessions.insert("query", results(1));
+        let third = sessions.insert("query", results(1));
+
+        // Reading the oldest search makes the second one least recently used
+        assert!(sessions.page(&first, 0, 1).is_some());
+        let fourth = sessions.insert("query", results(1));
+
+        assert!(sessions.page(&second, 0, 1).is_none());
+        for id in [&first, &third, &fourth] {
+            assert!(sessions.page(id, 0, 1).is_some(), "{} was evicted", id);
+        }
+    }
+
+    #[test]
+    fn test_sessions_expire_unless_read() {
+        let sessions = SearchSessions::with_limits(MAX_SEARCH_SESSIONS, Duration::from_millis(80));
+        let read = sessions.insert("query", results(3));
+        let unread = sessions.insert("query", results(3));
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(sessions.page(&read, 0, 1).is_some());
+        std::thread::sleep(Duration::from_millis(50));
+
+        assert!(sessions.page(&read, 1, 1).is_some());
+        assert!(sessions.page(&unread, 0, 1).is_none());
+    }
+
+    #[test]
+    fn test_release_and_clear() {
+        let sessions = SearchSessions::new();
+        let released = sessions.insert("query", results(2));
+        let kept = sessions.insert("query", results(2));
+
+        assert!(sessions.release(&released));
+        assert!(!sessions.release(&released));
+        assert!(sessions.page(&released, 0, 1).is_none());
+        assert!(sessions.page(&kept, 0, 1).is_some());
+
+        sessions.clear();
+        assert!(sessions.page(&kept, 0, 1).is_none());
+    }
+}
@@ -0,0 +1,188 @@
+/// Number shortcuts that stay on their results while a query is typed
+///
+/// Alt+1 to Alt+9 launch the first nine results, but scores shift with each
+/// keystroke, so the result under Alt+2 could change just before it's
+/// pressed. With `stable_result_slots` on, a search whose query extends or
+/// shortens the previous one keeps the results both returned in their
+/// previous order, unless one now scores more than `SLOT_HYSTERESIS` above
+/// another. Results new to the top nine join below them rather than taking
+/// slot 1 mid-typing. The slots are forgotten when the window hides.
+
+use crate::types::{ResultSlot, SearchResult};
+use std::sync::Mutex;
+
+/// Results with a number shortcut
+pub const SLOT_COUNT: usize = 9;
+
+/// Score lead a result needs to move above one holding an earlier slot
+pub const SLOT_HYSTERESIS: f64 = 10.0;
+
+/// Top results of the last search of the session
+#[derive(Debug)]
+struct PreviousSlots {
+    /// Lowercase query of the search
+    query: String,
+    /// Ids in slot order
+    ids: Vec<String>,
+}
+
+/// The slots of the search being typed, kept until the window hides
+#[derive(Debug, Default)]
+pub struct SlotMemory(Mutex<Option<PreviousSlots>>);
+
+impl SlotMemory {
+    /// Reorders the top of `results` to keep the previous search's slots when
+    /// `query` continues it, then remembers the slots `results` end up with
+    pub fn stabilize(&self, query: &str, results: &mut Vec<SearchResult>) {
+        let query = query.to_lowercase();
+        let mut previous = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(previous) = previous.as_ref().filter(|previous| continues(&previous.query, &query)) {
+            keep_slots(&previous.ids, results);
+        }
+
+        let ids = results.iter().take(SLOT_COUNT).map(|result| result.id.clone()).collect();
+        *previous = Some(PreviousSlots { query, ids });
+    }
+
+    /// Forgets the slots, so the next search ranks freely
+    pub fn clear(&self) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// Whether `query` is the previous one typed further or backspaced
+fn continues(previous: &str, query: &str) -> bool {
+    !previous.is_empty() && !query.is_empty() && (query.starts_with(previous) || previous.starts_with(query))
+}
+
+/// Puts the top results still there back in their previous order, unless
+/// one leads another by `SLOT_HYSTERESIS`, ahead of the ones new to the top
+fn keep_slots(previous_ids: &[String], results: &mut Vec<SearchResult>) {
+    let top = results.len().min(SLOT_COUNT);
+    let (mut kept, entering): (Vec<SearchResult>, Vec<SearchResult>) =
+        results.drain(..top).partition(|result| previous_ids.contains(&result.id));
+    kept.sort_by_key(|result| previous_ids.iter().position(|id| *id == result.id));
+
+    let mut block: Vec<SearchResult> = Vec::with_capacity(top);
+    for result in kept {
+        let at = block
+            .iter()
+            .position(|placed| result.score - placed.score > SLOT_HYSTERESIS)
+            .unwrap_or(block.len());
+        block.insert(at, result);
+    }
+    block.extend(entering);
+    results.splice(..0, block);
+}
+
+/// Slots of the first results on a page
+pub fn assign_slots(results: &[SearchResult]) -> Vec<ResultSlot> {
+    results
+        .iter()
+        .take(SLOT_COUNT)
+        .enumerate()
+        .map(|(index, result)| ResultSlot {
+            slot: index as u8 + 1,
+            result_id: result.id.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ResultAction, ResultType};
+    use std::collections::HashMap;
+
+    fn result(id: &str, score: f64) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: id.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type: ResultType::Application,
+            score,
+            metadata: HashMap::new(),
+            action: ResultAction::OpenFile { path: format!("C:\\Apps\\{}.exe", id) },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    /// Results ranked by score, as the engine returns them
+    fn ranked(scores: &[(&str, f64)]) -> Vec<SearchResult> {
+        let mut results: Vec<SearchResult> = scores.iter().map(|(id, score)| result(id, *score)).collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|result| result.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_slots_hold_under_small_score_shifts() {
+        let memory = SlotMemory::default();
+
+        let mut results = ranked(&[("notepad", 60.0), ("notes", 55.0), ("notion", 50.0)]);
+        memory.stabilize("no", &mut results);
+        assert_eq!(ids(&results), vec!["notepad", "notes", "notion"]);
+
+        // "notes" edges ahead, but not by enough to take slot 1
+        let mut results = ranked(&[("notepad", 58.0), ("notes", 63.0), ("notion", 52.0)]);
+        memory.stabilize("not", &mut results);
+        assert_eq!(ids(&results), vec!["notepad", "notes", "notion"]);
+
+        // A new result scoring a little higher joins at the bottom of the block
+        let mut results = ranked(&[("notepad", 60.0), ("notebook", 66.0), ("notes", 62.0), ("notion", 50.0)]);
+        memory.stabilize("note", &mut results);
+        assert_eq!(ids(&results), vec!["notepad", "notes", "notion", "notebook"]);
+
+        // Backspacing continues the session too
+        let mut results = ranked(&[("notes", 64.0), ("notebook", 61.0), ("notepad", 60.0), ("notion", 52.0)]);
+        memory.stabilize("not", &mut results);
+        assert_eq!(ids(&results), vec!["notepad", "notes", "notion", "notebook"]);
+        assert_eq!(assign_slots(&results)[3], ResultSlot { slot: 4, result_id: "notebook".to_string() });
+    }
+
+    #[test]
+    fn test_large_leads_and_new_queries_rank_freely() {
+        let memory = SlotMemory::default();
+
+        let mut results = ranked(&[("calc", 60.0), ("calendar", 50.0), ("camera", 40.0)]);
+        memory.stabilize("ca", &mut results);
+
+        // "calendar" now leads by more than the hysteresis, while a new
+        // result still joins below, however high it scores
+        let mut results = ranked(&[("calc", 45.0), ("calendar", 70.0), ("calendly", 95.0), ("camera", 41.0)]);
+        memory.stabilize("cal", &mut results);
+        assert_eq!(ids(&results), vec!["calendar", "calc", "camera", "calendly"]);
+
+        // A result pushed out of the top nine doesn't hold its slot
+        let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i"];
+        let mut scores: Vec<(&str, f64)> = names.iter().enumerate().map(|(i, name)| (*name, 90.0 - i as f64)).collect();
+        scores.push(("calendly", 20.0));
+        let mut results = ranked(&scores);
+        memory.stabilize("cale", &mut results);
+        assert_eq!(ids(&results)[..SLOT_COUNT], names);
+        assert_eq!(ids(&results)[SLOT_COUNT], "calendly");
+
+        // A query that doesn't continue the last one starts over
+        let mut results = ranked(&[("term", 40.0), ("calc", 80.0)]);
+        memory.stabilize("te", &mut results);
+        assert_eq!(ids(&results), vec!["calc", "term"]);
+    }
+
+    #[test]
+    fn test_clearing_ends_the_session() {
+        let memory = SlotMemory::default();
+        let mut results = ranked(&[("notepad", 60.0), ("notes", 55.0)]);
+        memory.stabilize("no", &mut results);
+
+        memory.clear();
+        let mut results = ranked(&[("notepad", 58.0), ("notes", 63.0)]);
+        memory.stabilize("not", &mut results);
+        assert_eq!(ids(&results), vec!["notes", "notepad"]);
+    }
+}
@@ -0,0 +1,263 @@
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::normalize_for_search;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Maximum number of terms kept in the dictionary
+pub const MAX_DICTIONARY_TERMS: usize = 20_000;
+
+/// Maximum number of terms requested from each provider
+pub const TERMS_PER_PROVIDER: usize = 5_000;
+
+/// How long a dictionary is used before it is rebuilt from the provider caches
+pub const DICTIONARY_TTL: Duration = Duration::from_secs(300);
+
+/// Shortest word kept as a term of its own
+const MIN_TERM_LEN: usize = 3;
+
+/// Edit distance, length difference and first-character mismatch; lower is better
+type Rank = (usize, usize, bool);
+
+/// Computes the Damerau-Levenshtein distance (optimal string alignment) between
+/// two strings, giving up once it exceeds `max`
+///
+/// Returns `None` when the distance is larger than `max`.
+pub fn damerau_levenshtein(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut before_previous: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        let mut row_min = current[0];
+
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+
+            // Adjacent transposition
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+
+            row_min = row_min.min(current[j]);
+        }
+
+        // Every later row is at least this row's minimum
+        if row_min > max {
+            return None;
+        }
+
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let distance = previous[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+/// Returns how many edits a query of `len` characters may be away from a suggestion
+///
+/// Short queries get no suggestions since almost everything is a couple of
+/// edits away from them.
+pub fn max_distance_for(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// Terms that a mistyped query can be corrected to
+#[derive(Debug)]
+pub struct SuggestionDictionary {
+    /// Normalized term and the text shown to the user, in insertion order
+    terms: Vec<(String, String)>,
+    /// When the dictionary was built
+    built_at: Instant,
+}
+
+impl SuggestionDictionary {
+    /// Builds a dictionary from names such as app and bookmark titles
+    ///
+    /// Each name contributes itself and each of its words, so "Google Chrome"
+    /// can correct both "gogle chrome" and "chrme".
+    pub fn build<I>(names: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut seen = HashSet::new();
+        let mut terms = Vec::new();
+
+        'names: for name in names {
+            let words = name
+                .split_whitespace()
+                .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+                .filter(|word| word.chars().count() >= MIN_TERM_LEN);
+
+            for display in std::iter::once(name.trim()).chain(words) {
+                if terms.len() >= MAX_DICTIONARY_TERMS {
+                    break 'names;
+                }
+
+                let normalized = normalize_for_search(display);
+                if normalized.chars().count() >= MIN_TERM_LEN && seen.insert(normalized.clone()) {
+                    terms.push((normalized, display.to_string()));
+                }
+            }
+        }
+
+        Self {
+            terms,
+            built_at: Instant::now(),
+        }
+    }
+
+    /// Returns the number of terms
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns true if the dictionary holds no terms
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Checks whether the dictionary should be rebuilt
+    pub fn is_stale(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.built_at) >= DICTIONARY_TTL
+    }
+
+    /// Finds the closest term to a mistyped query
+    ///
+    /// Prefers the fewest edits, then the closest length, then terms starting
+    /// with the same character. Exact matches are not suggestions.
+    pub fn suggest(&self, query: &str) -> Option<&str> {
+        let query = normalize_for_search(query);
+        let query_len = query.chars().count();
+        let max = max_distance_for(query_len);
+        if max == 0 {
+            return None;
+        }
+
+        let first_char = query.chars().next();
+        let mut best: Option<(Rank, &str)> = None;
+
+        for term in &self.terms {
+            let term_len = term.0.chars().count();
+            if term_len.abs_diff(query_len) > max {
+                continue;
+            }
+
+            let Some(distance) = damerau_levenshtein(&query, &term.0, max) else {
+                continue;
+            };
+            if distance == 0 {
+                continue;
+            }
+
+            let rank = (distance, term_len.abs_diff(query_len), term.0.chars().next() != first_char);
+            if best.is_none_or(|(best_rank, _)| rank < best_rank) {
+                best = Some((rank, term.1.as_str()));
+            }
+        }
+
+        best.map(|(_, display)| display)
+    }
+}
+
+/// Creates the "Did you mean" result for a corrected query
+pub fn suggestion_result(term: &str) -> SearchResult {
+    SearchResult {
+        id: format!("suggestion:{}", term),
+        title: format!("Did you mean: {}?", term),
+        subtitle: format!("Search for \"{}\" instead", term),
+        icon: None,
+        result_type: ResultType::Suggestion,
+        score: 0.0,
+        metadata: HashMap::new(),
+        action: ResultAction::SetQuery {
+            query: term.to_string(),
+        },
+        normalized_title: None,
+        enrichment_pending: false,
+        integrity: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary(names: &[&str]) -> SuggestionDictionary {
+        SuggestionDictionary::build(names.iter().map(|name| name.to_string()))
+    }
+
+    #[test]
+    fn test_damerau_levenshtein_distance() {
+        assert_eq!(damerau_levenshtein("chrome", "chrome", 2), Some(0));
+        assert_eq!(damerau_levenshtein("chrme", "chrome", 2), Some(1));
+        assert_eq!(damerau_levenshtein("chorme", "chrome", 2), Some(1)); // transposition
+        assert_eq!(damerau_levenshtein("kitten", "sitting", 3), Some(3));
+        assert_eq!(damerau_levenshtein("", "abc", 3), Some(3));
+
+        // Bounded: anything beyond `max` is reported as None
+        assert_eq!(damerau_levenshtein("kitten", "sitting", 2), None);
+        assert_eq!(damerau_levenshtein("a", "abcdef", 2), None);
+        assert_eq!(damerau_levenshtein("spotify", "firefox", 2), None);
+    }
+
+    #[test]
+    fn test_max_distance_depends_on_length() {
+        assert_eq!(max_distance_for(3), 0);
+        assert_eq!(max_distance_for(5), 1);
+        assert_eq!(max_distance_for(6), 2);
+    }
+
+    #[test]
+    fn test_suggest_closest_term() {
+        let dictionary = dictionary(&["Google Chrome", "Visual Studio Code", "Spotify", "GitHub"]);
+
+        assert_eq!(dictionary.suggest("chrme"), Some("Chrome"));
+        assert_eq!(dictionary.suggest("spotfy"), Some("Spotify"));
+        assert_eq!(dictionary.suggest("gogle chrome"), Some("Google Chrome"));
+        assert_eq!(dictionary.suggest("Vsiual"), Some("Visual"));
+    }
+
+    #[test]
+    fn test_no_suggestion_for_unrelated_or_short_queries() {
+        let dictionary = dictionary(&["Google Chrome", "Spotify"]);
+
+        assert_eq!(dictionary.suggest("xqzvwk"), None);
+        assert_eq!(dictionary.suggest("chr"), None);
+        assert_eq!(dictionary.suggest("chrome"), None);
+    }
+
+    #[test]
+    fn test_dictionary_dedups_and_caps_terms() {
+        let dictionary = dictionary(&["Chrome", "chrome", "Google Chrome", "ab"]);
+        assert_eq!(dictionary.len(), 3); // chrome, google chrome, google
+
+        let many = SuggestionDictionary::build((0..MAX_DICTIONARY_TERMS + 100).map(|i| format!("term{}", i)));
+        assert_eq!(many.len(), MAX_DICTIONARY_TERMS);
+        assert!(!many.is_stale(Instant::now()));
+        assert!(many.is_stale(Instant::now() + DICTIONARY_TTL));
+    }
+
+    #[test]
+    fn test_suggestion_result_sets_query() {
+        let result = suggestion_result("Chrome");
+        assert_eq!(result.result_type, ResultType::Suggestion);
+        assert_eq!(result.title, "Did you mean: Chrome?");
+        assert!(matches!(result.action, ResultAction::SetQuery { ref query } if query == "Chrome"));
+    }
+}
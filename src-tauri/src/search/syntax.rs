@@ -0,0 +1,164 @@
+use crate::types::{ResultAction, ResultType, SearchResult};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Metadata key naming the syntax feature a help or hint result describes
+pub const SYNTAX_FEATURE_KEY: &str = "syntax_feature";
+
+/// A piece of query syntax a provider understands, e.g. `sort:` or `clip:`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxFeature {
+    /// Stable identifier; providers sharing a feature report the same one
+    pub id: &'static str,
+    /// Name shown in the help result title
+    pub title: &'static str,
+    /// What the syntax does
+    pub description: &'static str,
+    /// Query the help result fills in when selected
+    pub example: &'static str,
+    /// Word that introduces the syntax and takes a value, e.g. `sort:`
+    pub token: Option<&'static str>,
+    /// Values accepted right after `token`
+    pub values: &'static [&'static str],
+}
+
+impl SyntaxFeature {
+    /// Whether `word` is this feature's token without a value yet
+    fn is_incomplete_token(&self, word: &str) -> bool {
+        !self.values.is_empty() && self.token.is_some_and(|token| word.eq_ignore_ascii_case(token))
+    }
+}
+
+/// Query syntax offered by the registered providers
+///
+/// The engine fills it from the enabled providers whenever the providers or
+/// their options change, so help only lists what actually works. Clones
+/// share the features, letting the help provider read what the engine wrote.
+#[derive(Debug, Clone, Default)]
+pub struct SyntaxRegistry(Arc<RwLock<Vec<SyntaxFeature>>>);
+
+impl SyntaxRegistry {
+    /// Replaces the features, keeping the first of any sharing an id
+    pub fn set(&self, features: Vec<SyntaxFeature>) {
+        let mut unique: Vec<SyntaxFeature> = Vec::with_capacity(features.len());
+        for feature in features {
+            if !unique.iter().any(|known| known.id == feature.id) {
+                unique.push(feature);
+            }
+        }
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = unique;
+    }
+
+    /// Returns the available features in registration order
+    pub fn features(&self) -> Vec<SyntaxFeature> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Words that start the features' syntax, lowercase and without a trailing `:`
+    ///
+    /// Taken from the token, or from the example for features started by a
+    /// keyword such as `ssh`.
+    pub fn keywords(&self) -> Vec<String> {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter_map(|feature| feature.token.or_else(|| feature.example.split_whitespace().next()))
+            .map(|word| word.trim_end_matches(':').to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect()
+    }
+
+    /// Finds the feature whose token ends the query without a value, e.g. `report sort:`
+    pub fn incomplete_token(&self, query: &str) -> Option<SyntaxFeature> {
+        let last_word = query.split_whitespace().next_back()?;
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|feature| feature.is_incomplete_token(last_word))
+            .cloned()
+    }
+
+    /// Low-score result listing the values the query's trailing token accepts
+    ///
+    /// Selecting it completes the token with the first value.
+    pub fn hint_for(&self, query: &str) -> Option<SearchResult> {
+        let feature = self.incomplete_token(query)?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert(SYNTAX_FEATURE_KEY.to_string(), serde_json::json!(feature.id));
+
+        Some(SearchResult {
+            id: format!("syntax_hint:{}", feature.id),
+            title: format!("{} {}", feature.token.unwrap_or_default(), feature.values.join(", ")),
+            subtitle: feature.description.to_string(),
+            icon: None,
+            result_type: ResultType::Suggestion,
+            score: 0.0,
+            metadata,
+            action: ResultAction::SetQuery {
+                query: format!("{}{}", query.trim(), feature.values[0]),
+            },
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SORT: SyntaxFeature = SyntaxFeature {
+        id: "sort",
+        title: "Sort files",
+        description: "Orders file results",
+        example: "sort:new report",
+        token: Some("sort:"),
+        values: &["new", "old"],
+    };
+
+    const CLIP: SyntaxFeature = SyntaxFeature {
+        id: "clip",
+        title: "Clipboard history",
+        description: "Searches copied text",
+        example: "clip: today",
+        token: Some("clip:"),
+        values: &[],
+    };
+
+    #[test]
+    fn test_incomplete_token_detection() {
+        let registry = SyntaxRegistry::default();
+        registry.set(vec![CLIP, SORT]);
+
+        assert_eq!(registry.incomplete_token("report sort:").map(|f| f.id), Some("sort"));
+        assert_eq!(registry.incomplete_token("SORT:").map(|f| f.id), Some("sort"));
+        assert_eq!(registry.incomplete_token("report sort:new"), None);
+        assert_eq!(registry.incomplete_token("sort: report"), None);
+        // Tokens that are complete on their own never get a hint
+        assert_eq!(registry.incomplete_token("clip:"), None);
+
+        let hint = registry.hint_for("report sort:").unwrap();
+        assert_eq!(hint.title, "sort: new, old");
+        assert_eq!(hint.score, 0.0);
+        assert!(matches!(hint.action, ResultAction::SetQuery { ref query } if query == "report sort:new"));
+    }
+
+    #[test]
+    fn test_features_shared_by_providers_are_listed_once() {
+        let registry = SyntaxRegistry::default();
+        let shared = registry.clone();
+        registry.set(vec![SORT, CLIP, SORT]);
+
+        let ids: Vec<&str> = shared.features().iter().map(|f| f.id).collect();
+        assert_eq!(ids, vec!["sort", "clip"]);
+        assert_eq!(shared.keywords(), vec!["sort", "clip"]);
+
+        registry.set(Vec::new());
+        assert!(shared.features().is_empty());
+        assert!(shared.hint_for("sort:").is_none());
+    }
+}
@@ -0,0 +1,173 @@
+/// Test utilities shared by the search tests
+///
+/// `FixtureProvider` answers searches from a JSON fixture file in
+/// `src/search/fixtures`, so tests can build a deterministic set of apps,
+/// bookmarks, files and clipboard items without touching the machine.
+
+use crate::error::{LauncherError, Result};
+use crate::search::{QueryContext, SearchProvider};
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::utils::normalize_for_search;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Directory holding the fixture files and golden snapshots
+pub fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src").join("search").join("fixtures")
+}
+
+/// Reads and parses `fixtures/<name>.json`
+pub fn load_fixture<T: serde::de::DeserializeOwned>(name: &str) -> T {
+    let path = fixtures_dir().join(format!("{}.json", name));
+    let content = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Failed to read fixture {}: {}", path.display(), e));
+    serde_json::from_str(&content)
+        .unwrap_or_else(|e| panic!("Failed to parse fixture {}: {}", path.display(), e))
+}
+
+/// One searchable entry in a fixture file
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixtureItem {
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    /// Score the provider reports before usage is added
+    pub score: f64,
+    /// Extra words the item matches, also offered as spelling suggestions
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+/// A provider described by a fixture file
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixtureFile {
+    pub name: String,
+    pub priority: u8,
+    pub result_type: ResultType,
+    /// Query prefix such as `clip:` that is stripped before matching
+    #[serde(default)]
+    pub prefix: Option<String>,
+    pub items: Vec<FixtureItem>,
+}
+
+/// How often each result id was opened; loaded from `fixtures/usage.json`
+///
+/// The engine has no usage ranking of its own, so fixture providers fold
+/// these counts into the scores they report, like real providers would.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct UsageTable(HashMap<String, u32>);
+
+impl UsageTable {
+    /// Score bonus for a result, growing logarithmically with its use count
+    pub fn boost(&self, id: &str) -> f64 {
+        let count = self.0.get(id).copied().unwrap_or(0);
+        (f64::from(count) + 1.0).log2() * 4.0
+    }
+}
+
+/// Search provider serving the items of a fixture file
+pub struct FixtureProvider {
+    fixture: FixtureFile,
+    usage: UsageTable,
+}
+
+impl FixtureProvider {
+    /// Loads `fixtures/<name>.json`, scoring results with `usage`
+    pub fn load(name: &str, usage: UsageTable) -> Self {
+        Self {
+            fixture: load_fixture(name),
+            usage,
+        }
+    }
+
+    /// Whether every query word appears in the title or a keyword
+    fn matches(item: &FixtureItem, words: &[String]) -> bool {
+        let title = normalize_for_search(&item.title);
+        let keywords: Vec<String> = item.keywords.iter().map(|k| normalize_for_search(k)).collect();
+
+        words
+            .iter()
+            .all(|word| title.contains(word.as_str()) || keywords.iter().any(|k| k.contains(word.as_str())))
+    }
+
+    fn action(&self, item: &FixtureItem) -> ResultAction {
+        match self.fixture.result_type {
+            ResultType::Application => ResultAction::LaunchApp { path: item.subtitle.clone() },
+            ResultType::Bookmark | ResultType::History => ResultAction::OpenUrl { url: item.subtitle.clone() },
+            ResultType::Clipboard => ResultAction::CopyToClipboard { content: item.title.clone() },
+            _ => ResultAction::OpenFile { path: item.subtitle.clone() },
+        }
+    }
+}
+
+#[async_trait]
+impl SearchProvider for FixtureProvider {
+    fn name(&self) -> &str {
+        &self.fixture.name
+    }
+
+    fn priority(&self) -> u8 {
+        self.fixture.priority
+    }
+
+    fn result_types(&self) -> Option<&'static [ResultType]> {
+        None
+    }
+
+    async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+        let query = query.query.as_str();
+        let query = match &self.fixture.prefix {
+            Some(prefix) => query.trim().strip_prefix(prefix.as_str()).unwrap_or(query),
+            None => query,
+        };
+
+        let words: Vec<String> = normalize_for_search(query).split_whitespace().map(str::to_string).collect();
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(self
+            .fixture
+            .items
+            .iter()
+            .filter(|item| Self::matches(item, &words))
+            .map(|item| SearchResult {
+                id: item.id.clone(),
+                title: item.title.clone(),
+                subtitle: item.subtitle.clone(),
+                icon: None,
+                result_type: self.fixture.result_type,
+                score: item.score + self.usage.boost(&item.id),
+                metadata: HashMap::new(),
+                action: self.action(item),
+                normalized_title: None,
+                enrichment_pending: false,
+                integrity: None,
+            })
+            .collect())
+    }
+
+    async fn execute(&self, result: &SearchResult) -> Result<()> {
+        if self.fixture.items.iter().any(|item| item.id == result.id) {
+            Ok(())
+        } else {
+            Err(LauncherError::NotFound(format!("Fixture result {}", result.id)))
+        }
+    }
+
+    async fn suggestion_terms(&self, limit: usize) -> Vec<String> {
+        self.fixture
+            .items
+            .iter()
+            .flat_map(|item| std::iter::once(item.title.clone()).chain(item.keywords.iter().cloned()))
+            .take(limit)
+            .collect()
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,118 @@
+/// Words skipped when matching word by word, unless settings list others
+pub const DEFAULT_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "at", "by", "for", "from", "in", "of", "on", "or", "the", "to", "with",
+    "as", "com", "da", "das", "de", "do", "dos", "em", "na", "no", "os", "para", "um", "uma",
+];
+
+/// Query words matched at most, so long queries stay cheap to rank
+pub const MAX_MATCH_TOKENS: usize = 6;
+
+/// Characters of a title or subtitle searched for query words
+pub const MAX_MATCHED_CHARS: usize = 256;
+
+/// Bonus when every word is in the title
+pub const ALL_IN_TITLE_BONUS: f64 = 40.0;
+
+/// Bonus when every word is in the title or subtitle, some only in the subtitle
+pub const ALL_ACROSS_BONUS: f64 = 20.0;
+
+/// Bonus when every word matched, scaled by the share that did for partial matches
+pub const PARTIAL_BONUS: f64 = 10.0;
+
+/// Returns the words worth matching one by one: longer than one character,
+/// not a stopword and not repeated, at most `MAX_MATCH_TOKENS` of them
+///
+/// `tokens` and `stopwords` must be folded the same way.
+pub fn significant_tokens(tokens: &[String], stopwords: &[String]) -> Vec<String> {
+    let mut significant: Vec<String> = Vec::new();
+    for token in tokens {
+        if token.chars().count() > 1 && !stopwords.contains(token) && !significant.contains(token) {
+            significant.push(token.clone());
+            if significant.len() == MAX_MATCH_TOKENS {
+                break;
+            }
+        }
+    }
+    significant
+}
+
+/// The first `MAX_MATCHED_CHARS` characters of `text`
+pub fn matched_part(text: &str) -> &str {
+    match text.char_indices().nth(MAX_MATCHED_CHARS) {
+        Some((end, _)) => &text[..end],
+        None => text,
+    }
+}
+
+/// Scores how many of `tokens` appear in a folded title and subtitle
+///
+/// The title boosts only reward the query as one phrase, so "budget 2024
+/// xlsx" gets nothing from them for "2024 budget final.xlsx".
+/// Only queries with two or more significant words get a bonus; a single
+/// word is already covered by the phrase boosts. Partial credit needs at
+/// least two thirds of the words.
+pub fn token_bonus(title: &str, subtitle: &str, tokens: &[String]) -> f64 {
+    if tokens.len() < 2 {
+        return 0.0;
+    }
+
+    let (title, subtitle) = (matched_part(title), matched_part(subtitle));
+    let mut in_title = 0;
+    let mut in_either = 0;
+    for token in tokens {
+        if title.contains(token.as_str()) {
+            in_title += 1;
+            in_either += 1;
+        } else if subtitle.contains(token.as_str()) {
+            in_either += 1;
+        }
+    }
+
+    let count = tokens.len();
+    if in_title == count {
+        ALL_IN_TITLE_BONUS
+    } else if in_either == count {
+        ALL_ACROSS_BONUS
+    } else if in_either * 3 >= count * 2 {
+        PARTIAL_BONUS * in_either as f64 / count as f64
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_significant_tokens() {
+        let stopwords = words("the of de");
+        assert_eq!(significant_tokens(&words("report of the 2024 q budget"), &stopwords), words("report 2024 budget"));
+        assert_eq!(significant_tokens(&words("a b c"), &stopwords), Vec::<String>::new());
+        assert_eq!(significant_tokens(&words("foto foto praia"), &stopwords), words("foto praia"));
+
+        // Configured stopwords replace the defaults
+        assert_eq!(significant_tokens(&words("the budget"), &[]), words("the budget"));
+        assert_eq!(significant_tokens(&words("one two three four five six seven"), &[]).len(), MAX_MATCH_TOKENS);
+    }
+
+    #[test]
+    fn test_bonus_by_where_words_appear() {
+        let tokens = words("budget 2024 xlsx");
+        assert_eq!(token_bonus("2024 budget final.xlsx", "", &tokens), ALL_IN_TITLE_BONUS);
+        assert_eq!(token_bonus("budget final.xlsx", "c:\\finance\\2024", &tokens), ALL_ACROSS_BONUS);
+        assert_eq!(token_bonus("budget final.xlsx", "", &tokens), PARTIAL_BONUS * 2.0 / 3.0);
+        assert_eq!(token_bonus("budget.docx", "", &tokens), 0.0);
+
+        // A single word is left to the phrase boosts
+        assert_eq!(token_bonus("budget", "", &words("budget")), 0.0);
+
+        // Words past the matched part don't count
+        let long_title = format!("{} 2024", "budget ".repeat(MAX_MATCHED_CHARS));
+        assert_eq!(token_bonus(&long_title, "", &words("budget 2024")), 0.0);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::error::{LauncherError, Result};
+use base64::Engine as _;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Id of the engine used until settings pick another
+pub const DEFAULT_WEB_SEARCH_ENGINE: &str = "google";
+
+/// Marks where the encoded query goes in a URL template
+const QUERY_PLACEHOLDER: &str = "{query}";
+
+/// A site the web search fallback sends queries to
+#[derive(Debug, PartialEq, Eq)]
+pub struct WebSearchEngine {
+    /// Identifier stored in the `web_search_engine` setting
+    pub id: &'static str,
+    /// Name shown in result titles
+    pub name: &'static str,
+    /// Search URL with `{query}` where the encoded query goes
+    pub url_template: &'static str,
+    /// Identifier of the bundled favicon
+    pub icon: &'static str,
+}
+
+/// Every supported engine; the first is the default
+pub const WEB_SEARCH_ENGINES: &[WebSearchEngine] = &[
+    WebSearchEngine {
+        id: DEFAULT_WEB_SEARCH_ENGINE,
+        name: "Google",
+        url_template: "https://www.google.com/search?q={query}",
+        icon: "google",
+    },
+    WebSearchEngine {
+        id: "bing",
+        name: "Bing",
+        url_template: "https://www.bing.com/search?q={query}",
+        icon: "bing",
+    },
+    WebSearchEngine {
+        id: "duckduckgo",
+        name: "DuckDuckGo",
+        url_template: "https://duckduckgo.com/?q={query}",
+        icon: "duckduckgo",
+    },
+];
+
+impl WebSearchEngine {
+    /// Looks up an engine by its settings id
+    pub fn find(id: &str) -> Option<&'static WebSearchEngine> {
+        WEB_SEARCH_ENGINES.iter().find(|engine| engine.id == id)
+    }
+
+    /// Builds the URL searching this engine for `query`
+    pub fn construct_search_url(&self, query: &str) -> String {
+        self.url_template.replace(QUERY_PLACEHOLDER, &urlencoding::encode(query))
+    }
+
+    /// The engine's favicon as a data URI, for result icons
+    pub fn favicon(&self) -> Option<String> {
+        let svg: &[u8] = match self.icon {
+            "google" => include_bytes!("../../icons/web_search/google.svg"),
+            "bing" => include_bytes!("../../icons/web_search/bing.svg"),
+            "duckduckgo" => include_bytes!("../../icons/web_search/duckduckgo.svg"),
+            _ => return None,
+        };
+        Some(format!(
+            "data:image/svg+xml;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(svg)
+        ))
+    }
+}
+
+/// The engine picked in settings
+///
+/// Clones share the choice, so the web search provider and the search
+/// engine's fallback for web search actions always build the same URL.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveWebSearchEngine(Arc<AtomicUsize>);
+
+impl ActiveWebSearchEngine {
+    /// Returns the engine currently in use
+    pub fn get(&self) -> &'static WebSearchEngine {
+        let index = self.0.load(Ordering::Relaxed);
+        WEB_SEARCH_ENGINES.get(index).unwrap_or(&WEB_SEARCH_ENGINES[0])
+    }
+
+    /// Switches to the engine with the given id, returning whether it changed
+    pub fn set(&self, id: &str) -> Result<bool> {
+        let index = WEB_SEARCH_ENGINES
+            .iter()
+            .position(|engine| engine.id == id)
+            .ok_or_else(|| LauncherError::ConfigError(format!("Unknown web search engine '{}'", id)))?;
+        Ok(self.0.swap(index, Ordering::Relaxed) != index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder_in_queries_is_encoded() {
+        let url = WebSearchEngine::find("duckduckgo").unwrap().construct_search_url("{query}");
+        assert_eq!(url, "https://duckduckgo.com/?q=%7Bquery%7D");
+    }
+
+    #[test]
+    fn test_every_engine_has_a_favicon() {
+        for engine in WEB_SEARCH_ENGINES {
+            let favicon = engine.favicon().unwrap();
+            assert!(favicon.starts_with("data:image/svg+xml;base64,"), "{}", engine.id);
+            assert!(engine.url_template.contains(QUERY_PLACEHOLDER), "{}", engine.id);
+        }
+    }
+
+    #[test]
+    fn test_active_engine_is_shared_between_clones() {
+        let active = ActiveWebSearchEngine::default();
+        let shared = active.clone();
+        assert_eq!(active.get().id, DEFAULT_WEB_SEARCH_ENGINE);
+
+        assert!(shared.set("bing").unwrap());
+        assert!(!shared.set("bing").unwrap());
+        assert_eq!(active.get().name, "Bing");
+
+        assert!(active.set("altavista").is_err());
+        assert_eq!(shared.get().name, "Bing");
+    }
+}
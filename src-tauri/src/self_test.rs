@@ -0,0 +1,706 @@
+/// End-to-end readiness check for support requests
+///
+/// When search seems to return nothing, `run_self_test` (or `/doctor` in the
+/// launcher) works through the whole path a query takes: the engine answers
+/// a trivial query, each provider answers a query it should know, the
+/// hotkey is registered, settings survive a load and save, the data
+/// directories are writable and Everything and Windows Search respond.
+/// Every check runs within its own timeout and a failing check doesn't stop
+/// the ones after it. `SelfTestReport::to_text` is the version for pasting
+/// into an issue, with every path but the self-test's own file removed.
+
+use crate::error::{LauncherError, Result};
+use crate::hotkey::ActiveHotkey;
+use crate::search::SearchEngine;
+use crate::settings::{AppSettings, SaveOutcome};
+use crate::types::ProviderHealth;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// Time each check gets before it counts as failed
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Query the engine check sends; anything installed matches it
+const ENGINE_QUERY: &str = "a";
+
+/// Providers searching files, which are asked for the self-test file
+const FILE_PROVIDERS: &[&str] = &["FileSearch", "WindowsSearch"];
+
+/// How a check went, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// Working, but possibly not as the user expects
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+}
+
+/// What a check found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckOutcome {
+    pub fn pass(detail: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Pass, detail)
+    }
+
+    pub fn warn(detail: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Warn, detail)
+    }
+
+    pub fn fail(detail: impl Into<String>) -> Self {
+        Self::new(CheckStatus::Fail, detail)
+    }
+
+    fn new(status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// One line of the report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub duration_ms: u64,
+    pub detail: String,
+}
+
+/// Result of a self-test, as returned by `run_self_test`
+#[derive(Debug, Clone, Serialize)]
+pub struct SelfTestReport {
+    pub started_at: DateTime<Utc>,
+    /// Worst status of any check
+    pub status: CheckStatus,
+    pub passed: usize,
+    pub warnings: usize,
+    pub failures: usize,
+    pub checks: Vec<SelfTestCheck>,
+    /// File the file search checks looked for, already removed again
+    pub probe_file: Option<PathBuf>,
+}
+
+impl SelfTestReport {
+    /// Sums up `checks`; without any the report passes
+    pub fn from_checks(started_at: DateTime<Utc>, checks: Vec<SelfTestCheck>, probe_file: Option<PathBuf>) -> Self {
+        let count = |status| checks.iter().filter(|check| check.status == status).count();
+        Self {
+            started_at,
+            status: checks.iter().map(|check| check.status).max().unwrap_or(CheckStatus::Pass),
+            passed: count(CheckStatus::Pass),
+            warnings: count(CheckStatus::Warn),
+            failures: count(CheckStatus::Fail),
+            checks,
+            probe_file,
+        }
+    }
+
+    /// Writes every check to the log
+    pub fn log(&self) {
+        for check in &self.checks {
+            match check.status {
+                CheckStatus::Pass => info!("Self-test {}: {} ({} ms)", check.name, check.detail, check.duration_ms),
+                status => warn!(
+                    "Self-test {} {}: {} ({} ms)",
+                    check.name,
+                    status.label(),
+                    check.detail,
+                    check.duration_ms
+                ),
+            }
+        }
+        info!(
+            "Self-test finished: {} passed, {} warnings, {} failures",
+            self.passed, self.warnings, self.failures
+        );
+    }
+
+    /// Plain text version for an issue, redacted for the current user
+    pub fn to_text(&self) -> String {
+        self.to_text_with(&Redactor::current_user(self.probe_file.as_deref()))
+    }
+
+    /// Plain text version with every line passed through `redactor`
+    pub fn to_text_with(&self, redactor: &Redactor) -> String {
+        let mut text = format!(
+            "Better Finder {} self-test, {}\n{}: {} passed, {} warnings, {} failures\n\n",
+            env!("CARGO_PKG_VERSION"),
+            self.started_at.format("%Y-%m-%d %H:%M UTC"),
+            self.status.label(),
+            self.passed,
+            self.warnings,
+            self.failures
+        );
+        for check in &self.checks {
+            text.push_str(&format!(
+                "[{}] {} ({} ms): {}\n",
+                check.status.label(),
+                check.name,
+                check.duration_ms,
+                check.detail
+            ));
+        }
+        redactor.redact(&text)
+    }
+}
+
+/// Removes paths and the user name from text meant to be shared
+///
+/// Paths are replaced by `<path>` except `keep`, the self-test file, whose
+/// home folder is shown as `%USERPROFILE%`. Paths may contain spaces, so the
+/// words after one that still contain a separator count as part of it.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    home: Option<String>,
+    user: Option<String>,
+    keep: Option<String>,
+}
+
+impl Redactor {
+    pub fn new(home: Option<&str>, user: Option<&str>, keep: Option<&Path>) -> Self {
+        let non_empty = |value: Option<&str>| value.map(str::trim).filter(|value| !value.is_empty()).map(str::to_string);
+        Self {
+            home: non_empty(home),
+            user: non_empty(user),
+            keep: keep.map(|path| path.display().to_string()),
+        }
+    }
+
+    /// Redacts the home folder and name of the user running the app
+    pub fn current_user(keep: Option<&Path>) -> Self {
+        let var = |names: &[&str]| names.iter().find_map(|name| std::env::var(name).ok());
+        Self::new(
+            var(&["USERPROFILE", "HOME"]).as_deref(),
+            var(&["USERNAME", "USER"]).as_deref(),
+            keep,
+        )
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let segments: Vec<String> = match &self.keep {
+            Some(keep) => text.split(keep.as_str()).map(Self::redact_paths).collect(),
+            None => vec![Self::redact_paths(text)],
+        };
+        let kept = self.keep.as_deref().map(|keep| self.shorten_home(keep)).unwrap_or_default();
+
+        match &self.user {
+            Some(user) => replace_word(&segments.join(&kept), user, "<user>"),
+            None => segments.join(&kept),
+        }
+    }
+
+    fn shorten_home(&self, path: &str) -> String {
+        match &self.home {
+            Some(home) if path.len() >= home.len() && path[..home.len()].eq_ignore_ascii_case(home) => {
+                format!("%USERPROFILE%{}", &path[home.len()..])
+            }
+            _ => path.to_string(),
+        }
+    }
+
+    fn redact_paths(text: &str) -> String {
+        let lines: Vec<String> = text
+            .split('\n')
+            .map(|line| {
+                let mut words = Vec::new();
+                let mut in_path = false;
+                for word in line.split(' ') {
+                    if looks_like_path(word) {
+                        words.push("<path>");
+                        in_path = true;
+                    } else if in_path && (word.contains('\\') || word.contains('/')) && !word.contains("://") {
+                        // The rest of a path with a space in it
+                    } else {
+                        words.push(word);
+                        in_path = false;
+                    }
+                }
+                words.join(" ")
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// Whether a word is an absolute path, ignoring quotes and punctuation around it
+fn looks_like_path(word: &str) -> bool {
+    let word = word
+        .trim_start_matches(['"', '\'', '(', '['])
+        .trim_end_matches(['"', '\'', ')', ']', ',', ';', ':', '.']);
+    let bytes = word.as_bytes();
+
+    let drive = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'\\' | b'/');
+    drive || word.starts_with(r"\\") || (word.len() > 1 && word.starts_with('/')) || word.starts_with("~/") || word.starts_with(r"~\")
+}
+
+/// Replaces `word` where it stands on its own, ignoring case
+fn replace_word(text: &str, word: &str, with: &str) -> String {
+    let lower_text = text.to_lowercase();
+    let lower_word = word.to_lowercase();
+    // Lowercasing can change byte lengths outside ASCII; leave such text alone
+    if lower_text.len() != text.len() || lower_word.len() != word.len() {
+        return text.to_string();
+    }
+
+    let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let mut result = String::with_capacity(text.len());
+    let mut rest = 0;
+    let mut from = 0;
+    while let Some(found) = lower_text[from..].find(&lower_word) {
+        let start = from + found;
+        let end = start + word.len();
+        if !is_word_char(text[..start].chars().next_back()) && !is_word_char(text[end..].chars().next()) {
+            result.push_str(&text[rest..start]);
+            result.push_str(with);
+            rest = end;
+        }
+        from = end;
+    }
+    result.push_str(&text[rest..]);
+    result
+}
+
+type CheckFuture = Pin<Box<dyn Future<Output = Result<CheckOutcome>> + Send>>;
+type Check = Box<dyn FnOnce() -> CheckFuture + Send>;
+
+/// A temporary file the file search checks look for, removed when dropped
+struct ProbeFile {
+    path: PathBuf,
+}
+
+impl ProbeFile {
+    fn create() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("betterfinder-self-test-{}.txt", std::process::id()));
+        std::fs::write(&path, "Created by the Better Finder self-test, safe to delete")?;
+        Ok(Self { path })
+    }
+
+    /// File name without the extension, which is what the checks search for
+    fn stem(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for ProbeFile {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!("Failed to remove the self-test file {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Scripted checks, run one after another
+pub struct SelfTest {
+    checks: Vec<(String, Check)>,
+    timeout: Duration,
+    probe_file: Option<ProbeFile>,
+}
+
+impl Default for SelfTest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfTest {
+    /// Creates a self-test without checks, each given `CHECK_TIMEOUT`
+    pub fn new() -> Self {
+        Self::with_timeout(CHECK_TIMEOUT)
+    }
+
+    /// Creates a self-test whose checks each get `timeout`
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            checks: Vec::new(),
+            timeout,
+            probe_file: None,
+        }
+    }
+
+    /// Adds a check, run after the ones added before it
+    pub fn add_check<F, Fut>(&mut self, name: impl Into<String>, check: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<CheckOutcome>> + Send + 'static,
+    {
+        let check: Check = Box::new(move || Box::pin(check()));
+        self.checks.push((name.into(), check));
+    }
+
+    /// Adds the engine's trivial query and a check for every registered provider
+    ///
+    /// Providers with a canned query must answer it; the others are judged by
+    /// their health. The file search providers look for a file created here
+    /// and removed once the self-test has run.
+    pub async fn add_engine_checks(&mut self, engine: Arc<SearchEngine>) {
+        let search_engine = Arc::clone(&engine);
+        self.add_check("engine", move || async move {
+            let results = search_engine.search(ENGINE_QUERY).await;
+            Ok(CheckOutcome::pass(format!("{} results for \"{}\"", results.len(), ENGINE_QUERY)))
+        });
+
+        let statuses = engine.provider_health().await;
+        let probe_stem = if statuses.iter().any(|status| FILE_PROVIDERS.contains(&status.provider.as_str())) {
+            match ProbeFile::create() {
+                Ok(probe) => {
+                    let stem = probe.stem();
+                    self.probe_file = Some(probe);
+                    Ok(stem)
+                }
+                Err(e) => Err(format!("Couldn't create the self-test file: {}", e)),
+            }
+        } else {
+            Err(String::new())
+        };
+
+        for status in statuses {
+            let name = format!("provider {}", status.provider);
+            let canned = if FILE_PROVIDERS.contains(&status.provider.as_str()) {
+                match &probe_stem {
+                    Ok(stem) => Some(CannedQuery::expecting(stem, CheckStatus::Warn)),
+                    Err(e) => {
+                        let e = e.clone();
+                        self.add_check(name, move || async move { Ok(CheckOutcome::fail(e)) });
+                        continue;
+                    }
+                }
+            } else {
+                CannedQuery::for_provider(&status.provider)
+            };
+
+            match canned {
+                Some(canned) => {
+                    let engine = Arc::clone(&engine);
+                    self.add_check(name, move || async move {
+                        let results = engine.search_provider(&status.provider, &canned.query).await?;
+                        Ok(canned.judge(&results))
+                    });
+                }
+                None => self.add_check(name, move || async move { Ok(health_outcome(&status.health)) }),
+            }
+        }
+    }
+
+    /// Runs every check, returning the report once all have finished or timed out
+    ///
+    /// The self-test file is removed before this returns.
+    pub async fn run(self) -> SelfTestReport {
+        let started_at = Utc::now();
+        let mut checks = Vec::with_capacity(self.checks.len());
+
+        for (name, check) in self.checks {
+            let started = Instant::now();
+            let outcome = match tokio::time::timeout(self.timeout, check()).await {
+                Ok(Ok(outcome)) => outcome,
+                Ok(Err(e)) => CheckOutcome::fail(e.to_string()),
+                Err(_) => CheckOutcome::fail(format!("No answer within {} ms", self.timeout.as_millis())),
+            };
+            checks.push(SelfTestCheck {
+                name,
+                status: outcome.status,
+                duration_ms: started.elapsed().as_millis() as u64,
+                detail: outcome.detail,
+            });
+        }
+
+        let probe_path = self.probe_file.as_ref().map(|probe| probe.path.clone());
+        drop(self.probe_file);
+        SelfTestReport::from_checks(started_at, checks, probe_path)
+    }
+}
+
+/// A query a provider should answer, and how to judge the answer
+struct CannedQuery {
+    query: String,
+    /// Text a result title must contain; any result will do without one
+    expected: Option<String>,
+    /// Status when nothing matching comes back
+    when_missing: CheckStatus,
+}
+
+impl CannedQuery {
+    /// The canned query of a provider, `None` for those judged by health alone
+    fn for_provider(provider: &str) -> Option<Self> {
+        let (query, when_missing) = match provider {
+            "Calculator" => ("1+1", CheckStatus::Fail),
+            "AppSearch" => ("a", CheckStatus::Fail),
+            // An empty history is normal
+            "Clipboard History" => ("clip:", CheckStatus::Warn),
+            _ => return None,
+        };
+        Some(Self {
+            query: query.to_string(),
+            expected: None,
+            when_missing,
+        })
+    }
+
+    fn expecting(title: &str, when_missing: CheckStatus) -> Self {
+        Self {
+            query: title.to_string(),
+            expected: Some(title.to_lowercase()),
+            when_missing,
+        }
+    }
+
+    fn judge(&self, results: &[crate::types::SearchResult]) -> CheckOutcome {
+        let found = match &self.expected {
+            Some(expected) => results.iter().any(|result| result.title.to_lowercase().contains(expected)),
+            None => !results.is_empty(),
+        };
+
+        if found {
+            CheckOutcome::pass(format!("{} results for \"{}\"", results.len(), self.query))
+        } else if self.expected.is_some() {
+            CheckOutcome::new(
+                self.when_missing,
+                "The self-test file wasn't found; the index may not have caught up yet",
+            )
+        } else {
+            CheckOutcome::new(self.when_missing, format!("No results for \"{}\"", self.query))
+        }
+    }
+}
+
+/// Judges a provider without a canned query by its health
+fn health_outcome(health: &ProviderHealth) -> CheckOutcome {
+    match health {
+        ProviderHealth::Ready => CheckOutcome::pass(health.to_string()),
+        ProviderHealth::Failed { .. } => CheckOutcome::fail(health.to_string()),
+        _ => CheckOutcome::warn(health.to_string()),
+    }
+}
+
+/// Judges the hotkey that opens the launcher
+pub fn hotkey_outcome(active: Option<ActiveHotkey>) -> CheckOutcome {
+    match active {
+        None => CheckOutcome::fail("No hotkey is registered"),
+        Some(hotkey) if hotkey.is_fallback => CheckOutcome::warn(format!(
+            "{} is registered because {} couldn't be",
+            hotkey.shortcut, hotkey.preferred
+        )),
+        Some(hotkey) => CheckOutcome::pass(format!("{} is registered", hotkey.shortcut)),
+    }
+}
+
+/// Loads the settings and saves them unchanged
+pub async fn check_settings_round_trip() -> Result<CheckOutcome> {
+    let loaded = AppSettings::load()?;
+    match loaded.save().await? {
+        SaveOutcome::Saved(saved) if serde_json::to_value(&saved)? == serde_json::to_value(&loaded)? => {
+            Ok(CheckOutcome::pass("Loaded and saved unchanged"))
+        }
+        SaveOutcome::Saved(_) => Ok(CheckOutcome::warn("The settings changed while they were saved")),
+        SaveOutcome::Conflict { fields, .. } => Ok(CheckOutcome::warn(format!(
+            "The settings file was edited outside the app: {}",
+            fields.join(", ")
+        ))),
+    }
+}
+
+/// Writes and removes a file in `dir`, creating it if needed
+pub async fn check_writable(dir: PathBuf) -> Result<CheckOutcome> {
+    tokio::task::spawn_blocking(move || {
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(".self-test-{}", std::process::id()));
+        std::fs::write(&path, b"ok")?;
+        std::fs::remove_file(&path)?;
+        Ok(CheckOutcome::pass(format!("{} is writable", dir.display())))
+    })
+    .await
+    .map_err(|e| LauncherError::ExecutionError(format!("Failed to spawn write check: {}", e)))?
+}
+
+/// Directories the app writes to: its data folder and the temp folder
+pub fn required_directories() -> Vec<(&'static str, PathBuf)> {
+    let mut dirs = Vec::new();
+    match std::env::var_os("APPDATA") {
+        Some(app_data) => dirs.push(("data directory", PathBuf::from(app_data).join("BetterFinder"))),
+        None => warn!("APPDATA not found, the data directory can't be checked"),
+    }
+    dirs.push(("temp directory", std::env::temp_dir()));
+    dirs
+}
+
+/// Whether Everything is running with its database loaded
+pub fn everything_outcome() -> CheckOutcome {
+    match crate::search::providers::everything::EverythingClient::new() {
+        Ok(_) => CheckOutcome::pass("Everything is running with its database loaded"),
+        // File search falls back to Windows Search without it
+        Err(e) => CheckOutcome::warn(e.to_string()),
+    }
+}
+
+/// Whether the Windows Search service can be queried
+pub fn windows_search_outcome() -> CheckOutcome {
+    match crate::search::providers::search_index::search_service_health().error() {
+        None => CheckOutcome::pass("The Windows Search service is running"),
+        Some(e) => CheckOutcome::warn(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{QueryContext, SearchProvider};
+    use crate::types::{ResultAction, ResultType, SearchResult};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    /// Answers every query with one result titled after it, or fails
+    struct MockProvider {
+        name: &'static str,
+        fails: bool,
+    }
+
+    #[async_trait]
+    impl SearchProvider for MockProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+
+        async fn search(&self, query: &QueryContext) -> Result<Vec<SearchResult>> {
+            if self.fails {
+                return Err(LauncherError::ProviderError("index unavailable".to_string()));
+            }
+            Ok(vec![SearchResult {
+                id: query.query.clone(),
+                title: format!("{}.txt", query.query),
+                subtitle: String::new(),
+                icon: None,
+                result_type: ResultType::File,
+                score: 50.0,
+                metadata: HashMap::new(),
+                action: ResultAction::OpenFile { path: query.query.clone() },
+                normalized_title: None,
+                enrichment_pending: false,
+                integrity: None,
+            }])
+        }
+
+        async fn execute(&self, _result: &SearchResult) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn check(name: &str, status: CheckStatus) -> SelfTestCheck {
+        SelfTestCheck {
+            name: name.to_string(),
+            status,
+            duration_ms: 1,
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_report_takes_the_worst_status() {
+        let report = SelfTestReport::from_checks(
+            Utc::now(),
+            vec![
+                check("engine", CheckStatus::Pass),
+                check("hotkey", CheckStatus::Warn),
+                check("settings", CheckStatus::Pass),
+            ],
+            None,
+        );
+        assert_eq!(report.status, CheckStatus::Warn);
+        assert_eq!((report.passed, report.warnings, report.failures), (2, 1, 0));
+
+        let mut checks = report.checks.clone();
+        checks.push(check("provider Calculator", CheckStatus::Fail));
+        let report = SelfTestReport::from_checks(Utc::now(), checks, None);
+        assert_eq!(report.status, CheckStatus::Fail);
+        assert_eq!(report.failures, 1);
+
+        assert_eq!(SelfTestReport::from_checks(Utc::now(), Vec::new(), None).status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_redaction_keeps_only_the_self_test_file() {
+        let probe = Path::new(r"C:\Users\alice\AppData\Local\Temp\betterfinder-self-test-7.txt");
+        let redactor = Redactor::new(Some(r"C:\Users\alice"), Some("alice"), Some(probe));
+
+        let mut data_dir = check("data directory", CheckStatus::Fail);
+        data_dir.detail = r#"Access denied: "C:\Users\alice\AppData\Roaming\BetterFinder\settings.json""#.to_string();
+        let mut file_search = check("provider FileSearch", CheckStatus::Warn);
+        file_search.detail = format!(r"Found D:\Work\Secret Plans\q3.docx instead of {}", probe.display());
+        let mut hotkey = check("hotkey", CheckStatus::Pass);
+        hotkey.detail = "Registered for Alice, see https://example.com/a/b".to_string();
+
+        let report = SelfTestReport::from_checks(Utc::now(), vec![data_dir, file_search, hotkey], Some(probe.into()));
+        let text = report.to_text_with(&redactor);
+
+        assert!(!text.to_lowercase().contains("alice"), "{}", text);
+        assert!(!text.contains("Secret") && !text.contains("q3.docx"), "{}", text);
+        assert!(!text.contains("Roaming"), "{}", text);
+        assert!(text.contains(r"Access denied: <path>"), "{}", text);
+        assert!(text.contains(r"Found <path> instead of %USERPROFILE%\AppData\Local\Temp\betterfinder-self-test-7.txt"), "{}", text);
+        assert!(text.contains("Registered for <user>, see https://example.com/a/b"), "{}", text);
+        assert!(text.contains("[FAIL] data directory"), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_failing_provider_fails_its_check_only() {
+        let engine = Arc::new(SearchEngine::new());
+        engine.register_provider(Arc::new(MockProvider { name: "Calculator", fails: true })).await;
+        engine.register_provider(Arc::new(MockProvider { name: "AppSearch", fails: false })).await;
+        engine.register_provider(Arc::new(MockProvider { name: "FileSearch", fails: false })).await;
+
+        let mut self_test = SelfTest::with_timeout(Duration::from_millis(500));
+        self_test.add_engine_checks(Arc::clone(&engine)).await;
+        self_test.add_check("slow", || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(CheckOutcome::pass("done"))
+        });
+        self_test.add_check("hotkey", || async { Ok(hotkey_outcome(None)) });
+
+        let report = self_test.run().await;
+        let statuses: HashMap<&str, CheckStatus> =
+            report.checks.iter().map(|check| (check.name.as_str(), check.status)).collect();
+
+        assert_eq!(statuses.len(), 6);
+        assert_eq!(statuses["engine"], CheckStatus::Pass);
+        assert_eq!(statuses["provider Calculator"], CheckStatus::Fail);
+        assert_eq!(statuses["provider AppSearch"], CheckStatus::Pass);
+        assert_eq!(statuses["provider FileSearch"], CheckStatus::Pass);
+        assert_eq!(statuses["slow"], CheckStatus::Fail);
+        assert_eq!(statuses["hotkey"], CheckStatus::Fail);
+        let calculator = report.checks.iter().find(|check| check.name == "provider Calculator").unwrap();
+        assert!(calculator.detail.contains("index unavailable"));
+        assert_eq!(report.status, CheckStatus::Fail);
+
+        // The file search check found the file, which is gone again
+        let probe = report.probe_file.expect("file search providers get a self-test file");
+        assert!(!probe.exists());
+    }
+}
@@ -1,7 +1,21 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 use crate::error::{LauncherError, Result};
+use crate::search::duplicates::CONFIRMED_DUPLICATE_WINDOW;
+use crate::search::plain::DEFAULT_PLAIN_TEXT_MARKERS;
+use crate::search::provider_config::{provider_options, validate_provider_settings};
+use crate::search::providers::extension_ranking::{ExtensionClass, MAX_CLASS_WEIGHT};
+use crate::search::providers::macros::validate_macros;
+use crate::search::providers::weather::WeatherPreferences;
+use crate::search::token_match::DEFAULT_STOPWORDS;
+use crate::search::web_search::{WebSearchEngine, DEFAULT_WEB_SEARCH_ENGINE};
+use crate::types::ResultAction;
+use crate::utils::launchers::{validate_args_template, validate_program, Launchers};
 
 /// Application settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +37,433 @@ pub struct AppSettings {
 
     /// Whether to start with Windows
     pub start_with_windows: bool,
+
+    /// Whether to hide the window after a result executes successfully
+    #[serde(default = "default_hide_after_execute")]
+    pub hide_after_execute: bool,
+
+    /// Number of clipboard items to keep, unless `provider_settings.clipboard` sets `max_items`
+    #[serde(default = "default_clipboard_history_size")]
+    pub clipboard_history_size: usize,
+
+    /// Whether search results carry a score breakdown for the debug overlay
+    #[serde(default)]
+    pub debug_mode: bool,
+
+    /// Whether file results offer a "Move to Recycle Bin" action
+    #[serde(default)]
+    pub enable_file_delete_action: bool,
+
+    /// Whether many file results from one folder collapse into an expandable group
+    #[serde(default)]
+    pub group_file_results: bool,
+
+    /// Whether the top results keep their Alt+1..9 shortcut while the query
+    /// is typed, instead of swapping places on every keystroke
+    #[serde(default)]
+    pub stable_result_slots: bool,
+
+    /// Whether matching ignores accents, so "joao" finds "João"
+    #[serde(default = "default_fold_diacritics")]
+    pub fold_diacritics: bool,
+
+    /// Whether executed results are kept out of completions and the boost
+    /// for results run in the last few minutes
+    #[serde(default)]
+    pub privacy_mode: bool,
+
+    /// Whether search analytics are recorded for tuning; they stay on this
+    /// computer unless exported, and never include queries or paths
+    #[serde(default)]
+    pub analytics_local: bool,
+
+    /// Id of the engine web searches go to
+    #[serde(default = "default_web_search_engine")]
+    pub web_search_engine: String,
+
+    /// Unit weather results show temperatures in
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+
+    /// City a bare "weather" query uses when Windows can't report the device position
+    #[serde(default)]
+    pub weather_home_city: Option<String>,
+
+    /// When the launcher plays feedback sounds
+    #[serde(default)]
+    pub sound_feedback: SoundFeedback,
+
+    /// WAV files replacing the bundled feedback sounds
+    #[serde(default)]
+    pub custom_sounds: CustomSounds,
+
+    /// Where each category of notification is shown; categories left out use
+    /// their default route
+    #[serde(default = "default_notification_routing")]
+    pub notification_routing: HashMap<NotificationCategory, NotificationRoute>,
+
+    /// File manager folders open in; Explorer when unset
+    #[serde(default)]
+    pub preferred_file_manager: Option<PathBuf>,
+
+    /// Terminal "Open in terminal" starts; the system's default terminal when unset
+    #[serde(default)]
+    pub preferred_terminal: Option<PreferredTerminal>,
+
+    /// Whether restoring a clipboard item also pastes it into the previously active window
+    #[serde(default)]
+    pub clipboard_auto_paste: bool,
+
+    /// Global hotkey evaluating the selected text as a calculation; off when unset
+    #[serde(default)]
+    pub quick_math_hotkey: Option<String>,
+
+    /// What the quick math hotkey does with the result
+    #[serde(default)]
+    pub quick_math_output: QuickMathOutput,
+
+    /// Extra bookmark files loaded alongside the detected browsers (applied on restart)
+    #[serde(default)]
+    pub custom_bookmark_files: Vec<CustomBookmarkFile>,
+
+    /// Open bookmarks in the browser and profile they were saved in instead
+    /// of the default browser
+    #[serde(default)]
+    pub open_bookmarks_in_source_browser: bool,
+
+    /// Folders or path fragments hidden from file results on top of the
+    /// built-in ones
+    #[serde(default)]
+    pub excluded_paths: Vec<String>,
+
+    /// Folders whose files rank higher, like those on the Desktop, Documents
+    /// and Downloads
+    #[serde(default)]
+    pub preferred_paths: Vec<String>,
+
+    /// Score adjustments for file types, e.g. `{"noise": 0}` stops logs and
+    /// temp files ranking lower; classes left out keep their defaults
+    #[serde(default)]
+    pub extension_class_weights: HashMap<ExtensionClass, f64>,
+
+    /// Words ranking skips when matching a query word by word, such as
+    /// "the" in "notes of the meeting"
+    #[serde(default = "default_ranking_stopwords")]
+    pub ranking_stopwords: Vec<String>,
+
+    /// Characters that, leading a query, search for the rest as typed, so
+    /// `'2+2` finds files instead of a sum; empty turns this off
+    #[serde(default = "default_plain_text_markers")]
+    pub plain_text_markers: String,
+
+    /// Keywords running an action with the rest of the query filled in,
+    /// e.g. `proj website` opening `C:\Projects\website`
+    #[serde(default)]
+    pub macros: Vec<QueryMacro>,
+
+    /// Width of the main window in logical pixels
+    #[serde(default = "default_window_width")]
+    pub window_width: u32,
+
+    /// Number of result rows the main window is tall enough to show
+    #[serde(default = "default_max_visible_results")]
+    pub max_visible_results: usize,
+
+    /// Whether result rows use the denser layout
+    #[serde(default)]
+    pub compact_mode: bool,
+
+    /// Whether the main window appears at once, without the system's show animation
+    #[serde(default)]
+    pub disable_show_animation: bool,
+
+    /// Whether results carry labels for screen readers even when Windows
+    /// reports none running
+    #[serde(default)]
+    pub screen_reader_support: bool,
+
+    /// Lets results the frontend didn't get from a search execute anyway;
+    /// honoured in development builds only, see `allows_unsigned_results`
+    #[serde(default)]
+    pub allow_unsigned_results: bool,
+
+    /// Milliseconds in which executing the same result again is ignored; 0 disables
+    #[serde(default = "default_duplicate_window_ms")]
+    pub duplicate_window_ms: u64,
+
+    /// Working set in MB above which caches are trimmed automatically; 0 disables
+    #[serde(default = "default_memory_trim_threshold_mb")]
+    pub memory_trim_threshold_mb: u64,
+
+    /// Per-provider options keyed by section (e.g. "recent_files"); missing
+    /// sections and fields use each provider's defaults
+    #[serde(default)]
+    pub provider_settings: HashMap<String, serde_json::Value>,
+
+    /// Named sets of overrides, e.g. "work" and "personal"
+    #[serde(default)]
+    pub profiles: Vec<SettingsProfile>,
+
+    /// Profile whose overrides apply on top of the other fields; none when unset
+    #[serde(default)]
+    pub active_profile: Option<String>,
+}
+
+/// Reads a field that is present, so `null` becomes `Some(None)` and clears it
+fn present<'de, T, D>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Declares `PartialAppSettings` from the overridable `AppSettings` fields
+macro_rules! partial_settings {
+    ($($(#[$attr:meta])* $field:ident: $ty:ty,)*) => {
+        /// `AppSettings` with every field optional, as a profile overrides them
+        ///
+        /// Fields left out keep their base value; fields given replace it
+        /// whole, so a profile's `provider_settings` replaces all sections.
+        /// Optional fields such as `quick_math_hotkey` are cleared with `null`.
+        /// Kept in step with `AppSettings` by a test.
+        #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        pub struct PartialAppSettings {
+            $(
+                $(#[$attr])*
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub $field: Option<$ty>,
+            )*
+        }
+    };
+}
+
+partial_settings! {
+    hotkey: String,
+    theme: Theme,
+    max_results: usize,
+    enabled_providers: EnabledProviders,
+    search_delay: u64,
+    start_with_windows: bool,
+    hide_after_execute: bool,
+    clipboard_history_size: usize,
+    debug_mode: bool,
+    enable_file_delete_action: bool,
+    group_file_results: bool,
+    stable_result_slots: bool,
+    fold_diacritics: bool,
+    privacy_mode: bool,
+    analytics_local: bool,
+    web_search_engine: String,
+    temperature_unit: TemperatureUnit,
+    #[serde(deserialize_with = "present")]
+    weather_home_city: Option<String>,
+    sound_feedback: SoundFeedback,
+    custom_sounds: CustomSounds,
+    notification_routing: HashMap<NotificationCategory, NotificationRoute>,
+    #[serde(deserialize_with = "present")]
+    preferred_file_manager: Option<PathBuf>,
+    #[serde(deserialize_with = "present")]
+    preferred_terminal: Option<PreferredTerminal>,
+    clipboard_auto_paste: bool,
+    #[serde(deserialize_with = "present")]
+    quick_math_hotkey: Option<String>,
+    quick_math_output: QuickMathOutput,
+    custom_bookmark_files: Vec<CustomBookmarkFile>,
+    open_bookmarks_in_source_browser: bool,
+    excluded_paths: Vec<String>,
+    preferred_paths: Vec<String>,
+    extension_class_weights: HashMap<ExtensionClass, f64>,
+    ranking_stopwords: Vec<String>,
+    plain_text_markers: String,
+    macros: Vec<QueryMacro>,
+    window_width: u32,
+    max_visible_results: usize,
+    compact_mode: bool,
+    disable_show_animation: bool,
+    screen_reader_support: bool,
+    allow_unsigned_results: bool,
+    duplicate_window_ms: u64,
+    memory_trim_threshold_mb: u64,
+    provider_settings: HashMap<String, serde_json::Value>,
+}
+
+/// A named set of overrides the launcher can switch to, e.g. "work"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    #[serde(default)]
+    pub overrides: PartialAppSettings,
+}
+
+fn default_hide_after_execute() -> bool {
+    true
+}
+
+fn default_fold_diacritics() -> bool {
+    true
+}
+
+fn default_web_search_engine() -> String {
+    DEFAULT_WEB_SEARCH_ENGINE.to_string()
+}
+
+fn default_clipboard_history_size() -> usize {
+    100
+}
+
+fn default_window_width() -> u32 {
+    600
+}
+
+fn default_max_visible_results() -> usize {
+    8
+}
+
+fn default_memory_trim_threshold_mb() -> u64 {
+    400
+}
+
+fn default_ranking_stopwords() -> Vec<String> {
+    DEFAULT_STOPWORDS.iter().map(|word| word.to_string()).collect()
+}
+
+fn default_plain_text_markers() -> String {
+    DEFAULT_PLAIN_TEXT_MARKERS.to_string()
+}
+
+fn default_duplicate_window_ms() -> u64 {
+    500
+}
+
+fn default_notification_routing() -> HashMap<NotificationCategory, NotificationRoute> {
+    NotificationCategory::ALL
+        .iter()
+        .map(|category| (*category, category.default_route()))
+        .collect()
+}
+
+/// Height of the search input area above the results, in logical pixels
+const SEARCH_BAR_HEIGHT: f64 = 64.0;
+
+/// Height of one result row in the regular layout
+const RESULT_ROW_HEIGHT: f64 = 52.0;
+
+/// Height of one result row in compact mode
+const COMPACT_RESULT_ROW_HEIGHT: f64 = 36.0;
+
+/// Computes the main window height needed to show `visible_rows` results
+pub fn window_height(visible_rows: usize, compact_mode: bool) -> f64 {
+    let row_height = if compact_mode {
+        COMPACT_RESULT_ROW_HEIGHT
+    } else {
+        RESULT_ROW_HEIGHT
+    };
+
+    SEARCH_BAR_HEIGHT + visible_rows as f64 * row_height
+}
+
+/// Returns a usable scale factor, treating bogus monitor reports as 100%
+pub fn sanitize_scale_factor(scale_factor: f64) -> f64 {
+    if scale_factor.is_finite() && scale_factor > 0.0 {
+        scale_factor
+    } else {
+        1.0
+    }
+}
+
+/// Converts a length in logical pixels to physical pixels at `scale_factor`
+fn to_physical(logical: f64, scale_factor: f64) -> u32 {
+    (logical * sanitize_scale_factor(scale_factor)).round() as u32
+}
+
+/// Computes the main window height in physical pixels on a monitor with the
+/// given scale factor (1.0 = 96 DPI, 1.75 = 175%)
+pub fn physical_window_height(visible_rows: usize, compact_mode: bool, scale_factor: f64) -> u32 {
+    to_physical(window_height(visible_rows, compact_mode), scale_factor)
+}
+
+/// Returns the physical top-left position that centers a window on a monitor
+///
+/// All values are physical pixels; the monitor position may be negative for
+/// monitors left of or above the primary one.
+pub fn centered_position(
+    monitor_position: (i32, i32),
+    monitor_size: (u32, u32),
+    window_size: (u32, u32),
+) -> (i32, i32) {
+    let offset = |monitor: u32, window: u32| (i64::from(monitor) - i64::from(window)) / 2;
+
+    (
+        monitor_position.0 + offset(monitor_size.0, window_size.0) as i32,
+        monitor_position.1 + offset(monitor_size.1, window_size.1) as i32,
+    )
+}
+
+/// A monitor as the window placement sees it, in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorGeometry {
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub scale_factor: f64,
+}
+
+/// Where the main window goes on one monitor
+///
+/// Computed and applied while the window is hidden, so showing it only has
+/// to check the placement still fits instead of moving and resizing it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowPlacement {
+    pub monitor: MonitorGeometry,
+    /// Physical top-left position
+    pub position: (i32, i32),
+    /// Physical width and height
+    pub size: (u32, u32),
+}
+
+impl WindowPlacement {
+    /// Sizes the window for the monitor's DPI and centers it there
+    pub fn for_monitor(monitor: MonitorGeometry, appearance: &Appearance) -> Self {
+        let size = appearance.physical_size(monitor.scale_factor);
+        Self {
+            monitor,
+            position: centered_position(monitor.position, monitor.size, size),
+            size,
+        }
+    }
+
+    /// Whether the window can show as placed on `monitor` with `appearance`
+    ///
+    /// False once the cursor moved to another monitor, the monitor's layout
+    /// or DPI changed, or the appearance settings did.
+    pub fn fits(&self, monitor: MonitorGeometry, appearance: &Appearance) -> bool {
+        *self == Self::for_monitor(monitor, appearance)
+    }
+}
+
+/// Payload of the `appearance-changed` event
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Appearance {
+    /// Window width in logical pixels
+    pub window_width: u32,
+    /// Window height in logical pixels, derived from the visible rows
+    pub window_height: f64,
+    /// Number of result rows that fit in the window
+    pub max_visible_results: usize,
+    /// Whether result rows use the denser layout
+    pub compact_mode: bool,
+}
+
+impl Appearance {
+    /// Returns the window width and height in physical pixels on a monitor
+    /// with the given scale factor
+    pub fn physical_size(&self, scale_factor: f64) -> (u32, u32) {
+        (
+            to_physical(self.window_width as f64, scale_factor),
+            physical_window_height(self.max_visible_results, self.compact_mode, scale_factor),
+        )
+    }
 }
 
 /// UI theme options
@@ -34,6 +475,141 @@ pub enum Theme {
     System,
 }
 
+/// Unit temperatures are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+/// Where the result of the quick math hotkey goes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickMathOutput {
+    /// Shown in a toast, leaving the selection alone
+    #[default]
+    Toast,
+    /// Typed over the selection
+    Replace,
+}
+
+/// When feedback sounds play
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundFeedback {
+    /// Never
+    #[default]
+    Off,
+    /// Only when a result fails to open
+    ErrorsOnly,
+    /// When the hotkey shows the launcher, a result fails or an update is available
+    All,
+}
+
+/// What a notification is about, routed on its own in settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// A provider failing, being limited or turned off
+    ProviderHealth,
+    /// Updates found or installed
+    Updates,
+    /// The outcome of opening a result or of the quick math hotkey
+    Execution,
+    /// Privacy mode and forgotten data
+    Privacy,
+    /// Anything else, such as a hotkey already in use
+    General,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 5] = [
+        NotificationCategory::ProviderHealth,
+        NotificationCategory::Updates,
+        NotificationCategory::Execution,
+        NotificationCategory::Privacy,
+        NotificationCategory::General,
+    ];
+
+    /// Route used when settings don't name one
+    ///
+    /// Provider trouble is rarely worth interrupting for, so it only shows
+    /// in the launcher; updates show both ways so they aren't missed.
+    pub fn default_route(self) -> NotificationRoute {
+        match self {
+            NotificationCategory::ProviderHealth => NotificationRoute::InApp,
+            NotificationCategory::Updates => NotificationRoute::Both,
+            NotificationCategory::Execution | NotificationCategory::Privacy | NotificationCategory::General => {
+                NotificationRoute::Toast
+            }
+        }
+    }
+}
+
+/// Where notifications of a category are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationRoute {
+    /// As a toast
+    Toast,
+    /// As a banner in the launcher, held until it next opens if hidden
+    InApp,
+    /// Both as a toast and, while the launcher is open, as a banner
+    Both,
+    /// Not at all; the event is only logged
+    Off,
+}
+
+/// WAV files replacing the bundled feedback sounds; empty entries keep the bundled sound
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomSounds {
+    pub shown: Option<String>,
+    pub execution_failed: Option<String>,
+    pub update_available: Option<String>,
+}
+
+/// A terminal chosen in settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreferredTerminal {
+    /// Terminal executable
+    pub path: PathBuf,
+    /// Arguments, with `{path}` where the folder goes
+    pub args_template: String,
+}
+
+/// Format of a bookmark file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BookmarkFileFormat {
+    /// Chrome-style `Bookmarks` JSON (Chrome, Edge, Opera, Brave, ...)
+    Chromium,
+    /// Firefox `places.sqlite` database
+    Firefox,
+}
+
+/// A bookmark file registered by the user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomBookmarkFile {
+    /// Path to the bookmark file
+    pub path: String,
+    /// Format of the file
+    pub format: BookmarkFileFormat,
+    /// Name shown for results from this file
+    pub label: String,
+}
+
+/// A query macro: typing `keyword argument` runs `template_action` with
+/// `{arg}` replaced by the argument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryMacro {
+    pub keyword: String,
+    /// Action whose text fields may use the `{arg}` placeholder
+    pub template_action: ResultAction,
+}
+
 /// Configuration for which providers are enabled
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnabledProviders {
@@ -44,6 +620,12 @@ pub struct EnabledProviders {
     pub clipboard: bool,
     pub bookmarks: bool,
     pub recent_files: bool,
+    /// Browser history is opt-in since it is more private than bookmarks (applied on restart)
+    #[serde(default)]
+    pub browser_history: bool,
+    /// Weather is opt-in since it sends the searched city or position to Open-Meteo (applied on restart)
+    #[serde(default)]
+    pub weather: bool,
 }
 
 impl Default for AppSettings {
@@ -55,6 +637,45 @@ impl Default for AppSettings {
             enabled_providers: EnabledProviders::default(),
             search_delay: 150,
             start_with_windows: false,
+            hide_after_execute: default_hide_after_execute(),
+            clipboard_history_size: default_clipboard_history_size(),
+            debug_mode: false,
+            enable_file_delete_action: false,
+            group_file_results: false,
+            stable_result_slots: false,
+            fold_diacritics: default_fold_diacritics(),
+            privacy_mode: false,
+            analytics_local: false,
+            web_search_engine: default_web_search_engine(),
+            temperature_unit: TemperatureUnit::default(),
+            weather_home_city: None,
+            sound_feedback: SoundFeedback::default(),
+            custom_sounds: CustomSounds::default(),
+            notification_routing: default_notification_routing(),
+            preferred_file_manager: None,
+            preferred_terminal: None,
+            clipboard_auto_paste: false,
+            quick_math_hotkey: None,
+            quick_math_output: QuickMathOutput::default(),
+            custom_bookmark_files: Vec::new(),
+            open_bookmarks_in_source_browser: false,
+            excluded_paths: Vec::new(),
+            preferred_paths: Vec::new(),
+            extension_class_weights: HashMap::new(),
+            ranking_stopwords: default_ranking_stopwords(),
+            plain_text_markers: default_plain_text_markers(),
+            macros: Vec::new(),
+            window_width: default_window_width(),
+            max_visible_results: default_max_visible_results(),
+            compact_mode: false,
+            disable_show_animation: false,
+            screen_reader_support: false,
+            allow_unsigned_results: false,
+            duplicate_window_ms: default_duplicate_window_ms(),
+            memory_trim_threshold_mb: default_memory_trim_threshold_mb(),
+            provider_settings: HashMap::new(),
+            profiles: Vec::new(),
+            active_profile: None,
         }
     }
 }
@@ -69,69 +690,632 @@ impl Default for EnabledProviders {
             clipboard: true,
             bookmarks: true,
             recent_files: true,
+            browser_history: false,
+            weather: false,
         }
     }
 }
 
+/// Range a whole-number setting must fall in, with the error shown otherwise
+#[derive(Debug, Clone, Copy)]
+pub struct SettingRange {
+    pub min: u64,
+    pub max: u64,
+    pub error: &'static str,
+}
+
+/// A field of `AppSettings` as the user sees it
+#[derive(Debug, Clone, Copy)]
+pub struct SettingSpec {
+    /// Name of the `AppSettings` field
+    pub key: &'static str,
+    /// Name shown in search results
+    pub name: &'static str,
+    /// Part of the Settings window the field is shown in
+    pub section: &'static str,
+    /// Lowercase alternative names and phrases matched against queries
+    pub keywords: &'static [&'static str],
+    /// Allowed range, checked by `AppSettings::validate`
+    pub range: Option<SettingRange>,
+    /// Provider the setting only matters for, as returned by `SearchProvider::name`
+    pub provider: Option<&'static str>,
+}
+
+impl SettingSpec {
+    const fn new(key: &'static str, name: &'static str, section: &'static str, keywords: &'static [&'static str]) -> Self {
+        Self { key, name, section, keywords, range: None, provider: None }
+    }
+
+    const fn range(mut self, min: u64, max: u64, error: &'static str) -> Self {
+        self.range = Some(SettingRange { min, max, error });
+        self
+    }
+
+    const fn provider(mut self, provider: &'static str) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+}
+
+/// Every `AppSettings` field, in the order the Settings window shows them
+///
+/// Validation reads the ranges from here and settings search the names and
+/// keywords, so a new field needs an entry; a test checks none is missing.
+pub const SETTINGS_REGISTRY: &[SettingSpec] = &[
+    // Hotkeys
+    SettingSpec::new("hotkey", "Global hotkey", "hotkeys", &["shortcut", "keyboard shortcut", "open launcher", "key combination"]),
+    SettingSpec::new("quick_math_hotkey", "Quick math hotkey", "hotkeys", &["calculate selection", "evaluate selection", "shortcut"]),
+    SettingSpec::new("quick_math_output", "Quick math output", "hotkeys", &["replace selection", "show result", "toast"]),
+    // Appearance
+    SettingSpec::new("theme", "Theme", "appearance", &["dark mode", "light mode", "colors"]),
+    SettingSpec::new("window_width", "Window width", "appearance", &["window size", "wider", "narrower"])
+        .range(400, 1200, "Window width must be between 400 and 1200"),
+    SettingSpec::new("max_visible_results", "Visible results", "appearance", &["window height", "rows", "taller"])
+        .range(3, 20, "Visible results must be between 3 and 20"),
+    SettingSpec::new("compact_mode", "Compact mode", "appearance", &["dense", "smaller rows", "density"]),
+    SettingSpec::new("disable_show_animation", "Show instantly", "appearance", &["animation", "fade", "faster", "latency"]),
+    SettingSpec::new("screen_reader_support", "Screen reader support", "appearance", &["accessibility", "narrator", "nvda", "spoken labels"]),
+    // Search
+    SettingSpec::new("max_results", "Max results", "search", &["result count", "number of results", "limit"])
+        .range(1, 50, "Max results must be between 1 and 50"),
+    SettingSpec::new("search_delay", "Search delay", "search", &["debounce", "typing delay", "latency"])
+        .range(0, 1000, "Search delay must be less than 1000ms"),
+    SettingSpec::new("hide_after_execute", "Hide after opening a result", "search", &["keep open", "close window", "stay open"]),
+    SettingSpec::new("enable_file_delete_action", "Move to Recycle Bin action", "search", &["delete files", "recycle bin", "remove file"]),
+    SettingSpec::new("group_file_results", "Group file results by folder", "search", &["collapse", "folders", "grouping"]),
+    SettingSpec::new("stable_result_slots", "Stable number shortcuts", "search", &["alt", "shortcuts", "slots", "reorder"]),
+    SettingSpec::new("fold_diacritics", "Ignore accents", "search", &["diacritics", "accent folding"]),
+    SettingSpec::new("privacy_mode", "Privacy mode", "search", &["private", "forget", "recent results", "history"]),
+    SettingSpec::new("analytics_local", "Local search analytics", "search", &["statistics", "telemetry", "tuning", "export"]),
+    SettingSpec::new("web_search_engine", "Web search engine", "search", &["google", "bing", "duckduckgo", "search engine"])
+        .provider("WebSearch"),
+    SettingSpec::new("excluded_paths", "Excluded folders", "search", &["hide folders", "ignore paths", "exclude"]),
+    SettingSpec::new("preferred_paths", "Preferred folders", "search", &["rank higher", "favorite folders", "boost"]),
+    SettingSpec::new("extension_class_weights", "File type preferences", "search", &["extensions", "documents first", "logs", "temp files"]),
+    SettingSpec::new("ranking_stopwords", "Ignored query words", "search", &["stopwords", "common words", "ranking"]),
+    SettingSpec::new("plain_text_markers", "Plain text search", "search", &["escape", "backtick", "quote", "literal"]),
+    SettingSpec::new("macros", "Query macros", "search", &["macro", "keyword", "template", "custom command"]),
+    SettingSpec::new("debug_mode", "Debug mode", "search", &["score breakdown", "ranking", "explain"]),
+    SettingSpec::new("allow_unsigned_results", "Allow unsigned results", "search", &["integrity", "strict", "development"]),
+    SettingSpec::new("duplicate_window_ms", "Ignore repeated Enter", "search", &["double enter", "double click", "debounce"])
+        .range(0, 2000, "The repeat window must be at most 2000ms"),
+    // Clipboard
+    SettingSpec::new("clipboard_history_size", "Clipboard history size", "clipboard", &["clipboard items", "history length"])
+        .range(1, 500, "Clipboard history size must be between 1 and 500")
+        .provider("Clipboard History"),
+    SettingSpec::new("clipboard_auto_paste", "Paste restored clipboard items", "clipboard", &["auto paste", "clipboard paste"])
+        .provider("Clipboard History"),
+    // Sounds
+    SettingSpec::new("sound_feedback", "Sounds", "sounds", &["sound feedback", "audio", "beep", "mute"]),
+    SettingSpec::new("custom_sounds", "Custom sounds", "sounds", &["wav", "sound files"]),
+    // Launchers
+    SettingSpec::new("preferred_file_manager", "File manager", "launchers", &["explorer", "folders open in", "total commander"]),
+    SettingSpec::new("preferred_terminal", "Terminal", "launchers", &["open in terminal", "powershell", "command prompt", "windows terminal"]),
+    // Providers
+    SettingSpec::new("enabled_providers", "Search providers", "providers", &["enable", "disable", "sources"]),
+    SettingSpec::new("custom_bookmark_files", "Bookmark files", "providers", &["bookmarks", "browser bookmarks"])
+        .provider("Bookmarks"),
+    SettingSpec::new("open_bookmarks_in_source_browser", "Open bookmarks in their browser", "providers", &["bookmarks", "browser profile", "chrome", "firefox"])
+        .provider("Bookmarks"),
+    SettingSpec::new("provider_settings", "Provider options", "providers", &["provider settings", "per provider"]),
+    // Weather
+    SettingSpec::new("temperature_unit", "Temperature unit", "weather", &["celsius", "fahrenheit", "weather units"])
+        .provider("Weather"),
+    SettingSpec::new("weather_home_city", "Weather home city", "weather", &["location", "city"])
+        .provider("Weather"),
+    // System
+    SettingSpec::new("start_with_windows", "Start with Windows", "system", &["autostart", "startup", "launch at login"]),
+    SettingSpec::new("notification_routing", "Notifications", "system", &["toast", "banner", "alerts", "mute notifications"]),
+    SettingSpec::new("memory_trim_threshold_mb", "Memory trim threshold", "system", &["memory", "ram", "cache size", "working set"]),
+    // Profiles
+    SettingSpec::new("profiles", "Profiles", "profiles", &["work", "personal", "context", "overrides"]),
+    SettingSpec::new("active_profile", "Active profile", "profiles", &["switch profile", "work", "personal"]),
+];
+
+/// A setting settings search can find: an `AppSettings` field or a provider option
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingEntry {
+    /// Field name, or `provider_settings.<section>.<field>` for provider options
+    pub key: String,
+    pub name: String,
+    /// Part of the Settings window the setting is shown in
+    pub section: String,
+    pub keywords: Vec<String>,
+    /// Provider the setting only matters for
+    pub provider: Option<String>,
+}
+
+/// Lists every searchable setting: `SETTINGS_REGISTRY` followed by the
+/// options providers declare through `ProviderConfig`
+pub fn settings_entries() -> Vec<SettingEntry> {
+    let fields = SETTINGS_REGISTRY.iter().map(|spec| SettingEntry {
+        key: spec.key.to_string(),
+        name: spec.name.to_string(),
+        section: spec.section.to_string(),
+        keywords: spec.keywords.iter().map(|keyword| keyword.to_string()).collect(),
+        provider: spec.provider.map(str::to_string),
+    });
+
+    let options = provider_options().into_iter().map(|option| SettingEntry {
+        key: format!("provider_settings.{}.{}", option.section, option.field),
+        name: option.description.to_string(),
+        section: "providers".to_string(),
+        keywords: vec![option.provider.to_lowercase(), option.section.replace('_', " "), option.field.replace('_', " ")],
+        provider: Some(option.provider.to_string()),
+    });
+
+    fields.chain(options).collect()
+}
+
+/// Serializes saves, so two `update_settings` calls can't interleave their writes
+static SAVE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// What this process last wrote to each settings file, to tell our own
+/// writes apart from edits made by hand while the app runs
+static KNOWN_FILES: Mutex<Option<HashMap<PathBuf, KnownFile>>> = Mutex::new(None);
+
+/// A settings file as this process last wrote it, or first read it
+#[derive(Debug, Clone)]
+struct KnownFile {
+    stamp: FileStamp,
+    contents: serde_json::Value,
+}
+
+/// Modification time and size of a file; a change in either means someone rewrote it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+fn known_file(path: &Path) -> Option<KnownFile> {
+    let known = KNOWN_FILES.lock().unwrap_or_else(|e| e.into_inner());
+    known.as_ref()?.get(path).cloned()
+}
+
+/// Records `contents` as this process's view of the file; `replace` is false
+/// for reads, which only fill in a missing entry
+fn remember_file(path: &Path, contents: serde_json::Value, replace: bool) {
+    let Some(stamp) = FileStamp::of(path) else {
+        return;
+    };
+    let mut known = KNOWN_FILES.lock().unwrap_or_else(|e| e.into_inner());
+    let files = known.get_or_insert_with(HashMap::new);
+    if replace || !files.contains_key(path) {
+        files.insert(path.to_path_buf(), KnownFile { stamp, contents });
+    }
+}
+
+/// Replaces `path` with `contents` so a crash leaves either the old or the new file
+///
+/// The contents go to a temporary file in the same directory, are flushed to
+/// disk, and the temporary file is then renamed over the target.
+/// `before_rename` runs in between; tests use it to fail at that point.
+fn write_atomic_with(
+    path: &Path,
+    contents: &[u8],
+    before_rename: impl FnOnce(&Path) -> std::io::Result<()>,
+) -> Result<()> {
+    let failed = |e: std::io::Error| LauncherError::SettingsError(format!("Failed to write settings: {}", e));
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| LauncherError::SettingsError(format!("Failed to create settings directory: {}", e)))?;
+    }
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    let written = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| before_rename(&temp_path))
+        .and_then(|()| fs::rename(&temp_path, path));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp_path);
+        return Err(failed(e));
+    }
+
+    Ok(())
+}
+
+/// Combines our changes with edits made to the file since we last wrote it
+///
+/// Works field by field: a field only one side changed takes that side's
+/// value. Returns the fields both sides changed to different values.
+fn merge_settings(
+    base: &serde_json::Value,
+    ours: &serde_json::Value,
+    theirs: &serde_json::Value,
+) -> std::result::Result<serde_json::Value, Vec<String>> {
+    let (Some(base), Some(ours), Some(theirs)) = (base.as_object(), ours.as_object(), theirs.as_object()) else {
+        return Err(vec!["settings".to_string()]);
+    };
+
+    let mut merged = serde_json::Map::new();
+    let mut conflicts = Vec::new();
+    let keys: std::collections::BTreeSet<&String> = ours.keys().chain(theirs.keys()).collect();
+    for key in keys {
+        let (b, o, t) = (base.get(key), ours.get(key), theirs.get(key));
+        let value = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            conflicts.push(key.clone());
+            continue;
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+
+    if conflicts.is_empty() {
+        Ok(serde_json::Value::Object(merged))
+    } else {
+        Err(conflicts)
+    }
+}
+
+/// What happened to a save
+#[derive(Debug, Clone)]
+pub enum SaveOutcome {
+    /// The file was written; holds the settings read back from it, including
+    /// edits made outside the app to other fields
+    Saved(AppSettings),
+    /// The file was edited outside the app in the same fields, so nothing was written
+    Conflict {
+        /// The settings as they are on disk
+        on_disk: AppSettings,
+        /// Fields changed both here and on disk
+        fields: Vec<String>,
+    },
+}
+
+/// Payload of the `settings-conflict` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsConflict {
+    pub settings: AppSettings,
+    pub fields: Vec<String>,
+}
+
 impl AppSettings {
     /// Load settings from disk, or create default if not found
+    ///
+    /// These are the settings as stored, which the Settings view edits; what
+    /// applies is `effective`.
     pub fn load() -> Result<Self> {
-        let path = Self::settings_path()?;
-        
+        Self::load_from(&Self::settings_path()?)
+    }
+
+    /// Loads the settings with the active profile's overrides applied
+    pub fn load_effective() -> Result<Self> {
+        Self::load()?.effective()
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
         if path.exists() {
-            let contents = fs::read_to_string(&path)
+            let contents = fs::read_to_string(path)
                 .map_err(|e| LauncherError::SettingsError(format!("Failed to read settings: {}", e)))?;
             
             let settings: AppSettings = serde_json::from_str(&contents)
                 .map_err(|e| LauncherError::SettingsError(format!("Failed to parse settings: {}", e)))?;
             
             settings.validate()?;
+            remember_file(path, serde_json::to_value(&settings)?, false);
             Ok(settings)
         } else {
             let settings = Self::default();
-            settings.save()?;
+            settings.write_to(path)?;
             Ok(settings)
         }
     }
 
     /// Save settings to disk
-    pub fn save(&self) -> Result<()> {
+    ///
+    /// Saves run one at a time and replace the file atomically. Edits made to
+    /// the file by hand since this process last wrote it are kept when they
+    /// touch other fields; when they touch the same fields nothing is written
+    /// and the outcome is a conflict.
+    pub async fn save(&self) -> Result<SaveOutcome> {
+        self.save_to(&Self::settings_path()?).await
+    }
+
+    /// Waits for a save in progress to finish writing the file
+    ///
+    /// Changes are saved as they're made, so this is all exiting has to wait for.
+    pub async fn finish_pending_save() {
+        let _guard = SAVE_LOCK.lock().await;
+    }
+
+    async fn save_to(&self, path: &Path) -> Result<SaveOutcome> {
         self.validate()?;
-        
-        let path = Self::settings_path()?;
-        
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| LauncherError::SettingsError(format!("Failed to create settings directory: {}", e)))?;
+        let _guard = SAVE_LOCK.lock().await;
+
+        let ours = serde_json::to_value(self)?;
+        let merged = match Self::edited_since_known(path) {
+            Some((base, theirs, on_disk)) => match merge_settings(&base, &ours, &theirs) {
+                Ok(merged) => {
+                    tracing::info!("Settings file was edited outside the app, keeping those edits");
+                    let merged: AppSettings = serde_json::from_value(merged)?;
+                    merged.validate()?;
+                    merged
+                }
+                Err(fields) => {
+                    tracing::warn!("Settings file was edited outside the app, conflicting fields: {:?}", fields);
+                    return Ok(SaveOutcome::Conflict { on_disk, fields });
+                }
+            },
+            None => self.clone(),
+        };
+
+        merged.write_to(path)?;
+
+        // Callers continue with exactly what is stored
+        Ok(SaveOutcome::Saved(Self::load_from(path)?))
+    }
+
+    /// Returns what this process last knew of the file, what is on disk now,
+    /// and the on-disk settings, if the file changed since we last wrote it
+    fn edited_since_known(path: &Path) -> Option<(serde_json::Value, serde_json::Value, AppSettings)> {
+        let known = known_file(path)?;
+        if FileStamp::of(path)? == known.stamp {
+            return None;
         }
-        
+
+        let contents = fs::read_to_string(path).ok()?;
+        match serde_json::from_str::<AppSettings>(&contents) {
+            Ok(on_disk) => {
+                let theirs = serde_json::to_value(&on_disk).ok()?;
+                Some((known.contents, theirs, on_disk))
+            }
+            Err(e) => {
+                tracing::warn!("Settings file was edited outside the app and can't be parsed, replacing it: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Writes the settings atomically and remembers them as ours
+    fn write_to(&self, path: &Path) -> Result<()> {
         let contents = serde_json::to_string_pretty(self)
             .map_err(|e| LauncherError::SettingsError(format!("Failed to serialize settings: {}", e)))?;
-        
-        fs::write(&path, contents)
-            .map_err(|e| LauncherError::SettingsError(format!("Failed to write settings: {}", e)))?;
-        
+
+        write_atomic_with(path, contents.as_bytes(), |_| Ok(()))?;
+        remember_file(path, serde_json::to_value(self)?, true);
         Ok(())
     }
 
-    /// Validate settings
+    /// Returns the profile with this name, ignoring case
+    pub fn profile(&self, name: &str) -> Result<&SettingsProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| LauncherError::NotFound(format!("Unknown profile '{}'", name)))
+    }
+
+    /// Returns these settings with the overrides laid over them
+    fn merged_with(&self, overrides: &PartialAppSettings) -> Result<AppSettings> {
+        let mut merged = serde_json::to_value(self)?;
+        let overrides = serde_json::to_value(overrides)?;
+        if let (Some(fields), serde_json::Value::Object(overrides)) = (merged.as_object_mut(), overrides) {
+            fields.extend(overrides);
+        }
+        serde_json::from_value(merged)
+            .map_err(|e| LauncherError::SettingsError(format!("Failed to apply profile overrides: {}", e)))
+    }
+
+    /// Returns the settings that apply: these with the active profile's
+    /// overrides, or these as they are without an active profile
+    pub fn effective(&self) -> Result<AppSettings> {
+        match &self.active_profile {
+            Some(name) => self.merged_with(&self.profile(name)?.overrides),
+            None => Ok(self.clone()),
+        }
+    }
+
+    /// Returns these settings with `name` as the active profile, or none
+    ///
+    /// The profile's name is stored as written in `profiles`.
+    pub fn with_active_profile(&self, name: Option<&str>) -> Result<AppSettings> {
+        let active_profile = match name {
+            Some(name) => Some(self.profile(name)?.name.clone()),
+            None => None,
+        };
+        Ok(AppSettings {
+            active_profile,
+            ..self.clone()
+        })
+    }
+
+    /// Validate settings, including every profile merged over them
     pub fn validate(&self) -> Result<()> {
+        self.validate_fields()?;
+
+        for (index, profile) in self.profiles.iter().enumerate() {
+            if profile.name.trim().is_empty() {
+                return Err(LauncherError::ConfigError("Profile names cannot be empty".to_string()));
+            }
+            if self.profiles[..index].iter().any(|other| other.name.eq_ignore_ascii_case(&profile.name)) {
+                return Err(LauncherError::ConfigError(format!("There are two profiles named '{}'", profile.name)));
+            }
+            self.merged_with(&profile.overrides)?
+                .validate_fields()
+                .map_err(|e| match e {
+                    LauncherError::ConfigError(message) => {
+                        LauncherError::ConfigError(format!("Profile '{}': {}", profile.name, message))
+                    }
+                    e => e,
+                })?;
+        }
+
+        if let Some(active) = &self.active_profile {
+            self.profile(active)?;
+        }
+        Ok(())
+    }
+
+    /// Validates the fields, leaving profiles out
+    fn validate_fields(&self) -> Result<()> {
         if self.hotkey.is_empty() {
             return Err(LauncherError::ConfigError("Hotkey cannot be empty".to_string()));
         }
         
-        if self.max_results == 0 || self.max_results > 50 {
-            return Err(LauncherError::ConfigError("Max results must be between 1 and 50".to_string()));
+        if let Some(quick_math_hotkey) = &self.quick_math_hotkey {
+            if quick_math_hotkey.eq_ignore_ascii_case(&self.hotkey) {
+                return Err(LauncherError::ConfigError(
+                    "The quick math hotkey must differ from the launcher hotkey".to_string(),
+                ));
+            }
+        }
+        
+        let fields = serde_json::to_value(self)?;
+        for spec in SETTINGS_REGISTRY {
+            let Some(range) = spec.range else {
+                continue;
+            };
+            let in_range = fields
+                .get(spec.key)
+                .and_then(serde_json::Value::as_u64)
+                .is_some_and(|value| (range.min..=range.max).contains(&value));
+            if !in_range {
+                return Err(LauncherError::ConfigError(range.error.to_string()));
+            }
+        }
+        
+        if WebSearchEngine::find(&self.web_search_engine).is_none() {
+            return Err(LauncherError::ConfigError(format!("Unknown web search engine '{}'", self.web_search_engine)));
+        }
+        
+        self.custom_sounds.validate()?;
+        
+        if let Some(terminal) = &self.preferred_terminal {
+            validate_args_template(&terminal.args_template)?;
+        }
+        
+        validate_provider_settings(&self.provider_settings)?;
+        validate_macros(&self.macros)?;
+        
+        if self.extension_class_weights.values().any(|weight| !weight.is_finite() || weight.abs() > MAX_CLASS_WEIGHT) {
+            return Err(LauncherError::ConfigError(format!(
+                "File type weights must be between -{0} and {0}",
+                MAX_CLASS_WEIGHT
+            )));
         }
         
-        if self.search_delay > 1000 {
-            return Err(LauncherError::ConfigError("Search delay must be less than 1000ms".to_string()));
+        // A letter, digit or space would turn ordinary queries into plain text
+        if self.plain_text_markers.chars().any(|c| c.is_alphanumeric() || c.is_whitespace()) {
+            return Err(LauncherError::ConfigError(
+                "Plain text markers must be symbols, such as ' or `".to_string(),
+            ));
         }
         
         Ok(())
     }
 
+    /// Checks that the configured file manager and terminal are installed
+    ///
+    /// Only checked when saving: a tool uninstalled later falls back to the
+    /// default instead of making the settings file invalid.
+    pub fn validate_launchers(&self) -> Result<()> {
+        if let Some(file_manager) = &self.preferred_file_manager {
+            validate_program(file_manager)?;
+        }
+        if let Some(terminal) = &self.preferred_terminal {
+            validate_program(&terminal.path)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the external tools folders and terminals open with
+    pub fn launchers(&self) -> Launchers {
+        Launchers {
+            file_manager: self.preferred_file_manager.clone(),
+            terminal: self.preferred_terminal.clone(),
+        }
+    }
+
+    /// Returns the unit and home city the weather provider uses
+    pub fn weather_preferences(&self) -> WeatherPreferences {
+        WeatherPreferences {
+            unit: self.temperature_unit,
+            home_city: self
+                .weather_home_city
+                .as_deref()
+                .map(str::trim)
+                .filter(|city| !city.is_empty())
+                .map(str::to_string),
+        }
+    }
+
+    /// Returns the per-provider options handed to the search engine
+    ///
+    /// The clipboard section falls back to `clipboard_history_size`, which
+    /// predates `provider_settings`.
+    pub fn resolved_provider_settings(&self) -> HashMap<String, serde_json::Value> {
+        let mut settings = self.provider_settings.clone();
+        let clipboard = settings
+            .entry("clipboard".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if let Some(section) = clipboard.as_object_mut() {
+            section
+                .entry("max_items")
+                .or_insert_with(|| serde_json::json!(self.clipboard_history_size));
+        }
+        settings
+    }
+
+    /// Returns the window geometry and density these settings ask for
+    pub fn appearance(&self) -> Appearance {
+        Appearance {
+            window_width: self.window_width,
+            window_height: window_height(self.max_visible_results, self.compact_mode),
+            max_visible_results: self.max_visible_results,
+            compact_mode: self.compact_mode,
+        }
+    }
+
+    /// Decide whether the main window should hide after executing a result
+    ///
+    /// An explicit `keep_open` overrides the configured default. Failed
+    /// executions never hide the window so the error stays visible.
+    pub fn should_hide_after_execute(&self, succeeded: bool, keep_open: Option<bool>) -> bool {
+        let keep_open = keep_open.unwrap_or(!self.hide_after_execute);
+        succeeded && !keep_open
+    }
+
+    /// Whether results sent for execution skip the integrity check
+    ///
+    /// Only development builds honour the setting, so a release can't be
+    /// talked out of the check by editing settings.json.
+    pub fn allows_unsigned_results(&self) -> bool {
+        cfg!(debug_assertions) && self.allow_unsigned_results
+    }
+
+    /// Windows in which repeated executions are ignored, for ordinary results
+    /// and for those asking for confirmation, which never get less than the
+    /// default unless turned off
+    pub fn duplicate_windows(&self) -> (Duration, Duration) {
+        let window = Duration::from_millis(self.duplicate_window_ms);
+        if window.is_zero() {
+            return (window, window);
+        }
+        (window, window.max(CONFIRMED_DUPLICATE_WINDOW))
+    }
+
     /// Get the path to the settings file
     fn settings_path() -> Result<PathBuf> {
         #[cfg(target_os = "windows")]
@@ -168,6 +1352,85 @@ impl AppSettings {
 mod tests {
     use super::*;
 
+    fn temp_settings_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("better_finder_settings_test").join(name);
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("settings.json")
+    }
+
+    /// Rewrites the file as an editor would, making sure the stamp changes
+    fn edit_by_hand(path: &Path, edit: impl FnOnce(&mut serde_json::Value)) {
+        let mut json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        edit(&mut json);
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(path, serde_json::to_string_pretty(&json).unwrap() + "\n\n").unwrap();
+    }
+
+    #[test]
+    fn test_failed_write_keeps_the_previous_file() {
+        let path = temp_settings_path("atomic");
+        write_atomic_with(&path, b"old", |_| Ok(())).unwrap();
+
+        let result = write_atomic_with(&path, b"new", |temp| {
+            assert_eq!(fs::read(temp).unwrap(), b"new");
+            Err(std::io::Error::other("power cut"))
+        });
+
+        assert!(result.unwrap_err().to_string().contains("power cut"));
+        assert_eq!(fs::read(&path).unwrap(), b"old");
+        let leftovers: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().collect();
+        assert_eq!(leftovers.len(), 1, "temporary file was left behind");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_saves_never_interleave() {
+        let path = temp_settings_path("concurrent");
+        AppSettings::load_from(&path).unwrap();
+
+        let saves = (1..=8).map(|n| {
+            let path = path.clone();
+            tokio::spawn(async move {
+                let settings = AppSettings { max_results: n, ..AppSettings::default() };
+                settings.save_to(&path).await.unwrap()
+            })
+        });
+        for save in futures::future::join_all(saves).await {
+            assert!(matches!(save.unwrap(), SaveOutcome::Saved(_)));
+        }
+
+        let saved = AppSettings::load_from(&path).unwrap();
+        assert!((1..=8).contains(&saved.max_results));
+    }
+
+    #[tokio::test]
+    async fn test_edits_made_by_hand_are_merged_or_reported() {
+        let path = temp_settings_path("external");
+        let loaded = AppSettings::load_from(&path).unwrap();
+
+        // A different field edited by hand is kept alongside ours
+        edit_by_hand(&path, |json| json["search_delay"] = serde_json::json!(300));
+        let ours = AppSettings { max_results: 12, ..loaded.clone() };
+        match ours.save_to(&path).await.unwrap() {
+            SaveOutcome::Saved(saved) => {
+                assert_eq!(saved.max_results, 12);
+                assert_eq!(saved.search_delay, 300);
+            }
+            other => panic!("expected a merged save, got {:?}", other),
+        }
+
+        // The same field edited both ways is a conflict and leaves the file alone
+        edit_by_hand(&path, |json| json["max_results"] = serde_json::json!(20));
+        let ours = AppSettings { max_results: 5, ..loaded };
+        match ours.save_to(&path).await.unwrap() {
+            SaveOutcome::Conflict { on_disk, fields } => {
+                assert_eq!(fields, vec!["max_results".to_string()]);
+                assert_eq!(on_disk.max_results, 20);
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+        assert_eq!(AppSettings::load_from(&path).unwrap().max_results, 20);
+    }
+
     #[test]
     fn test_default_settings() {
         let settings = AppSettings::default();
@@ -186,6 +1449,12 @@ mod tests {
         assert!(settings.validate().is_err());
 
         settings.hotkey = "Ctrl+K".to_string();
+        settings.quick_math_hotkey = Some("ctrl+k".to_string());
+        assert!(settings.validate().is_err());
+
+        settings.quick_math_hotkey = Some("Ctrl+Shift+M".to_string());
+        assert!(settings.validate().is_ok());
+
         settings.max_results = 0;
         assert!(settings.validate().is_err());
 
@@ -195,6 +1464,112 @@ mod tests {
         settings.max_results = 8;
         settings.search_delay = 2000;
         assert!(settings.validate().is_err());
+
+        settings.search_delay = 150;
+        settings.clipboard_history_size = 0;
+        assert!(settings.validate().is_err());
+
+        settings.clipboard_history_size = 501;
+        assert!(settings.validate().is_err());
+
+        settings.clipboard_history_size = 500;
+        assert!(settings.validate().is_ok());
+
+        settings.window_width = 399;
+        assert!(settings.validate().is_err());
+
+        settings.window_width = 1200;
+        settings.max_visible_results = 2;
+        assert!(settings.validate().is_err());
+
+        settings.max_visible_results = 21;
+        assert!(settings.validate().is_err());
+
+        settings.max_visible_results = 20;
+        assert!(settings.validate().is_ok());
+
+        settings.duplicate_window_ms = 2001;
+        assert!(settings.validate().is_err());
+    }
+
+    #[test]
+    fn test_window_height() {
+        // The defaults match the height the window was originally built with
+        assert_eq!(window_height(8, false), 480.0);
+        assert_eq!(window_height(8, true), 352.0);
+        assert!(window_height(20, true) < window_height(20, false));
+        assert_eq!(window_height(0, false), SEARCH_BAR_HEIGHT);
+
+        let appearance = AppSettings::default().appearance();
+        assert_eq!(appearance.window_width, 600);
+        assert_eq!(appearance.window_height, 480.0);
+    }
+
+    #[test]
+    fn test_physical_window_height_scales_with_dpi() {
+        let heights: Vec<u32> = [1.0, 1.25, 1.75, 2.0]
+            .iter()
+            .map(|scale| physical_window_height(8, false, *scale))
+            .collect();
+        assert_eq!(heights, vec![480, 600, 840, 960]);
+
+        let compact: Vec<u32> = [1.0, 1.25, 1.75, 2.0]
+            .iter()
+            .map(|scale| physical_window_height(8, true, *scale))
+            .collect();
+        assert_eq!(compact, vec![352, 440, 616, 704]);
+
+        // Fractional results round to the nearest pixel
+        assert_eq!(physical_window_height(3, true, 1.25), 215);
+
+        // Bogus scale factors fall back to 100%
+        assert_eq!(physical_window_height(8, false, 0.0), 480);
+        assert_eq!(physical_window_height(8, false, f64::NAN), 480);
+    }
+
+    #[test]
+    fn test_appearance_physical_size() {
+        let appearance = AppSettings::default().appearance();
+        assert_eq!(appearance.physical_size(1.0), (600, 480));
+        assert_eq!(appearance.physical_size(1.25), (750, 600));
+        assert_eq!(appearance.physical_size(1.75), (1050, 840));
+        assert_eq!(appearance.physical_size(2.0), (1200, 960));
+    }
+
+    #[test]
+    fn test_centered_position_on_each_monitor() {
+        // 100% primary monitor
+        assert_eq!(centered_position((0, 0), (1920, 1080), (600, 480)), (660, 300));
+        // 175% monitor to the left of the primary one
+        assert_eq!(
+            centered_position((-3840, 0), (3840, 2160), (1050, 840)),
+            (-2445, 660)
+        );
+        // A window larger than the monitor is still centered
+        assert_eq!(centered_position((0, 0), (800, 600), (1000, 700)), (-100, -50));
+    }
+
+    #[test]
+    fn test_window_placement_prepared_for_monitor() {
+        let appearance = AppSettings::default().appearance();
+        let primary = MonitorGeometry { position: (0, 0), size: (1920, 1080), scale_factor: 1.0 };
+        let left = MonitorGeometry { position: (-3840, 0), size: (3840, 2160), scale_factor: 1.75 };
+
+        let placement = WindowPlacement::for_monitor(primary, &appearance);
+        let size = appearance.physical_size(1.0);
+        assert_eq!(placement.size, size);
+        assert_eq!(placement.position, centered_position((0, 0), (1920, 1080), size));
+
+        let scaled = WindowPlacement::for_monitor(left, &appearance);
+        assert_eq!(scaled.size, appearance.physical_size(1.75));
+        assert!(scaled.position.0 < 0);
+
+        // Shows as placed until the monitor, its DPI or the appearance changes
+        assert!(placement.fits(primary, &appearance));
+        assert!(!placement.fits(left, &appearance));
+        assert!(!placement.fits(MonitorGeometry { scale_factor: 1.25, ..primary }, &appearance));
+        let wider = Appearance { window_width: appearance.window_width + 100, ..appearance };
+        assert!(!placement.fits(primary, &wider));
     }
 
     #[test]
@@ -205,5 +1580,284 @@ mod tests {
         
         assert_eq!(settings.hotkey, deserialized.hotkey);
         assert_eq!(settings.max_results, deserialized.max_results);
+        assert_eq!(settings.hide_after_execute, deserialized.hide_after_execute);
+    }
+
+    #[test]
+    fn test_hide_after_execute_defaults_when_missing() {
+        let mut json = serde_json::to_value(AppSettings::default()).unwrap();
+        json.as_object_mut().unwrap().remove("hide_after_execute");
+        json.as_object_mut().unwrap().remove("clipboard_history_size");
+        json.as_object_mut().unwrap().remove("debug_mode");
+        json.as_object_mut().unwrap().remove("enable_file_delete_action");
+        json.as_object_mut().unwrap().remove("group_file_results");
+        json.as_object_mut().unwrap().remove("fold_diacritics");
+        json.as_object_mut().unwrap().remove("web_search_engine");
+        json.as_object_mut().unwrap().remove("temperature_unit");
+        json.as_object_mut().unwrap().remove("weather_home_city");
+        json.as_object_mut().unwrap().remove("sound_feedback");
+        json.as_object_mut().unwrap().remove("custom_sounds");
+        json.as_object_mut().unwrap().remove("clipboard_auto_paste");
+        json.as_object_mut().unwrap().remove("quick_math_hotkey");
+        json.as_object_mut().unwrap().remove("quick_math_output");
+        json.as_object_mut().unwrap().remove("window_width");
+        json.as_object_mut().unwrap().remove("max_visible_results");
+        json.as_object_mut().unwrap().remove("compact_mode");
+        json.as_object_mut().unwrap().remove("memory_trim_threshold_mb");
+        json.as_object_mut().unwrap().remove("duplicate_window_ms");
+        json.as_object_mut().unwrap().remove("provider_settings");
+
+        let settings: AppSettings = serde_json::from_value(json).unwrap();
+        assert!(settings.hide_after_execute);
+        assert_eq!(settings.clipboard_history_size, 100);
+        assert!(!settings.debug_mode);
+        assert!(!settings.enable_file_delete_action);
+        assert!(!settings.group_file_results);
+        assert!(settings.fold_diacritics);
+        assert_eq!(settings.web_search_engine, "google");
+        assert_eq!(settings.temperature_unit, TemperatureUnit::Celsius);
+        assert_eq!(settings.weather_home_city, None);
+        assert_eq!(settings.sound_feedback, SoundFeedback::Off);
+        assert_eq!(settings.custom_sounds, CustomSounds::default());
+        assert!(!settings.clipboard_auto_paste);
+        assert_eq!(settings.quick_math_hotkey, None);
+        assert_eq!(settings.quick_math_output, QuickMathOutput::Toast);
+        assert_eq!(settings.window_width, 600);
+        assert_eq!(settings.max_visible_results, 8);
+        assert!(!settings.compact_mode);
+        assert_eq!(settings.memory_trim_threshold_mb, 400);
+        assert_eq!(settings.duplicate_window_ms, 500);
+        assert_eq!(settings.notification_routing[&NotificationCategory::ProviderHealth], NotificationRoute::InApp);
+        assert_eq!(settings.notification_routing.len(), NotificationCategory::ALL.len());
+        assert!(settings.provider_settings.is_empty());
+    }
+
+    #[test]
+    fn test_notification_routing_serializes_by_category_name() {
+        let json = serde_json::json!({ "provider_health": "off", "updates": "in_app" });
+        let routing: HashMap<NotificationCategory, NotificationRoute> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(routing[&NotificationCategory::ProviderHealth], NotificationRoute::Off);
+        assert_eq!(serde_json::to_value(&routing).unwrap(), json);
+    }
+
+    #[test]
+    fn test_unknown_web_search_engine_is_rejected() {
+        let mut settings = AppSettings {
+            web_search_engine: "altavista".to_string(),
+            ..AppSettings::default()
+        };
+        let error = settings.validate().unwrap_err().to_string();
+        assert!(error.contains("altavista"), "{}", error);
+
+        settings.web_search_engine = "duckduckgo".to_string();
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_extension_class_weights_are_keyed_by_class_and_bounded() {
+        let json = serde_json::json!({ "noise": 0.0, "images": 20.0 });
+        let mut settings = AppSettings {
+            extension_class_weights: serde_json::from_value(json).unwrap(),
+            ..AppSettings::default()
+        };
+        assert_eq!(settings.extension_class_weights[&ExtensionClass::Noise], 0.0);
+        assert!(settings.validate().is_ok());
+
+        settings.extension_class_weights.insert(ExtensionClass::Noise, -500.0);
+        assert!(settings.validate().is_err());
+
+        let unknown = serde_json::json!({ "spreadsheets": 5.0 });
+        assert!(serde_json::from_value::<HashMap<ExtensionClass, f64>>(unknown).is_err());
+    }
+
+    #[test]
+    fn test_preferred_terminal_needs_placeholder_and_program() {
+        let program = std::env::current_exe().unwrap();
+        let mut settings = AppSettings {
+            preferred_terminal: Some(PreferredTerminal {
+                path: program.clone(),
+                args_template: "--new-tab".to_string(),
+            }),
+            ..AppSettings::default()
+        };
+        assert!(settings.validate().unwrap_err().to_string().contains("{path}"));
+
+        settings.preferred_terminal = Some(PreferredTerminal {
+            path: program.with_file_name("uninstalled-terminal.exe"),
+            args_template: "--new-tab -d {path}".to_string(),
+        });
+        // An uninstalled terminal still loads, but can't be saved
+        assert!(settings.validate().is_ok());
+        assert!(settings.validate_launchers().is_err());
+
+        settings.preferred_terminal = Some(PreferredTerminal { path: program, args_template: "-d {path}".to_string() });
+        assert!(settings.validate_launchers().is_ok());
+    }
+
+    #[test]
+    fn test_provider_settings_validation_names_the_provider() {
+        let mut settings = AppSettings::default();
+        settings.provider_settings.insert("web_search".to_string(), serde_json::json!({ "min_query_length": 0 }));
+
+        let error = settings.validate().unwrap_err().to_string();
+        assert!(error.contains("WebSearch"), "{}", error);
+        assert!(error.contains("min_query_length"), "{}", error);
+
+        settings.provider_settings.insert("web_search".to_string(), serde_json::json!({ "min_query_length": 2 }));
+        assert!(settings.validate().is_ok());
+    }
+
+    #[test]
+    fn test_resolved_provider_settings_keep_clipboard_history_size() {
+        let mut settings = AppSettings { clipboard_history_size: 40, ..Default::default() };
+        assert_eq!(settings.resolved_provider_settings()["clipboard"], serde_json::json!({ "max_items": 40 }));
+
+        settings.provider_settings.insert("clipboard".to_string(), serde_json::json!({ "max_items": 25 }));
+        assert_eq!(settings.resolved_provider_settings()["clipboard"], serde_json::json!({ "max_items": 25 }));
+    }
+
+    #[test]
+    fn test_should_hide_after_execute_matrix() {
+        // (succeeded, keep_open, hide_after_execute setting, expected)
+        let cases = [
+            (true, None, true, true),
+            (true, None, false, false),
+            (true, Some(false), true, true),
+            (true, Some(false), false, true),
+            (true, Some(true), true, false),
+            (true, Some(true), false, false),
+            (false, None, true, false),
+            (false, None, false, false),
+            (false, Some(false), true, false),
+            (false, Some(false), false, false),
+            (false, Some(true), true, false),
+            (false, Some(true), false, false),
+        ];
+
+        for (succeeded, keep_open, hide_after_execute, expected) in cases {
+            let settings = AppSettings {
+                hide_after_execute,
+                ..AppSettings::default()
+            };
+            assert_eq!(
+                settings.should_hide_after_execute(succeeded, keep_open),
+                expected,
+                "succeeded={}, keep_open={:?}, hide_after_execute={}",
+                succeeded,
+                keep_open,
+                hide_after_execute
+            );
+        }
+    }
+
+    fn work_profile() -> SettingsProfile {
+        serde_json::from_value(serde_json::json!({
+            "name": "Work",
+            "overrides": {
+                "hotkey": "Ctrl+Alt+W",
+                "web_search_engine": "bing",
+                "excluded_paths": ["D:\\Personal"],
+                "weather_home_city": null,
+                "provider_settings": { "file_search": { "max_results": 20 } },
+            },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_profile_overrides_merge_shallowly() {
+        let base = AppSettings {
+            excluded_paths: vec!["node_modules".to_string()],
+            preferred_paths: vec![r"C:\Projects".to_string()],
+            weather_home_city: Some("Lisbon".to_string()),
+            provider_settings: HashMap::from([("clipboard".to_string(), serde_json::json!({ "max_items": 50 }))]),
+            profiles: vec![work_profile()],
+            ..AppSettings::default()
+        };
+        assert_eq!(base.effective().unwrap().hotkey, "Ctrl+K");
+
+        let work = base.with_active_profile(Some("work")).unwrap();
+        assert_eq!(work.active_profile.as_deref(), Some("Work"));
+        let effective = work.effective().unwrap();
+        assert_eq!(effective.hotkey, "Ctrl+Alt+W");
+        assert_eq!(effective.web_search_engine, "bing");
+        // Given fields replace the base value whole, others keep it
+        assert_eq!(effective.excluded_paths, vec![r"D:\Personal".to_string()]);
+        assert_eq!(effective.preferred_paths, vec![r"C:\Projects".to_string()]);
+        assert!(!effective.provider_settings.contains_key("clipboard"));
+        assert_eq!(effective.provider_settings["file_search"]["max_results"], 20);
+        assert_eq!(effective.weather_home_city, None);
+        // The profiles themselves are never overridden
+        assert_eq!(effective.profiles.len(), 1);
+        assert_eq!(effective.active_profile.as_deref(), Some("Work"));
+
+        let back = work.with_active_profile(None).unwrap().effective().unwrap();
+        assert_eq!(back.hotkey, "Ctrl+K");
+        assert_eq!(back.weather_home_city.as_deref(), Some("Lisbon"));
+    }
+
+    #[test]
+    fn test_unknown_and_invalid_profiles_are_rejected() {
+        let mut settings = AppSettings {
+            profiles: vec![work_profile()],
+            ..AppSettings::default()
+        };
+        assert!(matches!(settings.with_active_profile(Some("home")), Err(LauncherError::NotFound(_))));
+
+        settings.active_profile = Some("home".to_string());
+        assert!(settings.validate().is_err());
+        assert!(settings.effective().is_err());
+        settings.active_profile = None;
+        assert!(settings.validate().is_ok());
+
+        // Each profile is checked merged over the base settings
+        settings.quick_math_hotkey = Some("Ctrl+Alt+W".to_string());
+        let error = settings.validate().unwrap_err().to_string();
+        assert!(error.contains("Profile 'Work'"), "{}", error);
+        settings.quick_math_hotkey = None;
+
+        settings.profiles.push(SettingsProfile {
+            name: "WORK".to_string(),
+            overrides: PartialAppSettings::default(),
+        });
+        assert!(settings.validate().is_err());
+
+        // Misspelled fields aren't silently ignored
+        let typo = serde_json::json!({ "name": "Home", "overrides": { "web_search": "bing" } });
+        assert!(serde_json::from_value::<SettingsProfile>(typo).is_err());
+    }
+
+    #[test]
+    fn test_partial_settings_mirror_every_field() {
+        let mut json = serde_json::to_value(AppSettings::default()).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("profiles");
+        fields.remove("active_profile");
+        let mut expected: Vec<String> = fields.keys().cloned().collect();
+
+        // Fails on fields only AppSettings has; those only the mirror has stay unset
+        let partial: PartialAppSettings = serde_json::from_value(json).unwrap();
+        let mirrored = serde_json::to_value(partial).unwrap();
+        let mut actual: Vec<String> = mirrored.as_object().unwrap().keys().cloned().collect();
+        expected.sort_unstable();
+        actual.sort_unstable();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_registry_covers_every_field() {
+        let json = serde_json::to_value(AppSettings::default()).unwrap();
+        let mut fields: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        let mut registered: Vec<&str> = SETTINGS_REGISTRY.iter().map(|spec| spec.key).collect();
+        fields.sort_unstable();
+        registered.sort_unstable();
+        assert_eq!(fields, registered);
+
+        let entries = settings_entries();
+        let option = entries
+            .iter()
+            .find(|entry| entry.key == "provider_settings.file_search.max_results")
+            .expect("provider options are listed");
+        assert_eq!(option.provider.as_deref(), Some("FileSearch"));
     }
 }
@@ -0,0 +1,334 @@
+/// Saves state before the app exits, within the little time Windows allows at session end
+///
+/// When the user signs out or shuts down, Windows sends `WM_ENDSESSION` and
+/// kills the process soon after it returns, so anything still being written
+/// is lost. `ShutdownCoordinator::flush` runs the registered flushers one at
+/// a time, most important first, each within its own budget and all within
+/// `SHUTDOWN_BUDGET`. Caches that can be rebuilt, such as icons and
+/// favicons, have no flusher. Session end, the tray's Exit item and the
+/// `quit_app` command all go through it.
+///
+/// A marker file is written once every flusher finished. A start that
+/// doesn't find it checks the SQLite stores right away instead of in the
+/// deferred startup tasks.
+
+use crate::error::Result;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+/// Time all flushers together may take
+///
+/// Windows gives an app about five seconds after `WM_ENDSESSION` before it
+/// is killed; the rest is left for the process to exit.
+pub const SHUTDOWN_BUDGET: Duration = Duration::from_secs(2);
+
+/// Why the app is exiting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// Windows is signing out or shutting down
+    SessionEnd,
+    /// The user quit from the tray or the `quit_app` command
+    Quit,
+    /// The app is exiting for another reason
+    Exit,
+}
+
+/// How one flusher did
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlushOutcome {
+    Flushed,
+    Failed(String),
+    /// Cut off at its budget
+    TimedOut,
+    /// Not started, as the overall budget was spent
+    Skipped,
+}
+
+/// What flushing before exit did, by flusher in the order they ran
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlushReport {
+    pub outcomes: Vec<(&'static str, FlushOutcome)>,
+}
+
+impl FlushReport {
+    /// Whether every flusher ran to the end, so no write was cut off
+    pub fn is_complete(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|(_, outcome)| matches!(outcome, FlushOutcome::Flushed | FlushOutcome::Failed(_)))
+    }
+}
+
+type FlushFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+type Flusher = Box<dyn Fn() -> FlushFuture + Send + Sync>;
+
+/// Runs every registered flusher once, when the app first starts exiting
+pub struct ShutdownCoordinator {
+    flushers: Mutex<Vec<(&'static str, Duration, Flusher)>>,
+    budget: Duration,
+    marker: CleanShutdownMarker,
+    /// Set by the first flush; later callers wait for it and get its report
+    report: OnceCell<FlushReport>,
+}
+
+impl ShutdownCoordinator {
+    /// Creates a coordinator without flushers, recording clean exits with `marker`
+    pub fn new(marker: CleanShutdownMarker) -> Self {
+        Self::with_budget(marker, SHUTDOWN_BUDGET)
+    }
+
+    /// Creates a coordinator whose flushers together get `budget`
+    pub fn with_budget(marker: CleanShutdownMarker, budget: Duration) -> Self {
+        Self {
+            flushers: Mutex::new(Vec::new()),
+            budget,
+            marker,
+            report: OnceCell::new(),
+        }
+    }
+
+    /// Adds a flusher given at most `budget`; flushers run in the order they
+    /// were added, so the most important come first
+    pub fn add_flusher<F, Fut>(&self, name: &'static str, budget: Duration, flusher: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let flusher: Flusher = Box::new(move || Box::pin(flusher()));
+        self.flushers.lock().unwrap_or_else(|e| e.into_inner()).push((name, budget, flusher));
+    }
+
+    /// Runs the flushers and marks the exit clean if none was cut off
+    ///
+    /// Only the first call flushes; calls made meanwhile or after wait for it
+    /// and return the same report.
+    pub async fn flush(&self, reason: ShutdownReason) -> FlushReport {
+        self.report.get_or_init(|| self.run(reason)).await.clone()
+    }
+
+    async fn run(&self, reason: ShutdownReason) -> FlushReport {
+        info!("Saving state before exit ({:?})", reason);
+        let deadline = Instant::now() + self.budget;
+
+        // Futures are created up front so the lock isn't held while they run
+        let tasks: Vec<_> = self
+            .flushers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(name, budget, flusher)| (*name, *budget, flusher()))
+            .collect();
+
+        let mut report = FlushReport::default();
+        for (name, budget, task) in tasks {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let outcome = if remaining.is_zero() {
+                FlushOutcome::Skipped
+            } else {
+                match tokio::time::timeout(budget.min(remaining), task).await {
+                    Ok(Ok(())) => FlushOutcome::Flushed,
+                    Ok(Err(e)) => FlushOutcome::Failed(e.to_string()),
+                    Err(_) => FlushOutcome::TimedOut,
+                }
+            };
+
+            match &outcome {
+                FlushOutcome::Flushed => debug!("Flushed {} before exit", name),
+                outcome => warn!("Flushing {} before exit: {:?}", name, outcome),
+            }
+            report.outcomes.push((name, outcome));
+        }
+
+        if report.is_complete() {
+            if let Err(e) = self.marker.mark_clean() {
+                warn!("Failed to record the clean exit: {}", e);
+            }
+        } else {
+            warn!("A flush was cut off, the stores will be checked on the next start");
+        }
+        report
+    }
+}
+
+/// A file recording that the app last exited after saving its state
+///
+/// It is taken away when the app starts and written again on a clean exit,
+/// so a start that doesn't find it follows a crash, a kill or a power loss.
+/// The very first start finds none either; its stores are new and quick to
+/// check.
+pub struct CleanShutdownMarker {
+    path: Option<PathBuf>,
+}
+
+impl CleanShutdownMarker {
+    /// Uses the marker under `%APPDATA%\BetterFinder`
+    pub fn load() -> Self {
+        match std::env::var_os("APPDATA") {
+            Some(app_data) => Self::at(&PathBuf::from(app_data).join("BetterFinder").join("clean_shutdown")),
+            None => {
+                warn!("APPDATA not found, clean exits can't be recorded");
+                Self { path: None }
+            }
+        }
+    }
+
+    /// Uses the marker at `path`
+    pub fn at(path: &Path) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Whether the last run exited cleanly, removing the marker so this run
+    /// counts as unclean until it exits cleanly too
+    ///
+    /// Without a place to keep the marker there are no stores to check either,
+    /// so every run counts as clean.
+    pub fn take_previous(&self) -> bool {
+        let Some(path) = &self.path else {
+            return true;
+        };
+
+        let clean = path.exists();
+        if clean {
+            if let Err(e) = std::fs::remove_file(path) {
+                warn!("Failed to remove {}: {}", path.display(), e);
+            }
+        }
+        clean
+    }
+
+    /// Records that this run is exiting cleanly
+    pub fn mark_clean(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, chrono::Utc::now().to_rfc3339())?;
+        Ok(())
+    }
+}
+
+/// Saves state and exits, from the tray's Exit item or the `quit_app` command
+pub fn quit(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let coordinator = app.try_state::<Arc<ShutdownCoordinator>>().map(|state| Arc::clone(&state));
+        if let Some(coordinator) = coordinator {
+            coordinator.flush(ShutdownReason::Quit).await;
+        }
+        app.exit(0);
+    });
+}
+
+/// Saves state from a thread outside the async runtime, returning once done
+///
+/// Used where the caller can't return before state is saved: the session end
+/// message and the final exit event.
+pub fn flush_blocking(coordinator: &ShutdownCoordinator, reason: ShutdownReason) -> FlushReport {
+    tauri::async_runtime::block_on(coordinator.flush(reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LauncherError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn temp_marker(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn test_slow_flusher_is_cut_off_while_others_complete() {
+        let path = temp_marker("shutdown_budget");
+        let coordinator = ShutdownCoordinator::with_budget(CleanShutdownMarker::at(&path), Duration::from_millis(500));
+        let finished = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&finished);
+        coordinator.add_flusher("clipboard_history", Duration::from_millis(200), move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+        coordinator.add_flusher("stuck", Duration::from_millis(100), || async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(())
+        });
+        let counter = Arc::clone(&finished);
+        coordinator.add_flusher("settings", Duration::from_millis(200), move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(LauncherError::SettingsError("read-only".to_string()))
+            }
+        });
+        coordinator.add_flusher("stuck_past_budget", Duration::from_secs(30), || async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(())
+        });
+        coordinator.add_flusher("never_started", Duration::from_millis(200), || async { Ok(()) });
+
+        let started = Instant::now();
+        let report = coordinator.flush(ShutdownReason::SessionEnd).await;
+        assert!(started.elapsed() < Duration::from_secs(2), "{:?}", started.elapsed());
+
+        let outcomes: Vec<_> = report.outcomes.iter().map(|(name, outcome)| (*name, outcome.clone())).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("clipboard_history", FlushOutcome::Flushed),
+                ("stuck", FlushOutcome::TimedOut),
+                ("settings", FlushOutcome::Failed("Settings error: read-only".to_string())),
+                ("stuck_past_budget", FlushOutcome::TimedOut),
+                ("never_started", FlushOutcome::Skipped),
+            ]
+        );
+        assert_eq!(finished.load(Ordering::SeqCst), 2);
+
+        // A write was cut off, so the exit isn't clean, and flushing again is a no-op
+        assert!(!path.exists());
+        assert_eq!(coordinator.flush(ShutdownReason::Exit).await, report);
+    }
+
+    #[tokio::test]
+    async fn test_clean_marker_across_runs() {
+        let path = temp_marker("clean_shutdown");
+        let flushed_run = || async {
+            let marker = CleanShutdownMarker::at(&path);
+            let previous_clean = marker.take_previous();
+            let coordinator = ShutdownCoordinator::new(marker);
+            coordinator.add_flusher("recent_files", Duration::from_millis(100), || async { Ok(()) });
+            assert!(coordinator.flush(ShutdownReason::Quit).await.is_complete());
+            previous_clean
+        };
+
+        // The first run finds no marker and leaves one
+        assert!(!flushed_run().await);
+        assert!(path.exists());
+
+        // The next finds it; killed before flushing, it leaves none
+        assert!(CleanShutdownMarker::at(&path).take_previous());
+        assert!(!path.exists());
+
+        // So the run after it counts the last exit as unclean, then exits cleanly
+        assert!(!flushed_run().await);
+        assert!(flushed_run().await);
+        let _ = std::fs::remove_file(&path);
+    }
+}
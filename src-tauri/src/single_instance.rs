@@ -0,0 +1,439 @@
+use crate::error::{LauncherError, Result};
+use crate::uri_scheme::{find_uri_arg, UriCommand};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+#[cfg(target_os = "windows")]
+use windows::core::HSTRING;
+#[cfg(target_os = "windows")]
+use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_ALREADY_EXISTS, HANDLE};
+
+/// Event emitted to the frontend when another launch activates this instance
+pub const ACTIVATION_EVENT: &str = "instance-activated";
+
+/// Largest activation message accepted from another instance
+const MAX_MESSAGE_BYTES: usize = 64 * 1024;
+
+/// Session-local mutex held by the running instance
+#[cfg(target_os = "windows")]
+const MUTEX_NAME: &str = "Local\\BetterFinder.SingleInstance";
+
+/// How often a second launch tries to reach the running instance
+#[cfg(target_os = "windows")]
+const FORWARD_ATTEMPTS: u32 = 10;
+
+/// Wait between attempts, covering a running instance that is still starting up
+#[cfg(target_os = "windows")]
+const FORWARD_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// Message a second launch sends to the running instance
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationMessage {
+    /// Query to pre-fill the search box with
+    #[serde(default)]
+    pub query: Option<String>,
+    /// `betterfinder://` link the launch was opened with
+    ///
+    /// Forwarded as is, since whether a linked file exists can only be
+    /// decided by the instance that opens it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+impl ActivationMessage {
+    /// Builds the message for this launch from its command line
+    ///
+    /// A `betterfinder://` argument makes this a link launch; invalid links
+    /// are logged and only bring up the launcher.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Self {
+        let args: Vec<String> = args.into_iter().collect();
+        let Some(uri) = find_uri_arg(args.iter().cloned()) else {
+            return Self {
+                query: parse_cli_query(args),
+                link: None,
+            };
+        };
+
+        match UriCommand::parse(&uri) {
+            Ok(_) => Self {
+                query: None,
+                link: Some(uri),
+            },
+            Err(e) => {
+                tracing::warn!("Ignoring launch link: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Serializes the message for sending to the running instance
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Parses a message received from another instance
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() > MAX_MESSAGE_BYTES {
+            return Err(LauncherError::SecurityError(format!(
+                "Activation message of {} bytes exceeds the {} byte limit",
+                bytes.len(),
+                MAX_MESSAGE_BYTES
+            )));
+        }
+
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// Extracts the initial query from the command line, e.g. `betterfinder.exe "search term"`
+///
+/// The first argument (the executable) and flags starting with `-` are
+/// skipped; everything after `--` is taken literally. Remaining arguments
+/// are joined with spaces.
+pub fn parse_cli_query<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    let mut words = Vec::new();
+    let mut literal = false;
+
+    for arg in args.into_iter().skip(1) {
+        if !literal {
+            if arg == "--" {
+                literal = true;
+                continue;
+            }
+            if arg.starts_with('-') {
+                continue;
+            }
+        }
+        words.push(arg);
+    }
+
+    let query = words.join(" ").trim().to_string();
+    (!query.is_empty()).then_some(query)
+}
+
+/// The query this instance was launched with, handed to the frontend once it loads
+pub struct PendingQuery(Mutex<Option<String>>);
+
+impl PendingQuery {
+    pub fn new(query: Option<String>) -> Self {
+        Self(Mutex::new(query))
+    }
+
+    /// Returns the query the first time it is asked for
+    pub fn take(&self) -> Option<String> {
+        self.0.lock().map(|mut query| query.take()).unwrap_or(None)
+    }
+}
+
+/// A channel other instances send activation messages through
+pub trait ActivationSource: Send {
+    /// Blocks until the next raw message arrives; `None` once the source is closed
+    fn next_message(&mut self) -> Option<Result<Vec<u8>>>;
+}
+
+/// Handles activation messages until `source` closes
+///
+/// Messages that fail to arrive or to parse are logged and skipped.
+pub fn run_activation_listener(
+    source: &mut dyn ActivationSource,
+    mut on_activate: impl FnMut(ActivationMessage),
+) {
+    while let Some(received) = source.next_message() {
+        match received.and_then(|bytes| ActivationMessage::from_bytes(&bytes)) {
+            Ok(message) => on_activate(message),
+            Err(e) => tracing::warn!("Ignoring activation message: {}", e),
+        }
+    }
+
+    tracing::debug!("Activation listener stopped");
+}
+
+/// Whether this launch should run or hand over to the running instance
+pub enum InstanceRole {
+    /// This is the only instance; keep the guard alive while the app runs
+    Primary(InstanceGuard),
+    /// Another instance was activated and this launch should exit
+    Secondary,
+}
+
+/// Holds the single-instance mutex until dropped
+pub struct InstanceGuard {
+    #[cfg(target_os = "windows")]
+    mutex: Option<HANDLE>,
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        use windows::Win32::System::Threading::ReleaseMutex;
+
+        if let Some(mutex) = self.mutex.take() {
+            unsafe {
+                let _ = ReleaseMutex(mutex);
+                let _ = CloseHandle(mutex);
+            }
+        }
+    }
+}
+
+/// Name of the pipe the running instance listens on, one per user
+#[cfg(target_os = "windows")]
+fn pipe_name() -> String {
+    let user = std::env::var("USERNAME").unwrap_or_else(|_| "default".to_string());
+    format!("\\\\.\\pipe\\BetterFinder.Activation.{}", user)
+}
+
+/// Becomes the running instance, or forwards `message` to the one already running
+///
+/// A mutex left behind by an instance that crashed is detected as abandoned
+/// and taken over.
+#[cfg(target_os = "windows")]
+pub fn acquire_or_forward(message: &ActivationMessage) -> InstanceRole {
+    use windows::Win32::Foundation::{WAIT_ABANDONED, WAIT_OBJECT_0};
+    use windows::Win32::System::Threading::{CreateMutexW, WaitForSingleObject};
+
+    let mutex = match unsafe { CreateMutexW(None, true, &HSTRING::from(MUTEX_NAME)) } {
+        Ok(mutex) => mutex,
+        Err(e) => {
+            tracing::warn!("Failed to create single-instance mutex, running anyway: {}", e);
+            return InstanceRole::Primary(InstanceGuard { mutex: None });
+        }
+    };
+
+    if unsafe { GetLastError() } != ERROR_ALREADY_EXISTS {
+        tracing::info!("Acquired single-instance mutex");
+        return InstanceRole::Primary(InstanceGuard { mutex: Some(mutex) });
+    }
+
+    tracing::info!("Another instance is running, forwarding activation");
+    match forward_activation(message) {
+        Ok(()) => {
+            unsafe {
+                let _ = CloseHandle(mutex);
+            }
+            return InstanceRole::Secondary;
+        }
+        Err(e) => tracing::warn!("Failed to reach the running instance: {}", e),
+    }
+
+    // Nobody answers: take over if the owner exited without releasing the mutex
+    let wait = unsafe { WaitForSingleObject(mutex, 0) };
+    if wait == WAIT_ABANDONED || wait == WAIT_OBJECT_0 {
+        tracing::warn!("Previous instance exited without releasing the mutex, taking over");
+        return InstanceRole::Primary(InstanceGuard { mutex: Some(mutex) });
+    }
+
+    tracing::error!("The running instance is not responding; exiting");
+    unsafe {
+        let _ = CloseHandle(mutex);
+    }
+    InstanceRole::Secondary
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn acquire_or_forward(_message: &ActivationMessage) -> InstanceRole {
+    InstanceRole::Primary(InstanceGuard {})
+}
+
+/// Sends `message` to the running instance's pipe, retrying while it starts up
+#[cfg(target_os = "windows")]
+fn forward_activation(message: &ActivationMessage) -> Result<()> {
+    use std::io::Write;
+
+    let bytes = message.to_bytes()?;
+    let mut last_error = None;
+
+    for _ in 0..FORWARD_ATTEMPTS {
+        match std::fs::OpenOptions::new().write(true).open(pipe_name()) {
+            Ok(mut pipe) => {
+                pipe.write_all(&bytes)?;
+                return Ok(());
+            }
+            Err(e) => {
+                last_error = Some(e);
+                std::thread::sleep(FORWARD_RETRY_DELAY);
+            }
+        }
+    }
+
+    Err(last_error
+        .map(LauncherError::from)
+        .unwrap_or_else(|| LauncherError::NotFound("Activation pipe".to_string())))
+}
+
+/// Receives activation messages over a named pipe, one client at a time
+#[cfg(target_os = "windows")]
+struct NamedPipeSource {
+    name: HSTRING,
+}
+
+#[cfg(target_os = "windows")]
+impl ActivationSource for NamedPipeSource {
+    fn next_message(&mut self) -> Option<Result<Vec<u8>>> {
+        use windows::Win32::Foundation::ERROR_PIPE_CONNECTED;
+        use windows::Win32::Storage::FileSystem::{ReadFile, PIPE_ACCESS_INBOUND};
+        use windows::Win32::System::Pipes::{
+            ConnectNamedPipe, CreateNamedPipeW, DisconnectNamedPipe, PIPE_READMODE_BYTE,
+            PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+        };
+
+        unsafe {
+            let pipe = CreateNamedPipeW(
+                &self.name,
+                PIPE_ACCESS_INBOUND,
+                PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+                PIPE_UNLIMITED_INSTANCES,
+                0,
+                4096,
+                0,
+                None,
+            );
+            if pipe.is_invalid() {
+                tracing::error!("Failed to create activation pipe: {:?}", GetLastError());
+                return None;
+            }
+
+            // A client connecting before this call is reported as ERROR_PIPE_CONNECTED
+            if let Err(e) = ConnectNamedPipe(pipe, None) {
+                if e.code() != ERROR_PIPE_CONNECTED.to_hresult() {
+                    let _ = CloseHandle(pipe);
+                    return Some(Err(LauncherError::ExecutionError(format!(
+                        "Failed to accept activation client: {}",
+                        e
+                    ))));
+                }
+            }
+
+            // Read until the client closes its end
+            let mut message = Vec::new();
+            let mut buffer = [0u8; 4096];
+            loop {
+                let mut read = 0u32;
+                if ReadFile(pipe, Some(&mut buffer), Some(&mut read), None).is_err() || read == 0 {
+                    break;
+                }
+                message.extend_from_slice(&buffer[..read as usize]);
+                if message.len() > MAX_MESSAGE_BYTES {
+                    break;
+                }
+            }
+
+            let _ = DisconnectNamedPipe(pipe);
+            let _ = CloseHandle(pipe);
+
+            Some(Ok(message))
+        }
+    }
+}
+
+/// Listens for activation messages from later launches on a background thread
+#[cfg(target_os = "windows")]
+pub fn start_activation_listener(on_activate: impl FnMut(ActivationMessage) + Send + 'static) {
+    let mut source = NamedPipeSource {
+        name: HSTRING::from(pipe_name()),
+    };
+
+    std::thread::spawn(move || run_activation_listener(&mut source, on_activate));
+    tracing::info!("Listening for activation from other launches");
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn start_activation_listener(_on_activate: impl FnMut(ActivationMessage) + Send + 'static) {
+    tracing::debug!("Single-instance activation is only supported on Windows");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        std::iter::once("betterfinder.exe")
+            .chain(list.iter().copied())
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_cli_query() {
+        assert_eq!(parse_cli_query(args(&[])), None);
+        assert_eq!(parse_cli_query(args(&["search term"])), Some("search term".to_string()));
+        assert_eq!(parse_cli_query(args(&["quarterly", "report"])), Some("quarterly report".to_string()));
+        assert_eq!(parse_cli_query(args(&["   "])), None);
+
+        // Flags are skipped; "--" lets a query start with a dash
+        assert_eq!(parse_cli_query(args(&["--minimized", "notes"])), Some("notes".to_string()));
+        assert_eq!(parse_cli_query(args(&["--minimized"])), None);
+        assert_eq!(parse_cli_query(args(&["--", "-5 + 3"])), Some("-5 + 3".to_string()));
+    }
+
+    #[test]
+    fn test_activation_message_round_trip() {
+        let message = ActivationMessage::from_args(args(&["café menu"]));
+        let bytes = message.to_bytes().unwrap();
+
+        assert_eq!(ActivationMessage::from_bytes(&bytes).unwrap(), message);
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            r#"{"query":"café menu"}"#
+        );
+
+        // Older or minimal senders may leave the query out
+        assert_eq!(ActivationMessage::from_bytes(b"{}").unwrap(), ActivationMessage::default());
+        assert!(ActivationMessage::from_bytes(b"activate").is_err());
+        assert!(ActivationMessage::from_bytes(&vec![b' '; MAX_MESSAGE_BYTES + 1]).is_err());
+    }
+
+    struct ScriptedSource(VecDeque<Result<Vec<u8>>>);
+
+    impl ActivationSource for ScriptedSource {
+        fn next_message(&mut self) -> Option<Result<Vec<u8>>> {
+            self.0.pop_front()
+        }
+    }
+
+    #[test]
+    fn test_listener_delivers_valid_messages_until_closed() {
+        let query = |q: &str| ActivationMessage {
+            query: Some(q.to_string()),
+            ..Default::default()
+        };
+        let mut source = ScriptedSource(VecDeque::from(vec![
+            Ok(query("first").to_bytes().unwrap()),
+            Err(LauncherError::ExecutionError("client vanished".to_string())),
+            Ok(b"not json".to_vec()),
+            Ok(ActivationMessage::default().to_bytes().unwrap()),
+            Ok(query("last").to_bytes().unwrap()),
+        ]));
+
+        let mut received = Vec::new();
+        run_activation_listener(&mut source, |message| received.push(message));
+
+        assert_eq!(received, vec![query("first"), ActivationMessage::default(), query("last")]);
+    }
+
+    #[test]
+    fn test_link_launch_forwards_the_link() {
+        let link = "betterfinder://search?query=foo&scope=C:%5CProjects";
+        let message = ActivationMessage::from_args(args(&["--query", "ignored", link]));
+        assert_eq!(message.link.as_deref(), Some(link));
+        assert_eq!(message.query, None);
+
+        let bytes = message.to_bytes().unwrap();
+        assert_eq!(ActivationMessage::from_bytes(&bytes).unwrap(), message);
+
+        // A bad link still activates the launcher, without a query
+        assert_eq!(
+            ActivationMessage::from_args(args(&["betterfinder://format?drive=C"])),
+            ActivationMessage::default()
+        );
+    }
+
+    #[test]
+    fn test_pending_query_is_taken_once() {
+        let pending = PendingQuery::new(Some("notes".to_string()));
+
+        assert_eq!(pending.take(), Some("notes".to_string()));
+        assert_eq!(pending.take(), None);
+    }
+}
@@ -0,0 +1,198 @@
+/// Starts the providers that need initializing side by side
+///
+/// Each start runs as its own task and registers its provider as soon as it
+/// is ready, so parsing bookmarks no longer holds up application search. At
+/// most `MAX_CONCURRENT_STARTS` run at once, to keep the file and browser
+/// scans from thrashing the disk. Steps that depend on each other, such as
+/// the file access tracker and the recent files provider it writes to, or
+/// Everything and the Windows Search fallback, belong to the same start.
+///
+/// Every finished start is reported with its duration, which the UI gets as
+/// `provider-registered`, and once the last one lands `providers-ready`
+/// lists them all.
+
+use crate::types::ProviderStartup;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+/// Starts running at the same time
+pub const MAX_CONCURRENT_STARTS: usize = 3;
+
+/// Event emitted with each `ProviderStartup` as it finishes
+pub const PROVIDER_REGISTERED_EVENT: &str = "provider-registered";
+
+/// Event emitted with every `ProviderStartup` once the last one finished
+pub const PROVIDERS_READY_EVENT: &str = "providers-ready";
+
+/// Progress of the provider starts
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartupEvent {
+    /// One start finished, registered or not
+    Registered(ProviderStartup),
+    /// Every start finished, listed in the order they did
+    Ready(Vec<ProviderStartup>),
+}
+
+/// A start, resolving to whether its provider was registered
+type StartFuture = Pin<Box<dyn Future<Output = bool> + Send>>;
+
+/// Provider starts to run together
+pub struct ProviderStartups {
+    starts: Vec<(String, StartFuture)>,
+    /// Starts running at the same time
+    limit: usize,
+}
+
+impl ProviderStartups {
+    /// Creates an empty set running at most `limit` starts at once
+    pub fn new(limit: usize) -> Self {
+        Self {
+            starts: Vec::new(),
+            limit: limit.max(1),
+        }
+    }
+
+    /// Adds the start of `provider`; starts get a slot in the order they're added
+    pub fn add(&mut self, provider: &str, start: impl Future<Output = bool> + Send + 'static) {
+        self.starts.push((provider.to_string(), Box::pin(start)));
+    }
+
+    /// Runs every start, reporting each as it finishes and all of them once
+    /// the last one did, and returns them in the order they finished
+    ///
+    /// A start's duration counts from when it got a slot, not from the wait
+    /// for one.
+    pub async fn run(self, mut on_event: impl FnMut(StartupEvent)) -> Vec<ProviderStartup> {
+        let slots = Arc::new(Semaphore::new(self.limit));
+        let mut running: FuturesUnordered<_> = self
+            .starts
+            .into_iter()
+            .map(|(provider, start)| {
+                let slots = Arc::clone(&slots);
+                let task = tokio::spawn(async move {
+                    // The semaphore is never closed, so a slot always comes
+                    let _slot = slots.acquire_owned().await;
+                    let started = Instant::now();
+                    let registered = start.await;
+                    (registered, started.elapsed())
+                });
+                async move {
+                    let (registered, duration) = task.await.unwrap_or_else(|e| {
+                        warn!("Starting {} was cut short: {}", provider, e);
+                        (false, Duration::ZERO)
+                    });
+                    ProviderStartup {
+                        provider,
+                        duration_ms: duration.as_millis() as u64,
+                        registered,
+                    }
+                }
+            })
+            .collect();
+
+        let mut startups = Vec::with_capacity(running.len());
+        while let Some(startup) = running.next().await {
+            info!("{} started in {}ms", startup.provider, startup.duration_ms);
+            on_event(StartupEvent::Registered(startup.clone()));
+            startups.push(startup);
+        }
+
+        on_event(StartupEvent::Ready(startups.clone()));
+        startups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// A start taking `millis` that logs when it begins and ends
+    fn timed_start(name: &'static str, millis: u64, log: &Arc<Mutex<Vec<String>>>) -> impl Future<Output = bool> + Send + 'static {
+        let log = Arc::clone(log);
+        async move {
+            log.lock().unwrap().push(format!("begin {}", name));
+            tokio::time::sleep(Duration::from_millis(millis)).await;
+            log.lock().unwrap().push(format!("end {}", name));
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_starts_overlap_and_report_in_finishing_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut startups = ProviderStartups::new(MAX_CONCURRENT_STARTS);
+        startups.add("Bookmarks", timed_start("Bookmarks", 300, &log));
+        startups.add("App Search", timed_start("App Search", 100, &log));
+        startups.add("Recent Files", timed_start("Recent Files", 200, &log));
+
+        let mut events = Vec::new();
+        let started = Instant::now();
+        let finished = startups.run(|event| events.push(event)).await;
+        let elapsed = started.elapsed();
+
+        // About the slowest start, well short of all three in a row
+        assert!(elapsed >= Duration::from_millis(300), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(550), "{:?}", elapsed);
+
+        let order: Vec<&str> = finished.iter().map(|s| s.provider.as_str()).collect();
+        assert_eq!(order, vec!["App Search", "Recent Files", "Bookmarks"]);
+        assert!(finished[2].duration_ms >= 300);
+        assert!(finished.iter().all(|s| s.registered));
+
+        // One event per start as it lands, then one for all of them
+        assert_eq!(events.len(), 4);
+        for (event, startup) in events.iter().zip(&finished) {
+            assert_eq!(event, &StartupEvent::Registered(startup.clone()));
+        }
+        assert_eq!(events[3], StartupEvent::Ready(finished));
+    }
+
+    #[tokio::test]
+    async fn test_limit_bounds_running_starts_and_keeps_steps_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let running = Arc::new(AtomicUsize::new(0));
+        let most = Arc::new(AtomicUsize::new(0));
+        let mut startups = ProviderStartups::new(2);
+        for name in ["a", "b", "c", "d", "e"] {
+            let running = Arc::clone(&running);
+            let most = Arc::clone(&most);
+            let start = timed_start(name, 50, &log);
+            startups.add(name, async move {
+                most.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+                start.await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                true
+            });
+        }
+        // Hooked up before the provider it writes to registers, as the file
+        // access tracker is, however long the hookup takes
+        let steps = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&steps);
+        startups.add("Recent Files", async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            recorded.lock().unwrap().push("tracker");
+            recorded.lock().unwrap().push("register");
+            false
+        });
+
+        let finished = startups.run(|_| {}).await;
+        assert_eq!(finished.len(), 6);
+        assert_eq!(most.load(Ordering::SeqCst), 2);
+        assert_eq!(*steps.lock().unwrap(), vec!["tracker", "register"]);
+        // A start that didn't register is still reported
+        let recent = finished.iter().find(|s| s.provider == "Recent Files").unwrap();
+        assert!(!recent.registered);
+
+        // Every start ran to the end exactly once
+        let log = log.lock().unwrap();
+        assert_eq!(log.len(), 10);
+        assert!(["a", "b", "c", "d", "e"].iter().all(|name| log.contains(&format!("end {}", name))));
+    }
+}
@@ -0,0 +1,323 @@
+/// Refreshes stale state after the system resumes from sleep or the session is unlocked
+///
+/// While the machine sleeps, apps get installed, bookmarks sync and the
+/// clipboard monitor or global hotkey can stop working, yet the periodic
+/// timers only notice much later. A hidden window on its own thread receives
+/// `WM_POWERBROADCAST` and session change messages and forwards them to
+/// `ResumeCoordinator::run`, which waits for the burst of related messages to
+/// settle and then calls `on_system_resumed` once.
+///
+/// The same window receives `WM_ENDSESSION` when Windows signs out or shuts
+/// down. The process may be killed as soon as that message returns, so the
+/// session end hook runs before it does.
+
+use crate::error::Result;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Quiet period after the last event before the refresh runs
+///
+/// Windows sends several messages for one resume, and the network is often
+/// not up yet when the first arrives.
+pub const RESUME_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// A system event after which caches may be stale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemEvent {
+    /// Resumed from sleep or hibernation
+    Resumed,
+    /// The user unlocked the session
+    Unlocked,
+}
+
+type RefreshHook = Box<dyn Fn() + Send + Sync>;
+
+/// Runs every registered refresh hook once per resume or unlock
+pub struct ResumeCoordinator {
+    hooks: Mutex<Vec<(&'static str, RefreshHook)>>,
+    debounce: Duration,
+}
+
+impl ResumeCoordinator {
+    /// Creates a coordinator without hooks
+    pub fn new() -> Self {
+        Self::with_debounce(RESUME_DEBOUNCE)
+    }
+
+    /// Creates a coordinator waiting `debounce` for events to settle
+    pub fn with_debounce(debounce: Duration) -> Self {
+        Self {
+            hooks: Mutex::new(Vec::new()),
+            debounce,
+        }
+    }
+
+    /// Adds a hook run after every resume; hooks must not block for long
+    pub fn add_hook<F>(&self, name: &'static str, hook: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.hooks.lock().unwrap_or_else(|e| e.into_inner()).push((name, Box::new(hook)));
+    }
+
+    /// Runs every hook in the order they were added, returning their names
+    pub fn on_system_resumed(&self) -> Vec<&'static str> {
+        let hooks = self.hooks.lock().unwrap_or_else(|e| e.into_inner());
+        info!("System resumed, running {} refresh hooks", hooks.len());
+
+        hooks
+            .iter()
+            .map(|(name, hook)| {
+                debug!("Running resume hook '{}'", name);
+                hook();
+                *name
+            })
+            .collect()
+    }
+
+    /// Turns bursts of events into single `on_system_resumed` calls
+    ///
+    /// After an event arrives, waits until none has arrived for the debounce
+    /// period. Returns when the sender is dropped.
+    pub fn run(&self, events: Receiver<SystemEvent>) {
+        while let Ok(first) = events.recv() {
+            debug!("System event: {:?}", first);
+            let disconnected = loop {
+                match events.recv_timeout(self.debounce) {
+                    Ok(event) => debug!("System event: {:?}", event),
+                    Err(RecvTimeoutError::Timeout) => break false,
+                    Err(RecvTimeoutError::Disconnected) => break true,
+                }
+            };
+
+            self.on_system_resumed();
+
+            if disconnected {
+                break;
+            }
+        }
+    }
+}
+
+impl Default for ResumeCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs when the session ends, on the thread receiving system events; the
+/// process may be killed once it returns
+type SessionEndHook = Box<dyn Fn() + Send>;
+
+/// Starts listening for resume and unlock events on background threads
+///
+/// `on_session_end` runs when Windows signs out or shuts down, and has to
+/// save whatever must survive before returning.
+pub fn watch_system_resume<F>(coordinator: Arc<ResumeCoordinator>, on_session_end: F) -> Result<()>
+where
+    F: Fn() + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::Builder::new()
+        .name("system-events".to_string())
+        .spawn(move || {
+            if let Err(e) = listen_for_system_events(sender, Box::new(on_session_end)) {
+                tracing::warn!("Stopped listening for resume events: {}", e);
+            }
+        })?;
+
+    std::thread::Builder::new()
+        .name("resume-coordinator".to_string())
+        .spawn(move || coordinator.run(receiver))?;
+
+    info!("Watching for system resume and session unlock");
+    Ok(())
+}
+
+#[cfg(windows)]
+thread_local! {
+    /// Where the window procedure forwards events; set on the listener thread
+    static EVENT_SENDER: std::cell::RefCell<Option<std::sync::mpsc::Sender<SystemEvent>>> =
+        const { std::cell::RefCell::new(None) };
+
+    /// What the window procedure runs on session end; set on the listener thread
+    static SESSION_END_HOOK: std::cell::RefCell<Option<SessionEndHook>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Creates a hidden window receiving power and session messages, then pumps
+/// its messages until the window is destroyed
+///
+/// A top-level window is used because message-only windows don't receive
+/// broadcasts such as `WM_POWERBROADCAST`.
+#[cfg(windows)]
+fn listen_for_system_events(sender: std::sync::mpsc::Sender<SystemEvent>, on_session_end: SessionEndHook) -> Result<()> {
+    use crate::error::LauncherError;
+    use windows::core::w;
+    use windows::Win32::Foundation::{HINSTANCE, HWND};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::System::RemoteDesktop::{
+        WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, HMENU, MSG,
+        WINDOW_EX_STYLE, WNDCLASSW, WS_OVERLAPPED,
+    };
+
+    EVENT_SENDER.with(|slot| *slot.borrow_mut() = Some(sender));
+    SESSION_END_HOOK.with(|slot| *slot.borrow_mut() = Some(on_session_end));
+
+    unsafe {
+        let instance: HINSTANCE = GetModuleHandleW(None)
+            .map_err(|e| LauncherError::WindowError(format!("Failed to get module handle: {}", e)))?
+            .into();
+        let class_name = w!("BetterFinderSystemEvents");
+
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(system_event_proc),
+            hInstance: instance,
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+        if RegisterClassW(&class) == 0 {
+            return Err(LauncherError::WindowError(
+                "Failed to register the system event window class".to_string(),
+            ));
+        }
+
+        // Never shown; it only exists to receive broadcasts
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            class_name,
+            w!("Better Finder system events"),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            HWND::default(),
+            HMENU::default(),
+            instance,
+            None,
+        )
+        .map_err(|e| LauncherError::WindowError(format!("Failed to create system event window: {}", e)))?;
+
+        // Unlock notifications are optional; resume still works without them
+        if let Err(e) = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) {
+            tracing::warn!("Failed to register for session notifications: {}", e);
+        }
+
+        let mut message = MSG::default();
+        while GetMessageW(&mut message, HWND::default(), 0, 0).as_bool() {
+            let _ = TranslateMessage(&message);
+            DispatchMessageW(&message);
+        }
+
+        let _ = WTSUnRegisterSessionNotification(hwnd);
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn system_event_proc(
+    hwnd: windows::Win32::Foundation::HWND,
+    message: u32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::Foundation::LRESULT;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DefWindowProcW, PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, WM_ENDSESSION, WM_POWERBROADCAST,
+        WM_WTSSESSION_CHANGE, WTS_SESSION_UNLOCK,
+    };
+
+    // A zero wParam means the session end was cancelled and the app keeps running
+    if message == WM_ENDSESSION && wparam.0 != 0 {
+        SESSION_END_HOOK.with(|slot| {
+            if let Some(hook) = slot.borrow().as_ref() {
+                hook();
+            }
+        });
+        return LRESULT(0);
+    }
+
+    let event = match (message, wparam.0 as u32) {
+        (WM_POWERBROADCAST, PBT_APMRESUMEAUTOMATIC | PBT_APMRESUMESUSPEND) => Some(SystemEvent::Resumed),
+        (WM_WTSSESSION_CHANGE, WTS_SESSION_UNLOCK) => Some(SystemEvent::Unlocked),
+        _ => None,
+    };
+
+    if let Some(event) = event {
+        EVENT_SENDER.with(|slot| {
+            if let Some(sender) = slot.borrow().as_ref() {
+                let _ = sender.send(event);
+            }
+        });
+    }
+
+    DefWindowProcW(hwnd, message, wparam, lparam)
+}
+
+#[cfg(not(windows))]
+fn listen_for_system_events(_sender: std::sync::mpsc::Sender<SystemEvent>, _on_session_end: SessionEndHook) -> Result<()> {
+    debug!("Resume and unlock events are only supported on Windows");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    /// Coordinator whose hooks record each run into a shared log
+    fn recording_coordinator(names: &[&'static str]) -> (Arc<ResumeCoordinator>, Arc<Mutex<Vec<&'static str>>>) {
+        let coordinator = Arc::new(ResumeCoordinator::with_debounce(Duration::from_millis(30)));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        for &name in names {
+            let log = Arc::clone(&log);
+            coordinator.add_hook(name, move || log.lock().unwrap().push(name));
+        }
+        (coordinator, log)
+    }
+
+    #[test]
+    fn test_every_hook_runs_once_per_resume() {
+        let (coordinator, log) =
+            recording_coordinator(&["clipboard_monitor", "providers", "global_hotkey"]);
+
+        assert_eq!(
+            coordinator.on_system_resumed(),
+            vec!["clipboard_monitor", "providers", "global_hotkey"]
+        );
+        assert_eq!(*log.lock().unwrap(), vec!["clipboard_monitor", "providers", "global_hotkey"]);
+    }
+
+    #[test]
+    fn test_bursts_of_events_refresh_once() {
+        let (coordinator, log) = recording_coordinator(&["providers"]);
+        let (sender, receiver) = mpsc::channel();
+        let runner = {
+            let coordinator = Arc::clone(&coordinator);
+            std::thread::spawn(move || coordinator.run(receiver))
+        };
+
+        // Resume sends several power messages, then the user unlocks
+        sender.send(SystemEvent::Resumed).unwrap();
+        sender.send(SystemEvent::Resumed).unwrap();
+        sender.send(SystemEvent::Unlocked).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(log.lock().unwrap().len(), 1);
+
+        // A later unlock is a separate refresh
+        sender.send(SystemEvent::Unlocked).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(log.lock().unwrap().len(), 2);
+
+        drop(sender);
+        runner.join().unwrap();
+        assert_eq!(log.lock().unwrap().len(), 2);
+    }
+}
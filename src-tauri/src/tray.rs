@@ -1,10 +1,20 @@
 use tauri::{
     AppHandle, Manager,
     tray::{TrayIconBuilder, TrayIconEvent, MouseButton, MouseButtonState},
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{CheckMenuItemBuilder, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
     image::Image,
 };
+use crate::drag_drop::DragDrop;
 use crate::error::LauncherError;
+use crate::search::providers::ActiveProfiles;
+use std::sync::Arc;
+
+/// Id of the tray icon, for rebuilding its menu
+const TRAY_ID: &str = "main";
+
+/// Prefix of the profile menu items, followed by the profile name; the bare
+/// prefix is the "Default settings" item
+const PROFILE_ITEM_PREFIX: &str = "profile:";
 
 /// Initialize the system tray icon and menu
 pub fn init_tray(app: &AppHandle) -> Result<(), LauncherError> {
@@ -17,7 +27,7 @@ pub fn init_tray(app: &AppHandle) -> Result<(), LauncherError> {
     let menu = build_tray_menu(app)?;
 
     // Create the tray icon
-    let _tray = TrayIconBuilder::new()
+    let _tray = TrayIconBuilder::with_id(TRAY_ID)
         .icon(icon)
         .menu(&menu)
         .tooltip("Global Search Launcher")
@@ -50,7 +60,23 @@ fn load_tray_icon() -> Result<Image<'static>, LauncherError> {
     Ok(Image::new_owned(rgba_data, width, height))
 }
 
-/// Build the tray menu with Open Settings, About, and Exit options
+/// Rebuilds the tray menu, e.g. after the profiles changed
+pub fn refresh_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                tracing::warn!("Failed to update tray menu: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
+/// Build the tray menu with Open Settings, About, and Exit options, plus a
+/// Profile submenu when profiles are configured
 fn build_tray_menu(app: &AppHandle) -> Result<tauri::menu::Menu<tauri::Wry>, LauncherError> {
     let open_settings = MenuItemBuilder::with_id("open_settings", "Open Settings")
         .build(app)
@@ -67,15 +93,52 @@ fn build_tray_menu(app: &AppHandle) -> Result<tauri::menu::Menu<tauri::Wry>, Lau
         .build(app)
         .map_err(|e| LauncherError::TrayError(format!("Failed to create menu item: {}", e)))?;
 
-    MenuBuilder::new(app)
-        .item(&open_settings)
-        .item(&about)
+    let mut menu = MenuBuilder::new(app).item(&open_settings);
+    if let Some(profiles) = build_profile_menu(app)? {
+        menu = menu.item(&profiles);
+    }
+    menu.item(&about)
         .item(&separator)
         .item(&exit)
         .build()
         .map_err(|e| LauncherError::TrayError(format!("Failed to build menu: {}", e)))
 }
 
+/// Builds the Profile submenu with the active profile checked, or `None`
+/// without profiles
+fn build_profile_menu(app: &AppHandle) -> Result<Option<tauri::menu::Submenu<tauri::Wry>>, LauncherError> {
+    let profiles = app
+        .try_state::<ActiveProfiles>()
+        .map(|profiles| profiles.get())
+        .unwrap_or_default();
+    if profiles.names.is_empty() {
+        return Ok(None);
+    }
+
+    let item = |name: Option<&String>| {
+        let id = format!("{}{}", PROFILE_ITEM_PREFIX, name.map_or("", |name| name.as_str()));
+        let text = name.map_or("Default settings", |name| name.as_str());
+        let checked = match (name, &profiles.active) {
+            (Some(name), Some(active)) => name.eq_ignore_ascii_case(active),
+            (None, None) => true,
+            _ => false,
+        };
+        CheckMenuItemBuilder::with_id(id, text)
+            .checked(checked)
+            .build(app)
+            .map_err(|e| LauncherError::TrayError(format!("Failed to create menu item: {}", e)))
+    };
+
+    let mut submenu = SubmenuBuilder::new(app, "Profile").item(&item(None)?).separator();
+    for name in &profiles.names {
+        submenu = submenu.item(&item(Some(name))?);
+    }
+    submenu
+        .build()
+        .map(Some)
+        .map_err(|e| LauncherError::TrayError(format!("Failed to build profile menu: {}", e)))
+}
+
 /// Handle tray menu item clicks
 fn handle_menu_event(app: &AppHandle, menu_id: &str) {
     tracing::info!("Tray menu item clicked: {}", menu_id);
@@ -93,7 +156,19 @@ fn handle_menu_event(app: &AppHandle, menu_id: &str) {
         }
         "exit" => {
             tracing::info!("Exit menu item clicked, quitting application");
-            app.exit(0);
+            crate::shutdown::quit(app);
+        }
+        _ if menu_id.starts_with(PROFILE_ITEM_PREFIX) => {
+            let name = Some(&menu_id[PROFILE_ITEM_PREFIX.len()..]).filter(|name| !name.is_empty());
+            let name = name.map(str::to_string);
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = crate::activate_profile(&app, name.as_deref()).await {
+                    tracing::error!("Failed to switch profile: {}", e);
+                    // Restore the check marks the click toggled
+                    refresh_menu(&app);
+                }
+            });
         }
         _ => {
             tracing::warn!("Unknown menu item clicked: {}", menu_id);
@@ -124,21 +199,20 @@ fn handle_tray_event(tray: &tauri::tray::TrayIcon, event: TrayIconEvent) {
 fn toggle_main_window(app: &AppHandle) -> Result<(), LauncherError> {
     if let Some(window) = app.get_webview_window("main") {
         if window.is_visible().unwrap_or(false) {
+            // Hiding the window would cancel a drag out of it
+            let dragging = app
+                .try_state::<Arc<DragDrop>>()
+                .is_some_and(|drag_drop| drag_drop.is_dragging());
+            if dragging {
+                return Ok(());
+            }
             window
                 .hide()
                 .map_err(|e| LauncherError::WindowError(format!("Failed to hide window: {}", e)))?;
             tracing::info!("Main window hidden");
+            crate::window_show::prepare_next_show(app, &window);
         } else {
-            window
-                .show()
-                .map_err(|e| LauncherError::WindowError(format!("Failed to show window: {}", e)))?;
-            window
-                .set_focus()
-                .map_err(|e| LauncherError::WindowError(format!("Failed to focus window: {}", e)))?;
-            window
-                .center()
-                .map_err(|e| LauncherError::WindowError(format!("Failed to center window: {}", e)))?;
-            tracing::info!("Main window shown and centered");
+            crate::window_show::show_main_window(app)?;
         }
         Ok(())
     } else {
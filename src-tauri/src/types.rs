@@ -1,6 +1,20 @@
+use crate::search::recent_boost::RecentExecution;
+use crate::utils::normalize_for_search;
+use crate::utils::process::LaunchedProcess;
+use crate::utils::show_latency::ShowLatencyStats;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
+/// Metadata flag marking results whose provider order must survive ranking
+pub const PRESERVE_PROVIDER_ORDER_KEY: &str = "preserve_provider_order";
+
+/// Metadata flag marking results whose execution must not hide the window
+pub const KEEP_WINDOW_OPEN_KEY: &str = "keep_window_open";
+
+/// Metadata flag marking results the user confirms before they run, e.g. shutdown
+pub const REQUIRES_CONFIRMATION_KEY: &str = "requires_confirmation";
+
 /// Represents a search result from any provider
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -21,20 +35,434 @@ pub struct SearchResult {
     pub metadata: HashMap<String, serde_json::Value>,
     /// Action to execute when result is selected
     pub action: ResultAction,
+    /// Precomputed normalized title for matching; never sent to the frontend
+    #[serde(skip)]
+    pub(crate) normalized_title: Option<String>,
+    /// Whether details such as the icon are still missing; the provider's
+    /// `enrich` fills them in once the frontend asks for the result
+    #[serde(default)]
+    pub enrichment_pending: bool,
+    /// Opaque token proving the engine produced this id and action; the
+    /// frontend must send it back unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) integrity: Option<String>,
+}
+
+impl SearchResult {
+    /// Returns the lowercase, diacritic-folded title used for matching
+    pub fn normalized_title(&self) -> Cow<'_, str> {
+        match &self.normalized_title {
+            Some(normalized) => Cow::Borrowed(normalized),
+            None => Cow::Owned(normalize_for_search(&self.title)),
+        }
+    }
+
+    /// Returns the file path a File or RecentFile result points at
+    pub fn file_path(&self) -> Option<&str> {
+        match &self.action {
+            ResultAction::OpenFile { path } => Some(path.as_str()),
+            _ => self.metadata.get("path").and_then(|v| v.as_str()),
+        }
+    }
+
+    /// Returns true if the result was pinned by the user
+    pub fn is_pinned(&self) -> bool {
+        self.metadata
+            .get("pinned")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if ranking must keep the provider's score and order,
+    /// e.g. for a file listing sorted by date
+    pub fn preserves_provider_order(&self) -> bool {
+        self.metadata
+            .get(PRESERVE_PROVIDER_ORDER_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the window stays open after the result executes,
+    /// e.g. for a result opening the Settings view inside it
+    pub fn keeps_window_open(&self) -> bool {
+        self.metadata
+            .get(KEEP_WINDOW_OPEN_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the user confirms the result before it runs
+    pub fn requires_confirmation(&self) -> bool {
+        self.metadata
+            .get(REQUIRES_CONFIRMATION_KEY)
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
 }
 
 /// Types of search results
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ResultType {
     File,
     Application,
     QuickAction,
     Calculator,
+    DateTime,
     Clipboard,
     Bookmark,
+    History,
     RecentFile,
     WebSearch,
+    Ssh,
+    /// A project recently opened in a developer tool
+    Project,
+    Suggestion,
+    Generator,
+    /// Figures about text the user gave, e.g. its word count, or the text
+    /// converted, e.g. to uppercase or base64
+    Text,
+    /// A file's checksum, or whether it matches an expected one
+    Hash,
+    Url,
+    /// A query saved under a name, run again when executed
+    SavedSearch,
+    /// Several file results from one folder, collapsed into one row
+    FileGroup,
+}
+
+/// How a file was used through the launcher
+///
+/// Ordered from strongest to weakest: opening a file means working on it,
+/// while revealing it or copying its path only passes it on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionKind {
+    Opened,
+    Revealed,
+    Copied,
+}
+
+impl InteractionKind {
+    /// Name stored in the recent files database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Opened => "opened",
+            Self::Revealed => "revealed",
+            Self::Copied => "copied",
+        }
+    }
+
+    /// Parses a stored name; unknown names count as opened, like rows
+    /// written before kinds were recorded
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "revealed" => Self::Revealed,
+            "copied" => Self::Copied,
+            _ => Self::Opened,
+        }
+    }
+
+    /// What one interaction of this kind adds to a file's frecency
+    pub fn weight(&self) -> f64 {
+        match self {
+            Self::Opened => 1.0,
+            Self::Revealed => 0.3,
+            Self::Copied => 0.1,
+        }
+    }
+}
+
+/// A file used through a result, reported to the file access tracker
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileAccess {
+    pub path: String,
+    pub kind: InteractionKind,
+    /// Type of the result the file was used through
+    pub result_type: ResultType,
+}
+
+/// Payload of the `provider-progress` event, emitted during long provider scans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderProgress {
+    /// Name of the provider doing the work
+    pub provider: String,
+    /// Steps finished so far
+    pub completed: usize,
+    /// Total number of steps
+    pub total: usize,
+    /// Completion percentage (0-100)
+    pub percent: u8,
+}
+
+impl ProviderProgress {
+    /// Creates a progress update, computing the percentage
+    pub fn new(provider: &str, completed: usize, total: usize) -> Self {
+        let percent = (completed.min(total) * 100).checked_div(total).unwrap_or(100) as u8;
+
+        Self {
+            provider: provider.to_string(),
+            completed,
+            total,
+            percent,
+        }
+    }
+}
+
+/// Health of a search provider, as reported by `get_provider_health`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ProviderHealth {
+    /// Answering searches
+    Ready,
+    /// Answering searches from an index that may miss new files
+    Stale { reason: String },
+    /// Answering searches, but some of its data failed to load
+    Degraded { reason: String },
+    /// Loading its data in the background; searches return nothing meanwhile
+    Initializing,
+    /// Lazy provider waiting for its first relevant query
+    NotInitialized,
+    /// Initialization failed; the provider is skipped
+    Failed { error: String },
+    /// Turned off or shut down
+    Disabled,
+}
+
+impl std::fmt::Display for ProviderHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderHealth::Ready => write!(f, "ready"),
+            ProviderHealth::Stale { reason } => write!(f, "ready, but the index may be out of date: {}", reason),
+            ProviderHealth::Degraded { reason } => write!(f, "ready, with problems: {}", reason),
+            ProviderHealth::Initializing => write!(f, "initializing"),
+            ProviderHealth::NotInitialized => write!(f, "not yet initialized (lazy)"),
+            ProviderHealth::Failed { error } => write!(f, "failed: {}", error),
+            ProviderHealth::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+/// Health of one registered provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderStatus {
+    pub provider: String,
+    pub health: ProviderHealth,
+    /// Human-readable form of `health`
+    pub message: String,
+    /// Peeks the provider was dropped from for missing the peek budget
+    #[serde(default)]
+    pub slow_peeks: u32,
+}
+
+/// Size of one in-memory cache, as reported by `get_diagnostics`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub name: String,
+    pub entries: usize,
+    /// Approximate bytes held, exact for cached strings and blobs
+    pub bytes: usize,
+    /// Bytes the cache evicts down to, if it has a budget
+    pub byte_budget: Option<usize>,
+}
+
+/// Memory snapshot returned by `get_diagnostics`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// Process working set, when the platform reports it
+    pub working_set_bytes: Option<u64>,
+    pub result_cache: CacheStats,
+    /// Share of result cache lookups answered from the cache since startup
+    pub result_cache_hit_rate: Option<f64>,
+    /// Provider caches such as application icons and favicons
+    pub caches: Vec<CacheStats>,
+    /// Processes recently started by executing results, oldest first
+    pub processes: Vec<LaunchedProcess>,
+    /// Time from the hotkey to a focused search box
+    pub show_latency: ShowLatencyStats,
+    /// Results executed in the last few minutes, newest first
+    pub recent_executions: Vec<RecentExecution>,
+    /// How long each provider took to start, in the order they finished
+    #[serde(default)]
+    pub provider_startup: Vec<ProviderStartup>,
+}
+
+/// How long a provider took to start, as reported by `get_diagnostics` and
+/// the `provider-registered` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderStartup {
+    pub provider: String,
+    pub duration_ms: u64,
+    /// Whether it was registered; false when it failed or was skipped
+    pub registered: bool,
+}
+
+/// One section requested from `search_sections`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchSection {
+    /// Result types shown in this section; empty matches every type
+    pub types: Vec<ResultType>,
+    /// Maximum number of results in this section
+    pub limit: usize,
+}
+
+impl SearchSection {
+    /// Returns true if results of this type belong in the section
+    pub fn accepts(&self, result_type: ResultType) -> bool {
+        self.types.is_empty() || self.types.contains(&result_type)
+    }
+}
+
+/// Response of `search_query`: the first page of a search's results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResponse {
+    /// Id for fetching later pages with `get_results_page`; `None` when
+    /// `results` already holds every result
+    pub search_id: Option<String>,
+    /// Number of results the search found, of the active facets when narrowed
+    pub total: usize,
+    pub results: Vec<SearchResult>,
+    /// What the search found per type and provider; `None` when `results` is empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facets: Option<ResultFacets>,
+    /// Number shortcuts of the first results, for the frontend to show as is
+    #[serde(default)]
+    pub slots: Vec<ResultSlot>,
+}
+
+/// Result launched by Alt and a number
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultSlot {
+    /// 1 to 9
+    pub slot: u8,
+    pub result_id: String,
+}
+
+/// Result counts of a search, for filter chips such as "Files 12 · Apps 3"
+///
+/// Counts cover every result the search found, whichever facets are active.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultFacets {
+    /// In the order each type first appears in the ranked results
+    pub types: Vec<TypeFacet>,
+    /// In the order each provider first appears in the ranked results
+    pub providers: Vec<ProviderFacet>,
+}
+
+/// Results of one type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeFacet {
+    #[serde(rename = "type")]
+    pub result_type: ResultType,
+    pub count: usize,
+    /// Results of the type on the returned page
+    pub shown: usize,
+    /// Whether results of the type were left off the returned page
+    pub truncated: bool,
+}
+
+/// Results one provider returned
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderFacet {
+    pub provider: String,
+    pub count: usize,
+}
+
+/// A slice of a search's results returned by `get_results_page`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultPage {
+    /// Index of the first result in the page
+    pub offset: usize,
+    /// Number of results the search found, of the requested types when narrowed
+    pub total: usize,
+    pub results: Vec<SearchResult>,
+}
+
+/// Stage of a result execution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ExecutionState {
+    Started,
+    /// Long actions, such as hashing a large file, report how far they got
+    Progress { done: u64, total: u64 },
+    Succeeded,
+    Failed { error: String },
+    Cancelled,
+}
+
+/// Payload of the `execution-status` event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionStatus {
+    /// ID returned by `execute_result`
+    pub execution_id: String,
+    /// ID of the executed result
+    pub result_id: String,
+    /// Current stage of the execution
+    #[serde(flatten)]
+    pub state: ExecutionState,
+}
+
+/// Entry of the `results-enriched` event's payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichedResult {
+    /// Search the result belongs to, as returned by `search_query`
+    pub search_id: String,
+    /// The result with its details filled in
+    pub result: SearchResult,
+}
+
+/// An execution started by `SearchEngine::execute_result`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    /// ID used by `execution-status` events and `cancel_execution`
+    pub id: String,
+    /// Whether the same result was executed moments ago, so nothing new
+    /// started and `id` is that execution's
+    pub duplicate: bool,
+}
+
+/// Returned by `execute_result` before the action completes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionTicket {
+    /// ID used by `execution-status` events and `cancel_execution`
+    pub execution_id: String,
+    /// Whether the window hides once the action succeeds
+    pub hide_on_success: bool,
+    /// Whether this repeated an execution moments ago and was ignored; the
+    /// frontend leaves the earlier one to report
+    pub duplicate: bool,
+}
+
+/// Action applied to every result of a multi-selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchAction {
+    /// Opens each file with its default application
+    Open,
+    /// Copies the paths to the clipboard, one per line
+    CopyPaths,
+    /// Shows each file selected in Explorer
+    RevealInExplorer,
+}
+
+/// A result of a batch that could not be processed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchFailure {
+    /// ID of the failed result
+    pub id: String,
+    /// Why it failed
+    pub error: String,
+}
+
+/// Returned by `execute_results_batch` once every result was processed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    /// IDs of the results that succeeded, in batch order
+    pub succeeded: Vec<String>,
+    /// Results that failed, in batch order
+    pub failed: Vec<BatchFailure>,
+    /// IDs of the results skipped because the same action ran on them moments ago
+    pub suppressed: Vec<String>,
 }
 
 /// Action to perform when a result is executed
@@ -47,4 +475,8 @@ pub enum ResultAction {
     CopyToClipboard { content: String },
     OpenUrl { url: String },
     WebSearch { query: String },
+    /// Replaces the search box query, e.g. with a spelling correction
+    SetQuery { query: String },
+    /// Shows the members of a file group in place
+    ExpandGroup { group_id: String },
 }
@@ -1,94 +1,514 @@
-use tauri::{AppHandle, Emitter};
-use tauri_plugin_updater::UpdaterExt;
+use crate::error::Result;
+use crate::utils::sound::{SoundEvent, SoundPlayer};
+use crate::utils::write_json_export;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
 use tracing::{error, info, warn};
 
+/// A fetched manifest is reused for this many hours before checking again
+const MANIFEST_FRESH_HOURS: i64 = 6;
+
+/// Event sent once after the app starts on a newer version than it last ran
+pub const WHATS_NEW_EVENT: &str = "show-whats-new";
+
+/// The release an update check offered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateManifest {
+    pub version: String,
+    /// Release notes as Markdown
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+}
+
+impl UpdateManifest {
+    fn from_update(update: &Update) -> Self {
+        Self {
+            version: update.version.clone(),
+            notes: update.body.clone(),
+            pub_date: update.raw_json.get("pub_date").and_then(|date| date.as_str()).map(str::to_string),
+        }
+    }
+}
+
+/// What the last successful update check found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedCheck {
+    /// `None` when the app was up to date
+    pub manifest: Option<UpdateManifest>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl CachedCheck {
+    /// Whether the check is too old to answer for the server
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        now.signed_duration_since(self.fetched_at) >= chrono::Duration::hours(MANIFEST_FRESH_HOURS)
+    }
+}
+
+/// Update state kept in `%APPDATA%\BetterFinder\updates.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateCache {
+    pub last_check: Option<CachedCheck>,
+    /// Version the app ran as last time, to notice it was updated
+    pub last_run_version: Option<String>,
+}
+
+/// Serializes reading and writing the cache file
+static CACHE_LOCK: Mutex<()> = Mutex::new(());
+
+impl UpdateCache {
+    fn path() -> Option<PathBuf> {
+        let app_data = std::env::var_os("APPDATA")?;
+        Some(PathBuf::from(app_data).join("BetterFinder").join("updates.json"))
+    }
+
+    /// Reads the cache at `path`, starting over if it is missing or unreadable
+    pub fn load_from(path: &Path) -> Self {
+        match std::fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable update cache {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        write_json_export(path, self)
+    }
+
+    /// Reads the cache, changes it with `change` and writes it back
+    fn modify<T>(change: impl FnOnce(&mut Self) -> T) -> T {
+        let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(path) = Self::path() else {
+            warn!("APPDATA not found, update checks are not cached");
+            return change(&mut Self::default());
+        };
+
+        let mut cache = Self::load_from(&path);
+        let outcome = change(&mut cache);
+        if let Err(e) = cache.save_to(&path) {
+            warn!("Failed to save update cache {}: {}", path.display(), e);
+        }
+        outcome
+    }
+
+    fn load() -> Self {
+        let _guard = CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self::path().map(|path| Self::load_from(&path)).unwrap_or_default()
+    }
+
+    /// Records `current` as the running version
+    ///
+    /// Returns what's new when the app last ran an older version. Nothing is
+    /// returned on the first run, when there is no earlier version to compare.
+    pub fn record_run(&mut self, current: &str) -> Option<WhatsNew> {
+        let previous = self.last_run_version.replace(current.to_string())?;
+        if !is_newer(current, &previous) {
+            return None;
+        }
+
+        // The check that offered this version cached its notes
+        let notes = self
+            .last_check
+            .as_ref()
+            .and_then(|check| check.manifest.as_ref())
+            .filter(|manifest| manifest.version == current)
+            .and_then(|manifest| manifest.notes.as_deref())
+            .map(parse_release_notes)
+            .unwrap_or_default();
+
+        Some(WhatsNew {
+            previous_version: previous,
+            current_version: current.to_string(),
+            notes,
+        })
+    }
+}
+
+/// Payload of the `show-whats-new` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WhatsNew {
+    pub previous_version: String,
+    pub current_version: String,
+    /// Bullet points of the current version's notes; empty if they weren't cached
+    pub notes: Vec<String>,
+}
+
+/// Returned by `get_update_info`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    /// The newer release on offer, if any
+    pub update: Option<UpdateManifest>,
+    /// Bullet points of the update's notes, for "What's new"
+    pub notes: Vec<String>,
+    /// When the server was last asked; `None` if it never answered
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// Whether the info is old, or the server couldn't be reached for newer info
+    pub stale: bool,
+}
+
+impl UpdateInfo {
+    /// Describes what `check` found for an app running `current_version`
+    ///
+    /// A cached release that isn't newer, such as the one just installed, is
+    /// no longer on offer.
+    pub fn from_check(current_version: &str, check: Option<&CachedCheck>, now: DateTime<Utc>, fetch_failed: bool) -> Self {
+        let update = check
+            .and_then(|check| check.manifest.clone())
+            .filter(|manifest| is_newer(&manifest.version, current_version));
+        let notes = update
+            .as_ref()
+            .and_then(|manifest| manifest.notes.as_deref())
+            .map(parse_release_notes)
+            .unwrap_or_default();
+
+        Self {
+            current_version: current_version.to_string(),
+            update,
+            notes,
+            fetched_at: check.map(|check| check.fetched_at),
+            stale: fetch_failed || check.is_none_or(|check| check.is_stale(now)),
+        }
+    }
+}
+
+/// Splits Markdown release notes into one entry per bullet point
+///
+/// `-`, `*`, `+` and numbered items start an entry, and indented lines
+/// continue the one before. Headings are dropped. Notes without any bullet
+/// points become one entry per paragraph instead.
+pub fn parse_release_notes(markdown: &str) -> Vec<String> {
+    let mut bullets: Vec<String> = Vec::new();
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut in_bullet = false;
+    let mut in_paragraph = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            in_bullet = false;
+            in_paragraph = false;
+            continue;
+        }
+
+        if let Some(item) = bullet_text(trimmed) {
+            bullets.push(item.to_string());
+            in_bullet = true;
+            continue;
+        }
+
+        let indented = line.starts_with([' ', '\t']);
+        match bullets.last_mut() {
+            Some(last) if in_bullet && indented => {
+                last.push(' ');
+                last.push_str(trimmed);
+            }
+            _ => {
+                in_bullet = false;
+                match paragraphs.last_mut() {
+                    Some(last) if in_paragraph => {
+                        last.push(' ');
+                        last.push_str(trimmed);
+                    }
+                    _ => paragraphs.push(trimmed.to_string()),
+                }
+                in_paragraph = true;
+            }
+        }
+    }
+
+    if bullets.is_empty() {
+        paragraphs
+    } else {
+        bullets
+    }
+}
+
+/// The text of a bullet or numbered list line, without its marker
+fn bullet_text(line: &str) -> Option<&str> {
+    if let Some(item) = line.strip_prefix(['-', '*', '+']).and_then(|rest| rest.strip_prefix(' ')) {
+        return Some(item.trim());
+    }
+
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return None;
+    }
+    line[digits..]
+        .strip_prefix(['.', ')'])
+        .and_then(|rest| rest.strip_prefix(' '))
+        .map(str::trim)
+}
+
+/// Whether `version` is newer than `than`
+///
+/// Compares the dot-separated numbers; pre-release suffixes are ignored.
+/// Versions that aren't numbers count as newer whenever they differ.
+fn is_newer(version: &str, than: &str) -> bool {
+    let numbers = |version: &str| -> Option<Vec<u64>> {
+        let version = version.trim().trim_start_matches('v');
+        let release = version.split(['-', '+']).next().unwrap_or(version);
+        release.split('.').map(|part| part.parse().ok()).collect()
+    };
+
+    match (numbers(version), numbers(than)) {
+        (Some(version), Some(than)) => version > than,
+        _ => version != than,
+    }
+}
+
+/// Asks the update server for the latest release and caches its answer
+async fn fetch_update(app: &AppHandle) -> std::result::Result<(CachedCheck, Option<Update>), String> {
+    let updater = app.updater().map_err(|e| format!("Updater not available: {}", e))?;
+    let update = updater
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))?;
+
+    let check = CachedCheck {
+        manifest: update.as_ref().map(UpdateManifest::from_update),
+        fetched_at: Utc::now(),
+    };
+    UpdateCache::modify(|cache| cache.last_check = Some(check.clone()));
+    Ok((check, update))
+}
+
 /// Check for updates and prompt user if available
 pub async fn check_for_updates(app: AppHandle) {
     info!("Checking for application updates...");
-    
-    match app.updater() {
-        Ok(updater) => {
-            match updater.check().await {
-                Ok(Some(update)) => {
-                    info!(
-                        "Update available: {} -> {}",
-                        update.current_version,
-                        update.version
-                    );
-                    
-                    // Show update notification to user
-                    if let Err(e) = app.emit("update-available", &update.version) {
-                        error!("Failed to emit update-available event: {}", e);
-                    }
-                    
-                    // Download and install the update
-                    match update.download_and_install(|chunk_length, content_length| {
-                        if let Some(total) = content_length {
-                            let progress = (chunk_length as f64 / total as f64) * 100.0;
-                            info!("Download progress: {:.2}%", progress);
-                        }
-                    }, || {
-                        info!("Download complete, installing update...");
-                    }).await {
-                        Ok(_) => {
-                            info!("Update installed successfully");
-                            // Notify user that update is ready
-                            if let Err(e) = app.emit("update-installed", ()) {
-                                error!("Failed to emit update-installed event: {}", e);
-                            }
-                        }
-                        Err(e) => {
-                            error!("Failed to download and install update: {}", e);
-                            if let Err(e) = app.emit("update-error", e.to_string()) {
-                                error!("Failed to emit update-error event: {}", e);
-                            }
-                        }
-                    }
+
+    match fetch_update(&app).await {
+        Ok((_, Some(update))) => {
+            info!(
+                "Update available: {} -> {}",
+                update.current_version,
+                update.version
+            );
+
+            // Show update notification to user
+            if let Err(e) = app.emit("update-available", &update.version) {
+                error!("Failed to emit update-available event: {}", e);
+            }
+            if let Some(sound_player) = app.try_state::<Arc<SoundPlayer>>() {
+                sound_player.play(SoundEvent::UpdateAvailable);
+            }
+
+            // Download and install the update
+            match update.download_and_install(|chunk_length, content_length| {
+                if let Some(total) = content_length {
+                    let progress = (chunk_length as f64 / total as f64) * 100.0;
+                    info!("Download progress: {:.2}%", progress);
                 }
-                Ok(None) => {
-                    info!("No updates available");
+            }, || {
+                info!("Download complete, installing update...");
+            }).await {
+                Ok(_) => {
+                    info!("Update installed successfully");
+                    // Notify user that update is ready
+                    if let Err(e) = app.emit("update-installed", ()) {
+                        error!("Failed to emit update-installed event: {}", e);
+                    }
                 }
                 Err(e) => {
-                    warn!("Failed to check for updates: {}", e);
-                    // Don't emit error for update check failures - fail silently
+                    error!("Failed to download and install update: {}", e);
+                    if let Err(e) = app.emit("update-error", e.to_string()) {
+                        error!("Failed to emit update-error event: {}", e);
+                    }
                 }
             }
         }
+        Ok((_, None)) => {
+            info!("No updates available");
+        }
         Err(e) => {
-            warn!("Updater not available: {}", e);
+            // Don't emit error for update check failures - fail silently
+            warn!("{}", e);
         }
     }
 }
 
 /// Initialize updater and check for updates on startup
+///
+/// Also records the running version, announcing what's new once when it is
+/// newer than the version that ran last.
 pub fn init_updater(app: AppHandle) {
+    let current_version = app.package_info().version.to_string();
+    let whats_new = UpdateCache::modify(|cache| cache.record_run(&current_version));
+
     // Check for updates 5 seconds after startup to avoid blocking
     tokio::spawn(async move {
         tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+        // By now the frontend is listening
+        if let Some(whats_new) = whats_new {
+            info!("Updated from {} to {}", whats_new.previous_version, whats_new.current_version);
+            if let Err(e) = app.emit(WHATS_NEW_EVENT, &whats_new) {
+                error!("Failed to emit {} event: {}", WHATS_NEW_EVENT, e);
+            }
+        }
+
         check_for_updates(app).await;
     });
 }
 
 #[tauri::command]
-pub async fn check_for_updates_manual(app: AppHandle) -> Result<String, String> {
+pub async fn check_for_updates_manual(app: AppHandle) -> std::result::Result<String, String> {
     info!("Manual update check requested");
-    
-    match app.updater() {
-        Ok(updater) => {
-            match updater.check().await {
-                Ok(Some(update)) => {
+
+    match fetch_update(&app).await {
+        Ok((_, Some(update))) => Ok(format!("Update available: {}", update.version)),
+        Ok((_, None)) => Ok("No updates available".to_string()),
+        Err(e) => {
+            // A flaky connection reuses the last answer
+            let current_version = app.package_info().version.to_string();
+            let info = UpdateInfo::from_check(&current_version, UpdateCache::load().last_check.as_ref(), Utc::now(), true);
+            match (info.update, info.fetched_at) {
+                (Some(update), Some(fetched_at)) => {
+                    warn!("{}, using the check from {}", e, fetched_at);
                     Ok(format!("Update available: {}", update.version))
                 }
-                Ok(None) => {
-                    Ok("No updates available".to_string())
-                }
-                Err(e) => {
-                    Err(format!("Failed to check for updates: {}", e))
-                }
+                _ => Err(e),
             }
         }
+    }
+}
+
+/// Tauri command returning the cached update check
+///
+/// Asks the server again only once the cached answer is stale; if that
+/// fails the cached answer is returned marked stale.
+#[tauri::command]
+pub async fn get_update_info(app: AppHandle) -> std::result::Result<UpdateInfo, String> {
+    let current_version = app.package_info().version.to_string();
+    let cached = UpdateCache::load().last_check;
+    if cached.as_ref().is_some_and(|check| !check.is_stale(Utc::now())) {
+        return Ok(UpdateInfo::from_check(&current_version, cached.as_ref(), Utc::now(), false));
+    }
+
+    match fetch_update(&app).await {
+        Ok((check, _)) => Ok(UpdateInfo::from_check(&current_version, Some(&check), Utc::now(), false)),
         Err(e) => {
-            Err(format!("Updater not available: {}", e))
+            warn!("{}, using the cached update info", e);
+            Ok(UpdateInfo::from_check(&current_version, cached.as_ref(), Utc::now(), true))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(version: &str, notes: &str) -> UpdateManifest {
+        UpdateManifest {
+            version: version.to_string(),
+            notes: Some(notes.to_string()),
+            pub_date: Some("2026-10-01T12:00:00Z".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_cached_check_goes_stale() {
+        let now = Utc::now();
+        let check = CachedCheck {
+            manifest: Some(manifest("1.3.0", "- Faster search")),
+            fetched_at: now - chrono::Duration::hours(1),
+        };
+        assert!(!check.is_stale(now));
+        assert!(check.is_stale(now + chrono::Duration::hours(MANIFEST_FRESH_HOURS)));
+
+        let fresh = UpdateInfo::from_check("1.2.0", Some(&check), now, false);
+        assert!(!fresh.stale);
+        assert_eq!(fresh.update.as_ref().map(|m| m.version.as_str()), Some("1.3.0"));
+        assert_eq!(fresh.notes, vec!["Faster search".to_string()]);
+
+        // A failed fetch falls back to the cache, marked stale
+        let offline = UpdateInfo::from_check("1.2.0", Some(&check), now, true);
+        assert!(offline.stale);
+        assert_eq!(offline.update, fresh.update);
+
+        // Never checked
+        let unknown = UpdateInfo::from_check("1.2.0", None, now, false);
+        assert!(unknown.stale && unknown.update.is_none() && unknown.fetched_at.is_none());
+
+        // The cached release was installed since
+        assert!(UpdateInfo::from_check("1.3.0", Some(&check), now, false).update.is_none());
+    }
+
+    #[test]
+    fn test_release_notes_split_into_bullets() {
+        let notes = "## What's new\n\
+                     \n\
+                     - Search the launcher's settings\n\
+                     * Macros with an argument,\n  e.g. `proj website`\n\
+                     + **Faster** hotkey\n\
+                     \n\
+                     ### Fixes\n\
+                     1. Recent files no longer count reveals as opens\n\
+                     2) Tray icon tooltip\n\
+                       - Nested item\n";
+        assert_eq!(
+            parse_release_notes(notes),
+            vec![
+                "Search the launcher's settings",
+                "Macros with an argument, e.g. `proj website`",
+                "**Faster** hotkey",
+                "Recent files no longer count reveals as opens",
+                "Tray icon tooltip",
+                "Nested item",
+            ]
+        );
+
+        // Prose without bullets keeps its paragraphs
+        assert_eq!(
+            parse_release_notes("# 1.3.0\nBug fixes and\nperformance work.\n\nThanks!"),
+            vec!["Bug fixes and performance work.", "Thanks!"]
+        );
+        assert!(parse_release_notes("").is_empty());
+        assert_eq!(parse_release_notes("-not a bullet"), vec!["-not a bullet"]);
+    }
+
+    #[test]
+    fn test_version_transition_recorded_once() {
+        let mut cache = UpdateCache::default();
+
+        // First run: nothing to compare with
+        assert_eq!(cache.record_run("1.2.0"), None);
+        assert_eq!(cache.last_run_version.as_deref(), Some("1.2.0"));
+        assert_eq!(cache.record_run("1.2.0"), None);
+
+        cache.last_check = Some(CachedCheck {
+            manifest: Some(manifest("1.10.0", "- Query macros\n- Faster hotkey")),
+            fetched_at: Utc::now(),
+        });
+        let whats_new = cache.record_run("1.10.0").unwrap();
+        assert_eq!(whats_new.previous_version, "1.2.0");
+        assert_eq!(whats_new.current_version, "1.10.0");
+        assert_eq!(whats_new.notes, vec!["Query macros", "Faster hotkey"]);
+
+        // Announced once, and never for a downgrade
+        assert_eq!(cache.record_run("1.10.0"), None);
+        assert_eq!(cache.record_run("1.9.0"), None);
+
+        // Survives a restart through the file
+        let path = std::env::temp_dir().join(format!("bf_update_cache_{}.json", std::process::id()));
+        cache.save_to(&path).unwrap();
+        let mut reloaded = UpdateCache::load_from(&path);
+        assert_eq!(reloaded, cache);
+        let whats_new = reloaded.record_run("2.0.0-beta.1").unwrap();
+        assert_eq!(whats_new.previous_version, "1.9.0");
+        assert!(whats_new.notes.is_empty());
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(UpdateCache::load_from(&path), UpdateCache::default());
+    }
+}
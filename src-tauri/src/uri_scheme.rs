@@ -0,0 +1,610 @@
+use crate::error::{LauncherError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Scheme registered for links such as `betterfinder://search?query=notes`
+pub const URI_SCHEME: &str = "betterfinder";
+
+/// Event emitted to the frontend when a link or Explorer verb opens the launcher
+pub const OPEN_WITH_CONTEXT_EVENT: &str = "open-with-context";
+
+/// Longest URI accepted, well above any path Explorer passes
+const MAX_URI_LENGTH: usize = 4096;
+
+/// Query prefix listing clipboard history
+const CLIPBOARD_PREFIX: &str = "clip:";
+
+/// Key of the scheme under `HKEY_CURRENT_USER`
+const PROTOCOL_KEY: &str = r"Software\Classes\betterfinder";
+
+/// Explorer verbs: on the background of an open folder (`%V` is that folder)
+/// and on a folder itself (`%1` is the folder)
+const CONTEXT_MENU_VERBS: [(&str, &str); 2] = [
+    (r"Software\Classes\Directory\Background\shell\BetterFinder", "%V"),
+    (r"Software\Classes\Directory\shell\BetterFinder", "%1"),
+];
+
+/// What the launcher should show after being opened through a link
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchContext {
+    /// Text to put in the search box, including the scope
+    pub query: String,
+    /// Folder the search is limited to
+    pub scope: Option<String>,
+    /// Result to select once it shows up, for links to commands
+    #[serde(default)]
+    pub select_result_id: Option<String>,
+}
+
+/// A parsed `betterfinder://` link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriCommand {
+    /// `betterfinder://search?query=...&scope=...`; both parameters are optional
+    Search { query: Option<String>, scope: Option<String> },
+    /// `betterfinder://clip` opens clipboard history
+    Clipboard,
+    /// `betterfinder://open?path=...` opens a file or folder
+    Open { path: String },
+    /// `betterfinder://url?url=...` opens a web page
+    Url { url: String },
+    /// `betterfinder://app?name=...` finds an application by name
+    App { name: String },
+    /// `betterfinder://action?id=...&title=...&command=...` finds a command
+    /// result; the command is only shown, never run from the link
+    Action { id: String, title: Option<String>, command: Option<String> },
+}
+
+impl UriCommand {
+    /// Parses a link, rejecting unknown hosts and parameters, repeated or
+    /// missing parameters, malformed percent escapes and non-UTF-8 text
+    ///
+    /// Explorer substitutes folder paths into the verb's link without
+    /// encoding them, so unreserved characters such as spaces and backslashes
+    /// are taken literally. Folders whose names contain `%`, `&` or `#` are
+    /// therefore rejected when passed that way.
+    pub fn parse(uri: &str) -> Result<Self> {
+        if uri.len() > MAX_URI_LENGTH {
+            return Err(invalid(format!("link is longer than {} bytes", MAX_URI_LENGTH)));
+        }
+        if uri.contains('#') {
+            return Err(invalid("links can't contain '#'".to_string()));
+        }
+
+        let rest = uri
+            .split_once("://")
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(URI_SCHEME))
+            .map(|(_, rest)| rest)
+            .ok_or_else(|| invalid(format!("not a {}:// link", URI_SCHEME)))?;
+
+        let (location, params) = rest.split_once('?').unwrap_or((rest, ""));
+        let host = location.strip_suffix('/').unwrap_or(location).to_ascii_lowercase();
+        let mut params = Params::parse(params)?;
+
+        let command = match host.as_str() {
+            "search" => {
+                let query = params.take("query")?.map(|q| q.trim().to_string()).filter(|q| !q.is_empty());
+                let scope = params.take("scope")?;
+                if let Some(scope) = &scope {
+                    validate_path("scope", scope)?;
+                }
+                UriCommand::Search { query, scope }
+            }
+            "clip" => UriCommand::Clipboard,
+            "open" => {
+                let path = params.require("path")?;
+                validate_path("path", &path)?;
+                UriCommand::Open { path }
+            }
+            "url" => UriCommand::Url { url: params.require("url")? },
+            "app" => UriCommand::App { name: params.require("name")? },
+            "action" => UriCommand::Action {
+                id: params.require("id")?,
+                title: params.take("title")?,
+                command: params.take("command")?,
+            },
+            _ => return Err(invalid(format!("unknown link target '{}'", host))),
+        };
+
+        params.finish(&host)?;
+        Ok(command)
+    }
+
+    /// Builds the link opening a search, encoding both parts
+    pub fn search_uri(query: Option<&str>, scope: Option<&str>) -> String {
+        UriCommand::Search {
+            query: query.map(str::to_string),
+            scope: scope.map(str::to_string),
+        }
+        .to_uri()
+    }
+
+    /// Builds the link for this command, encoding every parameter
+    pub fn to_uri(&self) -> String {
+        let (host, params): (&str, Vec<(&str, Option<&str>)>) = match self {
+            UriCommand::Search { query, scope } => {
+                ("search", vec![("query", query.as_deref()), ("scope", scope.as_deref())])
+            }
+            UriCommand::Clipboard => ("clip", Vec::new()),
+            UriCommand::Open { path } => ("open", vec![("path", Some(path.as_str()))]),
+            UriCommand::Url { url } => ("url", vec![("url", Some(url.as_str()))]),
+            UriCommand::App { name } => ("app", vec![("name", Some(name.as_str()))]),
+            UriCommand::Action { id, title, command } => (
+                "action",
+                vec![("id", Some(id.as_str())), ("title", title.as_deref()), ("command", command.as_deref())],
+            ),
+        };
+
+        let params: Vec<String> = params
+            .into_iter()
+            .filter_map(|(key, value)| value.map(|value| format!("{}={}", key, urlencoding::encode(value))))
+            .collect();
+
+        if params.is_empty() {
+            format!("{}://{}", URI_SCHEME, host)
+        } else {
+            format!("{}://{}?{}", URI_SCHEME, host, params.join("&"))
+        }
+    }
+
+    /// What the launcher shows for this link
+    ///
+    /// Links that open something show the query that finds it instead, with
+    /// the linked result preselected when its id is known.
+    pub fn context(&self) -> LaunchContext {
+        let query = |query: &str| LaunchContext {
+            query: query.to_string(),
+            scope: None,
+            select_result_id: None,
+        };
+
+        match self {
+            UriCommand::Search { query, scope } => LaunchContext {
+                query: scoped_query(scope.as_deref(), query.as_deref().unwrap_or("")),
+                scope: scope.clone(),
+                select_result_id: None,
+            },
+            UriCommand::Clipboard => query(CLIPBOARD_PREFIX),
+            UriCommand::Open { path } => query(path),
+            UriCommand::Url { url } => query(url),
+            UriCommand::App { name } => query(name),
+            UriCommand::Action { id, title, command } => LaunchContext {
+                select_result_id: Some(id.clone()),
+                ..query(title.as_deref().or(command.as_deref()).unwrap_or(""))
+            },
+        }
+    }
+}
+
+fn invalid(reason: String) -> LauncherError {
+    LauncherError::SecurityError(format!("Invalid {}:// link: {}", URI_SCHEME, reason))
+}
+
+/// Decoded `key=value` parameters of a link, taken out one by one
+struct Params(Vec<(String, String)>);
+
+impl Params {
+    /// Splits `key=value&...`, decoding both sides
+    fn parse(params: &str) -> Result<Self> {
+        params
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair
+                    .split_once('=')
+                    .ok_or_else(|| invalid(format!("parameter '{}' has no value", pair)))?;
+                Ok((percent_decode(key)?, percent_decode(value)?))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Params)
+    }
+
+    /// Removes and returns a parameter, failing if it appears more than once
+    fn take(&mut self, key: &str) -> Result<Option<String>> {
+        let mut values: Vec<String> = Vec::new();
+        self.0.retain(|(k, v)| {
+            let matches = k == key;
+            if matches {
+                values.push(v.clone());
+            }
+            !matches
+        });
+
+        if values.len() > 1 {
+            return Err(invalid(format!("parameter '{}' is repeated", key)));
+        }
+        Ok(values.pop())
+    }
+
+    /// Removes and returns a parameter that must be present and non-empty
+    fn require(&mut self, key: &str) -> Result<String> {
+        self.take(key)?
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| invalid(format!("parameter '{}' is missing", key)))
+    }
+
+    /// Fails if any parameter was not taken
+    fn finish(self, host: &str) -> Result<()> {
+        match self.0.first() {
+            Some((key, _)) => Err(invalid(format!("unknown {} parameter '{}'", host, key))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Decodes `%XX` escapes, rejecting incomplete escapes, invalid UTF-8 and
+/// control characters
+///
+/// `+` is kept as is: links are built with `%20`, and Explorer passes paths
+/// that may contain a literal `+`.
+fn percent_decode(text: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let escape = tail
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| invalid(format!("malformed escape in '{}'", text)))?;
+            bytes.push(escape);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    let decoded = String::from_utf8(bytes).map_err(|_| invalid(format!("'{}' is not UTF-8", text)))?;
+    if decoded.chars().any(char::is_control) {
+        return Err(invalid("control characters are not allowed".to_string()));
+    }
+    Ok(decoded)
+}
+
+/// Accepts absolute drive paths (`C:\Projects`) and UNC paths (`\\server\share`)
+fn validate_path(name: &str, path: &str) -> Result<()> {
+    let bytes = path.as_bytes();
+    let is_drive_path = bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b":\\";
+    let is_unc_path = path.starts_with(r"\\")
+        && !path.starts_with(r"\\?\")
+        && !path.starts_with(r"\\.\")
+        && path[2..].split('\\').take(2).filter(|part| !part.is_empty()).count() == 2;
+
+    if !is_drive_path && !is_unc_path {
+        return Err(invalid(format!("{} '{}' is not an absolute path", name, path)));
+    }
+    if path.split('\\').any(|part| part == "..") {
+        return Err(invalid(format!("{} '{}' contains '..'", name, path)));
+    }
+    if path.contains('"') {
+        return Err(invalid(format!("{} '{}' contains a quote", name, path)));
+    }
+    Ok(())
+}
+
+/// Limits a query to a folder with Everything's path syntax: a term ending
+/// in `\` only matches files below that folder
+pub fn scoped_query(scope: Option<&str>, query: &str) -> String {
+    let Some(scope) = scope else {
+        return query.to_string();
+    };
+
+    let folder = if scope.ends_with('\\') {
+        scope.to_string()
+    } else {
+        format!("{}\\", scope)
+    };
+    let folder = if folder.contains(' ') {
+        format!("\"{}\"", folder)
+    } else {
+        folder
+    };
+
+    format!("{} {}", folder, query).trim_end().to_string()
+}
+
+/// Finds a `betterfinder://` link among the launch arguments
+pub fn find_uri_arg<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    let prefix = format!("{}:", URI_SCHEME);
+    args.into_iter()
+        .skip(1)
+        .find(|arg| arg.get(..prefix.len()).is_some_and(|start| start.eq_ignore_ascii_case(&prefix)))
+}
+
+/// A string value to write under `HKEY_CURRENT_USER`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryValue {
+    pub key: String,
+    /// Value name; empty for the key's default value
+    pub name: &'static str,
+    pub data: String,
+}
+
+impl RegistryValue {
+    fn new(key: &str, name: &'static str, data: impl Into<String>) -> Self {
+        Self {
+            key: key.to_string(),
+            name,
+            data: data.into(),
+        }
+    }
+}
+
+/// Values registering the `betterfinder://` scheme for `exe`
+pub fn protocol_registry_values(exe: &Path) -> Vec<RegistryValue> {
+    let exe = exe.display();
+    vec![
+        RegistryValue::new(PROTOCOL_KEY, "", "URL:Better Finder"),
+        RegistryValue::new(PROTOCOL_KEY, "URL Protocol", ""),
+        RegistryValue::new(&format!(r"{}\DefaultIcon", PROTOCOL_KEY), "", format!("\"{}\",0", exe)),
+        RegistryValue::new(
+            &format!(r"{}\shell\open\command", PROTOCOL_KEY),
+            "",
+            format!("\"{}\" \"%1\"", exe),
+        ),
+    ]
+}
+
+/// Values adding "Search in Better Finder" to Explorer's folder menus
+pub fn context_menu_registry_values(exe: &Path) -> Vec<RegistryValue> {
+    let exe_display = exe.display();
+    CONTEXT_MENU_VERBS
+        .iter()
+        .flat_map(|(key, placeholder)| {
+            [
+                RegistryValue::new(key, "", "Search in Better Finder"),
+                RegistryValue::new(key, "Icon", format!("\"{}\",0", exe_display)),
+                RegistryValue::new(
+                    &format!(r"{}\command", key),
+                    "",
+                    format!("\"{}\" \"{}://search?scope={}\"", exe_display, URI_SCHEME, placeholder),
+                ),
+            ]
+        })
+        .collect()
+}
+
+fn current_exe() -> Result<std::path::PathBuf> {
+    std::env::current_exe()
+        .map_err(|e| LauncherError::SettingsError(format!("Failed to get executable path: {}", e)))
+}
+
+/// Registers the `betterfinder://` scheme for the running executable
+///
+/// Runs on every start so the command follows the app when it moves.
+pub fn register_uri_scheme() -> Result<()> {
+    write_registry_values(&protocol_registry_values(&current_exe()?))?;
+    tracing::info!("Registered the {}:// scheme", URI_SCHEME);
+    Ok(())
+}
+
+/// Adds "Search in Better Finder" to Explorer's folder context menus
+pub fn install_context_menu() -> Result<()> {
+    let exe = current_exe()?;
+    write_registry_values(&protocol_registry_values(&exe))?;
+    write_registry_values(&context_menu_registry_values(&exe))?;
+    tracing::info!("Installed the Explorer context menu entry");
+    Ok(())
+}
+
+/// Removes the Explorer context menu entries; the scheme stays registered
+pub fn uninstall_context_menu() -> Result<()> {
+    for (key, _) in CONTEXT_MENU_VERBS {
+        delete_registry_tree(key)?;
+    }
+    tracing::info!("Removed the Explorer context menu entry");
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn write_registry_values(values: &[RegistryValue]) -> Result<()> {
+    use windows::core::HSTRING;
+    use windows::Win32::System::Registry::{
+        RegCloseKey, RegCreateKeyExW, RegSetValueExW, HKEY, HKEY_CURRENT_USER, KEY_WRITE,
+        REG_OPTION_NON_VOLATILE, REG_SZ,
+    };
+
+    for value in values {
+        unsafe {
+            let mut hkey = HKEY::default();
+            let result = RegCreateKeyExW(
+                HKEY_CURRENT_USER,
+                &HSTRING::from(value.key.as_str()),
+                0,
+                None,
+                REG_OPTION_NON_VOLATILE,
+                KEY_WRITE,
+                None,
+                &mut hkey,
+                None,
+            );
+            if result.is_err() {
+                return Err(LauncherError::SettingsError(format!(
+                    "Failed to create registry key {}: {:?}",
+                    value.key, result.0
+                )));
+            }
+
+            let data: Vec<u16> = value.data.encode_utf16().chain(std::iter::once(0)).collect();
+            let result = RegSetValueExW(
+                hkey,
+                &HSTRING::from(value.name),
+                0,
+                REG_SZ,
+                Some(data.as_slice().align_to::<u8>().1),
+            );
+            RegCloseKey(hkey).ok();
+
+            if result.is_err() {
+                return Err(LauncherError::SettingsError(format!(
+                    "Failed to set registry value {}\\{}: {:?}",
+                    value.key, value.name, result.0
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn delete_registry_tree(key: &str) -> Result<()> {
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::ERROR_FILE_NOT_FOUND;
+    use windows::Win32::System::Registry::{RegDeleteTreeW, HKEY_CURRENT_USER};
+
+    let result = unsafe { RegDeleteTreeW(HKEY_CURRENT_USER, &HSTRING::from(key)) };
+    // Already removed is fine
+    if result.is_err() && result != ERROR_FILE_NOT_FOUND {
+        return Err(LauncherError::SettingsError(format!(
+            "Failed to delete registry key {}: {:?}",
+            key, result.0
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write_registry_values(_values: &[RegistryValue]) -> Result<()> {
+    Err(LauncherError::SettingsError(
+        "Link and context menu registration is only supported on Windows".to_string(),
+    ))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn delete_registry_tree(_key: &str) -> Result<()> {
+    Err(LauncherError::SettingsError(
+        "Link and context menu registration is only supported on Windows".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(query: Option<&str>, scope: Option<&str>) -> UriCommand {
+        UriCommand::Search {
+            query: query.map(str::to_string),
+            scope: scope.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_links() {
+        assert_eq!(
+            UriCommand::parse("betterfinder://search?query=foo&scope=C:%5CProjects").unwrap(),
+            search(Some("foo"), Some(r"C:\Projects"))
+        );
+        assert_eq!(UriCommand::parse("BetterFinder://Search/?query=a%20b").unwrap(), search(Some("a b"), None));
+        assert_eq!(UriCommand::parse("betterfinder://search").unwrap(), search(None, None));
+        assert_eq!(UriCommand::parse("betterfinder://clip").unwrap(), UriCommand::Clipboard);
+        assert_eq!(UriCommand::parse("betterfinder://clip/").unwrap(), UriCommand::Clipboard);
+
+        // Explorer passes folder paths without encoding them
+        assert_eq!(
+            UriCommand::parse(r"betterfinder://search?scope=C:\My Projects\a+b").unwrap(),
+            search(None, Some(r"C:\My Projects\a+b"))
+        );
+        assert_eq!(
+            UriCommand::parse(r"betterfinder://search?scope=\\nas\share\docs").unwrap(),
+            search(None, Some(r"\\nas\share\docs"))
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_malformed_links() {
+        for uri in [
+            "https://search?query=foo",
+            "betterfinder:search?query=foo",
+            "betterfinder://settings",
+            "betterfinder://clip?query=x",
+            "betterfinder://search?q=foo",
+            "betterfinder://search?query=a&query=b",
+            "betterfinder://search?query",
+            "betterfinder://search?query=100%",
+            "betterfinder://search?query=%zz",
+            "betterfinder://search?query=%C3",
+            "betterfinder://search?query=a%0Ab",
+            "betterfinder://search?query=a#frag",
+            "betterfinder://search?scope=Projects",
+            "betterfinder://search?scope=C:%5C..%5CWindows",
+            r"betterfinder://search?scope=\\?\C:\x",
+            r"betterfinder://search?scope=\\server",
+        ] {
+            assert!(UriCommand::parse(uri).is_err(), "{} was accepted", uri);
+        }
+
+        let long = format!("betterfinder://search?query={}", "a".repeat(MAX_URI_LENGTH));
+        assert!(UriCommand::parse(&long).is_err());
+    }
+
+    #[test]
+    fn test_scope_with_spaces_and_non_ascii_round_trips() {
+        let scope = r"C:\Users\Zoë\Mes Documents\Projets été";
+        let uri = UriCommand::search_uri(Some("rapport final"), Some(scope));
+        assert!(uri.is_ascii());
+        assert!(!uri.contains(' '));
+
+        assert_eq!(UriCommand::parse(&uri).unwrap(), search(Some("rapport final"), Some(scope)));
+        assert_eq!(UriCommand::search_uri(None, None), "betterfinder://search");
+    }
+
+    #[test]
+    fn test_context_prefills_scoped_query() {
+        let context = search(Some("budget"), Some(r"C:\Projects")).context();
+        assert_eq!(context.query, r"C:\Projects\ budget");
+        assert_eq!(context.scope.as_deref(), Some(r"C:\Projects"));
+
+        // Folders with spaces are quoted; an empty query lists the folder
+        assert_eq!(
+            search(None, Some(r"D:\My Files\")).context().query,
+            r#""D:\My Files\""#
+        );
+        assert_eq!(search(Some("notes"), None).context().query, "notes");
+        assert_eq!(UriCommand::Clipboard.context().query, "clip:");
+    }
+
+    #[test]
+    fn test_registry_values() {
+        let exe = Path::new(r"C:\Program Files\Better Finder\better-finder.exe");
+
+        let protocol = protocol_registry_values(exe);
+        assert!(protocol.contains(&RegistryValue::new(r"Software\Classes\betterfinder", "URL Protocol", "")));
+        assert!(protocol.contains(&RegistryValue::new(
+            r"Software\Classes\betterfinder\shell\open\command",
+            "",
+            r#""C:\Program Files\Better Finder\better-finder.exe" "%1""#,
+        )));
+
+        let menu = context_menu_registry_values(exe);
+        assert!(menu.contains(&RegistryValue::new(
+            r"Software\Classes\Directory\Background\shell\BetterFinder\command",
+            "",
+            r#""C:\Program Files\Better Finder\better-finder.exe" "betterfinder://search?scope=%V""#,
+        )));
+        assert!(menu.contains(&RegistryValue::new(
+            r"Software\Classes\Directory\shell\BetterFinder\command",
+            "",
+            r#""C:\Program Files\Better Finder\better-finder.exe" "betterfinder://search?scope=%1""#,
+        )));
+        assert!(menu
+            .iter()
+            .filter(|value| value.name.is_empty() && !value.key.ends_with("command"))
+            .all(|value| value.data == "Search in Better Finder"));
+    }
+
+    #[test]
+    fn test_find_uri_arg() {
+        let args = |list: &[&str]| -> Vec<String> {
+            std::iter::once("betterfinder.exe").chain(list.iter().copied()).map(str::to_string).collect()
+        };
+
+        assert_eq!(
+            find_uri_arg(args(&["BETTERFINDER://clip"])),
+            Some("BETTERFINDER://clip".to_string())
+        );
+        assert_eq!(find_uri_arg(args(&["betterfinder notes"])), None);
+        assert_eq!(find_uri_arg(args(&[])), None);
+    }
+}
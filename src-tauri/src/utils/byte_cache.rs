@@ -0,0 +1,180 @@
+use crate::types::CacheStats;
+use lru::LruCache;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+
+/// LRU cache of strings bounded by both entry count and total bytes
+///
+/// Used for encoded icons and favicons, whose size varies from a short
+/// identifier to several kilobytes of base64. Inserting past either limit
+/// evicts the least recently used entries until the cache fits again.
+pub struct ByteBudgetCache<K: Hash + Eq> {
+    entries: LruCache<K, String>,
+    /// Sum of the lengths of all cached values
+    bytes: usize,
+    byte_budget: usize,
+}
+
+impl<K: Hash + Eq> ByteBudgetCache<K> {
+    /// Creates a cache holding at most `max_entries` values and `byte_budget` bytes
+    pub fn new(max_entries: usize, byte_budget: usize) -> Self {
+        let max_entries = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            entries: LruCache::new(max_entries),
+            bytes: 0,
+            byte_budget,
+        }
+    }
+
+    /// Returns a cached value, marking it as recently used
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&String>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key)
+    }
+
+    /// Caches a value, returning how many entries were evicted to make room
+    ///
+    /// Values larger than the whole budget are not cached.
+    pub fn put(&mut self, key: K, value: String) -> usize {
+        if value.len() > self.byte_budget {
+            return 0;
+        }
+
+        self.bytes += value.len();
+        let mut evicted = 0;
+        if let Some((old_key, old_value)) = self.entries.push(key, value) {
+            self.bytes -= old_value.len();
+            // A replaced value for the same key is not an eviction
+            if !self.entries.contains(&old_key) {
+                evicted += 1;
+            }
+        }
+
+        evicted + self.evict_to(self.byte_budget)
+    }
+
+    /// Drops cold entries until at most half the budget is used
+    ///
+    /// Returns the number of entries removed.
+    pub fn trim(&mut self) -> usize {
+        self.evict_to(self.byte_budget / 2)
+    }
+
+    /// Evicts least recently used entries until the cache holds at most `limit` bytes
+    fn evict_to(&mut self, limit: usize) -> usize {
+        let mut evicted = 0;
+        while self.bytes > limit {
+            match self.entries.pop_lru() {
+                Some((_, value)) => {
+                    self.bytes -= value.len();
+                    evicted += 1;
+                }
+                None => break,
+            }
+        }
+        evicted
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the total length of the cached values
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Reports the cache's size under the given name
+    pub fn stats(&self, name: &str) -> CacheStats {
+        CacheStats {
+            name: name.to_string(),
+            entries: self.len(),
+            bytes: self.bytes,
+            byte_budget: Some(self.byte_budget),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(len: usize) -> String {
+        "x".repeat(len)
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_past_byte_budget() {
+        let mut cache = ByteBudgetCache::new(10, 100);
+        assert_eq!(cache.put("a", value(40)), 0);
+        assert_eq!(cache.put("b", value(40)), 0);
+        assert_eq!(cache.bytes(), 80);
+
+        // Touching "a" makes "b" the coldest entry
+        cache.get("a");
+        assert_eq!(cache.put("c", value(30)), 1);
+        assert_eq!(cache.bytes(), 70);
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some());
+
+        // A large value can push out several entries at once
+        assert_eq!(cache.put("d", value(90)), 2);
+        assert_eq!(cache.bytes(), 90);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_entry_limit_and_replacement_keep_byte_count() {
+        let mut cache = ByteBudgetCache::new(2, 1000);
+        cache.put("a", value(10));
+        cache.put("b", value(20));
+        assert_eq!(cache.put("c", value(30)), 1);
+        assert_eq!(cache.bytes(), 50);
+
+        // Replacing a value swaps its size without counting as an eviction
+        assert_eq!(cache.put("c", value(5)), 0);
+        assert_eq!(cache.bytes(), 25);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_oversized_values_are_not_cached() {
+        let mut cache = ByteBudgetCache::new(10, 100);
+        cache.put("a", value(50));
+        assert_eq!(cache.put("huge", value(101)), 0);
+        assert!(cache.get("huge").is_none());
+        assert_eq!(cache.bytes(), 50);
+    }
+
+    #[test]
+    fn test_trim_keeps_half_the_budget() {
+        let mut cache = ByteBudgetCache::new(10, 100);
+        for key in ["a", "b", "c", "d"] {
+            cache.put(key, value(25));
+        }
+        assert_eq!(cache.trim(), 2);
+        assert_eq!(cache.bytes(), 50);
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("d").is_some());
+
+        let stats = cache.stats("Icons");
+        assert_eq!((stats.entries, stats.bytes, stats.byte_budget), (2, 50, Some(100)));
+
+        cache.clear();
+        assert_eq!(cache.bytes(), 0);
+        assert!(cache.is_empty());
+    }
+}
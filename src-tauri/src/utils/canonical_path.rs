@@ -0,0 +1,139 @@
+use std::path::{Component, Path, PathBuf};
+
+/// Finds the path a file finally lives at, past any junctions and symlinks
+///
+/// Implemented with GetFinalPathNameByHandleW on Windows; tests substitute
+/// fixed mappings.
+pub trait PathResolver: Send + Sync {
+    /// The final path of `path`, or `None` when it can't be resolved, e.g.
+    /// because it's missing or a link loops
+    fn final_path(&self, path: &Path) -> Option<PathBuf>;
+}
+
+/// Resolves paths through the file system
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemResolver;
+
+impl PathResolver for SystemResolver {
+    #[cfg(windows)]
+    fn final_path(&self, path: &Path) -> Option<PathBuf> {
+        use std::os::windows::ffi::{OsStrExt, OsStringExt};
+        use windows::Win32::Foundation::{CloseHandle, HANDLE};
+        use windows::Win32::Storage::FileSystem::{
+            CreateFileW, GetFinalPathNameByHandleW, FILE_FLAG_BACKUP_SEMANTICS, FILE_NAME_NORMALIZED,
+            FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE, GETFINALPATHNAMEBYHANDLE_FLAGS, OPEN_EXISTING,
+            VOLUME_NAME_DOS,
+        };
+
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+
+        unsafe {
+            // No access rights are needed to ask for the name, and backup
+            // semantics lets folders open too
+            let handle = CreateFileW(
+                windows::core::PCWSTR(wide.as_ptr()),
+                0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                None,
+                OPEN_EXISTING,
+                FILE_FLAG_BACKUP_SEMANTICS,
+                HANDLE::default(),
+            )
+            .ok()?;
+
+            let flags = GETFINALPATHNAMEBYHANDLE_FLAGS(FILE_NAME_NORMALIZED.0 | VOLUME_NAME_DOS.0);
+            let mut buffer = vec![0u16; 260];
+            let mut len = GetFinalPathNameByHandleW(handle, &mut buffer, flags) as usize;
+            if len > buffer.len() {
+                // Too small; `len` is the size needed, terminator included
+                buffer.resize(len, 0);
+                len = GetFinalPathNameByHandleW(handle, &mut buffer, flags) as usize;
+            }
+            let _ = CloseHandle(handle);
+
+            if len == 0 || len > buffer.len() {
+                return None;
+            }
+            let resolved = std::ffi::OsString::from_wide(&buffer[..len]);
+            Some(PathBuf::from(strip_verbatim_prefix(&resolved.to_string_lossy())))
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn final_path(&self, path: &Path) -> Option<PathBuf> {
+        std::fs::canonicalize(path).ok()
+    }
+}
+
+/// Removes the `\\?\` prefix GetFinalPathNameByHandleW puts before the paths it returns
+///
+/// `\\?\C:\Docs` becomes `C:\Docs` and `\\?\UNC\server\share` becomes
+/// `\\server\share`. Paths with no drive letter after the prefix, such as
+/// volume GUID paths, can't be written without it and are kept whole.
+pub fn strip_verbatim_prefix(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        return format!(r"\\{}", rest);
+    }
+
+    match path.strip_prefix(r"\\?\") {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => rest.to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// Resolves `.` and `..` in `path` without touching the file system
+pub fn lexically_normalized(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                // `..` never climbs above the root or drive
+                if !matches!(normalized.components().next_back(), None | Some(Component::RootDir | Component::Prefix(_))) {
+                    normalized.pop();
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The path `path` finally leads to, or its lexically normalized form when
+/// it can't be resolved
+///
+/// Two paths reaching one file through a junction or symlink give the same
+/// canonical path.
+pub fn canonical_path(path: &Path, resolver: &dyn PathResolver) -> PathBuf {
+    resolver.final_path(path).unwrap_or_else(|| lexically_normalized(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_verbatim_prefix() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\D:\Docs\report.docx"), r"D:\Docs\report.docx");
+        assert_eq!(strip_verbatim_prefix(r"\\?\UNC\nas\share\notes.txt"), r"\\nas\share\notes.txt");
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\Volume{0b2c7a5e-1f3d-4c2a-9e61-7d1c3b0a9f42}\notes.txt"),
+            r"\\?\Volume{0b2c7a5e-1f3d-4c2a-9e61-7d1c3b0a9f42}\notes.txt"
+        );
+        assert_eq!(strip_verbatim_prefix(r"C:\Users\ana"), r"C:\Users\ana");
+    }
+
+    #[test]
+    fn test_unresolved_paths_fall_back_to_lexical_form() {
+        struct Unresolvable;
+        impl PathResolver for Unresolvable {
+            fn final_path(&self, _path: &Path) -> Option<PathBuf> {
+                None
+            }
+        }
+
+        assert_eq!(canonical_path(Path::new("/docs/./drafts/../report.txt"), &Unresolvable), Path::new("/docs/report.txt"));
+        assert_eq!(lexically_normalized(Path::new("/../docs")), Path::new("/docs"));
+        assert_eq!(lexically_normalized(Path::new("docs/../../notes")), Path::new("notes"));
+    }
+}
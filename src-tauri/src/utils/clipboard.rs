@@ -0,0 +1,167 @@
+use crate::error::{LauncherError, Result};
+use async_trait::async_trait;
+
+/// Where the text on the clipboard is read from
+///
+/// The system clipboard in the app; tests provide fixed content.
+#[async_trait]
+pub trait ClipboardSource: Send + Sync {
+    /// Text on the clipboard, or `None` when it holds something else
+    async fn read_text(&self) -> Result<Option<String>>;
+}
+
+/// The system clipboard, read through `get_clipboard_text`
+pub struct SystemClipboard;
+
+#[async_trait]
+impl ClipboardSource for SystemClipboard {
+    async fn read_text(&self) -> Result<Option<String>> {
+        get_clipboard_text().await
+    }
+}
+
+/// Reads the text on the Windows clipboard
+///
+/// Returns `None` when the clipboard holds no text.
+#[cfg(windows)]
+pub async fn get_clipboard_text() -> Result<Option<String>> {
+    use windows::Win32::Foundation::*;
+    use windows::Win32::System::DataExchange::*;
+    use windows::Win32::System::Memory::*;
+
+    tokio::task::spawn_blocking(|| unsafe {
+        if OpenClipboard(HWND(std::ptr::null_mut())).is_err() {
+            return Err(LauncherError::ExecutionError(
+                "Failed to open clipboard".to_string(),
+            ));
+        }
+
+        const CF_UNICODETEXT: u32 = 13;
+        if IsClipboardFormatAvailable(CF_UNICODETEXT).is_err() {
+            CloseClipboard().ok();
+            return Ok(None);
+        }
+
+        let handle = match GetClipboardData(CF_UNICODETEXT) {
+            Ok(handle) if !handle.0.is_null() => handle,
+            _ => {
+                CloseClipboard().ok();
+                return Err(LauncherError::ExecutionError(
+                    "Failed to get clipboard data".to_string(),
+                ));
+            }
+        };
+
+        let hmem = HGLOBAL(handle.0);
+        let ptr = GlobalLock(hmem);
+        if ptr.is_null() {
+            CloseClipboard().ok();
+            return Err(LauncherError::ExecutionError(
+                "Failed to lock clipboard memory".to_string(),
+            ));
+        }
+
+        // Bounded by the allocation, in case the text isn't terminated
+        let units = std::slice::from_raw_parts(ptr as *const u16, GlobalSize(hmem) / 2);
+        let len = units.iter().position(|&unit| unit == 0).unwrap_or(units.len());
+        let text = String::from_utf16_lossy(&units[..len]);
+
+        GlobalUnlock(hmem).ok();
+        CloseClipboard().ok();
+        Ok(Some(text))
+    })
+    .await
+    .map_err(|e| {
+        LauncherError::ExecutionError(format!("Failed to spawn clipboard task: {}", e))
+    })?
+}
+
+#[cfg(not(windows))]
+pub async fn get_clipboard_text() -> Result<Option<String>> {
+    Err(LauncherError::ExecutionError(
+        "Clipboard operations not supported on this platform".to_string(),
+    ))
+}
+
+/// Copies text to the Windows clipboard
+#[cfg(windows)]
+pub async fn set_clipboard_text(text: &str) -> Result<()> {
+    use windows::Win32::Foundation::*;
+    use windows::Win32::System::DataExchange::*;
+    use windows::Win32::System::Memory::*;
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+
+    let text_owned = text.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        unsafe {
+            // Open the clipboard
+            if OpenClipboard(HWND(std::ptr::null_mut())).is_err() {
+                return Err(LauncherError::ExecutionError(
+                    "Failed to open clipboard".to_string(),
+                ));
+            }
+
+            // Empty the clipboard
+            if EmptyClipboard().is_err() {
+                CloseClipboard().ok();
+                return Err(LauncherError::ExecutionError(
+                    "Failed to empty clipboard".to_string(),
+                ));
+            }
+
+            // Convert text to wide string
+            let wide: Vec<u16> = OsStr::new(&text_owned)
+                .encode_wide()
+                .chain(std::iter::once(0))
+                .collect();
+
+            // Allocate global memory
+            let len = wide.len() * std::mem::size_of::<u16>();
+            let hmem = GlobalAlloc(GMEM_MOVEABLE, len)
+                .map_err(|_| LauncherError::ExecutionError("Failed to allocate memory".to_string()))?;
+
+            // Lock the memory and copy the text
+            let ptr = GlobalLock(hmem);
+            if ptr.is_null() {
+                GlobalFree(hmem).ok();
+                CloseClipboard().ok();
+                return Err(LauncherError::ExecutionError(
+                    "Failed to lock memory".to_string(),
+                ));
+            }
+
+            std::ptr::copy_nonoverlapping(wide.as_ptr(), ptr as *mut u16, wide.len());
+            GlobalUnlock(hmem).ok();
+
+            // Set the clipboard data
+            const CF_UNICODETEXT: u32 = 13;
+            if SetClipboardData(CF_UNICODETEXT, HANDLE(hmem.0)).is_err() {
+                GlobalFree(hmem).ok();
+                CloseClipboard().ok();
+                return Err(LauncherError::ExecutionError(
+                    "Failed to set clipboard data".to_string(),
+                ));
+            }
+
+            // Close the clipboard
+            CloseClipboard().ok();
+
+            Ok(())
+        }
+    })
+    .await
+    .map_err(|e| {
+        LauncherError::ExecutionError(format!("Failed to spawn clipboard task: {}", e))
+    })??;
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub async fn set_clipboard_text(_text: &str) -> Result<()> {
+    Err(LauncherError::ExecutionError(
+        "Clipboard operations not supported on this platform".to_string(),
+    ))
+}
@@ -0,0 +1,443 @@
+/// Integrity checks and salvage for the app's SQLite stores
+///
+/// A hard power loss can leave a store "malformed", after which every query
+/// against it fails. Once a day, and right away after a run that didn't shut
+/// down cleanly, each store runs `PRAGMA quick_check`; a damaged store has whatever rows are still readable copied into a fresh
+/// database, and the damaged file is kept beside it with a timestamp.
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, Connection, ErrorCode};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Time after a clean check during which a store isn't checked again
+pub const CHECK_INTERVAL_HOURS: i64 = 24;
+
+/// Table the time of the last clean check is kept in
+const META_TABLE: &str = "integrity_meta";
+
+/// Key of the last clean check in `META_TABLE`
+const LAST_CLEAN_CHECK_KEY: &str = "last_clean_check";
+
+/// When a store found clean is checked again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckPolicy {
+    /// Once `CHECK_INTERVAL_HOURS` have passed since the last clean check
+    Scheduled,
+    /// Now, e.g. because the last run was killed mid-write
+    Always,
+}
+
+/// Turns on write-ahead logging with `synchronous=NORMAL`
+///
+/// Every store sets this when it opens, which keeps a crash from tearing a
+/// write in half without paying for a sync on every commit.
+pub fn configure_durability(conn: &Connection) -> Result<()> {
+    conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    Ok(())
+}
+
+/// Copies the write-ahead log into the database file and empties it
+///
+/// Run before exiting, so the next start doesn't have to replay the log.
+pub fn checkpoint(conn: &Connection) -> Result<()> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    Ok(())
+}
+
+/// What checking a store found
+#[derive(Debug, Clone, PartialEq)]
+pub enum IntegrityOutcome {
+    /// The store is missing or was found clean less than a day ago, under `CheckPolicy::Scheduled`
+    Skipped,
+    /// `quick_check` found no problems
+    Clean,
+    /// The store was damaged and rebuilt from its readable rows
+    Recovered(Recovery),
+}
+
+/// What was salvaged from a damaged store
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovery {
+    /// Where the damaged file was moved
+    pub archive_path: PathBuf,
+    /// Tables that could be recreated
+    pub tables: usize,
+    /// Rows copied into the fresh database
+    pub rows: usize,
+}
+
+/// The state of a store before any repair
+enum Health {
+    CheckedRecently,
+    Healthy,
+    Damaged(String),
+}
+
+/// Checks the store at `path` and rebuilds it when it's damaged
+///
+/// Callers must close their own connections first: the damaged file is
+/// renamed, which Windows refuses while it's open.
+pub fn check_database(path: &Path, now: DateTime<Utc>, policy: CheckPolicy) -> Result<IntegrityOutcome> {
+    if !path.exists() {
+        return Ok(IntegrityOutcome::Skipped);
+    }
+
+    match health(path, now, policy)? {
+        Health::CheckedRecently => Ok(IntegrityOutcome::Skipped),
+        Health::Healthy => Ok(IntegrityOutcome::Clean),
+        Health::Damaged(problem) => {
+            warn!("{} is damaged: {}", path.display(), problem);
+            let recovery = recover(path, now)?;
+            info!(
+                "Recovered {} rows in {} tables of {}, damaged copy kept as {}",
+                recovery.rows,
+                recovery.tables,
+                path.display(),
+                recovery.archive_path.display()
+            );
+            Ok(IntegrityOutcome::Recovered(recovery))
+        }
+    }
+}
+
+/// Where a damaged store is moved, e.g. `recent_files.corrupt-20240131-081500.db`
+pub fn archive_path(path: &Path, now: DateTime<Utc>) -> PathBuf {
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    let mut name = format!("{}.corrupt-{}", stem, now.format("%Y%m%d-%H%M%S"));
+    if let Some(extension) = path.extension() {
+        name.push('.');
+        name.push_str(&extension.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+/// `path` with `suffix` appended, for SQLite's `-wal` and `-shm` files
+fn sidecar(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Whether an error means the file itself is damaged, rather than busy or unreadable
+fn is_corruption(error: &rusqlite::Error) -> bool {
+    matches!(
+        error.sqlite_error_code(),
+        Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+    )
+}
+
+fn health(path: &Path, now: DateTime<Utc>, policy: CheckPolicy) -> Result<Health> {
+    let conn = Connection::open(path)?;
+
+    if let (CheckPolicy::Scheduled, Some(last)) = (policy, last_clean_check(&conn)) {
+        if (now - last).num_hours() < CHECK_INTERVAL_HOURS {
+            return Ok(Health::CheckedRecently);
+        }
+    }
+
+    let problems = conn.prepare("PRAGMA quick_check").and_then(|mut stmt| {
+        stmt.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()
+    });
+
+    match problems {
+        Ok(problems) if problems == ["ok"] => {
+            record_clean_check(&conn, now)?;
+            Ok(Health::Healthy)
+        }
+        Ok(problems) => Ok(Health::Damaged(problems.join("; "))),
+        Err(e) if is_corruption(&e) => Ok(Health::Damaged(e.to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn last_clean_check(conn: &Connection) -> Option<DateTime<Utc>> {
+    let value: String = conn
+        .query_row(
+            &format!("SELECT value FROM {} WHERE key = ?1", META_TABLE),
+            params![LAST_CLEAN_CHECK_KEY],
+            |row| row.get(0),
+        )
+        .ok()?;
+
+    DateTime::parse_from_rfc3339(&value).ok().map(|time| time.with_timezone(&Utc))
+}
+
+fn record_clean_check(conn: &Connection, now: DateTime<Utc>) -> Result<()> {
+    conn.execute(
+        &format!("CREATE TABLE IF NOT EXISTS {} (key TEXT PRIMARY KEY, value TEXT NOT NULL)", META_TABLE),
+        [],
+    )?;
+    conn.execute(
+        &format!("INSERT OR REPLACE INTO {} (key, value) VALUES (?1, ?2)", META_TABLE),
+        params![LAST_CLEAN_CHECK_KEY, now.to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// Rebuilds a damaged store in place, archiving the damaged file
+fn recover(path: &Path, now: DateTime<Utc>) -> Result<Recovery> {
+    let fresh = sidecar(path, ".recovering");
+    let _ = std::fs::remove_file(&fresh);
+
+    let (tables, rows) = {
+        let target = Connection::open(&fresh)?;
+        let salvaged = salvage(path, &target)?;
+        record_clean_check(&target, now)?;
+        salvaged
+    };
+
+    let archive = archive_path(path, now);
+    std::fs::rename(path, &archive)?;
+
+    // The log may hold the newest writes, so it stays with the damaged copy
+    let wal = sidecar(path, "-wal");
+    if wal.exists() {
+        if let Err(e) = std::fs::rename(&wal, sidecar(&archive, "-wal")) {
+            warn!("Failed to archive {}: {}", wal.display(), e);
+        }
+    }
+    let _ = std::fs::remove_file(sidecar(path, "-shm"));
+
+    std::fs::rename(&fresh, path)?;
+
+    Ok(Recovery {
+        archive_path: archive,
+        tables,
+        rows,
+    })
+}
+
+/// Copies every readable table and row of `source` into `target`
+///
+/// Returns how many tables were recreated and rows copied. Indexes are
+/// rebuilt afterwards; those that no longer fit the salvaged rows are dropped.
+fn salvage(source: &Path, target: &Connection) -> Result<(usize, usize)> {
+    let source = Connection::open(source)?;
+
+    // Without this SQLite rejects a file shorter than its header says
+    // outright; with it, pages that are still there can be read. Nothing is
+    // written through this connection.
+    source.pragma_update(None, "writable_schema", true)?;
+
+    let schema = source
+        .prepare(
+            "SELECT type, name, sql FROM sqlite_master
+             WHERE sql IS NOT NULL AND name NOT LIKE 'sqlite_%'",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+                .collect::<rusqlite::Result<Vec<_>>>()
+        })
+        .unwrap_or_else(|e| {
+            warn!("Schema of the damaged database is unreadable: {}", e);
+            Vec::new()
+        });
+
+    target.execute_batch("BEGIN")?;
+
+    let mut tables = 0;
+    let mut rows = 0;
+    for (_, name, sql) in schema.iter().filter(|(kind, _, _)| kind == "table") {
+        if let Err(e) = target.execute(sql, []) {
+            warn!("Failed to recreate table {}: {}", name, e);
+            continue;
+        }
+        tables += 1;
+        rows += copy_rows(&source, target, name)?;
+    }
+
+    for (_, name, sql) in schema.iter().filter(|(kind, _, _)| kind != "table") {
+        if let Err(e) = target.execute(sql, []) {
+            warn!("Dropped {} while recovering: {}", name, e);
+        }
+    }
+
+    target.execute_batch("COMMIT")?;
+    Ok((tables, rows))
+}
+
+/// Copies the readable rows of one table, returning how many were copied
+///
+/// Rows are read from both ends of the table, so rows past a damaged page
+/// survive as well as those before it.
+fn copy_rows(source: &Connection, target: &Connection, table: &str) -> Result<usize> {
+    let quoted = format!("\"{}\"", table.replace('"', "\"\""));
+
+    let scans = [
+        format!("SELECT rowid, * FROM {} ORDER BY rowid", quoted),
+        format!("SELECT rowid, * FROM {} ORDER BY rowid DESC", quoted),
+    ];
+    let mut copied = 0;
+    for (index, scan) in scans.iter().enumerate() {
+        match source.prepare(scan) {
+            Ok(mut select) => copied += copy_scan(&mut select, target, &quoted, true)?,
+            // WITHOUT ROWID tables can only be read front to back
+            Err(_) if index == 0 => {
+                let Ok(mut select) = source.prepare(&format!("SELECT * FROM {}", quoted)) else {
+                    break;
+                };
+                return copy_scan(&mut select, target, &quoted, false);
+            }
+            Err(_) => break,
+        }
+    }
+    Ok(copied)
+}
+
+/// Inserts rows from `select` until it runs out or hits a damaged page
+fn copy_scan(select: &mut rusqlite::Statement, target: &Connection, table: &str, with_rowid: bool) -> Result<usize> {
+    let names: Vec<String> = select.column_names().iter().map(|name| format!("\"{}\"", name.replace('"', "\"\""))).collect();
+    let columns = if with_rowid {
+        std::iter::once("rowid".to_string()).chain(names.iter().skip(1).cloned()).collect::<Vec<_>>()
+    } else {
+        names
+    };
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    let mut insert = target.prepare(&format!(
+        "INSERT OR IGNORE INTO {} ({}) VALUES ({})",
+        table,
+        columns.join(", "),
+        placeholders
+    ))?;
+
+    let mut copied = 0;
+    let mut rows = match select.query([]) {
+        Ok(rows) => rows,
+        Err(_) => return Ok(0),
+    };
+    while let Ok(Some(row)) = rows.next() {
+        let Ok(values) = (0..columns.len()).map(|i| row.get::<_, Value>(i)).collect::<rusqlite::Result<Vec<_>>>() else {
+            break;
+        };
+        copied += insert.execute(rusqlite::params_from_iter(values))?;
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const ROWS: i64 = 2000;
+
+    fn test_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("BetterFinder").join("integrity_test").join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.join("store.db")
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 31, 8, 15, 0).unwrap()
+    }
+
+    /// Writes a store spanning many pages, then cuts it to `keep` of its length
+    fn truncated_store(name: &str, keep: f64) -> PathBuf {
+        let path = test_path(name);
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE items (id INTEGER PRIMARY KEY, text TEXT NOT NULL);
+                 CREATE INDEX idx_text ON items(text);",
+            )
+            .unwrap();
+            let tx = conn.unchecked_transaction().unwrap();
+            for id in 0..ROWS {
+                tx.execute("INSERT INTO items (id, text) VALUES (?1, ?2)", params![id, format!("{:0>200}", id)])
+                    .unwrap();
+            }
+            tx.commit().unwrap();
+        }
+
+        let length = std::fs::metadata(&path).unwrap().len();
+        let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len((length as f64 * keep) as u64 / 4096 * 4096).unwrap();
+        path
+    }
+
+    fn row_count(path: &Path) -> i64 {
+        Connection::open(path).unwrap().query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn test_archive_name_keeps_extension() {
+        assert_eq!(
+            archive_path(Path::new("data/recent_files.db"), now()),
+            Path::new("data/recent_files.corrupt-20240131-081500.db")
+        );
+        assert_eq!(archive_path(Path::new("data/store"), now()), Path::new("data/store.corrupt-20240131-081500"));
+    }
+
+    #[test]
+    fn test_clean_store_is_checked_once_a_day() {
+        let path = test_path("clean");
+        Connection::open(&path).unwrap().execute("CREATE TABLE items (id INTEGER)", []).unwrap();
+
+        assert_eq!(check_database(&path, now(), CheckPolicy::Scheduled).unwrap(), IntegrityOutcome::Clean);
+        assert_eq!(
+            check_database(&path, now() + chrono::Duration::hours(23), CheckPolicy::Scheduled).unwrap(),
+            IntegrityOutcome::Skipped
+        );
+        assert_eq!(
+            check_database(&path, now() + chrono::Duration::hours(25), CheckPolicy::Scheduled).unwrap(),
+            IntegrityOutcome::Clean
+        );
+
+        // Checked anyway when the last run didn't exit cleanly
+        assert_eq!(
+            check_database(&path, now() + chrono::Duration::hours(26), CheckPolicy::Always).unwrap(),
+            IntegrityOutcome::Clean
+        );
+        assert_eq!(
+            check_database(&path.with_file_name("missing.db"), now(), CheckPolicy::Always).unwrap(),
+            IntegrityOutcome::Skipped
+        );
+    }
+
+    #[test]
+    fn test_truncated_store_is_detected_and_salvaged() {
+        let path = truncated_store("truncated", 0.5);
+
+        let IntegrityOutcome::Recovered(recovery) = check_database(&path, now(), CheckPolicy::Scheduled).unwrap() else {
+            panic!("truncated store passed the check");
+        };
+        assert_eq!(recovery.tables, 1);
+        assert_eq!(recovery.archive_path, path.with_file_name("store.corrupt-20240131-081500.db"));
+        assert!(recovery.archive_path.exists());
+
+        // Rows on the surviving pages come back, and only those
+        let salvaged = row_count(&path);
+        assert_eq!(salvaged, recovery.rows as i64);
+        assert!(salvaged > 0 && salvaged < ROWS, "{}", salvaged);
+        let first: String = Connection::open(&path)
+            .unwrap()
+            .query_row("SELECT text FROM items WHERE id = 0", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(first, format!("{:0>200}", 0));
+
+        // The rebuilt store is clean and its check counts as today's
+        let conn = Connection::open(&path).unwrap();
+        let check: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0)).unwrap();
+        assert_eq!(check, "ok");
+        assert_eq!(check_database(&path, now(), CheckPolicy::Scheduled).unwrap(), IntegrityOutcome::Skipped);
+    }
+
+    #[test]
+    fn test_store_without_readable_schema_starts_empty() {
+        let path = truncated_store("header_only", 0.0);
+        std::fs::write(&path, b"SQLite format 3\0 but nothing else").unwrap();
+
+        let IntegrityOutcome::Recovered(recovery) = check_database(&path, now(), CheckPolicy::Scheduled).unwrap() else {
+            panic!("unreadable store passed the check");
+        };
+        assert_eq!((recovery.tables, recovery.rows), (0, 0));
+        assert!(recovery.archive_path.exists());
+        Connection::open(&path).unwrap().execute("CREATE TABLE items (id INTEGER)", []).unwrap();
+    }
+}
@@ -0,0 +1,340 @@
+use crate::error::Result;
+use crate::types::{ResultAction, ResultType, SearchResult};
+use crate::uri_scheme::{LaunchContext, UriCommand};
+use crate::utils::recycle_bin;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Identifier of the "Copy link" secondary action every result offers
+pub const COPY_LINK_ACTION_ID: &str = "copy_link";
+
+/// Extensions of files that run code when opened; links never open them directly
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "exe", "msi", "bat", "cmd", "com", "ps1", "vbs", "vbe", "js", "jse", "wsf", "wsh", "scr", "pif",
+    "lnk", "url", "reg", "hta", "cpl", "msc", "jar",
+];
+
+/// Extensions of files a link may open without asking: documents and media
+/// whose usual handlers don't run code from them
+///
+/// An allowlist, as the types that do run code are too many to list, from
+/// macro-enabled documents to `.appref-ms` and `.settingcontent-ms`.
+const LINK_OPENABLE_EXTENSIONS: &[&str] = &[
+    "pdf", "txt", "md", "log", "csv", "rtf", "docx", "xlsx", "pptx", "odt", "ods", "odp", "png", "jpg",
+    "jpeg", "gif", "bmp", "webp", "tif", "tiff", "heic", "mp3", "m4a", "wav", "flac", "ogg", "mp4", "m4v",
+    "mkv", "mov", "avi", "webm",
+];
+
+/// What to do with a `betterfinder://` link
+#[derive(Debug, Clone)]
+pub enum PendingAction {
+    /// Open the result right away without showing the launcher
+    Execute(SearchResult),
+    /// Show the launcher on a query, with a result to preselect
+    Show(LaunchContext),
+}
+
+/// The `secondary_actions` entry for copying a result's link
+pub fn copy_link_action() -> serde_json::Value {
+    serde_json::json!({
+        "id": COPY_LINK_ACTION_ID,
+        "title": "Copy link",
+        "requires_confirmation": false,
+    })
+}
+
+/// Builds a link that re-creates `result` on another machine
+///
+/// Files link their path, web pages their URL and applications their name,
+/// which the receiving launcher searches for. Commands link their result id
+/// and command line so the receiving launcher can preselect its own copy of
+/// the command. Clipboard items link clipboard history, never their content.
+pub fn result_to_uri(result: &SearchResult) -> String {
+    let command = match (&result.result_type, &result.action) {
+        (ResultType::Clipboard, _) => UriCommand::Clipboard,
+        (_, ResultAction::OpenFile { path }) => UriCommand::Open { path: path.clone() },
+        (_, ResultAction::OpenUrl { url }) => UriCommand::Url { url: url.clone() },
+        (_, ResultAction::LaunchApp { .. }) => UriCommand::App {
+            name: result.title.clone(),
+        },
+        (_, ResultAction::ExecuteCommand { command, args }) => UriCommand::Action {
+            id: result.id.clone(),
+            title: Some(result.title.clone()),
+            command: Some(std::iter::once(command).chain(args).cloned().collect::<Vec<_>>().join(" ")),
+        },
+        (_, ResultAction::WebSearch { query } | ResultAction::SetQuery { query }) => UriCommand::Search {
+            query: Some(query.clone()),
+            scope: None,
+        },
+        _ => UriCommand::Search {
+            query: Some(result.title.clone()),
+            scope: None,
+        },
+    };
+
+    command.to_uri()
+}
+
+/// Decides what a received link does
+///
+/// Only links to http(s) pages and to existing local files of an inert type
+/// (see [`is_link_openable`]) open directly. Everything else, in particular
+/// commands and quick actions, shows the launcher so the user confirms by
+/// pressing Enter. Links to files that don't exist search for the file's name
+/// instead, and so do links to network shares, which aren't even looked at:
+/// checking for the file would already connect to the share.
+pub fn uri_to_pending_action(uri: &str) -> Result<PendingAction> {
+    let command = UriCommand::parse(uri)?;
+
+    let action = match &command {
+        UriCommand::Open { path } => {
+            let file = Path::new(path);
+            if recycle_bin::is_unc_path(path) {
+                tracing::info!("Linked file {} is on a network share, searching for it", path);
+                return Ok(search_file_name(path));
+            }
+            if !file.exists() {
+                tracing::info!("Linked file {} does not exist, searching for it", path);
+                return Ok(search_file_name(path));
+            }
+            if !is_link_openable(file) || recycle_bin::is_network_path(path) {
+                PendingAction::Show(command.context())
+            } else {
+                PendingAction::Execute(linked_result(
+                    path,
+                    ResultType::File,
+                    ResultAction::OpenFile { path: path.clone() },
+                ))
+            }
+        }
+        UriCommand::Url { url } if is_web_url(url) => PendingAction::Execute(linked_result(
+            url,
+            ResultType::Url,
+            ResultAction::OpenUrl { url: url.clone() },
+        )),
+        _ => PendingAction::Show(command.context()),
+    };
+
+    Ok(action)
+}
+
+/// Shows the launcher searching for the name of the linked file
+fn search_file_name(path: &str) -> PendingAction {
+    let query = Some(file_name(path).to_string());
+    PendingAction::Show(UriCommand::Search { query, scope: None }.context())
+}
+
+/// Returns true for folders and for files a link may open without asking
+pub(crate) fn is_link_openable(path: &Path) -> bool {
+    if path.is_dir() {
+        return true;
+    }
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| LINK_OPENABLE_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+}
+
+pub(crate) fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXECUTABLE_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+}
+
+pub(crate) fn is_web_url(url: &str) -> bool {
+    let lower = url.to_ascii_lowercase();
+    (lower.starts_with("https://") || lower.starts_with("http://")) && !url.contains(char::is_whitespace)
+}
+
+/// Last component of a Windows path, whichever separator it uses
+fn file_name(path: &str) -> &str {
+    path.trim_end_matches(['\\', '/'])
+        .rsplit(['\\', '/'])
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or(path)
+}
+
+/// A result opening what a link points at
+fn linked_result(target: &str, result_type: ResultType, action: ResultAction) -> SearchResult {
+    let title = match result_type {
+        ResultType::File => file_name(target),
+        _ => target,
+    };
+
+    SearchResult {
+        id: format!("deeplink:{}", target),
+        title: title.to_string(),
+        subtitle: target.to_string(),
+        icon: None,
+        result_type,
+        score: 0.0,
+        metadata: HashMap::new(),
+        action,
+        normalized_title: None,
+        enrichment_pending: false,
+        integrity: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, title: &str, result_type: ResultType, action: ResultAction) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: title.to_string(),
+            subtitle: String::new(),
+            icon: None,
+            result_type,
+            score: 50.0,
+            metadata: HashMap::new(),
+            action,
+            normalized_title: None,
+            enrichment_pending: false,
+            integrity: None,
+        }
+    }
+
+    fn shown(uri: &str) -> LaunchContext {
+        match uri_to_pending_action(uri).unwrap() {
+            PendingAction::Show(context) => context,
+            PendingAction::Execute(result) => panic!("{} executed {:?}", uri, result.action),
+        }
+    }
+
+    #[test]
+    fn test_existing_files_and_web_pages_round_trip_to_direct_opens() {
+        let dir = std::env::temp_dir().join("better_finder_deeplink_tést");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("rapport final.pdf");
+        std::fs::write(&file, b"%PDF").unwrap();
+        let path = file.to_string_lossy().into_owned();
+
+        let uri = result_to_uri(&result("f", "rapport final.pdf", ResultType::File, ResultAction::OpenFile { path: path.clone() }));
+        assert!(uri.starts_with("betterfinder://open?path="), "{}", uri);
+        // Linux temp paths aren't Windows paths; the link itself is still well-formed
+        if cfg!(windows) {
+            match uri_to_pending_action(&uri).unwrap() {
+                PendingAction::Execute(opened) => {
+                    assert!(matches!(opened.action, ResultAction::OpenFile { path: p } if p == path));
+                }
+                other => panic!("expected a direct open, got {:?}", other),
+            }
+        }
+        std::fs::remove_dir_all(&dir).ok();
+
+        let bookmark = result(
+            "b",
+            "Rust docs",
+            ResultType::Bookmark,
+            ResultAction::OpenUrl { url: "https://doc.rust-lang.org/std/?search=Vec&x=1".to_string() },
+        );
+        match uri_to_pending_action(&result_to_uri(&bookmark)).unwrap() {
+            PendingAction::Execute(opened) => {
+                assert_eq!(opened.result_type, ResultType::Url);
+                assert!(matches!(opened.action, ResultAction::OpenUrl { url } if url == "https://doc.rust-lang.org/std/?search=Vec&x=1"));
+            }
+            other => panic!("expected a direct open, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_apps_searches_and_clipboard_round_trip_to_queries() {
+        let app = result("app:notepad", "Notepad", ResultType::Application, ResultAction::LaunchApp { path: r"C:\Windows\notepad.exe".to_string() });
+        assert_eq!(result_to_uri(&app), "betterfinder://app?name=Notepad");
+        assert_eq!(shown(&result_to_uri(&app)).query, "Notepad");
+
+        let web = result("web", "Search the web", ResultType::WebSearch, ResultAction::WebSearch { query: "tauri & rust".to_string() });
+        assert_eq!(shown(&result_to_uri(&web)).query, "tauri & rust");
+
+        let calc = result("calc", "= 42", ResultType::Calculator, ResultAction::CopyToClipboard { content: "42".to_string() });
+        assert_eq!(shown(&result_to_uri(&calc)).query, "= 42");
+
+        let clip = result("clipboard:1", "my password", ResultType::Clipboard, ResultAction::CopyToClipboard { content: "hunter2".to_string() });
+        let uri = result_to_uri(&clip);
+        assert_eq!(uri, "betterfinder://clip");
+        assert_eq!(shown(&uri).query, "clip:");
+    }
+
+    #[test]
+    fn test_commands_and_unsafe_targets_never_auto_execute() {
+        let shutdown = result(
+            "quick_action:shutdown",
+            "Shut down",
+            ResultType::QuickAction,
+            ResultAction::ExecuteCommand { command: "shutdown".to_string(), args: vec!["/s".to_string(), "/t".to_string(), "0".to_string()] },
+        );
+        let uri = result_to_uri(&shutdown);
+        assert!(uri.contains("command=shutdown%20%2Fs%20%2Ft%200"), "{}", uri);
+
+        let context = shown(&uri);
+        assert_eq!(context.query, "Shut down");
+        assert_eq!(context.select_result_id.as_deref(), Some("quick_action:shutdown"));
+
+        // Hand-written links get the same treatment
+        assert_eq!(shown("betterfinder://action?id=x&command=format%20C%3A").query, "format C:");
+        for uri in [
+            "betterfinder://url?url=javascript%3Aalert(1)",
+            "betterfinder://url?url=file%3A%2F%2F%2FC%3A%2FWindows%2Fsystem32%2Fcalc.exe",
+        ] {
+            shown(uri);
+        }
+
+        let exe = std::env::current_exe().unwrap();
+        if cfg!(windows) {
+            let uri = UriCommand::Open { path: exe.to_string_lossy().into_owned() }.to_uri();
+            shown(&uri);
+        }
+        assert!(is_executable(Path::new(r"C:\Users\me\Downloads\setup.EXE")));
+        assert!(!is_executable(Path::new(r"C:\Users\me\notes.txt")));
+    }
+
+    #[test]
+    fn test_network_share_links_search_without_touching_the_share() {
+        for uri in [
+            r"betterfinder://open?path=%5C%5Chost%5Cshare%5Cx.chm",
+            r"betterfinder://open?path=%5C%5Chost%5Cshare%5Creport.pdf",
+            r"betterfinder://open?path=%5C%5Chost%5Cshare%5Cmacro.docm",
+        ] {
+            let context = shown(uri);
+            assert!(!context.query.contains("host"), "{}", context.query);
+        }
+        assert_eq!(shown(r"betterfinder://open?path=%5C%5Chost%5Cshare%5Cx.chm").query, "x.chm");
+    }
+
+    #[test]
+    fn test_only_inert_file_types_open_from_links() {
+        let dir = std::env::temp_dir().join("better_finder_deeplink_types");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for name in [
+            "help.chm", "report.docm", "app.appref-ms", "panel.settingcontent-ms", "docs.library-ms",
+            "disk.iso", "package.msix", "module.psm1", "page.html", "no_extension",
+        ] {
+            let file = dir.join(name);
+            std::fs::write(&file, b"x").unwrap();
+            assert!(!is_link_openable(&file), "{}", name);
+            if cfg!(windows) {
+                shown(&UriCommand::Open { path: file.to_string_lossy().into_owned() }.to_uri());
+            }
+        }
+
+        for name in ["report.pdf", "photo.JPG", "notes.txt"] {
+            assert!(is_link_openable(&dir.join(name)), "{}", name);
+        }
+        assert!(is_link_openable(&dir));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_missing_files_search_for_their_name() {
+        let context = shown(r"betterfinder://open?path=C:%5CNowhere%5CBudget%202024.xlsx");
+        assert_eq!(context.query, "Budget 2024.xlsx");
+        assert_eq!(context.select_result_id, None);
+
+        assert!(uri_to_pending_action("betterfinder://open?path=relative%5Cfile.txt").is_err());
+        assert!(uri_to_pending_action("betterfinder://open").is_err());
+        assert!(uri_to_pending_action("betterfinder://url?url=https%3A%2F%2Fa.b&extra=1").is_err());
+    }
+}
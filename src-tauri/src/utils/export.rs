@@ -0,0 +1,50 @@
+use crate::error::{LauncherError, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Writes `value` as pretty-printed JSON to a user-chosen file
+///
+/// The JSON goes to a temporary file beside `path` that is then renamed over
+/// it, so a failed export never leaves a truncated file behind.
+pub fn write_json_export(path: &Path, value: &impl Serialize) -> Result<()> {
+    if !path.is_absolute() {
+        return Err(LauncherError::SecurityError(format!(
+            "Export path must be absolute: {}",
+            path.display()
+        )));
+    }
+
+    let json = serde_json::to_vec_pretty(value)?;
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+
+    std::fs::write(&temp_path, json)?;
+    if let Err(e) = std::fs::rename(&temp_path, path) {
+        let _ = std::fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_json_export_replaces_file() {
+        let path = std::env::temp_dir().join("bf_export_test.json");
+        std::fs::write(&path, "old").unwrap();
+
+        write_json_export(&path, &serde_json::json!({ "items": [1, 2] })).unwrap();
+
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["items"], serde_json::json!([1, 2]));
+        assert!(!path.with_extension("json.tmp").exists());
+
+        assert!(write_json_export(Path::new("relative.json"), &1).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
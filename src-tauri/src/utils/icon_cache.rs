@@ -1,5 +1,5 @@
-use lru::LruCache;
-use std::num::NonZeroUsize;
+use crate::types::CacheStats;
+use crate::utils::byte_cache::ByteBudgetCache;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -18,9 +18,12 @@ const MAX_ICON_SIZE_FOR_BASE64: usize = 10_240; // 10KB
 /// Default icon cache capacity
 const DEFAULT_CACHE_CAPACITY: usize = 100;
 
+/// Default total size of cached icons (in bytes)
+const DEFAULT_BYTE_BUDGET: usize = 4 * 1024 * 1024; // 4MB
+
 /// Icon cache for storing extracted and encoded icons
 pub struct IconCache {
-    cache: Arc<RwLock<LruCache<PathBuf, String>>>,
+    cache: Arc<RwLock<ByteBudgetCache<PathBuf>>>,
 }
 
 impl IconCache {
@@ -31,9 +34,14 @@ impl IconCache {
 
     /// Creates a new IconCache with specified capacity
     pub fn with_capacity(capacity: usize) -> Self {
-        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(100).unwrap());
+        Self::with_limits(capacity, DEFAULT_BYTE_BUDGET)
+    }
+
+    /// Creates a new IconCache bounded by entry count and total icon bytes
+    pub fn with_limits(capacity: usize, byte_budget: usize) -> Self {
+        let capacity = if capacity == 0 { DEFAULT_CACHE_CAPACITY } else { capacity };
         Self {
-            cache: Arc::new(RwLock::new(LruCache::new(capacity))),
+            cache: Arc::new(RwLock::new(ByteBudgetCache::new(capacity, byte_budget))),
         }
     }
 
@@ -84,6 +92,21 @@ impl IconCache {
         debug!("Icon cache cleared");
     }
 
+    /// Drops the least recently used icons down to half the byte budget
+    ///
+    /// Returns the number of icons removed.
+    pub async fn trim(&self) -> usize {
+        let mut cache = self.cache.write().await;
+        let removed = cache.trim();
+        debug!("Icon cache trimmed {} entries", removed);
+        removed
+    }
+
+    /// Reports entry count and bytes held for diagnostics
+    pub async fn stats(&self) -> CacheStats {
+        self.cache.read().await.stats("Icons")
+    }
+
     /// Returns the number of cached icons
     pub async fn len(&self) -> usize {
         let cache = self.cache.read().await;
@@ -263,6 +286,23 @@ mod tests {
         assert_eq!(cache.get(&PathBuf::from("file3.txt")).await, Some("icon3".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_icon_cache_byte_budget() {
+        let cache = IconCache::with_limits(10, 12);
+
+        cache.put(PathBuf::from("file1.txt"), "icon-1".to_string()).await;
+        cache.put(PathBuf::from("file2.txt"), "icon-2".to_string()).await;
+        cache.put(PathBuf::from("file3.txt"), "icon-3".to_string()).await;
+
+        // Only two 6-byte icons fit in 12 bytes
+        assert_eq!(cache.get(&PathBuf::from("file1.txt")).await, None);
+        let stats = cache.stats().await;
+        assert_eq!((stats.entries, stats.bytes), (2, 12));
+
+        assert_eq!(cache.trim().await, 1);
+        assert_eq!(cache.len().await, 1);
+    }
+
     #[test]
     fn test_generic_icon_names() {
         assert_eq!(IconCache::get_generic_icon(&PathBuf::from("test.txt")), "file-text");
@@ -0,0 +1,397 @@
+use crate::error::{LauncherError, Result};
+use crate::settings::PreferredTerminal;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+/// Marks where the folder goes in a terminal's argument template
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// Registry key holding the Windows 11 "default terminal" choice
+#[cfg(windows)]
+const CONSOLE_STARTUP_KEY: &str = "Console\\%%Startup";
+
+/// Ids the "default terminal" setting stores for Windows Terminal and its preview
+const WINDOWS_TERMINAL_IDS: &[&str] = &[
+    "{2EACA947-7F5F-4CFA-BA87-8F7FBEEFBE69}",
+    "{06EC847C-C0A5-46B8-92CB-7C92F6E35CD5}",
+];
+
+/// Id the "default terminal" setting stores for the classic console host
+const CONSOLE_HOST_ID: &str = "{B23D10C0-E52E-411E-9D5B-C09FDF709C7D}";
+
+/// A program and its command line, ready to spawn
+///
+/// The command line is passed through verbatim, since Explorer and many
+/// terminals parse their own arguments instead of following the C runtime rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchCommand {
+    pub program: PathBuf,
+    pub command_line: String,
+    /// Working directory to start the program in
+    pub current_dir: Option<PathBuf>,
+}
+
+impl LaunchCommand {
+    fn new(program: impl Into<PathBuf>, command_line: String) -> Self {
+        Self {
+            program: program.into(),
+            command_line,
+            current_dir: None,
+        }
+    }
+
+    fn in_dir(self, folder: &str) -> Self {
+        Self {
+            current_dir: Some(PathBuf::from(folder)),
+            ..self
+        }
+    }
+
+    /// Starts the program without waiting for it
+    pub fn spawn(&self) -> Result<()> {
+        info!("Launching {} {}", self.program.display(), self.command_line);
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+
+            let mut command = std::process::Command::new(&self.program);
+            command.raw_arg(&self.command_line);
+            if let Some(dir) = &self.current_dir {
+                command.current_dir(dir);
+            }
+            command
+                .spawn()
+                .map_err(|e| {
+                    LauncherError::ExecutionError(format!("Failed to start {}: {}", self.program.display(), e))
+                })?;
+            Ok(())
+        }
+        #[cfg(not(windows))]
+        {
+            Err(LauncherError::ExecutionError(format!(
+                "Starting {} is not implemented for this platform",
+                self.program.display()
+            )))
+        }
+    }
+}
+
+/// Program that shows folders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileManager {
+    Explorer,
+    /// An executable taking the folder to show as its only argument
+    Custom(PathBuf),
+}
+
+impl FileManager {
+    /// Shows the contents of `folder`
+    pub fn open_folder(&self, folder: &str) -> LaunchCommand {
+        match self {
+            FileManager::Explorer => LaunchCommand::new("explorer.exe", quote_path(folder)),
+            FileManager::Custom(program) => LaunchCommand::new(program, quote_path(folder)),
+        }
+    }
+
+    /// Shows the folder holding `file`, with the file selected where the
+    /// file manager supports it
+    pub fn reveal(&self, file: &str) -> LaunchCommand {
+        match self {
+            FileManager::Explorer => LaunchCommand::new("explorer.exe", format!("/select,{}", quote_path(file))),
+            FileManager::Custom(_) => self.open_folder(parent_folder(file)),
+        }
+    }
+}
+
+/// Program that opens a shell in a folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Terminal {
+    WindowsTerminal,
+    /// The classic console running cmd
+    Console,
+    Custom(PreferredTerminal),
+}
+
+impl Terminal {
+    /// Opens a shell whose working directory is `folder`
+    ///
+    /// The console gets the folder as its working directory only, so cmd
+    /// never parses it. Folders whose name a terminal's command line can't
+    /// carry safely are refused.
+    pub fn open_in(&self, folder: &str) -> Result<LaunchCommand> {
+        let command = match self {
+            Terminal::WindowsTerminal => {
+                check_command_line_path(folder)?;
+                // wt splits its command line into tabs at every `;`, quoted or not
+                let escaped = quote_path(folder).replace(';', "\\;");
+                LaunchCommand::new("wt.exe", format!("-d {}", escaped))
+            }
+            Terminal::Console => LaunchCommand::new("cmd.exe", "/K".to_string()),
+            Terminal::Custom(terminal) => {
+                LaunchCommand::new(&terminal.path, substitute_path(&terminal.args_template, folder)?)
+            }
+        };
+        Ok(command.in_dir(folder))
+    }
+}
+
+/// The external tools chosen in settings
+///
+/// Tools are resolved each time they're used, so one that was uninstalled
+/// since it was configured falls back to the system default instead of failing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Launchers {
+    pub file_manager: Option<PathBuf>,
+    pub terminal: Option<PreferredTerminal>,
+}
+
+impl Launchers {
+    /// The configured file manager if it's installed, otherwise Explorer
+    pub fn file_manager(&self) -> FileManager {
+        match &self.file_manager {
+            Some(program) if program.is_file() => FileManager::Custom(program.clone()),
+            Some(program) => {
+                warn!("File manager {} is not installed, using Explorer", program.display());
+                FileManager::Explorer
+            }
+            None => FileManager::Explorer,
+        }
+    }
+
+    /// The configured terminal if it's installed, otherwise the system's
+    /// default terminal, otherwise the console
+    pub fn terminal(&self) -> Terminal {
+        self.terminal_with(detect_default_terminal)
+    }
+
+    fn terminal_with(&self, detect: impl FnOnce() -> Option<Terminal>) -> Terminal {
+        match &self.terminal {
+            Some(terminal) if terminal.path.is_file() => return Terminal::Custom(terminal.clone()),
+            Some(terminal) => warn!("Terminal {} is not installed, using the default", terminal.path.display()),
+            None => {}
+        }
+
+        detect().unwrap_or(Terminal::Console)
+    }
+}
+
+/// Checks that a terminal argument template says where the folder goes
+pub fn validate_args_template(template: &str) -> Result<()> {
+    if !template.contains(PATH_PLACEHOLDER) {
+        return Err(LauncherError::ConfigError(format!(
+            "Terminal arguments '{}' must contain {}",
+            template, PATH_PLACEHOLDER
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that a configured tool is an installed executable
+pub fn validate_program(program: &Path) -> Result<()> {
+    if !program.is_absolute() || !program.is_file() {
+        return Err(LauncherError::ConfigError(format!(
+            "{} is not an installed program",
+            program.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Replaces the placeholder in `template` with `folder`, quoting it unless
+/// the template already does
+pub fn substitute_path(template: &str, folder: &str) -> Result<String> {
+    check_command_line_path(folder)?;
+    let quoted_placeholder = format!("\"{}\"", PATH_PLACEHOLDER);
+    Ok(template
+        .split(&quoted_placeholder)
+        .map(|part| part.replace(PATH_PLACEHOLDER, &quote_path(folder)))
+        .collect::<Vec<_>>()
+        .join(&format!("\"{}\"", escape_trailing_backslashes(folder))))
+}
+
+/// Refuses paths that would break out of their quotes: `"` ends them, and
+/// a terminal running cmd expands `%VAR%` even inside them
+fn check_command_line_path(path: &str) -> Result<()> {
+    if path.contains(['"', '%']) {
+        return Err(LauncherError::ExecutionError(format!(
+            "{} can't be passed to a terminal, as it contains \" or %",
+            path
+        )));
+    }
+    Ok(())
+}
+
+/// Quotes a path for a command line
+fn quote_path(path: &str) -> String {
+    format!("\"{}\"", escape_trailing_backslashes(path))
+}
+
+/// Doubles trailing backslashes, which would otherwise escape a closing quote
+fn escape_trailing_backslashes(path: &str) -> String {
+    let trailing = path.len() - path.trim_end_matches('\\').len();
+    format!("{}{}", path, "\\".repeat(trailing))
+}
+
+/// Folder holding a Windows path, whichever separator it uses
+fn parent_folder(path: &str) -> &str {
+    let trimmed = path.trim_end_matches(['\\', '/']);
+    match trimmed.rfind(['\\', '/']) {
+        // Keep the separator of a drive root, since "C:" is the current directory on C:
+        Some(index) if trimmed[..index].ends_with(':') => &trimmed[..=index],
+        Some(index) => &trimmed[..index],
+        None => path,
+    }
+}
+
+/// Reads the Windows 11 "default terminal" setting
+///
+/// Returns `None` when it's unset or left to Windows, and for terminals
+/// other than Windows Terminal and the console.
+pub fn detect_default_terminal() -> Option<Terminal> {
+    let id = read_default_terminal_id()?;
+    debug!("Default terminal id: {}", id);
+    terminal_for_id(&id)
+}
+
+fn terminal_for_id(id: &str) -> Option<Terminal> {
+    if WINDOWS_TERMINAL_IDS.iter().any(|known| known.eq_ignore_ascii_case(id)) {
+        Some(Terminal::WindowsTerminal)
+    } else if CONSOLE_HOST_ID.eq_ignore_ascii_case(id) {
+        Some(Terminal::Console)
+    } else {
+        None
+    }
+}
+
+#[cfg(windows)]
+fn read_default_terminal_id() -> Option<String> {
+    use windows::core::HSTRING;
+    use windows::Win32::System::Registry::*;
+
+    unsafe {
+        let mut hkey = HKEY::default();
+        if RegOpenKeyExW(HKEY_CURRENT_USER, &HSTRING::from(CONSOLE_STARTUP_KEY), 0, KEY_READ, &mut hkey).is_err() {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; 64];
+        let mut buffer_size = (buffer.len() * 2) as u32;
+        let mut value_type = REG_VALUE_TYPE::default();
+        let result = RegQueryValueExW(
+            hkey,
+            &HSTRING::from("DelegationTerminal"),
+            None,
+            Some(&mut value_type),
+            Some(buffer.as_mut_ptr() as *mut u8),
+            Some(&mut buffer_size),
+        );
+        let _ = RegCloseKey(hkey);
+
+        if result.is_err() || value_type != REG_SZ {
+            return None;
+        }
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len]))
+    }
+}
+
+#[cfg(not(windows))]
+fn read_default_terminal_id() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed_program(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("better_finder_launchers_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let program = dir.join(name);
+        std::fs::write(&program, b"MZ").unwrap();
+        program
+    }
+
+    #[test]
+    fn test_setting_then_detected_default_then_console() {
+        let alacritty = PreferredTerminal {
+            path: installed_program("alacritty.exe"),
+            args_template: "--working-directory {path}".to_string(),
+        };
+        let configured = Launchers { terminal: Some(alacritty.clone()), ..Launchers::default() };
+        assert_eq!(configured.terminal_with(|| Some(Terminal::WindowsTerminal)), Terminal::Custom(alacritty));
+
+        let unset = Launchers::default();
+        assert_eq!(unset.terminal_with(|| Some(Terminal::WindowsTerminal)), Terminal::WindowsTerminal);
+        assert_eq!(unset.terminal_with(|| None), Terminal::Console);
+
+        assert_eq!(terminal_for_id("{2eaca947-7f5f-4cfa-ba87-8f7fbeefbe69}"), Some(Terminal::WindowsTerminal));
+        assert_eq!(terminal_for_id("{00000000-0000-0000-0000-000000000000}"), None);
+
+        let files = installed_program("Files.exe");
+        let launchers = Launchers { file_manager: Some(files.clone()), ..Launchers::default() };
+        assert_eq!(launchers.file_manager(), FileManager::Custom(files));
+        assert_eq!(unset.file_manager(), FileManager::Explorer);
+    }
+
+    #[test]
+    fn test_uninstalled_tools_fall_back() {
+        let gone = std::env::temp_dir().join("better_finder_launchers_test").join("uninstalled.exe");
+        let launchers = Launchers {
+            file_manager: Some(gone.clone()),
+            terminal: Some(PreferredTerminal { path: gone.clone(), args_template: "{path}".to_string() }),
+        };
+
+        assert_eq!(launchers.file_manager(), FileManager::Explorer);
+        assert_eq!(launchers.terminal_with(|| Some(Terminal::WindowsTerminal)), Terminal::WindowsTerminal);
+        assert_eq!(launchers.terminal_with(|| None), Terminal::Console);
+        assert!(validate_program(&gone).is_err());
+    }
+
+    #[test]
+    fn test_paths_are_quoted_in_command_lines() {
+        assert_eq!(substitute_path("-d {path}", r"C:\Projects").unwrap(), r#"-d "C:\Projects""#);
+        assert_eq!(substitute_path("-d {path}", r"C:\My Projects").unwrap(), r#"-d "C:\My Projects""#);
+        // A quoted placeholder isn't quoted twice, and a trailing backslash can't escape the quote
+        assert_eq!(substitute_path(r#"--cwd "{path}" --title {path}"#, r"C:\My Projects\").unwrap(), r#"--cwd "C:\My Projects\\" --title "C:\My Projects\\""#);
+        let console = Terminal::Console.open_in(r"D:\").unwrap();
+        assert_eq!(console.command_line, "/K");
+        assert_eq!(console.current_dir, Some(PathBuf::from(r"D:\")));
+
+        let reveal = FileManager::Explorer.reveal(r"C:\Users\me\My Report.pdf");
+        assert_eq!(reveal.command_line, r#"/select,"C:\Users\me\My Report.pdf""#);
+        let files = FileManager::Custom(PathBuf::from(r"C:\Apps\Files.exe"));
+        assert_eq!(files.reveal(r"C:\Users\My Name\report.pdf").command_line, r#""C:\Users\My Name""#);
+        assert_eq!(files.reveal(r"C:\notes.txt").command_line, r#""C:\\""#);
+
+        assert!(validate_args_template("--cwd {path}").is_ok());
+        assert!(validate_args_template("--cwd").is_err());
+    }
+
+    #[test]
+    fn test_folder_names_cannot_inject_commands() {
+        // cmd never sees the folder: it starts there
+        for folder in [r"C:\x&calc", r"C:\x^&calc", r"C:\x;calc", r"C:\100%PATH%"] {
+            let console = Terminal::Console.open_in(folder).unwrap();
+            assert_eq!(console.command_line, "/K", "{}", folder);
+            assert_eq!(console.current_dir, Some(PathBuf::from(folder)));
+        }
+
+        // Quoted, with wt's tab separator escaped
+        let wt = |folder| Terminal::WindowsTerminal.open_in(folder).map(|command| command.command_line);
+        assert_eq!(wt(r"C:\x&calc").unwrap(), r#"-d "C:\x&calc""#);
+        assert_eq!(wt(r"C:\x^calc").unwrap(), r#"-d "C:\x^calc""#);
+        assert_eq!(wt(r"C:\x;calc").unwrap(), r#"-d "C:\x\;calc""#);
+        assert!(wt(r"C:\%COMSPEC%").is_err());
+
+        let custom = Terminal::Custom(PreferredTerminal {
+            path: PathBuf::from(r"C:\Apps\term.exe"),
+            args_template: "/c cd {path}".to_string(),
+        });
+        assert_eq!(custom.open_in(r"C:\x&calc").unwrap().command_line, r#"/c cd "C:\x&calc""#);
+        assert_eq!(custom.open_in(r"C:\x^&calc").unwrap().command_line, r#"/c cd "C:\x^&calc""#);
+        assert!(custom.open_in(r"C:\%USERPROFILE%").is_err());
+        assert!(custom.open_in(r#"C:\x" & calc & ""#).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::{Duration, Instant};
+
+/// How often the background task compares the working set to the threshold
+pub const MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Minimum time between two automatic trims
+///
+/// Trimming rarely brings the working set back under the threshold at once,
+/// since the allocator keeps freed pages, so trims are spaced out rather than
+/// repeated on every check.
+const TRIM_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+/// Returns the process working set in bytes, where the platform reports it
+#[cfg(windows)]
+pub fn process_working_set() -> Option<u64> {
+    use windows::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+    use windows::Win32::System::Threading::GetCurrentProcess;
+
+    let mut counters = PROCESS_MEMORY_COUNTERS::default();
+    let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+    unsafe { GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) }.ok()?;
+    Some(counters.WorkingSetSize as u64)
+}
+
+#[cfg(not(windows))]
+pub fn process_working_set() -> Option<u64> {
+    None
+}
+
+/// Decides when the working set is high enough to trim caches automatically
+#[derive(Debug)]
+pub struct MemoryWatchdog {
+    /// Working set above which caches are trimmed; 0 disables the watchdog
+    threshold_bytes: u64,
+    last_trim: Option<Instant>,
+}
+
+impl MemoryWatchdog {
+    pub fn new(threshold_mb: u64) -> Self {
+        Self {
+            threshold_bytes: threshold_mb.saturating_mul(1024 * 1024),
+            last_trim: None,
+        }
+    }
+
+    /// Changes the threshold, e.g. after the settings were saved
+    pub fn set_threshold_mb(&mut self, threshold_mb: u64) {
+        self.threshold_bytes = threshold_mb.saturating_mul(1024 * 1024);
+    }
+
+    /// Returns whether caches should be trimmed now, recording the trim if so
+    pub fn should_trim(&mut self, working_set: u64, now: Instant) -> bool {
+        if self.threshold_bytes == 0 || working_set <= self.threshold_bytes {
+            return false;
+        }
+        if self
+            .last_trim
+            .is_some_and(|last| now.saturating_duration_since(last) < TRIM_COOLDOWN)
+        {
+            return false;
+        }
+
+        self.last_trim = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn test_trims_only_above_threshold() {
+        let mut watchdog = MemoryWatchdog::new(400);
+        let now = Instant::now();
+
+        assert!(!watchdog.should_trim(300 * MB, now));
+        assert!(!watchdog.should_trim(400 * MB, now));
+        assert!(watchdog.should_trim(401 * MB, now));
+    }
+
+    #[test]
+    fn test_cooldown_between_trims() {
+        let mut watchdog = MemoryWatchdog::new(400);
+        let start = Instant::now();
+
+        assert!(watchdog.should_trim(500 * MB, start));
+        assert!(!watchdog.should_trim(500 * MB, start + MEMORY_CHECK_INTERVAL));
+        assert!(watchdog.should_trim(500 * MB, start + TRIM_COOLDOWN));
+    }
+
+    #[test]
+    fn test_zero_threshold_disables_trimming() {
+        let mut watchdog = MemoryWatchdog::new(0);
+        assert!(!watchdog.should_trim(u64::MAX, Instant::now()));
+
+        watchdog.set_threshold_mb(100);
+        assert!(watchdog.should_trim(200 * MB, Instant::now()));
+    }
+}
@@ -3,6 +3,21 @@ pub mod validation;
 pub mod theme;
 pub mod icon_cache;
 pub mod notification;
+pub mod clipboard;
+pub mod text;
+pub mod recycle_bin;
+pub mod time;
+pub mod export;
+pub mod byte_cache;
+pub mod canonical_path;
+pub mod memory;
+pub mod structured_text;
+pub mod deeplink;
+pub mod sound;
+pub mod launchers;
+pub mod db_integrity;
+pub mod process;
+pub mod show_latency;
 
 #[cfg(test)]
 mod theme_test;
@@ -11,3 +26,7 @@ pub use logging::init_logging;
 pub use validation::*;
 pub use icon_cache::IconCache;
 pub use notification::*;
+pub use text::normalize_for_search;
+pub use recycle_bin::move_to_recycle_bin;
+pub use export::write_json_export;
+pub use byte_cache::ByteBudgetCache;
@@ -1,5 +1,14 @@
-use tauri::{AppHandle, Emitter};
+use crate::settings::{NotificationCategory, NotificationRoute};
 use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Event carrying notifications routed to the in-launcher banner
+pub const NOTIFICATION_EVENT: &str = "notification";
+
+/// Banners held for the next time the launcher opens; older ones are dropped
+pub const MAX_QUEUED_NOTIFICATIONS: usize = 10;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct NotificationPayload {
@@ -7,58 +16,293 @@ pub struct NotificationPayload {
     pub message: Option<String>,
 }
 
-/// Send an error notification to the frontend
-pub fn notify_error(app: &AppHandle, title: impl Into<String>, message: Option<impl Into<String>>) {
-    let payload = NotificationPayload {
-        title: title.into(),
-        message: message.map(|m| m.into()),
-    };
-    
-    tracing::error!("Error notification: {} - {:?}", payload.title, payload.message);
-    
-    if let Err(e) = app.emit("error", &payload) {
-        tracing::error!("Failed to emit error event: {}", e);
+/// How serious a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Success,
+    Info,
+}
+
+impl Severity {
+    /// Event the toast for this severity is emitted as
+    fn toast_event(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Success => "success",
+            Severity::Info => "info",
+        }
+    }
+
+    /// Milliseconds a banner of this severity stays before closing itself
+    pub fn auto_dismiss_ms(self) -> u64 {
+        match self {
+            Severity::Error => 10_000,
+            Severity::Warning => 8_000,
+            Severity::Success | Severity::Info => 4_000,
+        }
     }
 }
 
-/// Send a success notification to the frontend
-pub fn notify_success(app: &AppHandle, title: impl Into<String>, message: Option<impl Into<String>>) {
-    let payload = NotificationPayload {
-        title: title.into(),
-        message: message.map(|m| m.into()),
+/// A notification shown as a banner in the launcher, the payload of `NOTIFICATION_EVENT`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InAppNotification {
+    pub category: NotificationCategory,
+    pub severity: Severity,
+    pub title: String,
+    pub message: Option<String>,
+    pub auto_dismiss_ms: u64,
+}
+
+/// Where one notification goes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Delivery {
+    pub toast: bool,
+    /// Shown as a banner right away
+    pub banner: bool,
+    /// Held as a banner until the launcher next opens
+    pub queued: bool,
+}
+
+/// Decides where a notification on `route` goes
+///
+/// A banner can only show while the launcher is open, so an in-app-only
+/// notification waits for the next open instead. One also routed to a toast
+/// has been seen by then and is not repeated.
+pub fn delivery(route: NotificationRoute, window_visible: bool) -> Delivery {
+    match route {
+        NotificationRoute::Off => Delivery::default(),
+        NotificationRoute::Toast => Delivery { toast: true, ..Delivery::default() },
+        NotificationRoute::InApp => Delivery {
+            banner: window_visible,
+            queued: !window_visible,
+            ..Delivery::default()
+        },
+        NotificationRoute::Both => Delivery {
+            toast: true,
+            banner: window_visible,
+            queued: false,
+        },
+    }
+}
+
+/// Routes notifications by category, as the `notification_routing` setting says
+#[derive(Debug, Default)]
+pub struct NotificationRouter {
+    routing: Mutex<HashMap<NotificationCategory, NotificationRoute>>,
+    /// Banners waiting for the launcher to open, oldest first
+    queued: Mutex<VecDeque<InAppNotification>>,
+}
+
+impl NotificationRouter {
+    /// Creates a router using each category's default route
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the `notification_routing` setting
+    pub fn configure(&self, routing: HashMap<NotificationCategory, NotificationRoute>) {
+        *self.routing.lock().unwrap_or_else(|e| e.into_inner()) = routing;
+    }
+
+    /// The route notifications of `category` take
+    pub fn route_for(&self, category: NotificationCategory) -> NotificationRoute {
+        let routing = self.routing.lock().unwrap_or_else(|e| e.into_inner());
+        routing.get(&category).copied().unwrap_or_else(|| category.default_route())
+    }
+
+    /// Decides where `notification` goes, queueing its banner if it has to
+    /// wait for the launcher to open
+    pub fn route(&self, notification: &InAppNotification, window_visible: bool) -> Delivery {
+        let delivery = delivery(self.route_for(notification.category), window_visible);
+        if delivery.queued {
+            let mut queued = self.queued.lock().unwrap_or_else(|e| e.into_inner());
+            if queued.len() == MAX_QUEUED_NOTIFICATIONS {
+                queued.pop_front();
+            }
+            queued.push_back(notification.clone());
+        }
+        delivery
+    }
+
+    /// Takes the banners waiting for the launcher, oldest first
+    pub fn take_queued(&self) -> Vec<InAppNotification> {
+        self.queued.lock().unwrap_or_else(|e| e.into_inner()).drain(..).collect()
+    }
+}
+
+fn notify(
+    app: &AppHandle,
+    category: NotificationCategory,
+    severity: Severity,
+    title: String,
+    message: Option<String>,
+) {
+    match severity {
+        Severity::Error => tracing::error!("Error notification: {} - {:?}", title, message),
+        Severity::Warning => tracing::warn!("Warning notification: {} - {:?}", title, message),
+        Severity::Success => tracing::info!("Success notification: {} - {:?}", title, message),
+        Severity::Info => tracing::info!("Info notification: {} - {:?}", title, message),
+    }
+
+    let notification = InAppNotification {
+        category,
+        severity,
+        title,
+        message,
+        auto_dismiss_ms: severity.auto_dismiss_ms(),
+    };
+    let window_visible = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+    let delivery = match app.try_state::<Arc<NotificationRouter>>() {
+        Some(router) => router.route(&notification, window_visible),
+        None => delivery(category.default_route(), window_visible),
     };
-    
-    tracing::info!("Success notification: {} - {:?}", payload.title, payload.message);
-    
-    if let Err(e) = app.emit("success", &payload) {
-        tracing::error!("Failed to emit success event: {}", e);
+
+    if delivery.toast {
+        let payload = NotificationPayload {
+            title: notification.title.clone(),
+            message: notification.message.clone(),
+        };
+        if let Err(e) = app.emit(severity.toast_event(), &payload) {
+            tracing::error!("Failed to emit {} event: {}", severity.toast_event(), e);
+        }
+    }
+    if delivery.banner {
+        if let Err(e) = app.emit(NOTIFICATION_EVENT, &notification) {
+            tracing::error!("Failed to emit {} event: {}", NOTIFICATION_EVENT, e);
+        }
     }
 }
 
-/// Send a warning notification to the frontend
-pub fn notify_warning(app: &AppHandle, title: impl Into<String>, message: Option<impl Into<String>>) {
-    let payload = NotificationPayload {
-        title: title.into(),
-        message: message.map(|m| m.into()),
+/// Shows the banners held while the launcher was hidden; called once it is visible
+pub fn show_queued_notifications(app: &AppHandle) {
+    let Some(router) = app.try_state::<Arc<NotificationRouter>>() else {
+        return;
     };
-    
-    tracing::warn!("Warning notification: {} - {:?}", payload.title, payload.message);
-    
-    if let Err(e) = app.emit("warning", &payload) {
-        tracing::error!("Failed to emit warning event: {}", e);
+    for notification in router.take_queued() {
+        if let Err(e) = app.emit(NOTIFICATION_EVENT, &notification) {
+            tracing::error!("Failed to emit {} event: {}", NOTIFICATION_EVENT, e);
+        }
     }
 }
 
+/// Send an error notification to the frontend
+pub fn notify_error(
+    app: &AppHandle,
+    category: NotificationCategory,
+    title: impl Into<String>,
+    message: Option<impl Into<String>>,
+) {
+    notify(app, category, Severity::Error, title.into(), message.map(|m| m.into()));
+}
+
+/// Send a success notification to the frontend
+pub fn notify_success(
+    app: &AppHandle,
+    category: NotificationCategory,
+    title: impl Into<String>,
+    message: Option<impl Into<String>>,
+) {
+    notify(app, category, Severity::Success, title.into(), message.map(|m| m.into()));
+}
+
+/// Send a warning notification to the frontend
+pub fn notify_warning(
+    app: &AppHandle,
+    category: NotificationCategory,
+    title: impl Into<String>,
+    message: Option<impl Into<String>>,
+) {
+    notify(app, category, Severity::Warning, title.into(), message.map(|m| m.into()));
+}
+
 /// Send an info notification to the frontend
-pub fn notify_info(app: &AppHandle, title: impl Into<String>, message: Option<impl Into<String>>) {
-    let payload = NotificationPayload {
-        title: title.into(),
-        message: message.map(|m| m.into()),
-    };
-    
-    tracing::info!("Info notification: {} - {:?}", payload.title, payload.message);
-    
-    if let Err(e) = app.emit("info", &payload) {
-        tracing::error!("Failed to emit info event: {}", e);
+pub fn notify_info(
+    app: &AppHandle,
+    category: NotificationCategory,
+    title: impl Into<String>,
+    message: Option<impl Into<String>>,
+) {
+    notify(app, category, Severity::Info, title.into(), message.map(|m| m.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(category: NotificationCategory, title: &str) -> InAppNotification {
+        InAppNotification {
+            category,
+            severity: Severity::Warning,
+            title: title.to_string(),
+            message: None,
+            auto_dismiss_ms: Severity::Warning.auto_dismiss_ms(),
+        }
+    }
+
+    #[test]
+    fn test_delivery_matrix() {
+        let cases = [
+            (NotificationRoute::Toast, true, (true, false, false)),
+            (NotificationRoute::Toast, false, (true, false, false)),
+            (NotificationRoute::InApp, true, (false, true, false)),
+            (NotificationRoute::InApp, false, (false, false, true)),
+            (NotificationRoute::Both, true, (true, true, false)),
+            (NotificationRoute::Both, false, (true, false, false)),
+            (NotificationRoute::Off, true, (false, false, false)),
+            (NotificationRoute::Off, false, (false, false, false)),
+        ];
+        for (route, visible, (toast, banner, queued)) in cases {
+            assert_eq!(
+                delivery(route, visible),
+                Delivery { toast, banner, queued },
+                "{:?} with the window visible: {}",
+                route,
+                visible
+            );
+        }
+    }
+
+    #[test]
+    fn test_hidden_window_queues_in_app_banners() {
+        let router = NotificationRouter::new();
+        router.configure(HashMap::from([
+            (NotificationCategory::ProviderHealth, NotificationRoute::InApp),
+            (NotificationCategory::Execution, NotificationRoute::Off),
+        ]));
+
+        let delivery = router.route(&notification(NotificationCategory::ProviderHealth, "limited"), false);
+        assert!(delivery.queued && !delivery.toast);
+        router.route(&notification(NotificationCategory::Execution, "copied"), false);
+        router.route(&notification(NotificationCategory::ProviderHealth, "shown"), true);
+
+        let queued = router.take_queued();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].title, "limited");
+        assert!(router.take_queued().is_empty());
+
+        for i in 0..MAX_QUEUED_NOTIFICATIONS + 3 {
+            router.route(&notification(NotificationCategory::ProviderHealth, &i.to_string()), false);
+        }
+        let queued = router.take_queued();
+        assert_eq!(queued.len(), MAX_QUEUED_NOTIFICATIONS);
+        assert_eq!(queued[0].title, "3");
+    }
+
+    #[test]
+    fn test_unrouted_categories_use_defaults() {
+        let router = NotificationRouter::new();
+        router.configure(HashMap::from([(NotificationCategory::Updates, NotificationRoute::Off)]));
+
+        assert_eq!(router.route_for(NotificationCategory::Updates), NotificationRoute::Off);
+        assert_eq!(router.route_for(NotificationCategory::ProviderHealth), NotificationRoute::InApp);
+        assert_eq!(router.route_for(NotificationCategory::General), NotificationRoute::Toast);
+        assert_eq!(router.route(&notification(NotificationCategory::Updates, "update"), true), Delivery::default());
     }
 }
@@ -0,0 +1,343 @@
+use crate::error::{LauncherError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::process::{Child, Command};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// The same command started again within this window is coalesced, so a UI
+/// firing twice doesn't open everything twice
+pub const COALESCE_WINDOW: Duration = Duration::from_millis(300);
+
+/// How long commands expected to finish at once, such as `start` or
+/// `rundll32`, are waited for before they are left running
+pub const INSTANT_EXIT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How often a waited-for process is polled
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Launched processes kept for diagnostics; the oldest are dropped first
+const MAX_TRACKED: usize = 32;
+
+/// Whether to wait for a process after starting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wait {
+    /// Leave it running, e.g. an application
+    Detached,
+    /// Wait up to this long and report a failing exit code
+    UpTo(Duration),
+}
+
+/// What happened to a command handed to `ProcessTracker::run`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnOutcome {
+    /// Started and still running, or not waited for
+    Started { pid: u32 },
+    /// Finished successfully within the wait
+    Exited,
+    /// Skipped, as the same command was just started
+    Coalesced,
+}
+
+/// State of a launched process when last checked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ProcessState {
+    Running,
+    /// `code` is `None` when the process was terminated
+    Exited { code: Option<i32> },
+}
+
+/// A process started through the tracker, as reported by `get_diagnostics`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LaunchedProcess {
+    /// What the process was started for, e.g. `open_file`
+    pub kind: String,
+    pub command_line: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub state: ProcessState,
+}
+
+/// A launched process and, while it may still run, its handle
+struct Tracked {
+    process: LaunchedProcess,
+    child: Option<Child>,
+}
+
+impl Tracked {
+    /// Updates the state from the handle, dropping it once the process exited
+    fn refresh(&mut self) {
+        let Some(child) = &mut self.child else {
+            return;
+        };
+
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                self.process.state = ProcessState::Exited { code: status.code() };
+                self.child = None;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                debug!("Failed to poll process {}: {}", self.process.pid, e);
+                self.child = None;
+            }
+        }
+    }
+}
+
+/// Starts processes for the execute paths
+///
+/// Coalesces repeated starts of the same command, optionally waits for
+/// commands that should finish at once so their failures surface, and keeps
+/// the last launched processes for diagnostics.
+pub struct ProcessTracker {
+    coalesce_window: Duration,
+    /// When each command was last started, keyed by kind and command line
+    recent: Mutex<HashMap<String, Instant>>,
+    launched: Mutex<VecDeque<Tracked>>,
+}
+
+impl ProcessTracker {
+    pub fn new(coalesce_window: Duration) -> Self {
+        Self {
+            coalesce_window,
+            recent: Mutex::new(HashMap::new()),
+            launched: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Returns true if `key` was started within the coalesce window, and
+    /// records the start otherwise
+    fn should_coalesce(&self, key: &str, now: Instant) -> bool {
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        recent.retain(|_, started| now.duration_since(*started) < self.coalesce_window);
+
+        if recent.contains_key(key) {
+            return true;
+        }
+        recent.insert(key.to_string(), now);
+        false
+    }
+
+    /// Forgets the start of `key`, so retrying a failed command isn't coalesced
+    fn forget(&self, key: &str) {
+        self.recent.lock().unwrap_or_else(|e| e.into_inner()).remove(key);
+    }
+
+    /// Starts `command`, coalescing repeats and waiting as `wait` says
+    ///
+    /// A process exiting with a failure code within the wait is an error,
+    /// mapped by `exit_code_error`.
+    pub async fn run(&self, kind: &str, command: Command, wait: Wait) -> Result<SpawnOutcome> {
+        let command_line = command_line(&command);
+        let key = format!("{}:{}", kind, command_line);
+        if self.should_coalesce(&key, Instant::now()) {
+            debug!("Coalesced repeated {} command: {}", kind, command_line);
+            return Ok(SpawnOutcome::Coalesced);
+        }
+
+        let outcome = self.spawn(kind, command, command_line, wait).await;
+        if outcome.is_err() {
+            self.forget(&key);
+        }
+        outcome
+    }
+
+    async fn spawn(&self, kind: &str, mut command: Command, command_line: String, wait: Wait) -> Result<SpawnOutcome> {
+
+        info!("Starting {} command: {}", kind, command_line);
+        let started_at = Utc::now();
+        let mut child = command.spawn().map_err(|e| {
+            LauncherError::ExecutionError(format!("Failed to start {}: {}", program_name(&command), e))
+        })?;
+        let pid = child.id();
+
+        let mut state = ProcessState::Running;
+        if let Wait::UpTo(timeout) = wait {
+            let deadline = Instant::now() + timeout;
+            loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => {
+                        state = ProcessState::Exited { code: status.code() };
+                        break;
+                    }
+                    Ok(None) if Instant::now() < deadline => tokio::time::sleep(POLL_INTERVAL).await,
+                    Ok(None) => break,
+                    Err(e) => {
+                        warn!("Failed to wait for {}: {}", command_line, e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let process = LaunchedProcess {
+            kind: kind.to_string(),
+            command_line,
+            pid,
+            started_at,
+            state: state.clone(),
+        };
+        let child = (state == ProcessState::Running).then_some(child);
+        self.track(Tracked { process, child });
+
+        match state {
+            ProcessState::Running => Ok(SpawnOutcome::Started { pid }),
+            ProcessState::Exited { code: Some(0) } => Ok(SpawnOutcome::Exited),
+            ProcessState::Exited { code } => Err(exit_code_error(&program_name(&command), code)),
+        }
+    }
+
+    fn track(&self, tracked: Tracked) {
+        let mut launched = self.launched.lock().unwrap_or_else(|e| e.into_inner());
+        launched.push_back(tracked);
+        while launched.len() > MAX_TRACKED {
+            launched.pop_front();
+        }
+    }
+
+    /// The last launched processes, oldest first, with their current state
+    pub fn launched(&self) -> Vec<LaunchedProcess> {
+        let mut launched = self.launched.lock().unwrap_or_else(|e| e.into_inner());
+        launched
+            .iter_mut()
+            .map(|tracked| {
+                tracked.refresh();
+                tracked.process.clone()
+            })
+            .collect()
+    }
+}
+
+/// The tracker shared by every execute path
+pub fn tracker() -> &'static ProcessTracker {
+    static TRACKER: OnceLock<ProcessTracker> = OnceLock::new();
+    TRACKER.get_or_init(|| ProcessTracker::new(COALESCE_WINDOW))
+}
+
+/// Starts `command` through the shared tracker, see `ProcessTracker::run`
+pub async fn start(kind: &str, command: Command, wait: Wait) -> Result<()> {
+    tracker().run(kind, command, wait).await.map(|_| ())
+}
+
+/// Maps a failing exit code to an error saying what went wrong
+///
+/// The well-known Windows codes are spelled out; others are reported as is.
+pub fn exit_code_error(program: &str, code: Option<i32>) -> LauncherError {
+    match code {
+        Some(2) => LauncherError::NotFound(format!("{}: the file was not found", program)),
+        Some(3) => LauncherError::NotFound(format!("{}: the path was not found", program)),
+        Some(5) => LauncherError::ExecutionError(format!("{}: access was denied", program)),
+        Some(1115) | Some(1190) => {
+            LauncherError::ExecutionError(format!("{}: a shutdown is already in progress", program))
+        }
+        Some(1223) => LauncherError::ExecutionError(format!("{}: the operation was cancelled", program)),
+        Some(code) => LauncherError::ExecutionError(format!("{} exited with code {}", program, code)),
+        None => LauncherError::ExecutionError(format!("{} was terminated", program)),
+    }
+}
+
+/// The program's file name, for messages
+fn program_name(command: &Command) -> String {
+    std::path::Path::new(command.get_program())
+        .file_name()
+        .unwrap_or(command.get_program())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// The program and its arguments, joined by spaces
+fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `script` with the platform's shell
+    fn shell(script: &str) -> Command {
+        #[cfg(windows)]
+        {
+            let mut command = Command::new("cmd");
+            command.args(["/C", script]);
+            command
+        }
+        #[cfg(not(windows))]
+        {
+            let mut command = Command::new("sh");
+            command.args(["-c", script]);
+            command
+        }
+    }
+
+    /// A command that keeps running for about two seconds
+    fn slow_command() -> Command {
+        if cfg!(windows) {
+            shell("ping -n 3 127.0.0.1 >NUL")
+        } else {
+            shell("sleep 2")
+        }
+    }
+
+    #[test]
+    fn test_repeated_command_coalesced_within_window() {
+        let tracker = ProcessTracker::new(COALESCE_WINDOW);
+        let start = Instant::now();
+
+        assert!(!tracker.should_coalesce("open_url:https://a.dev", start));
+        assert!(tracker.should_coalesce("open_url:https://a.dev", start + Duration::from_millis(100)));
+        assert!(!tracker.should_coalesce("open_url:https://b.dev", start + Duration::from_millis(100)));
+        assert!(!tracker.should_coalesce("open_url:https://a.dev", start + Duration::from_millis(400)));
+    }
+
+    #[tokio::test]
+    async fn test_wait_reports_fast_exit() {
+        let tracker = ProcessTracker::new(COALESCE_WINDOW);
+
+        let outcome = tracker.run("test", shell("exit 0"), Wait::UpTo(Duration::from_secs(5))).await;
+        assert_eq!(outcome.unwrap(), SpawnOutcome::Exited);
+        let again = tracker.run("test", shell("exit 0"), Wait::UpTo(Duration::from_secs(5))).await;
+        assert_eq!(again.unwrap(), SpawnOutcome::Coalesced);
+
+        // A failed command can be retried at once
+        for _ in 0..2 {
+            let failure = tracker.run("test", shell("exit 2"), Wait::UpTo(Duration::from_secs(5))).await;
+            assert!(matches!(failure, Err(LauncherError::NotFound(_))));
+        }
+
+        let launched = tracker.launched();
+        assert_eq!(launched.len(), 3);
+        assert_eq!(launched[0].state, ProcessState::Exited { code: Some(0) });
+        assert_eq!(launched[2].state, ProcessState::Exited { code: Some(2) });
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out_on_slow_command() {
+        let tracker = ProcessTracker::new(COALESCE_WINDOW);
+
+        let started = Instant::now();
+        let outcome = tracker.run("test", slow_command(), Wait::UpTo(Duration::from_millis(200))).await;
+        assert!(matches!(outcome, Ok(SpawnOutcome::Started { .. })));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(tracker.launched()[0].state, ProcessState::Running);
+    }
+
+    #[test]
+    fn test_exit_codes_mapped_to_errors() {
+        assert!(matches!(exit_code_error("cmd", Some(2)), LauncherError::NotFound(_)));
+        assert!(matches!(exit_code_error("cmd", Some(3)), LauncherError::NotFound(_)));
+        assert!(exit_code_error("shutdown", Some(1190)).to_string().contains("shutdown is already in progress"));
+        assert!(exit_code_error("rundll32.exe", Some(1223)).to_string().contains("cancelled"));
+        assert!(exit_code_error("cmd", Some(7)).to_string().contains("cmd exited with code 7"));
+        assert!(exit_code_error("cmd", None).to_string().contains("cmd was terminated"));
+    }
+}
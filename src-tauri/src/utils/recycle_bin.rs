@@ -0,0 +1,297 @@
+use crate::error::{LauncherError, Result};
+use crate::utils::canonical_path::{PathResolver, SystemResolver};
+use std::path::Path;
+
+/// `GetDriveTypeW` results, which the enabled windows features don't expose
+#[cfg(windows)]
+const DRIVE_FIXED: u32 = 3;
+#[cfg(windows)]
+const DRIVE_REMOTE: u32 = 4;
+
+/// Returns true for UNC paths (`\\server\share`, `\\?\UNC\server\share`)
+///
+/// Extended-length local paths such as `\\?\C:\file` are not network paths.
+pub fn is_unc_path(path: &str) -> bool {
+    let normalized = path.replace('/', "\\");
+
+    if let Some(rest) = normalized.strip_prefix("\\\\?\\") {
+        return rest.len() >= 4 && rest[..4].eq_ignore_ascii_case("UNC\\");
+    }
+
+    normalized.starts_with("\\\\")
+}
+
+/// Type of the drive holding `path`, or `None` when its volume can't be found
+#[cfg(windows)]
+fn drive_type(path: &str) -> Option<u32> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Storage::FileSystem::{GetDriveTypeW, GetVolumePathNameW};
+
+    let wide_path: Vec<u16> = OsStr::new(path)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut volume = [0u16; 261];
+
+    unsafe {
+        if GetVolumePathNameW(windows::core::PCWSTR(wide_path.as_ptr()), &mut volume).is_err() {
+            return None;
+        }
+        Some(GetDriveTypeW(windows::core::PCWSTR(volume.as_ptr())))
+    }
+}
+
+/// Returns true if the path lives on a network share or a mapped network drive
+#[cfg(windows)]
+pub fn is_network_path(path: &str) -> bool {
+    is_unc_path(path) || drive_type(path) == Some(DRIVE_REMOTE)
+}
+
+#[cfg(not(windows))]
+pub fn is_network_path(path: &str) -> bool {
+    is_unc_path(path)
+}
+
+/// Returns true if the path lives on a fixed local drive, the only kind
+/// with a Recycle Bin; removable, optical and RAM drives have none
+#[cfg(windows)]
+pub fn is_fixed_drive(path: &str) -> bool {
+    drive_type(path) == Some(DRIVE_FIXED)
+}
+
+#[cfg(not(windows))]
+pub fn is_fixed_drive(_path: &str) -> bool {
+    true
+}
+
+/// Moves a file or folder to the Recycle Bin so the deletion can be undone
+///
+/// Network shares and removable drives have no Recycle Bin, and neither do
+/// paths the shell can't fully qualify, so it would delete them permanently;
+/// those paths are refused. This call blocks; run it with `spawn_blocking`.
+#[cfg(windows)]
+pub fn move_to_recycle_bin(path: &str) -> Result<()> {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Foundation::*;
+    use windows::Win32::UI::Shell::*;
+
+    let full_path = check_recyclable(path)?;
+
+    // pFrom is a list of paths terminated by an extra null
+    let wide_path: Vec<u16> = OsStr::new(full_path)
+        .encode_wide()
+        .chain([0, 0])
+        .collect();
+
+    let flags = FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_SILENT | FOF_NOERRORUI;
+    let mut operation = SHFILEOPSTRUCTW {
+        hwnd: HWND(std::ptr::null_mut()),
+        wFunc: FO_DELETE,
+        pFrom: windows::core::PCWSTR(wide_path.as_ptr()),
+        pTo: windows::core::PCWSTR::null(),
+        fFlags: flags.0 as u16,
+        fAnyOperationsAborted: BOOL(0),
+        hNameMappings: std::ptr::null_mut(),
+        lpszProgressTitle: windows::core::PCWSTR::null(),
+    };
+
+    let code = unsafe { SHFileOperationW(&mut operation) };
+
+    if code != 0 {
+        return Err(LauncherError::ExecutionError(format!(
+            "Failed to move {} to the Recycle Bin (error {:#x})",
+            path, code
+        )));
+    }
+
+    if operation.fAnyOperationsAborted.as_bool() {
+        return Err(LauncherError::ExecutionError(format!(
+            "Moving {} to the Recycle Bin was cancelled",
+            path
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn move_to_recycle_bin(path: &str) -> Result<()> {
+    check_recyclable(path)?;
+
+    Err(LauncherError::ExecutionError(
+        "Recycle Bin operations not supported on this platform".to_string(),
+    ))
+}
+
+/// Rejects paths that cannot be recycled before the shell is involved,
+/// returning the path to hand it
+///
+/// That is the path as given, never the one it resolves to: recycling a
+/// symlink or junction must remove the link, not the folder it points at.
+fn check_recyclable(path: &str) -> Result<&str> {
+    check_recyclable_on(path, &SystemResolver, is_fixed_drive)
+}
+
+/// `check_recyclable` resolving paths with `resolver` and telling fixed
+/// drives apart with `is_fixed`
+fn check_recyclable_on<'a>(
+    path: &'a str,
+    resolver: &dyn PathResolver,
+    is_fixed: impl Fn(&str) -> bool,
+) -> Result<&'a str> {
+    if is_network_path(path) {
+        return Err(no_recycle_bin(path, "a network location"));
+    }
+
+    // Relative paths would be resolved against wherever the launcher runs from
+    if !Path::new(path).is_absolute() {
+        return Err(LauncherError::ExecutionError(format!(
+            "{} is not a full path, so the Recycle Bin can't be used; it was not deleted",
+            path
+        )));
+    }
+
+    let resolved = resolver
+        .final_path(Path::new(path))
+        .ok_or_else(|| LauncherError::NotFound(path.to_string()))?;
+    if !resolved.is_absolute() {
+        return Err(LauncherError::ExecutionError(format!(
+            "{} did not resolve to a full path, so the Recycle Bin can't be used; it was not deleted",
+            path
+        )));
+    }
+
+    // A junction may lead somewhere else than the path suggests
+    let resolved = resolved.to_string_lossy();
+    if is_network_path(&resolved) {
+        return Err(no_recycle_bin(path, "a network location"));
+    }
+    if !is_fixed(&resolved) {
+        return Err(no_recycle_bin(path, "a removable or virtual drive"));
+    }
+
+    Ok(path)
+}
+
+/// Error for a path on a volume without a Recycle Bin
+fn no_recycle_bin(path: &str, location: &str) -> LauncherError {
+    LauncherError::ExecutionError(format!(
+        "{} is on {}, which has no Recycle Bin; it was not deleted",
+        path, location
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unc_paths_detected() {
+        assert!(is_unc_path(r"\\server\share\file.txt"));
+        assert!(is_unc_path("//server/share/file.txt"));
+        assert!(is_unc_path(r"\\?\UNC\server\share\file.txt"));
+        assert!(is_unc_path(r"\\?\unc\server\share\file.txt"));
+    }
+
+    #[test]
+    fn test_local_paths_not_unc() {
+        assert!(!is_unc_path(r"C:\Users\me\file.txt"));
+        assert!(!is_unc_path(r"\\?\C:\Users\me\file.txt"));
+        assert!(!is_unc_path("relative\\file.txt"));
+        assert!(!is_unc_path(""));
+    }
+
+    #[test]
+    fn test_network_path_refused_without_deleting() {
+        let err = move_to_recycle_bin(r"\\server\share\report.docx").unwrap_err();
+        assert!(err.to_string().contains("network location"));
+    }
+
+    #[test]
+    fn test_missing_file_reports_not_found() {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        path.push("recycle_missing_test.txt");
+        let _ = std::fs::remove_file(&path);
+
+        let err = move_to_recycle_bin(&path.to_string_lossy()).unwrap_err();
+        assert!(matches!(err, LauncherError::NotFound(_)));
+    }
+
+    /// A file in the temp directory, which exists for the duration of a test
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push("BetterFinder");
+        std::fs::create_dir_all(&path).unwrap();
+        path.push(format!("{}_{}.txt", name, std::process::id()));
+        std::fs::write(&path, b"keep me").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_relative_path_refused_without_deleting() {
+        let err = move_to_recycle_bin(r"notes\report.docx").unwrap_err();
+        assert!(err.to_string().contains("not a full path"));
+
+        let err = move_to_recycle_bin("report.docx").unwrap_err();
+        assert!(err.to_string().contains("it was not deleted"));
+    }
+
+    #[test]
+    fn test_non_fixed_drive_refused_without_deleting() {
+        let path = temp_file("recycle_removable_test");
+        let path_str = path.to_string_lossy().to_string();
+
+        let err = check_recyclable_on(&path_str, &SystemResolver, |_| false).unwrap_err();
+        assert!(err.to_string().contains("has no Recycle Bin; it was not deleted"));
+        assert!(path.exists());
+
+        // The same file on a fixed drive goes through
+        assert_eq!(check_recyclable_on(&path_str, &SystemResolver, |_| true).unwrap(), path_str);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_recycling_a_link_leaves_its_target_alone() {
+        let mut dir = std::env::temp_dir();
+        dir.push("BetterFinder");
+        dir.push(format!("recycle_link_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let target = dir.join("target");
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::write(target.join("keep.txt"), b"keep me").unwrap();
+        let link = dir.join("link");
+
+        #[cfg(unix)]
+        let linked = std::os::unix::fs::symlink(&target, &link);
+        #[cfg(windows)]
+        let linked = std::os::windows::fs::symlink_dir(&target, &link);
+        if linked.is_err() {
+            // Creating symlinks needs Developer Mode or elevation on Windows
+            let _ = std::fs::remove_dir_all(&dir);
+            return;
+        }
+
+        // The checks follow the link, but the shell gets the link itself
+        let link_str = link.to_string_lossy().to_string();
+        let resolved_seen = std::cell::RefCell::new(String::new());
+        let recyclable = check_recyclable_on(&link_str, &SystemResolver, |resolved| {
+            *resolved_seen.borrow_mut() = resolved.to_string();
+            true
+        })
+        .unwrap();
+        assert_eq!(recyclable, link_str);
+        assert!(!resolved_seen.borrow().ends_with("link"), "{}", resolved_seen.borrow());
+
+        #[cfg(windows)]
+        {
+            move_to_recycle_bin(&link_str).unwrap();
+            assert!(link.symlink_metadata().is_err());
+        }
+        assert_eq!(std::fs::read(target.join("keep.txt")).unwrap(), b"keep me");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Latencies kept for the statistics; older ones are dropped first
+const MAX_SAMPLES: usize = 50;
+
+/// A hotkey press not followed by a ready input within this long is
+/// forgotten, so it can't be paired with an unrelated later show
+const STALE_AFTER: Duration = Duration::from_secs(5);
+
+/// Time from the hotkey to a focused search box, as reported by `get_diagnostics`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShowLatencyStats {
+    /// Number of shows measured, up to the last 50
+    pub samples: usize,
+    pub last_ms: Option<f64>,
+    pub median_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+}
+
+/// Measures how long the launcher takes to become usable after the hotkey
+///
+/// The hotkey handler records when it shows the window; the frontend calls
+/// `report_input_ready` once the search box has focus, closing the sample.
+#[derive(Debug, Default)]
+pub struct ShowLatency {
+    /// When the hotkey showing the window was pressed
+    pending: Mutex<Option<Instant>>,
+    samples: Mutex<VecDeque<Duration>>,
+}
+
+impl ShowLatency {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Notes that the hotkey is showing the window
+    pub fn record_hotkey(&self, at: Instant) {
+        *self.pending.lock().unwrap_or_else(|e| e.into_inner()) = Some(at);
+    }
+
+    /// Closes the pending sample, returning its latency
+    ///
+    /// Returns `None` without a pending hotkey, e.g. when the window was
+    /// shown from the tray, or when the hotkey press is stale.
+    pub fn report_input_ready(&self, at: Instant) -> Option<Duration> {
+        let pressed = self.pending.lock().unwrap_or_else(|e| e.into_inner()).take()?;
+        let latency = at.checked_duration_since(pressed)?;
+        if latency > STALE_AFTER {
+            return None;
+        }
+
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.push_back(latency);
+        while samples.len() > MAX_SAMPLES {
+            samples.pop_front();
+        }
+        Some(latency)
+    }
+
+    /// Statistics over the kept samples
+    pub fn stats(&self) -> ShowLatencyStats {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        let last_ms = samples.back().map(|latency| to_ms(*latency));
+
+        let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        // Nearest rank, so a single sample is its own median and 95th percentile
+        let percentile = |p: f64| -> Option<f64> {
+            let rank = ((p * sorted.len() as f64).ceil() as usize).max(1);
+            sorted.get(rank - 1).map(|latency| to_ms(*latency))
+        };
+
+        ShowLatencyStats {
+            samples: sorted.len(),
+            last_ms,
+            median_ms: percentile(0.5),
+            p95_ms: percentile(0.95),
+            max_ms: sorted.last().map(|latency| to_ms(*latency)),
+        }
+    }
+}
+
+fn to_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// The measurements shared by the hotkey handler and `report_input_ready`
+pub fn show_latency() -> &'static ShowLatency {
+    static LATENCY: OnceLock<ShowLatency> = OnceLock::new();
+    LATENCY.get_or_init(ShowLatency::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_ready_closes_pending_hotkey() {
+        let latency = ShowLatency::new();
+        let pressed = Instant::now();
+
+        // Shown from the tray: nothing to measure
+        assert_eq!(latency.report_input_ready(pressed), None);
+
+        latency.record_hotkey(pressed);
+        assert_eq!(
+            latency.report_input_ready(pressed + Duration::from_millis(40)),
+            Some(Duration::from_millis(40))
+        );
+        // Each hotkey press is measured once
+        assert_eq!(latency.report_input_ready(pressed + Duration::from_millis(60)), None);
+
+        latency.record_hotkey(pressed);
+        assert_eq!(latency.report_input_ready(pressed + STALE_AFTER + Duration::from_millis(1)), None);
+        assert_eq!(latency.stats().samples, 1);
+    }
+
+    #[test]
+    fn test_stats_over_recent_samples() {
+        let latency = ShowLatency::new();
+        assert_eq!(latency.stats(), ShowLatencyStats::default());
+
+        let start = Instant::now();
+        for ms in [30, 10, 20, 40, 200] {
+            latency.record_hotkey(start);
+            latency.report_input_ready(start + Duration::from_millis(ms));
+        }
+
+        let stats = latency.stats();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.last_ms, Some(200.0));
+        assert_eq!(stats.median_ms, Some(30.0));
+        assert_eq!(stats.p95_ms, Some(200.0));
+        assert_eq!(stats.max_ms, Some(200.0));
+
+        for _ in 0..MAX_SAMPLES {
+            latency.record_hotkey(start);
+            latency.report_input_ready(start + Duration::from_millis(5));
+        }
+        let stats = latency.stats();
+        assert_eq!(stats.samples, MAX_SAMPLES);
+        assert_eq!(stats.max_ms, Some(5.0));
+    }
+}
@@ -0,0 +1,368 @@
+use crate::error::{LauncherError, Result};
+use crate::settings::{CustomSounds, SoundFeedback};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Largest WAV file accepted as a custom sound
+pub const MAX_SOUND_FILE_BYTES: u64 = 200 * 1024;
+
+/// Something the launcher can play a sound for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundEvent {
+    /// The hotkey brought up the launcher
+    Shown,
+    /// A result's action failed
+    ExecutionFailed,
+    /// A new version is ready to install
+    UpdateAvailable,
+}
+
+impl SoundEvent {
+    /// The WAV bundled for this event
+    fn bundled(self) -> &'static [u8] {
+        match self {
+            SoundEvent::Shown => include_bytes!("../../sounds/show.wav"),
+            SoundEvent::ExecutionFailed => include_bytes!("../../sounds/error.wav"),
+            SoundEvent::UpdateAvailable => include_bytes!("../../sounds/notify.wav"),
+        }
+    }
+}
+
+impl SoundFeedback {
+    /// Whether this setting plays a sound for `event`
+    pub fn plays(self, event: SoundEvent) -> bool {
+        match self {
+            SoundFeedback::Off => false,
+            SoundFeedback::ErrorsOnly => event == SoundEvent::ExecutionFailed,
+            SoundFeedback::All => true,
+        }
+    }
+}
+
+impl CustomSounds {
+    /// The user's file for `event`, if they picked one
+    pub fn path_for(&self, event: SoundEvent) -> Option<&str> {
+        match event {
+            SoundEvent::Shown => self.shown.as_deref(),
+            SoundEvent::ExecutionFailed => self.execution_failed.as_deref(),
+            SoundEvent::UpdateAvailable => self.update_available.as_deref(),
+        }
+        .filter(|path| !path.trim().is_empty())
+    }
+
+    /// Checks that every file exists, is a WAV file and is small enough
+    pub fn validate(&self) -> Result<()> {
+        [SoundEvent::Shown, SoundEvent::ExecutionFailed, SoundEvent::UpdateAvailable]
+            .into_iter()
+            .filter_map(|event| self.path_for(event))
+            .try_for_each(|path| validate_sound_file(Path::new(path)))
+    }
+}
+
+/// Checks that a custom sound exists, is a `.wav` file and is at most
+/// `MAX_SOUND_FILE_BYTES`
+pub fn validate_sound_file(path: &Path) -> Result<()> {
+    let is_wav = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"));
+    if !is_wav {
+        return Err(LauncherError::ConfigError(format!("Sound {} is not a .wav file", path.display())));
+    }
+
+    let metadata = std::fs::metadata(path)
+        .map_err(|e| LauncherError::ConfigError(format!("Sound {} can't be read: {}", path.display(), e)))?;
+    if !metadata.is_file() {
+        return Err(LauncherError::ConfigError(format!("Sound {} is not a file", path.display())));
+    }
+    if metadata.len() > MAX_SOUND_FILE_BYTES {
+        return Err(LauncherError::ConfigError(format!(
+            "Sound {} is larger than {} KB",
+            path.display(),
+            MAX_SOUND_FILE_BYTES / 1024
+        )));
+    }
+
+    Ok(())
+}
+
+/// What to play for an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sound {
+    /// A WAV embedded in the executable
+    Bundled(&'static [u8]),
+    /// A WAV file the user picked
+    File(PathBuf),
+}
+
+/// Sound playback and the system's do-not-disturb state
+///
+/// Implemented with PlaySound in the app; tests record what would play.
+pub trait SoundOutput: Send + Sync {
+    /// Starts playing `sound` without waiting for it to finish
+    fn play(&self, sound: &Sound) -> Result<()>;
+    /// Whether Focus Assist, a presentation or a full-screen app asks for quiet
+    fn do_not_disturb(&self) -> bool;
+}
+
+/// Plays short sounds for launcher events, as the `sound_feedback` setting allows
+///
+/// Sounds start asynchronously and are skipped while the system is in
+/// do-not-disturb. A custom sound that went missing since it was picked
+/// falls back to the bundled one.
+pub struct SoundPlayer {
+    output: Arc<dyn SoundOutput>,
+    feedback: Mutex<SoundFeedback>,
+    custom_sounds: Mutex<CustomSounds>,
+}
+
+impl SoundPlayer {
+    /// Creates a silent player using the system's sound output
+    pub fn new() -> Self {
+        Self::with_output(Arc::new(SystemOutput))
+    }
+
+    /// Creates a silent player using the given output
+    pub fn with_output(output: Arc<dyn SoundOutput>) -> Self {
+        Self {
+            output,
+            feedback: Mutex::new(SoundFeedback::Off),
+            custom_sounds: Mutex::new(CustomSounds::default()),
+        }
+    }
+
+    /// Applies the `sound_feedback` and `custom_sounds` settings
+    pub fn configure(&self, feedback: SoundFeedback, custom_sounds: CustomSounds) {
+        *self.feedback.lock().unwrap_or_else(|e| e.into_inner()) = feedback;
+        *self.custom_sounds.lock().unwrap_or_else(|e| e.into_inner()) = custom_sounds;
+    }
+
+    /// The sound `event` plays under the current settings, if any
+    pub fn sound_for(&self, event: SoundEvent) -> Option<Sound> {
+        if !self.feedback.lock().unwrap_or_else(|e| e.into_inner()).plays(event) {
+            return None;
+        }
+
+        let custom_sounds = self.custom_sounds.lock().unwrap_or_else(|e| e.into_inner());
+        let sound = match custom_sounds.path_for(event).map(Path::new) {
+            Some(path) if path.is_file() => Sound::File(path.to_path_buf()),
+            Some(path) => {
+                tracing::debug!("Custom sound {} is gone, using the bundled one", path.display());
+                Sound::Bundled(event.bundled())
+            }
+            None => Sound::Bundled(event.bundled()),
+        };
+        Some(sound)
+    }
+
+    /// Plays the sound for `event`, returning whether one started
+    pub fn play(&self, event: SoundEvent) -> bool {
+        let Some(sound) = self.sound_for(event) else {
+            return false;
+        };
+        if self.output.do_not_disturb() {
+            tracing::debug!("Skipping {:?} sound in do-not-disturb", event);
+            return false;
+        }
+
+        match self.output.play(&sound) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Failed to play {:?} sound: {}", event, e);
+                false
+            }
+        }
+    }
+}
+
+impl Default for SoundPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// PlaySound and the shell's notification state
+struct SystemOutput;
+
+#[cfg(windows)]
+impl SoundOutput for SystemOutput {
+    fn play(&self, sound: &Sound) -> Result<()> {
+        use windows::core::{HSTRING, PCWSTR};
+        use windows::Win32::Foundation::HMODULE;
+        use windows::Win32::Media::Audio::{PlaySoundW, SND_ASYNC, SND_FILENAME, SND_MEMORY, SND_NODEFAULT};
+
+        // SND_ASYNC returns at once; bundled sounds are static, so the memory
+        // outlives the playback
+        let played = unsafe {
+            match sound {
+                Sound::Bundled(wav) => PlaySoundW(
+                    PCWSTR(wav.as_ptr() as *const u16),
+                    HMODULE::default(),
+                    SND_MEMORY | SND_ASYNC | SND_NODEFAULT,
+                ),
+                Sound::File(path) => PlaySoundW(
+                    &HSTRING::from(path.as_os_str()),
+                    HMODULE::default(),
+                    SND_FILENAME | SND_ASYNC | SND_NODEFAULT,
+                ),
+            }
+        };
+
+        if played.as_bool() {
+            Ok(())
+        } else {
+            Err(LauncherError::ExecutionError("PlaySound failed".to_string()))
+        }
+    }
+
+    fn do_not_disturb(&self) -> bool {
+        use windows::Win32::UI::Shell::{
+            SHQueryUserNotificationState, QUNS_APP, QUNS_BUSY, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME,
+            QUNS_RUNNING_D3D_FULL_SCREEN,
+        };
+
+        match unsafe { SHQueryUserNotificationState() } {
+            Ok(state) => matches!(
+                state,
+                QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE | QUNS_QUIET_TIME | QUNS_APP
+            ),
+            Err(e) => {
+                tracing::debug!("Failed to query the notification state: {}", e);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+impl SoundOutput for SystemOutput {
+    fn play(&self, _sound: &Sound) -> Result<()> {
+        Err(LauncherError::ExecutionError(
+            "Sound feedback not supported on this platform".to_string(),
+        ))
+    }
+
+    fn do_not_disturb(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const EVENTS: [SoundEvent; 3] = [SoundEvent::Shown, SoundEvent::ExecutionFailed, SoundEvent::UpdateAvailable];
+
+    #[derive(Default)]
+    struct RecordingOutput {
+        played: Mutex<Vec<Sound>>,
+        quiet: AtomicBool,
+    }
+
+    impl SoundOutput for RecordingOutput {
+        fn play(&self, sound: &Sound) -> Result<()> {
+            self.played.lock().unwrap().push(sound.clone());
+            Ok(())
+        }
+
+        fn do_not_disturb(&self) -> bool {
+            self.quiet.load(Ordering::Relaxed)
+        }
+    }
+
+    fn temp_file(name: &str, bytes: usize) -> PathBuf {
+        let dir = std::env::temp_dir().join("better_finder_sound_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, vec![0u8; bytes]).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_setting_decides_which_events_play() {
+        let expected = [
+            (SoundFeedback::Off, [false, false, false]),
+            (SoundFeedback::ErrorsOnly, [false, true, false]),
+            (SoundFeedback::All, [true, true, true]),
+        ];
+
+        for (feedback, plays) in expected {
+            let output = Arc::new(RecordingOutput::default());
+            let player = SoundPlayer::with_output(output.clone());
+            player.configure(feedback, CustomSounds::default());
+
+            for (event, should_play) in EVENTS.into_iter().zip(plays) {
+                assert_eq!(player.play(event), should_play, "{:?} / {:?}", feedback, event);
+            }
+            assert_eq!(output.played.lock().unwrap().len(), plays.iter().filter(|p| **p).count());
+        }
+    }
+
+    #[test]
+    fn test_do_not_disturb_silences_everything() {
+        let output = Arc::new(RecordingOutput::default());
+        output.quiet.store(true, Ordering::Relaxed);
+        let player = SoundPlayer::with_output(output.clone());
+        player.configure(SoundFeedback::All, CustomSounds::default());
+
+        for event in EVENTS {
+            assert!(player.sound_for(event).is_some());
+            assert!(!player.play(event));
+        }
+        assert!(output.played.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_custom_sounds_replace_bundled_ones_while_they_exist() {
+        let path = temp_file("custom-error.wav", 1024);
+        let player = SoundPlayer::with_output(Arc::new(RecordingOutput::default()));
+        player.configure(
+            SoundFeedback::ErrorsOnly,
+            CustomSounds {
+                execution_failed: Some(path.to_string_lossy().into_owned()),
+                ..CustomSounds::default()
+            },
+        );
+
+        assert_eq!(player.sound_for(SoundEvent::ExecutionFailed), Some(Sound::File(path.clone())));
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            player.sound_for(SoundEvent::ExecutionFailed),
+            Some(Sound::Bundled(SoundEvent::ExecutionFailed.bundled()))
+        );
+    }
+
+    #[test]
+    fn test_custom_sound_validation() {
+        let small = temp_file("small.WAV", 2048);
+        let large = temp_file("large.wav", MAX_SOUND_FILE_BYTES as usize + 1);
+        let mp3 = temp_file("chime.mp3", 2048);
+        let folder = std::env::temp_dir().join("better_finder_sound_test").join("folder.wav");
+        std::fs::create_dir_all(&folder).unwrap();
+
+        assert!(validate_sound_file(&small).is_ok());
+        for (path, reason) in [
+            (large.clone(), "larger than 200 KB"),
+            (mp3.clone(), "not a .wav"),
+            (folder.clone(), "not a file"),
+            (small.with_file_name("missing.wav"), "can't be read"),
+        ] {
+            let error = validate_sound_file(&path).unwrap_err().to_string();
+            assert!(error.contains(reason), "{}: {}", path.display(), error);
+        }
+
+        let sounds = CustomSounds {
+            shown: Some(small.to_string_lossy().into_owned()),
+            update_available: Some(String::new()),
+            ..CustomSounds::default()
+        };
+        assert!(sounds.validate().is_ok());
+        let sounds = CustomSounds {
+            execution_failed: Some(mp3.to_string_lossy().into_owned()),
+            ..sounds
+        };
+        assert!(sounds.validate().is_err());
+    }
+}
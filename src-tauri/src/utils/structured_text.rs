@@ -0,0 +1,281 @@
+/// Pretty-printing for JSON and XML copied as a single line
+///
+/// Both printers only re-indent: keys keep their order and numbers, strings
+/// and attribute values are copied byte for byte. Input that doesn't parse
+/// returns `None`, so callers can treat it as plain text.
+
+/// Indentation used by both printers
+const INDENT: &str = "  ";
+
+fn push_newline(out: &mut String, depth: usize) {
+    out.push('\n');
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+/// Re-indents a JSON object or array, or returns `None` if it isn't one
+///
+/// Lenient JSON5-style input (comments, single quotes, trailing commas,
+/// unquoted keys) is rejected.
+pub fn pretty_json(text: &str) -> Option<String> {
+    let text = text.trim();
+    if !(text.starts_with('{') || text.starts_with('[')) {
+        return None;
+    }
+    serde_json::from_str::<serde::de::IgnoredAny>(text).ok()?;
+
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                // Keep empty containers on one line
+                while chars.next_if(|next| next.is_whitespace()).is_some() {}
+                if let Some(close) = chars.next_if(|next| matches!(next, '}' | ']')) {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    push_newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                push_newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                push_newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+
+    Some(out)
+}
+
+/// A piece of an XML document
+#[derive(Debug, PartialEq, Eq)]
+enum XmlToken<'a> {
+    Open { name: &'a str, raw: &'a str },
+    Close { name: &'a str, raw: &'a str },
+    /// Self-closing tags, comments, CDATA, declarations and processing instructions
+    Standalone(&'a str),
+    Text(&'a str),
+}
+
+/// Name of a tag, e.g. `item` in `<item id="1">` or `</item>`
+fn tag_name(raw: &str) -> &str {
+    raw.trim_start_matches(['<', '/'])
+        .trim_end_matches(['>', '/'])
+        .split(|c: char| c.is_whitespace())
+        .next()
+        .unwrap_or("")
+}
+
+/// Splits XML into tags and text, or `None` if a tag is never closed
+fn tokenize_xml(text: &str) -> Option<Vec<XmlToken<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let content = rest[..end].trim();
+            if !content.is_empty() {
+                tokens.push(XmlToken::Text(content));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->")? + 3
+        } else if rest.starts_with("<![CDATA[") {
+            rest.find("]]>")? + 3
+        } else {
+            // Attribute values may contain '>'
+            let mut quote = None;
+            let position = rest.char_indices().skip(1).find(|&(_, c)| match quote {
+                Some(q) if c == q => {
+                    quote = None;
+                    false
+                }
+                Some(_) => false,
+                None if c == '"' || c == '\'' => {
+                    quote = Some(c);
+                    false
+                }
+                None => c == '>',
+            });
+            position?.0 + 1
+        };
+
+        let raw = &rest[..end];
+        let name = tag_name(raw);
+        tokens.push(if raw.starts_with("</") {
+            XmlToken::Close { name, raw }
+        } else if raw.starts_with("<!") || raw.starts_with("<?") || raw.ends_with("/>") {
+            XmlToken::Standalone(raw)
+        } else {
+            if name.is_empty() {
+                return None;
+            }
+            XmlToken::Open { name, raw }
+        });
+        rest = &rest[end..];
+    }
+
+    Some(tokens)
+}
+
+/// Re-indents an XML document, or returns `None` if it isn't well formed
+///
+/// Elements holding only text stay on one line. Tags must nest properly and
+/// the document needs at least one element with no stray text around it.
+pub fn pretty_xml(text: &str) -> Option<String> {
+    let text = text.trim();
+    if !(text.starts_with('<') && text.ends_with('>')) {
+        return None;
+    }
+
+    let tokens = tokenize_xml(text)?;
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut open: Vec<&str> = Vec::new();
+    let mut has_element = false;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let depth = open.len();
+        match &tokens[index] {
+            XmlToken::Open { name, raw } => {
+                has_element = true;
+                push_newline(&mut out, depth);
+                out.push_str(raw);
+
+                // <name>text</name> stays on one line
+                if let (Some(XmlToken::Text(content)), Some(XmlToken::Close { name: closing, raw: close })) =
+                    (tokens.get(index + 1), tokens.get(index + 2))
+                {
+                    if closing == name {
+                        out.push_str(content);
+                        out.push_str(close);
+                        index += 3;
+                        continue;
+                    }
+                }
+                open.push(name);
+            }
+            XmlToken::Close { name, raw } => {
+                if open.pop() != Some(*name) {
+                    return None;
+                }
+                push_newline(&mut out, depth - 1);
+                out.push_str(raw);
+            }
+            XmlToken::Standalone(raw) => {
+                has_element |= raw.ends_with("/>") && !raw.starts_with("<?");
+                push_newline(&mut out, depth);
+                out.push_str(raw);
+            }
+            XmlToken::Text(content) => {
+                if depth == 0 {
+                    return None;
+                }
+                push_newline(&mut out, depth);
+                out.push_str(content);
+            }
+        }
+        index += 1;
+    }
+
+    (open.is_empty() && has_element).then(|| out.trim_start().to_string())
+}
+
+/// The first `max_lines` lines of `text`, with "…" on a final line if any were cut
+pub fn preview_lines(text: &str, max_lines: usize) -> String {
+    let mut lines = text.lines();
+    let mut preview: Vec<&str> = lines.by_ref().take(max_lines).collect();
+    if lines.next().is_some() {
+        preview.push("…");
+    }
+    preview.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_json_keeps_order_and_values() {
+        let pretty = pretty_json(r#"{"b":1.50,"a":[1,{"x":"a,b:{c}"}],"e":{},"s":"q\"}"}"#).unwrap();
+        assert_eq!(
+            pretty,
+            "{\n  \"b\": 1.50,\n  \"a\": [\n    1,\n    {\n      \"x\": \"a,b:{c}\"\n    }\n  ],\n  \"e\": {},\n  \"s\": \"q\\\"}\"\n}"
+        );
+        assert_eq!(pretty_json("[ ]").unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_pretty_json_rejects_lenient_and_scalar_input() {
+        assert_eq!(pretty_json("{'a': 1}"), None);
+        assert_eq!(pretty_json("{a: 1}"), None);
+        assert_eq!(pretty_json("[1, 2,]"), None);
+        assert_eq!(pretty_json("{\"a\": 1 // note\n}"), None);
+        assert_eq!(pretty_json("{\"a\": 1"), None);
+        assert_eq!(pretty_json("42"), None);
+        assert_eq!(pretty_json("\"text\""), None);
+    }
+
+    #[test]
+    fn test_pretty_xml() {
+        let pretty = pretty_xml(
+            r#"<?xml version="1.0"?><root a="x>y"><item id="1">One</item><empty/><!-- note --><list><i>2</i></list></root>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            pretty,
+            "<?xml version=\"1.0\"?>\n<root a=\"x>y\">\n  <item id=\"1\">One</item>\n  <empty/>\n  <!-- note -->\n  <list>\n    <i>2</i>\n  </list>\n</root>"
+        );
+    }
+
+    #[test]
+    fn test_pretty_xml_rejects_malformed_input() {
+        assert_eq!(pretty_xml("<a><b></a></b>"), None);
+        assert_eq!(pretty_xml("<a><b></b>"), None);
+        assert_eq!(pretty_xml("<a></a>trailing<b/>"), None);
+        assert_eq!(pretty_xml("<a attr=\"unterminated></a>"), None);
+        assert_eq!(pretty_xml("<!-- only a comment -->"), None);
+        assert_eq!(pretty_xml("1 < 2 and 3 > 2"), None);
+    }
+
+    #[test]
+    fn test_preview_lines() {
+        assert_eq!(preview_lines("a\nb\nc", 3), "a\nb\nc");
+        assert_eq!(preview_lines("a\nb\nc\nd", 2), "a\nb\n…");
+    }
+}
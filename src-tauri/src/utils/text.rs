@@ -0,0 +1,49 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalize text for matching: NFKD, strip diacritics, lowercase
+///
+/// "São Paulo" and "sao paulo" normalize to the same string, so matchers can
+/// compare precomputed forms without allocating per keystroke.
+pub fn normalize_for_search(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_ascii_lowercase();
+    }
+
+    text.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ascii() {
+        assert_eq!(normalize_for_search("Visual Studio Code"), "visual studio code");
+        assert_eq!(normalize_for_search(""), "");
+    }
+
+    #[test]
+    fn test_normalize_strips_diacritics() {
+        assert_eq!(normalize_for_search("São Paulo"), "sao paulo");
+        assert_eq!(normalize_for_search("Crème Brûlée"), "creme brulee");
+        assert_eq!(normalize_for_search("ÅNGSTRÖM"), "angstrom");
+        assert_eq!(normalize_for_search("naïve café"), "naive cafe");
+    }
+
+    #[test]
+    fn test_normalize_compatibility_forms() {
+        // Full-width letters and ligatures fold to their plain forms
+        assert_eq!(normalize_for_search("Ｆｉｌｅ"), "file");
+        assert_eq!(normalize_for_search("ﬁle"), "file");
+    }
+
+    #[test]
+    fn test_normalize_keeps_non_latin_scripts() {
+        assert_eq!(normalize_for_search("Москва"), "москва");
+        assert_eq!(normalize_for_search("東京"), "東京");
+    }
+}
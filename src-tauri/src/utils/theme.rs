@@ -1,27 +1,70 @@
 use crate::error::Result;
 use crate::settings::Theme;
+use serde::Serialize;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
 
 #[cfg(target_os = "windows")]
-use windows::Win32::System::Registry::{RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_CURRENT_USER, KEY_READ, REG_VALUE_TYPE};
+use windows::Win32::System::Registry::{RegCloseKey, RegOpenKeyExW, RegQueryValueExW, HKEY, HKEY_CURRENT_USER, KEY_READ, REG_VALUE_TYPE};
 #[cfg(target_os = "windows")]
 use windows::core::PCWSTR;
 
-/// Detect the current Windows system theme
+/// Registry key holding the light/dark app theme
 #[cfg(target_os = "windows")]
-pub fn detect_system_theme() -> Result<Theme> {
+const PERSONALIZE_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize";
+
+/// Registry key holding the accent color
+#[cfg(target_os = "windows")]
+const DWM_KEY: &str = "Software\\Microsoft\\Windows\\DWM";
+
+/// How long registry notifications must stay quiet before the appearance is re-read
+///
+/// Switching dark mode or the accent writes several values in a burst.
+pub const APPEARANCE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The theme to apply and the Windows accent color, returned by `get_resolved_theme`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedTheme {
+    pub theme: Theme,
+    /// Accent color as "#RRGGBBAA", or `None` when it can't be read
+    pub accent: Option<String>,
+}
+
+/// What Windows currently reports for the app theme and accent color
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemAppearance {
+    pub theme: Theme,
+    pub accent: Option<String>,
+}
+
+impl SystemAppearance {
+    /// Reads the current system theme and accent color
+    pub fn current() -> Self {
+        Self {
+            theme: detect_system_theme().unwrap_or(Theme::Dark),
+            accent: get_accent_color(),
+        }
+    }
+}
+
+/// A change to the system appearance, reported by the watcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppearanceChange {
+    Theme(Theme),
+    Accent(Option<String>),
+}
+
+/// Reads a DWORD value from a key under HKEY_CURRENT_USER
+#[cfg(target_os = "windows")]
+fn read_user_dword(key_path: &str, value_name: &str) -> Option<u32> {
     use std::ptr;
-    
+
+    let key_path: Vec<u16> = key_path.encode_utf16().chain(std::iter::once(0)).collect();
+    let value_name: Vec<u16> = value_name.encode_utf16().chain(std::iter::once(0)).collect();
+
     unsafe {
-        let key_path: Vec<u16> = "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize\0"
-            .encode_utf16()
-            .collect();
-        
-        let value_name: Vec<u16> = "AppsUseLightTheme\0"
-            .encode_utf16()
-            .collect();
-        
         let mut h_key: HKEY = HKEY::default();
-        
+
         // Open registry key
         let result = RegOpenKeyExW(
             HKEY_CURRENT_USER,
@@ -30,17 +73,16 @@ pub fn detect_system_theme() -> Result<Theme> {
             KEY_READ,
             &mut h_key,
         );
-        
+
         if result.is_err() {
-            tracing::warn!("Failed to open registry key for theme detection, defaulting to dark theme");
-            return Ok(Theme::Dark);
+            return None;
         }
-        
+
         // Query the value
         let mut data: u32 = 0;
         let mut data_size: u32 = std::mem::size_of::<u32>() as u32;
         let mut value_type: REG_VALUE_TYPE = REG_VALUE_TYPE::default();
-        
+
         let result = RegQueryValueExW(
             h_key,
             PCWSTR(value_name.as_ptr()),
@@ -49,14 +91,23 @@ pub fn detect_system_theme() -> Result<Theme> {
             Some(&mut data as *mut u32 as *mut u8),
             Some(&mut data_size),
         );
-        
-        if result.is_err() {
-            tracing::warn!("Failed to query registry value for theme detection, defaulting to dark theme");
-            return Ok(Theme::Dark);
-        }
-        
+
+        let _ = RegCloseKey(h_key);
+
+        result.is_ok().then_some(data)
+    }
+}
+
+/// Detect the current Windows system theme
+#[cfg(target_os = "windows")]
+pub fn detect_system_theme() -> Result<Theme> {
+    match read_user_dword(PERSONALIZE_KEY, "AppsUseLightTheme") {
         // 0 = Dark theme, 1 = Light theme
-        Ok(if data == 0 { Theme::Dark } else { Theme::Light })
+        Some(data) => Ok(if data == 0 { Theme::Dark } else { Theme::Light }),
+        None => {
+            tracing::warn!("Failed to read registry value for theme detection, defaulting to dark theme");
+            Ok(Theme::Dark)
+        }
     }
 }
 
@@ -66,6 +117,27 @@ pub fn detect_system_theme() -> Result<Theme> {
     Ok(Theme::Dark)
 }
 
+/// Converts the DWM `AccentColor` DWORD, stored as 0xAABBGGRR, to "#RRGGBBAA"
+pub fn accent_from_abgr(value: u32) -> String {
+    let [red, green, blue, alpha] = value.to_le_bytes();
+    format!("#{:02X}{:02X}{:02X}{:02X}", red, green, blue, alpha)
+}
+
+/// Returns the Windows accent color as "#RRGGBBAA"
+#[cfg(target_os = "windows")]
+pub fn get_accent_color() -> Option<String> {
+    let accent = read_user_dword(DWM_KEY, "AccentColor").map(accent_from_abgr);
+    if accent.is_none() {
+        tracing::debug!("No accent color found in the registry");
+    }
+    accent
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn get_accent_color() -> Option<String> {
+    None
+}
+
 /// Resolve the actual theme to use based on settings
 pub fn resolve_theme(theme_setting: Theme) -> Result<Theme> {
     match theme_setting {
@@ -74,9 +146,140 @@ pub fn resolve_theme(theme_setting: Theme) -> Result<Theme> {
     }
 }
 
+/// Resolve the theme to use along with the system accent color
+pub fn resolve_theme_with_accent(theme_setting: Theme) -> Result<ResolvedTheme> {
+    Ok(ResolvedTheme {
+        theme: resolve_theme(theme_setting)?,
+        accent: get_accent_color(),
+    })
+}
+
+/// Turns bursts of change notifications into appearance changes
+///
+/// After a notification arrives, waits until none has arrived for `quiet`,
+/// then calls `read` once and reports each part that differs from the last
+/// reading. Returns when the sender is dropped.
+pub fn debounce_appearance_changes(
+    notifications: Receiver<()>,
+    quiet: Duration,
+    initial: SystemAppearance,
+    mut read: impl FnMut() -> SystemAppearance,
+    mut emit: impl FnMut(AppearanceChange),
+) {
+    let mut last = initial;
+
+    while notifications.recv().is_ok() {
+        let disconnected = loop {
+            match notifications.recv_timeout(quiet) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break false,
+                Err(RecvTimeoutError::Disconnected) => break true,
+            }
+        };
+
+        let current = read();
+        if current.theme != last.theme {
+            emit(AppearanceChange::Theme(current.theme));
+        }
+        if current.accent != last.accent {
+            emit(AppearanceChange::Accent(current.accent.clone()));
+        }
+        last = current;
+
+        if disconnected {
+            break;
+        }
+    }
+}
+
+/// Blocks until a value under the given HKEY_CURRENT_USER key changes
+#[cfg(target_os = "windows")]
+fn wait_for_key_change(key_path: &str) -> Result<()> {
+    use crate::error::LauncherError;
+    use windows::Win32::System::Registry::{RegNotifyChangeKeyValue, KEY_NOTIFY, REG_NOTIFY_CHANGE_LAST_SET};
+    use windows::Win32::Foundation::{BOOL, HANDLE};
+
+    let key_path_wide: Vec<u16> = key_path.encode_utf16().chain(std::iter::once(0)).collect();
+
+    unsafe {
+        let mut h_key: HKEY = HKEY::default();
+        let result = RegOpenKeyExW(
+            HKEY_CURRENT_USER,
+            PCWSTR(key_path_wide.as_ptr()),
+            0,
+            KEY_NOTIFY,
+            &mut h_key,
+        );
+        if result.is_err() {
+            return Err(LauncherError::SettingsError(format!(
+                "Failed to open {} for change notifications: {:?}",
+                key_path, result
+            )));
+        }
+
+        // Synchronous: returns once a value under the key is set
+        let result = RegNotifyChangeKeyValue(
+            h_key,
+            BOOL::from(false),
+            REG_NOTIFY_CHANGE_LAST_SET,
+            HANDLE::default(),
+            BOOL::from(false),
+        );
+        let _ = RegCloseKey(h_key);
+
+        if result.is_err() {
+            return Err(LauncherError::SettingsError(format!(
+                "Failed to wait for changes to {}: {:?}",
+                key_path, result
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Watches the Windows personalization keys on background threads and reports
+/// dark mode and accent color changes made while the app runs
+#[cfg(target_os = "windows")]
+pub fn watch_system_appearance(emit: impl FnMut(AppearanceChange) + Send + 'static) {
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    for key_path in [PERSONALIZE_KEY, DWM_KEY] {
+        let sender = sender.clone();
+        std::thread::spawn(move || loop {
+            if let Err(e) = wait_for_key_change(key_path) {
+                tracing::warn!("Stopped watching appearance changes: {}", e);
+                break;
+            }
+            if sender.send(()).is_err() {
+                break;
+            }
+        });
+    }
+
+    std::thread::spawn(move || {
+        debounce_appearance_changes(
+            receiver,
+            APPEARANCE_DEBOUNCE,
+            SystemAppearance::current(),
+            SystemAppearance::current,
+            emit,
+        );
+    });
+
+    tracing::info!("Watching system theme and accent color");
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn watch_system_appearance(_emit: impl FnMut(AppearanceChange) + Send + 'static) {
+    tracing::debug!("System appearance watching is only supported on Windows");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
 
     #[test]
     fn test_resolve_theme_explicit() {
@@ -99,4 +302,90 @@ mod tests {
         let theme = detect_system_theme();
         assert!(theme.is_ok());
     }
+
+    #[test]
+    fn test_accent_from_abgr() {
+        // Windows' default blue accent
+        assert_eq!(accent_from_abgr(0xFFD7_7800), "#0078D7FF");
+        assert_eq!(accent_from_abgr(0x8000_00FF), "#FF000080");
+        assert_eq!(accent_from_abgr(0), "#00000000");
+    }
+
+    fn appearance(theme: Theme, accent: &str) -> SystemAppearance {
+        SystemAppearance {
+            theme,
+            accent: Some(accent.to_string()),
+        }
+    }
+
+    /// Runs the debouncer over `bursts`, setting the system appearance before each
+    /// burst and waiting between them; returns the changes and the number of reads
+    fn run_debouncer(bursts: Vec<(SystemAppearance, usize)>) -> (Vec<AppearanceChange>, usize) {
+        let system = Arc::new(Mutex::new(appearance(Theme::Dark, "#0078D7FF")));
+        let reads = Arc::new(Mutex::new(0));
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let (sender, receiver) = mpsc::channel();
+
+        let debouncer = {
+            let system = Arc::clone(&system);
+            let reads = Arc::clone(&reads);
+            let changes = Arc::clone(&changes);
+            let initial = system.lock().unwrap().clone();
+            std::thread::spawn(move || {
+                debounce_appearance_changes(
+                    receiver,
+                    Duration::from_millis(20),
+                    initial,
+                    || {
+                        *reads.lock().unwrap() += 1;
+                        system.lock().unwrap().clone()
+                    },
+                    |change| changes.lock().unwrap().push(change),
+                )
+            })
+        };
+
+        for (next, notifications) in bursts {
+            *system.lock().unwrap() = next;
+            for _ in 0..notifications {
+                sender.send(()).unwrap();
+            }
+            std::thread::sleep(Duration::from_millis(150));
+        }
+
+        drop(sender);
+        debouncer.join().unwrap();
+
+        let changes = changes.lock().unwrap().clone();
+        let reads = *reads.lock().unwrap();
+        (changes, reads)
+    }
+
+    #[test]
+    fn test_debouncer_collapses_bursts() {
+        let (changes, reads) = run_debouncer(vec![(appearance(Theme::Light, "#0078D7FF"), 5)]);
+
+        assert_eq!(reads, 1);
+        assert_eq!(changes, vec![AppearanceChange::Theme(Theme::Light)]);
+    }
+
+    #[test]
+    fn test_debouncer_reports_only_changed_parts() {
+        let (changes, reads) = run_debouncer(vec![
+            // A notification that changed nothing we show
+            (appearance(Theme::Dark, "#0078D7FF"), 1),
+            (appearance(Theme::Dark, "#E81123FF"), 3),
+            (appearance(Theme::Light, "#0063B1FF"), 2),
+        ]);
+
+        assert_eq!(reads, 3);
+        assert_eq!(
+            changes,
+            vec![
+                AppearanceChange::Accent(Some("#E81123FF".to_string())),
+                AppearanceChange::Theme(Theme::Light),
+                AppearanceChange::Accent(Some("#0063B1FF".to_string())),
+            ]
+        );
+    }
 }
@@ -0,0 +1,272 @@
+use chrono::{DateTime, Datelike, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use tracing::warn;
+
+/// Metadata key holding the relative time, e.g. "5 min ago"
+pub const RELATIVE_TIME_KEY: &str = "relative";
+
+/// Metadata key holding the absolute time in the user's timezone, e.g. "14:32, 3 Jan"
+pub const ABSOLUTE_TIME_KEY: &str = "absolute_local";
+
+const MONTHS_EN: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+const MONTHS_PT: [&str; 12] = [
+    "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+];
+
+/// Languages the time strings are available in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    Pt,
+}
+
+impl Locale {
+    /// Picks a locale from a language tag such as "pt-BR" or "en_US.UTF-8",
+    /// falling back to English
+    pub fn from_tag(tag: &str) -> Self {
+        if tag.to_ascii_lowercase().starts_with("pt") {
+            Locale::Pt
+        } else {
+            Locale::En
+        }
+    }
+
+    /// Returns the user's locale, detected once per run
+    pub fn system() -> Self {
+        static SYSTEM: OnceLock<Locale> = OnceLock::new();
+        *SYSTEM.get_or_init(|| {
+            system_locale_tag()
+                .map(|tag| Locale::from_tag(&tag))
+                .unwrap_or(Locale::En)
+        })
+    }
+
+    fn months(self) -> &'static [&'static str; 12] {
+        match self {
+            Locale::En => &MONTHS_EN,
+            Locale::Pt => &MONTHS_PT,
+        }
+    }
+}
+
+/// Reads the user's locale name from Windows
+#[cfg(windows)]
+fn system_locale_tag() -> Option<String> {
+    use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+    let mut buffer = [0u16; 85]; // LOCALE_NAME_MAX_LENGTH
+    let len = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+    if len <= 1 {
+        return None;
+    }
+
+    // The returned length includes the terminating null
+    Some(String::from_utf16_lossy(&buffer[..len as usize - 1]))
+}
+
+/// Reads the user's locale from the standard environment variables
+#[cfg(not(windows))]
+fn system_locale_tag() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Returns the user's IANA timezone, detected once per run
+///
+/// Falls back to UTC when the OS zone can't be read or isn't in the tz database.
+pub fn local_timezone() -> Tz {
+    static LOCAL: OnceLock<Tz> = OnceLock::new();
+    *LOCAL.get_or_init(|| {
+        match iana_time_zone::get_timezone() {
+            Ok(name) => name.parse::<Tz>().unwrap_or_else(|_| {
+                warn!("Unknown timezone '{}', showing times in UTC", name);
+                Tz::UTC
+            }),
+            Err(e) => {
+                warn!("Failed to read the system timezone: {}, showing times in UTC", e);
+                Tz::UTC
+            }
+        }
+    })
+}
+
+/// Formats how long ago `timestamp` was, e.g. "Just now" or "3 hours ago"
+///
+/// Timestamps in the future (clock skew, or a clock moved back) read as
+/// "Just now" rather than a negative age.
+pub fn format_relative(timestamp: DateTime<Utc>, now: DateTime<Utc>, locale: Locale) -> String {
+    let elapsed = now.signed_duration_since(timestamp).max(chrono::Duration::zero());
+
+    let minutes = elapsed.num_minutes();
+    let hours = elapsed.num_hours();
+    let days = elapsed.num_days();
+
+    match locale {
+        Locale::En => {
+            if elapsed.num_seconds() < 60 {
+                "Just now".to_string()
+            } else if minutes < 60 {
+                format!("{} min ago", minutes)
+            } else if hours < 24 {
+                format!("{} {} ago", hours, if hours == 1 { "hour" } else { "hours" })
+            } else {
+                format!("{} {} ago", days, if days == 1 { "day" } else { "days" })
+            }
+        }
+        Locale::Pt => {
+            if elapsed.num_seconds() < 60 {
+                "Agora mesmo".to_string()
+            } else if minutes < 60 {
+                format!("há {} min", minutes)
+            } else if hours < 24 {
+                format!("há {} {}", hours, if hours == 1 { "hora" } else { "horas" })
+            } else {
+                format!("há {} {}", days, if days == 1 { "dia" } else { "dias" })
+            }
+        }
+    }
+}
+
+/// Formats `timestamp` in the given timezone, e.g. "14:32, 3 Jan"
+///
+/// The year is added when it differs from the current year in that timezone.
+pub fn format_absolute(timestamp: DateTime<Utc>, now: DateTime<Utc>, tz: Tz, locale: Locale) -> String {
+    let local = timestamp.with_timezone(&tz);
+    let month = locale.months()[local.month0() as usize];
+    let formatted = format!("{}, {} {}", local.format("%H:%M"), local.day(), month);
+
+    if local.year() == now.with_timezone(&tz).year() {
+        formatted
+    } else {
+        format!("{} {}", formatted, local.year())
+    }
+}
+
+/// Adds the relative and absolute forms of `timestamp` to a result's metadata,
+/// using the user's locale and timezone
+pub fn insert_time_metadata(metadata: &mut HashMap<String, serde_json::Value>, timestamp: DateTime<Utc>) {
+    let now = Utc::now();
+    let locale = Locale::system();
+
+    metadata.insert(
+        RELATIVE_TIME_KEY.to_string(),
+        serde_json::json!(format_relative(timestamp, now, locale)),
+    );
+    metadata.insert(
+        ABSOLUTE_TIME_KEY.to_string(),
+        serde_json::json!(format_absolute(timestamp, now, local_timezone(), locale)),
+    );
+}
+
+/// Whether `timestamp` is more than `older_than` before `now`
+///
+/// No age matches everything, and an age reaching back before the
+/// representable range matches nothing.
+pub fn is_older_than(timestamp: DateTime<Utc>, older_than: Option<std::time::Duration>, now: DateTime<Utc>) -> bool {
+    let Some(older_than) = older_than else {
+        return true;
+    };
+
+    chrono::Duration::from_std(older_than)
+        .ok()
+        .and_then(|age| now.checked_sub_signed(age))
+        .is_some_and(|cutoff| timestamp < cutoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_relative_bucket_boundaries() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        let ago = |duration: Duration| format_relative(now - duration, now, Locale::En);
+
+        assert_eq!(ago(Duration::seconds(59)), "Just now");
+        assert_eq!(ago(Duration::seconds(60)), "1 min ago");
+        assert_eq!(ago(Duration::minutes(59)), "59 min ago");
+        assert_eq!(ago(Duration::minutes(60)), "1 hour ago");
+        assert_eq!(ago(Duration::hours(23)), "23 hours ago");
+        assert_eq!(ago(Duration::hours(24)), "1 day ago");
+        assert_eq!(ago(Duration::days(3)), "3 days ago");
+    }
+
+    #[test]
+    fn test_relative_portuguese() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        let ago = |duration: Duration| format_relative(now - duration, now, Locale::Pt);
+
+        assert_eq!(ago(Duration::seconds(59)), "Agora mesmo");
+        assert_eq!(ago(Duration::minutes(5)), "há 5 min");
+        assert_eq!(ago(Duration::hours(1)), "há 1 hora");
+        assert_eq!(ago(Duration::days(2)), "há 2 dias");
+    }
+
+    #[test]
+    fn test_future_timestamps_clamp_to_just_now() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+
+        assert_eq!(format_relative(now + Duration::seconds(5), now, Locale::En), "Just now");
+        assert_eq!(format_relative(now + Duration::days(2), now, Locale::En), "Just now");
+        assert_eq!(format_relative(now + Duration::hours(3), now, Locale::Pt), "Agora mesmo");
+    }
+
+    #[test]
+    fn test_absolute_time_in_timezone() {
+        let instant = at("2026-01-03T14:32:00Z");
+        let now = at("2026-06-01T00:00:00Z");
+
+        assert_eq!(format_absolute(instant, now, chrono_tz::Europe::Lisbon, Locale::En), "14:32, 3 Jan");
+        assert_eq!(format_absolute(instant, now, chrono_tz::America::New_York, Locale::En), "09:32, 3 Jan");
+        assert_eq!(format_absolute(instant, now, chrono_tz::America::Sao_Paulo, Locale::Pt), "11:32, 3 jan");
+
+        // Late on New Year's Eve in UTC is already next year in Tokyo
+        let new_year = at("2026-12-31T20:00:00Z");
+        assert_eq!(format_absolute(new_year, now, chrono_tz::Asia::Tokyo, Locale::En), "05:00, 1 Jan 2027");
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("pt-BR"), Locale::Pt);
+        assert_eq!(Locale::from_tag("pt_PT.UTF-8"), Locale::Pt);
+        assert_eq!(Locale::from_tag("en-US"), Locale::En);
+        assert_eq!(Locale::from_tag("de-DE"), Locale::En);
+    }
+
+    #[test]
+    fn test_insert_time_metadata() {
+        let mut metadata = HashMap::new();
+        insert_time_metadata(&mut metadata, Utc::now());
+
+        assert!(metadata[RELATIVE_TIME_KEY].is_string());
+        assert!(metadata[ABSOLUTE_TIME_KEY].is_string());
+    }
+
+    #[test]
+    fn test_is_older_than_cutoff() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 3, 12, 0, 0).unwrap();
+        let day = Some(std::time::Duration::from_secs(86_400));
+
+        assert!(is_older_than(now - Duration::hours(25), day, now));
+        assert!(!is_older_than(now - Duration::hours(24), day, now), "the cutoff itself is kept");
+        assert!(!is_older_than(now - Duration::hours(1), day, now));
+
+        // No age clears everything, even entries from the future
+        assert!(is_older_than(now + Duration::hours(1), None, now));
+
+        // An age too large to subtract keeps everything
+        assert!(!is_older_than(now - Duration::days(365), Some(std::time::Duration::MAX), now));
+    }
+}
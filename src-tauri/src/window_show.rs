@@ -0,0 +1,170 @@
+use crate::auto_paste::AutoPaste;
+use crate::error::{LauncherError, Result};
+use crate::search::SearchEngine;
+use crate::settings::{Appearance, MonitorGeometry, WindowPlacement};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow};
+
+/// Event sent right before the main window becomes visible, so the frontend
+/// can clear the previous query before anyone sees it
+pub const ABOUT_TO_SHOW_EVENT: &str = "window-about-to-show";
+
+/// Placement applied to the hidden main window for its next show
+#[derive(Debug, Default)]
+pub struct PreparedPlacement(Mutex<Option<WindowPlacement>>);
+
+impl PreparedPlacement {
+    fn set(&self, placement: Option<WindowPlacement>) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = placement;
+    }
+
+    fn take(&self) -> Option<WindowPlacement> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+fn window_error(action: &str, e: tauri::Error) -> LauncherError {
+    LauncherError::WindowError(format!("Failed to {}: {}", action, e))
+}
+
+/// Describes a monitor for `WindowPlacement`
+fn geometry(monitor: &tauri::Monitor) -> MonitorGeometry {
+    MonitorGeometry {
+        position: (monitor.position().x, monitor.position().y),
+        size: (monitor.size().width, monitor.size().height),
+        scale_factor: monitor.scale_factor(),
+    }
+}
+
+/// Sizes the main window for the target monitor's DPI and centers it there
+///
+/// Returns the placement, or `None` when no monitor is known and the window
+/// was centered wherever it is.
+pub(crate) fn place_main_window(window: &WebviewWindow, appearance: &Appearance) -> tauri::Result<Option<WindowPlacement>> {
+    let Some(monitor) = crate::target_monitor(window) else {
+        crate::apply_window_appearance(window, appearance)?;
+        window.center()?;
+        return Ok(None);
+    };
+
+    let placement = WindowPlacement::for_monitor(geometry(&monitor), appearance);
+
+    // Move first so a DPI change from crossing monitors can't undo the size
+    window.set_position(tauri::PhysicalPosition::new(placement.position.0, placement.position.1))?;
+    window.set_size(tauri::PhysicalSize::new(placement.size.0, placement.size.1))?;
+    tracing::debug!("Placed main window: {:?}", placement);
+    Ok(Some(placement))
+}
+
+/// Ends the search session of the hidden window, so the next one's results
+/// get their number shortcuts afresh
+pub fn end_search_session(app: &AppHandle) {
+    if let Some(engine) = app.try_state::<Arc<SearchEngine>>() {
+        engine.reset_result_slots();
+    }
+}
+
+/// Places the hidden main window for its next show and ends its search session
+///
+/// Runs after hiding, so the next show only checks that the cursor is still
+/// on the same monitor instead of moving and resizing the window first.
+pub fn prepare_next_show(app: &AppHandle, window: &WebviewWindow) {
+    end_search_session(app);
+    let placement = match place_main_window(window, &crate::load_appearance()) {
+        Ok(placement) => placement,
+        Err(e) => {
+            tracing::warn!("Failed to place the hidden window for its next show: {}", e);
+            None
+        }
+    };
+    if let Some(prepared) = app.try_state::<PreparedPlacement>() {
+        prepared.set(placement);
+    }
+}
+
+/// Shows and focuses the main window on the monitor under the cursor
+///
+/// Focuses it only if it is already visible, so the frontend asking to show
+/// a window the hotkey just showed costs nothing.
+pub fn show_main_window(app: &AppHandle) -> Result<()> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| LauncherError::WindowError("Main window not found".to_string()))?;
+
+    if window.is_visible().unwrap_or(false) {
+        return window.set_focus().map_err(|e| window_error("focus window", e));
+    }
+
+    // Clipboard items are pasted back into whatever had focus before the launcher
+    if let Some(auto_paste) = app.try_state::<Arc<AutoPaste>>() {
+        auto_paste.remember_foreground();
+    }
+
+    if let Err(e) = app.emit(ABOUT_TO_SHOW_EVENT, ()) {
+        tracing::warn!("Failed to emit {} event: {}", ABOUT_TO_SHOW_EVENT, e);
+    }
+
+    let appearance = crate::load_appearance();
+    let prepared = app.try_state::<PreparedPlacement>().and_then(|prepared| prepared.take());
+    let fits = prepared
+        .zip(crate::target_monitor(&window))
+        .is_some_and(|(placement, monitor)| placement.fits(geometry(&monitor), &appearance));
+    if !fits {
+        place_main_window(&window, &appearance).map_err(|e| window_error("place window", e))?;
+    }
+
+    reveal(&window).map_err(|e| window_error("show window", e))?;
+    tracing::info!("Window shown (prepared placement used: {})", fits);
+
+    crate::utils::show_queued_notifications(app);
+    Ok(())
+}
+
+/// Shows the window without activating it, then brings it to the foreground
+///
+/// Showing and activating at once makes Windows wait for the activation
+/// before the first paint; in this order the window paints at once and takes
+/// the focus right after.
+#[cfg(windows)]
+fn reveal(window: &WebviewWindow) -> tauri::Result<()> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{SetForegroundWindow, ShowWindow, SW_SHOWNOACTIVATE};
+
+    let hwnd = HWND(window.hwnd()?.0 as *mut _);
+    unsafe {
+        let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+        let _ = SetForegroundWindow(hwnd);
+    }
+    // Moves keyboard focus into the webview
+    window.set_focus()
+}
+
+#[cfg(not(windows))]
+fn reveal(window: &WebviewWindow) -> tauri::Result<()> {
+    window.show()?;
+    window.set_focus()
+}
+
+/// Turns the system's show and hide animation for the window on or off
+#[cfg(windows)]
+pub fn set_show_animation(window: &WebviewWindow, enabled: bool) -> Result<()> {
+    use windows::Win32::Foundation::{BOOL, HWND};
+    use windows::Win32::Graphics::Dwm::{DwmSetWindowAttribute, DWMWA_TRANSITIONS_FORCEDISABLED};
+
+    let hwnd = HWND(window.hwnd().map_err(|e| window_error("get window handle", e))?.0 as *mut _);
+    let disabled = BOOL::from(!enabled);
+    unsafe {
+        DwmSetWindowAttribute(
+            hwnd,
+            DWMWA_TRANSITIONS_FORCEDISABLED,
+            &disabled as *const BOOL as *const _,
+            std::mem::size_of::<BOOL>() as u32,
+        )
+    }
+    .map_err(|e| LauncherError::WindowError(format!("Failed to set window transitions: {}", e)))
+}
+
+#[cfg(not(windows))]
+pub fn set_show_animation(_window: &WebviewWindow, _enabled: bool) -> Result<()> {
+    Ok(())
+}
@@ -5,16 +5,21 @@ import ToastContainer from './components/ToastContainer';
 import { UpdateNotification } from './components/UpdateNotification';
 import { useWindowVisibility } from './hooks/useWindowVisibility';
 import { useTheme } from './hooks/useTheme';
+import { useAppearance } from './hooks/useAppearance';
 import { useToast } from './hooks/useToast';
 import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/core';
+import { InAppNotification, SettingsLocation } from './types';
 
 // Create a global toast context
 export let globalToast: ReturnType<typeof useToast> | null = null;
 
 function App() {
-  const { isVisible, hideWindow } = useWindowVisibility();
+  const { isVisible, hideWindow, initialQuery, initialSelectId, clearInitialQuery } = useWindowVisibility();
   const { refreshTheme } = useTheme();
+  useAppearance();
   const [settingsOpen, setSettingsOpen] = useState(false);
+  const [settingsFocus, setSettingsFocus] = useState<SettingsLocation | null>(null);
   const toast = useToast();
 
   // Make toast available globally
@@ -23,8 +28,10 @@ function App() {
   }, [toast]);
 
   useEffect(() => {
-    // Listen for settings open event (can be triggered from system tray)
-    const unlisten = listen('open-settings', () => {
+    // Listen for settings open event (can be triggered from system tray, or
+    // from a settings search result naming the option to show)
+    const unlisten = listen<SettingsLocation | null>('open-settings', (event) => {
+      setSettingsFocus(event.payload ?? null);
       setSettingsOpen(true);
     });
 
@@ -43,16 +50,38 @@ function App() {
       toast.warning(event.payload.title, event.payload.message);
     });
 
+    // Listen for info events from backend
+    const unlistenInfo = listen<{ title: string; message?: string }>('info', (event) => {
+      toast.info(event.payload.title, event.payload.message);
+    });
+
+    // The quick math hotkey evaluates the selection in whichever app has focus
+    // Notifications routed to the launcher, including those held while it was hidden
+    const unlistenNotification = listen<InAppNotification>('notification', (event) => {
+      const { severity, title, message, auto_dismiss_ms } = event.payload;
+      toast.showToast(severity, title, message ?? undefined, auto_dismiss_ms);
+    });
+
+    const unlistenQuickMath = listen('quick-math-pressed', () => {
+      invoke('evaluate_clipboard').catch((error) => {
+        toast.error('Quick math failed', `${error}`);
+      });
+    });
+
     return () => {
       unlisten.then((fn) => fn());
       unlistenError.then((fn) => fn());
       unlistenSuccess.then((fn) => fn());
       unlistenWarning.then((fn) => fn());
+      unlistenInfo.then((fn) => fn());
+      unlistenNotification.then((fn) => fn());
+      unlistenQuickMath.then((fn) => fn());
     };
   }, [toast]);
 
   const handleSettingsClose = () => {
     setSettingsOpen(false);
+    setSettingsFocus(null);
     // Refresh theme when settings are closed in case it changed
     refreshTheme();
   };
@@ -72,11 +101,18 @@ function App() {
       <SearchBar 
         isVisible={isVisible} 
         onClose={hideWindow}
-        onOpenSettings={() => setSettingsOpen(true)}
+        onOpenSettings={() => {
+          setSettingsFocus(null);
+          setSettingsOpen(true);
+        }}
+        initialQuery={initialQuery}
+        initialSelectId={initialSelectId}
+        onInitialQueryApplied={clearInitialQuery}
       />
       <Settings 
         isOpen={settingsOpen} 
         onClose={handleSettingsClose} 
+        focus={settingsFocus}
       />
       <ToastContainer toasts={toast.toasts} onClose={toast.closeToast} />
       <UpdateNotification />
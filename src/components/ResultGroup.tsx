@@ -9,11 +9,12 @@ interface ResultGroupProps {
   selectedIndex: number;
   onSelectResult: (index: number) => void;
   onExecuteResult: (index: number) => void;
+  markedIds?: Set<string>;
   query?: string;
 }
 
 interface GroupedResults {
-  type: ResultType;
+  type: ResultType | 'pinned';
   title: string;
   results: SearchResult[];
   startIndex: number;
@@ -30,6 +31,7 @@ interface FlattenedItem {
 const ITEM_HEIGHT = 56; // Height of result item in pixels
 const HEADER_HEIGHT = 32; // Height of group header in pixels
 const SEPARATOR_HEIGHT = 9; // Height of separator (margin + border)
+const PREVIEW_LINE_HEIGHT = 16; // Height of one line of a result's code preview
 const MAX_HEIGHT = 480; // Maximum height of results container
 const OVERSCAN_COUNT = 3; // Number of items to render outside visible area
 
@@ -38,34 +40,65 @@ const ResultGroup: React.FC<ResultGroupProps> = ({
   selectedIndex,
   onSelectResult,
   onExecuteResult,
+  markedIds,
   query,
 }) => {
   // Group results by type
   const groupedResults = useMemo(() => {
     const groups: GroupedResults[] = [];
     const typeMap = new Map<ResultType, SearchResult[]>();
+    const pinnedResults: SearchResult[] = [];
 
-    // Group results by type
+    // Group results by type, keeping pinned results in their own group
     results.forEach((result) => {
-      const existing = typeMap.get(result.type);
+      if (result.metadata?.pinned) {
+        pinnedResults.push(result);
+        return;
+      }
+
+      // File groups stay among the files they collapse
+      const type = result.type === ResultType.FileGroup ? ResultType.File : result.type;
+      const existing = typeMap.get(type);
       if (existing) {
         existing.push(result);
       } else {
-        typeMap.set(result.type, [result]);
+        typeMap.set(type, [result]);
       }
     });
 
     // Convert to array with start indices
     let currentIndex = 0;
+
+    // Pinned results always come first
+    if (pinnedResults.length > 0) {
+      groups.push({
+        type: 'pinned',
+        title: 'PINNED',
+        results: pinnedResults,
+        startIndex: currentIndex,
+      });
+      currentIndex += pinnedResults.length;
+    }
+
     const typeOrder = [
+      ResultType.Url,
+      ResultType.SavedSearch,
       ResultType.RecentFile,
       ResultType.File,
       ResultType.Application,
+      ResultType.Project,
       ResultType.QuickAction,
       ResultType.Calculator,
+      ResultType.DateTime,
+      ResultType.Generator,
+      ResultType.Text,
+      ResultType.Hash,
       ResultType.Bookmark,
+      ResultType.History,
+      ResultType.Ssh,
       ResultType.Clipboard,
       ResultType.WebSearch,
+      ResultType.Suggestion,
     ];
 
     typeOrder.forEach((type) => {
@@ -95,14 +128,34 @@ const ResultGroup: React.FC<ResultGroupProps> = ({
         return 'QUICK ACTIONS';
       case ResultType.Calculator:
         return 'CALCULATOR';
+      case ResultType.DateTime:
+        return 'DATE & TIME';
+      case ResultType.Generator:
+        return 'GENERATED';
+      case ResultType.Text:
+        return 'TEXT';
+      case ResultType.Hash:
+        return 'CHECKSUM';
+      case ResultType.Url:
+        return 'OPEN';
+      case ResultType.SavedSearch:
+        return 'SAVED SEARCHES';
       case ResultType.Clipboard:
         return 'CLIPBOARD';
       case ResultType.Bookmark:
         return 'BOOKMARKS';
+      case ResultType.History:
+        return 'HISTORY';
       case ResultType.RecentFile:
         return 'RECENT FILES';
       case ResultType.WebSearch:
         return 'WEB SEARCH';
+      case ResultType.Ssh:
+        return 'SSH';
+      case ResultType.Project:
+        return 'PROJECTS';
+      case ResultType.Suggestion:
+        return 'SUGGESTION';
       default:
         return 'RESULTS';
     }
@@ -145,6 +198,10 @@ const ResultGroup: React.FC<ResultGroupProps> = ({
     const item = flattenedItems[index];
     if (item.type === 'header') return HEADER_HEIGHT;
     if (item.type === 'separator') return SEPARATOR_HEIGHT;
+    const preview = item.data?.metadata?.preview;
+    if (typeof preview === 'string') {
+      return ITEM_HEIGHT + preview.split('\n').length * PREVIEW_LINE_HEIGHT + 4;
+    }
     return ITEM_HEIGHT;
   };
 
@@ -208,12 +265,13 @@ const ResultGroup: React.FC<ResultGroupProps> = ({
             isSelected={absoluteIndex === selectedIndex}
             onSelect={() => onSelectResult(absoluteIndex)}
             onExecute={() => onExecuteResult(absoluteIndex)}
+            isMarked={markedIds?.has(result.id)}
             highlightedTitle={highlightedTitle}
           />
         </div>
       );
     };
-  }, [flattenedItems, query, selectedIndex, onSelectResult, onExecuteResult]);
+  }, [flattenedItems, query, selectedIndex, markedIds, onSelectResult, onExecuteResult]);
 
   // Render results with virtual scrolling
   return (
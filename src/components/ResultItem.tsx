@@ -1,12 +1,26 @@
 import React from 'react';
 import { SearchResult, ResultType } from '../types';
-import { File, AppWindow, Zap, Calculator, Clipboard, Bookmark, Clock, Globe } from 'lucide-react';
+import { File, Folder, AppWindow, Zap, Calculator, CalendarClock, Clipboard, Bookmark, Clock, Globe, History, Terminal, SpellCheck, CheckSquare, Dices, Link, Mail, CloudSun, FolderCode, Type, Fingerprint, ListFilter } from 'lucide-react';
+
+// Clipboard kinds with their own badge; everything else shows 'Clip'
+const CLIPBOARD_KIND_BADGES: Record<string, string> = {
+  json: 'JSON',
+  xml: 'XML',
+  urls: 'URLs',
+  table: 'Table',
+};
+
+const TEXT_KIND_BADGES: Record<string, string> = {
+  count: 'Count',
+  regex: 'Match',
+};
 
 interface ResultItemProps {
   result: SearchResult;
   isSelected: boolean;
   onSelect: () => void;
   onExecute: () => void;
+  isMarked?: boolean;
   highlightedTitle?: React.ReactNode;
 }
 
@@ -15,6 +29,7 @@ const ResultItem: React.FC<ResultItemProps> = ({
   isSelected,
   onSelect,
   onExecute,
+  isMarked = false,
   highlightedTitle,
 }) => {
   const itemRef = React.useRef<HTMLDivElement>(null);
@@ -71,14 +86,41 @@ const ResultItem: React.FC<ResultItemProps> = ({
         return <Zap className={iconClass + " text-primary"} />;
       case ResultType.Calculator:
         return <Calculator className={iconClass + " text-primary"} />;
+      case ResultType.DateTime:
+        return <CalendarClock className={iconClass + " text-primary"} />;
+      case ResultType.Generator:
+        return <Dices className={iconClass + " text-primary"} />;
+      case ResultType.Text:
+        return <Type className={iconClass + " text-primary"} />;
+      case ResultType.Hash:
+        return <Fingerprint className={iconClass + " text-primary"} />;
+      case ResultType.SavedSearch:
+        return <ListFilter className={iconClass + " text-primary"} />;
+      case ResultType.Url:
+        if (result.metadata?.kind === 'weather') {
+          return <CloudSun className={iconClass + " text-primary"} />;
+        }
+        return result.metadata?.kind === 'email'
+          ? <Mail className={iconClass + " text-primary"} />
+          : <Link className={iconClass + " text-primary"} />;
       case ResultType.Clipboard:
         return <Clipboard className={iconClass + " text-primary"} />;
       case ResultType.Bookmark:
         return <Bookmark className={iconClass + " text-primary"} />;
+      case ResultType.History:
+        return <History className={iconClass + " text-primary"} />;
       case ResultType.RecentFile:
         return <Clock className={iconClass + " text-primary"} />;
       case ResultType.WebSearch:
         return <Globe className={iconClass + " text-primary"} />;
+      case ResultType.Ssh:
+        return <Terminal className={iconClass + " text-primary"} />;
+      case ResultType.Project:
+        return <FolderCode className={iconClass + " text-primary"} />;
+      case ResultType.Suggestion:
+        return <SpellCheck className={iconClass + " text-primary"} />;
+      case ResultType.FileGroup:
+        return <Folder className={iconClass + " text-primary"} />;
       default:
         return <File className={iconClass + " text-primary"} />;
     }
@@ -95,24 +137,76 @@ const ResultItem: React.FC<ResultItemProps> = ({
         return 'Action';
       case ResultType.Calculator:
         return 'Calc';
+      case ResultType.DateTime:
+        return 'Time';
+      case ResultType.Generator:
+        return 'Random';
+      case ResultType.Text:
+        return TEXT_KIND_BADGES[result.metadata?.kind] ?? 'Text';
+      case ResultType.Hash:
+        return 'Hash';
+      case ResultType.SavedSearch:
+        return 'Saved';
+      case ResultType.Url:
+        return result.metadata?.kind === 'email' ? 'Email' : 'URL';
       case ResultType.Clipboard:
-        return 'Clip';
+        return CLIPBOARD_KIND_BADGES[result.metadata?.kind] ?? 'Clip';
       case ResultType.Bookmark:
         return 'Bookmark';
+      case ResultType.History:
+        return 'History';
       case ResultType.RecentFile:
         return 'Recent';
       case ResultType.WebSearch:
         return 'Web';
+      case ResultType.Ssh:
+        return 'SSH';
+      case ResultType.Project:
+        return 'Project';
+      case ResultType.Suggestion:
+        return 'Suggestion';
+      case ResultType.FileGroup:
+        return 'Folder';
       default:
         return '';
     }
   };
 
+  // Checksum comparisons and failed text transforms color their verdict
+  const getTitleColor = () => {
+    switch (result.metadata?.tone) {
+      case 'success':
+        return 'text-green-500';
+      case 'danger':
+        return 'text-red-500';
+      default:
+        return 'text-text-primary';
+    }
+  };
+
+  // Absolute time on hover, e.g. "Copied 14:32, 3 Jan"
+  const getTimeTooltip = () => {
+    const absolute = result.metadata?.absolute_local;
+    if (typeof absolute !== 'string') {
+      return undefined;
+    }
+
+    switch (result.type) {
+      case ResultType.Clipboard:
+        return `Copied ${absolute}`;
+      case ResultType.RecentFile:
+        return `Opened ${absolute}`;
+      default:
+        return absolute;
+    }
+  };
+
   return (
     <div
       ref={itemRef}
+      title={getTimeTooltip()}
       className={`
-        flex items-center gap-3 px-4 py-3 cursor-pointer
+        result-item flex items-center gap-3 px-4 py-3 cursor-pointer
         ripple-container interactive
         ${isSelected 
           ? 'bg-surface-hover translate-x-1' 
@@ -123,16 +217,17 @@ const ResultItem: React.FC<ResultItemProps> = ({
       onMouseEnter={handleMouseEnter}
       role="option"
       aria-selected={isSelected}
+      aria-label={typeof result.metadata?.spoken_label === 'string' ? result.metadata.spoken_label : undefined}
     >
-      {/* Icon */}
-      <div className="flex-shrink-0">
-        {getIcon()}
+      {/* Icon; marked results show a checkbox instead, high contrast themes a shaped glyph */}
+      <div className="result-icon flex-shrink-0" data-semantic-kind={result.metadata?.semantic_kind}>
+        {isMarked ? <CheckSquare className="w-8 h-8 flex-shrink-0 text-primary" aria-label="Marked" /> : getIcon()}
       </div>
 
       {/* Content */}
       <div className="flex-1 min-w-0">
         <div className="flex items-center gap-2">
-          <h3 className="text-sm font-medium text-text-primary truncate">
+          <h3 className={`text-sm font-medium ${getTitleColor()} truncate`}>
             {highlightedTitle || result.title}
           </h3>
           <span className="text-xs px-1.5 py-0.5 rounded bg-primary/20 text-primary flex-shrink-0">
@@ -144,6 +239,12 @@ const ResultItem: React.FC<ResultItemProps> = ({
             {result.subtitle}
           </p>
         )}
+        {/* Pretty-printed JSON or XML from the clipboard history */}
+        {typeof result.metadata?.preview === 'string' && (
+          <pre className="text-xs leading-4 font-mono text-text-secondary overflow-hidden mt-1">
+            {result.metadata.preview}
+          </pre>
+        )}
       </div>
     </div>
   );
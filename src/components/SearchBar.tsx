@@ -1,7 +1,10 @@
-import React, { useState, useEffect, useRef } from 'react';
-import { Search, X, Settings } from 'lucide-react';
+import React, { useState, useEffect, useMemo, useRef } from 'react';
+import { Search, X, Settings, Check, Loader2 } from 'lucide-react';
+import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/core';
 import { useKeyboard, useKeyboardSelection } from '../hooks/useKeyboard';
 import { useSearch } from '../hooks/useSearch';
+import { ActionType, BatchAction, ExecutionStatus, MAX_BATCH_SIZE, ProviderProgress, ResultType, SavedSearch, SearchResult } from '../types';
 import ResultGroup from './ResultGroup';
 import ResultSkeleton from './ResultSkeleton';
 
@@ -9,19 +12,71 @@ interface SearchBarProps {
   isVisible: boolean;
   onClose: () => void;
   onOpenSettings?: () => void;
+  // Query to pre-fill once the bar is visible, e.g. from the command line
+  initialQuery?: string | null;
+  // Result to select once the initial query's results show up
+  initialSelectId?: string | null;
+  onInitialQueryApplied?: () => void;
 }
 
-const SearchBar: React.FC<SearchBarProps> = ({ isVisible, onClose, onOpenSettings }) => {
+const SearchBar: React.FC<SearchBarProps> = ({
+  isVisible,
+  onClose,
+  onOpenSettings,
+  initialQuery,
+  initialSelectId,
+  onInitialQueryApplied,
+}) => {
   const [isAnimatingOut, setIsAnimatingOut] = useState(false);
+  const [showExecuted, setShowExecuted] = useState(false);
+  const [runningExecution, setRunningExecution] = useState<string | null>(null);
+  const [executionPercent, setExecutionPercent] = useState<number | null>(null);
+  const [appIndexPercent, setAppIndexPercent] = useState<number | null>(null);
+  // IDs of file results marked with Ctrl+Space for a batch open
+  const [markedIds, setMarkedIds] = useState<Set<string>>(new Set());
+  // IDs of file groups expanded with Enter; their members follow them in the list
+  const [expandedGroupIds, setExpandedGroupIds] = useState<Set<string>>(new Set());
   const inputRef = useRef<HTMLInputElement>(null);
   const containerRef = useRef<HTMLDivElement>(null);
+  // hide_on_success per execution id, and successes reported before their ticket arrived
+  const hideOnSuccessRef = useRef(new Map<string, boolean>());
+  const earlySuccessesRef = useRef(new Set<string>());
+  const onSucceededRef = useRef<(hideOnSuccess: boolean) => void>(() => {});
+  // Result to select once the search for an accepted completion returns
+  const pendingSelectIdRef = useRef<string | null>(null);
   
   // Use the search hook
-  const { query, setQuery, results, isLoading, executeResult } = useSearch();
+  const {
+    query,
+    setQuery,
+    results: searchResults,
+    isLoading,
+    completion,
+    acceptCompletion,
+    executeResult,
+    cancelExecution,
+    recycleResult,
+    copyFormatted,
+    copyLink,
+    demoteResult,
+    openInTerminal,
+    executeResultsBatch,
+  } = useSearch();
+
+  const results = useMemo(
+    () =>
+      searchResults.flatMap((result) =>
+        result.type === ResultType.FileGroup && expandedGroupIds.has(result.id)
+          ? [result, ...((result.metadata?.members as SearchResult[] | undefined) ?? [])]
+          : [result]
+      ),
+    [searchResults, expandedGroupIds]
+  );
 
   // Keyboard selection management
   const {
     selectedIndex,
+    setSelectedIndex,
     moveUp,
     moveDown,
     resetSelection,
@@ -36,19 +91,242 @@ const SearchBar: React.FC<SearchBarProps> = ({ isVisible, onClose, onOpenSetting
     }, 100); // Match animation duration
   };
 
-  // Handle result execution
-  const handleExecuteResult = async () => {
+  // Hide after a successful execution, or flash a checkmark when staying open
+  onSucceededRef.current = (hideOnSuccess: boolean) => {
+    if (hideOnSuccess) {
+      handleClose();
+    } else {
+      setShowExecuted(true);
+      setTimeout(() => setShowExecuted(false), 1500);
+    }
+  };
+
+  // Marks and expanded groups only apply to the results they were made on
+  useEffect(() => {
+    setMarkedIds(new Set());
+    setExpandedGroupIds(new Set());
+  }, [searchResults]);
+
+  // Preselect the completed result once it shows up
+  useEffect(() => {
+    const pendingId = pendingSelectIdRef.current;
+    if (!pendingId) {
+      return;
+    }
+    const index = results.findIndex((result) => result.id === pendingId);
+    if (index >= 0) {
+      setSelectedIndex(index);
+      pendingSelectIdRef.current = null;
+    }
+  }, [results, setSelectedIndex]);
+
+  // Tab accepts the ghost-text completion
+  const handleAcceptCompletion = () => {
+    const resultId = acceptCompletion();
+    if (resultId === null) {
+      return false;
+    }
+    pendingSelectIdRef.current = resultId;
+    resetSelection();
+    return true;
+  };
+
+  // Ctrl+Space marks or unmarks the selected file result
+  const handleToggleMark = () => {
+    const selectedResult = results[selectedIndex];
+    if (!selectedResult || ![ResultType.File, ResultType.RecentFile].includes(selectedResult.type)) {
+      return;
+    }
+
+    setMarkedIds((current) => {
+      const next = new Set(current);
+      if (next.has(selectedResult.id)) {
+        next.delete(selectedResult.id);
+      } else if (next.size < MAX_BATCH_SIZE) {
+        next.add(selectedResult.id);
+      }
+      return next;
+    });
+  };
+
+  // Opens every marked result in one batch
+  const handleExecuteMarked = async (invertDefault: boolean) => {
+    const batch = results.filter((result) => markedIds.has(result.id));
+    const summary = await executeResultsBatch(batch, BatchAction.Open);
+    if (summary && summary.succeeded.length > 0 && summary.failed.length === 0) {
+      setMarkedIds(new Set());
+      onSucceededRef.current(!invertDefault);
+    }
+  };
+
+  // Handle result execution; Shift+Enter inverts the hide-after-execute default
+  const handleExecuteResult = async (invertDefault = false) => {
+    if (markedIds.size > 0) {
+      await handleExecuteMarked(invertDefault);
+      return;
+    }
+
     if (results.length > 0 && selectedIndex >= 0 && selectedIndex < results.length) {
       const selectedResult = results[selectedIndex];
+      // Spelling suggestions replace the query instead of running anything
+      if (selectedResult.action.type === ActionType.SetQuery) {
+        setQuery((selectedResult.action as unknown as { query: string }).query);
+        resetSelection();
+        inputRef.current?.focus();
+        return;
+      }
+      // File groups expand or collapse in place
+      if (selectedResult.action.type === ActionType.ExpandGroup) {
+        setExpandedGroupIds((current) => {
+          const next = new Set(current);
+          if (!next.delete(selectedResult.id)) {
+            next.add(selectedResult.id);
+          }
+          return next;
+        });
+        return;
+      }
       try {
-        await executeResult(selectedResult);
-        handleClose();
+        const ticket = await executeResult(selectedResult, invertDefault);
+        // A repeated Enter; the earlier run already reports its own outcome
+        if (ticket.duplicate) {
+          return;
+        }
+        if (earlySuccessesRef.current.delete(ticket.execution_id)) {
+          onSucceededRef.current(ticket.hide_on_success);
+        } else {
+          hideOnSuccessRef.current.set(ticket.execution_id, ticket.hide_on_success);
+        }
       } catch (error) {
         console.error('Failed to execute result:', error);
       }
     }
   };
 
+  // Shift+Delete recycles the selected file result once the user confirms
+  const handleRecycleResult = async () => {
+    if (selectedIndex >= 0 && selectedIndex < results.length) {
+      await recycleResult(results[selectedIndex]);
+    }
+  };
+
+  // Ctrl+Enter copies JSON and XML clipboard items pretty-printed; other results run as usual
+  const handleCopyFormatted = async () => {
+    if (selectedIndex < 0 || selectedIndex >= results.length) {
+      return;
+    }
+
+    if (await copyFormatted(results[selectedIndex])) {
+      handleClose();
+    } else {
+      await handleExecuteResult(false);
+    }
+  };
+
+  // Ctrl+Shift+C copies a betterfinder:// link to the selected result
+  const handleCopyLink = async () => {
+    if (selectedIndex >= 0 && selectedIndex < results.length) {
+      await copyLink(results[selectedIndex]);
+    }
+  };
+
+  // Ctrl+Shift+D marks the selected result as not useful for this query
+  const handleDemoteResult = async () => {
+    if (selectedIndex >= 0 && selectedIndex < results.length) {
+      await demoteResult(results[selectedIndex]);
+    }
+  };
+
+  // Ctrl+Shift+T opens the terminal in the selected file's folder
+  const handleOpenTerminal = async () => {
+    const result = results[selectedIndex];
+    if (result?.type === ResultType.File || result?.type === ResultType.RecentFile) {
+      await openInTerminal(result);
+    }
+  };
+
+  // Show a spinner while a result runs; failures are reported by an error toast
+  useEffect(() => {
+    const unlisten = listen<ExecutionStatus>('execution-status', (event) => {
+      const { execution_id: executionId } = event.payload;
+      const clearRunning = () => {
+        setRunningExecution((current) => (current === executionId ? null : current));
+        setExecutionPercent(null);
+      };
+
+      switch (event.payload.state) {
+        case 'started':
+          setRunningExecution(executionId);
+          setExecutionPercent(null);
+          break;
+        case 'progress': {
+          const { done, total } = event.payload;
+          setExecutionPercent(total > 0 ? Math.floor((done / total) * 100) : null);
+          break;
+        }
+        case 'succeeded': {
+          clearRunning();
+          const hideOnSuccess = hideOnSuccessRef.current.get(executionId);
+          if (hideOnSuccess === undefined) {
+            earlySuccessesRef.current.add(executionId);
+          } else {
+            hideOnSuccessRef.current.delete(executionId);
+            onSucceededRef.current(hideOnSuccess);
+          }
+          break;
+        }
+        case 'failed':
+        case 'cancelled':
+          clearRunning();
+          hideOnSuccessRef.current.delete(executionId);
+          break;
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // A saved search ran again in the backend; show its results for the query
+  useEffect(() => {
+    const unlisten = listen<SavedSearch>('run-saved-search', (event) => {
+      setQuery(event.payload.query);
+      resetSelection();
+      inputRef.current?.focus();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [resetSelection]);
+
+  // An action such as "Insert expression" put text in the search box for editing
+  useEffect(() => {
+    const unlisten = listen<string>('set-query', (event) => {
+      setQuery(event.payload);
+      resetSelection();
+      inputRef.current?.focus();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [resetSelection]);
+
+  // Show app indexing progress while the background scan runs
+  useEffect(() => {
+    const unlisten = listen<ProviderProgress>('provider-progress', (event) => {
+      if (event.payload.provider === 'AppSearch') {
+        setAppIndexPercent(event.payload.percent < 100 ? event.payload.percent : null);
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   // Auto-focus on mount and when visibility changes
   useEffect(() => {
     if (isVisible) {
@@ -58,15 +336,40 @@ const SearchBar: React.FC<SearchBarProps> = ({ isVisible, onClose, onOpenSetting
       if (inputRef.current) {
         inputRef.current.focus();
       }
+      // After the next paint the search box takes input; closes the hotkey latency sample
+      requestAnimationFrame(async () => {
+        try {
+          await invoke('report_input_ready');
+        } catch (error) {
+          console.error('Failed to report input ready:', error);
+        }
+      });
     }
   }, [isVisible, resetSelection]);
 
+  // Runs after the effect above, so a launch query replaces the cleared one
+  useEffect(() => {
+    if (isVisible && initialQuery) {
+      setQuery(initialQuery);
+      pendingSelectIdRef.current = initialSelectId ?? null;
+      resetSelection();
+      onInitialQueryApplied?.();
+    }
+  }, [isVisible, initialQuery, initialSelectId]);
+
   // Keyboard navigation
   useKeyboard({
     onArrowUp: moveUp,
     onArrowDown: moveDown,
     onEnter: handleExecuteResult,
+    onCtrlEnter: handleCopyFormatted,
     onEscape: handleClose,
+    onShiftDelete: handleRecycleResult,
+    onCopyLink: handleCopyLink,
+    onDemote: handleDemoteResult,
+    onOpenTerminal: handleOpenTerminal,
+    onToggleMark: handleToggleMark,
+    onTab: handleAcceptCompletion,
     enabled: isVisible,
   });
 
@@ -74,6 +377,7 @@ const SearchBar: React.FC<SearchBarProps> = ({ isVisible, onClose, onOpenSetting
   const handleInputChange = (e: React.ChangeEvent<HTMLInputElement>) => {
     const value = e.target.value;
     setQuery(value);
+    pendingSelectIdRef.current = null;
     resetSelection(); // Reset selection when query changes
   };
 
@@ -101,17 +405,56 @@ const SearchBar: React.FC<SearchBarProps> = ({ isVisible, onClose, onOpenSetting
       {/* Search Input */}
       <div className="flex items-center gap-3 px-4 py-3 border-b border-border bg-surface">
         <Search className="w-5 h-5 flex-shrink-0" style={{ color: 'var(--color-text-secondary)' }} />
-        <input
-          ref={inputRef}
-          type="text"
-          value={query}
-          onChange={handleInputChange}
-          placeholder="Search files, apps, and more..."
-          className="flex-1 bg-transparent text-base outline-none placeholder:text-text-secondary"
-          style={{ color: 'var(--color-text-primary)' }}
-          autoComplete="off"
-          spellCheck={false}
-        />
+        <div className="relative flex-1 flex">
+          {/* Ghost text: the typed query kept invisible so the suffix lines up after it */}
+          {completion && (
+            <span
+              className="absolute inset-0 text-base whitespace-pre overflow-hidden pointer-events-none"
+              aria-hidden="true"
+            >
+              <span className="invisible">{query}</span>
+              <span className="text-text-secondary">{completion.suffix}</span>
+            </span>
+          )}
+          <input
+            ref={inputRef}
+            type="text"
+            value={query}
+            onChange={handleInputChange}
+            placeholder="Search files, apps, and more..."
+            className="relative flex-1 bg-transparent text-base outline-none placeholder:text-text-secondary"
+            style={{ color: 'var(--color-text-primary)' }}
+            autoComplete="off"
+            spellCheck={false}
+            aria-autocomplete="inline"
+          />
+        </div>
+        {appIndexPercent !== null && (
+          <span className="text-xs text-text-secondary flex-shrink-0" aria-live="polite">
+            Indexing apps… {appIndexPercent}%
+          </span>
+        )}
+        {runningExecution && executionPercent !== null && (
+          <span className="text-xs text-text-secondary flex-shrink-0" aria-live="polite">
+            {executionPercent}%
+          </span>
+        )}
+        {runningExecution && (
+          <button
+            onClick={() => cancelExecution(runningExecution)}
+            className="p-1 hover:bg-surface-hover rounded transition-colors flex-shrink-0"
+            aria-label="Cancel running action"
+            title="Still opening… click to stop waiting"
+          >
+            <Loader2 className="w-4 h-4 text-text-secondary animate-spin" />
+          </button>
+        )}
+        {showExecuted && (
+          <Check
+            className="w-4 h-4 flex-shrink-0 text-green-500 animate-fade-in"
+            aria-label="Result executed"
+          />
+        )}
         {query && (
           <button
             onClick={handleClear}
@@ -156,7 +499,8 @@ const SearchBar: React.FC<SearchBarProps> = ({ isVisible, onClose, onOpenSetting
               onSelectResult={() => {
                 // Selection is handled by keyboard hook and mouse hover
               }}
-              onExecuteResult={handleExecuteResult}
+              onExecuteResult={() => handleExecuteResult()}
+              markedIds={markedIds}
               query={query}
             />
           </div>
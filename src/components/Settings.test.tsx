@@ -23,9 +23,50 @@ describe('Settings Component', () => {
       clipboard: true,
       bookmarks: true,
       recent_files: true,
+      browser_history: false,
+      weather: false,
     },
     search_delay: 150,
     start_with_windows: false,
+    hide_after_execute: true,
+    clipboard_history_size: 100,
+    debug_mode: false,
+    enable_file_delete_action: false,
+    group_file_results: false,
+    stable_result_slots: false,
+    fold_diacritics: true,
+    privacy_mode: false,
+    analytics_local: false,
+    web_search_engine: 'google',
+    temperature_unit: 'celsius',
+    weather_home_city: null,
+    sound_feedback: 'off',
+    custom_sounds: { shown: null, execution_failed: null, update_available: null },
+    notification_routing: { provider_health: 'in_app', updates: 'both' },
+    preferred_file_manager: null,
+    preferred_terminal: null,
+    clipboard_auto_paste: false,
+    quick_math_hotkey: null,
+    quick_math_output: 'toast',
+    custom_bookmark_files: [],
+    open_bookmarks_in_source_browser: false,
+    excluded_paths: [],
+    preferred_paths: [],
+    extension_class_weights: {},
+    ranking_stopwords: ['the', 'of'],
+    plain_text_markers: "`'",
+    macros: [],
+    window_width: 600,
+    max_visible_results: 8,
+    compact_mode: false,
+    disable_show_animation: false,
+    screen_reader_support: false,
+    allow_unsigned_results: false,
+    duplicate_window_ms: 500,
+    memory_trim_threshold_mb: 400,
+    provider_settings: {},
+    profiles: [],
+    active_profile: null,
   };
 
   const mockOnClose = vi.fn();
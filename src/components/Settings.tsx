@@ -1,27 +1,73 @@
 import React, { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { AppSettings, Theme } from '../types';
+import { listen } from '@tauri-apps/api/event';
+import { ActiveHotkey, AppSettings, NotificationRoute, ProviderConfigSchema, SettingsConflict, SettingsLocation, Theme } from '../types';
 import { X, Settings as SettingsIcon } from 'lucide-react';
 
 interface SettingsProps {
   isOpen: boolean;
   onClose: () => void;
+  /** Option to scroll to once the settings are shown */
+  focus?: SettingsLocation | null;
 }
 
-const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
+const Settings: React.FC<SettingsProps> = ({ isOpen, onClose, focus }) => {
   const [settings, setSettings] = useState<AppSettings | null>(null);
   const [loading, setLoading] = useState(true);
   const [saving, setSaving] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [autoStartStatus, setAutoStartStatus] = useState<boolean | null>(null);
+  const [activeHotkey, setActiveHotkey] = useState<ActiveHotkey | null>(null);
+  const [hotkeyError, setHotkeyError] = useState<string | null>(null);
+  const [quickMathHotkeyError, setQuickMathHotkeyError] = useState<string | null>(null);
+  const [providerSchemas, setProviderSchemas] = useState<ProviderConfigSchema[]>([]);
+  const [disabledProviders, setDisabledProviders] = useState<string[]>([]);
 
   useEffect(() => {
     if (isOpen) {
       loadSettings();
       checkAutoStartStatus();
+      loadActiveHotkey();
+      loadProviderSchemas();
+      loadDisabledProviders();
     }
   }, [isOpen]);
 
+  // Scroll to the option a settings search result pointed at; provider
+  // options fall back to the block listing them
+  useEffect(() => {
+    if (!isOpen || loading || !focus) {
+      return;
+    }
+
+    const target =
+      document.querySelector(`[data-setting-key~="${focus.key}"]`) ??
+      document.querySelector(`[data-setting-key~="${focus.key.split('.')[0]}"]`);
+    target?.scrollIntoView({ block: 'center', behavior: 'smooth' });
+  }, [isOpen, loading, focus, providerSchemas]);
+
+  // A save that clashed with edits made to the settings file shows what is on disk now
+  useEffect(() => {
+    const unlisten = listen<SettingsConflict>('settings-conflict', (event) => {
+      setSettings(event.payload.settings);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // A profile switched from the launcher or tray stays active when these settings are saved
+  useEffect(() => {
+    const unlisten = listen<string | null>('profile-changed', (event) => {
+      setSettings((current) => current && { ...current, active_profile: event.payload });
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const loadSettings = async () => {
     try {
       setLoading(true);
@@ -46,6 +92,86 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
     }
   };
 
+  const loadActiveHotkey = async () => {
+    try {
+      const active = await invoke<ActiveHotkey | null>('get_active_hotkey');
+      setActiveHotkey(active ?? null);
+    } catch (err) {
+      console.error('Failed to get active hotkey:', err);
+      setActiveHotkey(null);
+    }
+  };
+
+  const loadProviderSchemas = async () => {
+    try {
+      const schemas = await invoke<ProviderConfigSchema[]>('get_provider_config_schema');
+      setProviderSchemas(Array.isArray(schemas) ? schemas : []);
+    } catch (err) {
+      console.error('Failed to get provider options:', err);
+      setProviderSchemas([]);
+    }
+  };
+
+  const loadDisabledProviders = async () => {
+    try {
+      const providers = await invoke<string[]>('get_disabled_providers');
+      setDisabledProviders(Array.isArray(providers) ? providers : []);
+    } catch (err) {
+      console.error('Failed to get disabled providers:', err);
+      setDisabledProviders([]);
+    }
+  };
+
+  // Providers skipped after failing on several launches in a row start again on the next launch
+  const retryProvider = async (provider: string) => {
+    try {
+      setError(null);
+      await invoke('retry_provider', { provider });
+      setDisabledProviders((providers) => providers.filter((p) => p !== provider));
+    } catch (err) {
+      setError(`Failed to re-enable ${provider}: ${err}`);
+    }
+  };
+
+  // Registering the hotkey briefly tells us whether another app holds it
+  const checkHotkey = async (shortcut: string, setShortcutError = setHotkeyError) => {
+    try {
+      await invoke('validate_hotkey', { shortcut });
+      setShortcutError(null);
+    } catch (err) {
+      setShortcutError(`${err}`);
+    }
+  };
+
+  // Explorer context menu entries live in the registry, outside the settings file
+  const setContextMenuInstalled = async (install: boolean) => {
+    try {
+      setError(null);
+      await invoke(install ? 'install_context_menu' : 'uninstall_context_menu');
+    } catch (err) {
+      setError(`Failed to update the Explorer context menu: ${err}`);
+    }
+  };
+
+  // Demotions are kept by the search engine, outside the settings file
+  const clearFeedback = async () => {
+    try {
+      setError(null);
+      await invoke('clear_feedback');
+    } catch (err) {
+      setError(`Failed to clear search feedback: ${err}`);
+    }
+  };
+
+  const clearAnalytics = async () => {
+    try {
+      setError(null);
+      await invoke('clear_analytics');
+    } catch (err) {
+      setError(`Failed to clear search analytics: ${err}`);
+    }
+  };
+
   const saveSettings = async () => {
     if (!settings) return;
 
@@ -85,6 +211,24 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
     }
   };
 
+  // An empty field falls back to the provider's default
+  const updateProviderOption = (section: string, field: string, value: number | boolean | null) => {
+    if (!settings) return;
+
+    const options = { ...settings.provider_settings[section] };
+    if (value === null) {
+      delete options[field];
+    } else {
+      options[field] = value;
+    }
+
+    const providerSettings = { ...settings.provider_settings, [section]: options };
+    if (Object.keys(options).length === 0) {
+      delete providerSettings[section];
+    }
+    setSettings({ ...settings, provider_settings: providerSettings });
+  };
+
   if (!isOpen) return null;
 
   return (
@@ -118,7 +262,7 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
           ) : settings ? (
             <div className="space-y-6">
               {/* Hotkey */}
-              <div>
+              <div data-setting-key="hotkey">
                 <label className="block text-sm font-medium text-text-primary mb-2">
                   Global Hotkey
                 </label>
@@ -126,16 +270,71 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
                   type="text"
                   value={settings.hotkey}
                   onChange={(e) => updateSetting('hotkey', e.target.value)}
+                  onBlur={(e) => checkHotkey(e.target.value)}
                   className="w-full px-4 py-2 border border-border rounded-lg bg-background text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
                   placeholder="e.g., Ctrl+K"
                 />
                 <p className="mt-1 text-sm text-text-secondary">
                   Press this key combination to open the search bar
                 </p>
+                {hotkeyError && (
+                  <p className="mt-1 text-sm text-red-600">{hotkeyError}</p>
+                )}
+                {activeHotkey?.is_fallback && (
+                  <p className="mt-1 text-sm text-amber-600">
+                    {activeHotkey.preferred} is used by another app. {activeHotkey.shortcut} opens
+                    the launcher until you save a free hotkey.
+                  </p>
+                )}
+              </div>
+
+              {/* Quick Math */}
+              <div data-setting-key="quick_math_hotkey quick_math_output">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  Quick Math Hotkey
+                </label>
+                <input
+                  type="text"
+                  value={settings.quick_math_hotkey ?? ''}
+                  onChange={(e) => updateSetting('quick_math_hotkey', e.target.value || null)}
+                  onBlur={(e) =>
+                    e.target.value
+                      ? checkHotkey(e.target.value, setQuickMathHotkeyError)
+                      : setQuickMathHotkeyError(null)
+                  }
+                  className="w-full px-4 py-2 border border-border rounded-lg bg-background text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                  placeholder="Off, e.g. Ctrl+Shift+M"
+                />
+                <p className="mt-1 text-sm text-text-secondary">
+                  Select a calculation in any app and press this to evaluate it
+                </p>
+                {quickMathHotkeyError && (
+                  <p className="mt-1 text-sm text-red-600">{quickMathHotkeyError}</p>
+                )}
+                {settings.quick_math_hotkey && (
+                  <div className="grid grid-cols-2 gap-3 mt-3">
+                    {([
+                      ['toast', 'Show result'],
+                      ['replace', 'Replace selection'],
+                    ] as const).map(([value, label]) => (
+                      <button
+                        key={value}
+                        onClick={() => updateSetting('quick_math_output', value)}
+                        className={`px-4 py-2 rounded-lg border-2 transition-all ${
+                          settings.quick_math_output === value
+                            ? 'border-primary bg-primary/10 text-primary'
+                            : 'border-border hover:border-primary/50 text-text-primary'
+                        }`}
+                      >
+                        {label}
+                      </button>
+                    ))}
+                  </div>
+                )}
               </div>
 
               {/* Theme */}
-              <div>
+              <div data-setting-key="theme">
                 <label className="block text-sm font-medium text-text-primary mb-2">
                   Theme
                 </label>
@@ -157,7 +356,7 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
               </div>
 
               {/* Max Results */}
-              <div>
+              <div data-setting-key="max_results">
                 <label className="block text-sm font-medium text-text-primary mb-2">
                   Maximum Results: {settings.max_results}
                 </label>
@@ -175,8 +374,246 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
                 </div>
               </div>
 
+              {/* Clipboard History Size */}
+              <div data-setting-key="clipboard_history_size">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  Clipboard History: {settings.clipboard_history_size} items
+                </label>
+                <input
+                  type="range"
+                  min="10"
+                  max="500"
+                  step="10"
+                  value={settings.clipboard_history_size}
+                  onChange={(e) => updateSetting('clipboard_history_size', parseInt(e.target.value))}
+                  className="w-full h-2 bg-surface-hover rounded-lg appearance-none cursor-pointer accent-primary"
+                />
+                <div className="flex justify-between text-xs text-text-secondary mt-1">
+                  <span>10</span>
+                  <span>500</span>
+                </div>
+              </div>
+
+              {/* Clipboard auto-paste */}
+              <div data-setting-key="clipboard_auto_paste">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Paste clipboard items
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Paste a restored item into the app you were using, not just copy it
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.clipboard_auto_paste}
+                    onChange={(e) => updateSetting('clipboard_auto_paste', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* Sound Feedback */}
+              <div data-setting-key="sound_feedback custom_sounds">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  Sounds
+                </label>
+                <div className="grid grid-cols-3 gap-3">
+                  {([
+                    ['off', 'Off'],
+                    ['errors_only', 'Errors only'],
+                    ['all', 'All'],
+                  ] as const).map(([value, label]) => (
+                    <button
+                      key={value}
+                      onClick={() => updateSetting('sound_feedback', value)}
+                      className={`px-4 py-2 rounded-lg border-2 transition-all ${
+                        settings.sound_feedback === value
+                          ? 'border-primary bg-primary/10 text-primary'
+                          : 'border-border hover:border-primary/50 text-text-primary'
+                      }`}
+                    >
+                      {label}
+                    </button>
+                  ))}
+                </div>
+                {settings.sound_feedback !== 'off' && (
+                  <div className="mt-3 space-y-2">
+                    {([
+                      ['shown', 'Launcher shown'],
+                      ['execution_failed', 'Action failed'],
+                      ['update_available', 'Update available'],
+                    ] as const).map(([key, label]) => (
+                      <input
+                        key={key}
+                        type="text"
+                        value={settings.custom_sounds[key] ?? ''}
+                        onChange={(e) =>
+                          updateSetting('custom_sounds', {
+                            ...settings.custom_sounds,
+                            [key]: e.target.value || null,
+                          })
+                        }
+                        placeholder={`${label}: path to a .wav file, or empty for the built-in sound`}
+                        className="w-full px-4 py-2 border border-border rounded-lg bg-background text-sm text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                      />
+                    ))}
+                  </div>
+                )}
+              </div>
+
+              {/* File Manager and Terminal */}
+              <div data-setting-key="preferred_file_manager preferred_terminal">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  File Manager and Terminal
+                </label>
+                <div className="space-y-2">
+                  <input
+                    type="text"
+                    value={settings.preferred_file_manager ?? ''}
+                    onChange={(e) => updateSetting('preferred_file_manager', e.target.value || null)}
+                    placeholder="File manager: path to its .exe, or empty for Explorer"
+                    className="w-full px-4 py-2 border border-border rounded-lg bg-background text-sm text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                  />
+                  <input
+                    type="text"
+                    value={settings.preferred_terminal?.path ?? ''}
+                    onChange={(e) =>
+                      updateSetting(
+                        'preferred_terminal',
+                        e.target.value
+                          ? {
+                              path: e.target.value,
+                              args_template: settings.preferred_terminal?.args_template ?? '{path}',
+                            }
+                          : null
+                      )
+                    }
+                    placeholder="Terminal: path to its .exe, or empty for the default terminal"
+                    className="w-full px-4 py-2 border border-border rounded-lg bg-background text-sm text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                  />
+                  {settings.preferred_terminal && (
+                    <input
+                      type="text"
+                      value={settings.preferred_terminal.args_template}
+                      onChange={(e) => {
+                        const terminal = settings.preferred_terminal;
+                        if (terminal) {
+                          updateSetting('preferred_terminal', { ...terminal, args_template: e.target.value });
+                        }
+                      }}
+                      placeholder="Terminal arguments, with {path} where the folder goes"
+                      className="w-full px-4 py-2 border border-border rounded-lg bg-background text-sm text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                    />
+                  )}
+                </div>
+                <p className="mt-1 text-xs text-text-secondary">
+                  Ctrl+Shift+T opens the terminal in the selected file's folder
+                </p>
+              </div>
+
+              {/* Window Width */}
+              <div data-setting-key="window_width">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  Window Width: {settings.window_width}px
+                </label>
+                <input
+                  type="range"
+                  min="400"
+                  max="1200"
+                  step="20"
+                  value={settings.window_width}
+                  onChange={(e) => updateSetting('window_width', parseInt(e.target.value))}
+                  className="w-full h-2 bg-surface-hover rounded-lg appearance-none cursor-pointer accent-primary"
+                />
+                <div className="flex justify-between text-xs text-text-secondary mt-1">
+                  <span>400</span>
+                  <span>1200</span>
+                </div>
+              </div>
+
+              {/* Visible Results */}
+              <div data-setting-key="max_visible_results">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  Visible Results: {settings.max_visible_results}
+                </label>
+                <input
+                  type="range"
+                  min="3"
+                  max="20"
+                  value={settings.max_visible_results}
+                  onChange={(e) => updateSetting('max_visible_results', parseInt(e.target.value))}
+                  className="w-full h-2 bg-surface-hover rounded-lg appearance-none cursor-pointer accent-primary"
+                />
+                <div className="flex justify-between text-xs text-text-secondary mt-1">
+                  <span>3</span>
+                  <span>20</span>
+                </div>
+              </div>
+
+              {/* Compact mode */}
+              <div data-setting-key="compact_mode">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Compact mode
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Use shorter result rows to fit more on screen
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.compact_mode}
+                    onChange={(e) => updateSetting('compact_mode', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* Show instantly */}
+              <div data-setting-key="disable_show_animation">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Show instantly
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Skip the system's fade when the launcher opens and closes
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.disable_show_animation}
+                    onChange={(e) => updateSetting('disable_show_animation', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* Screen reader support */}
+              <div data-setting-key="screen_reader_support">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Screen reader support
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Describe each result in full, even when no screen reader is detected
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.screen_reader_support}
+                    onChange={(e) => updateSetting('screen_reader_support', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
               {/* Search Providers */}
-              <div>
+              <div data-setting-key="enabled_providers">
                 <label className="block text-sm font-medium text-text-primary mb-3">
                   Search Providers
                 </label>
@@ -205,8 +642,144 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
                 </div>
               </div>
 
+              {/* Providers skipped after repeated startup errors */}
+              {disabledProviders.length > 0 && (
+                <div>
+                  <label className="block text-sm font-medium text-text-primary mb-3">
+                    Disabled After Errors
+                  </label>
+                  <div className="space-y-2">
+                    {disabledProviders.map((provider) => (
+                      <div
+                        key={provider}
+                        className="flex items-center justify-between p-3 rounded-lg bg-surface-hover"
+                      >
+                        <span className="text-text-primary">{provider}</span>
+                        <button
+                          onClick={() => retryProvider(provider)}
+                          className="px-3 py-1 text-sm rounded-lg border border-border hover:border-primary/50 text-text-primary"
+                        >
+                          Retry on next start
+                        </button>
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )}
+
+              {/* Weather */}
+              {settings.enabled_providers.weather && (
+                <div data-setting-key="temperature_unit weather_home_city">
+                  <label className="block text-sm font-medium text-text-primary mb-2">
+                    Weather
+                  </label>
+                  <div className="grid grid-cols-2 gap-3">
+                    {([
+                      ['celsius', 'Celsius'],
+                      ['fahrenheit', 'Fahrenheit'],
+                    ] as const).map(([value, label]) => (
+                      <button
+                        key={value}
+                        onClick={() => updateSetting('temperature_unit', value)}
+                        className={`px-4 py-2 rounded-lg border-2 transition-all ${
+                          settings.temperature_unit === value
+                            ? 'border-primary bg-primary/10 text-primary'
+                            : 'border-border hover:border-primary/50 text-text-primary'
+                        }`}
+                      >
+                        {label}
+                      </button>
+                    ))}
+                  </div>
+                  <input
+                    type="text"
+                    value={settings.weather_home_city ?? ''}
+                    onChange={(e) => updateSetting('weather_home_city', e.target.value || null)}
+                    placeholder="Home city, used when Windows can't tell where you are"
+                    className="mt-3 w-full px-4 py-2 border border-border rounded-lg bg-background text-sm text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                  />
+                </div>
+              )}
+
+              {/* Provider options, rendered from each provider's schema */}
+              {providerSchemas.length > 0 && (
+                <div data-setting-key="provider_settings">
+                  <label className="block text-sm font-medium text-text-primary mb-3">
+                    Provider Options
+                  </label>
+                  <div className="space-y-4">
+                    {providerSchemas.map((provider) => (
+                      <div key={provider.key} className="space-y-2">
+                        <div className="text-text-primary font-medium">{provider.provider}</div>
+                        {Object.entries(provider.schema.properties).map(([field, option]) => (
+                          <label
+                            key={field}
+                            data-setting-key={`provider_settings.${provider.key}.${field}`}
+                            className="flex items-center justify-between gap-4 p-3 rounded-lg hover:bg-surface-hover"
+                          >
+                            <span className="text-sm text-text-secondary">{option.description}</span>
+                            {option.type === 'boolean' ? (
+                              <input
+                                type="checkbox"
+                                checked={Boolean(settings.provider_settings[provider.key]?.[field] ?? option.default)}
+                                onChange={(e) =>
+                                  updateProviderOption(
+                                    provider.key,
+                                    field,
+                                    e.target.checked === option.default ? null : e.target.checked
+                                  )
+                                }
+                                aria-label={`${provider.provider} ${field.replace(/_/g, ' ')}`}
+                                className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                              />
+                            ) : (
+                              <input
+                                type="number"
+                                min={option.minimum}
+                                max={option.maximum}
+                                placeholder={String(option.default)}
+                                value={(settings.provider_settings[provider.key]?.[field] as number | undefined) ?? ''}
+                                onChange={(e) =>
+                                  updateProviderOption(
+                                    provider.key,
+                                    field,
+                                    e.target.value === '' ? null : parseInt(e.target.value)
+                                  )
+                                }
+                                aria-label={`${provider.provider} ${field.replace(/_/g, ' ')}`}
+                                className="w-28 px-3 py-1 border border-border rounded-lg bg-background text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                              />
+                            )}
+                          </label>
+                        ))}
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )}
+
+              {/* Hide after execute */}
+              <div data-setting-key="hide_after_execute">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Hide after opening a result
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Close the launcher after a result opens. Shift+Enter does the opposite.
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.hide_after_execute}
+                    onChange={(e) => updateSetting('hide_after_execute', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
               {/* Start with Windows */}
-              <div>
+              <div data-setting-key="start_with_windows">
                 <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
                   <div>
                     <div className="text-text-primary font-medium">
@@ -229,6 +802,211 @@ const Settings: React.FC<SettingsProps> = ({ isOpen, onClose }) => {
                   />
                 </label>
               </div>
+
+              {/* Notification routing */}
+              <div data-setting-key="notification_routing">
+                <label className="block text-sm font-medium text-text-primary mb-2">
+                  Notifications
+                </label>
+                <div className="space-y-2">
+                  {([
+                    ['provider_health', 'Provider problems', 'in_app'],
+                    ['updates', 'Updates', 'both'],
+                    ['execution', 'Opening results', 'toast'],
+                    ['privacy', 'Privacy', 'toast'],
+                    ['general', 'Other', 'toast'],
+                  ] as const).map(([category, label, fallback]) => (
+                    <div key={category} className="flex items-center justify-between gap-4">
+                      <span className="text-sm text-text-primary">{label}</span>
+                      <select
+                        value={settings.notification_routing[category] ?? fallback}
+                        onChange={(e) =>
+                          updateSetting('notification_routing', {
+                            ...settings.notification_routing,
+                            [category]: e.target.value as NotificationRoute,
+                          })
+                        }
+                        className="px-3 py-1 border border-border rounded-lg bg-background text-sm text-text-primary focus:ring-2 focus:ring-primary focus:border-transparent"
+                      >
+                        <option value="toast">Toast</option>
+                        <option value="in_app">In the launcher</option>
+                        <option value="both">Both</option>
+                        <option value="off">Off</option>
+                      </select>
+                    </div>
+                  ))}
+                </div>
+              </div>
+
+              {/* Explorer context menu */}
+              <div className="flex items-center justify-between gap-4 p-3 rounded-lg">
+                <div>
+                  <div className="text-text-primary font-medium">
+                    Explorer context menu
+                  </div>
+                  <div className="text-sm text-text-secondary">
+                    Right-click a folder to search it with "Search in Better Finder"
+                  </div>
+                </div>
+                <div className="flex gap-2">
+                  <button
+                    onClick={() => setContextMenuInstalled(true)}
+                    className="px-3 py-1 border border-border text-text-primary hover:bg-surface-hover rounded-lg transition-colors"
+                  >
+                    Add
+                  </button>
+                  <button
+                    onClick={() => setContextMenuInstalled(false)}
+                    className="px-3 py-1 border border-border text-text-primary hover:bg-surface-hover rounded-lg transition-colors"
+                  >
+                    Remove
+                  </button>
+                </div>
+              </div>
+
+              {/* File delete action */}
+              <div data-setting-key="enable_file_delete_action">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Move files to Recycle Bin
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Shift+Delete on a file result recycles it after you confirm
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.enable_file_delete_action}
+                    onChange={(e) => updateSetting('enable_file_delete_action', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* File grouping */}
+              <div data-setting-key="group_file_results">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Group files by folder
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Collapse many matches from one folder or project into a row you can expand
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.group_file_results}
+                    onChange={(e) => updateSetting('group_file_results', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* Accent folding */}
+              <div data-setting-key="fold_diacritics">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Ignore accents
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Match "joao" to "João" and "cafe" to "Café"
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.fold_diacritics}
+                    onChange={(e) => updateSetting('fold_diacritics', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* Privacy mode */}
+              <div data-setting-key="privacy_mode">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Privacy mode
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Forget what you open instead of suggesting it and moving it up for the next few minutes
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.privacy_mode}
+                    onChange={(e) => updateSetting('privacy_mode', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
+
+              {/* Search feedback */}
+              <div className="flex items-center justify-between gap-4 p-3 rounded-lg">
+                <div>
+                  <div className="text-text-primary font-medium">
+                    Search feedback
+                  </div>
+                  <div className="text-sm text-text-secondary">
+                    Ctrl+Shift+D marks a result as not useful for the query; three times hides it
+                  </div>
+                </div>
+                <button
+                  onClick={clearFeedback}
+                  className="px-3 py-1 border border-border text-text-primary hover:bg-surface-hover rounded-lg transition-colors"
+                >
+                  Clear
+                </button>
+              </div>
+
+              {/* Local search analytics */}
+              <div data-setting-key="analytics_local" className="flex items-center justify-between gap-4 p-3 rounded-lg hover:bg-surface-hover">
+                <label className="flex flex-1 items-center justify-between gap-4 cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Local search analytics
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Keep timings and which results you pick on this computer to tune ranking; queries and paths are never recorded
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.analytics_local}
+                    onChange={(e) => updateSetting('analytics_local', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+                <button
+                  onClick={clearAnalytics}
+                  className="px-3 py-1 border border-border text-text-primary hover:bg-surface-hover rounded-lg transition-colors"
+                >
+                  Clear
+                </button>
+              </div>
+
+              {/* Debug mode */}
+              <div data-setting-key="debug_mode">
+                <label className="flex items-center justify-between p-3 rounded-lg hover:bg-surface-hover cursor-pointer">
+                  <div>
+                    <div className="text-text-primary font-medium">
+                      Debug mode
+                    </div>
+                    <div className="text-sm text-text-secondary">
+                      Attach a score breakdown to each search result
+                    </div>
+                  </div>
+                  <input
+                    type="checkbox"
+                    checked={settings.debug_mode}
+                    onChange={(e) => updateSetting('debug_mode', e.target.checked)}
+                    className="w-5 h-5 text-primary bg-background border-border rounded focus:ring-primary focus:ring-2"
+                  />
+                </label>
+              </div>
             </div>
           ) : null}
         </div>
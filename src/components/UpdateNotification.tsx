@@ -1,10 +1,25 @@
 import { useUpdater } from '../hooks/useUpdater';
-import { Download, X, CheckCircle, AlertCircle } from 'lucide-react';
+import { Download, X, CheckCircle, AlertCircle, Sparkles } from 'lucide-react';
+
+function ReleaseNotes({ notes }: { notes: string[] }) {
+  if (notes.length === 0) {
+    return null;
+  }
+
+  return (
+    <ul className="text-sm opacity-90 mt-2 list-disc pl-4 space-y-0.5 max-h-40 overflow-y-auto">
+      {notes.map((note, index) => (
+        <li key={index}>{note}</li>
+      ))}
+    </ul>
+  );
+}
 
 export function UpdateNotification() {
-  const { updateAvailable, updateVersion, updateInstalled, updateError, dismissUpdate } = useUpdater();
+  const { updateAvailable, updateVersion, updateNotes, whatsNew, updateInstalled, updateError, dismissUpdate } =
+    useUpdater();
 
-  if (!updateAvailable && !updateInstalled && !updateError) {
+  if (!updateAvailable && !updateInstalled && !updateError && !whatsNew) {
     return null;
   }
 
@@ -18,6 +33,25 @@ export function UpdateNotification() {
             <p className="text-sm opacity-90">
               Version {updateVersion} is available. The update is being downloaded and will be installed automatically.
             </p>
+            <ReleaseNotes notes={updateNotes} />
+          </div>
+          <button
+            onClick={dismissUpdate}
+            className="text-white/80 hover:text-white transition-colors"
+            aria-label="Dismiss"
+          >
+            <X className="w-5 h-5" />
+          </button>
+        </div>
+      )}
+
+      {whatsNew && (
+        <div className="bg-blue-500 text-white rounded-lg shadow-lg p-4 flex items-start gap-3 animate-slideIn">
+          <Sparkles className="w-5 h-5 mt-0.5 flex-shrink-0" />
+          <div className="flex-1">
+            <h3 className="font-semibold mb-1">What's new in {whatsNew.current_version}</h3>
+            <p className="text-sm opacity-90">Updated from version {whatsNew.previous_version}.</p>
+            <ReleaseNotes notes={whatsNew.notes} />
           </div>
           <button
             onClick={dismissUpdate}
@@ -0,0 +1,45 @@
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { AppSettings, Appearance } from '../types';
+
+export function useAppearance() {
+  const [compactMode, setCompactMode] = useState(false);
+
+  // Load initial density
+  useEffect(() => {
+    invoke<AppSettings>('get_effective_settings')
+      .then((settings) => {
+        setCompactMode(settings.compact_mode);
+        applyDensity(settings.compact_mode);
+      })
+      .catch((error) => {
+        console.error('Failed to load appearance:', error);
+      });
+  }, []);
+
+  // The backend resizes the window itself; only the row density is applied here
+  useEffect(() => {
+    const unlisten = listen<Appearance>('appearance-changed', (event) => {
+      setCompactMode(event.payload.compact_mode);
+      applyDensity(event.payload.compact_mode);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const applyDensity = (compact: boolean) => {
+    const root = document.documentElement;
+    if (compact) {
+      root.setAttribute('data-density', 'compact');
+    } else {
+      root.removeAttribute('data-density');
+    }
+  };
+
+  return {
+    compactMode,
+  };
+}
@@ -99,6 +99,132 @@ describe('useKeyboard', () => {
     expect(onEscape).toHaveBeenCalledTimes(1);
   });
 
+  it('should call onShiftDelete only when Shift is held', () => {
+    const onShiftDelete = vi.fn();
+
+    renderHook(() =>
+      useKeyboard({
+        onArrowDown: vi.fn(),
+        onArrowUp: vi.fn(),
+        onEnter: vi.fn(),
+        onEscape: vi.fn(),
+        onShiftDelete,
+      })
+    );
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 'Delete' }));
+    });
+    expect(onShiftDelete).not.toHaveBeenCalled();
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 'Delete', shiftKey: true }));
+    });
+    expect(onShiftDelete).toHaveBeenCalledTimes(1);
+  });
+
+  it('should call onCopyLink only for Ctrl+Shift+C', () => {
+    const onCopyLink = vi.fn();
+
+    renderHook(() =>
+      useKeyboard({
+        onArrowDown: vi.fn(),
+        onArrowUp: vi.fn(),
+        onEnter: vi.fn(),
+        onEscape: vi.fn(),
+        onCopyLink,
+      })
+    );
+
+    // Plain Ctrl+C keeps copying the selected text
+    const copy = new KeyboardEvent('keydown', { key: 'c', ctrlKey: true, cancelable: true });
+    act(() => {
+      window.dispatchEvent(copy);
+    });
+    expect(onCopyLink).not.toHaveBeenCalled();
+    expect(copy.defaultPrevented).toBe(false);
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 'C', ctrlKey: true, shiftKey: true }));
+    });
+    expect(onCopyLink).toHaveBeenCalledTimes(1);
+  });
+
+  it('should call onDemote only for Ctrl+Shift+D', () => {
+    const onDemote = vi.fn();
+
+    renderHook(() =>
+      useKeyboard({
+        onArrowDown: vi.fn(),
+        onArrowUp: vi.fn(),
+        onEnter: vi.fn(),
+        onEscape: vi.fn(),
+        onDemote,
+      })
+    );
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 'd', ctrlKey: true }));
+    });
+    expect(onDemote).not.toHaveBeenCalled();
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 'D', ctrlKey: true, shiftKey: true }));
+    });
+    expect(onDemote).toHaveBeenCalledTimes(1);
+  });
+
+  it('should call onOpenTerminal only for Ctrl+Shift+T', () => {
+    const onOpenTerminal = vi.fn();
+
+    renderHook(() =>
+      useKeyboard({
+        onArrowDown: vi.fn(),
+        onArrowUp: vi.fn(),
+        onEnter: vi.fn(),
+        onEscape: vi.fn(),
+        onOpenTerminal,
+      })
+    );
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 't', ctrlKey: true }));
+    });
+    expect(onOpenTerminal).not.toHaveBeenCalled();
+
+    act(() => {
+      window.dispatchEvent(new KeyboardEvent('keydown', { key: 'T', ctrlKey: true, shiftKey: true }));
+    });
+    expect(onOpenTerminal).toHaveBeenCalledTimes(1);
+  });
+
+  it('should keep Tab for focus unless onTab used it', () => {
+    const onTab = vi.fn().mockReturnValueOnce(true).mockReturnValueOnce(false);
+
+    renderHook(() =>
+      useKeyboard({
+        onArrowDown: vi.fn(),
+        onArrowUp: vi.fn(),
+        onEnter: vi.fn(),
+        onEscape: vi.fn(),
+        onTab,
+      })
+    );
+
+    const accepted = new KeyboardEvent('keydown', { key: 'Tab', cancelable: true });
+    act(() => {
+      window.dispatchEvent(accepted);
+    });
+    expect(accepted.defaultPrevented).toBe(true);
+
+    const ignored = new KeyboardEvent('keydown', { key: 'Tab', cancelable: true });
+    act(() => {
+      window.dispatchEvent(ignored);
+    });
+    expect(ignored.defaultPrevented).toBe(false);
+    expect(onTab).toHaveBeenCalledTimes(2);
+  });
+
   it('should not call handlers when disabled', () => {
     const onArrowDown = vi.fn();
     const onArrowUp = vi.fn();
@@ -3,20 +3,43 @@ import { useEffect, useCallback, useState } from 'react';
 export interface UseKeyboardOptions {
   onArrowUp: () => void;
   onArrowDown: () => void;
-  onEnter: () => void;
+  onEnter: (shiftKey: boolean) => void;
+  onCtrlEnter?: () => void;
   onEscape: () => void;
+  onShiftDelete?: () => void;
+  onCopyLink?: () => void;
+  onDemote?: () => void;
+  onOpenTerminal?: () => void;
+  onToggleMark?: () => void;
+  // Returns whether Tab was used; otherwise it keeps moving focus
+  onTab?: () => boolean;
   enabled?: boolean;
 }
 
 /**
  * Custom hook for handling keyboard navigation
  * Handles ArrowUp, ArrowDown, Enter, and Escape keys
+ * Enter reports whether Shift was held so callers can invert the default action
+ * Ctrl+Enter triggers the optional secondary handler, falling back to Enter
+ * Shift+Delete triggers the optional delete handler
+ * Ctrl+Shift+C triggers the optional copy link handler
+ * Ctrl+Shift+D triggers the optional "Not useful" handler
+ * Ctrl+Shift+T triggers the optional open in terminal handler
+ * Ctrl+Space triggers the optional mark handler used for multi-select
+ * Tab triggers the optional completion handler
  */
 export const useKeyboard = ({
   onArrowUp,
   onArrowDown,
   onEnter,
+  onCtrlEnter,
   onEscape,
+  onShiftDelete,
+  onCopyLink,
+  onDemote,
+  onOpenTerminal,
+  onToggleMark,
+  onTab,
   enabled = true,
 }: UseKeyboardOptions) => {
   const handleKeyDown = useCallback(
@@ -34,15 +57,57 @@ export const useKeyboard = ({
           break;
         case 'Enter':
           event.preventDefault();
-          onEnter();
+          if (event.ctrlKey && onCtrlEnter) {
+            onCtrlEnter();
+          } else {
+            onEnter(event.shiftKey);
+          }
           break;
         case 'Escape':
           event.preventDefault();
           onEscape();
           break;
+        case 'Delete':
+          if (event.shiftKey && onShiftDelete) {
+            event.preventDefault();
+            onShiftDelete();
+          }
+          break;
+        case 'c':
+        case 'C':
+          if (event.ctrlKey && event.shiftKey && onCopyLink) {
+            event.preventDefault();
+            onCopyLink();
+          }
+          break;
+        case 'd':
+        case 'D':
+          if (event.ctrlKey && event.shiftKey && onDemote) {
+            event.preventDefault();
+            onDemote();
+          }
+          break;
+        case 't':
+        case 'T':
+          if (event.ctrlKey && event.shiftKey && onOpenTerminal) {
+            event.preventDefault();
+            onOpenTerminal();
+          }
+          break;
+        case ' ':
+          if (event.ctrlKey && onToggleMark) {
+            event.preventDefault();
+            onToggleMark();
+          }
+          break;
+        case 'Tab':
+          if (!event.shiftKey && onTab?.()) {
+            event.preventDefault();
+          }
+          break;
       }
     },
-    [enabled, onArrowUp, onArrowDown, onEnter, onEscape]
+    [enabled, onArrowUp, onArrowDown, onEnter, onCtrlEnter, onEscape, onShiftDelete, onCopyLink, onDemote, onOpenTerminal, onToggleMark, onTab]
   );
 
   useEffect(() => {
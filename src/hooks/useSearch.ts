@@ -1,17 +1,72 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { SearchResult } from '../types';
+import { listen } from '@tauri-apps/api/event';
+import {
+  ActionDescriptor,
+  AppSettings,
+  BatchAction,
+  BatchSummary,
+  Completion,
+  EnrichedResult,
+  ExecutionTicket,
+  FeedbackPenalty,
+  ResultFacets,
+  ResultPage,
+  ResultType,
+  SearchResponse,
+  SearchResult,
+  SecondaryAction,
+} from '../types';
 
 interface UseSearchResult {
   query: string;
   setQuery: (query: string) => void;
   results: SearchResult[];
+  totalResults: number;
+  facets: ResultFacets | null;
+  activeFacets: ResultType[];
+  setActiveFacets: (types: ResultType[]) => Promise<void>;
+  loadMoreResults: () => Promise<void>;
   isLoading: boolean;
   error: string | null;
-  executeResult: (result: SearchResult) => Promise<void>;
+  completion: Completion | null;
+  acceptCompletion: () => string | null;
+  executeResult: (result: SearchResult, invertDefault?: boolean) => Promise<ExecutionTicket>;
+  cancelExecution: (executionId: string) => Promise<void>;
+  recycleResult: (result: SearchResult) => Promise<boolean>;
+  copyFormatted: (result: SearchResult) => Promise<boolean>;
+  copyLink: (result: SearchResult) => Promise<boolean>;
+  demoteResult: (result: SearchResult) => Promise<boolean>;
+  openInTerminal: (result: SearchResult) => Promise<boolean>;
+  executeResultsBatch: (results: SearchResult[], action: BatchAction) => Promise<BatchSummary | null>;
+  getResultActions: (result: SearchResult) => Promise<ActionDescriptor[]>;
+  executeResultAction: (result: SearchResult, actionId: string, confirmed?: boolean) => Promise<boolean>;
 }
 
 const DEBOUNCE_DELAY = 150; // milliseconds
+const PAGE_SIZE = 50;
+
+/**
+ * Lets the backend drop the results it kept for paging through a search
+ */
+function releaseSearch(searchId: string | null) {
+  if (searchId) {
+    invoke('release_search', { searchId }).catch((err) => console.error('Release search error:', err));
+  }
+}
+
+/**
+ * Asks the backend to fill in icons and other details the search left out;
+ * the results arrive through the results-enriched event
+ */
+function requestEnrichment(searchId: string | null, results: SearchResult[]) {
+  const resultIds = results.filter((result) => result.enrichment_pending).map((result) => result.id);
+  if (searchId && resultIds.length > 0) {
+    invoke('request_enrichment', { searchId, resultIds }).catch((err) =>
+      console.error('Request enrichment error:', err)
+    );
+  }
+}
 
 /**
  * Custom hook for managing search functionality
@@ -20,12 +75,21 @@ const DEBOUNCE_DELAY = 150; // milliseconds
 export function useSearch(): UseSearchResult {
   const [query, setQuery] = useState('');
   const [results, setResults] = useState<SearchResult[]>([]);
+  const [totalResults, setTotalResults] = useState(0);
+  const [facets, setFacets] = useState<ResultFacets | null>(null);
+  const [activeFacets, setActiveFacetsState] = useState<ResultType[]>([]);
   const [isLoading, setIsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  // Completion together with the query it was computed for
+  const [completionState, setCompletionState] = useState<{ query: string; completion: Completion } | null>(null);
   
   // Use ref to track the latest query for debouncing
   const debounceTimerRef = useRef<NodeJS.Timeout | null>(null);
   const abortControllerRef = useRef<AbortController | null>(null);
+  // Id of the shown search while the backend keeps more of its results
+  const searchIdRef = useRef<string | null>(null);
+  // Types the shown results are narrowed to; empty for every type
+  const activeFacetsRef = useRef<ResultType[]>([]);
 
   /**
    * Performs the actual search by calling the Tauri backend
@@ -38,9 +102,17 @@ export function useSearch(): UseSearchResult {
 
     // Create new abort controller for this search
     abortControllerRef.current = new AbortController();
+    const signal = abortControllerRef.current.signal;
+
+    releaseSearch(searchIdRef.current);
+    searchIdRef.current = null;
+    activeFacetsRef.current = [];
+    setActiveFacetsState([]);
 
     if (searchQuery.trim() === '') {
       setResults([]);
+      setTotalResults(0);
+      setFacets(null);
       setIsLoading(false);
       setError(null);
       return;
@@ -50,14 +122,20 @@ export function useSearch(): UseSearchResult {
     setError(null);
 
     try {
-      const searchResults = await invoke<SearchResult[]>('search_query', {
+      const response = await invoke<SearchResponse>('search_query', {
         query: searchQuery,
       });
 
       // Only update if this search wasn't aborted
-      if (!abortControllerRef.current.signal.aborted) {
-        setResults(searchResults);
+      if (!signal.aborted) {
+        searchIdRef.current = response.search_id;
+        setResults(response.results);
+        setTotalResults(response.total);
+        setFacets(response.facets ?? null);
         setError(null);
+        requestEnrichment(response.search_id, response.results);
+      } else {
+        releaseSearch(response.search_id);
       }
     } catch (err) {
       // Only update error if this search wasn't aborted
@@ -66,6 +144,8 @@ export function useSearch(): UseSearchResult {
         console.error('Search error:', errorMessage);
         setError(errorMessage);
         setResults([]);
+        setTotalResults(0);
+        setFacets(null);
       }
     } finally {
       // Only update loading state if this search wasn't aborted
@@ -75,6 +155,87 @@ export function useSearch(): UseSearchResult {
     }
   }, []);
 
+  /**
+   * Narrows the shown results to the given types, or shows every type again
+   * when empty; the backend serves them from the kept search without
+   * searching again
+   */
+  const setActiveFacets = useCallback(async (types: ResultType[]) => {
+    activeFacetsRef.current = types;
+    setActiveFacetsState(types);
+
+    try {
+      const response = await invoke<SearchResponse>('search_query_faceted', {
+        query,
+        activeFacets: types,
+      });
+      // Another chip was picked or the query changed meanwhile
+      if (activeFacetsRef.current !== types) {
+        return;
+      }
+      if (response.search_id !== searchIdRef.current) {
+        releaseSearch(searchIdRef.current);
+        searchIdRef.current = response.search_id;
+      }
+      setResults(response.results);
+      setTotalResults(response.total);
+      setFacets(response.facets ?? null);
+      requestEnrichment(response.search_id, response.results);
+    } catch (err) {
+      console.error('Facet search error:', err);
+    }
+  }, [query]);
+
+  /**
+   * Appends the next page of the current search's results
+   * Does nothing once every result is shown or the backend dropped the search.
+   */
+  const loadMoreResults = useCallback(async () => {
+    const searchId = searchIdRef.current;
+    if (!searchId || results.length >= totalResults) {
+      return;
+    }
+
+    try {
+      const page = await invoke<ResultPage>('get_results_page', {
+        searchId,
+        offset: results.length,
+        limit: PAGE_SIZE,
+        types: activeFacetsRef.current,
+      });
+      // A newer search replaced this one while the page loaded
+      if (searchIdRef.current === searchId) {
+        setResults((prev) => [...prev, ...page.results]);
+        requestEnrichment(searchId, page.results);
+      }
+    } catch (err) {
+      console.error('Load more results error:', err);
+      if (searchIdRef.current === searchId) {
+        searchIdRef.current = null;
+      }
+    }
+  }, [results.length, totalResults]);
+
+  /**
+   * Swaps in results of the shown search once their details were filled in
+   */
+  useEffect(() => {
+    const unlisten = listen<EnrichedResult[]>('results-enriched', (event) => {
+      const enriched = new Map(
+        event.payload
+          .filter((entry) => entry.search_id === searchIdRef.current)
+          .map((entry) => [entry.result.id, entry.result])
+      );
+      if (enriched.size > 0) {
+        setResults((prev) => prev.map((result) => enriched.get(result.id) ?? result));
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   /**
    * Effect to handle debounced search when query changes
    */
@@ -98,12 +259,62 @@ export function useSearch(): UseSearchResult {
   }, [query, performSearch]);
 
   /**
-   * Executes a search result action
+   * Fetches the inline completion on every keystroke; the backend only reads
+   * in-memory caches, so this is not debounced like the search
+   */
+  useEffect(() => {
+    if (query.trim() === '') {
+      setCompletionState(null);
+      return;
+    }
+
+    let cancelled = false;
+    invoke<Completion | null>('get_completion', { query })
+      .then((completion) => {
+        if (!cancelled) {
+          setCompletionState(completion ? { query, completion } : null);
+        }
+      })
+      .catch((err) => {
+        console.error('Completion error:', err);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [query]);
+
+  const completion = completionState?.query === query ? completionState.completion : null;
+
+  /**
+   * Completes the query to the suggested text
+   * Returns the id of the result to preselect, or null when there was nothing to accept.
+   */
+  const acceptCompletion = useCallback(() => {
+    if (!completion) {
+      return null;
+    }
+    setQuery(query + completion.suffix);
+    setCompletionState(null);
+    return completion.result_id;
+  }, [completion, query]);
+
+  /**
+   * Starts executing a search result action
+   * Resolves once the action has started; completion arrives as execution-status events.
+   * With invertDefault the window does the opposite of the hide_after_execute setting.
    */
-  const executeResult = useCallback(async (result: SearchResult) => {
+  const executeResult = useCallback(async (result: SearchResult, invertDefault = false) => {
     try {
-      await invoke('execute_result', { result });
-      console.log('Result executed successfully:', result.title);
+      let keepOpen: boolean | undefined;
+      if (invertDefault) {
+        const settings = await invoke<AppSettings>('get_effective_settings');
+        keepOpen = settings.hide_after_execute;
+      }
+
+      const ticket = await invoke<ExecutionTicket>('execute_result', { result, keepOpen });
+      console.log('Result execution started:', result.title);
+      return ticket;
     } catch (err) {
       const errorMessage = err instanceof Error ? err.message : 'Failed to execute result';
       console.error('Execute error:', errorMessage);
@@ -111,12 +322,183 @@ export function useSearch(): UseSearchResult {
     }
   }, []);
 
+  /**
+   * Stops waiting for a running execution
+   */
+  const cancelExecution = useCallback(async (executionId: string) => {
+    try {
+      await invoke('cancel_execution', { executionId });
+    } catch (err) {
+      console.error('Cancel execution error:', err);
+    }
+  }, []);
+
+  /**
+   * Moves a file result to the Recycle Bin after the user confirms
+   * Resolves to false when the result has no recycle action or the user declined.
+   */
+  const recycleResult = useCallback(async (result: SearchResult) => {
+    const actions: SecondaryAction[] = result.metadata.secondary_actions ?? [];
+    const recycle = actions.find((action) => action.id === 'move_to_recycle_bin');
+    if (!recycle) {
+      return false;
+    }
+
+    if (recycle.requires_confirmation && !window.confirm(`Move "${result.title}" to the Recycle Bin?`)) {
+      return false;
+    }
+
+    try {
+      await invoke('recycle_file_result', { result, confirmed: true });
+      setResults((prev) => prev.filter((r) => r.id !== result.id));
+      return true;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Recycle error:', errorMessage);
+      setError(errorMessage);
+      return false;
+    }
+  }, []);
+
+  /**
+   * Copies the pretty-printed form of a JSON or XML clipboard item
+   * Resolves to false when the result has no formatted copy.
+   */
+  const copyFormatted = useCallback(async (result: SearchResult) => {
+    const actions: SecondaryAction[] = result.metadata.secondary_actions ?? [];
+    if (!actions.some((action) => action.id === 'copy_formatted')) {
+      return false;
+    }
+
+    try {
+      await invoke('copy_formatted_clipboard_item', { resultId: result.id });
+      return true;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Copy formatted error:', errorMessage);
+      setError(errorMessage);
+      return false;
+    }
+  }, []);
+
+  /**
+   * Copies a betterfinder:// link that opens the result on another machine
+   */
+  const copyLink = useCallback(async (result: SearchResult) => {
+    try {
+      await invoke<string>('copy_result_link', { result });
+      return true;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Copy link error:', errorMessage);
+      setError(errorMessage);
+      return false;
+    }
+  }, []);
+
+  /**
+   * Marks a result as not useful for the current query
+   * Results demoted often enough disappear from the list right away.
+   */
+  const demoteResult = useCallback(async (result: SearchResult) => {
+    try {
+      const penalty = await invoke<FeedbackPenalty>('demote_result', { query, resultId: result.id });
+      if (penalty.suppressed) {
+        setResults((prev) => prev.filter((r) => r.id !== result.id));
+      }
+      return true;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Demote result error:', errorMessage);
+      setError(errorMessage);
+      return false;
+    }
+  }, [query]);
+
+  /**
+   * Opens the terminal in a file result's folder
+   */
+  const openInTerminal = useCallback(async (result: SearchResult) => {
+    try {
+      await invoke('open_in_terminal', { result });
+      return true;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Open in terminal error:', errorMessage);
+      setError(errorMessage);
+      return false;
+    }
+  }, []);
+
+  /**
+   * Applies one action to several file results and waits for all of them
+   */
+  const executeResultsBatch = useCallback(async (batch: SearchResult[], action: BatchAction) => {
+    try {
+      const summary = await invoke<BatchSummary>('execute_results_batch', { results: batch, action });
+      if (summary.failed.length > 0) {
+        console.error('Batch items failed:', summary.failed);
+      }
+      return summary;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Batch execute error:', errorMessage);
+      setError(errorMessage);
+      return null;
+    }
+  }, []);
+
+  /**
+   * Lists what can be done with a result, primary action first
+   */
+  const getResultActions = useCallback(async (result: SearchResult) => {
+    try {
+      return await invoke<ActionDescriptor[]>('get_result_actions', { result });
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Get result actions error:', errorMessage);
+      setError(errorMessage);
+      return [];
+    }
+  }, []);
+
+  /**
+   * Runs one of the actions from getResultActions
+   */
+  const executeResultAction = useCallback(async (result: SearchResult, actionId: string, confirmed = false) => {
+    try {
+      await invoke<string | null>('execute_result_action_by_id', { result, actionId, query, confirmed });
+      return true;
+    } catch (err) {
+      const errorMessage = err instanceof Error ? err.message : String(err);
+      console.error('Execute result action error:', errorMessage);
+      setError(errorMessage);
+      return false;
+    }
+  }, [query]);
+
   return {
     query,
     setQuery,
     results,
+    totalResults,
+    facets,
+    activeFacets,
+    setActiveFacets,
+    loadMoreResults,
     isLoading,
     error,
+    completion,
+    acceptCompletion,
     executeResult,
+    cancelExecution,
+    recycleResult,
+    copyFormatted,
+    copyLink,
+    demoteResult,
+    openInTerminal,
+    executeResultsBatch,
+    getResultActions,
+    executeResultAction,
   };
 }
@@ -13,10 +13,11 @@ describe('useTheme', () => {
     vi.clearAllMocks();
     // Reset document theme
     document.documentElement.removeAttribute('data-theme');
+    document.documentElement.style.removeProperty('--color-primary');
   });
 
   it('should load and apply dark theme by default', async () => {
-    vi.mocked(invoke).mockResolvedValue('dark');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'dark', accent: null });
     vi.mocked(listen).mockResolvedValue(() => {});
 
     const { result } = renderHook(() => useTheme());
@@ -29,7 +30,7 @@ describe('useTheme', () => {
   });
 
   it('should load and apply light theme', async () => {
-    vi.mocked(invoke).mockResolvedValue('light');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'light', accent: null });
     vi.mocked(listen).mockResolvedValue(() => {});
 
     const { result } = renderHook(() => useTheme());
@@ -44,7 +45,7 @@ describe('useTheme', () => {
   it('should handle theme changes via event', async () => {
     let eventCallback: ((event: any) => void) | undefined;
     
-    vi.mocked(invoke).mockResolvedValue('dark');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'dark', accent: null });
     vi.mocked(listen).mockImplementation((event, callback) => {
       if (event === 'theme-changed') {
         eventCallback = callback as any;
@@ -59,7 +60,7 @@ describe('useTheme', () => {
     });
 
     // Simulate theme change event
-    vi.mocked(invoke).mockResolvedValue('light');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'light', accent: null });
     if (eventCallback) {
       eventCallback({ payload: Theme.Light });
     }
@@ -72,7 +73,7 @@ describe('useTheme', () => {
   });
 
   it('should apply dark theme to document root', async () => {
-    vi.mocked(invoke).mockResolvedValue('dark');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'dark', accent: null });
     vi.mocked(listen).mockResolvedValue(() => {});
 
     renderHook(() => useTheme());
@@ -86,7 +87,7 @@ describe('useTheme', () => {
     // Start with dark theme
     document.documentElement.setAttribute('data-theme', 'dark');
     
-    vi.mocked(invoke).mockResolvedValue('light');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'light', accent: null });
     vi.mocked(listen).mockResolvedValue(() => {});
 
     renderHook(() => useTheme());
@@ -107,8 +108,33 @@ describe('useTheme', () => {
     });
   });
 
+  it('should apply the Windows accent color and follow accent changes', async () => {
+    let accentCallback: ((event: any) => void) | undefined;
+
+    vi.mocked(invoke).mockResolvedValue({ theme: 'dark', accent: '#0078D7FF' });
+    vi.mocked(listen).mockImplementation((event, callback) => {
+      if (event === 'accent-changed') {
+        accentCallback = callback as any;
+      }
+      return Promise.resolve(() => {});
+    });
+
+    renderHook(() => useTheme());
+
+    await waitFor(() => {
+      expect(document.documentElement.style.getPropertyValue('--color-primary')).toBe('#0078D7FF');
+    });
+
+    accentCallback?.({ payload: '#E81123FF' });
+    expect(document.documentElement.style.getPropertyValue('--color-primary')).toBe('#E81123FF');
+
+    // Without an accent the theme's own primary color applies again
+    accentCallback?.({ payload: null });
+    expect(document.documentElement.style.getPropertyValue('--color-primary')).toBe('');
+  });
+
   it('should refresh theme when refreshTheme is called', async () => {
-    vi.mocked(invoke).mockResolvedValue('dark');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'dark', accent: null });
     vi.mocked(listen).mockResolvedValue(() => {});
 
     const { result } = renderHook(() => useTheme());
@@ -118,7 +144,7 @@ describe('useTheme', () => {
     });
 
     // Change the mock to return light theme
-    vi.mocked(invoke).mockResolvedValue('light');
+    vi.mocked(invoke).mockResolvedValue({ theme: 'light', accent: null });
 
     // Call refreshTheme
     result.current.refreshTheme();
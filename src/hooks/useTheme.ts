@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
-import { Theme } from '../types';
+import { ResolvedTheme, Theme } from '../types';
 
 export function useTheme() {
   const [theme, setTheme] = useState<Theme>(Theme.Dark);
@@ -24,11 +24,23 @@ export function useTheme() {
     };
   }, []);
 
+  // Listen for Windows accent color changes
+  useEffect(() => {
+    const unlisten = listen<string | null>('accent-changed', (event) => {
+      applyAccent(event.payload);
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const loadTheme = async () => {
     try {
-      const resolvedThemeValue = await invoke<'light' | 'dark'>('get_resolved_theme');
-      setResolvedTheme(resolvedThemeValue);
-      applyTheme(resolvedThemeValue);
+      const resolved = await invoke<ResolvedTheme>('get_resolved_theme');
+      setResolvedTheme(resolved.theme);
+      applyTheme(resolved.theme);
+      applyAccent(resolved.accent);
     } catch (error) {
       console.error('Failed to load theme:', error);
       // Default to dark theme on error
@@ -38,9 +50,10 @@ export function useTheme() {
 
   const resolveAndApplyTheme = async (themeValue: Theme) => {
     try {
-      const resolvedThemeValue = await invoke<'light' | 'dark'>('get_resolved_theme');
-      setResolvedTheme(resolvedThemeValue);
-      applyTheme(resolvedThemeValue);
+      const resolved = await invoke<ResolvedTheme>('get_resolved_theme');
+      setResolvedTheme(resolved.theme);
+      applyTheme(resolved.theme);
+      applyAccent(resolved.accent);
     } catch (error) {
       console.error('Failed to resolve theme:', error);
       // Fallback: if theme is explicitly set, use it; otherwise default to dark
@@ -58,6 +71,16 @@ export function useTheme() {
     }
   };
 
+  // The accent replaces the theme's primary color used for highlights
+  const applyAccent = (accent: string | null) => {
+    const root = document.documentElement;
+    if (accent) {
+      root.style.setProperty('--color-primary', accent);
+    } else {
+      root.style.removeProperty('--color-primary');
+    }
+  };
+
   return {
     theme,
     resolvedTheme,
@@ -1,10 +1,15 @@
 import { useEffect, useState } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
+import { UpdateInfo, WhatsNew } from '../types';
 
 interface UpdaterState {
   updateAvailable: boolean;
   updateVersion: string | null;
+  // Bullet points of the available update's release notes
+  updateNotes: string[];
+  // Set once after the app starts on a newer version
+  whatsNew: WhatsNew | null;
   updateInstalled: boolean;
   updateError: string | null;
   isChecking: boolean;
@@ -14,6 +19,8 @@ export function useUpdater() {
   const [state, setState] = useState<UpdaterState>({
     updateAvailable: false,
     updateVersion: null,
+    updateNotes: [],
+    whatsNew: null,
     updateInstalled: false,
     updateError: null,
     isChecking: false,
@@ -28,6 +35,19 @@ export function useUpdater() {
         updateAvailable: true,
         updateVersion: event.payload,
       }));
+
+      // The check that found the update cached its notes
+      invoke<UpdateInfo>('get_update_info')
+        .then((info) => {
+          setState((prev) => ({ ...prev, updateNotes: info.notes }));
+        })
+        .catch((error) => {
+          console.error('Failed to get update info:', error);
+        });
+    });
+
+    const unlistenWhatsNew = listen<WhatsNew>('show-whats-new', (event) => {
+      setState((prev) => ({ ...prev, whatsNew: event.payload }));
     });
 
     // Listen for update-installed event
@@ -53,6 +73,7 @@ export function useUpdater() {
     // Cleanup listeners
     return () => {
       unlistenAvailable.then((fn) => fn());
+      unlistenWhatsNew.then((fn) => fn());
       unlistenInstalled.then((fn) => fn());
       unlistenError.then((fn) => fn());
     };
@@ -81,6 +102,8 @@ export function useUpdater() {
       ...prev,
       updateAvailable: false,
       updateVersion: null,
+      updateNotes: [],
+      whatsNew: null,
       updateInstalled: false,
       updateError: null,
     }));
@@ -1,9 +1,48 @@
 import { useState, useEffect } from 'react';
 import { listen } from '@tauri-apps/api/event';
 import { invoke } from '@tauri-apps/api/core';
+import { ActivationMessage, LaunchContext } from '../types';
 
 export const useWindowVisibility = () => {
   const [isVisible, setIsVisible] = useState(true);
+  // Query passed on the command line, waiting to be put in the search box
+  const [initialQuery, setInitialQuery] = useState<string | null>(null);
+  // Result a link asked to select once the initial query's results arrive
+  const [initialSelectId, setInitialSelectId] = useState<string | null>(null);
+
+  useEffect(() => {
+    // Query this instance was launched with
+    invoke<string | null>('take_initial_query')
+      .then((query) => {
+        if (query) {
+          setInitialQuery(query);
+        }
+      })
+      .catch((error) => {
+        console.error('Failed to get initial query:', error);
+      });
+
+    // Launching the app again shows this instance, with that launch's query
+    const unlistenPromise = listen<ActivationMessage>('instance-activated', (event) => {
+      setIsVisible(true);
+      setInitialQuery(event.payload.query);
+      invoke('show_window').catch((error) => {
+        console.error('Failed to show window:', error);
+      });
+    });
+
+    // betterfinder:// links and the Explorer verb open on their scoped query
+    const unlistenContextPromise = listen<LaunchContext>('open-with-context', (event) => {
+      setIsVisible(true);
+      setInitialQuery(event.payload.query);
+      setInitialSelectId(event.payload.select_result_id ?? null);
+    });
+
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+      unlistenContextPromise.then((unlisten) => unlisten());
+    };
+  }, []);
 
   useEffect(() => {
     // Listen for hotkey press events
@@ -11,8 +50,14 @@ export const useWindowVisibility = () => {
       setIsVisible((prev) => !prev);
     });
 
+    // The hotkey shows a hidden window from the backend, without hotkey-pressed
+    const unlistenShowPromise = listen('window-about-to-show', () => {
+      setIsVisible(true);
+    });
+
     return () => {
       unlistenPromise.then((unlisten) => unlisten());
+      unlistenShowPromise.then((unlisten) => unlisten());
     };
   }, []);
 
@@ -32,11 +77,18 @@ export const useWindowVisibility = () => {
   const showWindow = () => setIsVisible(true);
   const hideWindow = () => setIsVisible(false);
   const toggleWindow = () => setIsVisible((prev) => !prev);
+  const clearInitialQuery = () => {
+    setInitialQuery(null);
+    setInitialSelectId(null);
+  };
 
   return {
     isVisible,
     showWindow,
     hideWindow,
     toggleWindow,
+    initialQuery,
+    initialSelectId,
+    clearInitialQuery,
   };
 };
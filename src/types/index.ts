@@ -7,6 +7,171 @@ export interface SearchResult {
   score: number;
   metadata: Record<string, any>;
   action: ResultAction;
+  // Set while details such as the icon are still being filled in; see request_enrichment
+  enrichment_pending?: boolean;
+  // Opaque backend signature of id and action; send the result back unchanged
+  integrity?: string;
+}
+
+// Returned by search_query; search_id is set when get_results_page has more
+// or results await enrichment
+export interface SearchResponse {
+  search_id: string | null;
+  total: number;
+  results: SearchResult[];
+  // Counts for filter chips; missing when results is empty
+  facets?: ResultFacets;
+  // Alt+number shortcuts of the first results, as the backend assigned them
+  slots: ResultSlot[];
+}
+
+export interface ResultSlot {
+  slot: number;
+  result_id: string;
+}
+
+// Counts cover every result found, whichever facets are active
+export interface ResultFacets {
+  types: TypeFacet[];
+  providers: ProviderFacet[];
+}
+
+export interface TypeFacet {
+  type: ResultType;
+  count: number;
+  // Results of the type on the returned page
+  shown: number;
+  truncated: boolean;
+}
+
+export interface ProviderFacet {
+  provider: string;
+  count: number;
+}
+
+export interface ResultPage {
+  offset: number;
+  total: number;
+  results: SearchResult[];
+}
+
+// Entry of the results-enriched event
+export interface EnrichedResult {
+  search_id: string;
+  result: SearchResult;
+}
+
+export interface ProviderProgress {
+  provider: string;
+  completed: number;
+  total: number;
+  percent: number;
+}
+
+export interface SearchSection {
+  types: ResultType[];
+  limit: number;
+}
+
+export interface SecondaryAction {
+  id: string;
+  title: string;
+  requires_confirmation: boolean;
+}
+
+export type ExecutionState =
+  | { state: 'started' }
+  | { state: 'progress'; done: number; total: number }
+  | { state: 'succeeded' }
+  | { state: 'failed'; error: string }
+  | { state: 'cancelled' };
+
+export type ExecutionStatus = ExecutionState & {
+  execution_id: string;
+  result_id: string;
+};
+
+export interface ExecutionTicket {
+  execution_id: string;
+  hide_on_success: boolean;
+  // The same result ran moments ago; execution_id is that earlier run
+  duplicate: boolean;
+}
+
+// Inline completion of the search query, accepted with Tab
+export interface Completion {
+  suffix: string;
+  text: string;
+  result_id: string;
+}
+
+export enum BatchAction {
+  Open = 'open',
+  CopyPaths = 'copy_paths',
+  RevealInExplorer = 'reveal_in_explorer',
+}
+
+export interface BatchFailure {
+  id: string;
+  error: string;
+}
+
+export interface BatchSummary {
+  succeeded: string[];
+  failed: BatchFailure[];
+  // Items skipped because the same batch action ran on them moments ago
+  suppressed: string[];
+}
+
+// Mirrors MAX_BATCH_SIZE in the search engine
+export const MAX_BATCH_SIZE = 25;
+
+export interface ScoreBreakdown {
+  base: number;
+  exact_match: number;
+  prefix_match: number;
+  contains_match: number;
+  token_match: number;
+  recent_execution: number;
+  pinned: boolean;
+  demotions: number;
+  demotion_penalty: number;
+  total: number;
+}
+
+export interface ActionDescriptor {
+  id: string;
+  label: string;
+  icon?: string | null;
+  requires_confirmation: boolean;
+  is_primary: boolean;
+}
+
+// Sent with `open-settings` when a settings search result is chosen
+export interface SettingsLocation {
+  section: string;
+  key: string;
+}
+
+export interface FeedbackPenalty {
+  demotions: number;
+  penalty: number;
+  suppressed: boolean;
+}
+
+export interface Pin {
+  id: string;
+  alias: string;
+  result: SearchResult;
+  pinned_at: string;
+}
+
+// A query saved under a name; also sent with `run-saved-search` when it runs
+export interface SavedSearch {
+  name: string;
+  query: string;
+  sections: SearchSection[];
+  saved_at: string;
 }
 
 export enum ResultType {
@@ -14,10 +179,21 @@ export enum ResultType {
   Application = 'application',
   QuickAction = 'quick_action',
   Calculator = 'calculator',
+  DateTime = 'date_time',
   Clipboard = 'clipboard',
   Bookmark = 'bookmark',
+  History = 'history',
   RecentFile = 'recent_file',
   WebSearch = 'web_search',
+  Ssh = 'ssh',
+  Project = 'project',
+  Suggestion = 'suggestion',
+  Generator = 'generator',
+  Text = 'text',
+  Hash = 'hash',
+  Url = 'url',
+  SavedSearch = 'saved_search',
+  FileGroup = 'file_group',
 }
 
 export interface ResultAction {
@@ -32,6 +208,8 @@ export enum ActionType {
   CopyToClipboard = 'copy_to_clipboard',
   OpenUrl = 'open_url',
   WebSearch = 'web_search',
+  SetQuery = 'set_query',
+  ExpandGroup = 'expand_group',
 }
 
 export interface AppSettings {
@@ -41,6 +219,145 @@ export interface AppSettings {
   enabled_providers: EnabledProviders;
   search_delay: number;
   start_with_windows: boolean;
+  hide_after_execute: boolean;
+  clipboard_history_size: number;
+  debug_mode: boolean;
+  enable_file_delete_action: boolean;
+  group_file_results: boolean;
+  // Keeps the top results under the same Alt+number while typing
+  stable_result_slots: boolean;
+  fold_diacritics: boolean;
+  // Keeps executed results out of completions and the recent results boost
+  privacy_mode: boolean;
+  // Records search timings and result ranks on this computer, never queries or paths
+  analytics_local: boolean;
+  // Id of the engine web searches go to, e.g. "google"
+  web_search_engine: string;
+  temperature_unit: TemperatureUnit;
+  // City a bare "weather" uses when Windows can't report the device position
+  weather_home_city: string | null;
+  sound_feedback: SoundFeedback;
+  custom_sounds: CustomSounds;
+  // Where each category of notification shows; missing categories use their default
+  notification_routing: Partial<Record<NotificationCategory, NotificationRoute>>;
+  preferred_file_manager: string | null;
+  preferred_terminal: PreferredTerminal | null;
+  clipboard_auto_paste: boolean;
+  // Hotkey evaluating the selected text as a calculation; off when null
+  quick_math_hotkey: string | null;
+  quick_math_output: QuickMathOutput;
+  custom_bookmark_files: CustomBookmarkFile[];
+  open_bookmarks_in_source_browser: boolean;
+  excluded_paths: string[];
+  preferred_paths: string[];
+  // Score adjustment per file type class; missing classes use their default
+  extension_class_weights: Partial<Record<ExtensionClass, number>>;
+  // Words ranking skips when matching a query word by word
+  ranking_stopwords: string[];
+  // Characters that, leading a query, search for the rest as typed; '' turns this off
+  plain_text_markers: string;
+  macros: QueryMacro[];
+  window_width: number;
+  max_visible_results: number;
+  compact_mode: boolean;
+  disable_show_animation: boolean;
+  screen_reader_support: boolean;
+  // Development builds only: execute results that didn't come from a search
+  allow_unsigned_results: boolean;
+  duplicate_window_ms: number;
+  memory_trim_threshold_mb: number;
+  // Per-provider options keyed by section; missing fields use the provider's defaults
+  provider_settings: Record<string, Record<string, number | boolean>>;
+  profiles: SettingsProfile[];
+  // Profile whose overrides apply on top of these settings; none when null
+  active_profile: string | null;
+}
+
+// Named settings overrides, e.g. "Work", switched with switch_profile
+export interface SettingsProfile {
+  name: string;
+  overrides: Partial<Omit<AppSettings, 'profiles' | 'active_profile'>>;
+}
+
+export type ProviderOptionSchema =
+  | {
+      type: 'integer';
+      description: string;
+      minimum: number;
+      maximum: number;
+      default: number;
+    }
+  | {
+      type: 'boolean';
+      description: string;
+      default: boolean;
+    };
+
+// Returned by get_provider_config_schema, one per provider with options
+export interface ProviderConfigSchema {
+  key: string;
+  provider: string;
+  defaults: Record<string, number | boolean>;
+  schema: {
+    type: 'object';
+    properties: Record<string, ProviderOptionSchema>;
+  };
+}
+
+export interface Appearance {
+  window_width: number;
+  window_height: number;
+  max_visible_results: number;
+  compact_mode: boolean;
+}
+
+export type TemperatureUnit = 'celsius' | 'fahrenheit';
+
+// Whether quick math shows its result in a toast or types it over the selection
+export type QuickMathOutput = 'toast' | 'replace';
+
+export type SoundFeedback = 'off' | 'errors_only' | 'all';
+
+export type NotificationCategory = 'provider_health' | 'updates' | 'execution' | 'privacy' | 'general';
+
+// Toast, launcher banner (held until the launcher next opens), both, or nothing
+export type NotificationRoute = 'toast' | 'in_app' | 'both' | 'off';
+
+// File types that rank alike; noise covers temp files, logs and dumps
+export type ExtensionClass = 'documents' | 'images' | 'media' | 'code' | 'archives' | 'noise';
+
+// Payload of the `notification` event, shown as a banner in the launcher
+export interface InAppNotification {
+  category: NotificationCategory;
+  severity: 'error' | 'warning' | 'success' | 'info';
+  title: string;
+  message: string | null;
+  auto_dismiss_ms: number;
+}
+
+// WAV files (under 200 KB) replacing the bundled sounds; empty keeps the bundled one
+export interface CustomSounds {
+  shown: string | null;
+  execution_failed: string | null;
+  update_available: string | null;
+}
+
+// A terminal chosen in settings; args_template marks the folder with {path}
+export interface PreferredTerminal {
+  path: string;
+  args_template: string;
+}
+
+// `keyword argument` runs template_action with {arg} replaced by the argument
+export interface QueryMacro {
+  keyword: string;
+  template_action: ResultAction;
+}
+
+export interface CustomBookmarkFile {
+  path: string;
+  format: 'chromium' | 'firefox';
+  label: string;
 }
 
 export enum Theme {
@@ -49,6 +366,137 @@ export enum Theme {
   System = 'system',
 }
 
+// Returned by get_resolved_theme; accent is the Windows accent color as "#RRGGBBAA"
+export interface ResolvedTheme {
+  theme: 'light' | 'dark';
+  accent: string | null;
+}
+
+export interface CacheStats {
+  name: string;
+  entries: number;
+  bytes: number;
+  byte_budget: number | null;
+}
+
+// Returned by get_diagnostics; working_set_bytes is null off Windows
+export interface Diagnostics {
+  working_set_bytes: number | null;
+  result_cache: CacheStats;
+  result_cache_hit_rate: number | null;
+  caches: CacheStats[];
+  processes: LaunchedProcess[];
+  show_latency: ShowLatencyStats;
+  recent_executions: RecentExecution[];
+  provider_startup: ProviderStartup[];
+}
+
+// Payload of provider-registered, and each entry of providers-ready
+export interface ProviderStartup {
+  provider: string;
+  duration_ms: number;
+  // False when the provider failed to start or was skipped
+  registered: boolean;
+}
+
+// Returned by run_self_test and copy_self_test_report; status is the worst check's
+export type SelfTestStatus = 'pass' | 'warn' | 'fail';
+
+export interface SelfTestCheck {
+  name: string;
+  status: SelfTestStatus;
+  duration_ms: number;
+  detail: string;
+}
+
+export interface SelfTestReport {
+  started_at: string;
+  status: SelfTestStatus;
+  passed: number;
+  warnings: number;
+  failures: number;
+  checks: SelfTestCheck[];
+  probe_file: string | null;
+}
+
+// A result boosted for being executed in the last few minutes
+export interface RecentExecution {
+  result_id: string;
+  age_ms: number;
+  boost: number;
+}
+
+// Hotkey-to-focused-search-box latency over the last shows, in milliseconds
+export interface ShowLatencyStats {
+  samples: number;
+  last_ms: number | null;
+  median_ms: number | null;
+  p95_ms: number | null;
+  max_ms: number | null;
+}
+
+// A process started by executing a result; code is null when it was terminated
+export interface LaunchedProcess {
+  kind: string;
+  command_line: string;
+  pid: number;
+  started_at: string;
+  state: 'running' | 'exited';
+  code?: number | null;
+}
+
+// Returned by get_active_hotkey; is_fallback is set while the configured hotkey is taken
+export interface ActiveHotkey {
+  shortcut: string;
+  preferred: string;
+  is_fallback: boolean;
+}
+
+// A release offered by the update server; notes are Markdown
+export interface UpdateManifest {
+  version: string;
+  notes: string | null;
+  pub_date: string | null;
+}
+
+// Returned by get_update_info; stale is set when the server couldn't be asked for newer info
+export interface UpdateInfo {
+  current_version: string;
+  update: UpdateManifest | null;
+  notes: string[];
+  fetched_at: string | null;
+  stale: boolean;
+}
+
+// Payload of show-whats-new, sent once after the app starts on a newer version
+export interface WhatsNew {
+  previous_version: string;
+  current_version: string;
+  notes: string[];
+}
+
+// Payload of settings-conflict, sent when a save clashes with edits made to the settings file
+export interface SettingsConflict {
+  settings: AppSettings;
+  fields: string[];
+}
+
+// Payload of instance-activated, sent when the app is launched again
+export interface ActivationMessage {
+  query: string | null;
+  // betterfinder:// link the app was launched with; handled by the running instance
+  link?: string;
+}
+
+// Payload of open-with-context, sent for betterfinder:// links and the Explorer verb
+export interface LaunchContext {
+  // Search box text, with the scope already applied
+  query: string;
+  scope: string | null;
+  // Result to select once it shows up, for links to commands
+  select_result_id?: string | null;
+}
+
 export interface EnabledProviders {
   files: boolean;
   applications: boolean;
@@ -57,4 +505,6 @@ export interface EnabledProviders {
   clipboard: boolean;
   bookmarks: boolean;
   recent_files: boolean;
+  browser_history: boolean;
+  weather: boolean;
 }